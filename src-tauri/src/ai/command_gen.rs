@@ -0,0 +1,140 @@
+//! 结构化命令生成
+//!
+//! 要求 AI 按 JSON Schema 返回命令、说明、危险等级和是否需要 sudo，
+//! 解析后再用本地规则校正，前端据此渲染统一的确认卡片
+
+use serde::{Deserialize, Serialize};
+
+use crate::ssh::protection::match_destructive;
+
+/// 结构化模式的系统提示词
+pub const STRUCTURED_SYSTEM_PROMPT: &str = r#"你是 Linux 命令生成器。根据描述生成 Shell 命令，只输出 JSON，不要解释。
+
+格式：
+{"command": "命令", "explanation": "一句话说明", "dangerLevel": "safe|caution|dangerous", "requiresSudo": false}
+
+规则：
+1. 优先常用命令，避免复杂参数
+2. 只读操作为 safe；修改文件、服务或配置为 caution；删除数据、格式化、关机等不可逆操作为 dangerous
+3. 需要 root 权限时命令以 sudo 开头，并设置 requiresSudo 为 true
+4. 需求不明确时 command 为空字符串，explanation 说明需要补充的信息"#;
+
+/// 命令的危险等级
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DangerLevel {
+    Safe,
+    Caution,
+    Dangerous,
+}
+
+/// 生成的命令
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeneratedCommand {
+    pub command: String,
+    pub explanation: String,
+    pub danger_level: DangerLevel,
+    pub requires_sudo: bool,
+    /// 命中的危险命令模式（本地规则），前端高亮提示
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub risk: Option<String>,
+}
+
+/// AI 原始输出
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawCommand {
+    #[serde(default)]
+    command: String,
+    #[serde(default)]
+    explanation: String,
+    danger_level: Option<DangerLevel>,
+    #[serde(default)]
+    requires_sudo: bool,
+}
+
+/// 输出约束，传给支持结构化输出的 Provider
+pub fn schema() -> serde_json::Value {
+    serde_json::json!({
+        "type": "object",
+        "properties": {
+            "command": { "type": "string" },
+            "explanation": { "type": "string" },
+            "dangerLevel": { "type": "string", "enum": ["safe", "caution", "dangerous"] },
+            "requiresSudo": { "type": "boolean" },
+        },
+        "required": ["command", "explanation", "dangerLevel", "requiresSudo"],
+        "additionalProperties": false,
+    })
+}
+
+/// 解析并校正 AI 返回的命令（允许包裹在 Markdown 代码块或说明文字中）
+///
+/// 危险等级不低于本地危险命令规则的判断，命令以 sudo 开头时总是标记为需要 sudo
+pub fn parse_generated_command(text: &str) -> Result<GeneratedCommand, String> {
+    let start = text.find('{').ok_or("AI response does not contain a command")?;
+    let end = text.rfind('}').filter(|&end| end > start).ok_or("AI response does not contain a command")?;
+    let raw: RawCommand = serde_json::from_str(&text[start..=end])
+        .map_err(|e| format!("Invalid command from AI: {}", e))?;
+
+    let command = raw.command.trim().to_string();
+    let explanation = raw.explanation.trim().to_string();
+    if command.is_empty() {
+        return Err(if explanation.is_empty() {
+            "AI could not produce a command for this request".to_string()
+        } else {
+            explanation
+        });
+    }
+
+    let risk = match_destructive(&command);
+    let mut danger_level = raw.danger_level.unwrap_or(DangerLevel::Caution);
+    if risk.is_some() {
+        danger_level = DangerLevel::Dangerous;
+    }
+    let requires_sudo = raw.requires_sudo
+        || command
+            .split([';', '|', '&', '\n'])
+            .any(|segment| segment.split_whitespace().next() == Some("sudo"));
+
+    Ok(GeneratedCommand {
+        command,
+        explanation,
+        danger_level,
+        requires_sudo,
+        risk: risk.map(str::to_string),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_generated_command() {
+        let response = r#"```json
+{"command": "find . -name \"*.log\"", "explanation": "查找日志文件", "dangerLevel": "safe", "requiresSudo": false}
+```"#;
+        let generated = parse_generated_command(response).unwrap();
+        assert_eq!(generated.command, "find . -name \"*.log\"");
+        assert_eq!(generated.danger_level, DangerLevel::Safe);
+        assert!(!generated.requires_sudo);
+
+        // 本地规则提升危险等级，sudo 前缀补全标记
+        let generated = parse_generated_command(
+            r#"{"command": "cd /tmp && sudo rm -rf build", "explanation": "删除", "dangerLevel": "caution", "requiresSudo": false}"#,
+        )
+        .unwrap();
+        assert_eq!(generated.danger_level, DangerLevel::Dangerous);
+        assert!(generated.requires_sudo);
+        assert!(generated.risk.is_some());
+
+        assert_eq!(
+            parse_generated_command(r#"{"command": "", "explanation": "请说明要停止哪个服务"}"#).unwrap_err(),
+            "请说明要停止哪个服务"
+        );
+        assert!(parse_generated_command(r#"{"command": "ls", "dangerLevel": "unknown"}"#).is_err());
+        assert!(parse_generated_command("ls -la").is_err());
+    }
+}
@@ -0,0 +1,202 @@
+//! AI 文件助手
+//!
+//! 将自然语言的文件操作需求（如“压缩 /var/log 下 7 天前的日志并下载”）交给 AI 转换为
+//! 由远程命令和 SFTP 传输组成的执行计划。计划先返回给用户审阅，确认后才按顺序执行，
+//! 传输步骤经由传输队列完成。
+
+use serde::{Deserialize, Serialize};
+
+use crate::ssh::protection::match_destructive;
+
+/// 单个计划最多包含的步骤数
+pub const MAX_PLAN_STEPS: usize = 20;
+
+/// 执行进度事件
+pub const FILE_ASSISTANT_EVENT: &str = "ai-file-assistant-step";
+
+/// 生成计划的系统提示词
+pub const PLAN_SYSTEM_PROMPT: &str = r#"你是 Linux 文件操作助手。把用户的文件操作需求转换为执行计划，只输出 JSON，不要解释。
+
+格式：
+{"summary": "一句话说明计划", "steps": [步骤...]}
+
+步骤只能是以下三种：
+{"type": "exec", "command": "在远程主机执行的 shell 命令", "description": "说明"}
+{"type": "download", "remotePath": "远程绝对路径", "description": "说明"}
+{"type": "upload", "localPath": "本地绝对路径", "remotePath": "远程目标目录", "description": "说明"}
+
+规则：
+1. 远程路径必须是绝对路径，下载的文件保存到用户选择的本地目录
+2. 需要打包、筛选或删除文件时使用 exec 步骤，临时文件放在 /tmp
+3. 优先使用非交互、可重复执行的命令，不使用 sudo
+4. 需求不明确或无法完成时返回 {"summary": "原因", "steps": []}"#;
+
+/// 计划中的一个步骤
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum FilePlanStep {
+    /// 在远程主机执行命令
+    Exec {
+        command: String,
+        #[serde(default)]
+        description: String,
+    },
+    /// 下载远程文件或目录到本地目录
+    Download {
+        remote_path: String,
+        #[serde(default)]
+        description: String,
+    },
+    /// 上传本地文件或目录到远程目录
+    Upload {
+        local_path: String,
+        remote_path: String,
+        #[serde(default)]
+        description: String,
+    },
+}
+
+impl FilePlanStep {
+    fn validate(&self) -> Result<(), String> {
+        match self {
+            FilePlanStep::Exec { command, .. } if command.trim().is_empty() => {
+                Err("Exec step has an empty command".to_string())
+            }
+            FilePlanStep::Download { remote_path, .. } | FilePlanStep::Upload { remote_path, .. }
+                if !remote_path.starts_with('/') =>
+            {
+                Err(format!("Remote path must be absolute: {}", remote_path))
+            }
+            FilePlanStep::Upload { local_path, .. } if local_path.trim().is_empty() => {
+                Err("Upload step has an empty local path".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// 命中的危险命令模式（仅 exec 步骤）
+    pub fn risk(&self) -> Option<&'static str> {
+        match self {
+            FilePlanStep::Exec { command, .. } => match_destructive(command),
+            _ => None,
+        }
+    }
+}
+
+/// 待审阅的计划步骤
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PlannedStep {
+    #[serde(flatten)]
+    pub step: FilePlanStep,
+    /// 命中的危险命令模式，前端高亮提示
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub risk: Option<String>,
+}
+
+/// 执行计划
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FilePlan {
+    pub summary: String,
+    pub steps: Vec<PlannedStep>,
+}
+
+/// AI 原始输出中的计划
+#[derive(Deserialize)]
+struct RawPlan {
+    #[serde(default)]
+    summary: String,
+    #[serde(default)]
+    steps: Vec<FilePlanStep>,
+}
+
+/// 解析 AI 返回的计划（允许包裹在 Markdown 代码块或说明文字中）
+pub fn parse_plan(text: &str) -> Result<FilePlan, String> {
+    let start = text.find('{').ok_or("AI response does not contain a plan")?;
+    let end = text.rfind('}').filter(|&end| end > start).ok_or("AI response does not contain a plan")?;
+    let raw: RawPlan = serde_json::from_str(&text[start..=end])
+        .map_err(|e| format!("Invalid plan from AI: {}", e))?;
+
+    if raw.steps.is_empty() {
+        let reason = raw.summary.trim();
+        return Err(if reason.is_empty() {
+            "AI could not produce a plan for this request".to_string()
+        } else {
+            reason.to_string()
+        });
+    }
+    if raw.steps.len() > MAX_PLAN_STEPS {
+        return Err(format!("Plan has too many steps ({} > {})", raw.steps.len(), MAX_PLAN_STEPS));
+    }
+
+    let steps = raw
+        .steps
+        .into_iter()
+        .map(|step| {
+            step.validate()?;
+            let risk = step.risk().map(str::to_string);
+            Ok(PlannedStep { step, risk })
+        })
+        .collect::<Result<_, String>>()?;
+
+    Ok(FilePlan {
+        summary: raw.summary.trim().to_string(),
+        steps,
+    })
+}
+
+/// 步骤执行状态
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum StepStatus {
+    Running,
+    Succeeded,
+    Failed,
+    /// 前面的步骤失败，未执行
+    Skipped,
+}
+
+/// 步骤执行进度（`ai-file-assistant-step` 事件内容，也作为执行结果返回）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StepProgress {
+    pub connection_id: String,
+    pub index: usize,
+    pub status: StepStatus,
+    /// 命令输出、传输目标或错误信息
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_plan() {
+        let response = r#"好的：
+```json
+{"summary": "打包旧日志并下载",
+ "steps": [
+   {"type": "exec", "command": "find /var/log -name '*.log' -mtime +7 | tar czf /tmp/logs.tgz -T -", "description": "打包"},
+   {"type": "download", "remotePath": "/tmp/logs.tgz"},
+   {"type": "exec", "command": "rm -rf /tmp/logs.tgz", "description": "清理"}
+ ]}
+```"#;
+        let plan = parse_plan(response).unwrap();
+        assert_eq!(plan.summary, "打包旧日志并下载");
+        assert_eq!(plan.steps.len(), 3);
+        assert_eq!(
+            plan.steps[1].step,
+            FilePlanStep::Download { remote_path: "/tmp/logs.tgz".to_string(), description: String::new() }
+        );
+        assert!(plan.steps[0].risk.is_none());
+        assert!(plan.steps[2].risk.is_some());
+
+        assert_eq!(parse_plan(r#"{"summary": "请说明要下载哪些文件", "steps": []}"#).unwrap_err(), "请说明要下载哪些文件");
+        assert!(parse_plan(r#"{"steps": [{"type": "download", "remotePath": "logs.tgz"}]}"#).is_err());
+        assert!(parse_plan(r#"{"steps": [{"type": "chmod", "path": "/tmp"}]}"#).is_err());
+        assert!(parse_plan("no plan").is_err());
+    }
+}
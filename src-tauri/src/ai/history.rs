@@ -68,6 +68,12 @@ pub struct AIConversationMeta {
     pub is_archived: bool,
     /// 连接状态
     pub connection_status: ConnectionStatus,
+    /// 分支来源对话 ID（从其他对话的某条消息分叉而来）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<String>,
+    /// 分叉点：父对话中最后一条被复制的消息索引
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch_point: Option<usize>,
 }
 
 /// 对话会话完整数据
@@ -182,9 +188,57 @@ impl AIChatHistory {
             .ok_or_else(|| format!("会话 {} 不存在", id))?;
 
         self.conversations.remove(pos);
+
+        // 父对话删除后，分支成为独立对话
+        for conversation in self.conversations.iter_mut() {
+            if conversation.meta.parent_id.as_deref() == Some(id) {
+                conversation.meta.parent_id = None;
+                conversation.meta.branch_point = None;
+            }
+        }
         Ok(())
     }
 
+    /// 从指定消息处分叉对话
+    ///
+    /// 复制父对话中 `message_index`（含）之前的消息作为新对话，原对话保持不变
+    pub fn fork_conversation(&mut self, id: &str, message_index: usize, new_id: String) -> Result<AIConversationMeta, String> {
+        let parent = self.get_conversation(id)
+            .ok_or_else(|| format!("会话 {} 不存在", id))?;
+        if message_index >= parent.messages.len() {
+            return Err(format!("消息索引 {} 超出范围（共 {} 条）", message_index, parent.messages.len()));
+        }
+
+        let now = Utc::now();
+        let messages = parent.messages[..=message_index].to_vec();
+        let meta = AIConversationMeta {
+            id: new_id,
+            title: format!("{} (分支)", parent.meta.title),
+            created_at: now,
+            updated_at: now,
+            message_count: messages.len(),
+            is_archived: false,
+            parent_id: Some(parent.meta.id.clone()),
+            branch_point: Some(message_index),
+            ..parent.meta.clone()
+        };
+
+        self.conversations.push(AIConversation {
+            meta: meta.clone(),
+            messages,
+        });
+        Ok(meta)
+    }
+
+    /// 获取从指定对话分叉出的所有分支
+    pub fn list_branches(&self, id: &str) -> Vec<AIConversationMeta> {
+        self.conversations
+            .iter()
+            .filter(|c| c.meta.parent_id.as_deref() == Some(id))
+            .map(|c| c.meta.clone())
+            .collect()
+    }
+
     /// 归档/取消归档会话
     pub fn toggle_archive(&mut self, id: &str) -> Result<(), String> {
         let conversation = self.conversations
@@ -346,3 +400,63 @@ pub fn conversation_to_text(conv: &AIConversation) -> String {
 
     output
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conversation(id: &str, messages: &[&str]) -> AIConversation {
+        let now = Utc::now();
+        AIConversation {
+            meta: AIConversationMeta {
+                id: id.to_string(),
+                title: "磁盘空间不足".to_string(),
+                connection_id: "conn-1".to_string(),
+                server_identity: ServerIdentity {
+                    session_id: "session-1".to_string(),
+                    session_name: "web".to_string(),
+                    host: "10.0.0.1".to_string(),
+                    port: 22,
+                    username: "root".to_string(),
+                },
+                created_at: now,
+                updated_at: now,
+                message_count: messages.len(),
+                is_archived: false,
+                connection_status: ConnectionStatus::Active,
+                parent_id: None,
+                branch_point: None,
+            },
+            messages: messages
+                .iter()
+                .map(|content| AIChatMessage {
+                    role: "user".to_string(),
+                    content: content.to_string(),
+                    timestamp: now,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_fork_conversation() {
+        let mut history = AIChatHistory::default();
+        history.upsert_conversation(conversation("root", &["df -h", "清理日志", "扩容磁盘"]));
+
+        let branch = history.fork_conversation("root", 1, "branch".to_string()).unwrap();
+        assert_eq!(branch.parent_id.as_deref(), Some("root"));
+        assert_eq!(branch.branch_point, Some(1));
+        assert_eq!(branch.message_count, 2);
+        assert_eq!(history.get_conversation("branch").unwrap().messages[1].content, "清理日志");
+        assert_eq!(history.get_conversation("root").unwrap().messages.len(), 3);
+        assert_eq!(history.list_branches("root").len(), 1);
+        assert!(history.fork_conversation("root", 3, "other".to_string()).is_err());
+
+        // 旧格式的历史文件没有分支字段
+        let json = serde_json::to_value(&history.get_conversation("root").unwrap().meta).unwrap();
+        assert!(json.get("parentId").is_none());
+
+        history.delete_conversation("root").unwrap();
+        assert!(history.get_conversation("branch").unwrap().meta.parent_id.is_none());
+    }
+}
@@ -1,8 +1,10 @@
 // AI Provider Manager - 管理和缓存 AI Provider 实例
 
 use super::provider::AIProvider;
-use super::{OpenAIProvider, OllamaProvider};
+use super::server::SERVER_PROVIDER_TYPE;
+use super::{OpenAIProvider, OllamaProvider, ServerProvider};
 use crate::commands::ai::AIProviderConfig;
+use crate::commands::auth::ApiClientStateWrapper;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use tracing::{debug, info};
@@ -15,6 +17,8 @@ pub struct AIProviderManager {
     /// Key: provider 配置的哈希值
     /// Value: Arc 包装的 Provider 实例（可以安全地在多个请求之间共享）
     cache: Arc<Mutex<HashMap<String, Arc<dyn AIProvider + Send + Sync>>>>,
+    /// 服务器托管 Provider 使用的 API Client 状态
+    api_client_state: Option<ApiClientStateWrapper>,
 }
 
 impl AIProviderManager {
@@ -23,9 +27,16 @@ impl AIProviderManager {
         info!("[AIProviderManager] Initializing AI Provider Manager");
         Self {
             cache: Arc::new(Mutex::new(HashMap::new())),
+            api_client_state: None,
         }
     }
 
+    /// 设置 API Client 状态，启用服务器托管的 Provider
+    pub fn with_api_client_state(mut self, api_client_state: ApiClientStateWrapper) -> Self {
+        self.api_client_state = Some(api_client_state);
+        self
+    }
+
     /// 生成 provider 配置的唯一标识符
     ///
     /// 基于配置的关键参数生成哈希值，用于缓存 key
@@ -52,7 +63,7 @@ impl AIProviderManager {
 
         // API key 也参与哈希（不同 API key 应该是不同的实例）
         if let Some(ref key) = config.api_key {
-            key.hash(&mut hasher);
+            key.as_str().hash(&mut hasher);
         }
 
         format!("{}:{:x}", config.provider_type, hasher.finish())
@@ -109,6 +120,18 @@ impl AIProviderManager {
         config: &AIProviderConfig,
     ) -> Result<Arc<dyn AIProvider + Send + Sync>, String> {
         match config.provider_type.as_str() {
+            SERVER_PROVIDER_TYPE => {
+                debug!("[AIProviderManager] Creating server-managed provider");
+                let api_client_state = self
+                    .api_client_state
+                    .clone()
+                    .ok_or("Server-managed AI is not available".to_string())?;
+                Ok(Arc::new(ServerProvider::new(
+                    api_client_state,
+                    config.temperature,
+                    config.max_tokens,
+                )))
+            }
             "ollama" => {
                 debug!("[AIProviderManager] Creating Ollama provider");
                 Ok(Arc::new(OllamaProvider::new(
@@ -126,7 +149,8 @@ impl AIProviderManager {
                 );
                 let api_key = config
                     .api_key
-                    .clone()
+                    .as_deref()
+                    .cloned()
                     .ok_or("API key is required for this provider".to_string())?;
                 Ok(Arc::new(OpenAIProvider::new(
                     api_key,
@@ -276,7 +300,7 @@ mod tests {
     fn test_cache_key_generation() {
         let config1 = AIProviderConfig {
             provider_type: "openai".to_string(),
-            api_key: Some("sk-test123".to_string()),
+            api_key: Some("sk-test123".to_string().into()),
             base_url: Some("https://api.openai.com/v1".to_string()),
             model: "gpt-4".to_string(),
             temperature: Some(0.7),
@@ -285,7 +309,7 @@ mod tests {
 
         let config2 = AIProviderConfig {
             provider_type: "openai".to_string(),
-            api_key: Some("sk-test123".to_string()),
+            api_key: Some("sk-test123".to_string().into()),
             base_url: Some("https://api.openai.com/v1".to_string()),
             model: "gpt-4".to_string(),
             temperature: Some(0.7),
@@ -294,7 +318,7 @@ mod tests {
 
         let config3 = AIProviderConfig {
             provider_type: "openai".to_string(),
-            api_key: Some("sk-different".to_string()),
+            api_key: Some("sk-different".to_string().into()),
             base_url: Some("https://api.openai.com/v1".to_string()),
             model: "gpt-4".to_string(),
             temperature: Some(0.7),
@@ -3,10 +3,15 @@
 pub mod provider;
 pub mod openai;
 pub mod ollama;
+pub mod server;
 pub mod manager;
 pub mod history;
+pub mod file_assistant;
+pub mod command_gen;
+pub mod runtime;
 
 pub use provider::ChatMessage;
 pub use openai::OpenAIProvider;
 pub use ollama::OllamaProvider;
+pub use server::ServerProvider;
 pub use manager::AIProviderManager;
@@ -19,6 +19,9 @@ struct OllamaRequest {
     prompt: String,
     stream: bool,
     options: OllamaOptions,
+    /// 输出格式约束（JSON Schema），为空时不发送
+    #[serde(skip_serializing_if = "Option::is_none")]
+    format: Option<serde_json::Value>,
 }
 
 /// Ollama 响应
@@ -76,10 +79,13 @@ impl OllamaProvider {
     }
 }
 
-#[async_trait]
-impl AIProvider for OllamaProvider {
-    /// 发送聊天请求到 Ollama API
-    async fn chat(&self, messages: Vec<ChatMessage>) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+impl OllamaProvider {
+    /// 发送生成请求，`format` 不为空时要求模型按该 JSON Schema 输出
+    async fn generate(
+        &self,
+        messages: Vec<ChatMessage>,
+        format: Option<serde_json::Value>,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
         let url = format!("{}/api/generate", self.base_url);
 
         tracing::info!("[Ollama] Sending request to: {}", url);
@@ -99,6 +105,7 @@ impl AIProvider for OllamaProvider {
                 temperature: self.temperature,
                 num_predict: self.max_tokens,
             },
+            format,
         };
 
         tracing::debug!("[Ollama] Request body: {:?}", serde_json::to_string(&request));
@@ -135,6 +142,23 @@ impl AIProvider for OllamaProvider {
         tracing::info!("[Ollama] Response: {}", content_preview);
         Ok(content.clone())
     }
+}
+
+#[async_trait]
+impl AIProvider for OllamaProvider {
+    /// 发送聊天请求到 Ollama API
+    async fn chat(&self, messages: Vec<ChatMessage>) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        self.generate(messages, None).await
+    }
+
+    /// 使用 Ollama 的 `format` 参数约束输出
+    async fn chat_json(
+        &self,
+        messages: Vec<ChatMessage>,
+        schema: &serde_json::Value,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        self.generate(messages, Some(schema.clone())).await
+    }
 
     /// 测试 Ollama 服务连接
     async fn test_connection(&self) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
@@ -15,6 +15,9 @@ struct OpenAIRequest {
     temperature: f32,
     max_tokens: u32,
     stream: bool,
+    /// 结构化输出约束（`json_schema`），为空时不发送
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_format: Option<serde_json::Value>,
 }
 
 /// OpenAI 响应体
@@ -81,10 +84,13 @@ impl OpenAIProvider {
     }
 }
 
-#[async_trait]
-impl AIProvider for OpenAIProvider {
-    /// 发送聊天请求到 OpenAI API
-    async fn chat(&self, messages: Vec<ChatMessage>) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+impl OpenAIProvider {
+    /// 发送非流式聊天请求，`response_format` 不为空时要求模型按其约束输出
+    async fn complete(
+        &self,
+        messages: Vec<ChatMessage>,
+        response_format: Option<serde_json::Value>,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
         let url = format!("{}/chat/completions", self.base_url);
 
         tracing::info!("[OpenAI] Sending request to: {}", url);
@@ -99,6 +105,7 @@ impl AIProvider for OpenAIProvider {
             temperature: self.temperature,
             max_tokens: self.max_tokens,
             stream: false,
+            response_format,
         };
 
         tracing::debug!("[OpenAI] Request body: {:?}", serde_json::to_string(&request));
@@ -136,6 +143,39 @@ impl AIProvider for OpenAIProvider {
         tracing::info!("[OpenAI] Response: {}", content_preview);
         Ok(content.clone())
     }
+}
+
+#[async_trait]
+impl AIProvider for OpenAIProvider {
+    /// 发送聊天请求到 OpenAI API
+    async fn chat(&self, messages: Vec<ChatMessage>) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        self.complete(messages, None).await
+    }
+
+    /// 使用 `response_format: json_schema` 发送请求
+    ///
+    /// 部分 OpenAI 兼容服务不支持结构化输出，请求失败时退回普通聊天
+    async fn chat_json(
+        &self,
+        messages: Vec<ChatMessage>,
+        schema: &serde_json::Value,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let response_format = serde_json::json!({
+            "type": "json_schema",
+            "json_schema": {
+                "name": "response",
+                "strict": true,
+                "schema": schema,
+            },
+        });
+        match self.complete(messages.clone(), Some(response_format)).await {
+            Ok(content) => Ok(content),
+            Err(e) => {
+                tracing::warn!("[OpenAI] Structured output failed, retrying without schema: {}", e);
+                self.complete(messages, None).await
+            }
+        }
+    }
 
     /// 测试 OpenAI API 连接
     async fn test_connection(&self) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
@@ -178,6 +218,7 @@ impl OpenAIProvider {
             temperature: self.temperature,
             max_tokens: self.max_tokens,
             stream: true,
+            response_format: None,
         };
 
         let response = self.client
@@ -17,6 +17,17 @@ pub trait AIProvider: Send + Sync {
     /// 发送聊天请求
     async fn chat(&self, messages: Vec<ChatMessage>) -> Result<String, Box<dyn std::error::Error + Send + Sync>>;
 
+    /// 发送要求按 JSON Schema 输出的聊天请求
+    ///
+    /// 默认实现退回普通聊天，由调用方在提示词中说明格式并自行校验
+    async fn chat_json(
+        &self,
+        messages: Vec<ChatMessage>,
+        _schema: &serde_json::Value,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        self.chat(messages).await
+    }
+
     /// 测试连接
     async fn test_connection(&self) -> Result<bool, Box<dyn std::error::Error + Send + Sync>>;
 }
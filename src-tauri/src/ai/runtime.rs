@@ -0,0 +1,177 @@
+// AI 运行时配置 - 提示词模板、安全规则与 Provider 池的原子快照
+//
+// 每次 AI 调用开始时取一份快照（Arc），热重载只替换指针，
+// 正在进行中的请求/流继续持有旧快照，不会被中途打断。
+
+use super::server::SERVER_PROVIDER_TYPE;
+use crate::commands::ai::AIProviderConfig;
+use crate::config::storage::{AIConfig, AIPromptTemplates, AISafetyRules};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, RwLock};
+
+/// 热重载状态事件名
+pub const RELOAD_STATUS_EVENT: &str = "ai-reload-status";
+
+/// 热重载状态事件负载
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReloadStatus {
+    /// 是否已应用新配置
+    pub applied: bool,
+    /// 校验错误（非空时未应用）
+    pub errors: Vec<String>,
+    /// 从缓存中移除的 Provider 数量
+    pub removed_count: usize,
+}
+
+/// AI 运行时配置快照
+#[derive(Debug, Clone, Default)]
+pub struct AIRuntimeConfig {
+    pub prompt_templates: AIPromptTemplates,
+    pub safety_rules: AISafetyRules,
+    pub providers: Vec<AIProviderConfig>,
+}
+
+impl AIRuntimeConfig {
+    pub fn from_config(config: &AIConfig) -> Self {
+        Self {
+            prompt_templates: config.prompt_templates.clone(),
+            safety_rules: config.safety_rules.clone(),
+            providers: config
+                .providers
+                .iter()
+                .map(|p| AIProviderConfig {
+                    provider_type: p.provider_type.clone(),
+                    api_key: p.api_key.clone(),
+                    base_url: p.base_url.clone(),
+                    model: p.model.clone(),
+                    temperature: Some(p.temperature),
+                    max_tokens: Some(p.max_tokens),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// 校验 AI 配置，返回所有错误（空表示通过）
+pub fn validate(config: &AIConfig) -> Vec<String> {
+    let mut errors = Vec::new();
+
+    for provider in &config.providers {
+        let name = if provider.name.trim().is_empty() { &provider.id } else { &provider.name };
+
+        // 服务器托管的 Provider 使用服务器配置的模型和凭据
+        let server_managed = provider.provider_type == SERVER_PROVIDER_TYPE;
+        if provider.model.trim().is_empty() && !server_managed {
+            errors.push(format!("Provider {}: 模型不能为空", name));
+        }
+        if provider.enabled
+            && provider.provider_type != "ollama"
+            && !server_managed
+            && provider.api_key.as_deref().is_none_or(|key| key.trim().is_empty())
+        {
+            errors.push(format!("Provider {}: 缺少 API Key", name));
+        }
+        if let Some(url) = provider.base_url.as_deref().filter(|url| !url.trim().is_empty()) {
+            if !url.starts_with("http://") && !url.starts_with("https://") {
+                errors.push(format!("Provider {}: Base URL 必须以 http:// 或 https:// 开头", name));
+            }
+        }
+        if !(0.0..=2.0).contains(&provider.temperature) {
+            errors.push(format!("Provider {}: temperature 必须在 0 到 2 之间", name));
+        }
+        if provider.max_tokens == 0 {
+            errors.push(format!("Provider {}: max_tokens 必须大于 0", name));
+        }
+    }
+
+    if !config.default_provider.is_empty()
+        && !config.providers.iter().any(|p| p.id == config.default_provider)
+    {
+        errors.push(format!("默认 Provider {} 不存在", config.default_provider));
+    }
+
+    if config.safety_rules.blocked_patterns.iter().any(|p| p.trim().is_empty()) {
+        errors.push("安全规则中存在空白规则".to_string());
+    }
+
+    errors
+}
+
+/// AI 运行时，持有当前生效的配置快照
+#[derive(Default)]
+pub struct AIRuntime {
+    current: RwLock<Option<Arc<AIRuntimeConfig>>>,
+}
+
+impl AIRuntime {
+    /// 获取当前快照，尚未加载时返回 None
+    pub fn current(&self) -> Option<Arc<AIRuntimeConfig>> {
+        self.current.read().unwrap().clone()
+    }
+
+    /// 原子替换快照，返回旧快照
+    pub fn replace(&self, config: AIRuntimeConfig) -> Option<Arc<AIRuntimeConfig>> {
+        self.current.write().unwrap().replace(Arc::new(config))
+    }
+
+    /// 丢弃当前快照（其中包含明文 API Key），下次使用时重新加载
+    pub fn clear(&self) {
+        self.current.write().unwrap().take();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::storage::{AIProviderConfig as StoredProvider, AIShortcuts};
+
+    fn config(provider: StoredProvider) -> AIConfig {
+        AIConfig {
+            default_provider: provider.id.clone(),
+            providers: vec![provider],
+            shortcuts: AIShortcuts {
+                explain_command: String::new(),
+                open_chat: String::new(),
+                nl_to_command: String::new(),
+            },
+            prompt_templates: AIPromptTemplates::default(),
+            safety_rules: AISafetyRules::default(),
+        }
+    }
+
+    #[test]
+    fn test_validate_and_swap() {
+        let provider = StoredProvider {
+            id: "p1".to_string(),
+            provider_type: "openai".to_string(),
+            name: "OpenAI".to_string(),
+            api_key: None,
+            base_url: Some("api.openai.com".to_string()),
+            model: "gpt-4o".to_string(),
+            temperature: 0.7,
+            max_tokens: 2000,
+            enabled: true,
+            ..Default::default()
+        };
+        let mut cfg = config(provider);
+        assert_eq!(validate(&cfg).len(), 2);
+
+        cfg.providers[0].api_key = Some("sk-test".to_string().into());
+        cfg.providers[0].base_url = Some("https://api.openai.com/v1".to_string());
+        cfg.safety_rules.blocked_patterns = vec!["rm -rf /".to_string()];
+        assert!(validate(&cfg).is_empty());
+
+        let runtime = AIRuntime::default();
+        assert!(runtime.current().is_none());
+        runtime.replace(AIRuntimeConfig::from_config(&cfg));
+        let held = runtime.current().unwrap();
+
+        cfg.safety_rules.blocked_patterns.clear();
+        let old = runtime.replace(AIRuntimeConfig::from_config(&cfg)).unwrap();
+        // 旧快照仍可被进行中的请求使用
+        assert!(Arc::ptr_eq(&held, &old));
+        assert_eq!(held.safety_rules.matches("sudo RM -RF / "), Some("rm -rf /"));
+        assert!(runtime.current().unwrap().safety_rules.matches("rm -rf /").is_none());
+    }
+}
@@ -0,0 +1,93 @@
+// 服务器托管的 AI Provider - 通过 ApiClient 由同步服务器转发请求
+
+use super::provider::{AIProvider, ChatMessage};
+use crate::commands::auth::ApiClientStateWrapper;
+use crate::models::ai_proxy::{ServerAiChatMessage, ServerAiChatRequest, ServerAiQuota};
+use async_trait::async_trait;
+
+/// Provider 类型标识
+pub const SERVER_PROVIDER_TYPE: &str = "server";
+
+/// 服务器托管 Provider
+///
+/// 使用服务器配置的凭据和模型，客户端无需保存 API Key；
+/// 每次请求时读取当前登录用户的 ApiClient，切换账号后无需重建
+pub struct ServerProvider {
+    api_client_state: ApiClientStateWrapper,
+    temperature: Option<f32>,
+    max_tokens: Option<u32>,
+}
+
+impl ServerProvider {
+    pub fn new(
+        api_client_state: ApiClientStateWrapper,
+        temperature: Option<f32>,
+        max_tokens: Option<u32>,
+    ) -> Self {
+        Self {
+            api_client_state,
+            temperature,
+            max_tokens,
+        }
+    }
+
+    /// 获取当前用户的配额
+    pub async fn quota(&self) -> anyhow::Result<ServerAiQuota> {
+        let client = self.api_client_state.wait_client().await?;
+        let (quota, _, _) = client.ai_quota().await.map_err(describe_error)?;
+        Ok(quota)
+    }
+}
+
+/// 将服务器返回的状态码转换为可读的错误
+fn describe_error(error: anyhow::Error) -> anyhow::Error {
+    let message = error.to_string();
+    if message.contains("API error (429") {
+        anyhow::anyhow!("今日 AI 请求次数已用完")
+    } else if message.contains("API error (503") {
+        anyhow::anyhow!("服务器未启用 AI 代理")
+    } else if message.contains("API error (502") {
+        anyhow::anyhow!("服务器 AI 代理请求失败")
+    } else {
+        error
+    }
+}
+
+#[async_trait]
+impl AIProvider for ServerProvider {
+    /// 通过服务器转发聊天请求
+    async fn chat(&self, messages: Vec<ChatMessage>) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let client = self.api_client_state.wait_client().await?;
+
+        tracing::info!("[ServerAI] Sending request, messages count: {}", messages.len());
+
+        let request = ServerAiChatRequest {
+            messages: messages
+                .into_iter()
+                .map(|m| ServerAiChatMessage { role: m.role, content: m.content })
+                .collect(),
+            temperature: self.temperature,
+            max_tokens: self.max_tokens,
+        };
+        let (result, _, _) = client.ai_chat(&request).await.map_err(describe_error)?;
+
+        tracing::info!(
+            "[ServerAI] Response from {} ({} chars, remaining: {:?})",
+            result.model,
+            result.content.len(),
+            result.remaining
+        );
+        Ok(result.content)
+    }
+
+    /// 测试服务器是否已启用 AI 代理
+    async fn test_connection(&self) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        match self.quota().await {
+            Ok(quota) => Ok(quota.enabled),
+            Err(e) => {
+                tracing::error!("[ServerAI] Connection test failed: {}", e);
+                Ok(false)
+            }
+        }
+    }
+}
@@ -0,0 +1,5 @@
+// 自动补全模块 - 按主机合并 PATH 可执行文件、命令历史和命令片段
+
+pub mod provider;
+
+pub use provider::{AutocompleteProvider, Suggestion};
@@ -0,0 +1,200 @@
+use crate::models::snippet::{CommandHistoryEntry, Snippet};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// 列出远程 PATH 下所有可执行文件名的命令（兼容 sh/bash/zsh）
+pub const LIST_PATH_BINARIES_COMMAND: &str =
+    "for d in $(echo \"$PATH\" | tr ':' ' '); do ls -1 \"$d\" 2>/dev/null; done";
+
+/// 默认返回的建议数量
+pub const DEFAULT_SUGGESTION_LIMIT: usize = 20;
+
+/// 建议来源
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum SuggestionKind {
+    History,
+    Snippet,
+    Binary,
+}
+
+/// 补全建议
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Suggestion {
+    /// 补全后的完整命令
+    pub text: String,
+    pub kind: SuggestionKind,
+    /// 附加说明（片段名称、历史使用次数等）
+    pub description: Option<String>,
+    pub score: i64,
+}
+
+/// 自动补全提供者
+///
+/// 远程 PATH 可执行文件列表按主机缓存，每台主机只通过 exec 采集一次
+pub struct AutocompleteProvider {
+    binaries: RwLock<HashMap<String, Arc<Vec<String>>>>,
+}
+
+impl AutocompleteProvider {
+    pub fn new() -> Self {
+        Self {
+            binaries: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// 获取主机已缓存的可执行文件列表
+    pub async fn cached_binaries(&self, host_key: &str) -> Option<Arc<Vec<String>>> {
+        self.binaries.read().await.get(host_key).cloned()
+    }
+
+    /// 由 exec 输出更新主机的可执行文件列表（去重并排序）
+    pub async fn set_binaries(&self, host_key: &str, exec_stdout: &str) -> Arc<Vec<String>> {
+        let mut names: Vec<String> = exec_stdout
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.contains('/'))
+            .map(str::to_string)
+            .collect();
+        names.sort_unstable();
+        names.dedup();
+
+        let names = Arc::new(names);
+        self.binaries.write().await.insert(host_key.to_string(), names.clone());
+        names
+    }
+
+    /// 清除主机缓存（下次补全时重新采集）
+    pub async fn invalidate(&self, host_key: &str) {
+        self.binaries.write().await.remove(host_key);
+    }
+}
+
+impl Default for AutocompleteProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 合并三种来源并按得分排序
+///
+/// - 历史命令：基础分最高，使用次数越多、越近期越靠前
+/// - 片段：按命令或名称前缀匹配
+/// - 可执行文件：仅在输入第一个单词时参与匹配，名称越短越靠前
+///
+/// 相同文本只保留得分最高的一条
+pub fn rank_suggestions(
+    prefix: &str,
+    history: &[CommandHistoryEntry],
+    snippets: &[Snippet],
+    binaries: &[String],
+    limit: usize,
+) -> Vec<Suggestion> {
+    let mut merged: HashMap<String, Suggestion> = HashMap::new();
+
+    // 历史按最近使用排序，位置越靠前加分越多
+    let history_len = history.len() as i64;
+    for (index, entry) in history.iter().enumerate() {
+        if entry.command.starts_with(prefix) && entry.command != prefix {
+            let recency = history_len - index as i64;
+            push_suggestion(&mut merged, Suggestion {
+                text: entry.command.clone(),
+                kind: SuggestionKind::History,
+                description: Some(format!("×{}", entry.use_count)),
+                score: 10_000 + entry.use_count.min(1_000) * 10 + recency.min(1_000),
+            });
+        }
+    }
+
+    for snippet in snippets {
+        if snippet.command.starts_with(prefix) || snippet.name.starts_with(prefix) {
+            push_suggestion(&mut merged, Suggestion {
+                text: snippet.command.clone(),
+                kind: SuggestionKind::Snippet,
+                description: Some(snippet.name.clone()),
+                score: 5_000,
+            });
+        }
+    }
+
+    if !prefix.is_empty() && !prefix.contains(char::is_whitespace) {
+        // 二分查找定位前缀起点（binaries 已排序）
+        let start = binaries.partition_point(|name| name.as_str() < prefix);
+        for name in binaries[start..].iter().take_while(|name| name.starts_with(prefix)) {
+            if name != prefix {
+                push_suggestion(&mut merged, Suggestion {
+                    text: name.clone(),
+                    kind: SuggestionKind::Binary,
+                    description: None,
+                    score: 1_000 - (name.len() as i64).min(999),
+                });
+            }
+        }
+    }
+
+    let mut suggestions: Vec<Suggestion> = merged.into_values().collect();
+    suggestions.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.text.cmp(&b.text)));
+    suggestions.truncate(limit);
+    suggestions
+}
+
+/// 加入建议，相同文本保留得分较高的一条
+fn push_suggestion(merged: &mut HashMap<String, Suggestion>, suggestion: Suggestion) {
+    match merged.get(&suggestion.text) {
+        Some(existing) if existing.score >= suggestion.score => {}
+        _ => {
+            merged.insert(suggestion.text.clone(), suggestion);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history(command: &str, use_count: i64) -> CommandHistoryEntry {
+        CommandHistoryEntry {
+            user_id: "u".to_string(),
+            host_key: "root@example.com:22".to_string(),
+            command: command.to_string(),
+            use_count,
+            last_used_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_history_ranks_above_binaries() {
+        let binaries = vec!["git".to_string(), "gitk".to_string(), "grep".to_string()];
+        let suggestions = rank_suggestions("gi", &[history("git status", 3)], &[], &binaries, 10);
+
+        let texts: Vec<&str> = suggestions.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, vec!["git status", "git", "gitk"]);
+        assert_eq!(suggestions[0].kind, SuggestionKind::History);
+    }
+
+    #[test]
+    fn test_binaries_only_match_first_word() {
+        let binaries = vec!["ls".to_string(), "lsof".to_string()];
+        let suggestions = rank_suggestions("sudo ls", &[], &[], &binaries, 10);
+        assert!(suggestions.is_empty());
+    }
+
+    #[test]
+    fn test_duplicate_text_keeps_highest_score() {
+        let binaries = vec!["htop".to_string()];
+        let suggestions = rank_suggestions("ht", &[history("htop", 1)], &[], &binaries, 10);
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].kind, SuggestionKind::History);
+    }
+
+    #[test]
+    fn test_limit_is_applied() {
+        let binaries: Vec<String> = (0..50).map(|i| format!("cmd{:02}", i)).collect();
+        let suggestions = rank_suggestions("cmd", &[], &[], &binaries, 5);
+        assert_eq!(suggestions.len(), 5);
+        assert_eq!(suggestions[0].text, "cmd00");
+    }
+}
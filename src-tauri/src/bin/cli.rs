@@ -0,0 +1,5 @@
+//! 无界面命令行入口，见 `ssh_terminal_lib::cli`
+
+fn main() -> std::process::ExitCode {
+    ssh_terminal_lib::cli::main()
+}
@@ -0,0 +1,486 @@
+//! 无界面命令行模式
+//!
+//! 复用应用的数据库、加密会话存储和 SSH/SFTP 实现，在命令行中使用已保存的会话
+//! 执行一次传输或命令，便于 CI 和脚本调用：
+//!
+//! ```text
+//! ssh-terminal-cli --session prod-web --upload ./dist /srv/app
+//! ssh-terminal-cli --session prod-web --download /var/log/app.log ./logs
+//! ssh-terminal-cli --session prod-web --exec systemctl restart app
+//! ```
+//!
+//! 会话按 ID 或名称查找（当前登录用户，未登录时为本地匿名用户）。
+//! 只支持 SSH 会话；不会打开终端，也不会执行登录脚本和启动命令。
+
+use crate::database::repositories::SshSessionRepository;
+use crate::database::DbPool;
+use crate::error::{Result, SSHError};
+use crate::sftp::client::SftpClient;
+use crate::sftp::SymlinkPolicy;
+use crate::ssh::backends::russh::{RusshBackend, RusshTransport};
+use std::io::{IsTerminal, Write};
+use std::path::Path;
+use std::process::ExitCode;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
+
+/// 参数错误时的退出码
+const USAGE_EXIT_CODE: u8 = 2;
+
+/// 远程命令的超时时间
+const EXEC_TIMEOUT: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// 进度输出的最小间隔
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(200);
+
+const USAGE: &str = "\
+Usage:
+  ssh-terminal-cli --session <name|id> [--follow-symlinks] --upload <local> <remote>
+  ssh-terminal-cli --session <name|id> [--follow-symlinks] --download <remote> <local>
+  ssh-terminal-cli --session <name|id> --exec <command...>
+  ssh-terminal-cli --list-sessions
+
+Options:
+  -s, --session <name|id>   Saved session to connect with
+      --upload <local> <remote>
+                            Upload a file or directory (directory contents go into <remote>)
+      --download <remote> <local>
+                            Download a file or directory (directory contents go into <local>)
+      --follow-symlinks     Transfer what symbolic links point to instead of recreating the links
+      --exec <command...>   Run a command; all remaining arguments form the command
+      --list-sessions       List saved sessions
+  -h, --help                Show this help";
+
+/// 命令行要执行的操作
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CliAction {
+    Upload { local: String, remote: String },
+    Download { remote: String, local: String },
+    Exec { command: String },
+    ListSessions,
+    Help,
+}
+
+/// 解析后的命令行参数
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CliArgs {
+    pub session: Option<String>,
+    pub action: CliAction,
+    /// 目录传输时符号链接的处理方式
+    pub symlinks: SymlinkPolicy,
+}
+
+/// 解析命令行参数（不含程序名）
+pub fn parse_args<I>(args: I) -> std::result::Result<CliArgs, String>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter();
+    let mut session = None;
+    let mut action = None;
+    let mut symlinks = SymlinkPolicy::Preserve;
+
+    fn value(args: &mut impl Iterator<Item = String>, flag: &str) -> std::result::Result<String, String> {
+        args.next().ok_or_else(|| format!("{} requires a value", flag))
+    }
+
+    while let Some(arg) = args.next() {
+        let next = match arg.as_str() {
+            "-s" | "--session" => {
+                session = Some(value(&mut args, &arg)?);
+                continue;
+            }
+            "--follow-symlinks" => {
+                symlinks = SymlinkPolicy::Follow;
+                continue;
+            }
+            "--upload" => CliAction::Upload {
+                local: value(&mut args, &arg)?,
+                remote: value(&mut args, &arg)?,
+            },
+            "--download" => CliAction::Download {
+                remote: value(&mut args, &arg)?,
+                local: value(&mut args, &arg)?,
+            },
+            "--exec" => {
+                let command = args.by_ref().collect::<Vec<_>>().join(" ");
+                if command.is_empty() {
+                    return Err("--exec requires a command".to_string());
+                }
+                CliAction::Exec { command }
+            }
+            "--list-sessions" => CliAction::ListSessions,
+            "-h" | "--help" => CliAction::Help,
+            other => return Err(format!("unknown argument: {}", other)),
+        };
+        if action.replace(next).is_some() {
+            return Err("only one of --upload, --download, --exec, --list-sessions may be given".to_string());
+        }
+    }
+
+    let action = action.ok_or_else(|| "no action given".to_string())?;
+    if session.is_none() && matches!(action, CliAction::Upload { .. } | CliAction::Download { .. } | CliAction::Exec { .. }) {
+        return Err("--session is required".to_string());
+    }
+    Ok(CliArgs { session, action, symlinks })
+}
+
+/// 命令行入口，返回进程退出码（执行远程命令时为远程命令的退出码）
+pub fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, USAGE);
+            return ExitCode::from(USAGE_EXIT_CODE);
+        }
+    };
+    if args.action == CliAction::Help {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+
+    // 日志输出到 stderr，默认只显示警告，避免干扰命令输出
+    let filter = tracing_subscriber::EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("warn").add_directive("russh=off".parse().unwrap()));
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_target(false)
+        .with_writer(std::io::stderr)
+        .init();
+
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!("error: failed to start runtime: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match runtime.block_on(run(args)) {
+        Ok(code) => ExitCode::from(code),
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run(args: CliArgs) -> Result<u8> {
+    let pool = crate::database::init_db_pool()
+        .map_err(|e| SSHError::Storage(format!("无法打开数据库: {}", e)))?;
+
+    let session = match (args.action == CliAction::ListSessions, args.session) {
+        (false, Some(session)) => session,
+        _ => {
+            list_sessions(&pool)?;
+            return Ok(0);
+        }
+    };
+
+    let session_id = resolve_session(&pool, &session)?;
+    let config = crate::commands::session::load_session_from_db(&pool, &session_id)
+        .await
+        .map_err(SSHError::Storage)?
+        .ok_or_else(|| SSHError::SessionNotFound(session.clone()))?;
+    if !config.backend_type.is_russh() {
+        return Err(SSHError::NotSupported(format!("会话 {} 不是 SSH 会话", config.name)));
+    }
+
+    // Ctrl+C 取消正在进行的传输
+    let cancellation_token = CancellationToken::new();
+    {
+        let token = cancellation_token.clone();
+        tokio::spawn(async move {
+            if tokio::signal::ctrl_c().await.is_ok() {
+                token.cancel();
+            }
+        });
+    }
+
+    let transport = RusshBackend::connect_standalone(&config).await?;
+    let result = run_action(&transport, args.action, args.symlinks, &cancellation_token).await;
+    transport.disconnect().await;
+    result
+}
+
+async fn run_action(
+    transport: &RusshTransport,
+    action: CliAction,
+    symlinks: SymlinkPolicy,
+    cancellation_token: &CancellationToken,
+) -> Result<u8> {
+    match action {
+        CliAction::Exec { command } => {
+            let channel = transport.open_exec_channel(&command).await?;
+            let output = tokio::select! {
+                output = crate::ssh::backends::russh::read_exec_output_with(channel, |stream, data| {
+                    let _ = match stream {
+                        crate::ssh::backend::ExecStream::Stdout => std::io::stdout().write_all(data),
+                        crate::ssh::backend::ExecStream::Stderr => std::io::stderr().write_all(data),
+                    };
+                }) => output?,
+                _ = tokio::time::sleep(EXEC_TIMEOUT) => {
+                    return Err(SSHError::Ssh(format!("Command timed out after {:?}", EXEC_TIMEOUT)));
+                }
+                _ = cancellation_token.cancelled() => return Err(SSHError::Io("命令已取消".to_string())),
+            };
+            let _ = std::io::stdout().flush();
+            // 没有退出码（如被信号终止）时按失败处理
+            Ok(output.exit_status.map(|status| status.min(255) as u8).unwrap_or(1))
+        }
+        CliAction::Upload { local, remote } => {
+            let mut client = transport.create_sftp_client().await?;
+            let (bytes, files) = upload(&mut client, &local, &remote, symlinks, cancellation_token).await?;
+            eprintln!("Uploaded {} file(s), {} bytes: {} -> {}", files, bytes, local, remote);
+            Ok(0)
+        }
+        CliAction::Download { remote, local } => {
+            let mut client = transport.create_sftp_client().await?;
+            let (bytes, files) = download(&mut client, &remote, &local, symlinks, cancellation_token).await?;
+            eprintln!("Downloaded {} file(s), {} bytes: {} -> {}", files, bytes, remote, local);
+            Ok(0)
+        }
+        CliAction::ListSessions | CliAction::Help => Ok(0),
+    }
+}
+
+/// 按 ID 或名称查找会话，名称重复时要求使用 ID
+fn resolve_session(pool: &DbPool, session: &str) -> Result<String> {
+    let repo = SshSessionRepository::new(pool.clone());
+    if let Ok(Some(found)) = repo.find_by_id(session) {
+        return Ok(found.id);
+    }
+
+    let (user_id, _) = crate::commands::session::get_current_user_info(pool).map_err(SSHError::Storage)?;
+    let sessions = repo.find_by_user(&user_id).map_err(|e| SSHError::Storage(e.to_string()))?;
+    let matches: Vec<_> = sessions.iter().filter(|s| s.name == session).collect();
+    match matches.as_slice() {
+        [] => Err(SSHError::SessionNotFound(session.to_string())),
+        [found] => Ok(found.id.clone()),
+        _ => Err(SSHError::Storage(format!(
+            "存在多个名为 {} 的会话，请使用 ID: {}",
+            session,
+            matches.iter().map(|s| s.id.as_str()).collect::<Vec<_>>().join(", ")
+        ))),
+    }
+}
+
+fn list_sessions(pool: &DbPool) -> Result<()> {
+    let (user_id, _) = crate::commands::session::get_current_user_info(pool).map_err(SSHError::Storage)?;
+    let sessions = SshSessionRepository::new(pool.clone())
+        .find_by_user(&user_id)
+        .map_err(|e| SSHError::Storage(e.to_string()))?;
+    for session in sessions {
+        println!("{}\t{}\t{}@{}:{}", session.id, session.name, session.username, session.host, session.port);
+    }
+    Ok(())
+}
+
+/// 上传文件或目录，返回 (字节数, 文件数)
+///
+/// 远程路径是已存在的目录时，单个文件上传到该目录下；目录的内容上传到远程路径中
+async fn upload(
+    client: &mut SftpClient,
+    local: &str,
+    remote: &str,
+    symlinks: SymlinkPolicy,
+    cancellation_token: &CancellationToken,
+) -> Result<(u64, u64)> {
+    let metadata = tokio::fs::metadata(local).await
+        .map_err(|_| SSHError::NotFound(format!("本地路径不存在: {}", local)))?;
+
+    if !metadata.is_dir() {
+        let remote = match client.stat(remote).await? {
+            Some(info) if info.is_dir => join_remote(remote, &file_name(local)),
+            _ => remote.to_string(),
+        };
+        let progress = Progress::new(&remote);
+        let bytes = client
+            .upload_file_stream(local, &remote, cancellation_token, |done, total| progress.report(done, total), false, false)
+            .await?;
+        progress.finish();
+        return Ok((bytes, 1));
+    }
+
+    client.create_dir(remote, true).await?;
+    let scan = crate::sftp::client::scan_local_directory(local, remote, symlinks, cancellation_token).await?;
+    for (remote_link_path, target) in &scan.symlinks {
+        if let Some(parent) = Path::new(remote_link_path).parent().and_then(|p| p.to_str()) {
+            client.create_dir(parent, true).await?;
+        }
+        client.create_symlink(remote_link_path, target, true).await?;
+    }
+    let files = scan.files;
+    let mut bytes = 0;
+    for (local_path, remote_path, _) in &files {
+        let progress = Progress::new(remote_path);
+        bytes += client
+            .upload_file_stream(local_path, remote_path, cancellation_token, |done, total| progress.report(done, total), false, false)
+            .await?;
+        progress.finish();
+    }
+    Ok((bytes, files.len() as u64))
+}
+
+/// 下载文件或目录，返回 (字节数, 文件数)
+///
+/// 本地路径是已存在的目录时，单个文件下载到该目录下；目录的内容下载到本地路径中
+async fn download(
+    client: &mut SftpClient,
+    remote: &str,
+    local: &str,
+    symlinks: SymlinkPolicy,
+    cancellation_token: &CancellationToken,
+) -> Result<(u64, u64)> {
+    let info = client.stat(remote).await?
+        .ok_or_else(|| SSHError::NotFound(format!("远程路径不存在: {}", remote)))?;
+
+    if !info.is_dir {
+        let local = if Path::new(local).is_dir() {
+            Path::new(local).join(&info.name).to_string_lossy().to_string()
+        } else {
+            local.to_string()
+        };
+        if let Some(parent) = Path::new(&local).parent().filter(|p| !p.as_os_str().is_empty()) {
+            tokio::fs::create_dir_all(parent).await
+                .map_err(|e| SSHError::Io(format!("无法创建本地目录: {}", e)))?;
+        }
+        let progress = Progress::new(&local);
+        let bytes = client
+            .download_file_stream(remote, &local, cancellation_token, |done, total| progress.report(done, total), false)
+            .await?;
+        progress.finish();
+        return Ok((bytes, 1));
+    }
+
+    let mut bytes = 0;
+    let mut files = 0;
+    let mut dirs = vec![(remote.to_string(), Path::new(local).to_path_buf())];
+    // 跟随符号链接时按规范路径去重，避免链接成环导致无限下载
+    let mut visited = std::collections::HashSet::new();
+    while let Some((remote_dir, local_dir)) = dirs.pop() {
+        if symlinks == SymlinkPolicy::Follow {
+            let canonical = client.canonicalize(&remote_dir).await.unwrap_or_else(|_| remote_dir.clone());
+            if !visited.insert(canonical) {
+                continue;
+            }
+        }
+        if cancellation_token.is_cancelled() {
+            return Err(SSHError::Io("下载已取消".to_string()));
+        }
+        tokio::fs::create_dir_all(&local_dir).await
+            .map_err(|e| SSHError::Io(format!("无法创建本地目录: {}", e)))?;
+
+        for entry in client.list_dir(&remote_dir).await? {
+            let local_path = local_dir.join(&entry.name);
+            if entry.is_symlink && symlinks == SymlinkPolicy::Preserve {
+                let target = match entry.link_target {
+                    Some(target) => target,
+                    None => client.read_link(&entry.path).await?,
+                };
+                crate::sftp::client::create_local_symlink(&target, &local_path, entry.is_dir).await?;
+                continue;
+            }
+            if entry.is_dir {
+                dirs.push((entry.path, local_path));
+                continue;
+            }
+            let local_path = local_path.to_string_lossy().to_string();
+            let progress = Progress::new(&local_path);
+            bytes += client
+                .download_file_stream(&entry.path, &local_path, cancellation_token, |done, total| progress.report(done, total), false)
+                .await?;
+            progress.finish();
+            files += 1;
+        }
+    }
+    Ok((bytes, files))
+}
+
+fn file_name(path: &str) -> String {
+    Path::new(path)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string())
+}
+
+fn join_remote(dir: &str, name: &str) -> String {
+    format!("{}/{}", dir.trim_end_matches('/'), name)
+}
+
+/// 单个文件的传输进度，只在 stderr 是终端时输出
+struct Progress {
+    label: String,
+    enabled: bool,
+    last: Mutex<Option<Instant>>,
+}
+
+impl Progress {
+    fn new(label: &str) -> Self {
+        Self {
+            label: label.to_string(),
+            enabled: std::io::stderr().is_terminal(),
+            last: Mutex::new(None),
+        }
+    }
+
+    fn report(&self, transferred: u64, total: u64) {
+        if !self.enabled {
+            return;
+        }
+        let mut last = self.last.lock().unwrap();
+        if last.is_some_and(|at| at.elapsed() < PROGRESS_INTERVAL) && transferred < total {
+            return;
+        }
+        *last = Some(Instant::now());
+        let percent = transferred.saturating_mul(100).checked_div(total).unwrap_or(100);
+        eprint!("\r{} {:>3}% ({}/{} bytes)", self.label, percent, transferred, total);
+    }
+
+    fn finish(&self) {
+        if self.enabled && self.last.lock().unwrap().is_some() {
+            eprintln!();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> std::result::Result<CliArgs, String> {
+        parse_args(args.iter().map(|s| s.to_string()))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            parse(&["--session", "prod-web", "--upload", "./dist", "/srv/app"]).unwrap(),
+            CliArgs {
+                session: Some("prod-web".to_string()),
+                action: CliAction::Upload { local: "./dist".to_string(), remote: "/srv/app".to_string() },
+                symlinks: SymlinkPolicy::Preserve,
+            }
+        );
+        assert_eq!(
+            parse(&["-s", "db", "--download", "/var/log", "./logs", "--follow-symlinks"]).unwrap().symlinks,
+            SymlinkPolicy::Follow
+        );
+        assert_eq!(
+            parse(&["-s", "db", "--exec", "ls", "-la", "/tmp"]).unwrap().action,
+            CliAction::Exec { command: "ls -la /tmp".to_string() }
+        );
+        assert_eq!(parse(&["--list-sessions"]).unwrap().action, CliAction::ListSessions);
+    }
+
+    #[test]
+    fn test_parse_args_errors() {
+        assert!(parse(&[]).is_err());
+        assert!(parse(&["--upload", "a", "b"]).is_err());
+        assert!(parse(&["-s", "x", "--download", "/a"]).is_err());
+        assert!(parse(&["-s", "x", "--exec"]).is_err());
+        assert!(parse(&["-s", "x", "--upload", "a", "b", "--download", "c", "d"]).is_err());
+        assert!(parse(&["--verbose"]).is_err());
+    }
+}
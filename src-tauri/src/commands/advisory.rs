@@ -0,0 +1,40 @@
+//! 服务器版本安全公告命令
+
+use crate::commands::auth::ApiClientStateWrapper;
+use crate::commands::session::SSHManagerState;
+use crate::ssh::advisory::{AdvisoryList, AdvisoryReport};
+use tauri::State;
+
+/// 从同步服务器更新安全公告列表（需要登录），返回当前使用的列表
+///
+/// 服务器返回的列表不比当前列表新时保持不变
+#[tauri::command]
+pub async fn ssh_advisories_update(
+    api_client_state: State<'_, ApiClientStateWrapper>,
+) -> Result<AdvisoryList, String> {
+    let client = api_client_state.wait_client().await.map_err(|e| e.to_string())?;
+    let (list, _, _) = client.ssh_advisories().await.map_err(|e| e.to_string())?;
+
+    let current = AdvisoryList::load();
+    if list.updated <= current.updated {
+        tracing::debug!("[Advisory] Server list {} is not newer than {}", list.updated, current.updated);
+        return Ok(current);
+    }
+    list.save().map_err(|e| e.to_string())?;
+    tracing::info!("[Advisory] Updated advisory list to {} ({} entries)", list.updated, list.advisories.len());
+    Ok(list)
+}
+
+/// 检查连接的服务器版本，未命中已知漏洞时返回 None
+#[tauri::command]
+pub async fn ssh_advisory_check(
+    manager: State<'_, SSHManagerState>,
+    connection_id: String,
+) -> Result<Option<AdvisoryReport>, String> {
+    let connection = manager.get_connection(&connection_id).await.map_err(|e| e.to_string())?;
+    let server_id = connection
+        .server_id()
+        .await
+        .ok_or_else(|| "Server identification is only available for connected SSH sessions".to_string())?;
+    Ok(AdvisoryList::load().check(&server_id))
+}
@@ -1,52 +1,131 @@
 // AI 相关 Tauri 命令
 
-use crate::ai::{ChatMessage, AIProviderManager, OpenAIProvider};
+use crate::ai::command_gen::{self, GeneratedCommand};
+use crate::ai::runtime::{self, AIRuntime, AIRuntimeConfig, ReloadStatus, RELOAD_STATUS_EVENT};
+use crate::ai::server::SERVER_PROVIDER_TYPE;
+use crate::ai::{ChatMessage, AIProviderManager, OpenAIProvider, ServerProvider};
+use crate::commands::auth::ApiClientStateWrapper;
+use crate::models::ai_proxy::AIServerQuota;
+use crate::config::storage::AIPromptTemplates;
+use crate::config::Storage;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tauri::{AppHandle, Emitter, State};
+use zeroize::Zeroizing;
 
 /// AI Provider 配置
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AIProviderConfig {
     #[serde(rename = "type")]
     pub provider_type: String,
-    pub api_key: Option<String>,
+    /// 释放时清零，Debug 输出不包含
+    pub api_key: Option<Zeroizing<String>>,
     pub base_url: Option<String>,
     pub model: String,
     pub temperature: Option<f32>,
     pub max_tokens: Option<u32>,
 }
 
+impl std::fmt::Debug for AIProviderConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AIProviderConfig")
+            .field("provider_type", &self.provider_type)
+            .field("has_api_key", &self.api_key.is_some())
+            .field("base_url", &self.base_url)
+            .field("model", &self.model)
+            .field("temperature", &self.temperature)
+            .field("max_tokens", &self.max_tokens)
+            .finish()
+    }
+}
+
 /// AI Manager 状态
 pub struct AIManagerState {
     /// AI Provider 管理器，负责缓存和复用 Provider 实例
-    manager: Arc<AIProviderManager>,
+    /// 在首次调用 AI 命令时创建，不占用应用启动时间
+    manager: std::sync::OnceLock<Arc<AIProviderManager>>,
+    /// 当前生效的提示词模板、安全规则与 Provider 列表快照
+    runtime: AIRuntime,
+    /// 服务器托管 Provider 通过 ApiClient 转发请求
+    api_client_state: ApiClientStateWrapper,
 }
 
 impl AIManagerState {
-    pub fn new() -> Self {
+    pub fn new(api_client_state: ApiClientStateWrapper) -> Self {
         Self {
-            manager: Arc::new(AIProviderManager::new()),
+            manager: std::sync::OnceLock::new(),
+            runtime: AIRuntime::default(),
+            api_client_state,
         }
     }
 
     /// 获取 Provider 管理器的引用
     pub fn manager(&self) -> &Arc<AIProviderManager> {
-        &self.manager
+        self.manager.get_or_init(|| {
+            Arc::new(AIProviderManager::new().with_api_client_state(self.api_client_state.clone()))
+        })
+    }
+
+    /// 获取运行时配置快照，首次调用时从磁盘加载
+    pub fn runtime(&self, app: &AppHandle) -> Arc<AIRuntimeConfig> {
+        if let Some(current) = self.runtime.current() {
+            return current;
+        }
+        let config = Storage::load_ai_config(Some(app))
+            .ok()
+            .flatten()
+            .map(|config| AIRuntimeConfig::from_config(&config))
+            .unwrap_or_default();
+        self.runtime.replace(config);
+        self.runtime.current().unwrap_or_default()
+    }
+
+    /// 替换运行时配置快照，返回旧快照
+    pub fn replace_runtime(&self, config: AIRuntimeConfig) -> Option<Arc<AIRuntimeConfig>> {
+        self.runtime.replace(config)
+    }
+
+    /// 清除内存中的 API Key：丢弃缓存的 Provider 实例和运行时快照
+    ///
+    /// 退出登录时调用，下次使用 AI 时重新从加密存储加载
+    pub fn clear_secrets(&self) {
+        if let Some(manager) = self.manager.get() {
+            manager.clear_cache();
+        }
+        self.runtime.clear();
     }
 }
 
+/// 读取自定义提示词模板，未设置时返回 None（使用内置模板）
+fn custom_prompt(
+    app: &AppHandle,
+    ai_manager: &AIManagerState,
+    pick: impl FnOnce(&AIPromptTemplates) -> Option<&String>,
+) -> Option<String> {
+    let runtime = ai_manager.runtime(app);
+    pick(&runtime.prompt_templates)
+        .filter(|prompt| !prompt.trim().is_empty())
+        .cloned()
+}
+
 /// AI 聊天命令（流式）
 #[tauri::command]
 pub async fn ai_chat_stream(
     app: AppHandle,
+    ai_manager: State<'_, AIManagerState>,
     config: AIProviderConfig,
     messages: Vec<ChatMessage>,
 ) -> Result<String, String> {
     // 流式功能需要直接使用 provider 实例（不通过缓存）
     // 因为 OpenAI 的流式实现需要保持对底层的引用
     let provider = match config.provider_type.as_str() {
+        SERVER_PROVIDER_TYPE => {
+            // 服务器代理不支持流式，完整回复作为一个数据块发送
+            let content = ai_chat(ai_manager, config, messages).await?;
+            let _ = app.emit("ai-chat-chunk", content.clone());
+            return Ok(content);
+        }
         "ollama" => {
             // Ollama 暂不支持流式
             return Err("Ollama streaming not supported yet".to_string());
@@ -55,7 +134,7 @@ pub async fn ai_chat_stream(
             // OpenAI 兼容接口
             let api_key = config.api_key.ok_or("API key is required".to_string())?;
             OpenAIProvider::new(
-                api_key,
+                api_key.as_str().to_string(),
                 config.base_url,
                 config.model,
                 config.temperature,
@@ -90,6 +169,7 @@ pub async fn ai_chat(
 /// AI 命令解释
 #[tauri::command]
 pub async fn ai_explain_command(
+    app: AppHandle,
     ai_manager: State<'_, AIManagerState>,
     command: String,
     config: AIProviderConfig,
@@ -110,10 +190,13 @@ pub async fn ai_explain_command(
 - 示例必须是可执行的真实命令
 - 总字数不超过80字";
 
+    let system_prompt = custom_prompt(&app, &ai_manager, |t| t.explain_command.as_ref())
+        .unwrap_or_else(|| system_prompt.to_string());
+
     let messages = vec![
         ChatMessage {
             role: "system".to_string(),
-            content: system_prompt.to_string(),
+            content: system_prompt,
         },
         ChatMessage {
             role: "user".to_string(),
@@ -127,6 +210,7 @@ pub async fn ai_explain_command(
 /// AI 自然语言转命令
 #[tauri::command]
 pub async fn ai_generate_command(
+    app: AppHandle,
     ai_manager: State<'_, AIManagerState>,
     input: String,
     config: AIProviderConfig,
@@ -144,10 +228,13 @@ pub async fn ai_generate_command(
 \"查log文件\" → find . -name \"*.log\"
 \"停止nginx\" → systemctl stop nginx";
 
+    let system_prompt = custom_prompt(&app, &ai_manager, |t| t.nl_to_command.as_ref())
+        .unwrap_or_else(|| system_prompt.to_string());
+
     let messages = vec![
         ChatMessage {
             role: "system".to_string(),
-            content: system_prompt.to_string(),
+            content: system_prompt,
         },
         ChatMessage {
             role: "user".to_string(),
@@ -155,12 +242,58 @@ pub async fn ai_generate_command(
         },
     ];
 
-    ai_chat(ai_manager, config, messages).await
+    let safety_rules = ai_manager.runtime(&app).safety_rules.clone();
+    let command = ai_chat(ai_manager, config, messages).await?;
+    if let Some(pattern) = safety_rules.matches(&command) {
+        tracing::warn!("[AI] Generated command blocked by safety rule: {}", pattern);
+        return Err(format!("生成的命令命中安全规则 \"{}\"，已拦截", pattern));
+    }
+    Ok(command)
+}
+
+/// AI 自然语言转命令（结构化输出）
+///
+/// 按 JSON Schema 返回命令、说明、危险等级和是否需要 sudo，
+/// 使用内置提示词（自定义的自然语言转命令模板只用于 `ai_generate_command`）
+#[tauri::command]
+pub async fn ai_generate_command_structured(
+    app: AppHandle,
+    ai_manager: State<'_, AIManagerState>,
+    input: String,
+    config: AIProviderConfig,
+) -> Result<GeneratedCommand, String> {
+    let messages = vec![
+        ChatMessage {
+            role: "system".to_string(),
+            content: command_gen::STRUCTURED_SYSTEM_PROMPT.to_string(),
+        },
+        ChatMessage {
+            role: "user".to_string(),
+            content: format!("用户需求: {}", input),
+        },
+    ];
+
+    let safety_rules = ai_manager.runtime(&app).safety_rules.clone();
+    let provider = ai_manager.manager()
+        .get_or_create_provider(&config)
+        .map_err(|e| e.to_string())?;
+    let response = provider
+        .chat_json(messages, &command_gen::schema())
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let generated = command_gen::parse_generated_command(&response)?;
+    if let Some(pattern) = safety_rules.matches(&generated.command) {
+        tracing::warn!("[AI] Generated command blocked by safety rule: {}", pattern);
+        return Err(format!("生成的命令命中安全规则 \"{}\"，已拦截", pattern));
+    }
+    Ok(generated)
 }
 
 /// AI 错误分析
 #[tauri::command]
 pub async fn ai_analyze_error(
+    app: AppHandle,
     ai_manager: State<'_, AIManagerState>,
     error: String,
     config: AIProviderConfig,
@@ -181,10 +314,13 @@ pub async fn ai_analyze_error(
 - 按成功率排序方案
 - 总字数不超过120字";
 
+    let system_prompt = custom_prompt(&app, &ai_manager, |t| t.error_analysis.as_ref())
+        .unwrap_or_else(|| system_prompt.to_string());
+
     let messages = vec![
         ChatMessage {
             role: "system".to_string(),
-            content: system_prompt.to_string(),
+            content: system_prompt,
         },
         ChatMessage {
             role: "user".to_string(),
@@ -195,6 +331,15 @@ pub async fn ai_analyze_error(
     ai_chat(ai_manager, config, messages).await
 }
 
+/// 获取服务器 AI 代理的配额（需要登录）
+#[tauri::command]
+pub async fn ai_server_quota(
+    api_client_state: State<'_, ApiClientStateWrapper>,
+) -> Result<AIServerQuota, String> {
+    let provider = ServerProvider::new(api_client_state.inner().clone(), None, None);
+    provider.quota().await.map(Into::into).map_err(|e| e.to_string())
+}
+
 /// 测试 AI 连接
 #[tauri::command]
 pub async fn ai_test_connection(
@@ -264,7 +409,9 @@ pub struct CacheInfo {
 
 /// 手动触发热重载
 ///
-/// 当配置文件被外部修改时，可以手动调用此命令来同步缓存
+/// 当配置文件被外部修改时，可以手动调用此命令来同步提示词模板、安全规则与 Provider 缓存。
+/// 新配置校验失败时保留当前配置；校验通过后原子替换快照，进行中的请求继续使用旧快照。
+/// 结果同时通过 `ai-reload-status` 事件通知前端
 #[tauri::command]
 pub async fn ai_hot_reload(
     ai_manager: State<'_, AIManagerState>,
@@ -274,32 +421,74 @@ pub async fn ai_hot_reload(
     let current_config = crate::config::Storage::load_ai_config(Some(&app))
         .map_err(|e| e.to_string())?;
 
-    if current_config.is_some() {
-        // 清除所有缓存（因为我们没有旧配置的信息，所以清除所有）
-        let old_cache_size = ai_manager.manager().cache_size();
-        ai_manager.manager().clear_cache();
-
-        tracing::info!(
-            "[AI Hot Reload] Manual reload: Cleared {} providers from cache",
-            old_cache_size
-        );
-
-        Ok(HotReloadResult {
-            success: true,
-            removed_count: old_cache_size,
-            message: format!("缓存已清除，下次调用将使用新配置创建 Provider"),
-        })
-    } else {
-        Ok(HotReloadResult {
+    let Some(config) = current_config else {
+        return Ok(HotReloadResult {
             success: true,
             removed_count: 0,
             message: "未找到 AI 配置，无需重载".to_string(),
-        })
+            errors: Vec::new(),
+        });
+    };
+
+    let errors = runtime::validate(&config);
+    if !errors.is_empty() {
+        tracing::warn!("[AI Hot Reload] Config rejected: {:?}", errors);
+        let _ = app.emit(RELOAD_STATUS_EVENT, ReloadStatus {
+            applied: false,
+            errors: errors.clone(),
+            removed_count: 0,
+        });
+        return Ok(HotReloadResult {
+            success: false,
+            removed_count: 0,
+            message: "配置校验失败，继续使用当前配置".to_string(),
+            errors,
+        });
+    }
+
+    let removed_count = reload_runtime(&ai_manager, &config);
+    let _ = app.emit(RELOAD_STATUS_EVENT, ReloadStatus {
+        applied: true,
+        errors: Vec::new(),
+        removed_count,
+    });
+
+    Ok(HotReloadResult {
+        success: true,
+        removed_count,
+        message: "已重新加载提示词模板、安全规则和 Provider 配置".to_string(),
+        errors: Vec::new(),
+    })
+}
+
+/// 原子替换运行时快照并移除已变更的 Provider 缓存，返回移除数量
+pub(crate) fn reload_runtime(
+    ai_manager: &AIManagerState,
+    config: &crate::config::storage::AIConfig,
+) -> usize {
+    let new_runtime = AIRuntimeConfig::from_config(config);
+    let new_providers = new_runtime.providers.clone();
+
+    match ai_manager.replace_runtime(new_runtime) {
+        Some(old) => ai_manager
+            .manager()
+            .hot_reload(&old.providers, &new_providers)
+            .unwrap_or_else(|e| {
+                tracing::warn!("[AI Hot Reload] Provider reload failed: {}", e);
+                0
+            }),
+        None => {
+            // 尚无旧快照，无法比较差异，清除全部缓存
+            let removed = ai_manager.manager().cache_size();
+            ai_manager.manager().clear_cache();
+            removed
+        }
     }
 }
 
 /// 热重载结果
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct HotReloadResult {
     /// 是否成功
     pub success: bool,
@@ -307,4 +496,7 @@ pub struct HotReloadResult {
     pub removed_count: usize,
     /// 结果消息
     pub message: String,
+    /// 配置校验错误
+    #[serde(default)]
+    pub errors: Vec<String>,
 }
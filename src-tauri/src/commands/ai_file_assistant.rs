@@ -0,0 +1,158 @@
+//! AI 文件助手命令
+//!
+//! `ai_file_assistant_plan` 只生成计划，不做任何修改；用户审阅确认后由
+//! `ai_file_assistant_execute` 按顺序执行
+
+use std::time::Duration;
+use tauri::{Emitter, State, Window};
+
+use crate::ai::file_assistant::{
+    parse_plan, FilePlan, FilePlanStep, StepProgress, StepStatus, FILE_ASSISTANT_EVENT, PLAN_SYSTEM_PROMPT,
+};
+use crate::ai::ChatMessage;
+use crate::database::DbPool;
+use crate::sftp::{ConflictPolicy, TransferOperation};
+
+use super::ai::{ai_chat, AIManagerState, AIProviderConfig};
+use super::session::SSHManagerState;
+use super::sftp::{sftp_transfer, SftpManagerState};
+
+/// exec 步骤的超时时间（打包大量文件可能较慢）
+const EXEC_STEP_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// 返回给前端的命令输出最大长度（字符）
+const MAX_STEP_OUTPUT_CHARS: usize = 2000;
+
+/// 将自然语言的文件操作需求转换为待审阅的执行计划
+///
+/// # 参数
+/// - `connection_id`: 连接 ID
+/// - `request`: 用户需求
+/// - `remote_dir`: 当前远程目录（作为相对描述的参考）
+/// - `config`: AI Provider 配置
+#[tauri::command]
+pub async fn ai_file_assistant_plan(
+    ai_manager: State<'_, AIManagerState>,
+    manager: State<'_, SSHManagerState>,
+    connection_id: String,
+    request: String,
+    remote_dir: Option<String>,
+    config: AIProviderConfig,
+) -> Result<FilePlan, String> {
+    if request.trim().is_empty() {
+        return Err("Request cannot be empty".to_string());
+    }
+    let connection = manager.get_connection(&connection_id).await.map_err(|e| e.to_string())?;
+
+    let mut context = format!("远程主机: {}", connection.host_key());
+    if let Some(remote_dir) = remote_dir {
+        context.push_str(&format!("\n当前远程目录: {}", remote_dir));
+    }
+
+    let messages = vec![
+        ChatMessage {
+            role: "system".to_string(),
+            content: PLAN_SYSTEM_PROMPT.to_string(),
+        },
+        ChatMessage {
+            role: "user".to_string(),
+            content: format!("{}\n\n需求: {}", context, request.trim()),
+        },
+    ];
+
+    let response = ai_chat(ai_manager, config, messages).await?;
+    parse_plan(&response)
+}
+
+/// 执行已确认的计划
+///
+/// 按顺序执行步骤并发送 `ai-file-assistant-step` 进度事件；某一步失败后停止，其余步骤标记为跳过。
+/// 传输步骤经由传输队列执行，下载的文件保存到 `local_dir`，重名时自动重命名
+///
+/// # 返回
+/// 每个步骤的执行结果
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn ai_file_assistant_execute(
+    manager: State<'_, SSHManagerState>,
+    sftp_manager: State<'_, SftpManagerState>,
+    pool: State<'_, DbPool>,
+    window: Window,
+    connection_id: String,
+    plan: FilePlan,
+    local_dir: String,
+) -> Result<Vec<StepProgress>, String> {
+    let connection = manager.get_connection(&connection_id).await.map_err(|e| e.to_string())?;
+
+    let report = |index: usize, status: StepStatus, message: Option<String>| {
+        let progress = StepProgress {
+            connection_id: connection_id.clone(),
+            index,
+            status,
+            message,
+        };
+        let _ = window.emit(FILE_ASSISTANT_EVENT, &progress);
+        progress
+    };
+
+    let mut results = Vec::with_capacity(plan.steps.len());
+    let mut failed = false;
+    for (index, planned) in plan.steps.into_iter().enumerate() {
+        if failed {
+            results.push(report(index, StepStatus::Skipped, None));
+            continue;
+        }
+        report(index, StepStatus::Running, None);
+
+        let outcome = match planned.step {
+            FilePlanStep::Exec { command, .. } => match connection.exec(&command, EXEC_STEP_TIMEOUT).await {
+                Ok(output) if output.success() => Ok(truncate_output(output.stdout_lossy())),
+                Ok(output) => Err(format!(
+                    "Exited with status {}: {}",
+                    output.exit_status.map_or_else(|| "unknown".to_string(), |s| s.to_string()),
+                    truncate_output(output.stderr_lossy())
+                )),
+                Err(e) => Err(e.to_string()),
+            },
+            FilePlanStep::Download { remote_path, .. } => sftp_transfer(
+                sftp_manager.clone(), pool.clone(), connection_id.clone(),
+                local_dir.clone(), remote_path, TransferOperation::Download,
+                Some(ConflictPolicy::Rename), None, None, None, window.clone(),
+            )
+            .await
+            .map(|result| result.target_path)
+            .map_err(|e| e.to_string()),
+            FilePlanStep::Upload { local_path, remote_path, .. } => sftp_transfer(
+                sftp_manager.clone(), pool.clone(), connection_id.clone(),
+                local_path, remote_path, TransferOperation::Upload,
+                Some(ConflictPolicy::Rename), None, None, None, window.clone(),
+            )
+            .await
+            .map(|result| result.target_path)
+            .map_err(|e| e.to_string()),
+        };
+
+        match outcome {
+            Ok(message) => {
+                let message = (!message.is_empty()).then_some(message);
+                results.push(report(index, StepStatus::Succeeded, message));
+            }
+            Err(error) => {
+                tracing::warn!("[AI File Assistant] Step {} failed on {}: {}", index, connection_id, error);
+                failed = true;
+                results.push(report(index, StepStatus::Failed, Some(error)));
+            }
+        }
+    }
+
+    Ok(results)
+}
+
+/// 截断过长的命令输出
+fn truncate_output(output: String) -> String {
+    let output = output.trim();
+    match output.char_indices().nth(MAX_STEP_OUTPUT_CHARS) {
+        Some((end, _)) => format!("{}…", &output[..end]),
+        None => output.to_string(),
+    }
+}
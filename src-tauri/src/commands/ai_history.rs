@@ -124,3 +124,21 @@ pub async fn ai_history_update_connection_status(
     history.update_connection_status(&id, connection_status)?;
     history.save()
 }
+
+/// 从指定消息处分叉对话
+///
+/// 复制 `message_index`（含）之前的消息创建新对话，用于尝试另一种排查思路而不影响原对话
+#[tauri::command]
+pub async fn ai_history_fork(id: String, message_index: usize) -> Result<AIConversationMeta, String> {
+    let mut history = AIChatHistory::load()?;
+    let meta = history.fork_conversation(&id, message_index, uuid::Uuid::new_v4().to_string())?;
+    history.save()?;
+    Ok(meta)
+}
+
+/// 获取对话的所有分支
+#[tauri::command]
+pub async fn ai_history_list_branches(id: String) -> Result<Vec<AIConversationMeta>, String> {
+    let history = AIChatHistory::load()?;
+    Ok(history.list_branches(&id))
+}
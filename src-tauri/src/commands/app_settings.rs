@@ -2,7 +2,12 @@ use tauri::State;
 
 use crate::database::repositories::AppSettingsRepository;
 use crate::database::repositories::app_settings_repository::AppSettings;
+use crate::commands::auth::ApiClientStateWrapper;
 use crate::database::DbPool;
+use crate::services::ApiTimeouts;
+use crate::ssh::dns::DnsResolverConfig;
+use crate::ssh::limits::ConnectionLimits;
+use crate::ssh::watermark::WatermarkSettings;
 
 /// 获取服务器地址
 #[tauri::command]
@@ -61,6 +66,41 @@ pub async fn app_settings_set_sync_interval(
     repo.set_sync_interval(interval).map_err(|e| e.to_string())
 }
 
+/// 获取 API 请求超时设置（按接口类别，单位秒）
+#[tauri::command]
+pub async fn app_settings_get_api_timeouts(
+    pool: State<'_, DbPool>,
+) -> Result<ApiTimeouts, String> {
+    let repo = AppSettingsRepository::new(pool.inner().clone());
+    let timeouts_json = repo.get_api_timeouts().map_err(|e| e.to_string())?;
+
+    Ok(timeouts_json
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default())
+}
+
+/// 设置 API 请求超时，立即对当前和之后创建的 API 客户端生效
+#[tauri::command]
+pub async fn app_settings_set_api_timeouts(
+    timeouts: ApiTimeouts,
+    pool: State<'_, DbPool>,
+    api_client_state: State<'_, ApiClientStateWrapper>,
+) -> Result<(), String> {
+    // 默认值与未设置等价
+    let timeouts_json = if timeouts == ApiTimeouts::default() {
+        None
+    } else {
+        Some(serde_json::to_string(&timeouts).map_err(|e| e.to_string())?)
+    };
+
+    let repo = AppSettingsRepository::new(pool.inner().clone());
+    repo.set_api_timeouts(timeouts_json.as_deref()).map_err(|e| e.to_string())?;
+
+    ApiTimeouts::set_configured(timeouts);
+    api_client_state.set_timeouts(timeouts);
+    Ok(())
+}
+
 /// 获取语言设置
 #[tauri::command]
 pub async fn app_settings_get_language(
@@ -89,6 +129,102 @@ pub async fn app_settings_set_language(
     Ok(())
 }
 
+/// 获取全局 DNS 解析器（None 表示使用系统解析器）
+#[tauri::command]
+pub async fn app_settings_get_dns_resolver(
+    pool: State<'_, DbPool>,
+) -> Result<Option<DnsResolverConfig>, String> {
+    let repo = AppSettingsRepository::new(pool.inner().clone());
+    let resolver_json = repo.get_dns_resolver().map_err(|e| e.to_string())?;
+
+    resolver_json
+        .map(|json| serde_json::from_str(&json))
+        .transpose()
+        .map_err(|e| e.to_string())
+}
+
+/// 设置全局 DNS 解析器
+#[tauri::command]
+pub async fn app_settings_set_dns_resolver(
+    resolver: Option<DnsResolverConfig>,
+    pool: State<'_, DbPool>,
+) -> Result<(), String> {
+    // 系统解析器与未设置等价
+    let resolver = resolver.filter(|r| *r != DnsResolverConfig::System);
+    if let Some(resolver) = &resolver {
+        resolver.validate()?;
+    }
+
+    let resolver_json = resolver
+        .as_ref()
+        .map(serde_json::to_string)
+        .transpose()
+        .map_err(|e| e.to_string())?;
+
+    let repo = AppSettingsRepository::new(pool.inner().clone());
+    repo.set_dns_resolver(resolver_json.as_deref()).map_err(|e| e.to_string())
+}
+
+/// 获取并发连接数限制
+#[tauri::command]
+pub async fn app_settings_get_connection_limits(
+    pool: State<'_, DbPool>,
+) -> Result<ConnectionLimits, String> {
+    let repo = AppSettingsRepository::new(pool.inner().clone());
+    let limits_json = repo.get_connection_limits().map_err(|e| e.to_string())?;
+
+    Ok(limits_json
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default())
+}
+
+/// 设置并发连接数限制（每台主机和全局，0 表示不限制），对之后建立的连接生效
+#[tauri::command]
+pub async fn app_settings_set_connection_limits(
+    limits: ConnectionLimits,
+    pool: State<'_, DbPool>,
+) -> Result<(), String> {
+    // 不限制时与未设置等价
+    let limits_json = if limits == ConnectionLimits::default() {
+        None
+    } else {
+        Some(serde_json::to_string(&limits).map_err(|e| e.to_string())?)
+    };
+
+    let repo = AppSettingsRepository::new(pool.inner().clone());
+    repo.set_connection_limits(limits_json.as_deref()).map_err(|e| e.to_string())
+}
+
+/// 获取终端水印设置
+#[tauri::command]
+pub async fn app_settings_get_watermark(
+    pool: State<'_, DbPool>,
+) -> Result<WatermarkSettings, String> {
+    let repo = AppSettingsRepository::new(pool.inner().clone());
+    let watermark_json = repo.get_watermark().map_err(|e| e.to_string())?;
+
+    Ok(watermark_json
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default())
+}
+
+/// 设置终端水印，对之后建立的连接生效
+#[tauri::command]
+pub async fn app_settings_set_watermark(
+    watermark: WatermarkSettings,
+    pool: State<'_, DbPool>,
+) -> Result<(), String> {
+    // 关闭时与未设置等价
+    let watermark_json = if watermark.enabled {
+        Some(serde_json::to_string(&watermark).map_err(|e| e.to_string())?)
+    } else {
+        None
+    };
+
+    let repo = AppSettingsRepository::new(pool.inner().clone());
+    repo.set_watermark(watermark_json.as_deref()).map_err(|e| e.to_string())
+}
+
 /// 获取所有应用设置
 #[tauri::command]
 pub async fn app_settings_get_all(
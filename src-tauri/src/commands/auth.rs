@@ -1,25 +1,53 @@
 use anyhow::Result;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use tauri::State;
+use tokio::sync::Notify;
 
 use crate::database::DbPool;
 use crate::models::user_auth::*;
-use crate::services::{AuthService, ApiClient};
+use crate::services::{AuthService, ApiClient, ApiTimeouts};
 use crate::types::response::ApiResponse;
 
 /// 全局 API Client 状态
 #[derive(Clone)]
 pub struct ApiClientState {
     client: Arc<Mutex<Option<ApiClient>>>,
+    /// 启动时在后台恢复已登录用户的 API Client，完成前访问服务器的命令需要等待
+    restored: Arc<AtomicBool>,
+    restored_notify: Arc<Notify>,
 }
 
 impl ApiClientState {
     pub fn new() -> Self {
         Self {
             client: Arc::new(Mutex::new(None)),
+            restored: Arc::new(AtomicBool::new(false)),
+            restored_notify: Arc::new(Notify::new()),
         }
     }
 
+    /// 标记启动时的恢复已完成（无论是否恢复出 API Client）
+    pub fn mark_restored(&self) {
+        self.restored.store(true, Ordering::SeqCst);
+        self.restored_notify.notify_waiters();
+    }
+
+    /// 等待启动时的恢复完成后获取 API Client
+    ///
+    /// 前端在启动后立即同步时，后台恢复可能尚未完成，直接调用 `get_client` 会失败
+    pub async fn wait_client(&self) -> Result<ApiClient> {
+        loop {
+            // 先注册通知再检查标记，避免错过两者之间的 notify_waiters
+            let notified = self.restored_notify.notified();
+            if self.restored.load(Ordering::SeqCst) {
+                break;
+            }
+            notified.await;
+        }
+        self.get_client()
+    }
+
     /// 设置 API Client
     pub fn set_client(&self, client: ApiClient) {
         let mut guard = self.client.lock()
@@ -37,10 +65,24 @@ impl ApiClientState {
     }
 
     /// 清除 API Client
+    ///
+    /// 先清除令牌：其他地方持有的 ApiClient 副本共享同一份令牌，一并失效
     pub fn clear(&self) {
         let mut guard = self.client.lock()
             .expect("Failed to acquire api_client lock");
-        *guard = None;
+        if let Some(client) = guard.take() {
+            client.clear_token();
+            client.clear_refresh_token();
+        }
+    }
+
+    /// 更新当前 API Client 的请求超时时间
+    pub fn set_timeouts(&self, timeouts: ApiTimeouts) {
+        let mut guard = self.client.lock()
+            .expect("Failed to acquire api_client lock");
+        if let Some(client) = guard.as_mut() {
+            client.set_timeouts(timeouts);
+        }
     }
 
     /// 设置 token
@@ -111,11 +153,15 @@ pub async fn auth_register(
 }
 
 /// 用户登出
+///
+/// 同时清除内存中已解密的 AI Provider API Key
 #[tauri::command]
 pub async fn auth_logout(
     pool: State<'_, DbPool>,
     api_client_state: State<'_, ApiClientStateWrapper>,
+    ai_manager: State<'_, crate::commands::ai::AIManagerState>,
 ) -> Result<(), String> {
+    ai_manager.clear_secrets();
     let service = AuthService::new(pool.inner().clone(), Some(api_client_state.inner().clone()));
     service.logout().map_err(|e| e.to_string())
 }
@@ -227,6 +273,57 @@ pub async fn auth_send_verify_code(
     }
 }
 
+/// 验证邮箱（注册时未填写验证码，或宽限期过后登录被拒绝时调用）
+/// 直接返回服务器的原始响应 {code, message, data}
+#[tauri::command]
+pub async fn auth_verify_email(
+    email: String,
+    verify_code: String,
+    pool: State<'_, DbPool>,
+) -> Result<crate::types::response::ApiResponse<()>, String> {
+    let service = AuthService::new(pool.inner().clone(), None);
+    match service.verify_email(email, verify_code).await {
+        Ok((_, code, message)) => Ok(crate::types::response::ApiResponse {
+            code,
+            message,
+            data: Some(()),
+        }),
+        Err(e) => {
+            let (code, message) = extract_server_error(&e.to_string());
+            Ok(crate::types::response::ApiResponse {
+                code,
+                message,
+                data: None,
+            })
+        }
+    }
+}
+
+/// 为已注册但未验证邮箱的账号重新发送验证码
+/// 限频（60 秒一次、每日 10 次）的提示由服务器返回在 message 中
+#[tauri::command]
+pub async fn auth_resend_verify_code(
+    email: String,
+    pool: State<'_, DbPool>,
+) -> Result<crate::types::response::ApiResponse<EmailResult>, String> {
+    let service = AuthService::new(pool.inner().clone(), None);
+    match service.resend_verify_code(email).await {
+        Ok((result, code, message)) => Ok(crate::types::response::ApiResponse {
+            code,
+            message,
+            data: Some(result),
+        }),
+        Err(e) => {
+            let (code, message) = extract_server_error(&e.to_string());
+            Ok(crate::types::response::ApiResponse {
+                code,
+                message,
+                data: None,
+            })
+        }
+    }
+}
+
 /// 辅助函数：从错误消息中提取服务器返回的 code 和 message
 fn extract_server_error(error_str: &str) -> (u16, String) {
     // 匹配格式: API error (400 Bad Request): {"code":400,"message":"邮箱已注册","data":null}
@@ -0,0 +1,134 @@
+//! 自动补全命令
+//!
+//! 按主机合并远程 PATH 可执行文件、该主机的命令历史和命令片段，前缀过滤和排序在 Rust 端完成
+
+use crate::autocomplete::provider::{rank_suggestions, DEFAULT_SUGGESTION_LIMIT, LIST_PATH_BINARIES_COMMAND};
+use crate::autocomplete::{AutocompleteProvider, Suggestion};
+use crate::commands::session::SSHManagerState;
+use crate::commands::ssh_session::get_current_user_info;
+use crate::database::DbPool;
+use crate::database::repositories::SnippetRepository;
+use crate::ssh::connection::ConnectionInstance;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::State;
+
+/// 全局自动补全状态
+pub type AutocompleteState = Arc<AutocompleteProvider>;
+
+/// 采集 PATH 可执行文件的超时时间
+const COLLECT_BINARIES_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 通过 exec 采集远程 PATH 下的可执行文件并缓存
+async fn collect_binaries(
+    provider: &AutocompleteProvider,
+    connection: &ConnectionInstance,
+) -> Result<Arc<Vec<String>>, String> {
+    let output = connection
+        .exec(LIST_PATH_BINARIES_COMMAND, COLLECT_BINARIES_TIMEOUT)
+        .await
+        .map_err(|e| format!("Failed to collect PATH binaries: {}", e))?;
+
+    let binaries = provider.set_binaries(&connection.host_key(), &output.stdout_lossy()).await;
+    tracing::info!("Collected {} PATH binaries for {}", binaries.len(), connection.host_key());
+    Ok(binaries)
+}
+
+/// 获取补全建议
+///
+/// # 参数
+/// - `connection_id`: 连接 ID
+/// - `prefix`: 当前输入的命令行内容
+/// - `limit`: 返回数量上限，默认 20
+#[tauri::command]
+pub async fn autocomplete_suggest(
+    pool: State<'_, DbPool>,
+    manager: State<'_, SSHManagerState>,
+    autocomplete: State<'_, AutocompleteState>,
+    connection_id: String,
+    prefix: String,
+    limit: Option<usize>,
+) -> Result<Vec<Suggestion>, String> {
+    let connection = manager.get_connection(&connection_id).await
+        .map_err(|e| e.to_string())?;
+    let host_key = connection.host_key();
+
+    // PATH 只采集一次，失败时本次不使用可执行文件来源，下次再重试
+    let binaries = match autocomplete.cached_binaries(&host_key).await {
+        Some(binaries) => binaries,
+        None => collect_binaries(&autocomplete, &connection).await.unwrap_or_else(|e| {
+            tracing::warn!("{}", e);
+            Arc::new(Vec::new())
+        }),
+    };
+
+    let current_user = get_current_user_info(&pool);
+    let repo = SnippetRepository::new(pool.inner().clone());
+    let history = repo.find_history(&current_user.user_id, &host_key)
+        .map_err(|e| format!("Failed to load history: {}", e))?;
+    let snippets = repo.find_by_user(&current_user.user_id)
+        .map_err(|e| format!("Failed to load snippets: {}", e))?;
+
+    Ok(rank_suggestions(
+        &prefix,
+        &history,
+        &snippets,
+        &binaries,
+        limit.unwrap_or(DEFAULT_SUGGESTION_LIMIT),
+    ))
+}
+
+/// 记录在该主机上执行过的命令
+#[tauri::command]
+pub async fn autocomplete_record_command(
+    pool: State<'_, DbPool>,
+    manager: State<'_, SSHManagerState>,
+    connection_id: String,
+    command: String,
+) -> Result<(), String> {
+    let command = command.trim();
+    if command.is_empty() {
+        return Ok(());
+    }
+
+    let connection = manager.get_connection(&connection_id).await
+        .map_err(|e| e.to_string())?;
+    let current_user = get_current_user_info(&pool);
+
+    let repo = SnippetRepository::new(pool.inner().clone());
+    repo.record_command(&current_user.user_id, &connection.host_key(), command)
+        .map_err(|e| format!("Failed to record command: {}", e))
+}
+
+/// 重新采集远程 PATH 可执行文件（安装新软件后使用）
+///
+/// 返回采集到的可执行文件数量
+#[tauri::command]
+pub async fn autocomplete_refresh_binaries(
+    manager: State<'_, SSHManagerState>,
+    autocomplete: State<'_, AutocompleteState>,
+    connection_id: String,
+) -> Result<usize, String> {
+    let connection = manager.get_connection(&connection_id).await
+        .map_err(|e| e.to_string())?;
+
+    autocomplete.invalidate(&connection.host_key()).await;
+    let binaries = collect_binaries(&autocomplete, &connection).await?;
+    Ok(binaries.len())
+}
+
+/// 清空该主机的命令历史
+#[tauri::command]
+pub async fn autocomplete_clear_history(
+    pool: State<'_, DbPool>,
+    manager: State<'_, SSHManagerState>,
+    connection_id: String,
+) -> Result<(), String> {
+    let connection = manager.get_connection(&connection_id).await
+        .map_err(|e| e.to_string())?;
+    let current_user = get_current_user_info(&pool);
+
+    let repo = SnippetRepository::new(pool.inner().clone());
+    repo.clear_history(&current_user.user_id, &connection.host_key())
+        .map_err(|e| format!("Failed to clear history: {}", e))
+}
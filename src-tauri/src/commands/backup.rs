@@ -0,0 +1,96 @@
+//! 本地数据备份命令
+//!
+//! 定期将本地数据库和配置文件加密备份到用户选择的目录，独立于云同步
+
+use crate::database::DbPool;
+use crate::services::backup_service::{BackupConfig, BackupInfo, BackupService};
+use std::path::PathBuf;
+use tauri::State;
+
+/// 获取备份配置（不返回密码）
+#[tauri::command]
+pub async fn backup_get_config() -> Result<serde_json::Value, String> {
+    let config = BackupService::load_config().map_err(|e| e.to_string())?;
+
+    Ok(serde_json::json!({
+        "enabled": config.enabled,
+        "directory": config.directory,
+        "intervalHours": config.interval_hours,
+        "retention": config.retention,
+        "hasPassword": config.password_encrypted.is_some(),
+        "lastBackupAt": config.last_backup_at,
+    }))
+}
+
+/// 更新备份配置
+///
+/// # 参数
+/// - `password`: 新的备份密码，None 表示保持不变
+#[tauri::command]
+pub async fn backup_set_config(
+    enabled: bool,
+    directory: Option<String>,
+    interval_hours: u32,
+    retention: u32,
+    password: Option<String>,
+) -> Result<(), String> {
+    let mut config = BackupService::load_config().map_err(|e| e.to_string())?;
+
+    if let Some(password) = password.filter(|p| !p.is_empty()) {
+        BackupService::set_password(&mut config, &password).map_err(|e| e.to_string())?;
+    }
+    if enabled && (directory.is_none() || config.password_encrypted.is_none()) {
+        return Err("启用自动备份前需要设置备份目录和备份密码".to_string());
+    }
+
+    config.enabled = enabled;
+    config.directory = directory;
+    config.interval_hours = interval_hours.max(1);
+    config.retention = retention.max(1);
+
+    BackupService::save_config(&config).map_err(|e| e.to_string())
+}
+
+/// 立即创建备份
+#[tauri::command]
+pub async fn backup_now(pool: State<'_, DbPool>) -> Result<BackupInfo, String> {
+    let pool = pool.inner().clone();
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut config = BackupService::load_config()?;
+        let info = BackupService::create_backup(&pool, &config)?;
+        config.last_backup_at = Some(info.created_at);
+        BackupService::save_config(&config)?;
+        Ok::<_, anyhow::Error>(info)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())
+}
+
+/// 列出备份目录中的备份
+#[tauri::command]
+pub async fn backup_list() -> Result<Vec<BackupInfo>, String> {
+    let config: BackupConfig = BackupService::load_config().map_err(|e| e.to_string())?;
+    match config.directory {
+        Some(directory) => BackupService::list_backups(&PathBuf::from(directory)).map_err(|e| e.to_string()),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// 从备份恢复
+///
+/// 配置文件立即恢复，数据库在重启应用后生效
+///
+/// # 参数
+/// - `path`: 备份文件路径
+/// - `password`: 备份密码，None 时使用当前配置的密码
+#[tauri::command]
+pub async fn backup_restore(path: String, password: Option<String>) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        BackupService::restore_backup(&PathBuf::from(path), password.as_deref())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())
+}
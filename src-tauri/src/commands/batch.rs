@@ -0,0 +1,268 @@
+//! 批量命令执行
+//!
+//! 在多台主机上并发执行同一条命令：已连接的会话复用现有连接，否则新建连接，
+//! 执行完毕后关闭新建的连接。各主机的输出和退出码通过 `batch-exec` 事件实时发送
+
+use crate::commands::session::{ensure_session_loaded, SSHManagerState};
+use crate::database::DbPool;
+use crate::error::{Result, SSHError};
+use crate::ssh::backend::{ExecOutput, ExecStream};
+use crate::ssh::connection::ConnectionInstance;
+use crate::ssh::manager::SSHManager;
+use crate::ssh::protection::match_destructive;
+use crate::ssh::session::SessionStatus;
+use encoding_rs::Decoder;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, State};
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+/// 批量执行事件
+const BATCH_EXEC_EVENT: &str = "batch-exec";
+
+/// 默认同时执行的主机数
+const DEFAULT_CONCURRENCY: usize = 8;
+
+/// 最大同时执行的主机数
+const MAX_CONCURRENCY: usize = 32;
+
+/// 每台主机的默认超时时间（秒）
+const DEFAULT_TIMEOUT_SECS: u64 = 300;
+
+/// 批量执行事件
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchExecEvent {
+    pub batch_id: String,
+    /// 主机对应的会话 ID（`completed` 事件为空）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    #[serde(flatten)]
+    pub kind: BatchEventKind,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum BatchEventKind {
+    /// 没有可复用的连接，正在建立新连接
+    Connecting,
+    /// 命令开始执行
+    Started { connection_id: String, reused: bool },
+    /// 命令输出（已按会话编码解码）
+    Output { stream: ExecStream, data: String },
+    /// 命令执行结束
+    Finished { exit_status: Option<u32>, duration_ms: u64 },
+    /// 连接或执行失败
+    Failed { error: String },
+    /// 所有主机执行完毕，退出码为 0 的计为成功
+    Completed { succeeded: usize, failed: usize },
+}
+
+/// 在多个会话上批量执行命令
+///
+/// 立即返回批次 ID，执行过程通过 `batch-exec` 事件发送（事件中带有批次 ID）。
+///
+/// # 参数
+/// - `session_ids`: 会话配置 ID 列表
+/// - `command`: 远程命令
+/// - `concurrency`: 同时执行的主机数，默认 8，最大 32
+/// - `timeout`: 每台主机的超时时间（秒），默认 300
+/// - `confirm_protected`: 允许在受保护会话上执行危险命令
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn batch_exec(
+    app: AppHandle,
+    manager: State<'_, SSHManagerState>,
+    pool: State<'_, DbPool>,
+    session_ids: Vec<String>,
+    command: String,
+    concurrency: Option<usize>,
+    timeout: Option<u64>,
+    confirm_protected: Option<bool>,
+) -> Result<String> {
+    if command.trim().is_empty() {
+        return Err(SSHError::Ssh("Command cannot be empty".to_string()));
+    }
+
+    // 去重并保持顺序
+    let mut seen = HashSet::new();
+    let session_ids: Vec<String> = session_ids.into_iter().filter(|id| seen.insert(id.clone())).collect();
+    if session_ids.is_empty() {
+        return Err(SSHError::Ssh("No sessions selected".to_string()));
+    }
+
+    let batch_id = uuid::Uuid::new_v4().to_string();
+    let concurrency = concurrency.unwrap_or(DEFAULT_CONCURRENCY).clamp(1, MAX_CONCURRENCY);
+    let job = Arc::new(BatchJob {
+        app,
+        manager: manager.inner().clone(),
+        pool: pool.inner().clone(),
+        batch_id: batch_id.clone(),
+        command,
+        timeout: Duration::from_secs(timeout.unwrap_or(DEFAULT_TIMEOUT_SECS).max(1)),
+        confirm_protected: confirm_protected.unwrap_or(false),
+    });
+    tracing::info!(
+        "[Batch] Starting {} on {} host(s), concurrency {}",
+        batch_id,
+        session_ids.len(),
+        concurrency
+    );
+
+    tauri::async_runtime::spawn(async move {
+        let slots = Arc::new(Semaphore::new(concurrency));
+        let mut tasks = JoinSet::new();
+        for session_id in session_ids {
+            let job = job.clone();
+            let slots = slots.clone();
+            tasks.spawn(async move {
+                let _permit = slots.acquire_owned().await.ok();
+                job.run_host(session_id).await
+            });
+        }
+
+        let (mut succeeded, mut failed) = (0, 0);
+        while let Some(result) = tasks.join_next().await {
+            match result {
+                Ok(true) => succeeded += 1,
+                _ => failed += 1,
+            }
+        }
+
+        tracing::info!("[Batch] {} finished: {} succeeded, {} failed", job.batch_id, succeeded, failed);
+        job.emit(None, BatchEventKind::Completed { succeeded, failed });
+    });
+
+    Ok(batch_id)
+}
+
+/// 一次批量执行
+struct BatchJob {
+    app: AppHandle,
+    manager: SSHManagerState,
+    pool: DbPool,
+    batch_id: String,
+    command: String,
+    timeout: Duration,
+    confirm_protected: bool,
+}
+
+impl BatchJob {
+    /// 在单台主机上执行，返回命令是否成功退出
+    async fn run_host(&self, session_id: String) -> bool {
+        let started = Instant::now();
+        match self.exec_on_session(&session_id).await {
+            Ok(output) => {
+                let success = output.success();
+                self.emit(
+                    Some(&session_id),
+                    BatchEventKind::Finished {
+                        exit_status: output.exit_status,
+                        duration_ms: started.elapsed().as_millis() as u64,
+                    },
+                );
+                success
+            }
+            Err(e) => {
+                tracing::warn!("[Batch] {} failed on {}: {}", self.batch_id, session_id, e);
+                self.emit(Some(&session_id), BatchEventKind::Failed { error: e.to_string() });
+                false
+            }
+        }
+    }
+
+    async fn exec_on_session(&self, session_id: &str) -> Result<ExecOutput> {
+        ensure_session_loaded(&self.manager, &self.pool, session_id).await?;
+        let config = self.manager.get_session_config(session_id).await?;
+        if config.protected && !self.confirm_protected {
+            if let Some(pattern) = match_destructive(&self.command) {
+                return Err(SSHError::NotSupported(format!(
+                    "Destructive command ({}) requires confirmation on protected session {}",
+                    pattern, config.name
+                )));
+            }
+        }
+
+        let (connection, created) = match find_connected(&self.manager, session_id).await {
+            Some(connection) => (connection, false),
+            None => {
+                self.emit(Some(session_id), BatchEventKind::Connecting);
+                (self.connect(session_id).await?, true)
+            }
+        };
+        self.emit(
+            Some(session_id),
+            BatchEventKind::Started { connection_id: connection.id.clone(), reused: !created },
+        );
+
+        let mut stdout = connection.config.encoding.decoder();
+        let mut stderr = connection.config.encoding.decoder();
+        let result = connection
+            .exec_with(&self.command, self.timeout, |stream, data| {
+                let decoder = match stream {
+                    ExecStream::Stdout => &mut stdout,
+                    ExecStream::Stderr => &mut stderr,
+                };
+                let text = decode(decoder, data);
+                if !text.is_empty() {
+                    self.emit(Some(session_id), BatchEventKind::Output { stream, data: text });
+                }
+            })
+            .await;
+
+        if created {
+            self.close(&connection.id).await;
+        }
+        result
+    }
+
+    /// 为会话新建连接
+    async fn connect(&self, session_id: &str) -> Result<ConnectionInstance> {
+        let connection_id = self.manager.create_connection(session_id).await?;
+        if let Err(e) = self.manager.connect_connection(&connection_id).await {
+            self.close(&connection_id).await;
+            return Err(e);
+        }
+        self.manager.get_connection(&connection_id).await
+    }
+
+    async fn close(&self, connection_id: &str) {
+        if let Err(e) = self.manager.delete_session(connection_id).await {
+            tracing::debug!("[Batch] Failed to close connection {}: {}", connection_id, e);
+        }
+    }
+
+    fn emit(&self, session_id: Option<&str>, kind: BatchEventKind) {
+        let event = BatchExecEvent {
+            batch_id: self.batch_id.clone(),
+            session_id: session_id.map(str::to_string),
+            kind,
+        };
+        if let Err(e) = self.app.emit(BATCH_EXEC_EVENT, event) {
+            tracing::warn!("[Batch] Failed to emit event: {}", e);
+        }
+    }
+}
+
+/// 查找会话已建立的连接
+async fn find_connected(manager: &SSHManager, session_id: &str) -> Option<ConnectionInstance> {
+    for connection in manager.connections().await {
+        if connection.session_id == session_id && matches!(connection.status().await, SessionStatus::Connected) {
+            return Some(connection);
+        }
+    }
+    None
+}
+
+/// 解码一块输出，被截断的多字节字符留到下一块
+fn decode(decoder: &mut Decoder, data: &[u8]) -> String {
+    let capacity = decoder
+        .max_utf8_buffer_length(data.len())
+        .unwrap_or(data.len() * 3 + 16);
+    let mut text = String::with_capacity(capacity);
+    let _ = decoder.decode_to_string(data, &mut text, false);
+    text
+}
@@ -0,0 +1,150 @@
+//! 会话批量编辑命令
+
+use crate::commands::session::{load_identity_credentials, load_session_from_db, SSHManagerState};
+use crate::commands::ssh_session::{get_current_user_info, stored_session_info};
+use crate::config::Storage;
+use crate::database::DbPool;
+use crate::database::repositories::{IdentityRepository, SshSessionRepository};
+use crate::services::bulk_edit_service::{BulkEditReport, BulkSessionChange, ResolvedIdentity};
+use crate::ssh::events::SessionChangeKind;
+use crate::ssh::session::SessionConfig;
+use tauri::{AppHandle, State};
+
+/// 批量修改多个会话的同一字段
+///
+/// `dry_run` 为 true 时只返回差异；否则同时写入会话存储文件、数据库和内存中的会话配置，
+/// 任一会话不存在或任一步写入失败时整体不生效
+#[tauri::command]
+pub async fn session_bulk_edit(
+    app: AppHandle,
+    pool: State<'_, DbPool>,
+    manager: State<'_, SSHManagerState>,
+    session_ids: Vec<String>,
+    change: BulkSessionChange,
+    dry_run: bool,
+) -> Result<BulkEditReport, String> {
+    let current_user = get_current_user_info(&pool);
+    let repo = SshSessionRepository::new(pool.inner().clone());
+
+    // 引用的身份（解除引用时为 None）
+    let identity = match &change {
+        BulkSessionChange::Identity { identity_id: Some(identity_id) } => {
+            let (username, auth_method) = load_identity_credentials(&pool, identity_id)?;
+            Some(ResolvedIdentity { id: identity_id.clone(), username, auth_method })
+        }
+        _ => None,
+    };
+
+    let storage = Storage::new(Some(&app)).map_err(|e| e.to_string())?;
+    let stored_sessions = storage.load_sessions().unwrap_or_default();
+
+    // 计算差异：优先使用内存中的配置，其次是会话存储文件和数据库
+    let mut report = BulkEditReport { dry_run, ..Default::default() };
+    let mut db_sessions = Vec::new();
+    for session_id in &session_ids {
+        let db_session = repo.find_by_id(session_id)
+            .map_err(|e| format!("Failed to find session: {}", e))?
+            .filter(|s| !s.is_deleted && s.user_id == current_user.user_id);
+
+        let config: Option<SessionConfig> = match manager.get_session_config(session_id).await {
+            Ok(config) => Some(config),
+            Err(_) => match stored_sessions.iter().find(|(id, _)| id == session_id) {
+                Some((_, config)) => Some(config.clone()),
+                None if db_session.is_some() => load_session_from_db(&pool, session_id).await?,
+                None => None,
+            },
+        };
+        let Some(config) = config else {
+            report.missing.push(session_id.clone());
+            continue;
+        };
+
+        let identity_id = match db_session {
+            Some(_) => repo.find_identity_id(session_id)
+                .map_err(|e| format!("Failed to get identity: {}", e))?,
+            None => None,
+        };
+        match change.diff(session_id, &config, identity_id.as_deref()) {
+            Some(diff) => {
+                report.changes.push(diff);
+                if let Some(session) = db_session {
+                    db_sessions.push(session);
+                }
+            }
+            None => report.unchanged.push(session_id.clone()),
+        }
+    }
+
+    if dry_run || report.changes.is_empty() {
+        return Ok(report);
+    }
+    if !report.missing.is_empty() {
+        return Err(format!("Sessions not found: {}", report.missing.join(", ")));
+    }
+
+    let changed = |id: &str| report.changes.iter().any(|diff| diff.session_id == id);
+
+    // 1. 会话存储文件
+    let mut updated_storage = stored_sessions.clone();
+    let mut storage_changed = false;
+    for (_, config) in updated_storage.iter_mut().filter(|(id, _)| changed(id)) {
+        change.apply(config, identity.as_ref());
+        storage_changed = true;
+    }
+    if storage_changed {
+        storage.save_sessions(&updated_storage).map_err(|e| e.to_string())?;
+    }
+
+    // 2. 数据库（一个事务），失败时恢复会话存储文件
+    let now = chrono::Utc::now().timestamp();
+    let identity_record = match &identity {
+        Some(identity) => IdentityRepository::new(pool.inner().clone())
+            .find_by_id(&identity.id)
+            .map_err(|e| format!("Failed to find identity: {}", e))?,
+        None => None,
+    };
+    for session in db_sessions.iter_mut() {
+        match &change {
+            BulkSessionChange::Username { value } => session.username = value.clone(),
+            BulkSessionChange::Port { value } => session.port = *value,
+            BulkSessionChange::Group { value } => session.group_name = value.clone(),
+            BulkSessionChange::Identity { .. } => {
+                if let Some(identity) = &identity_record {
+                    session.username = identity.username.clone();
+                    session.auth_method_encrypted = identity.auth_method_encrypted.clone();
+                    session.auth_nonce = identity.auth_nonce.clone();
+                }
+            }
+            // 心跳间隔和主机密钥检查不保存在数据库中
+            BulkSessionChange::KeepAliveInterval { .. } | BulkSessionChange::StrictHostKeyChecking { .. } => continue,
+        }
+        session.updated_at = now;
+        session.client_ver += 1;
+        session.is_dirty = true;
+    }
+    let identity_id = match &change {
+        BulkSessionChange::Identity { identity_id } => Some(identity_id.as_deref()),
+        _ => None,
+    };
+    if let Err(e) = repo.update_batch(&db_sessions, identity_id) {
+        if storage_changed {
+            if let Err(restore_err) = storage.save_sessions(&stored_sessions) {
+                tracing::error!("Failed to restore session storage after bulk edit error: {}", restore_err);
+            }
+        }
+        return Err(format!("Failed to update sessions: {}", e));
+    }
+
+    // 3. 内存中的会话配置（未加载的会话下次连接时从存储加载）
+    for diff in &report.changes {
+        let update = change.to_update(identity.as_ref());
+        if manager.update_session(&diff.session_id, update).await.is_err() {
+            if let Some(session) = db_sessions.iter().find(|s| s.id == diff.session_id) {
+                manager.change_log().record(SessionChangeKind::Updated, &session.id, Some(stored_session_info(session)));
+            }
+        }
+    }
+
+    tracing::info!("Bulk edited {} of {} session(s): {}", report.changes.len(), session_ids.len(), change.field());
+    Ok(report)
+}
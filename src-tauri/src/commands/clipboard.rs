@@ -0,0 +1,190 @@
+//! 剪贴板历史命令
+//!
+//! 记录从终端复制的文本及其来源连接，需用户在设置中开启。
+//! 内容使用 device_id 派生的密钥加密存储，只保留最近的若干条。
+
+use crate::commands::session::SSHManagerState;
+use crate::commands::ssh_session::get_current_user_info;
+use crate::database::DbPool;
+use crate::database::repositories::{AppSettingsRepository, ClipboardRepository};
+use crate::models::clipboard::{ClipboardEntry, ClipboardRecord};
+use crate::services::CryptoService;
+use tauri::State;
+
+/// 每个用户最多保留的记录数
+const MAX_CLIPBOARD_ENTRIES: i64 = 200;
+
+/// 单条记录的最大长度（字节），超出的复制内容不记录
+const MAX_CLIPBOARD_CONTENT_SIZE: usize = 64 * 1024;
+
+/// 获取剪贴板历史是否启用
+#[tauri::command]
+pub async fn clipboard_history_get_enabled(
+    pool: State<'_, DbPool>,
+) -> Result<bool, String> {
+    let repo = AppSettingsRepository::new(pool.inner().clone());
+    repo.get_clipboard_history_enabled().map_err(|e| e.to_string())
+}
+
+/// 设置剪贴板历史是否启用（关闭时清空已有记录）
+#[tauri::command]
+pub async fn clipboard_history_set_enabled(
+    enabled: bool,
+    pool: State<'_, DbPool>,
+) -> Result<(), String> {
+    let repo = AppSettingsRepository::new(pool.inner().clone());
+    repo.set_clipboard_history_enabled(enabled).map_err(|e| e.to_string())?;
+
+    if !enabled {
+        let current_user = get_current_user_info(&pool);
+        ClipboardRepository::new(pool.inner().clone())
+            .clear(&current_user.user_id)
+            .map_err(|e| format!("Failed to clear clipboard history: {}", e))?;
+    }
+
+    Ok(())
+}
+
+/// 记录一次终端复制
+///
+/// 未开启剪贴板历史、内容为空、过大或与上一条相同时不记录，返回 None
+#[tauri::command]
+pub async fn clipboard_history_add(
+    pool: State<'_, DbPool>,
+    manager: State<'_, SSHManagerState>,
+    connection_id: Option<String>,
+    content: String,
+) -> Result<Option<ClipboardEntry>, String> {
+    let settings_repo = AppSettingsRepository::new(pool.inner().clone());
+    if !settings_repo.get_clipboard_history_enabled().unwrap_or(false) {
+        return Ok(None);
+    }
+    if content.trim().is_empty() || content.len() > MAX_CLIPBOARD_CONTENT_SIZE {
+        return Ok(None);
+    }
+
+    let current_user = get_current_user_info(&pool);
+    let key = CryptoService::derive_key_from_device_id(&current_user.device_id)
+        .map_err(|e| format!("Failed to derive key: {}", e))?;
+    let repo = ClipboardRepository::new(pool.inner().clone());
+
+    // 连续复制相同内容只记录一次
+    let latest = repo.find_by_user(&current_user.user_id, None)
+        .map_err(|e| format!("Failed to load clipboard history: {}", e))?
+        .into_iter()
+        .next();
+    if let Some(latest) = latest {
+        if CryptoService::decrypt_with_key(&latest.content_encrypted, &key).ok().as_deref() == Some(content.as_str()) {
+            return Ok(None);
+        }
+    }
+
+    // 来源连接信息（连接已关闭时只保留 connection_id）
+    let (session_name, host_key) = match &connection_id {
+        Some(id) => match manager.get_connection(id).await {
+            Ok(connection) => (Some(connection.config.name.clone()), Some(connection.host_key())),
+            Err(_) => (None, None),
+        },
+        None => (None, None),
+    };
+
+    let content_encrypted = CryptoService::encrypt_with_key(&content, &key)
+        .map_err(|e| format!("Failed to encrypt content: {}", e))?;
+
+    let record = ClipboardRecord {
+        id: uuid::Uuid::new_v4().to_string(),
+        user_id: current_user.user_id,
+        connection_id,
+        session_name,
+        host_key,
+        content_encrypted,
+        content_length: content.len() as i64,
+        created_at: chrono::Utc::now().timestamp_millis(),
+    };
+
+    repo.insert(&record, MAX_CLIPBOARD_ENTRIES)
+        .map_err(|e| format!("Failed to save clipboard entry: {}", e))?;
+
+    Ok(Some(ClipboardEntry {
+        id: record.id,
+        connection_id: record.connection_id,
+        session_name: record.session_name,
+        host_key: record.host_key,
+        content,
+        created_at: record.created_at,
+    }))
+}
+
+/// 查询剪贴板历史
+///
+/// # 参数
+/// - `query`: 内容关键字（不区分大小写），为空时返回全部
+/// - `connection_id`: 只返回来自该连接的记录
+/// - `limit`: 返回数量上限
+#[tauri::command]
+pub async fn clipboard_history_list(
+    pool: State<'_, DbPool>,
+    query: Option<String>,
+    connection_id: Option<String>,
+    limit: Option<usize>,
+) -> Result<Vec<ClipboardEntry>, String> {
+    let current_user = get_current_user_info(&pool);
+    let repo = ClipboardRepository::new(pool.inner().clone());
+
+    let records = repo.find_by_user(&current_user.user_id, connection_id.as_deref())
+        .map_err(|e| format!("Failed to load clipboard history: {}", e))?;
+    if records.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let key = CryptoService::derive_key_from_device_id(&current_user.device_id)
+        .map_err(|e| format!("Failed to derive key: {}", e))?;
+    let query = query.map(|q| q.to_lowercase()).filter(|q| !q.is_empty());
+
+    let entries = records
+        .into_iter()
+        .filter_map(|record| {
+            // 无法解密的记录（如 device_id 已变化）直接跳过
+            let content = CryptoService::decrypt_with_key(&record.content_encrypted, &key).ok()?;
+            if let Some(query) = &query {
+                if !content.to_lowercase().contains(query) {
+                    return None;
+                }
+            }
+            Some(ClipboardEntry {
+                id: record.id,
+                connection_id: record.connection_id,
+                session_name: record.session_name,
+                host_key: record.host_key,
+                content,
+                created_at: record.created_at,
+            })
+        })
+        .take(limit.unwrap_or(usize::MAX))
+        .collect();
+
+    Ok(entries)
+}
+
+/// 删除单条剪贴板历史
+#[tauri::command]
+pub async fn clipboard_history_delete(
+    pool: State<'_, DbPool>,
+    entry_id: String,
+) -> Result<(), String> {
+    let current_user = get_current_user_info(&pool);
+    ClipboardRepository::new(pool.inner().clone())
+        .delete(&entry_id, &current_user.user_id)
+        .map_err(|e| format!("Failed to delete clipboard entry: {}", e))
+}
+
+/// 清空剪贴板历史，返回删除的条数
+#[tauri::command]
+pub async fn clipboard_history_clear(
+    pool: State<'_, DbPool>,
+) -> Result<usize, String> {
+    let current_user = get_current_user_info(&pool);
+    ClipboardRepository::new(pool.inner().clone())
+        .clear(&current_user.user_id)
+        .map_err(|e| format!("Failed to clear clipboard history: {}", e))
+}
@@ -0,0 +1,73 @@
+//! 崩溃报告命令
+//!
+//! 崩溃报告由 panic hook 写入本地存储目录，只有用户主动选择上传时才会发送到服务器
+
+use crate::database::DbPool;
+use crate::database::repositories::{AppSettingsRepository, UserAuthRepository};
+use crate::models::crash_report::{CrashReport, CrashReportSummary, CrashReportUploadRequest};
+use crate::services::crash_service::CrashService;
+use crate::services::ApiClient;
+use tauri::State;
+
+/// 列出本地崩溃报告（最新的在前）
+#[tauri::command]
+pub async fn crash_report_list() -> Result<Vec<CrashReportSummary>, String> {
+    CrashService::list_reports().map_err(|e| e.to_string())
+}
+
+/// 获取崩溃报告详情
+#[tauri::command]
+pub async fn crash_report_get(report_id: String) -> Result<CrashReport, String> {
+    CrashService::load_report(&report_id).map_err(|e| e.to_string())
+}
+
+/// 删除崩溃报告
+#[tauri::command]
+pub async fn crash_report_delete(report_id: String) -> Result<(), String> {
+    CrashService::delete_report(&report_id).map_err(|e| e.to_string())
+}
+
+/// 上传崩溃报告到已配置的服务器
+///
+/// 只上传报告本身；已登录时附带 device_id，便于关联同一设备的多次崩溃
+#[tauri::command]
+pub async fn crash_report_upload(
+    pool: State<'_, DbPool>,
+    report_id: String,
+) -> Result<(), String> {
+    let mut report = CrashService::load_report(&report_id).map_err(|e| e.to_string())?;
+
+    let settings_repo = AppSettingsRepository::new(pool.inner().clone());
+    let server_url = settings_repo.get_server_url().map_err(|e| e.to_string())?;
+    let language = settings_repo.get_language().ok();
+    let device_id = UserAuthRepository::new(pool.inner().clone())
+        .find_current()
+        .ok()
+        .flatten()
+        .map(|user| user.device_id);
+
+    let request = CrashReportUploadRequest {
+        report_id: report.id.clone(),
+        device_id,
+        app_version: report.app_version.clone(),
+        os: report.os.clone(),
+        os_version: report.os_version.clone(),
+        arch: report.arch.clone(),
+        message: report.message.clone(),
+        location: report.location.clone(),
+        backtrace: report.backtrace.clone(),
+        log_tail: report.log_tail.join("\n"),
+        crashed_at: report.created_at,
+    };
+
+    let api_client = ApiClient::new(server_url, language).map_err(|e| e.to_string())?;
+    api_client.submit_crash_report(&request)
+        .await
+        .map_err(|e| format!("Failed to upload crash report: {}", e))?;
+
+    report.uploaded = true;
+    CrashService::save_report(&report).map_err(|e| e.to_string())?;
+
+    tracing::info!("Crash report uploaded: {}", report.id);
+    Ok(())
+}
@@ -0,0 +1,111 @@
+//! `ssh://` 和 `sftp://` 链接处理
+//!
+//! 应用注册为这两个协议的处理程序。点击链接时解析目标并创建临时连接，
+//! 通过 `deeplink-open` 事件通知前端打开终端或文件标签页。
+//! 应用由链接启动时前端尚未监听事件，链接暂存到前端启动完成后调用 `deeplink_take_pending` 时再打开。
+
+use crate::commands::session::{quick_connect_config, SSHManagerState};
+use crate::database::DbPool;
+use crate::ssh::quick_connect::{parse_uri, UriScheme};
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+/// 链接打开成功的事件
+pub const DEEPLINK_OPEN_EVENT: &str = "deeplink-open";
+
+/// 链接无法打开的事件（载荷为错误信息）
+pub const DEEPLINK_ERROR_EVENT: &str = "deeplink-error";
+
+/// 由链接创建的连接
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeepLinkOpened {
+    pub uri: String,
+    pub connection_id: String,
+    /// `sftp` 时前端打开文件标签页
+    pub scheme: UriScheme,
+    /// sftp 链接中的远程路径
+    pub path: Option<String>,
+}
+
+/// 应用启动时收到的链接
+#[derive(Default)]
+pub struct DeepLinkState {
+    pending: Mutex<Vec<String>>,
+}
+
+/// 解析链接并创建临时连接
+///
+/// 主机匹配已保存会话的名称时使用其配置；否则使用默认私钥认证，并严格校验主机密钥
+async fn open(app: &AppHandle, uri: &str) -> Result<DeepLinkOpened, String> {
+    let link = parse_uri(uri)?;
+    let manager = app.state::<SSHManagerState>();
+    let pool = app.state::<DbPool>();
+
+    let name = match link.target.port {
+        Some(port) => format!("{}:{}", link.target.host, port),
+        None => link.target.host.clone(),
+    };
+    let config = quick_connect_config(&pool, &name, link.target, None)
+        .await
+        .map_err(|e| e.to_string())?;
+    let connection_id = manager
+        .create_temporary_connection(config)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    tracing::info!("[DeepLink] Opened {:?} link as connection {}", link.scheme, connection_id);
+    Ok(DeepLinkOpened {
+        uri: uri.to_string(),
+        connection_id,
+        scheme: link.scheme,
+        path: link.path,
+    })
+}
+
+/// 应用由链接启动时暂存链接
+pub fn defer_urls(app: &AppHandle, urls: Vec<String>) {
+    app.state::<DeepLinkState>().pending.lock().unwrap().extend(urls);
+}
+
+/// 打开系统传入的链接并通知前端
+pub fn handle_urls(app: &AppHandle, urls: Vec<String>) {
+    for uri in urls {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            match open(&app, &uri).await {
+                Ok(opened) => {
+                    let _ = app.emit(DEEPLINK_OPEN_EVENT, opened);
+                }
+                Err(e) => {
+                    tracing::warn!("[DeepLink] Failed to open {}: {}", uri, e);
+                    let _ = app.emit(DEEPLINK_ERROR_EVENT, format!("{}: {}", uri, e));
+                }
+            }
+        });
+    }
+}
+
+/// 打开链接（如从剪贴板粘贴的链接）
+#[tauri::command]
+pub async fn deeplink_open(app: AppHandle, uri: String) -> Result<DeepLinkOpened, String> {
+    open(&app, &uri).await
+}
+
+/// 打开应用启动时收到的链接（前端启动完成后调用一次），无法打开的链接通过 `deeplink-error` 事件通知
+#[tauri::command]
+pub async fn deeplink_take_pending(app: AppHandle, state: State<'_, DeepLinkState>) -> Result<Vec<DeepLinkOpened>, String> {
+    let urls = std::mem::take(&mut *state.pending.lock().unwrap());
+    let mut opened = Vec::new();
+    for uri in urls {
+        match open(&app, &uri).await {
+            Ok(link) => opened.push(link),
+            Err(e) => {
+                tracing::warn!("[DeepLink] Failed to open {}: {}", uri, e);
+                let _ = app.emit(DEEPLINK_ERROR_EVENT, format!("{}: {}", uri, e));
+            }
+        }
+    }
+    Ok(opened)
+}
@@ -0,0 +1,17 @@
+//! 局域网 SSH 主机发现命令
+
+use crate::ssh::discovery::{self, DiscoveredHost, DEFAULT_TIMEOUT_SECS};
+use std::time::Duration;
+
+/// 发现局域网内的 SSH 主机（mDNS/Bonjour 服务，可选扫描本机 /24 网段的 22 端口）
+///
+/// # 参数
+/// - `timeout`: mDNS 浏览时长（秒），默认 3，最长 30
+/// - `scan_subnet`: 是否扫描本机所在网段，默认不扫描
+#[tauri::command]
+pub async fn discover_ssh_hosts(timeout: Option<u64>, scan_subnet: Option<bool>) -> Result<Vec<DiscoveredHost>, String> {
+    let timeout = Duration::from_secs(timeout.unwrap_or(DEFAULT_TIMEOUT_SECS));
+    discovery::discover(timeout, scan_subnet.unwrap_or(false))
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,109 @@
+//! Docker 容器终端命令
+//!
+//! `connection_id` 为空时使用本机 Docker，否则在该 SSH 连接所在主机上执行
+
+use crate::error::{Result, SSHError};
+use crate::ssh::docker::{self, DockerContainer};
+use std::time::Duration;
+use tauri::State;
+
+use super::session::SSHManagerState;
+
+/// `docker ps` 超时时间
+const LIST_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// 命令失败时的说明：优先使用 stderr，为空时依次使用 stdout 和退出码
+fn failure_detail(stderr: &[u8], stdout: &[u8], exit_code: Option<i64>) -> String {
+    for output in [stderr, stdout] {
+        let text = String::from_utf8_lossy(output);
+        let text = text.trim();
+        if !text.is_empty() {
+            return text.to_string();
+        }
+    }
+    match exit_code {
+        Some(code) => format!("退出码 {}", code),
+        None => "未返回退出码".to_string(),
+    }
+}
+
+/// 列出运行中的容器
+///
+/// # 参数
+/// - `connection_id`: SSH 连接 ID，为空时列出本机容器
+#[tauri::command]
+pub async fn docker_list_containers(
+    manager: State<'_, SSHManagerState>,
+    connection_id: Option<String>,
+) -> Result<Vec<DockerContainer>> {
+    let output = match connection_id {
+        Some(connection_id) => {
+            let connection = manager.get_connection(&connection_id).await?;
+            let output = connection.exec(&docker::list_command(), LIST_TIMEOUT).await?;
+            if !output.success() {
+                return Err(SSHError::Ssh(format!(
+                    "docker ps 执行失败: {}",
+                    failure_detail(&output.stderr, &output.stdout, output.exit_status.map(i64::from))
+                )));
+            }
+            output.stdout_lossy()
+        }
+        None => {
+            let output = tokio::time::timeout(
+                LIST_TIMEOUT,
+                tokio::process::Command::new("docker")
+                    .args(["ps", "--format", docker::LIST_FORMAT])
+                    .output(),
+            )
+            .await
+            .map_err(|_| SSHError::Ssh("docker ps 执行超时".to_string()))??;
+            if !output.status.success() {
+                return Err(SSHError::Ssh(format!(
+                    "docker ps 执行失败: {}",
+                    failure_detail(&output.stderr, &output.stdout, output.status.code().map(i64::from))
+                )));
+            }
+            String::from_utf8_lossy(&output.stdout).into_owned()
+        }
+    };
+
+    Ok(docker::parse_containers(&output))
+}
+
+/// 打开容器终端
+///
+/// 远程容器会使用该连接的配置另建一条 SSH 连接并执行 `docker exec -it`，
+/// 本机容器使用本地后端。返回新建的临时连接 ID，之后通过 `session_connect` 建立连接
+///
+/// # 参数
+/// - `connection_id`: SSH 连接 ID，为空时进入本机容器
+/// - `container`: 容器名称或 ID
+/// - `shell`: 容器内的 shell，为空时优先使用 bash
+#[tauri::command]
+pub async fn session_open_docker(
+    manager: State<'_, SSHManagerState>,
+    connection_id: Option<String>,
+    container: String,
+    shell: Option<String>,
+) -> Result<String> {
+    let command = docker::exec_command(&container, shell.as_deref())
+        .map_err(SSHError::ConnectionFailed)?;
+
+    let config = match connection_id {
+        Some(connection_id) => {
+            let connection = manager.get_connection(&connection_id).await?;
+            let mut config = connection.config.clone();
+            config.name = format!("{} ({})", container.trim(), config.name);
+            config.remote_command = Some(command);
+            config.startup_commands.clear();
+            config
+        }
+        None => {
+            let mut config = super::session::local_session_config(Some(command));
+            config.name = container.trim().to_string();
+            config
+        }
+    };
+
+    manager.create_temporary_connection(config).await
+}
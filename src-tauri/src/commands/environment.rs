@@ -0,0 +1,67 @@
+//! 远程环境检查命令
+//!
+//! 采集连接上的环境变量、PATH 和 shell 启动文件，保存为快照并与之前的快照或其他服务器比较
+
+use crate::error::{Result, SSHError};
+use crate::ssh::environment::{self, EnvDiff, EnvSnapshot};
+use std::time::Duration;
+use tauri::State;
+
+use super::session::SSHManagerState;
+
+/// 采集脚本超时时间（登录 shell 可能加载较慢的启动文件）
+const COLLECT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 采集连接的环境快照并保存
+///
+/// # 参数
+/// - `connection_id`: SSH 连接 ID
+#[tauri::command]
+pub async fn env_snapshot(
+    manager: State<'_, SSHManagerState>,
+    connection_id: String,
+) -> Result<EnvSnapshot> {
+    let connection = manager.get_connection(&connection_id).await?;
+    let output = connection.exec(&environment::build_command(), COLLECT_TIMEOUT).await?;
+    if output.stdout.is_empty() {
+        return Err(SSHError::Ssh(format!(
+            "环境采集失败: {}",
+            output.stderr_lossy().trim()
+        )));
+    }
+
+    let mut snapshot = environment::parse_output(&output.stdout_lossy());
+    snapshot.session_id = connection.session_id.clone();
+    snapshot.target = format!(
+        "{}@{}:{}",
+        connection.config.username, connection.config.host, connection.config.port
+    );
+    environment::save_snapshot(&snapshot)?;
+
+    tracing::info!(
+        "Environment snapshot of {}: {} variables, {} PATH entries, {} rc files",
+        snapshot.target,
+        snapshot.variables.len(),
+        snapshot.path.len(),
+        snapshot.rc_files.len()
+    );
+    Ok(snapshot)
+}
+
+/// 列出会话保存的快照（从新到旧）
+#[tauri::command]
+pub async fn env_snapshot_list(session_id: String) -> Result<Vec<EnvSnapshot>> {
+    environment::load_snapshots(&session_id)
+}
+
+/// 比较两个快照（可以来自同一会话的不同时间，也可以来自不同服务器）
+///
+/// # 参数
+/// - `before_id`: 作为基准的快照 ID
+/// - `after_id`: 与基准比较的快照 ID
+#[tauri::command]
+pub async fn env_snapshot_diff(before_id: String, after_id: String) -> Result<EnvDiff> {
+    let before = environment::find_snapshot(&before_id)?;
+    let after = environment::find_snapshot(&after_id)?;
+    Ok(environment::diff(&before, &after))
+}
@@ -0,0 +1,68 @@
+//! 一次性远程命令执行
+//!
+//! 在已有连接上打开独立的 exec channel 执行命令，不分配 PTY，也不影响交互式终端，
+//! 供监控、快捷操作等上层功能使用
+
+use crate::error::{Result, SSHError};
+use serde::Serialize;
+use std::time::{Duration, Instant};
+use tauri::State;
+
+use super::session::SSHManagerState;
+
+/// 默认超时时间（秒）
+const DEFAULT_EXEC_TIMEOUT_SECS: u64 = 30;
+
+/// 最大超时时间（秒）
+const MAX_EXEC_TIMEOUT_SECS: u64 = 3600;
+
+/// 命令执行结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecResult {
+    pub stdout: String,
+    pub stderr: String,
+    /// 退出码（服务器未返回时为空，如命令被信号终止）
+    pub exit_status: Option<u32>,
+    pub duration_ms: u64,
+}
+
+/// 在连接上执行一次性命令
+///
+/// # 参数
+/// - `connection_id`: 连接 ID
+/// - `command`: 远程命令，由远程用户的 shell 解释
+/// - `timeout`: 超时时间（秒），默认 30，最大 3600
+#[tauri::command]
+pub async fn ssh_exec(
+    manager: State<'_, SSHManagerState>,
+    connection_id: String,
+    command: String,
+    timeout: Option<u64>,
+) -> Result<ExecResult> {
+    if command.trim().is_empty() {
+        return Err(SSHError::Ssh("Command cannot be empty".to_string()));
+    }
+    let timeout = Duration::from_secs(
+        timeout.unwrap_or(DEFAULT_EXEC_TIMEOUT_SECS).clamp(1, MAX_EXEC_TIMEOUT_SECS),
+    );
+
+    let connection = manager.get_connection(&connection_id).await?;
+    let started = Instant::now();
+    let output = connection.exec(&command, timeout).await?;
+    let duration_ms = started.elapsed().as_millis() as u64;
+
+    tracing::debug!(
+        "[Exec] Command on {} exited with {:?} in {} ms",
+        connection_id,
+        output.exit_status,
+        duration_ms
+    );
+
+    Ok(ExecResult {
+        stdout: output.stdout_lossy(),
+        stderr: output.stderr_lossy(),
+        exit_status: output.exit_status,
+        duration_ms,
+    })
+}
@@ -0,0 +1,147 @@
+//! 端口转发命令
+
+use crate::commands::session::SSHManagerState;
+use crate::database::repositories::SshSessionRepository;
+use crate::database::DbPool;
+use crate::error::Result;
+use crate::ssh::forwarding::{ForwardInfo, ForwardManager, RemoteForwardSpec};
+use crate::ssh::session::SessionConfigUpdate;
+use std::sync::Arc;
+use tauri::State;
+
+/// 端口转发管理器状态
+pub type ForwardManagerState = Arc<ForwardManager>;
+
+/// 创建本地转发（ssh -L）
+///
+/// `bind_address` 默认为 127.0.0.1；`bind_port` 为 0 时由系统分配
+#[tauri::command]
+pub async fn forward_local_create(
+    forwards: State<'_, ForwardManagerState>,
+    connection_id: String,
+    bind_address: Option<String>,
+    bind_port: u16,
+    remote_host: String,
+    remote_port: u16,
+) -> Result<ForwardInfo> {
+    forwards
+        .create_local(&connection_id, bind_address, bind_port, remote_host, remote_port)
+        .await
+}
+
+/// 创建动态转发（ssh -D），在本地提供 SOCKS5 代理
+///
+/// `bind_address` 默认为 127.0.0.1；`bind_port` 为 0 时由系统分配
+#[tauri::command]
+pub async fn forward_dynamic_create(
+    forwards: State<'_, ForwardManagerState>,
+    connection_id: String,
+    bind_address: Option<String>,
+    bind_port: u16,
+) -> Result<ForwardInfo> {
+    forwards.create_dynamic(&connection_id, bind_address, bind_port).await
+}
+
+/// 创建远程转发（ssh -R）
+///
+/// 转发会保存到会话配置，之后连接该会话时自动建立
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn forward_remote_create(
+    forwards: State<'_, ForwardManagerState>,
+    manager: State<'_, SSHManagerState>,
+    pool: State<'_, DbPool>,
+    connection_id: String,
+    remote_bind_address: Option<String>,
+    remote_port: u16,
+    local_host: String,
+    local_port: u16,
+) -> Result<ForwardInfo> {
+    let spec = RemoteForwardSpec {
+        remote_bind_address: remote_bind_address.unwrap_or_default().trim().to_string(),
+        remote_port,
+        local_host: local_host.trim().to_string(),
+        local_port,
+    };
+    let info = forwards.create_remote(&connection_id, spec.clone()).await?;
+
+    let session_id = manager.get_connection(&connection_id).await?.session_id;
+    save_remote_forwards(&manager, &pool, &session_id, |specs| {
+        if !specs.contains(&spec) {
+            specs.push(spec);
+        }
+    })
+    .await;
+
+    Ok(info)
+}
+
+/// 删除远程转发，并从会话配置中移除
+#[tauri::command]
+pub async fn forward_remote_delete(
+    forwards: State<'_, ForwardManagerState>,
+    manager: State<'_, SSHManagerState>,
+    pool: State<'_, DbPool>,
+    forward_id: String,
+) -> Result<()> {
+    let (session_id, spec) = forwards.delete_remote(&forward_id).await?;
+    save_remote_forwards(&manager, &pool, &session_id, |specs| specs.retain(|s| *s != spec)).await;
+    Ok(())
+}
+
+/// 修改会话保存的远程转发列表（内存中的会话配置和数据库）
+///
+/// 临时连接没有保存的会话，更新失败时只记录日志
+async fn save_remote_forwards(
+    manager: &SSHManagerState,
+    pool: &DbPool,
+    session_id: &str,
+    modify: impl FnOnce(&mut Vec<RemoteForwardSpec>),
+) {
+    let repo = SshSessionRepository::new(pool.clone());
+    let mut specs: Vec<RemoteForwardSpec> = match manager.get_session_config(session_id).await {
+        Ok(config) => config.remote_forwards,
+        Err(_) => repo
+            .find_remote_forwards(session_id)
+            .ok()
+            .flatten()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default(),
+    };
+    modify(&mut specs);
+
+    let update = SessionConfigUpdate {
+        remote_forwards: Some(specs.clone()),
+        ..Default::default()
+    };
+    if let Err(e) = manager.update_session(session_id, update).await {
+        tracing::debug!("Session {} not loaded in memory: {}", session_id, e);
+    }
+
+    let json = if specs.is_empty() {
+        None
+    } else {
+        serde_json::to_string(&specs).ok()
+    };
+    if let Err(e) = repo.set_remote_forwards(session_id, json.as_deref()) {
+        tracing::warn!("Failed to save remote forwards for session {}: {}", session_id, e);
+    }
+}
+
+/// 列出端口转发（可按连接过滤）
+#[tauri::command]
+pub async fn forward_list(
+    forwards: State<'_, ForwardManagerState>,
+    connection_id: Option<String>,
+) -> Result<Vec<ForwardInfo>> {
+    Ok(forwards.list(connection_id.as_deref()).await)
+}
+
+/// 删除端口转发
+#[tauri::command]
+pub async fn forward_delete(
+    forwards: State<'_, ForwardManagerState>,
+    forward_id: String,
+) -> Result<()> {
+    forwards.delete(&forward_id).await
+}
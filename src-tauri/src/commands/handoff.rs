@@ -0,0 +1,49 @@
+use tauri::{AppHandle, State};
+
+use crate::commands::auth::ApiClientStateWrapper;
+use crate::database::DbPool;
+use crate::services::handoff_service::{HandoffServiceState, OnlineDevice};
+
+/// 连接设备 WebSocket（登录后调用）
+#[tauri::command]
+pub async fn handoff_start(
+    app: AppHandle,
+    pool: State<'_, DbPool>,
+    api_client_state: State<'_, ApiClientStateWrapper>,
+    handoff: State<'_, HandoffServiceState>,
+) -> Result<(), String> {
+    handoff
+        .start(app, &pool, api_client_state.inner().clone())
+        .map_err(|e| e.to_string())
+}
+
+/// 断开设备 WebSocket（退出登录时调用）
+#[tauri::command]
+pub async fn handoff_stop(
+    app: AppHandle,
+    handoff: State<'_, HandoffServiceState>,
+) -> Result<(), String> {
+    handoff.stop(&app);
+    Ok(())
+}
+
+/// 获取同一账号当前在线的其他设备
+#[tauri::command]
+pub async fn handoff_list_devices(
+    handoff: State<'_, HandoffServiceState>,
+) -> Result<Vec<OnlineDevice>, String> {
+    Ok(handoff.devices())
+}
+
+/// 请求另一台设备打开会话
+#[tauri::command]
+pub async fn handoff_open_on_device(
+    pool: State<'_, DbPool>,
+    handoff: State<'_, HandoffServiceState>,
+    session_id: String,
+    target_client_id: String,
+) -> Result<(), String> {
+    handoff
+        .send(&pool, &target_client_id, &session_id)
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,76 @@
+//! 连接事件钩子命令
+//!
+//! 钩子在连接建立、断开和传输完成时执行用户配置的本地程序，事件上下文以 JSON 写入 stdin
+
+use crate::services::hook_service::{HookConfig, HookEvent, HookRunResult, HookService};
+
+/// 列出所有钩子
+#[tauri::command]
+pub async fn hook_list() -> Result<Vec<HookConfig>, String> {
+    HookService::load_hooks().map_err(|e| e.to_string())
+}
+
+/// 保存钩子（id 为空时创建，否则更新）
+#[tauri::command]
+pub async fn hook_save(mut hook: HookConfig) -> Result<HookConfig, String> {
+    if hook.program.trim().is_empty() {
+        return Err("钩子程序路径不能为空".to_string());
+    }
+
+    let mut hooks = HookService::load_hooks().map_err(|e| e.to_string())?;
+
+    if hook.id.is_empty() {
+        hook.id = uuid::Uuid::new_v4().to_string();
+        hooks.push(hook.clone());
+    } else if let Some(existing) = hooks.iter_mut().find(|h| h.id == hook.id) {
+        *existing = hook.clone();
+    } else {
+        return Err(format!("Hook not found: {}", hook.id));
+    }
+
+    HookService::save_hooks(&hooks).map_err(|e| e.to_string())?;
+    Ok(hook)
+}
+
+/// 删除钩子
+#[tauri::command]
+pub async fn hook_delete(hook_id: String) -> Result<(), String> {
+    let mut hooks = HookService::load_hooks().map_err(|e| e.to_string())?;
+    hooks.retain(|h| h.id != hook_id);
+    HookService::save_hooks(&hooks).map_err(|e| e.to_string())
+}
+
+/// 使用示例上下文执行钩子，返回执行结果（用于配置时调试脚本）
+#[tauri::command]
+pub async fn hook_test(hook_id: String) -> Result<HookRunResult, String> {
+    let hooks = HookService::load_hooks().map_err(|e| e.to_string())?;
+    let hook = hooks
+        .into_iter()
+        .find(|h| h.id == hook_id)
+        .ok_or_else(|| format!("Hook not found: {}", hook_id))?;
+
+    let mut context = match hook.event {
+        HookEvent::OnConnect | HookEvent::OnDisconnect => serde_json::json!({
+            "connectionId": "test-connection",
+            "sessionId": "test-session",
+            "name": "Test Session",
+            "host": "example.com",
+            "port": 22,
+            "username": "root",
+        }),
+        HookEvent::OnTransferComplete => serde_json::json!({
+            "direction": "upload",
+            "connectionId": "test-connection",
+            "taskId": "test-task",
+            "localPath": "/tmp/example.txt",
+            "remotePath": "/root/example.txt",
+            "bytesTransferred": 1024,
+            "filesCompleted": 1,
+        }),
+    };
+    context["event"] = hook.event.as_str().into();
+    context["timestamp"] = chrono::Utc::now().timestamp_millis().into();
+    context["test"] = true.into();
+
+    HookService::run_hook(&hook, &context).await.map_err(|e| e.to_string())
+}
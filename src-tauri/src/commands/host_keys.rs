@@ -0,0 +1,34 @@
+//! 主机密钥管理命令
+//!
+//! 查看、删除和导出应用 known_hosts 中已信任的服务器主机密钥
+
+use crate::ssh::known_hosts::{self, HostKeyEntry};
+
+/// 列出所有已信任的主机密钥（含 SHA256/MD5 指纹和密钥类型）
+#[tauri::command]
+pub async fn ssh_host_keys_list() -> Result<Vec<HostKeyEntry>, String> {
+    known_hosts::list().map_err(|e| e.to_string())
+}
+
+/// 删除主机的密钥，下次连接时重新记录
+///
+/// # 参数
+/// - `port`: 默认为 22
+/// - `key_type`: 只删除该类型的密钥（如 ssh-ed25519），为空时删除主机的所有密钥
+///
+/// 返回删除的条数
+#[tauri::command]
+pub async fn ssh_host_key_remove(
+    host: String,
+    port: Option<u16>,
+    key_type: Option<String>,
+) -> Result<usize, String> {
+    known_hosts::remove(&host, port.unwrap_or(22), key_type.as_deref()).map_err(|e| e.to_string())
+}
+
+/// 导出 OpenSSH 格式的 known_hosts 内容，`host` 为空时导出全部
+#[tauri::command]
+pub async fn ssh_host_key_export(host: Option<String>, port: Option<u16>) -> Result<String, String> {
+    let host = host.filter(|h| !h.trim().is_empty());
+    known_hosts::export(host.as_deref().map(|h| (h, port.unwrap_or(22)))).map_err(|e| e.to_string())
+}
@@ -0,0 +1,223 @@
+//! 共享身份命令
+//!
+//! 身份保存一份加密的用户名和认证方式，多个会话可以引用同一个身份。
+//! 修改身份（如轮换密码或私钥）时，所有引用它的会话会同步更新。
+
+use crate::commands::session::SSHManagerState;
+use crate::commands::ssh_session::{convert_front_end_auth_method, get_current_user_info, stored_session_info};
+use crate::database::DbPool;
+use crate::database::repositories::{IdentityRepository, SshSessionRepository};
+use crate::models::identity::Identity;
+use crate::services::CryptoService;
+use crate::ssh::events::SessionChangeKind;
+use tauri::State;
+
+/// 转换为前端格式（不包含认证信息）
+fn identity_to_frontend(identity: &Identity) -> serde_json::Value {
+    serde_json::json!({
+        "id": identity.id,
+        "name": identity.name,
+        "username": identity.username,
+        "createdAt": identity.created_at,
+        "updatedAt": identity.updated_at,
+    })
+}
+
+/// 获取属于当前用户的身份
+fn find_owned_identity(repo: &IdentityRepository, identity_id: &str, user_id: &str) -> Result<Identity, String> {
+    let identity = repo.find_by_id(identity_id)
+        .map_err(|e| format!("Failed to find identity: {}", e))?
+        .ok_or_else(|| format!("Identity not found: {}", identity_id))?;
+
+    if identity.user_id != user_id {
+        return Err("Identity belongs to different user".to_string());
+    }
+
+    Ok(identity)
+}
+
+/// 创建共享身份
+#[tauri::command]
+pub async fn identity_create(
+    pool: State<'_, DbPool>,
+    name: String,
+    username: String,
+    auth_method: serde_json::Value,
+) -> Result<serde_json::Value, String> {
+    let current_user = get_current_user_info(&pool);
+    let auth_method = convert_front_end_auth_method(&auth_method)?;
+
+    let (auth_method_encrypted, auth_nonce) = CryptoService::encrypt_password(
+        &serde_json::to_string(&auth_method).unwrap(),
+        &current_user.device_id,
+    )
+        .map_err(|e| format!("Failed to encrypt auth method: {}", e))?;
+
+    let now = chrono::Utc::now().timestamp();
+    let identity = Identity {
+        id: uuid::Uuid::new_v4().to_string(),
+        user_id: current_user.user_id,
+        name,
+        username,
+        auth_method_encrypted,
+        auth_nonce,
+        created_at: now,
+        updated_at: now,
+    };
+
+    let repo = IdentityRepository::new(pool.inner().clone());
+    repo.create(&identity)
+        .map_err(|e| format!("Failed to create identity: {}", e))?;
+
+    tracing::info!("Created identity: {}", identity.id);
+    Ok(identity_to_frontend(&identity))
+}
+
+/// 列出当前用户的所有身份
+#[tauri::command]
+pub async fn identity_list(
+    pool: State<'_, DbPool>,
+) -> Result<Vec<serde_json::Value>, String> {
+    let current_user = get_current_user_info(&pool);
+    let repo = IdentityRepository::new(pool.inner().clone());
+
+    let identities = repo.find_by_user(&current_user.user_id)
+        .map_err(|e| format!("Failed to list identities: {}", e))?;
+
+    Ok(identities.iter().map(identity_to_frontend).collect())
+}
+
+/// 更新身份，并将新凭据写入所有引用它的会话
+///
+/// 返回被更新的会话数量
+#[tauri::command]
+pub async fn identity_update(
+    pool: State<'_, DbPool>,
+    manager: State<'_, SSHManagerState>,
+    identity_id: String,
+    updates: serde_json::Value,
+) -> Result<usize, String> {
+    let current_user = get_current_user_info(&pool);
+    let repo = IdentityRepository::new(pool.inner().clone());
+    let mut identity = find_owned_identity(&repo, &identity_id, &current_user.user_id)?;
+
+    if let Some(name) = updates.get("name").and_then(|v| v.as_str()) {
+        identity.name = name.to_string();
+    }
+    if let Some(username) = updates.get("username").and_then(|v| v.as_str()) {
+        identity.username = username.to_string();
+    }
+    if let Some(auth_method_value) = updates.get("authMethod") {
+        let auth_method = convert_front_end_auth_method(auth_method_value)?;
+
+        let (auth_method_encrypted, auth_nonce) = CryptoService::encrypt_password(
+            &serde_json::to_string(&auth_method).unwrap(),
+            &current_user.device_id,
+        )
+            .map_err(|e| format!("Failed to encrypt auth method: {}", e))?;
+
+        identity.auth_method_encrypted = auth_method_encrypted;
+        identity.auth_nonce = auth_nonce;
+    }
+
+    identity.updated_at = chrono::Utc::now().timestamp();
+    repo.update(&identity)
+        .map_err(|e| format!("Failed to update identity: {}", e))?;
+
+    // 将凭据写入引用该身份的会话
+    let session_repo = SshSessionRepository::new(pool.inner().clone());
+    let session_ids = session_repo.apply_identity(
+        &identity.id,
+        &identity.username,
+        &identity.auth_method_encrypted,
+        &identity.auth_nonce,
+    )
+        .map_err(|e| format!("Failed to update sessions: {}", e))?;
+
+    for session_id in &session_ids {
+        if let Ok(Some(session)) = session_repo.find_by_id(session_id) {
+            manager.change_log().record(SessionChangeKind::Updated, session_id, Some(stored_session_info(&session)));
+        }
+    }
+
+    tracing::info!("Updated identity {} and {} sessions", identity.id, session_ids.len());
+    Ok(session_ids.len())
+}
+
+/// 删除身份
+///
+/// 引用该身份的会话会解除引用，但保留当前的凭据副本，仍然可以连接
+#[tauri::command]
+pub async fn identity_delete(
+    pool: State<'_, DbPool>,
+    identity_id: String,
+) -> Result<(), String> {
+    let current_user = get_current_user_info(&pool);
+    let repo = IdentityRepository::new(pool.inner().clone());
+    find_owned_identity(&repo, &identity_id, &current_user.user_id)?;
+
+    let session_repo = SshSessionRepository::new(pool.inner().clone());
+    session_repo.clear_identity(&identity_id)
+        .map_err(|e| format!("Failed to detach sessions: {}", e))?;
+
+    repo.delete(&identity_id)
+        .map_err(|e| format!("Failed to delete identity: {}", e))?;
+
+    tracing::info!("Deleted identity: {}", identity_id);
+    Ok(())
+}
+
+/// 设置会话使用的身份
+///
+/// `identity_id` 为 None 时解除引用；设置身份时会立即用身份的凭据覆盖会话的凭据
+#[tauri::command]
+pub async fn db_ssh_session_set_identity(
+    pool: State<'_, DbPool>,
+    manager: State<'_, SSHManagerState>,
+    session_id: String,
+    identity_id: Option<String>,
+) -> Result<(), String> {
+    let current_user = get_current_user_info(&pool);
+    let session_repo = SshSessionRepository::new(pool.inner().clone());
+
+    let mut session = session_repo.find_by_id(&session_id)
+        .map_err(|e| format!("Failed to find session: {}", e))?
+        .ok_or_else(|| format!("Session not found: {}", session_id))?;
+
+    if session.user_id != current_user.user_id {
+        return Err("Session belongs to different user".to_string());
+    }
+
+    if let Some(identity_id) = &identity_id {
+        let repo = IdentityRepository::new(pool.inner().clone());
+        let identity = find_owned_identity(&repo, identity_id, &current_user.user_id)?;
+
+        session.username = identity.username;
+        session.auth_method_encrypted = identity.auth_method_encrypted;
+        session.auth_nonce = identity.auth_nonce;
+        session.updated_at = chrono::Utc::now().timestamp();
+        session.client_ver += 1;
+        session.is_dirty = true;
+
+        session_repo.update(&session)
+            .map_err(|e| format!("Failed to update session: {}", e))?;
+
+        manager.change_log().record(SessionChangeKind::Updated, &session_id, Some(stored_session_info(&session)));
+    }
+
+    session_repo.set_identity_id(&session_id, identity_id.as_deref())
+        .map_err(|e| format!("Failed to set identity: {}", e))?;
+
+    Ok(())
+}
+
+/// 获取会话引用的身份 ID
+#[tauri::command]
+pub async fn db_ssh_session_get_identity(
+    pool: State<'_, DbPool>,
+    session_id: String,
+) -> Result<Option<String>, String> {
+    let session_repo = SshSessionRepository::new(pool.inner().clone());
+    session_repo.find_identity_id(&session_id)
+        .map_err(|e| format!("Failed to get identity: {}", e))
+}
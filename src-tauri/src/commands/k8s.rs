@@ -0,0 +1,154 @@
+//! Kubernetes Pod 终端命令
+//!
+//! `connection_id` 为空时使用本机 kubectl，否则在该 SSH 连接所在的堡垒机上执行；
+//! `kubeconfig` 为对应主机上的路径
+
+use crate::error::{Result, SSHError};
+use crate::ssh::k8s::{self, K8sPod, KubectlTarget};
+use std::time::Duration;
+use tauri::State;
+
+use super::session::SSHManagerState;
+
+/// kubectl 查询超时时间
+const KUBECTL_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// 执行 kubectl 查询并返回标准输出
+async fn run_kubectl(
+    manager: &SSHManagerState,
+    connection_id: Option<String>,
+    args: Vec<String>,
+) -> Result<String> {
+    match connection_id {
+        Some(connection_id) => {
+            let connection = manager.get_connection(&connection_id).await?;
+            let output = connection.exec(&k8s::remote_command(&args), KUBECTL_TIMEOUT).await?;
+            if !output.success() {
+                return Err(SSHError::Ssh(format!(
+                    "kubectl 执行失败: {}",
+                    output.stderr_lossy().trim()
+                )));
+            }
+            Ok(output.stdout_lossy())
+        }
+        None => {
+            let output = tokio::time::timeout(
+                KUBECTL_TIMEOUT,
+                tokio::process::Command::new("kubectl").args(&args).output(),
+            )
+            .await
+            .map_err(|_| SSHError::Ssh("kubectl 执行超时".to_string()))??;
+            if !output.status.success() {
+                return Err(SSHError::Ssh(format!(
+                    "kubectl 执行失败: {}",
+                    String::from_utf8_lossy(&output.stderr).trim()
+                )));
+            }
+            Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+        }
+    }
+}
+
+/// 列出命名空间
+///
+/// # 参数
+/// - `connection_id`: 堡垒机的 SSH 连接 ID，为空时使用本机 kubectl
+/// - `kubeconfig`: kubeconfig 路径，为空时使用默认配置
+/// - `context`: kubeconfig 中的 context
+#[tauri::command]
+pub async fn k8s_list_namespaces(
+    manager: State<'_, SSHManagerState>,
+    connection_id: Option<String>,
+    kubeconfig: Option<String>,
+    context: Option<String>,
+) -> Result<Vec<String>> {
+    let target = KubectlTarget {
+        kubeconfig: kubeconfig.as_deref(),
+        context: context.as_deref(),
+    };
+    let args = k8s::list_namespaces_args(&target).map_err(SSHError::Ssh)?;
+    let output = run_kubectl(&manager, connection_id, args).await?;
+    k8s::parse_namespaces(&output).map_err(SSHError::Ssh)
+}
+
+/// 列出 Pod
+///
+/// # 参数
+/// - `connection_id`: 堡垒机的 SSH 连接 ID，为空时使用本机 kubectl
+/// - `kubeconfig`: kubeconfig 路径，为空时使用默认配置
+/// - `context`: kubeconfig 中的 context
+/// - `namespace`: 命名空间，为空时列出全部命名空间
+#[tauri::command]
+pub async fn k8s_list_pods(
+    manager: State<'_, SSHManagerState>,
+    connection_id: Option<String>,
+    kubeconfig: Option<String>,
+    context: Option<String>,
+    namespace: Option<String>,
+) -> Result<Vec<K8sPod>> {
+    let target = KubectlTarget {
+        kubeconfig: kubeconfig.as_deref(),
+        context: context.as_deref(),
+    };
+    let args = k8s::list_pods_args(&target, namespace.as_deref()).map_err(SSHError::Ssh)?;
+    let output = run_kubectl(&manager, connection_id, args).await?;
+    k8s::parse_pods(&output).map_err(SSHError::Ssh)
+}
+
+/// 打开 Pod 终端
+///
+/// 与 `session_open_docker` 相同：远程 Pod 使用堡垒机连接的配置另建一条 SSH 连接
+/// 并执行 `kubectl exec -it`，本机使用本地后端。返回新建的临时连接 ID
+///
+/// # 参数
+/// - `connection_id`: 堡垒机的 SSH 连接 ID，为空时使用本机 kubectl
+/// - `kubeconfig`: kubeconfig 路径，为空时使用默认配置
+/// - `context`: kubeconfig 中的 context
+/// - `namespace`: Pod 所在命名空间
+/// - `pod`: Pod 名称
+/// - `container`: 容器名称，为空时使用 Pod 的默认容器
+/// - `shell`: 容器内的 shell，为空时优先使用 bash
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn k8s_exec_pod(
+    manager: State<'_, SSHManagerState>,
+    connection_id: Option<String>,
+    kubeconfig: Option<String>,
+    context: Option<String>,
+    namespace: String,
+    pod: String,
+    container: Option<String>,
+    shell: Option<String>,
+) -> Result<String> {
+    let target = KubectlTarget {
+        kubeconfig: kubeconfig.as_deref(),
+        context: context.as_deref(),
+    };
+    let command = k8s::exec_command(
+        &target,
+        &namespace,
+        &pod,
+        container.as_deref(),
+        shell.as_deref(),
+    )
+    .map_err(SSHError::ConnectionFailed)?;
+    let name = format!("{}/{}", namespace.trim(), pod.trim());
+
+    let config = match connection_id {
+        Some(connection_id) => {
+            let connection = manager.get_connection(&connection_id).await?;
+            let mut config = connection.config.clone();
+            config.name = format!("{} ({})", name, config.name);
+            config.remote_command = Some(command);
+            config.startup_commands.clear();
+            config
+        }
+        None => {
+            let mut config = super::session::local_session_config(Some(command));
+            config.name = name;
+            config
+        }
+    };
+
+    manager.create_temporary_connection(config).await
+}
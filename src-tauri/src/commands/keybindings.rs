@@ -69,7 +69,7 @@ pub async fn storage_keybindings_import(
 
     manager.save_keybindings(&data).map_err(|e| e.to_string())?;
 
-    println!("[Keybindings] Imported configuration successfully");
+    tracing::info!("[Keybindings] Imported configuration successfully");
     Ok(())
 }
 
@@ -84,6 +84,6 @@ pub async fn storage_keybindings_reset() -> std::result::Result<(), String> {
     // 保存默认配置
     manager.save_keybindings(&default_data).map_err(|e| e.to_string())?;
 
-    println!("[Keybindings] Reset to default configuration");
+    tracing::info!("[Keybindings] Reset to default configuration");
     Ok(())
 }
\ No newline at end of file
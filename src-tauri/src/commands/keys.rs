@@ -0,0 +1,131 @@
+//! SSH 密钥命令
+//!
+//! 生成的密钥保存在应用存储目录的 `keys` 子目录中，可直接作为会话的私钥认证使用
+
+use crate::config::Storage;
+use crate::error::{Result, SSHError};
+use crate::ssh::docker::is_safe_word;
+use crate::ssh::keys::{copy_id_command, generate_key, validate_public_key, KeyType};
+use serde::Serialize;
+use std::path::PathBuf;
+use std::time::Duration;
+use tauri::State;
+
+use super::session::SSHManagerState;
+
+/// ssh-copy-id 超时时间（秒）
+const COPY_ID_TIMEOUT_SECS: u64 = 30;
+
+/// 生成的密钥文件信息
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeneratedKeyInfo {
+    pub private_key_path: String,
+    pub public_key_path: String,
+    pub public_key: String,
+    pub fingerprint: String,
+}
+
+/// 密钥存储目录
+fn get_keys_dir() -> Result<PathBuf> {
+    Ok(Storage::get_app_storage_dir()?.join("keys"))
+}
+
+/// 写入私钥文件（Unix 上权限为 600）
+fn write_private_key(path: &PathBuf, content: &str) -> Result<()> {
+    #[cfg(unix)]
+    {
+        use std::io::Write;
+        use std::os::unix::fs::OpenOptionsExt;
+
+        let mut file = std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .mode(0o600)
+            .open(path)?;
+        file.write_all(content.as_bytes())?;
+    }
+    #[cfg(not(unix))]
+    std::fs::write(path, content)?;
+    Ok(())
+}
+
+/// 生成 SSH 密钥对
+///
+/// # 参数
+/// - `key_type`: `ed25519` 或 `rsa`
+/// - `bits`: RSA 位数，默认 3072
+/// - `passphrase`: 私钥口令，为空时不加密
+/// - `file_name`: 文件名，默认 `id_ed25519` / `id_rsa`，已存在时报错
+/// - `comment`: 公钥注释
+#[tauri::command]
+pub async fn ssh_keygen(
+    key_type: KeyType,
+    bits: Option<usize>,
+    passphrase: Option<String>,
+    file_name: Option<String>,
+    comment: Option<String>,
+) -> Result<GeneratedKeyInfo> {
+    let file_name = file_name
+        .filter(|name| !name.trim().is_empty())
+        .unwrap_or_else(|| key_type.default_file_name().to_string());
+    if !is_safe_word(&file_name) || file_name.contains('/') || file_name.starts_with('.') {
+        return Err(SSHError::Storage(format!("Invalid key file name: {}", file_name)));
+    }
+
+    let keys_dir = get_keys_dir()?;
+    std::fs::create_dir_all(&keys_dir)?;
+    let private_key_path = keys_dir.join(&file_name);
+    let public_key_path = keys_dir.join(format!("{}.pub", file_name));
+    if private_key_path.exists() || public_key_path.exists() {
+        return Err(SSHError::Storage(format!("Key file already exists: {}", private_key_path.display())));
+    }
+
+    // RSA 密钥生成较慢，放到阻塞线程中执行
+    let comment = comment.unwrap_or_default();
+    let key = tokio::task::spawn_blocking(move || {
+        generate_key(key_type, bits, passphrase.as_deref(), &comment)
+    })
+    .await
+    .map_err(|e| SSHError::Crypto(format!("Key generation task failed: {}", e)))??;
+
+    write_private_key(&private_key_path, &key.private_key)?;
+    std::fs::write(&public_key_path, format!("{}\n", key.public_key))?;
+
+    tracing::info!("[Keys] Generated {:?} key {}", key_type, key.fingerprint);
+
+    Ok(GeneratedKeyInfo {
+        private_key_path: private_key_path.to_string_lossy().to_string(),
+        public_key_path: public_key_path.to_string_lossy().to_string(),
+        public_key: key.public_key,
+        fingerprint: key.fingerprint,
+    })
+}
+
+/// 把公钥部署到远程主机的 `~/.ssh/authorized_keys`（ssh-copy-id）
+///
+/// 返回 true 表示已追加，false 表示公钥已存在
+#[tauri::command]
+pub async fn ssh_copy_id(
+    manager: State<'_, SSHManagerState>,
+    connection_id: String,
+    public_key: String,
+) -> Result<bool> {
+    let public_key = validate_public_key(&public_key)?;
+
+    let connection = manager.get_connection(&connection_id).await?;
+    let output = connection
+        .exec(&copy_id_command(&public_key), Duration::from_secs(COPY_ID_TIMEOUT_SECS))
+        .await?;
+
+    if output.exit_status != Some(0) {
+        return Err(SSHError::Ssh(format!(
+            "Failed to update authorized_keys: {}",
+            output.stderr_lossy().trim()
+        )));
+    }
+
+    let added = output.stdout_lossy().trim() == "added";
+    tracing::info!("[Keys] Public key {} on {}", if added { "added" } else { "already present" }, connection_id);
+    Ok(added)
+}
@@ -3,22 +3,57 @@ pub mod terminal;
 pub mod storage;
 pub mod sftp;
 pub mod recording;
+pub mod recording_cloud;
+pub mod workspace;
 pub mod keybindings;
 pub mod audio;
 pub mod ai;
 pub mod ai_history;
+pub mod ai_file_assistant;
 pub mod auth;
 pub mod sync;
 pub mod user_profile;
 pub mod app_settings;
 pub mod ssh_session;
+pub mod session_group;
+pub mod trash;
+pub mod traffic_stats;
 pub mod records;
+pub mod startup;
+pub mod session_note;
+pub mod identity;
+pub mod snippet;
+pub mod autocomplete;
+pub mod clipboard;
+pub mod backup;
+pub mod hooks;
+pub mod webhooks;
+pub mod plugins;
+pub mod crash;
+pub mod forward;
+pub mod ssh_config;
+pub mod host_keys;
+pub mod keys;
+pub mod variables;
+pub mod exec;
+pub mod batch;
+pub mod bulk_edit;
+pub mod password_rotation;
+pub mod handoff;
+pub mod docker;
+pub mod k8s;
+pub mod discovery;
+pub mod deeplink;
+pub mod advisory;
+pub mod environment;
 
 pub use session::*;
 pub use terminal::*;
 pub use storage::*;
 pub use sftp::*;
 pub use recording::*;
+pub use recording_cloud::*;
+pub use workspace::*;
 pub use keybindings::*;
 pub use audio::*;
 pub use ai::*;
@@ -27,7 +62,38 @@ pub use sync::*;
 pub use user_profile::*;
 pub use app_settings::*;
 pub use ssh_session::*;
+pub use session_group::*;
+pub use trash::*;
+pub use traffic_stats::*;
 pub use records::*;
+pub use startup::*;
+pub use session_note::*;
+pub use identity::*;
+pub use snippet::*;
+pub use autocomplete::*;
+pub use clipboard::*;
+pub use backup::*;
+pub use hooks::*;
+pub use webhooks::*;
+pub use plugins::*;
+pub use crash::*;
+pub use forward::*;
+pub use ssh_config::*;
+pub use host_keys::*;
+pub use keys::*;
+pub use variables::*;
+pub use exec::*;
+pub use batch::*;
+pub use bulk_edit::*;
+pub use password_rotation::*;
+pub use handoff::*;
+pub use discovery::*;
+pub use deeplink::*;
+pub use advisory::*;
+pub use environment::*;
+pub use docker::*;
+pub use k8s::*;
+pub use ai_file_assistant::*;
 
 // 导出 AI 配置相关的类型（用于 Tauri 命令序列化）
 #[allow(unused_imports)]
@@ -0,0 +1,229 @@
+//! 密码轮换命令
+//!
+//! 在已连接的会话上修改远程登录密码，用新密码重新登录校验成功后，
+//! 再把新密码写入会话存储文件、数据库和内存中的会话配置。
+//! 校验或写入失败时把远程密码改回原密码，会话存储保持不变。
+
+use crate::commands::session::SSHManagerState;
+use crate::commands::ssh_session::{get_current_user_info, stored_session_info};
+use crate::config::Storage;
+use crate::database::DbPool;
+use crate::database::repositories::SshSessionRepository;
+use crate::models::ssh_session::AuthMethod as DbAuthMethod;
+use crate::services::password_rotation_service::{
+    chpasswd_input, generate_password, validate_password, PasswdDialog, PasswordRotationReport, RotationMethod,
+};
+use crate::services::CryptoService;
+use crate::ssh::backends::russh::RusshBackend;
+use crate::ssh::connection::ConnectionInstance;
+use crate::ssh::events::SessionChangeKind;
+use crate::ssh::session::{AuthMethod, SessionConfigUpdate};
+use std::time::Duration;
+use tauri::{AppHandle, State};
+
+/// 修改远程密码的超时时间（秒）
+const CHANGE_TIMEOUT_SECS: u64 = 30;
+
+/// 用新密码重新登录的超时时间（秒）
+const VERIFY_TIMEOUT_SECS: u64 = 30;
+
+/// 生成随机强密码
+///
+/// - `length`: 默认 24，范围 12-128
+#[tauri::command]
+pub async fn password_generate(length: Option<usize>) -> Result<String, String> {
+    Ok(generate_password(length))
+}
+
+/// 在远程主机上把密码从 `current` 改为 `new`
+async fn change_remote_password(
+    connection: &ConnectionInstance,
+    method: RotationMethod,
+    current: &str,
+    new: &str,
+) -> Result<(), String> {
+    let timeout = Duration::from_secs(CHANGE_TIMEOUT_SECS);
+    match method {
+        RotationMethod::Passwd => {
+            let mut dialog = PasswdDialog::new(current, new);
+            let output = connection
+                .exec_interactive("LC_ALL=C passwd", timeout, |data| dialog.feed(data))
+                .await
+                .map_err(|e| e.to_string())?;
+            if let Some(failure) = dialog.failure() {
+                return Err(format!("passwd failed: {}", failure));
+            }
+            if output.exit_status != Some(0) {
+                return Err(format!("passwd exited with status {:?}", output.exit_status));
+            }
+        }
+        RotationMethod::Chpasswd => {
+            let input = chpasswd_input(&connection.config.username, new);
+            let output = connection
+                .exec_with_input("LC_ALL=C chpasswd", &input, timeout)
+                .await
+                .map_err(|e| e.to_string())?;
+            if output.exit_status != Some(0) {
+                return Err(format!("chpasswd failed: {}", output.stderr_lossy().trim()));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 把远程密码改回原密码，返回附加了回滚结果的错误信息
+async fn rollback(
+    connection: &ConnectionInstance,
+    method: RotationMethod,
+    current: &str,
+    new: &str,
+    error: String,
+) -> String {
+    match change_remote_password(connection, method, new, current).await {
+        Ok(()) => {
+            tracing::info!("[PasswordRotation] Restored previous password on {}", connection.id);
+            format!("{} (remote password restored)", error)
+        }
+        Err(rollback_err) => {
+            tracing::error!("[PasswordRotation] Failed to restore previous password on {}: {}", connection.id, rollback_err);
+            format!(
+                "{}; restoring the previous password also failed ({}), the remote password is now the generated one",
+                error, rollback_err
+            )
+        }
+    }
+}
+
+/// 轮换已连接会话的登录密码
+///
+/// 1. 通过 `passwd`（或 `chpasswd`）修改远程密码
+/// 2. 建立独立连接，用新密码登录校验
+/// 3. 写入会话存储文件和数据库（失败时恢复已写入的部分）并更新内存中的会话配置
+///
+/// 第 2、3 步失败时把远程密码改回原密码。`new_password` 为空时生成随机密码
+#[tauri::command]
+pub async fn password_rotate(
+    app: AppHandle,
+    pool: State<'_, DbPool>,
+    manager: State<'_, SSHManagerState>,
+    connection_id: String,
+    method: Option<RotationMethod>,
+    new_password: Option<String>,
+) -> Result<PasswordRotationReport, String> {
+    let method = method.unwrap_or_default();
+    let connection = manager.get_connection(&connection_id).await.map_err(|e| e.to_string())?;
+    let session_id = connection.session_id.clone();
+
+    if !connection.config.backend_type.is_russh() {
+        return Err("Password rotation is only supported for SSH sessions".to_string());
+    }
+    let AuthMethod::Password { password: current } = &connection.config.auth_method else {
+        return Err("Password rotation requires a session that uses password authentication".to_string());
+    };
+    let current = current.clone();
+
+    // 引用共享身份的会话应修改身份，避免与其他会话的凭据不一致
+    let current_user = get_current_user_info(&pool);
+    let repo = SshSessionRepository::new(pool.inner().clone());
+    let db_session = repo.find_by_id(&session_id)
+        .map_err(|e| format!("Failed to find session: {}", e))?
+        .filter(|s| !s.is_deleted && s.user_id == current_user.user_id);
+    if db_session.is_some() {
+        let identity_id = repo.find_identity_id(&session_id)
+            .map_err(|e| format!("Failed to get identity: {}", e))?;
+        if identity_id.is_some() {
+            return Err("This session uses a shared identity; rotate the identity's password instead".to_string());
+        }
+    }
+
+    let new = match new_password.filter(|p| !p.is_empty()) {
+        Some(password) => {
+            validate_password(&password)?;
+            password
+        }
+        None => generate_password(None),
+    };
+    if new == current {
+        return Err("The new password must differ from the current password".to_string());
+    }
+
+    // 1. 修改远程密码
+    change_remote_password(&connection, method, &current, &new).await?;
+    tracing::info!("[PasswordRotation] Changed remote password on {} via {:?}", connection_id, method);
+
+    // 2. 用新密码重新登录
+    let mut verify_config = connection.config.clone();
+    verify_config.auth_method = AuthMethod::Password { password: new.clone() };
+    let verified = tokio::time::timeout(Duration::from_secs(VERIFY_TIMEOUT_SECS), RusshBackend::verify_login(&verify_config))
+        .await
+        .map_err(|_| "Login with the new password timed out".to_string())
+        .and_then(|result| result.map_err(|e| format!("Login with the new password failed: {}", e)));
+    if let Err(e) = verified {
+        return Err(rollback(&connection, method, &current, &new, e).await);
+    }
+
+    // 3. 会话存储文件
+    let storage = Storage::new(Some(&app)).map_err(|e| e.to_string())?;
+    let stored_sessions = storage.load_sessions().unwrap_or_default();
+    let mut updated_storage = stored_sessions.clone();
+    let mut storage_updated = false;
+    for (_, config) in updated_storage.iter_mut().filter(|(id, _)| *id == session_id) {
+        config.auth_method = AuthMethod::Password { password: new.clone() };
+        storage_updated = true;
+    }
+    if storage_updated {
+        if let Err(e) = storage.save_sessions(&updated_storage) {
+            let error = format!("Failed to update session storage: {}", e);
+            return Err(rollback(&connection, method, &current, &new, error).await);
+        }
+    }
+
+    // 4. 数据库，失败时恢复会话存储文件
+    let mut database_updated = false;
+    if let Some(mut session) = db_session {
+        let auth_json = serde_json::to_string(&DbAuthMethod::Password { password: new.clone() })
+            .map_err(|e| format!("Failed to serialize auth method: {}", e))?;
+        let written = CryptoService::encrypt_password(&auth_json, &current_user.device_id)
+            .map_err(|e| format!("Failed to encrypt auth method: {}", e))
+            .and_then(|(auth_method_encrypted, auth_nonce)| {
+                session.auth_method_encrypted = auth_method_encrypted;
+                session.auth_nonce = auth_nonce;
+                session.updated_at = chrono::Utc::now().timestamp();
+                session.client_ver += 1;
+                session.is_dirty = true;
+                repo.update(&session).map_err(|e| format!("Failed to update session: {}", e))
+            });
+        match written {
+            Ok(session) => {
+                database_updated = true;
+                manager.change_log().record(SessionChangeKind::Updated, &session.id, Some(stored_session_info(&session)));
+            }
+            Err(e) => {
+                if storage_updated {
+                    if let Err(restore_err) = storage.save_sessions(&stored_sessions) {
+                        tracing::error!("Failed to restore session storage after password rotation error: {}", restore_err);
+                    }
+                }
+                return Err(rollback(&connection, method, &current, &new, e).await);
+            }
+        }
+    }
+
+    // 5. 内存中的会话配置（未加载的会话下次连接时从存储加载）
+    let update = SessionConfigUpdate {
+        auth_method: Some(AuthMethod::Password { password: new }),
+        ..Default::default()
+    };
+    if manager.update_session(&session_id, update).await.is_err() {
+        tracing::debug!("[PasswordRotation] Session {} not loaded in memory", session_id);
+    }
+
+    tracing::info!("[PasswordRotation] Rotated password for session {}", session_id);
+    Ok(PasswordRotationReport {
+        session_id,
+        method,
+        verified: true,
+        storage_updated,
+        database_updated,
+    })
+}
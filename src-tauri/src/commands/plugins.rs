@@ -0,0 +1,65 @@
+//! 插件命令
+//!
+//! 插件声明的命令统一通过 `plugin_invoke` 调用，菜单项由前端按位置查询后渲染
+
+use crate::plugins::manager::{PluginInfo, PluginMenuItem};
+use crate::plugins::manifest::MenuContext;
+use crate::plugins::PluginManager;
+use std::sync::Arc;
+use tauri::State;
+
+pub type PluginManagerState = Arc<PluginManager>;
+
+/// 列出所有插件
+#[tauri::command]
+pub async fn plugin_list(plugins: State<'_, PluginManagerState>) -> Result<Vec<PluginInfo>, String> {
+    Ok(plugins.list().await)
+}
+
+/// 重新扫描插件目录并重启所有插件
+#[tauri::command]
+pub async fn plugin_reload(plugins: State<'_, PluginManagerState>) -> Result<Vec<PluginInfo>, String> {
+    plugins.reload().await.map_err(|e| e.to_string())?;
+    Ok(plugins.list().await)
+}
+
+/// 插件目录（用户将插件放在其子目录中）
+#[tauri::command]
+pub async fn plugin_dir() -> Result<String, String> {
+    PluginManager::plugins_dir()
+        .map(|dir| dir.to_string_lossy().to_string())
+        .map_err(|e| e.to_string())
+}
+
+/// 启用或禁用插件
+#[tauri::command]
+pub async fn plugin_set_enabled(
+    plugins: State<'_, PluginManagerState>,
+    plugin_id: String,
+    enabled: bool,
+) -> Result<(), String> {
+    plugins.set_enabled(&plugin_id, enabled).await.map_err(|e| e.to_string())
+}
+
+/// 调用插件命令，返回插件的响应
+#[tauri::command]
+pub async fn plugin_invoke(
+    plugins: State<'_, PluginManagerState>,
+    plugin_id: String,
+    command: String,
+    params: Option<serde_json::Value>,
+) -> Result<serde_json::Value, String> {
+    plugins
+        .invoke(&plugin_id, &command, params.unwrap_or(serde_json::Value::Null))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 某个位置（终端、SFTP 文件、会话列表）的插件菜单项
+#[tauri::command]
+pub async fn plugin_menu_items(
+    plugins: State<'_, PluginManagerState>,
+    context: MenuContext,
+) -> Result<Vec<PluginMenuItem>, String> {
+    Ok(plugins.menu_items(context).await)
+}
@@ -1,7 +1,11 @@
 use crate::error::Result;
+use crate::services::recording_edit_service::{self, RecordingEditService, CHAPTER_SOURCE_MANUAL, DEFAULT_CHAPTER_KEYS};
+use crate::services::thumbnail_service::ThumbnailService;
+use crate::services::transcription_service::{self, TranscriptSegment, TranscriptionEngine, TranscriptionService};
+use crate::utils::redaction::Redactor;
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tauri::AppHandle;
 
 /// 通用文件写入命令（用于视频导出等场景）
@@ -9,7 +13,7 @@ use tauri::AppHandle;
 pub async fn fs_write_file(path: String, contents: Vec<u8>) -> std::result::Result<(), String> {
     let len = contents.len();
     fs::write(&path, contents).map_err(|e| format!("Failed to write file: {}", e))?;
-    println!("[FS] Written {} bytes to {}", len, path);
+    tracing::info!("[FS] Written {} bytes to {}", len, path);
     Ok(())
 }
 
@@ -69,6 +73,22 @@ fn default_video_format() -> String {
     "webm".to_string()
 }
 
+fn default_chapter_source() -> String {
+    CHAPTER_SOURCE_MANUAL.to_string()
+}
+
+/// 章节标记
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordingChapter {
+    /// 相对录制开始的时间（毫秒）
+    pub offset: i64,
+    pub title: String,
+    /// 来源：manual（手动添加）或 event（由标记事件生成）
+    #[serde(default = "default_chapter_source")]
+    pub source: String,
+}
+
 /// 录制元数据
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -93,6 +113,12 @@ pub struct RecordingMetadata {
     /// 关联的视频文件路径（相对于 recordings 目录）
     #[serde(skip_serializing_if = "Option::is_none")]
     pub video_file: Option<String>,
+    /// 章节标记（按时间排序）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub chapters: Vec<RecordingChapter>,
+    /// 语音转写（按时间排序，由 `recording_transcribe` 生成）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub transcript: Vec<TranscriptSegment>,
 }
 
 /// 录制文件
@@ -112,13 +138,41 @@ pub struct RecordingFileItem {
     pub metadata: RecordingMetadata,
     pub created_at: i64,
     pub file_size: u64,
+    /// 视频封面（相对于 recordings 目录）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thumbnail_file: Option<String>,
+    /// 视频动态预览（相对于 recordings 目录）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preview_file: Option<String>,
+}
+
+/// 录制搜索命中的位置
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordingSearchMatch {
+    /// 命中的字段：sessionName、description、tag、chapter 或 transcript
+    pub field: String,
+    /// 章节和转写片段的时间（毫秒，相对录制开始）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<i64>,
+    pub text: String,
+}
+
+/// 录制搜索结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordingSearchResult {
+    pub id: String,
+    pub session_name: String,
+    pub start_time: i64,
+    pub matches: Vec<RecordingSearchMatch>,
 }
 
 // ========== 辅助函数 ==========
 
 /// 获取录制文件存储目录
 /// 使用统一的存储目录：C:\Users\{Username}\.tauri-terminal\recording
-fn get_recordings_dir(_app: &AppHandle) -> Result<PathBuf> {
+pub(crate) fn get_recordings_dir(_app: &AppHandle) -> Result<PathBuf> {
     use crate::config::storage;
 
     // 使用统一的录制存储目录
@@ -172,7 +226,7 @@ fn get_file_metadata(path: &PathBuf) -> Result<(i64, u64)> {
 }
 
 /// 从文件路径加载录制文件
-fn load_recording_file_from_path(path: &PathBuf) -> Result<RecordingFile> {
+pub(crate) fn load_recording_file_from_path(path: &PathBuf) -> Result<RecordingFile> {
     let content = fs::read_to_string(path).map_err(|e| {
         crate::error::SSHError::Storage(format!("Failed to read recording file: {}", e))
     })?;
@@ -184,13 +238,94 @@ fn load_recording_file_from_path(path: &PathBuf) -> Result<RecordingFile> {
     Ok(file)
 }
 
+/// 将录制文件写回磁盘
+fn write_recording_file(path: &Path, recording_file: &RecordingFile) -> std::result::Result<(), String> {
+    let json_content = serde_json::to_string_pretty(recording_file)
+        .map_err(|e| format!("Failed to serialize recording file: {}", e))?;
+    fs::write(path, json_content).map_err(|e| format!("Failed to write recording file: {}", e))
+}
+
+/// 按 ID 加载录制目录中的录制文件
+fn load_recording_by_id(recordings_dir: &Path, file_id: &str) -> std::result::Result<(PathBuf, RecordingFile), String> {
+    let file_path = recordings_dir.join(format!("{}.json", file_id));
+    if !file_path.exists() {
+        return Err(format!("Recording file not found: {}", file_id));
+    }
+    let recording_file = load_recording_file_from_path(&file_path).map_err(|e| e.to_string())?;
+    Ok((file_path, recording_file))
+}
+
+/// 在录制元数据中查找关键词（不区分大小写）
+fn search_metadata(metadata: &RecordingMetadata, query: &str) -> Vec<RecordingSearchMatch> {
+    let needle = query.to_lowercase();
+    let hit = |text: &str| text.to_lowercase().contains(&needle);
+    let found = |field: &str, offset: Option<i64>, text: &str| RecordingSearchMatch {
+        field: field.to_string(),
+        offset,
+        text: text.to_string(),
+    };
+
+    let mut matches = Vec::new();
+    if hit(&metadata.session_name) {
+        matches.push(found("sessionName", None, &metadata.session_name));
+    }
+    if let Some(description) = metadata.description.as_deref().filter(|d| hit(d)) {
+        matches.push(found("description", None, description));
+    }
+    for tag in metadata.tags.iter().filter(|t| hit(t)) {
+        matches.push(found("tag", None, tag));
+    }
+    for chapter in metadata.chapters.iter().filter(|c| hit(&c.title)) {
+        matches.push(found("chapter", Some(chapter.offset), &chapter.title));
+    }
+    for segment in transcription_service::search_transcript(&metadata.transcript, query) {
+        matches.push(found("transcript", Some(segment.start), &segment.text));
+    }
+    matches
+}
+
+/// 屏蔽录制事件中的敏感信息
+///
+/// 按时间顺序处理输出和输入事件：输出中的私钥、密钥被替换，
+/// 密码提示之后输入的内容以 `*` 替换
+fn redact_recording(recording_file: &mut RecordingFile) {
+    let mut redactor = Redactor::default();
+
+    for event in &mut recording_file.events {
+        match event.event_type {
+            RecordingEventType::Output => {
+                // 输出数据格式：{ "data": [字节数组] }
+                let Some(bytes) = event.data.get("data")
+                    .and_then(|d| serde_json::from_value::<Vec<u8>>(d.clone()).ok())
+                else {
+                    continue;
+                };
+                let text = String::from_utf8_lossy(&bytes);
+                if let std::borrow::Cow::Owned(redacted) = redactor.redact_output(&text) {
+                    event.data["data"] = serde_json::json!(redacted.into_bytes());
+                }
+            }
+            RecordingEventType::Input => {
+                // 输入数据格式：{ "data": "字符串" }
+                let Some(input) = event.data.get("data").and_then(|d| d.as_str()) else {
+                    continue;
+                };
+                if let std::borrow::Cow::Owned(redacted) = redactor.redact_input(input) {
+                    event.data["data"] = serde_json::json!(redacted);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
 // ========== Tauri 命令 ==========
 
 /// 保存录制文件
 #[tauri::command]
 pub async fn recording_save(
     app: AppHandle,
-    recording_file: RecordingFile,
+    mut recording_file: RecordingFile,
     file_name: Option<String>,
 ) -> std::result::Result<String, String> {
     let recordings_dir = get_recordings_dir(&app).map_err(|e| e.to_string())?;
@@ -205,6 +340,9 @@ pub async fn recording_save(
 
     let file_path = recordings_dir.join(&filename);
 
+    // 写入磁盘前屏蔽敏感信息
+    redact_recording(&mut recording_file);
+
     // 序列化为 JSON
     let json_content = serde_json::to_string_pretty(&recording_file)
         .map_err(|e| format!("Failed to serialize recording file: {}", e))?;
@@ -213,7 +351,7 @@ pub async fn recording_save(
     fs::write(&file_path, json_content)
         .map_err(|e| format!("Failed to write recording file: {}", e))?;
 
-    println!(
+    tracing::info!(
         "[Recording] Saved recording file: {}",
         file_path.display()
     );
@@ -235,7 +373,7 @@ pub async fn recording_load(
 
     let file = load_recording_file_from_path(&path).map_err(|e| e.to_string())?;
 
-    println!("[Recording] Loaded recording file: {}", file_path);
+    tracing::info!("[Recording] Loaded recording file: {}", file_path);
 
     Ok(file)
 }
@@ -265,7 +403,7 @@ pub async fn recording_list(
         let recording_file = match load_recording_file_from_path(&path) {
             Ok(file) => file,
             Err(e) => {
-                eprintln!(
+                tracing::warn!(
                     "[Recording] Failed to load recording file {}: {}",
                     path.display(),
                     e
@@ -277,6 +415,14 @@ pub async fn recording_list(
         // 获取文件元数据
         let (modified, file_size) = get_file_metadata(&path).map_err(|e| e.to_string())?;
 
+        // 视频的缩略图（保存视频时生成）
+        let thumbnails = recording_file
+            .metadata
+            .video_file
+            .as_deref()
+            .map(|video_file| ThumbnailService::existing(&recordings_dir, video_file))
+            .unwrap_or_default();
+
         // 创建列表项
         let item = RecordingFileItem {
             id: path.file_stem()
@@ -287,6 +433,8 @@ pub async fn recording_list(
             metadata: recording_file.metadata,
             created_at: modified,
             file_size,
+            thumbnail_file: thumbnails.poster,
+            preview_file: thumbnails.preview,
         };
 
         items.push(item);
@@ -295,7 +443,7 @@ pub async fn recording_list(
     // 按创建时间倒序排列
     items.sort_by(|a, b| b.created_at.cmp(&a.created_at));
 
-    println!("[Recording] Listed {} recording files", items.len());
+    tracing::info!("[Recording] Listed {} recording files", items.len());
 
     Ok(items)
 }
@@ -321,8 +469,9 @@ pub async fn recording_delete(
             let video_path = recordings_dir.join(&video_file);
             if video_path.exists() {
                 let _ = fs::remove_file(&video_path);
-                println!("[Recording] Deleted video file: {}", video_file);
+                tracing::info!("[Recording] Deleted video file: {}", video_file);
             }
+            ThumbnailService::remove(&recordings_dir, &video_file);
         }
     }
 
@@ -330,18 +479,19 @@ pub async fn recording_delete(
     fs::remove_file(&json_path)
         .map_err(|e| format!("Failed to delete recording file: {}", e))?;
 
-    println!("[Recording] Deleted recording file: {}", file_id);
+    tracing::info!("[Recording] Deleted recording file: {}", file_id);
 
     Ok(())
 }
 
-/// 保存视频文件（Blob 数据）到磁盘
+/// 保存视频文件（Blob 数据）到磁盘，并生成封面（`animated_preview` 时同时生成动态预览）
 #[tauri::command]
 pub async fn recording_save_video(
     app: AppHandle,
     recording_id: String,
     video_data: Vec<u8>,
     file_extension: String,
+    animated_preview: Option<bool>,
 ) -> std::result::Result<String, String> {
     let recordings_dir = get_recordings_dir(&app).map_err(|e| e.to_string())?;
 
@@ -353,17 +503,20 @@ pub async fn recording_save_video(
     fs::write(&video_path, video_data)
         .map_err(|e| format!("Failed to write video file: {}", e))?;
 
-    println!(
+    tracing::info!(
         "[Recording] Saved video file: {} ({} bytes)",
         video_path.display(),
         video_path.metadata().map(|m| m.len()).unwrap_or(0)
     );
 
+    // 缩略图生成失败不影响视频保存
+    ThumbnailService::generate(&recordings_dir, &video_filename, animated_preview.unwrap_or(false)).await;
+
     // 返回相对于 recordings 目录的路径
     Ok(video_filename)
 }
 
-/// 加载视频文件数据
+/// 加载视频文件数据（也用于加载封面和动态预览）
 #[tauri::command]
 pub async fn recording_load_video(
     app: AppHandle,
@@ -379,7 +532,7 @@ pub async fn recording_load_video(
     let video_data = fs::read(&video_path)
         .map_err(|e| format!("Failed to read video file: {}", e))?;
 
-    println!(
+    tracing::info!(
         "[Recording] Loaded video file: {} ({} bytes)",
         video_filename,
         video_data.len()
@@ -429,7 +582,181 @@ pub async fn recording_update_metadata(
     fs::write(&file_path, json_content)
         .map_err(|e| format!("Failed to write recording file: {}", e))?;
 
-    println!("[Recording] Updated metadata for recording file: {}", file_id);
+    tracing::info!("[Recording] Updated metadata for recording file: {}", file_id);
 
     Ok(())
 }
+
+/// 设置录制的章节标记（替换原有章节），返回排序后的章节
+#[tauri::command]
+pub async fn recording_set_chapters(
+    app: AppHandle,
+    file_id: String,
+    chapters: Vec<RecordingChapter>,
+) -> std::result::Result<Vec<RecordingChapter>, String> {
+    let recordings_dir = get_recordings_dir(&app).map_err(|e| e.to_string())?;
+    let (file_path, mut recording_file) = load_recording_by_id(&recordings_dir, &file_id)?;
+
+    let length = recording_edit_service::recording_length(&recording_file);
+    let mut chapters: Vec<RecordingChapter> = chapters
+        .into_iter()
+        .filter(|c| c.offset >= 0 && c.offset <= length)
+        .collect();
+    recording_edit_service::normalize_chapters(&mut chapters);
+
+    recording_file.metadata.chapters = chapters.clone();
+    write_recording_file(&file_path, &recording_file)?;
+
+    tracing::info!("[Recording] Set {} chapters for recording file: {}", chapters.len(), file_id);
+
+    Ok(chapters)
+}
+
+/// 由录制中的标记事件生成章节
+///
+/// # 参数
+/// - `keys`: 作为章节的 metadata 事件 key，默认为 marker 和 recording_resumed
+///
+/// 保留手动添加的章节，替换之前由事件生成的章节，返回合并后的章节
+#[tauri::command]
+pub async fn recording_chapters_from_events(
+    app: AppHandle,
+    file_id: String,
+    keys: Option<Vec<String>>,
+) -> std::result::Result<Vec<RecordingChapter>, String> {
+    let recordings_dir = get_recordings_dir(&app).map_err(|e| e.to_string())?;
+    let (file_path, mut recording_file) = load_recording_by_id(&recordings_dir, &file_id)?;
+
+    let keys = keys.unwrap_or_else(|| DEFAULT_CHAPTER_KEYS.iter().map(|k| k.to_string()).collect());
+    let mut chapters: Vec<RecordingChapter> = recording_file
+        .metadata
+        .chapters
+        .drain(..)
+        .filter(|c| c.source == CHAPTER_SOURCE_MANUAL)
+        .collect();
+    chapters.extend(recording_edit_service::chapters_from_events(&recording_file, &keys));
+    recording_edit_service::normalize_chapters(&mut chapters);
+
+    recording_file.metadata.chapters = chapters.clone();
+    write_recording_file(&file_path, &recording_file)?;
+
+    tracing::info!("[Recording] Generated chapters for recording file: {} ({} total)", file_id, chapters.len());
+
+    Ok(chapters)
+}
+
+/// 裁剪录制到指定时间段，保存为新的录制文件（原录制不变）
+///
+/// # 参数
+/// - `start_ms`、`end_ms`: 相对录制开始的毫秒数
+/// - `include_video`: 同时截取关联的视频（需要系统安装 ffmpeg）
+///
+/// 返回新录制文件的 ID
+#[tauri::command]
+pub async fn recording_trim(
+    app: AppHandle,
+    file_id: String,
+    start_ms: i64,
+    end_ms: i64,
+    include_video: Option<bool>,
+) -> std::result::Result<String, String> {
+    let recordings_dir = get_recordings_dir(&app).map_err(|e| e.to_string())?;
+    let (_, recording_file) = load_recording_by_id(&recordings_dir, &file_id)?;
+
+    let mut trimmed = recording_edit_service::trim_recording(&recording_file, start_ms, end_ms)?;
+    let new_id = format!("{}_trim_{}-{}", file_id, start_ms / 1000, end_ms / 1000);
+
+    if include_video.unwrap_or(false) {
+        if let Some(video_file) = &recording_file.metadata.video_file {
+            let extension = Path::new(video_file).extension().and_then(|e| e.to_str()).unwrap_or("webm");
+            let new_video = format!("{}.{}", new_id, extension);
+            let output = recordings_dir.join(&new_video);
+            RecordingEditService::trim_video(&recordings_dir.join(video_file), &output, start_ms, end_ms).await?;
+            ThumbnailService::generate(&recordings_dir, &new_video, false).await;
+            trimmed.metadata.video_file = Some(new_video);
+        }
+    }
+
+    let file_path = recordings_dir.join(format!("{}.json", new_id));
+    write_recording_file(&file_path, &trimmed)?;
+
+    tracing::info!(
+        "[Recording] Trimmed recording file: {} -> {} ({} events)",
+        file_id, new_id, trimmed.metadata.event_count
+    );
+
+    Ok(new_id)
+}
+
+/// 转写录制视频中的语音，结果保存到录制元数据
+///
+/// # 参数
+/// - `engine`: `{ engine: "api", apiKey, baseUrl?, model? }`（OpenAI 兼容的 Whisper 接口）
+///   或 `{ engine: "local", modelPath, binary? }`（本地 whisper.cpp）
+/// - `language`: 语言代码（如 zh、en），为空时自动识别
+///
+/// 需要录制时保存了带音轨的视频，并且系统安装了 ffmpeg；返回转写片段
+#[tauri::command]
+pub async fn recording_transcribe(
+    app: AppHandle,
+    file_id: String,
+    engine: TranscriptionEngine,
+    language: Option<String>,
+) -> std::result::Result<Vec<TranscriptSegment>, String> {
+    let recordings_dir = get_recordings_dir(&app).map_err(|e| e.to_string())?;
+    let (file_path, mut recording_file) = load_recording_by_id(&recordings_dir, &file_id)?;
+
+    let video_file = recording_file
+        .metadata
+        .video_file
+        .clone()
+        .ok_or_else(|| format!("Recording has no video with audio: {}", file_id))?;
+    let language = language.filter(|l| !l.trim().is_empty());
+    let length = recording_edit_service::recording_length(&recording_file);
+
+    let transcript = TranscriptionService::transcribe(
+        &recordings_dir.join(&video_file),
+        &engine,
+        language.as_deref(),
+        length,
+    )
+    .await?;
+
+    recording_file.metadata.transcript = transcript.clone();
+    write_recording_file(&file_path, &recording_file)?;
+
+    tracing::info!("[Recording] Transcribed recording file: {} ({} segments)", file_id, transcript.len());
+
+    Ok(transcript)
+}
+
+/// 搜索录制：匹配会话名称、描述、标签、章节标题和语音转写文本
+///
+/// 返回有命中的录制（按开始时间倒序），章节和转写的命中带有时间，可直接跳转回放
+#[tauri::command]
+pub async fn recording_search(
+    app: AppHandle,
+    query: String,
+) -> std::result::Result<Vec<RecordingSearchResult>, String> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut results: Vec<RecordingSearchResult> = recording_list(app)
+        .await?
+        .into_iter()
+        .filter_map(|item| {
+            let matches = search_metadata(&item.metadata, query);
+            (!matches.is_empty()).then_some(RecordingSearchResult {
+                id: item.id,
+                session_name: item.metadata.session_name,
+                start_time: item.metadata.start_time,
+                matches,
+            })
+        })
+        .collect();
+    results.sort_by_key(|r| std::cmp::Reverse(r.start_time));
+
+    Ok(results)
+}
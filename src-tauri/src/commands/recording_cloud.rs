@@ -0,0 +1,191 @@
+//! 录制文件云端库
+//!
+//! 用户主动选择上传时才会把录制发送到同步服务器；每个录制可设置为私有、
+//! 不公开（知道 ID 的用户可下载）或共享给服务器上的所有用户
+
+use crate::commands::auth::ApiClientStateWrapper;
+use crate::commands::recording::{get_recordings_dir, load_recording_file_from_path};
+use crate::models::recording_cloud::{CloudRecording, ServerRecordingUpload, ServerRecordingVisibilityRequest};
+use std::fs;
+use std::path::Path;
+use tauri::{AppHandle, State};
+
+/// 录制文件的可见性
+const VISIBILITIES: [&str; 3] = ["private", "unlisted", "shared"];
+
+/// 将服务器返回的状态码转换为可读的错误
+fn describe_error(error: anyhow::Error) -> String {
+    let message = error.to_string();
+    if message.contains("API error (503") {
+        "服务器未启用录制上传".to_string()
+    } else if message.contains("API error (413") {
+        "录制文件过大或云端存储空间不足".to_string()
+    } else if message.contains("API error (404") {
+        "云端录制不存在或无权访问".to_string()
+    } else {
+        message
+    }
+}
+
+fn validate_visibility(visibility: &str) -> Result<(), String> {
+    if VISIBILITIES.contains(&visibility) {
+        Ok(())
+    } else {
+        Err(format!("Invalid visibility: {}", visibility))
+    }
+}
+
+/// 视频文件的 MIME 类型
+fn video_content_type(file_name: &str) -> &'static str {
+    match Path::new(file_name).extension().and_then(|e| e.to_str()) {
+        Some("mp4") => "video/mp4",
+        _ => "video/webm",
+    }
+}
+
+/// 上传本地录制到云端库
+///
+/// # 参数
+/// - `file_id`: 本地录制 ID（`recording_list` 返回的 id）
+/// - `visibility`: private、unlisted 或 shared，为空时为 private
+/// - `include_video`: 同时上传关联的视频文件
+///
+/// 返回上传后的云端录制（录制文件在前，视频在后）
+#[tauri::command]
+pub async fn recording_upload(
+    app: AppHandle,
+    api_client_state: State<'_, ApiClientStateWrapper>,
+    file_id: String,
+    visibility: Option<String>,
+    include_video: Option<bool>,
+) -> Result<Vec<CloudRecording>, String> {
+    let visibility = visibility.unwrap_or_else(|| "private".to_string());
+    validate_visibility(&visibility)?;
+
+    let recordings_dir = get_recordings_dir(&app).map_err(|e| e.to_string())?;
+    let json_name = format!("{}.json", file_id);
+    let json_path = recordings_dir.join(&json_name);
+    if !json_path.exists() {
+        return Err(format!("Recording file not found: {}", file_id));
+    }
+    // 保存时已屏蔽敏感信息，直接上传磁盘上的内容
+    let recording = load_recording_file_from_path(&json_path).map_err(|e| e.to_string())?;
+    let metadata = &recording.metadata;
+
+    let client = api_client_state.wait_client().await.map_err(|e| e.to_string())?;
+    let mut files = vec![(json_name, "recording", None)];
+    if include_video.unwrap_or(false) {
+        if let Some(video_file) = &metadata.video_file {
+            files.push((video_file.clone(), "video", Some(video_content_type(video_file).to_string())));
+        }
+    }
+
+    let mut uploaded = Vec::new();
+    for (file_name, kind, content_type) in files {
+        let data = fs::read(recordings_dir.join(&file_name))
+            .map_err(|e| format!("Failed to read {}: {}", file_name, e))?;
+        let upload = ServerRecordingUpload {
+            title: metadata.session_name.clone(),
+            file_name,
+            kind: kind.to_string(),
+            visibility: visibility.clone(),
+            session_name: Some(metadata.session_name.clone()),
+            recording_id: Some(file_id.clone()),
+            duration: metadata.duration,
+            content_type,
+        };
+        let (result, _, _) = client
+            .upload_recording(&upload, data.into())
+            .await
+            .map_err(describe_error)?;
+        tracing::info!("[Recording] Uploaded {} as {} ({} bytes)", upload.file_name, result.id, result.size);
+        uploaded.push(result.into());
+    }
+
+    Ok(uploaded)
+}
+
+/// 列出云端录制
+///
+/// # 参数
+/// - `scope`: mine（默认，自己上传的）或 shared（共享给所有用户的）
+#[tauri::command]
+pub async fn recording_cloud_list(
+    api_client_state: State<'_, ApiClientStateWrapper>,
+    scope: Option<String>,
+) -> Result<Vec<CloudRecording>, String> {
+    let scope = scope.unwrap_or_else(|| "mine".to_string());
+    if !matches!(scope.as_str(), "mine" | "shared") {
+        return Err(format!("Invalid scope: {}", scope));
+    }
+
+    let client = api_client_state.wait_client().await.map_err(|e| e.to_string())?;
+    let (recordings, _, _) = client.list_recordings(&scope).await.map_err(describe_error)?;
+    Ok(recordings.into_iter().map(Into::into).collect())
+}
+
+/// 下载云端录制到本地录制目录，返回本地文件路径
+///
+/// 本地已有同名文件时在文件名后附加云端 ID，不覆盖本地录制
+#[tauri::command]
+pub async fn recording_cloud_download(
+    app: AppHandle,
+    api_client_state: State<'_, ApiClientStateWrapper>,
+    id: String,
+    file_name: String,
+) -> Result<String, String> {
+    let file_name = Path::new(&file_name)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .filter(|name| !name.starts_with('.'))
+        .ok_or_else(|| format!("Invalid file name: {}", file_name))?
+        .to_string();
+
+    let client = api_client_state.wait_client().await.map_err(|e| e.to_string())?;
+    let data = client.download_recording(&id).await.map_err(describe_error)?;
+
+    let recordings_dir = get_recordings_dir(&app).map_err(|e| e.to_string())?;
+    let mut path = recordings_dir.join(&file_name);
+    if path.exists() {
+        let name = Path::new(&file_name);
+        let stem = name.file_stem().and_then(|s| s.to_str()).unwrap_or("recording");
+        let short_id: String = id.chars().take(8).collect();
+        path = recordings_dir.join(match name.extension().and_then(|e| e.to_str()) {
+            Some(ext) => format!("{}_{}.{}", stem, short_id, ext),
+            None => format!("{}_{}", stem, short_id),
+        });
+    }
+
+    fs::write(&path, &data).map_err(|e| format!("Failed to write recording file: {}", e))?;
+    tracing::info!("[Recording] Downloaded {} to {} ({} bytes)", id, path.display(), data.len());
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// 设置云端录制的可见性（仅上传者）
+#[tauri::command]
+pub async fn recording_cloud_set_visibility(
+    api_client_state: State<'_, ApiClientStateWrapper>,
+    id: String,
+    visibility: String,
+) -> Result<CloudRecording, String> {
+    validate_visibility(&visibility)?;
+    let client = api_client_state.wait_client().await.map_err(|e| e.to_string())?;
+    let request = ServerRecordingVisibilityRequest { visibility };
+    let (recording, _, _) = client
+        .update_recording_visibility(&id, &request)
+        .await
+        .map_err(describe_error)?;
+    Ok(recording.into())
+}
+
+/// 删除云端录制（仅上传者，不影响本地文件）
+#[tauri::command]
+pub async fn recording_cloud_delete(
+    api_client_state: State<'_, ApiClientStateWrapper>,
+    id: String,
+) -> Result<(), String> {
+    let client = api_client_state.wait_client().await.map_err(|e| e.to_string())?;
+    client.delete_recording(&id).await.map_err(describe_error)?;
+    tracing::info!("[Recording] Deleted cloud recording {}", id);
+    Ok(())
+}
@@ -1,7 +1,12 @@
 use crate::error::Result;
 use crate::ssh::manager::SSHManager;
-use crate::ssh::session::{SessionConfig, SessionConfigUpdate, AuthMethod};
-use crate::database::repositories::{SshSessionRepository, UserAuthRepository};
+use crate::ssh::session::{SessionConfig, SessionConfigUpdate, AuthMethod, BackendType};
+use crate::ssh::quick_connect::{parse_target, QuickConnectTarget};
+use crate::ssh::dns::{self, DnsResolverConfig};
+use crate::ssh::profiles::{find_profile, ConnectionProfile};
+use crate::ssh::serial::{self, SerialConfig, SerialPortInfo};
+use crate::ssh::wsl::{self, WslDistribution};
+use crate::database::repositories::{IdentityRepository, SshSessionRepository, UserAuthRepository};
 use crate::services::CryptoService;
 use crate::models::ssh_session::AuthMethod as DbAuthMethod;
 use std::sync::Arc;
@@ -11,7 +16,7 @@ use tauri::State;
 pub type SSHManagerState = Arc<SSHManager>;
 
 /// 当前用户信息（用于加解密）
-fn get_current_user_info(pool: &crate::database::DbPool) -> std::result::Result<(String, String), String> {
+pub(crate) fn get_current_user_info(pool: &crate::database::DbPool) -> std::result::Result<(String, String), String> {
     const ANONYMOUS_USER_ID: &str = "anonymous_local";
     const ANONYMOUS_DEVICE_ID: &str = "ssh-terminal-local-device-v1";
 
@@ -44,10 +49,22 @@ fn convert_db_auth_method(db_auth: &DbAuthMethod) -> AuthMethod {
     }
 }
 
+/// 跳板机链路的最大深度（防止循环引用）
+const MAX_JUMP_DEPTH: usize = 8;
+
 /// 从数据库加载会话配置到内存
-async fn load_session_from_db(
+pub(crate) async fn load_session_from_db(
     pool: &crate::database::DbPool,
     session_id: &str,
+) -> std::result::Result<Option<SessionConfig>, String> {
+    load_session_config(pool, session_id, 0)
+}
+
+/// 加载会话配置，并递归加载其跳板机会话
+fn load_session_config(
+    pool: &crate::database::DbPool,
+    session_id: &str,
+    depth: usize,
 ) -> std::result::Result<Option<SessionConfig>, String> {
     let repo = SshSessionRepository::new(pool.clone());
 
@@ -69,6 +86,57 @@ async fn load_session_from_db(
     let db_auth_method: DbAuthMethod = serde_json::from_str(&auth_method_json)
         .map_err(|e| format!("Failed to parse auth method: {}", e))?;
 
+    // 会话级 DNS 解析器（解析失败时忽略，使用全局设置）
+    let dns_resolver = repo
+        .find_dns_resolver(session_id)
+        .ok()
+        .flatten()
+        .and_then(|json| serde_json::from_str(&json).ok());
+
+    let remote_command = repo.find_remote_command(session_id).ok().flatten();
+    let remote_forwards = repo
+        .find_remote_forwards(session_id)
+        .ok()
+        .flatten()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default();
+    let encoding = repo
+        .find_encoding(session_id)
+        .ok()
+        .flatten()
+        .and_then(|name| serde_json::from_value(serde_json::Value::String(name)).ok())
+        .unwrap_or_default();
+    let login_script = repo
+        .find_login_script(session_id)
+        .ok()
+        .flatten()
+        .and_then(|(encrypted, nonce)| CryptoService::decrypt_password(&encrypted, &nonce, &device_id).ok())
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default();
+    let protected = repo.find_protected(session_id).unwrap_or(false);
+    let elevation = repo
+        .find_elevation(session_id)
+        .ok()
+        .flatten()
+        .and_then(|json| serde_json::from_str(&json).ok());
+    let algorithms = repo
+        .find_algorithms(session_id)
+        .ok()
+        .flatten()
+        .and_then(|json| serde_json::from_str(&json).ok());
+
+    // 跳板机会话已删除或链路过深时直连
+    let jump_host = match repo.find_jump_session_id(session_id).ok().flatten() {
+        Some(jump_id) if depth < MAX_JUMP_DEPTH => {
+            load_session_config(pool, &jump_id, depth + 1)?.map(Box::new)
+        }
+        Some(jump_id) => {
+            tracing::warn!("Jump host chain too deep at session {}, ignoring {}", session_id, jump_id);
+            None
+        }
+        None => None,
+    };
+
     Ok(Some(SessionConfig {
         name: session.name,
         host: session.host,
@@ -81,6 +149,22 @@ async fn load_session_from_db(
         rows: session.rows,
         strict_host_key_checking: true,
         keep_alive_interval: 30,
+        dns_resolver,
+        jump_host,
+        remote_command,
+        encoding,
+        remote_forwards,
+        login_script,
+        protected,
+        elevation,
+        algorithms,
+        startup_commands: Vec::new(),
+        backend_type: Default::default(),
+        serial: None,
+        wsl_distribution: None,
+        roaming: false,
+        wake_on_lan: None,
+        pre_connect: Vec::new(),
     }))
 }
 
@@ -113,40 +197,344 @@ pub async fn session_create_temp(
     manager.create_temporary_connection(config).await
 }
 
+/// 解析快速连接输入（用于命令面板实时校验）
+#[tauri::command]
+pub async fn session_quick_connect_parse(input: String) -> std::result::Result<QuickConnectTarget, String> {
+    parse_target(&input)
+}
+
+/// 快速连接任意目标
+///
+/// 输入可以是 `user@host:port`、IPv6 字面量或已保存会话的名称（如从 ssh config 导入的 Host 别名）。
+/// 匹配到已保存会话时使用其配置，输入中的用户名和端口会覆盖会话配置；
+/// 未指定认证方式时使用 ~/.ssh 下的默认私钥。返回新建的临时连接 ID
+#[tauri::command]
+pub async fn session_quick_connect(
+    manager: State<'_, SSHManagerState>,
+    pool: State<'_, crate::database::DbPool>,
+    input: String,
+    auth_method: Option<AuthMethod>,
+) -> Result<String> {
+    let target = parse_target(&input)
+        .map_err(|e| crate::error::SSHError::ConnectionFailed(format!("无效的连接目标: {}", e)))?;
+    let config = quick_connect_config(&pool, input.trim(), target, auth_method).await?;
+
+    manager.create_temporary_connection(config).await
+}
+
+/// 快速连接目标的会话配置
+///
+/// 主机匹配已保存会话的名称时使用其配置（目标中的用户名和端口覆盖会话配置），
+/// 否则新建配置，未指定认证方式时使用 ~/.ssh 下的默认私钥
+pub(crate) async fn quick_connect_config(
+    pool: &crate::database::DbPool,
+    name: &str,
+    target: QuickConnectTarget,
+    auth_method: Option<AuthMethod>,
+) -> Result<SessionConfig> {
+    let alias = find_session_by_alias(pool, &target.host)
+        .await
+        .map_err(crate::error::SSHError::Storage)?;
+
+    let config = match alias {
+        Some(mut config) => {
+            if let Some(username) = target.username {
+                config.username = username;
+            }
+            if let Some(port) = target.port {
+                config.port = port;
+            }
+            if let Some(auth_method) = auth_method {
+                config.auth_method = auth_method;
+            }
+            config
+        }
+        None => {
+            let username = target.username.clone()
+                .or_else(|| std::env::var("USER").ok())
+                .or_else(|| std::env::var("USERNAME").ok())
+                .ok_or_else(|| crate::error::SSHError::ConnectionFailed("请指定用户名，例如 user@host".to_string()))?;
+            let auth_method = match auth_method {
+                Some(auth_method) => auth_method,
+                None => default_identity_auth().ok_or_else(|| {
+                    crate::error::SSHError::AuthenticationFailed("未指定认证方式，且未找到默认私钥".to_string())
+                })?,
+            };
+
+            SessionConfig {
+                name: name.to_string(),
+                host: target.host.clone(),
+                port: target.port_or_default(),
+                username,
+                auth_method,
+                terminal_type: None,
+                columns: None,
+                rows: None,
+                strict_host_key_checking: true,
+                group: "默认分组".to_string(),
+                keep_alive_interval: 30,
+                dns_resolver: None,
+                jump_host: None,
+                remote_command: None,
+                encoding: Default::default(),
+                remote_forwards: Vec::new(),
+                login_script: Vec::new(),
+                protected: false,
+                elevation: None,
+                algorithms: None,
+                startup_commands: Vec::new(),
+                backend_type: Default::default(),
+                serial: None,
+                wsl_distribution: None,
+                roaming: false,
+                wake_on_lan: None,
+                pre_connect: Vec::new(),
+            }
+        }
+    };
+
+    Ok(config)
+}
+
+/// 打开本机终端
+///
+/// 创建使用本地后端的临时连接，`command` 为空时启动用户的默认 shell。返回新建的临时连接 ID
+#[tauri::command]
+pub async fn session_open_local(
+    manager: State<'_, SSHManagerState>,
+    command: Option<String>,
+) -> Result<String> {
+    manager.create_temporary_connection(local_session_config(command)).await
+}
+
+/// 本地后端的会话配置，`command` 为空时启动用户的默认 shell
+pub(crate) fn local_session_config(command: Option<String>) -> SessionConfig {
+    let username = std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_default();
+
+    SessionConfig {
+        name: command.clone().unwrap_or_else(|| "Local".to_string()),
+        host: "localhost".to_string(),
+        port: 0,
+        username,
+        auth_method: AuthMethod::Password { password: String::new() },
+        terminal_type: None,
+        columns: None,
+        rows: None,
+        strict_host_key_checking: false,
+        group: "默认分组".to_string(),
+        keep_alive_interval: 0,
+        dns_resolver: None,
+        jump_host: None,
+        remote_command: command,
+        encoding: Default::default(),
+        remote_forwards: Vec::new(),
+        login_script: Vec::new(),
+        protected: false,
+        elevation: None,
+        algorithms: None,
+        startup_commands: Vec::new(),
+        backend_type: BackendType::Local,
+        serial: None,
+        wsl_distribution: None,
+        roaming: false,
+        wake_on_lan: None,
+        pre_connect: Vec::new(),
+    }
+}
+
+/// 列出本机串口
+#[tauri::command]
+pub async fn serial_list_ports() -> std::result::Result<Vec<SerialPortInfo>, String> {
+    serial::list_ports()
+}
+
+/// 打开串口终端（网络设备 Console）
+///
+/// # 参数
+/// - `path`: 串口设备路径（如 `COM3`、`/dev/ttyUSB0`）
+/// - `settings`: 线路参数，为空时使用 9600 8N1
+///
+/// # 返回
+/// 连接 ID，之后与 SSH 连接一样通过 `session_connect` 建立连接
+#[tauri::command]
+pub async fn session_open_serial(
+    manager: State<'_, SSHManagerState>,
+    path: String,
+    settings: Option<SerialConfig>,
+) -> Result<String> {
+    let settings = settings.unwrap_or_default();
+    settings.validate().map_err(crate::error::SSHError::ConnectionFailed)?;
+
+    let config = SessionConfig {
+        name: format!("{} ({})", path, settings.baud_rate),
+        host: path,
+        port: 0,
+        username: String::new(),
+        auth_method: AuthMethod::Password { password: String::new() },
+        terminal_type: None,
+        columns: None,
+        rows: None,
+        strict_host_key_checking: false,
+        group: "默认分组".to_string(),
+        keep_alive_interval: 0,
+        dns_resolver: None,
+        jump_host: None,
+        remote_command: None,
+        encoding: Default::default(),
+        remote_forwards: Vec::new(),
+        login_script: Vec::new(),
+        protected: false,
+        elevation: None,
+        algorithms: None,
+        startup_commands: Vec::new(),
+        backend_type: BackendType::Serial,
+        serial: Some(settings),
+        wsl_distribution: None,
+        roaming: false,
+        wake_on_lan: None,
+        pre_connect: Vec::new(),
+    };
+
+    manager.create_temporary_connection(config).await
+}
+
+/// 列出已安装的 WSL 发行版（非 Windows 平台返回空列表）
+#[tauri::command]
+pub async fn wsl_list_distributions() -> std::result::Result<Vec<WslDistribution>, String> {
+    wsl::list_distributions().await
+}
+
+/// 打开 WSL 终端（仅 Windows）
+///
+/// # 参数
+/// - `distribution`: 发行版名称，为空时使用默认发行版
+///
+/// # 返回
+/// 连接 ID，之后通过 `session_connect` 启动 `wsl.exe`
+#[tauri::command]
+pub async fn session_open_wsl(
+    manager: State<'_, SSHManagerState>,
+    distribution: Option<String>,
+) -> Result<String> {
+    let distribution = distribution.map(|d| d.trim().to_string()).filter(|d| !d.is_empty());
+    if let Some(distribution) = distribution.as_deref() {
+        wsl::validate_distribution(distribution).map_err(crate::error::SSHError::ConnectionFailed)?;
+    }
+
+    let mut config = local_session_config(None);
+    config.name = distribution.clone().unwrap_or_else(|| "WSL".to_string());
+    config.backend_type = BackendType::Wsl;
+    config.wsl_distribution = distribution;
+
+    manager.create_temporary_connection(config).await
+}
+
+/// 打开 Telnet 终端（不支持 SSH 的旧设备）
+///
+/// # 参数
+/// - `host`: 主机地址
+/// - `port`: 端口，默认 23
+///
+/// # 返回
+/// 连接 ID，登录在终端中交互完成
+#[tauri::command]
+pub async fn session_open_telnet(
+    manager: State<'_, SSHManagerState>,
+    host: String,
+    port: Option<u16>,
+) -> Result<String> {
+    let host = host.trim().to_string();
+    if host.is_empty() {
+        return Err(crate::error::SSHError::ConnectionFailed("Host cannot be empty".to_string()));
+    }
+    let port = port.unwrap_or(23);
+
+    let config = SessionConfig {
+        name: format!("telnet://{}:{}", host, port),
+        host,
+        port,
+        username: String::new(),
+        auth_method: AuthMethod::Password { password: String::new() },
+        terminal_type: None,
+        columns: None,
+        rows: None,
+        strict_host_key_checking: false,
+        group: "默认分组".to_string(),
+        keep_alive_interval: 0,
+        dns_resolver: None,
+        jump_host: None,
+        remote_command: None,
+        encoding: Default::default(),
+        remote_forwards: Vec::new(),
+        login_script: Vec::new(),
+        protected: false,
+        elevation: None,
+        algorithms: None,
+        startup_commands: Vec::new(),
+        backend_type: BackendType::Telnet,
+        serial: None,
+        wsl_distribution: None,
+        roaming: false,
+        wake_on_lan: None,
+        pre_connect: Vec::new(),
+    };
+
+    manager.create_temporary_connection(config).await
+}
+
+/// 按名称（不区分大小写）查找已保存的会话，作为主机别名
+async fn find_session_by_alias(
+    pool: &crate::database::DbPool,
+    alias: &str,
+) -> std::result::Result<Option<SessionConfig>, String> {
+    let (user_id, _) = get_current_user_info(pool)?;
+    let repo = SshSessionRepository::new(pool.clone());
+    let sessions = repo.find_by_user(&user_id)
+        .map_err(|e| format!("Failed to list sessions: {}", e))?;
+
+    match sessions.iter().find(|s| s.name.eq_ignore_ascii_case(alias)) {
+        Some(session) => load_session_from_db(pool, &session.id).await,
+        None => Ok(None),
+    }
+}
+
+/// ~/.ssh 下的默认私钥（按 OpenSSH 的查找顺序）
+pub(crate) fn default_identity_auth() -> Option<AuthMethod> {
+    let ssh_dir = dirs::home_dir()?.join(".ssh");
+    ["id_ed25519", "id_ecdsa", "id_rsa"]
+        .iter()
+        .map(|name| ssh_dir.join(name))
+        .find(|path| path.is_file())
+        .map(|path| AuthMethod::PublicKey {
+            private_key_path: path.to_string_lossy().to_string(),
+            passphrase: None,
+        })
+}
+
 /// 连接会话
 #[tauri::command]
 pub async fn session_connect(
     manager: State<'_, SSHManagerState>,
     pool: State<'_, crate::database::DbPool>,
     session_id: String,
+    profile: Option<String>,
 ) -> Result<String> {
     // 检查是否是已存在的连接实例
     let is_connection = manager.get_connection(&session_id).await.is_ok();
 
     if !is_connection {
-        // 不是连接实例，检查会话配置是否在内存中
-        let session_exists = manager.get_session_config(&session_id).await.is_ok();
-
-        if !session_exists {
-            // 不在内存中，尝试从数据库加载
-            println!("[session_connect] ⚠️  Session config not in memory, loading from database: {}", session_id);
-
-            match load_session_from_db(&pool, &session_id).await {
-                Ok(Some(config)) => {
-                    println!("[session_connect] ✅ Loaded from database: {}", config.name);
-                    // 创建内存会话配置
-                    manager.create_session_with_id(Some(session_id.clone()), config).await?;
-                }
-                Ok(None) => {
-                    // 会话在数据库中也不存在
-                    return Err(crate::error::SSHError::SessionNotFound(session_id));
-                }
-                Err(e) => {
-                    return Err(crate::error::SSHError::Storage(format!("Failed to load session from database: {}", e)));
-                }
-            }
-        } else {
-            println!("[session_connect] ✅ Session config found in memory");
+        ensure_session_loaded(&manager, &pool, &session_id).await?;
+
+        // 使用指定的连接 profile 创建新的连接实例
+        if let Some(profile) = profile.filter(|p| !p.is_empty()) {
+            let mut config = manager.get_session_config(&session_id).await?;
+            apply_profile(&pool, &session_id, &profile, &mut config)
+                .map_err(crate::error::SSHError::Storage)?;
+            let connection_id = manager.create_connection_with_config(&session_id, config).await?;
+            manager.connect_connection(&connection_id).await?;
+            return Ok(connection_id);
         }
     }
 
@@ -154,15 +542,137 @@ pub async fn session_connect(
     Ok(connection_id)
 }
 
+/// 将会话的连接 profile 应用到会话配置（profile 引用的身份在此解密）
+fn apply_profile(
+    pool: &crate::database::DbPool,
+    session_id: &str,
+    key: &str,
+    config: &mut SessionConfig,
+) -> std::result::Result<(), String> {
+    let repo = SshSessionRepository::new(pool.clone());
+    let profiles: Vec<ConnectionProfile> = repo
+        .find_profiles(session_id)
+        .map_err(|e| format!("Failed to get profiles: {}", e))?
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default();
+    let profile = find_profile(&profiles, key)
+        .ok_or_else(|| format!("Profile not found: {}", key))?;
+
+    let identity = match &profile.identity_id {
+        Some(identity_id) => Some(load_identity_credentials(pool, identity_id)?),
+        None => None,
+    };
+
+    profile.apply(config, identity);
+    Ok(())
+}
+
+/// 解密当前用户的共享身份，返回 (用户名, 认证方式)
+pub(crate) fn load_identity_credentials(
+    pool: &crate::database::DbPool,
+    identity_id: &str,
+) -> std::result::Result<(String, AuthMethod), String> {
+    let (user_id, device_id) = get_current_user_info(pool)?;
+    let identity = IdentityRepository::new(pool.clone())
+        .find_by_id(identity_id)
+        .map_err(|e| format!("Failed to find identity: {}", e))?
+        .filter(|identity| identity.user_id == user_id)
+        .ok_or_else(|| format!("Identity not found: {}", identity_id))?;
+    let auth_method_json = CryptoService::decrypt_password(
+        &identity.auth_method_encrypted,
+        &identity.auth_nonce,
+        &device_id,
+    )
+        .map_err(|e| format!("Failed to decrypt auth method: {}", e))?;
+    let db_auth_method: DbAuthMethod = serde_json::from_str(&auth_method_json)
+        .map_err(|e| format!("Failed to parse auth method: {}", e))?;
+    Ok((identity.username, convert_db_auth_method(&db_auth_method)))
+}
+
+/// 确保会话配置已在内存中，不在时从数据库加载
+pub(crate) async fn ensure_session_loaded(
+    manager: &SSHManager,
+    pool: &crate::database::DbPool,
+    session_id: &str,
+) -> Result<()> {
+    if manager.get_session_config(session_id).await.is_ok() {
+        tracing::info!("[session_connect] ✅ Session config found in memory");
+        return Ok(());
+    }
+
+    // 不在内存中，尝试从数据库加载
+    tracing::info!("[session_connect] ⚠️  Session config not in memory, loading from database: {}", session_id);
+
+    match load_session_from_db(pool, session_id).await {
+        Ok(Some(config)) => {
+            tracing::info!("[session_connect] ✅ Loaded from database: {}", config.name);
+            // 创建内存会话配置
+            manager.create_session_with_id(Some(session_id.to_string()), config).await?;
+            Ok(())
+        }
+        // 会话在数据库中也不存在
+        Ok(None) => Err(crate::error::SSHError::SessionNotFound(session_id.to_string())),
+        Err(e) => Err(crate::error::SSHError::Storage(format!("Failed to load session from database: {}", e))),
+    }
+}
+
 /// 断开会话
 #[tauri::command]
 pub async fn session_disconnect(
     manager: State<'_, SSHManagerState>,
+    forwards: State<'_, crate::commands::forward::ForwardManagerState>,
+    sftp: State<'_, crate::commands::sftp::SftpManagerState>,
     session_id: String,
 ) -> Result<()> {
+    // 关闭该连接上的端口转发，释放连接池中的 SFTP channel
+    forwards.delete_for_connection(&session_id).await;
+    sftp.purge_connection_pool(&session_id).await;
     manager.disconnect_session(&session_id).await
 }
 
+/// 延长高权限会话的时限，返回新的剩余秒数
+#[tauri::command]
+pub async fn session_elevation_extend(
+    manager: State<'_, SSHManagerState>,
+    connection_id: String,
+    minutes: u32,
+) -> Result<u64> {
+    manager.extend_elevation(&connection_id, minutes.max(1)).await
+}
+
+/// 获取连接当前的健康状态（延迟、丢包率、最后活动时间），尚未探测时返回 None
+///
+/// 之后的变化通过 `ssh-health-{connectionId}` 事件推送
+#[tauri::command]
+pub async fn session_health(
+    manager: State<'_, SSHManagerState>,
+    connection_id: String,
+) -> Result<Option<crate::ssh::health::HealthSnapshot>> {
+    manager.connection_health(&connection_id).await
+}
+
+/// 连接前检查：DNS 解析、TCP 可达性、私钥文件和主机密钥状态，返回检查清单
+///
+/// 只完成密钥交换，不进行认证，也不打开会话
+#[tauri::command]
+pub async fn session_preflight(
+    manager: State<'_, SSHManagerState>,
+    session_id: String,
+) -> Result<crate::ssh::preflight::PreflightReport> {
+    manager.preflight(&session_id).await
+}
+
+/// 获取连接当前的流量统计（累计字节数、吞吐量、连接时长）
+///
+/// 之后的变化通过 `ssh-stats-{connectionId}` 事件推送
+#[tauri::command]
+pub async fn connection_stats(
+    manager: State<'_, SSHManagerState>,
+    connection_id: String,
+) -> Result<crate::ssh::stats::StatsSnapshot> {
+    manager.connection_stats(&connection_id).await
+}
+
 /// 列出所有会话
 #[tauri::command]
 pub async fn session_list(
@@ -171,6 +681,17 @@ pub async fn session_list(
     Ok(manager.list_sessions().await)
 }
 
+/// 增量列出会话
+///
+/// `since` 为上次返回的 revision；为空或变更已过期时返回全量列表
+#[tauri::command]
+pub async fn session_list_since(
+    manager: State<'_, SSHManagerState>,
+    since: Option<u64>,
+) -> Result<crate::ssh::events::SessionListDelta> {
+    Ok(manager.list_sessions_since(since).await)
+}
+
 /// 获取单个会话
 #[tauri::command]
 pub async fn session_get(
@@ -199,3 +720,11 @@ pub async fn session_update(
 ) -> Result<()> {
     manager.update_session(&session_id, updates).await
 }
+
+/// 使用指定的 DNS 解析器解析主机，返回解析到的地址（用于测试解析器配置）
+#[tauri::command]
+pub async fn dns_resolve_test(host: String, resolver: DnsResolverConfig) -> Result<Vec<String>> {
+    resolver.validate().map_err(crate::error::SSHError::ConnectionFailed)?;
+    let addrs = dns::resolve(&resolver, host.trim(), 22).await?;
+    Ok(addrs.iter().map(|addr| addr.ip().to_string()).collect())
+}
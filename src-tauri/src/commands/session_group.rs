@@ -0,0 +1,173 @@
+//! 会话分组命令
+//!
+//! 分组保存颜色、图标、排序和描述，随会话一起同步到服务器；会话仍通过 group_name 关联分组
+
+use crate::commands::ssh_session::get_current_user_info;
+use crate::database::DbPool;
+use crate::database::repositories::SessionGroupRepository;
+use crate::models::session_group::{SessionGroup, DEFAULT_GROUP_NAME};
+use tauri::State;
+
+/// 列出当前用户的所有分组（首次调用时从现有会话的分组名迁移）
+#[tauri::command]
+pub async fn session_group_list(
+    pool: State<'_, DbPool>,
+) -> Result<Vec<SessionGroup>, String> {
+    let current_user = get_current_user_info(&pool);
+    let repo = SessionGroupRepository::new(pool.inner().clone());
+
+    repo.import_from_sessions(&current_user.user_id)
+        .map_err(|e| format!("Failed to import session groups: {}", e))?;
+    repo.find_by_user(&current_user.user_id)
+        .map_err(|e| format!("Failed to list session groups: {}", e))
+}
+
+/// 创建或更新分组
+///
+/// 重命名时分组下的会话一起改名；默认分组不能重命名
+#[tauri::command]
+pub async fn session_group_save(
+    pool: State<'_, DbPool>,
+    group_id: Option<String>,
+    name: String,
+    color: Option<String>,
+    icon: Option<String>,
+    description: Option<String>,
+) -> Result<SessionGroup, String> {
+    let current_user = get_current_user_info(&pool);
+    let repo = SessionGroupRepository::new(pool.inner().clone());
+    let now = chrono::Utc::now().timestamp();
+
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Group name cannot be empty".to_string());
+    }
+
+    let same_name = repo.find_by_name(&current_user.user_id, &name)
+        .map_err(|e| format!("Failed to find session group: {}", e))?
+        .filter(|group| !group.is_deleted);
+    if let Some(existing) = &same_name {
+        if group_id.as_deref() != Some(existing.id.as_str()) {
+            return Err(format!("Group already exists: {}", name));
+        }
+    }
+
+    let group = match group_id {
+        Some(group_id) => {
+            let mut group = repo.find_by_id(&group_id)
+                .map_err(|e| format!("Failed to find session group: {}", e))?
+                .filter(|group| !group.is_deleted)
+                .ok_or_else(|| format!("Session group not found: {}", group_id))?;
+
+            if group.user_id != current_user.user_id {
+                return Err("Session group belongs to different user".to_string());
+            }
+
+            if group.name != name {
+                if group.name == DEFAULT_GROUP_NAME {
+                    return Err("The default group cannot be renamed".to_string());
+                }
+                repo.reassign_sessions(&current_user.user_id, &group.name, &name)
+                    .map_err(|e| format!("Failed to rename sessions' group: {}", e))?;
+                group.name = name;
+            }
+            group.color = color;
+            group.icon = icon;
+            group.description = description;
+            group.client_ver += 1;
+            group.is_dirty = true;
+            group.updated_at = now;
+            group
+        }
+        None => {
+            let existing = repo.find_by_user(&current_user.user_id)
+                .map_err(|e| format!("Failed to list session groups: {}", e))?;
+            // 本地删除后尚未同步的同名分组会被覆盖
+            let deleted = repo.find_by_name(&current_user.user_id, &name)
+                .map_err(|e| format!("Failed to find session group: {}", e))?;
+
+            SessionGroup {
+                id: deleted.as_ref().map_or_else(|| uuid::Uuid::new_v4().to_string(), |g| g.id.clone()),
+                user_id: current_user.user_id,
+                name,
+                color,
+                icon,
+                sort_order: existing.iter().map(|g| g.sort_order + 1).max().unwrap_or(0),
+                description,
+                server_ver: deleted.as_ref().map_or(0, |g| g.server_ver),
+                client_ver: deleted.as_ref().map_or(1, |g| g.client_ver + 1),
+                is_dirty: true,
+                is_deleted: false,
+                last_synced_at: deleted.as_ref().and_then(|g| g.last_synced_at),
+                created_at: now,
+                updated_at: now,
+            }
+        }
+    };
+
+    repo.save(&group)
+        .map_err(|e| format!("Failed to save session group: {}", e))?;
+
+    Ok(group)
+}
+
+/// 删除分组，分组下的会话移动到默认分组；默认分组不能删除
+#[tauri::command]
+pub async fn session_group_delete(
+    pool: State<'_, DbPool>,
+    group_id: String,
+) -> Result<usize, String> {
+    let current_user = get_current_user_info(&pool);
+    let repo = SessionGroupRepository::new(pool.inner().clone());
+
+    let group = repo.find_by_id(&group_id)
+        .map_err(|e| format!("Failed to find session group: {}", e))?
+        .filter(|group| !group.is_deleted && group.user_id == current_user.user_id)
+        .ok_or_else(|| format!("Session group not found: {}", group_id))?;
+
+    if group.name == DEFAULT_GROUP_NAME {
+        return Err("The default group cannot be deleted".to_string());
+    }
+
+    let moved = repo.reassign_sessions(&current_user.user_id, &group.name, DEFAULT_GROUP_NAME)
+        .map_err(|e| format!("Failed to move sessions to default group: {}", e))?;
+    repo.delete(&group)
+        .map_err(|e| format!("Failed to delete session group: {}", e))?;
+
+    tracing::info!("Deleted session group {} ({} session(s) moved to default group)", group.name, moved);
+    Ok(moved)
+}
+
+/// 按给定顺序重新排序分组
+#[tauri::command]
+pub async fn session_group_reorder(
+    pool: State<'_, DbPool>,
+    group_ids: Vec<String>,
+) -> Result<Vec<SessionGroup>, String> {
+    let current_user = get_current_user_info(&pool);
+    let repo = SessionGroupRepository::new(pool.inner().clone());
+    let now = chrono::Utc::now().timestamp();
+
+    let groups = repo.find_by_user(&current_user.user_id)
+        .map_err(|e| format!("Failed to list session groups: {}", e))?;
+
+    for mut group in groups {
+        // 未列出的分组排在最后，保持原有相对顺序
+        let sort_order = group_ids
+            .iter()
+            .position(|id| *id == group.id)
+            .map_or(group_ids.len() as i32 + group.sort_order, |index| index as i32);
+        if group.sort_order == sort_order {
+            continue;
+        }
+        group.sort_order = sort_order;
+        group.client_ver += 1;
+        group.is_dirty = true;
+        group.updated_at = now;
+        repo.save(&group)
+            .map_err(|e| format!("Failed to reorder session groups: {}", e))?;
+    }
+
+    repo.find_by_user(&current_user.user_id)
+        .map_err(|e| format!("Failed to list session groups: {}", e))
+}
@@ -0,0 +1,384 @@
+//! 会话笔记与附件命令
+//!
+//! 为保存的会话附加 Markdown 笔记和运维手册等小文件，附件内容保存在本地存储目录
+//!
+//! 笔记和附件默认只保存在本地，可以单独开启云同步
+
+use crate::config::Storage;
+use crate::database::DbPool;
+use crate::database::repositories::{SessionNoteRepository, UserAuthRepository};
+use crate::models::session_note::{SessionAttachment, SessionNote, MAX_SYNC_ATTACHMENT_SIZE};
+use std::path::{Path, PathBuf};
+use tauri::State;
+
+/// 未登录用户的固定用户ID
+const ANONYMOUS_USER_ID: &str = "anonymous_local";
+
+/// 单个附件的最大大小（10 MB）
+const MAX_ATTACHMENT_SIZE: u64 = 10 * 1024 * 1024;
+
+/// 获取当前用户 ID，未登录时返回匿名用户ID
+fn get_current_user_id(pool: &DbPool) -> String {
+    let auth_repo = UserAuthRepository::new(pool.clone());
+    match auth_repo.find_current() {
+        Ok(Some(user)) => user.user_id,
+        _ => ANONYMOUS_USER_ID.to_string(),
+    }
+}
+
+/// 附件存储根目录
+fn attachments_dir() -> Result<PathBuf, String> {
+    Storage::get_app_storage_dir()
+        .map(|dir| dir.join("attachments"))
+        .map_err(|e| e.to_string())
+}
+
+/// 校验会话 ID：只允许字母、数字、`-` 和 `_`
+///
+/// 会话 ID 会作为附件目录名使用，拒绝路径分隔符、`..` 和绝对路径，防止写出附件目录
+fn validate_session_id(session_id: &str) -> Result<(), String> {
+    let valid = !session_id.is_empty()
+        && session_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("无效的会话 ID: {}", session_id))
+    }
+}
+
+/// 附件 ID 与会话 ID 的字符规则相同（都会用作路径的一部分）
+fn validate_attachment_id(id: &str) -> Result<(), String> {
+    validate_session_id(id).map_err(|_| format!("无效的附件 ID: {}", id))
+}
+
+/// 写入附件内容，返回在存储目录中的相对路径
+pub(crate) async fn store_attachment_file(session_id: &str, id: &str, data: &[u8]) -> Result<String, String> {
+    validate_session_id(session_id)?;
+    validate_attachment_id(id)?;
+
+    let stored_path = format!("{}/{}", session_id, id);
+    let target = attachments_dir()?.join(&stored_path);
+    if let Some(parent) = target.parent() {
+        tokio::fs::create_dir_all(parent).await
+            .map_err(|e| format!("无法创建附件目录: {}", e))?;
+    }
+    tokio::fs::write(&target, data).await
+        .map_err(|e| format!("无法写入附件: {}", e))?;
+
+    Ok(stored_path)
+}
+
+/// 读取附件内容
+pub(crate) async fn read_attachment_file(attachment: &SessionAttachment) -> Result<Vec<u8>, String> {
+    let path = attachments_dir()?.join(&attachment.stored_path);
+    tokio::fs::read(&path).await
+        .map_err(|e| format!("无法读取附件: {}", e))
+}
+
+/// 删除附件文件（失败只记录警告）
+pub(crate) async fn remove_attachment_file(attachment: &SessionAttachment) {
+    let Ok(path) = attachments_dir().map(|dir| dir.join(&attachment.stored_path)) else {
+        return;
+    };
+    if let Err(e) = tokio::fs::remove_file(&path).await {
+        tracing::warn!("Failed to remove attachment file {}: {}", path.display(), e);
+    }
+}
+
+/// 根据扩展名推断 MIME 类型
+fn guess_mime_type(file_name: &str) -> Option<String> {
+    let ext = Path::new(file_name).extension()?.to_str()?.to_lowercase();
+    let mime = match ext.as_str() {
+        "md" | "markdown" => "text/markdown",
+        "txt" | "log" => "text/plain",
+        "json" => "application/json",
+        "yaml" | "yml" => "application/yaml",
+        "sh" => "application/x-sh",
+        "pdf" => "application/pdf",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "svg" => "image/svg+xml",
+        _ => return None,
+    };
+    Some(mime.to_string())
+}
+
+/// 删除会话的笔记和附件（会话被删除时调用，失败只记录警告）
+pub(crate) async fn delete_session_notes(pool: &DbPool, session_id: &str) {
+    if validate_session_id(session_id).is_err() {
+        return;
+    }
+
+    if let Err(e) = SessionNoteRepository::new(pool.clone()).delete_for_session(session_id) {
+        tracing::warn!("Failed to delete notes of session {}: {}", session_id, e);
+    }
+
+    let Ok(dir) = attachments_dir().map(|dir| dir.join(session_id)) else {
+        return;
+    };
+    if let Err(e) = tokio::fs::remove_dir_all(&dir).await {
+        if e.kind() != std::io::ErrorKind::NotFound {
+            tracing::warn!("Failed to remove attachments of session {}: {}", session_id, e);
+        }
+    }
+}
+
+/// 获取会话笔记
+#[tauri::command]
+pub async fn session_note_get(
+    pool: State<'_, DbPool>,
+    session_id: String,
+) -> Result<Option<SessionNote>, String> {
+    let repo = SessionNoteRepository::new(pool.inner().clone());
+    repo.find_note(&session_id)
+        .map_err(|e| format!("Failed to get note: {}", e))
+}
+
+/// 保存会话笔记（Markdown）
+#[tauri::command]
+pub async fn session_note_save(
+    pool: State<'_, DbPool>,
+    session_id: String,
+    content: String,
+) -> Result<SessionNote, String> {
+    validate_session_id(&session_id)?;
+    let repo = SessionNoteRepository::new(pool.inner().clone());
+    let now = chrono::Utc::now().timestamp();
+
+    let note = match repo.find_note(&session_id).map_err(|e| format!("Failed to get note: {}", e))? {
+        Some(mut note) => {
+            note.content = content;
+            note.updated_at = now;
+            note
+        }
+        None => SessionNote {
+            session_id: session_id.clone(),
+            user_id: get_current_user_id(&pool),
+            content,
+            sync_enabled: false,
+            server_ver: 0,
+            client_ver: 1,
+            is_dirty: true,
+            last_synced_at: None,
+            created_at: now,
+            updated_at: now,
+        },
+    };
+
+    repo.upsert_note(&note)
+        .map_err(|e| format!("Failed to save note: {}", e))?;
+
+    tracing::info!("Saved note for session: {}", session_id);
+    repo.find_note(&session_id)
+        .map_err(|e| format!("Failed to get note: {}", e))?
+        .ok_or_else(|| format!("Note not found: {}", session_id))
+}
+
+/// 开启或关闭笔记的云同步
+///
+/// 关闭后笔记仍保留在本地，下次同步时删除服务器上的副本
+#[tauri::command]
+pub async fn session_note_set_sync(
+    pool: State<'_, DbPool>,
+    session_id: String,
+    sync_enabled: bool,
+) -> Result<SessionNote, String> {
+    let user_id = get_current_user_id(&pool);
+    if sync_enabled && user_id == ANONYMOUS_USER_ID {
+        return Err("请先登录后再开启同步".to_string());
+    }
+
+    let repo = SessionNoteRepository::new(pool.inner().clone());
+    repo.set_note_sync(&session_id, &user_id, sync_enabled)
+        .map_err(|e| format!("Failed to update note: {}", e))?;
+
+    repo.find_note(&session_id)
+        .map_err(|e| format!("Failed to get note: {}", e))?
+        .ok_or_else(|| format!("Note not found: {}", session_id))
+}
+
+/// 删除会话笔记
+#[tauri::command]
+pub async fn session_note_delete(
+    pool: State<'_, DbPool>,
+    session_id: String,
+) -> Result<(), String> {
+    validate_session_id(&session_id)?;
+    let repo = SessionNoteRepository::new(pool.inner().clone());
+    repo.delete_note(&session_id)
+        .map_err(|e| format!("Failed to delete note: {}", e))
+}
+
+/// 添加附件（复制本地文件到存储目录）
+///
+/// # 参数
+/// - `session_id`: 会话 ID
+/// - `file_path`: 要附加的本地文件路径
+#[tauri::command]
+pub async fn session_attachment_add(
+    pool: State<'_, DbPool>,
+    session_id: String,
+    file_path: String,
+) -> Result<SessionAttachment, String> {
+    validate_session_id(&session_id)?;
+    let source = Path::new(&file_path);
+    let metadata = tokio::fs::metadata(source).await
+        .map_err(|e| format!("无法读取文件 '{}': {}", file_path, e))?;
+
+    if !metadata.is_file() {
+        return Err(format!("不是文件: {}", file_path));
+    }
+    if metadata.len() > MAX_ATTACHMENT_SIZE {
+        return Err(format!(
+            "附件过大 ({} 字节)，最大允许 {} 字节",
+            metadata.len(),
+            MAX_ATTACHMENT_SIZE
+        ));
+    }
+
+    let file_name = source
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| format!("无效的文件名: {}", file_path))?
+        .to_string();
+
+    let id = uuid::Uuid::new_v4().to_string();
+    let stored_path = format!("{}/{}", session_id, id);
+    let target = attachments_dir()?.join(&session_id).join(&id);
+
+    if let Some(parent) = target.parent() {
+        tokio::fs::create_dir_all(parent).await
+            .map_err(|e| format!("无法创建附件目录: {}", e))?;
+    }
+    tokio::fs::copy(source, &target).await
+        .map_err(|e| format!("无法复制附件: {}", e))?;
+
+    let now = chrono::Utc::now().timestamp();
+    let attachment = SessionAttachment {
+        id,
+        session_id,
+        user_id: get_current_user_id(&pool),
+        mime_type: guess_mime_type(&file_name),
+        file_name,
+        size: metadata.len() as i64,
+        stored_path,
+        sync_enabled: false,
+        server_ver: 0,
+        client_ver: 1,
+        is_dirty: true,
+        last_synced_at: None,
+        created_at: now,
+        updated_at: now,
+    };
+
+    let repo = SessionNoteRepository::new(pool.inner().clone());
+    if let Err(e) = repo.create_attachment(&attachment) {
+        let _ = tokio::fs::remove_file(&target).await;
+        return Err(format!("Failed to save attachment: {}", e));
+    }
+
+    tracing::info!("Added attachment {} to session {}", attachment.file_name, attachment.session_id);
+    Ok(attachment)
+}
+
+/// 列出会话的所有附件
+#[tauri::command]
+pub async fn session_attachment_list(
+    pool: State<'_, DbPool>,
+    session_id: String,
+) -> Result<Vec<SessionAttachment>, String> {
+    let repo = SessionNoteRepository::new(pool.inner().clone());
+    repo.find_attachments_by_session(&session_id)
+        .map_err(|e| format!("Failed to list attachments: {}", e))
+}
+
+/// 读取附件内容
+#[tauri::command]
+pub async fn session_attachment_read(
+    pool: State<'_, DbPool>,
+    attachment_id: String,
+) -> Result<Vec<u8>, String> {
+    let repo = SessionNoteRepository::new(pool.inner().clone());
+    let attachment = repo.find_attachment(&attachment_id)
+        .map_err(|e| format!("Failed to get attachment: {}", e))?
+        .ok_or_else(|| format!("Attachment not found: {}", attachment_id))?;
+
+    read_attachment_file(&attachment).await
+}
+
+/// 导出附件到指定路径
+#[tauri::command]
+pub async fn session_attachment_export(
+    pool: State<'_, DbPool>,
+    attachment_id: String,
+    target_path: String,
+) -> Result<(), String> {
+    let repo = SessionNoteRepository::new(pool.inner().clone());
+    let attachment = repo.find_attachment(&attachment_id)
+        .map_err(|e| format!("Failed to get attachment: {}", e))?
+        .ok_or_else(|| format!("Attachment not found: {}", attachment_id))?;
+
+    let path = attachments_dir()?.join(&attachment.stored_path);
+    tokio::fs::copy(&path, &target_path).await
+        .map_err(|e| format!("无法导出附件: {}", e))?;
+
+    Ok(())
+}
+
+/// 删除附件（同时删除本地文件）
+#[tauri::command]
+pub async fn session_attachment_delete(
+    pool: State<'_, DbPool>,
+    attachment_id: String,
+) -> Result<(), String> {
+    let repo = SessionNoteRepository::new(pool.inner().clone());
+    let Some(attachment) = repo.find_attachment(&attachment_id)
+        .map_err(|e| format!("Failed to get attachment: {}", e))?
+    else {
+        return Ok(());
+    };
+
+    repo.delete_attachment(&attachment_id)
+        .map_err(|e| format!("Failed to delete attachment: {}", e))?;
+
+    remove_attachment_file(&attachment).await;
+
+    Ok(())
+}
+
+/// 开启或关闭附件的云同步（只有不超过 1 MB 的附件可以同步）
+///
+/// 关闭后附件仍保留在本地，下次同步时删除服务器上的副本
+#[tauri::command]
+pub async fn session_attachment_set_sync(
+    pool: State<'_, DbPool>,
+    attachment_id: String,
+    sync_enabled: bool,
+) -> Result<SessionAttachment, String> {
+    let user_id = get_current_user_id(&pool);
+    if sync_enabled && user_id == ANONYMOUS_USER_ID {
+        return Err("请先登录后再开启同步".to_string());
+    }
+
+    let repo = SessionNoteRepository::new(pool.inner().clone());
+    let attachment = repo.find_attachment(&attachment_id)
+        .map_err(|e| format!("Failed to get attachment: {}", e))?
+        .ok_or_else(|| format!("Attachment not found: {}", attachment_id))?;
+
+    if sync_enabled && attachment.size as u64 > MAX_SYNC_ATTACHMENT_SIZE {
+        return Err(format!(
+            "附件过大 ({} 字节)，最大可同步 {} 字节",
+            attachment.size,
+            MAX_SYNC_ATTACHMENT_SIZE
+        ));
+    }
+
+    repo.set_attachment_sync(&attachment_id, &user_id, sync_enabled)
+        .map_err(|e| format!("Failed to update attachment: {}", e))?;
+
+    repo.find_attachment(&attachment_id)
+        .map_err(|e| format!("Failed to get attachment: {}", e))?
+        .ok_or_else(|| format!("Attachment not found: {}", attachment_id))
+}
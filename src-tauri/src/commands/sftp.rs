@@ -4,15 +4,32 @@
 
 use crate::database::repositories::UserAuthRepository;
 use crate::database::DbPool;
-use crate::error::Result;
-use crate::sftp::{SftpFileInfo, SftpManager, UploadDirectoryResult};
+use crate::error::{Result, SSHError};
+use crate::sftp::manager::{TransferQueueState, TransferTicket};
+use crate::sftp::checksum::ChecksumVerification;
+use crate::sftp::edit::{EditSessionInfo, EditSyncEvent, EditSyncStatus, EDIT_SYNC_EVENT};
+use crate::sftp::client::SftpClient;
+use crate::sftp::watchdog::WatchdogConfig;
+use crate::sftp::{ConflictPolicy, SftpFileInfo, SftpManager, SymlinkPolicy, TransferOperation, TransferProgress, TransferSource, TransferStatus, UploadDirectoryResult};
 use std::sync::Arc;
 use std::path::Path;
+use crate::services::hook_service::{HookEvent, HookService};
+use crate::services::webhook_service::{WebhookEvent, WebhookService};
 use tauri::{State, Emitter};
+use tauri::ipc::{Channel, InvokeResponseBody};
 
 /// SFTP Manager 状态
 pub type SftpManagerState = Arc<SftpManager>;
 
+/// 目录上传默认同时上传的文件数量
+const DEFAULT_UPLOAD_CONCURRENCY: usize = 4;
+
+/// 目录下载默认使用的 SFTP channel 数量
+const DEFAULT_DOWNLOAD_CONCURRENCY: usize = 4;
+
+/// 目录下载最多使用的 SFTP channel 数量
+const MAX_DOWNLOAD_CONCURRENCY: usize = 16;
+
 /// 匿名用户的固定用户ID
 const ANONYMOUS_USER_ID: &str = "anonymous_local";
 
@@ -26,6 +43,96 @@ fn get_current_user_id(pool: &DbPool) -> String {
     }
 }
 
+/// 构造传输队列中的任务
+fn queue_item(
+    task_id: &str,
+    connection_id: &str,
+    operation: TransferOperation,
+    local_path: &str,
+    remote_path: &str,
+    file_size: u64,
+) -> TransferProgress {
+    let local = TransferSource::Local { path: local_path.to_string() };
+    let remote = TransferSource::Remote { connection_id: connection_id.to_string(), path: remote_path.to_string() };
+    let (source, destination) = match operation {
+        TransferOperation::Upload => (local, remote),
+        _ => (remote, local),
+    };
+    TransferProgress {
+        id: task_id.to_string(),
+        operation,
+        source,
+        destination,
+        file_size,
+        transferred: 0,
+        speed: 0,
+        status: TransferStatus::Pending,
+    }
+}
+
+/// 在传输队列中等待，轮到该任务时返回；任务结束（ticket drop）后让出位置
+///
+/// 排队期间被取消时把传输记录标记为已取消并发送状态变更事件
+async fn wait_in_queue(
+    manager: &SftpManager,
+    pool: &DbPool,
+    window: &tauri::Window,
+    connection_id: &str,
+    item: TransferProgress,
+    cancellation_token: &tokio_util::sync::CancellationToken,
+) -> Result<TransferTicket> {
+    let task_id = item.id.clone();
+    let operation = item.operation.clone();
+    let error = match manager.transfer_queue().enqueue(item, cancellation_token).await {
+        Ok(ticket) => return Ok(ticket),
+        Err(e) => e,
+    };
+
+    manager.cleanup_cancellation_token(&task_id).await;
+    let error_message = Some(error.to_string());
+    if let Ok(conn) = pool.get() {
+        match operation {
+            TransferOperation::Upload => {
+                let _ = crate::database::repositories::UploadRecordsRepository::update_status(
+                    &conn,
+                    &task_id,
+                    crate::database::repositories::UploadStatus::Cancelled,
+                    error_message.clone(),
+                );
+                let _ = window.emit("sftp-upload-status-change", crate::sftp::UploadStatusChangeEvent {
+                    task_id: task_id.clone(),
+                    connection_id: connection_id.to_string(),
+                    status: "cancelled".to_string(),
+                    bytes_transferred: 0,
+                    files_completed: 0,
+                    total_files: 0,
+                    error_message,
+                    completed_at: Some(chrono::Utc::now().timestamp_millis()),
+                });
+            }
+            _ => {
+                let _ = crate::database::repositories::DownloadRecordsRepository::update_status(
+                    &conn,
+                    &task_id,
+                    crate::database::repositories::DownloadStatus::Cancelled,
+                    error_message.clone(),
+                );
+                let _ = window.emit("sftp-download-status-change", crate::sftp::DownloadStatusChangeEvent {
+                    task_id: task_id.clone(),
+                    connection_id: connection_id.to_string(),
+                    status: "cancelled".to_string(),
+                    bytes_transferred: 0,
+                    files_completed: 0,
+                    total_files: 0,
+                    error_message,
+                    completed_at: Some(chrono::Utc::now().timestamp_millis()),
+                });
+            }
+        }
+    }
+    Err(error)
+}
+
 /// 列出目录内容
 ///
 /// # 参数
@@ -108,6 +215,37 @@ pub async fn sftp_rename(
     manager.rename(&connection_id, &old_path, &new_path).await
 }
 
+/// 创建符号链接
+///
+/// # 参数
+/// - `connection_id`: SSH 连接 ID
+/// - `link_path`: 新建的链接路径
+/// - `target_path`: 链接指向的路径（可以是相对路径，不要求存在）
+#[tauri::command]
+pub async fn sftp_create_symlink(
+    manager: State<'_, SftpManagerState>,
+    connection_id: String,
+    link_path: String,
+    target_path: String,
+) -> Result<()> {
+    tracing::info!("Creating symlink: {} -> {} on connection {}", link_path, target_path, connection_id);
+    manager.create_symlink(&connection_id, &link_path, &target_path).await
+}
+
+/// 读取符号链接指向的路径
+///
+/// # 参数
+/// - `connection_id`: SSH 连接 ID
+/// - `path`: 链接路径
+#[tauri::command]
+pub async fn sftp_read_link(
+    manager: State<'_, SftpManagerState>,
+    connection_id: String,
+    path: String,
+) -> Result<String> {
+    manager.read_link(&connection_id, &path).await
+}
+
 /// 修改文件权限
 ///
 /// # 参数
@@ -125,6 +263,160 @@ pub async fn sftp_chmod(
     manager.chmod(&connection_id, &path, mode).await
 }
 
+/// 扫描远程目录中的权限风险
+///
+/// 查找全局可写的文件和目录、setuid/setgid 程序，返回按风险等级排序的报告
+///
+/// # 参数
+/// - `connection_id`: SSH 连接 ID
+/// - `path`: 扫描的根目录（不跨文件系统）
+/// - `limit`: 最多返回的条目数，默认 1000
+#[tauri::command]
+pub async fn sftp_permission_audit(
+    manager: State<'_, SftpManagerState>,
+    connection_id: String,
+    path: String,
+    limit: Option<usize>,
+) -> Result<crate::sftp::audit::PermissionAuditReport> {
+    let limit = limit.unwrap_or(crate::sftp::audit::DEFAULT_AUDIT_LIMIT).max(1);
+    manager.permission_audit(&connection_id, &path, limit).await
+}
+
+/// 搜索远程文件
+///
+/// 按名称通配符（`*`、`?`）和大小、修改时间等条件搜索 `root` 下的文件，
+/// 匹配项通过 `sftp-search-result` 事件分批发送，可通过 `sftp_cancel_search` 取消
+///
+/// # 参数
+/// - `connection_id`: SSH 连接 ID
+/// - `root`: 搜索的根目录
+/// - `pattern`: 名称通配符，为空时匹配所有名称
+/// - `filters`: 过滤条件（类型、大小、修改时间、深度、数量上限）
+/// - `search_id`: 前端生成的搜索 ID，用于区分事件和取消搜索
+#[tauri::command]
+pub async fn sftp_search(
+    manager: State<'_, SftpManagerState>,
+    connection_id: String,
+    root: String,
+    pattern: String,
+    filters: Option<crate::sftp::search::SearchFilters>,
+    search_id: String,
+    window: tauri::Window,
+) -> Result<crate::sftp::search::SearchSummary> {
+    use crate::sftp::search::{SearchResultEvent, SEARCH_RESULT_EVENT};
+
+    tracing::info!("Searching {} for '{}' on connection {} (search {})", root, pattern, connection_id, search_id);
+    let filters = filters.unwrap_or_default();
+    manager
+        .search(&connection_id, &search_id, &root, &pattern, &filters, |matches| {
+            let _ = window.emit(SEARCH_RESULT_EVENT, SearchResultEvent {
+                search_id: search_id.clone(),
+                connection_id: connection_id.clone(),
+                matches,
+            });
+        })
+        .await
+}
+
+/// 取消远程文件搜索
+#[tauri::command]
+pub async fn sftp_cancel_search(
+    manager: State<'_, SftpManagerState>,
+    search_id: String,
+) -> Result<()> {
+    tracing::info!("Cancelling search {}", search_id);
+    manager.cancel_task(&search_id).await
+}
+
+/// 用本地编辑器编辑远程文件
+///
+/// 下载到临时目录后打开编辑器，之后每次保存都会自动上传，
+/// 同步状态通过 `sftp-edit-sync` 事件发送
+///
+/// # 参数
+/// - `connection_id`: 连接 ID
+/// - `path`: 远程文件路径
+/// - `editor`: 编辑器命令（如 `code --wait`），为空时使用系统默认程序打开
+#[tauri::command]
+pub async fn sftp_edit_open(
+    manager: State<'_, SftpManagerState>,
+    window: tauri::Window,
+    connection_id: String,
+    path: String,
+    editor: Option<String>,
+) -> Result<EditSessionInfo> {
+    let info = manager.open_edit(&connection_id, &path, editor.as_deref()).await?;
+    let _ = window.emit(EDIT_SYNC_EVENT, EditSyncEvent {
+        edit_id: info.edit_id.clone(),
+        connection_id: info.connection_id.clone(),
+        remote_path: info.remote_path.clone(),
+        status: EditSyncStatus::Opened,
+        bytes: None,
+        error: None,
+        timestamp: info.opened_at,
+    });
+    Ok(info)
+}
+
+/// 结束编辑会话（之后的保存不再上传）
+///
+/// 本地文件有未上传的修改时保留临时文件，并发送 `failed` 事件
+#[tauri::command]
+pub async fn sftp_edit_close(
+    manager: State<'_, SftpManagerState>,
+    window: tauri::Window,
+    edit_id: String,
+) -> Result<()> {
+    let event = manager
+        .close_edit(&edit_id)
+        .ok_or_else(|| SSHError::NotFound(format!("编辑会话不存在: {}", edit_id)))?;
+    let _ = window.emit(EDIT_SYNC_EVENT, event);
+    Ok(())
+}
+
+/// 列出当前的编辑会话
+#[tauri::command]
+pub async fn sftp_edit_list(manager: State<'_, SftpManagerState>) -> Result<Vec<EditSessionInfo>> {
+    Ok(manager.list_edits())
+}
+
+/// 获取文件所在主机及途经的跳板机
+///
+/// 经由跳板机连接的会话，文件操作和传输都在最终主机上进行
+#[tauri::command]
+pub async fn sftp_route(
+    manager: State<'_, SftpManagerState>,
+    connection_id: String,
+) -> Result<crate::sftp::route::SftpRoute> {
+    manager.route(&connection_id).await
+}
+
+/// 列出远程用户
+///
+/// 结果按连接缓存，`refresh` 为 true 时重新读取
+#[tauri::command]
+pub async fn sftp_remote_users(
+    manager: State<'_, SftpManagerState>,
+    connection_id: String,
+    refresh: Option<bool>,
+) -> Result<Vec<crate::sftp::accounts::RemoteUser>> {
+    let accounts = manager.remote_accounts(&connection_id, refresh.unwrap_or(false)).await?;
+    Ok(accounts.users.clone())
+}
+
+/// 列出远程用户组
+///
+/// 与 `sftp_remote_users` 共用缓存，`refresh` 为 true 时重新读取
+#[tauri::command]
+pub async fn sftp_remote_groups(
+    manager: State<'_, SftpManagerState>,
+    connection_id: String,
+    refresh: Option<bool>,
+) -> Result<Vec<crate::sftp::accounts::RemoteGroup>> {
+    let accounts = manager.remote_accounts(&connection_id, refresh.unwrap_or(false)).await?;
+    Ok(accounts.groups.clone())
+}
+
 /// 读取文件内容
 ///
 /// # 参数
@@ -143,6 +435,48 @@ pub async fn sftp_read_file(
     manager.read_file(&connection_id, &path).await
 }
 
+/// 分块流式读取文件内容
+///
+/// 适用于超过直接读取上限的大文件，数据按顺序以原始字节通过 `on_chunk` Channel 分块发送
+/// （不经过 JSON 序列化），命令返回即表示读取结束
+///
+/// # 参数
+/// - `connection_id`: SSH 连接 ID
+/// - `path`: 文件路径
+/// - `stream_id`: 前端生成的流 ID，用于取消读取
+/// - `on_chunk`: 接收文件分块的 Channel
+///
+/// # 返回
+/// 读取的总字节数
+#[tauri::command]
+pub async fn sftp_read_file_stream(
+    manager: State<'_, SftpManagerState>,
+    connection_id: String,
+    path: String,
+    stream_id: String,
+    on_chunk: Channel<InvokeResponseBody>,
+) -> Result<u64> {
+    tracing::info!("Streaming file: {} on connection {} (stream {})", path, connection_id, stream_id);
+
+    manager
+        .read_file_stream(&connection_id, &stream_id, &path, |_offset, data, _size| {
+            on_chunk
+                .send(InvokeResponseBody::Raw(data))
+                .map_err(|e| crate::error::SSHError::Io(format!("发送文件分块失败: {}", e)))
+        })
+        .await
+}
+
+/// 取消流式读取
+#[tauri::command]
+pub async fn sftp_cancel_read_stream(
+    manager: State<'_, SftpManagerState>,
+    stream_id: String,
+) -> Result<()> {
+    tracing::info!("Cancelling read stream {}", stream_id);
+    manager.cancel_task(&stream_id).await
+}
+
 /// 写入文件内容
 ///
 /// # 参数
@@ -202,11 +536,20 @@ pub async fn local_list_dir(path: String) -> Result<Vec<SftpFileInfo>> {
             format!("{}{}{}", path, std::path::MAIN_SEPARATOR, file_name)
         };
 
+        // 符号链接按链接目标判断是否为目录
+        let (is_dir, link_target) = if metadata.is_symlink() {
+            let target = tokio::fs::read_link(entry.path()).await.ok()
+                .map(|target| target.to_string_lossy().to_string());
+            (entry.path().is_dir(), target)
+        } else {
+            (metadata.is_dir(), None)
+        };
+
         let file_info = SftpFileInfo {
             name: file_name.clone(),
             path: file_path,
             size: metadata.len(),
-            is_dir: metadata.is_dir(),
+            is_dir,
             is_symlink: metadata.is_symlink(),
             modified: metadata.modified()
                 .map(|t| t.duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs())
@@ -224,6 +567,7 @@ pub async fn local_list_dir(path: String) -> Result<Vec<SftpFileInfo>> {
             },
             owner: None,
             group: None,
+            link_target,
         };
 
         entries.push(file_info);
@@ -316,6 +660,7 @@ pub async fn local_drive_root(drive: String) -> Result<String> {
 /// - `connection_id`: SSH 连接 ID
 /// - `local_path`: 本地文件路径
 /// - `remote_path`: 远程保存路径
+/// - `verify_checksum`: 上传完成后校验两端 SHA-256，不一致时返回错误
 /// - `window`: Tauri 窗口实例（用于发送进度事件）
 ///
 /// # 返回
@@ -327,8 +672,26 @@ pub async fn sftp_upload_file(
     connection_id: String,
     local_path: String,
     remote_path: String,
+    verify_checksum: Option<bool>,
     window: tauri::Window,
 ) -> Result<u64> {
+    let (bytes, checksum) = upload_file(
+        &manager, &pool, &window, connection_id, local_path, remote_path, verify_checksum.unwrap_or(false),
+    ).await?;
+    ensure_checksum_matched(checksum.as_ref())?;
+    Ok(bytes)
+}
+
+/// 创建上传记录并执行单文件上传，`verify_checksum` 为 true 时返回校验结果
+async fn upload_file(
+    manager: &SftpManager,
+    pool: &DbPool,
+    window: &tauri::Window,
+    connection_id: String,
+    local_path: String,
+    remote_path: String,
+    verify_checksum: bool,
+) -> Result<(u64, Option<ChecksumVerification>)> {
     tracing::info!("=== Upload File Start ===");
     tracing::info!("Connection ID: {}", connection_id);
     tracing::info!("Local path: {}", local_path);
@@ -344,7 +707,7 @@ pub async fn sftp_upload_file(
     }
 
     // 获取当前用户 ID
-    let user_id = get_current_user_id(&pool);
+    let user_id = get_current_user_id(pool);
 
     // 生成任务 ID
     let task_id = format!("upload-file-{}-{}", connection_id, uuid::Uuid::new_v4().to_string().split('-').next().unwrap_or(""));
@@ -375,12 +738,96 @@ pub async fn sftp_upload_file(
         error_message: None,
         created_at: now,
         updated_at: now,
+        checksum_sha256: None,
+        checksum_verified: None,
+        transfer_kind: Some("file".to_string()),
     };
 
     if let Ok(conn) = pool.get() {
         let _ = crate::database::repositories::UploadRecordsRepository::create(&conn, &upload_record);
     }
 
+    let bytes = run_upload_file(
+        manager, pool, window, task_id.clone(), connection_id.clone(), local_path.clone(), remote_path.clone(), false,
+    ).await?;
+    if !verify_checksum {
+        return Ok((bytes, None));
+    }
+
+    let checksum = verify_transfer_checksum(manager, &task_id, &connection_id, &local_path, &remote_path).await?;
+    if let Ok(conn) = pool.get() {
+        let _ = crate::database::repositories::UploadRecordsRepository::update_checksum(
+            &conn,
+            &task_id,
+            &checksum.local_sha256,
+            checksum.matched,
+        );
+    }
+    Ok((bytes, Some(checksum)))
+}
+
+/// 计算本地文件和远程文件的 SHA-256 并比较
+async fn verify_transfer_checksum(
+    manager: &SftpManager,
+    task_id: &str,
+    connection_id: &str,
+    local_path: &str,
+    remote_path: &str,
+) -> Result<ChecksumVerification> {
+    let cancellation_token = manager.get_cancellation_token(task_id).await;
+    let (local, remote) = tokio::join!(
+        crate::sftp::checksum::local_sha256(local_path),
+        manager.remote_sha256(connection_id, remote_path, None, task_id, &cancellation_token),
+    );
+    manager.cleanup_cancellation_token(task_id).await;
+
+    let local = local.map_err(|e| crate::error::SSHError::Io(format!("无法计算本地文件校验和: {}", e)))?;
+    let checksum = ChecksumVerification::new(local, remote?);
+    if checksum.matched {
+        tracing::info!("Checksum verified for {}: {}", task_id, checksum.local_sha256);
+    } else {
+        tracing::warn!(
+            "Checksum mismatch for {}: local {}, remote {}",
+            task_id, checksum.local_sha256, checksum.remote_sha256
+        );
+    }
+    Ok(checksum)
+}
+
+/// 校验和不一致时返回错误（用于只返回字节数的单文件命令）
+fn ensure_checksum_matched(checksum: Option<&ChecksumVerification>) -> Result<()> {
+    match checksum {
+        Some(checksum) if !checksum.matched => Err(crate::error::SSHError::Io(format!(
+            "校验和不一致：本地 {}，远程 {}",
+            checksum.local_sha256, checksum.remote_sha256
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// 执行单文件上传任务（上传记录已创建），`resume` 为 true 时从远程文件已有的部分继续上传
+#[allow(clippy::too_many_arguments)]
+async fn run_upload_file(
+    manager: &SftpManager,
+    pool: &DbPool,
+    window: &tauri::Window,
+    task_id: String,
+    connection_id: String,
+    local_path: String,
+    remote_path: String,
+    resume: bool,
+) -> Result<u64> {
+    let local_path_obj = std::path::Path::new(&local_path);
+
+    // 获取取消令牌
+    let cancellation_token = manager.get_cancellation_token(&task_id).await;
+
+    // 在传输队列中排队，轮到该任务时才开始
+    let file_size = local_path_obj.metadata().map(|m| m.len()).unwrap_or(0);
+    let item = queue_item(&task_id, &connection_id, TransferOperation::Upload, &local_path, &remote_path, file_size);
+    let _ticket = wait_in_queue(manager, pool, window, &connection_id, item, &cancellation_token).await?;
+    let now = chrono::Utc::now().timestamp();
+
     // 更新状态为 uploading
     if let Ok(conn) = pool.get() {
         let _ = crate::database::repositories::UploadRecordsRepository::update_status(
@@ -391,12 +838,9 @@ pub async fn sftp_upload_file(
         );
     }
 
-    // 获取取消令牌
-    let cancellation_token = manager.get_cancellation_token(&task_id).await;
-
     // 🔥 为任务创建独立的 SFTP Client
     let sftp_client = manager.create_task_client(&connection_id, &task_id).await?;
-    let mut client_guard = sftp_client.lock().await;
+    let client_guard = sftp_client.lock().await;
 
     // 获取文件大小
     let file_size = local_path_obj.metadata()
@@ -453,6 +897,11 @@ pub async fn sftp_upload_file(
         &remote_path,
         &cancellation_token,
         move |transferred, total| {
+            // 更新已传输字节数（失败时写入记录，用于断点续传）
+            if let Ok(mut bytes) = transferred_bytes_for_callback.lock() {
+                *bytes = transferred;
+            }
+
             // 使用节流机制：每 200ms 最多发送一次事件
             let now = std::time::Instant::now();
             let should_emit = {
@@ -479,11 +928,6 @@ pub async fn sftp_upload_file(
                     0
                 };
 
-                // 更新已传输字节数
-                if let Ok(mut bytes) = transferred_bytes_for_callback.lock() {
-                    *bytes = transferred;
-                }
-
                 // 发送进度事件（前端显示用）
                 let progress_event = crate::sftp::UploadProgressEvent {
                     task_id: task_id_for_callback.clone(),
@@ -507,11 +951,12 @@ pub async fn sftp_upload_file(
             }
         },
         false,
+        resume,
     ).await;
 
-    // 🔥 清理任务 SFTP Client 和取消令牌（无论成功或失败）
+    // 🔥 清理任务 SFTP Client 和取消令牌（无论成功或失败，失败时客户端不归还连接池）
     // 注意：先清理 client，再清理取消令牌，确保传输函数已经返回
-    manager.cleanup_task_client(&task_id).await;
+    manager.cleanup_task_client(&task_id, result.is_ok()).await;
     manager.cleanup_cancellation_token(&task_id).await;
 
     // 返回上传结果
@@ -541,6 +986,7 @@ pub async fn sftp_upload_file(
                     error_message: None,
                     completed_at: Some(chrono::Utc::now().timestamp_millis()),
                 });
+                fire_transfer_hook("upload", &connection_id, &task_id, &local_path, &remote_path, transferred as i64, 1);
             }
 
             Ok(transferred)
@@ -561,7 +1007,8 @@ pub async fn sftp_upload_file(
             // 获取状态字符串（在移动 status 之前）
             let status_str = status.as_str();
 
-            // 更新数据库状态
+            // 更新数据库状态，并记录已传输的字节数
+            let bytes_transferred = transferred_bytes.lock().map(|bytes| *bytes).unwrap_or(0) as i64;
             if let Ok(conn) = pool.get() {
                 let _ = crate::database::repositories::UploadRecordsRepository::update_status(
                     &conn,
@@ -569,13 +1016,14 @@ pub async fn sftp_upload_file(
                     status,
                     Some(error_msg.clone()),
                 );
+                let _ = crate::database::repositories::UploadRecordsRepository::update_progress(&conn, &task_id, bytes_transferred, 0);
 
                 // 发送状态变更事件
                 let _ = window.emit("sftp-upload-status-change", crate::sftp::UploadStatusChangeEvent {
                     task_id: task_id.clone(),
                     connection_id: connection_id.clone(),
                     status: status_str.to_string(),
-                    bytes_transferred: 0,
+                    bytes_transferred,
                     files_completed: 0,
                     total_files: 1,
                     error_message: Some(error_msg),
@@ -596,6 +1044,7 @@ pub async fn sftp_upload_file(
 /// - `connection_id`: SSH 连接 ID
 /// - `remote_path`: 远程文件路径
 /// - `local_path`: 本地保存路径
+/// - `verify_checksum`: 下载完成后校验两端 SHA-256，不一致时返回错误
 /// - `window`: Tauri 窗口实例（用于发送进度事件）
 ///
 /// # 返回
@@ -607,8 +1056,26 @@ pub async fn sftp_download_file(
     connection_id: String,
     remote_path: String,
     local_path: String,
+    verify_checksum: Option<bool>,
     window: tauri::Window,
 ) -> Result<u64> {
+    let (bytes, checksum) = download_file(
+        &manager, &pool, &window, connection_id, remote_path, local_path, verify_checksum.unwrap_or(false),
+    ).await?;
+    ensure_checksum_matched(checksum.as_ref())?;
+    Ok(bytes)
+}
+
+/// 创建下载记录并执行单文件下载，`verify_checksum` 为 true 时返回校验结果
+async fn download_file(
+    manager: &SftpManager,
+    pool: &DbPool,
+    window: &tauri::Window,
+    connection_id: String,
+    remote_path: String,
+    local_path: String,
+    verify_checksum: bool,
+) -> Result<(u64, Option<ChecksumVerification>)> {
     tracing::info!("=== Download File Start ===");
     tracing::info!("Connection ID: {}", connection_id);
     tracing::info!("Remote path: {}", remote_path);
@@ -625,7 +1092,7 @@ pub async fn sftp_download_file(
     }
 
     // 获取当前用户 ID
-    let user_id = get_current_user_id(&pool);
+    let user_id = get_current_user_id(pool);
 
     // 生成任务 ID
     let task_id = format!("download-file-{}-{}", connection_id, uuid::Uuid::new_v4().to_string().split('-').next().unwrap_or(""));
@@ -651,12 +1118,44 @@ pub async fn sftp_download_file(
         error_message: None,
         created_at: now,
         updated_at: now,
+        transfer_kind: Some("file".to_string()),
     };
 
     if let Ok(conn) = pool.get() {
         let _ = crate::database::repositories::DownloadRecordsRepository::create(&conn, &download_record);
     }
 
+    let bytes = run_download_file(
+        manager, pool, window, task_id.clone(), connection_id.clone(), remote_path.clone(), local_path.clone(), false,
+    ).await?;
+    if !verify_checksum {
+        return Ok((bytes, None));
+    }
+
+    let checksum = verify_transfer_checksum(manager, &task_id, &connection_id, &local_path, &remote_path).await?;
+    Ok((bytes, Some(checksum)))
+}
+
+/// 执行单文件下载任务（下载记录已创建），`resume` 为 true 时从本地文件已有的部分继续下载
+#[allow(clippy::too_many_arguments)]
+async fn run_download_file(
+    manager: &SftpManager,
+    pool: &DbPool,
+    window: &tauri::Window,
+    task_id: String,
+    connection_id: String,
+    remote_path: String,
+    local_path: String,
+    resume: bool,
+) -> Result<u64> {
+    // 获取取消令牌
+    let cancellation_token = manager.get_cancellation_token(&task_id).await;
+
+    // 在传输队列中排队，轮到该任务时才开始
+    let item = queue_item(&task_id, &connection_id, TransferOperation::Download, &local_path, &remote_path, 0);
+    let _ticket = wait_in_queue(manager, pool, window, &connection_id, item, &cancellation_token).await?;
+    let now = chrono::Utc::now().timestamp();
+
     // 更新状态为 downloading
     if let Ok(conn) = pool.get() {
         let _ = crate::database::repositories::DownloadRecordsRepository::update_status(
@@ -667,9 +1166,6 @@ pub async fn sftp_download_file(
         );
     }
 
-    // 获取取消令牌
-    let cancellation_token = manager.get_cancellation_token(&task_id).await;
-
     // 🔥 为任务创建独立的 SFTP Client
     let sftp_client = manager.create_task_client(&connection_id, &task_id).await?;
     let client_guard = sftp_client.lock().await;
@@ -722,6 +1218,11 @@ pub async fn sftp_download_file(
         &local_path,
         &cancellation_token,
         move |transferred, total| {
+            // 更新已传输字节数（失败时写入记录，用于断点续传）
+            if let Ok(mut bytes) = transferred_bytes_for_callback.lock() {
+                *bytes = transferred;
+            }
+
             // 使用节流机制：每 200ms 最多发送一次事件
             let now = std::time::Instant::now();
             let should_emit = {
@@ -748,11 +1249,6 @@ pub async fn sftp_download_file(
                     0
                 };
 
-                // 更新已传输字节数
-                if let Ok(mut bytes) = transferred_bytes_for_callback.lock() {
-                    *bytes = transferred;
-                }
-
                 // 发送进度事件（前端显示用）
                 let progress_event = crate::sftp::DownloadProgressEvent {
                     task_id: task_id_for_callback.clone(),
@@ -769,12 +1265,13 @@ pub async fn sftp_download_file(
                 };
                 let _ = window_for_callback.emit("sftp-download-progress", &progress_event);
             }
-        }
+        },
+        resume,
     ).await;
 
-    // 🔥 清理任务 SFTP Client 和取消令牌（无论成功或失败）
+    // 🔥 清理任务 SFTP Client 和取消令牌（无论成功或失败，失败时客户端不归还连接池）
     // 注意：先清理 client，再清理取消令牌，确保传输函数已经返回
-    manager.cleanup_task_client(&task_id).await;
+    manager.cleanup_task_client(&task_id, result.is_ok()).await;
     manager.cleanup_cancellation_token(&task_id).await;
 
     // 返回下载结果
@@ -804,6 +1301,7 @@ pub async fn sftp_download_file(
                     error_message: None,
                     completed_at: Some(chrono::Utc::now().timestamp_millis()),
                 });
+                fire_transfer_hook("download", &connection_id, &task_id, &local_path, &remote_path, transferred as i64, 1);
             }
 
             Ok(transferred)
@@ -824,7 +1322,8 @@ pub async fn sftp_download_file(
             // 获取状态字符串（在移动 status 之前）
             let status_str = status.as_str();
 
-            // 更新数据库状态
+            // 更新数据库状态，并记录已传输的字节数
+            let bytes_transferred = transferred_bytes.lock().map(|bytes| *bytes).unwrap_or(0) as i64;
             if let Ok(conn) = pool.get() {
                 let _ = crate::database::repositories::DownloadRecordsRepository::update_status(
                     &conn,
@@ -832,13 +1331,14 @@ pub async fn sftp_download_file(
                     status,
                     Some(error_msg.clone()),
                 );
+                let _ = crate::database::repositories::DownloadRecordsRepository::update_progress(&conn, &task_id, bytes_transferred, 0);
 
                 // 发送状态变更事件
                 let _ = window.emit("sftp-download-status-change", crate::sftp::DownloadStatusChangeEvent {
                     task_id: task_id.clone(),
                     connection_id: connection_id.clone(),
                     status: status_str.to_string(),
-                    bytes_transferred: 0,
+                    bytes_transferred,
                     files_completed: 0,
                     total_files: 1,
                     error_message: Some(error_msg),
@@ -851,6 +1351,207 @@ pub async fn sftp_download_file(
     }
 }
 
+/// 断点续传中断的单文件上传或下载任务
+///
+/// 根据 `task_id` 在上传/下载记录中查找任务，从已传输的部分继续（上传时以远程文件大小、
+/// 下载时以本地文件大小为起点），任务 ID 保持不变。目录传输任务不支持续传。
+/// 续传前会校验已传输部分与源文件开头的 SHA-256，不一致时（如文件在两次传输之间被修改）
+/// 重新传输整个文件。
+///
+/// # 参数
+/// - `task_id`: 任务 ID
+/// - `connection_id`: 使用的 SSH 连接 ID，重新连接后连接 ID 会变化，不传则使用记录中的连接
+///
+/// # 返回
+/// 文件的最终大小
+#[tauri::command]
+pub async fn sftp_resume_task(
+    manager: State<'_, SftpManagerState>,
+    pool: State<'_, DbPool>,
+    task_id: String,
+    connection_id: Option<String>,
+    window: tauri::Window,
+) -> Result<u64> {
+    use crate::database::repositories::{DownloadRecordsRepository, UploadRecordsRepository};
+    use crate::error::SSHError;
+
+    if manager.is_task_running(&task_id).await {
+        return Err(SSHError::Io(format!("任务正在进行中: {}", task_id)));
+    }
+
+    let conn = pool.get().map_err(|e| SSHError::Storage(e.to_string()))?;
+    let upload = UploadRecordsRepository::find_by_task_id(&conn, &task_id)
+        .map_err(|e| SSHError::Storage(e.to_string()))?;
+    let download = match upload {
+        Some(_) => None,
+        None => DownloadRecordsRepository::find_by_task_id(&conn, &task_id)
+            .map_err(|e| SSHError::Storage(e.to_string()))?,
+    };
+    drop(conn);
+
+    let (status, transfer_kind, record_connection_id) = match (&upload, &download) {
+        (Some(record), _) => (&record.status, &record.transfer_kind, &record.connection_id),
+        (None, Some(record)) => (&record.status, &record.transfer_kind, &record.connection_id),
+        (None, None) => return Err(SSHError::NotFound(format!("传输任务不存在: {}", task_id))),
+    };
+    if status == "completed" {
+        return Err(SSHError::Io(format!("任务已完成: {}", task_id)));
+    }
+    if transfer_kind.as_deref() != Some("file") {
+        return Err(SSHError::NotSupported("仅支持续传单文件传输任务".to_string()));
+    }
+    let connection_id = connection_id.unwrap_or_else(|| record_connection_id.clone());
+    tracing::info!("Resuming transfer task {} on connection {}", task_id, connection_id);
+
+    match (upload, download) {
+        (Some(record), _) => {
+            if !Path::new(&record.local_path).exists() {
+                return Err(SSHError::NotFound(format!("本地文件不存在: {}", record.local_path)));
+            }
+            let resume = upload_prefix_matches(&manager, &task_id, &connection_id, &record.local_path, &record.remote_path).await?;
+            run_upload_file(&manager, &pool, &window, task_id, connection_id, record.local_path, record.remote_path, resume).await
+        }
+        (None, Some(record)) => {
+            let resume = download_prefix_matches(&manager, &task_id, &connection_id, &record.remote_path, &record.local_path).await?;
+            run_download_file(&manager, &pool, &window, task_id, connection_id, record.remote_path, record.local_path, resume).await
+        }
+        (None, None) => unreachable!(),
+    }
+}
+
+/// 检查远程文件已有的部分是否与本地文件开头一致
+///
+/// 远程文件不存在、为空或比本地文件大时返回 true，此时上传会从头开始
+async fn upload_prefix_matches(
+    manager: &SftpManager,
+    task_id: &str,
+    connection_id: &str,
+    local_path: &str,
+    remote_path: &str,
+) -> Result<bool> {
+    let local_size = tokio::fs::metadata(local_path).await
+        .map_err(|e| crate::error::SSHError::Io(format!("无法获取文件元数据: {}", e)))?
+        .len();
+    let remote_size = match manager.stat(connection_id, remote_path).await? {
+        Some(info) if info.size > 0 && info.size <= local_size => info.size,
+        _ => return Ok(true),
+    };
+
+    let cancellation_token = manager.get_cancellation_token(task_id).await;
+    let (local, remote) = tokio::join!(
+        crate::sftp::checksum::local_prefix_sha256(local_path, remote_size),
+        manager.remote_sha256(connection_id, remote_path, Some(remote_size), task_id, &cancellation_token),
+    );
+    manager.cleanup_cancellation_token(task_id).await;
+
+    let local = local.map_err(|e| crate::error::SSHError::Io(format!("无法计算本地文件校验和: {}", e)))?;
+    let checksum = ChecksumVerification::new(local, remote?);
+    if !checksum.matched {
+        tracing::warn!(
+            "Remote prefix of {} ({} bytes) differs from {}, restarting upload {}",
+            remote_path, remote_size, local_path, task_id
+        );
+    }
+    Ok(checksum.matched)
+}
+
+/// 检查本地已下载的部分是否与远程文件开头一致
+///
+/// 本地文件不存在、为空或不小于远程文件（以及远程文件不存在）时返回 true，此时下载会从头开始或直接报错
+async fn download_prefix_matches(
+    manager: &SftpManager,
+    task_id: &str,
+    connection_id: &str,
+    remote_path: &str,
+    local_path: &str,
+) -> Result<bool> {
+    let local_size = match tokio::fs::metadata(local_path).await {
+        Ok(meta) if meta.is_file() => meta.len(),
+        _ => return Ok(true),
+    };
+    match manager.stat(connection_id, remote_path).await? {
+        Some(info) if local_size > 0 && local_size < info.size => {}
+        _ => return Ok(true),
+    }
+
+    let cancellation_token = manager.get_cancellation_token(task_id).await;
+    let (local, remote) = tokio::join!(
+        crate::sftp::checksum::local_prefix_sha256(local_path, local_size),
+        manager.remote_sha256(connection_id, remote_path, Some(local_size), task_id, &cancellation_token),
+    );
+    manager.cleanup_cancellation_token(task_id).await;
+
+    let local = local.map_err(|e| crate::error::SSHError::Io(format!("无法计算本地文件校验和: {}", e)))?;
+    let checksum = ChecksumVerification::new(local, remote?);
+    if !checksum.matched {
+        tracing::warn!(
+            "Local prefix of {} ({} bytes) differs from {}, restarting download {}",
+            local_path, local_size, remote_path, task_id
+        );
+    }
+    Ok(checksum.matched)
+}
+
+/// 获取传输队列状态
+#[tauri::command]
+pub async fn sftp_queue_state(manager: State<'_, SftpManagerState>) -> Result<TransferQueueState> {
+    Ok(manager.transfer_queue().snapshot())
+}
+
+/// 暂停传输队列：排队中的任务不再开始，正在进行的任务继续执行
+#[tauri::command]
+pub async fn sftp_queue_pause(manager: State<'_, SftpManagerState>) -> Result<TransferQueueState> {
+    let queue = manager.transfer_queue();
+    queue.pause();
+    Ok(queue.snapshot())
+}
+
+/// 恢复传输队列
+#[tauri::command]
+pub async fn sftp_queue_resume(manager: State<'_, SftpManagerState>) -> Result<TransferQueueState> {
+    let queue = manager.transfer_queue();
+    queue.resume();
+    Ok(queue.snapshot())
+}
+
+/// 调整排队顺序：`task_ids` 中的任务按给定顺序移到队首
+#[tauri::command]
+pub async fn sftp_queue_reorder(
+    manager: State<'_, SftpManagerState>,
+    task_ids: Vec<String>,
+) -> Result<TransferQueueState> {
+    let queue = manager.transfer_queue();
+    queue.reorder(&task_ids);
+    Ok(queue.snapshot())
+}
+
+/// 设置同时进行的传输任务数量（1-16）
+#[tauri::command]
+pub async fn sftp_queue_set_concurrency(
+    manager: State<'_, SftpManagerState>,
+    max_concurrent: usize,
+) -> Result<TransferQueueState> {
+    let queue = manager.transfer_queue();
+    queue.set_max_concurrent(max_concurrent);
+    Ok(queue.snapshot())
+}
+
+/// 获取传输看门狗配置
+#[tauri::command]
+pub async fn sftp_watchdog_config(manager: State<'_, SftpManagerState>) -> Result<WatchdogConfig> {
+    Ok(manager.watchdog().config())
+}
+
+/// 设置传输看门狗：无进度多少秒后发送 `sftp-task-stalled` 事件，以及多少秒后自动取消（为空时不自动取消）
+#[tauri::command]
+pub async fn sftp_watchdog_configure(
+    manager: State<'_, SftpManagerState>,
+    stall_timeout_secs: u64,
+    auto_cancel_secs: Option<u64>,
+) -> Result<WatchdogConfig> {
+    Ok(manager.watchdog().configure(WatchdogConfig { stall_timeout_secs, auto_cancel_secs }))
+}
+
 /// 上传目录及其所有子目录和文件
 ///
 /// # 参数
@@ -860,11 +1561,15 @@ pub async fn sftp_download_file(
 /// - `local_dir_path`: 本地目录路径
 /// - `remote_dir_path`: 远程目录路径
 /// - `task_id`: 上传任务的唯一 ID
+/// - `concurrency`: 同时上传的文件数量，默认 4
+/// - `symlink_policy`: 符号链接的处理方式，默认在远程重新创建链接
+/// - `conflict_policy`: 远程文件已存在时的处理策略，默认覆盖；跳过的文件记录在结果的 `skipped_files` 中
 /// - `window`: Tauri 窗口实例（用于发送进度事件）
 ///
 /// # 返回
 /// 上传结果统计信息
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn sftp_upload_directory(
     manager: State<'_, SftpManagerState>,
     pool: State<'_, DbPool>,
@@ -872,6 +1577,9 @@ pub async fn sftp_upload_directory(
     local_dir_path: String,
     remote_dir_path: String,
     task_id: String,
+    concurrency: Option<usize>,
+    symlink_policy: Option<SymlinkPolicy>,
+    conflict_policy: Option<ConflictPolicy>,
     window: tauri::Window,
 ) -> Result<UploadDirectoryResult> {
     tracing::info!("=== Upload Directory Start ===");
@@ -918,12 +1626,22 @@ pub async fn sftp_upload_directory(
         error_message: None,
         created_at: now,
         updated_at: now,
+        checksum_sha256: None,
+        checksum_verified: None,
+        transfer_kind: Some("directory".to_string()),
     };
 
     if let Ok(conn) = pool.get() {
         let _ = crate::database::repositories::UploadRecordsRepository::create(&conn, &upload_record);
     }
 
+    // 获取取消令牌（基于 task_id）
+    let cancellation_token = manager.get_cancellation_token(&task_id).await;
+
+    // 在传输队列中排队，轮到该任务时才开始
+    let item = queue_item(&task_id, &connection_id, TransferOperation::Upload, &local_dir_path, &remote_dir_path, 0);
+    let _ticket = wait_in_queue(&manager, &pool, &window, &connection_id, item, &cancellation_token).await?;
+
     // 更新状态为 uploading
     if let Ok(conn) = pool.get() {
         let _ = crate::database::repositories::UploadRecordsRepository::update_status(
@@ -934,9 +1652,6 @@ pub async fn sftp_upload_directory(
         );
     }
 
-    // 获取取消令牌（基于 task_id）
-    let cancellation_token = manager.get_cancellation_token(&task_id).await;
-
     // 🔥 为任务创建独立的 SFTP Client
     let sftp_client = manager.create_task_client(&connection_id, &task_id).await?;
     let mut client_guard = sftp_client.lock().await;
@@ -948,11 +1663,14 @@ pub async fn sftp_upload_directory(
         &window,
         &connection_id,
         &task_id,
-        &cancellation_token
+        &cancellation_token,
+        concurrency.unwrap_or(DEFAULT_UPLOAD_CONCURRENCY),
+        symlink_policy.unwrap_or_default(),
+        conflict_policy.unwrap_or_default(),
     ).await;
 
     // 🔥 清理任务 SFTP Client 和取消令牌
-    manager.cleanup_task_client(&task_id).await;
+    manager.cleanup_task_client(&task_id, result.is_ok()).await;
     manager.cleanup_cancellation_token(&task_id).await;
 
     // 处理上传结果
@@ -1011,6 +1729,7 @@ pub async fn sftp_upload_directory(
                     error_message: None,
                     completed_at: Some(chrono::Utc::now().timestamp_millis()),
                 });
+                fire_transfer_hook("upload", &connection_id, &task_id, &local_dir_path, &remote_dir_path, upload_result.total_size as i64, upload_result.total_files as i64);
             }
 
             Ok(upload_result)
@@ -1108,11 +1827,15 @@ pub async fn sftp_cancel_upload(
 /// - `remote_dir_path`: 远程目录路径
 /// - `local_dir_path`: 本地保存路径
 /// - `task_id`: 下载任务的唯一 ID
+/// - `concurrency`: 同时使用的 SFTP channel 数量，默认 4
+/// - `symlink_policy`: 符号链接的处理方式，默认在本地重新创建链接
+/// - `conflict_policy`: 本地文件已存在时的处理策略，默认覆盖；跳过的文件记录在结果的 `skipped_files` 中
 /// - `window`: Tauri 窗口实例（用于发送进度事件）
 ///
 /// # 返回
 /// 下载结果统计信息
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn sftp_download_directory(
     manager: State<'_, SftpManagerState>,
     pool: State<'_, DbPool>,
@@ -1120,6 +1843,9 @@ pub async fn sftp_download_directory(
     remote_dir_path: String,
     local_dir_path: String,
     task_id: String,
+    concurrency: Option<usize>,
+    symlink_policy: Option<SymlinkPolicy>,
+    conflict_policy: Option<ConflictPolicy>,
     window: tauri::Window,
 ) -> Result<crate::sftp::DownloadDirectoryResult> {
     tracing::info!("=== Download Directory Start ===");
@@ -1161,12 +1887,20 @@ pub async fn sftp_download_directory(
         error_message: None,
         created_at: now,
         updated_at: now,
+        transfer_kind: Some("directory".to_string()),
     };
 
     if let Ok(conn) = pool.get() {
         let _ = crate::database::repositories::DownloadRecordsRepository::create(&conn, &download_record);
     }
 
+    // 获取取消令牌（基于 task_id）
+    let cancellation_token = manager.get_cancellation_token(&task_id).await;
+
+    // 在传输队列中排队，轮到该任务时才开始
+    let item = queue_item(&task_id, &connection_id, TransferOperation::Download, &local_dir_path, &remote_dir_path, 0);
+    let _ticket = wait_in_queue(&manager, &pool, &window, &connection_id, item, &cancellation_token).await?;
+
     // 更新状态为 downloading
     if let Ok(conn) = pool.get() {
         let _ = crate::database::repositories::DownloadRecordsRepository::update_status(
@@ -1177,13 +1911,28 @@ pub async fn sftp_download_directory(
         );
     }
 
-    // 获取取消令牌（基于 task_id）
-    let cancellation_token = manager.get_cancellation_token(&task_id).await;
-
     // 🔥 为任务创建独立的 SFTP Client
     let sftp_client = manager.create_task_client(&connection_id, &task_id).await?;
     let mut client_guard = sftp_client.lock().await;
 
+    // 额外的并发 worker 各自使用一个独立的任务 SFTP Client，创建失败时使用已有的 worker 继续
+    let concurrency = concurrency.unwrap_or(DEFAULT_DOWNLOAD_CONCURRENCY).clamp(1, MAX_DOWNLOAD_CONCURRENCY);
+    let mut worker_task_ids = Vec::new();
+    let mut extra_clients = Vec::new();
+    for i in 1..concurrency {
+        let worker_task_id = format!("{}#{}", task_id, i);
+        match manager.create_task_client(&connection_id, &worker_task_id).await {
+            Ok(client) => {
+                extra_clients.push(client);
+                worker_task_ids.push(worker_task_id);
+            }
+            Err(e) => {
+                tracing::warn!("Failed to create download worker {} for task {}: {}", i, task_id, e);
+                break;
+            }
+        }
+    }
+
     // 执行下载操作
     let result = client_guard.download_directory_recursive(
         &remote_dir_path,
@@ -1192,13 +1941,21 @@ pub async fn sftp_download_directory(
         &connection_id,
         &task_id,
         &cancellation_token,
+        symlink_policy.unwrap_or_default(),
+        &extra_clients,
+        conflict_policy.unwrap_or_default(),
         |_transferred, _total| {
             // 进度回调，暂不使用
         }
     ).await;
+    drop(client_guard);
+    drop(extra_clients);
 
     // 🔥 清理任务 SFTP Client 和取消令牌
-    manager.cleanup_task_client(&task_id).await;
+    manager.cleanup_task_client(&task_id, result.is_ok()).await;
+    for worker_task_id in &worker_task_ids {
+        manager.cleanup_task_client(worker_task_id, result.is_ok()).await;
+    }
     manager.cleanup_cancellation_token(&task_id).await;
 
     // 处理下载结果
@@ -1231,6 +1988,7 @@ pub async fn sftp_download_directory(
                     error_message: None,
                     completed_at: Some(chrono::Utc::now().timestamp_millis()),
                 });
+                fire_transfer_hook("download", &connection_id, &task_id, &local_dir_path, &remote_dir_path, download_result.total_size as i64, download_result.total_files as i64);
             }
 
             Ok(download_result)
@@ -1318,3 +2076,372 @@ pub async fn sftp_cancel_download(
     
     Ok(())
 }
+
+/// 触发传输完成钩子和 webhook 通知
+///
+/// `direction` 为 `upload` 或 `download`，传输成功完成后调用
+fn fire_transfer_hook(
+    direction: &str,
+    connection_id: &str,
+    task_id: &str,
+    local_path: &str,
+    remote_path: &str,
+    bytes_transferred: i64,
+    files_completed: i64,
+) {
+    let context = serde_json::json!({
+        "direction": direction,
+        "connectionId": connection_id,
+        "taskId": task_id,
+        "localPath": local_path,
+        "remotePath": remote_path,
+        "bytesTransferred": bytes_transferred,
+        "filesCompleted": files_completed,
+    });
+    WebhookService::notify(WebhookEvent::TransferComplete, context.clone());
+    HookService::fire(HookEvent::OnTransferComplete, context);
+}
+
+/// 拼接远程路径
+fn join_remote_path(dir: &str, name: &str) -> String {
+    if dir.ends_with('/') {
+        format!("{}{}", dir, name)
+    } else {
+        format!("{}/{}", dir, name)
+    }
+}
+
+/// 双栏传输（编排命令）
+///
+/// 根据传输方向和源路径类型自动选择单文件或目录传输，按冲突策略处理已存在的目标，
+/// 传输任务在传输队列中排队执行
+///
+/// # 参数
+/// - `connection_id`: SSH 连接 ID
+/// - `local_path`: 本地路径（上传时为源，下载时为目标目录）
+/// - `remote_path`: 远程路径（下载时为源，上传时为目标目录）
+/// - `operation`: 传输方向（Upload / Download）
+/// - `conflict_policy`: 目标已存在时的处理策略，默认覆盖；目录合并时同样用于其中的每个文件
+/// - `concurrency`: 目录上传时同时上传的文件数量 / 目录下载时使用的 SFTP channel 数量
+/// - `verify_checksum`: 单文件传输完成后校验两端 SHA-256，结果写入 `checksum`
+/// - `symlink_policy`: 目录传输时符号链接的处理方式，默认保留链接
+///
+/// # 返回
+/// 传输结果
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn sftp_transfer(
+    manager: State<'_, SftpManagerState>,
+    pool: State<'_, DbPool>,
+    connection_id: String,
+    local_path: String,
+    remote_path: String,
+    operation: crate::sftp::TransferOperation,
+    conflict_policy: Option<ConflictPolicy>,
+    concurrency: Option<usize>,
+    verify_checksum: Option<bool>,
+    symlink_policy: Option<SymlinkPolicy>,
+    window: tauri::Window,
+) -> Result<crate::sftp::TransferResult> {
+    use crate::sftp::{numbered_name, ConflictAction, TransferResult};
+
+    let conflict_policy = conflict_policy.unwrap_or_default();
+    tracing::info!("=== Transfer Start ({:?}, {:?}) ===", operation, conflict_policy);
+
+    // 确定源路径、类型、修改时间和初始目标路径
+    let (source_path, is_dir, source_modified, name, target_dir) = match operation {
+        TransferOperation::Upload => {
+            let metadata = tokio::fs::metadata(&local_path).await
+                .map_err(|_| crate::error::SSHError::NotFound(format!("本地路径不存在: {}", local_path)))?;
+            let name = Path::new(&local_path)
+                .file_name()
+                .and_then(|n| n.to_str())
+                .ok_or_else(|| crate::error::SSHError::Io(format!("无效的本地路径: {}", local_path)))?
+                .to_string();
+            (local_path.clone(), metadata.is_dir(), crate::sftp::local_modified(&metadata), name, remote_path.clone())
+        }
+        TransferOperation::Download => {
+            let info = manager.stat(&connection_id, &remote_path).await?
+                .ok_or_else(|| crate::error::SSHError::NotFound(format!("远程路径不存在: {}", remote_path)))?;
+            (remote_path.clone(), info.is_dir, info.modified, info.name, local_path.clone())
+        }
+        TransferOperation::RemoteToRemote => {
+            return Err(crate::error::SSHError::NotSupported("远程到远程传输请使用 sftp_transfer_remote".to_string()));
+        }
+    };
+
+    let build_target = |name: &str| match operation {
+        TransferOperation::Upload => join_remote_path(&target_dir, name),
+        _ => Path::new(&target_dir).join(name).to_string_lossy().to_string(),
+    };
+
+    // 按冲突策略确定最终目标路径
+    let mut target_path = build_target(&name);
+    let mut attempt = 0u32;
+    loop {
+        let target_modified = match operation {
+            TransferOperation::Upload => manager.stat(&connection_id, &target_path).await?.map(|info| info.modified),
+            _ => tokio::fs::metadata(&target_path).await.ok().map(|metadata| crate::sftp::local_modified(&metadata)),
+        };
+        let Some(target_modified) = target_modified else {
+            break;
+        };
+
+        // 目录的修改时间不代表其中文件的新旧，仅比较新旧时合并目录，由目录传输逐个文件比较
+        let action = if is_dir && conflict_policy == ConflictPolicy::Newer {
+            ConflictAction::Write
+        } else {
+            conflict_policy.resolve(source_modified, target_modified)
+        };
+        match action {
+            ConflictAction::Write => break,
+            ConflictAction::Skip => {
+                tracing::info!("Target exists, skipping: {}", target_path);
+                return Ok(TransferResult {
+                    operation,
+                    source_path,
+                    target_path,
+                    is_dir,
+                    skipped: true,
+                    bytes_transferred: 0,
+                    total_files: 0,
+                    checksum: None,
+                });
+            }
+            ConflictAction::Fail => {
+                return Err(crate::error::SSHError::Io(format!("目标已存在: {}", target_path)));
+            }
+            ConflictAction::Rename => {
+                attempt += 1;
+                target_path = build_target(&numbered_name(&name, attempt, is_dir));
+            }
+        }
+    }
+
+    tracing::info!("Transferring {} -> {} (dir: {})", source_path, target_path, is_dir);
+
+    let verify_checksum = verify_checksum.unwrap_or(false);
+    let (bytes_transferred, total_files, checksum) = match (&operation, is_dir) {
+        (TransferOperation::Upload, false) => {
+            let (bytes, checksum) = upload_file(
+                &manager, &pool, &window, connection_id.clone(),
+                source_path.clone(), target_path.clone(), verify_checksum,
+            ).await?;
+            (bytes, 1, checksum)
+        }
+        (TransferOperation::Upload, true) => {
+            let task_id = format!("upload-dir-{}", uuid::Uuid::new_v4());
+            let result = sftp_upload_directory(
+                manager.clone(), pool.clone(), connection_id.clone(),
+                source_path.clone(), target_path.clone(), task_id, concurrency, symlink_policy, Some(conflict_policy), window.clone(),
+            ).await?;
+            (result.total_size, result.total_files, None)
+        }
+        (_, false) => {
+            let (bytes, checksum) = download_file(
+                &manager, &pool, &window, connection_id.clone(),
+                source_path.clone(), target_path.clone(), verify_checksum,
+            ).await?;
+            (bytes, 1, checksum)
+        }
+        (_, true) => {
+            let task_id = format!("download-dir-{}", uuid::Uuid::new_v4());
+            let result = sftp_download_directory(
+                manager.clone(), pool.clone(), connection_id.clone(),
+                source_path.clone(), target_path.clone(), task_id, concurrency, symlink_policy, Some(conflict_policy), window.clone(),
+            ).await?;
+            (result.total_size, result.total_files, None)
+        }
+    };
+
+    Ok(TransferResult {
+        operation,
+        source_path,
+        target_path,
+        is_dir,
+        skipped: false,
+        bytes_transferred,
+        total_files,
+        checksum,
+    })
+}
+
+/// 远程到远程传输进度事件名（内容为 [`TransferProgress`]）
+const REMOTE_TRANSFER_PROGRESS_EVENT: &str = "sftp-remote-transfer-progress";
+
+/// 扫描源目录，在目标连接上创建对应的目录结构和符号链接
+///
+/// 返回需要复制的文件列表：(源路径, 目标路径, 大小)
+async fn plan_remote_copy(
+    source: &mut SftpClient,
+    target: &mut SftpClient,
+    source_dir: &str,
+    target_dir: &str,
+    cancellation_token: &tokio_util::sync::CancellationToken,
+) -> Result<Vec<(String, String, u64)>> {
+    target.create_dir(target_dir, true).await?;
+
+    let mut files = Vec::new();
+    let mut dir_queue = vec![(source_dir.to_string(), target_dir.to_string())];
+    while let Some((source_path, target_path)) = dir_queue.pop() {
+        if cancellation_token.is_cancelled() {
+            return Err(crate::error::SSHError::Io("传输已取消".to_string()));
+        }
+
+        for entry in source.list_dir(&source_path).await? {
+            let entry_source = join_remote_path(&source_path, &entry.name);
+            let entry_target = join_remote_path(&target_path, &entry.name);
+            if let Some(link_target) = entry.link_target.as_deref().filter(|_| entry.is_symlink) {
+                target.create_symlink(&entry_target, link_target, true).await?;
+            } else if entry.is_dir {
+                target.create_dir(&entry_target, false).await?;
+                dir_queue.push((entry_source, entry_target));
+            } else {
+                files.push((entry_source, entry_target, entry.size));
+            }
+        }
+    }
+    Ok(files)
+}
+
+/// 在两个连接之间直接复制文件或目录
+///
+/// 源连接的任务客户端读取、目标连接的任务客户端写入，数据不经过本地磁盘。
+/// 任务在传输队列中排队执行，可通过 `sftp_cancel_transfer` 取消，进度以 `sftp-remote-transfer-progress` 事件发送
+///
+/// # 参数
+/// - `source_connection_id`: 源 SSH 连接 ID
+/// - `source_path`: 源文件或目录路径
+/// - `target_connection_id`: 目标 SSH 连接 ID（可以与源连接相同）
+/// - `target_path`: 目标路径（已存在的文件会被覆盖，目录会合并）
+/// - `task_id`: 任务 ID，为空时自动生成
+///
+/// # 返回
+/// 传输结果
+#[tauri::command]
+pub async fn sftp_transfer_remote(
+    manager: State<'_, SftpManagerState>,
+    source_connection_id: String,
+    source_path: String,
+    target_connection_id: String,
+    target_path: String,
+    task_id: Option<String>,
+    window: tauri::Window,
+) -> Result<crate::sftp::TransferResult> {
+    use std::time::{Duration, Instant};
+
+    let task_id = task_id.unwrap_or_else(|| format!("remote-{}", uuid::Uuid::new_v4()));
+    tracing::info!(
+        "=== Remote Transfer Start ({}) {}:{} -> {}:{} ===",
+        task_id, source_connection_id, source_path, target_connection_id, target_path
+    );
+
+    let info = manager.stat(&source_connection_id, &source_path).await?
+        .ok_or_else(|| crate::error::SSHError::NotFound(format!("远程路径不存在: {}", source_path)))?;
+
+    let mut progress = TransferProgress {
+        id: task_id.clone(),
+        operation: TransferOperation::RemoteToRemote,
+        source: TransferSource::Remote { connection_id: source_connection_id.clone(), path: source_path.clone() },
+        destination: TransferSource::Remote { connection_id: target_connection_id.clone(), path: target_path.clone() },
+        file_size: if info.is_dir { 0 } else { info.size },
+        transferred: 0,
+        speed: 0,
+        status: TransferStatus::Pending,
+    };
+
+    // 在传输队列中排队，轮到该任务时才开始
+    let cancellation_token = manager.get_cancellation_token(&task_id).await;
+    let _ticket = match manager.transfer_queue().enqueue(progress.clone(), &cancellation_token).await {
+        Ok(ticket) => ticket,
+        Err(e) => {
+            manager.cleanup_cancellation_token(&task_id).await;
+            return Err(e);
+        }
+    };
+
+    // 源和目标各使用一个独立的任务 SFTP Client
+    let target_task_id = format!("{}#target", task_id);
+    let result = async {
+        let source_client = manager.create_task_client(&source_connection_id, &task_id).await?;
+        let target_client = manager.create_task_client(&target_connection_id, &target_task_id).await?;
+        let mut source = source_client.lock().await;
+        let mut target = target_client.lock().await;
+
+        let files = if info.is_dir {
+            plan_remote_copy(&mut source, &mut target, &source_path, &target_path, &cancellation_token).await?
+        } else {
+            vec![(source_path.clone(), target_path.clone(), info.size)]
+        };
+        progress.file_size = files.iter().map(|(_, _, size)| size).sum();
+        progress.status = TransferStatus::InProgress;
+        let _ = window.emit(REMOTE_TRANSFER_PROGRESS_EVENT, &progress);
+
+        let start_time = Instant::now();
+        let last_emit_time = std::sync::Mutex::new(Instant::now());
+        let mut completed_bytes = 0u64;
+        for (file_source, file_target, _) in &files {
+            let copied = source.copy_file_to(
+                file_source,
+                &target,
+                file_target,
+                &cancellation_token,
+                |transferred, _total| {
+                    // 节流：每 200ms 最多发送一次事件
+                    let now = Instant::now();
+                    let mut last = last_emit_time.lock().unwrap();
+                    if now.duration_since(*last) >= Duration::from_millis(200) {
+                        *last = now;
+                        let mut event = progress.clone();
+                        event.transferred = completed_bytes + transferred;
+                        event.speed = (event.transferred as f64 / start_time.elapsed().as_secs_f64().max(0.001)) as u64;
+                        let _ = window.emit(REMOTE_TRANSFER_PROGRESS_EVENT, &event);
+                    }
+                },
+            ).await?;
+            completed_bytes += copied;
+        }
+
+        progress.transferred = completed_bytes;
+        progress.speed = (completed_bytes as f64 / start_time.elapsed().as_secs_f64().max(0.001)) as u64;
+        Ok::<_, crate::error::SSHError>(files.len() as u64)
+    }
+    .await;
+
+    // 🔥 清理任务 SFTP Client 和取消令牌
+    manager.cleanup_task_client(&task_id, result.is_ok()).await;
+    manager.cleanup_task_client(&target_task_id, result.is_ok()).await;
+    manager.cleanup_cancellation_token(&task_id).await;
+
+    progress.status = match &result {
+        Ok(_) => TransferStatus::Completed,
+        Err(_) if cancellation_token.is_cancelled() => TransferStatus::Cancelled,
+        Err(e) => TransferStatus::Failed { reason: e.to_string() },
+    };
+    let _ = window.emit(REMOTE_TRANSFER_PROGRESS_EVENT, &progress);
+
+    let total_files = result?;
+    tracing::info!("Remote transfer {} completed: {} files, {} bytes", task_id, total_files, progress.transferred);
+
+    Ok(crate::sftp::TransferResult {
+        operation: TransferOperation::RemoteToRemote,
+        source_path,
+        target_path,
+        is_dir: info.is_dir,
+        skipped: false,
+        bytes_transferred: progress.transferred,
+        total_files,
+
+        checksum: None,
+    })
+}
+
+/// 取消远程到远程传输
+#[tauri::command]
+pub async fn sftp_cancel_transfer(
+    manager: State<'_, SftpManagerState>,
+    task_id: String,
+) -> Result<()> {
+    tracing::info!("Cancelling remote transfer {}", task_id);
+    manager.cancel_task(&task_id).await
+}
@@ -0,0 +1,94 @@
+//! 命令片段命令
+
+use crate::commands::ssh_session::get_current_user_info;
+use crate::database::DbPool;
+use crate::database::repositories::SnippetRepository;
+use crate::models::snippet::Snippet;
+use tauri::State;
+
+/// 创建命令片段
+#[tauri::command]
+pub async fn snippet_create(
+    pool: State<'_, DbPool>,
+    name: String,
+    command: String,
+    description: Option<String>,
+) -> Result<Snippet, String> {
+    let current_user = get_current_user_info(&pool);
+    let now = chrono::Utc::now().timestamp();
+
+    let snippet = Snippet {
+        id: uuid::Uuid::new_v4().to_string(),
+        user_id: current_user.user_id,
+        name,
+        command,
+        description,
+        created_at: now,
+        updated_at: now,
+    };
+
+    let repo = SnippetRepository::new(pool.inner().clone());
+    repo.create(&snippet)
+        .map_err(|e| format!("Failed to create snippet: {}", e))?;
+
+    Ok(snippet)
+}
+
+/// 列出当前用户的所有命令片段
+#[tauri::command]
+pub async fn snippet_list(
+    pool: State<'_, DbPool>,
+) -> Result<Vec<Snippet>, String> {
+    let current_user = get_current_user_info(&pool);
+    let repo = SnippetRepository::new(pool.inner().clone());
+    repo.find_by_user(&current_user.user_id)
+        .map_err(|e| format!("Failed to list snippets: {}", e))
+}
+
+/// 更新命令片段
+#[tauri::command]
+pub async fn snippet_update(
+    pool: State<'_, DbPool>,
+    snippet_id: String,
+    name: Option<String>,
+    command: Option<String>,
+    description: Option<String>,
+) -> Result<Snippet, String> {
+    let current_user = get_current_user_info(&pool);
+    let repo = SnippetRepository::new(pool.inner().clone());
+
+    let mut snippet = repo.find_by_id(&snippet_id)
+        .map_err(|e| format!("Failed to find snippet: {}", e))?
+        .ok_or_else(|| format!("Snippet not found: {}", snippet_id))?;
+
+    if snippet.user_id != current_user.user_id {
+        return Err("Snippet belongs to different user".to_string());
+    }
+
+    if let Some(name) = name {
+        snippet.name = name;
+    }
+    if let Some(command) = command {
+        snippet.command = command;
+    }
+    if description.is_some() {
+        snippet.description = description;
+    }
+    snippet.updated_at = chrono::Utc::now().timestamp();
+
+    repo.update(&snippet)
+        .map_err(|e| format!("Failed to update snippet: {}", e))?;
+
+    Ok(snippet)
+}
+
+/// 删除命令片段
+#[tauri::command]
+pub async fn snippet_delete(
+    pool: State<'_, DbPool>,
+    snippet_id: String,
+) -> Result<(), String> {
+    let repo = SnippetRepository::new(pool.inner().clone());
+    repo.delete(&snippet_id)
+        .map_err(|e| format!("Failed to delete snippet: {}", e))
+}
@@ -0,0 +1,257 @@
+//! OpenSSH 配置导入命令
+
+use crate::commands::session::{default_identity_auth, SSHManagerState};
+use crate::commands::ssh_session::{get_current_user_info, stored_session_info, CurrentUserInfo};
+use crate::database::DbPool;
+use crate::database::repositories::SshSessionRepository;
+use crate::models::ssh_session::{AuthMethod as DbAuthMethod, SshSession};
+use crate::services::CryptoService;
+use crate::ssh::config_import::{self, SshConfigHost};
+use crate::ssh::events::SessionChangeKind;
+use crate::ssh::session::{AuthMethod, SessionConfig};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tauri::State;
+
+/// 跳板机链路的最大深度（防止 ProxyJump 循环引用）
+const MAX_JUMP_DEPTH: usize = 8;
+
+/// 导入的主机
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportedSshHost {
+    pub alias: String,
+    /// 转换后的会话配置（包含跳板机链路）
+    pub config: SessionConfig,
+    /// 写入数据库后的会话 ID（仅预览时为空）
+    pub session_id: Option<String>,
+    /// 已存在同名会话，未重复创建
+    pub skipped: bool,
+}
+
+/// 导入 OpenSSH 客户端配置
+///
+/// `path` 默认为 ~/.ssh/config。`save` 为 false 时只返回转换结果用于预览；
+/// 为 true 时写入数据库，已存在同名会话的主机会跳过，ProxyJump 保存为跳板机会话引用
+#[tauri::command]
+pub async fn session_import_ssh_config(
+    pool: State<'_, DbPool>,
+    manager: State<'_, SSHManagerState>,
+    path: Option<String>,
+    save: bool,
+) -> Result<Vec<ImportedSshHost>, String> {
+    let path = match path.filter(|p| !p.trim().is_empty()) {
+        Some(path) => PathBuf::from(path),
+        None => dirs::home_dir()
+            .ok_or_else(|| "Cannot determine home directory".to_string())?
+            .join(".ssh")
+            .join("config"),
+    };
+    let hosts = config_import::load_file(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+
+    let default_user = std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_else(|_| "root".to_string());
+    let default_auth = default_identity_auth().unwrap_or_else(|| AuthMethod::PublicKey {
+        private_key_path: dirs::home_dir()
+            .map(|home| home.join(".ssh").join("id_rsa").to_string_lossy().to_string())
+            .unwrap_or_default(),
+        passphrase: None,
+    });
+
+    let mut imported = Vec::with_capacity(hosts.len());
+    for host in &hosts {
+        let mut config = host.to_session_config(&default_user, &default_auth);
+        config.jump_host = build_jump_chain(&hosts, &host.proxy_jump, &default_user, &default_auth, 0)?;
+        imported.push(ImportedSshHost {
+            alias: host.alias.clone(),
+            config,
+            session_id: None,
+            skipped: false,
+        });
+    }
+
+    if save {
+        let mut importer = Importer {
+            repo: SshSessionRepository::new(pool.inner().clone()),
+            current_user: get_current_user_info(&pool),
+            manager: manager.inner(),
+            ids: HashMap::new(),
+            default_user: &default_user,
+            default_auth: &default_auth,
+        };
+        importer.save(&hosts, &mut imported)?;
+    }
+
+    tracing::info!("Imported {} host(s) from {} (save: {})", imported.len(), path.display(), save);
+    Ok(imported)
+}
+
+/// 构建跳板机链路，返回最后一跳（其 `jump_host` 指向前一跳）
+///
+/// 链路第一跳是别名时，沿用该主机自己的 ProxyJump
+fn build_jump_chain(
+    hosts: &[SshConfigHost],
+    hops: &[String],
+    default_user: &str,
+    default_auth: &AuthMethod,
+    depth: usize,
+) -> Result<Option<Box<SessionConfig>>, String> {
+    if hops.is_empty() {
+        return Ok(None);
+    }
+    if depth >= MAX_JUMP_DEPTH {
+        return Err(format!("ProxyJump chain too deep: {}", hops.join(",")));
+    }
+
+    let mut previous = None;
+    for (index, hop) in hops.iter().enumerate() {
+        let mut config = match config_import::find_host(hosts, hop) {
+            Some(host) => {
+                let mut config = host.to_session_config(default_user, default_auth);
+                if index == 0 {
+                    config.jump_host = build_jump_chain(hosts, &host.proxy_jump, default_user, default_auth, depth + 1)?;
+                }
+                config
+            }
+            None => config_import::hop_session_config(hop, default_user, default_auth)
+                .map_err(|e| format!("Invalid ProxyJump host {}: {}", hop, e))?,
+        };
+        if index > 0 {
+            config.jump_host = previous;
+        }
+        previous = Some(Box::new(config));
+    }
+    Ok(previous)
+}
+
+/// 将导入的主机写入数据库
+struct Importer<'a> {
+    repo: SshSessionRepository,
+    current_user: CurrentUserInfo,
+    manager: &'a SSHManagerState,
+    /// 别名（或跳板机目标）-> 会话 ID
+    ids: HashMap<String, String>,
+    default_user: &'a str,
+    default_auth: &'a AuthMethod,
+}
+
+impl Importer<'_> {
+    fn save(&mut self, hosts: &[SshConfigHost], imported: &mut [ImportedSshHost]) -> Result<(), String> {
+        // 已存在的同名会话不重复创建
+        let existing = self.repo.find_by_user(&self.current_user.user_id)
+            .map_err(|e| format!("Failed to list sessions: {}", e))?;
+        for session in existing {
+            self.ids.entry(session.name.clone()).or_insert(session.id);
+        }
+
+        let mut created = Vec::new();
+        for (host, item) in hosts.iter().zip(imported.iter_mut()) {
+            if let Some(id) = self.ids.get(&host.alias) {
+                item.session_id = Some(id.clone());
+                item.skipped = true;
+                continue;
+            }
+            let config = host.to_session_config(self.default_user, self.default_auth);
+            let id = self.create(&config)?;
+            self.ids.insert(host.alias.clone(), id.clone());
+            item.session_id = Some(id);
+            created.push(host);
+        }
+
+        // 先保存每个主机直接使用的跳板机，再补全多级链路中间跳的引用
+        for host in &created {
+            if let Some(last) = host.proxy_jump.last() {
+                let jump_id = self.hop_id(hosts, last)?;
+                self.link(&host.alias, &jump_id, true)?;
+            }
+        }
+        for host in &created {
+            for pair in host.proxy_jump.windows(2) {
+                let previous = self.hop_id(hosts, &pair[0])?;
+                self.hop_id(hosts, &pair[1])?;
+                self.link(&pair[1], &previous, false)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 跳板机对应的会话 ID，不是已知别名时创建新会话
+    fn hop_id(&mut self, hosts: &[SshConfigHost], hop: &str) -> Result<String, String> {
+        if let Some(id) = self.ids.get(hop) {
+            return Ok(id.clone());
+        }
+        let config = match config_import::find_host(hosts, hop) {
+            Some(host) => host.to_session_config(self.default_user, self.default_auth),
+            None => config_import::hop_session_config(hop, self.default_user, self.default_auth)
+                .map_err(|e| format!("Invalid ProxyJump host {}: {}", hop, e))?,
+        };
+        let id = self.create(&config)?;
+        self.ids.insert(hop.to_string(), id.clone());
+        Ok(id)
+    }
+
+    /// 设置跳板机引用；`overwrite` 为 false 时保留已有的引用
+    fn link(&self, alias: &str, jump_id: &str, overwrite: bool) -> Result<(), String> {
+        let Some(id) = self.ids.get(alias) else {
+            return Ok(());
+        };
+        if id == jump_id {
+            return Ok(());
+        }
+        if !overwrite && self.repo.find_jump_session_id(id).ok().flatten().is_some() {
+            return Ok(());
+        }
+        self.repo.set_jump_session_id(id, Some(jump_id))
+            .map_err(|e| format!("Failed to set jump host: {}", e))
+    }
+
+    fn create(&self, config: &SessionConfig) -> Result<String, String> {
+        let auth_method = match &config.auth_method {
+            AuthMethod::Password { password } => DbAuthMethod::Password { password: password.clone() },
+            AuthMethod::PublicKey { private_key_path, passphrase } => DbAuthMethod::PrivateKey {
+                private_key_path: private_key_path.clone(),
+                passphrase: passphrase.clone(),
+                key_data: None,
+            },
+        };
+        let (auth_method_encrypted, auth_nonce) = CryptoService::encrypt_password(
+            &serde_json::to_string(&auth_method).unwrap(),
+            &self.current_user.device_id,
+        )
+            .map_err(|e| format!("Failed to encrypt auth method: {}", e))?;
+
+        let now = chrono::Utc::now().timestamp();
+        let session = SshSession {
+            id: uuid::Uuid::new_v4().to_string(),
+            user_id: self.current_user.user_id.clone(),
+            name: config.name.clone(),
+            host: config.host.clone(),
+            port: config.port,
+            username: config.username.clone(),
+            group_name: config.group.clone(),
+            terminal_type: None,
+            columns: None,
+            rows: None,
+            auth_method_encrypted,
+            auth_nonce,
+            auth_key_salt: None,
+            server_ver: 0,
+            client_ver: 1,
+            is_dirty: true,
+            last_synced_at: None,
+            is_deleted: false,
+            deleted_at: None,
+            created_at: now,
+            updated_at: now,
+        };
+
+        self.repo.create(&session)
+            .map_err(|e| format!("Failed to create session: {}", e))?;
+        self.manager.change_log().record(SessionChangeKind::Added, &session.id, Some(stored_session_info(&session)));
+
+        Ok(session.id)
+    }
+}
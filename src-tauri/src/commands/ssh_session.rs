@@ -1,7 +1,16 @@
 use crate::database::DbPool;
-use crate::database::repositories::{UserAuthRepository, SshSessionRepository};
+use crate::database::repositories::{UserAuthRepository, SshSessionRepository, IdentityRepository, SessionGroupRepository, VariableRepository};
 use crate::models::ssh_session::{SshSession, AuthMethod};
 use crate::services::CryptoService;
+use crate::commands::session::SSHManagerState;
+use crate::ssh::dns::DnsResolverConfig;
+use crate::ssh::algorithms::{AlgorithmCatalog, AlgorithmPreferences};
+use crate::ssh::elevation::ElevationPolicy;
+use crate::ssh::encoding::TerminalEncoding;
+use crate::ssh::events::SessionChangeKind;
+use crate::ssh::login_script::LoginStep;
+use crate::ssh::profiles::{self, ConnectionProfile};
+use crate::ssh::session::{SessionInfo, SessionStatus};
 use tauri::State;
 
 /// 未登录用户的固定用户ID
@@ -11,13 +20,13 @@ const ANONYMOUS_USER_ID: &str = "anonymous_local";
 const ANONYMOUS_DEVICE_ID: &str = "ssh-terminal-local-device-v1";
 
 /// 当前用户信息
-struct CurrentUserInfo {
-    user_id: String,
-    device_id: String,
+pub(crate) struct CurrentUserInfo {
+    pub(crate) user_id: String,
+    pub(crate) device_id: String,
 }
 
 /// 获取当前用户信息
-fn get_current_user_info(pool: &DbPool) -> CurrentUserInfo {
+pub(crate) fn get_current_user_info(pool: &DbPool) -> CurrentUserInfo {
     let auth_repo = UserAuthRepository::new(pool.clone());
 
     match auth_repo.find_current() {
@@ -36,7 +45,7 @@ fn get_current_user_info(pool: &DbPool) -> CurrentUserInfo {
 }
 
 /// 将前端的 AuthMethod 转换为内部的 AuthMethod 枚举
-fn convert_front_end_auth_method(auth_method: &serde_json::Value) -> Result<AuthMethod, String> {
+pub(crate) fn convert_front_end_auth_method(auth_method: &serde_json::Value) -> Result<AuthMethod, String> {
     if let Some(password_obj) = auth_method.get("Password") {
         if let Some(password) = password_obj.get("password") {
             match password.as_str() {
@@ -102,10 +111,27 @@ fn auth_method_to_frontend(auth_method: &AuthMethod) -> serde_json::Value {
     }
 }
 
+/// 由数据库会话构建会话信息（用于变更事件）
+pub(crate) fn stored_session_info(session: &SshSession) -> SessionInfo {
+    SessionInfo {
+        id: session.id.clone(),
+        name: session.name.clone(),
+        host: session.host.clone(),
+        port: session.port,
+        username: session.username.clone(),
+        status: SessionStatus::Disconnected { reason: None },
+        connected_at: None,
+        group: session.group_name.clone(),
+        connection_session_id: None,
+        connection_id: None,
+    }
+}
+
 /// 创建 SSH 会话并保存到数据库
 #[tauri::command]
 pub async fn db_ssh_session_create(
     pool: State<'_, DbPool>,
+    manager: State<'_, SSHManagerState>,
     config: serde_json::Value,
 ) -> Result<String, String> {
     let current_user = get_current_user_info(&pool);
@@ -192,6 +218,8 @@ pub async fn db_ssh_session_create(
     repo.create(&session)
         .map_err(|e| format!("Failed to create session: {}", e))?;
 
+    manager.change_log().record(SessionChangeKind::Added, &session_id, Some(stored_session_info(&session)));
+
     tracing::info!("Created SSH session: {} (user: {})", session_id, current_user.user_id);
 
     Ok(session_id)
@@ -201,6 +229,7 @@ pub async fn db_ssh_session_create(
 #[tauri::command]
 pub async fn db_ssh_session_update(
     pool: State<'_, DbPool>,
+    manager: State<'_, SSHManagerState>,
     session_id: String,
     updates: serde_json::Value,
 ) -> Result<(), String> {
@@ -265,6 +294,8 @@ pub async fn db_ssh_session_update(
     repo.update(&session)
         .map_err(|e| format!("Failed to update session: {}", e))?;
 
+    manager.change_log().record(SessionChangeKind::Updated, &session_id, Some(stored_session_info(&session)));
+
     tracing::info!("Updated SSH session: {}", session_id);
 
     Ok(())
@@ -274,6 +305,7 @@ pub async fn db_ssh_session_update(
 #[tauri::command]
 pub async fn db_ssh_session_delete(
     pool: State<'_, DbPool>,
+    manager: State<'_, SSHManagerState>,
     session_id: String,
 ) -> Result<(), String> {
     let current_user = get_current_user_info(&pool);
@@ -291,6 +323,13 @@ pub async fn db_ssh_session_delete(
     repo.delete(&session_id)
         .map_err(|e| format!("Failed to delete session: {}", e))?;
 
+    if let Err(e) = VariableRepository::new(pool.inner().clone()).delete_for_session(&current_user.user_id, &session_id) {
+        tracing::warn!("Failed to delete variables of session {}: {}", session_id, e);
+    }
+    crate::commands::session_note::delete_session_notes(&pool, &session_id).await;
+
+    manager.change_log().record(SessionChangeKind::Removed, &session_id, None);
+
     tracing::info!("Deleted SSH session: {}", session_id);
 
     Ok(())
@@ -422,6 +461,21 @@ pub async fn db_ssh_session_migrate_to_user(
     )
         .map_err(|e| format!("Failed to migrate sessions: {}", e))?;
 
+    // 会话引用的共享身份也需要一并迁移
+    let identity_repo = IdentityRepository::new(pool.inner().clone());
+    identity_repo.batch_update_user_id(
+        ANONYMOUS_USER_ID,
+        &current_user.user_id,
+        ANONYMOUS_DEVICE_ID,
+        &current_user.device_id,
+    )
+        .map_err(|e| format!("Failed to migrate identities: {}", e))?;
+
+    // 分组元数据一并迁移
+    SessionGroupRepository::new(pool.inner().clone())
+        .batch_update_user_id(ANONYMOUS_USER_ID, &current_user.user_id)
+        .map_err(|e| format!("Failed to migrate session groups: {}", e))?;
+
     if migrated_count > 0 {
         tracing::info!(
             "Successfully migrated {} sessions from anonymous to user {}",
@@ -432,3 +486,482 @@ pub async fn db_ssh_session_migrate_to_user(
 
     Ok(migrated_count)
 }
+
+/// 设置会话的 DNS 解析器（None 表示使用全局设置）
+#[tauri::command]
+pub async fn db_ssh_session_set_dns_resolver(
+    pool: State<'_, DbPool>,
+    manager: State<'_, SSHManagerState>,
+    session_id: String,
+    resolver: Option<DnsResolverConfig>,
+) -> Result<(), String> {
+    if let Some(resolver) = &resolver {
+        resolver.validate()?;
+    }
+
+    let current_user = get_current_user_info(&pool);
+    let repo = SshSessionRepository::new(pool.inner().clone());
+
+    let session = repo.find_by_id(&session_id)
+        .map_err(|e| format!("Failed to find session: {}", e))?
+        .ok_or_else(|| format!("Session not found: {}", session_id))?;
+    if session.user_id != current_user.user_id {
+        return Err("Session belongs to different user".to_string());
+    }
+
+    let resolver_json = resolver
+        .as_ref()
+        .map(serde_json::to_string)
+        .transpose()
+        .map_err(|e| format!("Failed to serialize DNS resolver: {}", e))?;
+    repo.set_dns_resolver(&session_id, resolver_json.as_deref())
+        .map_err(|e| format!("Failed to set DNS resolver: {}", e))?;
+
+    manager.set_session_dns_resolver(&session_id, resolver).await;
+
+    Ok(())
+}
+
+/// 获取会话的 DNS 解析器
+#[tauri::command]
+pub async fn db_ssh_session_get_dns_resolver(
+    pool: State<'_, DbPool>,
+    session_id: String,
+) -> Result<Option<DnsResolverConfig>, String> {
+    let repo = SshSessionRepository::new(pool.inner().clone());
+    let resolver_json = repo.find_dns_resolver(&session_id)
+        .map_err(|e| format!("Failed to get DNS resolver: {}", e))?;
+
+    resolver_json
+        .map(|json| serde_json::from_str(&json))
+        .transpose()
+        .map_err(|e| format!("Failed to parse DNS resolver: {}", e))
+}
+
+/// 设置会话连接后执行的远程命令（空或 None 表示登录 shell）
+#[tauri::command]
+pub async fn db_ssh_session_set_remote_command(
+    pool: State<'_, DbPool>,
+    manager: State<'_, SSHManagerState>,
+    session_id: String,
+    command: Option<String>,
+) -> Result<(), String> {
+    let command = command
+        .map(|c| c.trim().to_string())
+        .filter(|c| !c.is_empty());
+
+    let current_user = get_current_user_info(&pool);
+    let repo = SshSessionRepository::new(pool.inner().clone());
+
+    let session = repo.find_by_id(&session_id)
+        .map_err(|e| format!("Failed to find session: {}", e))?
+        .ok_or_else(|| format!("Session not found: {}", session_id))?;
+    if session.user_id != current_user.user_id {
+        return Err("Session belongs to different user".to_string());
+    }
+
+    repo.set_remote_command(&session_id, command.as_deref())
+        .map_err(|e| format!("Failed to set remote command: {}", e))?;
+
+    // 已加载到内存的会话同步更新（空字符串恢复为登录 shell）
+    let update = crate::ssh::session::SessionConfigUpdate {
+        remote_command: Some(command.unwrap_or_default()),
+        ..Default::default()
+    };
+    if let Err(e) = manager.update_session(&session_id, update).await {
+        tracing::debug!("Session {} not loaded in memory: {}", session_id, e);
+    }
+
+    Ok(())
+}
+
+/// 获取会话连接后执行的远程命令
+#[tauri::command]
+pub async fn db_ssh_session_get_remote_command(
+    pool: State<'_, DbPool>,
+    session_id: String,
+) -> Result<Option<String>, String> {
+    let repo = SshSessionRepository::new(pool.inner().clone());
+    repo.find_remote_command(&session_id)
+        .map_err(|e| format!("Failed to get remote command: {}", e))
+}
+
+/// 设置会话的字符编码
+#[tauri::command]
+pub async fn db_ssh_session_set_encoding(
+    pool: State<'_, DbPool>,
+    manager: State<'_, SSHManagerState>,
+    session_id: String,
+    encoding: TerminalEncoding,
+) -> Result<(), String> {
+    let current_user = get_current_user_info(&pool);
+    let repo = SshSessionRepository::new(pool.inner().clone());
+
+    let session = repo.find_by_id(&session_id)
+        .map_err(|e| format!("Failed to find session: {}", e))?
+        .ok_or_else(|| format!("Session not found: {}", session_id))?;
+    if session.user_id != current_user.user_id {
+        return Err("Session belongs to different user".to_string());
+    }
+
+    // 以 serde 名称保存（如 "gbk"），UTF-8 保存为 NULL
+    let name = match serde_json::to_value(encoding) {
+        Ok(serde_json::Value::String(name)) if !encoding.is_utf8() => Some(name),
+        _ => None,
+    };
+    repo.set_encoding(&session_id, name.as_deref())
+        .map_err(|e| format!("Failed to set encoding: {}", e))?;
+
+    // 已加载到内存的会话同步更新（下次连接生效）
+    let update = crate::ssh::session::SessionConfigUpdate {
+        encoding: Some(encoding),
+        ..Default::default()
+    };
+    if let Err(e) = manager.update_session(&session_id, update).await {
+        tracing::debug!("Session {} not loaded in memory: {}", session_id, e);
+    }
+
+    Ok(())
+}
+
+/// 获取会话的字符编码
+#[tauri::command]
+pub async fn db_ssh_session_get_encoding(
+    pool: State<'_, DbPool>,
+    session_id: String,
+) -> Result<TerminalEncoding, String> {
+    let repo = SshSessionRepository::new(pool.inner().clone());
+    let name = repo.find_encoding(&session_id)
+        .map_err(|e| format!("Failed to get encoding: {}", e))?;
+
+    Ok(name
+        .and_then(|name| serde_json::from_value(serde_json::Value::String(name)).ok())
+        .unwrap_or_default())
+}
+
+/// 设置会话的跳板机（引用另一个已保存的会话，None 表示直连）
+#[tauri::command]
+pub async fn db_ssh_session_set_jump_session(
+    pool: State<'_, DbPool>,
+    session_id: String,
+    jump_session_id: Option<String>,
+) -> Result<(), String> {
+    let current_user = get_current_user_info(&pool);
+    let repo = SshSessionRepository::new(pool.inner().clone());
+
+    for id in std::iter::once(&session_id).chain(jump_session_id.as_ref()) {
+        let session = repo.find_by_id(id)
+            .map_err(|e| format!("Failed to find session: {}", e))?
+            .ok_or_else(|| format!("Session not found: {}", id))?;
+        if session.user_id != current_user.user_id {
+            return Err("Session belongs to different user".to_string());
+        }
+    }
+    if jump_session_id.as_deref() == Some(session_id.as_str()) {
+        return Err("Session cannot use itself as jump host".to_string());
+    }
+
+    // 下次从数据库加载会话时生效
+    repo.set_jump_session_id(&session_id, jump_session_id.as_deref())
+        .map_err(|e| format!("Failed to set jump host: {}", e))
+}
+
+/// 获取会话的跳板机会话 ID
+#[tauri::command]
+pub async fn db_ssh_session_get_jump_session(
+    pool: State<'_, DbPool>,
+    session_id: String,
+) -> Result<Option<String>, String> {
+    let repo = SshSessionRepository::new(pool.inner().clone());
+    repo.find_jump_session_id(&session_id)
+        .map_err(|e| format!("Failed to get jump host: {}", e))
+}
+
+/// 设置会话的登录脚本
+///
+/// 脚本使用设备密钥加密保存，空列表表示清除。应答中的 `${password}` 在连接时替换为会话密码
+#[tauri::command]
+pub async fn db_ssh_session_set_login_script(
+    pool: State<'_, DbPool>,
+    manager: State<'_, SSHManagerState>,
+    session_id: String,
+    steps: Vec<LoginStep>,
+) -> Result<(), String> {
+    let current_user = get_current_user_info(&pool);
+    let repo = SshSessionRepository::new(pool.inner().clone());
+
+    let session = repo.find_by_id(&session_id)
+        .map_err(|e| format!("Failed to find session: {}", e))?
+        .ok_or_else(|| format!("Session not found: {}", session_id))?;
+    if session.user_id != current_user.user_id {
+        return Err("Session belongs to different user".to_string());
+    }
+    if steps.iter().any(|step| step.prompt.is_empty()) {
+        return Err("Login script prompt cannot be empty".to_string());
+    }
+
+    let encrypted = if steps.is_empty() {
+        None
+    } else {
+        let json = serde_json::to_string(&steps)
+            .map_err(|e| format!("Failed to serialize login script: {}", e))?;
+        Some(CryptoService::encrypt_password(&json, &current_user.device_id)
+            .map_err(|e| format!("Failed to encrypt login script: {}", e))?)
+    };
+    repo.set_login_script(&session_id, encrypted.as_ref().map(|(s, n)| (s.as_str(), n.as_str())))
+        .map_err(|e| format!("Failed to set login script: {}", e))?;
+
+    // 已加载到内存的会话同步更新（下次连接生效）
+    let update = crate::ssh::session::SessionConfigUpdate {
+        login_script: Some(steps),
+        ..Default::default()
+    };
+    if let Err(e) = manager.update_session(&session_id, update).await {
+        tracing::debug!("Session {} not loaded in memory: {}", session_id, e);
+    }
+
+    Ok(())
+}
+
+/// 获取会话的登录脚本（解密后返回，用于编辑）
+#[tauri::command]
+pub async fn db_ssh_session_get_login_script(
+    pool: State<'_, DbPool>,
+    session_id: String,
+) -> Result<Vec<LoginStep>, String> {
+    let current_user = get_current_user_info(&pool);
+    let repo = SshSessionRepository::new(pool.inner().clone());
+
+    let session = repo.find_by_id(&session_id)
+        .map_err(|e| format!("Failed to find session: {}", e))?
+        .ok_or_else(|| format!("Session not found: {}", session_id))?;
+    if session.user_id != current_user.user_id {
+        return Err("Session belongs to different user".to_string());
+    }
+
+    let Some((encrypted, nonce)) = repo.find_login_script(&session_id)
+        .map_err(|e| format!("Failed to get login script: {}", e))?
+    else {
+        return Ok(Vec::new());
+    };
+
+    let json = CryptoService::decrypt_password(&encrypted, &nonce, &current_user.device_id)
+        .map_err(|e| format!("Failed to decrypt login script: {}", e))?;
+    serde_json::from_str(&json).map_err(|e| format!("Failed to parse login script: {}", e))
+}
+
+/// 设置会话是否受保护
+///
+/// 受保护会话中输入 `rm -rf`、`drop database`、`shutdown` 等危险命令时，
+/// 回车需要经过前端确认才会发送（下次连接生效）
+#[tauri::command]
+pub async fn db_ssh_session_set_protected(
+    pool: State<'_, DbPool>,
+    manager: State<'_, SSHManagerState>,
+    session_id: String,
+    protected: bool,
+) -> Result<(), String> {
+    let current_user = get_current_user_info(&pool);
+    let repo = SshSessionRepository::new(pool.inner().clone());
+
+    let session = repo.find_by_id(&session_id)
+        .map_err(|e| format!("Failed to find session: {}", e))?
+        .ok_or_else(|| format!("Session not found: {}", session_id))?;
+    if session.user_id != current_user.user_id {
+        return Err("Session belongs to different user".to_string());
+    }
+
+    repo.set_protected(&session_id, protected)
+        .map_err(|e| format!("Failed to set protected: {}", e))?;
+
+    let update = crate::ssh::session::SessionConfigUpdate {
+        protected: Some(protected),
+        ..Default::default()
+    };
+    if let Err(e) = manager.update_session(&session_id, update).await {
+        tracing::debug!("Session {} not loaded in memory: {}", session_id, e);
+    }
+
+    Ok(())
+}
+
+/// 获取会话是否受保护
+#[tauri::command]
+pub async fn db_ssh_session_get_protected(
+    pool: State<'_, DbPool>,
+    session_id: String,
+) -> Result<bool, String> {
+    let repo = SshSessionRepository::new(pool.inner().clone());
+    repo.find_protected(&session_id)
+        .map_err(|e| format!("Failed to get protected: {}", e))
+}
+
+/// 设置会话的高权限时限（None 表示不限时）
+///
+/// 以 root/管理员身份连接后开始计时，到期后断开或写入降权命令（下次连接生效）
+#[tauri::command]
+pub async fn db_ssh_session_set_elevation(
+    pool: State<'_, DbPool>,
+    manager: State<'_, SSHManagerState>,
+    session_id: String,
+    elevation: Option<ElevationPolicy>,
+) -> Result<(), String> {
+    let current_user = get_current_user_info(&pool);
+    let repo = SshSessionRepository::new(pool.inner().clone());
+
+    let session = repo.find_by_id(&session_id)
+        .map_err(|e| format!("Failed to find session: {}", e))?
+        .ok_or_else(|| format!("Session not found: {}", session_id))?;
+    if session.user_id != current_user.user_id {
+        return Err("Session belongs to different user".to_string());
+    }
+
+    let elevation = elevation.filter(|policy| policy.minutes > 0);
+    let json = elevation
+        .as_ref()
+        .map(serde_json::to_string)
+        .transpose()
+        .map_err(|e| format!("Failed to serialize elevation policy: {}", e))?;
+    repo.set_elevation(&session_id, json.as_deref())
+        .map_err(|e| format!("Failed to set elevation policy: {}", e))?;
+
+    let update = crate::ssh::session::SessionConfigUpdate {
+        elevation: Some(elevation),
+        ..Default::default()
+    };
+    if let Err(e) = manager.update_session(&session_id, update).await {
+        tracing::debug!("Session {} not loaded in memory: {}", session_id, e);
+    }
+
+    Ok(())
+}
+
+/// 获取会话的高权限时限
+#[tauri::command]
+pub async fn db_ssh_session_get_elevation(
+    pool: State<'_, DbPool>,
+    session_id: String,
+) -> Result<Option<ElevationPolicy>, String> {
+    let repo = SshSessionRepository::new(pool.inner().clone());
+    let json = repo.find_elevation(&session_id)
+        .map_err(|e| format!("Failed to get elevation policy: {}", e))?;
+
+    Ok(json.and_then(|json| serde_json::from_str(&json).ok()))
+}
+
+/// 设置会话的算法偏好（None 或默认设置表示使用默认算法，下次连接生效）
+#[tauri::command]
+pub async fn db_ssh_session_set_algorithms(
+    pool: State<'_, DbPool>,
+    manager: State<'_, SSHManagerState>,
+    session_id: String,
+    algorithms: Option<AlgorithmPreferences>,
+) -> Result<(), String> {
+    let current_user = get_current_user_info(&pool);
+    let repo = SshSessionRepository::new(pool.inner().clone());
+
+    let session = repo.find_by_id(&session_id)
+        .map_err(|e| format!("Failed to find session: {}", e))?
+        .ok_or_else(|| format!("Session not found: {}", session_id))?;
+    if session.user_id != current_user.user_id {
+        return Err("Session belongs to different user".to_string());
+    }
+
+    let algorithms = algorithms.unwrap_or_default();
+    let json = if algorithms.is_default() {
+        None
+    } else {
+        Some(serde_json::to_string(&algorithms)
+            .map_err(|e| format!("Failed to serialize algorithm preferences: {}", e))?)
+    };
+    repo.set_algorithms(&session_id, json.as_deref())
+        .map_err(|e| format!("Failed to set algorithm preferences: {}", e))?;
+
+    let update = crate::ssh::session::SessionConfigUpdate {
+        algorithms: Some(algorithms),
+        ..Default::default()
+    };
+    if let Err(e) = manager.update_session(&session_id, update).await {
+        tracing::debug!("Session {} not loaded in memory: {}", session_id, e);
+    }
+
+    Ok(())
+}
+
+/// 获取会话的算法偏好
+#[tauri::command]
+pub async fn db_ssh_session_get_algorithms(
+    pool: State<'_, DbPool>,
+    session_id: String,
+) -> Result<AlgorithmPreferences, String> {
+    let repo = SshSessionRepository::new(pool.inner().clone());
+    let json = repo.find_algorithms(&session_id)
+        .map_err(|e| format!("Failed to get algorithm preferences: {}", e))?;
+
+    Ok(json
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default())
+}
+
+/// 设置会话的连接 profile 列表（空列表表示没有 profile，连接时选择）
+#[tauri::command]
+pub async fn db_ssh_session_set_profiles(
+    pool: State<'_, DbPool>,
+    session_id: String,
+    profiles: Vec<ConnectionProfile>,
+) -> Result<Vec<ConnectionProfile>, String> {
+    let current_user = get_current_user_info(&pool);
+    let repo = SshSessionRepository::new(pool.inner().clone());
+
+    let session = repo.find_by_id(&session_id)
+        .map_err(|e| format!("Failed to find session: {}", e))?
+        .ok_or_else(|| format!("Session not found: {}", session_id))?;
+    if session.user_id != current_user.user_id {
+        return Err("Session belongs to different user".to_string());
+    }
+
+    let profiles = profiles::normalize_profiles(profiles)?;
+
+    // 引用的身份必须属于当前用户
+    let identity_repo = IdentityRepository::new(pool.inner().clone());
+    for identity_id in profiles.iter().filter_map(|p| p.identity_id.as_deref()) {
+        let identity = identity_repo.find_by_id(identity_id)
+            .map_err(|e| format!("Failed to find identity: {}", e))?
+            .ok_or_else(|| format!("Identity not found: {}", identity_id))?;
+        if identity.user_id != current_user.user_id {
+            return Err("Identity belongs to different user".to_string());
+        }
+    }
+
+    let json = if profiles.is_empty() {
+        None
+    } else {
+        Some(serde_json::to_string(&profiles)
+            .map_err(|e| format!("Failed to serialize profiles: {}", e))?)
+    };
+    repo.set_profiles(&session_id, json.as_deref())
+        .map_err(|e| format!("Failed to set profiles: {}", e))?;
+
+    Ok(profiles)
+}
+
+/// 获取会话的连接 profile 列表
+#[tauri::command]
+pub async fn db_ssh_session_get_profiles(
+    pool: State<'_, DbPool>,
+    session_id: String,
+) -> Result<Vec<ConnectionProfile>, String> {
+    let repo = SshSessionRepository::new(pool.inner().clone());
+    let json = repo.find_profiles(&session_id)
+        .map_err(|e| format!("Failed to get profiles: {}", e))?;
+
+    Ok(json
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default())
+}
+
+/// 会话编辑器中可选的算法列表（默认算法在前，之后是兼容旧设备的算法）
+#[tauri::command]
+pub async fn ssh_algorithm_catalog() -> Result<AlgorithmCatalog, String> {
+    Ok(crate::ssh::backends::DefaultBackend::algorithm_catalog())
+}
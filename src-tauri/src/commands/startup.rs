@@ -0,0 +1,71 @@
+//! 启动阶段状态
+//!
+//! setup() 中的耗时步骤会在后台执行，每完成一个阶段发送 `startup-phase` 事件，
+//! 前端可以据此显示启动进度；页面加载晚于事件时可通过 `app_startup_status` 查询当前阶段
+
+use serde::Serialize;
+use std::sync::Mutex;
+use std::time::Instant;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+/// 启动阶段
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum StartupPhase {
+    /// 数据库已初始化
+    Database,
+    /// 核心管理器（SSH/SFTP）已就绪
+    Managers,
+    /// 已恢复登录用户的 API Client
+    ApiClient,
+    /// 全部初始化完成
+    Ready,
+}
+
+/// 启动阶段事件
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartupPhaseEvent {
+    pub phase: StartupPhase,
+    /// 距离 setup 开始的毫秒数
+    pub elapsed_ms: u64,
+}
+
+/// 启动状态（全局）
+pub struct StartupState {
+    started_at: Instant,
+    current: Mutex<StartupPhaseEvent>,
+}
+
+impl StartupState {
+    pub fn new() -> Self {
+        Self {
+            started_at: Instant::now(),
+            current: Mutex::new(StartupPhaseEvent {
+                phase: StartupPhase::Database,
+                elapsed_ms: 0,
+            }),
+        }
+    }
+}
+
+/// 记录并广播启动阶段
+pub fn report_startup_phase(app: &AppHandle, phase: StartupPhase) {
+    let state = app.state::<StartupState>();
+    let event = StartupPhaseEvent {
+        phase,
+        elapsed_ms: state.started_at.elapsed().as_millis() as u64,
+    };
+    tracing::info!("Startup phase {:?} reached after {} ms", phase, event.elapsed_ms);
+
+    *state.current.lock().unwrap() = event.clone();
+    if let Err(e) = app.emit("startup-phase", &event) {
+        tracing::warn!("Failed to emit startup-phase: {}", e);
+    }
+}
+
+/// 查询当前启动阶段
+#[tauri::command]
+pub fn app_startup_status(state: State<'_, StartupState>) -> StartupPhaseEvent {
+    state.current.lock().unwrap().clone()
+}
@@ -1,6 +1,11 @@
 use crate::error::Result;
 use crate::config::Storage;
+use crate::database::DbPool;
 use crate::ssh::session::SessionConfig;
+use crate::services::import_service::{self, ImportSource};
+use crate::services::ai_config_service::{AIConfigImportSummary, AIConfigService};
+use serde::Serialize;
+use std::path::Path;
 use tauri::{State, AppHandle};
 
 use super::session::SSHManagerState;
@@ -17,17 +22,17 @@ pub async fn storage_sessions_save(
     // 获取内存中的所有会话配置及其ID
     let memory_sessions = manager.get_all_session_configs_with_ids().await;
 
-    println!("Found {} session configs in memory", memory_sessions.len());
+    tracing::info!("Found {} session configs in memory", memory_sessions.len());
 
     // 先从存储加载已存在的会话
     let storage = Storage::new(Some(&app))?;
     let existing_sessions = match storage.load_sessions() {
         Ok(sessions) => {
-            println!("Loaded {} existing sessions from storage", sessions.len());
+            tracing::info!("Loaded {} existing sessions from storage", sessions.len());
             sessions
         }
         Err(e) => {
-            println!("Failed to load existing sessions (file may not exist): {}", e);
+            tracing::warn!("Failed to load existing sessions (file may not exist): {}", e);
             Vec::new()
         }
     };
@@ -51,7 +56,7 @@ pub async fn storage_sessions_save(
         .into_iter()
         .collect();
 
-    println!("Saving {} merged session configs to storage", sessions_to_save.len());
+    tracing::info!("Saving {} merged session configs to storage", sessions_to_save.len());
 
     storage.save_sessions(&sessions_to_save)?;
 
@@ -85,6 +90,7 @@ pub async fn storage_session_create(
         SessionStorage {
             version: "1.0".to_string(),
             sessions: Vec::new(),
+            trash: Vec::new(),
         }
     };
 
@@ -98,21 +104,24 @@ pub async fn storage_session_create(
     // 使用原子写入
     storage.write_to_file(&new_content)?;
 
-    println!("Created and saved session: {}", session_id);
+    tracing::info!("Created and saved session: {}", session_id);
 
     Ok(session_id)
 }
 
-/// 删除会话并直接更新存储（优化版：无需解密/加密）
+/// 删除会话并直接更新存储（移入回收站，30 天内可恢复）
 #[tauri::command]
 pub async fn storage_session_delete(
     session_id: String,
     app: AppHandle,
+    pool: State<'_, DbPool>,
 ) -> Result<()> {
     let storage = Storage::new(Some(&app))?;
 
     // 使用优化的删除方法
-    storage.delete_session_by_id(&session_id)?;
+    if storage.delete_session_by_id(&session_id)? {
+        super::session_note::delete_session_notes(&pool, &session_id).await;
+    }
 
     Ok(())
 }
@@ -170,6 +179,31 @@ pub async fn storage_session_update(
             if let Some(keep_alive_interval) = updates.keep_alive_interval {
                 config.keep_alive_interval = keep_alive_interval;
             }
+            if let Some(jump_host) = updates.jump_host {
+                config.jump_host = Some(jump_host);
+            }
+            if let Some(remote_command) = updates.remote_command {
+                // 空字符串表示恢复为登录 shell
+                config.remote_command = Some(remote_command).filter(|c| !c.trim().is_empty());
+            }
+            if let Some(encoding) = updates.encoding {
+                config.encoding = encoding;
+            }
+            if let Some(remote_forwards) = updates.remote_forwards {
+                config.remote_forwards = remote_forwards;
+            }
+            if let Some(login_script) = updates.login_script {
+                config.login_script = login_script;
+            }
+            if let Some(protected) = updates.protected {
+                config.protected = protected;
+            }
+            if let Some(elevation) = updates.elevation {
+                config.elevation = elevation;
+            }
+            if let Some(algorithms) = updates.algorithms {
+                config.algorithms = (!algorithms.is_default()).then_some(algorithms);
+            }
             updated = true;
             break;
         }
@@ -182,7 +216,7 @@ pub async fn storage_session_update(
     // 保存回文件
     storage.save_sessions(&existing_sessions)?;
 
-    println!("Updated session in storage: {}", session_id);
+    tracing::info!("Updated session in storage: {}", session_id);
 
     Ok(())
 }
@@ -228,55 +262,19 @@ pub async fn storage_ai_config_save(
     app: AppHandle,
     ai_manager: State<'_, AIManagerState>,
 ) -> std::result::Result<(), String> {
-    // 1. 加载旧配置（如果存在）
-    let old_config = crate::config::Storage::load_ai_config(Some(&app))
-        .unwrap_or(None);
+    // 1. 确保旧配置快照已加载，用于比较 Provider 变更
+    ai_manager.runtime(&app);
 
     // 2. 保存新配置
     crate::config::Storage::save_ai_config(&config, Some(&app))
         .map_err(|e| e.to_string())?;
 
-    // 3. 如果存在旧配置，执行智能热重载
-    if let Some(old_cfg) = old_config {
-        // 将 storage 配置转换为 Provider 配置
-        let old_provider_configs: Vec<crate::commands::ai::AIProviderConfig> = old_cfg.providers
-            .into_iter()
-            .map(|p| crate::commands::ai::AIProviderConfig {
-                provider_type: p.provider_type,
-                api_key: p.api_key,
-                base_url: p.base_url,
-                model: p.model,
-                temperature: Some(p.temperature),
-                max_tokens: Some(p.max_tokens),
-            })
-            .collect();
-
-        let new_provider_configs: Vec<crate::commands::ai::AIProviderConfig> = config.providers
-            .iter()
-            .map(|p| crate::commands::ai::AIProviderConfig {
-                provider_type: p.provider_type.clone(),
-                api_key: p.api_key.clone(),
-                base_url: p.base_url.clone(),
-                model: p.model.clone(),
-                temperature: Some(p.temperature),
-                max_tokens: Some(p.max_tokens),
-            })
-            .collect();
-
-        // 执行智能热重载
-        match ai_manager.manager().hot_reload(&old_provider_configs, &new_provider_configs) {
-            Ok(removed_count) => {
-                tracing::info!(
-                    "[AI Config] Hot reload completed: {} providers removed from cache",
-                    removed_count
-                );
-            }
-            Err(e) => {
-                tracing::warn!("[AI Config] Hot reload failed: {}", e);
-                // 热重载失败不影响配置保存
-            }
-        }
-    }
+    // 3. 原子替换提示词模板、安全规则与 Provider 快照，并清除变更的 Provider 缓存
+    let removed_count = crate::commands::ai::reload_runtime(&ai_manager, &config);
+    tracing::info!(
+        "[AI Config] Hot reload completed: {} providers removed from cache",
+        removed_count
+    );
 
     Ok(())
 }
@@ -292,3 +290,135 @@ pub async fn storage_ai_config_load(app: AppHandle) -> std::result::Result<Optio
 pub async fn storage_ai_config_get_default() -> crate::config::storage::AIConfig {
     crate::config::Storage::get_default_ai_config()
 }
+
+/// 导出 AI 配置到文件
+///
+/// 未提供口令时不导出 API Key；提供口令时 API Key 以口令加密后一并导出
+#[tauri::command]
+pub async fn ai_config_export(
+    app: AppHandle,
+    path: String,
+    passphrase: Option<String>,
+) -> std::result::Result<(), String> {
+    let config = Storage::load_ai_config(Some(&app))
+        .map_err(|e| e.to_string())?
+        .unwrap_or_else(Storage::get_default_ai_config);
+
+    AIConfigService::export_to_file(Path::new(&path), &config, passphrase.as_deref())
+        .map_err(|e| e.to_string())
+}
+
+/// 从文件导入 AI 配置，合并到当前配置并热重载
+#[tauri::command]
+pub async fn ai_config_import(
+    app: AppHandle,
+    ai_manager: State<'_, AIManagerState>,
+    path: String,
+    passphrase: Option<String>,
+) -> std::result::Result<AIConfigImportSummary, String> {
+    let bundle = AIConfigService::read_bundle(Path::new(&path)).map_err(|e| e.to_string())?;
+    let current = Storage::load_ai_config(Some(&app))
+        .map_err(|e| e.to_string())?
+        .unwrap_or_else(Storage::get_default_ai_config);
+
+    let (config, summary) = AIConfigService::merge_bundle(current, bundle, passphrase.as_deref())
+        .map_err(|e| e.to_string())?;
+    storage_ai_config_save(config, app, ai_manager).await?;
+
+    tracing::info!(
+        "[AI Config] Imported: {} added, {} updated, {} keys",
+        summary.added,
+        summary.updated,
+        summary.keys_imported
+    );
+    Ok(summary)
+}
+
+/// 导入记录的处理结果
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum SessionImportStatus {
+    /// 预览：可以导入
+    Ready,
+    Imported,
+    /// 已存在相同的 用户名@主机:端口
+    Skipped,
+    Failed,
+}
+
+/// 单条导入结果
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionImportResult {
+    /// 来源中的会话名称
+    pub name: String,
+    pub config: Option<SessionConfig>,
+    pub status: SessionImportStatus,
+    pub error: Option<String>,
+    pub session_id: Option<String>,
+}
+
+/// 从 PuTTY、WinSCP 或 Termius 导入会话
+///
+/// `dry_run` 为 true 时只解析并返回预览；否则写入存储，已存在相同目标的会话会跳过。
+/// PuTTY 未指定 `path` 时读取本机的 PuTTY 会话（Windows 注册表或 ~/.putty/sessions）
+#[tauri::command]
+pub async fn storage_sessions_import(
+    source: ImportSource,
+    path: Option<String>,
+    dry_run: bool,
+    app: AppHandle,
+) -> Result<Vec<SessionImportResult>> {
+    let default_user = std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_else(|_| "root".to_string());
+    let default_auth = super::session::default_identity_auth().unwrap_or_else(|| {
+        crate::ssh::session::AuthMethod::PublicKey {
+            private_key_path: dirs::home_dir()
+                .map(|home| home.join(".ssh").join("id_rsa").to_string_lossy().to_string())
+                .unwrap_or_default(),
+            passphrase: None,
+        }
+    });
+
+    let path = path.filter(|p| !p.trim().is_empty()).map(std::path::PathBuf::from);
+    let entries = import_service::parse_file(source, path.as_deref(), &default_user, &default_auth)
+        .map_err(crate::error::SSHError::Storage)?;
+
+    let storage = Storage::new(Some(&app))?;
+    let mut sessions = storage.load_sessions()?;
+    let target_key = |config: &SessionConfig| {
+        format!("{}@{}:{}", config.username, config.host.to_lowercase(), config.port)
+    };
+    let mut existing: std::collections::HashSet<String> =
+        sessions.iter().map(|(_, config)| target_key(config)).collect();
+
+    let mut results = Vec::with_capacity(entries.len());
+    for entry in entries {
+        let (status, session_id) = match &entry.config {
+            None => (SessionImportStatus::Failed, None),
+            Some(config) if !existing.insert(target_key(config)) => (SessionImportStatus::Skipped, None),
+            Some(_) if dry_run => (SessionImportStatus::Ready, None),
+            Some(config) => {
+                let session_id = uuid::Uuid::new_v4().to_string();
+                sessions.push((session_id.clone(), config.clone()));
+                (SessionImportStatus::Imported, Some(session_id))
+            }
+        };
+        results.push(SessionImportResult {
+            name: entry.name,
+            config: entry.config,
+            status,
+            error: entry.error,
+            session_id,
+        });
+    }
+
+    let imported = results.iter().filter(|r| r.status == SessionImportStatus::Imported).count();
+    if imported > 0 {
+        storage.save_sessions(&sessions)?;
+    }
+    tracing::info!("Imported {} of {} sessions from {:?} (dry run: {})", imported, results.len(), source, dry_run);
+
+    Ok(results)
+}
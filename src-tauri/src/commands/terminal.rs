@@ -1,9 +1,18 @@
 use crate::error::Result;
-use tauri::State;
+use crate::ssh::benchmark::{run_benchmark, BenchmarkOptions, BenchmarkReport};
+use crate::ssh::transcript::TranscriptRange;
+use tauri::ipc::{Channel, InvokeResponseBody};
+use tauri::{AppHandle, Emitter, State};
 
 use super::session::SSHManagerState;
 
+/// 基准测试输出事件
+const BENCHMARK_OUTPUT_EVENT: &str = "terminal-benchmark-output";
+
 /// 向会话写入数据
+///
+/// 受保护会话中输入危险命令时，回车及其后的输入会被拦下并发送
+/// `ssh-protect-confirm-{connectionId}` 事件，需要调用 `terminal_confirm_input` 确认
 #[tauri::command]
 pub async fn terminal_write(
     manager: State<'_, SSHManagerState>,
@@ -13,6 +22,17 @@ pub async fn terminal_write(
     manager.write_to_session(&session_id, data).await
 }
 
+/// 确认或取消受保护会话中被拦下的危险命令
+#[tauri::command]
+pub async fn terminal_confirm_input(
+    manager: State<'_, SSHManagerState>,
+    session_id: String,
+    token: String,
+    approved: bool,
+) -> Result<()> {
+    manager.confirm_protected_input(&session_id, &token, approved).await
+}
+
 /// 调整终端大小
 #[tauri::command]
 pub async fn terminal_resize(
@@ -23,3 +43,72 @@ pub async fn terminal_resize(
 ) -> Result<()> {
     manager.resize_session(&session_id, rows, cols).await
 }
+
+/// 订阅终端的二进制输出
+///
+/// 订阅后输出以原始字节通过 Channel 发送，替代 `ssh-output-{connectionId}` 事件
+#[tauri::command]
+pub async fn terminal_attach_output(
+    manager: State<'_, SSHManagerState>,
+    connection_id: String,
+    on_output: Channel<InvokeResponseBody>,
+) -> Result<()> {
+    manager.attach_output_channel(&connection_id, on_output).await
+}
+
+/// 查询连接的纯文本输出记录（已去除 ANSI 序列，按行组织）
+///
+/// `start` 为起始行号，为空时返回最后 `count` 行
+#[tauri::command]
+pub async fn terminal_transcript_get(
+    manager: State<'_, SSHManagerState>,
+    connection_id: String,
+    start: Option<u64>,
+    count: Option<usize>,
+) -> Result<TranscriptRange> {
+    manager.transcript_range(&connection_id, start, count).await
+}
+
+/// 订阅或取消订阅实时新行事件 `ssh-transcript-{connectionId}`
+#[tauri::command]
+pub async fn terminal_transcript_subscribe(
+    manager: State<'_, SSHManagerState>,
+    connection_id: String,
+    enabled: bool,
+) -> Result<()> {
+    manager.set_transcript_live_events(&connection_id, enabled).await
+}
+
+/// 清空连接的纯文本输出记录
+#[tauri::command]
+pub async fn terminal_transcript_clear(
+    manager: State<'_, SSHManagerState>,
+    connection_id: String,
+) -> Result<()> {
+    manager.clear_transcript(&connection_id).await
+}
+
+/// 终端性能基准测试
+///
+/// 使用回环后端测量输出吞吐量、输入延迟和事件管道开销，
+/// 测试输出通过 `terminal-benchmark-output` 事件发送，与真实连接的发送路径一致
+#[tauri::command]
+pub async fn terminal_benchmark(
+    app: AppHandle,
+    options: Option<BenchmarkOptions>,
+) -> Result<BenchmarkReport> {
+    let options = options.unwrap_or_default();
+    tracing::info!("Running terminal benchmark: {:?}", options);
+
+    let report = run_benchmark(&options, |chunk| {
+        app.emit(BENCHMARK_OUTPUT_EVENT, chunk).is_ok()
+    })
+    .await?;
+
+    tracing::info!(
+        "Terminal benchmark finished: {:.1} MB/s, input p50 {:.0} us",
+        report.throughput.mb_per_sec,
+        report.input_latency.p50_us
+    );
+    Ok(report)
+}
@@ -0,0 +1,108 @@
+//! 连接流量使用历史命令
+
+use crate::commands::auth::ApiClientStateWrapper;
+use crate::commands::ssh_session::get_current_user_info;
+use crate::database::DbPool;
+use crate::database::repositories::ConnectionStatsRepository;
+use crate::models::connection_stats::{
+    ConnectionStatsRecord, HeatmapBucket, ServerUsageBucket, ServerUsageHeatmapRequest, SessionTrafficTotal, UsageHeatmap,
+};
+use crate::services::usage_heatmap_service::{self, DEFAULT_HEATMAP_DAYS, MAX_HEATMAP_DAYS};
+use chrono::{Duration, Local, TimeZone};
+use tauri::State;
+
+/// 默认返回的记录数
+const DEFAULT_HISTORY_LIMIT: i64 = 100;
+
+/// 列出最近的连接使用记录，可按会话过滤
+#[tauri::command]
+pub async fn connection_stats_history(
+    pool: State<'_, DbPool>,
+    session_id: Option<String>,
+    limit: Option<i64>,
+) -> Result<Vec<ConnectionStatsRecord>, String> {
+    let current_user = get_current_user_info(&pool);
+    ConnectionStatsRepository::new(pool.inner().clone())
+        .find_by_user(&current_user.user_id, session_id.as_deref(), limit.unwrap_or(DEFAULT_HISTORY_LIMIT).max(1))
+        .map_err(|e| format!("Failed to list connection stats: {}", e))
+}
+
+/// 按会话汇总的流量使用量
+#[tauri::command]
+pub async fn connection_stats_totals(
+    pool: State<'_, DbPool>,
+) -> Result<Vec<SessionTrafficTotal>, String> {
+    let current_user = get_current_user_info(&pool);
+    ConnectionStatsRepository::new(pool.inner().clone())
+        .totals_by_session(&current_user.user_id)
+        .map_err(|e| format!("Failed to summarize connection stats: {}", e))
+}
+
+/// 使用热力图：最近 `days` 天（默认一年）每天每小时的连接数和连接时长
+///
+/// `merge_devices` 为 true 时（需要登录）把本机按小时汇总的数据上报到同步服务器，
+/// 返回所有设备汇总后的分桶；主机汇总只包含本机数据，不会上传
+#[tauri::command]
+pub async fn stats_heatmap(
+    pool: State<'_, DbPool>,
+    api_client_state: State<'_, ApiClientStateWrapper>,
+    days: Option<u32>,
+    merge_devices: Option<bool>,
+) -> Result<UsageHeatmap, String> {
+    let days = days.unwrap_or(DEFAULT_HEATMAP_DAYS).clamp(1, MAX_HEATMAP_DAYS);
+    let today = Local::now().date_naive();
+    let from = today - Duration::days(days as i64 - 1);
+    let since = from
+        .and_hms_opt(0, 0, 0)
+        .and_then(|start| Local.from_local_datetime(&start).earliest())
+        .map(|start| start.timestamp())
+        .unwrap_or_default();
+
+    let current_user = get_current_user_info(&pool);
+    let records = ConnectionStatsRepository::new(pool.inner().clone())
+        .find_since(&current_user.user_id, since)
+        .map_err(|e| format!("Failed to load connection stats: {}", e))?;
+    let (buckets, hosts) = usage_heatmap_service::aggregate(&records, &Local, from);
+
+    let mut heatmap = UsageHeatmap {
+        from_date: from.format("%Y-%m-%d").to_string(),
+        to_date: today.format("%Y-%m-%d").to_string(),
+        buckets,
+        hosts,
+        devices: 1,
+    };
+    if !merge_devices.unwrap_or(false) {
+        return Ok(heatmap);
+    }
+
+    let request = ServerUsageHeatmapRequest {
+        device_id: current_user.device_id,
+        from_date: heatmap.from_date.clone(),
+        buckets: heatmap
+            .buckets
+            .iter()
+            .map(|bucket| ServerUsageBucket {
+                date: bucket.date.clone(),
+                hour: bucket.hour as i32,
+                connections: bucket.connections,
+                duration_secs: bucket.duration_secs,
+            })
+            .collect(),
+    };
+    let client = api_client_state.wait_client().await.map_err(|e| e.to_string())?;
+    let (merged, _, _) = client.usage_heatmap(&request).await.map_err(|e| e.to_string())?;
+
+    tracing::info!("[Stats] Merged usage heat map from {} device(s)", merged.devices);
+    heatmap.devices = merged.devices.max(1);
+    heatmap.buckets = merged
+        .buckets
+        .into_iter()
+        .map(|bucket| HeatmapBucket {
+            date: bucket.date,
+            hour: bucket.hour.clamp(0, 23) as u8,
+            connections: bucket.connections,
+            duration_secs: bucket.duration_secs,
+        })
+        .collect();
+    Ok(heatmap)
+}
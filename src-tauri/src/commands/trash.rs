@@ -0,0 +1,95 @@
+//! 会话回收站命令
+//!
+//! 删除的会话保留 30 天（与服务器软删除一致），期间可离线恢复：
+//! 会话存储文件中的会话移入文件内的回收站，数据库中的会话为软删除
+
+use crate::commands::session::SSHManagerState;
+use crate::commands::ssh_session::{get_current_user_info, stored_session_info};
+use crate::config::storage::{TrashEntry, TRASH_RETENTION_DAYS};
+use crate::config::Storage;
+use crate::database::DbPool;
+use crate::database::repositories::SshSessionRepository;
+use crate::models::ssh_session::SshSession;
+use crate::ssh::events::SessionChangeKind;
+use tauri::{AppHandle, State};
+
+/// 回收站保留时长（秒）
+const TRASH_RETENTION_SECS: i64 = TRASH_RETENTION_DAYS * 24 * 60 * 60;
+
+fn trash_entry_from_db(session: &SshSession) -> TrashEntry {
+    let deleted_at = session.deleted_at.unwrap_or(session.updated_at);
+    TrashEntry {
+        id: session.id.clone(),
+        name: session.name.clone(),
+        host: session.host.clone(),
+        port: session.port,
+        username: session.username.clone(),
+        group: session.group_name.clone(),
+        deleted_at,
+        expires_at: deleted_at + TRASH_RETENTION_SECS,
+    }
+}
+
+/// 列出回收站中的会话（按删除时间倒序）
+#[tauri::command]
+pub async fn trash_list(
+    app: AppHandle,
+    pool: State<'_, DbPool>,
+) -> Result<Vec<TrashEntry>, String> {
+    let current_user = get_current_user_info(&pool);
+    let repo = SshSessionRepository::new(pool.inner().clone());
+    let since = chrono::Utc::now().timestamp() - TRASH_RETENTION_SECS;
+
+    let mut entries = Storage::new(Some(&app))
+        .and_then(|storage| storage.list_trash())
+        .map_err(|e| e.to_string())?;
+
+    let db_sessions = repo.find_deleted_since(&current_user.user_id, since)
+        .map_err(|e| format!("Failed to list deleted sessions: {}", e))?;
+    entries.extend(
+        db_sessions
+            .iter()
+            .filter(|s| !entries.iter().any(|e| e.id == s.id))
+            .map(trash_entry_from_db)
+            .collect::<Vec<_>>(),
+    );
+
+    entries.sort_by_key(|e| std::cmp::Reverse(e.deleted_at));
+    Ok(entries)
+}
+
+/// 从回收站恢复会话
+///
+/// 会话存储文件中的会话重新加载到会话管理器；数据库中的会话取消软删除，下次同步时推送到服务器
+#[tauri::command]
+pub async fn session_restore(
+    app: AppHandle,
+    pool: State<'_, DbPool>,
+    manager: State<'_, SSHManagerState>,
+    session_id: String,
+) -> Result<(), String> {
+    let storage = Storage::new(Some(&app)).map_err(|e| e.to_string())?;
+    if let Some((id, config)) = storage.restore_session(&session_id).map_err(|e| e.to_string())? {
+        manager.create_session_with_id(Some(id), config).await.map_err(|e| e.to_string())?;
+        tracing::info!("Restored session from trash: {}", session_id);
+        return Ok(());
+    }
+
+    let current_user = get_current_user_info(&pool);
+    let repo = SshSessionRepository::new(pool.inner().clone());
+    let since = chrono::Utc::now().timestamp() - TRASH_RETENTION_SECS;
+
+    let session = repo.find_by_id(&session_id)
+        .map_err(|e| format!("Failed to find session: {}", e))?
+        .filter(|s| s.is_deleted && s.user_id == current_user.user_id)
+        .filter(|s| s.deleted_at.unwrap_or(s.updated_at) > since)
+        .ok_or_else(|| format!("Session not in trash: {}", session_id))?;
+
+    repo.restore(&session.id)
+        .map_err(|e| format!("Failed to restore session: {}", e))?;
+
+    manager.change_log().record(SessionChangeKind::Added, &session.id, Some(stored_session_info(&session)));
+
+    tracing::info!("Restored SSH session from trash: {}", session_id);
+    Ok(())
+}
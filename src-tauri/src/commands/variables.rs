@@ -0,0 +1,142 @@
+//! 模板变量命令
+
+use crate::commands::session::SSHManagerState;
+use crate::commands::ssh_session::get_current_user_info;
+use crate::database::DbPool;
+use crate::database::repositories::VariableRepository;
+use crate::models::variable::Variable;
+use crate::ssh::variables;
+use serde::Serialize;
+use std::time::Duration;
+use tauri::State;
+
+/// 捕获命令的默认超时时间（秒）
+const DEFAULT_CAPTURE_TIMEOUT_SECS: u64 = 30;
+
+/// 捕获命令的最大超时时间（秒）
+const MAX_CAPTURE_TIMEOUT_SECS: u64 = 600;
+
+/// 命令输出捕获结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptureResult {
+    pub variable: Variable,
+    /// 命令的标准输出，便于核对提取结果
+    pub stdout: String,
+    pub exit_status: Option<u32>,
+}
+
+/// 列出变量
+///
+/// `session_id` 为空时返回全部变量，否则返回全局变量和该会话的变量
+#[tauri::command]
+pub async fn variable_list(
+    pool: State<'_, DbPool>,
+    session_id: Option<String>,
+) -> Result<Vec<Variable>, String> {
+    let current_user = get_current_user_info(&pool);
+    let repo = VariableRepository::new(pool.inner().clone());
+    repo.find_by_user(&current_user.user_id, session_id.as_deref())
+        .map_err(|e| format!("Failed to list variables: {}", e))
+}
+
+/// 设置变量（`session_id` 为空时为全局变量）
+#[tauri::command]
+pub async fn variable_set(
+    pool: State<'_, DbPool>,
+    name: String,
+    value: String,
+    session_id: Option<String>,
+) -> Result<Variable, String> {
+    let name = name.trim();
+    if !variables::is_valid_name(name) {
+        return Err(format!("Invalid variable name: {}", name));
+    }
+
+    let current_user = get_current_user_info(&pool);
+    let repo = VariableRepository::new(pool.inner().clone());
+    repo.set(&current_user.user_id, session_id.as_deref(), name, &value)
+        .map_err(|e| format!("Failed to set variable: {}", e))
+}
+
+/// 删除变量
+#[tauri::command]
+pub async fn variable_delete(
+    pool: State<'_, DbPool>,
+    name: String,
+    session_id: Option<String>,
+) -> Result<(), String> {
+    let current_user = get_current_user_info(&pool);
+    let repo = VariableRepository::new(pool.inner().clone());
+    repo.delete(&current_user.user_id, session_id.as_deref(), &name)
+        .map_err(|e| format!("Failed to delete variable: {}", e))
+}
+
+/// 预览模板替换结果（用于会话编辑表单）
+#[tauri::command]
+pub async fn variable_render(
+    pool: State<'_, DbPool>,
+    template: String,
+    session_id: Option<String>,
+) -> Result<String, String> {
+    let current_user = get_current_user_info(&pool);
+    let repo = VariableRepository::new(pool.inner().clone());
+    let vars = repo
+        .resolve_for_session(&current_user.user_id, session_id.as_deref().unwrap_or_default())
+        .map_err(|e| format!("Failed to load variables: {}", e))?;
+    variables::render(&template, &vars)
+}
+
+/// 执行命令，用正则表达式从标准输出中提取值并存为会话变量
+///
+/// # 参数
+/// - `regex`: 优先取名为 `value` 的捕获组，其次取第一个捕获组，没有捕获组时取整个匹配
+/// - `name`: 变量名，存为连接所属会话的变量
+/// - `timeout`: 超时时间（秒），默认 30，最大 600
+///
+/// 没有匹配时返回错误，不修改已有变量
+#[tauri::command]
+pub async fn run_and_capture(
+    pool: State<'_, DbPool>,
+    manager: State<'_, SSHManagerState>,
+    connection_id: String,
+    command: String,
+    regex: String,
+    name: String,
+    timeout: Option<u64>,
+) -> Result<CaptureResult, String> {
+    let name = name.trim();
+    if !variables::is_valid_name(name) {
+        return Err(format!("Invalid variable name: {}", name));
+    }
+    if command.trim().is_empty() {
+        return Err("Command cannot be empty".to_string());
+    }
+    let pattern = regex::Regex::new(&regex).map_err(|e| format!("Invalid regex: {}", e))?;
+    let timeout = Duration::from_secs(
+        timeout.unwrap_or(DEFAULT_CAPTURE_TIMEOUT_SECS).clamp(1, MAX_CAPTURE_TIMEOUT_SECS),
+    );
+
+    let connection = manager.get_connection(&connection_id).await.map_err(|e| e.to_string())?;
+    let output = connection.exec(&command, timeout).await.map_err(|e| e.to_string())?;
+    let stdout = output.stdout_lossy();
+    let value = variables::capture(&stdout, &pattern).ok_or_else(|| {
+        format!(
+            "Output of '{}' (exit status {:?}) does not match {}",
+            command, output.exit_status, regex
+        )
+    })?;
+
+    let current_user = get_current_user_info(&pool);
+    let repo = VariableRepository::new(pool.inner().clone());
+    let variable = repo
+        .set(&current_user.user_id, Some(&connection.session_id), name, &value)
+        .map_err(|e| format!("Failed to set variable: {}", e))?;
+    tracing::debug!("[Variables] Captured {} for session {}", name, connection.session_id);
+
+    Ok(CaptureResult {
+        variable,
+        stdout,
+        exit_status: output.exit_status,
+    })
+}
@@ -0,0 +1,91 @@
+//! Webhook 通知命令
+//!
+//! 传输完成、用户钩子执行和主机离线时向配置的 URL 发送 JSON 通知
+
+use crate::services::webhook_service::{
+    build_client, WebhookConfig, WebhookDeliveryResult, WebhookEvent, WebhookService,
+};
+
+/// 列出所有 webhook
+#[tauri::command]
+pub async fn webhook_list() -> Result<Vec<WebhookConfig>, String> {
+    WebhookService::load_webhooks().map_err(|e| e.to_string())
+}
+
+/// 保存 webhook（id 为空时创建，否则更新）
+#[tauri::command]
+pub async fn webhook_save(mut webhook: WebhookConfig) -> Result<WebhookConfig, String> {
+    let url = reqwest::Url::parse(webhook.url.trim()).map_err(|e| format!("Webhook 地址无效: {}", e))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err("Webhook 地址必须使用 http 或 https".to_string());
+    }
+    if webhook.events.is_empty() {
+        return Err("至少需要订阅一个事件".to_string());
+    }
+    webhook.url = url.to_string();
+
+    let mut webhooks = WebhookService::load_webhooks().map_err(|e| e.to_string())?;
+
+    if webhook.id.is_empty() {
+        webhook.id = uuid::Uuid::new_v4().to_string();
+        webhooks.push(webhook.clone());
+    } else if let Some(existing) = webhooks.iter_mut().find(|w| w.id == webhook.id) {
+        *existing = webhook.clone();
+    } else {
+        return Err(format!("Webhook not found: {}", webhook.id));
+    }
+
+    WebhookService::save_webhooks(&webhooks).map_err(|e| e.to_string())?;
+    Ok(webhook)
+}
+
+/// 删除 webhook
+#[tauri::command]
+pub async fn webhook_delete(webhook_id: String) -> Result<(), String> {
+    let mut webhooks = WebhookService::load_webhooks().map_err(|e| e.to_string())?;
+    webhooks.retain(|w| w.id != webhook_id);
+    WebhookService::save_webhooks(&webhooks).map_err(|e| e.to_string())
+}
+
+/// 使用示例数据发送 webhook（订阅的第一个事件），返回投递结果（用于配置时调试接收端）
+#[tauri::command]
+pub async fn webhook_test(webhook_id: String) -> Result<WebhookDeliveryResult, String> {
+    let webhooks = WebhookService::load_webhooks().map_err(|e| e.to_string())?;
+    let webhook = webhooks
+        .into_iter()
+        .find(|w| w.id == webhook_id)
+        .ok_or_else(|| format!("Webhook not found: {}", webhook_id))?;
+
+    let event = webhook.events.first().copied().unwrap_or(WebhookEvent::TransferComplete);
+    let mut data = match event {
+        WebhookEvent::TransferComplete => serde_json::json!({
+            "direction": "upload",
+            "connectionId": "test-connection",
+            "taskId": "test-task",
+            "localPath": "/tmp/example.txt",
+            "remotePath": "/root/example.txt",
+            "bytesTransferred": 1024,
+            "filesCompleted": 1,
+        }),
+        WebhookEvent::HookTriggered => serde_json::json!({
+            "hookId": "test-hook",
+            "hookName": "Test Hook",
+            "hookEvent": "on-connect",
+            "exitCode": 0,
+            "durationMs": 12,
+        }),
+        WebhookEvent::HostOffline => serde_json::json!({
+            "connectionId": "test-connection",
+            "sessionId": "test-session",
+            "name": "Test Session",
+            "host": "example.com",
+            "port": 22,
+            "username": "root",
+        }),
+    };
+    data["test"] = true.into();
+
+    let client = build_client().map_err(|e| e.to_string())?;
+    let timestamp = chrono::Utc::now().timestamp_millis();
+    Ok(WebhookService::deliver(&client, &webhook, event, &data, timestamp).await)
+}
@@ -0,0 +1,283 @@
+//! 团队工作区与共享会话
+//!
+//! 工作区成员按角色限制操作：member 只能连接共享会话，不能查看、导出凭据或删除共享会话；
+//! owner 和 admin 可以管理成员和共享会话。权限在客户端和服务器两端检查。
+//! 共享会话的凭据只在连接时从服务器获取（服务器同时记录审计日志），只保存在内存中的临时连接里
+
+use crate::commands::auth::ApiClientStateWrapper;
+use crate::commands::session::{load_session_from_db, SSHManagerState};
+use crate::database::DbPool;
+use crate::models::workspace::{
+    ServerAddWorkspaceMemberRequest, ServerCreateSharedSessionRequest, ServerCreateWorkspaceRequest, SharedSession,
+    Workspace, WorkspaceAuditLog, WorkspaceMember, WorkspaceRole,
+};
+use crate::services::api_client::ApiClient;
+use crate::ssh::session::{AuthMethod, SessionConfig};
+use tauri::State;
+
+/// 将服务器返回的状态码转换为可读的错误
+fn describe_error(error: anyhow::Error) -> String {
+    let message = error.to_string();
+    if message.contains("API error (403") {
+        "当前角色没有权限执行此操作".to_string()
+    } else if message.contains("API error (404") {
+        "工作区、成员或共享会话不存在".to_string()
+    } else if message.contains("API error (409") {
+        "该用户已是工作区成员".to_string()
+    } else {
+        message
+    }
+}
+
+/// 当前用户在工作区中的角色
+async fn current_role(client: &ApiClient, workspace_id: &str) -> Result<WorkspaceRole, String> {
+    let (workspaces, _, _) = client.list_workspaces().await.map_err(describe_error)?;
+    workspaces
+        .into_iter()
+        .find(|w| w.id == workspace_id)
+        .map(|w| w.role)
+        .ok_or_else(|| format!("Workspace not found: {}", workspace_id))
+}
+
+/// 没有权限时返回错误（服务器同样会拒绝）
+fn require(allowed: bool, action: &str) -> Result<(), String> {
+    if allowed {
+        Ok(())
+    } else {
+        Err(format!("当前角色不能{}", action))
+    }
+}
+
+/// 创建工作区（创建者为 owner）
+#[tauri::command]
+pub async fn workspace_create(
+    api_client_state: State<'_, ApiClientStateWrapper>,
+    name: String,
+) -> Result<Workspace, String> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("工作区名称不能为空".to_string());
+    }
+
+    let client = api_client_state.wait_client().await.map_err(|e| e.to_string())?;
+    let (workspace, _, _) = client
+        .create_workspace(&ServerCreateWorkspaceRequest { name })
+        .await
+        .map_err(describe_error)?;
+    Ok(workspace.into())
+}
+
+/// 列出当前用户加入的工作区（包含角色）
+#[tauri::command]
+pub async fn workspace_list(
+    api_client_state: State<'_, ApiClientStateWrapper>,
+) -> Result<Vec<Workspace>, String> {
+    let client = api_client_state.wait_client().await.map_err(|e| e.to_string())?;
+    let (workspaces, _, _) = client.list_workspaces().await.map_err(describe_error)?;
+    Ok(workspaces.into_iter().map(Into::into).collect())
+}
+
+/// 列出工作区成员
+#[tauri::command]
+pub async fn workspace_member_list(
+    api_client_state: State<'_, ApiClientStateWrapper>,
+    workspace_id: String,
+) -> Result<Vec<WorkspaceMember>, String> {
+    let client = api_client_state.wait_client().await.map_err(|e| e.to_string())?;
+    let (members, _, _) = client.list_workspace_members(&workspace_id).await.map_err(describe_error)?;
+    Ok(members.into_iter().map(Into::into).collect())
+}
+
+/// 按邮箱添加成员（owner 和 admin）
+///
+/// # 参数
+/// - `role`: admin 或 member
+#[tauri::command]
+pub async fn workspace_member_add(
+    api_client_state: State<'_, ApiClientStateWrapper>,
+    workspace_id: String,
+    email: String,
+    role: WorkspaceRole,
+) -> Result<WorkspaceMember, String> {
+    if role == WorkspaceRole::Owner {
+        return Err("不能添加 owner 角色的成员".to_string());
+    }
+
+    let client = api_client_state.wait_client().await.map_err(|e| e.to_string())?;
+    require(current_role(&client, &workspace_id).await?.can_manage_members(), "管理成员")?;
+
+    let request = ServerAddWorkspaceMemberRequest { email, role };
+    let (member, _, _) = client
+        .add_workspace_member(&workspace_id, &request)
+        .await
+        .map_err(describe_error)?;
+    Ok(member.into())
+}
+
+/// 移除成员（owner 和 admin，owner 不能被移除）
+#[tauri::command]
+pub async fn workspace_member_remove(
+    api_client_state: State<'_, ApiClientStateWrapper>,
+    workspace_id: String,
+    user_id: String,
+) -> Result<(), String> {
+    let client = api_client_state.wait_client().await.map_err(|e| e.to_string())?;
+    require(current_role(&client, &workspace_id).await?.can_manage_members(), "管理成员")?;
+
+    client
+        .remove_workspace_member(&workspace_id, &user_id)
+        .await
+        .map_err(describe_error)?;
+    Ok(())
+}
+
+/// 列出工作区的共享会话（不包含凭据）
+#[tauri::command]
+pub async fn workspace_session_list(
+    api_client_state: State<'_, ApiClientStateWrapper>,
+    workspace_id: String,
+) -> Result<Vec<SharedSession>, String> {
+    let client = api_client_state.wait_client().await.map_err(|e| e.to_string())?;
+    let (sessions, _, _) = client.list_shared_sessions(&workspace_id).await.map_err(describe_error)?;
+    Ok(sessions.into_iter().map(Into::into).collect())
+}
+
+/// 将本地保存的会话共享到工作区（owner 和 admin）
+///
+/// 只能共享密码认证的会话：私钥文件保存在本机，其他成员无法使用
+#[tauri::command]
+pub async fn workspace_session_share(
+    pool: State<'_, DbPool>,
+    api_client_state: State<'_, ApiClientStateWrapper>,
+    workspace_id: String,
+    session_id: String,
+) -> Result<SharedSession, String> {
+    let config = load_session_from_db(&pool, &session_id)
+        .await?
+        .ok_or_else(|| format!("Session not found: {}", session_id))?;
+    if !matches!(config.auth_method, AuthMethod::Password { .. }) {
+        return Err("只能共享密码认证的会话".to_string());
+    }
+
+    let client = api_client_state.wait_client().await.map_err(|e| e.to_string())?;
+    require(current_role(&client, &workspace_id).await?.can_share_sessions(), "共享会话")?;
+
+    let credentials = serde_json::to_string(&config.auth_method)
+        .map_err(|e| format!("Failed to serialize auth method: {}", e))?;
+    let request = ServerCreateSharedSessionRequest {
+        name: config.name,
+        host: config.host,
+        port: config.port,
+        username: config.username,
+        credentials,
+    };
+    let (session, _, _) = client
+        .create_shared_session(&workspace_id, &request)
+        .await
+        .map_err(describe_error)?;
+
+    tracing::info!("[Workspace] Shared session {} to workspace {}", session_id, workspace_id);
+    Ok(session.into())
+}
+
+/// 删除共享会话（owner 和 admin，member 不能删除）
+#[tauri::command]
+pub async fn workspace_session_delete(
+    api_client_state: State<'_, ApiClientStateWrapper>,
+    workspace_id: String,
+    session_id: String,
+) -> Result<(), String> {
+    let client = api_client_state.wait_client().await.map_err(|e| e.to_string())?;
+    require(current_role(&client, &workspace_id).await?.can_delete_sessions(), "删除共享会话")?;
+
+    client
+        .delete_shared_session(&workspace_id, &session_id)
+        .await
+        .map_err(describe_error)?;
+    Ok(())
+}
+
+/// 查看或导出共享会话的凭据（owner 和 admin，member 不能查看）
+///
+/// 返回认证信息（与 `SessionConfig.authMethod` 格式相同）
+#[tauri::command]
+pub async fn workspace_session_credentials(
+    api_client_state: State<'_, ApiClientStateWrapper>,
+    workspace_id: String,
+    session_id: String,
+) -> Result<AuthMethod, String> {
+    let client = api_client_state.wait_client().await.map_err(|e| e.to_string())?;
+    require(current_role(&client, &workspace_id).await?.can_view_credentials(), "查看或导出凭据")?;
+
+    let (result, _, _) = client
+        .get_shared_session_credentials(&workspace_id, &session_id)
+        .await
+        .map_err(describe_error)?;
+    serde_json::from_str(&result.credentials).map_err(|e| format!("Failed to parse credentials: {}", e))
+}
+
+/// 连接共享会话（所有成员），返回新建的临时连接 ID
+///
+/// 服务器记录审计日志后才返回凭据；凭据只保存在内存中的临时连接里，不写入本地数据库，也不返回给前端
+#[tauri::command]
+pub async fn workspace_session_connect(
+    manager: State<'_, SSHManagerState>,
+    api_client_state: State<'_, ApiClientStateWrapper>,
+    workspace_id: String,
+    session_id: String,
+) -> Result<String, String> {
+    let client = api_client_state.wait_client().await.map_err(|e| e.to_string())?;
+    let (result, _, _) = client
+        .connect_shared_session(&workspace_id, &session_id)
+        .await
+        .map_err(describe_error)?;
+    let auth_method: AuthMethod = serde_json::from_str(&result.credentials)
+        .map_err(|e| format!("Failed to parse credentials: {}", e))?;
+
+    let session = result.session;
+    let config = SessionConfig {
+        name: session.name,
+        host: session.host,
+        port: session.port,
+        username: session.username,
+        auth_method,
+        terminal_type: None,
+        columns: None,
+        rows: None,
+        strict_host_key_checking: true,
+        group: "共享会话".to_string(),
+        keep_alive_interval: 30,
+        dns_resolver: None,
+        jump_host: None,
+        remote_command: None,
+        encoding: Default::default(),
+        remote_forwards: Vec::new(),
+        login_script: Vec::new(),
+        protected: false,
+        elevation: None,
+        algorithms: None,
+        startup_commands: Vec::new(),
+        backend_type: Default::default(),
+        serial: None,
+        wsl_distribution: None,
+        roaming: false,
+        wake_on_lan: None,
+        pre_connect: Vec::new(),
+    };
+
+    tracing::info!("[Workspace] Connecting shared session {} in workspace {}", session.id, workspace_id);
+    manager.create_temporary_connection(config).await.map_err(|e| e.to_string())
+}
+
+/// 查看工作区审计日志（owner 和 admin）
+#[tauri::command]
+pub async fn workspace_audit_log_list(
+    api_client_state: State<'_, ApiClientStateWrapper>,
+    workspace_id: String,
+) -> Result<Vec<WorkspaceAuditLog>, String> {
+    let client = api_client_state.wait_client().await.map_err(|e| e.to_string())?;
+    require(current_role(&client, &workspace_id).await?.can_view_audit_logs(), "查看审计日志")?;
+
+    let (logs, _, _) = client.list_workspace_audit_logs(&workspace_id).await.map_err(describe_error)?;
+    Ok(logs.into_iter().map(Into::into).collect())
+}
@@ -73,7 +73,7 @@ impl KeybindingsStorageManager {
     pub fn load_keybindings(&self) -> Result<KeybindingsData> {
         if !self.storage_path.exists() {
             // 如果文件不存在，创建默认配置并保存
-            println!("Keybindings file not found, creating default configuration");
+            tracing::info!("Keybindings file not found, creating default configuration");
             let default_data = Self::get_default_keybindings();
             self.save_keybindings(&default_data)?;
             return Ok(default_data);
@@ -85,7 +85,7 @@ impl KeybindingsStorageManager {
         let storage: KeybindingsStorage = serde_json::from_str(&content)
             .map_err(|e| SSHError::Storage(format!("Failed to parse keybindings file: {}", e)))?;
 
-        println!("Loaded {} keybindings from storage", storage.keybindings.keybindings.len());
+        tracing::info!("Loaded {} keybindings from storage", storage.keybindings.keybindings.len());
         Ok(storage.keybindings)
     }
 
@@ -102,7 +102,7 @@ impl KeybindingsStorageManager {
         fs::write(&self.storage_path, content)
             .map_err(|e| SSHError::Storage(format!("Failed to write keybindings file: {}", e)))?;
 
-        println!("Saved {} keybindings to storage", data.keybindings.len());
+        tracing::info!("Saved {} keybindings to storage", data.keybindings.len());
         Ok(())
     }
 
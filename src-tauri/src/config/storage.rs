@@ -10,12 +10,58 @@ use argon2::{Argon2, PasswordHasher};
 use argon2::password_hash::SaltString;
 use secrecy::{ExposeSecret, SecretString};
 use base64::Engine;
+use zeroize::Zeroizing;
+
+/// 已删除会话在回收站中的保留天数（与服务器软删除一致）
+pub const TRASH_RETENTION_DAYS: i64 = 30;
 
 /// 会话存储结构
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SessionStorage {
     pub version: String,
     pub sessions: Vec<SavedSession>,
+    /// 回收站（向后兼容：旧版本没有该字段）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub trash: Vec<TrashedSession>,
+}
+
+/// 回收站中的会话（仍为加密状态）
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TrashedSession {
+    #[serde(flatten)]
+    pub session: SavedSession,
+    /// 删除时间（Unix 秒）
+    pub deleted_at: i64,
+}
+
+/// 回收站条目（不含认证信息）
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashEntry {
+    pub id: String,
+    pub name: String,
+    pub host: String,
+    pub port: u16,
+    pub username: String,
+    pub group: String,
+    pub deleted_at: i64,
+    /// 超过该时间后永久删除（Unix 秒）
+    pub expires_at: i64,
+}
+
+impl From<&TrashedSession> for TrashEntry {
+    fn from(trashed: &TrashedSession) -> Self {
+        Self {
+            id: trashed.session.id.clone(),
+            name: trashed.session.name.clone(),
+            host: trashed.session.host.clone(),
+            port: trashed.session.port,
+            username: trashed.session.username.clone(),
+            group: trashed.session.group.clone(),
+            deleted_at: trashed.deleted_at,
+            expires_at: trashed.deleted_at + TRASH_RETENTION_DAYS * 24 * 60 * 60,
+        }
+    }
 }
 
 /// 应用配置存储结构
@@ -35,7 +81,7 @@ pub struct AIAppConfig {
 }
 
 /// AI Provider 配置
-#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[derive(Serialize, Deserialize, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct AIProviderConfig {
     #[serde(default)]
@@ -45,7 +91,7 @@ pub struct AIProviderConfig {
     #[serde(default)]
     pub name: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub api_key: Option<String>, // 明文 API Key（前端使用，不保存到文件）
+    pub api_key: Option<Zeroizing<String>>, // 明文 API Key（前端使用，不保存到文件，释放时清零）
     #[serde(default)]
     pub api_key_encrypted: Option<String>, // 加密的 API Key（保存到文件）
     #[serde(default)]
@@ -62,6 +108,23 @@ pub struct AIProviderConfig {
     pub enabled: bool,
 }
 
+/// Debug 输出不包含明文 API Key
+impl std::fmt::Debug for AIProviderConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AIProviderConfig")
+            .field("id", &self.id)
+            .field("provider_type", &self.provider_type)
+            .field("name", &self.name)
+            .field("has_api_key", &self.api_key.is_some())
+            .field("base_url", &self.base_url)
+            .field("model", &self.model)
+            .field("temperature", &self.temperature)
+            .field("max_tokens", &self.max_tokens)
+            .field("enabled", &self.enabled)
+            .finish_non_exhaustive()
+    }
+}
+
 fn default_temperature() -> f32 {
     0.7
 }
@@ -77,6 +140,12 @@ pub struct AIConfig {
     pub providers: Vec<AIProviderConfig>,
     pub default_provider: String,
     pub shortcuts: AIShortcuts,
+    /// 自定义提示词模板（未设置的使用内置模板）
+    #[serde(default)]
+    pub prompt_templates: AIPromptTemplates,
+    /// AI 生成命令的安全规则
+    #[serde(default)]
+    pub safety_rules: AISafetyRules,
 }
 
 /// AI 快捷键配置
@@ -88,6 +157,40 @@ pub struct AIShortcuts {
     pub nl_to_command: String,
 }
 
+/// AI 提示词模板（系统提示词）
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AIPromptTemplates {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub explain_command: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nl_to_command: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_analysis: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chat: Option<String>,
+}
+
+/// AI 安全规则
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AISafetyRules {
+    /// 禁止 AI 生成的命令片段（不区分大小写），命中时拒绝返回该命令
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blocked_patterns: Vec<String>,
+}
+
+impl AISafetyRules {
+    /// 返回命令命中的第一条规则
+    pub fn matches(&self, command: &str) -> Option<&str> {
+        let command = command.to_lowercase();
+        self.blocked_patterns
+            .iter()
+            .map(|pattern| pattern.trim())
+            .find(|pattern| !pattern.is_empty() && command.contains(&pattern.to_lowercase()))
+    }
+}
+
 /// 终端配置
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -221,8 +324,8 @@ impl Storage {
     fn get_or_create_encryption_key(key_path: &PathBuf) -> Result<SecretString> {
         if key_path.exists() {
             // 从文件加载密钥
-            let key_content = fs::read_to_string(key_path)
-                .map_err(|e| SSHError::Storage(format!("Failed to read encryption key: {}", e)))?;
+            let key_content = Zeroizing::new(fs::read_to_string(key_path)
+                .map_err(|e| SSHError::Storage(format!("Failed to read encryption key: {}", e)))?);
             Ok(SecretString::new(key_content.trim().to_string()))
         } else {
             // 生成新密钥
@@ -385,9 +488,13 @@ impl Storage {
 
         let saved_sessions = saved_sessions?;
 
+        // 保留回收站中的会话
+        let trash = self.read_storage_data().map(|data| data.trash).unwrap_or_default();
+
         let storage = SessionStorage {
             version: "1.0".to_string(),
             sessions: saved_sessions,
+            trash,
         };
 
         let content = serde_json::to_string_pretty(&storage)
@@ -409,12 +516,12 @@ impl Storage {
     /// 加密会话（使用 AES-256-GCM）
     fn encrypt_session(&self, id: String, session: SessionConfig) -> Result<SavedSession> {
         // 将 AuthMethod 序列化为 JSON
-        let auth_json = serde_json::to_string(&session.auth_method)
-            .map_err(|e| SSHError::Crypto(format!("Failed to serialize auth method: {}", e)))?;
+        let auth_json = Zeroizing::new(serde_json::to_string(&session.auth_method)
+            .map_err(|e| SSHError::Crypto(format!("Failed to serialize auth method: {}", e)))?);
 
         // 从密钥字符串派生 AES-256 密钥
         let key_bytes = self.derive_key_from_password(self.encryption_key.expose_secret())?;
-        let key = Key::<Aes256Gcm>::from_slice(&key_bytes);
+        let key = Key::<Aes256Gcm>::from_slice(key_bytes.as_slice());
 
         // 生成随机 nonce
         let nonce_bytes: [u8; 12] = rand::random();
@@ -447,8 +554,8 @@ impl Storage {
         })
     }
 
-    /// 从密码派生 AES-256 密钥
-    fn derive_key_from_password(&self, password: &str) -> Result<[u8; 32]> {
+    /// 从密码派生 AES-256 密钥（离开作用域时清零）
+    fn derive_key_from_password(&self, password: &str) -> Result<Zeroizing<[u8; 32]>> {
         // 使用固定的salt，确保加密和解密时密钥一致
         let salt = SaltString::from_b64("dGh1cmktdGVybWluYWwtZml4ZWQtc2FsdC0yMDI0")
             .map_err(|e| SSHError::Crypto(format!("Failed to create salt: {}", e)))?;
@@ -462,7 +569,7 @@ impl Storage {
         // 从哈希中提取32字节作为密钥
         let hash_output = password_hash.hash.expect("Password hash should be present");
         let hash_bytes = hash_output.as_bytes();
-        let mut key = [0u8; 32];
+        let mut key = Zeroizing::new([0u8; 32]);
         key.copy_from_slice(&hash_bytes[..32]);
         Ok(key)
     }
@@ -471,10 +578,10 @@ impl Storage {
     /// 返回 (id, SessionConfig) 元组
     fn decrypt_session(&self, saved: SavedSession) -> Result<(String, SessionConfig)> {
         // 尝试解密
-        let plaintext = if let Some(nonce_str) = &saved.nonce {
+        let plaintext = Zeroizing::new(if let Some(nonce_str) = &saved.nonce {
             // 有 nonce 字段，尝试 AES-256-GCM 解密
             let key_bytes = self.derive_key_from_password(self.encryption_key.expose_secret())?;
-            let key = Key::<Aes256Gcm>::from_slice(&key_bytes);
+            let key = Key::<Aes256Gcm>::from_slice(key_bytes.as_slice());
 
             // 解码 nonce 和密文
             let nonce_bytes = base64::engine::general_purpose::STANDARD
@@ -494,7 +601,7 @@ impl Storage {
                 Err(_) => {
                     // 解密失败，可能是旧格式数据被错误地添加了nonce
                     // 回退到 base64 解码
-                    println!("AES-GCM decryption failed, falling back to base64 for session: {}", saved.name);
+                    tracing::warn!("AES-GCM decryption failed, falling back to base64 for session: {}", saved.name);
                     base64::engine::general_purpose::STANDARD
                         .decode(&saved.auth_method_encrypted)
                         .map_err(|e| SSHError::Crypto(format!("Failed to decode base64: {}", e)))?
@@ -505,14 +612,12 @@ impl Storage {
             base64::engine::general_purpose::STANDARD
                 .decode(&saved.auth_method_encrypted)
                 .map_err(|e| SSHError::Crypto(format!("Failed to decode base64: {}", e)))?
-        };
+        });
 
-        // 反序列化 AuthMethod
+        // 反序列化 AuthMethod（解密后的内容包含密码，出错时不输出）
         let auth_method = serde_json::from_slice(&plaintext)
             .map_err(|e| {
-                println!("Failed to deserialize auth method for session '{}': {}", saved.name, e);
-                println!("Plaintext length: {}, first 100 bytes: {:?}", plaintext.len(), &plaintext[..plaintext.len().min(100)]);
-                println!("Plaintext (as string): {}", String::from_utf8_lossy(&plaintext));
+                tracing::warn!("Failed to deserialize auth method for session '{}': {}", saved.name, e);
                 SSHError::Crypto(format!("Failed to deserialize auth method: {}", e))
             })?;
 
@@ -528,6 +633,22 @@ impl Storage {
             strict_host_key_checking: true, // 默认启用严格的主机密钥验证
             group: saved.group,
             keep_alive_interval: 30, // 默认30秒
+            dns_resolver: None,
+            jump_host: None,
+            remote_command: None,
+            encoding: Default::default(),
+            remote_forwards: Vec::new(),
+            login_script: Vec::new(),
+            protected: false,
+            elevation: None,
+            algorithms: None,
+            startup_commands: Vec::new(),
+            backend_type: Default::default(),
+            serial: None,
+            wsl_distribution: None,
+            roaming: false,
+            wake_on_lan: None,
+            pre_connect: Vec::new(),
         };
 
         Ok((saved.id, config))
@@ -569,44 +690,98 @@ impl Storage {
         self.encrypt_session(id, session)
     }
 
-    /// 直接删除会话（优化版：无需解密/加密）
-    pub fn delete_session_by_id(&self, session_id: &str) -> Result<bool> {
+    /// 读取存储文件（不解密），文件不存在时返回空存储
+    fn read_storage_data(&self) -> Result<SessionStorage> {
         if !self.storage_path.exists() {
-            return Ok(false);
+            return Ok(SessionStorage {
+                version: "1.0".to_string(),
+                sessions: Vec::new(),
+                trash: Vec::new(),
+            });
         }
 
-        // 直接读取 JSON 文件，不解密
         let content = fs::read_to_string(&self.storage_path)
             .map_err(|e| SSHError::Storage(format!("Failed to read storage file: {}", e)))?;
 
-        let mut storage_data: SessionStorage = serde_json::from_str(&content)
-            .map_err(|e| SSHError::Storage(format!("Failed to parse storage file: {}", e)))?;
+        serde_json::from_str(&content)
+            .map_err(|e| SSHError::Storage(format!("Failed to parse storage file: {}", e)))
+    }
 
-        // 直接过滤掉要删除的会话（不解密）
-        let original_count = storage_data.sessions.len();
-        storage_data.sessions.retain(|s| s.id != session_id);
+    /// 写入存储文件（不加密，原子写入）
+    fn write_storage_data(&self, storage_data: &SessionStorage) -> Result<()> {
+        let content = serde_json::to_string_pretty(storage_data)
+            .map_err(|e| SSHError::Storage(format!("Failed to serialize sessions: {}", e)))?;
+
+        self.write_to_file(&content)
+    }
+
+    /// 移除回收站中超过保留期的会话，返回移除数量
+    fn purge_expired_trash(storage_data: &mut SessionStorage, now: i64) -> usize {
+        let cutoff = now - TRASH_RETENTION_DAYS * 24 * 60 * 60;
+        let original_count = storage_data.trash.len();
+        storage_data.trash.retain(|t| t.deleted_at > cutoff);
+        original_count - storage_data.trash.len()
+    }
 
-        if storage_data.sessions.len() == original_count {
+    /// 删除会话（软删除：移入回收站，保留 30 天，无需解密/加密）
+    pub fn delete_session_by_id(&self, session_id: &str) -> Result<bool> {
+        if !self.storage_path.exists() {
             return Ok(false);
         }
 
-        // 直接序列化并保存（无需加密）
-        let new_content = serde_json::to_string_pretty(&storage_data)
-            .map_err(|e| SSHError::Storage(format!("Failed to serialize sessions: {}", e)))?;
+        let mut storage_data = self.read_storage_data()?;
 
-        // 使用原子写入
-        let temp_path = self.storage_path.with_extension("tmp");
-        fs::write(&temp_path, new_content)
-            .map_err(|e| SSHError::Storage(format!("Failed to write temp file: {}", e)))?;
+        let Some(index) = storage_data.sessions.iter().position(|s| s.id == session_id) else {
+            return Ok(false);
+        };
 
-        fs::rename(&temp_path, &self.storage_path)
-            .map_err(|e| SSHError::Storage(format!("Failed to rename temp file: {}", e)))?;
+        let now = chrono::Utc::now().timestamp();
+        let session = storage_data.sessions.remove(index);
+        storage_data.trash.retain(|t| t.session.id != session_id);
+        storage_data.trash.push(TrashedSession { session, deleted_at: now });
+        Self::purge_expired_trash(&mut storage_data, now);
 
-        println!("Deleted session from storage: {}", session_id);
+        self.write_storage_data(&storage_data)?;
+
+        tracing::info!("Moved session to trash: {}", session_id);
 
         Ok(true)
     }
 
+    /// 列出回收站中的会话（按删除时间倒序），同时清理过期会话
+    pub fn list_trash(&self) -> Result<Vec<TrashEntry>> {
+        let mut storage_data = self.read_storage_data()?;
+
+        if Self::purge_expired_trash(&mut storage_data, chrono::Utc::now().timestamp()) > 0 {
+            self.write_storage_data(&storage_data)?;
+        }
+
+        let mut entries: Vec<TrashEntry> = storage_data.trash.iter().map(TrashEntry::from).collect();
+        entries.sort_by_key(|e| std::cmp::Reverse(e.deleted_at));
+        Ok(entries)
+    }
+
+    /// 从回收站恢复会话，返回恢复后的会话配置；回收站中没有该会话时返回 None
+    pub fn restore_session(&self, session_id: &str) -> Result<Option<(String, SessionConfig)>> {
+        let mut storage_data = self.read_storage_data()?;
+
+        let Some(index) = storage_data.trash.iter().position(|t| t.session.id == session_id) else {
+            return Ok(None);
+        };
+
+        let trashed = storage_data.trash.remove(index);
+        // 先解密，确保恢复的会话可用
+        let restored = self.decrypt_session(trashed.session.clone())?;
+        storage_data.sessions.retain(|s| s.id != session_id);
+        storage_data.sessions.push(trashed.session);
+
+        self.write_storage_data(&storage_data)?;
+
+        tracing::info!("Restored session from trash: {}", session_id);
+
+        Ok(Some(restored))
+    }
+
     /// 保存应用配置（使用原子写入，确保数据完整性）
     pub fn save_app_config(config: &TerminalConfig, app_handle: Option<&tauri::AppHandle>) -> Result<()> {
         let storage_dir = Self::get_storage_dir(app_handle)?;
@@ -725,6 +900,8 @@ impl Storage {
             providers: providers_to_save,
             default_provider: config.default_provider.clone(),
             shortcuts: config.shortcuts.clone(),
+            prompt_templates: config.prompt_templates.clone(),
+            safety_rules: config.safety_rules.clone(),
         };
 
         let app_config = AIAppConfig {
@@ -777,7 +954,7 @@ impl Storage {
             if let (Some(encrypted_key), Some(nonce)) = (&provider.api_key_encrypted, &provider.nonce) {
                 match storage.decrypt_api_key(encrypted_key, nonce) {
                     Ok(decrypted_key) => {
-                        provider_loaded.api_key = Some(decrypted_key);
+                        provider_loaded.api_key = Some(Zeroizing::new(decrypted_key));
                         // 清除加密字段，不返回给前端
                         provider_loaded.api_key_encrypted = None;
                         provider_loaded.nonce = None;
@@ -802,6 +979,8 @@ impl Storage {
             providers: providers_loaded,
             default_provider: app_config.ai_config.default_provider,
             shortcuts: app_config.ai_config.shortcuts,
+            prompt_templates: app_config.ai_config.prompt_templates,
+            safety_rules: app_config.ai_config.safety_rules,
         };
 
         Ok(Some(config_loaded))
@@ -842,6 +1021,8 @@ impl Storage {
                 open_chat: "Ctrl+Shift+I".to_string(),
                 nl_to_command: "Ctrl+Shift+N".to_string(),
             },
+            prompt_templates: AIPromptTemplates::default(),
+            safety_rules: AISafetyRules::default(),
         }
     }
 
@@ -849,7 +1030,7 @@ impl Storage {
     pub fn encrypt_api_key(&self, api_key: &str) -> Result<(String, String)> {
         // 从密钥字符串派生 AES-256 密钥
         let key_bytes = self.derive_key_from_password(self.encryption_key.expose_secret())?;
-        let key = Key::<Aes256Gcm>::from_slice(&key_bytes);
+        let key = Key::<Aes256Gcm>::from_slice(key_bytes.as_slice());
 
         // 生成随机 nonce
         let nonce_bytes: [u8; 12] = rand::random();
@@ -871,7 +1052,7 @@ impl Storage {
     /// 解密 API Key
     pub fn decrypt_api_key(&self, encrypted_key: &str, nonce_str: &str) -> Result<String> {
         let key_bytes = self.derive_key_from_password(self.encryption_key.expose_secret())?;
-        let key = Key::<Aes256Gcm>::from_slice(&key_bytes);
+        let key = Key::<Aes256Gcm>::from_slice(key_bytes.as_slice());
 
         // 解码 nonce 和密文
         let nonce_bytes = base64::engine::general_purpose::STANDARD
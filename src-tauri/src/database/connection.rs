@@ -2,6 +2,13 @@ use anyhow::Result;
 use r2d2::Pool;
 use r2d2_sqlite::SqliteConnectionManager;
 use std::path::PathBuf;
+use std::time::Duration;
+
+/// 写锁等待时间，超时后才返回 "database is locked"
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 每个连接缓存的预编译语句数量
+const STATEMENT_CACHE_CAPACITY: usize = 64;
 
 /// 数据库连接池类型
 pub type DbPool = Pool<SqliteConnectionManager>;
@@ -16,10 +23,22 @@ pub fn init_db_pool() -> Result<DbPool> {
         std::fs::create_dir_all(parent)?;
     }
 
+    // 应用待恢复的备份（恢复时数据库正在使用，只能在下次启动、打开连接前替换）
+    apply_pending_restore(&db_path)?;
+
     tracing::info!("Initializing database at: {}", db_path.display());
 
     // 创建连接管理器
-    let manager = SqliteConnectionManager::file(&db_path);
+    // WAL 模式允许读写并发，配合 busy_timeout 避免同步、录制保存和传输记录
+    // 并发写入时出现 "database is locked"
+    let manager = SqliteConnectionManager::file(&db_path).with_init(|conn| {
+        conn.busy_timeout(BUSY_TIMEOUT)?;
+        conn.set_prepared_statement_cache_capacity(STATEMENT_CACHE_CAPACITY);
+        conn.execute_batch(
+            "PRAGMA journal_mode = WAL;
+             PRAGMA synchronous = NORMAL;",
+        )
+    });
 
     // 创建连接池
     let pool = Pool::builder()
@@ -41,8 +60,29 @@ pub fn init_db_pool() -> Result<DbPool> {
     Ok(pool)
 }
 
+/// 待恢复数据库文件路径（由备份恢复写入，下次启动时替换当前数据库）
+pub fn pending_restore_path(db_path: &std::path::Path) -> PathBuf {
+    db_path.with_extension("db.restore")
+}
+
+/// 用待恢复的数据库替换当前数据库，并删除旧的 WAL 文件
+fn apply_pending_restore(db_path: &std::path::Path) -> Result<()> {
+    let restore_path = pending_restore_path(db_path);
+    if !restore_path.exists() {
+        return Ok(());
+    }
+
+    tracing::info!("Applying pending database restore from: {}", restore_path.display());
+    for suffix in ["db-wal", "db-shm"] {
+        let _ = std::fs::remove_file(db_path.with_extension(suffix));
+    }
+    std::fs::rename(&restore_path, db_path)?;
+
+    Ok(())
+}
+
 /// 获取数据库文件路径
-fn get_db_path() -> Result<PathBuf> {
+pub fn get_db_path() -> Result<PathBuf> {
     // 获取家目录下的 .tauri-terminal 文件夹
     let storage_dir = dirs::home_dir()
         .map(|dir| dir.join(".tauri-terminal"))
@@ -114,6 +114,32 @@ impl AppSettingsRepository {
         Ok(())
     }
 
+    /// 获取 API 请求超时设置（JSON，None 表示使用默认值）
+    pub fn get_api_timeouts(&self) -> Result<Option<String>> {
+        let conn = self.get_conn()?;
+
+        let timeouts: Option<String> = conn.query_row(
+            "SELECT api_timeouts FROM app_settings WHERE id = 1",
+            [],
+            |row| row.get(0),
+        )?;
+
+        Ok(timeouts)
+    }
+
+    /// 设置 API 请求超时
+    pub fn set_api_timeouts(&self, timeouts_json: Option<&str>) -> Result<()> {
+        let conn = self.get_conn()?;
+        let now = chrono::Utc::now().timestamp().to_string();
+
+        conn.execute(
+            "UPDATE app_settings SET api_timeouts = ?1, updated_at = ?2 WHERE id = 1",
+            r2d2_sqlite::rusqlite::params![timeouts_json, now],
+        )?;
+
+        Ok(())
+    }
+
     /// 获取语言设置
     pub fn get_language(&self) -> Result<String> {
         let conn = self.get_conn()?;
@@ -140,6 +166,111 @@ impl AppSettingsRepository {
         Ok(())
     }
 
+    /// 获取剪贴板历史是否启用
+    pub fn get_clipboard_history_enabled(&self) -> Result<bool> {
+        let conn = self.get_conn()?;
+
+        let enabled: Option<i64> = conn.query_row(
+            "SELECT clipboard_history_enabled FROM app_settings WHERE id = 1",
+            [],
+            |row| row.get(0),
+        )?;
+
+        Ok(enabled == Some(1))
+    }
+
+    /// 设置剪贴板历史是否启用
+    pub fn set_clipboard_history_enabled(&self, enabled: bool) -> Result<()> {
+        let conn = self.get_conn()?;
+        let now = chrono::Utc::now().timestamp().to_string();
+        let value = if enabled { 1 } else { 0 };
+
+        conn.execute(
+            "UPDATE app_settings SET clipboard_history_enabled = ?1, updated_at = ?2 WHERE id = 1",
+            [value.to_string().as_str(), now.as_str()],
+        )?;
+
+        Ok(())
+    }
+
+    /// 获取全局 DNS 解析器（JSON，None 表示使用系统解析器）
+    pub fn get_dns_resolver(&self) -> Result<Option<String>> {
+        let conn = self.get_conn()?;
+
+        let resolver: Option<String> = conn.query_row(
+            "SELECT dns_resolver FROM app_settings WHERE id = 1",
+            [],
+            |row| row.get(0),
+        )?;
+
+        Ok(resolver)
+    }
+
+    /// 设置全局 DNS 解析器
+    pub fn set_dns_resolver(&self, resolver_json: Option<&str>) -> Result<()> {
+        let conn = self.get_conn()?;
+        let now = chrono::Utc::now().timestamp().to_string();
+
+        conn.execute(
+            "UPDATE app_settings SET dns_resolver = ?1, updated_at = ?2 WHERE id = 1",
+            r2d2_sqlite::rusqlite::params![resolver_json, now],
+        )?;
+
+        Ok(())
+    }
+
+    /// 获取并发连接数限制（JSON，None 表示不限制）
+    pub fn get_connection_limits(&self) -> Result<Option<String>> {
+        let conn = self.get_conn()?;
+
+        let limits: Option<String> = conn.query_row(
+            "SELECT connection_limits FROM app_settings WHERE id = 1",
+            [],
+            |row| row.get(0),
+        )?;
+
+        Ok(limits)
+    }
+
+    /// 设置并发连接数限制
+    pub fn set_connection_limits(&self, limits_json: Option<&str>) -> Result<()> {
+        let conn = self.get_conn()?;
+        let now = chrono::Utc::now().timestamp().to_string();
+
+        conn.execute(
+            "UPDATE app_settings SET connection_limits = ?1, updated_at = ?2 WHERE id = 1",
+            r2d2_sqlite::rusqlite::params![limits_json, now],
+        )?;
+
+        Ok(())
+    }
+
+    /// 获取水印设置（JSON，None 表示关闭）
+    pub fn get_watermark(&self) -> Result<Option<String>> {
+        let conn = self.get_conn()?;
+
+        let watermark: Option<String> = conn.query_row(
+            "SELECT watermark FROM app_settings WHERE id = 1",
+            [],
+            |row| row.get(0),
+        )?;
+
+        Ok(watermark)
+    }
+
+    /// 设置水印
+    pub fn set_watermark(&self, watermark_json: Option<&str>) -> Result<()> {
+        let conn = self.get_conn()?;
+        let now = chrono::Utc::now().timestamp().to_string();
+
+        conn.execute(
+            "UPDATE app_settings SET watermark = ?1, updated_at = ?2 WHERE id = 1",
+            r2d2_sqlite::rusqlite::params![watermark_json, now],
+        )?;
+
+        Ok(())
+    }
+
     /// 获取所有应用设置
     pub fn get_all(&self) -> Result<AppSettings> {
         let conn = self.get_conn()?;
@@ -0,0 +1,107 @@
+use anyhow::Result;
+use r2d2::PooledConnection;
+use r2d2_sqlite::{rusqlite, SqliteConnectionManager};
+
+use crate::database::DbPool;
+use crate::models::clipboard::ClipboardRecord;
+
+/// 剪贴板历史 Repository
+pub struct ClipboardRepository {
+    pool: DbPool,
+}
+
+impl ClipboardRepository {
+    /// 创建新的 Repository 实例
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// 获取数据库连接
+    fn get_conn(&self) -> Result<PooledConnection<SqliteConnectionManager>> {
+        self.pool
+            .get()
+            .map_err(|e| anyhow::anyhow!("Failed to get database connection: {}", e))
+    }
+
+    /// 添加记录，并只保留用户最近的 `max_entries` 条
+    pub fn insert(&self, record: &ClipboardRecord, max_entries: i64) -> Result<()> {
+        let conn = self.get_conn()?;
+
+        conn.execute(
+            "INSERT INTO clipboard_history (
+                id, user_id, connection_id, session_name, host_key,
+                content_encrypted, content_length, created_at
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            rusqlite::params![
+                record.id,
+                record.user_id,
+                record.connection_id,
+                record.session_name,
+                record.host_key,
+                record.content_encrypted,
+                record.content_length,
+                record.created_at,
+            ],
+        )?;
+
+        conn.execute(
+            "DELETE FROM clipboard_history
+            WHERE user_id = ?1 AND id NOT IN (
+                SELECT id FROM clipboard_history
+                WHERE user_id = ?1
+                ORDER BY created_at DESC
+                LIMIT ?2
+            )",
+            rusqlite::params![record.user_id, max_entries],
+        )?;
+
+        Ok(())
+    }
+
+    /// 获取用户的记录（最新的在前），可按连接过滤
+    pub fn find_by_user(&self, user_id: &str, connection_id: Option<&str>) -> Result<Vec<ClipboardRecord>> {
+        let conn = self.get_conn()?;
+
+        let mut stmt = conn.prepare_cached(
+            "SELECT id, user_id, connection_id, session_name, host_key,
+                content_encrypted, content_length, created_at
+            FROM clipboard_history
+            WHERE user_id = ?1 AND (?2 IS NULL OR connection_id = ?2)
+            ORDER BY created_at DESC"
+        )?;
+
+        let records = stmt
+            .query_map(rusqlite::params![user_id, connection_id], |row| {
+                Ok(ClipboardRecord {
+                    id: row.get(0)?,
+                    user_id: row.get(1)?,
+                    connection_id: row.get(2)?,
+                    session_name: row.get(3)?,
+                    host_key: row.get(4)?,
+                    content_encrypted: row.get(5)?,
+                    content_length: row.get(6)?,
+                    created_at: row.get(7)?,
+                })
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(records)
+    }
+
+    /// 删除单条记录
+    pub fn delete(&self, id: &str, user_id: &str) -> Result<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "DELETE FROM clipboard_history WHERE id = ?1 AND user_id = ?2",
+            [id, user_id],
+        )?;
+        Ok(())
+    }
+
+    /// 清空用户的剪贴板历史
+    pub fn clear(&self, user_id: &str) -> Result<usize> {
+        let conn = self.get_conn()?;
+        let count = conn.execute("DELETE FROM clipboard_history WHERE user_id = ?1", [user_id])?;
+        Ok(count)
+    }
+}
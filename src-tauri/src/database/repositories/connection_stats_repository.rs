@@ -0,0 +1,136 @@
+use anyhow::Result;
+use r2d2::PooledConnection;
+use r2d2_sqlite::{rusqlite, SqliteConnectionManager};
+
+use crate::database::DbPool;
+use crate::models::connection_stats::{ConnectionStatsRecord, SessionTrafficTotal};
+
+/// 连接流量使用历史 Repository
+pub struct ConnectionStatsRepository {
+    pool: DbPool,
+}
+
+impl ConnectionStatsRepository {
+    /// 创建新的 Repository 实例
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// 获取数据库连接
+    fn get_conn(&self) -> Result<PooledConnection<SqliteConnectionManager>> {
+        self.pool
+            .get()
+            .map_err(|e| anyhow::anyhow!("Failed to get database connection: {}", e))
+    }
+
+    /// 将查询结果行转换为使用记录
+    fn map_record(row: &rusqlite::Row) -> rusqlite::Result<ConnectionStatsRecord> {
+        Ok(ConnectionStatsRecord {
+            id: row.get(0)?,
+            user_id: row.get(1)?,
+            session_id: row.get(2)?,
+            connection_id: row.get(3)?,
+            session_name: row.get(4)?,
+            host_key: row.get(5)?,
+            connected_at: row.get(6)?,
+            disconnected_at: row.get(7)?,
+            bytes_sent: row.get(8)?,
+            bytes_received: row.get(9)?,
+        })
+    }
+
+    /// 添加一条使用记录，返回记录 ID
+    pub fn insert(&self, record: &ConnectionStatsRecord) -> Result<i64> {
+        let conn = self.get_conn()?;
+
+        conn.execute(
+            "INSERT INTO connection_stats (
+                user_id, session_id, connection_id, session_name, host_key,
+                connected_at, disconnected_at, bytes_sent, bytes_received
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            rusqlite::params![
+                record.user_id,
+                record.session_id,
+                record.connection_id,
+                record.session_name,
+                record.host_key,
+                record.connected_at,
+                record.disconnected_at,
+                record.bytes_sent,
+                record.bytes_received,
+            ],
+        )?;
+
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// 获取用户最近的使用记录（最新的在前），可按会话过滤
+    pub fn find_by_user(&self, user_id: &str, session_id: Option<&str>, limit: i64) -> Result<Vec<ConnectionStatsRecord>> {
+        let conn = self.get_conn()?;
+
+        let mut stmt = conn.prepare_cached(
+            "SELECT id, user_id, session_id, connection_id, session_name, host_key,
+                connected_at, disconnected_at, bytes_sent, bytes_received
+            FROM connection_stats
+            WHERE user_id = ?1 AND (?2 IS NULL OR session_id = ?2)
+            ORDER BY disconnected_at DESC
+            LIMIT ?3"
+        )?;
+
+        let records = stmt
+            .query_map(rusqlite::params![user_id, session_id, limit], Self::map_record)?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(records)
+    }
+
+    /// 获取用户在指定时间之后断开的全部使用记录（按连接时间排序）
+    pub fn find_since(&self, user_id: &str, since: i64) -> Result<Vec<ConnectionStatsRecord>> {
+        let conn = self.get_conn()?;
+
+        let mut stmt = conn.prepare_cached(
+            "SELECT id, user_id, session_id, connection_id, session_name, host_key,
+                connected_at, disconnected_at, bytes_sent, bytes_received
+            FROM connection_stats
+            WHERE user_id = ?1 AND disconnected_at >= ?2
+            ORDER BY connected_at"
+        )?;
+
+        let records = stmt
+            .query_map(rusqlite::params![user_id, since], Self::map_record)?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(records)
+    }
+
+    /// 按会话汇总用户的流量使用量（流量多的在前）
+    pub fn totals_by_session(&self, user_id: &str) -> Result<Vec<SessionTrafficTotal>> {
+        let conn = self.get_conn()?;
+
+        let mut stmt = conn.prepare_cached(
+            "SELECT session_id, MAX(session_name), COUNT(*),
+                SUM(bytes_sent), SUM(bytes_received),
+                SUM(disconnected_at - connected_at), MAX(connected_at)
+            FROM connection_stats
+            WHERE user_id = ?1
+            GROUP BY session_id
+            ORDER BY SUM(bytes_sent + bytes_received) DESC"
+        )?;
+
+        let totals = stmt
+            .query_map([user_id], |row| {
+                Ok(SessionTrafficTotal {
+                    session_id: row.get(0)?,
+                    session_name: row.get(1)?,
+                    connections: row.get(2)?,
+                    bytes_sent: row.get(3)?,
+                    bytes_received: row.get(4)?,
+                    duration_secs: row.get(5)?,
+                    last_connected_at: row.get(6)?,
+                })
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(totals)
+    }
+}
@@ -64,6 +64,8 @@ pub struct DownloadRecord {
     pub error_message: Option<String>,
     pub created_at: i64,
     pub updated_at: i64,
+    /// 传输类型：`file` 或 `directory`，旧记录为空
+    pub transfer_kind: Option<String>,
 }
 
 /// 分页结果
@@ -91,8 +93,8 @@ impl DownloadRecordsRepository {
                 total_files, total_dirs, total_size, status,
                 bytes_transferred, files_completed, started_at,
                 completed_at, elapsed_ms, error_message,
-                created_at, updated_at
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)",
+                created_at, updated_at, transfer_kind
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18)",
             rusqlite::params![
                 &record.task_id,
                 &record.connection_id,
@@ -111,6 +113,7 @@ impl DownloadRecordsRepository {
                 record.error_message.as_ref().map(|s| s.as_str()),
                 record.created_at,
                 record.updated_at,
+                record.transfer_kind.as_deref(),
             ],
         )?;
 
@@ -169,7 +172,7 @@ impl DownloadRecordsRepository {
         )?;
 
         // 查询记录
-        let mut stmt = conn.prepare(
+        let mut stmt = conn.prepare_cached(
             "SELECT * FROM download_records
              WHERE user_id = ?1
              ORDER BY created_at DESC
@@ -178,28 +181,8 @@ impl DownloadRecordsRepository {
 
         let records: Result<Vec<DownloadRecord>, _> = stmt.query_map(
             rusqlite::params![user_id, page_size as i64, offset as i64],
-        |row| {
-            Ok(DownloadRecord {
-                id: row.get(0)?,
-                task_id: row.get(1)?,
-                connection_id: row.get(2)?,
-                user_id: row.get(3)?,
-                remote_path: row.get(4)?,
-                local_path: row.get(5)?,
-                total_files: row.get(6)?,
-                total_dirs: row.get(7)?,
-                total_size: row.get(8)?,
-                status: row.get(9)?,
-                bytes_transferred: row.get(10)?,
-                files_completed: row.get(11)?,
-                started_at: row.get(12)?,
-                completed_at: row.get(13)?,
-                elapsed_ms: row.get(14)?,
-                error_message: row.get(15)?,
-                created_at: row.get(16)?,
-                updated_at: row.get(17)?,
-            })
-        })?.collect();
+            Self::from_row,
+        )?.collect();
 
         Ok(PaginatedDownloadRecords {
             records: records?,
@@ -209,6 +192,38 @@ impl DownloadRecordsRepository {
         })
     }
 
+    /// 将查询结果行转换为记录（`SELECT *` 的列顺序）
+    fn from_row(row: &rusqlite::Row) -> rusqlite::Result<DownloadRecord> {
+        Ok(DownloadRecord {
+            id: row.get(0)?,
+            task_id: row.get(1)?,
+            connection_id: row.get(2)?,
+            user_id: row.get(3)?,
+            remote_path: row.get(4)?,
+            local_path: row.get(5)?,
+            total_files: row.get(6)?,
+            total_dirs: row.get(7)?,
+            total_size: row.get(8)?,
+            status: row.get(9)?,
+            bytes_transferred: row.get(10)?,
+            files_completed: row.get(11)?,
+            started_at: row.get(12)?,
+            completed_at: row.get(13)?,
+            elapsed_ms: row.get(14)?,
+            error_message: row.get(15)?,
+            created_at: row.get(16)?,
+            updated_at: row.get(17)?,
+            transfer_kind: row.get(18)?,
+        })
+    }
+
+    /// 根据 task_id 查询记录
+    pub fn find_by_task_id(conn: &Connection, task_id: &str) -> Result<Option<DownloadRecord>> {
+        let mut stmt = conn.prepare_cached("SELECT * FROM download_records WHERE task_id = ?1")?;
+        let mut rows = stmt.query_map([task_id], Self::from_row)?;
+        Ok(rows.next().transpose()?)
+    }
+
     /// 删除记录
     pub fn delete(conn: &Connection, id: i64) -> Result<()> {
         conn.execute("DELETE FROM download_records WHERE id = ?1", [id])?;
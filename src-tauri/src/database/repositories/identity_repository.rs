@@ -0,0 +1,167 @@
+use anyhow::Result;
+use r2d2::PooledConnection;
+use r2d2_sqlite::{rusqlite, SqliteConnectionManager};
+
+use crate::database::DbPool;
+use crate::models::identity::Identity;
+
+/// 共享身份 Repository
+pub struct IdentityRepository {
+    pool: DbPool,
+}
+
+impl IdentityRepository {
+    /// 创建新的 Repository 实例
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// 获取数据库连接
+    fn get_conn(&self) -> Result<PooledConnection<SqliteConnectionManager>> {
+        self.pool
+            .get()
+            .map_err(|e| anyhow::anyhow!("Failed to get database connection: {}", e))
+    }
+
+    /// 创建身份
+    pub fn create(&self, identity: &Identity) -> Result<()> {
+        let conn = self.get_conn()?;
+
+        conn.execute(
+            "INSERT INTO identities (
+                id, user_id, name, username, auth_method_encrypted, auth_nonce, created_at, updated_at
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            rusqlite::params![
+                identity.id,
+                identity.user_id,
+                identity.name,
+                identity.username,
+                identity.auth_method_encrypted,
+                identity.auth_nonce,
+                identity.created_at,
+                identity.updated_at,
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// 更新身份
+    pub fn update(&self, identity: &Identity) -> Result<()> {
+        let conn = self.get_conn()?;
+
+        conn.execute(
+            "UPDATE identities SET
+                name = ?1, username = ?2, auth_method_encrypted = ?3, auth_nonce = ?4, updated_at = ?5
+            WHERE id = ?6",
+            rusqlite::params![
+                identity.name,
+                identity.username,
+                identity.auth_method_encrypted,
+                identity.auth_nonce,
+                identity.updated_at,
+                identity.id,
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// 删除身份
+    pub fn delete(&self, id: &str) -> Result<()> {
+        let conn = self.get_conn()?;
+        conn.execute("DELETE FROM identities WHERE id = ?1", [id])?;
+        Ok(())
+    }
+
+    /// 根据 ID 获取身份
+    pub fn find_by_id(&self, id: &str) -> Result<Option<Identity>> {
+        let conn = self.get_conn()?;
+
+        let mut stmt = conn.prepare_cached(
+            "SELECT id, user_id, name, username, auth_method_encrypted, auth_nonce, created_at, updated_at
+            FROM identities
+            WHERE id = ?1"
+        )?;
+
+        let mut rows = stmt.query([id])?;
+
+        if let Some(row) = rows.next()? {
+            Ok(Some(Self::row_to_identity(row)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// 获取用户的所有身份
+    pub fn find_by_user(&self, user_id: &str) -> Result<Vec<Identity>> {
+        let conn = self.get_conn()?;
+
+        let mut stmt = conn.prepare_cached(
+            "SELECT id, user_id, name, username, auth_method_encrypted, auth_nonce, created_at, updated_at
+            FROM identities
+            WHERE user_id = ?1
+            ORDER BY name ASC"
+        )?;
+
+        let identities = stmt
+            .query_map([user_id], Self::row_to_identity)?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(identities)
+    }
+
+    /// 批量迁移身份到新用户（使用新的 device_id 重新加密认证信息）
+    pub fn batch_update_user_id(
+        &self,
+        old_user_id: &str,
+        new_user_id: &str,
+        old_device_id: &str,
+        new_device_id: &str,
+    ) -> Result<usize> {
+        let identities = self.find_by_user(old_user_id)?;
+        if identities.is_empty() {
+            return Ok(0);
+        }
+
+        let conn = self.get_conn()?;
+        let now = chrono::Utc::now().timestamp();
+
+        for identity in &identities {
+            let auth_method_json = crate::services::CryptoService::decrypt_password(
+                &identity.auth_method_encrypted,
+                &identity.auth_nonce,
+                old_device_id,
+            )
+            .map_err(|e| anyhow::anyhow!("Failed to decrypt identity {}: {}", identity.id, e))?;
+
+            let (auth_method_encrypted, auth_nonce) =
+                crate::services::CryptoService::encrypt_password(&auth_method_json, new_device_id)
+                    .map_err(|e| anyhow::anyhow!("Failed to encrypt identity {}: {}", identity.id, e))?;
+
+            conn.execute(
+                "UPDATE identities SET
+                    user_id = ?1, auth_method_encrypted = ?2, auth_nonce = ?3, updated_at = ?4
+                WHERE id = ?5",
+                (new_user_id, &auth_method_encrypted, &auth_nonce, now, &identity.id),
+            )?;
+        }
+
+        tracing::info!("Migrated {} identities from {} to {}", identities.len(), old_user_id, new_user_id);
+        Ok(identities.len())
+    }
+
+    /// 将数据库行转换为 Identity
+    fn row_to_identity(row: &rusqlite::Row) -> rusqlite::Result<Identity> {
+        Ok(Identity {
+            id: row.get(0)?,
+            user_id: row.get(1)?,
+            name: row.get(2)?,
+            username: row.get(3)?,
+            auth_method_encrypted: row.get(4)?,
+            auth_nonce: row.get(5)?,
+            created_at: row.get(6)?,
+            updated_at: row.get(7)?,
+        })
+    }
+}
@@ -4,17 +4,31 @@
 
 pub mod user_auth_repository;
 pub mod ssh_session_repository;
+pub mod session_group_repository;
 pub mod app_settings_repository;
 pub mod user_profile_repository;
 pub mod sync_state_repository;
 pub mod upload_records;
 pub mod download_records;
+pub mod session_note_repository;
+pub mod identity_repository;
+pub mod snippet_repository;
+pub mod clipboard_repository;
+pub mod variable_repository;
+pub mod connection_stats_repository;
 
 // 重新导出 Repository 类
 pub use user_auth_repository::UserAuthRepository;
 pub use ssh_session_repository::SshSessionRepository;
+pub use session_group_repository::SessionGroupRepository;
 pub use app_settings_repository::AppSettingsRepository;
 pub use user_profile_repository::UserProfileRepository;
 pub use sync_state_repository::SyncStateRepository;
 pub use upload_records::{UploadRecordsRepository, PaginatedUploadRecords, UploadRecord, UploadStatus};
-pub use download_records::{DownloadRecordsRepository, PaginatedDownloadRecords, DownloadRecord, DownloadStatus};
\ No newline at end of file
+pub use download_records::{DownloadRecordsRepository, PaginatedDownloadRecords, DownloadRecord, DownloadStatus};
+pub use session_note_repository::SessionNoteRepository;
+pub use identity_repository::IdentityRepository;
+pub use snippet_repository::SnippetRepository;
+pub use clipboard_repository::ClipboardRepository;
+pub use variable_repository::VariableRepository;
+pub use connection_stats_repository::ConnectionStatsRepository;
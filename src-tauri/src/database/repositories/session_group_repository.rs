@@ -0,0 +1,354 @@
+use anyhow::Result;
+use r2d2::PooledConnection;
+use r2d2_sqlite::{rusqlite, SqliteConnectionManager};
+
+use crate::database::DbPool;
+use crate::models::session_group::*;
+
+const GROUP_COLUMNS: &str = "id, user_id, name, color, icon, sort_order, description,
+    server_ver, client_ver, is_dirty, is_deleted, last_synced_at, created_at, updated_at";
+
+/// 迁移生成的分组 ID：由用户和分组名决定，多台设备各自迁移同一分组时得到相同的 ID，同步时合并为一条
+fn migrated_group_id(user_id: &str, name: &str) -> String {
+    format!("group-{:x}", md5::compute(format!("{}\n{}", user_id, name)))
+}
+
+/// 会话分组 Repository
+pub struct SessionGroupRepository {
+    pool: DbPool,
+}
+
+impl SessionGroupRepository {
+    /// 创建新的 Repository 实例
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// 获取数据库连接
+    fn get_conn(&self) -> Result<PooledConnection<SqliteConnectionManager>> {
+        self.pool
+            .get()
+            .map_err(|e| anyhow::anyhow!("Failed to get database connection: {}", e))
+    }
+
+    /// 保存分组（不存在时创建，存在时覆盖）
+    pub fn save(&self, group: &SessionGroup) -> Result<()> {
+        let conn = self.get_conn()?;
+
+        conn.execute(
+            "INSERT INTO session_groups (
+                id, user_id, name, color, icon, sort_order, description,
+                server_ver, client_ver, is_dirty, is_deleted, last_synced_at, created_at, updated_at
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)
+            ON CONFLICT(id) DO UPDATE SET
+                user_id = excluded.user_id,
+                name = excluded.name,
+                color = excluded.color,
+                icon = excluded.icon,
+                sort_order = excluded.sort_order,
+                description = excluded.description,
+                server_ver = excluded.server_ver,
+                client_ver = excluded.client_ver,
+                is_dirty = excluded.is_dirty,
+                is_deleted = excluded.is_deleted,
+                last_synced_at = excluded.last_synced_at,
+                updated_at = excluded.updated_at",
+            rusqlite::params![
+                group.id,
+                group.user_id,
+                group.name,
+                group.color,
+                group.icon,
+                group.sort_order,
+                group.description,
+                group.server_ver,
+                group.client_ver,
+                group.is_dirty,
+                group.is_deleted,
+                group.last_synced_at,
+                group.created_at,
+                group.updated_at,
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// 根据 ID 获取分组（包含已删除的分组）
+    pub fn find_by_id(&self, id: &str) -> Result<Option<SessionGroup>> {
+        let conn = self.get_conn()?;
+
+        let mut stmt = conn.prepare_cached(&format!(
+            "SELECT {} FROM session_groups WHERE id = ?1",
+            GROUP_COLUMNS
+        ))?;
+
+        let mut rows = stmt.query([id])?;
+        match rows.next()? {
+            Some(row) => Ok(Some(Self::row_to_group(row)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// 根据名称获取分组（包含已删除的分组）
+    pub fn find_by_name(&self, user_id: &str, name: &str) -> Result<Option<SessionGroup>> {
+        let conn = self.get_conn()?;
+
+        let mut stmt = conn.prepare_cached(&format!(
+            "SELECT {} FROM session_groups WHERE user_id = ?1 AND name = ?2",
+            GROUP_COLUMNS
+        ))?;
+
+        let mut rows = stmt.query([user_id, name])?;
+        match rows.next()? {
+            Some(row) => Ok(Some(Self::row_to_group(row)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// 获取用户的所有分组（按排序序号）
+    pub fn find_by_user(&self, user_id: &str) -> Result<Vec<SessionGroup>> {
+        let conn = self.get_conn()?;
+
+        let mut stmt = conn.prepare_cached(&format!(
+            "SELECT {} FROM session_groups
+            WHERE user_id = ?1 AND is_deleted = 0
+            ORDER BY sort_order ASC, name ASC",
+            GROUP_COLUMNS
+        ))?;
+
+        let groups = stmt
+            .query_map([user_id], Self::row_to_group)?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(groups)
+    }
+
+    /// 为会话中已使用、但还没有分组记录的分组名创建分组（从旧版本的纯字符串分组迁移）
+    pub fn import_from_sessions(&self, user_id: &str) -> Result<usize> {
+        let conn = self.get_conn()?;
+
+        let mut stmt = conn.prepare_cached(
+            "SELECT DISTINCT group_name FROM ssh_sessions
+            WHERE user_id = ?1 AND is_deleted = 0 AND group_name IS NOT NULL AND group_name != ''
+                AND group_name NOT IN (SELECT name FROM session_groups WHERE user_id = ?1 AND is_deleted = 0)
+            ORDER BY group_name ASC",
+        )?;
+        let mut names = stmt
+            .query_map([user_id], |row| row.get::<_, String>(0))?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        drop(stmt);
+
+        if !names.iter().any(|name| name == DEFAULT_GROUP_NAME)
+            && self.find_by_name(user_id, DEFAULT_GROUP_NAME)?.is_none()
+        {
+            names.insert(0, DEFAULT_GROUP_NAME.to_string());
+        }
+        if names.is_empty() {
+            return Ok(0);
+        }
+
+        let next_order: i32 = conn.query_row(
+            "SELECT COALESCE(MAX(sort_order) + 1, 0) FROM session_groups WHERE user_id = ?1",
+            [user_id],
+            |row| row.get(0),
+        )?;
+        let now = chrono::Utc::now().timestamp();
+
+        for (index, name) in names.iter().enumerate() {
+            match self.find_by_name(user_id, name)? {
+                // 本地删除过的同名分组：恢复
+                Some(mut group) => {
+                    group.is_deleted = false;
+                    group.is_dirty = true;
+                    group.client_ver += 1;
+                    group.updated_at = now;
+                    self.save(&group)?;
+                }
+                None => {
+                    self.save(&SessionGroup {
+                        id: migrated_group_id(user_id, name),
+                        user_id: user_id.to_string(),
+                        name: name.clone(),
+                        color: None,
+                        icon: None,
+                        sort_order: next_order + index as i32,
+                        description: None,
+                        server_ver: 0,
+                        client_ver: 1,
+                        is_dirty: true,
+                        is_deleted: false,
+                        last_synced_at: None,
+                        created_at: now,
+                        updated_at: now,
+                    })?;
+                }
+            }
+        }
+
+        tracing::info!("Imported {} session group(s) for user {}", names.len(), user_id);
+        Ok(names.len())
+    }
+
+    /// 将分组下的会话移动到另一个分组（重命名、删除分组时使用），会话标记为需要同步
+    pub fn reassign_sessions(&self, user_id: &str, from: &str, to: &str) -> Result<usize> {
+        let conn = self.get_conn()?;
+        let now = chrono::Utc::now().timestamp();
+
+        let count = conn.execute(
+            "UPDATE ssh_sessions SET
+                group_name = ?1,
+                updated_at = ?2,
+                client_ver = client_ver + 1,
+                is_dirty = 1
+            WHERE user_id = ?3 AND group_name = ?4 AND is_deleted = 0",
+            (to, now, user_id, from),
+        )?;
+
+        Ok(count)
+    }
+
+    /// 删除分组：从未同步过的直接删除，否则标记删除等待同步
+    pub fn delete(&self, group: &SessionGroup) -> Result<()> {
+        let conn = self.get_conn()?;
+
+        if group.server_ver == 0 {
+            conn.execute("DELETE FROM session_groups WHERE id = ?1", [&group.id])?;
+        } else {
+            conn.execute(
+                "UPDATE session_groups SET is_deleted = 1, is_dirty = 1, updated_at = ?1 WHERE id = ?2",
+                (chrono::Utc::now().timestamp(), &group.id),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// 获取所有需要同步的分组（脏数据）
+    pub fn get_dirty_groups(&self, user_id: &str) -> Result<Vec<SessionGroup>> {
+        let conn = self.get_conn()?;
+
+        let mut stmt = conn.prepare_cached(&format!(
+            "SELECT {} FROM session_groups WHERE user_id = ?1 AND is_dirty = 1 AND is_deleted = 0",
+            GROUP_COLUMNS
+        ))?;
+
+        let groups = stmt
+            .query_map([user_id], Self::row_to_group)?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(groups)
+    }
+
+    /// 获取已删除的分组 ID（仅返回未同步的删除操作）
+    pub fn get_deleted_groups(&self, user_id: &str) -> Result<Vec<String>> {
+        let conn = self.get_conn()?;
+
+        let mut stmt = conn.prepare_cached(
+            "SELECT id FROM session_groups WHERE user_id = ?1 AND is_deleted = 1 AND is_dirty = 1",
+        )?;
+
+        let ids = stmt
+            .query_map([user_id], |row| row.get::<_, String>(0))?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(ids)
+    }
+
+    /// 同步完成后清理脏标记，已同步的删除操作直接移除记录
+    pub fn clear_dirty_markers(&self, user_id: &str, sync_time: i64) -> Result<()> {
+        let conn = self.get_conn()?;
+
+        conn.execute(
+            "DELETE FROM session_groups WHERE user_id = ?1 AND is_deleted = 1 AND is_dirty = 1",
+            [user_id],
+        )?;
+        conn.execute(
+            "UPDATE session_groups SET is_dirty = 0, last_synced_at = ?1 WHERE user_id = ?2 AND is_dirty = 1",
+            (sync_time, user_id),
+        )?;
+
+        Ok(())
+    }
+
+    /// 更新服务器版本号（Push 成功后）
+    pub fn set_server_ver(&self, id: &str, server_ver: i32, sync_time: i64) -> Result<()> {
+        let conn = self.get_conn()?;
+
+        conn.execute(
+            "UPDATE session_groups SET server_ver = ?1, last_synced_at = ?2 WHERE id = ?3",
+            (server_ver, sync_time, id),
+        )?;
+
+        Ok(())
+    }
+
+    /// 应用服务器拉取的分组
+    ///
+    /// 服务器已删除的分组直接移除；本地版本不低于服务器时跳过；
+    /// 其他设备迁移生成的同名分组（ID 不同）以服务器为准
+    pub fn apply_server_group(&self, server: &ServerSessionGroup) -> Result<()> {
+        let conn = self.get_conn()?;
+
+        if server.deleted_at.is_some() {
+            conn.execute("DELETE FROM session_groups WHERE id = ?1", [&server.id])?;
+            return Ok(());
+        }
+
+        let existing = self.find_by_id(&server.id)?;
+        if existing.as_ref().is_some_and(|local| local.server_ver >= server.server_ver) {
+            return Ok(());
+        }
+
+        conn.execute(
+            "DELETE FROM session_groups WHERE user_id = ?1 AND name = ?2 AND id != ?3",
+            (&server.user_id, &server.name, &server.id),
+        )?;
+
+        let mut group: SessionGroup = server.clone().into();
+        if let Some(local) = existing {
+            group.is_dirty = local.is_dirty;
+            group.is_deleted = local.is_deleted;
+        }
+        self.save(&group)
+    }
+
+    /// 批量更新分组的 user_id（用于从匿名用户迁移到登录用户），已存在的同名分组保留登录用户的
+    pub fn batch_update_user_id(&self, old_user_id: &str, new_user_id: &str) -> Result<usize> {
+        let conn = self.get_conn()?;
+        let now = chrono::Utc::now().timestamp();
+
+        let count = conn.execute(
+            "UPDATE OR IGNORE session_groups SET
+                user_id = ?1,
+                updated_at = ?2,
+                client_ver = client_ver + 1,
+                is_dirty = 1
+            WHERE user_id = ?3",
+            (new_user_id, now, old_user_id),
+        )?;
+        conn.execute("DELETE FROM session_groups WHERE user_id = ?1", [old_user_id])?;
+
+        Ok(count)
+    }
+
+    /// 将数据库行转换为 SessionGroup
+    fn row_to_group(row: &rusqlite::Row) -> rusqlite::Result<SessionGroup> {
+        Ok(SessionGroup {
+            id: row.get(0)?,
+            user_id: row.get(1)?,
+            name: row.get(2)?,
+            color: row.get(3)?,
+            icon: row.get(4)?,
+            sort_order: row.get(5)?,
+            description: row.get(6)?,
+            server_ver: row.get(7)?,
+            client_ver: row.get(8)?,
+            is_dirty: row.get(9)?,
+            is_deleted: row.get(10)?,
+            last_synced_at: row.get(11)?,
+            created_at: row.get(12)?,
+            updated_at: row.get(13)?,
+        })
+    }
+}
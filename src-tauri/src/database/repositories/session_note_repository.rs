@@ -0,0 +1,515 @@
+use anyhow::Result;
+use r2d2::PooledConnection;
+use r2d2_sqlite::{rusqlite, SqliteConnectionManager};
+
+use crate::database::DbPool;
+use crate::models::session_note::*;
+
+/// 笔记查询列
+const NOTE_COLUMNS: &str = "session_id, user_id, content, sync_enabled, server_ver, client_ver,
+    is_dirty, last_synced_at, created_at, updated_at";
+
+/// 附件查询列
+const ATTACHMENT_COLUMNS: &str = "id, session_id, user_id, file_name, mime_type, size, stored_path,
+    sync_enabled, server_ver, client_ver, is_dirty, last_synced_at, created_at, updated_at";
+
+/// 会话笔记与附件 Repository
+///
+/// 已同步过的笔记和附件删除时只标记 `is_deleted`，下次同步通知服务器后再真正删除
+pub struct SessionNoteRepository {
+    pool: DbPool,
+}
+
+impl SessionNoteRepository {
+    /// 创建新的 Repository 实例
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// 获取数据库连接
+    fn get_conn(&self) -> Result<PooledConnection<SqliteConnectionManager>> {
+        self.pool
+            .get()
+            .map_err(|e| anyhow::anyhow!("Failed to get database connection: {}", e))
+    }
+
+    // ==================== 笔记 ====================
+
+    /// 获取会话笔记
+    pub fn find_note(&self, session_id: &str) -> Result<Option<SessionNote>> {
+        let conn = self.get_conn()?;
+
+        let mut stmt = conn.prepare_cached(&format!(
+            "SELECT {} FROM session_notes WHERE session_id = ?1 AND is_deleted = 0",
+            NOTE_COLUMNS
+        ))?;
+
+        let mut rows = stmt.query([session_id])?;
+
+        if let Some(row) = rows.next()? {
+            Ok(Some(Self::row_to_note(row)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// 保存会话笔记（不存在则创建），标记为待同步
+    pub fn upsert_note(&self, note: &SessionNote) -> Result<()> {
+        let conn = self.get_conn()?;
+
+        conn.execute(
+            "INSERT INTO session_notes (
+                session_id, user_id, content, sync_enabled, server_ver, client_ver,
+                is_dirty, is_deleted, created_at, updated_at
+            ) VALUES (?1, ?2, ?3, ?4, 0, 1, 1, 0, ?5, ?6)
+            ON CONFLICT(session_id) DO UPDATE SET
+                content = excluded.content,
+                client_ver = client_ver + 1,
+                is_dirty = 1,
+                is_deleted = 0,
+                updated_at = excluded.updated_at",
+            rusqlite::params![
+                note.session_id,
+                note.user_id,
+                note.content,
+                note.sync_enabled,
+                note.created_at,
+                note.updated_at,
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// 设置笔记是否同步（取消同步时下次同步会删除服务器上的副本）
+    pub fn set_note_sync(&self, session_id: &str, user_id: &str, enabled: bool) -> Result<()> {
+        let conn = self.get_conn()?;
+
+        conn.execute(
+            "UPDATE session_notes SET sync_enabled = ?1, user_id = ?2, is_dirty = 1
+            WHERE session_id = ?3 AND is_deleted = 0",
+            rusqlite::params![enabled, user_id, session_id],
+        )?;
+
+        Ok(())
+    }
+
+    /// 删除会话笔记
+    pub fn delete_note(&self, session_id: &str) -> Result<()> {
+        let conn = self.get_conn()?;
+        Self::delete_notes_where(&conn, "session_id = ?1", session_id)?;
+        Ok(())
+    }
+
+    /// 获取需要推送的笔记（已开启同步且有未同步的修改）
+    pub fn get_dirty_notes(&self, user_id: &str) -> Result<Vec<SessionNote>> {
+        let conn = self.get_conn()?;
+
+        let mut stmt = conn.prepare_cached(&format!(
+            "SELECT {} FROM session_notes
+            WHERE user_id = ?1 AND sync_enabled = 1 AND is_dirty = 1 AND is_deleted = 0",
+            NOTE_COLUMNS
+        ))?;
+
+        let notes = stmt
+            .query_map([user_id], Self::row_to_note)?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(notes)
+    }
+
+    /// 获取需要从服务器删除的笔记（已同步过，之后被删除或取消同步）
+    pub fn get_deleted_note_ids(&self, user_id: &str) -> Result<Vec<String>> {
+        let conn = self.get_conn()?;
+
+        let mut stmt = conn.prepare_cached(
+            "SELECT session_id FROM session_notes
+            WHERE user_id = ?1 AND is_dirty = 1 AND server_ver > 0 AND (is_deleted = 1 OR sync_enabled = 0)",
+        )?;
+
+        let ids = stmt
+            .query_map([user_id], |row| row.get::<_, String>(0))?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(ids)
+    }
+
+    /// 推送成功后清理笔记的脏标记
+    pub fn clear_note_dirty_markers(&self, user_id: &str, sync_time: i64) -> Result<()> {
+        let conn = self.get_conn()?;
+
+        conn.execute(
+            "DELETE FROM session_notes WHERE user_id = ?1 AND is_deleted = 1 AND is_dirty = 1",
+            [user_id],
+        )?;
+        // 取消同步的笔记在服务器上已删除
+        conn.execute(
+            "UPDATE session_notes SET server_ver = 0 WHERE user_id = ?1 AND sync_enabled = 0 AND is_dirty = 1",
+            [user_id],
+        )?;
+        conn.execute(
+            "UPDATE session_notes SET is_dirty = 0, last_synced_at = ?1 WHERE user_id = ?2 AND is_dirty = 1",
+            (sync_time, user_id),
+        )?;
+
+        Ok(())
+    }
+
+    /// 更新笔记的服务器版本号
+    pub fn set_note_server_ver(&self, session_id: &str, server_ver: i32, sync_time: i64) -> Result<()> {
+        let conn = self.get_conn()?;
+
+        conn.execute(
+            "UPDATE session_notes SET server_ver = ?1, last_synced_at = ?2 WHERE session_id = ?3",
+            (server_ver, sync_time, session_id),
+        )?;
+
+        Ok(())
+    }
+
+    /// 应用从服务器拉取的笔记（本地有未同步的修改时保留本地版本）
+    pub fn apply_server_note(&self, server: &ServerSessionNote) -> Result<()> {
+        let conn = self.get_conn()?;
+
+        if server.deleted_at.is_some() {
+            conn.execute(
+                "DELETE FROM session_notes WHERE session_id = ?1 AND sync_enabled = 1 AND is_dirty = 0",
+                [&server.session_id],
+            )?;
+            return Ok(());
+        }
+
+        conn.execute(
+            "INSERT INTO session_notes (
+                session_id, user_id, content, sync_enabled, server_ver, client_ver,
+                is_dirty, is_deleted, last_synced_at, created_at, updated_at
+            ) VALUES (?1, ?2, ?3, 1, ?4, ?5, 0, 0, ?6, ?7, ?6)
+            ON CONFLICT(session_id) DO UPDATE SET
+                content = excluded.content,
+                sync_enabled = 1,
+                server_ver = excluded.server_ver,
+                client_ver = excluded.client_ver,
+                last_synced_at = excluded.last_synced_at,
+                updated_at = excluded.updated_at
+            WHERE is_dirty = 0 AND server_ver < excluded.server_ver",
+            rusqlite::params![
+                server.session_id,
+                server.user_id,
+                server.content,
+                server.server_ver,
+                server.client_ver,
+                server.updated_at,
+                server.created_at,
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    // ==================== 附件 ====================
+
+    /// 创建附件记录
+    pub fn create_attachment(&self, attachment: &SessionAttachment) -> Result<()> {
+        let conn = self.get_conn()?;
+
+        conn.execute(
+            "INSERT INTO session_attachments (
+                id, session_id, user_id, file_name, mime_type, size,
+                stored_path, sync_enabled, server_ver, client_ver, is_dirty, is_deleted,
+                created_at, updated_at
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, 0, 1, 1, 0, ?9, ?10)",
+            rusqlite::params![
+                attachment.id,
+                attachment.session_id,
+                attachment.user_id,
+                attachment.file_name,
+                attachment.mime_type,
+                attachment.size,
+                attachment.stored_path,
+                attachment.sync_enabled,
+                attachment.created_at,
+                attachment.updated_at,
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// 根据 ID 获取附件
+    pub fn find_attachment(&self, id: &str) -> Result<Option<SessionAttachment>> {
+        let conn = self.get_conn()?;
+
+        let mut stmt = conn.prepare_cached(&format!(
+            "SELECT {} FROM session_attachments WHERE id = ?1 AND is_deleted = 0",
+            ATTACHMENT_COLUMNS
+        ))?;
+
+        let mut rows = stmt.query([id])?;
+
+        if let Some(row) = rows.next()? {
+            Ok(Some(Self::row_to_attachment(row)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// 获取会话的所有附件
+    pub fn find_attachments_by_session(&self, session_id: &str) -> Result<Vec<SessionAttachment>> {
+        let conn = self.get_conn()?;
+
+        let mut stmt = conn.prepare_cached(&format!(
+            "SELECT {} FROM session_attachments
+            WHERE session_id = ?1 AND is_deleted = 0
+            ORDER BY created_at ASC",
+            ATTACHMENT_COLUMNS
+        ))?;
+
+        let attachments = stmt
+            .query_map([session_id], Self::row_to_attachment)?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(attachments)
+    }
+
+    /// 设置附件是否同步（取消同步时下次同步会删除服务器上的副本）
+    pub fn set_attachment_sync(&self, id: &str, user_id: &str, enabled: bool) -> Result<()> {
+        let conn = self.get_conn()?;
+
+        conn.execute(
+            "UPDATE session_attachments SET sync_enabled = ?1, user_id = ?2, is_dirty = 1
+            WHERE id = ?3 AND is_deleted = 0",
+            rusqlite::params![enabled, user_id, id],
+        )?;
+
+        Ok(())
+    }
+
+    /// 删除附件记录
+    pub fn delete_attachment(&self, id: &str) -> Result<()> {
+        let conn = self.get_conn()?;
+        Self::delete_attachments_where(&conn, "id = ?1", id)?;
+        Ok(())
+    }
+
+    /// 获取需要推送的附件（已开启同步且有未同步的修改）
+    pub fn get_dirty_attachments(&self, user_id: &str) -> Result<Vec<SessionAttachment>> {
+        let conn = self.get_conn()?;
+
+        let mut stmt = conn.prepare_cached(&format!(
+            "SELECT {} FROM session_attachments
+            WHERE user_id = ?1 AND sync_enabled = 1 AND is_dirty = 1 AND is_deleted = 0
+            ORDER BY created_at ASC",
+            ATTACHMENT_COLUMNS
+        ))?;
+
+        let attachments = stmt
+            .query_map([user_id], Self::row_to_attachment)?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(attachments)
+    }
+
+    /// 获取需要从服务器删除的附件（已同步过，之后被删除或取消同步）
+    pub fn get_deleted_attachment_ids(&self, user_id: &str) -> Result<Vec<String>> {
+        let conn = self.get_conn()?;
+
+        let mut stmt = conn.prepare_cached(
+            "SELECT id FROM session_attachments
+            WHERE user_id = ?1 AND is_dirty = 1 AND server_ver > 0 AND (is_deleted = 1 OR sync_enabled = 0)",
+        )?;
+
+        let ids = stmt
+            .query_map([user_id], |row| row.get::<_, String>(0))?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(ids)
+    }
+
+    /// 推送成功后清理指定附件的脏标记（附件分批推送，未推送的保持待同步）
+    pub fn clear_attachment_dirty_markers(&self, ids: &[String], sync_time: i64) -> Result<()> {
+        let conn = self.get_conn()?;
+
+        for id in ids {
+            conn.execute(
+                "DELETE FROM session_attachments WHERE id = ?1 AND is_deleted = 1",
+                [id],
+            )?;
+            // 取消同步的附件在服务器上已删除
+            conn.execute(
+                "UPDATE session_attachments SET server_ver = 0 WHERE id = ?1 AND sync_enabled = 0",
+                [id],
+            )?;
+            conn.execute(
+                "UPDATE session_attachments SET is_dirty = 0, last_synced_at = ?1 WHERE id = ?2",
+                (sync_time, id),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// 更新附件的服务器版本号
+    pub fn set_attachment_server_ver(&self, id: &str, server_ver: i32, sync_time: i64) -> Result<()> {
+        let conn = self.get_conn()?;
+
+        conn.execute(
+            "UPDATE session_attachments SET server_ver = ?1, last_synced_at = ?2 WHERE id = ?3",
+            (server_ver, sync_time, id),
+        )?;
+
+        Ok(())
+    }
+
+    /// 判断服务器上的附件是否需要写入本地（本地没有未同步的修改且版本更旧）
+    pub fn should_apply_server_attachment(&self, id: &str, server_ver: i32) -> Result<bool> {
+        let conn = self.get_conn()?;
+
+        let local: Option<(i32, bool)> = conn
+            .query_row(
+                "SELECT server_ver, is_dirty FROM session_attachments WHERE id = ?1",
+                [id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .map(Some)
+            .or_else(|e| match e {
+                rusqlite::Error::QueryReturnedNoRows => Ok(None),
+                e => Err(e),
+            })?;
+
+        Ok(local.is_none_or(|(local_ver, is_dirty)| !is_dirty && local_ver < server_ver))
+    }
+
+    /// 保存从服务器拉取的附件记录（文件内容已写入 `stored_path`）
+    pub fn save_server_attachment(&self, attachment: &SessionAttachment) -> Result<()> {
+        let conn = self.get_conn()?;
+
+        conn.execute(
+            "INSERT INTO session_attachments (
+                id, session_id, user_id, file_name, mime_type, size,
+                stored_path, sync_enabled, server_ver, client_ver, is_dirty, is_deleted,
+                last_synced_at, created_at, updated_at
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, 1, ?8, ?9, 0, 0, ?10, ?11, ?12)
+            ON CONFLICT(id) DO UPDATE SET
+                session_id = excluded.session_id,
+                file_name = excluded.file_name,
+                mime_type = excluded.mime_type,
+                size = excluded.size,
+                stored_path = excluded.stored_path,
+                sync_enabled = 1,
+                server_ver = excluded.server_ver,
+                client_ver = excluded.client_ver,
+                last_synced_at = excluded.last_synced_at,
+                updated_at = excluded.updated_at",
+            rusqlite::params![
+                attachment.id,
+                attachment.session_id,
+                attachment.user_id,
+                attachment.file_name,
+                attachment.mime_type,
+                attachment.size,
+                attachment.stored_path,
+                attachment.server_ver,
+                attachment.client_ver,
+                attachment.last_synced_at,
+                attachment.created_at,
+                attachment.updated_at,
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// 删除从服务器同步删除的附件记录，返回被删除的附件（本地有未同步的修改时保留）
+    pub fn delete_server_deleted_attachment(&self, id: &str) -> Result<Option<SessionAttachment>> {
+        let Some(attachment) = self.find_attachment(id)? else {
+            return Ok(None);
+        };
+        if !attachment.sync_enabled || attachment.is_dirty {
+            return Ok(None);
+        }
+
+        let conn = self.get_conn()?;
+        conn.execute("DELETE FROM session_attachments WHERE id = ?1", [id])?;
+        Ok(Some(attachment))
+    }
+
+    /// 删除会话的笔记和所有附件记录
+    pub fn delete_for_session(&self, session_id: &str) -> Result<()> {
+        let conn = self.get_conn()?;
+        Self::delete_notes_where(&conn, "session_id = ?1", session_id)?;
+        Self::delete_attachments_where(&conn, "session_id = ?1", session_id)?;
+        Ok(())
+    }
+
+    /// 删除笔记：已同步过的只标记删除并清空内容，等待同步
+    fn delete_notes_where(conn: &rusqlite::Connection, condition: &str, value: &str) -> Result<()> {
+        let now = chrono::Utc::now().timestamp();
+        conn.execute(
+            &format!(
+                "UPDATE session_notes SET is_deleted = 1, is_dirty = 1, content = '', updated_at = ?2
+                WHERE {} AND server_ver > 0",
+                condition
+            ),
+            rusqlite::params![value, now],
+        )?;
+        conn.execute(
+            &format!("DELETE FROM session_notes WHERE {} AND server_ver = 0", condition),
+            [value],
+        )?;
+        Ok(())
+    }
+
+    /// 删除附件记录：已同步过的只标记删除，等待同步
+    fn delete_attachments_where(conn: &rusqlite::Connection, condition: &str, value: &str) -> Result<()> {
+        let now = chrono::Utc::now().timestamp();
+        conn.execute(
+            &format!(
+                "UPDATE session_attachments SET is_deleted = 1, is_dirty = 1, updated_at = ?2
+                WHERE {} AND server_ver > 0",
+                condition
+            ),
+            rusqlite::params![value, now],
+        )?;
+        conn.execute(
+            &format!("DELETE FROM session_attachments WHERE {} AND server_ver = 0", condition),
+            [value],
+        )?;
+        Ok(())
+    }
+
+    /// 将数据库行转换为 SessionNote
+    fn row_to_note(row: &rusqlite::Row) -> rusqlite::Result<SessionNote> {
+        Ok(SessionNote {
+            session_id: row.get(0)?,
+            user_id: row.get(1)?,
+            content: row.get(2)?,
+            sync_enabled: row.get(3)?,
+            server_ver: row.get(4)?,
+            client_ver: row.get(5)?,
+            is_dirty: row.get(6)?,
+            last_synced_at: row.get(7)?,
+            created_at: row.get(8)?,
+            updated_at: row.get(9)?,
+        })
+    }
+
+    /// 将数据库行转换为 SessionAttachment
+    fn row_to_attachment(row: &rusqlite::Row) -> rusqlite::Result<SessionAttachment> {
+        Ok(SessionAttachment {
+            id: row.get(0)?,
+            session_id: row.get(1)?,
+            user_id: row.get(2)?,
+            file_name: row.get(3)?,
+            mime_type: row.get(4)?,
+            size: row.get(5)?,
+            stored_path: row.get(6)?,
+            sync_enabled: row.get(7)?,
+            server_ver: row.get(8)?,
+            client_ver: row.get(9)?,
+            is_dirty: row.get(10)?,
+            last_synced_at: row.get(11)?,
+            created_at: row.get(12)?,
+            updated_at: row.get(13)?,
+        })
+    }
+}
@@ -0,0 +1,192 @@
+use anyhow::Result;
+use r2d2::PooledConnection;
+use r2d2_sqlite::{rusqlite, SqliteConnectionManager};
+
+use crate::database::DbPool;
+use crate::models::snippet::*;
+
+/// 每台主机保留的最大历史命令数
+const MAX_HISTORY_PER_HOST: i64 = 1000;
+
+/// 命令片段与主机命令历史 Repository
+pub struct SnippetRepository {
+    pool: DbPool,
+}
+
+impl SnippetRepository {
+    /// 创建新的 Repository 实例
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// 获取数据库连接
+    fn get_conn(&self) -> Result<PooledConnection<SqliteConnectionManager>> {
+        self.pool
+            .get()
+            .map_err(|e| anyhow::anyhow!("Failed to get database connection: {}", e))
+    }
+
+    // ==================== 命令片段 ====================
+
+    /// 创建片段
+    pub fn create(&self, snippet: &Snippet) -> Result<()> {
+        let conn = self.get_conn()?;
+
+        conn.execute(
+            "INSERT INTO snippets (id, user_id, name, command, description, created_at, updated_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            rusqlite::params![
+                snippet.id,
+                snippet.user_id,
+                snippet.name,
+                snippet.command,
+                snippet.description,
+                snippet.created_at,
+                snippet.updated_at,
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// 更新片段
+    pub fn update(&self, snippet: &Snippet) -> Result<()> {
+        let conn = self.get_conn()?;
+
+        conn.execute(
+            "UPDATE snippets SET name = ?1, command = ?2, description = ?3, updated_at = ?4 WHERE id = ?5",
+            rusqlite::params![
+                snippet.name,
+                snippet.command,
+                snippet.description,
+                snippet.updated_at,
+                snippet.id,
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// 删除片段
+    pub fn delete(&self, id: &str) -> Result<()> {
+        let conn = self.get_conn()?;
+        conn.execute("DELETE FROM snippets WHERE id = ?1", [id])?;
+        Ok(())
+    }
+
+    /// 根据 ID 获取片段
+    pub fn find_by_id(&self, id: &str) -> Result<Option<Snippet>> {
+        let conn = self.get_conn()?;
+
+        let mut stmt = conn.prepare_cached(
+            "SELECT id, user_id, name, command, description, created_at, updated_at
+            FROM snippets
+            WHERE id = ?1"
+        )?;
+
+        let mut rows = stmt.query([id])?;
+
+        if let Some(row) = rows.next()? {
+            Ok(Some(Self::row_to_snippet(row)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// 获取用户的所有片段
+    pub fn find_by_user(&self, user_id: &str) -> Result<Vec<Snippet>> {
+        let conn = self.get_conn()?;
+
+        let mut stmt = conn.prepare_cached(
+            "SELECT id, user_id, name, command, description, created_at, updated_at
+            FROM snippets
+            WHERE user_id = ?1
+            ORDER BY name ASC"
+        )?;
+
+        let snippets = stmt
+            .query_map([user_id], Self::row_to_snippet)?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(snippets)
+    }
+
+    /// 将数据库行转换为 Snippet
+    fn row_to_snippet(row: &rusqlite::Row) -> rusqlite::Result<Snippet> {
+        Ok(Snippet {
+            id: row.get(0)?,
+            user_id: row.get(1)?,
+            name: row.get(2)?,
+            command: row.get(3)?,
+            description: row.get(4)?,
+            created_at: row.get(5)?,
+            updated_at: row.get(6)?,
+        })
+    }
+
+    // ==================== 主机命令历史 ====================
+
+    /// 记录一次命令使用（已存在则累加次数），并裁剪超出上限的旧记录
+    pub fn record_command(&self, user_id: &str, host_key: &str, command: &str) -> Result<()> {
+        let conn = self.get_conn()?;
+        let now = chrono::Utc::now().timestamp();
+
+        conn.execute(
+            "INSERT INTO command_history (user_id, host_key, command, use_count, last_used_at)
+            VALUES (?1, ?2, ?3, 1, ?4)
+            ON CONFLICT(user_id, host_key, command) DO UPDATE SET
+                use_count = use_count + 1,
+                last_used_at = excluded.last_used_at",
+            (user_id, host_key, command, now),
+        )?;
+
+        conn.execute(
+            "DELETE FROM command_history
+            WHERE user_id = ?1 AND host_key = ?2 AND id NOT IN (
+                SELECT id FROM command_history
+                WHERE user_id = ?1 AND host_key = ?2
+                ORDER BY last_used_at DESC
+                LIMIT ?3
+            )",
+            (user_id, host_key, MAX_HISTORY_PER_HOST),
+        )?;
+
+        Ok(())
+    }
+
+    /// 获取主机的命令历史（最近使用的在前）
+    pub fn find_history(&self, user_id: &str, host_key: &str) -> Result<Vec<CommandHistoryEntry>> {
+        let conn = self.get_conn()?;
+
+        let mut stmt = conn.prepare_cached(
+            "SELECT user_id, host_key, command, use_count, last_used_at
+            FROM command_history
+            WHERE user_id = ?1 AND host_key = ?2
+            ORDER BY last_used_at DESC"
+        )?;
+
+        let entries = stmt
+            .query_map([user_id, host_key], |row| {
+                Ok(CommandHistoryEntry {
+                    user_id: row.get(0)?,
+                    host_key: row.get(1)?,
+                    command: row.get(2)?,
+                    use_count: row.get(3)?,
+                    last_used_at: row.get(4)?,
+                })
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(entries)
+    }
+
+    /// 清空主机的命令历史
+    pub fn clear_history(&self, user_id: &str, host_key: &str) -> Result<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "DELETE FROM command_history WHERE user_id = ?1 AND host_key = ?2",
+            [user_id, host_key],
+        )?;
+        Ok(())
+    }
+}
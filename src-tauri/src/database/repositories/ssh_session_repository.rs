@@ -118,11 +118,47 @@ impl SshSessionRepository {
         Ok(())
     }
 
+    /// 恢复软删除的会话（标记为脏和已恢复，下次同步时通知服务器取消软删除）
+    pub fn restore(&self, id: &str) -> Result<bool> {
+        let conn = self.get_conn()?;
+        let now = chrono::Utc::now().timestamp();
+
+        let affected = conn.execute(
+            "UPDATE ssh_sessions SET is_deleted = 0, deleted_at = NULL, restored = 1, is_dirty = 1, client_ver = client_ver + 1, updated_at = ?1
+             WHERE id = ?2 AND is_deleted = 1",
+            (now, id),
+        )?;
+
+        Ok(affected > 0)
+    }
+
+    /// 获取在指定时间之后删除的会话（回收站）
+    pub fn find_deleted_since(&self, user_id: &str, since: i64) -> Result<Vec<SshSession>> {
+        let ids: Vec<String> = {
+            let conn = self.get_conn()?;
+            let mut stmt = conn.prepare_cached(
+                "SELECT id FROM ssh_sessions WHERE user_id = ?1 AND is_deleted = 1 AND deleted_at > ?2
+                 ORDER BY deleted_at DESC"
+            )?;
+            let rows = stmt.query_map((user_id, since), |row| row.get::<_, String>(0))?;
+            rows.collect::<rusqlite::Result<_>>()?
+        };
+
+        let mut sessions = Vec::new();
+        for id in ids {
+            if let Some(session) = self.find_by_id(&id)? {
+                sessions.push(session);
+            }
+        }
+
+        Ok(sessions)
+    }
+
     /// 根据 ID 获取 SSH 会话
     pub fn find_by_id(&self, id: &str) -> Result<Option<SshSession>> {
         let conn = self.get_conn()?;
 
-        let mut stmt = conn.prepare(
+        let mut stmt = conn.prepare_cached(
             "SELECT
                 id, user_id, name, host, port, username, group_name,
                 terminal_type, columns, rows,
@@ -148,7 +184,7 @@ impl SshSessionRepository {
 
         tracing::info!("[find_by_user] Querying sessions for user_id: {}", user_id);
 
-        let mut stmt = conn.prepare(
+        let mut stmt = conn.prepare_cached(
             "SELECT
                 id, user_id, name, host, port, username, group_name,
                 terminal_type, columns, rows,
@@ -229,7 +265,7 @@ impl SshSessionRepository {
     pub fn get_dirty_sessions(&self, user_id: &str) -> Result<Vec<SshSession>> {
         let conn = self.get_conn()?;
 
-        let mut stmt = conn.prepare(
+        let mut stmt = conn.prepare_cached(
             "SELECT
                 id, user_id, name, host, port, username, group_name,
                 terminal_type, columns, rows,
@@ -308,7 +344,7 @@ impl SshSessionRepository {
     pub fn get_deleted_sessions(&self, user_id: &str) -> Result<Vec<String>> {
         let conn = self.get_conn()?;
 
-        let mut stmt = conn.prepare(
+        let mut stmt = conn.prepare_cached(
             "SELECT id FROM ssh_sessions WHERE user_id = ?1 AND is_deleted = 1 AND is_dirty = 1"
         )?;
 
@@ -322,6 +358,24 @@ impl SshSessionRepository {
         Ok(ids)
     }
 
+    /// 获取从回收站恢复、尚未同步的会话 ID
+    pub fn get_restored_sessions(&self, user_id: &str) -> Result<Vec<String>> {
+        let conn = self.get_conn()?;
+
+        let mut stmt = conn.prepare_cached(
+            "SELECT id FROM ssh_sessions WHERE user_id = ?1 AND is_deleted = 0 AND is_dirty = 1 AND restored = 1"
+        )?;
+
+        let rows = stmt.query_map([user_id], |row| row.get::<_, String>(0))?;
+
+        let mut ids = Vec::new();
+        for row in rows {
+            ids.push(row?);
+        }
+
+        Ok(ids)
+    }
+
     /// 根据 user_id 获取 SSH 会话列表
     pub fn find_by_user_id(&self, user_id: &str) -> Result<Vec<SshSession>> {
         self.find_by_user(user_id)
@@ -333,7 +387,7 @@ impl SshSessionRepository {
         let conn = self.get_conn()?;
 
         conn.execute(
-            "UPDATE ssh_sessions SET is_dirty = 0, last_synced_at = ?1 WHERE id = ?2",
+            "UPDATE ssh_sessions SET is_dirty = 0, restored = 0, last_synced_at = ?1 WHERE id = ?2",
             (sync_time, id),
         )?;
 
@@ -428,6 +482,363 @@ impl SshSessionRepository {
         Ok(updated_count)
     }
 
+    /// 在一个事务中批量更新会话（批量编辑），全部成功或全部回滚
+    ///
+    /// `identity_id` 为 Some 时同时设置会话引用的身份（`Some(None)` 表示解除引用）
+    pub fn update_batch(&self, sessions: &[SshSession], identity_id: Option<Option<&str>>) -> Result<()> {
+        let mut conn = self.get_conn()?;
+        let tx = conn.transaction()?;
+
+        for session in sessions {
+            tx.execute(
+                "UPDATE ssh_sessions SET
+                    username = ?1, port = ?2, group_name = ?3,
+                    auth_method_encrypted = ?4, auth_nonce = ?5,
+                    client_ver = ?6, is_dirty = ?7, updated_at = ?8
+                WHERE id = ?9",
+                rusqlite::params![
+                    session.username,
+                    session.port as i32,
+                    session.group_name,
+                    session.auth_method_encrypted,
+                    session.auth_nonce,
+                    session.client_ver,
+                    session.is_dirty as i32,
+                    session.updated_at,
+                    session.id,
+                ],
+            )?;
+            if let Some(identity_id) = identity_id {
+                tx.execute(
+                    "UPDATE ssh_sessions SET identity_id = ?1 WHERE id = ?2",
+                    rusqlite::params![identity_id, session.id],
+                )?;
+            }
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    // ==================== 共享身份 ====================
+
+    /// 设置会话引用的身份（None 表示解除引用，会话保留当前凭据副本）
+    pub fn set_identity_id(&self, session_id: &str, identity_id: Option<&str>) -> Result<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "UPDATE ssh_sessions SET identity_id = ?1 WHERE id = ?2",
+            rusqlite::params![identity_id, session_id],
+        )?;
+        Ok(())
+    }
+
+    /// 获取会话引用的身份 ID
+    pub fn find_identity_id(&self, session_id: &str) -> Result<Option<String>> {
+        let conn = self.get_conn()?;
+        let mut stmt = conn.prepare_cached("SELECT identity_id FROM ssh_sessions WHERE id = ?1")?;
+        let mut rows = stmt.query([session_id])?;
+
+        match rows.next()? {
+            Some(row) => Ok(row.get(0)?),
+            None => Ok(None),
+        }
+    }
+
+    /// 将身份的用户名和认证信息写入所有引用它的会话，并标记为需要同步
+    ///
+    /// 会话各自保留一份凭据副本，连接和同步流程无需感知身份；返回受影响的会话 ID
+    pub fn apply_identity(
+        &self,
+        identity_id: &str,
+        username: &str,
+        auth_method_encrypted: &str,
+        auth_nonce: &str,
+    ) -> Result<Vec<String>> {
+        let conn = self.get_conn()?;
+        let now = chrono::Utc::now().timestamp();
+
+        let session_ids = {
+            let mut stmt = conn.prepare_cached(
+                "SELECT id FROM ssh_sessions WHERE identity_id = ?1 AND is_deleted = 0"
+            )?;
+            let ids = stmt
+                .query_map([identity_id], |row| row.get::<_, String>(0))?
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+            ids
+        };
+
+        conn.execute(
+            "UPDATE ssh_sessions SET
+                username = ?1,
+                auth_method_encrypted = ?2,
+                auth_nonce = ?3,
+                updated_at = ?4,
+                client_ver = client_ver + 1,
+                is_dirty = 1
+            WHERE identity_id = ?5 AND is_deleted = 0",
+            (username, auth_method_encrypted, auth_nonce, now, identity_id),
+        )?;
+
+        Ok(session_ids)
+    }
+
+    /// 解除所有会话对指定身份的引用
+    pub fn clear_identity(&self, identity_id: &str) -> Result<usize> {
+        let conn = self.get_conn()?;
+        let count = conn.execute(
+            "UPDATE ssh_sessions SET identity_id = NULL WHERE identity_id = ?1",
+            [identity_id],
+        )?;
+        Ok(count)
+    }
+
+    // ==================== DNS 解析器 ====================
+
+    /// 设置会话的 DNS 解析器（JSON，None 表示使用全局设置）
+    pub fn set_dns_resolver(&self, session_id: &str, resolver_json: Option<&str>) -> Result<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "UPDATE ssh_sessions SET dns_resolver = ?1 WHERE id = ?2",
+            rusqlite::params![resolver_json, session_id],
+        )?;
+        Ok(())
+    }
+
+    /// 获取会话的 DNS 解析器（JSON）
+    pub fn find_dns_resolver(&self, session_id: &str) -> Result<Option<String>> {
+        let conn = self.get_conn()?;
+        let mut stmt = conn.prepare_cached("SELECT dns_resolver FROM ssh_sessions WHERE id = ?1")?;
+        let mut rows = stmt.query([session_id])?;
+
+        match rows.next()? {
+            Some(row) => Ok(row.get(0)?),
+            None => Ok(None),
+        }
+    }
+
+    // ==================== 远程命令 ====================
+
+    /// 设置会话连接后执行的远程命令（None 表示登录 shell）
+    pub fn set_remote_command(&self, session_id: &str, command: Option<&str>) -> Result<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "UPDATE ssh_sessions SET remote_command = ?1 WHERE id = ?2",
+            rusqlite::params![command, session_id],
+        )?;
+        Ok(())
+    }
+
+    /// 获取会话的远程命令
+    pub fn find_remote_command(&self, session_id: &str) -> Result<Option<String>> {
+        let conn = self.get_conn()?;
+        let mut stmt = conn.prepare_cached("SELECT remote_command FROM ssh_sessions WHERE id = ?1")?;
+        let mut rows = stmt.query([session_id])?;
+
+        match rows.next()? {
+            Some(row) => Ok(row.get(0)?),
+            None => Ok(None),
+        }
+    }
+
+    // ==================== 字符编码 ====================
+
+    /// 设置会话的字符编码（None 表示 UTF-8）
+    pub fn set_encoding(&self, session_id: &str, encoding: Option<&str>) -> Result<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "UPDATE ssh_sessions SET encoding = ?1 WHERE id = ?2",
+            rusqlite::params![encoding, session_id],
+        )?;
+        Ok(())
+    }
+
+    /// 获取会话的字符编码
+    pub fn find_encoding(&self, session_id: &str) -> Result<Option<String>> {
+        let conn = self.get_conn()?;
+        let mut stmt = conn.prepare_cached("SELECT encoding FROM ssh_sessions WHERE id = ?1")?;
+        let mut rows = stmt.query([session_id])?;
+
+        match rows.next()? {
+            Some(row) => Ok(row.get(0)?),
+            None => Ok(None),
+        }
+    }
+
+    // ==================== 远程端口转发 ====================
+
+    /// 设置会话保存的远程转发（JSON，None 表示没有）
+    pub fn set_remote_forwards(&self, session_id: &str, forwards_json: Option<&str>) -> Result<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "UPDATE ssh_sessions SET remote_forwards = ?1 WHERE id = ?2",
+            rusqlite::params![forwards_json, session_id],
+        )?;
+        Ok(())
+    }
+
+    /// 获取会话保存的远程转发（JSON）
+    pub fn find_remote_forwards(&self, session_id: &str) -> Result<Option<String>> {
+        let conn = self.get_conn()?;
+        let mut stmt = conn.prepare_cached("SELECT remote_forwards FROM ssh_sessions WHERE id = ?1")?;
+        let mut rows = stmt.query([session_id])?;
+
+        match rows.next()? {
+            Some(row) => Ok(row.get(0)?),
+            None => Ok(None),
+        }
+    }
+
+    // ==================== 跳板机 ====================
+
+    /// 设置会话使用的跳板机会话（None 表示直连）
+    pub fn set_jump_session_id(&self, session_id: &str, jump_session_id: Option<&str>) -> Result<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "UPDATE ssh_sessions SET jump_session_id = ?1 WHERE id = ?2",
+            rusqlite::params![jump_session_id, session_id],
+        )?;
+        Ok(())
+    }
+
+    /// 获取会话使用的跳板机会话 ID
+    pub fn find_jump_session_id(&self, session_id: &str) -> Result<Option<String>> {
+        let conn = self.get_conn()?;
+        let mut stmt = conn.prepare_cached("SELECT jump_session_id FROM ssh_sessions WHERE id = ?1")?;
+        let mut rows = stmt.query([session_id])?;
+
+        match rows.next()? {
+            Some(row) => Ok(row.get(0)?),
+            None => Ok(None),
+        }
+    }
+
+    // ==================== 登录脚本 ====================
+
+    /// 设置会话的登录脚本（加密后的 JSON 和 nonce，None 表示没有）
+    pub fn set_login_script(&self, session_id: &str, encrypted: Option<(&str, &str)>) -> Result<()> {
+        let conn = self.get_conn()?;
+        let (script, nonce) = encrypted.unzip();
+        conn.execute(
+            "UPDATE ssh_sessions SET login_script_encrypted = ?1, login_script_nonce = ?2 WHERE id = ?3",
+            rusqlite::params![script, nonce, session_id],
+        )?;
+        Ok(())
+    }
+
+    /// 获取会话的登录脚本（加密后的 JSON 和 nonce）
+    pub fn find_login_script(&self, session_id: &str) -> Result<Option<(String, String)>> {
+        let conn = self.get_conn()?;
+        let mut stmt = conn.prepare_cached(
+            "SELECT login_script_encrypted, login_script_nonce FROM ssh_sessions WHERE id = ?1",
+        )?;
+        let mut rows = stmt.query([session_id])?;
+
+        match rows.next()? {
+            Some(row) => {
+                let script: Option<String> = row.get(0)?;
+                let nonce: Option<String> = row.get(1)?;
+                Ok(script.zip(nonce))
+            }
+            None => Ok(None),
+        }
+    }
+
+    // ==================== 受保护会话 ====================
+
+    /// 设置会话是否受保护（输入危险命令时需要确认）
+    pub fn set_protected(&self, session_id: &str, protected: bool) -> Result<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "UPDATE ssh_sessions SET protected = ?1 WHERE id = ?2",
+            rusqlite::params![protected, session_id],
+        )?;
+        Ok(())
+    }
+
+    /// 获取会话是否受保护
+    pub fn find_protected(&self, session_id: &str) -> Result<bool> {
+        let conn = self.get_conn()?;
+        let mut stmt = conn.prepare_cached("SELECT protected FROM ssh_sessions WHERE id = ?1")?;
+        let mut rows = stmt.query([session_id])?;
+
+        match rows.next()? {
+            Some(row) => Ok(row.get::<_, Option<bool>>(0)?.unwrap_or(false)),
+            None => Ok(false),
+        }
+    }
+
+    // ==================== 高权限会话时限 ====================
+
+    /// 设置会话的高权限时限（JSON，None 表示不限时）
+    pub fn set_elevation(&self, session_id: &str, elevation_json: Option<&str>) -> Result<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "UPDATE ssh_sessions SET elevation = ?1 WHERE id = ?2",
+            rusqlite::params![elevation_json, session_id],
+        )?;
+        Ok(())
+    }
+
+    /// 获取会话的高权限时限（JSON）
+    pub fn find_elevation(&self, session_id: &str) -> Result<Option<String>> {
+        let conn = self.get_conn()?;
+        let mut stmt = conn.prepare_cached("SELECT elevation FROM ssh_sessions WHERE id = ?1")?;
+        let mut rows = stmt.query([session_id])?;
+
+        match rows.next()? {
+            Some(row) => Ok(row.get(0)?),
+            None => Ok(None),
+        }
+    }
+
+    // ==================== 算法偏好 ====================
+
+    /// 设置会话的算法偏好（JSON，None 表示使用默认算法）
+    pub fn set_algorithms(&self, session_id: &str, algorithms_json: Option<&str>) -> Result<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "UPDATE ssh_sessions SET algorithms = ?1 WHERE id = ?2",
+            rusqlite::params![algorithms_json, session_id],
+        )?;
+        Ok(())
+    }
+
+    /// 获取会话的算法偏好（JSON）
+    pub fn find_algorithms(&self, session_id: &str) -> Result<Option<String>> {
+        let conn = self.get_conn()?;
+        let mut stmt = conn.prepare_cached("SELECT algorithms FROM ssh_sessions WHERE id = ?1")?;
+        let mut rows = stmt.query([session_id])?;
+
+        match rows.next()? {
+            Some(row) => Ok(row.get(0)?),
+            None => Ok(None),
+        }
+    }
+
+    // ==================== 连接配置 ====================
+
+    /// 设置会话的连接 profile 列表（JSON，None 表示没有 profile）
+    pub fn set_profiles(&self, session_id: &str, profiles_json: Option<&str>) -> Result<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "UPDATE ssh_sessions SET profiles = ?1 WHERE id = ?2",
+            rusqlite::params![profiles_json, session_id],
+        )?;
+        Ok(())
+    }
+
+    /// 获取会话的连接 profile 列表（JSON）
+    pub fn find_profiles(&self, session_id: &str) -> Result<Option<String>> {
+        let conn = self.get_conn()?;
+        let mut stmt = conn.prepare_cached("SELECT profiles FROM ssh_sessions WHERE id = ?1")?;
+        let mut rows = stmt.query([session_id])?;
+
+        match rows.next()? {
+            Some(row) => Ok(row.get(0)?),
+            None => Ok(None),
+        }
+    }
+
     /// 将数据库行转换为 SshSession
     fn row_to_session(&self, row: &rusqlite::Row) -> Result<SshSession> {
         Ok(SshSession {
@@ -27,7 +27,7 @@ impl SyncStateRepository {
     pub fn get(&self, user_id: &str) -> Result<SyncStatus> {
         let conn = self.get_conn()?;
 
-        let mut stmt = conn.prepare(
+        let mut stmt = conn.prepare_cached(
             "SELECT user_id, last_sync_at, pending_count, conflict_count, last_error FROM sync_state WHERE user_id = ?1"
         )?;
 
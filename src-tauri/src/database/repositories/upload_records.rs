@@ -64,6 +64,12 @@ pub struct UploadRecord {
     pub error_message: Option<String>,
     pub created_at: i64,
     pub updated_at: i64,
+    /// 开启完整性校验时本地文件的 SHA-256
+    pub checksum_sha256: Option<String>,
+    /// 远程文件校验和是否一致，未校验时为空
+    pub checksum_verified: Option<bool>,
+    /// 传输类型：`file` 或 `directory`，旧记录为空
+    pub transfer_kind: Option<String>,
 }
 
 /// 分页结果
@@ -91,8 +97,8 @@ impl UploadRecordsRepository {
                 total_files, total_dirs, total_size, status,
                 bytes_transferred, files_completed, started_at,
                 completed_at, elapsed_ms, error_message,
-                created_at, updated_at
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)",
+                created_at, updated_at, transfer_kind
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18)",
             rusqlite::params![
                 &record.task_id,
                 &record.connection_id,
@@ -111,6 +117,7 @@ impl UploadRecordsRepository {
                 record.error_message.as_ref().map(|s| s.as_str()),
                 record.created_at,
                 record.updated_at,
+                record.transfer_kind.as_deref(),
             ],
         )?;
 
@@ -157,6 +164,17 @@ impl UploadRecordsRepository {
         Ok(())
     }
 
+    /// 记录完整性校验结果，校验和不一致时同时写入错误信息
+    pub fn update_checksum(conn: &Connection, task_id: &str, checksum_sha256: &str, verified: bool) -> Result<()> {
+        let now = chrono::Utc::now().timestamp();
+        let error_message = (!verified).then_some("校验和不一致");
+        conn.execute(
+            "UPDATE upload_records SET checksum_sha256 = ?1, checksum_verified = ?2, error_message = COALESCE(?3, error_message), updated_at = ?4 WHERE task_id = ?5",
+            rusqlite::params![checksum_sha256, verified, error_message, now, task_id],
+        )?;
+        Ok(())
+    }
+
     /// 分页查询
     pub fn list_paginated(conn: &Connection, user_id: &str, page: u32, page_size: u32) -> Result<PaginatedUploadRecords> {
         let offset = (page - 1) * page_size;
@@ -169,7 +187,7 @@ impl UploadRecordsRepository {
         )?;
 
         // 查询记录
-        let mut stmt = conn.prepare(
+        let mut stmt = conn.prepare_cached(
             "SELECT * FROM upload_records
              WHERE user_id = ?1
              ORDER BY created_at DESC
@@ -178,28 +196,8 @@ impl UploadRecordsRepository {
 
         let records: Result<Vec<UploadRecord>, _> = stmt.query_map(
             rusqlite::params![user_id, page_size as i64, offset as i64],
-        |row| {
-            Ok(UploadRecord {
-                id: row.get(0)?,
-                task_id: row.get(1)?,
-                connection_id: row.get(2)?,
-                user_id: row.get(3)?,
-                local_path: row.get(4)?,
-                remote_path: row.get(5)?,
-                total_files: row.get(6)?,
-                total_dirs: row.get(7)?,
-                total_size: row.get(8)?,
-                status: row.get(9)?,
-                bytes_transferred: row.get(10)?,
-                files_completed: row.get(11)?,
-                started_at: row.get(12)?,
-                completed_at: row.get(13)?,
-                elapsed_ms: row.get(14)?,
-                error_message: row.get(15)?,
-                created_at: row.get(16)?,
-                updated_at: row.get(17)?,
-            })
-        })?.collect();
+            Self::from_row,
+        )?.collect();
 
         Ok(PaginatedUploadRecords {
             records: records?,
@@ -209,6 +207,40 @@ impl UploadRecordsRepository {
         })
     }
 
+    /// 将查询结果行转换为记录（`SELECT *` 的列顺序）
+    fn from_row(row: &rusqlite::Row) -> rusqlite::Result<UploadRecord> {
+        Ok(UploadRecord {
+            id: row.get(0)?,
+            task_id: row.get(1)?,
+            connection_id: row.get(2)?,
+            user_id: row.get(3)?,
+            local_path: row.get(4)?,
+            remote_path: row.get(5)?,
+            total_files: row.get(6)?,
+            total_dirs: row.get(7)?,
+            total_size: row.get(8)?,
+            status: row.get(9)?,
+            bytes_transferred: row.get(10)?,
+            files_completed: row.get(11)?,
+            started_at: row.get(12)?,
+            completed_at: row.get(13)?,
+            elapsed_ms: row.get(14)?,
+            error_message: row.get(15)?,
+            created_at: row.get(16)?,
+            updated_at: row.get(17)?,
+            checksum_sha256: row.get(18)?,
+            checksum_verified: row.get(19)?,
+            transfer_kind: row.get(20)?,
+        })
+    }
+
+    /// 根据 task_id 查询记录
+    pub fn find_by_task_id(conn: &Connection, task_id: &str) -> Result<Option<UploadRecord>> {
+        let mut stmt = conn.prepare_cached("SELECT * FROM upload_records WHERE task_id = ?1")?;
+        let mut rows = stmt.query_map([task_id], Self::from_row)?;
+        Ok(rows.next().transpose()?)
+    }
+
     /// 删除记录
     pub fn delete(conn: &Connection, id: i64) -> Result<()> {
         conn.execute("DELETE FROM upload_records WHERE id = ?1", [id])?;
@@ -67,7 +67,7 @@ impl UserAuthRepository {
     pub fn find_current(&self) -> Result<Option<UserAuth>> {
         let conn = self.get_conn()?;
 
-        let mut stmt = conn.prepare(
+        let mut stmt = conn.prepare_cached(
             "SELECT
                 id, user_id, email, password_encrypted, password_nonce,
                 access_token_encrypted, refresh_token_encrypted, token_expires_at, device_id,
@@ -90,7 +90,7 @@ impl UserAuthRepository {
     pub fn find_current_credentials(&self) -> Result<Option<(String, String)>> {
         let conn = self.get_conn()?;
 
-        let mut stmt = conn.prepare(
+        let mut stmt = conn.prepare_cached(
             "SELECT password_encrypted, password_nonce
             FROM user_auth
             WHERE is_current = 1
@@ -110,7 +110,7 @@ impl UserAuthRepository {
     pub fn find_by_user_id(&self, user_id: &str) -> Result<Option<UserAuth>> {
         let conn = self.get_conn()?;
 
-        let mut stmt = conn.prepare(
+        let mut stmt = conn.prepare_cached(
             "SELECT
                 id, user_id, email, password_encrypted, password_nonce,
                 access_token_encrypted, refresh_token_encrypted, token_expires_at, device_id,
@@ -132,7 +132,7 @@ impl UserAuthRepository {
     pub fn find_by_email(&self, email: &str) -> Result<Option<UserAuth>> {
         let conn = self.get_conn()?;
 
-        let mut stmt = conn.prepare(
+        let mut stmt = conn.prepare_cached(
             "SELECT
                 id, user_id, email, password_encrypted, password_nonce,
                 access_token_encrypted, refresh_token_encrypted, token_expires_at, device_id,
@@ -154,7 +154,7 @@ impl UserAuthRepository {
     pub fn find_all(&self) -> Result<Vec<UserAuth>> {
         let conn = self.get_conn()?;
 
-        let mut stmt = conn.prepare(
+        let mut stmt = conn.prepare_cached(
             "SELECT
                 id, user_id, email, password_encrypted, password_nonce,
                 access_token_encrypted, refresh_token_encrypted, token_expires_at, device_id,
@@ -62,7 +62,7 @@ impl UserProfileRepository {
     pub fn find_by_user_id(&self, user_id: &str) -> Result<Option<UserProfile>> {
         let conn = self.get_conn()?;
 
-        let mut stmt = conn.prepare(
+        let mut stmt = conn.prepare_cached(
             "SELECT
                 id, user_id, username, phone, qq, wechat,
                 avatar_data, avatar_mime_type, bio, created_at, updated_at
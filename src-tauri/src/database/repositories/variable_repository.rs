@@ -0,0 +1,109 @@
+use anyhow::Result;
+use r2d2::PooledConnection;
+use r2d2_sqlite::{rusqlite, SqliteConnectionManager};
+use std::collections::HashMap;
+
+use crate::database::DbPool;
+use crate::models::variable::Variable;
+
+/// 全局变量在 scope 列中的值
+const GLOBAL_SCOPE: &str = "";
+
+/// 模板变量 Repository
+pub struct VariableRepository {
+    pool: DbPool,
+}
+
+impl VariableRepository {
+    /// 创建新的 Repository 实例
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// 获取数据库连接
+    fn get_conn(&self) -> Result<PooledConnection<SqliteConnectionManager>> {
+        self.pool
+            .get()
+            .map_err(|e| anyhow::anyhow!("Failed to get database connection: {}", e))
+    }
+
+    /// 设置变量（已存在则覆盖）
+    pub fn set(&self, user_id: &str, session_id: Option<&str>, name: &str, value: &str) -> Result<Variable> {
+        let conn = self.get_conn()?;
+        let now = chrono::Utc::now().timestamp();
+
+        conn.execute(
+            "INSERT INTO variables (user_id, scope, name, value, updated_at)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            ON CONFLICT(user_id, scope, name) DO UPDATE SET
+                value = excluded.value,
+                updated_at = excluded.updated_at",
+            rusqlite::params![user_id, session_id.unwrap_or(GLOBAL_SCOPE), name, value, now],
+        )?;
+
+        Ok(Variable {
+            user_id: user_id.to_string(),
+            session_id: session_id.map(str::to_string),
+            name: name.to_string(),
+            value: value.to_string(),
+            updated_at: now,
+        })
+    }
+
+    /// 删除变量
+    pub fn delete(&self, user_id: &str, session_id: Option<&str>, name: &str) -> Result<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "DELETE FROM variables WHERE user_id = ?1 AND scope = ?2 AND name = ?3",
+            rusqlite::params![user_id, session_id.unwrap_or(GLOBAL_SCOPE), name],
+        )?;
+        Ok(())
+    }
+
+    /// 获取用户的变量：`session_id` 为 None 时返回全部，否则返回全局变量和该会话的变量
+    pub fn find_by_user(&self, user_id: &str, session_id: Option<&str>) -> Result<Vec<Variable>> {
+        let conn = self.get_conn()?;
+
+        let mut stmt = conn.prepare_cached(
+            "SELECT user_id, scope, name, value, updated_at
+            FROM variables
+            WHERE user_id = ?1 AND (?2 IS NULL OR scope = '' OR scope = ?2)
+            ORDER BY scope ASC, name ASC"
+        )?;
+
+        let variables = stmt
+            .query_map(rusqlite::params![user_id, session_id], |row| {
+                let scope: String = row.get(1)?;
+                Ok(Variable {
+                    user_id: row.get(0)?,
+                    session_id: Some(scope).filter(|s| s != GLOBAL_SCOPE),
+                    name: row.get(2)?,
+                    value: row.get(3)?,
+                    updated_at: row.get(4)?,
+                })
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(variables)
+    }
+
+    /// 会话可用的变量表（会话变量覆盖同名全局变量）
+    pub fn resolve_for_session(&self, user_id: &str, session_id: &str) -> Result<HashMap<String, String>> {
+        let mut resolved = HashMap::new();
+        // 全局变量排在前面，会话变量后插入覆盖
+        for variable in self.find_by_user(user_id, Some(session_id))? {
+            resolved.insert(variable.name, variable.value);
+        }
+        Ok(resolved)
+    }
+
+    /// 删除会话的所有变量
+    pub fn delete_for_session(&self, user_id: &str, session_id: &str) -> Result<()> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "DELETE FROM variables WHERE user_id = ?1 AND scope = ?2",
+            [user_id, session_id],
+        )?;
+        Ok(())
+    }
+}
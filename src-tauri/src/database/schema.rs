@@ -87,6 +87,32 @@ pub fn init_schema(conn: &Connection) -> Result<()> {
         CREATE INDEX IF NOT EXISTS idx_ssh_sessions_group ON ssh_sessions(group_name);
         CREATE INDEX IF NOT EXISTS idx_ssh_sessions_is_deleted ON ssh_sessions(is_deleted);
 
+        -- ==========================================
+        -- 会话分组表（颜色、图标等元数据，会话通过 group_name 关联）
+        -- ==========================================
+        CREATE TABLE IF NOT EXISTS session_groups (
+            id TEXT PRIMARY KEY,
+            user_id TEXT NOT NULL,
+            name TEXT NOT NULL,
+            color TEXT,
+            icon TEXT,
+            sort_order INTEGER NOT NULL DEFAULT 0,
+            description TEXT,
+
+            -- 同步字段
+            server_ver INTEGER NOT NULL DEFAULT 0,
+            client_ver INTEGER NOT NULL DEFAULT 1,
+            is_dirty BOOLEAN NOT NULL DEFAULT 1,
+            is_deleted BOOLEAN NOT NULL DEFAULT 0,
+            last_synced_at INTEGER,
+
+            created_at INTEGER NOT NULL,
+            updated_at INTEGER NOT NULL,
+            UNIQUE(user_id, name)
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_session_groups_user_id ON session_groups(user_id);
+
         -- ==========================================
         -- 同步状态表（支持多用户）
         -- ==========================================
@@ -200,10 +226,191 @@ pub fn init_schema(conn: &Connection) -> Result<()> {
         CREATE INDEX IF NOT EXISTS idx_download_records_user_id ON download_records(user_id);
         CREATE INDEX IF NOT EXISTS idx_download_records_status ON download_records(status);
         CREATE INDEX IF NOT EXISTS idx_download_records_created_at ON download_records(created_at DESC);
+
+        -- ==========================================
+        -- 会话笔记表（Markdown，每个会话一条）
+        -- ==========================================
+        CREATE TABLE IF NOT EXISTS session_notes (
+            session_id TEXT PRIMARY KEY,
+            user_id TEXT NOT NULL,
+            content TEXT NOT NULL DEFAULT '',
+            created_at INTEGER NOT NULL,
+            updated_at INTEGER NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_session_notes_user_id ON session_notes(user_id);
+
+        -- ==========================================
+        -- 会话附件表（文件内容保存在本地存储目录）
+        -- ==========================================
+        CREATE TABLE IF NOT EXISTS session_attachments (
+            id TEXT PRIMARY KEY,
+            session_id TEXT NOT NULL,
+            user_id TEXT NOT NULL,
+            file_name TEXT NOT NULL,
+            mime_type TEXT,
+            size INTEGER NOT NULL DEFAULT 0,
+            stored_path TEXT NOT NULL,
+            created_at INTEGER NOT NULL,
+            updated_at INTEGER NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_session_attachments_session_id ON session_attachments(session_id);
+
+        -- ==========================================
+        -- 共享身份表（多个会话复用同一套凭据）
+        -- ==========================================
+        CREATE TABLE IF NOT EXISTS identities (
+            id TEXT PRIMARY KEY,
+            user_id TEXT NOT NULL,
+            name TEXT NOT NULL,
+            username TEXT NOT NULL,
+            auth_method_encrypted TEXT NOT NULL,
+            auth_nonce TEXT NOT NULL,
+            created_at INTEGER NOT NULL,
+            updated_at INTEGER NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_identities_user_id ON identities(user_id);
+
+        -- ==========================================
+        -- 命令片段表
+        -- ==========================================
+        CREATE TABLE IF NOT EXISTS snippets (
+            id TEXT PRIMARY KEY,
+            user_id TEXT NOT NULL,
+            name TEXT NOT NULL,
+            command TEXT NOT NULL,
+            description TEXT,
+            created_at INTEGER NOT NULL,
+            updated_at INTEGER NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_snippets_user_id ON snippets(user_id);
+
+        -- ==========================================
+        -- 主机命令历史表（用于自动补全）
+        -- ==========================================
+        CREATE TABLE IF NOT EXISTS command_history (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            user_id TEXT NOT NULL,
+            host_key TEXT NOT NULL,
+            command TEXT NOT NULL,
+            use_count INTEGER NOT NULL DEFAULT 1,
+            last_used_at INTEGER NOT NULL,
+            UNIQUE(user_id, host_key, command)
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_command_history_host ON command_history(user_id, host_key, last_used_at DESC);
+
+        -- ==========================================
+        -- 剪贴板历史表（内容加密存储，需用户开启）
+        -- ==========================================
+        CREATE TABLE IF NOT EXISTS clipboard_history (
+            id TEXT PRIMARY KEY,
+            user_id TEXT NOT NULL,
+            connection_id TEXT,
+            session_name TEXT,
+            host_key TEXT,
+            content_encrypted TEXT NOT NULL,
+            content_length INTEGER NOT NULL DEFAULT 0,
+            created_at INTEGER NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_clipboard_history_user ON clipboard_history(user_id, created_at DESC);
+
+        -- ==========================================
+        -- 模板变量表（scope 为空表示全局变量，否则为会话 ID）
+        -- ==========================================
+        CREATE TABLE IF NOT EXISTS variables (
+            user_id TEXT NOT NULL,
+            scope TEXT NOT NULL DEFAULT '',
+            name TEXT NOT NULL,
+            value TEXT NOT NULL,
+            updated_at INTEGER NOT NULL,
+            PRIMARY KEY(user_id, scope, name)
+        );
+
+        -- ==========================================
+        -- 连接流量使用历史表（断开连接时写入）
+        -- ==========================================
+        CREATE TABLE IF NOT EXISTS connection_stats (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            user_id TEXT NOT NULL,
+            session_id TEXT NOT NULL,
+            connection_id TEXT NOT NULL,
+            session_name TEXT NOT NULL,
+            host_key TEXT NOT NULL,
+            connected_at INTEGER NOT NULL,
+            disconnected_at INTEGER NOT NULL,
+            bytes_sent INTEGER NOT NULL DEFAULT 0,
+            bytes_received INTEGER NOT NULL DEFAULT 0
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_connection_stats_user ON connection_stats(user_id, disconnected_at DESC);
+        CREATE INDEX IF NOT EXISTS idx_connection_stats_session ON connection_stats(session_id);
         ",
     )?;
 
+    // 旧版本数据库的增量字段
+    add_column_if_missing(conn, "app_settings", "api_timeouts", "TEXT")?;
+    add_column_if_missing(conn, "ssh_sessions", "identity_id", "TEXT")?;
+    add_column_if_missing(conn, "app_settings", "clipboard_history_enabled", "BOOLEAN DEFAULT 0")?;
+    add_column_if_missing(conn, "ssh_sessions", "dns_resolver", "TEXT")?;
+    add_column_if_missing(conn, "app_settings", "dns_resolver", "TEXT")?;
+    add_column_if_missing(conn, "app_settings", "connection_limits", "TEXT")?;
+    add_column_if_missing(conn, "app_settings", "watermark", "TEXT")?;
+    add_column_if_missing(conn, "ssh_sessions", "remote_command", "TEXT")?;
+    add_column_if_missing(conn, "ssh_sessions", "encoding", "TEXT")?;
+    add_column_if_missing(conn, "ssh_sessions", "remote_forwards", "TEXT")?;
+    add_column_if_missing(conn, "ssh_sessions", "login_script_encrypted", "TEXT")?;
+    add_column_if_missing(conn, "ssh_sessions", "login_script_nonce", "TEXT")?;
+    add_column_if_missing(conn, "ssh_sessions", "jump_session_id", "TEXT")?;
+    add_column_if_missing(conn, "ssh_sessions", "protected", "BOOLEAN DEFAULT 0")?;
+    add_column_if_missing(conn, "ssh_sessions", "elevation", "TEXT")?;
+    add_column_if_missing(conn, "ssh_sessions", "algorithms", "TEXT")?;
+    add_column_if_missing(conn, "ssh_sessions", "profiles", "TEXT")?;
+    add_column_if_missing(conn, "upload_records", "checksum_sha256", "TEXT")?;
+    add_column_if_missing(conn, "upload_records", "checksum_verified", "BOOLEAN")?;
+    add_column_if_missing(conn, "upload_records", "transfer_kind", "TEXT")?;
+    add_column_if_missing(conn, "download_records", "transfer_kind", "TEXT")?;
+    add_column_if_missing(conn, "ssh_sessions", "restored", "BOOLEAN DEFAULT 0")?;
+    add_column_if_missing(conn, "session_notes", "sync_enabled", "BOOLEAN NOT NULL DEFAULT 0")?;
+    add_column_if_missing(conn, "session_notes", "server_ver", "INTEGER NOT NULL DEFAULT 0")?;
+    add_column_if_missing(conn, "session_notes", "client_ver", "INTEGER NOT NULL DEFAULT 1")?;
+    add_column_if_missing(conn, "session_notes", "is_dirty", "BOOLEAN NOT NULL DEFAULT 0")?;
+    add_column_if_missing(conn, "session_notes", "is_deleted", "BOOLEAN NOT NULL DEFAULT 0")?;
+    add_column_if_missing(conn, "session_notes", "last_synced_at", "INTEGER")?;
+    add_column_if_missing(conn, "session_attachments", "sync_enabled", "BOOLEAN NOT NULL DEFAULT 0")?;
+    add_column_if_missing(conn, "session_attachments", "server_ver", "INTEGER NOT NULL DEFAULT 0")?;
+    add_column_if_missing(conn, "session_attachments", "client_ver", "INTEGER NOT NULL DEFAULT 1")?;
+    add_column_if_missing(conn, "session_attachments", "is_dirty", "BOOLEAN NOT NULL DEFAULT 0")?;
+    add_column_if_missing(conn, "session_attachments", "is_deleted", "BOOLEAN NOT NULL DEFAULT 0")?;
+    add_column_if_missing(conn, "session_attachments", "last_synced_at", "INTEGER")?;
+    conn.execute_batch(
+        "CREATE INDEX IF NOT EXISTS idx_ssh_sessions_identity_id ON ssh_sessions(identity_id);",
+    )?;
+
     tracing::info!("Database schema initialized successfully");
 
     Ok(())
-}
\ No newline at end of file
+}
+
+/// 为已有表添加列（列已存在时跳过）
+///
+/// SQLite 不支持 `ADD COLUMN IF NOT EXISTS`，通过 `PRAGMA table_info` 检查
+fn add_column_if_missing(conn: &Connection, table: &str, column: &str, definition: &str) -> Result<()> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table))?;
+    let exists = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .collect::<std::result::Result<Vec<_>, _>>()?
+        .iter()
+        .any(|name| name == column);
+
+    if !exists {
+        tracing::info!("Adding column {}.{}", table, column);
+        conn.execute_batch(&format!("ALTER TABLE {} ADD COLUMN {} {};", table, column, definition))?;
+    }
+
+    Ok(())
+}
@@ -20,6 +20,9 @@ pub enum SSHError {
     #[error("不支持的操作: {0}")]
     NotSupported(String),
 
+    #[error("连接数已达上限: {0}")]
+    LimitExceeded(String),
+
     #[error("IO错误: {0}")]
     IoError(#[from] std::io::Error),
 
@@ -4,23 +4,28 @@ mod ssh;
 mod config;
 mod sftp;
 mod audio;
+mod autocomplete;
 mod ai;
 mod database;
 mod models;
 mod services;
 mod utils;
 mod types;
+mod plugins;
+#[cfg(feature = "headless")]
+pub mod cli;
 
 use commands::session::SSHManagerState;
 use commands::sftp::SftpManagerState;
 use commands::auth::ApiClientStateWrapper;
 use ssh::manager::SSHManager;
+use ssh::forwarding::ForwardManager;
 use sftp::manager::SftpManager;
 use std::sync::Arc;
 use tauri::Manager;
 
 use crate::database::repositories::{UserAuthRepository, AppSettingsRepository};
-use crate::services::{ApiClient, CryptoService};
+use crate::services::{ApiClient, ApiTimeouts, CryptoService};
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -44,13 +49,31 @@ pub fn run() {
         .with_env_filter(filter)
         .with_target(false) // 不显示模块路径
         .with_timer(timer) // 使用北京时间
+        .with_writer(services::crash_service::BreadcrumbWriter) // 保留最近日志，写入崩溃报告
         .init();
 
-    tauri::Builder::default()
+    // 安装 panic hook，崩溃时在存储目录生成崩溃报告
+    services::crash_service::CrashService::install_panic_hook();
+
+    let builder = tauri::Builder::default();
+
+    // 桌面平台保持单实例：再次启动（如点击 ssh:// 链接）时把链接转交给已运行的实例
+    #[cfg(desktop)]
+    let builder = builder.plugin(tauri_plugin_single_instance::init(|app, _argv, _cwd| {
+        if let Some(window) = app.get_webview_window("main") {
+            let _ = window.unminimize();
+            let _ = window.set_focus();
+        }
+    }));
+
+    builder
+        .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
         .setup(|app| {
+            app.manage(commands::startup::StartupState::new());
+
             // 初始化数据库连接池
             let db_pool = database::init_db_pool()
                 .map_err(|e| {
@@ -61,105 +84,88 @@ pub fn run() {
             // 克隆 db_pool 供后续使用
             let db_pool_for_init = db_pool.clone();
 
+            // 启动定时备份任务
+            services::backup_service::BackupService::start_scheduler(db_pool.clone());
+
             app.manage(db_pool);
+            commands::startup::report_startup_phase(app.handle(), commands::startup::StartupPhase::Database);
 
-            // 初始化 API Client 状态（全局单例）
+            // 初始化 API Client 状态（全局单例），登录用户的 client 在后台恢复
             let api_client_state = Arc::new(commands::auth::ApiClientState::new());
             app.manage(api_client_state.clone() as ApiClientStateWrapper);
 
-            // 在应用启动时初始化 API Client
-            // 1. 获取服务器地址（如果为空，使用默认值）
-            let app_settings_repo = AppSettingsRepository::new(db_pool_for_init.clone());
-            let server_url = match app_settings_repo.get_server_url() {
-                Ok(url) => url,
-                Err(_) => {
-                    tracing::warn!("Server URL not configured, using default: http://localhost:3000");
-                    "http://localhost:3000".to_string()
-                }
-            };
-
-            // 2. 检查是否有当前用户登录
-            let user_auth_repo = UserAuthRepository::new(db_pool_for_init.clone());
-            if let Some(current_user) = user_auth_repo.find_current().ok().flatten() {
-                tracing::info!("Found current user, initializing API client");
-                tracing::info!("  - user_id: {}", current_user.user_id);
-                tracing::info!("  - has_refresh_token: {}", current_user.refresh_token_encrypted.is_some());
-                tracing::info!("  - device_id: {}", current_user.device_id);
-
-                // 获取语言设置
-                let language = app_settings_repo.get_language().ok();
-
-                // 3. 创建并初始化 ApiClient
-                match ApiClient::new(server_url.clone(), language) {
-                    Ok(client) => {
-                        // 4. 解密并设置 access_token
-                        match CryptoService::decrypt_token(
-                            &current_user.access_token_encrypted,
-                            &current_user.device_id
-                        ) {
-                            Ok(token) => {
-                                client.set_token(token);
-
-                                // 5. 设置 refresh_token_encrypted（用于后续 token 刷新）
-                                if let Some(refresh_token) = &current_user.refresh_token_encrypted {
-                                    client.set_refresh_token(refresh_token.clone());
-                                    tracing::info!("Refresh token set from database");
-                                } else {
-                                    tracing::warn!("No refresh token found in database, auto-refresh will not work");
-                                }
-
-                                // 6. 设置 device_id（用于 token 加密解密）
-                                client.set_device_id(current_user.device_id.clone());
-
-                                // 7. 初始化 token 刷新回调
-                                client.init_token_refresh_callback(
-                                    db_pool_for_init.clone(),
-                                    current_user.user_id.clone(),
-                                    current_user.device_id.clone(),
-                                    {
-                                        let api_client_state_clone = api_client_state.clone();
-                                        move || api_client_state_clone.get_client()
-                                    }
-                                );
-
-                                api_client_state.set_client(client);
-                                tracing::info!("API client initialized successfully");
-                            }
-                            Err(e) => {
-                                tracing::error!("Failed to decrypt token: {}, clearing current user state", e);
-                                // 解密失败时清除当前用户登录状态，避免用户看到不一致的状态
-                                let _ = user_auth_repo.clear_current();
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        tracing::error!("Failed to create API client: {}, clearing current user state", e);
-                        // 创建 client 失败时也清除当前用户登录状态
-                        let _ = user_auth_repo.clear_current();
-                    }
-                }
-            } else {
-                tracing::info!("No current user found, skipping API client initialization");
-            }
+            // 初始化会话接力服务（登录后由前端启动设备连接）
+            app.manage(Arc::new(services::handoff_service::HandoffService::new()) as services::handoff_service::HandoffServiceState);
 
             // 初始化SSH管理器，传入AppHandle
             let ssh_manager = Arc::new(SSHManager::new(app.handle().clone()));
             app.manage(ssh_manager.clone() as SSHManagerState);
 
+            // 初始化端口转发管理器
+            let forward_manager = Arc::new(ForwardManager::new(ssh_manager.clone(), app.handle().clone()));
+            app.manage(forward_manager as commands::forward::ForwardManagerState);
+
             // 初始化SFTP管理器
             let sftp_manager = Arc::new(SftpManager::new(ssh_manager));
+            sftp_manager.transfer_queue().forward_events(app.handle().clone());
+            sftp_manager.clone().spawn_watchdog(app.handle().clone());
+            sftp_manager.clone().spawn_edit_watcher(app.handle().clone());
             app.manage(sftp_manager as SftpManagerState);
 
-            // 初始化音频捕获器状态
+            // 初始化音频捕获器状态（捕获器在开始录音时才创建）
             let audio_capturer = commands::audio::AudioCapturerState {
                 capturer: Arc::new(std::sync::Mutex::new(None)),
             };
             app.manage(audio_capturer);
 
-            // 初始化 AI Manager 状态
-            let ai_manager = commands::ai::AIManagerState::new();
+            // 初始化 AI Manager 状态（Provider 管理器在首次使用时创建）
+            let ai_manager = commands::ai::AIManagerState::new(api_client_state.clone());
             app.manage(ai_manager);
 
+            // 初始化自动补全状态（PATH 可执行文件按主机缓存）
+            app.manage(Arc::new(autocomplete::AutocompleteProvider::new()) as commands::autocomplete::AutocompleteState);
+
+            // 初始化插件管理器，在后台扫描并启动插件
+            let plugin_manager = Arc::new(plugins::PluginManager::new(app.handle().clone()));
+            app.manage(plugin_manager.clone() as commands::plugins::PluginManagerState);
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = plugin_manager.reload().await {
+                    tracing::warn!("Failed to load plugins: {}", e);
+                }
+            });
+
+            commands::startup::report_startup_phase(app.handle(), commands::startup::StartupPhase::Managers);
+
+            // 在后台恢复 API Client，避免 token 解密阻塞窗口显示
+            {
+                let app_handle = app.handle().clone();
+                tauri::async_runtime::spawn_blocking(move || {
+                    restore_api_client(&db_pool_for_init, &api_client_state);
+                    api_client_state.mark_restored();
+                    commands::startup::report_startup_phase(&app_handle, commands::startup::StartupPhase::ApiClient);
+                    commands::startup::report_startup_phase(&app_handle, commands::startup::StartupPhase::Ready);
+                });
+            }
+
+            // 注册 ssh:// 和 sftp:// 链接（安装包在安装时注册，Linux 和 Windows 开发模式下需要运行时注册）
+            app.manage(commands::deeplink::DeepLinkState::default());
+            {
+                use tauri_plugin_deep_link::DeepLinkExt;
+
+                #[cfg(any(target_os = "linux", all(debug_assertions, windows)))]
+                if let Err(e) = app.deep_link().register_all() {
+                    tracing::warn!("Failed to register deep link schemes: {}", e);
+                }
+
+                if let Ok(Some(urls)) = app.deep_link().get_current() {
+                    commands::deeplink::defer_urls(app.handle(), urls.iter().map(|url| url.to_string()).collect());
+                }
+                let app_handle = app.handle().clone();
+                app.deep_link().on_open_url(move |event| {
+                    commands::deeplink::handle_urls(&app_handle, event.urls().iter().map(|url| url.to_string()).collect());
+                });
+            }
+
             // 开发模式下自动打开开发者工具
             #[cfg(debug_assertions)]
             if let Some(window) = app.get_webview_window("main") {
@@ -172,10 +178,45 @@ pub fn run() {
             // Session 会话管理命令
             commands::session_create,
             commands::session_create_temp,
+            commands::session_quick_connect_parse,
+            commands::session_quick_connect,
+            commands::deeplink_open,
+            commands::deeplink_take_pending,
+            commands::session_open_local,
+            commands::serial_list_ports,
+            commands::session_open_serial,
+            commands::session_open_telnet,
+            commands::wsl_list_distributions,
+            commands::session_open_wsl,
+            commands::session_open_docker,
+            commands::docker_list_containers,
+            commands::k8s_list_namespaces,
+            commands::k8s_list_pods,
+            commands::k8s_exec_pod,
+            commands::session_import_ssh_config,
+            commands::ssh_host_keys_list,
+            commands::ssh_host_key_remove,
+            commands::ssh_host_key_export,
+            commands::ssh_advisories_update,
+            commands::ssh_advisory_check,
+            commands::env_snapshot,
+            commands::env_snapshot_list,
+            commands::env_snapshot_diff,
+            commands::ssh_keygen,
+            commands::ssh_copy_id,
+            commands::password_generate,
+            commands::password_rotate,
+            commands::dns_resolve_test,
+            commands::discover_ssh_hosts,
             commands::session_create_with_id,
             commands::session_connect,
             commands::session_disconnect,
+            commands::session_elevation_extend,
+            commands::session_health,
+            commands::session_preflight,
+            commands::connection_stats,
             commands::session_list,
+            commands::session_list_since,
             commands::session_get,
             commands::session_delete,
             commands::session_update,
@@ -186,12 +227,89 @@ pub fn run() {
             commands::db_ssh_session_list,
             commands::db_ssh_session_get_by_id,
             commands::db_ssh_session_migrate_to_user,
+            commands::db_ssh_session_set_identity,
+            commands::db_ssh_session_get_identity,
+            commands::db_ssh_session_set_dns_resolver,
+            commands::db_ssh_session_get_dns_resolver,
+            commands::db_ssh_session_set_remote_command,
+            commands::db_ssh_session_get_remote_command,
+            commands::db_ssh_session_set_encoding,
+            commands::db_ssh_session_get_encoding,
+            commands::db_ssh_session_set_jump_session,
+            commands::db_ssh_session_get_jump_session,
+            commands::db_ssh_session_set_login_script,
+            commands::db_ssh_session_get_login_script,
+            commands::db_ssh_session_set_protected,
+            commands::db_ssh_session_get_protected,
+            commands::db_ssh_session_set_elevation,
+            commands::db_ssh_session_get_elevation,
+            commands::db_ssh_session_set_algorithms,
+            commands::db_ssh_session_get_algorithms,
+            commands::db_ssh_session_set_profiles,
+            commands::db_ssh_session_get_profiles,
+            commands::ssh_algorithm_catalog,
+            // 共享身份命令
+            commands::identity_create,
+            commands::identity_list,
+            commands::identity_update,
+            commands::identity_delete,
+            // 会话批量编辑
+            commands::session_bulk_edit,
+            // 会话分组命令
+            commands::session_group_list,
+            commands::session_group_save,
+            commands::session_group_delete,
+            commands::session_group_reorder,
+            // 会话回收站命令
+            commands::trash_list,
+            commands::session_restore,
+            // 连接流量使用历史
+            commands::connection_stats_history,
+            commands::connection_stats_totals,
+            commands::stats_heatmap,
+            // 会话笔记与附件命令
+            commands::session_note_get,
+            commands::session_note_save,
+            commands::session_note_delete,
+            commands::session_note_set_sync,
+            commands::session_attachment_add,
+            commands::session_attachment_list,
+            commands::session_attachment_read,
+            commands::session_attachment_export,
+            commands::session_attachment_delete,
+            commands::session_attachment_set_sync,
             // Terminal 终端命令
             commands::terminal_write,
+            commands::terminal_confirm_input,
             commands::terminal_resize,
+            commands::terminal_attach_output,
+            commands::terminal_benchmark,
+            commands::terminal_transcript_get,
+            commands::terminal_transcript_subscribe,
+            commands::terminal_transcript_clear,
+            // 远程命令执行
+            commands::ssh_exec,
+            commands::batch_exec,
+            // 自动补全与命令片段命令
+            commands::autocomplete_suggest,
+            commands::autocomplete_record_command,
+            commands::autocomplete_refresh_binaries,
+            commands::autocomplete_clear_history,
+            commands::snippet_create,
+            commands::snippet_list,
+            commands::snippet_update,
+            commands::snippet_delete,
+            // 剪贴板历史命令
+            commands::clipboard_history_get_enabled,
+            commands::clipboard_history_set_enabled,
+            commands::clipboard_history_add,
+            commands::clipboard_history_list,
+            commands::clipboard_history_delete,
+            commands::clipboard_history_clear,
             // Storage 存储命令
             commands::storage_sessions_save,
             commands::storage_sessions_load,
+            commands::storage_sessions_import,
             commands::storage_sessions_clear,
             commands::storage_session_delete,
             commands::storage_session_create,
@@ -209,8 +327,21 @@ pub fn run() {
             commands::sftp_remove_file,
             commands::sftp_remove_dir,
             commands::sftp_rename,
+            commands::sftp_create_symlink,
+            commands::sftp_read_link,
             commands::sftp_chmod,
+            commands::sftp_permission_audit,
+            commands::sftp_route,
+            commands::sftp_search,
+            commands::sftp_cancel_search,
+            commands::sftp_edit_open,
+            commands::sftp_edit_close,
+            commands::sftp_edit_list,
+            commands::sftp_remote_users,
+            commands::sftp_remote_groups,
             commands::sftp_read_file,
+            commands::sftp_read_file_stream,
+            commands::sftp_cancel_read_stream,
             commands::sftp_write_file,
             commands::sftp_download_file,
             commands::sftp_download_directory,
@@ -218,6 +349,17 @@ pub fn run() {
             commands::sftp_upload_file,
             commands::sftp_upload_directory,
             commands::sftp_cancel_upload,
+            commands::sftp_resume_task,
+            commands::sftp_queue_state,
+            commands::sftp_queue_pause,
+            commands::sftp_queue_resume,
+            commands::sftp_queue_reorder,
+            commands::sftp_queue_set_concurrency,
+            commands::sftp_watchdog_config,
+            commands::sftp_watchdog_configure,
+            commands::sftp_transfer,
+            commands::sftp_transfer_remote,
+            commands::sftp_cancel_transfer,
             commands::local_list_dir,
             commands::local_home_dir,
             commands::local_available_drives,
@@ -230,6 +372,27 @@ pub fn run() {
             commands::recording_update_metadata,
             commands::recording_save_video,
             commands::recording_load_video,
+            commands::recording_set_chapters,
+            commands::recording_chapters_from_events,
+            commands::recording_trim,
+            commands::recording_transcribe,
+            commands::recording_search,
+            commands::recording_upload,
+            commands::recording_cloud_list,
+            commands::recording_cloud_download,
+            commands::recording_cloud_set_visibility,
+            commands::recording_cloud_delete,
+            commands::workspace_create,
+            commands::workspace_list,
+            commands::workspace_member_list,
+            commands::workspace_member_add,
+            commands::workspace_member_remove,
+            commands::workspace_session_list,
+            commands::workspace_session_share,
+            commands::workspace_session_delete,
+            commands::workspace_session_credentials,
+            commands::workspace_session_connect,
+            commands::workspace_audit_log_list,
             // Audio 音频命令
             commands::audio_start_capturing,
             commands::audio_stop_capturing,
@@ -240,7 +403,11 @@ pub fn run() {
             commands::ai_chat_stream,
             commands::ai_explain_command,
             commands::ai_generate_command,
+            commands::ai_generate_command_structured,
+            commands::ai_server_quota,
             commands::ai_analyze_error,
+            commands::ai_file_assistant_plan,
+            commands::ai_file_assistant_execute,
             commands::ai_test_connection,
             commands::ai_clear_cache,
             commands::ai_get_cache_info,
@@ -256,10 +423,14 @@ pub fn run() {
             commands::ai_history::ai_history_list_by_server,
             commands::ai_history::ai_history_list_by_server_id,
             commands::ai_history::ai_history_update_connection_status,
+            commands::ai_history::ai_history_fork,
+            commands::ai_history::ai_history_list_branches,
             // AI 配置存储命令
             commands::storage_ai_config_save,
             commands::storage_ai_config_load,
             commands::storage_ai_config_get_default,
+            commands::ai_config_export,
+            commands::ai_config_import,
             // 记录管理命令
             commands::list_upload_records,
             commands::delete_upload_record,
@@ -283,10 +454,17 @@ pub fn run() {
             commands::auth_has_current_user,
             commands::auth_delete_account,
             commands::auth_send_verify_code,
+            commands::auth_verify_email,
+            commands::auth_resend_verify_code,
             // 同步命令
             commands::sync_now,
             commands::sync_get_status,
             commands::sync_resolve_conflict,
+            // 设备间会话接力
+            commands::handoff_start,
+            commands::handoff_stop,
+            commands::handoff_list_devices,
+            commands::handoff_open_on_device,
             // 用户资料命令
             commands::user_profile_get,
             commands::user_profile_update,
@@ -298,10 +476,151 @@ pub fn run() {
             commands::app_settings_set_auto_sync_enabled,
             commands::app_settings_get_sync_interval,
             commands::app_settings_set_sync_interval,
+            commands::app_settings_get_api_timeouts,
+            commands::app_settings_set_api_timeouts,
             commands::app_settings_get_language,
             commands::app_settings_set_language,
+            commands::app_settings_get_dns_resolver,
+            commands::app_settings_set_dns_resolver,
+            commands::app_settings_get_connection_limits,
+            commands::app_settings_set_connection_limits,
+            commands::app_settings_get_watermark,
+            commands::app_settings_set_watermark,
             commands::app_settings_get_all,
+            // 本地备份命令
+            commands::backup_get_config,
+            commands::backup_set_config,
+            commands::backup_now,
+            commands::backup_list,
+            commands::backup_restore,
+            // 连接事件钩子命令
+            commands::hook_list,
+            commands::hook_save,
+            commands::hook_delete,
+            commands::hook_test,
+            // Webhook 通知命令
+            commands::webhook_list,
+            commands::webhook_save,
+            commands::webhook_delete,
+            commands::webhook_test,
+            // 插件命令
+            commands::plugin_list,
+            commands::plugin_reload,
+            commands::plugin_dir,
+            commands::plugin_set_enabled,
+            commands::plugin_invoke,
+            commands::plugin_menu_items,
+            // 崩溃报告命令
+            commands::crash_report_list,
+            commands::crash_report_get,
+            commands::crash_report_delete,
+            commands::crash_report_upload,
+            // 模板变量命令
+            commands::variable_list,
+            commands::variable_set,
+            commands::variable_delete,
+            commands::variable_render,
+            commands::run_and_capture,
+            // 端口转发命令
+            commands::forward_local_create,
+            commands::forward_dynamic_create,
+            commands::forward_remote_create,
+            commands::forward_remote_delete,
+            commands::forward_list,
+            commands::forward_delete,
+            // 启动状态
+            commands::app_startup_status,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
+
+/// 恢复已登录用户的 API Client
+///
+/// 包含设置读取和 token 解密（密钥派生较慢），在后台线程中执行，不阻塞窗口显示
+fn restore_api_client(db_pool: &database::DbPool, api_client_state: &Arc<commands::auth::ApiClientState>) {
+    // 1. 获取服务器地址（如果为空，使用默认值）
+    let app_settings_repo = AppSettingsRepository::new(db_pool.clone());
+
+    // 加载 API 请求超时设置，之后创建的 ApiClient 都使用该值
+    if let Some(timeouts) = app_settings_repo
+        .get_api_timeouts()
+        .ok()
+        .flatten()
+        .and_then(|json| serde_json::from_str::<ApiTimeouts>(&json).ok())
+    {
+        ApiTimeouts::set_configured(timeouts);
+    }
+
+    let server_url = match app_settings_repo.get_server_url() {
+        Ok(url) => url,
+        Err(_) => {
+            tracing::warn!("Server URL not configured, using default: http://localhost:3000");
+            "http://localhost:3000".to_string()
+        }
+    };
+
+    // 2. 检查是否有当前用户登录
+    let user_auth_repo = UserAuthRepository::new(db_pool.clone());
+    if let Some(current_user) = user_auth_repo.find_current().ok().flatten() {
+        tracing::info!("Found current user, initializing API client");
+        tracing::info!("  - user_id: {}", current_user.user_id);
+        tracing::info!("  - has_refresh_token: {}", current_user.refresh_token_encrypted.is_some());
+        tracing::info!("  - device_id: {}", current_user.device_id);
+
+        // 获取语言设置
+        let language = app_settings_repo.get_language().ok();
+
+        // 3. 创建并初始化 ApiClient
+        match ApiClient::new(server_url.clone(), language) {
+            Ok(client) => {
+                // 4. 解密并设置 access_token
+                match CryptoService::decrypt_token(
+                    &current_user.access_token_encrypted,
+                    &current_user.device_id
+                ) {
+                    Ok(token) => {
+                        client.set_token(token);
+
+                        // 5. 设置 refresh_token_encrypted（用于后续 token 刷新）
+                        if let Some(refresh_token) = &current_user.refresh_token_encrypted {
+                            client.set_refresh_token(refresh_token.clone());
+                            tracing::info!("Refresh token set from database");
+                        } else {
+                            tracing::warn!("No refresh token found in database, auto-refresh will not work");
+                        }
+
+                        // 6. 设置 device_id（用于 token 加密解密）
+                        client.set_device_id(current_user.device_id.clone());
+
+                        // 7. 初始化 token 刷新回调
+                        client.init_token_refresh_callback(
+                            db_pool.clone(),
+                            current_user.user_id.clone(),
+                            current_user.device_id.clone(),
+                            {
+                                let api_client_state_clone = api_client_state.clone();
+                                move || api_client_state_clone.get_client()
+                            }
+                        );
+
+                        api_client_state.set_client(client);
+                        tracing::info!("API client initialized successfully");
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to decrypt token: {}, clearing current user state", e);
+                        // 解密失败时清除当前用户登录状态，避免用户看到不一致的状态
+                        let _ = user_auth_repo.clear_current();
+                    }
+                }
+            }
+            Err(e) => {
+                tracing::error!("Failed to create API client: {}, clearing current user state", e);
+                // 创建 client 失败时也清除当前用户登录状态
+                let _ = user_auth_repo.clear_current();
+            }
+        }
+    } else {
+        tracing::info!("No current user found, skipping API client initialization");
+    }
+}
@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+
+// ==================== 服务器请求类型（snake_case 格式）====================
+
+/// AI 代理聊天消息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerAiChatMessage {
+    pub role: String,
+    pub content: String,
+}
+
+/// AI 代理聊天请求（发送给服务器）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerAiChatRequest {
+    pub messages: Vec<ServerAiChatMessage>,
+    pub temperature: Option<f32>,
+    pub max_tokens: Option<u32>,
+}
+
+/// AI 代理聊天响应
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerAiChatResult {
+    pub content: String,
+    pub model: String,
+    /// 今日剩余次数，不限制时为空
+    pub remaining: Option<u64>,
+}
+
+/// AI 代理配额
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerAiQuota {
+    pub enabled: bool,
+    pub model: String,
+    pub daily_quota: u64,
+    pub used: u64,
+    pub remaining: Option<u64>,
+}
+
+/// 返回给前端的 AI 代理配额
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AIServerQuota {
+    pub enabled: bool,
+    pub model: String,
+    /// 每日请求次数上限，0 表示不限制
+    pub daily_quota: u64,
+    pub used: u64,
+    pub remaining: Option<u64>,
+}
+
+impl From<ServerAiQuota> for AIServerQuota {
+    fn from(quota: ServerAiQuota) -> Self {
+        Self {
+            enabled: quota.enabled,
+            model: quota.model,
+            daily_quota: quota.daily_quota,
+            used: quota.used,
+            remaining: quota.remaining,
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+/// 剪贴板历史记录（数据库存储格式，内容加密）
+#[derive(Debug, Clone)]
+pub struct ClipboardRecord {
+    pub id: String,
+    pub user_id: String,
+    pub connection_id: Option<String>,
+    pub session_name: Option<String>,
+    /// 来源主机（username@host:port）
+    pub host_key: Option<String>,
+    /// 加密后的内容（Base64(nonce || ciphertext)）
+    pub content_encrypted: String,
+    pub content_length: i64,
+    /// 毫秒时间戳（连续复制可能在同一秒内）
+    pub created_at: i64,
+}
+
+/// 剪贴板历史条目（返回给前端，已解密）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClipboardEntry {
+    pub id: String,
+    pub connection_id: Option<String>,
+    pub session_name: Option<String>,
+    pub host_key: Option<String>,
+    pub content: String,
+    pub created_at: i64,
+}
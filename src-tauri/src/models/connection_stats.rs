@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+
+/// 连接流量使用记录（断开连接时写入）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectionStatsRecord {
+    pub id: i64,
+    #[serde(skip_serializing)]
+    pub user_id: String,
+    pub session_id: String,
+    pub connection_id: String,
+    pub session_name: String,
+    /// 主机（username@host:port）
+    pub host_key: String,
+    pub connected_at: i64,
+    pub disconnected_at: i64,
+    pub bytes_sent: i64,
+    pub bytes_received: i64,
+}
+
+/// 按会话汇总的流量使用量
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionTrafficTotal {
+    pub session_id: String,
+    pub session_name: String,
+    pub connections: i64,
+    pub bytes_sent: i64,
+    pub bytes_received: i64,
+    /// 累计连接时长（秒）
+    pub duration_secs: i64,
+    pub last_connected_at: i64,
+}
+
+/// 使用热力图中一个小时的使用量（本地时间）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct HeatmapBucket {
+    /// 日期（YYYY-MM-DD）
+    pub date: String,
+    /// 小时（0-23）
+    pub hour: u8,
+    /// 该小时内开始的连接数
+    pub connections: i64,
+    /// 该小时内的连接时长（秒）
+    pub duration_secs: i64,
+}
+
+/// 热力图范围内按主机汇总的使用量
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct HeatmapHost {
+    /// 主机（username@host:port）
+    pub host_key: String,
+    pub session_name: String,
+    pub connections: i64,
+    pub duration_secs: i64,
+}
+
+/// 使用热力图（`stats_heatmap` 返回）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageHeatmap {
+    /// 起始日期（含）
+    pub from_date: String,
+    /// 结束日期（今天）
+    pub to_date: String,
+    /// 有使用量的小时，按时间排序
+    pub buckets: Vec<HeatmapBucket>,
+    /// 本设备使用最多的主机（不上传到服务器）
+    pub hosts: Vec<HeatmapHost>,
+    /// 汇总的设备数，只使用本机数据时为 1
+    pub devices: u64,
+}
+
+// ==================== 服务器请求类型（snake_case 格式）====================
+
+/// 服务器的分桶格式
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerUsageBucket {
+    pub date: String,
+    pub hour: i32,
+    pub connections: i64,
+    pub duration_secs: i64,
+}
+
+/// 上报本设备的使用量
+#[derive(Debug, Clone, Serialize)]
+pub struct ServerUsageHeatmapRequest {
+    pub device_id: String,
+    pub from_date: String,
+    pub buckets: Vec<ServerUsageBucket>,
+}
+
+/// 服务器返回的跨设备汇总
+#[derive(Debug, Clone, Deserialize)]
+pub struct ServerUsageHeatmapResult {
+    pub devices: u64,
+    pub buckets: Vec<ServerUsageBucket>,
+}
@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+
+/// 崩溃报告（panic 时写入存储目录 crash_reports/<id>.json）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrashReport {
+    pub id: String,
+    /// 发生时间（毫秒）
+    pub created_at: i64,
+    pub app_version: String,
+    pub os: String,
+    pub os_version: Option<String>,
+    pub arch: String,
+    /// panic 信息
+    pub message: String,
+    /// panic 位置（file:line:column）
+    pub location: Option<String>,
+    pub thread: Option<String>,
+    pub backtrace: String,
+    /// panic 前最近的日志
+    pub log_tail: Vec<String>,
+    /// 是否已上传到服务器
+    #[serde(default)]
+    pub uploaded: bool,
+}
+
+/// 崩溃报告摘要（列表展示用）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrashReportSummary {
+    pub id: String,
+    pub created_at: i64,
+    pub app_version: String,
+    pub message: String,
+    pub location: Option<String>,
+    pub uploaded: bool,
+}
+
+impl From<&CrashReport> for CrashReportSummary {
+    fn from(report: &CrashReport) -> Self {
+        Self {
+            id: report.id.clone(),
+            created_at: report.created_at,
+            app_version: report.app_version.clone(),
+            message: report.message.clone(),
+            location: report.location.clone(),
+            uploaded: report.uploaded,
+        }
+    }
+}
+
+// ==================== 服务器请求类型（snake_case 格式）====================
+
+/// 上传崩溃报告请求（发送给服务器）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrashReportUploadRequest {
+    pub report_id: String,
+    pub device_id: Option<String>,
+    pub app_version: String,
+    pub os: String,
+    pub os_version: Option<String>,
+    pub arch: String,
+    pub message: String,
+    pub location: Option<String>,
+    pub backtrace: String,
+    pub log_tail: String,
+    pub crashed_at: i64,
+}
+
+/// 上传崩溃报告响应
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerCrashReportResult {
+    pub id: String,
+}
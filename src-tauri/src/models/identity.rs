@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+/// 共享身份（用户名 + 认证方式，加密存储一次，可被多个会话引用）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Identity {
+    pub id: String,
+    pub user_id: String,
+    /// 显示名称（如 "生产环境 deploy 账号"）
+    pub name: String,
+    pub username: String,
+
+    // 认证信息（加密存储，格式与 ssh_sessions 相同）
+    pub auth_method_encrypted: String,
+    pub auth_nonce: String,
+
+    pub created_at: i64,
+    pub updated_at: i64,
+}
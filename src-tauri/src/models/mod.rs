@@ -2,7 +2,19 @@
 pub mod user_auth;
 pub mod user_profile;
 pub mod ssh_session;
+pub mod session_group;
 pub mod sync;
+pub mod session_note;
+pub mod identity;
+pub mod snippet;
+pub mod clipboard;
+pub mod crash_report;
+pub mod ai_proxy;
+pub mod recording_cloud;
+pub mod variable;
+pub mod connection_stats;
+pub mod workspace;
 
 pub use ssh_session::*;
-// 注意: user_auth、user_profile 和 sync 通过完整路径引用，避免污染命名空间
+pub use session_group::*;
+// 注意: 其余模块（user_auth、user_profile、sync、session_note 等）通过完整路径引用，避免污染命名空间
@@ -0,0 +1,86 @@
+use serde::{Deserialize, Serialize};
+
+// ==================== 服务器请求类型（snake_case 格式）====================
+
+/// 上传录制文件的参数（作为查询参数发送，请求体为文件内容）
+#[derive(Debug, Clone, Serialize)]
+pub struct ServerRecordingUpload {
+    pub title: String,
+    pub file_name: String,
+    /// recording、cast 或 video
+    pub kind: String,
+    pub visibility: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recording_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+}
+
+/// 更新可见性请求
+#[derive(Debug, Clone, Serialize)]
+pub struct ServerRecordingVisibilityRequest {
+    pub visibility: String,
+}
+
+/// 服务器返回的录制文件
+#[derive(Debug, Clone, Deserialize)]
+pub struct ServerRecording {
+    pub id: String,
+    pub title: String,
+    pub file_name: String,
+    pub kind: String,
+    pub content_type: String,
+    pub size: i64,
+    pub visibility: String,
+    pub session_name: Option<String>,
+    pub recording_id: Option<String>,
+    pub duration: Option<f64>,
+    pub is_owner: bool,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// 返回给前端的云端录制文件
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CloudRecording {
+    pub id: String,
+    pub title: String,
+    pub file_name: String,
+    pub kind: String,
+    pub content_type: String,
+    pub size: i64,
+    /// private、unlisted 或 shared
+    pub visibility: String,
+    pub session_name: Option<String>,
+    /// 本地录制 ID（同一录制的 JSON 与视频相同）
+    pub recording_id: Option<String>,
+    pub duration: Option<f64>,
+    pub is_owner: bool,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+impl From<ServerRecording> for CloudRecording {
+    fn from(recording: ServerRecording) -> Self {
+        Self {
+            id: recording.id,
+            title: recording.title,
+            file_name: recording.file_name,
+            kind: recording.kind,
+            content_type: recording.content_type,
+            size: recording.size,
+            visibility: recording.visibility,
+            session_name: recording.session_name,
+            recording_id: recording.recording_id,
+            duration: recording.duration,
+            is_owner: recording.is_owner,
+            created_at: recording.created_at,
+            updated_at: recording.updated_at,
+        }
+    }
+}
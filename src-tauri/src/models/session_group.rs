@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+
+/// 默认分组名称（会话未指定分组时使用，不可删除或重命名）
+pub const DEFAULT_GROUP_NAME: &str = "默认分组";
+
+/// 会话分组（本地数据库，会话通过 group_name 关联）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionGroup {
+    pub id: String,
+    pub user_id: String,
+    pub name: String,
+    /// 颜色（如 #3b82f6）
+    pub color: Option<String>,
+    /// 图标名称
+    pub icon: Option<String>,
+    /// 排序序号（越小越靠前）
+    pub sort_order: i32,
+    pub description: Option<String>,
+
+    // 同步字段
+    pub server_ver: i32,
+    pub client_ver: i32,
+    pub is_dirty: bool,
+    pub is_deleted: bool,
+    pub last_synced_at: Option<i64>,
+
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// 服务器会话分组（snake_case 格式，deleted_at 不为空表示已在其他设备删除）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerSessionGroup {
+    pub id: String,
+    pub user_id: String,
+    pub name: String,
+    pub color: Option<String>,
+    pub icon: Option<String>,
+    pub sort_order: i32,
+    pub description: Option<String>,
+    pub server_ver: i32,
+    pub client_ver: i32,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub deleted_at: Option<i64>,
+}
+
+impl From<ServerSessionGroup> for SessionGroup {
+    fn from(server: ServerSessionGroup) -> Self {
+        Self {
+            id: server.id,
+            user_id: server.user_id,
+            name: server.name,
+            color: server.color,
+            icon: server.icon,
+            sort_order: server.sort_order,
+            description: server.description,
+            server_ver: server.server_ver,
+            client_ver: server.client_ver,
+            is_dirty: false,
+            is_deleted: false,
+            last_synced_at: Some(server.updated_at),
+            created_at: server.created_at,
+            updated_at: server.updated_at,
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+
+/// 会话笔记（Markdown，每个会话一条，开启 `sync_enabled` 后参与云同步）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionNote {
+    pub session_id: String,
+    pub user_id: String,
+    /// Markdown 内容
+    pub content: String,
+    /// 是否同步到服务器（默认只保存在本地）
+    pub sync_enabled: bool,
+
+    // 同步字段
+    pub server_ver: i32,
+    pub client_ver: i32,
+    pub is_dirty: bool,
+    pub last_synced_at: Option<i64>,
+
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// 会话附件（运维手册、拓扑图等小文件，内容保存在本地存储目录）
+///
+/// 开启 `sync_enabled` 后参与云同步，只有不超过 [`MAX_SYNC_ATTACHMENT_SIZE`] 的附件可以同步
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionAttachment {
+    pub id: String,
+    pub session_id: String,
+    pub user_id: String,
+    pub file_name: String,
+    pub mime_type: Option<String>,
+    pub size: i64,
+    /// 附件文件在本地存储目录中的相对路径
+    pub stored_path: String,
+    /// 是否同步到服务器（默认只保存在本地）
+    pub sync_enabled: bool,
+
+    // 同步字段
+    pub server_ver: i32,
+    pub client_ver: i32,
+    pub is_dirty: bool,
+    pub last_synced_at: Option<i64>,
+
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// 可同步的单个附件大小上限（1 MB，与服务器一致）
+pub const MAX_SYNC_ATTACHMENT_SIZE: u64 = 1024 * 1024;
+
+/// 服务器会话笔记（snake_case 格式，deleted_at 不为空表示已在其他设备删除或取消同步）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerSessionNote {
+    pub session_id: String,
+    pub user_id: String,
+    pub content: String,
+    pub server_ver: i32,
+    pub client_ver: i32,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub deleted_at: Option<i64>,
+}
+
+/// 服务器会话附件（content 为 Base64 编码的文件内容）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerSessionAttachment {
+    pub id: String,
+    pub session_id: String,
+    pub user_id: String,
+    pub file_name: String,
+    pub mime_type: Option<String>,
+    pub size: i64,
+    pub content: String,
+    pub server_ver: i32,
+    pub client_ver: i32,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub deleted_at: Option<i64>,
+}
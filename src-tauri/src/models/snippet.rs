@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+/// 命令片段（常用命令模板）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Snippet {
+    pub id: String,
+    pub user_id: String,
+    pub name: String,
+    pub command: String,
+    pub description: Option<String>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// 主机命令历史（按主机聚合，记录使用次数）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandHistoryEntry {
+    pub user_id: String,
+    /// 主机标识（username@host:port）
+    pub host_key: String,
+    pub command: String,
+    pub use_count: i64,
+    pub last_used_at: i64,
+}
@@ -36,7 +36,7 @@ pub struct ServerSshSession {
 // ==================== 本地类型（用于数据库）====================
 
 /// SSH 认证方法
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub enum AuthMethod {
     /// 密码认证
     Password { password: String },
@@ -48,6 +48,21 @@ pub enum AuthMethod {
     },
 }
 
+/// Debug 输出不包含密码、私钥口令和私钥内容
+impl std::fmt::Debug for AuthMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Password { .. } => f.debug_struct("Password").finish_non_exhaustive(),
+            Self::PrivateKey { private_key_path, passphrase, key_data } => f
+                .debug_struct("PrivateKey")
+                .field("private_key_path", private_key_path)
+                .field("has_passphrase", &passphrase.is_some())
+                .field("has_key_data", &key_data.is_some())
+                .finish(),
+        }
+    }
+}
+
 /// SSH 会话配置（用于本地数据库）
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
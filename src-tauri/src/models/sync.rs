@@ -16,6 +16,25 @@ pub struct SyncRequest {
     pub ssh_sessions: Vec<SshSessionPushItem>,
     /// 删除的会话 ID
     pub deleted_session_ids: Vec<String>,
+    /// 从回收站恢复的会话 ID
+    #[serde(default)]
+    pub restored_session_ids: Vec<String>,
+    /// 会话分组更新
+    pub session_groups: Vec<SessionGroupPushItem>,
+    /// 删除的分组 ID
+    pub deleted_group_ids: Vec<String>,
+    /// 会话笔记更新（只包含开启同步的笔记）
+    #[serde(default)]
+    pub session_notes: Vec<SessionNotePushItem>,
+    /// 删除或取消同步的笔记（会话 ID）
+    #[serde(default)]
+    pub deleted_note_ids: Vec<String>,
+    /// 会话附件更新（只包含开启同步的附件）
+    #[serde(default)]
+    pub session_attachments: Vec<SessionAttachmentPushItem>,
+    /// 删除或取消同步的附件 ID
+    #[serde(default)]
+    pub deleted_attachment_ids: Vec<String>,
 }
 
 /// SSH 会话推送项（snake_case 格式，用于与服务器通信）
@@ -38,6 +57,44 @@ pub struct SshSessionPushItem {
     pub updated_at: i64,
 }
 
+/// 会话分组推送项（snake_case 格式，用于与服务器通信）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionGroupPushItem {
+    pub id: String,
+    pub name: String,
+    pub color: Option<String>,
+    pub icon: Option<String>,
+    pub sort_order: i32,
+    pub description: Option<String>,
+    pub client_ver: i32,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// 会话笔记推送项（snake_case 格式，用于与服务器通信）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionNotePushItem {
+    pub session_id: String,
+    pub content: String,
+    pub client_ver: i32,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// 会话附件推送项（snake_case 格式，content 为 Base64 编码的文件内容）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionAttachmentPushItem {
+    pub id: String,
+    pub session_id: String,
+    pub file_name: String,
+    pub mime_type: Option<String>,
+    pub size: i64,
+    pub content: String,
+    pub client_ver: i32,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
 /// 更新用户资料请求
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UpdateProfileRequest {
@@ -130,6 +187,20 @@ pub struct ServerSyncResponse {
     /// Pull 结果
     pub user_profile: Option<crate::models::user_profile::ServerUserProfile>,
     pub ssh_sessions: Vec<crate::models::ServerSshSession>,
+    /// 会话分组（旧版本服务器不返回）
+    #[serde(default)]
+    pub session_groups: Vec<crate::models::ServerSessionGroup>,
+    #[serde(default)]
+    pub group_versions: HashMap<String, i32>,
+    /// 会话笔记和附件（旧版本服务器不返回）
+    #[serde(default)]
+    pub session_notes: Vec<crate::models::session_note::ServerSessionNote>,
+    #[serde(default)]
+    pub note_versions: HashMap<String, i32>,
+    #[serde(default)]
+    pub session_attachments: Vec<crate::models::session_note::ServerSessionAttachment>,
+    #[serde(default)]
+    pub attachment_versions: HashMap<String, i32>,
     /// 冲突信息
     pub conflicts: Vec<ServerConflictInfo>,
     /// 消息
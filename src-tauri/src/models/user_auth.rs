@@ -1,4 +1,26 @@
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use zeroize::Zeroize;
+
+/// 为带 `password` 字段的请求实现不输出密码的 Debug，并在 drop 时清零密码
+macro_rules! protect_password {
+    ($($name:ident),* $(,)?) => {$(
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_struct(stringify!($name))
+                    .field("email", &self.email)
+                    .field("password", &"***")
+                    .finish_non_exhaustive()
+            }
+        }
+
+        impl Drop for $name {
+            fn drop(&mut self) {
+                self.password.zeroize();
+            }
+        }
+    )*};
+}
 
 /// 用户认证信息
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,7 +43,7 @@ pub struct UserAuth {
 
 /// 登录请求
 /// 注意：server_url 字段仅用于向后兼容，实际从后端 app_settings 表读取
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LoginRequest {
     pub email: String,
@@ -32,7 +54,7 @@ pub struct LoginRequest {
 
 /// 注册请求
 /// 注意：server_url 字段仅用于向后兼容，实际从后端 app_settings 表读取
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RegisterRequest {
     pub email: String,
@@ -43,6 +65,8 @@ pub struct RegisterRequest {
     pub server_url: Option<String>,
 }
 
+protect_password!(LoginRequest, RegisterRequest);
+
 /// 认证响应（客户端期望格式）
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -54,6 +78,9 @@ pub struct AuthResponse {
     pub device_id: String,
     pub server_url: String,
     pub expires_at: i64,
+    /// 邮箱是否已验证（自动登录或旧版服务器未返回时为 None）
+    #[serde(default)]
+    pub email_verified: Option<bool>,
 }
 
 // ==================== 服务器返回类型（snake_case 格式）====================
@@ -67,6 +94,8 @@ pub struct ServerRegisterResult {
     pub device_id: String,
     pub access_token: String,
     pub refresh_token: String,
+    #[serde(default)]
+    pub email_verified: Option<bool>,
 }
 
 /// 服务器登录结果（服务器返回格式）
@@ -75,6 +104,8 @@ pub struct ServerLoginResult {
     pub device_id: String,
     pub access_token: String,
     pub refresh_token: String,
+    #[serde(default)]
+    pub email_verified: Option<bool>,
 }
 
 /// 服务器刷新 Token 结果
@@ -85,14 +116,14 @@ pub struct ServerRefreshResult {
 }
 
 /// 服务器登录请求（不含 server_url）
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct ServerLoginRequest {
     pub email: String,
     pub password: String,
 }
 
 /// 服务器注册请求（不含 server_url）
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct ServerRegisterRequest {
     pub email: String,
     pub password: String,
@@ -100,12 +131,21 @@ pub struct ServerRegisterRequest {
     pub verify_code: Option<String>,
 }
 
+protect_password!(ServerLoginRequest, ServerRegisterRequest);
+
 /// 发送验证码请求
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SendVerifyCodeRequest {
     pub email: String,
 }
 
+/// 验证邮箱请求（注册后补充验证）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerifyEmailRequest {
+    pub email: String,
+    pub verify_code: String,
+}
+
 /// 邮箱验证结果
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+
+/// 模板变量（用于会话字段中的 `{{name}}` 占位符）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Variable {
+    pub user_id: String,
+    /// 所属会话 ID，None 表示全局变量
+    pub session_id: Option<String>,
+    pub name: String,
+    pub value: String,
+    pub updated_at: i64,
+}
@@ -0,0 +1,227 @@
+use serde::{Deserialize, Serialize};
+
+/// 工作区成员角色（与服务器一致）
+///
+/// - `owner`: 创建者，拥有全部权限
+/// - `admin`: 管理成员和共享会话，可以查看和导出凭据
+/// - `member`: 只能连接共享会话，不能查看或导出凭据，不能删除共享会话
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WorkspaceRole {
+    Owner,
+    Admin,
+    Member,
+}
+
+impl WorkspaceRole {
+    /// 添加和移除成员
+    pub fn can_manage_members(self) -> bool {
+        self != Self::Member
+    }
+
+    /// 共享新会话
+    pub fn can_share_sessions(self) -> bool {
+        self != Self::Member
+    }
+
+    /// 删除共享会话
+    pub fn can_delete_sessions(self) -> bool {
+        self != Self::Member
+    }
+
+    /// 查看和导出共享会话的凭据
+    pub fn can_view_credentials(self) -> bool {
+        self != Self::Member
+    }
+
+    /// 查看审计日志
+    pub fn can_view_audit_logs(self) -> bool {
+        self != Self::Member
+    }
+}
+
+// ==================== 服务器请求类型（snake_case 格式）====================
+
+/// 创建工作区请求
+#[derive(Debug, Clone, Serialize)]
+pub struct ServerCreateWorkspaceRequest {
+    pub name: String,
+}
+
+/// 添加成员请求
+#[derive(Debug, Clone, Serialize)]
+pub struct ServerAddWorkspaceMemberRequest {
+    pub email: String,
+    pub role: WorkspaceRole,
+}
+
+/// 共享会话请求（credentials 为认证信息 JSON，由服务器加密保存）
+#[derive(Clone, Serialize)]
+pub struct ServerCreateSharedSessionRequest {
+    pub name: String,
+    pub host: String,
+    pub port: u16,
+    pub username: String,
+    pub credentials: String,
+}
+
+// ==================== 服务器响应类型（snake_case 格式）====================
+
+/// 服务器返回的工作区
+#[derive(Debug, Clone, Deserialize)]
+pub struct ServerWorkspace {
+    pub id: String,
+    pub name: String,
+    pub owner_id: String,
+    pub role: WorkspaceRole,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// 服务器返回的工作区成员
+#[derive(Debug, Clone, Deserialize)]
+pub struct ServerWorkspaceMember {
+    pub user_id: String,
+    pub email: Option<String>,
+    pub role: WorkspaceRole,
+    pub created_at: i64,
+}
+
+/// 服务器返回的共享会话（不包含凭据）
+#[derive(Debug, Clone, Deserialize)]
+pub struct ServerSharedSession {
+    pub id: String,
+    pub workspace_id: String,
+    pub name: String,
+    pub host: String,
+    pub port: u16,
+    pub username: String,
+    pub created_by: String,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// 服务器返回的共享会话凭据（连接时或管理员导出时）
+#[derive(Clone, Deserialize)]
+pub struct ServerSharedSessionCredentials {
+    pub session: ServerSharedSession,
+    pub credentials: String,
+}
+
+/// 服务器返回的审计日志
+#[derive(Debug, Clone, Deserialize)]
+pub struct ServerWorkspaceAuditLog {
+    pub id: String,
+    pub shared_session_id: String,
+    pub user_id: String,
+    pub action: String,
+    pub session_name: String,
+    pub target: String,
+    pub created_at: i64,
+}
+
+// ==================== 客户端类型（用于 Tauri 命令）====================
+
+/// 工作区（包含当前用户的角色）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Workspace {
+    pub id: String,
+    pub name: String,
+    pub owner_id: String,
+    pub role: WorkspaceRole,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+impl From<ServerWorkspace> for Workspace {
+    fn from(server: ServerWorkspace) -> Self {
+        Self {
+            id: server.id,
+            name: server.name,
+            owner_id: server.owner_id,
+            role: server.role,
+            created_at: server.created_at,
+            updated_at: server.updated_at,
+        }
+    }
+}
+
+/// 工作区成员
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceMember {
+    pub user_id: String,
+    pub email: Option<String>,
+    pub role: WorkspaceRole,
+    pub created_at: i64,
+}
+
+impl From<ServerWorkspaceMember> for WorkspaceMember {
+    fn from(server: ServerWorkspaceMember) -> Self {
+        Self {
+            user_id: server.user_id,
+            email: server.email,
+            role: server.role,
+            created_at: server.created_at,
+        }
+    }
+}
+
+/// 共享会话（不包含凭据，凭据只在连接时从服务器获取并保存在内存中）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SharedSession {
+    pub id: String,
+    pub workspace_id: String,
+    pub name: String,
+    pub host: String,
+    pub port: u16,
+    pub username: String,
+    pub created_by: String,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+impl From<ServerSharedSession> for SharedSession {
+    fn from(server: ServerSharedSession) -> Self {
+        Self {
+            id: server.id,
+            workspace_id: server.workspace_id,
+            name: server.name,
+            host: server.host,
+            port: server.port,
+            username: server.username,
+            created_by: server.created_by,
+            created_at: server.created_at,
+            updated_at: server.updated_at,
+        }
+    }
+}
+
+/// 审计日志
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceAuditLog {
+    pub id: String,
+    pub shared_session_id: String,
+    pub user_id: String,
+    pub action: String,
+    pub session_name: String,
+    pub target: String,
+    pub created_at: i64,
+}
+
+impl From<ServerWorkspaceAuditLog> for WorkspaceAuditLog {
+    fn from(server: ServerWorkspaceAuditLog) -> Self {
+        Self {
+            id: server.id,
+            shared_session_id: server.shared_session_id,
+            user_id: server.user_id,
+            action: server.action,
+            session_name: server.session_name,
+            target: server.target,
+            created_at: server.created_at,
+        }
+    }
+}
@@ -0,0 +1,356 @@
+//! 插件管理器
+//!
+//! 启动时扫描 `<应用存储目录>/plugins/*/plugin.json`，启动所有已启用的插件，
+//! 发送 `initialize` 请求完成握手；之后负责转发命令调用和终端输出。
+
+use anyhow::{anyhow, Result};
+use bytes::Bytes;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::{mpsc, RwLock};
+
+use super::manifest::{ContextMenuAction, MenuContext, PluginCommand, PluginManifest, PluginSubscription};
+use super::rpc::PluginProcess;
+use crate::config::Storage;
+
+/// 插件目录名（位于应用存储目录）
+const PLUGINS_DIR: &str = "plugins";
+
+/// 插件启用状态文件（位于应用存储目录，记录被禁用的插件 ID）
+const PLUGIN_STATE_FILE: &str = "plugins.json";
+
+/// 插件事件前缀（`plugin-event-{pluginId}`），转发插件的 `host/emit` 通知
+pub const PLUGIN_EVENT_PREFIX: &str = "plugin-event-";
+
+/// 握手超时时间
+const INITIALIZE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 命令调用超时时间
+const INVOKE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// 退出时等待插件自行结束的时间
+const SHUTDOWN_GRACE: Duration = Duration::from_secs(2);
+
+/// 每个插件缓存的终端输出块数量，插件处理不过来时丢弃新输出，不阻塞终端
+const OUTPUT_QUEUE_SIZE: usize = 256;
+
+/// 插件信息（`plugin_list` 返回）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginInfo {
+    pub id: String,
+    pub name: String,
+    pub version: String,
+    pub description: Option<String>,
+    pub dir: String,
+    pub enabled: bool,
+    pub running: bool,
+    /// 启动或握手失败的原因
+    pub error: Option<String>,
+    pub commands: Vec<PluginCommand>,
+    pub context_menu: Vec<ContextMenuAction>,
+    pub subscriptions: Vec<PluginSubscription>,
+}
+
+/// 某个位置的插件菜单项
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginMenuItem {
+    pub plugin_id: String,
+    pub plugin_name: String,
+    pub id: String,
+    pub title: String,
+    pub command: String,
+}
+
+struct LoadedPlugin {
+    manifest: PluginManifest,
+    dir: PathBuf,
+    enabled: bool,
+    process: Option<Arc<PluginProcess>>,
+    error: Option<String>,
+}
+
+/// 插件管理器
+pub struct PluginManager {
+    app_handle: AppHandle,
+    plugins: RwLock<HashMap<String, LoadedPlugin>>,
+    /// 订阅了终端输出的插件
+    output_subscribers: Mutex<HashMap<String, mpsc::Sender<(String, Bytes)>>>,
+}
+
+impl PluginManager {
+    pub fn new(app_handle: AppHandle) -> Self {
+        Self {
+            app_handle,
+            plugins: RwLock::new(HashMap::new()),
+            output_subscribers: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 插件目录
+    pub fn plugins_dir() -> Result<PathBuf> {
+        Ok(Storage::get_app_storage_dir()?.join(PLUGINS_DIR))
+    }
+
+    /// 重新扫描插件目录，停止所有插件后启动已启用的插件
+    pub async fn reload(&self) -> Result<()> {
+        self.shutdown().await;
+
+        let dir = Self::plugins_dir()?;
+        std::fs::create_dir_all(&dir)?;
+        let disabled = load_disabled()?;
+
+        let mut loaded = HashMap::new();
+        for entry in std::fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if !path.is_dir() {
+                continue;
+            }
+            let manifest = match PluginManifest::load(&path) {
+                Ok(manifest) => manifest,
+                Err(e) => {
+                    tracing::warn!("[Plugin] Skipping {}: {}", path.display(), e);
+                    continue;
+                }
+            };
+            if loaded.contains_key(&manifest.id) {
+                tracing::warn!("[Plugin] Duplicate plugin id '{}' in {}", manifest.id, path.display());
+                continue;
+            }
+
+            let mut plugin = LoadedPlugin {
+                enabled: !disabled.contains(&manifest.id),
+                manifest,
+                dir: path,
+                process: None,
+                error: None,
+            };
+            if plugin.enabled {
+                self.start(&mut plugin).await;
+            }
+            loaded.insert(plugin.manifest.id.clone(), plugin);
+        }
+
+        tracing::info!("[Plugin] Loaded {} plugin(s)", loaded.len());
+        *self.plugins.write().await = loaded;
+        Ok(())
+    }
+
+    /// 列出所有插件
+    pub async fn list(&self) -> Vec<PluginInfo> {
+        let plugins = self.plugins.read().await;
+        let mut list = Vec::with_capacity(plugins.len());
+        for plugin in plugins.values() {
+            let running = match &plugin.process {
+                Some(process) => process.is_running().await,
+                None => false,
+            };
+            list.push(PluginInfo {
+                id: plugin.manifest.id.clone(),
+                name: plugin.manifest.name.clone(),
+                version: plugin.manifest.version.clone(),
+                description: plugin.manifest.description.clone(),
+                dir: plugin.dir.to_string_lossy().to_string(),
+                enabled: plugin.enabled,
+                running,
+                error: plugin.error.clone(),
+                commands: plugin.manifest.commands.clone(),
+                context_menu: plugin.manifest.context_menu.clone(),
+                subscriptions: plugin.manifest.subscriptions.clone(),
+            });
+        }
+        list.sort_by(|a, b| a.name.cmp(&b.name));
+        list
+    }
+
+    /// 启用或禁用插件（立即启动或停止，并记住设置）
+    pub async fn set_enabled(&self, plugin_id: &str, enabled: bool) -> Result<()> {
+        let mut plugins = self.plugins.write().await;
+        let plugin = plugins
+            .get_mut(plugin_id)
+            .ok_or_else(|| anyhow!("Plugin not found: {}", plugin_id))?;
+
+        let mut disabled = load_disabled()?;
+        if enabled {
+            disabled.remove(plugin_id);
+        } else {
+            disabled.insert(plugin_id.to_string());
+        }
+        save_disabled(&disabled)?;
+
+        plugin.enabled = enabled;
+        if enabled && plugin.process.is_none() {
+            self.start(plugin).await;
+        } else if !enabled {
+            self.stop(plugin).await;
+        }
+        Ok(())
+    }
+
+    /// 调用插件声明的命令
+    pub async fn invoke(&self, plugin_id: &str, command: &str, params: Value) -> Result<Value> {
+        let process = {
+            let plugins = self.plugins.read().await;
+            let plugin = plugins
+                .get(plugin_id)
+                .ok_or_else(|| anyhow!("Plugin not found: {}", plugin_id))?;
+            if !plugin.manifest.has_command(command) {
+                return Err(anyhow!("Plugin '{}' has no command '{}'", plugin_id, command));
+            }
+            plugin
+                .process
+                .clone()
+                .ok_or_else(|| anyhow!("Plugin '{}' is not running", plugin_id))?
+        };
+
+        process.request(command, params, INVOKE_TIMEOUT).await
+    }
+
+    /// 某个位置的所有菜单项（仅运行中的插件，已退出的插件进程不再显示菜单项）
+    pub async fn menu_items(&self, context: MenuContext) -> Vec<PluginMenuItem> {
+        let plugins = self.plugins.read().await;
+        let mut items = Vec::new();
+        for plugin in plugins.values() {
+            let running = match &plugin.process {
+                Some(process) => process.is_running().await,
+                None => false,
+            };
+            if !running {
+                continue;
+            }
+            items.extend(
+                plugin
+                    .manifest
+                    .context_menu
+                    .iter()
+                    .filter(|action| action.context == context)
+                    .map(|action| PluginMenuItem {
+                        plugin_id: plugin.manifest.id.clone(),
+                        plugin_name: plugin.manifest.name.clone(),
+                        id: action.id.clone(),
+                        title: action.title.clone(),
+                        command: action.command.clone(),
+                    }),
+            );
+        }
+        items.sort_by(|a, b| (&a.plugin_name, &a.title).cmp(&(&b.plugin_name, &b.title)));
+        items
+    }
+
+    /// 将终端输出转发给订阅的插件（不阻塞，队列已满时丢弃）
+    pub fn publish_output(&self, connection_id: &str, chunk: &Bytes) {
+        let subscribers = self.output_subscribers.lock().unwrap();
+        for (plugin_id, sender) in subscribers.iter() {
+            if sender.try_send((connection_id.to_string(), chunk.clone())).is_err() {
+                tracing::trace!("[Plugin {}] Output queue full, dropping {} bytes", plugin_id, chunk.len());
+            }
+        }
+    }
+
+    /// 停止所有插件
+    pub async fn shutdown(&self) {
+        let mut plugins = self.plugins.write().await;
+        for plugin in plugins.values_mut() {
+            self.stop(plugin).await;
+        }
+    }
+
+    /// 启动插件进程并完成握手，失败原因记录在 `error` 中
+    async fn start(&self, plugin: &mut LoadedPlugin) {
+        let plugin_id = plugin.manifest.id.clone();
+        let app_handle = self.app_handle.clone();
+        let event_plugin_id = plugin_id.clone();
+        let on_notification = move |method: String, params: Value| match method.as_str() {
+            "host/emit" => {
+                let event_name = format!("{}{}", PLUGIN_EVENT_PREFIX, event_plugin_id);
+                let _ = app_handle.emit(&event_name, params);
+            }
+            "host/log" => {
+                let message = params.get("message").and_then(|m| m.as_str()).unwrap_or_default();
+                tracing::info!("[Plugin {}] {}", event_plugin_id, message);
+            }
+            other => tracing::debug!("[Plugin {}] Unknown notification: {}", event_plugin_id, other),
+        };
+
+        let process = match PluginProcess::spawn(
+            &plugin_id,
+            &plugin.dir,
+            &plugin.manifest.command,
+            &plugin.manifest.args,
+            on_notification,
+        ) {
+            Ok(process) => Arc::new(process),
+            Err(e) => {
+                tracing::warn!("[Plugin] {}", e);
+                plugin.error = Some(e.to_string());
+                return;
+            }
+        };
+
+        let initialize = serde_json::json!({
+            "appVersion": env!("CARGO_PKG_VERSION"),
+            "pluginDir": plugin.dir.to_string_lossy(),
+            "platform": std::env::consts::OS,
+        });
+        if let Err(e) = process.request("initialize", initialize, INITIALIZE_TIMEOUT).await {
+            tracing::warn!("[Plugin] Handshake with '{}' failed: {}", plugin_id, e);
+            process.shutdown(SHUTDOWN_GRACE).await;
+            plugin.error = Some(e.to_string());
+            return;
+        }
+
+        if plugin.manifest.subscriptions.contains(&PluginSubscription::TerminalOutput) {
+            let (sender, mut receiver) = mpsc::channel::<(String, Bytes)>(OUTPUT_QUEUE_SIZE);
+            let output_process = process.clone();
+            tokio::spawn(async move {
+                while let Some((connection_id, chunk)) = receiver.recv().await {
+                    let params = serde_json::json!({
+                        "connectionId": connection_id,
+                        // 分块边界可能截断多字节字符，插件需要原始字节时应自行缓冲
+                        "data": String::from_utf8_lossy(&chunk),
+                    });
+                    if output_process.notify("terminal/output", params).await.is_err() {
+                        break;
+                    }
+                }
+            });
+            self.output_subscribers.lock().unwrap().insert(plugin_id.clone(), sender);
+        }
+
+        tracing::info!("[Plugin] Started '{}' {}", plugin_id, plugin.manifest.version);
+        plugin.process = Some(process);
+        plugin.error = None;
+    }
+
+    async fn stop(&self, plugin: &mut LoadedPlugin) {
+        self.output_subscribers.lock().unwrap().remove(&plugin.manifest.id);
+        if let Some(process) = plugin.process.take() {
+            process.shutdown(SHUTDOWN_GRACE).await;
+            tracing::info!("[Plugin] Stopped '{}'", plugin.manifest.id);
+        }
+    }
+}
+
+fn load_disabled() -> Result<HashSet<String>> {
+    let path = Storage::get_app_storage_dir()?.join(PLUGIN_STATE_FILE);
+    if !path.exists() {
+        return Ok(HashSet::new());
+    }
+    Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+}
+
+fn save_disabled(disabled: &HashSet<String>) -> Result<()> {
+    let path = Storage::get_app_storage_dir()?.join(PLUGIN_STATE_FILE);
+    let temp_path = path.with_extension("tmp");
+    let mut ids: Vec<_> = disabled.iter().collect();
+    ids.sort();
+    std::fs::write(&temp_path, serde_json::to_string_pretty(&ids)?)?;
+    std::fs::rename(&temp_path, &path)?;
+    Ok(())
+}
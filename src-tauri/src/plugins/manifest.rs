@@ -0,0 +1,173 @@
+//! 插件清单（`plugin.json`）
+//!
+//! 清单声明插件的启动方式和提供的扩展点：可调用的命令、上下文菜单项
+//! 以及订阅的事件流。命令由插件进程实现，菜单项只能引用已声明的命令。
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+
+/// 清单文件名（位于插件目录）
+pub const MANIFEST_FILE: &str = "plugin.json";
+
+/// 插件清单
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginManifest {
+    /// 唯一 ID，只能包含小写字母、数字、`-`、`_` 和 `.`
+    pub id: String,
+    pub name: String,
+    pub version: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// 启动插件的程序（相对路径相对于插件目录）
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// 插件提供的命令
+    #[serde(default)]
+    pub commands: Vec<PluginCommand>,
+    /// 上下文菜单项
+    #[serde(default)]
+    pub context_menu: Vec<ContextMenuAction>,
+    /// 订阅的事件流
+    #[serde(default)]
+    pub subscriptions: Vec<PluginSubscription>,
+}
+
+/// 插件命令，调用时以命令名作为 JSON-RPC 方法名发送给插件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginCommand {
+    pub name: String,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// 菜单项出现的位置
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum MenuContext {
+    /// 终端右键菜单，参数为 `{ connectionId, selection }`
+    Terminal,
+    /// 远程 SFTP 文件右键菜单，参数为 `{ connectionId, path, isDir }`
+    SftpFile,
+    /// 会话卡片的插件菜单，参数为 `{ sessionId, host, port, username }`
+    Session,
+}
+
+/// 上下文菜单项，点击后调用 `command`，参数为菜单所在位置的上下文
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContextMenuAction {
+    pub id: String,
+    pub title: String,
+    pub context: MenuContext,
+    pub command: String,
+}
+
+/// 可订阅的事件流
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum PluginSubscription {
+    /// 所有连接的终端输出（`terminal/output` 通知）
+    TerminalOutput,
+}
+
+impl PluginManifest {
+    /// 读取并校验插件目录中的清单
+    pub fn load(dir: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(dir.join(MANIFEST_FILE))?;
+        let manifest: Self = serde_json::from_str(&content)?;
+        manifest.validate()?;
+        Ok(manifest)
+    }
+
+    /// 校验 ID、启动命令以及命令和菜单项的引用关系
+    pub fn validate(&self) -> Result<()> {
+        let valid_id = !self.id.is_empty()
+            && self.id.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || matches!(b, b'-' | b'_' | b'.'));
+        if !valid_id {
+            return Err(anyhow!("Invalid plugin id '{}'", self.id));
+        }
+        if self.command.trim().is_empty() {
+            return Err(anyhow!("Plugin '{}' has no command", self.id));
+        }
+
+        let mut commands = HashSet::new();
+        for command in &self.commands {
+            // `initialize`、`shutdown` 和带 `/` 的方法名保留给宿主
+            if command.name.is_empty() || command.name.contains('/') || matches!(command.name.as_str(), "initialize" | "shutdown") {
+                return Err(anyhow!("Plugin '{}' declares reserved command name '{}'", self.id, command.name));
+            }
+            if !commands.insert(command.name.as_str()) {
+                return Err(anyhow!("Plugin '{}' declares command '{}' twice", self.id, command.name));
+            }
+        }
+
+        let mut actions = HashSet::new();
+        for action in &self.context_menu {
+            if !actions.insert(action.id.as_str()) {
+                return Err(anyhow!("Plugin '{}' declares menu item '{}' twice", self.id, action.id));
+            }
+            if !commands.contains(action.command.as_str()) {
+                return Err(anyhow!(
+                    "Menu item '{}' of plugin '{}' refers to undeclared command '{}'",
+                    action.id, self.id, action.command
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 是否声明了命令
+    pub fn has_command(&self, name: &str) -> bool {
+        self.commands.iter().any(|c| c.name == name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest(json: serde_json::Value) -> PluginManifest {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_validate() {
+        let valid = manifest(serde_json::json!({
+            "id": "git-status",
+            "name": "Git Status",
+            "version": "0.1.0",
+            "command": "python3",
+            "args": ["main.py"],
+            "commands": [{ "name": "status" }],
+            "contextMenu": [{ "id": "show", "title": "Git status", "context": "sftpFile", "command": "status" }],
+            "subscriptions": ["terminalOutput"],
+        }));
+        assert!(valid.validate().is_ok());
+        assert!(valid.has_command("status"));
+        assert_eq!(valid.context_menu[0].context, MenuContext::SftpFile);
+
+        let mut invalid = valid.clone();
+        invalid.id = "Git Status".to_string();
+        assert!(invalid.validate().is_err());
+
+        let mut invalid = valid.clone();
+        invalid.context_menu[0].command = "missing".to_string();
+        assert!(invalid.validate().is_err());
+
+        let mut invalid = valid.clone();
+        invalid.commands.push(PluginCommand { name: "initialize".to_string(), title: None, description: None });
+        assert!(invalid.validate().is_err());
+
+        let mut invalid = valid;
+        invalid.commands.push(invalid.commands[0].clone());
+        assert!(invalid.validate().is_err());
+    }
+}
@@ -0,0 +1,16 @@
+//! 插件系统
+//!
+//! 插件是放在 `<应用存储目录>/plugins/<name>/` 下的独立本地程序，由 `plugin.json` 描述。
+//! 宿主通过 stdin/stdout 与插件逐行交换 JSON-RPC 2.0 消息：
+//!
+//! - 宿主 → 插件：`initialize` 握手、调用插件声明的命令（方法名即命令名）、
+//!   `terminal/output` 终端输出通知（需订阅）、`shutdown` 退出通知
+//! - 插件 → 宿主：`host/emit` 转发为前端 `plugin-event-{pluginId}` 事件、`host/log` 写入日志
+//!
+//! 插件进程崩溃或超时不会影响应用本身，调用方只会收到错误。
+
+pub mod manifest;
+pub mod manager;
+pub mod rpc;
+
+pub use manager::PluginManager;
@@ -0,0 +1,224 @@
+//! 插件进程和 JSON-RPC 通信
+//!
+//! 每条消息是一行 JSON（JSON-RPC 2.0）。宿主发送请求并按 ID 等待响应；
+//! 插件发来的通知（没有 ID 的消息）交给调用方处理，stderr 输出写入日志。
+
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin};
+use tokio::sync::oneshot;
+
+/// 插件发来的消息
+#[derive(Debug, PartialEq)]
+pub enum Incoming {
+    /// 对宿主请求的响应
+    Response { id: u64, result: std::result::Result<Value, String> },
+    /// 插件主动发送的通知
+    Notification { method: String, params: Value },
+}
+
+type Pending = Arc<Mutex<HashMap<u64, oneshot::Sender<std::result::Result<Value, String>>>>>;
+
+/// 运行中的插件进程
+pub struct PluginProcess {
+    plugin_id: String,
+    stdin: tokio::sync::Mutex<ChildStdin>,
+    child: tokio::sync::Mutex<Child>,
+    pending: Pending,
+    next_id: AtomicU64,
+}
+
+impl PluginProcess {
+    /// 在插件目录中启动进程，`on_notification` 处理插件发来的通知
+    pub fn spawn<F>(plugin_id: &str, dir: &Path, command: &str, args: &[String], on_notification: F) -> Result<Self>
+    where
+        F: Fn(String, Value) + Send + 'static,
+    {
+        // 相对路径的程序相对于插件目录，其他（如 python3、node）从 PATH 查找
+        let program = if command.starts_with("./") || command.starts_with(".\\") {
+            dir.join(command).to_string_lossy().to_string()
+        } else {
+            command.to_string()
+        };
+
+        let mut child = tokio::process::Command::new(&program)
+            .args(args)
+            .current_dir(dir)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| anyhow!("Failed to start plugin '{}' ({}): {}", plugin_id, program, e))?;
+
+        let stdin = child.stdin.take().ok_or_else(|| anyhow!("Plugin stdin unavailable"))?;
+        let stdout = child.stdout.take().ok_or_else(|| anyhow!("Plugin stdout unavailable"))?;
+        let stderr = child.stderr.take().ok_or_else(|| anyhow!("Plugin stderr unavailable"))?;
+        let pending: Pending = Arc::new(Mutex::new(HashMap::new()));
+
+        {
+            let plugin_id = plugin_id.to_string();
+            let pending = pending.clone();
+            tokio::spawn(async move {
+                let mut lines = BufReader::new(stdout).lines();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    match parse_message(&line) {
+                        Some(Incoming::Response { id, result }) => {
+                            if let Some(sender) = pending.lock().unwrap().remove(&id) {
+                                let _ = sender.send(result);
+                            }
+                        }
+                        Some(Incoming::Notification { method, params }) => on_notification(method, params),
+                        None => tracing::debug!("[Plugin {}] Ignoring non JSON-RPC output: {}", plugin_id, line),
+                    }
+                }
+                // 进程退出：让所有等待中的请求立即失败
+                pending.lock().unwrap().clear();
+                tracing::info!("[Plugin {}] stdout closed", plugin_id);
+            });
+        }
+
+        {
+            let plugin_id = plugin_id.to_string();
+            tokio::spawn(async move {
+                let mut lines = BufReader::new(stderr).lines();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    tracing::info!("[Plugin {}] {}", plugin_id, line);
+                }
+            });
+        }
+
+        Ok(Self {
+            plugin_id: plugin_id.to_string(),
+            stdin: tokio::sync::Mutex::new(stdin),
+            child: tokio::sync::Mutex::new(child),
+            pending,
+            next_id: AtomicU64::new(1),
+        })
+    }
+
+    /// 发送请求并等待响应
+    pub async fn request(&self, method: &str, params: Value, timeout: Duration) -> Result<Value> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (sender, receiver) = oneshot::channel();
+        self.pending.lock().unwrap().insert(id, sender);
+
+        if let Err(e) = self.write_line(&encode_request(Some(id), method, params)).await {
+            self.pending.lock().unwrap().remove(&id);
+            return Err(e);
+        }
+
+        match tokio::time::timeout(timeout, receiver).await {
+            Ok(Ok(result)) => result.map_err(|e| anyhow!(e)),
+            Ok(Err(_)) => Err(anyhow!("Plugin '{}' exited before responding to '{}'", self.plugin_id, method)),
+            Err(_) => {
+                self.pending.lock().unwrap().remove(&id);
+                Err(anyhow!("Plugin '{}' did not respond to '{}' within {:?}", self.plugin_id, method, timeout))
+            }
+        }
+    }
+
+    /// 发送通知（不等待响应）
+    pub async fn notify(&self, method: &str, params: Value) -> Result<()> {
+        self.write_line(&encode_request(None, method, params)).await
+    }
+
+    /// 通知插件退出，超时后结束进程
+    pub async fn shutdown(&self, grace: Duration) {
+        let _ = self.notify("shutdown", Value::Null).await;
+        let mut child = self.child.lock().await;
+        if tokio::time::timeout(grace, child.wait()).await.is_err() {
+            tracing::warn!("[Plugin {}] Did not exit after shutdown, killing", self.plugin_id);
+            let _ = child.kill().await;
+        }
+    }
+
+    /// 进程是否仍在运行
+    pub async fn is_running(&self) -> bool {
+        matches!(self.child.lock().await.try_wait(), Ok(None))
+    }
+
+    async fn write_line(&self, line: &str) -> Result<()> {
+        let mut stdin = self.stdin.lock().await;
+        stdin.write_all(line.as_bytes()).await?;
+        stdin.write_all(b"\n").await?;
+        stdin.flush().await?;
+        Ok(())
+    }
+}
+
+/// 编码 JSON-RPC 请求，`id` 为 None 时为通知
+pub fn encode_request(id: Option<u64>, method: &str, params: Value) -> String {
+    let mut message = serde_json::json!({
+        "jsonrpc": "2.0",
+        "method": method,
+        "params": params,
+    });
+    if let Some(id) = id {
+        message["id"] = id.into();
+    }
+    message.to_string()
+}
+
+/// 解析插件输出的一行，不是 JSON-RPC 消息时返回 None
+pub fn parse_message(line: &str) -> Option<Incoming> {
+    let message: Value = serde_json::from_str(line.trim()).ok()?;
+
+    if let Some(method) = message.get("method").and_then(|m| m.as_str()) {
+        return Some(Incoming::Notification {
+            method: method.to_string(),
+            params: message.get("params").cloned().unwrap_or(Value::Null),
+        });
+    }
+
+    let id = message.get("id")?.as_u64()?;
+    let result = match message.get("error") {
+        Some(error) => Err(error
+            .get("message")
+            .and_then(|m| m.as_str())
+            .map(str::to_string)
+            .unwrap_or_else(|| error.to_string())),
+        None => Ok(message.get("result").cloned().unwrap_or(Value::Null)),
+    };
+    Some(Incoming::Response { id, result })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_request() {
+        let request: Value = serde_json::from_str(&encode_request(Some(3), "status", serde_json::json!({ "path": "/" }))).unwrap();
+        assert_eq!(request["id"], 3);
+        assert_eq!(request["method"], "status");
+        assert_eq!(request["params"]["path"], "/");
+
+        let notification: Value = serde_json::from_str(&encode_request(None, "shutdown", Value::Null)).unwrap();
+        assert!(notification.get("id").is_none());
+    }
+
+    #[test]
+    fn test_parse_message() {
+        assert_eq!(
+            parse_message(r#"{"jsonrpc":"2.0","id":1,"result":{"ok":true}}"#),
+            Some(Incoming::Response { id: 1, result: Ok(serde_json::json!({ "ok": true })) })
+        );
+        assert_eq!(
+            parse_message(r#"{"jsonrpc":"2.0","id":2,"error":{"code":-32601,"message":"Method not found"}}"#),
+            Some(Incoming::Response { id: 2, result: Err("Method not found".to_string()) })
+        );
+        assert_eq!(
+            parse_message(r#"{"jsonrpc":"2.0","method":"host/emit","params":{"event":"done"}}"#),
+            Some(Incoming::Notification { method: "host/emit".to_string(), params: serde_json::json!({ "event": "done" }) })
+        );
+        assert_eq!(parse_message("plain debug output"), None);
+    }
+}
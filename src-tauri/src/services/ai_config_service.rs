@@ -0,0 +1,253 @@
+//! AI 配置导入导出
+//!
+//! 将 provider、快捷键和提示词模板打包为一个 JSON 文件，方便团队分发统一的 AI 配置。
+//! API Key 默认不导出；导出时提供口令则以口令加密后一并写入文件，导入时需要相同的口令。
+
+use anyhow::Result;
+use base64::{engine::general_purpose, Engine as _};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use zeroize::Zeroizing;
+
+use crate::config::storage::{AIConfig, AIPromptTemplates, AIProviderConfig, AISafetyRules, AIShortcuts};
+use crate::services::CryptoService;
+
+/// 导出文件格式标识
+const BUNDLE_FORMAT: &str = "ssh-terminal-ai-config";
+
+/// 导出文件格式版本
+const BUNDLE_VERSION: u32 = 1;
+
+/// AI 配置导出文件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AIConfigBundle {
+    pub format: String,
+    pub version: u32,
+    pub exported_at: i64,
+    /// 不含 API Key 的 provider 列表
+    pub providers: Vec<AIProviderConfig>,
+    pub default_provider: String,
+    pub shortcuts: AIShortcuts,
+    #[serde(default)]
+    pub prompt_templates: AIPromptTemplates,
+    #[serde(default)]
+    pub safety_rules: AISafetyRules,
+    /// 口令加密的 API Key（provider ID → Key 的 JSON，base64 编码）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encrypted_keys: Option<String>,
+}
+
+/// 导入结果
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AIConfigImportSummary {
+    /// 新增的 provider 数量
+    pub added: usize,
+    /// 覆盖的同 ID provider 数量
+    pub updated: usize,
+    /// 导入的 API Key 数量
+    pub keys_imported: usize,
+    /// 文件包含加密的 API Key 但未提供口令，Key 未导入
+    pub keys_locked: bool,
+}
+
+pub struct AIConfigService;
+
+impl AIConfigService {
+    /// 生成导出内容，`passphrase` 为 None 时不包含 API Key
+    pub fn export_bundle(config: &AIConfig, passphrase: Option<&str>) -> Result<AIConfigBundle> {
+        let keys: HashMap<&str, &str> = config
+            .providers
+            .iter()
+            .filter_map(|p| p.api_key.as_deref().filter(|k| !k.is_empty()).map(|k| (p.id.as_str(), k.as_str())))
+            .collect();
+
+        let encrypted_keys = match passphrase.filter(|p| !p.is_empty()) {
+            Some(passphrase) if !keys.is_empty() => {
+                let encrypted = CryptoService::encrypt_bytes(&serde_json::to_vec(&keys)?, passphrase)?;
+                Some(general_purpose::STANDARD.encode(encrypted))
+            }
+            _ => None,
+        };
+
+        let providers = config
+            .providers
+            .iter()
+            .map(|p| AIProviderConfig {
+                api_key: None,
+                api_key_encrypted: None,
+                nonce: None,
+                ..p.clone()
+            })
+            .collect();
+
+        Ok(AIConfigBundle {
+            format: BUNDLE_FORMAT.to_string(),
+            version: BUNDLE_VERSION,
+            exported_at: chrono::Utc::now().timestamp_millis(),
+            providers,
+            default_provider: config.default_provider.clone(),
+            shortcuts: config.shortcuts.clone(),
+            prompt_templates: config.prompt_templates.clone(),
+            safety_rules: config.safety_rules.clone(),
+            encrypted_keys,
+        })
+    }
+
+    /// 导出到文件
+    pub fn export_to_file(path: &Path, config: &AIConfig, passphrase: Option<&str>) -> Result<()> {
+        let bundle = Self::export_bundle(config, passphrase)?;
+        fs::write(path, serde_json::to_string_pretty(&bundle)?)?;
+        Ok(())
+    }
+
+    /// 读取并校验导出文件
+    pub fn read_bundle(path: &Path) -> Result<AIConfigBundle> {
+        let content = fs::read_to_string(path)?;
+        let bundle: AIConfigBundle = serde_json::from_str(&content)
+            .map_err(|e| anyhow::anyhow!("Not an AI config file: {}", e))?;
+        if bundle.format != BUNDLE_FORMAT {
+            return Err(anyhow::anyhow!("Not an AI config file: {}", path.display()));
+        }
+        if bundle.version > BUNDLE_VERSION {
+            return Err(anyhow::anyhow!(
+                "AI config file version {} is newer than supported version {}",
+                bundle.version,
+                BUNDLE_VERSION
+            ));
+        }
+        Ok(bundle)
+    }
+
+    /// 解密导出文件中的 API Key
+    fn unwrap_keys(bundle: &AIConfigBundle, passphrase: &str) -> Result<HashMap<String, String>> {
+        let Some(encrypted_keys) = &bundle.encrypted_keys else {
+            return Ok(HashMap::new());
+        };
+        let encrypted = general_purpose::STANDARD.decode(encrypted_keys)?;
+        let decrypted = CryptoService::decrypt_bytes(&encrypted, passphrase)
+            .map_err(|_| anyhow::anyhow!("Wrong passphrase or corrupted AI config file"))?;
+        Ok(serde_json::from_slice(&decrypted)?)
+    }
+
+    /// 将导出文件合并到当前配置
+    ///
+    /// provider 按 ID 合并：同 ID 的被覆盖（未导入 Key 时保留原有 Key），其余追加；
+    /// 快捷键和提示词模板直接使用文件中的设置
+    pub fn merge_bundle(
+        current: AIConfig,
+        bundle: AIConfigBundle,
+        passphrase: Option<&str>,
+    ) -> Result<(AIConfig, AIConfigImportSummary)> {
+        let mut summary = AIConfigImportSummary::default();
+        let keys = match passphrase.filter(|p| !p.is_empty()) {
+            Some(passphrase) => Self::unwrap_keys(&bundle, passphrase)?,
+            None => {
+                summary.keys_locked = bundle.encrypted_keys.is_some();
+                HashMap::new()
+            }
+        };
+
+        let mut providers = current.providers;
+        for mut provider in bundle.providers {
+            provider.api_key_encrypted = None;
+            provider.nonce = None;
+            provider.api_key = keys.get(&provider.id).cloned().map(Zeroizing::new);
+            if provider.api_key.is_some() {
+                summary.keys_imported += 1;
+            }
+
+            match providers.iter_mut().find(|p| p.id == provider.id) {
+                Some(existing) => {
+                    if provider.api_key.is_none() {
+                        provider.api_key = existing.api_key.take();
+                    }
+                    *existing = provider;
+                    summary.updated += 1;
+                }
+                None => {
+                    providers.push(provider);
+                    summary.added += 1;
+                }
+            }
+        }
+
+        let default_provider = if providers.iter().any(|p| p.id == bundle.default_provider) {
+            bundle.default_provider
+        } else {
+            current.default_provider
+        };
+
+        let config = AIConfig {
+            providers,
+            default_provider,
+            shortcuts: bundle.shortcuts,
+            prompt_templates: bundle.prompt_templates,
+            safety_rules: bundle.safety_rules,
+        };
+        Ok((config, summary))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn provider(id: &str, api_key: Option<&str>) -> AIProviderConfig {
+        AIProviderConfig {
+            id: id.to_string(),
+            provider_type: "openai".to_string(),
+            name: id.to_string(),
+            api_key: api_key.map(|key| Zeroizing::new(key.to_string())),
+            model: "gpt-4o-mini".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn config(providers: Vec<AIProviderConfig>) -> AIConfig {
+        AIConfig {
+            default_provider: providers[0].id.clone(),
+            providers,
+            shortcuts: AIShortcuts {
+                explain_command: "Ctrl+Shift+A".to_string(),
+                open_chat: "Ctrl+Shift+I".to_string(),
+                nl_to_command: "Ctrl+Shift+N".to_string(),
+            },
+            prompt_templates: AIPromptTemplates::default(),
+            safety_rules: AISafetyRules::default(),
+        }
+    }
+
+    #[test]
+    fn test_export_and_merge() {
+        let mut team = config(vec![provider("team", Some("sk-team")), provider("local", None)]);
+        team.prompt_templates.chat = Some("Answer briefly.".to_string());
+
+        // 不带口令：不包含 Key，合并时保留本地已有的 Key
+        let bundle = AIConfigService::export_bundle(&team, None).unwrap();
+        assert!(bundle.encrypted_keys.is_none());
+        assert!(bundle.providers.iter().all(|p| p.api_key.is_none()));
+
+        let mine = config(vec![provider("team", Some("sk-mine")), provider("other", None)]);
+        let (merged, summary) = AIConfigService::merge_bundle(mine.clone(), bundle, None).unwrap();
+        assert_eq!(summary, AIConfigImportSummary { added: 1, updated: 1, keys_imported: 0, keys_locked: false });
+        assert_eq!(merged.providers.len(), 3);
+        assert_eq!(merged.providers[0].api_key.as_deref().map(String::as_str), Some("sk-mine"));
+        assert_eq!(merged.prompt_templates.chat.as_deref(), Some("Answer briefly."));
+
+        // 带口令：Key 加密导出，口令错误时导入失败，未提供口令时跳过 Key
+        let bundle = AIConfigService::export_bundle(&team, Some("s3cret")).unwrap();
+        assert!(!bundle.encrypted_keys.as_deref().unwrap().contains("sk-team"));
+        assert!(AIConfigService::merge_bundle(mine.clone(), bundle.clone(), Some("wrong")).is_err());
+
+        let (_, summary) = AIConfigService::merge_bundle(mine.clone(), bundle.clone(), None).unwrap();
+        assert!(summary.keys_locked);
+
+        let (merged, summary) = AIConfigService::merge_bundle(mine, bundle, Some("s3cret")).unwrap();
+        assert_eq!(summary.keys_imported, 1);
+        assert_eq!(merged.providers[0].api_key.as_deref().map(String::as_str), Some("sk-team"));
+    }
+}
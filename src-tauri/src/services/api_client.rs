@@ -1,8 +1,10 @@
 use anyhow::Result;
 use anyhow::anyhow;
 use reqwest::{Client, header};
-use serde::{de::DeserializeOwned, Serialize};
-use std::sync::{Arc, Mutex};
+use secrecy::{ExposeSecret, SecretString};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+use std::time::Duration;
 
 use crate::models::user_auth::*;
 use crate::models::user_profile::*;
@@ -10,6 +12,16 @@ use crate::models::sync::{
     SyncRequest, ResolveConflictRequest,
     ServerSyncResponse, ServerResolveConflictResponse,
 };
+use crate::models::crash_report::{CrashReportUploadRequest, ServerCrashReportResult};
+use crate::models::ai_proxy::{ServerAiChatRequest, ServerAiChatResult, ServerAiQuota};
+use crate::models::recording_cloud::{ServerRecording, ServerRecordingUpload, ServerRecordingVisibilityRequest};
+use crate::models::connection_stats::{ServerUsageHeatmapRequest, ServerUsageHeatmapResult};
+use crate::models::workspace::{
+    ServerAddWorkspaceMemberRequest, ServerCreateSharedSessionRequest, ServerCreateWorkspaceRequest,
+    ServerSharedSession, ServerSharedSessionCredentials, ServerWorkspace, ServerWorkspaceAuditLog,
+    ServerWorkspaceMember,
+};
+use crate::ssh::advisory::AdvisoryList;
 use crate::types::response::ServerApiResponse;
 use crate::database::repositories::UserAuthRepository;
 use crate::services::CryptoService;
@@ -27,28 +39,145 @@ pub const NETWORK_ERROR: &str = "NETWORK_ERROR";
 /// 连接超时的错误码
 pub const TIMEOUT_ERROR: &str = "TIMEOUT_ERROR";
 
+/// 进程内共享的 reqwest 客户端
+///
+/// reqwest::Client 内部持有连接池，所有 ApiClient 共享同一个实例，
+/// 登录、切换账号时不再重新建立 TCP/TLS 连接
+static SHARED_HTTP_CLIENT: OnceLock<Client> = OnceLock::new();
+
+/// 获取（首次调用时创建）共享的 reqwest 客户端
+fn shared_http_client() -> Result<Client> {
+    if let Some(client) = SHARED_HTTP_CLIENT.get() {
+        return Ok(client.clone());
+    }
+
+    let client = Client::builder()
+        .connect_timeout(Duration::from_secs(10))
+        .pool_idle_timeout(Duration::from_secs(90))
+        .pool_max_idle_per_host(8)
+        .tcp_keepalive(Duration::from_secs(60))
+        .http2_adaptive_window(true)
+        .gzip(true)
+        .brotli(true)
+        .build()?;
+
+    Ok(SHARED_HTTP_CLIENT.get_or_init(|| client).clone())
+}
+
+/// 按接口类别划分的请求超时时间（秒），可在设置中调整
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiTimeouts {
+    /// 认证类接口（登录、注册、刷新 token、验证码）
+    #[serde(default = "default_auth_timeout_secs")]
+    pub auth_secs: u64,
+    /// 同步类接口，数据量较大；AI 代理请求和录制文件上传下载也使用该超时
+    #[serde(default = "default_sync_timeout_secs")]
+    pub sync_secs: u64,
+    /// 其他接口
+    #[serde(default = "default_default_timeout_secs")]
+    pub default_secs: u64,
+}
+
+fn default_auth_timeout_secs() -> u64 {
+    DEFAULT_API_TIMEOUTS.auth_secs
+}
+
+fn default_sync_timeout_secs() -> u64 {
+    DEFAULT_API_TIMEOUTS.sync_secs
+}
+
+fn default_default_timeout_secs() -> u64 {
+    DEFAULT_API_TIMEOUTS.default_secs
+}
+
+const DEFAULT_API_TIMEOUTS: ApiTimeouts = ApiTimeouts {
+    auth_secs: 15,
+    sync_secs: 60,
+    default_secs: 30,
+};
+
+/// 设置中配置的超时时间，新建的 ApiClient 使用该值
+static CONFIGURED_API_TIMEOUTS: RwLock<ApiTimeouts> = RwLock::new(DEFAULT_API_TIMEOUTS);
+
+impl Default for ApiTimeouts {
+    fn default() -> Self {
+        DEFAULT_API_TIMEOUTS
+    }
+}
+
+impl ApiTimeouts {
+    /// 获取设置中配置的超时时间
+    pub fn configured() -> Self {
+        *CONFIGURED_API_TIMEOUTS.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 更新设置中配置的超时时间（启动时从应用设置加载，修改设置时调用）
+    pub fn set_configured(timeouts: ApiTimeouts) {
+        *CONFIGURED_API_TIMEOUTS.write().unwrap_or_else(|e| e.into_inner()) = timeouts;
+    }
+
+    /// 根据请求路径选择超时时间
+    fn for_path(&self, path: &str) -> Duration {
+        let path = path.trim_start_matches('/');
+        let secs = if path.starts_with("auth/") || path.starts_with("api/email/") {
+            self.auth_secs
+        } else if path.starts_with("api/sync") || path.starts_with("api/ai/") || path.starts_with("api/recordings") {
+            self.sync_secs
+        } else {
+            self.default_secs
+        };
+        // 0 秒会让所有请求立即超时，至少保留 1 秒
+        Duration::from_secs(secs.max(1))
+    }
+}
+
+/// 将请求发送失败转换为带错误码的提示
+fn describe_send_error(err: reqwest::Error) -> anyhow::Error {
+    if err.is_timeout() {
+        return anyhow!("{}: 请求超时，请检查网络连接", TIMEOUT_ERROR);
+    }
+    if !err.is_connect() {
+        return anyhow!("API error: {}", err);
+    }
+    let err_str = err.to_string().to_lowercase();
+    if err_str.contains("connection refused") {
+        anyhow!("{}: 无法连接到服务器，请检查服务器是否运行", NETWORK_ERROR)
+    } else if err_str.contains("no route to host") {
+        anyhow!("{}: 无法访问服务器，请检查网络连接和服务器地址", NETWORK_ERROR)
+    } else if err_str.contains("dns") {
+        anyhow!("{}: DNS 解析失败，请检查服务器地址是否正确", NETWORK_ERROR)
+    } else if err_str.contains("connection reset") || err_str.contains("reset by peer") {
+        anyhow!("{}: 连接被重置，服务器可能已关闭", NETWORK_ERROR)
+    } else {
+        anyhow!("{}: 无法连接到服务器，请检查服务器是否运行或网络连接", NETWORK_ERROR)
+    }
+}
+
 /// HTTP API 客户端
 /// 用于与服务器进行通信
 #[derive(Clone)]
 pub struct ApiClient {
     client: Client,
     server_url: String,
-    access_token: Arc<Mutex<Option<String>>>,
-    refresh_token_encrypted: Arc<Mutex<Option<String>>>,
+    // 令牌只以 SecretString 保存，清除或替换时内存会被清零
+    access_token: Arc<Mutex<Option<SecretString>>>,
+    refresh_token_encrypted: Arc<Mutex<Option<SecretString>>>,
     device_id: Arc<Mutex<Option<String>>>,
     token_update_callback: TokenUpdateCallback,
     language: Arc<Mutex<Option<String>>>,
+    timeouts: ApiTimeouts,
 }
 
 impl ApiClient {
     /// 创建新的 API 客户端实例
+    ///
+    /// 底层 HTTP 连接池在所有实例之间共享，请求超时使用设置中配置的值
     pub fn new(server_url: String, language: Option<String>) -> Result<Self> {
         // 规范化服务器 URL（去除末尾斜杠）
         let server_url = server_url.trim_end_matches('/').to_string();
 
-        let client = Client::builder()
-            .timeout(std::time::Duration::from_secs(30))
-            .build()?;
+        let client = shared_http_client()?;
 
         Ok(Self {
             client,
@@ -58,20 +187,26 @@ impl ApiClient {
             device_id: Arc::new(Mutex::new(None)),
             token_update_callback: Arc::new(Mutex::new(None)),
             language: Arc::new(Mutex::new(language)),
+            timeouts: ApiTimeouts::configured(),
         })
     }
 
+    /// 设置各类接口的超时时间
+    pub fn set_timeouts(&mut self, timeouts: ApiTimeouts) {
+        self.timeouts = timeouts;
+    }
+
     /// 设置访问令牌
     pub fn set_token(&self, token: String) {
         let mut guard = self.access_token.lock()
             .expect("Failed to acquire token lock");
-        *guard = Some(token);
+        *guard = Some(SecretString::new(token));
     }
 
     /// 设置刷新令牌（明文存储，服务器返回的 refresh_token 本身已加密）
     pub fn set_refresh_token(&self, refresh_token: String) {
         let mut guard = self.refresh_token_encrypted.lock().unwrap();
-        *guard = Some(refresh_token);
+        *guard = Some(SecretString::new(refresh_token));
     }
 
     /// 设置设备 ID
@@ -188,7 +323,7 @@ impl ApiClient {
     }
 
     /// 获取当前令牌
-    fn get_token(&self) -> Option<String> {
+    fn get_token(&self) -> Option<SecretString> {
         let guard = self.access_token.lock()
             .expect("Failed to acquire token lock");
         guard.clone()
@@ -208,7 +343,8 @@ impl ApiClient {
 
         let mut request = self.client
             .get(&url)
-            .header(header::AUTHORIZATION, format!("Bearer {}", token));
+            .timeout(self.timeouts.for_path(path))
+            .header(header::AUTHORIZATION, format!("Bearer {}", token.expose_secret()));
 
         if let Some(lang) = language {
             request = request.header("Accept-Language", lang);
@@ -228,7 +364,8 @@ impl ApiClient {
 
                         let mut request = self.client
                             .get(&url)
-                            .header(header::AUTHORIZATION, format!("Bearer {}", new_token));
+                            .timeout(self.timeouts.for_path(path))
+                            .header(header::AUTHORIZATION, format!("Bearer {}", new_token.expose_secret()));
 
                         if let Some(lang) = language {
                             request = request.header("Accept-Language", lang);
@@ -274,7 +411,8 @@ impl ApiClient {
 
         let mut request = self.client
             .post(&url)
-            .header(header::AUTHORIZATION, format!("Bearer {}", token))
+            .timeout(self.timeouts.for_path(path))
+            .header(header::AUTHORIZATION, format!("Bearer {}", token.expose_secret()))
             .header(header::CONTENT_TYPE, "application/json");
 
         if let Some(lang) = language {
@@ -295,7 +433,8 @@ impl ApiClient {
 
                         let mut request = self.client
                             .post(&url)
-                            .header(header::AUTHORIZATION, format!("Bearer {}", new_token))
+                            .timeout(self.timeouts.for_path(path))
+                            .header(header::AUTHORIZATION, format!("Bearer {}", new_token.expose_secret()))
                             .header(header::CONTENT_TYPE, "application/json");
 
                         if let Some(lang) = language {
@@ -342,7 +481,8 @@ impl ApiClient {
 
         let mut request = self.client
             .put(&url)
-            .header(header::AUTHORIZATION, format!("Bearer {}", token))
+            .timeout(self.timeouts.for_path(path))
+            .header(header::AUTHORIZATION, format!("Bearer {}", token.expose_secret()))
             .header(header::CONTENT_TYPE, "application/json");
 
         if let Some(lang) = language {
@@ -363,7 +503,8 @@ impl ApiClient {
 
                         let mut request = self.client
                             .put(&url)
-                            .header(header::AUTHORIZATION, format!("Bearer {}", new_token))
+                            .timeout(self.timeouts.for_path(path))
+                            .header(header::AUTHORIZATION, format!("Bearer {}", new_token.expose_secret()))
                             .header(header::CONTENT_TYPE, "application/json");
 
                         if let Some(lang) = language {
@@ -410,7 +551,8 @@ impl ApiClient {
 
         let mut request = self.client
             .delete(&url)
-            .header(header::AUTHORIZATION, format!("Bearer {}", token));
+            .timeout(self.timeouts.for_path(path))
+            .header(header::AUTHORIZATION, format!("Bearer {}", token.expose_secret()));
 
         if let Some(lang) = language {
             request = request.header("Accept-Language", lang);
@@ -430,7 +572,8 @@ impl ApiClient {
 
                         let mut request = self.client
                             .delete(&url)
-                            .header(header::AUTHORIZATION, format!("Bearer {}", new_token));
+                            .timeout(self.timeouts.for_path(path))
+                            .header(header::AUTHORIZATION, format!("Bearer {}", new_token.expose_secret()));
 
                         if let Some(lang) = language {
                             request = request.header("Accept-Language", lang);
@@ -467,6 +610,46 @@ impl ApiClient {
         }
     }
 
+    /// 发送带认证的请求并返回原始响应（请求体或响应体不是 JSON 的接口）
+    ///
+    /// 服务器返回 401 时刷新 token 后重试一次
+    async fn send_auth_raw<F>(&self, path: &str, build: F) -> Result<reqwest::Response>
+    where
+        F: Fn(&Client, &str) -> reqwest::RequestBuilder,
+    {
+        let url = self.build_url(path);
+        let mut refreshed = false;
+        loop {
+            let token = self.get_token()
+                .ok_or_else(|| anyhow::anyhow!("No access token available"))?;
+
+            let mut request = build(&self.client, &url)
+                .timeout(self.timeouts.for_path(path))
+                .header(header::AUTHORIZATION, format!("Bearer {}", token.expose_secret()));
+            if let Some(lang) = self.get_language() {
+                request = request.header("Accept-Language", lang);
+            }
+
+            let response = request.send().await.map_err(describe_send_error)?;
+            if response.status().as_u16() != 401 || refreshed {
+                return Ok(response);
+            }
+
+            tracing::warn!("HTTP 401 error, attempting to refresh");
+            if let Err(e) = self.try_refresh_token().await {
+                tracing::error!("Token refresh failed: {}", e);
+                if Self::is_refresh_failure(&e) {
+                    self.clear_token();
+                    self.clear_refresh_token();
+                    return Err(e);
+                }
+                return Err(anyhow!("Authentication failed: Token refresh failed - {}", e));
+            }
+            tracing::info!("Retrying request with new token: {}", path);
+            refreshed = true;
+        }
+    }
+
     /// 发送 POST 请求（不带认证）
     async fn post_public<T: Serialize, R: DeserializeOwned>(&self, path: &str, body: &T) -> Result<(R, u16, String)> {
         let url = self.build_url(path);
@@ -474,6 +657,7 @@ impl ApiClient {
 
         let mut request = self.client
             .post(&url)
+            .timeout(self.timeouts.for_path(path))
             .header(header::CONTENT_TYPE, "application/json");
 
         if let Some(lang) = language {
@@ -608,13 +792,14 @@ impl ApiClient {
 
         let mut request = self.client
             .post(&url)
+            .timeout(self.timeouts.for_path("auth/refresh"))
             .header(header::CONTENT_TYPE, "application/json");
 
         if let Some(lang) = language {
             request = request.header("Accept-Language", lang);
         }
 
-        let response = request.json(&serde_json::json!({ "refresh_token": refresh_token })).send().await?;
+        let response = request.json(&serde_json::json!({ "refresh_token": refresh_token.expose_secret() })).send().await?;
 
         if response.status().is_success() {
             let text = response.text().await?;
@@ -625,7 +810,7 @@ impl ApiClient {
                 if let Some(result) = server_response.data() {
                     // 更新 access_token
                     if let Ok(mut guard) = self.access_token.lock() {
-                        *guard = Some(result.access_token.clone());
+                        *guard = Some(SecretString::new(result.access_token.clone()));
                     }
 
                     // 调用回调更新数据库
@@ -676,6 +861,18 @@ impl ApiClient {
         self.post_public("api/email/send-verify-code-sync", req).await
     }
 
+    /// 验证邮箱（返回服务器格式）
+    pub async fn verify_email(&self, req: &VerifyEmailRequest) -> Result<((), u16, String)> {
+        tracing::info!("API: verify_email for {}", req.email);
+        self.post_public("auth/verify-email", req).await
+    }
+
+    /// 为未验证的账号重新发送验证码（返回服务器格式）
+    pub async fn resend_verify_code(&self, req: &SendVerifyCodeRequest) -> Result<(EmailResult, u16, String)> {
+        tracing::info!("API: resend_verify_code for {}", req.email);
+        self.post_public("auth/resend-verify", req).await
+    }
+
     /// 刷新访问令牌（返回服务器格式）
     pub async fn refresh_token(&self, refresh_token: &str) -> Result<(ServerRefreshResult, u16, String)> {
         tracing::info!("API: refresh_token");
@@ -717,4 +914,200 @@ impl ApiClient {
         tracing::info!("API: resolve_conflict for {:?}", req);
         self.post_auth("api/sync/resolve-conflict", req).await
     }
+
+    // ==================== 崩溃报告 API ====================
+
+    /// 上传崩溃报告（无需登录）
+    pub async fn submit_crash_report(&self, req: &CrashReportUploadRequest) -> Result<(ServerCrashReportResult, u16, String)> {
+        tracing::info!("API: submit_crash_report {}", req.report_id);
+        self.post_public("crash", req).await
+    }
+
+    // ==================== AI 代理 API ====================
+
+    /// 通过服务器转发 AI 聊天请求
+    pub async fn ai_chat(&self, req: &ServerAiChatRequest) -> Result<(ServerAiChatResult, u16, String)> {
+        tracing::info!("API: ai_chat ({} messages)", req.messages.len());
+        self.post_auth("api/ai/chat", req).await
+    }
+
+    /// 获取 AI 代理配额
+    pub async fn ai_quota(&self) -> Result<(ServerAiQuota, u16, String)> {
+        tracing::info!("API: ai_quota");
+        self.get_auth("api/ai/quota").await
+    }
+
+    // ==================== 安全公告 API ====================
+
+    /// 获取 SSH 服务器安全公告列表
+    pub async fn ssh_advisories(&self) -> Result<(AdvisoryList, u16, String)> {
+        tracing::info!("API: ssh_advisories");
+        self.get_auth("api/ssh/advisories").await
+    }
+
+    // ==================== 使用统计 API ====================
+
+    /// 上报本设备的使用热力图，返回所有设备的汇总
+    pub async fn usage_heatmap(&self, req: &ServerUsageHeatmapRequest) -> Result<(ServerUsageHeatmapResult, u16, String)> {
+        tracing::info!("API: usage_heatmap ({} buckets)", req.buckets.len());
+        self.post_auth("api/stats/heatmap", req).await
+    }
+
+    // ==================== 录制文件 API ====================
+
+    /// 上传录制文件（元数据作为查询参数，请求体为文件内容）
+    pub async fn upload_recording(&self, upload: &ServerRecordingUpload, data: bytes::Bytes) -> Result<(ServerRecording, u16, String)> {
+        tracing::info!("API: upload_recording {} ({} bytes)", upload.file_name, data.len());
+        let response = self
+            .send_auth_raw("api/recordings", |client, url| {
+                client.post(url).query(upload).body(data.clone())
+            })
+            .await?;
+        self.handle_response(response).await
+    }
+
+    /// 列出云端录制文件（scope 为 mine 或 shared）
+    pub async fn list_recordings(&self, scope: &str) -> Result<(Vec<ServerRecording>, u16, String)> {
+        tracing::info!("API: list_recordings ({})", scope);
+        self.get_auth(&format!("api/recordings?scope={}", scope)).await
+    }
+
+    /// 下载录制文件内容
+    pub async fn download_recording(&self, id: &str) -> Result<Vec<u8>> {
+        tracing::info!("API: download_recording {}", id);
+        let path = format!("api/recordings/{}/content", id);
+        let response = self.send_auth_raw(&path, |client, url| client.get(url)).await?;
+        let status = response.status();
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            tracing::error!("API error ({}): {}", status, text);
+            return Err(anyhow!("API error ({}): {}", status, text));
+        }
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    /// 更新云端录制文件的可见性
+    pub async fn update_recording_visibility(&self, id: &str, req: &ServerRecordingVisibilityRequest) -> Result<(ServerRecording, u16, String)> {
+        tracing::info!("API: update_recording_visibility {} -> {}", id, req.visibility);
+        self.put_auth(&format!("api/recordings/{}/visibility", id), req).await
+    }
+
+    /// 删除云端录制文件
+    pub async fn delete_recording(&self, id: &str) -> Result<(String, u16, String)> {
+        tracing::info!("API: delete_recording {}", id);
+        self.delete_auth(&format!("api/recordings/{}", id)).await
+    }
+
+    // ==================== 团队工作区 API ====================
+
+    /// 创建工作区（创建者为 owner）
+    pub async fn create_workspace(&self, req: &ServerCreateWorkspaceRequest) -> Result<(ServerWorkspace, u16, String)> {
+        tracing::info!("API: create_workspace");
+        self.post_auth("api/workspaces", req).await
+    }
+
+    /// 列出当前用户加入的工作区
+    pub async fn list_workspaces(&self) -> Result<(Vec<ServerWorkspace>, u16, String)> {
+        tracing::info!("API: list_workspaces");
+        self.get_auth("api/workspaces").await
+    }
+
+    /// 列出工作区成员
+    pub async fn list_workspace_members(&self, workspace_id: &str) -> Result<(Vec<ServerWorkspaceMember>, u16, String)> {
+        tracing::info!("API: list_workspace_members {}", workspace_id);
+        self.get_auth(&format!("api/workspaces/{}/members", workspace_id)).await
+    }
+
+    /// 按邮箱添加成员
+    pub async fn add_workspace_member(&self, workspace_id: &str, req: &ServerAddWorkspaceMemberRequest) -> Result<(ServerWorkspaceMember, u16, String)> {
+        tracing::info!("API: add_workspace_member {}", workspace_id);
+        self.post_auth(&format!("api/workspaces/{}/members", workspace_id), req).await
+    }
+
+    /// 移除成员
+    pub async fn remove_workspace_member(&self, workspace_id: &str, user_id: &str) -> Result<(String, u16, String)> {
+        tracing::info!("API: remove_workspace_member {} {}", workspace_id, user_id);
+        self.delete_auth(&format!("api/workspaces/{}/members/{}", workspace_id, user_id)).await
+    }
+
+    /// 列出共享会话（不包含凭据）
+    pub async fn list_shared_sessions(&self, workspace_id: &str) -> Result<(Vec<ServerSharedSession>, u16, String)> {
+        tracing::info!("API: list_shared_sessions {}", workspace_id);
+        self.get_auth(&format!("api/workspaces/{}/sessions", workspace_id)).await
+    }
+
+    /// 共享会话到工作区
+    pub async fn create_shared_session(&self, workspace_id: &str, req: &ServerCreateSharedSessionRequest) -> Result<(ServerSharedSession, u16, String)> {
+        tracing::info!("API: create_shared_session {} ({})", workspace_id, req.name);
+        self.post_auth(&format!("api/workspaces/{}/sessions", workspace_id), req).await
+    }
+
+    /// 删除共享会话
+    pub async fn delete_shared_session(&self, workspace_id: &str, session_id: &str) -> Result<(String, u16, String)> {
+        tracing::info!("API: delete_shared_session {} {}", workspace_id, session_id);
+        self.delete_auth(&format!("api/workspaces/{}/sessions/{}", workspace_id, session_id)).await
+    }
+
+    /// 导出共享会话的凭据（owner 和 admin）
+    pub async fn get_shared_session_credentials(&self, workspace_id: &str, session_id: &str) -> Result<(ServerSharedSessionCredentials, u16, String)> {
+        tracing::info!("API: get_shared_session_credentials {} {}", workspace_id, session_id);
+        self.get_auth(&format!("api/workspaces/{}/sessions/{}/credentials", workspace_id, session_id)).await
+    }
+
+    /// 连接共享会话（服务器记录审计日志后返回凭据）
+    pub async fn connect_shared_session(&self, workspace_id: &str, session_id: &str) -> Result<(ServerSharedSessionCredentials, u16, String)> {
+        tracing::info!("API: connect_shared_session {} {}", workspace_id, session_id);
+        self.post_auth(&format!("api/workspaces/{}/sessions/{}/connect", workspace_id, session_id), &()).await
+    }
+
+    /// 列出工作区审计日志
+    pub async fn list_workspace_audit_logs(&self, workspace_id: &str) -> Result<(Vec<ServerWorkspaceAuditLog>, u16, String)> {
+        tracing::info!("API: list_workspace_audit_logs {}", workspace_id);
+        self.get_auth(&format!("api/workspaces/{}/audit-logs", workspace_id)).await
+    }
+
+    // ==================== 设备 WebSocket ====================
+
+    /// 构建 WebSocket URL（http -> ws，https -> wss）
+    pub fn ws_url(&self, path: &str) -> String {
+        let url = self.build_url(path);
+        if let Some(rest) = url.strip_prefix("https://") {
+            format!("wss://{}", rest)
+        } else if let Some(rest) = url.strip_prefix("http://") {
+            format!("ws://{}", rest)
+        } else {
+            url
+        }
+    }
+
+    /// 当前的访问令牌（用于 WebSocket 握手）
+    pub fn access_token(&self) -> Option<SecretString> {
+        self.get_token()
+    }
+
+    /// 刷新访问令牌（WebSocket 握手返回 401 时调用）
+    pub async fn refresh_access_token(&self) -> Result<()> {
+        self.try_refresh_token().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timeouts_for_path() {
+        let timeouts: ApiTimeouts = serde_json::from_str(r#"{"authSecs":5,"syncSecs":120,"defaultSecs":0}"#).unwrap();
+
+        assert_eq!(timeouts.for_path("/auth/login"), Duration::from_secs(5));
+        assert_eq!(timeouts.for_path("api/email/send-verify-code"), Duration::from_secs(5));
+        assert_eq!(timeouts.for_path("api/sync"), Duration::from_secs(120));
+        // 0 秒至少保留 1 秒
+        assert_eq!(timeouts.for_path("api/user/profile"), Duration::from_secs(1));
+
+        // 缺少的字段使用默认值
+        let partial: ApiTimeouts = serde_json::from_str(r#"{"syncSecs":90}"#).unwrap();
+        assert_eq!(partial.auth_secs, ApiTimeouts::default().auth_secs);
+        assert_eq!(partial.default_secs, ApiTimeouts::default().default_secs);
+    }
 }
@@ -9,6 +9,7 @@ use crate::services::CryptoService;
 use crate::services::api_client::ApiClient;
 use crate::commands::auth::ApiClientStateWrapper;
 use crate::services::api_client::TOKEN_REFRESH_FAILED;
+use zeroize::Zeroizing;
 
 /// 认证服务
 pub struct AuthService {
@@ -26,14 +27,15 @@ async fn update_tokens_in_db(
     refresh_token: String,
     expires_at: Option<i64>,
 ) -> Result<()> {
-    // 加密 access_token（本地安全存储）
+    // 加密 access_token（本地安全存储），明文用完即清零
+    let access_token = Zeroizing::new(access_token);
     let token_encrypted = CryptoService::encrypt_token(&access_token, &device_id)?;
     // refresh_token 不加密（服务器返回的 refresh_token 本身已加密）
-    let refresh_plain = refresh_token;
+    let refresh_plain = Zeroizing::new(refresh_token);
 
     let repo = UserAuthRepository::new(pool);
     let expires = expires_at.unwrap_or_else(|| chrono::Utc::now().timestamp() + 24 * 60 * 60);
-    repo.update_token(&user_id, &token_encrypted, Some(&refresh_plain), expires)?;
+    repo.update_token(&user_id, &token_encrypted, Some(refresh_plain.as_str()), expires)?;
 
     Ok(())
 }
@@ -166,6 +168,7 @@ impl AuthService {
             device_id,
             server_url,
             expires_at,
+            email_verified: server_result.email_verified,
         };
 
         Ok((auth_response, code, message))
@@ -262,6 +265,7 @@ impl AuthService {
             device_id,
             server_url,
             expires_at,
+            email_verified: server_result.email_verified,
         };
 
         Ok((auth_response, code, message))
@@ -290,6 +294,32 @@ impl AuthService {
         Ok((result, code, message))
     }
 
+    /// 注册后验证邮箱（宽限期过后未验证的账号需要先验证才能登录）
+    pub async fn verify_email(&self, email: String, verify_code: String) -> Result<((), u16, String)> {
+        tracing::info!("Verify email request for: {}", email);
+
+        let settings_repo = AppSettingsRepository::new(self.pool.clone());
+        let server_url = settings_repo.get_server_url()?;
+        let language = settings_repo.get_language().ok();
+        let api_client = ApiClient::new(server_url, language)?;
+
+        let api_req = crate::models::user_auth::VerifyEmailRequest { email, verify_code };
+        api_client.verify_email(&api_req).await
+    }
+
+    /// 为已注册但未验证邮箱的账号重新发送验证码
+    pub async fn resend_verify_code(&self, email: String) -> Result<(EmailResult, u16, String)> {
+        tracing::info!("Resend verify code request for: {}", email);
+
+        let settings_repo = AppSettingsRepository::new(self.pool.clone());
+        let server_url = settings_repo.get_server_url()?;
+        let language = settings_repo.get_language().ok();
+        let api_client = ApiClient::new(server_url, language)?;
+
+        let api_req = crate::models::user_auth::SendVerifyCodeRequest { email };
+        api_client.resend_verify_code(&api_req).await
+    }
+
     /// 自动登录（启动时调用，不再调用 /auth/login，直接使用本地 token）
     pub async fn auto_login(&self) -> Result<AuthResponse> {
         tracing::info!("Attempting auto login from local storage");
@@ -358,6 +388,7 @@ impl AuthService {
                     device_id: auth.device_id.clone(),
                     server_url,
                     expires_at: auth.token_expires_at.unwrap_or(0),
+                    email_verified: None,
                 })
             }
             Err(e) if e.to_string().contains(TOKEN_REFRESH_FAILED) => {
@@ -388,6 +419,7 @@ impl AuthService {
                     device_id: auth.device_id.clone(),
                     server_url,
                     expires_at: auth.token_expires_at.unwrap_or(0),
+                    email_verified: None,
                 })
             }
         }
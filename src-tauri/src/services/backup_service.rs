@@ -0,0 +1,324 @@
+use anyhow::Result;
+use base64::{engine::general_purpose, Engine as _};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::config::Storage;
+use crate::database::connection::{get_db_path, pending_restore_path};
+use crate::database::DbPool;
+use crate::services::CryptoService;
+
+/// 备份文件魔数，用于识别备份文件
+const BACKUP_MAGIC: &[u8] = b"STBAK1\n";
+
+/// 备份文件扩展名
+const BACKUP_EXTENSION: &str = "stbak";
+
+/// 备份配置文件名（位于应用存储目录）
+const BACKUP_CONFIG_FILE: &str = "backup_config.json";
+
+/// 后台任务检查间隔
+const SCHEDULER_TICK: Duration = Duration::from_secs(10 * 60);
+
+/// 备份配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupConfig {
+    pub enabled: bool,
+    /// 备份目录（用户选择）
+    pub directory: Option<String>,
+    /// 备份间隔（小时）
+    pub interval_hours: u32,
+    /// 保留的备份数量
+    pub retention: u32,
+    /// 备份密码（使用本地密钥加密存储）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password_encrypted: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password_nonce: Option<String>,
+    /// 上次备份时间
+    pub last_backup_at: Option<i64>,
+}
+
+impl Default for BackupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            directory: None,
+            interval_hours: 24,
+            retention: 7,
+            password_encrypted: None,
+            password_nonce: None,
+            last_backup_at: None,
+        }
+    }
+}
+
+/// 备份文件信息
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupInfo {
+    pub path: String,
+    pub file_name: String,
+    pub size: u64,
+    pub created_at: i64,
+}
+
+/// 备份内容（加密前的 JSON）
+#[derive(Serialize, Deserialize)]
+struct BackupPayload {
+    version: u32,
+    created_at: i64,
+    app_version: String,
+    files: Vec<BackupFile>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BackupFile {
+    /// 文件名（数据库为 ssh_terminal.db，其余为存储目录下的配置文件）
+    name: String,
+    is_database: bool,
+    /// Base64 编码的文件内容
+    data: String,
+}
+
+/// 备份服务
+pub struct BackupService;
+
+impl BackupService {
+    /// 加载备份配置
+    pub fn load_config() -> Result<BackupConfig> {
+        let path = Storage::get_app_storage_dir()?.join(BACKUP_CONFIG_FILE);
+        if !path.exists() {
+            return Ok(BackupConfig::default());
+        }
+
+        let content = fs::read_to_string(&path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// 保存备份配置（原子写入）
+    pub fn save_config(config: &BackupConfig) -> Result<()> {
+        let dir = Storage::get_app_storage_dir()?;
+        fs::create_dir_all(&dir)?;
+
+        let path = dir.join(BACKUP_CONFIG_FILE);
+        let temp_path = path.with_extension("tmp");
+        fs::write(&temp_path, serde_json::to_string_pretty(config)?)?;
+        fs::rename(&temp_path, &path)?;
+
+        Ok(())
+    }
+
+    /// 设置备份密码
+    pub fn set_password(config: &mut BackupConfig, password: &str) -> Result<()> {
+        let storage = Storage::new(None)?;
+        let (encrypted, nonce) = storage.encrypt_api_key(password)?;
+        config.password_encrypted = Some(encrypted);
+        config.password_nonce = Some(nonce);
+        Ok(())
+    }
+
+    /// 读取备份密码
+    fn password(config: &BackupConfig) -> Result<String> {
+        match (&config.password_encrypted, &config.password_nonce) {
+            (Some(encrypted), Some(nonce)) => Ok(Storage::new(None)?.decrypt_api_key(encrypted, nonce)?),
+            _ => Err(anyhow::anyhow!("Backup password not configured")),
+        }
+    }
+
+    /// 立即创建一个备份并按保留数量清理旧备份
+    pub fn create_backup(pool: &DbPool, config: &BackupConfig) -> Result<BackupInfo> {
+        let directory = config.directory.as_deref()
+            .ok_or_else(|| anyhow::anyhow!("Backup directory not configured"))?;
+        let password = Self::password(config)?;
+        let directory = PathBuf::from(directory);
+        fs::create_dir_all(&directory)?;
+
+        let now = chrono::Utc::now();
+        let mut files = Vec::new();
+
+        // 使用 VACUUM INTO 生成一致的数据库快照（WAL 模式下直接复制文件可能不完整）
+        let snapshot_path = directory.join(format!(".snapshot-{}.db", uuid::Uuid::new_v4()));
+        {
+            let conn = pool.get()
+                .map_err(|e| anyhow::anyhow!("Failed to get database connection: {}", e))?;
+            conn.execute("VACUUM INTO ?1", [snapshot_path.to_string_lossy().as_ref()])?;
+        }
+        let db_data = fs::read(&snapshot_path);
+        let _ = fs::remove_file(&snapshot_path);
+        files.push(BackupFile {
+            name: "ssh_terminal.db".to_string(),
+            is_database: true,
+            data: general_purpose::STANDARD.encode(db_data?),
+        });
+
+        // 存储目录下的配置文件（不含子目录，如录制文件和附件）
+        let storage_dir = Storage::get_app_storage_dir()?;
+        if let Ok(entries) = fs::read_dir(&storage_dir) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if !path.is_file() || path.extension().and_then(|e| e.to_str()) == Some("tmp") {
+                    continue;
+                }
+                let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+                    continue;
+                };
+                files.push(BackupFile {
+                    name: name.to_string(),
+                    is_database: false,
+                    data: general_purpose::STANDARD.encode(fs::read(&path)?),
+                });
+            }
+        }
+
+        let payload = BackupPayload {
+            version: 1,
+            created_at: now.timestamp(),
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            files,
+        };
+        let encrypted = CryptoService::encrypt_bytes(&serde_json::to_vec(&payload)?, &password)?;
+
+        let file_name = format!("ssh-terminal-backup-{}.{}", now.format("%Y%m%d-%H%M%S"), BACKUP_EXTENSION);
+        let path = directory.join(&file_name);
+        let temp_path = path.with_extension("tmp");
+        fs::write(&temp_path, [BACKUP_MAGIC, &encrypted].concat())?;
+        fs::rename(&temp_path, &path)?;
+
+        tracing::info!("Backup created: {}", path.display());
+
+        Self::prune(&directory, config.retention as usize)?;
+
+        Ok(BackupInfo {
+            path: path.to_string_lossy().to_string(),
+            file_name,
+            size: fs::metadata(&path)?.len(),
+            created_at: now.timestamp(),
+        })
+    }
+
+    /// 列出备份目录中的备份（最新的在前）
+    pub fn list_backups(directory: &Path) -> Result<Vec<BackupInfo>> {
+        let mut backups = Vec::new();
+
+        if !directory.exists() {
+            return Ok(backups);
+        }
+
+        for entry in fs::read_dir(directory)?.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some(BACKUP_EXTENSION) {
+                continue;
+            }
+            let metadata = entry.metadata()?;
+            let created_at = metadata
+                .modified()
+                .ok()
+                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .map(|d| d.as_secs() as i64)
+                .unwrap_or(0);
+
+            backups.push(BackupInfo {
+                path: path.to_string_lossy().to_string(),
+                file_name: entry.file_name().to_string_lossy().to_string(),
+                size: metadata.len(),
+                created_at,
+            });
+        }
+
+        // 文件名包含时间戳，按文件名倒序即按时间倒序
+        backups.sort_by(|a, b| b.file_name.cmp(&a.file_name));
+        Ok(backups)
+    }
+
+    /// 删除超出保留数量的旧备份
+    fn prune(directory: &Path, retention: usize) -> Result<()> {
+        let retention = retention.max(1);
+        for backup in Self::list_backups(directory)?.into_iter().skip(retention) {
+            tracing::info!("Removing old backup: {}", backup.path);
+            fs::remove_file(&backup.path)?;
+        }
+        Ok(())
+    }
+
+    /// 从备份恢复
+    ///
+    /// 配置文件立即写回存储目录；数据库写入待恢复文件，重启应用后生效
+    pub fn restore_backup(path: &Path, password: Option<&str>) -> Result<()> {
+        let content = fs::read(path)?;
+        let encrypted = content
+            .strip_prefix(BACKUP_MAGIC)
+            .ok_or_else(|| anyhow::anyhow!("Not a backup file: {}", path.display()))?;
+
+        let password = match password {
+            Some(password) => password.to_string(),
+            None => Self::password(&Self::load_config()?)?,
+        };
+        let decrypted = CryptoService::decrypt_bytes(encrypted, &password)
+            .map_err(|_| anyhow::anyhow!("Wrong backup password or corrupted backup"))?;
+        let payload: BackupPayload = serde_json::from_slice(&decrypted)?;
+
+        let storage_dir = Storage::get_app_storage_dir()?;
+        fs::create_dir_all(&storage_dir)?;
+
+        for file in payload.files {
+            let data = general_purpose::STANDARD.decode(&file.data)?;
+
+            let target = if file.is_database {
+                pending_restore_path(&get_db_path()?)
+            } else {
+                // 防止备份中的文件名跳出存储目录
+                let name = Path::new(&file.name)
+                    .file_name()
+                    .ok_or_else(|| anyhow::anyhow!("Invalid file name in backup: {}", file.name))?;
+                storage_dir.join(name)
+            };
+
+            let temp_path = target.with_extension("tmp");
+            fs::write(&temp_path, data)?;
+            fs::rename(&temp_path, &target)?;
+        }
+
+        tracing::info!("Backup restored from {} (created at {})", path.display(), payload.created_at);
+        Ok(())
+    }
+
+    /// 启动定时备份任务
+    ///
+    /// 每隔一段时间检查配置，距离上次备份超过设定间隔时创建新备份
+    pub fn start_scheduler(pool: DbPool) {
+        tauri::async_runtime::spawn(async move {
+            loop {
+                tokio::time::sleep(SCHEDULER_TICK).await;
+
+                let pool = pool.clone();
+                let result = tauri::async_runtime::spawn_blocking(move || -> Result<()> {
+                    let mut config = Self::load_config()?;
+                    if !config.enabled || config.directory.is_none() {
+                        return Ok(());
+                    }
+
+                    let now = chrono::Utc::now().timestamp();
+                    let interval = config.interval_hours.max(1) as i64 * 3600;
+                    if config.last_backup_at.is_some_and(|last| now - last < interval) {
+                        return Ok(());
+                    }
+
+                    Self::create_backup(&pool, &config)?;
+                    config.last_backup_at = Some(now);
+                    Self::save_config(&config)
+                })
+                .await;
+
+                match result {
+                    Ok(Err(e)) => tracing::warn!("Scheduled backup failed: {}", e),
+                    Err(e) => tracing::warn!("Scheduled backup task panicked: {}", e),
+                    Ok(Ok(())) => {}
+                }
+            }
+        });
+    }
+}
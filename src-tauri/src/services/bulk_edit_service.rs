@@ -0,0 +1,183 @@
+//! 会话批量编辑
+//!
+//! 把同一个字段修改（用户名、端口、心跳间隔、分组、严格主机密钥检查、共享身份）应用到多个会话。
+//! 先按当前配置计算差异（dry-run 时只返回差异），实际执行时同时修改内存中的会话配置、
+//! 会话存储文件和数据库，任一步失败时回滚已写入的部分。
+
+use crate::ssh::session::{AuthMethod, SessionConfig, SessionConfigUpdate};
+use serde::{Deserialize, Serialize};
+
+/// 批量修改的字段
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "field", rename_all = "camelCase")]
+pub enum BulkSessionChange {
+    Username { value: String },
+    Port { value: u16 },
+    KeepAliveInterval { value: u64 },
+    Group { value: String },
+    StrictHostKeyChecking { value: bool },
+    /// 引用共享身份（用户名和认证方式随之覆盖），None 表示解除引用
+    #[serde(rename_all = "camelCase")]
+    Identity { identity_id: Option<String> },
+}
+
+/// 批量修改引用的身份（已解密）
+#[derive(Debug, Clone)]
+pub struct ResolvedIdentity {
+    pub id: String,
+    pub username: String,
+    pub auth_method: AuthMethod,
+}
+
+/// 单个会话的字段差异
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionFieldDiff {
+    pub session_id: String,
+    pub session_name: String,
+    pub field: String,
+    pub before: String,
+    pub after: String,
+}
+
+/// 批量编辑结果
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkEditReport {
+    pub dry_run: bool,
+    /// 会发生变化的会话
+    pub changes: Vec<SessionFieldDiff>,
+    /// 值已相同、无需修改的会话
+    pub unchanged: Vec<String>,
+    /// 找不到的会话
+    pub missing: Vec<String>,
+}
+
+impl BulkSessionChange {
+    /// 字段名（与 SessionConfig 的前端字段名一致）
+    pub fn field(&self) -> &'static str {
+        match self {
+            Self::Username { .. } => "username",
+            Self::Port { .. } => "port",
+            Self::KeepAliveInterval { .. } => "keepAliveInterval",
+            Self::Group { .. } => "group",
+            Self::StrictHostKeyChecking { .. } => "strictHostKeyChecking",
+            Self::Identity { .. } => "identity",
+        }
+    }
+
+    /// 修改后的值（身份为身份 ID，解除引用时为空）
+    fn after(&self) -> String {
+        match self {
+            Self::Username { value } | Self::Group { value } => value.clone(),
+            Self::Port { value } => value.to_string(),
+            Self::KeepAliveInterval { value } => value.to_string(),
+            Self::StrictHostKeyChecking { value } => value.to_string(),
+            Self::Identity { identity_id } => identity_id.clone().unwrap_or_default(),
+        }
+    }
+
+    /// 会话当前的值
+    fn before(&self, config: &SessionConfig, identity_id: Option<&str>) -> String {
+        match self {
+            Self::Username { .. } => config.username.clone(),
+            Self::Port { .. } => config.port.to_string(),
+            Self::KeepAliveInterval { .. } => config.keep_alive_interval.to_string(),
+            Self::Group { .. } => config.group.clone(),
+            Self::StrictHostKeyChecking { .. } => config.strict_host_key_checking.to_string(),
+            Self::Identity { .. } => identity_id.unwrap_or_default().to_string(),
+        }
+    }
+
+    /// 计算会话的差异，值相同时返回 None
+    pub fn diff(&self, session_id: &str, config: &SessionConfig, identity_id: Option<&str>) -> Option<SessionFieldDiff> {
+        let before = self.before(config, identity_id);
+        let after = self.after();
+        (before != after).then(|| SessionFieldDiff {
+            session_id: session_id.to_string(),
+            session_name: config.name.clone(),
+            field: self.field().to_string(),
+            before,
+            after,
+        })
+    }
+
+    /// 转换为内存会话配置的部分更新
+    pub fn to_update(&self, identity: Option<&ResolvedIdentity>) -> SessionConfigUpdate {
+        let mut update = SessionConfigUpdate::default();
+        match self {
+            Self::Username { value } => update.username = Some(value.clone()),
+            Self::Port { value } => update.port = Some(*value),
+            Self::KeepAliveInterval { value } => update.keep_alive_interval = Some(*value),
+            Self::Group { value } => update.group = Some(value.clone()),
+            Self::StrictHostKeyChecking { value } => update.strict_host_key_checking = Some(*value),
+            // 解除引用时会话保留当前凭据
+            Self::Identity { .. } => {
+                if let Some(identity) = identity {
+                    update.username = Some(identity.username.clone());
+                    update.auth_method = Some(identity.auth_method.clone());
+                }
+            }
+        }
+        update
+    }
+
+    /// 应用到会话配置（用于会话存储文件）
+    pub fn apply(&self, config: &mut SessionConfig, identity: Option<&ResolvedIdentity>) {
+        let update = self.to_update(identity);
+        if let Some(username) = update.username {
+            config.username = username;
+        }
+        if let Some(port) = update.port {
+            config.port = port;
+        }
+        if let Some(keep_alive_interval) = update.keep_alive_interval {
+            config.keep_alive_interval = keep_alive_interval;
+        }
+        if let Some(group) = update.group {
+            config.group = group;
+        }
+        if let Some(strict_host_key_checking) = update.strict_host_key_checking {
+            config.strict_host_key_checking = strict_host_key_checking;
+        }
+        if let Some(auth_method) = update.auth_method {
+            config.auth_method = auth_method;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_and_apply() {
+        let mut config: SessionConfig = serde_json::from_value(serde_json::json!({
+            "name": "web-1",
+            "host": "10.0.0.1",
+            "port": 22,
+            "username": "root",
+            "authMethod": { "Password": { "password": "secret" } },
+        }))
+        .unwrap();
+
+        let change: BulkSessionChange =
+            serde_json::from_value(serde_json::json!({ "field": "keepAliveInterval", "value": 60 })).unwrap();
+        let diff = change.diff("s1", &config, None).unwrap();
+        assert_eq!((diff.before.as_str(), diff.after.as_str()), ("30", "60"));
+        change.apply(&mut config, None);
+        assert_eq!(config.keep_alive_interval, 60);
+        assert!(change.diff("s1", &config, None).is_none());
+
+        let identity = ResolvedIdentity {
+            id: "id-1".to_string(),
+            username: "deploy".to_string(),
+            auth_method: AuthMethod::Password { password: "rotated".to_string() },
+        };
+        let change = BulkSessionChange::Identity { identity_id: Some(identity.id.clone()) };
+        assert_eq!(change.diff("s1", &config, None).unwrap().after, "id-1");
+        assert!(change.diff("s1", &config, Some("id-1")).is_none());
+        change.apply(&mut config, Some(&identity));
+        assert_eq!(config.username, "deploy");
+    }
+}
@@ -4,6 +4,7 @@ use aes_gcm::{
 };
 use anyhow::Result;
 use base64::{engine::general_purpose, Engine as _};
+use zeroize::Zeroizing;
 
 use crate::models::ssh_session::AuthMethod;
 
@@ -12,13 +13,13 @@ pub struct CryptoService;
 
 impl CryptoService {
     /// 从用户密码派生密钥（用于 SSH 认证信息加密）
-    /// 使用 PBKDF2 + SHA256，迭代 100,000 次
-    pub fn derive_key_from_password(password: &str, salt: &[u8]) -> Result<[u8; 32]> {
+    /// 使用 PBKDF2 + SHA256，迭代 100,000 次；密钥离开作用域时清零
+    pub fn derive_key_from_password(password: &str, salt: &[u8]) -> Result<Zeroizing<[u8; 32]>> {
         use pbkdf2::pbkdf2_hmac;
         use sha2::Sha256;
 
-        let mut key = [0u8; 32];
-        pbkdf2_hmac::<Sha256>(password.as_bytes(), salt, 100_000, &mut key);
+        let mut key = Zeroizing::new([0u8; 32]);
+        pbkdf2_hmac::<Sha256>(password.as_bytes(), salt, 100_000, &mut *key);
 
         Ok(key)
     }
@@ -43,10 +44,10 @@ impl CryptoService {
 
         // 2. 从用户密码派生密钥
         let key = Self::derive_key_from_password(user_password, &salt)?;
-        let cipher = Aes256Gcm::new(&key.into());
+        let cipher = Aes256Gcm::new(&(*key).into());
 
         // 3. 序列化认证信息
-        let auth_json = serde_json::to_string(auth_method)?;
+        let auth_json = Zeroizing::new(serde_json::to_string(auth_method)?);
 
         // 4. 加密
         let ciphertext = cipher
@@ -75,13 +76,13 @@ impl CryptoService {
 
         // 2. 从用户密码派生密钥
         let key = Self::derive_key_from_password(user_password, &salt)?;
-        let cipher = Aes256Gcm::new(&key.into());
+        let cipher = Aes256Gcm::new(&(*key).into());
 
         // 3. 解密
         let nonce = Nonce::from_slice(&nonce_bytes);
-        let plaintext = cipher
+        let plaintext = Zeroizing::new(cipher
             .decrypt(nonce, &*ciphertext)
-            .map_err(|e| anyhow::anyhow!("Decryption failed: {}", e))?;
+            .map_err(|e| anyhow::anyhow!("Decryption failed: {}", e))?);
 
         // 4. 反序列化
         let auth_method: AuthMethod = serde_json::from_slice(&plaintext)?;
@@ -90,7 +91,7 @@ impl CryptoService {
     }
 
     /// 从 device_id 派生密钥（用于 Token 加密）
-    pub fn derive_key_from_device_id(device_id: &str) -> Result<[u8; 32]> {
+    pub fn derive_key_from_device_id(device_id: &str) -> Result<Zeroizing<[u8; 32]>> {
         // 使用固定的盐值（因为 device_id 已是唯一标识）
         let salt = b"ssh-terminal-device-id-salt-v1";
         Self::derive_key_from_password(device_id, salt)
@@ -99,7 +100,7 @@ impl CryptoService {
     /// 加密 Token（使用 device_id 派生的密钥）
     pub fn encrypt_token(token: &str, device_id: &str) -> Result<String> {
         let key = Self::derive_key_from_device_id(device_id)?;
-        let cipher = Aes256Gcm::new(&key.into());
+        let cipher = Aes256Gcm::new(&(*key).into());
         let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
 
         let ciphertext = cipher
@@ -113,7 +114,7 @@ impl CryptoService {
     /// 解密 Token
     pub fn decrypt_token(encrypted: &str, device_id: &str) -> Result<String> {
         let key = Self::derive_key_from_device_id(device_id)?;
-        let cipher = Aes256Gcm::new(&key.into());
+        let cipher = Aes256Gcm::new(&(*key).into());
 
         let combined = general_purpose::STANDARD.decode(encrypted)?;
 
@@ -131,11 +132,42 @@ impl CryptoService {
         String::from_utf8(plaintext).map_err(|e| anyhow::anyhow!("Invalid UTF-8: {}", e))
     }
 
+    /// 使用已派生的密钥加密（批量加密时避免重复派生密钥）
+    /// 返回 Base64(nonce || ciphertext)
+    pub fn encrypt_with_key(data: &str, key: &[u8; 32]) -> Result<String> {
+        let cipher = Aes256Gcm::new(key.into());
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+
+        let ciphertext = cipher
+            .encrypt(&nonce, data.as_bytes())
+            .map_err(|e| anyhow::anyhow!("Encryption failed: {}", e))?;
+
+        let combined = [nonce.as_slice(), &ciphertext].concat();
+        Ok(general_purpose::STANDARD.encode(combined))
+    }
+
+    /// 使用已派生的密钥解密 [`encrypt_with_key`](Self::encrypt_with_key) 的结果
+    pub fn decrypt_with_key(encrypted: &str, key: &[u8; 32]) -> Result<String> {
+        let cipher = Aes256Gcm::new(key.into());
+        let combined = general_purpose::STANDARD.decode(encrypted)?;
+
+        if combined.len() < 12 {
+            return Err(anyhow::anyhow!("Invalid encrypted data length"));
+        }
+
+        let (nonce_bytes, ciphertext) = combined.split_at(12);
+        let plaintext = cipher
+            .decrypt(Nonce::from_slice(nonce_bytes), ciphertext)
+            .map_err(|e| anyhow::anyhow!("Decryption failed: {}", e))?;
+
+        String::from_utf8(plaintext).map_err(|e| anyhow::anyhow!("Invalid UTF-8: {}", e))
+    }
+
     /// 加密字符串（通用方法）
     pub fn encrypt_string(data: &str, password: &str) -> Result<String> {
         let salt = Self::generate_salt()?;
         let key = Self::derive_key_from_password(password, &salt)?;
-        let cipher = Aes256Gcm::new(&key.into());
+        let cipher = Aes256Gcm::new(&(*key).into());
         let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
 
         let ciphertext = cipher
@@ -159,7 +191,7 @@ impl CryptoService {
         let (nonce_bytes, ciphertext) = rest.split_at(12);
 
         let key = Self::derive_key_from_password(password, salt)?;
-        let cipher = Aes256Gcm::new(&key.into());
+        let cipher = Aes256Gcm::new(&(*key).into());
         let nonce = Nonce::from_slice(nonce_bytes);
 
         let plaintext = cipher
@@ -169,11 +201,43 @@ impl CryptoService {
         String::from_utf8(plaintext).map_err(|e| anyhow::anyhow!("Invalid UTF-8: {}", e))
     }
 
+    /// 加密二进制数据（使用密码派生密钥，用于备份文件）
+    /// 返回 salt(16) || nonce(12) || ciphertext
+    pub fn encrypt_bytes(data: &[u8], password: &str) -> Result<Vec<u8>> {
+        let salt = Self::generate_salt()?;
+        let key = Self::derive_key_from_password(password, &salt)?;
+        let cipher = Aes256Gcm::new(&(*key).into());
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+
+        let ciphertext = cipher
+            .encrypt(&nonce, data)
+            .map_err(|e| anyhow::anyhow!("Data encryption failed: {}", e))?;
+
+        Ok([&salt, nonce.as_slice(), &ciphertext].concat())
+    }
+
+    /// 解密 [`encrypt_bytes`](Self::encrypt_bytes) 的结果
+    pub fn decrypt_bytes(encrypted: &[u8], password: &str) -> Result<Vec<u8>> {
+        if encrypted.len() < 28 {
+            return Err(anyhow::anyhow!("Invalid encrypted data length"));
+        }
+
+        let (salt, rest) = encrypted.split_at(16);
+        let (nonce_bytes, ciphertext) = rest.split_at(12);
+
+        let key = Self::derive_key_from_password(password, salt)?;
+        let cipher = Aes256Gcm::new(&(*key).into());
+
+        cipher
+            .decrypt(Nonce::from_slice(nonce_bytes), ciphertext)
+            .map_err(|e| anyhow::anyhow!("Data decryption failed: {}", e))
+    }
+
     /// 加密用户密码（用于存储到数据库，使用 device_id 派生密钥）
     /// 返回：, > (加密数据 Base64, nonce Base64)
     pub fn encrypt_password(password: &str, device_id: &str) -> Result<(String, String)> {
         let key = Self::derive_key_from_device_id(device_id)?;
-        let cipher = Aes256Gcm::new(&key.into());
+        let cipher = Aes256Gcm::new(&(*key).into());
         let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
 
         let ciphertext = cipher
@@ -189,7 +253,7 @@ impl CryptoService {
     /// 解密用户密码（从数据库读取）
     pub fn decrypt_password(encrypted: &str, nonce_b64: &str, device_id: &str) -> Result<String> {
         let key = Self::derive_key_from_device_id(device_id)?;
-        let cipher = Aes256Gcm::new(&key.into());
+        let cipher = Aes256Gcm::new(&(*key).into());
 
         let ciphertext = general_purpose::STANDARD.decode(encrypted)?;
         let nonce_bytes = general_purpose::STANDARD.decode(nonce_b64)?;
@@ -260,4 +324,16 @@ mod tests {
 
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_encrypt_decrypt_with_key() {
+        let key = CryptoService::derive_key_from_device_id("device-123").expect("Key derivation failed");
+
+        let encrypted = CryptoService::encrypt_with_key("copied text", &key).expect("Encryption failed");
+        let decrypted = CryptoService::decrypt_with_key(&encrypted, &key).expect("Decryption failed");
+        assert_eq!(decrypted, "copied text");
+
+        let other_key = CryptoService::derive_key_from_device_id("device-456").expect("Key derivation failed");
+        assert!(CryptoService::decrypt_with_key(&encrypted, &other_key).is_err());
+    }
 }
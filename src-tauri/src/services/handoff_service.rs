@@ -0,0 +1,414 @@
+//! 设备间会话接力
+//!
+//! 登录后通过服务器的设备 WebSocket（`/api/devices/ws`）保持在线，接收同一账号的在线设备列表，
+//! 并可以请求另一台设备打开某个会话。请求使用由登录密码派生的密钥签名（HMAC-SHA256），
+//! 服务器不持有该密钥，只负责转发；接收方校验签名、时间和 nonce 后，通过 `session-handoff`
+//! 事件通知前端打开连接。
+
+use anyhow::{anyhow, Result};
+use base64::{engine::general_purpose, Engine as _};
+use futures::{SinkExt, StreamExt};
+use hmac::{Hmac, Mac};
+use secrecy::ExposeSecret;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+use tokio::net::TcpStream;
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::{self, client::IntoClientRequest, http::HeaderValue, Message};
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+use zeroize::Zeroizing;
+
+use crate::commands::auth::ApiClientStateWrapper;
+use crate::database::repositories::{SshSessionRepository, UserAuthRepository};
+use crate::database::DbPool;
+use crate::services::{ApiClient, CryptoService};
+
+/// 收到其他设备的打开会话请求（已校验）
+pub const HANDOFF_EVENT: &str = "session-handoff";
+
+/// 在线设备列表变化
+pub const HANDOFF_DEVICES_EVENT: &str = "handoff-devices";
+
+/// 接力失败（服务器报错或请求校验失败）
+pub const HANDOFF_ERROR_EVENT: &str = "handoff-error";
+
+/// 接力请求的有效期（秒），同时容忍设备之间的时钟偏差
+const MAX_REQUEST_AGE_SECS: i64 = 60;
+
+/// 记录最近处理过的 nonce 数量，用于拒绝重放的请求
+const SEEN_NONCE_CAPACITY: usize = 256;
+
+/// 断线重连的最短和最长间隔
+const RECONNECT_MIN: Duration = Duration::from_secs(2);
+const RECONNECT_MAX: Duration = Duration::from_secs(60);
+
+type HmacSha256 = Hmac<Sha256>;
+type DeviceSocket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// 在线设备（不含本机）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all(serialize = "camelCase", deserialize = "snake_case"))]
+pub struct OnlineDevice {
+    pub client_id: String,
+    pub name: String,
+    pub platform: String,
+    pub connected_at: i64,
+}
+
+/// 接力请求（签名的内容）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HandoffRequest {
+    pub session_id: String,
+    pub session_name: String,
+    pub from_client_id: String,
+    pub from_name: String,
+    /// 发出时间（秒）
+    pub issued_at: i64,
+    pub nonce: String,
+}
+
+/// 服务器推送的消息
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ServerMessage {
+    Devices { devices: Vec<OnlineDevice> },
+    Handoff { from_client_id: String, payload: String, signature: String },
+    Error { message: String },
+}
+
+/// 发送给服务器的消息
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ClientMessage {
+    Handoff { target_client_id: String, payload: String, signature: String },
+}
+
+/// 由登录密码派生签名密钥（同一账号的所有设备得到相同的密钥）
+pub fn handoff_key(password: &str, user_id: &str) -> Result<Zeroizing<[u8; 32]>> {
+    let salt = format!("ssh-terminal-handoff-v1:{}", user_id);
+    CryptoService::derive_key_from_password(password, salt.as_bytes())
+}
+
+/// 对请求内容签名，返回 Base64
+pub fn sign_payload(key: &[u8; 32], payload: &str) -> String {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(payload.as_bytes());
+    general_purpose::STANDARD.encode(mac.finalize().into_bytes())
+}
+
+/// 校验签名和有效期，返回请求内容
+pub fn verify_payload(key: &[u8; 32], payload: &str, signature: &str, now: i64) -> Result<HandoffRequest> {
+    let signature = general_purpose::STANDARD.decode(signature)?;
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(payload.as_bytes());
+    mac.verify_slice(&signature)
+        .map_err(|_| anyhow!("Invalid handoff signature"))?;
+
+    let request: HandoffRequest = serde_json::from_str(payload)?;
+    if (now - request.issued_at).abs() > MAX_REQUEST_AGE_SECS {
+        return Err(anyhow!("Handoff request expired"));
+    }
+    Ok(request)
+}
+
+/// 本机名称（显示在其他设备的设备列表中）
+fn device_name() -> String {
+    sysinfo::System::host_name().unwrap_or_else(|| "Unknown".to_string())
+}
+
+#[derive(Default)]
+struct HandoffInner {
+    task: Option<tauri::async_runtime::JoinHandle<()>>,
+    outgoing: Option<mpsc::UnboundedSender<String>>,
+    key: Option<Zeroizing<[u8; 32]>>,
+    devices: Vec<OnlineDevice>,
+    seen_nonces: VecDeque<String>,
+}
+
+/// 会话接力服务（全局单例）
+pub struct HandoffService {
+    /// 本次运行的客户端 ID，服务器据此区分同一账号的多台设备
+    client_id: String,
+    inner: Mutex<HandoffInner>,
+}
+
+/// Tauri State 类型别名
+pub type HandoffServiceState = Arc<HandoffService>;
+
+impl HandoffService {
+    pub fn new() -> Self {
+        Self {
+            client_id: uuid::Uuid::new_v4().to_string(),
+            inner: Mutex::new(HandoffInner::default()),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HandoffInner> {
+        self.inner.lock().expect("Failed to acquire handoff lock")
+    }
+
+    /// 连接设备 WebSocket（已连接时重新连接）
+    pub fn start(
+        self: &Arc<Self>,
+        app: AppHandle,
+        pool: &DbPool,
+        api_client_state: ApiClientStateWrapper,
+    ) -> Result<()> {
+        let user = UserAuthRepository::new(pool.clone())
+            .find_current()?
+            .ok_or_else(|| anyhow!("Not logged in"))?;
+        let password = Zeroizing::new(CryptoService::decrypt_password(&user.password_encrypted, &user.password_nonce, &user.device_id)?);
+        let key = handoff_key(&password, &user.user_id)?;
+
+        self.stop(&app);
+        let service = self.clone();
+        let task = tauri::async_runtime::spawn(async move {
+            service.run(app, api_client_state).await;
+        });
+
+        let mut inner = self.lock();
+        inner.key = Some(key);
+        inner.task = Some(task);
+        Ok(())
+    }
+
+    /// 断开设备 WebSocket（退出登录时调用）
+    pub fn stop(&self, app: &AppHandle) {
+        let had_devices = {
+            let mut inner = self.lock();
+            if let Some(task) = inner.task.take() {
+                task.abort();
+            }
+            inner.outgoing = None;
+            inner.key = None;
+            !std::mem::take(&mut inner.devices).is_empty()
+        };
+        if had_devices {
+            let _ = app.emit(HANDOFF_DEVICES_EVENT, Vec::<OnlineDevice>::new());
+        }
+    }
+
+    /// 当前在线的其他设备
+    pub fn devices(&self) -> Vec<OnlineDevice> {
+        self.lock().devices.clone()
+    }
+
+    /// 请求另一台设备打开会话
+    pub fn send(&self, pool: &DbPool, target_client_id: &str, session_id: &str) -> Result<()> {
+        let user = UserAuthRepository::new(pool.clone())
+            .find_current()?
+            .ok_or_else(|| anyhow!("Not logged in"))?;
+        let session = SshSessionRepository::new(pool.clone())
+            .find_by_id(session_id)?
+            .filter(|s| s.user_id == user.user_id && !s.is_deleted)
+            .ok_or_else(|| anyhow!("Session not found: {}", session_id))?;
+
+        let inner = self.lock();
+        let key = inner.key.clone().ok_or_else(|| anyhow!("Device connection not started"))?;
+        let outgoing = inner.outgoing.as_ref().ok_or_else(|| anyhow!("Not connected to sync server"))?;
+        if !inner.devices.iter().any(|d| d.client_id == target_client_id) {
+            return Err(anyhow!("Target device is offline"));
+        }
+
+        let request = HandoffRequest {
+            session_id: session.id,
+            session_name: session.name,
+            from_client_id: self.client_id.clone(),
+            from_name: device_name(),
+            issued_at: chrono::Utc::now().timestamp(),
+            nonce: uuid::Uuid::new_v4().to_string(),
+        };
+        let payload = serde_json::to_string(&request)?;
+        let message = ClientMessage::Handoff {
+            target_client_id: target_client_id.to_string(),
+            signature: sign_payload(&key, &payload),
+            payload,
+        };
+        outgoing
+            .send(serde_json::to_string(&message)?)
+            .map_err(|_| anyhow!("Not connected to sync server"))?;
+
+        tracing::info!("[Handoff] Sent session {} to device {}", session_id, target_client_id);
+        Ok(())
+    }
+
+    /// 保持连接，断开后按指数退避重连
+    async fn run(self: Arc<Self>, app: AppHandle, api_client_state: ApiClientStateWrapper) {
+        let mut backoff = RECONNECT_MIN;
+        loop {
+            match self.connect_once(&app, &api_client_state).await {
+                Ok(()) => {
+                    tracing::info!("[Handoff] Device connection closed");
+                    backoff = RECONNECT_MIN;
+                }
+                Err(e) => tracing::warn!("[Handoff] Device connection failed: {}", e),
+            }
+            self.lock().outgoing = None;
+            self.set_devices(&app, Vec::new());
+
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(RECONNECT_MAX);
+        }
+    }
+
+    async fn connect_once(&self, app: &AppHandle, api_client_state: &ApiClientStateWrapper) -> Result<()> {
+        let client = api_client_state.wait_client().await?;
+        let mut url = reqwest::Url::parse(&client.ws_url("api/devices/ws"))?;
+        url.query_pairs_mut()
+            .append_pair("client_id", &self.client_id)
+            .append_pair("name", &device_name())
+            .append_pair("platform", std::env::consts::OS);
+
+        let socket = match open_socket(&client, url.as_str()).await {
+            Err(tungstenite::Error::Http(response)) if response.status() == 401 => {
+                // access_token 过期，刷新后重试一次
+                client.refresh_access_token().await?;
+                open_socket(&client, url.as_str()).await?
+            }
+            result => result?,
+        };
+        tracing::info!("[Handoff] Connected as device {}", self.client_id);
+
+        let (mut sink, mut stream) = socket.split();
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        self.lock().outgoing = Some(tx);
+
+        loop {
+            tokio::select! {
+                outgoing = rx.recv() => {
+                    let Some(text) = outgoing else { break };
+                    sink.send(Message::Text(text)).await?;
+                }
+                incoming = stream.next() => match incoming {
+                    Some(Ok(Message::Text(text))) => self.handle_message(app, &text),
+                    Some(Ok(Message::Close(_))) | None => break,
+                    Some(Ok(_)) => {}
+                    Some(Err(e)) => return Err(e.into()),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_message(&self, app: &AppHandle, text: &str) {
+        let message = match serde_json::from_str::<ServerMessage>(text) {
+            Ok(message) => message,
+            Err(e) => {
+                tracing::warn!("[Handoff] Invalid message from server: {}", e);
+                return;
+            }
+        };
+
+        match message {
+            ServerMessage::Devices { devices } => {
+                let others = devices
+                    .into_iter()
+                    .filter(|device| device.client_id != self.client_id)
+                    .collect();
+                self.set_devices(app, others);
+            }
+            ServerMessage::Handoff { from_client_id, payload, signature } => {
+                match self.accept(&from_client_id, &payload, &signature) {
+                    Ok(request) => {
+                        tracing::info!(
+                            "[Handoff] Opening session {} from device {}",
+                            request.session_id,
+                            request.from_name
+                        );
+                        let _ = app.emit(HANDOFF_EVENT, request);
+                    }
+                    Err(e) => {
+                        tracing::warn!("[Handoff] Rejected request from {}: {}", from_client_id, e);
+                        let _ = app.emit(HANDOFF_ERROR_EVENT, e.to_string());
+                    }
+                }
+            }
+            ServerMessage::Error { message } => {
+                tracing::warn!("[Handoff] Server error: {}", message);
+                let _ = app.emit(HANDOFF_ERROR_EVENT, message);
+            }
+        }
+    }
+
+    /// 校验收到的请求（签名、有效期、发送方和重放）
+    fn accept(&self, from_client_id: &str, payload: &str, signature: &str) -> Result<HandoffRequest> {
+        let mut inner = self.lock();
+        let key = inner.key.clone().ok_or_else(|| anyhow!("Device connection not started"))?;
+        let request = verify_payload(&key, payload, signature, chrono::Utc::now().timestamp())?;
+
+        if request.from_client_id != from_client_id {
+            return Err(anyhow!("Handoff sender mismatch"));
+        }
+        if inner.seen_nonces.contains(&request.nonce) {
+            return Err(anyhow!("Handoff request replayed"));
+        }
+        if inner.seen_nonces.len() >= SEEN_NONCE_CAPACITY {
+            inner.seen_nonces.pop_front();
+        }
+        inner.seen_nonces.push_back(request.nonce.clone());
+        Ok(request)
+    }
+
+    fn set_devices(&self, app: &AppHandle, devices: Vec<OnlineDevice>) {
+        {
+            let mut inner = self.lock();
+            if inner.devices.is_empty() && devices.is_empty() {
+                return;
+            }
+            inner.devices = devices.clone();
+        }
+        let _ = app.emit(HANDOFF_DEVICES_EVENT, devices);
+    }
+}
+
+impl Default for HandoffService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 带 access_token 发起 WebSocket 握手
+async fn open_socket(client: &ApiClient, url: &str) -> std::result::Result<DeviceSocket, tungstenite::Error> {
+    let mut request = url.into_client_request()?;
+    if let Some(token) = client.access_token() {
+        let value = HeaderValue::from_str(&format!("Bearer {}", token.expose_secret()))
+            .map_err(|e| tungstenite::Error::HttpFormat(e.into()))?;
+        request.headers_mut().insert("Authorization", value);
+    }
+    let (socket, _) = tokio_tungstenite::connect_async(request).await?;
+    Ok(socket)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_and_verify() {
+        let key = [7u8; 32];
+        let request = HandoffRequest {
+            session_id: "s1".to_string(),
+            session_name: "web".to_string(),
+            from_client_id: "c1".to_string(),
+            from_name: "laptop".to_string(),
+            issued_at: 1_000,
+            nonce: "n1".to_string(),
+        };
+        let payload = serde_json::to_string(&request).unwrap();
+        let signature = sign_payload(&key, &payload);
+
+        let verified = verify_payload(&key, &payload, &signature, 1_030).unwrap();
+        assert_eq!(verified.session_id, "s1");
+
+        // 过期、篡改和错误的密钥都应拒绝
+        assert!(verify_payload(&key, &payload, &signature, 1_000 + MAX_REQUEST_AGE_SECS + 1).is_err());
+        let tampered = payload.replace("\"s1\"", "\"s2\"");
+        assert!(verify_payload(&key, &tampered, &signature, 1_000).is_err());
+        assert!(verify_payload(&[8u8; 32], &payload, &signature, 1_000).is_err());
+    }
+}
@@ -0,0 +1,201 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+use tokio::io::AsyncWriteExt;
+
+use crate::config::Storage;
+use crate::services::webhook_service::{WebhookEvent, WebhookService};
+
+/// 钩子配置文件名（位于应用存储目录）
+const HOOKS_CONFIG_FILE: &str = "hooks.json";
+
+/// 默认超时时间（秒）
+const DEFAULT_HOOK_TIMEOUT_SECS: u64 = 30;
+
+/// stdout/stderr 保留的最大长度
+const MAX_HOOK_OUTPUT: usize = 8 * 1024;
+
+/// 钩子触发事件
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+#[allow(clippy::enum_variant_names)]
+pub enum HookEvent {
+    /// 连接建立后
+    OnConnect,
+    /// 连接断开后
+    OnDisconnect,
+    /// SFTP 上传/下载完成后
+    OnTransferComplete,
+}
+
+impl HookEvent {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HookEvent::OnConnect => "on-connect",
+            HookEvent::OnDisconnect => "on-disconnect",
+            HookEvent::OnTransferComplete => "on-transfer-complete",
+        }
+    }
+}
+
+/// 钩子配置
+///
+/// 触发时执行 `program args...`，事件上下文以 JSON 形式写入 stdin
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HookConfig {
+    pub id: String,
+    pub name: String,
+    pub event: HookEvent,
+    /// 本地程序或脚本路径
+    pub program: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    pub timeout_secs: Option<u64>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// 钩子执行结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HookRunResult {
+    pub hook_id: String,
+    pub exit_code: Option<i32>,
+    pub stdout: String,
+    pub stderr: String,
+    pub duration_ms: u64,
+}
+
+/// 钩子服务
+pub struct HookService;
+
+impl HookService {
+    /// 加载所有钩子
+    pub fn load_hooks() -> Result<Vec<HookConfig>> {
+        let path = Storage::get_app_storage_dir()?.join(HOOKS_CONFIG_FILE);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = fs::read_to_string(&path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// 保存所有钩子（原子写入）
+    pub fn save_hooks(hooks: &[HookConfig]) -> Result<()> {
+        let dir = Storage::get_app_storage_dir()?;
+        fs::create_dir_all(&dir)?;
+
+        let path = dir.join(HOOKS_CONFIG_FILE);
+        let temp_path = path.with_extension("tmp");
+        fs::write(&temp_path, serde_json::to_string_pretty(hooks)?)?;
+        fs::rename(&temp_path, &path)?;
+
+        Ok(())
+    }
+
+    /// 触发事件（后台执行，不阻塞调用方）
+    ///
+    /// `context` 中会自动补充 `event` 和 `timestamp` 字段
+    pub fn fire(event: HookEvent, mut context: serde_json::Value) {
+        if let Some(object) = context.as_object_mut() {
+            object.insert("event".to_string(), event.as_str().into());
+            object.insert("timestamp".to_string(), chrono::Utc::now().timestamp_millis().into());
+        }
+
+        tauri::async_runtime::spawn(async move {
+            let hooks = match Self::load_hooks() {
+                Ok(hooks) => hooks,
+                Err(e) => {
+                    tracing::warn!("Failed to load hooks: {}", e);
+                    return;
+                }
+            };
+
+            for hook in hooks.into_iter().filter(|h| h.enabled && h.event == event) {
+                let outcome = Self::run_hook(&hook, &context).await;
+                match &outcome {
+                    Ok(result) if result.exit_code == Some(0) => {
+                        tracing::info!("Hook '{}' ({}) finished in {} ms", hook.name, event.as_str(), result.duration_ms);
+                    }
+                    Ok(result) => {
+                        tracing::warn!(
+                            "Hook '{}' ({}) exited with {:?}: {}",
+                            hook.name,
+                            event.as_str(),
+                            result.exit_code,
+                            result.stderr.trim()
+                        );
+                    }
+                    Err(e) => tracing::warn!("Hook '{}' ({}) failed: {}", hook.name, event.as_str(), e),
+                }
+
+                WebhookService::notify(WebhookEvent::HookTriggered, serde_json::json!({
+                    "hookId": hook.id,
+                    "hookName": hook.name,
+                    "hookEvent": event.as_str(),
+                    "exitCode": outcome.as_ref().ok().and_then(|r| r.exit_code),
+                    "durationMs": outcome.as_ref().ok().map(|r| r.duration_ms),
+                    "error": outcome.as_ref().err().map(|e| e.to_string()),
+                    "context": context.clone(),
+                }));
+            }
+        });
+    }
+
+    /// 执行单个钩子，将上下文 JSON 写入 stdin 并收集输出
+    pub async fn run_hook(hook: &HookConfig, context: &serde_json::Value) -> Result<HookRunResult> {
+        let started = Instant::now();
+        let timeout = Duration::from_secs(hook.timeout_secs.unwrap_or(DEFAULT_HOOK_TIMEOUT_SECS));
+
+        let mut child = tokio::process::Command::new(&hook.program)
+            .args(&hook.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            // 超时后 future 被丢弃时结束子进程
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| anyhow::anyhow!("Failed to start '{}': {}", hook.program, e))?;
+
+        let payload = serde_json::to_vec(context)?;
+        let stdin = child.stdin.take();
+        // 写入 stdin 和等待退出都受超时限制，并且同时进行：
+        // 不读取 stdin 的脚本在负载超过管道缓冲区时不会卡住写入，输出较多的脚本也不会卡住等待
+        let run = async move {
+            let write = async move {
+                if let Some(mut stdin) = stdin {
+                    // 脚本可能不读取 stdin，写入失败（管道已关闭）不视为错误
+                    let _ = stdin.write_all(&payload).await;
+                    // stdin 在这里被丢弃，关闭管道
+                }
+            };
+            let ((), output) = tokio::join!(write, child.wait_with_output());
+            output
+        };
+        let output = tokio::time::timeout(timeout, run)
+            .await
+            .map_err(|_| anyhow::anyhow!("Hook timed out after {:?}", timeout))??;
+
+        Ok(HookRunResult {
+            hook_id: hook.id.clone(),
+            exit_code: output.status.code(),
+            stdout: truncate_output(&output.stdout),
+            stderr: truncate_output(&output.stderr),
+            duration_ms: started.elapsed().as_millis() as u64,
+        })
+    }
+}
+
+/// 截断过长的输出
+fn truncate_output(data: &[u8]) -> String {
+    let data = &data[..data.len().min(MAX_HOOK_OUTPUT)];
+    String::from_utf8_lossy(data).into_owned()
+}
@@ -0,0 +1,476 @@
+//! 从其他 SSH 客户端导入会话
+//!
+//! 支持的格式：
+//! - PuTTY：注册表导出的 `.reg` 文件（Windows 上可直接读取注册表），以及
+//!   Linux/macOS 上 `~/.putty/sessions` 下的会话文件
+//! - WinSCP：导出的 `WinSCP.ini`
+//! - Termius：导出的 CSV 或 JSON
+//!
+//! 解析结果逐条返回，单条记录出错不影响其他记录。保存的密码（WinSCP 的混淆密码等）不导入。
+
+use crate::ssh::quick_connect::DEFAULT_SSH_PORT;
+use crate::ssh::session::{AuthMethod, SessionConfig};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// PuTTY 会话在注册表中的路径
+const PUTTY_REGISTRY_KEY: &str = r"HKEY_CURRENT_USER\Software\SimonTatham\PuTTY\Sessions";
+
+/// 未指定分组时使用的分组
+const DEFAULT_GROUP: &str = "默认分组";
+
+/// 导入来源
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum ImportSource {
+    Putty,
+    Winscp,
+    Termius,
+}
+
+/// 解析出的单条记录
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportEntry {
+    /// 来源中的会话名称
+    pub name: String,
+    /// 转换后的会话配置，解析失败时为空
+    pub config: Option<SessionConfig>,
+    pub error: Option<String>,
+}
+
+impl ImportEntry {
+    fn failed(name: impl Into<String>, error: impl Into<String>) -> Self {
+        Self { name: name.into(), config: None, error: Some(error.into()) }
+    }
+}
+
+/// 来源中读出的原始主机信息
+#[derive(Debug, Default)]
+struct RawHost {
+    name: String,
+    host: String,
+    port: Option<u16>,
+    username: Option<String>,
+    password: Option<String>,
+    key_file: Option<String>,
+    group: Option<String>,
+    /// 非 SSH 协议（telnet、ftp 等）
+    protocol: Option<String>,
+}
+
+impl RawHost {
+    fn into_entry(self, default_user: &str, default_auth: &AuthMethod) -> ImportEntry {
+        if let Some(protocol) = self.protocol.as_deref().filter(|p| !p.eq_ignore_ascii_case("ssh")) {
+            return ImportEntry::failed(self.name, format!("不支持的协议: {}", protocol));
+        }
+        if self.host.trim().is_empty() {
+            return ImportEntry::failed(self.name, "缺少主机地址");
+        }
+
+        let auth_method = match (self.password.filter(|p| !p.is_empty()), self.key_file.filter(|k| !k.is_empty())) {
+            (_, Some(private_key_path)) => AuthMethod::PublicKey { private_key_path, passphrase: None },
+            (Some(password), None) => AuthMethod::Password { password },
+            (None, None) => default_auth.clone(),
+        };
+        let name = if self.name.trim().is_empty() { self.host.clone() } else { self.name.clone() };
+
+        ImportEntry {
+            name: self.name,
+            config: Some(SessionConfig {
+                name,
+                host: self.host.trim().to_string(),
+                port: self.port.unwrap_or(DEFAULT_SSH_PORT),
+                username: self.username.filter(|u| !u.is_empty()).unwrap_or_else(|| default_user.to_string()),
+                auth_method,
+                terminal_type: None,
+                columns: None,
+                rows: None,
+                strict_host_key_checking: true,
+                group: self.group.filter(|g| !g.is_empty()).unwrap_or_else(|| DEFAULT_GROUP.to_string()),
+                keep_alive_interval: 30,
+                dns_resolver: None,
+                jump_host: None,
+                remote_command: None,
+                encoding: Default::default(),
+                remote_forwards: Vec::new(),
+                login_script: Vec::new(),
+                protected: false,
+                elevation: None,
+                algorithms: None,
+                startup_commands: Vec::new(),
+                backend_type: Default::default(),
+                serial: None,
+                wsl_distribution: None,
+                roaming: false,
+                wake_on_lan: None,
+                pre_connect: Vec::new(),
+            }),
+            error: None,
+        }
+    }
+}
+
+/// 解析导入文件
+///
+/// PuTTY 未指定路径时在 Windows 上读取注册表，其他平台读取 `~/.putty/sessions`
+pub fn parse_file(
+    source: ImportSource,
+    path: Option<&Path>,
+    default_user: &str,
+    default_auth: &AuthMethod,
+) -> Result<Vec<ImportEntry>, String> {
+    let hosts = match (source, path) {
+        (ImportSource::Putty, Some(path)) if path.is_dir() => parse_putty_dir(path)?,
+        (ImportSource::Putty, Some(path)) => parse_putty_reg(&read_text(path)?),
+        (ImportSource::Putty, None) => read_putty_default()?,
+        (_, None) => return Err("请选择要导入的文件".to_string()),
+        (ImportSource::Winscp, Some(path)) => parse_winscp_ini(&read_text(path)?),
+        (ImportSource::Termius, Some(path)) => parse_termius(&read_text(path)?)?,
+    };
+
+    Ok(hosts
+        .into_iter()
+        .map(|host| host.into_entry(default_user, default_auth))
+        .collect())
+}
+
+/// 读取文本文件，支持 UTF-16（注册表导出文件）和 UTF-8 BOM
+fn read_text(path: &Path) -> Result<String, String> {
+    let bytes = std::fs::read(path).map_err(|e| format!("无法读取 {}: {}", path.display(), e))?;
+    Ok(decode_text(&bytes))
+}
+
+fn decode_text(bytes: &[u8]) -> String {
+    if let Some(utf16) = bytes.strip_prefix(&[0xff, 0xfe]) {
+        let units: Vec<u16> = utf16.chunks_exact(2).map(|c| u16::from_le_bytes([c[0], c[1]])).collect();
+        return String::from_utf16_lossy(&units);
+    }
+    let bytes = bytes.strip_prefix(&[0xef, 0xbb, 0xbf]).unwrap_or(bytes);
+    String::from_utf8_lossy(bytes).to_string()
+}
+
+#[cfg(windows)]
+fn read_putty_default() -> Result<Vec<RawHost>, String> {
+    // 通过 reg.exe 导出，避免引入注册表依赖
+    let temp = std::env::temp_dir().join(format!("putty-sessions-{}.reg", uuid::Uuid::new_v4()));
+    let status = std::process::Command::new("reg")
+        .args(["export", PUTTY_REGISTRY_KEY])
+        .arg(&temp)
+        .arg("/y")
+        .status()
+        .map_err(|e| format!("无法读取 PuTTY 注册表: {}", e))?;
+    let content = read_text(&temp);
+    let _ = std::fs::remove_file(&temp);
+
+    if !status.success() {
+        return Err("未找到 PuTTY 会话".to_string());
+    }
+    Ok(parse_putty_reg(&content?))
+}
+
+#[cfg(not(windows))]
+fn read_putty_default() -> Result<Vec<RawHost>, String> {
+    let dir = dirs::home_dir()
+        .ok_or_else(|| "无法确定用户目录".to_string())?
+        .join(".putty")
+        .join("sessions");
+    parse_putty_dir(&dir)
+}
+
+/// `~/.putty/sessions` 下每个文件是一个会话，文件名为 URL 编码的会话名
+fn parse_putty_dir(dir: &Path) -> Result<Vec<RawHost>, String> {
+    let entries = std::fs::read_dir(dir).map_err(|e| format!("无法读取 {}: {}", dir.display(), e))?;
+    let mut hosts = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+        let name = percent_decode(&entry.file_name().to_string_lossy());
+        let content = read_text(&path)?;
+        let values = content.lines().filter_map(|line| line.split_once('='));
+        hosts.push(putty_host(name, values));
+    }
+    hosts.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(hosts)
+}
+
+/// 解析注册表导出文件
+fn parse_putty_reg(content: &str) -> Vec<RawHost> {
+    let prefix = format!("[{}\\", PUTTY_REGISTRY_KEY);
+    let mut hosts = Vec::new();
+    let mut current: Option<(String, Vec<(String, String)>)> = None;
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            if let Some((name, values)) = current.take() {
+                hosts.push(putty_host(name, values.iter().map(|(k, v)| (k.as_str(), v.as_str()))));
+            }
+            current = line
+                .strip_prefix(&prefix)
+                .and_then(|rest| rest.strip_suffix(']'))
+                .map(|name| (percent_decode(name), Vec::new()));
+        } else if let (Some((_, values)), Some((key, value))) = (current.as_mut(), line.split_once('=')) {
+            values.push((unquote_reg(key), parse_reg_value(value)));
+        }
+    }
+    if let Some((name, values)) = current {
+        hosts.push(putty_host(name, values.iter().map(|(k, v)| (k.as_str(), v.as_str()))));
+    }
+    hosts
+}
+
+fn putty_host<'a>(name: String, values: impl Iterator<Item = (&'a str, &'a str)>) -> RawHost {
+    let mut host = RawHost { name, ..Default::default() };
+    for (key, value) in values {
+        match key {
+            "HostName" => {
+                // PuTTY 允许在主机名中写 user@host
+                match value.rsplit_once('@') {
+                    Some((user, hostname)) => {
+                        host.username.get_or_insert_with(|| user.to_string());
+                        host.host = hostname.to_string();
+                    }
+                    None => host.host = value.to_string(),
+                }
+            }
+            "PortNumber" => host.port = value.parse().ok(),
+            "UserName" if !value.is_empty() => host.username = Some(value.to_string()),
+            "PublicKeyFile" => host.key_file = Some(value.to_string()),
+            "Protocol" => host.protocol = Some(value.to_string()),
+            _ => {}
+        }
+    }
+    host
+}
+
+fn unquote_reg(value: &str) -> String {
+    value.trim().trim_matches('"').replace("\\\\", "\\").replace("\\\"", "\"")
+}
+
+/// 注册表值：`"字符串"` 或 `dword:十六进制`
+fn parse_reg_value(value: &str) -> String {
+    match value.trim().strip_prefix("dword:") {
+        Some(hex) => u32::from_str_radix(hex, 16).map(|n| n.to_string()).unwrap_or_default(),
+        None => unquote_reg(value),
+    }
+}
+
+/// 解析 WinSCP.ini 中的 `[Sessions\名称]` 段，名称中的 `/` 表示文件夹
+fn parse_winscp_ini(content: &str) -> Vec<RawHost> {
+    let mut hosts = Vec::new();
+    let mut current: Option<RawHost> = None;
+
+    for line in content.lines() {
+        let line = line.trim();
+        if let Some(section) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            hosts.extend(current.take());
+            current = section.strip_prefix("Sessions\\").map(|name| {
+                let name = percent_decode(name);
+                let (group, name) = match name.rsplit_once('/') {
+                    Some((group, name)) => (Some(group.to_string()), name.to_string()),
+                    None => (None, name),
+                };
+                RawHost { name, group, ..Default::default() }
+            });
+            continue;
+        }
+
+        let (Some(host), Some((key, value))) = (current.as_mut(), line.split_once('=')) else {
+            continue;
+        };
+        let value = percent_decode(value);
+        match key {
+            "HostName" => host.host = value,
+            "PortNumber" => host.port = value.parse().ok(),
+            "UserName" => host.username = Some(value),
+            "PublicKeyFile" => host.key_file = Some(value),
+            // 0 = SCP，5 = SFTP（两者都基于 SSH），其余为 FTP/WebDAV/S3
+            "FSProtocol" if !matches!(value.as_str(), "0" | "5") => host.protocol = Some(format!("FSProtocol {}", value)),
+            _ => {}
+        }
+    }
+    hosts.extend(current);
+    hosts
+}
+
+/// Termius 导出：JSON 数组（或包含 `hosts` 的对象），否则按 CSV 解析
+fn parse_termius(content: &str) -> Result<Vec<RawHost>, String> {
+    let trimmed = content.trim_start();
+    if trimmed.starts_with('[') || trimmed.starts_with('{') {
+        let value: serde_json::Value = serde_json::from_str(trimmed)
+            .map_err(|e| format!("无法解析 Termius JSON: {}", e))?;
+        let items = match &value {
+            serde_json::Value::Array(items) => items.clone(),
+            _ => value.get("hosts").and_then(|h| h.as_array()).cloned().unwrap_or_default(),
+        };
+        return Ok(items.iter().map(termius_json_host).collect());
+    }
+    Ok(parse_termius_csv(content))
+}
+
+fn termius_json_host(item: &serde_json::Value) -> RawHost {
+    let text = |keys: &[&str]| {
+        keys.iter()
+            .find_map(|key| item.get(*key).and_then(|v| v.as_str()))
+            .map(str::to_string)
+    };
+    let port = ["port", "sshPort"]
+        .iter()
+        .find_map(|key| item.get(*key))
+        .and_then(|v| v.as_u64().or_else(|| v.as_str().and_then(|s| s.parse().ok())))
+        .and_then(|p| u16::try_from(p).ok());
+
+    RawHost {
+        name: text(&["label", "name"]).unwrap_or_default(),
+        host: text(&["address", "hostname", "host"]).unwrap_or_default(),
+        port,
+        username: text(&["username", "user"]),
+        password: text(&["password"]),
+        key_file: None,
+        group: text(&["group", "groupName"]),
+        protocol: None,
+    }
+}
+
+fn parse_termius_csv(content: &str) -> Vec<RawHost> {
+    let mut rows = parse_csv(content).into_iter();
+    let Some(header) = rows.next() else {
+        return Vec::new();
+    };
+    let column = |names: &[&str]| {
+        header.iter().position(|h| names.iter().any(|name| h.trim().eq_ignore_ascii_case(name)))
+    };
+    let name_col = column(&["label", "name", "alias"]);
+    let host_col = column(&["hostname/ip", "hostname", "host", "address", "ip"]);
+    let port_col = column(&["port", "ssh port"]);
+    let user_col = column(&["username", "user", "ssh username"]);
+    let password_col = column(&["password", "ssh password"]);
+    let group_col = column(&["group", "groups"]);
+
+    rows.filter(|row| row.iter().any(|cell| !cell.trim().is_empty()))
+        .map(|row| {
+            let cell = |col: Option<usize>| col.and_then(|i| row.get(i)).map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+            RawHost {
+                name: cell(name_col).unwrap_or_default(),
+                host: cell(host_col).unwrap_or_default(),
+                port: cell(port_col).and_then(|p| p.parse().ok()),
+                username: cell(user_col),
+                password: cell(password_col),
+                key_file: None,
+                group: cell(group_col),
+                protocol: None,
+            }
+        })
+        .collect()
+}
+
+/// 解析 CSV（支持双引号转义和字段内换行）
+fn parse_csv(content: &str) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => row.push(std::mem::take(&mut field)),
+            '\r' if !quoted => {}
+            '\n' if !quoted => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            c => field.push(c),
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    rows
+}
+
+/// 解码 `%XX` 转义（PuTTY 和 WinSCP 的会话名），无效的转义原样保留
+fn percent_decode(value: &str) -> String {
+    crate::utils::url::percent_decode(value).unwrap_or_else(|_| value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key() -> AuthMethod {
+        AuthMethod::PublicKey { private_key_path: "/home/me/.ssh/id_ed25519".to_string(), passphrase: None }
+    }
+
+    #[test]
+    fn test_parse_putty_reg() {
+        let content = "Windows Registry Editor Version 5.00\r\n\r\n\
+[HKEY_CURRENT_USER\\Software\\SimonTatham\\PuTTY\\Sessions\\web%20prod]\r\n\
+\"HostName\"=\"deploy@10.0.0.8\"\r\n\
+\"PortNumber\"=dword:00000016\r\n\
+\"Protocol\"=\"ssh\"\r\n\
+\"PublicKeyFile\"=\"C:\\\\keys\\\\web.ppk\"\r\n\
+\r\n\
+[HKEY_CURRENT_USER\\Software\\SimonTatham\\PuTTY\\Sessions\\router]\r\n\
+\"HostName\"=\"192.168.1.1\"\r\n\
+\"PortNumber\"=dword:00000017\r\n\
+\"Protocol\"=\"telnet\"\r\n";
+        let hosts = parse_putty_reg(content);
+        assert_eq!(hosts.len(), 2);
+
+        let entry = hosts.into_iter().next().unwrap().into_entry("me", &key());
+        let config = entry.config.unwrap();
+        assert_eq!(entry.name, "web prod");
+        assert_eq!(config.host, "10.0.0.8");
+        assert_eq!(config.port, 22);
+        assert_eq!(config.username, "deploy");
+        assert!(matches!(config.auth_method, AuthMethod::PublicKey { ref private_key_path, .. } if private_key_path == "C:\\keys\\web.ppk"));
+
+        let telnet = parse_putty_reg(content).pop().unwrap().into_entry("me", &key());
+        assert!(telnet.config.is_none());
+        assert!(telnet.error.is_some());
+    }
+
+    #[test]
+    fn test_parse_winscp_ini() {
+        let content = "[Configuration]\nFoo=1\n\n[Sessions\\Prod/db%201]\nHostName=db.example.com\nUserName=postgres\nPortNumber=2222\nFSProtocol=5\n\n[Sessions\\ftp]\nHostName=ftp.example.com\nFSProtocol=2\n";
+        let entries: Vec<ImportEntry> = parse_winscp_ini(content)
+            .into_iter()
+            .map(|h| h.into_entry("me", &key()))
+            .collect();
+
+        let config = entries[0].config.as_ref().unwrap();
+        assert_eq!(config.name, "db 1");
+        assert_eq!(config.group, "Prod");
+        assert_eq!(config.port, 2222);
+        assert_eq!(config.username, "postgres");
+        assert!(entries[1].config.is_none());
+    }
+
+    #[test]
+    fn test_parse_termius() {
+        let csv = "Groups,Label,Tags,Hostname/IP,Protocol,Port,Username,Password\r\n\
+Work,\"api, main\",,api.example.com,ssh,22,ubuntu,\"pa\"\"ss\"\r\n\
+,,,,,,,\r\n";
+        let hosts = parse_termius(csv).unwrap();
+        assert_eq!(hosts.len(), 1);
+        let config = hosts.into_iter().next().unwrap().into_entry("me", &key()).config.unwrap();
+        assert_eq!(config.name, "api, main");
+        assert_eq!(config.group, "Work");
+        assert!(matches!(config.auth_method, AuthMethod::Password { ref password } if password == "pa\"ss"));
+
+        let json = r#"{"hosts": [{"label": "db", "address": "10.1.1.1", "port": "2200"}]}"#;
+        let config = parse_termius(json).unwrap().pop().unwrap().into_entry("me", &key()).config.unwrap();
+        assert_eq!(config.port, 2200);
+        assert_eq!(config.username, "me");
+    }
+}
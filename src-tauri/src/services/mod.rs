@@ -4,6 +4,19 @@ pub mod auth_service;
 pub mod sync_service;
 pub mod user_profile_service;
 pub mod api_client;
+pub mod backup_service;
+pub mod hook_service;
+pub mod crash_service;
+pub mod import_service;
+pub mod handoff_service;
+pub mod ai_config_service;
+pub mod thumbnail_service;
+pub mod recording_edit_service;
+pub mod transcription_service;
+pub mod bulk_edit_service;
+pub mod password_rotation_service;
+pub mod usage_heatmap_service;
+pub mod webhook_service;
 
 pub use crypto_service::*;
 pub use auth_service::*;
@@ -0,0 +1,206 @@
+//! 密码轮换
+//!
+//! 生成强密码，并驱动远程主机上的 `passwd`（分配 PTY，按提示依次应答旧密码和新密码）
+//! 或 `chpasswd`（从标准输入读取 `用户名:新密码`，需要 root 权限）修改登录密码。
+//! 修改后的校验、会话存储更新和失败回滚由 `password_rotate` 命令完成。
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// 默认密码长度
+pub const DEFAULT_PASSWORD_LENGTH: usize = 24;
+
+/// 允许的密码长度范围
+const MIN_PASSWORD_LENGTH: usize = 12;
+const MAX_PASSWORD_LENGTH: usize = 128;
+
+/// 生成密码使用的字符集（去掉了容易混淆的 0/O、1/l/I，以及需要在 shell 中转义的字符）
+const LOWERCASE: &[u8] = b"abcdefghijkmnopqrstuvwxyz";
+const UPPERCASE: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ";
+const DIGITS: &[u8] = b"23456789";
+const SYMBOLS: &[u8] = b"!#%+-.=@^_~";
+
+/// 匹配提示时保留的最大输出字符数
+const MAX_BUFFER_CHARS: usize = 1024;
+
+/// 修改密码的方式
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RotationMethod {
+    /// 以当前用户身份运行 `passwd`
+    #[default]
+    Passwd,
+    /// 以 root 身份运行 `chpasswd`（不需要旧密码，可绕过部分密码策略提示）
+    Chpasswd,
+}
+
+/// 密码轮换结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PasswordRotationReport {
+    pub session_id: String,
+    pub method: RotationMethod,
+    /// 新密码已通过重新登录校验
+    pub verified: bool,
+    /// 新密码已写入会话存储文件
+    pub storage_updated: bool,
+    /// 新密码已写入数据库
+    pub database_updated: bool,
+}
+
+/// 生成包含大小写字母、数字和符号的随机密码
+pub fn generate_password(length: Option<usize>) -> String {
+    let length = length
+        .unwrap_or(DEFAULT_PASSWORD_LENGTH)
+        .clamp(MIN_PASSWORD_LENGTH, MAX_PASSWORD_LENGTH);
+    let classes = [LOWERCASE, UPPERCASE, DIGITS, SYMBOLS];
+    let all: Vec<u8> = classes.concat();
+    let mut rng = rand::rngs::OsRng;
+
+    // 每类字符至少一个，其余从全部字符中随机选取，最后打乱顺序
+    let mut password: Vec<u8> = classes
+        .iter()
+        .map(|class| class[rng.gen_range(0..class.len())])
+        .collect();
+    password.extend((classes.len()..length).map(|_| all[rng.gen_range(0..all.len())]));
+    password.shuffle(&mut rng);
+    String::from_utf8(password).expect("password characters are ASCII")
+}
+
+/// 校验用户提供的新密码
+pub fn validate_password(password: &str) -> Result<(), String> {
+    if password.chars().count() < MIN_PASSWORD_LENGTH {
+        return Err(format!("Password must be at least {} characters", MIN_PASSWORD_LENGTH));
+    }
+    // chpasswd 以行和冒号分隔，passwd 按行读取
+    if password.chars().any(|c| c.is_control()) {
+        return Err("Password must not contain control characters".to_string());
+    }
+    Ok(())
+}
+
+/// `chpasswd` 的标准输入
+pub fn chpasswd_input(username: &str, password: &str) -> Vec<u8> {
+    format!("{}:{}\n", username, password).into_bytes()
+}
+
+/// `passwd` 交互过程
+///
+/// 持续接收 PTY 输出，匹配到旧密码、新密码或确认新密码的提示时返回应答；
+/// 出现失败提示时记录原因
+pub struct PasswdDialog {
+    current: String,
+    new: String,
+    buffer: String,
+    /// 已应答的新密码提示次数
+    new_prompts: usize,
+    failure: Option<String>,
+}
+
+impl PasswdDialog {
+    pub fn new(current: &str, new: &str) -> Self {
+        Self {
+            current: current.to_string(),
+            new: new.to_string(),
+            buffer: String::new(),
+            new_prompts: 0,
+            failure: None,
+        }
+    }
+
+    /// 处理一段输出，需要应答时返回应答（含回车）
+    pub fn feed(&mut self, output: &[u8]) -> Option<Vec<u8>> {
+        self.buffer.push_str(&String::from_utf8_lossy(output));
+        let excess = self.buffer.chars().count().saturating_sub(MAX_BUFFER_CHARS);
+        if excess > 0 {
+            self.buffer = self.buffer.chars().skip(excess).collect();
+        }
+
+        let lower = self.buffer.to_lowercase();
+        if let Some(failure) = FAILURE_MARKERS.iter().find(|marker| lower.contains(*marker)) {
+            self.failure.get_or_insert_with(|| failure.to_string());
+            return None;
+        }
+
+        // 只在提示以冒号结尾（等待输入）时应答
+        let trimmed = lower.trim_end();
+        if !trimmed.ends_with(':') {
+            return None;
+        }
+        let prompt = trimmed.rsplit('\n').next().unwrap_or(trimmed);
+        let response = if prompt.contains("new") || prompt.contains("retype") || prompt.contains("re-enter") {
+            self.new_prompts += 1;
+            // 新密码最多输入两次（输入和确认），再次出现说明被拒绝
+            if self.new_prompts > 2 {
+                self.failure.get_or_insert_with(|| "new password rejected".to_string());
+                return None;
+            }
+            &self.new
+        } else if prompt.contains("password") {
+            &self.current
+        } else {
+            return None;
+        };
+
+        self.buffer.clear();
+        Some(format!("{}\n", response).into_bytes())
+    }
+
+    /// 交互过程中出现的失败原因
+    pub fn failure(&self) -> Option<&str> {
+        self.failure.as_deref()
+    }
+}
+
+/// `passwd` 的失败提示（小写）
+const FAILURE_MARKERS: &[&str] = &[
+    "authentication token manipulation error",
+    "authentication failure",
+    "bad password",
+    "do not match",
+    "password unchanged",
+    "have exhausted maximum number of retries",
+    "you must wait longer",
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_password() {
+        let password = generate_password(None);
+        assert_eq!(password.len(), DEFAULT_PASSWORD_LENGTH);
+        for class in [LOWERCASE, UPPERCASE, DIGITS, SYMBOLS] {
+            assert!(password.bytes().any(|b| class.contains(&b)));
+        }
+        assert_eq!(generate_password(Some(4)).len(), MIN_PASSWORD_LENGTH);
+        assert!(validate_password(&password).is_ok());
+        assert!(validate_password("short").is_err());
+        assert!(validate_password("long enough\npassword").is_err());
+        assert_eq!(chpasswd_input("deploy", "s3cret"), b"deploy:s3cret\n");
+    }
+
+    #[test]
+    fn test_passwd_dialog() {
+        let mut dialog = PasswdDialog::new("old-pass", "new-pass");
+        assert_eq!(dialog.feed(b"Changing password for deploy.\r\n"), None);
+        assert_eq!(dialog.feed(b"Current password: ").unwrap(), b"old-pass\n");
+        assert_eq!(dialog.feed(b"\r\nNew password: ").unwrap(), b"new-pass\n");
+        assert_eq!(dialog.feed(b"\r\nRetype new password: ").unwrap(), b"new-pass\n");
+        assert_eq!(dialog.feed(b"\r\npasswd: password updated successfully\r\n"), None);
+        assert!(dialog.failure().is_none());
+
+        let mut dialog = PasswdDialog::new("old-pass", "new-pass");
+        dialog.feed(b"(current) UNIX password: ");
+        dialog.feed(b"\r\npasswd: Authentication token manipulation error\r\n");
+        assert_eq!(dialog.failure(), Some("authentication token manipulation error"));
+
+        let mut dialog = PasswdDialog::new("old-pass", "new-pass");
+        dialog.feed(b"New password: ");
+        dialog.feed(b"Retype new password: ");
+        assert_eq!(dialog.feed(b"New password: "), None);
+        assert_eq!(dialog.failure(), Some("new password rejected"));
+    }
+}
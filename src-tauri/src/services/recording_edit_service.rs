@@ -0,0 +1,225 @@
+//! 录制剪辑：章节标记与裁剪
+//!
+//! 章节保存在录制元数据的 `chapters` 中，时间为相对录制开始的毫秒数；可以手动添加，
+//! 也可以由录制中的标记事件（`metadata` 事件，如 `marker`、`recording_resumed`）生成。
+//! 裁剪保留指定时间段内的事件，之前的输出和尺寸变化压缩到起点，保证回放时屏幕内容完整；
+//! 关联的视频通过 ffmpeg 截取同一时间段（优先直接复制流，失败时重新编码）。
+
+use crate::commands::recording::{RecordingChapter, RecordingEventType, RecordingFile};
+use crate::services::thumbnail_service::run_ffmpeg;
+use crate::services::transcription_service::TranscriptSegment;
+use std::path::Path;
+
+/// 默认用于生成章节的标记事件
+pub const DEFAULT_CHAPTER_KEYS: [&str; 2] = ["marker", "recording_resumed"];
+
+/// 章节来源
+pub const CHAPTER_SOURCE_MANUAL: &str = "manual";
+pub const CHAPTER_SOURCE_EVENT: &str = "event";
+
+/// 录制的总时长（毫秒，按事件时间计算）
+pub fn recording_length(recording: &RecordingFile) -> i64 {
+    let start = recording.metadata.start_time;
+    let last = recording.events.iter().map(|e| e.timestamp).max().unwrap_or(start);
+    recording.metadata.end_time.unwrap_or(last).max(last) - start
+}
+
+/// 按时间排序章节，同一时间只保留一个
+pub fn normalize_chapters(chapters: &mut Vec<RecordingChapter>) {
+    chapters.sort_by_key(|c| c.offset);
+    chapters.dedup_by_key(|c| c.offset);
+}
+
+/// 从标记事件生成章节
+///
+/// 标题优先使用事件值中的 `label`、`title` 或 `name`，否则使用事件 key
+pub fn chapters_from_events(recording: &RecordingFile, keys: &[String]) -> Vec<RecordingChapter> {
+    let start = recording.metadata.start_time;
+    let mut chapters: Vec<RecordingChapter> = recording
+        .events
+        .iter()
+        .filter(|e| matches!(e.event_type, RecordingEventType::Metadata))
+        .filter_map(|e| {
+            let key = e.data.get("key")?.as_str()?;
+            if !keys.iter().any(|k| k == key) {
+                return None;
+            }
+            let value = e.data.get("value");
+            let title = ["label", "title", "name"]
+                .iter()
+                .find_map(|field| value?.get(field)?.as_str())
+                .map(str::to_string)
+                .unwrap_or_else(|| key.to_string());
+            Some(RecordingChapter {
+                offset: (e.timestamp - start).max(0),
+                title,
+                source: CHAPTER_SOURCE_EVENT.to_string(),
+            })
+        })
+        .collect();
+    normalize_chapters(&mut chapters);
+    chapters
+}
+
+/// 裁剪录制事件到 `[start_ms, end_ms]`（相对录制开始的毫秒数）
+///
+/// 起点之前的输出和尺寸变化保留并移动到起点，输入和其他事件丢弃；
+/// 裁剪后的录制以起点为开始时间，章节和转写片段随之平移
+pub fn trim_recording(recording: &RecordingFile, start_ms: i64, end_ms: i64) -> Result<RecordingFile, String> {
+    let length = recording_length(recording);
+    let end_ms = end_ms.min(length);
+    if start_ms < 0 || start_ms >= end_ms {
+        return Err(format!(
+            "Invalid trim range: {}-{} ms (recording length {} ms)",
+            start_ms, end_ms, length
+        ));
+    }
+
+    let origin = recording.metadata.start_time;
+    let new_start = origin + start_ms;
+    let new_end = origin + end_ms;
+
+    let mut events = Vec::new();
+    for event in &recording.events {
+        if event.timestamp > new_end {
+            continue;
+        }
+        if event.timestamp >= new_start {
+            events.push(event.clone());
+        } else if matches!(event.event_type, RecordingEventType::Output | RecordingEventType::Resize) {
+            let mut event = event.clone();
+            event.timestamp = new_start;
+            events.push(event);
+        }
+    }
+
+    let mut trimmed = recording.clone();
+    trimmed.metadata.start_time = new_start;
+    trimmed.metadata.end_time = Some(new_end);
+    trimmed.metadata.duration = Some((end_ms - start_ms) as f64 / 1000.0);
+    trimmed.metadata.event_count = events.len();
+    trimmed.metadata.file_size = None;
+    trimmed.metadata.video_file = None;
+    trimmed.metadata.chapters = recording
+        .metadata
+        .chapters
+        .iter()
+        .filter(|c| c.offset >= start_ms && c.offset <= end_ms)
+        .map(|c| RecordingChapter {
+            offset: c.offset - start_ms,
+            ..c.clone()
+        })
+        .collect();
+    trimmed.metadata.transcript = recording
+        .metadata
+        .transcript
+        .iter()
+        .filter(|s| s.end > start_ms && s.start < end_ms)
+        .map(|s| TranscriptSegment {
+            start: (s.start - start_ms).max(0),
+            end: s.end.min(end_ms) - start_ms,
+            text: s.text.clone(),
+        })
+        .collect();
+    trimmed.events = events;
+    Ok(trimmed)
+}
+
+fn seconds(ms: i64) -> String {
+    format!("{:.3}", ms as f64 / 1000.0)
+}
+
+/// 截取视频的 ffmpeg 参数，`reencode` 为 false 时直接复制流
+fn trim_video_args(video: &Path, output: &Path, start_ms: i64, end_ms: i64, reencode: bool) -> Vec<String> {
+    let mut args = vec![
+        "-y".to_string(),
+        "-loglevel".to_string(),
+        "error".to_string(),
+        "-ss".to_string(),
+        seconds(start_ms),
+        "-i".to_string(),
+        video.to_string_lossy().into_owned(),
+        "-t".to_string(),
+        seconds(end_ms - start_ms),
+    ];
+    if !reencode {
+        args.extend(["-c", "copy", "-avoid_negative_ts", "make_zero"].map(String::from));
+    }
+    args.push(output.to_string_lossy().into_owned());
+    args
+}
+
+pub struct RecordingEditService;
+
+impl RecordingEditService {
+    /// 截取视频到新文件：优先直接复制流（快但只能在关键帧处切分），失败时重新编码
+    pub async fn trim_video(video: &Path, output: &Path, start_ms: i64, end_ms: i64) -> Result<(), String> {
+        if let Err(e) = run_ffmpeg(&trim_video_args(video, output, start_ms, end_ms, false)).await {
+            tracing::warn!("[Recording] Stream copy trim failed, re-encoding: {}", e);
+            run_ffmpeg(&trim_video_args(video, output, start_ms, end_ms, true)).await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::recording::{RecordingEvent, RecordingMetadata, TerminalSize};
+    use serde_json::json;
+
+    fn event(timestamp: i64, event_type: RecordingEventType, data: serde_json::Value) -> RecordingEvent {
+        RecordingEvent { timestamp, event_type, data }
+    }
+
+    #[test]
+    fn test_chapters_and_trim() {
+        let recording = RecordingFile {
+            version: "1.0".to_string(),
+            metadata: RecordingMetadata {
+                start_time: 1_000,
+                end_time: Some(11_000),
+                duration: Some(10.0),
+                terminal_size: TerminalSize { cols: 80, rows: 24 },
+                connection_id: "c1".to_string(),
+                session_name: "web".to_string(),
+                description: None,
+                tags: Vec::new(),
+                event_count: 5,
+                file_size: None,
+                terminal_config: None,
+                video_file: Some("web.webm".to_string()),
+                chapters: Vec::new(),
+                transcript: vec![TranscriptSegment { start: 2_000, end: 3_000, text: "ls".to_string() }],
+            },
+            events: vec![
+                event(1_000, RecordingEventType::Output, json!({ "data": [36] })),
+                event(2_000, RecordingEventType::Input, json!({ "data": "ls\r" })),
+                event(4_000, RecordingEventType::Metadata, json!({ "key": "marker", "value": { "label": "deploy" } })),
+                event(6_000, RecordingEventType::Output, json!({ "data": [97] })),
+                event(9_000, RecordingEventType::Output, json!({ "data": [98] })),
+            ],
+        };
+
+        let keys: Vec<String> = DEFAULT_CHAPTER_KEYS.iter().map(|k| k.to_string()).collect();
+        let chapters = chapters_from_events(&recording, &keys);
+        assert_eq!(chapters.len(), 1);
+        assert_eq!((chapters[0].offset, chapters[0].title.as_str()), (3_000, "deploy"));
+
+        let mut recording = recording;
+        recording.metadata.chapters = chapters;
+        let trimmed = trim_recording(&recording, 2_500, 6_000).unwrap();
+        assert_eq!(trimmed.metadata.start_time, 3_500);
+        assert_eq!(trimmed.metadata.duration, Some(3.5));
+        assert_eq!(trimmed.metadata.video_file, None);
+        // 起点之前的输出移动到起点，输入丢弃，终点之后的事件丢弃
+        let timestamps: Vec<i64> = trimmed.events.iter().map(|e| e.timestamp).collect();
+        assert_eq!(timestamps, vec![3_500, 4_000, 6_000]);
+        assert_eq!(trimmed.metadata.chapters[0].offset, 500);
+        assert_eq!((trimmed.metadata.transcript[0].start, trimmed.metadata.transcript[0].end), (0, 500));
+
+        assert!(trim_recording(&recording, 5_000, 5_000).is_err());
+        let args = trim_video_args(Path::new("a.webm"), Path::new("b.webm"), 2_500, 6_000, false);
+        assert!(args.windows(2).any(|w| w[0] == "-t" && w[1] == "3.500"));
+    }
+}
@@ -1,10 +1,13 @@
 use anyhow::Result;
 use anyhow::anyhow;
+use base64::Engine;
 
-use crate::database::repositories::{SshSessionRepository, SyncStateRepository, UserAuthRepository};
+use crate::commands::session_note::{read_attachment_file, remove_attachment_file, store_attachment_file};
+use crate::database::repositories::{SessionGroupRepository, SessionNoteRepository, SshSessionRepository, SyncStateRepository, UserAuthRepository};
 use crate::database::DbPool;
+use crate::models::session_note::{SessionAttachment, SessionNote, MAX_SYNC_ATTACHMENT_SIZE};
 use crate::models::sync::*;
-use crate::models::SshSession;
+use crate::models::{SessionGroup, SshSession};
 use crate::services::api_client::ApiClient;
 use crate::commands::auth::ApiClientStateWrapper;
 
@@ -38,10 +41,10 @@ impl SyncService {
     }
 
     /// 获取 API 客户端
-    fn get_api_client(&self) -> Result<ApiClient> {
+    async fn get_api_client(&self) -> Result<ApiClient> {
         match &self.api_client_state {
             Some(state) => {
-                state.get_client()
+                state.wait_client().await
             }
             None => {
                 Err(anyhow!("API client state not available - missing from service initialization"))
@@ -108,8 +111,39 @@ impl SyncService {
             }
         };
 
+        // 会话分组随会话一起同步（首次同步前从现有会话的分组名迁移）
+        let group_repo = SessionGroupRepository::new(self.pool.clone());
+        let (dirty_groups, deleted_group_ids) = if matches!(options, SyncOptions::SyncSessions | SyncOptions::SyncAll) {
+            group_repo.import_from_sessions(&current_user.user_id)?;
+            (
+                group_repo.get_dirty_groups(&current_user.user_id)?,
+                group_repo.get_deleted_groups(&current_user.user_id)?,
+            )
+        } else {
+            (vec![], vec![])
+        };
+
+        // 开启同步的笔记和附件随会话一起同步（附件分批推送，未推送的留到下次）
+        let note_repo = SessionNoteRepository::new(self.pool.clone());
+        let (note_items, deleted_note_ids, attachment_items, deleted_attachment_ids) =
+            if matches!(options, SyncOptions::SyncSessions | SyncOptions::SyncAll) {
+                (
+                    Self::note_push_items(note_repo.get_dirty_notes(&current_user.user_id)?),
+                    note_repo.get_deleted_note_ids(&current_user.user_id)?,
+                    Self::attachment_push_items(note_repo.get_dirty_attachments(&current_user.user_id)?).await,
+                    note_repo.get_deleted_attachment_ids(&current_user.user_id)?,
+                )
+            } else {
+                (vec![], vec![], vec![], vec![])
+            };
+        let pushed_attachment_ids: Vec<String> = attachment_items
+            .iter()
+            .map(|a| a.id.clone())
+            .chain(deleted_attachment_ids.iter().cloned())
+            .collect();
+
         // 4. 构建统一请求
-        let request = self.build_sync_request_with_options(
+        let mut request = self.build_sync_request_with_options(
             &current_user.user_id,
             last_sync_at,
             device_id,
@@ -117,13 +151,23 @@ impl SyncService {
             user_profile_update,
             deleted_session_ids,
         )?;
+        request.session_groups = Self::group_push_items(dirty_groups);
+        request.deleted_group_ids = deleted_group_ids;
+        request.session_notes = note_items;
+        request.deleted_note_ids = deleted_note_ids;
+        request.session_attachments = attachment_items;
+        request.deleted_attachment_ids = deleted_attachment_ids;
+        if matches!(options, SyncOptions::SyncSessions | SyncOptions::SyncAll) {
+            request.restored_session_ids = session_repo.get_restored_sessions(&current_user.user_id)?;
+        }
 
         // 5. 调用统一同步 API
-        let (sync_response, code, message) = self.get_api_client()?.sync(&request).await?;
+        let (sync_response, code, message) = self.get_api_client().await?.sync(&request).await?;
 
         // 6. 应用 Pull 结果
         let ssh_sessions_len = sync_response.ssh_sessions.len();
         self.apply_pull_data(&sync_response, &current_user.user_id)?;
+        self.apply_pull_attachments(&sync_response).await;
 
         // 7. 处理 Push 结果
         self.apply_push_result(&sync_response, &current_user.user_id)?;
@@ -144,6 +188,13 @@ impl SyncService {
                 session_repo.clear_dirty_marker(session_id, sync_response.last_sync_at)?;
             }
 
+            // 清理分组的脏标记
+            group_repo.clear_dirty_markers(&current_user.user_id, sync_response.last_sync_at)?;
+
+            // 清理笔记和已推送附件的脏标记
+            note_repo.clear_note_dirty_markers(&current_user.user_id, sync_response.last_sync_at)?;
+            note_repo.clear_attachment_dirty_markers(&pushed_attachment_ids, sync_response.last_sync_at)?;
+
             // 更新用户的最后同步时间
             let auth_repo = UserAuthRepository::new(self.pool.clone());
             auth_repo.update_last_sync(&current_user.user_id, sync_response.last_sync_at)?;
@@ -239,9 +290,150 @@ impl SyncService {
             user_profile,
             ssh_sessions,
             deleted_session_ids,
+            restored_session_ids: Vec::new(),
+            session_groups: Vec::new(),
+            deleted_group_ids: Vec::new(),
+            session_notes: Vec::new(),
+            deleted_note_ids: Vec::new(),
+            session_attachments: Vec::new(),
+            deleted_attachment_ids: Vec::new(),
         })
     }
 
+    /// 转换脏分组为推送项
+    fn group_push_items(groups: Vec<SessionGroup>) -> Vec<SessionGroupPushItem> {
+        groups
+            .into_iter()
+            .map(|g| SessionGroupPushItem {
+                id: g.id,
+                name: g.name,
+                color: g.color,
+                icon: g.icon,
+                sort_order: g.sort_order,
+                description: g.description,
+                client_ver: g.client_ver,
+                created_at: g.created_at,
+                updated_at: g.updated_at,
+            })
+            .collect()
+    }
+
+    /// 转换脏笔记为推送项
+    fn note_push_items(notes: Vec<SessionNote>) -> Vec<SessionNotePushItem> {
+        notes
+            .into_iter()
+            .map(|n| SessionNotePushItem {
+                session_id: n.session_id,
+                content: n.content,
+                client_ver: n.client_ver,
+                created_at: n.created_at,
+                updated_at: n.updated_at,
+            })
+            .collect()
+    }
+
+    /// 读取脏附件的内容并转换为推送项
+    ///
+    /// 单次请求的附件总大小不超过 [`MAX_SYNC_ATTACHMENT_SIZE`]，超出的附件保持待同步，下次再推送
+    async fn attachment_push_items(attachments: Vec<SessionAttachment>) -> Vec<SessionAttachmentPushItem> {
+        let mut items = Vec::new();
+        let mut batch_size = 0u64;
+
+        for attachment in attachments {
+            let data = match read_attachment_file(&attachment).await {
+                Ok(data) => data,
+                Err(e) => {
+                    tracing::warn!("Skipping attachment {}: {}", attachment.id, e);
+                    continue;
+                }
+            };
+
+            let size = data.len() as u64;
+            if size > MAX_SYNC_ATTACHMENT_SIZE {
+                tracing::warn!("Skipping attachment {} ({} bytes): too large to sync", attachment.id, size);
+                continue;
+            }
+            if !items.is_empty() && batch_size + size > MAX_SYNC_ATTACHMENT_SIZE {
+                break;
+            }
+            batch_size += size;
+
+            items.push(SessionAttachmentPushItem {
+                id: attachment.id,
+                session_id: attachment.session_id,
+                file_name: attachment.file_name,
+                mime_type: attachment.mime_type,
+                size: size as i64,
+                content: base64::engine::general_purpose::STANDARD.encode(&data),
+                client_ver: attachment.client_ver,
+                created_at: attachment.created_at,
+                updated_at: attachment.updated_at,
+            });
+        }
+
+        items
+    }
+
+    /// 应用拉取的附件（写入附件文件，服务器删除的附件同时删除本地文件）
+    async fn apply_pull_attachments(&self, response: &ServerSyncResponse) {
+        let note_repo = SessionNoteRepository::new(self.pool.clone());
+
+        for server in &response.session_attachments {
+            if server.deleted_at.is_some() {
+                match note_repo.delete_server_deleted_attachment(&server.id) {
+                    Ok(Some(attachment)) => remove_attachment_file(&attachment).await,
+                    Ok(None) => {}
+                    Err(e) => tracing::warn!("Failed to delete attachment {}: {}", server.id, e),
+                }
+                continue;
+            }
+
+            match note_repo.should_apply_server_attachment(&server.id, server.server_ver) {
+                Ok(true) => {}
+                Ok(false) => continue,
+                Err(e) => {
+                    tracing::warn!("Failed to check attachment {}: {}", server.id, e);
+                    continue;
+                }
+            }
+
+            let data = match base64::engine::general_purpose::STANDARD.decode(&server.content) {
+                Ok(data) => data,
+                Err(e) => {
+                    tracing::warn!("Invalid content of attachment {}: {}", server.id, e);
+                    continue;
+                }
+            };
+            let stored_path = match store_attachment_file(&server.session_id, &server.id, &data).await {
+                Ok(path) => path,
+                Err(e) => {
+                    tracing::warn!("Failed to store attachment {}: {}", server.id, e);
+                    continue;
+                }
+            };
+
+            let attachment = SessionAttachment {
+                id: server.id.clone(),
+                session_id: server.session_id.clone(),
+                user_id: server.user_id.clone(),
+                file_name: server.file_name.clone(),
+                mime_type: server.mime_type.clone(),
+                size: data.len() as i64,
+                stored_path,
+                sync_enabled: true,
+                server_ver: server.server_ver,
+                client_ver: server.client_ver,
+                is_dirty: false,
+                last_synced_at: Some(response.last_sync_at),
+                created_at: server.created_at,
+                updated_at: server.updated_at,
+            };
+            if let Err(e) = note_repo.save_server_attachment(&attachment) {
+                tracing::warn!("Failed to save attachment {}: {}", server.id, e);
+            }
+        }
+    }
+
     /// 应用 Pull 数据
     fn apply_pull_data(&self, response: &ServerSyncResponse, _user_id: &str) -> Result<()> {
         let session_repo = SshSessionRepository::new(self.pool.clone());
@@ -272,7 +464,23 @@ impl SyncService {
             }
         }
 
-        // 2. 应用用户资料
+        // 2. 应用会话分组
+        let group_repo = SessionGroupRepository::new(self.pool.clone());
+        for server_group in &response.session_groups {
+            if let Err(e) = group_repo.apply_server_group(server_group) {
+                tracing::warn!("Failed to apply session group {}: {}", server_group.id, e);
+            }
+        }
+
+        // 3. 应用会话笔记（附件在 apply_pull_attachments 中处理）
+        let note_repo = SessionNoteRepository::new(self.pool.clone());
+        for server_note in &response.session_notes {
+            if let Err(e) = note_repo.apply_server_note(server_note) {
+                tracing::warn!("Failed to apply session note {}: {}", server_note.session_id, e);
+            }
+        }
+
+        // 4. 应用用户资料
         if let Some(server_profile) = &response.user_profile {
             let profile_repo = crate::database::repositories::UserProfileRepository::new(self.pool.clone());
             // 转换 ServerUserProfile 为 UserProfile 并保存
@@ -296,6 +504,21 @@ impl SyncService {
             }
         }
 
+        // 更新分组服务器版本号
+        let group_repo = SessionGroupRepository::new(self.pool.clone());
+        for (id, server_ver) in &response.group_versions {
+            group_repo.set_server_ver(id, *server_ver, response.last_sync_at)?;
+        }
+
+        // 更新笔记和附件服务器版本号
+        let note_repo = SessionNoteRepository::new(self.pool.clone());
+        for (session_id, server_ver) in &response.note_versions {
+            note_repo.set_note_server_ver(session_id, *server_ver, response.last_sync_at)?;
+        }
+        for (id, server_ver) in &response.attachment_versions {
+            note_repo.set_attachment_server_ver(id, *server_ver, response.last_sync_at)?;
+        }
+
         // 更新同步状态
         let state_repo = SyncStateRepository::new(self.pool.clone());
         state_repo.update_conflict_count(user_id, response.conflicts.len() as i32)?;
@@ -321,7 +544,7 @@ impl SyncService {
         tracing::info!("Resolving conflict {} with strategy {:?}", conflict_id, strategy);
 
         // 获取 API 客户端
-        let api_client = self.get_api_client()?;
+        let api_client = self.get_api_client().await?;
 
         // 调用服务器 resolve-conflict API
         let request = ResolveConflictRequest {
@@ -360,7 +583,7 @@ impl SyncService {
             let deleted_session_ids = session_repo.get_deleted_sessions(&current_user.user_id)?;
 
             // 构建统一请求（不指定 last_sync_at，拉取所有数据）
-            let request = self.build_sync_request_with_options(
+            let mut request = self.build_sync_request_with_options(
                 &current_user.user_id,
                 None,
                 device_id,
@@ -368,12 +591,14 @@ impl SyncService {
                 None,
                 deleted_session_ids,
             )?;
+            request.restored_session_ids = session_repo.get_restored_sessions(&current_user.user_id)?;
 
             let (sync_response, _, sync_message) = api_client.sync(&request).await?;
 
             // 应用拉取的数据
             let ssh_sessions_len = sync_response.ssh_sessions.len();
             self.apply_pull_data(&sync_response, &current_user.user_id)?;
+            self.apply_pull_attachments(&sync_response).await;
 
             // 处理 Push 结果
             self.apply_push_result(&sync_response, &current_user.user_id)?;
@@ -0,0 +1,169 @@
+//! 录制视频缩略图
+//!
+//! 保存视频时调用系统的 ffmpeg 生成封面帧（`{视频名}.poster.jpg`），可选生成
+//! 几秒的动态预览（`{视频名}.preview.gif`），与视频放在同一目录。
+//! 未安装 ffmpeg 时跳过，列表中不返回缩略图。
+
+use serde::Serialize;
+use std::path::Path;
+use std::time::Duration;
+
+/// 单次 ffmpeg 调用的超时时间
+const FFMPEG_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 封面宽度（像素，高度按比例）
+const POSTER_WIDTH: u32 = 320;
+/// 动态预览的宽度、帧率和时长
+const PREVIEW_WIDTH: u32 = 240;
+const PREVIEW_FPS: u32 = 6;
+const PREVIEW_SECONDS: u32 = 4;
+
+/// 视频的缩略图文件名（相对于 recordings 目录，不存在时为 None）
+#[derive(Debug, Clone, Default, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Thumbnails {
+    pub poster: Option<String>,
+    pub preview: Option<String>,
+}
+
+fn file_stem(video_file: &str) -> &str {
+    Path::new(video_file)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or(video_file)
+}
+
+/// 封面文件名
+pub fn poster_file_name(video_file: &str) -> String {
+    format!("{}.poster.jpg", file_stem(video_file))
+}
+
+/// 动态预览文件名
+pub fn preview_file_name(video_file: &str) -> String {
+    format!("{}.preview.gif", file_stem(video_file))
+}
+
+/// 生成封面的 ffmpeg 参数：从开头的若干帧中挑选最有代表性的一帧
+fn poster_args(video: &Path, output: &Path) -> Vec<String> {
+    vec![
+        "-y".to_string(),
+        "-loglevel".to_string(),
+        "error".to_string(),
+        "-i".to_string(),
+        video.to_string_lossy().into_owned(),
+        "-vf".to_string(),
+        format!("thumbnail=50,scale={}:-2", POSTER_WIDTH),
+        "-frames:v".to_string(),
+        "1".to_string(),
+        output.to_string_lossy().into_owned(),
+    ]
+}
+
+/// 生成动态预览的 ffmpeg 参数：取开头几秒，降低帧率和尺寸
+fn preview_args(video: &Path, output: &Path) -> Vec<String> {
+    vec![
+        "-y".to_string(),
+        "-loglevel".to_string(),
+        "error".to_string(),
+        "-t".to_string(),
+        PREVIEW_SECONDS.to_string(),
+        "-i".to_string(),
+        video.to_string_lossy().into_owned(),
+        "-vf".to_string(),
+        format!("fps={},scale={}:-2:flags=lanczos", PREVIEW_FPS, PREVIEW_WIDTH),
+        "-loop".to_string(),
+        "0".to_string(),
+        output.to_string_lossy().into_owned(),
+    ]
+}
+
+/// 执行 ffmpeg，失败时返回错误说明
+pub(crate) async fn run_ffmpeg(args: &[String]) -> Result<(), String> {
+    let output = tokio::time::timeout(
+        FFMPEG_TIMEOUT,
+        tokio::process::Command::new("ffmpeg").args(args).output(),
+    )
+    .await
+    .map_err(|_| "ffmpeg timed out".to_string())?
+    .map_err(|e| format!("Failed to run ffmpeg: {}", e))?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!(
+            "ffmpeg failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
+
+pub struct ThumbnailService;
+
+impl ThumbnailService {
+    /// 为视频生成封面（以及可选的动态预览），失败时只记录日志
+    pub async fn generate(dir: &Path, video_file: &str, animated_preview: bool) -> Thumbnails {
+        let video = dir.join(video_file);
+
+        let poster = dir.join(poster_file_name(video_file));
+        if let Err(e) = run_ffmpeg(&poster_args(&video, &poster)).await {
+            tracing::warn!("[Thumbnail] Failed to generate poster for {}: {}", video_file, e);
+            return Thumbnails::default();
+        }
+
+        if animated_preview {
+            let preview = dir.join(preview_file_name(video_file));
+            if let Err(e) = run_ffmpeg(&preview_args(&video, &preview)).await {
+                tracing::warn!("[Thumbnail] Failed to generate preview for {}: {}", video_file, e);
+            }
+        }
+
+        let thumbnails = Self::existing(dir, video_file);
+        tracing::info!("[Thumbnail] Generated for {}: {:?}", video_file, thumbnails);
+        thumbnails
+    }
+
+    /// 视频已有的缩略图
+    pub fn existing(dir: &Path, video_file: &str) -> Thumbnails {
+        let present = |name: String| dir.join(&name).exists().then_some(name);
+        Thumbnails {
+            poster: present(poster_file_name(video_file)),
+            preview: present(preview_file_name(video_file)),
+        }
+    }
+
+    /// 删除视频的缩略图
+    pub fn remove(dir: &Path, video_file: &str) {
+        for name in [poster_file_name(video_file), preview_file_name(video_file)] {
+            let path = dir.join(&name);
+            if path.exists() {
+                let _ = std::fs::remove_file(&path);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_thumbnail_files() {
+        assert_eq!(poster_file_name("web_1700000000.webm"), "web_1700000000.poster.jpg");
+        assert_eq!(preview_file_name("web_1700000000.mp4"), "web_1700000000.preview.gif");
+
+        let args = poster_args(Path::new("/r/a.webm"), Path::new("/r/a.poster.jpg"));
+        assert_eq!(args.last().map(String::as_str), Some("/r/a.poster.jpg"));
+        assert!(args.windows(2).any(|w| w[0] == "-i" && w[1] == "/r/a.webm"));
+
+        let dir = std::env::temp_dir().join(format!("thumbs-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("a.poster.jpg"), b"jpg").unwrap();
+        assert_eq!(
+            ThumbnailService::existing(&dir, "a.webm"),
+            Thumbnails { poster: Some("a.poster.jpg".to_string()), preview: None }
+        );
+        ThumbnailService::remove(&dir, "a.webm");
+        assert_eq!(ThumbnailService::existing(&dir, "a.webm"), Thumbnails::default());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
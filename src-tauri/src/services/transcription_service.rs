@@ -0,0 +1,328 @@
+//! 录制语音转文字
+//!
+//! 从录制视频中提取音轨（麦克风与系统声音混合后写入视频），交给 Whisper 转写：
+//! 可以调用 OpenAI 兼容的 `/audio/transcriptions` 接口，也可以调用本地的 whisper.cpp。
+//! 转写结果按时间分段保存在录制元数据的 `transcript` 中，时间为相对录制开始的毫秒数。
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::Duration;
+
+/// 转写（包括本地模型推理和接口请求）的超时时间
+const TRANSCRIBE_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// 默认的 Whisper 接口地址和模型
+const DEFAULT_API_BASE_URL: &str = "https://api.openai.com/v1";
+const DEFAULT_API_MODEL: &str = "whisper-1";
+/// 默认的 whisper.cpp 可执行文件
+const DEFAULT_LOCAL_BINARY: &str = "whisper-cli";
+
+/// 转写文本片段
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptSegment {
+    /// 相对录制开始的时间（毫秒）
+    pub start: i64,
+    pub end: i64,
+    pub text: String,
+}
+
+/// 转写引擎
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "engine", rename_all = "camelCase")]
+pub enum TranscriptionEngine {
+    /// OpenAI 兼容的 Whisper 接口
+    #[serde(rename_all = "camelCase")]
+    Api {
+        api_key: String,
+        #[serde(default)]
+        base_url: Option<String>,
+        #[serde(default)]
+        model: Option<String>,
+    },
+    /// 本地 whisper.cpp（`binary` 默认为 whisper-cli）
+    #[serde(rename_all = "camelCase")]
+    Local {
+        #[serde(default)]
+        binary: Option<String>,
+        model_path: String,
+    },
+}
+
+/// 接口返回的 verbose_json
+#[derive(Debug, Deserialize)]
+struct ApiTranscription {
+    #[serde(default)]
+    text: String,
+    #[serde(default)]
+    segments: Vec<ApiSegment>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiSegment {
+    start: f64,
+    end: f64,
+    text: String,
+}
+
+/// whisper.cpp `-oj` 输出的 JSON
+#[derive(Debug, Deserialize)]
+struct LocalTranscription {
+    transcription: Vec<LocalSegment>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LocalSegment {
+    offsets: LocalOffsets,
+    text: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct LocalOffsets {
+    from: i64,
+    to: i64,
+}
+
+/// 去掉空白片段并整理文本
+fn clean_segments(segments: impl IntoIterator<Item = TranscriptSegment>) -> Vec<TranscriptSegment> {
+    segments
+        .into_iter()
+        .map(|s| TranscriptSegment { text: s.text.trim().to_string(), ..s })
+        .filter(|s| !s.text.is_empty())
+        .collect()
+}
+
+/// 解析接口返回的 verbose_json，没有分段时整段作为一个片段
+fn parse_api_response(body: &str, duration_ms: i64) -> Result<Vec<TranscriptSegment>, String> {
+    let response: ApiTranscription =
+        serde_json::from_str(body).map_err(|e| format!("Invalid transcription response: {}", e))?;
+    if response.segments.is_empty() {
+        return Ok(clean_segments([TranscriptSegment { start: 0, end: duration_ms, text: response.text }]));
+    }
+    Ok(clean_segments(response.segments.into_iter().map(|s| TranscriptSegment {
+        start: (s.start * 1000.0) as i64,
+        end: (s.end * 1000.0) as i64,
+        text: s.text,
+    })))
+}
+
+/// 解析 whisper.cpp 输出的 JSON
+fn parse_local_output(body: &str) -> Result<Vec<TranscriptSegment>, String> {
+    let output: LocalTranscription =
+        serde_json::from_str(body).map_err(|e| format!("Invalid whisper.cpp output: {}", e))?;
+    Ok(clean_segments(output.transcription.into_iter().map(|s| TranscriptSegment {
+        start: s.offsets.from,
+        end: s.offsets.to,
+        text: s.text,
+    })))
+}
+
+/// 在转写文本中查找关键词（不区分大小写），返回匹配的片段
+pub fn search_transcript<'a>(segments: &'a [TranscriptSegment], query: &str) -> Vec<&'a TranscriptSegment> {
+    let query = query.to_lowercase();
+    segments
+        .iter()
+        .filter(|s| s.text.to_lowercase().contains(&query))
+        .collect()
+}
+
+/// 提取音轨的 ffmpeg 参数：16kHz 单声道（Whisper 的输入格式）
+///
+/// 调用接口时编码为 Opus 以减小上传体积，本地模型使用 whisper.cpp 要求的 16 位 WAV
+fn extract_audio_args(video: &Path, output: &Path, compressed: bool) -> Vec<String> {
+    let mut args = vec![
+        "-y".to_string(),
+        "-loglevel".to_string(),
+        "error".to_string(),
+        "-i".to_string(),
+        video.to_string_lossy().into_owned(),
+        "-vn".to_string(),
+        "-ac".to_string(),
+        "1".to_string(),
+        "-ar".to_string(),
+        "16000".to_string(),
+    ];
+    if compressed {
+        args.extend(["-c:a", "libopus", "-b:a", "24k"].map(String::from));
+    } else {
+        args.extend(["-c:a", "pcm_s16le"].map(String::from));
+    }
+    args.push(output.to_string_lossy().into_owned());
+    args
+}
+
+/// 构造 multipart/form-data 请求体
+fn multipart_body(boundary: &str, fields: &[(&str, &str)], file_name: &str, file: &[u8]) -> Vec<u8> {
+    let mut body = Vec::with_capacity(file.len() + 1024);
+    for (name, value) in fields {
+        body.extend_from_slice(
+            format!("--{}\r\nContent-Disposition: form-data; name=\"{}\"\r\n\r\n{}\r\n", boundary, name, value).as_bytes(),
+        );
+    }
+    body.extend_from_slice(
+        format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{}\"\r\nContent-Type: audio/ogg\r\n\r\n",
+            boundary, file_name
+        )
+        .as_bytes(),
+    );
+    body.extend_from_slice(file);
+    body.extend_from_slice(format!("\r\n--{}--\r\n", boundary).as_bytes());
+    body
+}
+
+pub struct TranscriptionService;
+
+impl TranscriptionService {
+    /// 转写视频的音轨，`language` 为 ISO-639-1 代码（如 zh、en），为空时自动识别
+    pub async fn transcribe(
+        video: &Path,
+        engine: &TranscriptionEngine,
+        language: Option<&str>,
+        duration_ms: i64,
+    ) -> Result<Vec<TranscriptSegment>, String> {
+        let work_dir = std::env::temp_dir().join(format!("transcribe-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&work_dir).map_err(|e| format!("Failed to create temp dir: {}", e))?;
+
+        let result = Self::transcribe_in(&work_dir, video, engine, language, duration_ms).await;
+        let _ = std::fs::remove_dir_all(&work_dir);
+        result
+    }
+
+    async fn transcribe_in(
+        work_dir: &Path,
+        video: &Path,
+        engine: &TranscriptionEngine,
+        language: Option<&str>,
+        duration_ms: i64,
+    ) -> Result<Vec<TranscriptSegment>, String> {
+        let compressed = matches!(engine, TranscriptionEngine::Api { .. });
+        let audio = work_dir.join(if compressed { "audio.ogg" } else { "audio.wav" });
+        crate::services::thumbnail_service::run_ffmpeg(&extract_audio_args(video, &audio, compressed))
+            .await
+            .map_err(|e| format!("Failed to extract audio (the video may have no audio track): {}", e))?;
+
+        match engine {
+            TranscriptionEngine::Api { api_key, base_url, model } => {
+                let data = std::fs::read(&audio).map_err(|e| format!("Failed to read audio: {}", e))?;
+                Self::transcribe_api(api_key, base_url.as_deref(), model.as_deref(), language, &data, duration_ms).await
+            }
+            TranscriptionEngine::Local { binary, model_path } => {
+                Self::transcribe_local(binary.as_deref(), model_path, language, &audio, work_dir).await
+            }
+        }
+    }
+
+    async fn transcribe_api(
+        api_key: &str,
+        base_url: Option<&str>,
+        model: Option<&str>,
+        language: Option<&str>,
+        audio: &[u8],
+        duration_ms: i64,
+    ) -> Result<Vec<TranscriptSegment>, String> {
+        let base_url = base_url.filter(|u| !u.trim().is_empty()).unwrap_or(DEFAULT_API_BASE_URL);
+        let url = format!("{}/audio/transcriptions", base_url.trim_end_matches('/'));
+
+        let mut fields = vec![
+            ("model", model.filter(|m| !m.trim().is_empty()).unwrap_or(DEFAULT_API_MODEL)),
+            ("response_format", "verbose_json"),
+            ("timestamp_granularities[]", "segment"),
+        ];
+        if let Some(language) = language {
+            fields.push(("language", language));
+        }
+        let boundary = format!("----transcribe{}", uuid::Uuid::new_v4().simple());
+        let body = multipart_body(&boundary, &fields, "audio.ogg", audio);
+
+        let response = reqwest::Client::new()
+            .post(&url)
+            .bearer_auth(api_key)
+            .header("Content-Type", format!("multipart/form-data; boundary={}", boundary))
+            .body(body)
+            .timeout(TRANSCRIBE_TIMEOUT)
+            .send()
+            .await
+            .map_err(|e| format!("Transcription request failed: {}", e))?;
+
+        let status = response.status();
+        let text = response
+            .text()
+            .await
+            .map_err(|e| format!("Failed to read transcription response: {}", e))?;
+        if !status.is_success() {
+            return Err(format!("Transcription API error ({}): {}", status, text));
+        }
+        parse_api_response(&text, duration_ms)
+    }
+
+    async fn transcribe_local(
+        binary: Option<&str>,
+        model_path: &str,
+        language: Option<&str>,
+        audio: &Path,
+        work_dir: &Path,
+    ) -> Result<Vec<TranscriptSegment>, String> {
+        let output_base = work_dir.join("transcript");
+        let binary = binary.filter(|b| !b.trim().is_empty()).unwrap_or(DEFAULT_LOCAL_BINARY);
+
+        let mut command = tokio::process::Command::new(binary);
+        command
+            .arg("-m")
+            .arg(model_path)
+            .arg("-f")
+            .arg(audio)
+            .arg("-l")
+            .arg(language.unwrap_or("auto"))
+            .arg("-oj")
+            .arg("-of")
+            .arg(&output_base);
+
+        let output = tokio::time::timeout(TRANSCRIBE_TIMEOUT, command.output())
+            .await
+            .map_err(|_| "whisper.cpp timed out".to_string())?
+            .map_err(|e| format!("Failed to run {}: {}", binary, e))?;
+        if !output.status.success() {
+            return Err(format!(
+                "whisper.cpp failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+
+        let json = std::fs::read_to_string(output_base.with_extension("json"))
+            .map_err(|e| format!("Failed to read whisper.cpp output: {}", e))?;
+        parse_local_output(&json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_search() {
+        let api = r#"{"text":"ls then deploy","segments":[
+            {"start":0.0,"end":1.5,"text":" list the files "},
+            {"start":1.5,"end":3.2,"text":"  "},
+            {"start":3.2,"end":6.0,"text":" now Deploy it"}]}"#;
+        let segments = parse_api_response(api, 6_000).unwrap();
+        assert_eq!(segments.len(), 2);
+        assert_eq!((segments[1].start, segments[1].end), (3_200, 6_000));
+        assert_eq!(segments[0].text, "list the files");
+
+        let plain = parse_api_response(r#"{"text":" hello "}"#, 4_000).unwrap();
+        assert_eq!(plain, vec![TranscriptSegment { start: 0, end: 4_000, text: "hello".to_string() }]);
+
+        let local = r#"{"transcription":[{"timestamps":{},"offsets":{"from":0,"to":2000},"text":" 重启服务"}]}"#;
+        assert_eq!(parse_local_output(local).unwrap()[0].text, "重启服务");
+
+        let hits = search_transcript(&segments, "deploy");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].start, 3_200);
+
+        let body = multipart_body("b", &[("model", "whisper-1")], "audio.ogg", b"OggS");
+        let body = String::from_utf8(body).unwrap();
+        assert!(body.starts_with("--b\r\nContent-Disposition: form-data; name=\"model\"\r\n\r\nwhisper-1\r\n"));
+        assert!(body.ends_with("OggS\r\n--b--\r\n"));
+    }
+}
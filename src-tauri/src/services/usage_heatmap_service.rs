@@ -0,0 +1,133 @@
+//! 使用热力图
+//!
+//! 把断开连接时写入的使用记录按本地时间拆分到“日期 × 小时”的分桶中：
+//! 连接数计入连接开始的小时，连接时长按实际跨越的小时分摊。
+//! 同时按主机汇总，用于展示“在哪里工作”。
+
+use crate::models::connection_stats::{ConnectionStatsRecord, HeatmapBucket, HeatmapHost};
+use chrono::{NaiveDate, TimeZone, Timelike};
+use std::collections::{BTreeMap, HashMap};
+
+/// 默认统计的天数
+pub const DEFAULT_HEATMAP_DAYS: u32 = 365;
+
+/// 最多统计的天数
+pub const MAX_HEATMAP_DAYS: u32 = 366;
+
+/// 返回的主机数量
+const MAX_HEATMAP_HOSTS: usize = 10;
+
+/// 统计 `records` 在 `from` 及之后的使用量
+pub fn aggregate<Tz: TimeZone>(
+    records: &[ConnectionStatsRecord],
+    tz: &Tz,
+    from: NaiveDate,
+) -> (Vec<HeatmapBucket>, Vec<HeatmapHost>) {
+    let mut buckets: BTreeMap<(NaiveDate, u8), (i64, i64)> = BTreeMap::new();
+    let mut hosts: HashMap<&str, HeatmapHost> = HashMap::new();
+
+    for record in records {
+        let start = record.connected_at;
+        let end = record.disconnected_at.max(start);
+        let mut at = start;
+        let mut first = true;
+
+        loop {
+            let Some(local) = tz.timestamp_opt(at, 0).single() else {
+                break;
+            };
+            let into_hour = (local.minute() * 60 + local.second()) as i64;
+            let next = (at + 3600 - into_hour).min(end);
+            let date = local.date_naive();
+
+            if date >= from {
+                let connections = if first { 1 } else { 0 };
+                let bucket = buckets.entry((date, local.hour() as u8)).or_default();
+                bucket.0 += connections;
+                bucket.1 += next - at;
+
+                let host = hosts.entry(record.host_key.as_str()).or_insert_with(|| HeatmapHost {
+                    host_key: record.host_key.clone(),
+                    session_name: record.session_name.clone(),
+                    connections: 0,
+                    duration_secs: 0,
+                });
+                host.connections += connections;
+                host.duration_secs += next - at;
+            }
+
+            first = false;
+            if next >= end {
+                break;
+            }
+            at = next;
+        }
+    }
+
+    let buckets = buckets
+        .into_iter()
+        .map(|((date, hour), (connections, duration_secs))| HeatmapBucket {
+            date: date.format("%Y-%m-%d").to_string(),
+            hour,
+            connections,
+            duration_secs,
+        })
+        .collect();
+
+    let mut hosts: Vec<HeatmapHost> = hosts.into_values().collect();
+    hosts.sort_by(|a, b| b.duration_secs.cmp(&a.duration_secs).then_with(|| a.host_key.cmp(&b.host_key)));
+    hosts.truncate(MAX_HEATMAP_HOSTS);
+
+    (buckets, hosts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::FixedOffset;
+
+    fn record(host_key: &str, connected_at: i64, disconnected_at: i64) -> ConnectionStatsRecord {
+        ConnectionStatsRecord {
+            id: 0,
+            user_id: "u".to_string(),
+            session_id: "s".to_string(),
+            connection_id: "c".to_string(),
+            session_name: host_key.to_string(),
+            host_key: host_key.to_string(),
+            connected_at,
+            disconnected_at,
+            bytes_sent: 0,
+            bytes_received: 0,
+        }
+    }
+
+    #[test]
+    fn test_aggregate_splits_hours() {
+        // UTC+8：2024-03-01 23:30 ~ 2024-03-02 01:15
+        let tz = FixedOffset::east_opt(8 * 3600).unwrap();
+        let start = tz.with_ymd_and_hms(2024, 3, 1, 23, 30, 0).unwrap().timestamp();
+        let records = vec![
+            record("root@a:22", start, start + 6300),
+            record("root@b:22", start + 3600, start + 3600),
+        ];
+
+        let from = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        let (buckets, hosts) = aggregate(&records, &tz, from);
+        let summary: Vec<_> = buckets
+            .iter()
+            .map(|b| (b.date.as_str(), b.hour, b.connections, b.duration_secs))
+            .collect();
+        assert_eq!(
+            summary,
+            [("2024-03-01", 23, 1, 1800), ("2024-03-02", 0, 1, 3600), ("2024-03-02", 1, 0, 900)]
+        );
+        assert_eq!(hosts[0].host_key, "root@a:22");
+        assert_eq!(hosts[0].duration_secs, 6300);
+        assert_eq!(hosts[1].connections, 1);
+
+        // 起始日期之前的部分不计入，连接数也不计入
+        let (buckets, hosts) = aggregate(&records[..1], &tz, from.succ_opt().unwrap());
+        assert_eq!(buckets.iter().map(|b| b.connections).sum::<i64>(), 0);
+        assert_eq!(hosts[0].duration_secs, 4500);
+    }
+}
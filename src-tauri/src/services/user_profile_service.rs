@@ -27,10 +27,10 @@ impl UserProfileService {
     }
 
     /// 从全局状态获取或创建 API 客户端
-    fn get_api_client(&self) -> Result<ApiClient> {
+    async fn get_api_client(&self) -> Result<ApiClient> {
         // 如果有全局状态，优先使用
         if let Some(state) = &self.api_client_state {
-            return state.get_client();
+            return state.wait_client().await;
         }
 
         // 没有全局状态，返回错误（调用方应根据需要创建临时客户端）
@@ -73,7 +73,7 @@ impl UserProfileService {
         }
 
         // 本地不存在，从服务器获取
-        let api_client = match self.get_api_client() {
+        let api_client = match self.get_api_client().await {
             Ok(client) => client,
             Err(_) => self.create_temp_client(&current_user)?,
         };
@@ -108,7 +108,7 @@ impl UserProfileService {
     pub async fn delete_profile(&self) -> Result<()> {
         let current_user = self.get_current_user()?;
 
-        let api_client = match self.get_api_client() {
+        let api_client = match self.get_api_client().await {
             Ok(client) => client,
             Err(_) => self.create_temp_client(&current_user)?,
         };
@@ -0,0 +1,341 @@
+//! Webhook 通知
+//!
+//! 传输完成、用户钩子执行、主机离线（心跳超时）时向用户配置的 URL POST 一个 JSON。
+//! 配置了密钥的 webhook 会附带 HMAC-SHA256 签名，接收方可据此校验来源；
+//! 网络错误、5xx 和 429 会按退避间隔重试。
+
+use anyhow::Result;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::fs;
+use std::time::{Duration, Instant};
+
+use crate::config::Storage;
+
+/// Webhook 配置文件名（位于应用存储目录）
+const WEBHOOKS_CONFIG_FILE: &str = "webhooks.json";
+
+/// 单次请求超时时间
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 最多尝试次数（含第一次）
+const MAX_ATTEMPTS: u32 = 3;
+
+/// 第一次重试前的等待时间，之后每次翻倍
+const RETRY_BASE_DELAY: Duration = Duration::from_secs(2);
+
+/// 签名请求头，值为 `sha256=<hex>`
+pub const SIGNATURE_HEADER: &str = "X-SSH-Terminal-Signature";
+
+/// 签名时间戳请求头（秒），参与签名以防止重放
+pub const TIMESTAMP_HEADER: &str = "X-SSH-Terminal-Timestamp";
+
+/// 事件名请求头
+pub const EVENT_HEADER: &str = "X-SSH-Terminal-Event";
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Webhook 事件
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum WebhookEvent {
+    /// SFTP 上传/下载完成
+    TransferComplete,
+    /// 用户钩子已执行
+    HookTriggered,
+    /// 主机不再响应心跳
+    HostOffline,
+}
+
+impl WebhookEvent {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WebhookEvent::TransferComplete => "transfer-complete",
+            WebhookEvent::HookTriggered => "hook-triggered",
+            WebhookEvent::HostOffline => "host-offline",
+        }
+    }
+}
+
+/// 请求体格式
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum WebhookFormat {
+    /// `{ "event", "timestamp", "data" }`
+    #[default]
+    Json,
+    /// Slack/Mattermost 等兼容的 incoming webhook：`{ "text" }`
+    Slack,
+}
+
+/// Webhook 配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookConfig {
+    pub id: String,
+    pub name: String,
+    pub url: String,
+    /// 订阅的事件
+    pub events: Vec<WebhookEvent>,
+    #[serde(default)]
+    pub format: WebhookFormat,
+    /// 签名密钥，为空时不签名
+    #[serde(default)]
+    pub secret: Option<String>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// 投递结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookDeliveryResult {
+    pub webhook_id: String,
+    /// 最后一次响应的状态码（请求失败时为 None）
+    pub status: Option<u16>,
+    pub success: bool,
+    pub attempts: u32,
+    pub error: Option<String>,
+    pub duration_ms: u64,
+}
+
+/// Webhook 通知服务
+pub struct WebhookService;
+
+impl WebhookService {
+    /// 加载所有 webhook
+    pub fn load_webhooks() -> Result<Vec<WebhookConfig>> {
+        let path = Storage::get_app_storage_dir()?.join(WEBHOOKS_CONFIG_FILE);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = fs::read_to_string(&path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// 保存所有 webhook（原子写入）
+    pub fn save_webhooks(webhooks: &[WebhookConfig]) -> Result<()> {
+        let dir = Storage::get_app_storage_dir()?;
+        fs::create_dir_all(&dir)?;
+
+        let path = dir.join(WEBHOOKS_CONFIG_FILE);
+        let temp_path = path.with_extension("tmp");
+        fs::write(&temp_path, serde_json::to_string_pretty(webhooks)?)?;
+        fs::rename(&temp_path, &path)?;
+
+        Ok(())
+    }
+
+    /// 发送通知（后台执行，不阻塞调用方）
+    pub fn notify(event: WebhookEvent, data: serde_json::Value) {
+        tauri::async_runtime::spawn(async move {
+            let webhooks = match Self::load_webhooks() {
+                Ok(webhooks) => webhooks,
+                Err(e) => {
+                    tracing::warn!("Failed to load webhooks: {}", e);
+                    return;
+                }
+            };
+
+            let webhooks: Vec<_> = webhooks
+                .into_iter()
+                .filter(|w| w.enabled && w.events.contains(&event))
+                .collect();
+            if webhooks.is_empty() {
+                return;
+            }
+
+            let client = match build_client() {
+                Ok(client) => client,
+                Err(e) => {
+                    tracing::warn!("Failed to create webhook client: {}", e);
+                    return;
+                }
+            };
+            let timestamp = chrono::Utc::now().timestamp_millis();
+
+            for webhook in webhooks {
+                let result = Self::deliver(&client, &webhook, event, &data, timestamp).await;
+                if result.success {
+                    tracing::info!("Webhook '{}' ({}) delivered after {} attempt(s)", webhook.name, event.as_str(), result.attempts);
+                } else {
+                    tracing::warn!(
+                        "Webhook '{}' ({}) failed after {} attempt(s): {}",
+                        webhook.name,
+                        event.as_str(),
+                        result.attempts,
+                        result.error.as_deref().unwrap_or("unknown error")
+                    );
+                }
+            }
+        });
+    }
+
+    /// 发送单个 webhook，失败时按退避间隔重试
+    pub async fn deliver(
+        client: &reqwest::Client,
+        webhook: &WebhookConfig,
+        event: WebhookEvent,
+        data: &serde_json::Value,
+        timestamp: i64,
+    ) -> WebhookDeliveryResult {
+        let started = Instant::now();
+        let body = build_body(webhook.format, event, data, timestamp).to_string();
+        let timestamp_secs = (timestamp / 1000).to_string();
+
+        let mut status = None;
+        let mut error = None;
+        let mut attempts = 0;
+
+        while attempts < MAX_ATTEMPTS {
+            if attempts > 0 {
+                tokio::time::sleep(RETRY_BASE_DELAY * 2u32.pow(attempts - 1)).await;
+            }
+            attempts += 1;
+
+            let mut request = client
+                .post(&webhook.url)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .header(EVENT_HEADER, event.as_str())
+                .header(TIMESTAMP_HEADER, &timestamp_secs);
+            if let Some(secret) = webhook.secret.as_deref().filter(|s| !s.is_empty()) {
+                request = request.header(SIGNATURE_HEADER, sign(secret, &timestamp_secs, &body));
+            }
+
+            match request.body(body.clone()).send().await {
+                Ok(response) if response.status().is_success() => {
+                    return WebhookDeliveryResult {
+                        webhook_id: webhook.id.clone(),
+                        status: Some(response.status().as_u16()),
+                        success: true,
+                        attempts,
+                        error: None,
+                        duration_ms: started.elapsed().as_millis() as u64,
+                    };
+                }
+                Ok(response) => {
+                    let code = response.status().as_u16();
+                    status = Some(code);
+                    error = Some(format!("HTTP {}", code));
+                    if !is_retryable_status(code) {
+                        break;
+                    }
+                }
+                Err(e) => {
+                    status = None;
+                    error = Some(e.to_string());
+                }
+            }
+        }
+
+        WebhookDeliveryResult {
+            webhook_id: webhook.id.clone(),
+            status,
+            success: false,
+            attempts,
+            error,
+            duration_ms: started.elapsed().as_millis() as u64,
+        }
+    }
+}
+
+/// 创建发送 webhook 使用的 HTTP 客户端
+pub fn build_client() -> Result<reqwest::Client> {
+    Ok(reqwest::Client::builder().timeout(REQUEST_TIMEOUT).build()?)
+}
+
+/// 构造请求体
+fn build_body(format: WebhookFormat, event: WebhookEvent, data: &serde_json::Value, timestamp: i64) -> serde_json::Value {
+    match format {
+        WebhookFormat::Json => serde_json::json!({
+            "event": event.as_str(),
+            "timestamp": timestamp,
+            "data": data,
+        }),
+        WebhookFormat::Slack => serde_json::json!({ "text": summarize(event, data) }),
+    }
+}
+
+/// 生成一行可读的事件摘要（Slack 格式使用）
+fn summarize(event: WebhookEvent, data: &serde_json::Value) -> String {
+    let field = |key: &str| data.get(key).and_then(|v| v.as_str()).unwrap_or("?").to_string();
+    match event {
+        WebhookEvent::TransferComplete => format!(
+            "SSH Terminal: {} finished — {} → {} ({} bytes, {} file(s))",
+            field("direction"),
+            field("localPath"),
+            field("remotePath"),
+            data.get("bytesTransferred").and_then(|v| v.as_i64()).unwrap_or(0),
+            data.get("filesCompleted").and_then(|v| v.as_i64()).unwrap_or(0),
+        ),
+        WebhookEvent::HookTriggered => format!(
+            "SSH Terminal: hook '{}' ran on {} (exit code {})",
+            field("hookName"),
+            field("hookEvent"),
+            data.get("exitCode").and_then(|v| v.as_i64()).map_or("none".to_string(), |c| c.to_string()),
+        ),
+        WebhookEvent::HostOffline => format!(
+            "SSH Terminal: {} ({}@{}) stopped responding",
+            field("name"),
+            field("username"),
+            field("host"),
+        ),
+    }
+}
+
+/// 计算签名：`sha256=hex(HMAC-SHA256(secret, "<timestamp>.<body>"))`
+pub fn sign(secret: &str, timestamp: &str, body: &str) -> String {
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(timestamp.as_bytes());
+    mac.update(b".");
+    mac.update(body.as_bytes());
+    let digest = mac.finalize().into_bytes();
+    format!("sha256={}", digest.iter().map(|b| format!("{:02x}", b)).collect::<String>())
+}
+
+/// 服务端错误和限流值得重试，其他 4xx 重试也不会成功
+fn is_retryable_status(status: u16) -> bool {
+    status == 429 || status >= 500
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign() {
+        // echo -n '1700000000.{}' | openssl dgst -sha256 -hmac secret
+        assert_eq!(
+            sign("secret", "1700000000", "{}"),
+            "sha256=b8569b78799ff9e3cbff0fc2d63a33a2b57f3282abd07c37ae5e8e7d79a5f163"
+        );
+        assert_ne!(sign("secret", "1700000001", "{}"), sign("secret", "1700000000", "{}"));
+    }
+
+    #[test]
+    fn test_retryable_status() {
+        assert!(is_retryable_status(500));
+        assert!(is_retryable_status(503));
+        assert!(is_retryable_status(429));
+        assert!(!is_retryable_status(400));
+        assert!(!is_retryable_status(404));
+    }
+
+    #[test]
+    fn test_build_body() {
+        let data = serde_json::json!({ "name": "prod", "username": "root", "host": "10.0.0.1" });
+        let body = build_body(WebhookFormat::Json, WebhookEvent::HostOffline, &data, 42);
+        assert_eq!(body["event"], "host-offline");
+        assert_eq!(body["timestamp"], 42);
+        assert_eq!(body["data"]["host"], "10.0.0.1");
+
+        let body = build_body(WebhookFormat::Slack, WebhookEvent::HostOffline, &data, 42);
+        assert_eq!(body["text"], "SSH Terminal: prod (root@10.0.0.1) stopped responding");
+    }
+}
@@ -0,0 +1,154 @@
+//! 远程用户和用户组
+//!
+//! 通过 exec 读取 `getent passwd` / `getent group`（包含 LDAP、NIS 等来源），
+//! 不支持 getent 时回退到 /etc/passwd 和 /etc/group。结果按连接缓存，用于 chown
+//! 选择器和主机信息面板。
+
+use serde::Serialize;
+
+/// passwd 与 group 输出之间的分隔行
+const GROUP_SEPARATOR: &str = "--- ssh-terminal groups ---";
+
+/// 普通用户的最小 UID（大多数发行版的 UID_MIN）
+const MIN_REGULAR_UID: u32 = 1000;
+
+/// 远程用户
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteUser {
+    pub name: String,
+    pub uid: u32,
+    pub gid: u32,
+    /// GECOS 字段（通常为全名）
+    pub comment: String,
+    pub home: String,
+    pub shell: String,
+    /// 系统账户（UID 小于 1000 且不是 root，或 nobody）
+    pub is_system: bool,
+    /// 登录 shell 为 nologin/false，不能交互登录
+    pub can_login: bool,
+}
+
+/// 远程用户组
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteGroup {
+    pub name: String,
+    pub gid: u32,
+    /// 附加成员（不含以该组为主组的用户）
+    pub members: Vec<String>,
+}
+
+/// 远程主机的用户和用户组
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteAccounts {
+    pub users: Vec<RemoteUser>,
+    pub groups: Vec<RemoteGroup>,
+    pub collected_at: i64,
+}
+
+/// 采集命令
+pub fn build_command() -> String {
+    format!(
+        "(getent passwd 2>/dev/null || cat /etc/passwd); echo '{}'; (getent group 2>/dev/null || cat /etc/group)",
+        GROUP_SEPARATOR
+    )
+}
+
+/// 解析采集命令的输出，按 UID/GID 排序并去重（getent 可能返回重复条目）
+pub fn parse_accounts(output: &str) -> RemoteAccounts {
+    let (passwd, group) = output.split_once(GROUP_SEPARATOR).unwrap_or((output, ""));
+
+    let mut users: Vec<RemoteUser> = passwd.lines().filter_map(parse_passwd_line).collect();
+    users.sort_by(|a, b| a.uid.cmp(&b.uid).then_with(|| a.name.cmp(&b.name)));
+    users.dedup_by(|a, b| a.name == b.name);
+
+    let mut groups: Vec<RemoteGroup> = group.lines().filter_map(parse_group_line).collect();
+    groups.sort_by(|a, b| a.gid.cmp(&b.gid).then_with(|| a.name.cmp(&b.name)));
+    groups.dedup_by(|a, b| a.name == b.name);
+
+    RemoteAccounts {
+        users,
+        groups,
+        collected_at: chrono::Utc::now().timestamp(),
+    }
+}
+
+/// `name:x:uid:gid:comment:home:shell`
+fn parse_passwd_line(line: &str) -> Option<RemoteUser> {
+    let fields: Vec<&str> = line.trim().split(':').collect();
+    if fields.len() < 7 || fields[0].is_empty() || fields[0].starts_with('#') {
+        return None;
+    }
+    let uid: u32 = fields[2].parse().ok()?;
+    let shell = fields[6].to_string();
+
+    Some(RemoteUser {
+        name: fields[0].to_string(),
+        uid,
+        gid: fields[3].parse().ok()?,
+        comment: fields[4].split(',').next().unwrap_or_default().to_string(),
+        home: fields[5].to_string(),
+        is_system: (uid != 0 && uid < MIN_REGULAR_UID) || fields[0] == "nobody",
+        can_login: !(shell.is_empty() || shell.ends_with("nologin") || shell.ends_with("/false")),
+        shell,
+    })
+}
+
+/// `name:x:gid:member1,member2`
+fn parse_group_line(line: &str) -> Option<RemoteGroup> {
+    let fields: Vec<&str> = line.trim().split(':').collect();
+    if fields.len() < 4 || fields[0].is_empty() || fields[0].starts_with('#') {
+        return None;
+    }
+
+    Some(RemoteGroup {
+        name: fields[0].to_string(),
+        gid: fields[2].parse().ok()?,
+        members: fields[3]
+            .split(',')
+            .map(str::trim)
+            .filter(|m| !m.is_empty())
+            .map(str::to_string)
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_accounts() {
+        let output = format!(
+            "root:x:0:0:root:/root:/bin/bash\n\
+             alice:x:1000:1000:Alice Liddell,,,:/home/alice:/bin/zsh\n\
+             daemon:x:1:1:daemon:/usr/sbin:/usr/sbin/nologin\n\
+             nobody:x:65534:65534:nobody:/nonexistent:/usr/sbin/nologin\n\
+             broken line\n\
+             alice:x:1000:1000:Alice Liddell,,,:/home/alice:/bin/zsh\n\
+             {}\n\
+             root:x:0:\n\
+             sudo:x:27:alice, bob\n\
+             alice:x:1000:\n",
+            GROUP_SEPARATOR
+        );
+        let accounts = parse_accounts(&output);
+
+        let names: Vec<&str> = accounts.users.iter().map(|u| u.name.as_str()).collect();
+        assert_eq!(names, vec!["root", "daemon", "alice", "nobody"]);
+
+        let alice = &accounts.users[2];
+        assert_eq!(alice.comment, "Alice Liddell");
+        assert!(!alice.is_system && alice.can_login);
+        assert!(accounts.users[1].is_system && !accounts.users[1].can_login);
+        assert!(!accounts.users[0].is_system);
+        assert!(accounts.users[3].is_system);
+
+        assert_eq!(accounts.groups.len(), 3);
+        assert_eq!(accounts.groups[1].name, "sudo");
+        assert_eq!(accounts.groups[1].members, vec!["alice", "bob"]);
+        assert!(accounts.groups[0].members.is_empty());
+    }
+}
@@ -0,0 +1,188 @@
+//! 远程文件权限审计
+//!
+//! 通过 exec channel 在远程执行 `find`，找出全局可写的文件和目录（不含带 sticky 位的目录，
+//! 如 /tmp）以及 setuid/setgid 程序，并按风险等级汇总。依赖 GNU find 的 `-printf`。
+
+use crate::utils::shell::shell_quote;
+use serde::Serialize;
+
+/// 默认最多返回的条目数
+pub const DEFAULT_AUDIT_LIMIT: usize = 1000;
+
+/// 风险类型
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum PermissionRisk {
+    /// 全局可写且没有 sticky 位的目录（如 777）
+    WorldWritableDir,
+    WorldWritableFile,
+    Setuid,
+    Setgid,
+}
+
+impl PermissionRisk {
+    fn severity(&self) -> RiskSeverity {
+        match self {
+            PermissionRisk::WorldWritableDir => RiskSeverity::High,
+            PermissionRisk::WorldWritableFile | PermissionRisk::Setuid => RiskSeverity::Medium,
+            PermissionRisk::Setgid => RiskSeverity::Low,
+        }
+    }
+}
+
+/// 风险等级
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "camelCase")]
+pub enum RiskSeverity {
+    Low,
+    Medium,
+    High,
+}
+
+/// 单个有风险的文件或目录
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PermissionFinding {
+    pub path: String,
+    pub is_dir: bool,
+    /// 八进制权限（如 "4755"）
+    pub mode: String,
+    pub owner: String,
+    pub group: String,
+    pub size: u64,
+    pub risks: Vec<PermissionRisk>,
+    /// 各项风险中的最高等级；setuid 程序同时全局可写时提升为高
+    pub severity: RiskSeverity,
+}
+
+/// 审计报告
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PermissionAuditReport {
+    pub path: String,
+    pub scanned_at: i64,
+    pub findings: Vec<PermissionFinding>,
+    /// 结果超过上限被截断
+    pub truncated: bool,
+    pub world_writable_dirs: usize,
+    pub world_writable_files: usize,
+    pub setuid: usize,
+    pub setgid: usize,
+}
+
+/// 构建审计命令（多取一行用于判断是否截断）
+pub fn build_command(path: &str, limit: usize) -> String {
+    format!(
+        "find {} -xdev \\( -type f \\( -perm -0002 -o -perm -4000 -o -perm -2000 \\) \
+         -o -type d -perm -0002 ! -perm -1000 \\) \
+         -printf '%m\\t%y\\t%u\\t%g\\t%s\\t%p\\n' 2>/dev/null | head -n {}",
+        shell_quote(path),
+        limit + 1
+    )
+}
+
+/// 解析 find 输出，生成报告
+pub fn parse_report(path: &str, output: &str, limit: usize) -> PermissionAuditReport {
+    let mut findings: Vec<PermissionFinding> = output.lines().filter_map(parse_line).collect();
+    let truncated = findings.len() > limit;
+    findings.truncate(limit);
+    findings.sort_by(|a, b| b.severity.cmp(&a.severity).then_with(|| a.path.cmp(&b.path)));
+
+    let count = |risk: PermissionRisk| findings.iter().filter(|f| f.risks.contains(&risk)).count();
+    PermissionAuditReport {
+        path: path.to_string(),
+        scanned_at: chrono::Utc::now().timestamp(),
+        world_writable_dirs: count(PermissionRisk::WorldWritableDir),
+        world_writable_files: count(PermissionRisk::WorldWritableFile),
+        setuid: count(PermissionRisk::Setuid),
+        setgid: count(PermissionRisk::Setgid),
+        findings,
+        truncated,
+    }
+}
+
+fn parse_line(line: &str) -> Option<PermissionFinding> {
+    let mut fields = line.splitn(6, '\t');
+    let mode_text = fields.next()?;
+    let kind = fields.next()?;
+    let owner = fields.next()?.to_string();
+    let group = fields.next()?.to_string();
+    let size = fields.next()?.parse().unwrap_or(0);
+    let path = fields.next()?.to_string();
+
+    let mode = u32::from_str_radix(mode_text, 8).ok()?;
+    let is_dir = kind == "d";
+
+    let mut risks = Vec::new();
+    if mode & 0o002 != 0 {
+        risks.push(if is_dir { PermissionRisk::WorldWritableDir } else { PermissionRisk::WorldWritableFile });
+    }
+    if !is_dir && mode & 0o4000 != 0 {
+        risks.push(PermissionRisk::Setuid);
+    }
+    if !is_dir && mode & 0o2000 != 0 {
+        risks.push(PermissionRisk::Setgid);
+    }
+    if risks.is_empty() {
+        return None;
+    }
+
+    let mut severity = risks.iter().map(PermissionRisk::severity).max()?;
+    if risks.contains(&PermissionRisk::Setuid) && risks.contains(&PermissionRisk::WorldWritableFile) {
+        severity = RiskSeverity::High;
+    }
+
+    Some(PermissionFinding {
+        path,
+        is_dir,
+        mode: format!("{:04o}", mode),
+        owner,
+        group,
+        size,
+        risks,
+        severity,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_command_quotes_path() {
+        let command = build_command("/srv/it's", 10);
+        assert!(command.starts_with("find '/srv/it'\\''s' -xdev"));
+        assert!(command.ends_with("head -n 11"));
+    }
+
+    #[test]
+    fn test_parse_report() {
+        let output = "4755\tf\troot\troot\t54256\t/usr/bin/passwd\n\
+                      777\td\twww\twww\t4096\t/var/www/uploads\n\
+                      2755\tf\troot\tshadow\t100\t/usr/bin/chage\n\
+                      4777\tf\troot\troot\t10\t/opt/bad tool\n\
+                      garbage line\n";
+        let report = parse_report("/", output, 10);
+
+        assert!(!report.truncated);
+        assert_eq!(report.findings.len(), 4);
+        assert_eq!(report.world_writable_dirs, 1);
+        assert_eq!(report.world_writable_files, 1);
+        assert_eq!(report.setuid, 2);
+        assert_eq!(report.setgid, 1);
+
+        // 高风险排在前面
+        assert_eq!(report.findings[0].path, "/opt/bad tool");
+        assert_eq!(report.findings[0].severity, RiskSeverity::High);
+        assert_eq!(report.findings[1].mode, "0777");
+        assert_eq!(report.findings[3].severity, RiskSeverity::Low);
+    }
+
+    #[test]
+    fn test_parse_report_truncated() {
+        let output = "666\tf\tu\tg\t1\t/a\n666\tf\tu\tg\t1\t/b\n666\tf\tu\tg\t1\t/c\n";
+        let report = parse_report("/", output, 2);
+        assert!(report.truncated);
+        assert_eq!(report.findings.len(), 2);
+    }
+}
@@ -0,0 +1,138 @@
+//! 传输完整性校验
+//!
+//! 传输完成后分别计算本地和远程文件的 SHA-256：远程优先通过 exec 执行
+//! `sha256sum`（BusyBox、大多数 Linux）或 `shasum -a 256`（macOS、BSD），
+//! 两者都不可用或 exec 被禁用时由调用方回退为通过 SFTP 读取文件计算。
+//! 断点续传上传前也用同样的方式校验远程已有部分与本地文件开头是否一致。
+
+use crate::utils::shell::shell_quote;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio::io::AsyncReadExt;
+
+/// SHA-256 十六进制摘要长度
+const SHA256_HEX_LEN: usize = 64;
+
+/// 校验结果
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChecksumVerification {
+    pub local_sha256: String,
+    pub remote_sha256: String,
+    /// 两端摘要是否一致
+    pub matched: bool,
+}
+
+impl ChecksumVerification {
+    pub fn new(local_sha256: String, remote_sha256: String) -> Self {
+        let matched = local_sha256.eq_ignore_ascii_case(&remote_sha256);
+        Self { local_sha256, remote_sha256, matched }
+    }
+}
+
+/// 构造计算远程文件 SHA-256 的命令
+pub fn build_command(path: &str) -> String {
+    let path = shell_quote(path);
+    format!(
+        "sha256sum {path} 2>/dev/null || shasum -a 256 {path} 2>/dev/null",
+        path = path
+    )
+}
+
+/// 构造计算远程文件前 `len` 字节 SHA-256 的命令，用于断点续传前校验已上传的部分
+pub fn build_prefix_command(path: &str, len: u64) -> String {
+    let path = shell_quote(path);
+    format!(
+        "head -c {len} {path} 2>/dev/null | sha256sum 2>/dev/null || head -c {len} {path} 2>/dev/null | shasum -a 256 2>/dev/null",
+        len = len,
+        path = path
+    )
+}
+
+/// 从命令输出中取出摘要（`<hex>  <path>` 格式的第一个字段）
+pub fn parse_output(output: &str) -> Option<String> {
+    let digest = output.split_whitespace().next()?;
+    // BusyBox/coreutils 对含特殊字符的文件名会在摘要前加反斜杠
+    let digest = digest.trim_start_matches('\\');
+    (digest.len() == SHA256_HEX_LEN && digest.bytes().all(|b| b.is_ascii_hexdigit()))
+        .then(|| digest.to_ascii_lowercase())
+}
+
+/// 计算本地文件的 SHA-256
+pub async fn local_sha256(path: &str) -> std::io::Result<String> {
+    local_prefix_sha256(path, u64::MAX).await
+}
+
+/// 计算本地文件前 `len` 字节的 SHA-256
+pub async fn local_prefix_sha256(path: &str, len: u64) -> std::io::Result<String> {
+    let mut file = tokio::fs::File::open(path).await?.take(len);
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+    loop {
+        let n = file.read(&mut buffer).await?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buffer[..n]);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EMPTY_SHA256: &str = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+    #[test]
+    fn test_build_command() {
+        assert_eq!(
+            build_command("/tmp/it's.txt"),
+            "sha256sum '/tmp/it'\\''s.txt' 2>/dev/null || shasum -a 256 '/tmp/it'\\''s.txt' 2>/dev/null"
+        );
+    }
+
+    #[test]
+    fn test_build_prefix_command() {
+        assert_eq!(
+            build_prefix_command("/tmp/a b", 1024),
+            "head -c 1024 '/tmp/a b' 2>/dev/null | sha256sum 2>/dev/null || head -c 1024 '/tmp/a b' 2>/dev/null | shasum -a 256 2>/dev/null"
+        );
+    }
+
+    #[test]
+    fn test_parse_output() {
+        let upper = EMPTY_SHA256.to_ascii_uppercase();
+        assert_eq!(parse_output(&format!("{}  /tmp/a\n", EMPTY_SHA256)).as_deref(), Some(EMPTY_SHA256));
+        assert_eq!(parse_output(&format!("\\{}  /tmp/a\\nb\n", upper)).as_deref(), Some(EMPTY_SHA256));
+        assert_eq!(parse_output(""), None);
+        assert_eq!(parse_output("sha256sum: /tmp/a: No such file or directory"), None);
+    }
+
+    #[test]
+    fn test_verification_matched() {
+        assert!(ChecksumVerification::new(EMPTY_SHA256.to_string(), EMPTY_SHA256.to_ascii_uppercase()).matched);
+        assert!(!ChecksumVerification::new(EMPTY_SHA256.to_string(), "0".repeat(64)).matched);
+    }
+
+    #[tokio::test]
+    async fn test_local_sha256() {
+        let path = std::env::temp_dir().join(format!("checksum-test-{}", std::process::id()));
+        std::fs::write(&path, b"").unwrap();
+        let digest = local_sha256(path.to_str().unwrap()).await.unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(digest, EMPTY_SHA256);
+    }
+
+    #[tokio::test]
+    async fn test_local_prefix_sha256() {
+        let path = std::env::temp_dir().join(format!("checksum-prefix-test-{}", std::process::id()));
+        std::fs::write(&path, b"abcdef").unwrap();
+        let prefix = local_prefix_sha256(path.to_str().unwrap(), 0).await.unwrap();
+        let whole = local_prefix_sha256(path.to_str().unwrap(), 100).await.unwrap();
+        let full = local_sha256(path.to_str().unwrap()).await.unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(prefix, EMPTY_SHA256);
+        assert_eq!(whole, full);
+    }
+}
@@ -3,22 +3,42 @@
 //! 基于 russh_sftp::client::SftpSession 实现
 
 use crate::error::{Result, SSHError};
-use crate::sftp::{SftpFileInfo};
+use crate::sftp::{ConflictAction, ConflictPolicy, SftpFileInfo, SymlinkPolicy};
 use russh_sftp::client::SftpSession;
+use russh_sftp::protocol::OpenFlags;
+use std::io::SeekFrom;
 use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
 use tracing::{debug, error, info, warn};
 
 // 需要导入 Tauri 的 Event trait 来使用 emit 方法
 use tauri::Emitter;
 
+/// 直接读取（一次性返回整个文件）允许的最大文件大小
+///
+/// 超过该大小的文件需要通过分块流式读取，避免通过 IPC 传输过大的数据
+pub const MAX_DIRECT_READ_SIZE: u64 = 16 * 1024 * 1024;
+
+/// 流式读取时每个分块的大小
+pub const READ_CHUNK_SIZE: usize = 256 * 1024;
+
 /// SFTP 客户端
 ///
 /// 封装 russh_sftp::client::SftpSession，提供高级文件操作
 pub struct SftpClient {
     session: SftpSession,
     connection_id: String,
+    /// 共享 SSH 连接的租约，客户端存在期间连接保持打开
+    lease: Option<crate::ssh::manager::ChannelLease>,
+    /// 最近一次读写数据的时间（Unix 毫秒），供看门狗判断任务是否卡住
+    activity: Arc<AtomicU64>,
+}
+
+/// 当前 Unix 时间（毫秒）
+pub fn now_millis() -> u64 {
+    chrono::Utc::now().timestamp_millis() as u64
 }
 
 impl SftpClient {
@@ -30,9 +50,17 @@ impl SftpClient {
         Self {
             session,
             connection_id: "unknown".to_string(),
+            lease: None,
+            activity: Arc::new(AtomicU64::new(now_millis())),
         }
     }
 
+    /// 持有共享 SSH 连接的租约
+    pub fn with_lease(mut self, lease: crate::ssh::manager::ChannelLease) -> Self {
+        self.lease = Some(lease);
+        self
+    }
+
     /// 创建新的 SFTP 客户端
     ///
     /// # 参数
@@ -52,9 +80,34 @@ impl SftpClient {
         Ok(Self {
             session,
             connection_id,
+            lease: None,
+            activity: Arc::new(AtomicU64::new(now_millis())),
         })
     }
 
+    /// 最近一次读写数据的时间，任务执行期间由看门狗读取
+    pub fn activity(&self) -> Arc<AtomicU64> {
+        self.activity.clone()
+    }
+
+    /// 记录一次数据读写
+    pub fn touch(&self) {
+        self.activity.store(now_millis(), Ordering::Relaxed);
+    }
+
+    /// 所在的 SSH 连接（或途经的跳板机）是否已断开，无需与服务器往返
+    pub fn is_transport_closed(&self) -> bool {
+        self.lease.as_ref().is_some_and(|lease| lease.transport().is_closed())
+    }
+
+    /// 检查 SFTP 会话是否仍然可用
+    ///
+    /// 通过一次轻量的 realpath 请求确认底层 channel 没有失效，
+    /// 用于连接池复用客户端前的存活检查
+    pub async fn is_alive(&self) -> bool {
+        self.session.canonicalize(".").await.is_ok()
+    }
+
     /// 列出目录内容
     ///
     /// # 参数
@@ -67,6 +120,7 @@ impl SftpClient {
 
         let mut read_dir = self.session.read_dir(path).await
             .map_err(|e| SSHError::Ssh(format!("Failed to list directory '{}': {}", path, e)))?;
+        self.touch();
 
         let mut entries = Vec::new();
 
@@ -79,6 +133,9 @@ impl SftpClient {
 
                     file_info.name = entry.file_name();
                     file_info.path = format!("{}/{}", path.trim_end_matches('/'), entry.file_name());
+                    if file_info.is_symlink {
+                        self.resolve_symlink(&mut file_info).await;
+                    }
 
                     debug!("Found entry: {}", file_info.name);
                     entries.push(file_info);
@@ -94,6 +151,64 @@ impl SftpClient {
         Ok(entries)
     }
 
+    /// 读取符号链接的指向，并按链接目标填写目录标记和大小（悬空链接保持原样）
+    async fn resolve_symlink(&self, info: &mut SftpFileInfo) {
+        match self.session.read_link(info.path.as_str()).await {
+            Ok(target) => info.link_target = Some(target),
+            Err(e) => debug!("readlink '{}' failed: {}", info.path, e),
+        }
+        if let Ok(attrs) = self.session.metadata(info.path.as_str()).await {
+            info.is_dir = attrs.is_dir();
+            info.size = attrs.size.unwrap_or(info.size);
+        }
+    }
+
+    /// 解析路径中的符号链接和 `.`/`..`，返回服务器上的规范路径
+    pub async fn canonicalize(&self, path: &str) -> Result<String> {
+        self.session.canonicalize(path).await
+            .map_err(|e| SSHError::Ssh(format!("Failed to resolve '{}': {}", path, e)))
+    }
+
+    /// 读取符号链接指向的路径
+    pub async fn read_link(&self, path: &str) -> Result<String> {
+        self.session.read_link(path).await
+            .map_err(|e| SSHError::Ssh(format!("Failed to read link '{}': {}", path, e)))
+    }
+
+    /// 创建符号链接 `link_path`，指向 `target`
+    ///
+    /// OpenSSH 的 sftp-server 按与协议草案相反的顺序解析 SSH_FXP_SYMLINK 的两个路径，
+    /// 其他常见服务器也沿用了这一顺序，因此这里按 OpenSSH 的顺序发送。
+    /// `overwrite` 为 true 时先删除已存在的同名文件或链接
+    pub async fn create_symlink(&self, link_path: &str, target: &str, overwrite: bool) -> Result<()> {
+        debug!("Creating symlink: {} -> {}", link_path, target);
+        if overwrite && self.session.symlink_metadata(link_path).await.is_ok() {
+            self.session.remove_file(link_path).await
+                .map_err(|e| SSHError::Ssh(format!("Failed to replace '{}': {}", link_path, e)))?;
+        }
+        self.session.symlink(target, link_path).await
+            .map_err(|e| SSHError::Ssh(format!("Failed to create symlink '{}' -> '{}': {}", link_path, target, e)))
+    }
+
+    /// 获取路径信息
+    ///
+    /// # 返回
+    /// 路径存在时返回文件信息，不存在（或无法访问）时返回 None
+    pub async fn stat(&self, path: &str) -> Result<Option<SftpFileInfo>> {
+        match self.session.metadata(path).await {
+            Ok(attrs) => {
+                let mut file_info: SftpFileInfo = attrs.into();
+                file_info.name = path.trim_end_matches('/').rsplit('/').next().unwrap_or(path).to_string();
+                file_info.path = path.to_string();
+                Ok(Some(file_info))
+            }
+            Err(e) => {
+                debug!("stat '{}' failed: {}", path, e);
+                Ok(None)
+            }
+        }
+    }
+
     /// 创建目录
     ///
     /// # 参数
@@ -277,9 +392,23 @@ impl SftpClient {
     ///
     /// # 返回
     /// 文件内容的字节数组
+    ///
+    /// 文件大于 [`MAX_DIRECT_READ_SIZE`] 时返回错误，应改用 [`Self::read_file_chunked`]
     pub async fn read_file(&mut self, path: &str) -> Result<Vec<u8>> {
         debug!("Reading file: {}", path);
 
+        let size = self.session.metadata(path).await
+            .map_err(|e| SSHError::Ssh(format!("Failed to stat file '{}': {}", path, e)))?
+            .size
+            .unwrap_or(0);
+        if size > MAX_DIRECT_READ_SIZE {
+            warn!("Rejecting direct read of {} ({} bytes)", path, size);
+            return Err(SSHError::Io(format!(
+                "文件过大 ({} 字节)，超过直接读取上限 {} 字节，请使用流式读取",
+                size, MAX_DIRECT_READ_SIZE
+            )));
+        }
+
         let data = self.session.read(path).await
             .map_err(|e| SSHError::Ssh(format!("Failed to read file '{}': {}", path, e)))?;
 
@@ -287,6 +416,61 @@ impl SftpClient {
         Ok(data)
     }
 
+    /// 分块流式读取文件内容
+    ///
+    /// # 参数
+    /// - `path`: 文件路径
+    /// - `cancellation_token`: 取消令牌
+    /// - `on_chunk`: 每读取一个分块调用一次，参数为 (偏移量, 分块数据, 文件总大小)，
+    ///   分块数据直接转交所有权，调用方无需再复制
+    ///
+    /// # 返回
+    /// 读取的总字节数
+    pub async fn read_file_chunked<F>(
+        &self,
+        path: &str,
+        cancellation_token: &tokio_util::sync::CancellationToken,
+        mut on_chunk: F,
+    ) -> Result<u64>
+    where
+        F: FnMut(u64, Vec<u8>, u64) -> Result<()>,
+    {
+        debug!("Streaming file: {}", path);
+
+        let mut remote_file = self.session.open(path).await
+            .map_err(|e| SSHError::Ssh(format!("无法打开远程文件: {}", e)))?;
+
+        let file_size = remote_file.metadata().await
+            .map_err(|e| SSHError::Ssh(format!("无法获取文件元数据: {}", e)))?
+            .size.unwrap_or(0);
+
+        let mut offset = 0u64;
+
+        loop {
+            if cancellation_token.is_cancelled() {
+                return Err(SSHError::Io("读取已取消".to_string()));
+            }
+
+            // 每个分块使用新的缓冲区，读取后直接交给 on_chunk
+            let mut buffer = vec![0u8; READ_CHUNK_SIZE];
+            let n = remote_file.read(&mut buffer).await
+                .map_err(|e| SSHError::Ssh(format!("读取远程文件失败: {}", e)))?;
+
+            if n == 0 {
+                break; // EOF
+            }
+
+            self.touch();
+
+            buffer.truncate(n);
+            on_chunk(offset, buffer, file_size)?;
+            offset += n as u64;
+        }
+
+        debug!("Streamed {} bytes from {}", offset, path);
+        Ok(offset)
+    }
+
     /// 写入文件内容
     ///
     /// # 参数
@@ -378,6 +562,7 @@ impl SftpClient {
                 .map_err(|e| SSHError::Io(format!("Failed to write to local file: {}", e)))?;
 
             transferred += n as u64;
+            self.touch();
             progress_callback(transferred, file_size);
         }
 
@@ -455,6 +640,7 @@ impl SftpClient {
                 .map_err(|e| SSHError::Ssh(format!("Failed to write to remote file: {}", e)))?;
 
             transferred += n as u64;
+            self.touch();
             progress_callback(transferred, file_size);
         }
 
@@ -470,7 +656,7 @@ impl SftpClient {
     ///
     /// 如果目录不存在，递归创建父目录，然后创建目标目录
     #[allow(dead_code)]
-    fn ensure_dir_exists<'a>(&'a mut self, path: &'a str) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<()>> + Send + 'a>> {
+    fn ensure_dir_exists<'a>(&'a self, path: &'a str) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<()>> + Send + 'a>> {
         Box::pin(async move {
             info!("ensure_dir_exists called with path: '{}'", path);
 
@@ -546,13 +732,18 @@ impl SftpClient {
     /// - `cancellation_token`: 取消令牌
     /// - `progress_callback`: 进度回调函数 (transferred, total)
     /// - `skip_dir_check`: 是否跳过目录检查（批量上传时使用，提高性能）
+    /// - `resume`: 断点续传，远程文件已存在且不大于本地文件时从其末尾继续写入
+    ///
+    /// # 返回
+    /// 远程文件的最终大小（续传时包含已存在的部分）
     pub async fn upload_file_stream<F>(
-        &mut self,
+        &self,
         local_path: &str,
         remote_path: &str,
         cancellation_token: &tokio_util::sync::CancellationToken,
         progress_callback: F,
         skip_dir_check: bool,
+        resume: bool,
     ) -> Result<u64>
     where
         F: Fn(u64, u64), // (transferred, total)
@@ -585,13 +776,37 @@ impl SftpClient {
             }
         }
 
-        // 创建远程文件
-        let mut remote_file = self.session.create(remote_path).await
+        // 续传时以远程文件的当前大小作为起点；远程文件比本地文件大说明不是同一文件，重新上传
+        let offset = if resume {
+            match self.session.metadata(remote_path).await {
+                Ok(attrs) => attrs.size.filter(|size| *size <= file_size).unwrap_or(0),
+                Err(_) => 0,
+            }
+        } else {
+            0
+        };
+
+        // 创建远程文件（续传时保留已有内容）
+        let mut flags = OpenFlags::CREATE | OpenFlags::WRITE;
+        if offset == 0 {
+            flags |= OpenFlags::TRUNCATE;
+        }
+        let mut remote_file = self.session.open_with_flags(remote_path, flags).await
             .map_err(|e| SSHError::Ssh(format!("无法创建远程文件 '{}': {}", remote_path, e)))?;
 
+        if offset > 0 {
+            info!("Resuming upload of {} at offset {}", local_path, offset);
+            remote_file.seek(SeekFrom::Start(offset)).await
+                .map_err(|e| SSHError::Ssh(format!("无法定位远程文件 '{}': {}", remote_path, e)))?;
+            local_file.seek(SeekFrom::Start(offset)).await
+                .map_err(|e| SSHError::Io(format!("无法定位本地文件 '{}': {}", local_path, e)))?;
+            self.touch();
+            progress_callback(offset, file_size);
+        }
+
         // 分块读取和写入（64KB buffer）
         let mut buffer = vec![0u8; 64 * 1024];
-        let mut transferred = 0u64;
+        let mut transferred = offset;
 
         loop {
             // 检查是否被取消
@@ -617,6 +832,7 @@ impl SftpClient {
                 .map_err(|e| SSHError::Ssh(format!("无法写入远程文件 '{}': {}", remote_path, e)))?;
 
             transferred += n as u64;
+            self.touch();
             progress_callback(transferred, file_size);
         }
 
@@ -628,8 +844,51 @@ impl SftpClient {
         Ok(transferred)
     }
 
+    /// 目录上传时按冲突策略确定单个文件的远程路径，返回 None 表示跳过
+    ///
+    /// 仅在非覆盖策略下才查询远程文件，覆盖策略不增加额外的往返
+    async fn resolve_upload_target(&self, local_path: &str, remote_path: String, conflict: ConflictPolicy) -> Result<Option<String>> {
+        if conflict == ConflictPolicy::Overwrite {
+            return Ok(Some(remote_path));
+        }
+        let Some(existing) = self.stat(&remote_path).await? else {
+            return Ok(Some(remote_path));
+        };
+
+        let source_modified = tokio::fs::metadata(local_path).await
+            .map(|metadata| crate::sftp::local_modified(&metadata))
+            .unwrap_or(0);
+        match conflict.resolve(source_modified, existing.modified) {
+            ConflictAction::Write => Ok(Some(remote_path)),
+            ConflictAction::Skip => {
+                info!("Target exists, skipping: {}", remote_path);
+                Ok(None)
+            }
+            ConflictAction::Fail => Err(SSHError::Io(format!("目标已存在: {}", remote_path))),
+            ConflictAction::Rename => {
+                let (dir, name) = match remote_path.rsplit_once('/') {
+                    Some((dir, name)) => (format!("{}/", dir), name),
+                    None => (String::new(), remote_path.as_str()),
+                };
+                let mut n = 1;
+                loop {
+                    let candidate = format!("{}{}", dir, crate::sftp::numbered_name(name, n, false));
+                    if self.stat(&candidate).await?.is_none() {
+                        return Ok(Some(candidate));
+                    }
+                    n += 1;
+                }
+            }
+        }
+    }
+
     /// 递归上传目录及其所有内容
     ///
+    /// 分三个阶段执行：
+    /// 1. 使用有限数量的 worker 并发扫描本地目录结构
+    /// 2. 按深度创建远程目录结构
+    /// 3. 在同一个 SFTP 会话上并发上传 `concurrency` 个文件
+    ///
     /// # 参数
     /// - `local_dir`: 本地目录路径
     /// - `remote_dir`: 远程目录路径
@@ -637,9 +896,13 @@ impl SftpClient {
     /// - `connection_id`: 连接 ID
     /// - `task_id`: 上传任务的唯一 ID
     /// - `cancellation_token`: 取消令牌
+    /// - `concurrency`: 同时上传的文件数量（最小为 1）
+    /// - `symlinks`: 符号链接的处理方式（在远程重新创建或跟随上传）
+    /// - `conflict`: 远程文件已存在时的处理策略
     ///
     /// # 返回
     /// 上传结果统计
+    #[allow(clippy::too_many_arguments)]
     pub fn upload_directory_recursive<'a>(
         &'a mut self,
         local_dir: &'a str,
@@ -648,16 +911,20 @@ impl SftpClient {
         connection_id: &'a str,
         task_id: &'a str,
         cancellation_token: &'a tokio_util::sync::CancellationToken,
+        concurrency: usize,
+        symlinks: SymlinkPolicy,
+        conflict: ConflictPolicy,
     ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<crate::sftp::UploadDirectoryResult>> + Send + 'a>> {
         Box::pin(async move {
             use crate::sftp::{UploadDirectoryResult, UploadProgressEvent};
-            use std::time::Instant;
+            use futures::stream::{self, TryStreamExt};
+            use std::time::{Duration, Instant};
 
             info!("=== Recursive Directory Upload Start ===");
-            info!("Local: {}, Remote: {}", local_dir, remote_dir);
+            info!("Local: {}, Remote: {}, concurrency: {}", local_dir, remote_dir, concurrency);
 
             // 提取目录名作为 uploadName
-            let upload_name = Arc::new(Path::new(local_dir)
+            let upload_name = Path::new(local_dir)
                 .file_name()
                 .and_then(|n| n.to_str())
                 .unwrap_or_else(|| {
@@ -666,54 +933,17 @@ impl SftpClient {
                         .or_else(|| local_dir.rsplit('\\').next())
                         .unwrap_or(local_dir)
                 })
-                .to_string());
+                .to_string();
 
             let start_time = Instant::now();
             let start_time_timestamp = chrono::Utc::now().timestamp_millis() as u64; // Unix 时间戳（毫秒）
-            let mut total_files: u64 = 0;
-            let mut total_dirs: u64 = 0;
-            let mut total_size: u64 = 0;
-            let mut files_completed: u64 = 0;
-            let mut total_bytes_transferred: u64 = 0; // 修复：累计所有已传输字节数
 
             // 第一步：统计总文件数和总大小
             info!("Phase 1: Scanning directory structure...");
-            let mut dir_queue = vec![(local_dir.to_string(), remote_dir.to_string())];
-            let mut all_files: Vec<(String, String, u64)> = Vec::new(); // (local_path, remote_path, size)
-
-            while let Some((local_path, remote_path)) = dir_queue.pop() {
-                let mut entries = tokio::fs::read_dir(&local_path).await
-                    .map_err(|e| SSHError::Io(format!("无法读取本地目录 '{}': {}", local_path, e)))?;
-
-                while let Some(entry) = entries.next_entry().await
-                    .map_err(|e| SSHError::Io(format!("读取目录条目失败: {}", e)))? {
-
-                    let entry_path = entry.path();
-                    let entry_name = entry.file_name().to_string_lossy().to_string();
-                    let entry_type = entry.file_type().await
-                        .map_err(|e| SSHError::Io(format!("无法获取文件类型: {}", e)))?;
-
-                    if entry_type.is_dir() {
-                        let new_local = format!("{}/{}", local_path, entry_name);
-                        let new_remote = format!("{}/{}", remote_path, entry_name);
-                        dir_queue.push((new_local, new_remote));
-                        total_dirs += 1;
-                    } else if entry_type.is_file() {
-                        let metadata = entry.metadata().await
-                            .map_err(|e| SSHError::Io(format!("无法获取文件元数据: {}", e)))?;
-                        let file_size = metadata.len();
-
-                        let remote_file_path = format!("{}/{}", remote_path, entry_name);
-                        all_files.push((entry_path.to_string_lossy().to_string(), remote_file_path, file_size));
-
-                        total_files += 1;
-                        total_size += file_size;
-                    } else if entry_type.is_symlink() {
-                        // 符号链接：跳过并记录日志
-                        info!("Skipping symbolic link: {} (符号链接上传暂不支持)", entry_path.display());
-                    }
-                }
-            }
+            let LocalScan { files: all_files, total_dirs, symlinks: links } =
+                scan_local_directory(local_dir, remote_dir, symlinks, cancellation_token).await?;
+            let total_files = all_files.len() as u64;
+            let total_size: u64 = all_files.iter().map(|(_, _, size)| size).sum();
 
             info!("Scan complete: {} files, {} directories, total size: {} bytes", total_files, total_dirs, total_size);
 
@@ -723,7 +953,8 @@ impl SftpClient {
             // Phase 1.5: 批量创建所有需要的目录
             info!("Phase 1.5: Creating directory structure...");
             let mut unique_dirs: std::collections::HashSet<String> = std::collections::HashSet::new();
-            for (_, remote_file_path, _) in &all_files {
+            let remote_paths = all_files.iter().map(|(_, remote, _)| remote).chain(links.iter().map(|(remote, _)| remote));
+            for remote_file_path in remote_paths {
                 if let Some(parent) = Path::new(remote_file_path).parent() {
                     if let Some(parent_str) = parent.to_str() {
                         if !parent_str.is_empty() && parent_str != "/" {
@@ -745,126 +976,136 @@ impl SftpClient {
             }
             info!("Directory structure created: {} directories", sorted_dirs.len());
 
-            // 第二步：实际上传文件
-            info!("Phase 2: Uploading files...");
-            for (local_file_path, remote_file_path, _file_size) in all_files {
-                // 检查是否被取消
-                if cancellation_token.is_cancelled() {
-                    info!("Upload cancelled for connection: {}", connection_id);
-                    return Err(SSHError::Io("上传已取消".to_string()));
-                }
-
-                // 流式上传文件（跳过目录检查，已在 Phase 1.5 创建）
-                // 使用节流机制控制事件发送频率（200ms）
-                let window_clone = window.clone();
-                let task_id_clone = task_id.to_string();
-                let connection_id_clone = connection_id.to_string();
-                let local_file_path_clone = local_file_path.clone();
-                let total_files_value = total_files;
-                let total_size_value = total_size;
-                let files_completed_before = files_completed;
-                let total_bytes_before = total_bytes_transferred;
-                let start_time_clone = start_time.clone();
-                let start_time_timestamp_clone = start_time_timestamp;
-                let last_emit_time = std::sync::Arc::new(std::sync::Mutex::new(std::time::Instant::now()));
-                let upload_name_clone = Arc::clone(&upload_name);
-
-                let file_transferred = self.upload_file_stream(
-                    &local_file_path,
-                    &remote_file_path,
-                    cancellation_token,
-                    {
-                        let last_emit_time = last_emit_time.clone();
-                        move |transferred, _total| {
-                            let now = std::time::Instant::now();
-                            // 节流：每 200ms 最多发送一次事件
-                            {
-                                let mut last = last_emit_time.lock().unwrap();
-                                if now.duration_since(*last) >= std::time::Duration::from_millis(200) {
-                                    *last = now;
-
-                                    let total_bytes = total_bytes_before + transferred;
-                                    let elapsed_ms = start_time_clone.elapsed().as_millis() as u64;
-                                    let speed_bytes_per_sec = if elapsed_ms > 0 {
-                                        (total_bytes * 1000) / elapsed_ms
-                                    } else {
-                                        0
-                                    };
-
-                                    let progress_event = UploadProgressEvent {
-                                        task_id: task_id_clone.clone(),
-                                        connection_id: connection_id_clone.clone(),
-                                        current_file: local_file_path_clone.clone(),
-                                        current_dir: Path::new(&local_file_path_clone)
-                                            .parent()
-                                            .and_then(|p| p.to_str())
-                                            .unwrap_or("")
-                                            .to_string(),
-                                        files_completed: files_completed_before, // 文件未完成，不增加
-                                        total_files: total_files_value,
-                                        bytes_transferred: total_bytes,
-                                        total_bytes: total_size_value,
-                                        speed_bytes_per_sec,
-                                        start_time: start_time_timestamp_clone,
-                                        completed_time: chrono::Utc::now().timestamp_millis() as u64,
-                                        upload_name: upload_name_clone.to_string(),
-                                    };
-
-                                    let _ = window_clone.emit("sftp-upload-progress", &progress_event);
-                                }
-                            }
-                        }
-                    },
-                    true, // skip_dir_check: true
-                ).await?;
-
-                files_completed += 1;
-                total_bytes_transferred += file_transferred; // 修复：累计字节数
+            for (remote_link_path, target) in &links {
+                self.create_symlink(remote_link_path, target, true).await?;
+            }
+            if !links.is_empty() {
+                info!("Symbolic links created: {}", links.len());
+            }
 
-                // 计算传输速度（基于总传输时间）
+            // 第二步：并发上传文件
+            // 所有文件共享同一个 SFTP 会话，russh_sftp 会按请求 ID 复用 channel
+            info!("Phase 2: Uploading files...");
+            let this: &SftpClient = self;
+            let files_completed = AtomicU64::new(0);
+            let total_bytes_transferred = AtomicU64::new(0);
+            let skipped_files = std::sync::Mutex::new(Vec::new());
+            // 节流：所有并发文件共享同一个计时器，每 200ms 最多发送一次事件
+            let last_emit_time = std::sync::Mutex::new(Instant::now());
+
+            let build_event = |current_file: &str, files_completed: u64, bytes_transferred: u64| {
                 let elapsed_ms = start_time.elapsed().as_millis() as u64;
                 let speed_bytes_per_sec = if elapsed_ms > 0 {
-                    (total_bytes_transferred * 1000) / elapsed_ms
+                    (bytes_transferred * 1000) / elapsed_ms
                 } else {
                     0
                 };
 
-                // 发送进度事件（文件完成事件，不受节流限制）
-                let progress_event = UploadProgressEvent {
+                UploadProgressEvent {
                     task_id: task_id.to_string(),
                     connection_id: connection_id.to_string(),
-                    current_file: local_file_path.clone(),
-                    current_dir: Path::new(&local_file_path)
+                    current_file: current_file.to_string(),
+                    current_dir: Path::new(current_file)
                         .parent()
                         .and_then(|p| p.to_str())
                         .unwrap_or("")
                         .to_string(),
                     files_completed,
                     total_files,
-                    bytes_transferred: total_bytes_transferred, // 修复：使用累计字节数
+                    bytes_transferred,
                     total_bytes: total_size,
                     speed_bytes_per_sec,
                     start_time: start_time_timestamp,
                     completed_time: chrono::Utc::now().timestamp_millis() as u64,
-                    upload_name: Arc::clone(&upload_name).to_string(),
-                };
-
-                if let Err(e) = window.emit("sftp-upload-progress", &progress_event) {
-                    tracing::warn!("Failed to emit upload progress: {}", e);
+                    upload_name: upload_name.clone(),
                 }
+            };
 
-                info!("Uploaded {}/{} files: {} ({} bytes, {} KB/s)",
-                    files_completed, total_files,
-                    local_file_path,
-                    file_transferred,
-                    speed_bytes_per_sec / 1024
-                );
-            }
+            stream::iter(all_files.into_iter().map(Ok::<_, SSHError>))
+                .try_for_each_concurrent(concurrency.max(1), |(local_file_path, remote_file_path, file_size)| {
+                    let files_completed = &files_completed;
+                    let total_bytes_transferred = &total_bytes_transferred;
+                    let skipped_files = &skipped_files;
+                    let last_emit_time = &last_emit_time;
+                    let build_event = &build_event;
+
+                    async move {
+                        // 检查是否被取消
+                        if cancellation_token.is_cancelled() {
+                            info!("Upload cancelled for connection: {}", connection_id);
+                            return Err(SSHError::Io("上传已取消".to_string()));
+                        }
+
+                        // 远程文件已存在时按冲突策略处理，跳过的文件计入进度
+                        let Some(remote_file_path) = this.resolve_upload_target(&local_file_path, remote_file_path, conflict).await? else {
+                            skipped_files.lock().unwrap().push(local_file_path.clone());
+                            let completed = files_completed.fetch_add(1, Ordering::Relaxed) + 1;
+                            let total_bytes = total_bytes_transferred.fetch_add(file_size, Ordering::Relaxed) + file_size;
+                            let _ = window.emit("sftp-upload-progress", &build_event(&local_file_path, completed, total_bytes));
+                            return Ok(());
+                        };
+
+                        // 当前文件上一次回调时的已传输字节数，用于计算增量
+                        let file_reported = AtomicU64::new(0);
+
+                        // 流式上传文件（跳过目录检查，已在 Phase 1.5 创建）
+                        let file_transferred = this.upload_file_stream(
+                            &local_file_path,
+                            &remote_file_path,
+                            cancellation_token,
+                            |transferred, _total| {
+                                let delta = transferred - file_reported.swap(transferred, Ordering::Relaxed);
+                                let total_bytes = total_bytes_transferred.fetch_add(delta, Ordering::Relaxed) + delta;
+
+                                let now = Instant::now();
+                                let mut last = last_emit_time.lock().unwrap();
+                                if now.duration_since(*last) >= Duration::from_millis(200) {
+                                    *last = now;
+                                    drop(last);
+
+                                    // 文件未完成，不增加 files_completed
+                                    let progress_event = build_event(
+                                        &local_file_path,
+                                        files_completed.load(Ordering::Relaxed),
+                                        total_bytes,
+                                    );
+                                    let _ = window.emit("sftp-upload-progress", &progress_event);
+                                }
+                            },
+                            true, // skip_dir_check: true
+                            false,
+                        ).await?;
+
+                        let completed = files_completed.fetch_add(1, Ordering::Relaxed) + 1;
+
+                        // 发送进度事件（文件完成事件，不受节流限制）
+                        let progress_event = build_event(
+                            &local_file_path,
+                            completed,
+                            total_bytes_transferred.load(Ordering::Relaxed),
+                        );
+
+                        if let Err(e) = window.emit("sftp-upload-progress", &progress_event) {
+                            tracing::warn!("Failed to emit upload progress: {}", e);
+                        }
+
+                        info!("Uploaded {}/{} files: {} ({} bytes, {} KB/s)",
+                            completed, total_files,
+                            local_file_path,
+                            file_transferred,
+                            progress_event.speed_bytes_per_sec / 1024
+                        );
+
+                        Ok(())
+                    }
+                })
+                .await?;
 
             let elapsed_time = start_time.elapsed().as_millis() as u64;
+            let skipped_files = skipped_files.into_inner().unwrap();
 
             info!("=== Directory Upload Complete ===");
-            info!("Files: {}, Directories: {}, Total size: {} bytes", total_files, total_dirs, total_size);
+            info!("Files: {}, Directories: {}, Total size: {} bytes, Skipped: {}", total_files, total_dirs, total_size, skipped_files.len());
             info!("Elapsed time: {} ms", elapsed_time);
 
             Ok(UploadDirectoryResult {
@@ -872,6 +1113,7 @@ impl SftpClient {
                 total_dirs,
                 total_size,
                 elapsed_time_ms: elapsed_time,
+                skipped_files,
             })
         })
     }
@@ -879,8 +1121,8 @@ impl SftpClient {
     /// 递归下载目录
     ///
     /// 分两个阶段执行：
-    /// 1. 扫描远程目录结构，收集所有文件和目录
-    /// 2. 逐个下载文件，同时创建本地目录结构
+    /// 1. 扫描远程目录结构，收集所有文件并创建本地目录结构
+    /// 2. 当前客户端和 `extra_clients` 各作为一个 worker 并发下载文件
     ///
     /// # 参数
     /// - `remote_dir_path`: 远程目录路径
@@ -889,9 +1131,13 @@ impl SftpClient {
     /// - `connection_id`: SSH 连接 ID
     /// - `task_id`: 下载任务的唯一 ID
     /// - `cancellation_token`: 取消令牌
+    /// - `symlinks`: 符号链接的处理方式（在本地重新创建或跟随下载）
+    /// - `extra_clients`: 额外的任务客户端（每个使用独立的 SFTP channel），为空时逐个下载
+    /// - `conflict`: 本地文件已存在时的处理策略
     ///
     /// # 返回
     /// 下载结果统计信息
+    #[allow(clippy::too_many_arguments)]
     pub async fn download_directory_recursive<F>(
         &mut self,
         remote_dir_path: &str,
@@ -900,6 +1146,9 @@ impl SftpClient {
         connection_id: &str,
         task_id: &str,
         cancellation_token: &tokio_util::sync::CancellationToken,
+        symlinks: SymlinkPolicy,
+        extra_clients: &[Arc<tokio::sync::Mutex<SftpClient>>],
+        conflict: ConflictPolicy,
         _progress_callback: F,
     ) -> Result<crate::sftp::DownloadDirectoryResult>
     where
@@ -913,10 +1162,18 @@ impl SftpClient {
 
         // 🔥 阶段 1: 扫描远程目录结构
         let mut dir_queue = vec![(remote_dir_path.to_string(), local_dir_path.to_string())];
-        let mut all_files: Vec<(String, String, u64)> = Vec::new();
+        // (远程路径, 本地路径, 大小, 远程修改时间)
+        let mut all_files: Vec<(String, String, u64, u64)> = Vec::new();
         let mut total_files = 0u64;
         let mut total_dirs = 0u64;
         let mut total_size = 0u64;
+        // 需要在本地创建的符号链接 (本地路径, 链接指向, 是否指向目录)
+        let mut links: Vec<(String, String, bool)> = Vec::new();
+        // 跟随符号链接时已进入过的目录（规范路径），避免循环链接导致无限递归
+        let mut visited = std::collections::HashSet::new();
+        if let Ok(canonical) = self.canonicalize(remote_dir_path).await {
+            visited.insert(canonical);
+        }
 
         while let Some((remote_path, local_path)) = dir_queue.pop() {
             if cancellation_token.is_cancelled() {
@@ -943,136 +1200,175 @@ impl SftpClient {
                     format!("{}{}{}", local_path, std::path::MAIN_SEPARATOR, entry_name)
                 };
 
+                if entry.is_symlink && symlinks == SymlinkPolicy::Preserve {
+                    match entry.link_target {
+                        Some(target) => links.push((entry_local_path, target, entry.is_dir)),
+                        None => warn!("Skipping unreadable symbolic link: {}", entry_remote_path),
+                    }
+                    continue;
+                }
+
                 if entry.is_dir {
+                    if entry.is_symlink {
+                        if let Ok(canonical) = self.canonicalize(&entry_remote_path).await {
+                            if !visited.insert(canonical) {
+                                info!("Skipping already visited directory: {}", entry_remote_path);
+                                continue;
+                            }
+                        }
+                    }
                     dir_queue.push((entry_remote_path, entry_local_path));
                     total_dirs += 1;
-                } else if !entry.is_dir {
-                    all_files.push((entry_remote_path, entry_local_path, entry.size));
+                } else {
+                    all_files.push((entry_remote_path, entry_local_path, entry.size, entry.modified));
                     total_files += 1;
                     total_size += entry.size;
                 }
             }
         }
 
-        info!("Phase 1 complete: {} files, {} dirs, {} bytes", total_files, total_dirs, total_size);
-
-        // 🔥 阶段 2: 逐个下载文件
-        info!("Phase 2: Downloading files...");
-        let mut files_completed = 0u64;
-        let mut total_bytes_transferred = 0u64;
-
-        for (remote_file_path, local_file_path, _file_size) in all_files {
-            if cancellation_token.is_cancelled() {
-                info!("Download cancelled for task: {}", task_id);
-                return Err(SSHError::Io("下载已取消".to_string()));
-            }
-
-            // 流式下载文件
-            // 使用节流机制控制事件发送频率（200ms）
-            let window_clone = window.clone();
-            let task_id_clone = task_id.to_string();
-            let connection_id_clone = connection_id.to_string();
-            let remote_file_path_clone = remote_file_path.clone();
-            let total_files_value = total_files;
-                            let total_size_value = total_size;
-                            let files_completed_before = files_completed;
-                            let total_bytes_before = total_bytes_transferred;
-                            let start_time_clone = start_time.clone();
-                            let start_time_timestamp_clone = start_time_timestamp;
-                            let last_emit_time = std::sync::Arc::new(std::sync::Mutex::new(std::time::Instant::now()));
-            let file_transferred = self.download_file_stream(
-                &remote_file_path,
-                &local_file_path,
-                cancellation_token,
-                {
-                    let last_emit_time = last_emit_time.clone();
-                    move |transferred, _total| {
-                        let now = std::time::Instant::now();
-                        // 节流：每 200ms 最多发送一次事件
-                        {
-                            let mut last = last_emit_time.lock().unwrap();
-                            if now.duration_since(*last) >= std::time::Duration::from_millis(200) {
-                                *last = now;
-
-                                let total_bytes = total_bytes_before + transferred;
-                                let elapsed_ms = start_time_clone.elapsed().as_millis() as u64;
-                                let speed_bytes_per_sec = if elapsed_ms > 0 {
-                                    (total_bytes * 1000) / elapsed_ms
-                                } else {
-                                    0
-                                };
-
-                                let progress_event = crate::sftp::DownloadProgressEvent {
-                                    task_id: task_id_clone.clone(),
-                                    connection_id: connection_id_clone.clone(),
-                                    current_file: remote_file_path_clone.clone(),
-                                    current_dir: Path::new(&remote_file_path_clone)
-                                        .parent()
-                                        .and_then(|p| p.to_str())
-                                        .unwrap_or("")
-                                        .to_string(),
-                                    files_completed: files_completed_before, // 文件未完成，不增加
-                                    total_files: total_files_value,
-                                    bytes_transferred: total_bytes,
-                                    total_bytes: total_size_value,
-                                    speed_bytes_per_sec,
-                                    start_time: start_time_timestamp_clone,
-                                    completed_time: chrono::Utc::now().timestamp_millis() as u64,
-                                };
-
-                                let _ = window_clone.emit("sftp-download-progress", &progress_event);
-                            }
-                        }
-                    }
-                }
-            ).await?;
-
-            files_completed += 1;
-            total_bytes_transferred += file_transferred;
+        for (local_link_path, target, is_dir) in &links {
+            create_local_symlink(target, Path::new(local_link_path), *is_dir).await?;
+        }
 
-            // 计算传输速度（基于总传输时间）
+        info!(
+            "Phase 1 complete: {} files, {} dirs, {} symlinks, {} bytes",
+            total_files, total_dirs, links.len(), total_size
+        );
+
+        // 🔥 阶段 2: 多个 worker 并发下载文件
+        // 每个 worker 使用独立的 SFTP channel，大文件优先，避免最后只剩一个大文件在单个 channel 上传输
+        use std::sync::atomic::AtomicUsize;
+        use std::time::{Duration, Instant};
+
+        all_files.sort_by_key(|file| std::cmp::Reverse(file.2));
+        let extra_guards = futures::future::join_all(extra_clients.iter().map(|client| client.lock())).await;
+        let workers: Vec<&SftpClient> = std::iter::once(&*self)
+            .chain(extra_guards.iter().map(|guard| &**guard))
+            .collect();
+        info!("Phase 2: Downloading files with {} worker(s)...", workers.len());
+
+        let next_file = AtomicUsize::new(0);
+        let files_completed = AtomicU64::new(0);
+        let total_bytes_transferred = AtomicU64::new(0);
+        let skipped_files = std::sync::Mutex::new(Vec::new());
+        // 节流：所有 worker 共享同一个计时器，每 200ms 最多发送一次事件
+        let last_emit_time = std::sync::Mutex::new(Instant::now());
+
+        let build_event = |current_file: &str, files_completed: u64, bytes_transferred: u64| {
             let elapsed_ms = start_time.elapsed().as_millis() as u64;
             let speed_bytes_per_sec = if elapsed_ms > 0 {
-                (total_bytes_transferred * 1000) / elapsed_ms
+                (bytes_transferred * 1000) / elapsed_ms
             } else {
                 0
             };
 
-            // 发送进度事件（文件完成事件，不受节流限制）
-            let progress_event = crate::sftp::DownloadProgressEvent {
+            crate::sftp::DownloadProgressEvent {
                 task_id: task_id.to_string(),
                 connection_id: connection_id.to_string(),
-                current_file: remote_file_path.clone(),
-                current_dir: Path::new(&remote_file_path)
+                current_file: current_file.to_string(),
+                current_dir: Path::new(current_file)
                     .parent()
                     .and_then(|p| p.to_str())
                     .unwrap_or("")
                     .to_string(),
                 files_completed,
                 total_files,
-                bytes_transferred: total_bytes_transferred,
+                bytes_transferred,
                 total_bytes: total_size,
                 speed_bytes_per_sec,
                 start_time: start_time_timestamp,
                 completed_time: chrono::Utc::now().timestamp_millis() as u64,
-            };
-
-            if let Err(e) = window.emit("sftp-download-progress", &progress_event) {
-                tracing::warn!("Failed to emit download progress: {}", e);
             }
+        };
+
+        let all_files = &all_files;
+        let next_file = &next_file;
+        let files_completed = &files_completed;
+        let total_bytes_transferred = &total_bytes_transferred;
+        let skipped_files_ref = &skipped_files;
+        let last_emit_time = &last_emit_time;
+        let build_event = &build_event;
+
+        futures::future::try_join_all(workers.into_iter().map(|worker| async move {
+            while let Some((remote_file_path, local_file_path, file_size, remote_modified)) =
+                all_files.get(next_file.fetch_add(1, Ordering::Relaxed))
+            {
+                if cancellation_token.is_cancelled() {
+                    info!("Download cancelled for task: {}", task_id);
+                    return Err(SSHError::Io("下载已取消".to_string()));
+                }
 
-            info!("Downloaded {}/{} files: {} ({} bytes, {} KB/s)",
-                files_completed, total_files,
-                remote_file_path,
-                file_transferred,
-                speed_bytes_per_sec / 1024
-            );
-        }
+                // 本地文件已存在时按冲突策略处理，跳过的文件计入进度
+                let Some(local_file_path) = resolve_download_target(local_file_path, *remote_modified, conflict).await? else {
+                    skipped_files_ref.lock().unwrap().push(remote_file_path.clone());
+                    let completed = files_completed.fetch_add(1, Ordering::Relaxed) + 1;
+                    let total_bytes = total_bytes_transferred.fetch_add(*file_size, Ordering::Relaxed) + *file_size;
+                    let _ = window.emit("sftp-download-progress", &build_event(remote_file_path, completed, total_bytes));
+                    continue;
+                };
+
+                // 当前文件上一次回调时的已传输字节数，用于计算增量
+                let file_reported = AtomicU64::new(0);
+
+                let file_transferred = worker.download_file_stream(
+                    remote_file_path,
+                    &local_file_path,
+                    cancellation_token,
+                    |transferred, _total| {
+                        let delta = transferred.saturating_sub(file_reported.swap(transferred, Ordering::Relaxed));
+                        let total_bytes = total_bytes_transferred.fetch_add(delta, Ordering::Relaxed) + delta;
+
+                        let now = Instant::now();
+                        let mut last = last_emit_time.lock().unwrap();
+                        if now.duration_since(*last) >= Duration::from_millis(200) {
+                            *last = now;
+                            drop(last);
+
+                            // 文件未完成，不增加 files_completed
+                            let progress_event = build_event(
+                                remote_file_path,
+                                files_completed.load(Ordering::Relaxed),
+                                total_bytes,
+                            );
+                            let _ = window.emit("sftp-download-progress", &progress_event);
+                        }
+                    },
+                    false,
+                ).await?;
+
+                // 补上最后一次回调之后的字节，保证 bytes_transferred 与实际下载量一致
+                let remaining = file_transferred.saturating_sub(file_reported.load(Ordering::Relaxed));
+                total_bytes_transferred.fetch_add(remaining, Ordering::Relaxed);
+                let completed = files_completed.fetch_add(1, Ordering::Relaxed) + 1;
+
+                // 发送进度事件（文件完成事件，不受节流限制）
+                let progress_event = build_event(
+                    remote_file_path,
+                    completed,
+                    total_bytes_transferred.load(Ordering::Relaxed),
+                );
+
+                if let Err(e) = window.emit("sftp-download-progress", &progress_event) {
+                    tracing::warn!("Failed to emit download progress: {}", e);
+                }
+
+                info!("Downloaded {}/{} files: {} ({} bytes, {} KB/s)",
+                    completed, total_files,
+                    remote_file_path,
+                    file_transferred,
+                    progress_event.speed_bytes_per_sec / 1024
+                );
+            }
+            Ok(())
+        }))
+        .await?;
 
         let elapsed_time = start_time.elapsed().as_millis() as u64;
+        let skipped_files = skipped_files.into_inner().unwrap();
 
         info!("=== Directory Download Complete ===");
-        info!("Files: {}, Directories: {}, Total size: {} bytes", total_files, total_dirs, total_size);
+        info!("Files: {}, Directories: {}, Total size: {} bytes, Skipped: {}", total_files, total_dirs, total_size, skipped_files.len());
         info!("Elapsed time: {} ms", elapsed_time);
 
         Ok(crate::sftp::DownloadDirectoryResult {
@@ -1080,6 +1376,7 @@ impl SftpClient {
             total_dirs,
             total_size,
             elapsed_time_ms: elapsed_time,
+            skipped_files,
         })
     }
 
@@ -1093,15 +1390,17 @@ impl SftpClient {
     /// - `local_path`: 本地保存路径
     /// - `cancellation_token`: 取消令牌
     /// - `progress_callback`: 进度回调函数
+    /// - `resume`: 断点续传，本地文件已存在且小于远程文件时从其末尾继续写入
     ///
     /// # 返回
-    /// 传输的字节数
+    /// 本地文件的最终大小（续传时包含已存在的部分）
     pub async fn download_file_stream<F>(
         &self,
         remote_path: &str,
         local_path: &str,
         cancellation_token: &tokio_util::sync::CancellationToken,
         progress_callback: F,
+        resume: bool,
     ) -> Result<u64>
     where
         F: Fn(u64, u64),
@@ -1117,13 +1416,38 @@ impl SftpClient {
             .map_err(|e| SSHError::Ssh(format!("无法获取文件元数据: {}", e)))?
             .size.unwrap_or(0);
 
-        // 创建本地文件
-        let mut local_file = tokio::fs::File::create(local_path).await
+        // 续传时以本地文件的当前大小作为起点；本地文件不小于远程文件时重新下载
+        let offset = if resume {
+            match tokio::fs::metadata(local_path).await {
+                Ok(meta) if meta.is_file() && meta.len() < file_size => meta.len(),
+                _ => 0,
+            }
+        } else {
+            0
+        };
+
+        // 创建本地文件（续传时保留已有内容）
+        let mut local_file = tokio::fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(offset == 0)
+            .open(local_path)
+            .await
             .map_err(|e| SSHError::Io(format!("无法创建本地文件: {}", e)))?;
 
+        if offset > 0 {
+            info!("Resuming download of {} at offset {}", remote_path, offset);
+            local_file.seek(SeekFrom::Start(offset)).await
+                .map_err(|e| SSHError::Io(format!("无法定位本地文件: {}", e)))?;
+            remote_file.seek(SeekFrom::Start(offset)).await
+                .map_err(|e| SSHError::Ssh(format!("无法定位远程文件: {}", e)))?;
+            self.touch();
+            progress_callback(offset, file_size);
+        }
+
         // 流式传输（64KB 缓冲区）
         let mut buffer = vec![0u8; 64 * 1024];
-        let mut transferred = 0u64;
+        let mut transferred = offset;
 
         loop {
             // 检查是否被取消
@@ -1144,6 +1468,7 @@ impl SftpClient {
                 .map_err(|e| SSHError::Io(format!("写入本地文件失败: {}", e)))?;
 
             transferred += n as u64;
+            self.touch();
             progress_callback(transferred, file_size);
         }
 
@@ -1155,4 +1480,283 @@ impl SftpClient {
         info!("File download completed: {} bytes", transferred);
         Ok(transferred)
     }
+
+    /// 把远程文件直接写入另一个 SFTP 客户端（可以属于另一个连接），数据不经过本地磁盘
+    ///
+    /// # 参数
+    /// - `source_path`: 当前连接上的源文件路径
+    /// - `target`: 目标连接的 SFTP 客户端
+    /// - `target_path`: 目标文件路径（已存在时覆盖，父目录不存在时创建）
+    /// - `cancellation_token`: 取消令牌
+    /// - `progress_callback`: 进度回调函数
+    ///
+    /// # 返回
+    /// 传输的字节数
+    pub async fn copy_file_to<F>(
+        &self,
+        source_path: &str,
+        target: &SftpClient,
+        target_path: &str,
+        cancellation_token: &tokio_util::sync::CancellationToken,
+        progress_callback: F,
+    ) -> Result<u64>
+    where
+        F: Fn(u64, u64),
+    {
+        info!("Streaming remote copy: {} -> {}", source_path, target_path);
+
+        let mut source_file = self.session.open(source_path).await
+            .map_err(|e| SSHError::Ssh(format!("无法打开源文件 '{}': {}", source_path, e)))?;
+        let file_size = source_file.metadata().await
+            .map_err(|e| SSHError::Ssh(format!("无法获取文件 '{}' 的元数据: {}", source_path, e)))?
+            .size.unwrap_or(0);
+
+        if let Some(parent_str) = Path::new(target_path).parent().and_then(|p| p.to_str()) {
+            if !parent_str.is_empty() && parent_str != "/" {
+                target.ensure_dir_exists(parent_str).await?;
+            }
+        }
+        let mut target_file = target.session
+            .open_with_flags(target_path, OpenFlags::CREATE | OpenFlags::WRITE | OpenFlags::TRUNCATE)
+            .await
+            .map_err(|e| SSHError::Ssh(format!("无法创建目标文件 '{}': {}", target_path, e)))?;
+
+        let mut buffer = vec![0u8; 64 * 1024];
+        let mut transferred = 0u64;
+
+        loop {
+            if cancellation_token.is_cancelled() {
+                info!("Remote copy cancelled: {}", source_path);
+                return Err(SSHError::Io("传输已取消".to_string()));
+            }
+
+            let n = source_file.read(&mut buffer).await
+                .map_err(|e| SSHError::Ssh(format!("读取源文件 '{}' 失败: {}", source_path, e)))?;
+
+            if n == 0 {
+                break; // EOF
+            }
+
+            target_file.write_all(&buffer[..n]).await
+                .map_err(|e| SSHError::Ssh(format!("写入目标文件 '{}' 失败: {}", target_path, e)))?;
+
+            transferred += n as u64;
+            self.touch();
+            target.touch();
+            progress_callback(transferred, file_size);
+        }
+
+        target_file.sync_all().await
+            .map_err(|e| SSHError::Ssh(format!("无法刷新目标文件 '{}' 到服务器: {}", target_path, e)))?;
+
+        info!("Remote copy completed: {} bytes", transferred);
+        Ok(transferred)
+    }
+
+    /// 通过 SFTP 读取远程文件计算 SHA-256（远程无法执行校验命令时使用）
+    ///
+    /// `limit` 不为空时只计算文件前 `limit` 字节
+    pub async fn sha256(
+        &self,
+        path: &str,
+        limit: Option<u64>,
+        cancellation_token: &tokio_util::sync::CancellationToken,
+    ) -> Result<String> {
+        use sha2::{Digest, Sha256};
+
+        let mut file = self.session.open(path).await
+            .map_err(|e| SSHError::Ssh(format!("无法打开远程文件 '{}': {}", path, e)))?;
+
+        let mut hasher = Sha256::new();
+        let mut buffer = vec![0u8; 64 * 1024];
+        let mut remaining = limit.unwrap_or(u64::MAX);
+        while remaining > 0 {
+            if cancellation_token.is_cancelled() {
+                return Err(SSHError::Io("校验已取消".to_string()));
+            }
+
+            let chunk = remaining.min(buffer.len() as u64) as usize;
+            let n = file.read(&mut buffer[..chunk]).await
+                .map_err(|e| SSHError::Ssh(format!("读取远程文件 '{}' 失败: {}", path, e)))?;
+            if n == 0 {
+                break;
+            }
+            hasher.update(&buffer[..n]);
+            remaining -= n as u64;
+            self.touch();
+        }
+
+        Ok(format!("{:x}", hasher.finalize()))
+    }
+}
+
+/// 目录下载时按冲突策略确定单个文件的本地路径，返回 None 表示跳过
+async fn resolve_download_target(local_path: &str, remote_modified: u64, conflict: ConflictPolicy) -> Result<Option<String>> {
+    if conflict == ConflictPolicy::Overwrite {
+        return Ok(Some(local_path.to_string()));
+    }
+    let Ok(existing) = tokio::fs::metadata(local_path).await else {
+        return Ok(Some(local_path.to_string()));
+    };
+
+    match conflict.resolve(remote_modified, crate::sftp::local_modified(&existing)) {
+        ConflictAction::Write => Ok(Some(local_path.to_string())),
+        ConflictAction::Skip => {
+            info!("Target exists, skipping: {}", local_path);
+            Ok(None)
+        }
+        ConflictAction::Fail => Err(SSHError::Io(format!("目标已存在: {}", local_path))),
+        ConflictAction::Rename => {
+            let path = Path::new(local_path);
+            let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+            let mut n = 1;
+            loop {
+                let candidate = path.with_file_name(crate::sftp::numbered_name(&name, n, false));
+                if !candidate.exists() {
+                    return Ok(Some(candidate.to_string_lossy().to_string()));
+                }
+                n += 1;
+            }
+        }
+    }
+}
+
+/// 本地目录扫描时同时读取的目录数量
+const SCAN_WORKERS: usize = 8;
+
+/// 本地目录扫描结果
+pub(crate) struct LocalScan {
+    /// 所有文件 `(local_path, remote_path, size)`
+    pub files: Vec<(String, String, u64)>,
+    /// 子目录数量
+    pub total_dirs: u64,
+    /// 需要在远程创建的符号链接 `(remote_path, target)`（仅 [`SymlinkPolicy::Preserve`]）
+    pub symlinks: Vec<(String, String)>,
+}
+
+/// 并发扫描本地目录
+///
+/// 使用最多 [`SCAN_WORKERS`] 个任务同时读取目录；符号链接按 `symlinks` 保留或跟随，
+/// 跟随时按规范路径去重，已进入过的目录不会重复扫描
+pub(crate) async fn scan_local_directory(
+    local_dir: &str,
+    remote_dir: &str,
+    symlinks: SymlinkPolicy,
+    cancellation_token: &tokio_util::sync::CancellationToken,
+) -> Result<LocalScan> {
+    type ScanOutput = (Vec<(String, String)>, Vec<(String, String, u64)>, Vec<(String, String)>);
+
+    async fn scan_one(local_path: String, remote_path: String, symlinks: SymlinkPolicy) -> Result<ScanOutput> {
+        let mut subdirs = Vec::new();
+        let mut files = Vec::new();
+        let mut links = Vec::new();
+
+        let mut entries = tokio::fs::read_dir(&local_path).await
+            .map_err(|e| SSHError::Io(format!("无法读取本地目录 '{}': {}", local_path, e)))?;
+
+        while let Some(entry) = entries.next_entry().await
+            .map_err(|e| SSHError::Io(format!("读取目录条目失败: {}", e)))? {
+
+            let entry_path = entry.path();
+            let entry_name = entry.file_name().to_string_lossy().to_string();
+            let entry_remote_path = format!("{}/{}", remote_path, entry_name);
+            let entry_type = entry.file_type().await
+                .map_err(|e| SSHError::Io(format!("无法获取文件类型: {}", e)))?;
+
+            if entry_type.is_symlink() && symlinks == SymlinkPolicy::Preserve {
+                let target = tokio::fs::read_link(&entry_path).await
+                    .map_err(|e| SSHError::Io(format!("无法读取符号链接 '{}': {}", entry_path.display(), e)))?;
+                links.push((entry_remote_path, target.to_string_lossy().to_string()));
+                continue;
+            }
+
+            // 跟随符号链接时按链接目标的类型处理，悬空链接跳过
+            let metadata = if entry_type.is_symlink() {
+                match tokio::fs::metadata(&entry_path).await {
+                    Ok(metadata) => metadata,
+                    Err(e) => {
+                        warn!("Skipping dangling symbolic link {}: {}", entry_path.display(), e);
+                        continue;
+                    }
+                }
+            } else {
+                entry.metadata().await
+                    .map_err(|e| SSHError::Io(format!("无法获取文件元数据: {}", e)))?
+            };
+
+            if metadata.is_dir() {
+                subdirs.push((format!("{}/{}", local_path, entry_name), entry_remote_path));
+            } else if metadata.is_file() {
+                files.push((entry_path.to_string_lossy().to_string(), entry_remote_path, metadata.len()));
+            }
+        }
+
+        Ok((subdirs, files, links))
+    }
+
+    let mut pending = vec![(local_dir.to_string(), remote_dir.to_string())];
+    let mut visited = std::collections::HashSet::new();
+    let mut workers = tokio::task::JoinSet::new();
+    let mut scan = LocalScan { files: Vec::new(), total_dirs: 0, symlinks: Vec::new() };
+    let mut scanned_dirs = 0u64;
+
+    loop {
+        if cancellation_token.is_cancelled() {
+            return Err(SSHError::Io("上传已取消".to_string()));
+        }
+
+        while workers.len() < SCAN_WORKERS {
+            let Some((local_path, remote_path)) = pending.pop() else { break };
+            if symlinks == SymlinkPolicy::Follow {
+                if let Ok(canonical) = tokio::fs::canonicalize(&local_path).await {
+                    if !visited.insert(canonical) {
+                        info!("Skipping already visited directory: {}", local_path);
+                        continue;
+                    }
+                }
+            }
+            scanned_dirs += 1;
+            workers.spawn(scan_one(local_path, remote_path, symlinks));
+        }
+
+        let Some(joined) = workers.join_next().await else { break };
+        let (subdirs, files, links) = joined
+            .map_err(|e| SSHError::Io(format!("目录扫描任务失败: {}", e)))??;
+
+        pending.extend(subdirs);
+        scan.files.extend(files);
+        scan.symlinks.extend(links);
+    }
+
+    // 不计入根目录
+    scan.total_dirs = scanned_dirs.saturating_sub(1);
+    Ok(scan)
+}
+
+/// 在本地创建符号链接 `link_path`，指向 `target`（已存在的同名文件或链接会被替换）
+///
+/// Windows 需要区分文件链接和目录链接，并且需要开发者模式或管理员权限
+pub(crate) async fn create_local_symlink(target: &str, link_path: &Path, is_dir: bool) -> Result<()> {
+    if let Ok(existing) = tokio::fs::symlink_metadata(link_path).await {
+        // Windows 上的目录链接需要用 remove_dir 删除
+        if tokio::fs::remove_file(link_path).await.is_err() && existing.file_type().is_symlink() {
+            let _ = tokio::fs::remove_dir(link_path).await;
+        }
+    }
+
+    #[cfg(unix)]
+    let result = {
+        let _ = is_dir;
+        tokio::fs::symlink(target, link_path).await
+    };
+    #[cfg(windows)]
+    let result = if is_dir {
+        tokio::fs::symlink_dir(target, link_path).await
+    } else {
+        tokio::fs::symlink_file(target, link_path).await
+    };
+
+    result.map_err(|e| SSHError::Io(format!(
+        "无法创建符号链接 '{}' -> '{}': {}", link_path.display(), target, e
+    )))
 }
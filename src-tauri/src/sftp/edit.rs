@@ -0,0 +1,378 @@
+//! 远程文件本地编辑
+//!
+//! 把远程文件下载到临时目录并用本地编辑器打开，之后定期检查本地文件的修改时间和大小，
+//! 每次保存后上传回服务器。编辑器（尤其是先截断再写入的编辑器）保存时可能分多次写入，
+//! 因此只有连续两次检查结果相同、且与上次同步时不同才会上传。
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+/// 同步状态事件名
+pub const EDIT_SYNC_EVENT: &str = "sftp-edit-sync";
+
+/// 检查本地文件是否被保存的间隔
+pub const EDIT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 同步状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum EditSyncStatus {
+    /// 已下载并打开编辑器
+    Opened,
+    Uploading,
+    Synced,
+    /// 上传失败，或会话结束时本地文件有未上传的修改（临时文件保留，`error` 中给出路径）
+    Failed,
+    /// 编辑会话结束，临时文件已删除
+    Closed,
+}
+
+/// 同步状态事件（`sftp-edit-sync` 事件内容）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EditSyncEvent {
+    pub edit_id: String,
+    pub connection_id: String,
+    pub remote_path: String,
+    pub status: EditSyncStatus,
+    /// 上传的字节数（仅 synced）
+    pub bytes: Option<u64>,
+    pub error: Option<String>,
+    /// Unix 时间戳（毫秒）
+    pub timestamp: u64,
+}
+
+/// 本地文件的修改标记
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileStamp {
+    pub modified: SystemTime,
+    pub len: u64,
+}
+
+impl FileStamp {
+    /// 读取本地文件的修改标记，文件不存在（编辑器保存时可能先删除再重建）时返回 None
+    pub fn read(path: &Path) -> Option<Self> {
+        let metadata = std::fs::metadata(path).ok()?;
+        Some(Self {
+            modified: metadata.modified().ok()?,
+            len: metadata.len(),
+        })
+    }
+}
+
+/// 编辑会话信息（`sftp_edit_open`、`sftp_edit_list` 返回）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EditSessionInfo {
+    pub edit_id: String,
+    pub connection_id: String,
+    pub remote_path: String,
+    /// 本地临时文件路径
+    pub local_path: String,
+    /// 打开时间（Unix 时间戳，毫秒）
+    pub opened_at: u64,
+    /// 最近一次成功上传的时间（Unix 时间戳，毫秒）
+    pub last_synced_at: Option<u64>,
+}
+
+/// 编辑会话
+#[derive(Debug, Clone)]
+pub struct EditSession {
+    pub info: EditSessionInfo,
+    /// 临时目录（会话结束时整体删除）
+    pub temp_dir: PathBuf,
+    /// 最近一次同步（下载或上传）时的修改标记
+    pub synced: Option<FileStamp>,
+    /// 最近一次上传失败时的修改标记（文件不变时不再重试）
+    pub failed: Option<FileStamp>,
+    /// 上一次检查时的修改标记
+    pub last_seen: Option<FileStamp>,
+}
+
+impl EditSession {
+    pub fn local_path(&self) -> PathBuf {
+        PathBuf::from(&self.info.local_path)
+    }
+
+    /// 本地文件是否有尚未上传的修改
+    pub fn has_unsynced_changes(&self) -> bool {
+        has_unsynced_changes(self.synced, FileStamp::read(&self.local_path()))
+    }
+
+    /// 构造该会话的同步状态事件
+    pub fn event(&self, status: EditSyncStatus, bytes: Option<u64>, error: Option<String>) -> EditSyncEvent {
+        EditSyncEvent {
+            edit_id: self.info.edit_id.clone(),
+            connection_id: self.info.connection_id.clone(),
+            remote_path: self.info.remote_path.clone(),
+            status,
+            bytes,
+            error,
+            timestamp: crate::sftp::client::now_millis(),
+        }
+    }
+}
+
+/// 根据本次检查结果判断是否需要上传
+///
+/// 文件稳定（与上一次检查相同）且与上次同步时不同才上传，避免读到编辑器写了一半的文件
+pub fn should_upload(synced: Option<FileStamp>, last_seen: Option<FileStamp>, current: Option<FileStamp>) -> bool {
+    match current {
+        Some(current) => last_seen == Some(current) && synced != Some(current),
+        None => false,
+    }
+}
+
+/// 本地文件是否与上次同步时不同（文件已被删除时没有可保留的内容）
+pub fn has_unsynced_changes(synced: Option<FileStamp>, current: Option<FileStamp>) -> bool {
+    current.is_some() && current != synced
+}
+
+/// 编辑会话的临时目录：`<系统临时目录>/ssh-terminal-edit/<edit_id>`
+pub fn temp_dir_for(edit_id: &str) -> PathBuf {
+    std::env::temp_dir().join("ssh-terminal-edit").join(edit_id)
+}
+
+/// 创建编辑会话的临时目录（Unix 上权限为 700，避免同一台机器上的其他用户读取正在编辑的文件）
+pub fn create_temp_dir(path: &Path) -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::DirBuilderExt;
+
+        std::fs::DirBuilder::new().recursive(true).mode(0o700).create(path)
+    }
+    #[cfg(not(unix))]
+    std::fs::create_dir_all(path)
+}
+
+/// 写入下载的远程文件（Unix 上权限为 600）
+pub fn write_local_file(path: &Path, content: &[u8]) -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::io::Write;
+        use std::os::unix::fs::OpenOptionsExt;
+
+        let mut file = std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .mode(0o600)
+            .open(path)?;
+        file.write_all(content)
+    }
+    #[cfg(not(unix))]
+    std::fs::write(path, content)
+}
+
+/// 远程路径的文件名，用作本地临时文件名（保留扩展名，便于编辑器识别语法）
+pub fn local_file_name(remote_path: &str) -> String {
+    let name = remote_path.trim_end_matches('/').rsplit('/').next().unwrap_or("");
+    // 防止 `..` 之类的名称逃出临时目录
+    if name.is_empty() || name == "." || name == ".." {
+        "untitled".to_string()
+    } else {
+        name.replace('\\', "_")
+    }
+}
+
+/// 打开编辑器
+///
+/// 指定了编辑器命令（如 `code --wait`）时直接启动该命令，否则交给系统默认程序打开
+pub fn launch_editor(path: &Path, editor: Option<&str>) -> std::result::Result<(), String> {
+    let Some(editor) = editor.map(str::trim).filter(|editor| !editor.is_empty()) else {
+        return tauri_plugin_opener::open_path(path, None::<&str>).map_err(|e| e.to_string());
+    };
+
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or(editor);
+    let mut child = std::process::Command::new(program)
+        .args(parts)
+        .arg(path)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .map_err(|e| format!("无法启动编辑器 '{}': {}", program, e))?;
+    // 编辑器退出后回收进程，编辑会话本身不受影响（保存仍由轮询检测）
+    std::thread::spawn(move || {
+        let _ = child.wait();
+    });
+    Ok(())
+}
+
+/// 编辑会话表: edit_id -> EditSession
+#[derive(Default)]
+pub struct EditRegistry {
+    sessions: Mutex<HashMap<String, EditSession>>,
+}
+
+impl EditRegistry {
+    pub fn insert(&self, session: EditSession) {
+        self.sessions.lock().unwrap().insert(session.info.edit_id.clone(), session);
+    }
+
+    pub fn remove(&self, edit_id: &str) -> Option<EditSession> {
+        self.sessions.lock().unwrap().remove(edit_id)
+    }
+
+    pub fn snapshot(&self) -> Vec<EditSession> {
+        self.sessions.lock().unwrap().values().cloned().collect()
+    }
+
+    pub fn list(&self) -> Vec<EditSessionInfo> {
+        let mut list: Vec<_> = self.sessions.lock().unwrap().values().map(|s| s.info.clone()).collect();
+        list.sort_by_key(|info| info.opened_at);
+        list
+    }
+
+    /// 记录本次检查看到的修改标记
+    pub fn observe(&self, edit_id: &str, stamp: Option<FileStamp>) {
+        if let Some(session) = self.sessions.lock().unwrap().get_mut(edit_id) {
+            session.last_seen = stamp;
+        }
+    }
+
+    /// 记录上传成功
+    pub fn mark_synced(&self, edit_id: &str, stamp: FileStamp, at: u64) {
+        if let Some(session) = self.sessions.lock().unwrap().get_mut(edit_id) {
+            session.synced = Some(stamp);
+            session.info.last_synced_at = Some(at);
+        }
+    }
+
+    /// 记录上传失败，避免文件不变时每秒重试；再次保存后会重新上传
+    ///
+    /// 不更新 `synced`，结束会话时仍能识别出未上传的修改
+    pub fn mark_failed(&self, edit_id: &str, stamp: FileStamp) {
+        if let Some(session) = self.sessions.lock().unwrap().get_mut(edit_id) {
+            session.failed = Some(stamp);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stamp(secs: u64, len: u64) -> Option<FileStamp> {
+        Some(FileStamp {
+            modified: SystemTime::UNIX_EPOCH + Duration::from_secs(secs),
+            len,
+        })
+    }
+
+    #[test]
+    fn test_should_upload() {
+        // 未修改
+        assert!(!should_upload(stamp(1, 10), stamp(1, 10), stamp(1, 10)));
+        // 刚修改，等下一次检查确认写入完成
+        assert!(!should_upload(stamp(1, 10), stamp(1, 10), stamp(2, 12)));
+        // 修改后稳定
+        assert!(should_upload(stamp(1, 10), stamp(2, 12), stamp(2, 12)));
+        // 文件暂时不存在
+        assert!(!should_upload(stamp(1, 10), stamp(1, 10), None));
+    }
+
+    #[test]
+    fn test_has_unsynced_changes() {
+        // 断开前最后一次保存还没来得及上传
+        assert!(has_unsynced_changes(stamp(1, 10), stamp(2, 12)));
+        // 已同步
+        assert!(!has_unsynced_changes(stamp(2, 12), stamp(2, 12)));
+        // 本地文件已被删除
+        assert!(!has_unsynced_changes(stamp(1, 10), None));
+    }
+
+    #[test]
+    fn test_dirty_session_after_failed_upload() {
+        let dir = std::env::temp_dir().join(format!("ssh-terminal-edit-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let local = dir.join("a.txt");
+        std::fs::write(&local, "old").unwrap();
+
+        let registry = EditRegistry::default();
+        registry.insert(EditSession {
+            info: EditSessionInfo {
+                edit_id: "e1".to_string(),
+                connection_id: "c1".to_string(),
+                remote_path: "/tmp/a.txt".to_string(),
+                local_path: local.to_string_lossy().to_string(),
+                opened_at: 1,
+                last_synced_at: None,
+            },
+            temp_dir: dir.clone(),
+            synced: FileStamp::read(&local),
+            failed: None,
+            last_seen: FileStamp::read(&local),
+        });
+        assert!(!registry.snapshot()[0].has_unsynced_changes());
+
+        // 连接断开时保存的修改上传失败，结束会话时仍应视为未同步
+        std::fs::write(&local, "new content").unwrap();
+        let current = FileStamp::read(&local).unwrap();
+        registry.mark_failed("e1", current);
+        let session = registry.remove("e1").unwrap();
+        assert_eq!(session.failed, Some(current));
+        assert!(session.has_unsynced_changes());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_temp_files_are_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = temp_dir_for(&format!("test-{}", uuid::Uuid::new_v4()));
+        create_temp_dir(&dir).unwrap();
+        let local = dir.join("secret.conf");
+        write_local_file(&local, b"password=1").unwrap();
+
+        let dir_mode = std::fs::metadata(&dir).unwrap().permissions().mode() & 0o777;
+        let file_mode = std::fs::metadata(&local).unwrap().permissions().mode() & 0o777;
+        assert_eq!(dir_mode, 0o700);
+        assert_eq!(file_mode, 0o600);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_local_file_name() {
+        assert_eq!(local_file_name("/etc/nginx/nginx.conf"), "nginx.conf");
+        assert_eq!(local_file_name("/srv/app/"), "app");
+        assert_eq!(local_file_name("/srv/.."), "untitled");
+        assert_eq!(local_file_name("/"), "untitled");
+        assert_eq!(local_file_name("/tmp/a\\b.txt"), "a_b.txt");
+    }
+
+    #[test]
+    fn test_registry_sync_marks() {
+        let registry = EditRegistry::default();
+        registry.insert(EditSession {
+            info: EditSessionInfo {
+                edit_id: "e1".to_string(),
+                connection_id: "c1".to_string(),
+                remote_path: "/tmp/a.txt".to_string(),
+                local_path: "/tmp/local/a.txt".to_string(),
+                opened_at: 1,
+                last_synced_at: None,
+            },
+            temp_dir: PathBuf::from("/tmp/local"),
+            synced: stamp(1, 10),
+            failed: None,
+            last_seen: stamp(1, 10),
+        });
+
+        registry.observe("e1", stamp(2, 12));
+        registry.mark_synced("e1", stamp(2, 12).unwrap(), 5);
+        let session = registry.snapshot().pop().unwrap();
+        assert_eq!(session.synced, stamp(2, 12));
+        assert_eq!(session.last_seen, stamp(2, 12));
+        assert_eq!(registry.list()[0].last_synced_at, Some(5));
+
+        assert!(registry.remove("e1").is_some());
+        assert!(registry.list().is_empty());
+    }
+}
@@ -3,29 +3,91 @@
 //! 管理多个 SFTP 会话，复用现有 SSH 连接
 
 use crate::error::{Result, SSHError};
-use crate::sftp::client::SftpClient;
+use crate::sftp::client::{now_millis, SftpClient};
+use crate::sftp::watchdog::{owner_task_id, TaskWatchdog, CHECK_INTERVAL, TASK_STALLED_EVENT};
+use crate::sftp::edit::{self, EditRegistry, EditSession, EditSessionInfo, EditSyncEvent, EditSyncStatus, FileStamp, EDIT_POLL_INTERVAL, EDIT_SYNC_EVENT};
+use crate::sftp::search::{SearchFilters, SearchMatch, SearchMethod, SearchSummary};
+use crate::sftp::{TransferProgress, TransferStatus};
+use crate::ssh::backend::ExecStream;
 use crate::ssh::manager::SSHManager;
-use std::collections::HashMap;
+use crate::ssh::session::SessionStatus;
+use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
-use tokio::sync::Mutex;
-use tracing::{debug, info};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+use tokio::sync::{watch, Mutex};
+use tracing::{debug, info, warn};
+
+/// 传输队列状态事件（载荷为 [`TransferQueueState`]）
+pub const QUEUE_STATE_EVENT: &str = "sftp-queue-state";
+
+/// 默认同时执行的传输任务数量，超出的任务排队等待
+const DEFAULT_MAX_CONCURRENT_TRANSFERS: usize = 3;
+
+/// 允许设置的最大并发传输数量
+const MAX_CONCURRENT_TRANSFERS_LIMIT: usize = 16;
+
+/// 每个连接最多保留的空闲任务客户端数量
+const POOL_MAX_IDLE_PER_CONNECTION: usize = 4;
+
+/// 空闲任务客户端的过期时间
+const POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+
+/// 权限审计命令的超时时间（大目录树扫描较慢）
+const PERMISSION_AUDIT_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// 采集远程用户和用户组的超时时间
+const ACCOUNTS_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// 远程计算文件校验和的超时时间（大文件需要读取整个文件）
+const CHECKSUM_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// 检测远程 find 的超时时间
+const SEARCH_PROBE_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// 通过 find 搜索的超时时间
+const SEARCH_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// 通过 SFTP 搜索时同时列目录的客户端数量
+const SEARCH_CONCURRENCY: usize = 4;
+
+/// 任务客户端: (connection_id, SFTP Client)
+type TaskClient = (String, Arc<Mutex<SftpClient>>);
+
+/// 连接池中的空闲 SFTP Client
+struct PooledClient {
+    client: Arc<Mutex<SftpClient>>,
+    idle_since: Instant,
+}
 
 /// SFTP 管理器
 ///
 /// 负责创建和管理 SFTP 客户端会话
 /// 采用双客户端架构：
 /// - 浏览客户端：用于快速浏览操作（list_dir, remove_file 等）
-/// - 任务客户端：每个上传/下载任务使用独立的 SFTP Client
+/// - 任务客户端：每个上传/下载任务使用独立的 SFTP Client，
+///   任务结束后归还到按连接划分的连接池中，供后续任务复用
 pub struct SftpManager {
     ssh_manager: Arc<SSHManager>,
     // 浏览专用客户端映射: connection_id -> Arc<Mutex<SftpClient>>
     // 用于所有快速浏览操作（list_dir, remove_file, rename 等）
     browse_clients: Arc<Mutex<HashMap<String, Arc<Mutex<SftpClient>>>>>,
-    // 任务客户端映射: task_id -> Arc<Mutex<SftpClient>>
+    // 任务客户端映射: task_id -> (connection_id, Arc<Mutex<SftpClient>>)
     // 每个上传/下载任务使用独立的 SFTP Client，实现完全并发
-    task_clients: Arc<Mutex<HashMap<String, Arc<Mutex<SftpClient>>>>>,
+    task_clients: Arc<Mutex<HashMap<String, TaskClient>>>,
+    // 空闲任务客户端池: connection_id -> 空闲客户端列表
+    // 任务结束后客户端归还到池中，避免每次传输都重新打开 SFTP channel
+    client_pool: Arc<Mutex<HashMap<String, Vec<PooledClient>>>>,
+    // 传输队列：上传/下载任务按顺序排队，限制同时进行的任务数量
+    transfer_queue: Arc<TransferQueue>,
+    // 看门狗：检测长时间没有进度的任务
+    watchdog: Arc<TaskWatchdog>,
     // 取消令牌映射: task_id -> CancellationToken
     cancellation_tokens: Arc<Mutex<HashMap<String, tokio_util::sync::CancellationToken>>>,
+    // 远程用户和用户组缓存: connection_id -> 采集结果
+    accounts_cache: Arc<Mutex<HashMap<String, Arc<super::accounts::RemoteAccounts>>>>,
+    // 本地编辑会话: edit_id -> 临时文件和同步状态
+    edits: Arc<EditRegistry>,
 }
 
 impl SftpManager {
@@ -36,7 +98,12 @@ impl SftpManager {
             ssh_manager,
             browse_clients: Arc::new(Mutex::new(HashMap::new())),
             task_clients: Arc::new(Mutex::new(HashMap::new())),
+            client_pool: Arc::new(Mutex::new(HashMap::new())),
+            transfer_queue: Arc::new(TransferQueue::new(DEFAULT_MAX_CONCURRENT_TRANSFERS)),
+            watchdog: Arc::new(TaskWatchdog::default()),
             cancellation_tokens: Arc::new(Mutex::new(HashMap::new())),
+            accounts_cache: Arc::new(Mutex::new(HashMap::new())),
+            edits: Arc::new(EditRegistry::default()),
         }
     }
 
@@ -82,6 +149,20 @@ impl SftpManager {
         client_guard.rename(old_path, new_path).await
     }
 
+    /// 创建符号链接（使用浏览客户端）
+    pub async fn create_symlink(&self, connection_id: &str, link_path: &str, target: &str) -> Result<()> {
+        let client = self.get_or_create_browse_client(connection_id).await?;
+        let client_guard = client.lock().await;
+        client_guard.create_symlink(link_path, target, false).await
+    }
+
+    /// 读取符号链接指向的路径（使用浏览客户端）
+    pub async fn read_link(&self, connection_id: &str, path: &str) -> Result<String> {
+        let client = self.get_or_create_browse_client(connection_id).await?;
+        let client_guard = client.lock().await;
+        client_guard.read_link(path).await
+    }
+
     /// 修改权限（使用浏览客户端）
     pub async fn chmod(&self, connection_id: &str, path: &str, mode: u32) -> Result<()> {
         let client = self.get_or_create_browse_client(connection_id).await?;
@@ -89,6 +170,316 @@ impl SftpManager {
         client_guard.chmod(path, mode).await
     }
 
+    /// 扫描目录中权限有风险的文件（通过 exec 执行 find，不占用 SFTP 客户端）
+    pub async fn permission_audit(
+        &self,
+        connection_id: &str,
+        path: &str,
+        limit: usize,
+    ) -> Result<super::audit::PermissionAuditReport> {
+        let connection = self.ssh_manager.get_connection(connection_id).await?;
+        let command = super::audit::build_command(path, limit);
+        let output = connection.exec(&command, PERMISSION_AUDIT_TIMEOUT).await?;
+
+        // 无权限读取的子目录会使 find 返回非零退出码，输出中的结果仍然有效
+        if !output.success() && output.stdout.is_empty() && !output.stderr.is_empty() {
+            return Err(SSHError::Ssh(String::from_utf8_lossy(&output.stderr).trim().to_string()));
+        }
+
+        let report = super::audit::parse_report(path, &output.stdout_lossy(), limit);
+        info!(
+            "Permission audit of {} on {}: {} finding(s), truncated: {}",
+            path, connection_id, report.findings.len(), report.truncated
+        );
+        Ok(report)
+    }
+
+    /// 计算远程文件的 SHA-256，`limit` 不为空时只计算文件前 `limit` 字节
+    ///
+    /// 优先通过 exec 执行 `sha256sum`/`shasum`；命令不可用或 exec 失败时，
+    /// 使用任务客户端 `<task_id>#checksum` 通过 SFTP 读取文件计算
+    pub async fn remote_sha256(
+        &self,
+        connection_id: &str,
+        path: &str,
+        limit: Option<u64>,
+        task_id: &str,
+        cancellation_token: &tokio_util::sync::CancellationToken,
+    ) -> Result<String> {
+        let command = match limit {
+            Some(len) => super::checksum::build_prefix_command(path, len),
+            None => super::checksum::build_command(path),
+        };
+        match self.ssh_manager.get_connection(connection_id).await?.exec(&command, CHECKSUM_TIMEOUT).await {
+            Ok(output) => {
+                if let Some(digest) = super::checksum::parse_output(&output.stdout_lossy()) {
+                    return Ok(digest);
+                }
+                warn!("No checksum tool on {}, hashing {} over SFTP", connection_id, path);
+            }
+            Err(e) => warn!("Checksum command failed on {}: {}, hashing {} over SFTP", connection_id, e, path),
+        }
+
+        let checksum_task_id = format!("{}#checksum", task_id);
+        let client = self.create_task_client(connection_id, &checksum_task_id).await?;
+        let result = client.lock().await.sha256(path, limit, cancellation_token).await;
+        self.cleanup_task_client(&checksum_task_id, result.is_ok()).await;
+        result
+    }
+
+    /// 搜索远程文件，匹配项分批交给 `on_matches`
+    ///
+    /// 优先通过 exec 执行 `find`；远程 find 不支持 `-printf` 或 exec 不可用时，
+    /// 使用最多 [`SEARCH_CONCURRENCY`] 个任务客户端逐层列目录。可通过 `cancel_task(search_id)` 取消
+    pub async fn search<F>(
+        &self,
+        connection_id: &str,
+        search_id: &str,
+        root: &str,
+        pattern: &str,
+        filters: &SearchFilters,
+        mut on_matches: F,
+    ) -> Result<SearchSummary>
+    where
+        F: FnMut(Vec<SearchMatch>) + Send,
+    {
+        let cancellation_token = self.get_cancellation_token(search_id).await;
+
+        let result = match self.search_with_find(connection_id, root, pattern, filters, &cancellation_token, &mut on_matches).await {
+            Ok(Some(found)) => Ok((found, SearchMethod::Find)),
+            Ok(None) => self
+                .search_with_sftp(connection_id, search_id, root, pattern, filters, &cancellation_token, &mut on_matches)
+                .await
+                .map(|found| (found, SearchMethod::Sftp)),
+            Err(e) => Err(e),
+        };
+        self.cleanup_cancellation_token(search_id).await;
+
+        let ((total_matches, truncated), method) = result?;
+        info!(
+            "Search {} in {} on {} finished via {:?}: {} match(es), truncated: {}",
+            search_id, root, connection_id, method, total_matches, truncated
+        );
+        Ok(SearchSummary {
+            search_id: search_id.to_string(),
+            total_matches,
+            truncated,
+            cancelled: cancellation_token.is_cancelled(),
+            method,
+        })
+    }
+
+    /// 通过 find 搜索，返回 (匹配数, 是否截断)；find 不可用时返回 None
+    async fn search_with_find<F>(
+        &self,
+        connection_id: &str,
+        root: &str,
+        pattern: &str,
+        filters: &SearchFilters,
+        cancellation_token: &tokio_util::sync::CancellationToken,
+        on_matches: &mut F,
+    ) -> Result<Option<(usize, bool)>>
+    where
+        F: FnMut(Vec<SearchMatch>) + Send,
+    {
+        let connection = self.ssh_manager.get_connection(connection_id).await?;
+        match connection.exec(super::search::FIND_PROBE_COMMAND, SEARCH_PROBE_TIMEOUT).await {
+            Ok(output) if output.stdout_lossy().trim() == "ok" => {}
+            Ok(_) => {
+                warn!("find -printf is unavailable on {}, searching over SFTP", connection_id);
+                return Ok(None);
+            }
+            Err(e) => {
+                warn!("Search probe failed on {}: {}, searching over SFTP", connection_id, e);
+                return Ok(None);
+            }
+        }
+
+        let limit = filters.limit();
+        let command = super::search::build_find_command(root, pattern, filters);
+        let mut buffer = Vec::new();
+        let mut total = 0;
+        let mut truncated = false;
+        // 按行解析，超过上限的一行只用于判断截断
+        let mut take_lines = |text: &[u8], total: &mut usize, truncated: &mut bool| {
+            let mut matches = Vec::new();
+            for found in String::from_utf8_lossy(text).lines().filter_map(super::search::parse_find_line) {
+                if *total >= limit {
+                    *truncated = true;
+                    break;
+                }
+                *total += 1;
+                matches.push(found);
+            }
+            if !matches.is_empty() {
+                on_matches(matches);
+            }
+        };
+
+        let exec = connection.exec_with(&command, SEARCH_TIMEOUT, |stream, data| {
+            if stream != ExecStream::Stdout {
+                return;
+            }
+            buffer.extend_from_slice(data);
+            if let Some(end) = buffer.iter().rposition(|b| *b == b'\n') {
+                let lines: Vec<u8> = buffer.drain(..=end).collect();
+                take_lines(&lines, &mut total, &mut truncated);
+            }
+        });
+        tokio::select! {
+            result = exec => {
+                result?;
+            }
+            _ = cancellation_token.cancelled() => {
+                info!("Search on {} cancelled", connection_id);
+            }
+        }
+
+        // 最后一行可能没有换行符
+        if !buffer.is_empty() && !cancellation_token.is_cancelled() {
+            take_lines(&buffer, &mut total, &mut truncated);
+        }
+        Ok(Some((total, truncated)))
+    }
+
+    /// 通过 SFTP 逐层列目录搜索，返回 (匹配数, 是否截断)
+    ///
+    /// 每层目录分配给多个任务客户端并发读取；无权限读取的目录跳过，不进入符号链接指向的目录
+    #[allow(clippy::too_many_arguments)]
+    async fn search_with_sftp<F>(
+        &self,
+        connection_id: &str,
+        search_id: &str,
+        root: &str,
+        pattern: &str,
+        filters: &SearchFilters,
+        cancellation_token: &tokio_util::sync::CancellationToken,
+        on_matches: &mut F,
+    ) -> Result<(usize, bool)>
+    where
+        F: FnMut(Vec<SearchMatch>) + Send,
+    {
+        let mut task_ids = Vec::new();
+        let mut clients = Vec::new();
+        for i in 0..SEARCH_CONCURRENCY {
+            let task_id = if i == 0 { search_id.to_string() } else { format!("{}#{}", search_id, i) };
+            match self.create_task_client(connection_id, &task_id).await {
+                Ok(client) => {
+                    clients.push(client);
+                    task_ids.push(task_id);
+                }
+                Err(e) if i > 0 => {
+                    warn!("Searching {} with {} SFTP channel(s): {}", connection_id, i, e);
+                    break;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        let limit = filters.limit();
+        let mut total = 0;
+        let mut truncated = false;
+        let mut level = vec![root.to_string()];
+        let mut depth = 1;
+
+        while !level.is_empty() && depth <= filters.max_depth() && !truncated && !cancellation_token.is_cancelled() {
+            let queue = std::sync::Mutex::new(level);
+            let listings = futures::future::join_all(clients.iter().map(|client| {
+                let queue = &queue;
+                async move {
+                    let mut client = client.lock().await;
+                    let mut entries = Vec::new();
+                    while !cancellation_token.is_cancelled() {
+                        let Some(dir) = queue.lock().unwrap().pop() else { break };
+                        match client.list_dir(&dir).await {
+                            Ok(list) => entries.extend(list),
+                            Err(e) => debug!("Skipping {} during search: {}", dir, e),
+                        }
+                    }
+                    entries
+                }
+            }))
+            .await;
+
+            let mut next = Vec::new();
+            let mut matches = Vec::new();
+            for entry in listings.into_iter().flatten() {
+                let is_dir = entry.is_dir && !entry.is_symlink;
+                let modified = entry.modified as i64;
+                if is_dir {
+                    next.push(entry.path.clone());
+                }
+                if filters.matches(pattern, &entry.name, is_dir, entry.size, modified) {
+                    if total >= limit {
+                        truncated = true;
+                        break;
+                    }
+                    total += 1;
+                    matches.push(SearchMatch {
+                        path: entry.path,
+                        name: entry.name,
+                        is_dir,
+                        size: entry.size,
+                        modified,
+                    });
+                }
+            }
+            if !matches.is_empty() {
+                on_matches(matches);
+            }
+
+            level = next;
+            depth += 1;
+        }
+
+        for task_id in &task_ids {
+            self.cleanup_task_client(task_id, !cancellation_token.is_cancelled()).await;
+        }
+        Ok((total, truncated))
+    }
+
+    /// 获取远程用户和用户组（按连接缓存，`refresh` 为 true 时重新采集）
+    pub async fn remote_accounts(
+        &self,
+        connection_id: &str,
+        refresh: bool,
+    ) -> Result<Arc<super::accounts::RemoteAccounts>> {
+        if !refresh {
+            if let Some(accounts) = self.accounts_cache.lock().await.get(connection_id) {
+                return Ok(accounts.clone());
+            }
+        }
+
+        let connection = self.ssh_manager.get_connection(connection_id).await?;
+        let output = connection.exec(&super::accounts::build_command(), ACCOUNTS_TIMEOUT).await?;
+        let accounts = Arc::new(super::accounts::parse_accounts(&output.stdout_lossy()));
+        if accounts.users.is_empty() {
+            return Err(SSHError::Ssh(format!(
+                "Failed to read remote users: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+
+        info!(
+            "Collected {} users and {} groups on connection {}",
+            accounts.users.len(), accounts.groups.len(), connection_id
+        );
+        self.accounts_cache.lock().await.insert(connection_id.to_string(), accounts.clone());
+        Ok(accounts)
+    }
+
+    /// 获取路径信息（使用浏览客户端），路径不存在时返回 None
+    pub async fn stat(&self, connection_id: &str, path: &str) -> Result<Option<super::SftpFileInfo>> {
+        let client = self.get_or_create_browse_client(connection_id).await?;
+        let client_guard = client.lock().await;
+        client_guard.stat(path).await
+    }
+
+    /// 传输队列
+    pub fn transfer_queue(&self) -> Arc<TransferQueue> {
+        self.transfer_queue.clone()
+    }
+
     /// 读取文件（使用浏览客户端）
     pub async fn read_file(&self, connection_id: &str, path: &str) -> Result<Vec<u8>> {
         let client = self.get_or_create_browse_client(connection_id).await?;
@@ -96,6 +487,32 @@ impl SftpManager {
         client_guard.read_file(path).await
     }
 
+    /// 分块流式读取文件（使用独立的任务客户端）
+    ///
+    /// 大文件读取期间不会占用浏览客户端，可通过 `cancel_task(stream_id)` 取消
+    pub async fn read_file_stream<F>(
+        &self,
+        connection_id: &str,
+        stream_id: &str,
+        path: &str,
+        on_chunk: F,
+    ) -> Result<u64>
+    where
+        F: FnMut(u64, Vec<u8>, u64) -> Result<()>,
+    {
+        let client = self.create_task_client(connection_id, stream_id).await?;
+        let cancellation_token = self.get_cancellation_token(stream_id).await;
+
+        let result = {
+            let client_guard = client.lock().await;
+            client_guard.read_file_chunked(path, &cancellation_token, on_chunk).await
+        };
+
+        self.cleanup_task_client(stream_id, result.is_ok()).await;
+        self.cleanup_cancellation_token(stream_id).await;
+        result
+    }
+
     /// 写入文件（使用浏览客户端）
     pub async fn write_file(&self, connection_id: &str, path: &str, content: Vec<u8>) -> Result<()> {
         tracing::info!("=== Write File Start ===");
@@ -119,10 +536,15 @@ impl SftpManager {
     async fn get_or_create_browse_client(&self, connection_id: &str) -> Result<Arc<Mutex<SftpClient>>> {
         // 检查缓存
         {
-            let browse_clients = self.browse_clients.lock().await;
+            let mut browse_clients = self.browse_clients.lock().await;
             if let Some(client) = browse_clients.get(connection_id) {
-                debug!("Using cached browse SFTP client for connection: {}", connection_id);
-                return Ok(client.clone());
+                // 连接或途经的跳板机断开后（如漫游重连），旧客户端的 channel 已失效
+                if !client.lock().await.is_transport_closed() {
+                    debug!("Using cached browse SFTP client for connection: {}", connection_id);
+                    return Ok(client.clone());
+                }
+                debug!("Discarding browse SFTP client on closed transport for connection: {}", connection_id);
+                browse_clients.remove(connection_id);
             }
         }
 
@@ -139,33 +561,131 @@ impl SftpManager {
         Ok(client_arc)
     }
 
-    /// 为任务创建独立的 SFTP Client
+    /// 为任务获取独立的 SFTP Client
     ///
     /// 每个上传/下载任务使用独立的 SFTP Channel，实现完全并发
-    /// 基于同一个 SSH 连接打开新的 SFTP subsystem
+    /// 优先从连接池中取出未过期且仍然可用的空闲客户端，
+    /// 池中没有可用客户端时才基于同一个 SSH 连接打开新的 SFTP subsystem
     pub async fn create_task_client(&self, connection_id: &str, task_id: &str) -> Result<Arc<Mutex<SftpClient>>> {
-        info!("Creating task SFTP client for task: {} on connection: {}", task_id, connection_id);
-
-        // 创建新的 SFTP Client（基于同一个 SSH 连接）
-        let client = self.create_sftp_client(connection_id).await?;
-        let client_arc = Arc::new(Mutex::new(client));
+        let client_arc = match self.checkout_pooled_client(connection_id).await {
+            Some(client) => {
+                info!("Reusing pooled SFTP client for task: {} on connection: {}", task_id, connection_id);
+                client
+            }
+            None => {
+                info!("Creating task SFTP client for task: {} on connection: {}", task_id, connection_id);
+                let client = self.create_sftp_client(connection_id).await?;
+                Arc::new(Mutex::new(client))
+            }
+        };
 
-        // 注册到任务映射
+        // 注册到任务映射和看门狗
+        let activity = client_arc.lock().await.activity();
+        self.watchdog.watch(task_id, connection_id, activity, now_millis());
         let mut task_clients = self.task_clients.lock().await;
-        task_clients.insert(task_id.to_string(), client_arc.clone());
+        task_clients.insert(task_id.to_string(), (connection_id.to_string(), client_arc.clone()));
 
         Ok(client_arc)
     }
 
     /// 清理任务 SFTP Client
     ///
-    /// 在任务完成或失败后调用，释放资源
-    pub async fn cleanup_task_client(&self, task_id: &str) {
-        let mut task_clients = self.task_clients.lock().await;
-        task_clients.remove(task_id);
+    /// 在任务完成或失败后调用。只有成功完成的任务（`succeeded` 为 true）的客户端会归还到连接池中等待复用，
+    /// 失败或被取消的任务可能把 channel 留在某个操作中间，直接丢弃
+    pub async fn cleanup_task_client(&self, task_id: &str, succeeded: bool) {
+        self.watchdog.unwatch(task_id);
+        let removed = {
+            let mut task_clients = self.task_clients.lock().await;
+            task_clients.remove(task_id)
+        };
+
+        match removed {
+            Some((connection_id, client)) if succeeded => self.checkin_pooled_client(&connection_id, client).await,
+            Some((connection_id, _)) => debug!("Dropping SFTP client of unfinished task {} on connection: {}", task_id, connection_id),
+            None => {}
+        }
         info!("Task SFTP client cleaned up for task: {}", task_id);
     }
 
+    /// 文件所在主机及途经的跳板机
+    pub async fn route(&self, connection_id: &str) -> Result<super::route::SftpRoute> {
+        let connection = self.ssh_manager.get_connection(connection_id).await?;
+        Ok(super::route::SftpRoute::from_config(&connection.config))
+    }
+
+    /// 从连接池取出一个可用的空闲客户端
+    ///
+    /// 过期的客户端直接丢弃；取出的客户端会先做一次存活检查，
+    /// 失效的 channel（例如连接已断开）不会被交给任务使用
+    async fn checkout_pooled_client(&self, connection_id: &str) -> Option<Arc<Mutex<SftpClient>>> {
+        loop {
+            let candidate = {
+                let mut pool = self.client_pool.lock().await;
+                let idle = pool.get_mut(connection_id)?;
+                idle.retain(|c| c.idle_since.elapsed() < POOL_IDLE_TIMEOUT);
+                let candidate = idle.pop();
+                if idle.is_empty() {
+                    pool.remove(connection_id);
+                }
+                candidate?
+            };
+
+            let alive = {
+                let client = candidate.client.lock().await;
+                !client.is_transport_closed() && client.is_alive().await
+            };
+            if alive {
+                return Some(candidate.client);
+            }
+            debug!("Discarding dead pooled SFTP client for connection: {}", connection_id);
+        }
+    }
+
+    /// 将客户端归还到连接池
+    async fn checkin_pooled_client(&self, connection_id: &str, client: Arc<Mutex<SftpClient>>) {
+        let mut pool = self.client_pool.lock().await;
+        let idle = pool.entry(connection_id.to_string()).or_default();
+        idle.retain(|c| c.idle_since.elapsed() < POOL_IDLE_TIMEOUT);
+
+        if idle.len() < POOL_MAX_IDLE_PER_CONNECTION {
+            idle.push(PooledClient {
+                client,
+                idle_since: Instant::now(),
+            });
+            debug!("Returned SFTP client to pool for connection: {} ({} idle)", connection_id, idle.len());
+        } else {
+            debug!("SFTP client pool full for connection: {}, dropping client", connection_id);
+        }
+    }
+
+    /// 清空指定连接的客户端池
+    ///
+    /// 在连接断开后调用，释放池中所有空闲的 SFTP channel
+    pub async fn purge_connection_pool(&self, connection_id: &str) {
+        let mut pool = self.client_pool.lock().await;
+        if let Some(idle) = pool.remove(connection_id) {
+            info!("Purged {} pooled SFTP clients for connection: {}", idle.len(), connection_id);
+        }
+    }
+
+    /// 清理所有连接池中底层连接已断开或空闲超时的客户端
+    ///
+    /// 由看门狗定期调用，覆盖远程断开、删除会话等没有经过 `session_disconnect` 的情况
+    async fn purge_stale_pools(&self) {
+        let mut pool = self.client_pool.lock().await;
+        pool.retain(|connection_id, idle| {
+            let before = idle.len();
+            idle.retain(|c| {
+                c.idle_since.elapsed() < POOL_IDLE_TIMEOUT
+                    && !c.client.try_lock().is_ok_and(|client| client.is_transport_closed())
+            });
+            if idle.len() < before {
+                debug!("Purged {} stale pooled SFTP clients for connection: {}", before - idle.len(), connection_id);
+            }
+            !idle.is_empty()
+        });
+    }
+
     /// 创建 SFTP 客户端
     ///
     /// 通过打开 SSH 连接上的 SFTP 子系统来创建
@@ -219,6 +739,177 @@ impl SftpManager {
         self.close_browse_session(connection_id).await
     }
 
+    /// 传输看门狗
+    pub fn watchdog(&self) -> Arc<TaskWatchdog> {
+        self.watchdog.clone()
+    }
+
+    /// 定期运行看门狗，把卡住的任务转发为 `sftp-task-stalled` 事件，并放弃被自动取消的任务；
+    /// 同时清理连接池中已失效的客户端
+    pub fn spawn_watchdog(self: Arc<Self>, app: AppHandle) {
+        tauri::async_runtime::spawn(async move {
+            let mut interval = tokio::time::interval(CHECK_INTERVAL);
+            loop {
+                interval.tick().await;
+                for event in self.watchdog.check(now_millis()) {
+                    warn!(
+                        "Task {} has made no progress for {}s (cancelled: {})",
+                        event.task_id, event.idle_secs, event.cancelled
+                    );
+                    if event.cancelled {
+                        self.abandon_task(&event.task_id).await;
+                    }
+                    let _ = app.emit(TASK_STALLED_EVENT, &event);
+                }
+                self.purge_stale_pools().await;
+            }
+        });
+    }
+
+    /// 下载远程文件到临时目录并用本地编辑器打开
+    ///
+    /// 保存后由 [`Self::spawn_edit_watcher`] 上传回服务器
+    pub async fn open_edit(&self, connection_id: &str, remote_path: &str, editor: Option<&str>) -> Result<EditSessionInfo> {
+        let content = self.read_file(connection_id, remote_path).await?;
+
+        let edit_id = uuid::Uuid::new_v4().to_string();
+        let temp_dir = edit::temp_dir_for(&edit_id);
+        let local_path = temp_dir.join(edit::local_file_name(remote_path));
+        let write_local = || -> std::io::Result<()> {
+            edit::create_temp_dir(&temp_dir)?;
+            edit::write_local_file(&local_path, &content)
+        };
+        if let Err(e) = write_local() {
+            let _ = std::fs::remove_dir_all(&temp_dir);
+            return Err(SSHError::Io(format!("无法写入临时文件: {}", e)));
+        }
+
+        let stamp = FileStamp::read(&local_path);
+        let session = EditSession {
+            info: EditSessionInfo {
+                edit_id: edit_id.clone(),
+                connection_id: connection_id.to_string(),
+                remote_path: remote_path.to_string(),
+                local_path: local_path.to_string_lossy().to_string(),
+                opened_at: now_millis(),
+                last_synced_at: None,
+            },
+            temp_dir: temp_dir.clone(),
+            synced: stamp,
+            failed: None,
+            last_seen: stamp,
+        };
+        let info = session.info.clone();
+
+        if let Err(e) = edit::launch_editor(&local_path, editor) {
+            let _ = std::fs::remove_dir_all(&temp_dir);
+            return Err(SSHError::Io(e));
+        }
+        self.edits.insert(session);
+        info!("Editing {} ({} bytes) locally at {:?}", remote_path, content.len(), local_path);
+        Ok(info)
+    }
+
+    /// 结束编辑会话，返回要发送的 `sftp-edit-sync` 事件，会话不存在时返回 None
+    ///
+    /// 本地文件有未上传的修改时保留临时文件并返回 `failed` 事件（错误信息中给出本地路径），
+    /// 否则删除临时目录并返回 `closed` 事件
+    pub fn close_edit(&self, edit_id: &str) -> Option<EditSyncEvent> {
+        let session = self.edits.remove(edit_id)?;
+        if session.has_unsynced_changes() {
+            warn!(
+                "Closed edit session {} ({}) with unsynced changes, keeping {}",
+                edit_id, session.info.remote_path, session.info.local_path
+            );
+            let error = format!("本地文件有未上传的修改，已保留在 {}", session.info.local_path);
+            return Some(session.event(EditSyncStatus::Failed, None, Some(error)));
+        }
+        if let Err(e) = std::fs::remove_dir_all(&session.temp_dir) {
+            warn!("Failed to remove edit temp dir {:?}: {}", session.temp_dir, e);
+        }
+        info!("Closed edit session {} ({})", edit_id, session.info.remote_path);
+        Some(session.event(EditSyncStatus::Closed, None, None))
+    }
+
+    /// 当前的编辑会话
+    pub fn list_edits(&self) -> Vec<EditSessionInfo> {
+        self.edits.list()
+    }
+
+    /// 定期检查编辑中的本地文件，保存后上传并发送 `sftp-edit-sync` 事件
+    ///
+    /// 所属连接被关闭或断开（重连中的连接除外）时结束会话，见 [`Self::close_edit`]
+    pub fn spawn_edit_watcher(self: Arc<Self>, app: AppHandle) {
+        tauri::async_runtime::spawn(async move {
+            let mut interval = tokio::time::interval(EDIT_POLL_INTERVAL);
+            loop {
+                interval.tick().await;
+                for session in self.edits.snapshot() {
+                    let edit_id = session.info.edit_id.clone();
+                    let alive = match self.ssh_manager.get_connection(&session.info.connection_id).await {
+                        Ok(connection) => !matches!(
+                            connection.status().await,
+                            SessionStatus::Disconnected { .. } | SessionStatus::Error(_)
+                        ),
+                        Err(_) => false,
+                    };
+                    if !alive {
+                        if let Some(event) = self.close_edit(&edit_id) {
+                            let _ = app.emit(EDIT_SYNC_EVENT, event);
+                        }
+                        continue;
+                    }
+
+                    let current = FileStamp::read(&session.local_path());
+                    let upload = edit::should_upload(session.synced, session.last_seen, current)
+                        && session.failed != current;
+                    self.edits.observe(&edit_id, current);
+                    let Some(stamp) = current.filter(|_| upload) else {
+                        continue;
+                    };
+
+                    let _ = app.emit(EDIT_SYNC_EVENT, session.event(EditSyncStatus::Uploading, None, None));
+                    let result = match std::fs::read(session.local_path()) {
+                        Ok(content) => {
+                            let len = content.len() as u64;
+                            self.write_file(&session.info.connection_id, &session.info.remote_path, content)
+                                .await
+                                .map(|_| len)
+                        }
+                        Err(e) => Err(SSHError::Io(e.to_string())),
+                    };
+                    let event = match result {
+                        Ok(len) => {
+                            self.edits.mark_synced(&edit_id, stamp, now_millis());
+                            info!("Synced {} ({} bytes)", session.info.remote_path, len);
+                            session.event(EditSyncStatus::Synced, Some(len), None)
+                        }
+                        Err(e) => {
+                            self.edits.mark_failed(&edit_id, stamp);
+                            warn!("Failed to sync {}: {}", session.info.remote_path, e);
+                            session.event(EditSyncStatus::Failed, None, Some(e.to_string()))
+                        }
+                    };
+                    let _ = app.emit(EDIT_SYNC_EVENT, event);
+                }
+            }
+        });
+    }
+
+    /// 放弃卡住的任务
+    ///
+    /// 触发取消令牌并清理，该任务的所有客户端（包括 `<task_id>#<n>` worker）直接丢弃
+    /// （底层 channel 已不可靠，不归还到连接池），同时让出传输队列中的位置
+    async fn abandon_task(&self, task_id: &str) {
+        let owner_id = owner_task_id(task_id);
+        if let Some(token) = self.cancellation_tokens.lock().await.remove(owner_id) {
+            token.cancel();
+        }
+        self.task_clients.lock().await.retain(|id, _| owner_task_id(id) != owner_id);
+        self.transfer_queue.finish(owner_id);
+        info!("Abandoned stalled task: {}", task_id);
+    }
+
     /// 获取或创建取消令牌（基于 task_id）
     ///
     /// 返回该任务的取消令牌，如果不存在则创建新的
@@ -236,6 +927,11 @@ impl SftpManager {
         }
     }
 
+    /// 任务是否正在进行（存在取消令牌）
+    pub async fn is_task_running(&self, task_id: &str) -> bool {
+        self.cancellation_tokens.lock().await.contains_key(task_id)
+    }
+
     /// 取消任务操作（上传或下载）
     ///
     /// # 参数
@@ -280,6 +976,7 @@ impl SftpManager {
             // 这里我们只是从缓存中移除，让 SFTP session 自然关闭
             info!("Browse SFTP session removed from cache for connection: {}", connection_id);
         }
+        self.accounts_cache.lock().await.remove(connection_id);
 
         Ok(())
     }
@@ -295,8 +992,244 @@ impl SftpManager {
         let task_count = task_clients.len();
         browse_clients.clear();
         task_clients.clear();
+        self.client_pool.lock().await.clear();
+        self.accounts_cache.lock().await.clear();
 
         info!("All {} browse and {} task SFTP sessions removed from cache", browse_count, task_count);
         Ok(())
     }
 }
+
+/// 传输队列状态（`sftp-queue-state` 事件内容）
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferQueueState {
+    pub paused: bool,
+    pub max_concurrent: usize,
+    /// 正在进行的任务
+    pub running: Vec<TransferProgress>,
+    /// 排队中的任务（按执行顺序）
+    pub pending: Vec<TransferProgress>,
+}
+
+struct QueueInner {
+    paused: bool,
+    max_concurrent: usize,
+    running: Vec<TransferProgress>,
+    pending: VecDeque<TransferProgress>,
+}
+
+/// 传输队列
+///
+/// 上传/下载任务开始前在队列中等待，按排队顺序执行，同时最多运行 `max_concurrent` 个。
+/// 暂停只阻止新任务开始，已经开始的任务继续执行（可单独取消）。
+/// 每次状态变化都会通知订阅者，由 [`TransferQueue::forward_events`] 转发为前端事件。
+pub struct TransferQueue {
+    inner: std::sync::Mutex<QueueInner>,
+    changed: watch::Sender<u64>,
+}
+
+/// 已开始的队列任务，drop 时从队列中移除并让出位置
+pub struct TransferTicket {
+    queue: Arc<TransferQueue>,
+    id: String,
+}
+
+impl Drop for TransferTicket {
+    fn drop(&mut self) {
+        self.queue.finish(&self.id);
+    }
+}
+
+impl TransferQueue {
+    pub fn new(max_concurrent: usize) -> Self {
+        Self {
+            inner: std::sync::Mutex::new(QueueInner {
+                paused: false,
+                max_concurrent: max_concurrent.clamp(1, MAX_CONCURRENT_TRANSFERS_LIMIT),
+                running: Vec::new(),
+                pending: VecDeque::new(),
+            }),
+            changed: watch::channel(0).0,
+        }
+    }
+
+    /// 当前队列状态
+    pub fn snapshot(&self) -> TransferQueueState {
+        let inner = self.inner.lock().unwrap();
+        TransferQueueState {
+            paused: inner.paused,
+            max_concurrent: inner.max_concurrent,
+            running: inner.running.clone(),
+            pending: inner.pending.iter().cloned().collect(),
+        }
+    }
+
+    fn notify(&self) {
+        self.changed.send_modify(|version| *version += 1);
+    }
+
+    /// 把队列状态变化转发为 `sftp-queue-state` 事件
+    pub fn forward_events(self: Arc<Self>, app: AppHandle) {
+        let mut changed = self.changed.subscribe();
+        tauri::async_runtime::spawn(async move {
+            while changed.changed().await.is_ok() {
+                let _ = app.emit(QUEUE_STATE_EVENT, self.snapshot());
+            }
+        });
+    }
+
+    /// 任务排队，轮到该任务时返回；排队期间被取消时返回错误
+    pub async fn enqueue(
+        self: &Arc<Self>,
+        mut item: TransferProgress,
+        cancellation_token: &tokio_util::sync::CancellationToken,
+    ) -> Result<TransferTicket> {
+        let id = item.id.clone();
+        item.status = TransferStatus::Pending;
+        // 先订阅再检查，避免错过检查与等待之间的状态变化
+        let mut changed = self.changed.subscribe();
+        self.inner.lock().unwrap().pending.push_back(item);
+        self.notify();
+
+        loop {
+            if self.try_start(&id) {
+                info!("Transfer {} started from queue", id);
+                self.notify();
+                return Ok(TransferTicket { queue: self.clone(), id });
+            }
+
+            tokio::select! {
+                _ = changed.changed() => {}
+                _ = cancellation_token.cancelled() => {
+                    self.inner.lock().unwrap().pending.retain(|item| item.id != id);
+                    self.notify();
+                    info!("Transfer {} cancelled while queued", id);
+                    return Err(SSHError::Io("传输已取消".to_string()));
+                }
+            }
+        }
+    }
+
+    /// 队首任务在未暂停且有空位时开始
+    fn try_start(&self, id: &str) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+        let can_start = !inner.paused
+            && inner.running.len() < inner.max_concurrent
+            && inner.pending.front().is_some_and(|item| item.id == id);
+        if !can_start {
+            return false;
+        }
+        let mut item = inner.pending.pop_front().expect("queue front checked above");
+        item.status = TransferStatus::InProgress;
+        inner.running.push(item);
+        true
+    }
+
+    fn finish(&self, id: &str) {
+        self.inner.lock().unwrap().running.retain(|item| item.id != id);
+        self.notify();
+    }
+
+    /// 暂停队列：排队中的任务不再开始
+    pub fn pause(&self) {
+        self.inner.lock().unwrap().paused = true;
+        self.notify();
+    }
+
+    /// 恢复队列
+    pub fn resume(&self) {
+        self.inner.lock().unwrap().paused = false;
+        self.notify();
+    }
+
+    /// 调整排队顺序：`ids` 中的任务按给定顺序移到队首，其余任务保持原有顺序
+    pub fn reorder(&self, ids: &[String]) {
+        let mut inner = self.inner.lock().unwrap();
+        let mut front = Vec::new();
+        for id in ids {
+            if let Some(index) = inner.pending.iter().position(|item| &item.id == id) {
+                front.extend(inner.pending.remove(index));
+            }
+        }
+        for item in front.into_iter().rev() {
+            inner.pending.push_front(item);
+        }
+        drop(inner);
+        self.notify();
+    }
+
+    /// 设置同时执行的任务数量，返回实际生效的值
+    pub fn set_max_concurrent(&self, max_concurrent: usize) -> usize {
+        let max_concurrent = max_concurrent.clamp(1, MAX_CONCURRENT_TRANSFERS_LIMIT);
+        self.inner.lock().unwrap().max_concurrent = max_concurrent;
+        self.notify();
+        max_concurrent
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sftp::{TransferOperation, TransferSource};
+    use tokio_util::sync::CancellationToken;
+
+    fn item(id: &str) -> TransferProgress {
+        TransferProgress {
+            id: id.to_string(),
+            operation: TransferOperation::Upload,
+            source: TransferSource::Local { path: format!("/tmp/{}", id) },
+            destination: TransferSource::Remote { connection_id: "c".to_string(), path: format!("/srv/{}", id) },
+            file_size: 0,
+            transferred: 0,
+            speed: 0,
+            status: TransferStatus::Pending,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_transfer_queue_order() {
+        let queue = Arc::new(TransferQueue::new(1));
+        let token = CancellationToken::new();
+        let first = queue.enqueue(item("a"), &token).await.unwrap();
+
+        queue.pause();
+        let waiting: Vec<_> = ["b", "c"]
+            .into_iter()
+            .map(|id| {
+                let queue = queue.clone();
+                let token = token.clone();
+                tokio::spawn(async move { queue.enqueue(item(id), &token).await.map(|ticket| ticket.id.clone()) })
+            })
+            .collect();
+        while queue.snapshot().pending.len() < 2 {
+            tokio::task::yield_now().await;
+        }
+
+        queue.reorder(&["c".to_string()]);
+        let pending: Vec<_> = queue.snapshot().pending.into_iter().map(|item| item.id).collect();
+        assert_eq!(pending, ["c", "b"]);
+
+        // 暂停时释放位置也不会开始新任务
+        drop(first);
+        tokio::task::yield_now().await;
+        assert!(queue.snapshot().running.is_empty());
+
+        queue.set_max_concurrent(2);
+        queue.resume();
+        let mut started = Vec::new();
+        for handle in waiting {
+            started.push(handle.await.unwrap().unwrap());
+        }
+        started.sort();
+        assert_eq!(started, ["b", "c"]);
+        assert!(queue.snapshot().pending.is_empty());
+
+        // 排队中取消
+        queue.pause();
+        let cancelled = CancellationToken::new();
+        cancelled.cancel();
+        assert!(queue.enqueue(item("d"), &cancelled).await.is_err());
+        assert!(queue.snapshot().pending.is_empty());
+    }
+}
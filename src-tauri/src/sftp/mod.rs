@@ -4,6 +4,13 @@
 
 pub mod client;
 pub mod manager;
+pub mod audit;
+pub mod checksum;
+pub mod accounts;
+pub mod route;
+pub mod watchdog;
+pub mod search;
+pub mod edit;
 
 pub use manager::SftpManager;
 
@@ -14,12 +21,15 @@ pub struct SftpFileInfo {
     pub name: String,
     pub path: String,
     pub size: u64,
+    /// 目录，或指向目录的符号链接
     pub is_dir: bool,
     pub is_symlink: bool,
     pub modified: u64,     // Unix timestamp
     pub mode: u32,         // Unix permissions
     pub owner: Option<String>,
     pub group: Option<String>,
+    /// 符号链接指向的路径（仅列目录时读取）
+    pub link_target: Option<String>,
 }
 
 /// 从 russh_sftp::protocol::FileAttributes 转换
@@ -30,18 +40,18 @@ impl From<russh_sftp::protocol::FileAttributes> for SftpFileInfo {
             path: String::new(),
             size: attrs.size.unwrap_or(0),
             is_dir: attrs.is_dir(),
-            is_symlink: false, // TODO: 从 FileAttributes 获取
+            is_symlink: attrs.file_type().is_symlink(),
             modified: attrs.mtime.unwrap_or(0) as u64,
             mode: attrs.permissions.unwrap_or(0),
             owner: attrs.user,
             group: attrs.group,
+            link_target: None,
         }
     }
 }
 
 // ============================================================================
-// 未来特性：文件传输进度追踪
-// 以下类型和方法预留用于将来的文件传输进度追踪功能
+// 文件传输队列与进度追踪
 // ============================================================================
 
 /// 文件传输操作类型
@@ -54,11 +64,98 @@ pub enum TransferOperation {
     RemoteToRemote,
 }
 
+/// 目标已存在时的处理策略
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ConflictPolicy {
+    /// 覆盖（目录则合并）
+    #[default]
+    Overwrite,
+    /// 跳过
+    Skip,
+    /// 自动重命名为 "name (1).ext"
+    Rename,
+    /// 报错
+    Fail,
+    /// 源文件比目标新时覆盖，否则跳过（目录则合并）
+    Newer,
+}
+
+/// 目标文件已存在时对单个文件采取的处理
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictAction {
+    /// 写入（覆盖目标）
+    Write,
+    Skip,
+    /// 写入到 "name (n).ext"
+    Rename,
+    Fail,
+}
+
+impl ConflictPolicy {
+    /// 按策略决定单个已存在文件的处理方式（修改时间为 Unix 时间戳，秒）
+    pub fn resolve(self, source_modified: u64, target_modified: u64) -> ConflictAction {
+        match self {
+            ConflictPolicy::Overwrite => ConflictAction::Write,
+            ConflictPolicy::Skip => ConflictAction::Skip,
+            ConflictPolicy::Rename => ConflictAction::Rename,
+            ConflictPolicy::Fail => ConflictAction::Fail,
+            ConflictPolicy::Newer if source_modified > target_modified => ConflictAction::Write,
+            ConflictPolicy::Newer => ConflictAction::Skip,
+        }
+    }
+}
+
+/// 重命名策略下的第 n 个候选名称："a.txt" -> "a (n).txt"，目录和无扩展名文件直接追加
+pub fn numbered_name(name: &str, n: u32, is_dir: bool) -> String {
+    match name.rfind('.') {
+        Some(dot) if !is_dir && dot > 0 => format!("{} ({}){}", &name[..dot], n, &name[dot..]),
+        _ => format!("{} ({})", name, n),
+    }
+}
+
+/// 本地文件的修改时间（Unix 时间戳，秒），与 [`SftpFileInfo::modified`] 对应
+pub fn local_modified(metadata: &std::fs::Metadata) -> u64 {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+/// 递归传输时符号链接的处理方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SymlinkPolicy {
+    /// 在目标端创建指向相同路径的符号链接
+    #[default]
+    Preserve,
+    /// 跟随链接，传输链接指向的文件或目录（已进入过的目录不再重复进入，避免循环）
+    Follow,
+}
+
+/// 编排传输结果
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferResult {
+    pub operation: TransferOperation,
+    pub source_path: String,
+    /// 实际写入的目标路径（重命名策略下可能与源名称不同）
+    pub target_path: String,
+    pub is_dir: bool,
+    /// 因冲突策略为 Skip 而未传输
+    pub skipped: bool,
+    pub bytes_transferred: u64,
+    pub total_files: u64,
+    /// 完整性校验结果（仅单文件且开启校验时）
+    pub checksum: Option<checksum::ChecksumVerification>,
+}
+
 /// 传输源
-#[allow(dead_code)]
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(tag = "type")]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", rename_all_fields = "camelCase")]
 pub enum TransferSource {
     Local { path: String },
     Remote { connection_id: String, path: String },
@@ -76,8 +173,7 @@ pub enum TransferStatus {
     Cancelled,
 }
 
-/// 文件传输进度
-#[allow(dead_code)]
+/// 队列中的传输任务
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TransferProgress {
@@ -99,6 +195,8 @@ pub struct UploadDirectoryResult {
     pub total_dirs: u64,
     pub total_size: u64,
     pub elapsed_time_ms: u64,
+    /// 目标已存在、按冲突策略跳过的源文件路径
+    pub skipped_files: Vec<String>,
 }
 
 /// 上传进度事件
@@ -127,6 +225,8 @@ pub struct DownloadDirectoryResult {
     pub total_dirs: u64,
     pub total_size: u64,
     pub elapsed_time_ms: u64,
+    /// 目标已存在、按冲突策略跳过的源文件路径
+    pub skipped_files: Vec<String>,
 }
 
 /// 下载进度事件
@@ -175,3 +275,28 @@ pub struct DownloadStatusChangeEvent {
     pub error_message: Option<String>,
     pub completed_at: Option<i64>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conflict_resolve() {
+        assert_eq!(ConflictPolicy::Overwrite.resolve(1, 2), ConflictAction::Write);
+        assert_eq!(ConflictPolicy::Skip.resolve(2, 1), ConflictAction::Skip);
+        assert_eq!(ConflictPolicy::Rename.resolve(1, 1), ConflictAction::Rename);
+        assert_eq!(ConflictPolicy::Fail.resolve(1, 1), ConflictAction::Fail);
+        assert_eq!(ConflictPolicy::Newer.resolve(2, 1), ConflictAction::Write);
+        // 修改时间相同视为未更新
+        assert_eq!(ConflictPolicy::Newer.resolve(1, 1), ConflictAction::Skip);
+        assert_eq!(ConflictPolicy::Newer.resolve(1, 2), ConflictAction::Skip);
+    }
+
+    #[test]
+    fn test_numbered_name() {
+        assert_eq!(numbered_name("a.txt", 1, false), "a (1).txt");
+        assert_eq!(numbered_name("a.tar.gz", 2, false), "a.tar (2).gz");
+        assert_eq!(numbered_name(".bashrc", 1, false), ".bashrc (1)");
+        assert_eq!(numbered_name("dir.d", 3, true), "dir.d (3)");
+    }
+}
@@ -0,0 +1,77 @@
+//! SFTP 连接路径
+//!
+//! 经由跳板机（ProxyJump）连接的会话，SFTP channel 在最终主机的连接上打开，
+//! 跳板机只转发 SSH 流量。文件面板据此显示文件实际所在的主机和途经的跳板机。
+
+use crate::ssh::session::SessionConfig;
+use serde::Serialize;
+
+/// 文件所在主机及途经的跳板机
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SftpRoute {
+    /// 文件所在的主机（`user@host:port`）
+    pub target: String,
+    /// 途经的跳板机，按从本机出发的顺序
+    pub hops: Vec<String>,
+}
+
+impl SftpRoute {
+    pub fn from_config(config: &SessionConfig) -> Self {
+        let mut hops = Vec::new();
+        let mut jump = config.jump_host.as_deref();
+        while let Some(hop) = jump {
+            hops.push(endpoint(hop));
+            jump = hop.jump_host.as_deref();
+        }
+        // 配置中最内层的跳板机离本机最近
+        hops.reverse();
+
+        Self {
+            target: endpoint(config),
+            hops,
+        }
+    }
+}
+
+fn endpoint(config: &SessionConfig) -> String {
+    format!("{}@{}:{}", config.username, config.host, config.port)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_route_from_jump_chain() {
+        let config: SessionConfig = serde_json::from_value(serde_json::json!({
+            "name": "files",
+            "host": "10.0.0.5",
+            "port": 22,
+            "username": "deploy",
+            "authMethod": { "Password": { "password": "secret" } },
+            "jumpHost": {
+                "name": "inner",
+                "host": "inner.bastion",
+                "port": 2222,
+                "username": "ops",
+                "authMethod": { "Password": { "password": "secret" } },
+                "jumpHost": {
+                    "name": "outer",
+                    "host": "bastion.example.com",
+                    "port": 22,
+                    "username": "ops",
+                    "authMethod": { "Password": { "password": "secret" } },
+                },
+            },
+        }))
+        .unwrap();
+
+        let route = SftpRoute::from_config(&config);
+        assert_eq!(route.target, "deploy@10.0.0.5:22");
+        assert_eq!(route.hops, vec!["ops@bastion.example.com:22", "ops@inner.bastion:2222"]);
+
+        let direct = SftpRoute::from_config(config.jump_host.as_deref().unwrap().jump_host.as_deref().unwrap());
+        assert!(direct.hops.is_empty());
+    }
+}
@@ -0,0 +1,272 @@
+//! 远程文件搜索
+//!
+//! 优先通过 exec channel 执行 GNU `find`（`-printf` 输出类型、大小、修改时间和路径），
+//! 远程没有可用的 `find` 或 exec 被禁用时，由调用方回退为通过 SFTP 逐层列目录匹配。
+//! 两种方式使用相同的名称通配符（`*`、`?`）和过滤条件，都不跟随符号链接。
+
+use crate::utils::shell::shell_quote;
+use serde::{Deserialize, Serialize};
+
+/// 默认最多返回的匹配数
+pub const DEFAULT_SEARCH_LIMIT: usize = 1000;
+
+/// 默认最大搜索深度
+pub const DEFAULT_MAX_DEPTH: u32 = 16;
+
+/// 搜索结果事件名
+pub const SEARCH_RESULT_EVENT: &str = "sftp-search-result";
+
+/// 匹配的文件类型
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SearchFileType {
+    #[default]
+    Any,
+    File,
+    Dir,
+}
+
+/// 搜索过滤条件
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SearchFilters {
+    pub file_type: SearchFileType,
+    /// 最小大小（字节，含）
+    pub min_size: Option<u64>,
+    /// 最大大小（字节，含）
+    pub max_size: Option<u64>,
+    /// 修改时间不早于（Unix 时间戳，秒）
+    pub modified_after: Option<i64>,
+    /// 修改时间不晚于（Unix 时间戳，秒）
+    pub modified_before: Option<i64>,
+    /// 最大搜索深度（根目录的直接子项为 1）
+    pub max_depth: Option<u32>,
+    /// 名称匹配是否区分大小写
+    pub case_sensitive: bool,
+    /// 最多返回的匹配数
+    pub limit: Option<usize>,
+}
+
+impl SearchFilters {
+    pub fn max_depth(&self) -> u32 {
+        self.max_depth.unwrap_or(DEFAULT_MAX_DEPTH).max(1)
+    }
+
+    pub fn limit(&self) -> usize {
+        self.limit.unwrap_or(DEFAULT_SEARCH_LIMIT).max(1)
+    }
+
+    /// 条目是否满足名称和过滤条件
+    pub fn matches(&self, pattern: &str, name: &str, is_dir: bool, size: u64, modified: i64) -> bool {
+        let type_ok = match self.file_type {
+            SearchFileType::Any => true,
+            SearchFileType::File => !is_dir,
+            SearchFileType::Dir => is_dir,
+        };
+        type_ok
+            && self.min_size.is_none_or(|min| size >= min)
+            && self.max_size.is_none_or(|max| size <= max)
+            && self.modified_after.is_none_or(|after| modified >= after)
+            && self.modified_before.is_none_or(|before| modified <= before)
+            && glob_match(pattern, name, self.case_sensitive)
+    }
+}
+
+/// 单个匹配项
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchMatch {
+    pub path: String,
+    pub name: String,
+    pub is_dir: bool,
+    pub size: u64,
+    /// 修改时间（Unix 时间戳，秒）
+    pub modified: i64,
+}
+
+/// 搜索结果事件（`sftp-search-result` 事件内容），匹配项分批发送
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchResultEvent {
+    pub search_id: String,
+    pub connection_id: String,
+    pub matches: Vec<SearchMatch>,
+}
+
+/// 搜索方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SearchMethod {
+    Find,
+    Sftp,
+}
+
+/// 搜索汇总（`sftp_search` 返回）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchSummary {
+    pub search_id: String,
+    pub total_matches: usize,
+    /// 达到数量上限，结果被截断
+    pub truncated: bool,
+    pub cancelled: bool,
+    pub method: SearchMethod,
+}
+
+/// 检测远程 `find` 是否支持 `-printf` 的命令，支持时输出 `ok`
+pub const FIND_PROBE_COMMAND: &str = "find / -maxdepth 0 -printf ok 2>/dev/null";
+
+/// 构建搜索命令（多取一行用于判断是否截断）
+pub fn build_find_command(root: &str, pattern: &str, filters: &SearchFilters) -> String {
+    let mut command = format!("find {} -mindepth 1 -maxdepth {}", shell_quote(root), filters.max_depth());
+
+    match filters.file_type {
+        SearchFileType::Any => {}
+        SearchFileType::File => command.push_str(" ! -type d"),
+        SearchFileType::Dir => command.push_str(" -type d"),
+    }
+    if !pattern.is_empty() && pattern != "*" {
+        let test = if filters.case_sensitive { "-name" } else { "-iname" };
+        command.push_str(&format!(" {} {}", test, shell_quote(pattern)));
+    }
+    // `-size +Nc` 表示大于 N 字节，`-size -Nc` 表示小于 N 字节
+    if let Some(min) = filters.min_size.filter(|min| *min > 0) {
+        command.push_str(&format!(" -size +{}c", min - 1));
+    }
+    if let Some(max) = filters.max_size {
+        command.push_str(&format!(" -size -{}c", max.saturating_add(1)));
+    }
+    if let Some(after) = filters.modified_after {
+        command.push_str(&format!(" -newermt @{}", after - 1));
+    }
+    if let Some(before) = filters.modified_before {
+        command.push_str(&format!(" ! -newermt @{}", before));
+    }
+
+    command.push_str(&format!(
+        " -printf '%y\\t%s\\t%T@\\t%p\\n' 2>/dev/null | head -n {}",
+        filters.limit() + 1
+    ));
+    command
+}
+
+/// 解析 find 输出的一行：`类型\t大小\t修改时间\t路径`
+pub fn parse_find_line(line: &str) -> Option<SearchMatch> {
+    let mut fields = line.splitn(4, '\t');
+    let kind = fields.next()?;
+    let size = fields.next()?.parse().ok()?;
+    // `%T@` 带小数部分
+    let modified = fields.next()?.split('.').next()?.parse().ok()?;
+    let path = fields.next()?.to_string();
+    let name = path.rsplit('/').next().unwrap_or(&path).to_string();
+
+    Some(SearchMatch {
+        is_dir: kind == "d",
+        path,
+        name,
+        size,
+        modified,
+    })
+}
+
+/// 名称通配符匹配，支持 `*`（任意个字符）和 `?`（单个字符），空模式匹配所有名称
+pub fn glob_match(pattern: &str, name: &str, case_sensitive: bool) -> bool {
+    if pattern.is_empty() {
+        return true;
+    }
+    let fold = |c: char| if case_sensitive { c } else { c.to_lowercase().next().unwrap_or(c) };
+    let pattern: Vec<char> = pattern.chars().map(fold).collect();
+    let name: Vec<char> = name.chars().map(fold).collect();
+
+    let (mut p, mut n) = (0, 0);
+    // 最近一个 `*` 的位置及其当时对应的名称位置，用于回溯
+    let mut star: Option<(usize, usize)> = None;
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, n));
+            p += 1;
+        } else if let Some((star_p, star_n)) = star {
+            p = star_p + 1;
+            n = star_n + 1;
+            star = Some((star_p, star_n + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("*.log", "app.log", true));
+        assert!(!glob_match("*.log", "app.log.1", true));
+        assert!(glob_match("app-??.txt", "app-01.txt", true));
+        assert!(!glob_match("app-??.txt", "app-1.txt", true));
+        assert!(glob_match("*err*", "stderr.txt", true));
+        assert!(glob_match("README*", "readme.md", false));
+        assert!(!glob_match("README*", "readme.md", true));
+        assert!(glob_match("", "anything", true));
+    }
+
+    #[test]
+    fn test_filters_matches() {
+        let filters = SearchFilters {
+            file_type: SearchFileType::File,
+            min_size: Some(10),
+            max_size: Some(100),
+            modified_after: Some(1_000),
+            ..Default::default()
+        };
+        assert!(filters.matches("*.log", "a.log", false, 10, 1_000));
+        assert!(!filters.matches("*.log", "a.log", true, 10, 1_000));
+        assert!(!filters.matches("*.log", "a.log", false, 101, 1_000));
+        assert!(!filters.matches("*.log", "a.log", false, 50, 999));
+        assert!(!filters.matches("*.log", "a.txt", false, 50, 1_000));
+    }
+
+    #[test]
+    fn test_build_find_command() {
+        let filters = SearchFilters {
+            file_type: SearchFileType::File,
+            min_size: Some(1024),
+            max_size: Some(2048),
+            modified_after: Some(1_700_000_000),
+            max_depth: Some(3),
+            limit: Some(10),
+            ..Default::default()
+        };
+        assert_eq!(
+            build_find_command("/var/it's", "*.log", &filters),
+            "find '/var/it'\\''s' -mindepth 1 -maxdepth 3 ! -type d -iname '*.log' -size +1023c -size -2049c \
+             -newermt @1699999999 -printf '%y\\t%s\\t%T@\\t%p\\n' 2>/dev/null | head -n 11"
+        );
+        assert_eq!(
+            build_find_command("/", "*", &SearchFilters::default()),
+            format!("find '/' -mindepth 1 -maxdepth {} -printf '%y\\t%s\\t%T@\\t%p\\n' 2>/dev/null | head -n {}",
+                DEFAULT_MAX_DEPTH, DEFAULT_SEARCH_LIMIT + 1)
+        );
+    }
+
+    #[test]
+    fn test_parse_find_line() {
+        assert_eq!(
+            parse_find_line("f\t42\t1700000000.1234567890\t/var/log/app log.txt"),
+            Some(SearchMatch {
+                path: "/var/log/app log.txt".to_string(),
+                name: "app log.txt".to_string(),
+                is_dir: false,
+                size: 42,
+                modified: 1_700_000_000,
+            })
+        );
+        assert!(parse_find_line("d\t4096\t1700000000.0\t/var/log").unwrap().is_dir);
+        assert_eq!(parse_find_line("garbage"), None);
+    }
+}
@@ -0,0 +1,224 @@
+//! 传输看门狗
+//!
+//! 定期检查每个任务最近一次读写数据的时间：
+//! 超过 `stall_timeout_secs` 没有进度的任务发送 `sftp-task-stalled` 事件；
+//! 开启自动取消时，超过 `auto_cancel_secs` 的任务会被取消，
+//! 并清理其任务客户端和取消令牌，避免卡住的 channel 留下无法结束的任务。
+//!
+//! 同一任务的多个客户端（`<task_id>#<n>`、`<task_id>#target`）按所属任务合并检查，
+//! 只要其中任意一个客户端仍在读写就视为有进度：目录下载时空闲的 worker 不会让仍在传输的任务被取消。
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// 任务卡住事件名
+pub const TASK_STALLED_EVENT: &str = "sftp-task-stalled";
+
+/// 检查间隔
+pub const CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// 默认判定为卡住的无进度时间
+const DEFAULT_STALL_TIMEOUT_SECS: u64 = 30;
+
+/// 默认自动取消的无进度时间
+const DEFAULT_AUTO_CANCEL_SECS: u64 = 120;
+
+/// 判定为卡住的最短无进度时间
+const MIN_STALL_TIMEOUT_SECS: u64 = 5;
+
+/// 看门狗配置
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchdogConfig {
+    /// 无进度多少秒后判定为卡住
+    pub stall_timeout_secs: u64,
+    /// 无进度多少秒后自动取消，`None` 表示不自动取消
+    pub auto_cancel_secs: Option<u64>,
+}
+
+impl Default for WatchdogConfig {
+    fn default() -> Self {
+        Self {
+            stall_timeout_secs: DEFAULT_STALL_TIMEOUT_SECS,
+            auto_cancel_secs: Some(DEFAULT_AUTO_CANCEL_SECS),
+        }
+    }
+}
+
+/// 任务卡住事件（`sftp-task-stalled` 事件内容）
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskStalledEvent {
+    pub task_id: String,
+    pub connection_id: String,
+    /// 已经多少秒没有进度
+    pub idle_secs: u64,
+    /// 是否已被自动取消
+    pub cancelled: bool,
+}
+
+/// 任务客户端所属的任务 ID（去掉 `#<n>` 后缀）
+pub fn owner_task_id(task_id: &str) -> &str {
+    task_id.split('#').next().unwrap_or(task_id)
+}
+
+struct WatchedTask {
+    connection_id: String,
+    /// 该任务各个客户端的最近读写时间：(客户端任务 ID, 时间)
+    clients: Vec<(String, Arc<AtomicU64>)>,
+    stalled: bool,
+}
+
+impl WatchedTask {
+    /// 所有客户端中最近一次读写的时间
+    fn last_activity(&self) -> u64 {
+        self.clients
+            .iter()
+            .map(|(_, activity)| activity.load(Ordering::Relaxed))
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// 传输看门狗
+///
+/// 任务客户端创建时登记，清理时注销；由 `SftpManager` 定期调用 [`TaskWatchdog::check`]。
+/// 事件中的 `task_id` 始终是所属任务的 ID
+#[derive(Default)]
+pub struct TaskWatchdog {
+    config: Mutex<WatchdogConfig>,
+    tasks: Mutex<HashMap<String, WatchedTask>>,
+}
+
+impl TaskWatchdog {
+    /// 当前配置
+    pub fn config(&self) -> WatchdogConfig {
+        *self.config.lock().unwrap()
+    }
+
+    /// 更新配置，返回实际生效的值（自动取消时间不小于卡住判定时间）
+    pub fn configure(&self, config: WatchdogConfig) -> WatchdogConfig {
+        let stall_timeout_secs = config.stall_timeout_secs.max(MIN_STALL_TIMEOUT_SECS);
+        let config = WatchdogConfig {
+            stall_timeout_secs,
+            auto_cancel_secs: config.auto_cancel_secs.map(|secs| secs.max(stall_timeout_secs)),
+        };
+        *self.config.lock().unwrap() = config;
+        config
+    }
+
+    /// 登记任务客户端，从当前时间开始计算无进度时间
+    ///
+    /// `<task_id>#<n>` 形式的客户端合并到所属任务
+    pub fn watch(&self, task_id: &str, connection_id: &str, activity: Arc<AtomicU64>, now_ms: u64) {
+        activity.store(now_ms, Ordering::Relaxed);
+        let mut tasks = self.tasks.lock().unwrap();
+        let task = tasks
+            .entry(owner_task_id(task_id).to_string())
+            .or_insert_with(|| WatchedTask {
+                connection_id: connection_id.to_string(),
+                clients: Vec::new(),
+                stalled: false,
+            });
+        task.clients.retain(|(id, _)| id != task_id);
+        task.clients.push((task_id.to_string(), activity));
+    }
+
+    /// 注销任务客户端，所属任务的客户端全部注销后不再检查该任务
+    pub fn unwatch(&self, task_id: &str) {
+        let owner_id = owner_task_id(task_id);
+        let mut tasks = self.tasks.lock().unwrap();
+        if let Some(task) = tasks.get_mut(owner_id) {
+            task.clients.retain(|(id, _)| id != task_id);
+            if task.clients.is_empty() {
+                tasks.remove(owner_id);
+            }
+        }
+    }
+
+    /// 检查所有任务，返回需要发送的事件
+    ///
+    /// 每次卡住只报告一次，恢复进度后重新计算；自动取消的任务会被注销
+    pub fn check(&self, now_ms: u64) -> Vec<TaskStalledEvent> {
+        let config = self.config();
+        let mut events = Vec::new();
+
+        self.tasks.lock().unwrap().retain(|task_id, task| {
+            let idle_secs = now_ms.saturating_sub(task.last_activity()) / 1000;
+            if idle_secs < config.stall_timeout_secs {
+                task.stalled = false;
+                return true;
+            }
+
+            let cancelled = config.auto_cancel_secs.is_some_and(|secs| idle_secs >= secs);
+            if cancelled || !task.stalled {
+                task.stalled = true;
+                events.push(TaskStalledEvent {
+                    task_id: task_id.clone(),
+                    connection_id: task.connection_id.clone(),
+                    idle_secs,
+                    cancelled,
+                });
+            }
+            !cancelled
+        });
+
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_watchdog_check() {
+        let watchdog = TaskWatchdog::default();
+        watchdog.configure(WatchdogConfig { stall_timeout_secs: 10, auto_cancel_secs: Some(30) });
+        let activity = Arc::new(AtomicU64::new(0));
+        watchdog.watch("t1", "c1", activity.clone(), 1_000);
+
+        assert!(watchdog.check(5_000).is_empty());
+
+        // 卡住只报告一次
+        let events = watchdog.check(12_000);
+        assert_eq!(events.len(), 1);
+        assert_eq!((events[0].idle_secs, events[0].cancelled), (11, false));
+        assert!(watchdog.check(15_000).is_empty());
+
+        // 恢复进度后重新计算
+        activity.store(20_000, Ordering::Relaxed);
+        assert!(watchdog.check(21_000).is_empty());
+
+        let events = watchdog.check(55_000);
+        assert_eq!((events[0].idle_secs, events[0].cancelled), (35, true));
+        assert!(watchdog.check(60_000).is_empty());
+    }
+
+    #[test]
+    fn test_watchdog_merges_worker_clients() {
+        let watchdog = TaskWatchdog::default();
+        watchdog.configure(WatchdogConfig { stall_timeout_secs: 10, auto_cancel_secs: Some(30) });
+        let owner = Arc::new(AtomicU64::new(0));
+        let worker = Arc::new(AtomicU64::new(0));
+        watchdog.watch("t1", "c1", owner.clone(), 1_000);
+        watchdog.watch("t1#1", "c1", worker.clone(), 1_000);
+
+        // 主客户端空闲，worker 仍在传输
+        worker.store(40_000, Ordering::Relaxed);
+        assert!(watchdog.check(45_000).is_empty());
+
+        // worker 注销后只剩主客户端
+        watchdog.unwatch("t1#1");
+        let events = watchdog.check(45_000);
+        assert_eq!(events.len(), 1);
+        assert_eq!((events[0].task_id.as_str(), events[0].cancelled), ("t1", true));
+
+        // 全部注销后不再检查
+        watchdog.watch("t2#target", "c1", Arc::new(AtomicU64::new(0)), 1_000);
+        watchdog.unwatch("t2#target");
+        assert!(watchdog.check(100_000).is_empty());
+    }
+}
@@ -0,0 +1,270 @@
+//! 服务器版本安全公告检查
+//!
+//! 连接时记录服务器的 SSH 标识（如 `SSH-2.0-OpenSSH_9.6p1 Ubuntu-3ubuntu13.5`），
+//! 与内置的 OpenSSH/Dropbear 高危漏洞列表比对，命中时向前端发送警告事件。
+//! 列表可以从同步服务器更新，更新后保存在应用存储目录的 `ssh_advisories.json` 中。
+//!
+//! 只比较上游版本号：发行版常把修复反向移植到旧版本号上，
+//! 标识中带有发行版后缀时在结果中标注，由用户自行确认。
+
+use crate::config::storage::Storage;
+use crate::error::{Result, SSHError};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::path::PathBuf;
+
+/// 安全公告事件前缀，完整事件名为 `ssh-advisory-{connectionId}`
+pub const ADVISORY_EVENT_PREFIX: &str = "ssh-advisory-";
+
+/// 内置列表的日期，服务器返回的列表更新时覆盖内置列表
+const BUNDLED_UPDATED: &str = "2024-07-01";
+
+/// 服务器软件
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ServerProduct {
+    Openssh,
+    Dropbear,
+}
+
+/// 漏洞严重程度
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Medium,
+    High,
+    Critical,
+}
+
+/// 一条安全公告
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Advisory {
+    /// CVE 编号
+    pub id: String,
+    pub product: ServerProduct,
+    /// 受影响的最低版本（含），为空表示更早的版本均受影响
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub introduced: Option<String>,
+    /// 修复版本（不含）
+    pub fixed: String,
+    pub severity: Severity,
+    pub summary: String,
+}
+
+/// 安全公告列表
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdvisoryList {
+    /// 列表日期（YYYY-MM-DD）
+    pub updated: String,
+    pub advisories: Vec<Advisory>,
+}
+
+/// 解析后的服务器标识
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerVersion {
+    /// 完整标识
+    pub raw: String,
+    pub product: Option<ServerProduct>,
+    /// 上游版本号，如 `9.6p1`、`2022.83`
+    pub version: Option<String>,
+    /// 标识中的发行版后缀，如 `Ubuntu-3ubuntu13.5`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+/// 检查结果（通过 `ssh-advisory-{connectionId}` 事件发送）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdvisoryReport {
+    pub server: ServerVersion,
+    pub advisories: Vec<Advisory>,
+    /// 标识带有发行版后缀，修复可能已被反向移植
+    pub distro_patched_possible: bool,
+    /// 使用的公告列表日期
+    pub list_updated: String,
+}
+
+impl ServerVersion {
+    /// 解析 `SSH-protoversion-softwareversion SP comments`
+    pub fn parse(ident: &str) -> Self {
+        let ident = ident.trim();
+        let software = ident
+            .strip_prefix("SSH-")
+            .and_then(|rest| rest.split_once('-'))
+            .map(|(_, software)| software)
+            .unwrap_or_default();
+        let (software, comment) = match software.split_once(' ') {
+            Some((software, comment)) => (software, Some(comment.trim().to_string()).filter(|c| !c.is_empty())),
+            None => (software, None),
+        };
+
+        let (product, version) = match software.split_once('_') {
+            Some((name, version)) if name.eq_ignore_ascii_case("OpenSSH") => (Some(ServerProduct::Openssh), Some(version)),
+            Some((name, version)) if name.eq_ignore_ascii_case("dropbear") => (Some(ServerProduct::Dropbear), Some(version)),
+            _ => (None, None),
+        };
+
+        Self {
+            raw: ident.to_string(),
+            product,
+            version: version.filter(|v| v.starts_with(|c: char| c.is_ascii_digit())).map(str::to_string),
+            comment,
+        }
+    }
+}
+
+/// 版本号中的数字部分（`9.3p2` → [9, 3, 2]）
+fn version_key(version: &str) -> Vec<u32> {
+    version
+        .split(|c: char| !c.is_ascii_digit())
+        .filter(|part| !part.is_empty())
+        .filter_map(|part| part.parse().ok())
+        .collect()
+}
+
+/// 比较版本号，较短的一方缺少的部分视为 0
+fn compare_versions(a: &str, b: &str) -> Ordering {
+    let (a, b) = (version_key(a), version_key(b));
+    let len = a.len().max(b.len());
+    let pad = |v: &[u32]| v.iter().copied().chain(std::iter::repeat(0)).take(len).collect::<Vec<_>>();
+    pad(&a).cmp(&pad(&b))
+}
+
+impl Advisory {
+    /// 版本是否受影响
+    pub fn affects(&self, product: ServerProduct, version: &str) -> bool {
+        self.product == product
+            && compare_versions(version, &self.fixed) == Ordering::Less
+            && self
+                .introduced
+                .as_deref()
+                .is_none_or(|introduced| compare_versions(version, introduced) != Ordering::Less)
+    }
+}
+
+impl AdvisoryList {
+    /// 内置列表（只收录服务器端的高危漏洞）
+    pub fn bundled() -> Self {
+        use ServerProduct::{Dropbear, Openssh};
+        use Severity::{Critical, High};
+
+        let advisory = |id: &str, product, introduced: Option<&str>, fixed: &str, severity, summary: &str| Advisory {
+            id: id.to_string(),
+            product,
+            introduced: introduced.map(str::to_string),
+            fixed: fixed.to_string(),
+            severity,
+            summary: summary.to_string(),
+        };
+
+        Self {
+            updated: BUNDLED_UPDATED.to_string(),
+            advisories: vec![
+                advisory("CVE-2024-6387", Openssh, Some("8.5"), "9.8", Critical,
+                    "regreSSHion: signal handler race in sshd allows unauthenticated remote code execution as root"),
+                advisory("CVE-2024-6387", Openssh, None, "4.4", Critical,
+                    "regreSSHion: signal handler race in sshd allows unauthenticated remote code execution as root"),
+                advisory("CVE-2021-41617", Openssh, Some("6.2"), "8.8", High,
+                    "sshd runs AuthorizedKeysCommand/AuthorizedPrincipalsCommand with incorrect supplementary groups"),
+                advisory("CVE-2016-10012", Openssh, None, "7.4", High,
+                    "Privilege separation shared memory bounds checks can be bypassed for local privilege escalation"),
+                advisory("CVE-2015-5600", Openssh, None, "7.0", High,
+                    "Keyboard-interactive authentication allows unlimited password attempts within one connection"),
+                advisory("CVE-2016-7406", Dropbear, None, "2016.74", Critical,
+                    "Format string flaw in dropbear allows remote code execution"),
+                advisory("CVE-2017-9078", Dropbear, None, "2017.75", High,
+                    "Double free in server TCP listener cleanup allows authenticated remote code execution"),
+            ],
+        }
+    }
+
+    /// 当前使用的列表：已下载的列表比内置列表新时使用下载的列表
+    pub fn load() -> Self {
+        let bundled = Self::bundled();
+        let cached = store_path()
+            .ok()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|text| serde_json::from_str::<Self>(&text).ok());
+        match cached {
+            Some(list) if list.updated > bundled.updated => list,
+            _ => bundled,
+        }
+    }
+
+    /// 保存从服务器下载的列表
+    pub fn save(&self) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| SSHError::Storage(format!("Failed to serialize advisories: {}", e)))?;
+        std::fs::write(store_path()?, json)?;
+        Ok(())
+    }
+
+    /// 检查服务器标识，未命中任何公告或无法识别版本时返回 None
+    pub fn check(&self, ident: &str) -> Option<AdvisoryReport> {
+        let server = ServerVersion::parse(ident);
+        let (product, version) = (server.product?, server.version.as_deref()?);
+
+        let mut advisories: Vec<Advisory> = Vec::new();
+        for advisory in self.advisories.iter().filter(|a| a.affects(product, version)) {
+            if !advisories.iter().any(|a| a.id == advisory.id) {
+                advisories.push(advisory.clone());
+            }
+        }
+        if advisories.is_empty() {
+            return None;
+        }
+        advisories.sort_by_key(|a| std::cmp::Reverse(a.severity));
+
+        Some(AdvisoryReport {
+            distro_patched_possible: server.comment.is_some(),
+            server,
+            advisories,
+            list_updated: self.updated.clone(),
+        })
+    }
+}
+
+/// 已下载的公告列表路径
+fn store_path() -> Result<PathBuf> {
+    Ok(Storage::get_app_storage_dir()?.join("ssh_advisories.json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_server_versions() {
+        let server = ServerVersion::parse("SSH-2.0-OpenSSH_8.9p1 Ubuntu-3ubuntu0.10\r\n");
+        assert_eq!(server.product, Some(ServerProduct::Openssh));
+        assert_eq!(server.version.as_deref(), Some("8.9p1"));
+        assert_eq!(server.comment.as_deref(), Some("Ubuntu-3ubuntu0.10"));
+        assert_eq!(ServerVersion::parse("SSH-2.0-Go").product, None);
+
+        let server = ServerVersion::parse("SSH-2.0-OpenSSH_9.6p1 Ubuntu");
+        assert_eq!(server.raw, "SSH-2.0-OpenSSH_9.6p1 Ubuntu");
+        assert_eq!(server.product, Some(ServerProduct::Openssh));
+        assert_eq!(server.version.as_deref(), Some("9.6p1"));
+        assert_eq!(server.comment.as_deref(), Some("Ubuntu"));
+
+        let list = AdvisoryList::bundled();
+        let report = list.check("SSH-2.0-OpenSSH_8.9p1 Ubuntu-3ubuntu0.10").unwrap();
+        assert_eq!(report.advisories.iter().map(|a| a.id.as_str()).collect::<Vec<_>>(), ["CVE-2024-6387"]);
+        assert!(report.distro_patched_possible);
+
+        assert!(list.check("SSH-2.0-OpenSSH_9.8p1").is_none());
+        assert!(list.check("SSH-2.0-OpenSSH_9.8").is_none());
+        assert_eq!(list.check("SSH-2.0-OpenSSH_8.4p1").unwrap().advisories[0].id, "CVE-2021-41617");
+
+        let report = list.check("SSH-2.0-OpenSSH_7.2p2").unwrap();
+        assert_eq!(report.advisories.len(), 2);
+        assert!(!report.distro_patched_possible);
+
+        assert_eq!(list.check("SSH-2.0-dropbear_2016.72").unwrap().advisories[0].severity, Severity::Critical);
+        assert!(list.check("SSH-2.0-dropbear_2022.83").is_none());
+        assert!(list.check("SSH-2.0-dropbear").is_none());
+    }
+}
@@ -0,0 +1,151 @@
+//! 会话级 SSH 算法偏好
+//!
+//! 每类算法（密钥交换、主机密钥、加密、MAC）可以指定按优先级排列的列表，为空时使用预设：
+//! - `default`：russh 的默认算法
+//! - `legacy`：在默认算法之后追加 ssh-rsa、diffie-hellman-group1-sha1、CBC 加密等旧算法，
+//!   用于只支持旧算法的网络设备；新算法仍然优先协商
+
+use serde::{Deserialize, Serialize};
+
+/// 旧版密钥交换算法
+pub const LEGACY_KEX: &[&str] = &[
+    "diffie-hellman-group-exchange-sha1",
+    "diffie-hellman-group14-sha1",
+    "diffie-hellman-group1-sha1",
+];
+
+/// 旧版主机密钥算法
+pub const LEGACY_HOST_KEY: &[&str] = &["ssh-rsa", "ssh-dss"];
+
+/// 旧版加密算法
+pub const LEGACY_CIPHER: &[&str] = &["aes128-cbc", "aes192-cbc", "aes256-cbc", "3des-cbc"];
+
+/// 旧版 MAC 算法
+pub const LEGACY_MAC: &[&str] = &["hmac-sha1", "hmac-sha1-etm@openssh.com"];
+
+/// 算法预设
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum AlgorithmPreset {
+    #[default]
+    Default,
+    /// 兼容旧设备
+    Legacy,
+}
+
+/// 算法类别
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlgorithmKind {
+    Kex,
+    HostKey,
+    Cipher,
+    Mac,
+}
+
+impl AlgorithmKind {
+    fn legacy(&self) -> &'static [&'static str] {
+        match self {
+            AlgorithmKind::Kex => LEGACY_KEX,
+            AlgorithmKind::HostKey => LEGACY_HOST_KEY,
+            AlgorithmKind::Cipher => LEGACY_CIPHER,
+            AlgorithmKind::Mac => LEGACY_MAC,
+        }
+    }
+}
+
+/// 会话的算法偏好
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AlgorithmPreferences {
+    #[serde(default)]
+    pub preset: AlgorithmPreset,
+    /// 以下列表按优先级排列，为空时使用预设
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub kex: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub host_key: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cipher: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mac: Vec<String>,
+}
+
+impl AlgorithmPreferences {
+    /// 与默认设置相同（无需覆盖 russh 的默认值）
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    fn custom(&self, kind: AlgorithmKind) -> &[String] {
+        match kind {
+            AlgorithmKind::Kex => &self.kex,
+            AlgorithmKind::HostKey => &self.host_key,
+            AlgorithmKind::Cipher => &self.cipher,
+            AlgorithmKind::Mac => &self.mac,
+        }
+    }
+
+    /// 某类算法最终的优先级列表（去重，保持顺序）
+    ///
+    /// `defaults` 为后端的默认算法；自定义列表非空时只使用自定义列表
+    pub fn resolve<S: AsRef<str>>(&self, kind: AlgorithmKind, defaults: &[S]) -> Vec<String> {
+        let custom = self.custom(kind);
+        let candidates: Vec<&str> = if !custom.is_empty() {
+            custom.iter().map(|name| name.trim()).collect()
+        } else {
+            let legacy = match self.preset {
+                AlgorithmPreset::Default => &[][..],
+                AlgorithmPreset::Legacy => kind.legacy(),
+            };
+            defaults.iter().map(AsRef::as_ref).chain(legacy.iter().copied()).collect()
+        };
+
+        let mut names: Vec<String> = Vec::with_capacity(candidates.len());
+        for name in candidates {
+            if !name.is_empty() && !names.iter().any(|n| n == name) {
+                names.push(name.to_string());
+            }
+        }
+        names
+    }
+}
+
+/// 会话编辑器中可选的算法（后端默认算法加旧版算法）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlgorithmCatalog {
+    pub kex: Vec<String>,
+    pub host_key: Vec<String>,
+    pub cipher: Vec<String>,
+    pub mac: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve() {
+        let defaults = ["curve25519-sha256", "diffie-hellman-group14-sha256"];
+
+        let prefs = AlgorithmPreferences::default();
+        assert!(prefs.is_default());
+        assert_eq!(prefs.resolve(AlgorithmKind::Kex, &defaults), defaults);
+
+        let legacy = AlgorithmPreferences {
+            preset: AlgorithmPreset::Legacy,
+            ..Default::default()
+        };
+        let kex = legacy.resolve(AlgorithmKind::Kex, &defaults);
+        assert_eq!(kex[..2], defaults);
+        assert_eq!(kex.last().map(String::as_str), Some("diffie-hellman-group1-sha1"));
+        assert_eq!(legacy.resolve(AlgorithmKind::HostKey, &["ssh-ed25519"]), ["ssh-ed25519", "ssh-rsa", "ssh-dss"]);
+
+        let custom = AlgorithmPreferences {
+            preset: AlgorithmPreset::Legacy,
+            cipher: vec![" aes256-ctr".to_string(), "aes256-ctr".to_string(), String::new()],
+            ..Default::default()
+        };
+        assert_eq!(custom.resolve(AlgorithmKind::Cipher, &["chacha20-poly1305@openssh.com"]), ["aes256-ctr"]);
+    }
+}
@@ -57,6 +57,101 @@ pub trait SftpStream: AsyncRead + AsyncWrite + Send + Sync + Unpin {
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any;
 }
 
+/// exec 输出的来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ExecStream {
+    Stdout,
+    Stderr,
+}
+
+/// 远程命令执行结果（exec channel，不分配 PTY）
+#[derive(Debug, Clone, Default)]
+pub struct ExecOutput {
+    pub stdout: Vec<u8>,
+    pub stderr: Vec<u8>,
+    /// 退出码（服务器未返回时为 None）
+    pub exit_status: Option<u32>,
+}
+
+impl ExecOutput {
+    /// 以 UTF-8 解码的标准输出（非法字符替换为 U+FFFD）
+    pub fn stdout_lossy(&self) -> String {
+        String::from_utf8_lossy(&self.stdout).into_owned()
+    }
+
+    /// 以 UTF-8 解码的标准错误
+    pub fn stderr_lossy(&self) -> String {
+        String::from_utf8_lossy(&self.stderr).into_owned()
+    }
+
+    /// 命令是否成功退出
+    pub fn success(&self) -> bool {
+        self.exit_status == Some(0)
+    }
+}
+
+/// 远程断开事件前缀
+pub const DISCONNECTED_EVENT_PREFIX: &str = "ssh-disconnected-";
+
+/// 心跳超时事件前缀（`ssh-keepalive-timeout-{connectionId}`）
+pub const KEEPALIVE_TIMEOUT_EVENT_PREFIX: &str = "ssh-keepalive-timeout-";
+
+/// 心跳超时时的连接状态说明
+pub const KEEPALIVE_TIMEOUT_REASON: &str = "keepalive timeout";
+
+/// `ssh-keepalive-timeout-{connectionId}` 事件内容
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeepaliveTimeoutEvent {
+    /// 心跳间隔（秒）
+    pub interval_secs: u64,
+    /// 连续无响应的心跳次数上限
+    pub max_missed: usize,
+    /// 是否由漫游在后台重连
+    pub reconnecting: bool,
+}
+
+/// 远程关闭会话的原因（`ssh-disconnected-{connectionId}` 事件内容）
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DisconnectInfo {
+    /// 远程 shell 或命令的退出码
+    pub exit_status: Option<u32>,
+    /// 终止远程进程的信号名（如 `TERM`、`KILL`）
+    pub exit_signal: Option<String>,
+    pub core_dumped: bool,
+    /// 服务器发送的断开原因、信号附带的错误信息或连接错误
+    pub reason: Option<String>,
+    /// 服务器连续多次未响应心跳，连接被判定为失效
+    pub keepalive_timeout: bool,
+}
+
+impl DisconnectInfo {
+    /// 用于连接状态的简短说明
+    pub fn summary(&self) -> String {
+        if self.keepalive_timeout {
+            return KEEPALIVE_TIMEOUT_REASON.to_string();
+        }
+        if let Some(signal) = &self.exit_signal {
+            let mut summary = format!("Killed by signal {}", signal);
+            if self.core_dumped {
+                summary.push_str(" (core dumped)");
+            }
+            if let Some(reason) = &self.reason {
+                summary.push_str(": ");
+                summary.push_str(reason);
+            }
+            return summary;
+        }
+        match (&self.reason, self.exit_status) {
+            (Some(reason), _) => reason.clone(),
+            (None, Some(status)) => format!("Exited with status {}", status),
+            (None, None) => "Connection closed".to_string(),
+        }
+    }
+}
+
 /// 异步读取器 trait
 ///
 /// 用于从 SSH 会话读取数据
@@ -64,11 +159,52 @@ pub trait BackendReader: AsyncRead + Send + Unpin {
     /// as any conversion for downcasting
     #[allow(dead_code)]
     fn as_any(&self) -> &dyn std::any::Any;
+
+    /// 取出按块输出的接收端（零拷贝路径）
+    ///
+    /// 支持的后端直接返回底层 channel 收到的 `Bytes` 分块，
+    /// 调用方无需再经过 AsyncRead 拷贝；不支持时返回 None，回退到 AsyncRead
+    fn take_chunk_receiver(&mut self) -> Option<tokio::sync::mpsc::UnboundedReceiver<bytes::Bytes>> {
+        None
+    }
+
+    /// 远程关闭会话的原因（退出码、信号或服务器的断开原因）
+    ///
+    /// 输出结束后调用；本地主动断开时为 None
+    fn disconnect_info(&self) -> Option<DisconnectInfo> {
+        None
+    }
 }
 
-// 为实现 AsyncRead 的类型提供默认的 as_any 实现
-impl<T: AsyncRead + Send + Unpin + 'static> BackendReader for T {
-    fn as_any(&self) -> &dyn std::any::Any {
-        self
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disconnect_summary() {
+        let exited = DisconnectInfo { exit_status: Some(2), ..Default::default() };
+        assert_eq!(exited.summary(), "Exited with status 2");
+
+        let killed = DisconnectInfo {
+            exit_signal: Some("KILL".to_string()),
+            core_dumped: true,
+            ..Default::default()
+        };
+        assert_eq!(killed.summary(), "Killed by signal KILL (core dumped)");
+
+        let dropped = DisconnectInfo {
+            exit_status: Some(0),
+            reason: Some("Server shutting down".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(dropped.summary(), "Server shutting down");
+
+        let dead_peer = DisconnectInfo {
+            reason: Some("Connection lost".to_string()),
+            keepalive_timeout: true,
+            ..Default::default()
+        };
+        assert_eq!(dead_peer.summary(), KEEPALIVE_TIMEOUT_REASON);
+        assert_eq!(DisconnectInfo::default().summary(), "Connection closed");
     }
 }
@@ -0,0 +1,264 @@
+// 本地后端 - 在本机 PTY 中启动 shell，不建立网络连接
+//
+// 用于打开本地终端标签页，输出通过与 SSH 相同的读取路径发送给前端
+
+use crate::error::{Result, SSHError};
+use crate::ssh::backend::{BackendReader, DisconnectInfo, SSHBackend};
+use crate::ssh::session::{BackendType, SessionConfig};
+use crate::ssh::wsl;
+use async_trait::async_trait;
+use bytes::{Buf, Bytes};
+use portable_pty::{native_pty_system, Child, CommandBuilder, MasterPty, PtySize};
+use std::io::{self, Read, Write};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use tokio::io::{AsyncRead, ReadBuf};
+use tokio::sync::mpsc;
+
+/// 读取线程的缓冲区大小
+const READ_BUFFER_SIZE: usize = 8192;
+
+/// 输出结束后等待 shell 退出的次数（每次 50ms）
+const EXIT_WAIT_ATTEMPTS: usize = 20;
+
+/// 本地 shell 后端
+pub struct LocalBackend {
+    master: Option<Box<dyn MasterPty + Send>>,
+    writer: Option<Box<dyn Write + Send>>,
+    child: Option<Arc<Mutex<Box<dyn Child + Send + Sync>>>>,
+    receiver: Option<mpsc::UnboundedReceiver<Bytes>>,
+    disconnect_info: Arc<Mutex<Option<DisconnectInfo>>>,
+}
+
+impl LocalBackend {
+    pub fn new() -> Self {
+        Self {
+            master: None,
+            writer: None,
+            child: None,
+            receiver: None,
+            disconnect_info: Arc::new(Mutex::new(None)),
+        }
+    }
+}
+
+impl Default for LocalBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 要启动的程序：`command` 为空时使用用户的默认 shell，否则交给系统 shell 执行；
+/// WSL 会话改为启动 `wsl.exe`，命令在发行版内执行
+fn build_command(config: &SessionConfig) -> CommandBuilder {
+    if config.backend_type == BackendType::Wsl {
+        let mut builder = CommandBuilder::new("wsl.exe");
+        builder.args(wsl::command_args(
+            config.wsl_distribution.as_deref(),
+            config.remote_command.as_deref(),
+        ));
+        builder.env("TERM", config.terminal_type.as_deref().unwrap_or("xterm-256color"));
+        return builder;
+    }
+
+    let mut command = match config.remote_command.as_deref().filter(|c| !c.trim().is_empty()) {
+        Some(command) if cfg!(windows) => {
+            let mut builder = CommandBuilder::new("cmd.exe");
+            builder.args(["/C", command]);
+            builder
+        }
+        Some(command) => {
+            let shell = std::env::var("SHELL").unwrap_or_else(|_| "/bin/sh".to_string());
+            let mut builder = CommandBuilder::new(shell);
+            builder.args(["-c", command]);
+            builder
+        }
+        None => CommandBuilder::new_default_prog(),
+    };
+    command.env("TERM", config.terminal_type.as_deref().unwrap_or("xterm-256color"));
+    if let Some(home) = dirs::home_dir() {
+        command.cwd(home);
+    }
+    command
+}
+
+#[async_trait]
+impl SSHBackend for LocalBackend {
+    async fn connect(&mut self, config: &SessionConfig) -> Result<()> {
+        if config.backend_type == BackendType::Wsl {
+            if !cfg!(windows) {
+                return Err(SSHError::NotSupported("WSL is only available on Windows".to_string()));
+            }
+            if let Some(distribution) = config.wsl_distribution.as_deref() {
+                wsl::validate_distribution(distribution).map_err(SSHError::ConnectionFailed)?;
+            }
+        }
+
+        let pair = native_pty_system()
+            .openpty(PtySize {
+                rows: config.rows.unwrap_or(24),
+                cols: config.columns.unwrap_or(80),
+                pixel_width: 0,
+                pixel_height: 0,
+            })
+            .map_err(|e| SSHError::ConnectionFailed(format!("Failed to open local PTY: {}", e)))?;
+
+        let child = pair
+            .slave
+            .spawn_command(build_command(config))
+            .map_err(|e| SSHError::ConnectionFailed(format!("Failed to start local shell: {}", e)))?;
+        // 子进程已持有 slave 端，这里关闭后子进程退出时 reader 才能读到 EOF
+        drop(pair.slave);
+
+        let mut reader = pair
+            .master
+            .try_clone_reader()
+            .map_err(|e| SSHError::ConnectionFailed(format!("Failed to read local PTY: {}", e)))?;
+        let writer = pair
+            .master
+            .take_writer()
+            .map_err(|e| SSHError::ConnectionFailed(format!("Failed to write local PTY: {}", e)))?;
+
+        let child = Arc::new(Mutex::new(child));
+        let (sender, receiver) = mpsc::unbounded_channel();
+
+        // PTY 只提供阻塞读取，使用独立线程读取并转发到 channel
+        let exit_child = child.clone();
+        let disconnect_info = self.disconnect_info.clone();
+        std::thread::spawn(move || {
+            let mut buffer = [0u8; READ_BUFFER_SIZE];
+            loop {
+                match reader.read(&mut buffer) {
+                    Ok(0) | Err(_) => break,
+                    Ok(n) => {
+                        if sender.send(Bytes::copy_from_slice(&buffer[..n])).is_err() {
+                            break;
+                        }
+                    }
+                }
+            }
+
+            // 读到 EOF 时 shell 可能还未退出完毕，稍等片刻后记录退出码；
+            // 记录完成后才关闭 channel，保证上层读到结束时能取到退出码
+            for _ in 0..EXIT_WAIT_ATTEMPTS {
+                let status = exit_child.lock().ok().and_then(|mut child| child.try_wait().ok().flatten());
+                if let Some(status) = status {
+                    if let Ok(mut info) = disconnect_info.lock() {
+                        *info = Some(DisconnectInfo {
+                            exit_status: Some(status.exit_code()),
+                            ..Default::default()
+                        });
+                    }
+                    break;
+                }
+                std::thread::sleep(std::time::Duration::from_millis(50));
+            }
+            drop(sender);
+        });
+
+        self.master = Some(pair.master);
+        self.writer = Some(writer);
+        self.child = Some(child);
+        self.receiver = Some(receiver);
+        Ok(())
+    }
+
+    async fn write(&mut self, data: &[u8]) -> Result<()> {
+        let writer = self.writer.as_mut().ok_or(SSHError::NotConnected)?;
+        writer
+            .write_all(data)
+            .and_then(|_| writer.flush())
+            .map_err(|e| SSHError::Io(format!("Failed to write local PTY: {}", e)))
+    }
+
+    async fn resize(&mut self, rows: u16, cols: u16) -> Result<()> {
+        let master = self.master.as_ref().ok_or(SSHError::NotConnected)?;
+        master
+            .resize(PtySize {
+                rows,
+                cols,
+                pixel_width: 0,
+                pixel_height: 0,
+            })
+            .map_err(|e| SSHError::Io(format!("Failed to resize local PTY: {}", e)))
+    }
+
+    async fn disconnect(&mut self) -> Result<()> {
+        if let Some(child) = self.child.take() {
+            if let Ok(mut child) = child.lock() {
+                if let Ok(None) = child.try_wait() {
+                    let _ = child.kill();
+                }
+            }
+        }
+        self.writer = None;
+        self.master = None;
+        Ok(())
+    }
+
+    fn reader(&mut self) -> Result<Box<dyn BackendReader + Send>> {
+        let receiver = self.receiver.take().ok_or(SSHError::NotConnected)?;
+
+        Ok(Box::new(LocalReader {
+            receiver: Some(receiver),
+            buffer: Bytes::new(),
+            disconnect_info: self.disconnect_info.clone(),
+        }))
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// 本地后端的读取器
+pub struct LocalReader {
+    receiver: Option<mpsc::UnboundedReceiver<Bytes>>,
+    buffer: Bytes,
+    disconnect_info: Arc<Mutex<Option<DisconnectInfo>>>,
+}
+
+impl BackendReader for LocalReader {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn take_chunk_receiver(&mut self) -> Option<mpsc::UnboundedReceiver<Bytes>> {
+        if self.buffer.is_empty() {
+            self.receiver.take()
+        } else {
+            None
+        }
+    }
+
+    fn disconnect_info(&self) -> Option<DisconnectInfo> {
+        self.disconnect_info.lock().ok()?.clone()
+    }
+}
+
+impl AsyncRead for LocalReader {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = &mut *self;
+
+        if this.buffer.is_empty() {
+            let Some(receiver) = this.receiver.as_mut() else {
+                return Poll::Ready(Ok(()));
+            };
+            match receiver.poll_recv(cx) {
+                Poll::Ready(Some(data)) => this.buffer = data,
+                Poll::Ready(None) => return Poll::Ready(Ok(())),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        let to_copy = std::cmp::min(this.buffer.len(), buf.remaining());
+        buf.put_slice(&this.buffer[..to_copy]);
+        this.buffer.advance(to_copy);
+        Poll::Ready(Ok(()))
+    }
+}
@@ -0,0 +1,130 @@
+// 回环后端 - 写入的数据原样作为输出返回，不建立网络连接
+//
+// 用于基准测试读取/发送路径，排除网络和服务器的影响
+
+use crate::error::{Result, SSHError};
+use crate::ssh::backend::{BackendReader, SSHBackend};
+use crate::ssh::session::SessionConfig;
+use async_trait::async_trait;
+use bytes::{Buf, Bytes};
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::io::{AsyncRead, ReadBuf};
+use tokio::sync::mpsc;
+
+/// 回环后端
+///
+/// `write` 写入的数据原样出现在 reader 中；也可以通过 [`LoopbackBackend::output_sender`]
+/// 直接注入输出，模拟服务器的大量输出
+pub struct LoopbackBackend {
+    sender: Option<mpsc::UnboundedSender<Bytes>>,
+    receiver: Option<mpsc::UnboundedReceiver<Bytes>>,
+}
+
+impl LoopbackBackend {
+    pub fn new() -> Self {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        Self {
+            sender: Some(sender),
+            receiver: Some(receiver),
+        }
+    }
+
+    /// 获取输出注入端（断开后返回 None）
+    pub fn output_sender(&self) -> Option<mpsc::UnboundedSender<Bytes>> {
+        self.sender.clone()
+    }
+}
+
+impl Default for LoopbackBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SSHBackend for LoopbackBackend {
+    async fn connect(&mut self, _config: &SessionConfig) -> Result<()> {
+        Ok(())
+    }
+
+    async fn write(&mut self, data: &[u8]) -> Result<()> {
+        let sender = self.sender.as_ref().ok_or(SSHError::NotConnected)?;
+        sender
+            .send(Bytes::copy_from_slice(data))
+            .map_err(|_| SSHError::Io("Loopback reader closed".to_string()))
+    }
+
+    async fn resize(&mut self, _rows: u16, _cols: u16) -> Result<()> {
+        Ok(())
+    }
+
+    async fn disconnect(&mut self) -> Result<()> {
+        // 丢弃发送端后 reader 读到 EOF
+        self.sender = None;
+        Ok(())
+    }
+
+    fn reader(&mut self) -> Result<Box<dyn BackendReader + Send>> {
+        let receiver = self
+            .receiver
+            .take()
+            .ok_or_else(|| SSHError::NotSupported("Loopback reader already taken".to_string()))?;
+
+        Ok(Box::new(LoopbackReader {
+            receiver: Some(receiver),
+            buffer: Bytes::new(),
+        }))
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// 回环后端的读取器
+pub struct LoopbackReader {
+    receiver: Option<mpsc::UnboundedReceiver<Bytes>>,
+    buffer: Bytes,
+}
+
+impl BackendReader for LoopbackReader {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn take_chunk_receiver(&mut self) -> Option<mpsc::UnboundedReceiver<Bytes>> {
+        if self.buffer.is_empty() {
+            self.receiver.take()
+        } else {
+            None
+        }
+    }
+}
+
+impl AsyncRead for LoopbackReader {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = &mut *self;
+
+        if this.buffer.is_empty() {
+            let Some(receiver) = this.receiver.as_mut() else {
+                return Poll::Ready(Ok(()));
+            };
+            match receiver.poll_recv(cx) {
+                Poll::Ready(Some(data)) => this.buffer = data,
+                Poll::Ready(None) => return Poll::Ready(Ok(())),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        let to_copy = std::cmp::min(this.buffer.len(), buf.remaining());
+        buf.put_slice(&this.buffer[..to_copy]);
+        this.buffer.advance(to_copy);
+        Poll::Ready(Ok(()))
+    }
+}
@@ -3,6 +3,20 @@
 // 所有平台都使用 russh（纯 Rust 实现，包括 Android）
 pub mod russh;
 
+// 本地 shell 后端（仅桌面平台）
+#[cfg(not(target_os = "android"))]
+pub mod local;
+
+// 串口后端（仅桌面平台）
+#[cfg(not(target_os = "android"))]
+pub mod serial;
+
+// Telnet 后端（旧设备）
+pub mod telnet;
+
+// 回环后端（基准测试用）
+pub mod loopback;
+
 // SFTP channel 包装器
 pub mod sftp_channel;
 
@@ -1,7 +1,14 @@
 // russh 后端实现 - 纯 Rust 实现，支持所有平台（包括 Android）
 
 use crate::error::{Result, SSHError};
-use crate::ssh::backend::{BackendReader, SSHBackend};
+use crate::ssh::algorithms::{AlgorithmCatalog, AlgorithmKind, AlgorithmPreferences, AlgorithmPreset};
+use crate::ssh::backend::{BackendReader, DisconnectInfo, ExecOutput, ExecStream, SSHBackend, KEEPALIVE_TIMEOUT_REASON};
+use crate::ssh::encoding::Transcoder;
+use crate::ssh::forwarding::{self, RemoteForwardTable};
+use crate::ssh::happy_eyeballs;
+use crate::ssh::known_hosts;
+use crate::ssh::manager::{ChannelKind, ChannelLease, ConnectionHub};
+use crate::ssh::security_key::{self, SecurityKeyPrompt, TouchPromptSigner};
 use crate::ssh::session::{AuthMethod, SessionConfig};
 use async_trait::async_trait;
 use russh::client;
@@ -9,18 +16,24 @@ use russh::client::{Config, Handle, Msg};
 use russh::*;
 use russh::{ChannelReadHalf, ChannelWriteHalf};
 use russh::keys::{load_secret_key, PrivateKeyWithHashAlg};
+use bytes::{Buf, Bytes};
 use std::io;
 use std::io::Cursor;
+use std::future::Future;
 use std::pin::Pin;
+use std::sync::Arc;
 use std::task::{Context, Poll};
 use std::time::Duration;
 use tokio::io::{AsyncRead, ReadBuf};
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, RwLock};
 use tracing::{debug, error, info};
 
 // 导入 SFTP channel 包装器
 use super::sftp_channel::SftpChannelStream;
 
+/// 连续无响应的心跳次数上限，超过后 russh 断开连接
+pub const KEEPALIVE_MAX: usize = 3;
+
 /// SSH Channel 命令
 ///
 /// 用于在后台任务中控制 SSH channel
@@ -34,45 +47,234 @@ enum ChannelCommand {
 ///
 /// 纯 Rust SSH 实现，基于 russh 库
 pub struct RusshBackend {
-    handle: Option<Handle<RusshHandler>>,
+    /// 连接共享中心（未设置时使用独立的中心，不与其他连接共享）
+    hub: ConnectionHub,
+    /// 终端 channel 持有的共享连接租约，断开时释放
+    shell_lease: Option<ChannelLease>,
+    /// 使用安全密钥认证时的触摸提示
+    security_key_prompt: Option<SecurityKeyPrompt>,
+    /// 远程关闭会话的原因，由会话循环和 Handler 写入
+    disconnect_info: DisconnectSlot,
+    /// 远程端口转发表（由连接实例持有，重连后共享）
+    remote_forwards: Option<RemoteForwardTable>,
     command_sender: Option<mpsc::UnboundedSender<ChannelCommand>>,
-    receiver: Option<mpsc::UnboundedReceiver<Vec<u8>>>,
+    receiver: Option<mpsc::UnboundedReceiver<Bytes>>,
     connected: bool,
 }
 
+/// 远程关闭会话的原因，先写入的一方为准
+type DisconnectSlot = Arc<std::sync::Mutex<Option<DisconnectInfo>>>;
+
+/// 记录断开原因（已有记录时只补充缺少的原因）
+fn record_disconnect(slot: &DisconnectSlot, update: impl FnOnce(&mut DisconnectInfo)) {
+    if let Ok(mut info) = slot.lock() {
+        update(info.get_or_insert_with(DisconnectInfo::default));
+    }
+}
+
+/// 终端 channel 登记的断开原因和可选的远程端口转发表
+type TransportSubscriber = (DisconnectSlot, Option<RemoteForwardTable>);
+
+/// 共享同一 SSH 传输的各个终端 channel 登记的断开原因和远程端口转发表
+#[derive(Clone, Default)]
+struct TransportSubscribers(Arc<std::sync::Mutex<Vec<TransportSubscriber>>>);
+
+impl TransportSubscribers {
+    fn subscribe(&self, slot: DisconnectSlot, remote_forwards: Option<RemoteForwardTable>) {
+        if let Ok(mut subscribers) = self.0.lock() {
+            subscribers.push((slot, remote_forwards));
+        }
+    }
+
+    fn unsubscribe(&self, slot: &DisconnectSlot) {
+        if let Ok(mut subscribers) = self.0.lock() {
+            subscribers.retain(|(s, _)| !Arc::ptr_eq(s, slot));
+        }
+    }
+
+    /// 向所有终端记录连接断开的原因
+    fn record_reason(&self, message: &str) {
+        if let Ok(subscribers) = self.0.lock() {
+            for (slot, _) in subscribers.iter() {
+                record_disconnect(slot, |info| {
+                    info.reason.get_or_insert_with(|| message.to_string());
+                });
+            }
+        }
+    }
+
+    /// 服务器未响应心跳，向所有终端标记连接已失效
+    fn record_keepalive_timeout(&self) {
+        if let Ok(subscribers) = self.0.lock() {
+            for (slot, _) in subscribers.iter() {
+                record_disconnect(slot, |info| {
+                    info.keepalive_timeout = true;
+                    info.reason.get_or_insert_with(|| KEEPALIVE_TIMEOUT_REASON.to_string());
+                });
+            }
+        }
+    }
+
+    /// 查找登记了服务器端口 `port` 的远程端口转发表
+    fn remote_forward_for(&self, port: u32) -> Option<RemoteForwardTable> {
+        let subscribers = self.0.lock().ok()?;
+        subscribers
+            .iter()
+            .filter_map(|(_, table)| table.as_ref())
+            .find(|table| forwarding::has_remote_route(table, port))
+            .cloned()
+    }
+}
+
 /// russh 客户端 Handler
 ///
 /// 实现 client::Handler trait 来处理 SSH 协议事件
-pub struct RusshHandler;
+pub struct RusshHandler {
+    /// 共享此连接的终端登记的断开原因和远程端口转发表
+    subscribers: TransportSubscribers,
+    /// 是否路由服务器转发回来的连接（跳板机连接上为 false）
+    route_forwards: bool,
+    /// 本跳的主机和端口，用于校验主机密钥
+    host: String,
+    port: u16,
+    /// 主机密钥变化时是否拒绝连接
+    strict_host_key_checking: bool,
+    /// 服务器的 SSH 标识，首次密钥交换完成时记录
+    server_id: Arc<std::sync::OnceLock<String>>,
+}
 
 impl client::Handler for RusshHandler {
     type Error = russh::Error;
 
+    /// 服务器转发回来的连接（远程端口转发）
+    async fn server_channel_open_forwarded_tcpip(
+        &mut self,
+        channel: Channel<Msg>,
+        connected_address: &str,
+        connected_port: u32,
+        originator_address: &str,
+        originator_port: u32,
+        _session: &mut client::Session,
+    ) -> std::result::Result<(), Self::Error> {
+        debug!(
+            "Forwarded connection to {}:{} from {}:{}",
+            connected_address, connected_port, originator_address, originator_port
+        );
+
+        if !self.route_forwards {
+            debug!("Remote forwarding not enabled on this connection, dropping channel");
+            return Ok(());
+        }
+        match self.subscribers.remote_forward_for(connected_port) {
+            Some(table) => forwarding::spawn_forwarded_channel(
+                &table,
+                connected_port,
+                format!("{}:{}", originator_address, originator_port),
+                channel.into_stream(),
+            ),
+            None => debug!("No remote forward registered for port {}, dropping channel", connected_port),
+        }
+        Ok(())
+    }
+
+    /// 服务器发送 SSH_MSG_DISCONNECT 或连接出错
+    async fn disconnected(
+        &mut self,
+        reason: client::DisconnectReason<Self::Error>,
+    ) -> std::result::Result<(), Self::Error> {
+        let (message, result) = match reason {
+            client::DisconnectReason::ReceivedDisconnect(info) => {
+                info!("Server disconnected: {:?} {}", info.reason_code, info.message);
+                let message = if info.message.is_empty() {
+                    format!("{:?}", info.reason_code)
+                } else {
+                    info.message
+                };
+                (message, Ok(()))
+            }
+            // 超过 keepalive_max 次心跳无响应，russh 直接关闭连接，这里单独标记以便上层提示
+            client::DisconnectReason::Error(russh::Error::KeepaliveTimeout) => {
+                tracing::warn!("Server not responding to keepalives, connection considered dead");
+                self.subscribers.record_keepalive_timeout();
+                return Err(russh::Error::KeepaliveTimeout);
+            }
+            client::DisconnectReason::Error(e) => {
+                error!("Connection error: {}", e);
+                (e.to_string(), Err(e))
+            }
+        };
+        self.subscribers.record_reason(&message);
+        result
+    }
+
+    /// 密钥交换完成，记录服务器标识（用于安全公告检查）
+    async fn kex_done(
+        &mut self,
+        _shared_secret: Option<&[u8]>,
+        _names: &russh::Names,
+        session: &mut client::Session,
+    ) -> std::result::Result<(), Self::Error> {
+        self.server_id
+            .get_or_init(|| String::from_utf8_lossy(session.remote_sshid()).trim().to_string());
+        Ok(())
+    }
+
     /// 验证服务器主机密钥
     ///
-    /// 在生产环境中，应该实现 known_hosts 验证
+    /// 对照应用管理的 known_hosts：首次连接时记录，密钥变化或 known_hosts 无法读写时按严格检查设置拒绝
     async fn check_server_key(
         &mut self,
-        _server_public_key: &russh::keys::PublicKey,
+        server_public_key: &russh::keys::PublicKey,
     ) -> std::result::Result<bool, Self::Error> {
-        // TODO: 实现完整的主机密钥验证
-        // 目前暂时接受所有主机密钥（仅用于开发）
-        // 生产环境应该：
-        // 1. 读取 known_hosts 文件
-        // 2. 比较服务器公钥
-        // 3. 提示用户确认新密钥
-        debug!("Accepting server host key (verification not implemented)");
-        Ok(true)
+        let verdict = known_hosts::verify(&self.host, self.port, server_public_key);
+        let accepted = verdict.accepted(self.strict_host_key_checking);
+        if !accepted {
+            let host = known_hosts::host_pattern(&self.host, self.port);
+            let reason = if let known_hosts::HostKeyVerdict::StoreError(e) = &verdict {
+                format!("无法读写 known_hosts，已启用严格主机密钥检查，拒绝连接主机 {}: {}", host, e)
+            } else {
+                format!(
+                    "主机 {} 的密钥已变化（新密钥 {} {}），可能存在中间人攻击；核实指纹无误后请在「设置 → 主机密钥」中删除旧密钥",
+                    host,
+                    server_public_key.algorithm(),
+                    server_public_key.fingerprint(russh::keys::HashAlg::Sha256)
+                )
+            };
+            self.subscribers.record_reason(&reason);
+        }
+        Ok(accepted)
     }
 }
 
 /// russh 的异步读取器
 ///
 /// 从 mpsc channel 接收 SSH 输出数据
+///
+/// 输出以引用计数的 `Bytes` 分块传递，既可以通过 [`BackendReader::take_chunk_receiver`]
+/// 直接取走分块，也可以作为 AsyncRead 使用
 pub struct RusshReader {
-    receiver: mpsc::UnboundedReceiver<Vec<u8>>,
-    buffer: Option<Vec<u8>>,
-    position: usize,
+    receiver: Option<mpsc::UnboundedReceiver<Bytes>>,
+    buffer: Bytes,
+    disconnect_info: DisconnectSlot,
+}
+
+impl BackendReader for RusshReader {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn take_chunk_receiver(&mut self) -> Option<mpsc::UnboundedReceiver<Bytes>> {
+        // 已缓存但未读取的数据会丢失，因此只在尚未开始读取时交出接收端
+        if self.buffer.is_empty() {
+            self.receiver.take()
+        } else {
+            None
+        }
+    }
+
+    fn disconnect_info(&self) -> Option<DisconnectInfo> {
+        self.disconnect_info.lock().ok().and_then(|info| info.clone())
+    }
 }
 
 impl AsyncRead for RusshReader {
@@ -81,43 +283,29 @@ impl AsyncRead for RusshReader {
         cx: &mut Context<'_>,
         buf: &mut ReadBuf<'_>,
     ) -> Poll<io::Result<()>> {
-        // 如果当前 buffer 还有数据，先消费
-        if self.buffer.is_some() {
-            let buffer_len = {
-                let buffer = self.buffer.as_ref().unwrap();
-                buffer.len()
-            };
-
-            if self.position < buffer_len {
-                let mut temp_buffer = None;
-                std::mem::swap(&mut self.buffer, &mut temp_buffer);
+        let this = &mut *self;
 
-                if let Some(buffer) = temp_buffer {
-                    let remaining = &buffer[self.position..];
-                    let to_copy = std::cmp::min(remaining.len(), buf.capacity());
-                    buf.put_slice(&remaining[..to_copy]);
-                    self.position += to_copy;
-
-                    if self.position >= buffer.len() {
-                        self.buffer = None;
-                        self.position = 0;
-                    } else {
-                        self.buffer = Some(buffer);
-                    }
-                }
-                return Poll::Ready(Ok(()));
-            }
+        // 如果当前 buffer 还有数据，先消费（advance 只移动指针，不重新分配）
+        if !this.buffer.is_empty() {
+            let to_copy = std::cmp::min(this.buffer.len(), buf.remaining());
+            buf.put_slice(&this.buffer[..to_copy]);
+            this.buffer.advance(to_copy);
+            return Poll::Ready(Ok(()));
         }
 
+        let Some(receiver) = this.receiver.as_mut() else {
+            // 接收端已被取走，视为 EOF
+            return Poll::Ready(Ok(()));
+        };
+
         // 尝试从 channel 接收新数据
-        match self.receiver.poll_recv(cx) {
-            Poll::Ready(Some(data)) => {
-                let to_copy = std::cmp::min(data.len(), buf.capacity());
+        match receiver.poll_recv(cx) {
+            Poll::Ready(Some(mut data)) => {
+                let to_copy = std::cmp::min(data.len(), buf.remaining());
                 buf.put_slice(&data[..to_copy]);
-                if data.len() > to_copy {
-                    self.buffer = Some(data[to_copy..].to_vec());
-                    self.position = 0;
-                }
+                data.advance(to_copy);
+                // 保留剩余数据，下次读取时继续消费
+                this.buffer = data;
                 Poll::Ready(Ok(()))
             }
             Poll::Ready(None) => {
@@ -134,27 +322,47 @@ impl RusshBackend {
     /// 创建新的 russh 后端实例
     pub fn new() -> Self {
         let (_command_sender, _) = mpsc::unbounded_channel::<ChannelCommand>();
-        let (_output_sender, output_receiver) = mpsc::unbounded_channel::<Vec<u8>>();
+        let (_output_sender, output_receiver) = mpsc::unbounded_channel::<Bytes>();
         Self {
-            handle: None,
+            hub: ConnectionHub::default(),
+            shell_lease: None,
+            security_key_prompt: None,
+            disconnect_info: Arc::new(std::sync::Mutex::new(None)),
+            remote_forwards: None,
             command_sender: None,
             receiver: Some(output_receiver),
             connected: false,
         }
     }
 
-    /// 获取 SSH Handle 的引用
-    ///
-    /// 用于创建额外的 channel（如 SFTP）
-    #[allow(dead_code)]
-    pub fn get_handle(&self) -> Option<&Handle<RusshHandler>> {
-        self.handle.as_ref()
+    /// 使用共享的连接中心，连接到已有连接的主机时复用该连接，需在 connect 之前调用
+    pub fn set_connection_hub(&mut self, hub: ConnectionHub) {
+        self.hub = hub;
+    }
+
+    /// 在当前连接上租用一个 channel（exec、SFTP、端口转发），租约释放前连接保持共享
+    pub fn lease(&self, kind: ChannelKind) -> Result<ChannelLease> {
+        if !self.connected {
+            return Err(SSHError::NotConnected);
+        }
+        self.shell_lease
+            .as_ref()
+            .map(|lease| lease.share(kind))
+            .ok_or(SSHError::NotConnected)
+    }
+
+    /// 当前连接的服务器 SSH 标识
+    pub fn server_id(&self) -> Option<String> {
+        self.shell_lease
+            .as_ref()
+            .and_then(|lease| lease.transport().server_id())
+            .map(str::to_string)
     }
 
     /// 创建 russh 客户端配置
     ///
     /// 根据最佳实践配置算法偏好、超时等参数
-    fn create_config(config: &SessionConfig) -> Config {
+    pub(crate) fn create_config(config: &SessionConfig) -> Config {
         let mut russh_config = Config {
             // 流控制设置（与 russh-info.md 推荐值一致）
             window_size: 2097152,     // 2MB 窗口
@@ -170,27 +378,281 @@ impl RusshBackend {
             russh_config.keepalive_interval = Some(Duration::from_secs(config.keep_alive_interval));
             // 设置最大心跳次数，超过后断开连接
             // 默认 3 次，即 3 次心跳无响应后断开
-            russh_config.keepalive_max = 3;
+            russh_config.keepalive_max = KEEPALIVE_MAX;
 
             tracing::info!(
-                "SSH keepalive enabled: interval={}s, max=3",
-                config.keep_alive_interval
+                "SSH keepalive enabled: interval={}s, max={}",
+                config.keep_alive_interval,
+                KEEPALIVE_MAX
             );
         } else {
             tracing::info!("SSH keepalive disabled (interval=0)");
         }
 
+        // 会话自定义的算法偏好（如兼容旧设备的 ssh-rsa / diffie-hellman-group1-sha1）
+        if let Some(algorithms) = config.algorithms.as_ref().filter(|a| !a.is_default()) {
+            russh_config.preferred = Self::preferred_algorithms(algorithms);
+            tracing::info!("Using custom algorithm preferences: {:?}", russh_config.preferred);
+        }
+
         russh_config
     }
 
+    /// 可供选择的算法名（默认算法在前，之后是旧版算法）
+    pub fn algorithm_catalog() -> AlgorithmCatalog {
+        let defaults = Preferred::default();
+        let legacy = AlgorithmPreferences {
+            preset: AlgorithmPreset::Legacy,
+            ..Default::default()
+        };
+        let names = |kind, defaults: Vec<String>| legacy.resolve(kind, &defaults);
+        AlgorithmCatalog {
+            kex: names(AlgorithmKind::Kex, defaults.kex.iter().map(|n| n.as_ref().to_string()).collect()),
+            host_key: names(AlgorithmKind::HostKey, defaults.key.iter().map(|a| a.as_str().to_string()).collect()),
+            cipher: names(AlgorithmKind::Cipher, defaults.cipher.iter().map(|n| n.as_ref().to_string()).collect()),
+            mac: names(AlgorithmKind::Mac, defaults.mac.iter().map(|n| n.as_ref().to_string()).collect()),
+        }
+    }
+
+    /// 按会话的算法偏好生成 russh 的 `Preferred`
+    fn preferred_algorithms(prefs: &AlgorithmPreferences) -> Preferred {
+        let defaults = Preferred::default();
+        Preferred {
+            kex: preferred_list(prefs, AlgorithmKind::Kex, &defaults.kex, |n| n.as_ref().to_string(), |s| {
+                kex::Name::try_from(s).ok()
+            }),
+            key: preferred_list(prefs, AlgorithmKind::HostKey, &defaults.key, |a| a.as_str().to_string(), |s| {
+                s.parse::<russh::keys::Algorithm>().ok()
+            }),
+            cipher: preferred_list(prefs, AlgorithmKind::Cipher, &defaults.cipher, |n| n.as_ref().to_string(), |s| {
+                cipher::Name::try_from(s).ok()
+            }),
+            mac: preferred_list(prefs, AlgorithmKind::Mac, &defaults.mac, |n| n.as_ref().to_string(), |s| {
+                mac::Name::try_from(s).ok()
+            }),
+            ..defaults
+        }
+    }
+
+    /// 建立并认证到 `config` 所指主机的 SSH 连接
+    ///
+    /// 配置了跳板机时先递归连接跳板机，再通过 `direct-tcpip` channel 转发到目标主机，
+    /// 目标主机名由跳板机解析；途经的跳板机连接按顺序存入 `jump_handles`，
+    /// 需要在最终连接的整个生命周期内保持；`server_id` 记录最终主机的服务器标识
+    fn open_transport<'a>(
+        config: &'a SessionConfig,
+        jump_handles: &'a mut Vec<Handle<RusshHandler>>,
+        subscribers: TransportSubscribers,
+        route_forwards: bool,
+        server_id: Arc<std::sync::OnceLock<String>>,
+        prompt: Option<&'a SecurityKeyPrompt>,
+    ) -> Pin<Box<dyn Future<Output = Result<Handle<RusshHandler>>> + Send + 'a>> {
+        Box::pin(async move {
+            // 创建 russh 配置
+            let russh_config = std::sync::Arc::new(Self::create_config(config));
+            let handler = RusshHandler {
+                subscribers: subscribers.clone(),
+                route_forwards,
+                host: config.host.clone(),
+                port: config.port,
+                strict_host_key_checking: config.strict_host_key_checking,
+                server_id,
+            };
+
+            let mut handle = match &config.jump_host {
+                Some(jump) => {
+                    // 跳板机断开时同样记录原因
+                    let jump_handle = Self::open_transport(jump, jump_handles, subscribers, false, Default::default(), prompt).await?;
+
+                    info!("Opening tunnel via {}:{} to {}:{}", jump.host, jump.port, config.host, config.port);
+                    let channel = jump_handle
+                        .channel_open_direct_tcpip(config.host.as_str(), config.port as u32, "127.0.0.1", 0)
+                        .await
+                        .map_err(|e| SSHError::ConnectionFailed(format!(
+                            "跳板机 {} 无法连接到 {}:{}: {}",
+                            jump.host, config.host, config.port, e
+                        )))?;
+                    jump_handles.push(jump_handle);
+
+                    // 在隧道上进行 SSH 握手
+                    client::connect_stream(russh_config, channel.into_stream(), handler)
+                        .await
+                        .map_err(|e| SSHError::ConnectionFailed(format!("Failed to connect: {}", e)))?
+                }
+                None => {
+                    // 解析全部地址并按 Happy Eyeballs 方式建立 TCP 连接
+                    let stream = happy_eyeballs::connect(&config.host, config.port, config.dns_resolver.as_ref()).await?;
+
+                    // 在已建立的 TCP 连接上进行 SSH 握手
+                    client::connect_stream(russh_config, stream, handler)
+                        .await
+                        .map_err(|e| SSHError::ConnectionFailed(format!("Failed to connect: {}", e)))?
+                }
+            };
+
+            Self::authenticate(&mut handle, config, prompt).await?;
+            Ok(handle)
+        })
+    }
+
+    /// 根据会话配置的认证方式在已握手的连接上进行认证
+    ///
+    /// 私钥为 FIDO2 安全密钥时经由 ssh-agent 签名，等待触摸时通过 `prompt` 通知
+    async fn authenticate(
+        handle: &mut Handle<RusshHandler>,
+        config: &SessionConfig,
+        prompt: Option<&SecurityKeyPrompt>,
+    ) -> Result<()> {
+        // 根据认证方式进行认证
+        match &config.auth_method {
+            AuthMethod::Password { password } => {
+                info!("Authenticating with password for user: {}", config.username);
+                let auth_result = handle
+                    .authenticate_password(&config.username, password)
+                    .await
+                    .map_err(|e| {
+                        error!("Password authentication error for user {}: {}", config.username, e);
+                        SSHError::AuthenticationFailed(format!("密码认证错误: {}", e))
+                    })?;
+
+                if !auth_result.success() {
+                    error!("Password authentication failed for user: {}", config.username);
+                    return Err(SSHError::AuthenticationFailed(
+                        format!("密码认证失败: 用户名或密码错误 (user: {})", config.username),
+                    ));
+                }
+                info!("Password authentication successful for user: {}", config.username);
+            }
+            AuthMethod::PublicKey {
+                private_key_path,
+                passphrase,
+            } => {
+                info!("Authenticating with public key for user: {}, key path: {}", config.username, private_key_path);
+                let loaded = load_secret_key(private_key_path, passphrase.as_deref());
+
+                // 安全密钥的私钥文件只有密钥句柄，无法解析时根据旁边的 .pub 文件识别
+                let security_key = match &loaded {
+                    Ok(key) if security_key::is_security_key(&key.algorithm()) => Some(key.public_key().clone()),
+                    Ok(_) => None,
+                    Err(_) => security_key::sibling_public_key(private_key_path)
+                        .filter(|key| security_key::is_security_key(&key.algorithm())),
+                };
+                if let Some(public_key) = security_key {
+                    return Self::authenticate_security_key(handle, config, public_key, loaded.ok().as_ref(), prompt).await;
+                }
+
+                let key_pair = loaded.map_err(
+                    |e| {
+                        error!("Failed to load private key from {}: {}", private_key_path, e);
+                        SSHError::AuthenticationFailed(format!("无法加载私钥文件 '{}': {}", private_key_path, e))
+                    },
+                )?;
+
+                // 统一使用 PrivateKeyWithHashAlg 包装
+                // 参考 russh-info.md 中的最佳实践
+                let key_with_hash = if key_pair.algorithm().is_rsa() {
+                    // 获取服务器支持的 RSA 哈希算法
+                    let best_hash = handle
+                        .best_supported_rsa_hash()
+                        .await
+                        .map_err(|e| {
+                            error!("Failed to get supported RSA hash: {}", e);
+                            SSHError::AuthenticationFailed(format!(
+                                "无法获取服务器支持的 RSA 哈希算法: {}",
+                                e
+                            ))
+                        })?
+                        .flatten();
+
+                    if let Some(hash) = best_hash {
+                        info!("Using RSA key with {:?} hash", hash);
+                    } else {
+                        info!("Using RSA key");
+                    }
+                    PrivateKeyWithHashAlg::new(
+                        std::sync::Arc::new(key_pair),
+                        best_hash,
+                    )
+                } else {
+                    info!("Using {:?} key", key_pair.algorithm());
+                    PrivateKeyWithHashAlg::new(
+                        std::sync::Arc::new(key_pair),
+                        None,
+                    )
+                };
+
+                info!("Attempting public key authentication...");
+                let auth_result = handle
+                    .authenticate_publickey(&config.username, key_with_hash)
+                    .await
+                    .map_err(|e| {
+                        error!("Public key authentication error for user {}: {}", config.username, e);
+                        SSHError::AuthenticationFailed(format!(
+                            "公钥认证错误: {}",
+                            e
+                        ))
+                    })?;
+
+                if !auth_result.success() {
+                    error!("Public key authentication failed for user: {}", config.username);
+                    error!("Possible reasons: 1) Public key not authorized on server, 2) Private key doesn't match public key, 3) Wrong user");
+                    return Err(SSHError::AuthenticationFailed(
+                        format!("公钥认证失败 (user: {})\n可能原因:\n1. 服务器上未授权此公钥（检查 ~/.ssh/authorized_keys）\n2. 私钥与公钥不匹配\n3. 用户名错误", config.username),
+                    ));
+                }
+                info!("Public key authentication successful for user: {}", config.username);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 使用 FIDO2 安全密钥认证：由 ssh-agent 调用硬件签名，签名期间提示用户触摸密钥
+    async fn authenticate_security_key(
+        handle: &mut Handle<RusshHandler>,
+        config: &SessionConfig,
+        public_key: russh::keys::PublicKey,
+        private_key: Option<&russh::keys::PrivateKey>,
+        prompt: Option<&SecurityKeyPrompt>,
+    ) -> Result<()> {
+        info!("Authenticating with security key ({}) for user: {}", public_key.algorithm(), config.username);
+        let mut agent = security_key::connect_agent().await?;
+        security_key::ensure_identity(&mut agent, &public_key, private_key).await?;
+
+        let mut signer = TouchPromptSigner::new(agent, &config.host, prompt.cloned());
+        // russh 的签名器认证 future 无法在 `open_transport` 的 `Send` 装箱 future 中证明为 `Send`
+        // （编译器对 RPITIT 的高阶生命周期限制），在当前工作线程上阻塞执行，不跨越外层的 await
+        let username = config.username.clone();
+        let auth_result = tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current()
+                .block_on(handle.authenticate_publickey_with(username, public_key, None, &mut signer))
+        })
+        .map_err(|e| {
+            error!("Security key authentication error for user {}: {}", config.username, e);
+            SSHError::AuthenticationFailed(format!("安全密钥认证错误: {}", e))
+        })?;
+
+        if !auth_result.success() {
+            error!("Security key authentication failed for user: {}", config.username);
+            return Err(SSHError::AuthenticationFailed(format!(
+                "安全密钥认证失败 (user: {})\n可能原因:\n1. 未及时触摸安全密钥\n2. 服务器上未授权此公钥\n3. 服务器不支持安全密钥（需要 OpenSSH 8.2 及以上版本）",
+                config.username
+            )));
+        }
+        info!("Security key authentication successful for user: {}", config.username);
+        Ok(())
+    }
+
     /// 启动 SSH 会话管理任务
     ///
     /// 从 SSH channel 读取数据并处理命令
     fn start_session_loop(
         mut read_half: ChannelReadHalf,
         write_half: ChannelWriteHalf<Msg>,
-        output_sender: mpsc::UnboundedSender<Vec<u8>>,
+        output_sender: mpsc::UnboundedSender<Bytes>,
         mut command_receiver: mpsc::UnboundedReceiver<ChannelCommand>,
+        disconnect_info: DisconnectSlot,
+        mut transcoder: Option<Transcoder>,
     ) {
         tokio::spawn(async move {
             debug!("Starting SSH session loop");
@@ -200,22 +662,40 @@ impl RusshBackend {
                     msg = read_half.wait() => {
                         match msg {
                             Some(ChannelMsg::Data { data }) => {
-                                // 直接发送 CryptoVec 的数据
-                                // data: &CryptoVec
-                                let data_vec = data.to_vec();
-                                println!("[russh] SSH→Channel: {} bytes", data_vec.len());
-                                println!("[russh] Raw bytes: {:?}", &data_vec[..data_vec.len().min(50)]);
-                                let text = String::from_utf8_lossy(&data_vec);
-                                println!("[russh] Text: {}", text);
+                                // CryptoVec 在 drop 时会清零，这里拷贝一次到引用计数的 Bytes，
+                                // 之后直到发送给前端都不再拷贝；非 UTF-8 会话在此解码
+                                let chunk = match transcoder.as_mut() {
+                                    Some(transcoder) => transcoder.decode_output(&data),
+                                    None => Bytes::copy_from_slice(&data),
+                                };
+                                if chunk.is_empty() {
+                                    continue;
+                                }
 
                                 // 发送数据到输出 channel
-                                if let Err(e) = output_sender.send(data_vec) {
+                                if let Err(e) = output_sender.send(chunk) {
                                     error!("Failed to send data to output: {}", e);
                                     break;
                                 }
                             }
-                            Some(ChannelMsg::ExitStatus { exit_status }) => {
-                                info!("Remote command exited with status: {}", exit_status);
+                            Some(ChannelMsg::ExitStatus { exit_status: status }) => {
+                                info!("Remote command exited with status: {}", status);
+                                record_disconnect(&disconnect_info, |info| info.exit_status = Some(status));
+                                break;
+                            }
+                            Some(ChannelMsg::ExitSignal { signal_name, core_dumped, error_message, .. }) => {
+                                let signal = match signal_name {
+                                    Sig::Custom(name) => name,
+                                    other => format!("{:?}", other),
+                                };
+                                info!("Remote command killed by signal: {}", signal);
+                                record_disconnect(&disconnect_info, |info| {
+                                    info.exit_signal = Some(signal);
+                                    info.core_dumped = core_dumped;
+                                    if !error_message.is_empty() {
+                                        info.reason = Some(error_message);
+                                    }
+                                });
                                 break;
                             }
                             Some(ChannelMsg::Eof) => {
@@ -224,6 +704,7 @@ impl RusshBackend {
                             }
                             Some(ChannelMsg::Close) => {
                                 debug!("Channel closed by server");
+                                record_disconnect(&disconnect_info, |_| {});
                                 break;
                             }
                             Some(ChannelMsg::WindowAdjusted { .. }) => {
@@ -235,6 +716,9 @@ impl RusshBackend {
                             }
                             None => {
                                 debug!("Channel wait returned None, connection closed");
+                                record_disconnect(&disconnect_info, |info| {
+                                    info.reason.get_or_insert_with(|| "Connection lost".to_string());
+                                });
                                 break;
                             }
                         }
@@ -243,6 +727,10 @@ impl RusshBackend {
                     cmd = command_receiver.recv() => {
                         match cmd {
                             Some(ChannelCommand::Write(data)) => {
+                                let data = match transcoder.as_mut() {
+                                    Some(transcoder) => transcoder.encode_input(&data),
+                                    None => data,
+                                };
                                 // 写入数据到 SSH channel
                                 debug!("Writing {} bytes to SSH channel", data.len());
                                 // 使用 Cursor 将 Vec<u8> 转换为 AsyncRead
@@ -262,10 +750,13 @@ impl RusshBackend {
                             }
                             Some(ChannelCommand::Disconnect) => {
                                 debug!("Disconnect command received");
+                                // 连接可能仍被其他终端共享，只关闭本终端的 channel
+                                let _ = write_half.close().await;
                                 break;
                             }
                             None => {
                                 debug!("Command channel closed");
+                                let _ = write_half.close().await;
                                 break;
                             }
                         }
@@ -276,22 +767,62 @@ impl RusshBackend {
         });
     }
 
-    /// 直接创建 SFTP 客户端
+    /// 设置远程端口转发表，需在 connect 之前调用
+    pub fn set_remote_forward_table(&mut self, table: RemoteForwardTable) {
+        self.remote_forwards = Some(table);
+    }
+
+    /// 设置安全密钥触摸提示的接收者，需在 connect 之前调用
+    pub fn set_security_key_prompt(&mut self, prompt: SecurityKeyPrompt) {
+        self.security_key_prompt = Some(prompt);
+    }
+}
+
+/// 已认证的 SSH 连接（连同途经的跳板机连接），由 [`ConnectionHub`] 在多个终端、
+/// exec、SFTP 和端口转发之间共享
+pub struct RusshTransport {
+    /// 请求远程端口转发需要独占 Handle，其他操作只需共享引用
+    handle: RwLock<Handle<RusshHandler>>,
+    /// 经由的跳板机连接（按连接顺序），随最终连接一起保持和断开
+    jump_handles: Vec<Handle<RusshHandler>>,
+    subscribers: TransportSubscribers,
+    /// 最终主机的 SSH 标识（如 `SSH-2.0-OpenSSH_9.6p1`）
+    server_id: Arc<std::sync::OnceLock<String>>,
+}
+
+impl RusshTransport {
+    /// 服务器的 SSH 标识
+    pub fn server_id(&self) -> Option<&str> {
+        self.server_id.get().map(String::as_str)
+    }
+
+    /// 连接是否已关闭（正在请求远程端口转发时视为未关闭）
     ///
-    /// 这是一个特定于 RusshBackend 的方法，用于直接创建 SFTP 客户端
-    /// 绕过了 trait object 的限制
-    pub async fn create_sftp_client_direct(&self) -> Result<crate::sftp::client::SftpClient> {
-        if !self.connected {
-            return Err(SSHError::NotConnected);
-        }
+    /// 途经的任一跳板机断开时隧道随之失效，同样视为已关闭
+    pub fn is_closed(&self) -> bool {
+        self.handle.try_read().map(|handle| handle.is_closed()).unwrap_or(false)
+            || self.jump_handles.iter().any(|handle| handle.is_closed())
+    }
 
-        let handle = self.handle.as_ref()
-            .ok_or(SSHError::NotConnected)?;
+    /// 打开新的 session channel
+    pub async fn open_session(&self) -> Result<Channel<Msg>> {
+        self.handle
+            .read()
+            .await
+            .channel_open_session()
+            .await
+            .map_err(|e| SSHError::Ssh(format!("Failed to open channel: {}", e)))
+    }
 
-        debug!("Creating SFTP client directly from russh handle");
+    /// 创建 SFTP 客户端
+    pub async fn create_sftp_client(&self) -> Result<crate::sftp::client::SftpClient> {
+        debug!("Creating SFTP client on shared connection");
 
         // 使用 SftpChannelStream 打开 channel
-        let stream = SftpChannelStream::open(handle).await?;
+        let stream = {
+            let handle = self.handle.read().await;
+            SftpChannelStream::open(&handle).await?
+        };
 
         // 直接使用 SftpChannelStream 创建 SFTP session
         let session = russh_sftp::client::SftpSession::new(stream)
@@ -302,6 +833,225 @@ impl RusshBackend {
 
         Ok(crate::sftp::client::SftpClient::from_session(session))
     }
+
+    /// 打开新的 session channel 并执行命令（不分配 PTY）
+    ///
+    /// 只负责打开 channel 和发送 exec 请求，调用方通过 [`read_exec_output`] 读取输出
+    pub async fn open_exec_channel(&self, command: &str) -> Result<Channel<Msg>> {
+        let channel = self
+            .open_session()
+            .await
+            .map_err(|e| SSHError::Ssh(format!("Failed to open exec channel: {}", e)))?;
+
+        channel
+            .exec(true, command)
+            .await
+            .map_err(|e| SSHError::Ssh(format!("Failed to exec command: {}", e)))?;
+
+        Ok(channel)
+    }
+
+    /// 打开新的 session channel，分配 PTY 后执行命令
+    ///
+    /// 用于需要终端才能交互的命令（如 `passwd`），PTY 下标准输出和标准错误合并
+    pub async fn open_pty_exec_channel(&self, command: &str) -> Result<Channel<Msg>> {
+        let channel = self
+            .open_session()
+            .await
+            .map_err(|e| SSHError::Ssh(format!("Failed to open exec channel: {}", e)))?;
+
+        channel
+            .request_pty(true, "dumb", 80, 24, 0, 0, &[])
+            .await
+            .map_err(|e| SSHError::Ssh(format!("Failed to request PTY: {}", e)))?;
+        channel
+            .exec(true, command)
+            .await
+            .map_err(|e| SSHError::Ssh(format!("Failed to exec command: {}", e)))?;
+
+        Ok(channel)
+    }
+
+    /// 打开并立即关闭一个 session channel，返回等待服务器确认的往返时间
+    pub async fn probe_latency(&self) -> Result<Duration> {
+        let started = std::time::Instant::now();
+        let channel = self
+            .open_session()
+            .await
+            .map_err(|e| SSHError::Ssh(format!("Failed to open probe channel: {}", e)))?;
+        let elapsed = started.elapsed();
+
+        let _ = channel.close().await;
+        Ok(elapsed)
+    }
+
+    /// 请求服务器监听 `address:port` 并将连接转发回来，返回实际监听的端口
+    pub async fn request_remote_forward(&self, address: &str, port: u16) -> Result<u16> {
+        let bound_port = self
+            .handle
+            .write()
+            .await
+            .tcpip_forward(address, port as u32)
+            .await
+            .map_err(|e| SSHError::Ssh(format!("Failed to request remote forward on {}:{}: {}", address, port, e)))?;
+
+        // 请求端口非 0 时服务器不返回端口号
+        Ok(if port == 0 { bound_port as u16 } else { port })
+    }
+
+    /// 取消服务器端的端口监听
+    pub async fn cancel_remote_forward(&self, address: &str, port: u16) -> Result<()> {
+        self.handle
+            .read()
+            .await
+            .cancel_tcpip_forward(address, port as u32)
+            .await
+            .map_err(|e| SSHError::Ssh(format!("Failed to cancel remote forward on {}:{}: {}", address, port, e)))
+    }
+
+    /// 打开 `direct-tcpip` channel，由服务器连接到 `host:port`
+    pub async fn open_direct_tcpip(
+        &self,
+        host: &str,
+        port: u16,
+        originator: std::net::SocketAddr,
+    ) -> Result<Channel<Msg>> {
+        self.handle
+            .read()
+            .await
+            .channel_open_direct_tcpip(
+                host,
+                port as u32,
+                originator.ip().to_string(),
+                originator.port() as u32,
+            )
+            .await
+            .map_err(|e| SSHError::Ssh(format!("Failed to open direct-tcpip channel to {}:{}: {}", host, port, e)))
+    }
+
+    /// 断开连接，从最靠近目标的跳板机开始依次断开途经的跳板机
+    pub async fn disconnect(&self) {
+        info!("Disconnecting SSH transport");
+        if let Err(e) = self
+            .handle
+            .read()
+            .await
+            .disconnect(Disconnect::ByApplication, "", "English")
+            .await
+        {
+            error!("Failed to disconnect: {}", e);
+        }
+
+        for jump_handle in self.jump_handles.iter().rev() {
+            let _ = jump_handle
+                .disconnect(Disconnect::ByApplication, "", "English")
+                .await;
+        }
+    }
+}
+
+impl RusshBackend {
+    /// 建立一个独立的连接并完成认证后立即断开，用于校验凭据（不复用共享连接）
+    pub async fn verify_login(config: &SessionConfig) -> Result<()> {
+        Self::connect_standalone(config).await?.disconnect().await;
+        Ok(())
+    }
+
+    /// 建立一个独立的已认证连接，不打开终端，也不登记到共享连接表
+    ///
+    /// 用于校验凭据和无界面的命令行模式，调用方负责断开
+    pub async fn connect_standalone(config: &SessionConfig) -> Result<RusshTransport> {
+        let mut jump_handles = Vec::new();
+        let server_id = Arc::new(std::sync::OnceLock::new());
+        let handle = Self::open_transport(
+            config,
+            &mut jump_handles,
+            TransportSubscribers::default(),
+            false,
+            server_id.clone(),
+            None,
+        )
+        .await?;
+        Ok(RusshTransport {
+            handle: RwLock::new(handle),
+            jump_handles,
+            subscribers: TransportSubscribers::default(),
+            server_id,
+        })
+    }
+
+    /// 注销本终端登记的断开原因并释放共享连接租约
+    fn release_shell(&mut self) {
+        if let Some(lease) = self.shell_lease.take() {
+            lease.transport().subscribers.unsubscribe(&self.disconnect_info);
+        }
+    }
+}
+
+impl Drop for RusshBackend {
+    fn drop(&mut self) {
+        self.release_shell();
+    }
+}
+
+/// 将算法名列表转换为 russh 类型，忽略不支持的算法；全部不支持时使用默认值
+fn preferred_list<T: Clone + 'static>(
+    prefs: &AlgorithmPreferences,
+    kind: AlgorithmKind,
+    defaults: &[T],
+    name_of: impl Fn(&T) -> String,
+    parse: impl Fn(&str) -> Option<T>,
+) -> std::borrow::Cow<'static, [T]> {
+    let default_names: Vec<String> = defaults.iter().map(name_of).collect();
+    let parsed: Vec<T> = prefs
+        .resolve(kind, &default_names)
+        .iter()
+        .filter_map(|name| {
+            let algorithm = parse(name);
+            if algorithm.is_none() {
+                tracing::warn!("Unsupported {:?} algorithm ignored: {}", kind, name);
+            }
+            algorithm
+        })
+        .collect();
+
+    if parsed.is_empty() {
+        defaults.to_vec().into()
+    } else {
+        parsed.into()
+    }
+}
+
+/// 读取 exec channel 的全部输出，直到命令退出或 channel 关闭
+pub async fn read_exec_output(channel: Channel<Msg>) -> Result<ExecOutput> {
+    read_exec_output_with(channel, |_, _| {}).await
+}
+
+/// 读取 exec channel 的输出，每收到一块数据时回调 `on_data`（用于流式显示）
+pub async fn read_exec_output_with<F>(mut channel: Channel<Msg>, mut on_data: F) -> Result<ExecOutput>
+where
+    F: FnMut(ExecStream, &[u8]),
+{
+    let mut output = ExecOutput::default();
+
+    while let Some(msg) = channel.wait().await {
+        match msg {
+            ChannelMsg::Data { data } => {
+                on_data(ExecStream::Stdout, &data);
+                output.stdout.extend_from_slice(&data);
+            }
+            // ext == 1 为 SSH_EXTENDED_DATA_STDERR
+            ChannelMsg::ExtendedData { data, ext: 1 } => {
+                on_data(ExecStream::Stderr, &data);
+                output.stderr.extend_from_slice(&data);
+            }
+            ChannelMsg::ExitStatus { exit_status } => output.exit_status = Some(exit_status),
+            ChannelMsg::Close => break,
+            _ => {}
+        }
+    }
+
+    Ok(output)
 }
 
 impl Default for RusshBackend {
@@ -330,115 +1080,45 @@ impl SSHBackend for RusshBackend {
         let (command_sender, command_receiver) = mpsc::unbounded_channel();
         self.command_sender = Some(command_sender);
 
-        // 创建 russh 配置
-        let russh_config = std::sync::Arc::new(Self::create_config(config));
-
-        // 创建 handler
-        let handler = RusshHandler;
-
-        // 建立连接
-        let mut handle = client::connect(
-            russh_config,
-            (config.host.as_str(), config.port),
-            handler,
-        )
-        .await
-        .map_err(|e| SSHError::ConnectionFailed(format!("Failed to connect: {}", e)))?;
-
-        // 根据认证方式进行认证
-        match &config.auth_method {
-            AuthMethod::Password { password } => {
-                info!("Authenticating with password for user: {}", config.username);
-                let auth_result = handle
-                    .authenticate_password(&config.username, password)
-                    .await
-                    .map_err(|e| {
-                        error!("Password authentication error for user {}: {}", config.username, e);
-                        SSHError::AuthenticationFailed(format!("密码认证错误: {}", e))
-                    })?;
-
-                if !auth_result.success() {
-                    error!("Password authentication failed for user: {}", config.username);
-                    return Err(SSHError::AuthenticationFailed(
-                        format!("密码认证失败: 用户名或密码错误 (user: {})", config.username),
-                    ));
-                }
-                info!("Password authentication successful for user: {}", config.username);
+        // 同一主机已有连接时复用，否则建立新的 SSH 连接（配置了跳板机时经由跳板机转发）
+        self.release_shell();
+        self.disconnect_info = Arc::new(std::sync::Mutex::new(None));
+        let key = ConnectionHub::key_for(config);
+        let lease = match self.hub.acquire(&key, ChannelKind::Shell) {
+            Some(lease) => {
+                info!("Reusing shared SSH connection {}", key);
+                lease
             }
-            AuthMethod::PublicKey {
-                private_key_path,
-                passphrase,
-            } => {
-                info!("Authenticating with public key for user: {}, key path: {}", config.username, private_key_path);
-                let key_pair = load_secret_key(private_key_path, passphrase.as_deref()).map_err(
-                    |e| {
-                        error!("Failed to load private key from {}: {}", private_key_path, e);
-                        SSHError::AuthenticationFailed(format!("无法加载私钥文件 '{}': {}", private_key_path, e))
-                    },
-                )?;
-
-                // 统一使用 PrivateKeyWithHashAlg 包装
-                // 参考 russh-info.md 中的最佳实践
-                let key_with_hash = if key_pair.algorithm().is_rsa() {
-                    // 获取服务器支持的 RSA 哈希算法
-                    let best_hash = handle
-                        .best_supported_rsa_hash()
-                        .await
-                        .map_err(|e| {
-                            error!("Failed to get supported RSA hash: {}", e);
-                            SSHError::AuthenticationFailed(format!(
-                                "无法获取服务器支持的 RSA 哈希算法: {}",
-                                e
-                            ))
-                        })?
-                        .flatten();
-
-                    if let Some(hash) = best_hash {
-                        info!("Using RSA key with {:?} hash", hash);
-                    } else {
-                        info!("Using RSA key");
-                    }
-                    PrivateKeyWithHashAlg::new(
-                        std::sync::Arc::new(key_pair),
-                        best_hash,
-                    )
-                } else {
-                    info!("Using {:?} key", key_pair.algorithm());
-                    PrivateKeyWithHashAlg::new(
-                        std::sync::Arc::new(key_pair),
-                        None,
-                    )
+            None => {
+                let mut jump_handles = Vec::new();
+                let subscribers = TransportSubscribers::default();
+                let server_id = Arc::new(std::sync::OnceLock::new());
+                let handle = Self::open_transport(
+                    config,
+                    &mut jump_handles,
+                    subscribers.clone(),
+                    true,
+                    server_id.clone(),
+                    self.security_key_prompt.as_ref(),
+                )
+                .await?;
+                let transport = RusshTransport {
+                    handle: RwLock::new(handle),
+                    jump_handles,
+                    subscribers,
+                    server_id,
                 };
-
-                info!("Attempting public key authentication...");
-                let auth_result = handle
-                    .authenticate_publickey(&config.username, key_with_hash)
-                    .await
-                    .map_err(|e| {
-                        error!("Public key authentication error for user {}: {}", config.username, e);
-                        SSHError::AuthenticationFailed(format!(
-                            "公钥认证错误: {}",
-                            e
-                        ))
-                    })?;
-
-                if !auth_result.success() {
-                    error!("Public key authentication failed for user: {}", config.username);
-                    error!("Possible reasons: 1) Public key not authorized on server, 2) Private key doesn't match public key, 3) Wrong user");
-                    return Err(SSHError::AuthenticationFailed(
-                        format!("公钥认证失败 (user: {})\n可能原因:\n1. 服务器上未授权此公钥（检查 ~/.ssh/authorized_keys）\n2. 私钥与公钥不匹配\n3. 用户名错误", config.username),
-                    ));
-                }
-                info!("Public key authentication successful for user: {}", config.username);
+                self.hub.register(&key, Arc::new(transport), ChannelKind::Shell)
             }
-        }
+        };
 
         // 打开 session channel
         debug!("Opening session channel");
-        let channel = handle
-            .channel_open_session()
+        let channel = lease
+            .transport()
+            .open_session()
             .await
-            .map_err(|e| SSHError::ConnectionFailed(format!("Failed to open channel: {}", e)))?;
+            .map_err(|e| SSHError::ConnectionFailed(e.to_string()))?;
 
         // 请求 PTY
         let rows = config.rows.unwrap_or(24);
@@ -465,20 +1145,44 @@ impl SSHBackend for RusshBackend {
             .await
             .map_err(|e| SSHError::ConnectionFailed(format!("Failed to request PTY: {}", e)))?;
 
-        // 启动 shell
-        debug!("Requesting shell");
-        channel
-            .request_shell(true)
-            .await
-            .map_err(|e| SSHError::ConnectionFailed(format!("Failed to request shell: {}", e)))?;
+        // 启动远程命令或登录 shell
+        match config.remote_command.as_deref().map(str::trim).filter(|c| !c.is_empty()) {
+            Some(command) => {
+                debug!("Executing remote command: {}", command);
+                channel
+                    .exec(true, command)
+                    .await
+                    .map_err(|e| SSHError::ConnectionFailed(format!("Failed to execute remote command: {}", e)))?;
+            }
+            None => {
+                debug!("Requesting shell");
+                channel
+                    .request_shell(true)
+                    .await
+                    .map_err(|e| SSHError::ConnectionFailed(format!("Failed to request shell: {}", e)))?;
+            }
+        }
 
         // 分割 channel 为读写两半
         let (read_half, write_half) = channel.split();
 
+        // 登记断开原因和远程端口转发表，共享连接断开或服务器转发回连接时使用
+        lease
+            .transport()
+            .subscribers
+            .subscribe(self.disconnect_info.clone(), self.remote_forwards.clone());
+
         // 启动会话管理循环
-        Self::start_session_loop(read_half, write_half, output_sender, command_receiver);
+        Self::start_session_loop(
+            read_half,
+            write_half,
+            output_sender,
+            command_receiver,
+            self.disconnect_info.clone(),
+            config.encoding.transcoder(),
+        );
 
-        self.handle = Some(handle);
+        self.shell_lease = Some(lease);
         self.connected = true;
         info!("SSH connection established successfully");
         Ok(())
@@ -519,16 +1223,9 @@ impl SSHBackend for RusshBackend {
             let _ = sender.send(ChannelCommand::Disconnect);
         }
 
-        if let Some(handle) = self.handle.take() {
-            info!("Disconnecting SSH session");
-            handle
-                .disconnect(Disconnect::ByApplication, "", "English")
-                .await
-                .map_err(|e| {
-                    error!("Failed to disconnect: {}", e);
-                    SSHError::IoError(io::Error::new(io::ErrorKind::Other, e.to_string()))
-                })?;
-        }
+        // 关闭本终端的 channel；没有其他终端共享时由连接中心断开连接
+        info!("Disconnecting SSH session");
+        self.release_shell();
 
         self.connected = false;
         info!("SSH session disconnected");
@@ -538,9 +1235,9 @@ impl SSHBackend for RusshBackend {
     fn reader(&mut self) -> Result<Box<dyn BackendReader + Send>> {
         if let Some(receiver) = self.receiver.take() {
             let reader = RusshReader {
-                receiver,
-                buffer: None,
-                position: 0,
+                receiver: Some(receiver),
+                buffer: Bytes::new(),
+                disconnect_info: self.disconnect_info.clone(),
             };
             Ok(Box::new(reader))
         } else {
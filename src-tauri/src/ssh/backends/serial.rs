@@ -0,0 +1,214 @@
+// 串口后端 - 通过串口（COM 口 / /dev/ttyUSB）连接网络设备的 Console
+//
+// 会话的 host 为设备路径，线路参数来自 SessionConfig::serial
+
+use crate::error::{Result, SSHError};
+use crate::ssh::backend::{BackendReader, DisconnectInfo, SSHBackend};
+use crate::ssh::serial::{SerialConfig, SerialFlowControl, SerialParity};
+use crate::ssh::session::SessionConfig;
+use async_trait::async_trait;
+use bytes::{Buf, Bytes};
+use serialport::{DataBits, FlowControl, Parity, SerialPort, StopBits};
+use std::io::{self, Read, Write};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::io::{AsyncRead, ReadBuf};
+use tokio::sync::mpsc;
+
+/// 读取线程的缓冲区大小
+const READ_BUFFER_SIZE: usize = 4096;
+
+/// 读取超时，超时后检查是否已断开
+const READ_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// 串口后端
+pub struct SerialBackend {
+    writer: Option<Box<dyn SerialPort>>,
+    receiver: Option<mpsc::UnboundedReceiver<Bytes>>,
+    closed: Arc<AtomicBool>,
+    disconnect_info: Arc<Mutex<Option<DisconnectInfo>>>,
+}
+
+impl SerialBackend {
+    pub fn new() -> Self {
+        Self {
+            writer: None,
+            receiver: None,
+            closed: Arc::new(AtomicBool::new(false)),
+            disconnect_info: Arc::new(Mutex::new(None)),
+        }
+    }
+}
+
+impl Default for SerialBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn open_port(path: &str, config: &SerialConfig) -> Result<Box<dyn SerialPort>> {
+    config.validate().map_err(SSHError::ConnectionFailed)?;
+
+    let data_bits = match config.data_bits {
+        5 => DataBits::Five,
+        6 => DataBits::Six,
+        7 => DataBits::Seven,
+        _ => DataBits::Eight,
+    };
+    let parity = match config.parity {
+        SerialParity::None => Parity::None,
+        SerialParity::Odd => Parity::Odd,
+        SerialParity::Even => Parity::Even,
+    };
+    let stop_bits = if config.stop_bits == 2 { StopBits::Two } else { StopBits::One };
+    let flow_control = match config.flow_control {
+        SerialFlowControl::None => FlowControl::None,
+        SerialFlowControl::Software => FlowControl::Software,
+        SerialFlowControl::Hardware => FlowControl::Hardware,
+    };
+
+    serialport::new(path, config.baud_rate)
+        .data_bits(data_bits)
+        .parity(parity)
+        .stop_bits(stop_bits)
+        .flow_control(flow_control)
+        .timeout(READ_TIMEOUT)
+        .open()
+        .map_err(|e| SSHError::ConnectionFailed(format!("Failed to open serial port {}: {}", path, e)))
+}
+
+#[async_trait]
+impl SSHBackend for SerialBackend {
+    async fn connect(&mut self, config: &SessionConfig) -> Result<()> {
+        let path = config.host.trim();
+        if path.is_empty() {
+            return Err(SSHError::ConnectionFailed("Serial port is not specified".to_string()));
+        }
+        let serial = config.serial.clone().unwrap_or_default();
+        let port = open_port(path, &serial)?;
+        let mut reader = port
+            .try_clone()
+            .map_err(|e| SSHError::ConnectionFailed(format!("Failed to read serial port {}: {}", path, e)))?;
+
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let closed = self.closed.clone();
+        let disconnect_info = self.disconnect_info.clone();
+
+        // 串口只提供阻塞读取，使用独立线程读取并转发到 channel
+        std::thread::spawn(move || {
+            let mut buffer = [0u8; READ_BUFFER_SIZE];
+            while !closed.load(Ordering::Relaxed) {
+                match reader.read(&mut buffer) {
+                    Ok(0) => continue,
+                    Ok(n) => {
+                        if sender.send(Bytes::copy_from_slice(&buffer[..n])).is_err() {
+                            break;
+                        }
+                    }
+                    Err(e) if e.kind() == io::ErrorKind::TimedOut || e.kind() == io::ErrorKind::Interrupted => continue,
+                    Err(e) => {
+                        // 设备被拔出等错误，记录原因后关闭 channel
+                        if let Ok(mut info) = disconnect_info.lock() {
+                            *info = Some(DisconnectInfo {
+                                reason: Some(format!("Serial port error: {}", e)),
+                                ..Default::default()
+                            });
+                        }
+                        break;
+                    }
+                }
+            }
+        });
+
+        self.writer = Some(port);
+        self.receiver = Some(receiver);
+        Ok(())
+    }
+
+    async fn write(&mut self, data: &[u8]) -> Result<()> {
+        let writer = self.writer.as_mut().ok_or(SSHError::NotConnected)?;
+        writer
+            .write_all(data)
+            .and_then(|_| writer.flush())
+            .map_err(|e| SSHError::Io(format!("Failed to write serial port: {}", e)))
+    }
+
+    async fn resize(&mut self, _rows: u16, _cols: u16) -> Result<()> {
+        // 串口没有窗口大小的概念
+        Ok(())
+    }
+
+    async fn disconnect(&mut self) -> Result<()> {
+        self.closed.store(true, Ordering::Relaxed);
+        self.writer = None;
+        Ok(())
+    }
+
+    fn reader(&mut self) -> Result<Box<dyn BackendReader + Send>> {
+        let receiver = self.receiver.take().ok_or(SSHError::NotConnected)?;
+
+        Ok(Box::new(SerialReader {
+            receiver: Some(receiver),
+            buffer: Bytes::new(),
+            disconnect_info: self.disconnect_info.clone(),
+        }))
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// 串口后端的读取器
+pub struct SerialReader {
+    receiver: Option<mpsc::UnboundedReceiver<Bytes>>,
+    buffer: Bytes,
+    disconnect_info: Arc<Mutex<Option<DisconnectInfo>>>,
+}
+
+impl BackendReader for SerialReader {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn take_chunk_receiver(&mut self) -> Option<mpsc::UnboundedReceiver<Bytes>> {
+        if self.buffer.is_empty() {
+            self.receiver.take()
+        } else {
+            None
+        }
+    }
+
+    fn disconnect_info(&self) -> Option<DisconnectInfo> {
+        self.disconnect_info.lock().ok()?.clone()
+    }
+}
+
+impl AsyncRead for SerialReader {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = &mut *self;
+
+        if this.buffer.is_empty() {
+            let Some(receiver) = this.receiver.as_mut() else {
+                return Poll::Ready(Ok(()));
+            };
+            match receiver.poll_recv(cx) {
+                Poll::Ready(Some(data)) => this.buffer = data,
+                Poll::Ready(None) => return Poll::Ready(Ok(())),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        let to_copy = std::cmp::min(this.buffer.len(), buf.remaining());
+        buf.put_slice(&this.buffer[..to_copy]);
+        this.buffer.advance(to_copy);
+        Poll::Ready(Ok(()))
+    }
+}
@@ -0,0 +1,466 @@
+// Telnet 后端 - 用于不支持 SSH 的旧设备
+//
+// 只实现基本的选项协商：ECHO、SGA（抑制 Go-Ahead）、NAWS（窗口大小）和 TTYPE（终端类型），
+// 其余选项一律拒绝。协商数据由读取任务处理，终端只收到普通数据
+
+use crate::error::{Result, SSHError};
+use crate::ssh::backend::{BackendReader, DisconnectInfo, SSHBackend};
+use crate::ssh::session::SessionConfig;
+use async_trait::async_trait;
+use bytes::{Buf, Bytes};
+use std::io;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt, ReadBuf};
+use tokio::net::tcp::OwnedWriteHalf;
+use tokio::net::TcpStream;
+use tokio::sync::mpsc;
+
+/// 连接超时
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// 读取缓冲区大小
+const READ_BUFFER_SIZE: usize = 8192;
+
+// Telnet 命令（RFC 854）
+const IAC: u8 = 255;
+const DONT: u8 = 254;
+const DO: u8 = 253;
+const WONT: u8 = 252;
+const WILL: u8 = 251;
+const SB: u8 = 250;
+const SE: u8 = 240;
+
+// Telnet 选项
+const OPT_ECHO: u8 = 1;
+const OPT_SGA: u8 = 3;
+const OPT_TTYPE: u8 = 24;
+const OPT_NAWS: u8 = 31;
+
+// TTYPE 子协商（RFC 1091）
+const TTYPE_IS: u8 = 0;
+const TTYPE_SEND: u8 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ParseState {
+    Data,
+    /// 上一个字节是 CR
+    Cr,
+    Iac,
+    /// IAC WILL/WONT/DO/DONT，等待选项字节
+    Negotiate(u8),
+    Subnegotiation,
+    SubnegotiationIac,
+}
+
+/// Telnet 协议状态：解析服务器数据并生成协商应答
+#[derive(Debug)]
+pub struct TelnetProtocol {
+    state: ParseState,
+    subnegotiation: Vec<u8>,
+    terminal_type: String,
+    cols: u16,
+    rows: u16,
+    /// 服务器已请求窗口大小（DO NAWS）
+    naws: bool,
+    /// 本端已同意的选项（WILL），用于避免重复应答造成协商循环
+    local_enabled: Vec<u8>,
+    /// 远端已同意的选项（DO）
+    remote_enabled: Vec<u8>,
+}
+
+impl TelnetProtocol {
+    pub fn new(terminal_type: &str, cols: u16, rows: u16) -> Self {
+        Self {
+            state: ParseState::Data,
+            subnegotiation: Vec::new(),
+            terminal_type: terminal_type.to_uppercase(),
+            cols,
+            rows,
+            naws: false,
+            local_enabled: Vec::new(),
+            remote_enabled: Vec::new(),
+        }
+    }
+
+    /// 处理服务器发来的字节，返回 (终端数据, 需要发回服务器的协商应答)
+    pub fn receive(&mut self, input: &[u8]) -> (Vec<u8>, Vec<u8>) {
+        let mut data = Vec::with_capacity(input.len());
+        let mut reply = Vec::new();
+
+        for &byte in input {
+            self.state = match self.state {
+                ParseState::Data | ParseState::Cr => match byte {
+                    IAC => ParseState::Iac,
+                    // NVT 中 CR NUL 表示单独的回车
+                    0 if self.state == ParseState::Cr => ParseState::Data,
+                    b'\r' => {
+                        data.push(byte);
+                        ParseState::Cr
+                    }
+                    _ => {
+                        data.push(byte);
+                        ParseState::Data
+                    }
+                },
+                ParseState::Iac => match byte {
+                    IAC => {
+                        data.push(IAC);
+                        ParseState::Data
+                    }
+                    WILL | WONT | DO | DONT => ParseState::Negotiate(byte),
+                    SB => {
+                        self.subnegotiation.clear();
+                        ParseState::Subnegotiation
+                    }
+                    // NOP、GA、AYT 等命令不影响终端
+                    _ => ParseState::Data,
+                },
+                ParseState::Negotiate(command) => {
+                    self.negotiate(command, byte, &mut reply);
+                    ParseState::Data
+                }
+                ParseState::Subnegotiation => match byte {
+                    IAC => ParseState::SubnegotiationIac,
+                    _ => {
+                        self.subnegotiation.push(byte);
+                        ParseState::Subnegotiation
+                    }
+                },
+                ParseState::SubnegotiationIac => match byte {
+                    SE => {
+                        self.subnegotiate(&mut reply);
+                        ParseState::Data
+                    }
+                    IAC => {
+                        self.subnegotiation.push(IAC);
+                        ParseState::Subnegotiation
+                    }
+                    _ => ParseState::Subnegotiation,
+                },
+            };
+        }
+
+        (data, reply)
+    }
+
+    fn negotiate(&mut self, command: u8, option: u8, reply: &mut Vec<u8>) {
+        match command {
+            // 服务器提出由它回显 / 抑制 Go-Ahead：同意，其余拒绝
+            WILL => {
+                let accept = matches!(option, OPT_ECHO | OPT_SGA);
+                if accept && !self.remote_enabled.contains(&option) {
+                    self.remote_enabled.push(option);
+                    reply.extend_from_slice(&[IAC, DO, option]);
+                } else if !accept {
+                    reply.extend_from_slice(&[IAC, DONT, option]);
+                }
+            }
+            WONT => {
+                if let Some(pos) = self.remote_enabled.iter().position(|&o| o == option) {
+                    self.remote_enabled.remove(pos);
+                    reply.extend_from_slice(&[IAC, DONT, option]);
+                }
+            }
+            // 服务器请求本端启用选项
+            DO => {
+                let accept = matches!(option, OPT_SGA | OPT_TTYPE | OPT_NAWS);
+                if accept && !self.local_enabled.contains(&option) {
+                    self.local_enabled.push(option);
+                    reply.extend_from_slice(&[IAC, WILL, option]);
+                } else if !accept {
+                    reply.extend_from_slice(&[IAC, WONT, option]);
+                }
+                if option == OPT_NAWS {
+                    self.naws = true;
+                    reply.extend_from_slice(&self.window_size());
+                }
+            }
+            DONT => {
+                if let Some(pos) = self.local_enabled.iter().position(|&o| o == option) {
+                    self.local_enabled.remove(pos);
+                    reply.extend_from_slice(&[IAC, WONT, option]);
+                }
+                if option == OPT_NAWS {
+                    self.naws = false;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn subnegotiate(&mut self, reply: &mut Vec<u8>) {
+        if self.subnegotiation.as_slice() == [OPT_TTYPE, TTYPE_SEND] {
+            reply.extend_from_slice(&[IAC, SB, OPT_TTYPE, TTYPE_IS]);
+            reply.extend_from_slice(self.terminal_type.as_bytes());
+            reply.extend_from_slice(&[IAC, SE]);
+        }
+    }
+
+    /// 更新窗口大小，服务器启用了 NAWS 时返回需要发送的子协商
+    pub fn resize(&mut self, cols: u16, rows: u16) -> Option<Vec<u8>> {
+        self.cols = cols;
+        self.rows = rows;
+        self.naws.then(|| self.window_size())
+    }
+
+    /// NAWS 子协商（RFC 1073），数值中的 0xFF 需要转义
+    fn window_size(&self) -> Vec<u8> {
+        let mut message = vec![IAC, SB, OPT_NAWS];
+        for byte in self.cols.to_be_bytes().into_iter().chain(self.rows.to_be_bytes()) {
+            message.push(byte);
+            if byte == IAC {
+                message.push(IAC);
+            }
+        }
+        message.extend_from_slice(&[IAC, SE]);
+        message
+    }
+}
+
+/// 编码用户输入：转义 IAC，单独的 CR 按 NVT 规则发送为 CR NUL
+pub fn encode_input(data: &[u8]) -> Vec<u8> {
+    let mut output = Vec::with_capacity(data.len() + 2);
+    for (i, &byte) in data.iter().enumerate() {
+        match byte {
+            IAC => output.extend_from_slice(&[IAC, IAC]),
+            b'\r' if data.get(i + 1) != Some(&b'\n') => output.extend_from_slice(&[b'\r', 0]),
+            _ => output.push(byte),
+        }
+    }
+    output
+}
+
+/// Telnet 后端
+pub struct TelnetBackend {
+    writer: Option<Arc<tokio::sync::Mutex<OwnedWriteHalf>>>,
+    protocol: Arc<Mutex<TelnetProtocol>>,
+    receiver: Option<mpsc::UnboundedReceiver<Bytes>>,
+    reader_task: Option<tokio::task::JoinHandle<()>>,
+    disconnect_info: Arc<Mutex<Option<DisconnectInfo>>>,
+}
+
+impl TelnetBackend {
+    pub fn new() -> Self {
+        Self {
+            writer: None,
+            protocol: Arc::new(Mutex::new(TelnetProtocol::new("xterm-256color", 80, 24))),
+            receiver: None,
+            reader_task: None,
+            disconnect_info: Arc::new(Mutex::new(None)),
+        }
+    }
+}
+
+impl Default for TelnetBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SSHBackend for TelnetBackend {
+    async fn connect(&mut self, config: &SessionConfig) -> Result<()> {
+        let address = format!("{}:{}", config.host, config.port);
+        let stream = tokio::time::timeout(CONNECT_TIMEOUT, TcpStream::connect(&address))
+            .await
+            .map_err(|_| SSHError::ConnectionFailed(format!("Connecting to {} timed out", address)))?
+            .map_err(|e| SSHError::ConnectionFailed(format!("Failed to connect to {}: {}", address, e)))?;
+        let _ = stream.set_nodelay(true);
+
+        *self.protocol.lock().map_err(|_| SSHError::Io("Telnet state poisoned".to_string()))? = TelnetProtocol::new(
+            config.terminal_type.as_deref().unwrap_or("xterm-256color"),
+            config.columns.unwrap_or(80),
+            config.rows.unwrap_or(24),
+        );
+
+        let (mut read_half, write_half) = stream.into_split();
+        let writer = Arc::new(tokio::sync::Mutex::new(write_half));
+        let (sender, receiver) = mpsc::unbounded_channel();
+
+        let protocol = self.protocol.clone();
+        let reply_writer = writer.clone();
+        let disconnect_info = self.disconnect_info.clone();
+        let reader_task = tokio::spawn(async move {
+            let mut buffer = vec![0u8; READ_BUFFER_SIZE];
+            loop {
+                let n = match read_half.read(&mut buffer).await {
+                    Ok(0) => break,
+                    Ok(n) => n,
+                    Err(e) => {
+                        if let Ok(mut info) = disconnect_info.lock() {
+                            *info = Some(DisconnectInfo {
+                                reason: Some(e.to_string()),
+                                ..Default::default()
+                            });
+                        }
+                        break;
+                    }
+                };
+
+                let (data, reply) = match protocol.lock() {
+                    Ok(mut protocol) => protocol.receive(&buffer[..n]),
+                    Err(_) => break,
+                };
+                if !reply.is_empty() && reply_writer.lock().await.write_all(&reply).await.is_err() {
+                    break;
+                }
+                if !data.is_empty() && sender.send(Bytes::from(data)).is_err() {
+                    break;
+                }
+            }
+        });
+
+        self.writer = Some(writer);
+        self.receiver = Some(receiver);
+        self.reader_task = Some(reader_task);
+        Ok(())
+    }
+
+    async fn write(&mut self, data: &[u8]) -> Result<()> {
+        let writer = self.writer.as_ref().ok_or(SSHError::NotConnected)?;
+        writer
+            .lock()
+            .await
+            .write_all(&encode_input(data))
+            .await
+            .map_err(|e| SSHError::Io(format!("Failed to write to telnet connection: {}", e)))
+    }
+
+    async fn resize(&mut self, rows: u16, cols: u16) -> Result<()> {
+        let message = self
+            .protocol
+            .lock()
+            .map_err(|_| SSHError::Io("Telnet state poisoned".to_string()))?
+            .resize(cols, rows);
+        if let (Some(message), Some(writer)) = (message, self.writer.as_ref()) {
+            writer
+                .lock()
+                .await
+                .write_all(&message)
+                .await
+                .map_err(|e| SSHError::Io(format!("Failed to send window size: {}", e)))?;
+        }
+        Ok(())
+    }
+
+    async fn disconnect(&mut self) -> Result<()> {
+        if let Some(writer) = self.writer.take() {
+            let _ = writer.lock().await.shutdown().await;
+        }
+        if let Some(task) = self.reader_task.take() {
+            task.abort();
+        }
+        Ok(())
+    }
+
+    fn reader(&mut self) -> Result<Box<dyn BackendReader + Send>> {
+        let receiver = self.receiver.take().ok_or(SSHError::NotConnected)?;
+
+        Ok(Box::new(TelnetReader {
+            receiver: Some(receiver),
+            buffer: Bytes::new(),
+            disconnect_info: self.disconnect_info.clone(),
+        }))
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// Telnet 后端的读取器
+pub struct TelnetReader {
+    receiver: Option<mpsc::UnboundedReceiver<Bytes>>,
+    buffer: Bytes,
+    disconnect_info: Arc<Mutex<Option<DisconnectInfo>>>,
+}
+
+impl BackendReader for TelnetReader {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn take_chunk_receiver(&mut self) -> Option<mpsc::UnboundedReceiver<Bytes>> {
+        if self.buffer.is_empty() {
+            self.receiver.take()
+        } else {
+            None
+        }
+    }
+
+    fn disconnect_info(&self) -> Option<DisconnectInfo> {
+        self.disconnect_info.lock().ok()?.clone()
+    }
+}
+
+impl AsyncRead for TelnetReader {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = &mut *self;
+
+        if this.buffer.is_empty() {
+            let Some(receiver) = this.receiver.as_mut() else {
+                return Poll::Ready(Ok(()));
+            };
+            match receiver.poll_recv(cx) {
+                Poll::Ready(Some(data)) => this.buffer = data,
+                Poll::Ready(None) => return Poll::Ready(Ok(())),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        let to_copy = std::cmp::min(this.buffer.len(), buf.remaining());
+        buf.put_slice(&this.buffer[..to_copy]);
+        this.buffer.advance(to_copy);
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_telnet_negotiation() {
+        let mut protocol = TelnetProtocol::new("xterm-256color", 80, 24);
+
+        // 协商与数据混合：WILL ECHO、DO NAWS、DO LINEMODE(34)、转义的 0xFF、CR NUL
+        let (data, reply) = protocol.receive(&[
+            IAC, WILL, OPT_ECHO, b'o', b'k', IAC, DO, OPT_NAWS, IAC, DO, 34, IAC, IAC, b'\r', 0, b'\r', b'\n',
+        ]);
+        assert_eq!(data, vec![b'o', b'k', IAC, b'\r', b'\r', b'\n']);
+        assert_eq!(
+            reply,
+            vec![
+                IAC, DO, OPT_ECHO,
+                IAC, WILL, OPT_NAWS, IAC, SB, OPT_NAWS, 0, 80, 0, 24, IAC, SE,
+                IAC, WONT, 34,
+            ]
+        );
+
+        // 重复请求不再应答，避免协商循环
+        assert!(protocol.receive(&[IAC, WILL, OPT_ECHO]).1.is_empty());
+
+        // 终端类型子协商
+        let (_, reply) = protocol.receive(&[IAC, DO, OPT_TTYPE, IAC, SB, OPT_TTYPE, TTYPE_SEND, IAC, SE]);
+        let mut expected = vec![IAC, WILL, OPT_TTYPE, IAC, SB, OPT_TTYPE, TTYPE_IS];
+        expected.extend_from_slice(b"XTERM-256COLOR");
+        expected.extend_from_slice(&[IAC, SE]);
+        assert_eq!(reply, expected);
+
+        // 窗口大小中的 0xFF 需要转义
+        assert_eq!(
+            protocol.resize(255, 50),
+            Some(vec![IAC, SB, OPT_NAWS, 0, IAC, IAC, 0, 50, IAC, SE])
+        );
+
+        assert_eq!(encode_input(&[b'a', IAC, b'\r']), vec![b'a', IAC, IAC, b'\r', 0]);
+        assert_eq!(encode_input(b"ls\r\n"), b"ls\r\n".to_vec());
+    }
+}
@@ -0,0 +1,285 @@
+//! 终端性能基准测试
+//!
+//! 使用回环后端测量读取/发送路径的性能，排除网络和服务器的影响：
+//! - 输出吞吐量：后端分块输出经过发送路径的速度（MB/s）
+//! - 输入延迟：写入后端到回显数据发送出去的往返时间
+//! - 事件管道开销：发送事件相比只读取分块的额外耗时
+
+use crate::error::{Result, SSHError};
+use crate::ssh::backend::SSHBackend;
+use crate::ssh::backends::loopback::LoopbackBackend;
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+/// 单次测试的最大输出量
+const MAX_TOTAL_BYTES: usize = 256 * 1024 * 1024;
+
+/// 等待回显的超时时间
+const ECHO_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 基准测试参数
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct BenchmarkOptions {
+    /// 吞吐量测试的总输出量（字节）
+    pub total_bytes: usize,
+    /// 每个输出分块的大小（字节）
+    pub chunk_size: usize,
+    /// 输入延迟采样次数
+    pub latency_samples: usize,
+}
+
+impl Default for BenchmarkOptions {
+    fn default() -> Self {
+        Self {
+            total_bytes: 32 * 1024 * 1024,
+            chunk_size: 32 * 1024,
+            latency_samples: 200,
+        }
+    }
+}
+
+impl BenchmarkOptions {
+    /// 限制参数范围，避免测试时间过长
+    fn normalized(&self) -> Self {
+        let chunk_size = self.chunk_size.clamp(64, 1024 * 1024);
+        Self {
+            total_bytes: self.total_bytes.clamp(chunk_size, MAX_TOTAL_BYTES),
+            chunk_size,
+            latency_samples: self.latency_samples.clamp(1, 10_000),
+        }
+    }
+}
+
+/// 吞吐量结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThroughputResult {
+    pub bytes: u64,
+    pub chunks: u64,
+    pub duration_ms: f64,
+    pub mb_per_sec: f64,
+}
+
+/// 延迟统计（微秒）
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LatencyStats {
+    pub samples: usize,
+    pub min_us: f64,
+    pub mean_us: f64,
+    pub p50_us: f64,
+    pub p95_us: f64,
+    pub p99_us: f64,
+    pub max_us: f64,
+}
+
+/// 事件管道开销
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PipelineOverhead {
+    /// 只读取分块（不发送）的耗时
+    pub read_only_ms: f64,
+    /// 读取并发送的耗时
+    pub read_and_emit_ms: f64,
+    /// 平均每个分块的发送开销
+    pub per_chunk_us: f64,
+}
+
+/// 基准测试报告
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BenchmarkReport {
+    pub app_version: String,
+    pub chunk_size: usize,
+    pub throughput: ThroughputResult,
+    pub input_latency: LatencyStats,
+    pub event_pipeline: PipelineOverhead,
+    pub created_at: i64,
+}
+
+/// 运行基准测试
+///
+/// `emit` 为被测的发送路径（如发送 Tauri 事件），返回 false 表示发送失败
+pub async fn run_benchmark<F>(options: &BenchmarkOptions, emit: F) -> Result<BenchmarkReport>
+where
+    F: Fn(&[u8]) -> bool,
+{
+    let options = options.normalized();
+    let payload = build_payload(options.chunk_size);
+    let chunks = (options.total_bytes / options.chunk_size) as u64;
+
+    // 基线：只读取分块
+    let read_only = measure_output(&payload, chunks, |_| true).await?;
+    // 读取并发送
+    let read_and_emit = measure_output(&payload, chunks, &emit).await?;
+    let input_latency = measure_input_latency(options.latency_samples, &emit).await?;
+
+    let bytes = chunks * payload.len() as u64;
+    let emit_secs = read_and_emit.as_secs_f64();
+    let overhead = read_and_emit.saturating_sub(read_only);
+
+    Ok(BenchmarkReport {
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        chunk_size: options.chunk_size,
+        throughput: ThroughputResult {
+            bytes,
+            chunks,
+            duration_ms: emit_secs * 1000.0,
+            mb_per_sec: if emit_secs > 0.0 { bytes as f64 / (1024.0 * 1024.0) / emit_secs } else { 0.0 },
+        },
+        input_latency,
+        event_pipeline: PipelineOverhead {
+            read_only_ms: read_only.as_secs_f64() * 1000.0,
+            read_and_emit_ms: emit_secs * 1000.0,
+            per_chunk_us: overhead.as_secs_f64() * 1_000_000.0 / chunks.max(1) as f64,
+        },
+        created_at: chrono::Utc::now().timestamp_millis(),
+    })
+}
+
+/// 测量输出路径：后台任务注入分块，读取端逐块处理
+async fn measure_output<F>(payload: &Bytes, chunks: u64, emit: F) -> Result<Duration>
+where
+    F: Fn(&[u8]) -> bool,
+{
+    let mut backend = LoopbackBackend::new();
+    let sender = backend.output_sender().ok_or(SSHError::NotConnected)?;
+    let mut receiver = take_chunk_receiver(&mut backend)?;
+
+    let started = Instant::now();
+
+    let producer_payload = payload.clone();
+    tokio::spawn(async move {
+        for _ in 0..chunks {
+            if sender.send(producer_payload.clone()).is_err() {
+                break;
+            }
+        }
+    });
+
+    for _ in 0..chunks {
+        let chunk = receiver
+            .recv()
+            .await
+            .ok_or_else(|| SSHError::Io("Loopback output closed".to_string()))?;
+        if !emit(&chunk) {
+            return Err(SSHError::Io("Failed to emit benchmark output".to_string()));
+        }
+    }
+
+    Ok(started.elapsed())
+}
+
+/// 测量输入延迟：写入一个字节，等待回显并发送
+async fn measure_input_latency<F>(samples: usize, emit: F) -> Result<LatencyStats>
+where
+    F: Fn(&[u8]) -> bool,
+{
+    // 与真实连接一样，后端放在互斥锁中，写入时加锁
+    let mut backend = LoopbackBackend::new();
+    let mut receiver = take_chunk_receiver(&mut backend)?;
+    let backend = tokio::sync::Mutex::new(backend);
+
+    let mut durations = Vec::with_capacity(samples);
+    for i in 0..samples {
+        let key = [b'a' + (i % 26) as u8];
+        let started = Instant::now();
+
+        backend.lock().await.write(&key).await?;
+        let echo = tokio::time::timeout(ECHO_TIMEOUT, receiver.recv())
+            .await
+            .map_err(|_| SSHError::Io("Loopback echo timed out".to_string()))?
+            .ok_or_else(|| SSHError::Io("Loopback output closed".to_string()))?;
+        if !emit(&echo) {
+            return Err(SSHError::Io("Failed to emit benchmark output".to_string()));
+        }
+
+        durations.push(started.elapsed());
+    }
+
+    Ok(latency_stats(durations))
+}
+
+fn take_chunk_receiver(backend: &mut LoopbackBackend) -> Result<mpsc::UnboundedReceiver<Bytes>> {
+    backend
+        .reader()?
+        .take_chunk_receiver()
+        .ok_or_else(|| SSHError::NotSupported("Loopback chunk receiver unavailable".to_string()))
+}
+
+/// 生成接近真实终端输出的测试数据（带颜色的 `ls -l` 风格行）
+fn build_payload(chunk_size: usize) -> Bytes {
+    let mut payload = Vec::with_capacity(chunk_size + 128);
+    let mut line_no = 0usize;
+    while payload.len() < chunk_size {
+        let line = format!(
+            "-rw-r--r--  1 user staff {:>8} Jan  1 12:00 \x1b[01;32mfile_{:06}.log\x1b[0m\r\n",
+            line_no * 37 % 100_000,
+            line_no
+        );
+        payload.extend_from_slice(line.as_bytes());
+        line_no += 1;
+    }
+    payload.truncate(chunk_size);
+    Bytes::from(payload)
+}
+
+/// 计算延迟统计
+fn latency_stats(mut durations: Vec<Duration>) -> LatencyStats {
+    if durations.is_empty() {
+        return LatencyStats { samples: 0, min_us: 0.0, mean_us: 0.0, p50_us: 0.0, p95_us: 0.0, p99_us: 0.0, max_us: 0.0 };
+    }
+
+    durations.sort();
+    let to_us = |d: &Duration| d.as_secs_f64() * 1_000_000.0;
+    let percentile = |p: f64| {
+        let index = ((durations.len() - 1) as f64 * p).round() as usize;
+        to_us(&durations[index])
+    };
+
+    LatencyStats {
+        samples: durations.len(),
+        min_us: to_us(&durations[0]),
+        mean_us: durations.iter().map(to_us).sum::<f64>() / durations.len() as f64,
+        p50_us: percentile(0.50),
+        p95_us: percentile(0.95),
+        p99_us: percentile(0.99),
+        max_us: to_us(&durations[durations.len() - 1]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latency_stats() {
+        let durations = (1..=100).map(Duration::from_micros).collect();
+        let stats = latency_stats(durations);
+
+        assert_eq!(stats.samples, 100);
+        assert_eq!(stats.min_us, 1.0);
+        assert_eq!(stats.max_us, 100.0);
+        assert_eq!(stats.p50_us, 51.0);
+        assert_eq!(stats.p95_us, 95.0);
+        assert!((stats.mean_us - 50.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_build_payload_size() {
+        assert_eq!(build_payload(4096).len(), 4096);
+    }
+
+    #[tokio::test]
+    async fn test_run_benchmark_loopback() {
+        let options = BenchmarkOptions { total_bytes: 64 * 1024, chunk_size: 4096, latency_samples: 10 };
+        let report = run_benchmark(&options, |_| true).await.unwrap();
+
+        assert_eq!(report.throughput.chunks, 16);
+        assert_eq!(report.throughput.bytes, 64 * 1024);
+        assert_eq!(report.input_latency.samples, 10);
+    }
+}
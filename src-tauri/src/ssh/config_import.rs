@@ -0,0 +1,410 @@
+//! OpenSSH 客户端配置（~/.ssh/config）解析与导入
+//!
+//! 支持 `Host`、`HostName`、`Port`、`User`、`IdentityFile`、`ProxyJump` 和 `Include`，
+//! 其余指令忽略。与 OpenSSH 相同，同一选项以第一个匹配的值为准，因此末尾的
+//! `Host *` 可以为所有主机提供默认值。
+//!
+//! 分组按配置文件的分节保留：`Include` 引入的文件以文件名作为分组，
+//! 文件内的 `# Group: 名称` 注释开始一个新的分组。
+
+use crate::ssh::quick_connect::{parse_target, DEFAULT_SSH_PORT};
+use crate::ssh::session::{AuthMethod, SessionConfig};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// Include 最大嵌套深度（防止循环引用）
+const MAX_INCLUDE_DEPTH: usize = 8;
+
+/// 分组注释前缀（不区分大小写）
+const GROUP_COMMENT_PREFIX: &str = "group:";
+
+/// 未指定分组的主机使用的分组
+const DEFAULT_GROUP: &str = "默认分组";
+
+/// 配置文件中的一个 Host 条目（已合并通配符默认值）
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SshConfigHost {
+    /// Host 别名
+    pub alias: String,
+    /// 实际主机名，未配置 HostName 时为别名
+    pub host_name: String,
+    pub port: Option<u16>,
+    pub user: Option<String>,
+    /// 私钥路径（已展开 `~`）
+    pub identity_file: Option<String>,
+    /// 跳板机链路，按连接顺序排列
+    pub proxy_jump: Vec<String>,
+    pub group: Option<String>,
+}
+
+impl SshConfigHost {
+    /// 转换为会话配置（不含跳板机）
+    ///
+    /// 未配置 User 时使用 `default_user`，未配置 IdentityFile 时使用 `default_auth`
+    pub fn to_session_config(&self, default_user: &str, default_auth: &AuthMethod) -> SessionConfig {
+        let auth_method = match &self.identity_file {
+            Some(path) => AuthMethod::PublicKey {
+                private_key_path: path.clone(),
+                passphrase: None,
+            },
+            None => default_auth.clone(),
+        };
+
+        SessionConfig {
+            name: self.alias.clone(),
+            host: self.host_name.clone(),
+            port: self.port.unwrap_or(DEFAULT_SSH_PORT),
+            username: self.user.clone().unwrap_or_else(|| default_user.to_string()),
+            auth_method,
+            terminal_type: None,
+            columns: None,
+            rows: None,
+            strict_host_key_checking: true,
+            group: self.group.clone().unwrap_or_else(|| DEFAULT_GROUP.to_string()),
+            keep_alive_interval: 30,
+            dns_resolver: None,
+            jump_host: None,
+            remote_command: None,
+            encoding: Default::default(),
+            remote_forwards: Vec::new(),
+            login_script: Vec::new(),
+            protected: false,
+            elevation: None,
+            algorithms: None,
+            startup_commands: Vec::new(),
+            backend_type: Default::default(),
+            serial: None,
+            wsl_distribution: None,
+            roaming: false,
+            wake_on_lan: None,
+            pre_connect: Vec::new(),
+        }
+    }
+}
+
+/// ProxyJump 中的一跳：配置中的别名，或 `user@host:port` 形式的目标
+pub fn find_host<'a>(hosts: &'a [SshConfigHost], hop: &str) -> Option<&'a SshConfigHost> {
+    hosts.iter().find(|host| host.alias == hop)
+}
+
+/// 将 ProxyJump 中不是别名的一跳转换为会话配置
+pub fn hop_session_config(hop: &str, default_user: &str, default_auth: &AuthMethod) -> Result<SessionConfig, String> {
+    let target = parse_target(hop)?;
+    let host = SshConfigHost {
+        alias: hop.to_string(),
+        host_name: target.host.clone(),
+        port: target.port,
+        user: target.username,
+        identity_file: None,
+        proxy_jump: Vec::new(),
+        group: None,
+    };
+    Ok(host.to_session_config(default_user, default_auth))
+}
+
+/// 一个 Host 块
+#[derive(Debug)]
+struct Block {
+    patterns: Vec<String>,
+    options: Vec<(String, String)>,
+    group: Option<String>,
+}
+
+impl Block {
+    fn matches(&self, alias: &str) -> bool {
+        let mut matched = false;
+        for pattern in &self.patterns {
+            match pattern.strip_prefix('!') {
+                // 否定模式匹配时整个块不适用
+                Some(negated) if glob_match(negated, alias) => return false,
+                Some(_) => {}
+                None => matched |= glob_match(pattern, alias),
+            }
+        }
+        matched
+    }
+
+    fn option(&self, key: &str) -> Option<&str> {
+        self.options
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// 读取并解析配置文件，Include 相对于 ~/.ssh 解析
+pub fn load_file(path: &Path) -> std::io::Result<Vec<SshConfigHost>> {
+    let content = std::fs::read_to_string(path)?;
+    let base_dir = dirs::home_dir()
+        .map(|home| home.join(".ssh"))
+        .or_else(|| path.parent().map(Path::to_path_buf))
+        .unwrap_or_default();
+    Ok(parse(&content, &|pattern| read_includes(&base_dir, pattern)))
+}
+
+/// 解析配置内容
+///
+/// `include` 根据 Include 的路径模式返回 `(分组名, 内容)` 列表
+pub fn parse(content: &str, include: &dyn Fn(&str) -> Vec<(String, String)>) -> Vec<SshConfigHost> {
+    let mut blocks = Vec::new();
+    parse_blocks(content, None, include, 0, &mut blocks);
+
+    let mut hosts: Vec<SshConfigHost> = Vec::new();
+    for block in &blocks {
+        for alias in &block.patterns {
+            if alias.contains(['*', '?', '!']) || hosts.iter().any(|h| &h.alias == alias) {
+                continue;
+            }
+
+            // 第一个匹配的值生效
+            let lookup = |key: &str| blocks.iter().filter(|b| b.matches(alias)).find_map(|b| b.option(key));
+
+            hosts.push(SshConfigHost {
+                alias: alias.clone(),
+                host_name: lookup("hostname").unwrap_or(alias).to_string(),
+                port: lookup("port").and_then(|p| p.parse().ok()),
+                user: lookup("user").map(str::to_string),
+                identity_file: lookup("identityfile").map(expand_tilde),
+                proxy_jump: lookup("proxyjump")
+                    .filter(|v| !v.eq_ignore_ascii_case("none"))
+                    .map(|v| v.split(',').map(|hop| hop.trim().to_string()).filter(|hop| !hop.is_empty()).collect())
+                    .unwrap_or_default(),
+                group: block.group.clone(),
+            });
+        }
+    }
+    hosts
+}
+
+fn parse_blocks(
+    content: &str,
+    group: Option<String>,
+    include: &dyn Fn(&str) -> Vec<(String, String)>,
+    depth: usize,
+    blocks: &mut Vec<Block>,
+) {
+    let mut group = group;
+    // Host 之前的选项适用于所有主机
+    let mut current = Block { patterns: vec!["*".to_string()], options: Vec::new(), group: group.clone() };
+    // Match 块不支持，其中的选项全部忽略
+    let mut skipping = false;
+
+    for line in content.lines() {
+        let line = line.trim();
+        if let Some(comment) = line.strip_prefix('#') {
+            let comment = comment.trim();
+            if comment.len() > GROUP_COMMENT_PREFIX.len()
+                && comment.is_char_boundary(GROUP_COMMENT_PREFIX.len())
+                && comment[..GROUP_COMMENT_PREFIX.len()].eq_ignore_ascii_case(GROUP_COMMENT_PREFIX)
+            {
+                let name = comment[GROUP_COMMENT_PREFIX.len()..].trim();
+                group = Some(name.to_string()).filter(|name| !name.is_empty());
+            }
+            continue;
+        }
+        let Some((key, value)) = split_directive(line) else {
+            continue;
+        };
+
+        match key.as_str() {
+            "host" => {
+                blocks.push(std::mem::replace(&mut current, Block {
+                    patterns: split_args(&value),
+                    options: Vec::new(),
+                    group: group.clone(),
+                }));
+                skipping = false;
+            }
+            "match" => skipping = true,
+            "include" if depth < MAX_INCLUDE_DEPTH => {
+                for pattern in split_args(&value) {
+                    for (name, included) in include(&pattern) {
+                        parse_blocks(&included, Some(name), include, depth + 1, blocks);
+                    }
+                }
+            }
+            _ if skipping => {}
+            _ => current.options.push((key, value)),
+        }
+    }
+    blocks.push(current);
+}
+
+/// 拆分 `Key value` 或 `Key=value`，键转为小写，去掉值两端的引号
+fn split_directive(line: &str) -> Option<(String, String)> {
+    if line.is_empty() {
+        return None;
+    }
+    let split_at = line.find(|c: char| c.is_whitespace() || c == '=')?;
+    let key = line[..split_at].to_ascii_lowercase();
+    let value = line[split_at..]
+        .trim_start_matches(|c: char| c.is_whitespace() || c == '=')
+        .trim()
+        .trim_matches('"')
+        .to_string();
+    Some((key, value)).filter(|(_, value)| !value.is_empty())
+}
+
+/// 拆分空白分隔的参数（支持双引号）
+fn split_args(value: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    for c in value.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            c if c.is_whitespace() && !quoted => {
+                if !current.is_empty() {
+                    args.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        args.push(current);
+    }
+    args
+}
+
+/// 简单的通配符匹配（`*` 和 `?`）
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = backtrack {
+            p = star_p + 1;
+            t = star_t + 1;
+            backtrack = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+fn expand_tilde(path: &str) -> String {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest).to_string_lossy().to_string(),
+        _ => path.to_string(),
+    }
+}
+
+/// 读取 Include 匹配的文件，文件名（不含扩展名）作为分组
+fn read_includes(base_dir: &Path, pattern: &str) -> Vec<(String, String)> {
+    let path = PathBuf::from(expand_tilde(pattern));
+    let path = if path.is_absolute() { path } else { base_dir.join(path) };
+
+    let file_name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let mut files: Vec<PathBuf> = if file_name.contains(['*', '?']) {
+        path.parent()
+            .and_then(|dir| std::fs::read_dir(dir).ok())
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|p| p.is_file())
+            .filter(|p| p.file_name().is_some_and(|n| glob_match(&file_name, &n.to_string_lossy())))
+            .collect()
+    } else {
+        vec![path]
+    };
+    files.sort();
+
+    files
+        .into_iter()
+        .filter_map(|file| {
+            let content = std::fs::read_to_string(&file).ok()?;
+            let name = file.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+            Some((name, content))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn no_include(_: &str) -> Vec<(String, String)> {
+        Vec::new()
+    }
+
+    #[test]
+    fn test_parse_hosts_with_defaults() {
+        let config = r#"
+User = root
+
+Host web db
+    HostName 10.0.0.5
+    Port 2222
+
+# Group: 生产
+Host bastion
+    HostName bastion.example.com
+    User ops
+
+Host internal-*
+    ProxyJump bastion
+
+Host internal-api
+    HostName 192.168.1.10
+    ProxyJump "jump1,admin@jump2:2200"
+
+Host *
+    IdentityFile /keys/id_ed25519
+    Port 22
+"#;
+        let hosts = parse(config, &no_include);
+        let aliases: Vec<&str> = hosts.iter().map(|h| h.alias.as_str()).collect();
+        assert_eq!(aliases, vec!["web", "db", "bastion", "internal-api"]);
+
+        assert_eq!(hosts[0].host_name, "10.0.0.5");
+        assert_eq!(hosts[1].port, Some(2222));
+        assert_eq!(hosts[0].user.as_deref(), Some("root"));
+        assert_eq!(hosts[0].identity_file.as_deref(), Some("/keys/id_ed25519"));
+        assert_eq!(hosts[0].group, None);
+
+        assert_eq!(hosts[2].user.as_deref(), Some("root"));
+        assert_eq!(hosts[2].port, Some(22));
+        assert_eq!(hosts[2].group.as_deref(), Some("生产"));
+
+        // 通配符块先出现，其 ProxyJump 优先
+        assert_eq!(hosts[3].proxy_jump, vec!["bastion"]);
+    }
+
+    #[test]
+    fn test_include_uses_file_name_as_group() {
+        let include = |pattern: &str| {
+            assert_eq!(pattern, "conf.d/*");
+            vec![("work".to_string(), "Host ci\n  HostName ci.internal\n  ProxyJump none\n".to_string())]
+        };
+        let hosts = parse("Include conf.d/*\nHost home\n  HostName 192.168.0.2\n", &include);
+
+        assert_eq!(hosts.len(), 2);
+        assert_eq!(hosts[0].alias, "ci");
+        assert_eq!(hosts[0].group.as_deref(), Some("work"));
+        assert!(hosts[0].proxy_jump.is_empty());
+        assert_eq!(hosts[1].group, None);
+    }
+
+    #[test]
+    fn test_glob_and_negation() {
+        assert!(glob_match("*.example.com", "a.example.com"));
+        assert!(glob_match("web?", "web1"));
+        assert!(!glob_match("web?", "web10"));
+
+        let hosts = parse("Host * !skip\n  User alice\nHost skip keep\n", &no_include);
+        assert_eq!(hosts[0].alias, "skip");
+        assert_eq!(hosts[0].user, None);
+        assert_eq!(hosts[1].user.as_deref(), Some("alice"));
+    }
+}
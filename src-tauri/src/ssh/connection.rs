@@ -1,8 +1,19 @@
-use crate::ssh::backend::{SSHBackend, BackendReader};
+use crate::ssh::backend::{SSHBackend, BackendReader, ExecOutput, ExecStream};
 use crate::ssh::session::{SessionConfig, SessionStatus, SessionInfo};
+use crate::ssh::forwarding::RemoteForwardTable;
+use crate::ssh::health::{ActivityClock, HealthTracker};
+use crate::ssh::login_script::LoginScriptRunner;
+use crate::ssh::manager::{ChannelKind, ChannelLease};
+use crate::ssh::protection::CommandGuard;
+use crate::ssh::roaming::RoamingState;
+use crate::ssh::stats::{ThroughputMeter, TrafficCounter};
+use crate::ssh::transcript::Transcript;
 use std::sync::Arc;
+use std::time::Instant;
 use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
 use chrono::{DateTime, Utc};
+use tauri::ipc::{Channel, InvokeResponseBody};
 
 /// 实际的SSH连接实例
 #[derive(Clone)]
@@ -16,6 +27,43 @@ pub struct ConnectionInstance {
     // 后端连接
     pub backend: Arc<Mutex<Option<Box<dyn SSHBackend>>>>,
     pub backend_reader: Arc<Mutex<Option<Box<dyn BackendReader + Send>>>>,
+
+    /// 前端订阅的二进制输出通道，设置后输出不再通过 JSON 事件发送
+    pub output_channel: Arc<Mutex<Option<Channel<InvokeResponseBody>>>>,
+
+    /// 去除 ANSI 序列后的纯文本输出记录（重连后继续追加）
+    pub transcript: Arc<Mutex<Transcript>>,
+
+    /// 远程端口转发表（重连后重新向服务器请求）
+    pub remote_forwards: RemoteForwardTable,
+
+    /// 正在执行的登录脚本（执行完毕或超时后清除）
+    pub login_script: Arc<Mutex<Option<LoginScriptRunner>>>,
+
+    /// 受保护会话的输入跟踪（未受保护时为 None）
+    pub command_guard: Arc<Mutex<Option<CommandGuard>>>,
+
+    /// 高权限会话的到期时间（未限时为 None）
+    pub elevation_deadline: Arc<Mutex<Option<Instant>>>,
+    /// 高权限会话的倒计时任务
+    pub elevation_timer: Arc<Mutex<Option<JoinHandle<()>>>>,
+
+    /// 最后一次收发终端数据的时间
+    pub last_activity: Arc<ActivityClock>,
+    /// 最近的延迟探测结果
+    pub health: Arc<Mutex<HealthTracker>>,
+    /// 健康监测任务
+    pub health_monitor: Arc<Mutex<Option<JoinHandle<()>>>>,
+    /// 收发的终端数据字节数（断开连接时写入使用历史并清零）
+    pub traffic: Arc<TrafficCounter>,
+    /// 最近一次统计的吞吐量
+    pub throughput: Arc<Mutex<ThroughputMeter>>,
+    /// 流量统计事件发送任务
+    pub stats_monitor: Arc<Mutex<Option<JoinHandle<()>>>>,
+    /// 水印发送任务（未开启水印时为 None）
+    pub watermark_task: Arc<Mutex<Option<JoinHandle<()>>>>,
+    /// 断线续连时保留的输入、PTY 尺寸和未送达前端的输出
+    pub roaming: Arc<Mutex<RoamingState>>,
 }
 
 impl ConnectionInstance {
@@ -24,10 +72,25 @@ impl ConnectionInstance {
             id,
             session_id,
             config,
-            status: Arc::new(Mutex::new(SessionStatus::Disconnected)),
+            status: Arc::new(Mutex::new(SessionStatus::Disconnected { reason: None })),
             connected_at: Arc::new(Mutex::new(None)),
             backend: Arc::new(Mutex::new(None)),
             backend_reader: Arc::new(Mutex::new(None)),
+            output_channel: Arc::new(Mutex::new(None)),
+            transcript: Arc::new(Mutex::new(Transcript::default())),
+            remote_forwards: RemoteForwardTable::default(),
+            login_script: Arc::new(Mutex::new(None)),
+            command_guard: Arc::new(Mutex::new(None)),
+            elevation_deadline: Arc::new(Mutex::new(None)),
+            elevation_timer: Arc::new(Mutex::new(None)),
+            last_activity: Arc::new(ActivityClock::default()),
+            health: Arc::new(Mutex::new(HealthTracker::default())),
+            health_monitor: Arc::new(Mutex::new(None)),
+            traffic: Arc::new(TrafficCounter::default()),
+            throughput: Arc::new(Mutex::new(ThroughputMeter::default())),
+            stats_monitor: Arc::new(Mutex::new(None)),
+            watermark_task: Arc::new(Mutex::new(None)),
+            roaming: Arc::new(Mutex::new(RoamingState::default())),
         }
     }
 
@@ -39,6 +102,19 @@ impl ConnectionInstance {
         *self.status.lock().await = status;
     }
 
+    /// 已连接时长（秒，未连接时为 0）
+    pub async fn uptime_secs(&self) -> u64 {
+        self.connected_at
+            .lock()
+            .await
+            .map_or(0, |at| (Utc::now() - at).num_seconds().max(0) as u64)
+    }
+
+    /// 主机标识（username@host:port），用于按主机聚合数据
+    pub fn host_key(&self) -> String {
+        format!("{}@{}:{}", self.config.username, self.config.host, self.config.port)
+    }
+
     /// 返回SessionInfo（用于兼容旧API）
     pub async fn session_info(&self) -> SessionInfo {
         SessionInfo {
@@ -55,10 +131,10 @@ impl ConnectionInstance {
         }
     }
 
-    /// 创建 SFTP 客户端
+    /// 在共享的 SSH 连接上租用一个 channel
     ///
-    /// 此方法使用 Any trait 安全地 downcast backend
-    pub async fn create_sftp_client(&self) -> crate::error::Result<crate::sftp::client::SftpClient> {
+    /// 只在取得租约时短暂持有 backend 锁，之后打开 channel 不再占用终端连接
+    async fn lease(&self, kind: ChannelKind, feature: &str) -> crate::error::Result<ChannelLease> {
         use crate::ssh::backends::russh::RusshBackend;
 
         let backend_guard = self.backend.lock().await;
@@ -68,8 +144,171 @@ impl ConnectionInstance {
         // 使用 as_any() 和 downcast_ref() 进行安全的类型转换
         let russh_backend = backend.as_any()
             .downcast_ref::<RusshBackend>()
-            .ok_or(crate::error::SSHError::NotSupported("SFTP only supported with RusshBackend".to_string()))?;
+            .ok_or_else(|| crate::error::SSHError::NotSupported(format!("{} only supported with RusshBackend", feature)))?;
+
+        russh_backend.lease(kind)
+    }
+
+    /// 服务器的 SSH 标识（仅 russh 后端）
+    pub async fn server_id(&self) -> Option<String> {
+        use crate::ssh::backends::russh::RusshBackend;
+
+        let backend_guard = self.backend.lock().await;
+        backend_guard.as_ref()?
+            .as_any()
+            .downcast_ref::<RusshBackend>()?
+            .server_id()
+    }
+
+    /// 创建 SFTP 客户端
+    ///
+    /// SFTP 客户端持有连接租约，使用期间共享的 SSH 连接保持打开
+    pub async fn create_sftp_client(&self) -> crate::error::Result<crate::sftp::client::SftpClient> {
+        let lease = self.lease(ChannelKind::Sftp, "SFTP").await?;
+        let client = lease.transport().create_sftp_client().await?;
+        Ok(client.with_lease(lease))
+    }
+
+    /// 探测一次往返延迟
+    ///
+    /// backend 正被占用（如正在写入大量数据）时返回 Ok(None) 跳过本次探测，避免阻塞终端
+    pub async fn probe_latency(&self, timeout: std::time::Duration) -> crate::error::Result<Option<std::time::Duration>> {
+        use crate::ssh::backends::russh::RusshBackend;
+
+        let lease = {
+            let Ok(backend_guard) = self.backend.try_lock() else {
+                return Ok(None);
+            };
+            let backend = backend_guard.as_ref()
+                .ok_or(crate::error::SSHError::NotConnected)?;
+
+            let russh_backend = backend.as_any()
+                .downcast_ref::<RusshBackend>()
+                .ok_or(crate::error::SSHError::NotSupported("Latency probe only supported with RusshBackend".to_string()))?;
+
+            russh_backend.lease(ChannelKind::Exec)?
+        };
+
+        tokio::time::timeout(timeout, lease.transport().probe_latency())
+            .await
+            .map_err(|_| crate::error::SSHError::Ssh(format!("Latency probe timed out after {:?}", timeout)))?
+            .map(Some)
+    }
+
+    /// 打开 `direct-tcpip` channel，由远程主机连接到 `host:port`（用于端口转发）
+    ///
+    /// 返回的租约需要与 channel 一起保持，直到转发的连接结束
+    pub async fn open_direct_tcpip(
+        &self,
+        host: &str,
+        port: u16,
+        originator: std::net::SocketAddr,
+    ) -> crate::error::Result<(russh::Channel<russh::client::Msg>, ChannelLease)> {
+        let lease = self.lease(ChannelKind::Forward, "Port forwarding").await?;
+        let channel = lease.transport().open_direct_tcpip(host, port, originator).await?;
+        Ok((channel, lease))
+    }
+
+    /// 请求服务器监听端口并转发回本连接（`tcpip-forward`），返回实际监听的端口
+    pub async fn request_remote_forward(&self, address: &str, port: u16) -> crate::error::Result<u16> {
+        let lease = self.lease(ChannelKind::Forward, "Port forwarding").await?;
+        lease.transport().request_remote_forward(address, port).await
+    }
+
+    /// 取消服务器端的端口监听
+    pub async fn cancel_remote_forward(&self, address: &str, port: u16) -> crate::error::Result<()> {
+        let lease = self.lease(ChannelKind::Forward, "Port forwarding").await?;
+        lease.transport().cancel_remote_forward(address, port).await
+    }
+
+    /// 在远程主机上执行命令并收集输出
+    ///
+    /// 使用独立的 exec channel，不影响交互式终端；
+    /// 只在取得连接租约时持有 backend 锁，超时后返回错误
+    pub async fn exec(&self, command: &str, timeout: std::time::Duration) -> crate::error::Result<ExecOutput> {
+        self.exec_with(command, timeout, |_, _| {}).await
+    }
+
+    /// 执行命令，输出到达时回调 `on_data`，返回完整输出
+    pub async fn exec_with<F>(
+        &self,
+        command: &str,
+        timeout: std::time::Duration,
+        on_data: F,
+    ) -> crate::error::Result<ExecOutput>
+    where
+        F: FnMut(ExecStream, &[u8]),
+    {
+        use crate::ssh::backends::russh::read_exec_output_with;
+
+        let lease = self.lease(ChannelKind::Exec, "exec").await?;
+        let channel = lease.transport().open_exec_channel(command).await?;
+
+        tokio::time::timeout(timeout, read_exec_output_with(channel, on_data))
+            .await
+            .map_err(|_| crate::error::SSHError::Ssh(format!("Command timed out after {:?}", timeout)))?
+    }
+
+    /// 执行命令并把 `input` 写入标准输入后发送 EOF（如 `chpasswd`），返回完整输出
+    pub async fn exec_with_input(
+        &self,
+        command: &str,
+        input: &[u8],
+        timeout: std::time::Duration,
+    ) -> crate::error::Result<ExecOutput> {
+        use crate::ssh::backends::russh::read_exec_output;
+
+        let lease = self.lease(ChannelKind::Exec, "exec").await?;
+        let channel = lease.transport().open_exec_channel(command).await?;
+        channel.data(input).await
+            .map_err(|e| crate::error::SSHError::Ssh(format!("Failed to write command input: {}", e)))?;
+        channel.eof().await
+            .map_err(|e| crate::error::SSHError::Ssh(format!("Failed to close command input: {}", e)))?;
+
+        tokio::time::timeout(timeout, read_exec_output(channel))
+            .await
+            .map_err(|_| crate::error::SSHError::Ssh(format!("Command timed out after {:?}", timeout)))?
+    }
+
+    /// 分配 PTY 执行交互式命令（如 `passwd`）
+    ///
+    /// 每收到一段输出时调用 `respond`，返回的内容写回命令的输入；
+    /// PTY 下标准错误并入标准输出
+    pub async fn exec_interactive<F>(
+        &self,
+        command: &str,
+        timeout: std::time::Duration,
+        mut respond: F,
+    ) -> crate::error::Result<ExecOutput>
+    where
+        F: FnMut(&[u8]) -> Option<Vec<u8>>,
+    {
+        use russh::ChannelMsg;
+
+        let lease = self.lease(ChannelKind::Exec, "exec").await?;
+        let mut channel = lease.transport().open_pty_exec_channel(command).await?;
+
+        let interaction = async move {
+            let mut output = ExecOutput::default();
+            while let Some(msg) = channel.wait().await {
+                match msg {
+                    ChannelMsg::Data { data } => {
+                        output.stdout.extend_from_slice(&data);
+                        if let Some(reply) = respond(&data) {
+                            channel.data(reply.as_slice()).await
+                                .map_err(|e| crate::error::SSHError::Ssh(format!("Failed to write command input: {}", e)))?;
+                        }
+                    }
+                    ChannelMsg::ExitStatus { exit_status } => output.exit_status = Some(exit_status),
+                    ChannelMsg::Close => break,
+                    _ => {}
+                }
+            }
+            Ok::<_, crate::error::SSHError>(output)
+        };
 
-        russh_backend.create_sftp_client_direct().await
+        tokio::time::timeout(timeout, interaction)
+            .await
+            .map_err(|_| crate::error::SSHError::Ssh(format!("Command timed out after {:?}", timeout)))?
     }
 }
@@ -0,0 +1,262 @@
+//! 局域网 SSH 主机发现
+//!
+//! 通过 mDNS/Bonjour 浏览 `_ssh._tcp` 和 `_sftp-ssh._tcp` 服务，
+//! 并可选扫描本机所在 /24 网段的 22 端口（读取 SSH 版本标识确认是 SSH 服务）。
+//! 结果按地址和端口合并，前端可据此一键创建会话。
+
+use crate::error::{Result, SSHError};
+use futures::stream::{self, StreamExt};
+use mdns_sd::{ServiceDaemon, ServiceEvent};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::time::Duration;
+use tokio::io::AsyncReadExt;
+use tokio::net::{TcpStream, UdpSocket};
+
+/// 浏览的 mDNS 服务类型
+const SERVICE_TYPES: &[&str] = &["_ssh._tcp.local.", "_sftp-ssh._tcp.local."];
+
+/// 默认发现时长（秒）
+pub const DEFAULT_TIMEOUT_SECS: u64 = 3;
+
+/// 允许的最长发现时长（秒）
+const MAX_TIMEOUT_SECS: u64 = 30;
+
+/// 网段扫描的并发连接数
+const SCAN_CONCURRENCY: usize = 64;
+
+/// 网段扫描单个地址的连接超时
+const SCAN_CONNECT_TIMEOUT: Duration = Duration::from_millis(400);
+
+/// 等待 SSH 版本标识的超时时间
+const BANNER_TIMEOUT: Duration = Duration::from_millis(800);
+
+/// 发现来源
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "camelCase")]
+pub enum DiscoverySource {
+    Mdns,
+    Scan,
+}
+
+/// 发现的候选主机
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscoveredHost {
+    /// 建议的会话名称（mDNS 实例名或主机名，扫描结果为 IP）
+    pub name: String,
+    /// 连接使用的主机（mDNS 主机名或 IP）
+    pub host: String,
+    pub ip: String,
+    pub port: u16,
+    /// mDNS 服务类型（如 `_ssh._tcp`）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service: Option<String>,
+    /// 服务器的 SSH 版本标识（如 `SSH-2.0-OpenSSH_9.6`）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub banner: Option<String>,
+    pub sources: Vec<DiscoverySource>,
+}
+
+/// 发现局域网内的 SSH 主机
+///
+/// mDNS 浏览和网段扫描并行进行，`timeout` 为 mDNS 浏览时长
+pub async fn discover(timeout: Duration, scan_subnet: bool) -> Result<Vec<DiscoveredHost>> {
+    let timeout = timeout.min(Duration::from_secs(MAX_TIMEOUT_SECS));
+    let (mdns_hosts, scanned_hosts) = tokio::join!(browse_mdns(timeout), async {
+        if scan_subnet {
+            scan_local_subnet(22).await
+        } else {
+            Ok(Vec::new())
+        }
+    });
+
+    // mDNS 不可用（如没有多播路由）时仍返回扫描结果
+    let mdns_hosts = mdns_hosts.unwrap_or_else(|e| {
+        tracing::warn!("[Discovery] mDNS browse failed: {}", e);
+        Vec::new()
+    });
+    let scanned_hosts = scanned_hosts.unwrap_or_else(|e| {
+        tracing::warn!("[Discovery] Subnet scan failed: {}", e);
+        Vec::new()
+    });
+
+    let hosts = merge(mdns_hosts.into_iter().chain(scanned_hosts));
+    tracing::info!("[Discovery] Found {} SSH host(s)", hosts.len());
+    Ok(hosts)
+}
+
+/// 浏览 mDNS 服务
+async fn browse_mdns(timeout: Duration) -> Result<Vec<DiscoveredHost>> {
+    let daemon = ServiceDaemon::new().map_err(|e| SSHError::ConnectionFailed(format!("mDNS: {}", e)))?;
+    let receivers = SERVICE_TYPES
+        .iter()
+        .map(|service_type| {
+            daemon
+                .browse(service_type)
+                .map_err(|e| SSHError::ConnectionFailed(format!("mDNS browse {}: {}", service_type, e)))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut hosts = Vec::new();
+    let deadline = tokio::time::Instant::now() + timeout;
+    let mut events = stream::select_all(receivers.into_iter().map(|receiver| receiver.into_stream().boxed()));
+    while let Ok(Some(event)) = tokio::time::timeout_at(deadline, events.next()).await {
+        let ServiceEvent::ServiceResolved(info) = event else {
+            continue;
+        };
+        let fullname = info.get_fullname();
+        let service_type = info.get_type();
+        let name = fullname.strip_suffix(service_type).unwrap_or(fullname).trim_end_matches('.');
+        let hostname = info.get_hostname().trim_end_matches('.');
+        for ip in info.get_addresses() {
+            hosts.push(DiscoveredHost {
+                name: name.to_string(),
+                host: hostname.to_string(),
+                ip: ip.to_string(),
+                port: info.get_port(),
+                service: Some(service_type.trim_end_matches(".local.").to_string()),
+                banner: None,
+                sources: vec![DiscoverySource::Mdns],
+            });
+        }
+    }
+
+    if let Ok(done) = daemon.shutdown() {
+        let _ = done.recv_async().await;
+    }
+    Ok(hosts)
+}
+
+/// 本机用于访问局域网的 IPv4 地址
+///
+/// UDP connect 不发送数据，只让系统按路由表选出源地址
+async fn local_ipv4() -> Result<Ipv4Addr> {
+    let socket = UdpSocket::bind("0.0.0.0:0").await?;
+    socket.connect("192.0.2.1:9").await?;
+    match socket.local_addr()?.ip() {
+        IpAddr::V4(ip) if !ip.is_loopback() && !ip.is_unspecified() => Ok(ip),
+        ip => Err(SSHError::ConnectionFailed(format!("No usable local IPv4 address ({})", ip))),
+    }
+}
+
+/// 本机所在 /24 网段中除本机外的地址
+fn subnet_hosts(local: Ipv4Addr) -> Vec<Ipv4Addr> {
+    let [a, b, c, _] = local.octets();
+    (1..=254u8)
+        .map(|d| Ipv4Addr::new(a, b, c, d))
+        .filter(|ip| *ip != local)
+        .collect()
+}
+
+/// 扫描本机所在 /24 网段的 SSH 端口
+async fn scan_local_subnet(port: u16) -> Result<Vec<DiscoveredHost>> {
+    let local = local_ipv4().await?;
+    // 只扫描私有网段，避免在公网地址上发起大量连接
+    if !local.is_private() && !local.is_link_local() {
+        return Err(SSHError::ConnectionFailed(format!("{} is not on a private network", local)));
+    }
+    tracing::debug!("[Discovery] Scanning {}/24 port {}", local, port);
+
+    let hosts = stream::iter(subnet_hosts(local))
+        .map(|ip| probe(SocketAddr::new(IpAddr::V4(ip), port)))
+        .buffer_unordered(SCAN_CONCURRENCY)
+        .filter_map(|host| async move { host })
+        .collect()
+        .await;
+    Ok(hosts)
+}
+
+/// 连接地址并读取 SSH 版本标识，不是 SSH 服务时返回 None
+async fn probe(addr: SocketAddr) -> Option<DiscoveredHost> {
+    let mut stream = tokio::time::timeout(SCAN_CONNECT_TIMEOUT, TcpStream::connect(addr)).await.ok()?.ok()?;
+    let mut buf = [0u8; 256];
+    let n = tokio::time::timeout(BANNER_TIMEOUT, stream.read(&mut buf)).await.ok()?.ok()?;
+    let banner = parse_banner(&buf[..n])?;
+
+    Some(DiscoveredHost {
+        name: addr.ip().to_string(),
+        host: addr.ip().to_string(),
+        ip: addr.ip().to_string(),
+        port: addr.port(),
+        service: None,
+        banner: Some(banner),
+        sources: vec![DiscoverySource::Scan],
+    })
+}
+
+/// 从服务器的首行输出中取出 SSH 版本标识
+fn parse_banner(data: &[u8]) -> Option<String> {
+    let text = String::from_utf8_lossy(data);
+    // RFC 4253 允许版本标识前有其他行
+    text.lines()
+        .map(|line| line.trim_end_matches('\r'))
+        .find(|line| line.starts_with("SSH-"))
+        .map(str::to_string)
+}
+
+/// 按地址和端口合并结果，mDNS 提供的名称优先
+fn merge(hosts: impl IntoIterator<Item = DiscoveredHost>) -> Vec<DiscoveredHost> {
+    let mut merged: BTreeMap<(String, u16), DiscoveredHost> = BTreeMap::new();
+    for host in hosts {
+        match merged.get_mut(&(host.ip.clone(), host.port)) {
+            Some(existing) => {
+                if existing.service.is_none() {
+                    existing.name = host.name;
+                    existing.host = host.host;
+                    existing.service = host.service;
+                }
+                existing.banner = existing.banner.take().or(host.banner);
+                existing.sources.extend(host.sources);
+                existing.sources.sort();
+                existing.sources.dedup();
+            }
+            None => {
+                merged.insert((host.ip.clone(), host.port), host);
+            }
+        }
+    }
+    merged.into_values().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_and_banner() {
+        assert_eq!(
+            parse_banner(b"Welcome\r\nSSH-2.0-OpenSSH_9.6 Ubuntu\r\n").as_deref(),
+            Some("SSH-2.0-OpenSSH_9.6 Ubuntu")
+        );
+        assert_eq!(parse_banner(b"HTTP/1.1 400 Bad Request\r\n"), None);
+
+        let hosts = subnet_hosts(Ipv4Addr::new(192, 168, 1, 20));
+        assert_eq!(hosts.len(), 253);
+        assert!(!hosts.contains(&Ipv4Addr::new(192, 168, 1, 20)));
+
+        let scanned = DiscoveredHost {
+            name: "192.168.1.5".to_string(),
+            host: "192.168.1.5".to_string(),
+            ip: "192.168.1.5".to_string(),
+            port: 22,
+            service: None,
+            banner: Some("SSH-2.0-OpenSSH_9.6".to_string()),
+            sources: vec![DiscoverySource::Scan],
+        };
+        let announced = DiscoveredHost {
+            name: "nas".to_string(),
+            host: "nas.local".to_string(),
+            service: Some("_ssh._tcp".to_string()),
+            banner: None,
+            sources: vec![DiscoverySource::Mdns],
+            ..scanned.clone()
+        };
+        let merged = merge([scanned, announced]);
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged[0].host, "nas.local");
+        assert_eq!(merged[0].banner.as_deref(), Some("SSH-2.0-OpenSSH_9.6"));
+        assert_eq!(merged[0].sources, vec![DiscoverySource::Mdns, DiscoverySource::Scan]);
+    }
+}
@@ -0,0 +1,309 @@
+//! 自定义 DNS 解析
+//!
+//! 在 DNS 被污染或不可用的网络中，解析 SSH 主机时可以指定 DNS 服务器（UDP）
+//! 或 DNS-over-HTTPS（RFC 8484，wire format）端点，代替系统解析器。
+//! 可以全局配置，也可以在单个会话上覆盖。
+
+use crate::error::{Result, SSHError};
+use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::time::Duration;
+
+/// 单次查询超时
+const QUERY_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// UDP 查询重试次数
+const UDP_ATTEMPTS: usize = 2;
+
+const TYPE_A: u16 = 1;
+const TYPE_AAAA: u16 = 28;
+const CLASS_IN: u16 = 1;
+
+/// DNS 解析器配置
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum DnsResolverConfig {
+    /// 使用系统解析器
+    #[default]
+    System,
+    /// 指定 DNS 服务器，如 `1.1.1.1`、`8.8.8.8:53`、`[2606:4700::1111]:53`
+    Server { address: String },
+    /// DNS-over-HTTPS 端点，如 `https://cloudflare-dns.com/dns-query`
+    Doh { url: String },
+}
+
+impl DnsResolverConfig {
+    /// 校验配置
+    pub fn validate(&self) -> std::result::Result<(), String> {
+        match self {
+            DnsResolverConfig::System => Ok(()),
+            DnsResolverConfig::Server { address } => parse_server_address(address).map(|_| ()),
+            DnsResolverConfig::Doh { url } => {
+                if url.starts_with("https://") {
+                    Ok(())
+                } else {
+                    Err(format!("DoH 地址必须以 https:// 开头: {}", url))
+                }
+            }
+        }
+    }
+}
+
+/// 使用指定的解析器解析主机名，返回所有地址（IPv6 在前）
+///
+/// IP 字面量直接返回，不发起查询
+pub async fn resolve(resolver: &DnsResolverConfig, host: &str, port: u16) -> Result<Vec<SocketAddr>> {
+    if let Ok(ip) = host.parse::<IpAddr>() {
+        return Ok(vec![SocketAddr::new(ip, port)]);
+    }
+
+    // 带 %zone 的 IPv6 字面量交给系统解析
+    let resolver = if host.contains(':') { &DnsResolverConfig::System } else { resolver };
+
+    let ips = match resolver {
+        DnsResolverConfig::System => {
+            return tokio::net::lookup_host((host, port))
+                .await
+                .map(|addrs| addrs.collect())
+                .map_err(|e| SSHError::ConnectionFailed(format!("无法解析主机 {}: {}", host, e)));
+        }
+        DnsResolverConfig::Server { address } => {
+            let server = parse_server_address(address).map_err(SSHError::ConnectionFailed)?;
+            let (v6, v4) = tokio::join!(query_udp(server, host, TYPE_AAAA), query_udp(server, host, TYPE_A));
+            merge_answers(host, v6, v4)?
+        }
+        DnsResolverConfig::Doh { url } => {
+            let (v6, v4) = tokio::join!(query_doh(url, host, TYPE_AAAA), query_doh(url, host, TYPE_A));
+            merge_answers(host, v6, v4)?
+        }
+    };
+
+    Ok(ips.into_iter().map(|ip| SocketAddr::new(ip, port)).collect())
+}
+
+/// 合并 AAAA 和 A 查询结果，两者都失败时返回错误
+fn merge_answers(host: &str, v6: Result<Vec<IpAddr>>, v4: Result<Vec<IpAddr>>) -> Result<Vec<IpAddr>> {
+    match (v6, v4) {
+        (Err(e6), Err(e4)) => Err(SSHError::ConnectionFailed(format!("无法解析主机 {}: {}; {}", host, e6, e4))),
+        (v6, v4) => {
+            let ips: Vec<IpAddr> = v6.unwrap_or_default().into_iter().chain(v4.unwrap_or_default()).collect();
+            if ips.is_empty() {
+                Err(SSHError::ConnectionFailed(format!("主机 {} 没有 A/AAAA 记录", host)))
+            } else {
+                Ok(ips)
+            }
+        }
+    }
+}
+
+/// 解析 DNS 服务器地址（未指定端口时使用 53）
+fn parse_server_address(address: &str) -> std::result::Result<SocketAddr, String> {
+    if let Ok(addr) = address.parse::<SocketAddr>() {
+        return Ok(addr);
+    }
+    address
+        .parse::<IpAddr>()
+        .map(|ip| SocketAddr::new(ip, 53))
+        .map_err(|_| format!("无效的 DNS 服务器地址: {}", address))
+}
+
+/// 通过 UDP 查询
+async fn query_udp(server: SocketAddr, host: &str, record_type: u16) -> Result<Vec<IpAddr>> {
+    let bind_addr: SocketAddr = if server.is_ipv6() {
+        (Ipv6Addr::UNSPECIFIED, 0).into()
+    } else {
+        (Ipv4Addr::UNSPECIFIED, 0).into()
+    };
+    let socket = tokio::net::UdpSocket::bind(bind_addr).await?;
+    socket.connect(server).await?;
+
+    let id = rand::random::<u16>();
+    let query = build_query(id, host, record_type)?;
+    let mut buffer = vec![0u8; 4096];
+
+    for _ in 0..UDP_ATTEMPTS {
+        socket.send(&query).await?;
+        match tokio::time::timeout(QUERY_TIMEOUT, socket.recv(&mut buffer)).await {
+            Ok(Ok(len)) => return parse_response(&buffer[..len], id, record_type),
+            Ok(Err(e)) => return Err(e.into()),
+            Err(_) => continue,
+        }
+    }
+
+    Err(SSHError::ConnectionFailed(format!("DNS 服务器 {} 无响应", server)))
+}
+
+/// 通过 DNS-over-HTTPS 查询（POST application/dns-message）
+async fn query_doh(url: &str, host: &str, record_type: u16) -> Result<Vec<IpAddr>> {
+    // RFC 8484 建议 DoH 查询 ID 为 0，便于缓存
+    let query = build_query(0, host, record_type)?;
+
+    let client = reqwest::Client::builder()
+        .timeout(QUERY_TIMEOUT)
+        .build()
+        .map_err(|e| SSHError::ConnectionFailed(format!("DoH 客户端创建失败: {}", e)))?;
+
+    let response = client
+        .post(url)
+        .header("content-type", "application/dns-message")
+        .header("accept", "application/dns-message")
+        .body(query)
+        .send()
+        .await
+        .map_err(|e| SSHError::ConnectionFailed(format!("DoH 请求失败: {}", e)))?;
+
+    if !response.status().is_success() {
+        return Err(SSHError::ConnectionFailed(format!("DoH 请求失败: HTTP {}", response.status())));
+    }
+
+    let body = response
+        .bytes()
+        .await
+        .map_err(|e| SSHError::ConnectionFailed(format!("DoH 响应读取失败: {}", e)))?;
+    parse_response(&body, 0, record_type)
+}
+
+/// 构造 DNS 查询报文
+fn build_query(id: u16, host: &str, record_type: u16) -> Result<Vec<u8>> {
+    let mut packet = Vec::with_capacity(32 + host.len());
+    packet.extend_from_slice(&id.to_be_bytes());
+    packet.extend_from_slice(&0x0100u16.to_be_bytes()); // RD：递归查询
+    packet.extend_from_slice(&1u16.to_be_bytes()); // QDCOUNT
+    packet.extend_from_slice(&[0; 6]); // ANCOUNT / NSCOUNT / ARCOUNT
+
+    for label in host.trim_end_matches('.').split('.') {
+        if label.is_empty() || label.len() > 63 || !label.is_ascii() {
+            return Err(SSHError::ConnectionFailed(format!("无效的主机名: {}", host)));
+        }
+        packet.push(label.len() as u8);
+        packet.extend_from_slice(label.as_bytes());
+    }
+    packet.push(0);
+
+    packet.extend_from_slice(&record_type.to_be_bytes());
+    packet.extend_from_slice(&CLASS_IN.to_be_bytes());
+    Ok(packet)
+}
+
+/// 解析 DNS 响应报文，返回指定类型的地址记录
+fn parse_response(packet: &[u8], expected_id: u16, record_type: u16) -> Result<Vec<IpAddr>> {
+    let malformed = || SSHError::ConnectionFailed("DNS 响应格式错误".to_string());
+    let read_u16 = |pos: usize| -> Result<u16> {
+        packet.get(pos..pos + 2).map(|b| u16::from_be_bytes([b[0], b[1]])).ok_or_else(malformed)
+    };
+
+    if read_u16(0)? != expected_id {
+        return Err(SSHError::ConnectionFailed("DNS 响应 ID 不匹配".to_string()));
+    }
+    let flags = read_u16(2)?;
+    let rcode = flags & 0x000f;
+    if rcode == 3 {
+        // NXDOMAIN
+        return Ok(Vec::new());
+    }
+    if rcode != 0 {
+        return Err(SSHError::ConnectionFailed(format!("DNS 查询失败 (rcode {})", rcode)));
+    }
+
+    let question_count = read_u16(4)?;
+    let answer_count = read_u16(6)?;
+    let mut pos = 12;
+
+    for _ in 0..question_count {
+        pos = skip_name(packet, pos).ok_or_else(malformed)? + 4;
+    }
+
+    let mut addresses = Vec::new();
+    for _ in 0..answer_count {
+        pos = skip_name(packet, pos).ok_or_else(malformed)?;
+        let rtype = read_u16(pos)?;
+        let class = read_u16(pos + 2)?;
+        let rdlength = read_u16(pos + 8)? as usize;
+        let rdata = packet.get(pos + 10..pos + 10 + rdlength).ok_or_else(malformed)?;
+        pos += 10 + rdlength;
+
+        // 跳过 CNAME 等其他记录，CNAME 指向的地址记录会出现在同一响应中
+        if class != CLASS_IN || rtype != record_type {
+            continue;
+        }
+        match (rtype, rdata.len()) {
+            (TYPE_A, 4) => addresses.push(IpAddr::from(<[u8; 4]>::try_from(rdata).map_err(|_| malformed())?)),
+            (TYPE_AAAA, 16) => addresses.push(IpAddr::from(<[u8; 16]>::try_from(rdata).map_err(|_| malformed())?)),
+            _ => return Err(malformed()),
+        }
+    }
+
+    Ok(addresses)
+}
+
+/// 跳过报文中的域名（支持压缩指针），返回域名之后的位置
+fn skip_name(packet: &[u8], mut pos: usize) -> Option<usize> {
+    loop {
+        let len = *packet.get(pos)? as usize;
+        if len == 0 {
+            return Some(pos + 1);
+        }
+        if len & 0xc0 == 0xc0 {
+            return Some(pos + 2);
+        }
+        pos += 1 + len;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 构造响应：在查询后追加一条 CNAME 和一条指向问题名称的地址记录
+    fn build_response(id: u16, record_type: u16, rdata: &[u8]) -> Vec<u8> {
+        let mut packet = build_query(id, "example.com", record_type).unwrap();
+        packet[2..4].copy_from_slice(&0x8180u16.to_be_bytes());
+        packet[6..8].copy_from_slice(&2u16.to_be_bytes());
+
+        // CNAME 记录（名称使用压缩指针指向偏移 12）
+        packet.extend_from_slice(&[0xc0, 12]);
+        packet.extend_from_slice(&5u16.to_be_bytes());
+        packet.extend_from_slice(&CLASS_IN.to_be_bytes());
+        packet.extend_from_slice(&300u32.to_be_bytes());
+        packet.extend_from_slice(&2u16.to_be_bytes());
+        packet.extend_from_slice(&[0xc0, 12]);
+
+        packet.extend_from_slice(&[0xc0, 12]);
+        packet.extend_from_slice(&record_type.to_be_bytes());
+        packet.extend_from_slice(&CLASS_IN.to_be_bytes());
+        packet.extend_from_slice(&300u32.to_be_bytes());
+        packet.extend_from_slice(&(rdata.len() as u16).to_be_bytes());
+        packet.extend_from_slice(rdata);
+        packet
+    }
+
+    #[test]
+    fn test_build_query() {
+        let query = build_query(0x1234, "a.example.com.", TYPE_A).unwrap();
+        assert_eq!(&query[..4], &[0x12, 0x34, 0x01, 0x00]);
+        assert_eq!(&query[12..], b"\x01a\x07example\x03com\x00\x00\x01\x00\x01");
+        assert!(build_query(1, "bad..host", TYPE_A).is_err());
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let response = build_response(7, TYPE_A, &[93, 184, 216, 34]);
+        assert_eq!(parse_response(&response, 7, TYPE_A).unwrap(), vec!["93.184.216.34".parse::<IpAddr>().unwrap()]);
+        assert!(parse_response(&response, 8, TYPE_A).is_err());
+
+        let v6: Ipv6Addr = "2606:2800:220:1::".parse().unwrap();
+        let response = build_response(9, TYPE_AAAA, &v6.octets());
+        assert_eq!(parse_response(&response, 9, TYPE_AAAA).unwrap(), vec![IpAddr::V6(v6)]);
+
+        // 截断的报文
+        assert!(parse_response(&response[..response.len() - 3], 9, TYPE_AAAA).is_err());
+    }
+
+    #[test]
+    fn test_validate_config() {
+        assert!(DnsResolverConfig::Server { address: "1.1.1.1".into() }.validate().is_ok());
+        assert!(DnsResolverConfig::Server { address: "[2606:4700::1111]:53".into() }.validate().is_ok());
+        assert!(DnsResolverConfig::Server { address: "dns.local".into() }.validate().is_err());
+        assert!(DnsResolverConfig::Doh { url: "http://x/dns-query".into() }.validate().is_err());
+    }
+}
@@ -0,0 +1,101 @@
+//! Docker 容器终端
+//!
+//! 在远程主机（通过 SSH）或本机上执行 `docker exec -it` 进入容器，
+//! 并通过 `docker ps` 列出运行中的容器供前端选择
+
+use crate::utils::shell::shell_quote;
+use serde::{Deserialize, Serialize};
+
+/// `docker ps` 输出格式，字段以制表符分隔
+pub const LIST_FORMAT: &str = "{{.ID}}\t{{.Names}}\t{{.Image}}\t{{.Status}}";
+
+/// 未指定 shell 时优先使用 bash，不存在时退回 sh
+pub(crate) const DEFAULT_SHELL_PROBE: &str = "[ -x /bin/bash ] && exec /bin/bash || exec /bin/sh";
+
+/// 运行中的容器
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DockerContainer {
+    pub id: String,
+    pub name: String,
+    pub image: String,
+    pub status: String,
+}
+
+/// 通过 SSH exec channel 列出容器的远程命令
+pub fn list_command() -> String {
+    format!("docker ps --format {}", shell_quote(LIST_FORMAT))
+}
+
+/// 解析 `docker ps --format LIST_FORMAT` 的输出
+pub fn parse_containers(output: &str) -> Vec<DockerContainer> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.trim_end_matches('\r').splitn(4, '\t');
+            let id = fields.next()?.trim();
+            let name = fields.next()?.trim();
+            if id.is_empty() || name.is_empty() {
+                return None;
+            }
+            Some(DockerContainer {
+                id: id.to_string(),
+                name: name.to_string(),
+                image: fields.next().unwrap_or_default().trim().to_string(),
+                status: fields.next().unwrap_or_default().trim().to_string(),
+            })
+        })
+        .collect()
+}
+
+/// 构造进入容器的命令，`shell` 为空时自动选择 bash 或 sh
+///
+/// 容器名称和 shell 只允许 Docker 名称字符集，因此无需转义，
+/// 生成的命令在远程 POSIX shell 和本机 `cmd.exe` 中都能执行
+pub fn exec_command(container: &str, shell: Option<&str>) -> Result<String, String> {
+    let container = container.trim();
+    if !is_safe_word(container) || container.starts_with(['-', '.', '/']) {
+        return Err(format!("无效的容器名称: {}", container));
+    }
+
+    match shell.map(str::trim).filter(|s| !s.is_empty()) {
+        Some(shell) if is_safe_word(shell) && !shell.starts_with('-') => {
+            Ok(format!("docker exec -it {} {}", container, shell))
+        }
+        Some(shell) => Err(format!("无效的 shell: {}", shell)),
+        None => Ok(format!(
+            "docker exec -it {} /bin/sh -c \"{}\"",
+            container, DEFAULT_SHELL_PROBE
+        )),
+    }
+}
+
+pub(crate) fn is_safe_word(value: &str) -> bool {
+    !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-' | '/'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_exec_command() {
+        let output = "3f2a1b\tweb\tnginx:1.25\tUp 2 hours\r\n\nabc\tdb\tpostgres:16\tUp 5 minutes (healthy)\n";
+        let containers = parse_containers(output);
+        assert_eq!(containers.len(), 2);
+        assert_eq!(containers[0].name, "web");
+        assert_eq!(containers[1].status, "Up 5 minutes (healthy)");
+
+        assert_eq!(
+            exec_command("web", Some("bash")).unwrap(),
+            "docker exec -it web bash"
+        );
+        assert!(exec_command("web", None).unwrap().contains("exec /bin/bash || exec /bin/sh"));
+        assert!(exec_command("--privileged", None).is_err());
+        assert!(exec_command("web; rm -rf /", None).is_err());
+        assert_eq!(list_command(), "docker ps --format '{{.ID}}\t{{.Names}}\t{{.Image}}\t{{.Status}}'");
+    }
+}
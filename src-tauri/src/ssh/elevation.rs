@@ -0,0 +1,121 @@
+//! 限时的高权限会话
+//!
+//! 以 root/管理员身份打开的会话可以设置时限，到期后自动断开，或向 shell 写入降权命令
+//! （默认 `exit`，用于 `sudo -i`、`su` 之后退回普通用户）。倒计时通过
+//! `ssh-elevation-{connectionId}` 事件通知前端，前端可以延长时限。
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// 倒计时事件前缀
+pub const ELEVATION_EVENT_PREFIX: &str = "ssh-elevation-";
+
+/// 默认降权命令
+pub const DEFAULT_DOWNGRADE_COMMAND: &str = "exit";
+
+/// 剩余时间到达这些点时发送倒计时事件（秒，降序）
+const NOTICE_POINTS: [u64; 4] = [300, 60, 30, 10];
+
+/// 到期后的处理方式
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum ElevationAction {
+    #[default]
+    Disconnect,
+    /// 向 shell 写入降权命令；直接以 root 登录时相当于退出登录 shell
+    Downgrade,
+}
+
+/// 高权限会话的时限设置
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ElevationPolicy {
+    /// 时限（分钟）
+    pub minutes: u32,
+    #[serde(default)]
+    pub action: ElevationAction,
+    /// 降权命令，为空时使用 `exit`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub downgrade_command: Option<String>,
+    /// 对所有用户名生效（默认只对 root/管理员账户生效）
+    #[serde(default)]
+    pub always: bool,
+}
+
+impl ElevationPolicy {
+    pub fn duration(&self) -> Duration {
+        Duration::from_secs(u64::from(self.minutes) * 60)
+    }
+
+    /// 是否对该用户名生效
+    pub fn applies_to(&self, username: &str) -> bool {
+        self.minutes > 0 && (self.always || is_elevated_user(username))
+    }
+
+    pub fn downgrade_command(&self) -> &str {
+        self.downgrade_command
+            .as_deref()
+            .map(str::trim)
+            .filter(|c| !c.is_empty())
+            .unwrap_or(DEFAULT_DOWNGRADE_COMMAND)
+    }
+}
+
+/// 倒计时事件
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum ElevationEvent {
+    /// 剩余时间（开始计时、延长时限和到达提醒点时发送）
+    Countdown { remaining_secs: u64, action: ElevationAction },
+    /// 已到期并执行了处理
+    Expired { action: ElevationAction },
+}
+
+/// 是否为 root/管理员账户
+pub fn is_elevated_user(username: &str) -> bool {
+    matches!(
+        username.to_ascii_lowercase().as_str(),
+        "root" | "toor" | "admin" | "administrator"
+    )
+}
+
+/// 剩余 `remaining` 时，下一个提醒点（小于剩余时间的最大提醒点，没有时为 0 即到期）
+pub fn next_notice(remaining: Duration) -> Duration {
+    NOTICE_POINTS
+        .iter()
+        .map(|secs| Duration::from_secs(*secs))
+        .find(|point| *point < remaining)
+        .unwrap_or(Duration::ZERO)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_applies_to() {
+        let policy = ElevationPolicy {
+            minutes: 15,
+            action: ElevationAction::Disconnect,
+            downgrade_command: Some("  ".to_string()),
+            always: false,
+        };
+        assert!(policy.applies_to("root"));
+        assert!(policy.applies_to("Administrator"));
+        assert!(!policy.applies_to("deploy"));
+        assert_eq!(policy.downgrade_command(), "exit");
+
+        let always = ElevationPolicy { always: true, ..policy.clone() };
+        assert!(always.applies_to("deploy"));
+        let disabled = ElevationPolicy { minutes: 0, ..policy };
+        assert!(!disabled.applies_to("root"));
+    }
+
+    #[test]
+    fn test_next_notice() {
+        assert_eq!(next_notice(Duration::from_secs(900)), Duration::from_secs(300));
+        assert_eq!(next_notice(Duration::from_secs(300)), Duration::from_secs(60));
+        assert_eq!(next_notice(Duration::from_secs(45)), Duration::from_secs(30));
+        assert_eq!(next_notice(Duration::from_secs(10)), Duration::ZERO);
+    }
+}
@@ -0,0 +1,155 @@
+//! 终端字符编码转换
+//!
+//! 老旧主机可能使用 GBK、Big5 等非 UTF-8 编码。前端始终按 UTF-8 处理，
+//! 因此在后端将远程输出解码为 UTF-8，将用户输入从 UTF-8 编码为远程编码。
+//! 默认 UTF-8 时直接透传，不做任何转换。
+
+use bytes::Bytes;
+use encoding_rs::{CoderResult, Decoder, Encoder, Encoding, EncoderResult};
+use serde::{Deserialize, Serialize};
+
+/// 无法用远程编码表示的输入字符替换为 `?`
+const UNMAPPABLE_REPLACEMENT: u8 = b'?';
+
+/// 会话字符编码
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum TerminalEncoding {
+    #[default]
+    #[serde(rename = "utf-8")]
+    Utf8,
+    #[serde(rename = "gbk")]
+    Gbk,
+    #[serde(rename = "big5")]
+    Big5,
+    #[serde(rename = "euc-kr")]
+    EucKr,
+    #[serde(rename = "latin1")]
+    Latin1,
+}
+
+impl TerminalEncoding {
+    pub fn is_utf8(&self) -> bool {
+        *self == TerminalEncoding::Utf8
+    }
+
+    /// 对应的 encoding_rs 编码，UTF-8 返回 None（透传）
+    fn encoding(&self) -> Option<&'static Encoding> {
+        match self {
+            TerminalEncoding::Utf8 => None,
+            // GBK 解码器兼容 GB18030 扩展
+            TerminalEncoding::Gbk => Some(encoding_rs::GBK),
+            TerminalEncoding::Big5 => Some(encoding_rs::BIG5),
+            TerminalEncoding::EucKr => Some(encoding_rs::EUC_KR),
+            // WHATWG 中 latin1 即 windows-1252
+            TerminalEncoding::Latin1 => Some(encoding_rs::WINDOWS_1252),
+        }
+    }
+
+    /// 创建独立的输出解码器（UTF-8 时同样解码，用于 exec 输出等非终端数据）
+    pub fn decoder(&self) -> Decoder {
+        self.encoding()
+            .unwrap_or(encoding_rs::UTF_8)
+            .new_decoder_without_bom_handling()
+    }
+
+    /// 创建输出解码器和输入编码器，UTF-8 时返回 None
+    pub fn transcoder(&self) -> Option<Transcoder> {
+        self.encoding().map(|encoding| Transcoder {
+            decoder: encoding.new_decoder_without_bom_handling(),
+            encoder: encoding.new_encoder(),
+            pending_input: Vec::new(),
+        })
+    }
+}
+
+/// 有状态的双向转码器
+///
+/// 多字节字符可能被拆分到多个数据块中，解码器和编码器会保留不完整的部分
+pub struct Transcoder {
+    decoder: Decoder,
+    encoder: Encoder,
+    /// 输入中不完整的 UTF-8 字节
+    pending_input: Vec<u8>,
+}
+
+impl Transcoder {
+    /// 将远程输出解码为 UTF-8
+    pub fn decode_output(&mut self, data: &[u8]) -> Bytes {
+        let capacity = self
+            .decoder
+            .max_utf8_buffer_length(data.len())
+            .unwrap_or(data.len() * 3 + 16);
+        let mut output = String::with_capacity(capacity);
+        let (result, _, _) = self.decoder.decode_to_string(data, &mut output, false);
+        debug_assert_eq!(result, CoderResult::InputEmpty);
+        Bytes::from(output)
+    }
+
+    /// 将 UTF-8 输入编码为远程编码
+    pub fn encode_input(&mut self, data: &[u8]) -> Vec<u8> {
+        let mut bytes = std::mem::take(&mut self.pending_input);
+        bytes.extend_from_slice(data);
+
+        // 末尾被截断的 UTF-8 字符留到下次
+        let valid_up_to = match std::str::from_utf8(&bytes) {
+            Ok(_) => bytes.len(),
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(_) => bytes.len(),
+        };
+        self.pending_input = bytes.split_off(valid_up_to);
+        let text = String::from_utf8_lossy(&bytes);
+
+        let mut output = Vec::with_capacity(text.len() * 2 + 8);
+        let mut remaining: &str = &text;
+        loop {
+            let mut buffer = [0u8; 1024];
+            let (result, read, written) =
+                self.encoder.encode_from_utf8_without_replacement(remaining, &mut buffer, false);
+            output.extend_from_slice(&buffer[..written]);
+            remaining = &remaining[read..];
+
+            match result {
+                EncoderResult::InputEmpty => break,
+                EncoderResult::OutputFull => {}
+                EncoderResult::Unmappable(_) => output.push(UNMAPPABLE_REPLACEMENT),
+            }
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_utf8_is_passthrough() {
+        assert!(TerminalEncoding::Utf8.transcoder().is_none());
+        assert_eq!(serde_json::to_string(&TerminalEncoding::EucKr).unwrap(), "\"euc-kr\"");
+    }
+
+    #[test]
+    fn test_decode_split_multibyte() {
+        let mut transcoder = TerminalEncoding::Gbk.transcoder().unwrap();
+        // "中文" 的 GBK 编码
+        let gbk = [0xd6, 0xd0, 0xce, 0xc4];
+        let first = transcoder.decode_output(&gbk[..1]);
+        let rest = transcoder.decode_output(&gbk[1..]);
+        assert!(first.is_empty());
+        assert_eq!(&rest[..], "中文".as_bytes());
+    }
+
+    #[test]
+    fn test_encode_input() {
+        let mut transcoder = TerminalEncoding::Big5.transcoder().unwrap();
+        let utf8 = "ls 中\n".as_bytes();
+        // 在多字节字符中间拆分
+        let mut encoded = transcoder.encode_input(&utf8[..4]);
+        encoded.extend(transcoder.encode_input(&utf8[4..]));
+        assert_eq!(encoded, vec![b'l', b's', b' ', 0xa4, 0xa4, b'\n']);
+
+        // 无法表示的字符替换为 ?
+        let mut transcoder = TerminalEncoding::Latin1.transcoder().unwrap();
+        assert_eq!(transcoder.encode_input("é中".as_bytes()), vec![0xe9, b'?']);
+    }
+}
@@ -0,0 +1,407 @@
+//! 远程环境检查
+//!
+//! 通过 exec channel 执行一段 POSIX shell 脚本，采集环境变量（exec 环境和登录 shell 环境）、
+//! PATH 中每个目录是否存在以及常见的 shell 启动文件内容，整理成快照。
+//! 快照按会话保存在应用存储目录的 `env_snapshots.json` 中，可与之前的快照或其他服务器的快照比较，
+//! 用于排查"同一个命令在这台机器上能用、在那台上不能用"之类的 PATH 问题。
+
+use crate::config::storage::Storage;
+use crate::error::{Result, SSHError};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::PathBuf;
+
+/// 输出中分隔各部分的标记
+const SECTION_MARKER: &str = "@@SSHT-ENV ";
+
+/// 每个启动文件最多读取的字节数
+const MAX_RC_FILE_BYTES: usize = 16 * 1024;
+
+/// 每个会话保留的快照数量
+const MAX_SNAPSHOTS_PER_SESSION: usize = 10;
+
+/// 检查的 shell 启动文件（`~` 由脚本展开为 `$HOME`）
+const RC_FILES: &[&str] = &[
+    "/etc/environment",
+    "/etc/profile",
+    "/etc/bash.bashrc",
+    "/etc/zsh/zshenv",
+    "/etc/zsh/zprofile",
+    "~/.profile",
+    "~/.bash_profile",
+    "~/.bash_login",
+    "~/.bashrc",
+    "~/.zshenv",
+    "~/.zprofile",
+    "~/.zshrc",
+    "~/.config/fish/config.fish",
+];
+
+/// PATH 中的一个目录
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PathEntry {
+    pub dir: String,
+    pub exists: bool,
+    /// 前面已出现过同一目录
+    pub duplicate: bool,
+}
+
+/// shell 启动文件
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RcFile {
+    /// 文件路径（家目录下的文件以 `~` 开头）
+    pub path: String,
+    /// 文件内容（超过上限时截断）
+    pub content: String,
+    pub truncated: bool,
+}
+
+/// 环境快照
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvSnapshot {
+    pub id: String,
+    pub session_id: String,
+    /// `user@host:port`
+    pub target: String,
+    pub taken_at: i64,
+    /// 用户的登录 shell（`$SHELL`）
+    pub shell: Option<String>,
+    /// exec channel 中的环境变量（非交互、非登录 shell）
+    pub variables: BTreeMap<String, String>,
+    /// 登录 shell（`$SHELL -l`）中的环境变量，无法启动登录 shell 时为空
+    pub login_variables: BTreeMap<String, String>,
+    /// exec 环境中 PATH 的各个目录
+    pub path: Vec<PathEntry>,
+    pub rc_files: Vec<RcFile>,
+}
+
+/// 变量变化
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VariableChange {
+    pub name: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+/// 启动文件变化类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RcFileChangeKind {
+    Added,
+    Removed,
+    Modified,
+}
+
+/// 启动文件变化
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RcFileChange {
+    pub path: String,
+    pub kind: RcFileChangeKind,
+}
+
+/// 两个快照的差异
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvDiff {
+    /// exec 环境变量的增删改
+    pub variables: Vec<VariableChange>,
+    /// 登录 shell 环境变量的增删改
+    pub login_variables: Vec<VariableChange>,
+    /// 只在新快照 PATH 中出现的目录
+    pub path_added: Vec<String>,
+    /// 只在旧快照 PATH 中出现的目录
+    pub path_removed: Vec<String>,
+    /// 共同目录的先后顺序不同（影响同名命令的解析）
+    pub path_reordered: bool,
+    pub rc_files: Vec<RcFileChange>,
+}
+
+/// 构建采集脚本
+pub fn build_command() -> String {
+    let rc_files = RC_FILES
+        .iter()
+        .map(|path| match path.strip_prefix("~/") {
+            Some(rest) => format!("\"$HOME\"/{}", rest),
+            None => path.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    // env -0 以 NUL 分隔，变量值可以包含换行；BusyBox 等不支持时退回按行输出
+    format!(
+        "M='{marker}'; \
+         printf '%sSHELL\\n%s\\n' \"$M\" \"$SHELL\"; \
+         printf '%sENV\\n' \"$M\"; env -0 2>/dev/null || env; \
+         printf '\\n%sLOGIN_ENV\\n' \"$M\"; \
+         if [ -n \"$SHELL\" ]; then \"$SHELL\" -l -c 'env -0 2>/dev/null || env' </dev/null 2>/dev/null; fi; \
+         printf '\\n%sPATH\\n' \"$M\"; \
+         IFS=:; for d in $PATH; do if [ -d \"$d\" ]; then printf 'd %s\\n' \"$d\"; else printf 'x %s\\n' \"$d\"; fi; done; unset IFS; \
+         for f in {rc_files}; do if [ -f \"$f\" ] && [ -r \"$f\" ]; then \
+         printf '%sFILE %s %s\\n' \"$M\" \"$(wc -c < \"$f\" | tr -d ' ')\" \"$f\"; head -c {max} \"$f\"; printf '\\n'; fi; done",
+        marker = SECTION_MARKER,
+        rc_files = rc_files,
+        max = MAX_RC_FILE_BYTES,
+    )
+}
+
+/// 解析采集脚本的输出，会话和目标信息由调用方填写
+pub fn parse_output(output: &str) -> EnvSnapshot {
+    let mut snapshot = EnvSnapshot {
+        id: uuid::Uuid::new_v4().to_string(),
+        session_id: String::new(),
+        target: String::new(),
+        taken_at: chrono::Utc::now().timestamp_millis(),
+        shell: None,
+        variables: BTreeMap::new(),
+        login_variables: BTreeMap::new(),
+        path: Vec::new(),
+        rc_files: Vec::new(),
+    };
+
+    // 脚本在每部分之后补了一个换行，按 "\n标记" 切分时正好去掉；末尾补一个标记处理最后一部分
+    let text = format!("\n{}{}END", output, SECTION_MARKER);
+    let mut files = Vec::new();
+    for section in text.split(&format!("\n{}", SECTION_MARKER)).skip(1) {
+        let (header, body) = section.split_once('\n').unwrap_or((section, ""));
+        match header {
+            "SHELL" => snapshot.shell = Some(body.trim().to_string()).filter(|s| !s.is_empty()),
+            "ENV" => snapshot.variables = parse_env(body),
+            "LOGIN_ENV" => snapshot.login_variables = parse_env(body),
+            "PATH" => snapshot.path = parse_path(body),
+            _ => {
+                if let Some(file) = header.strip_prefix("FILE ") {
+                    let (size, file_path) = file.split_once(' ').unwrap_or(("0", file));
+                    files.push((file_path.to_string(), size.parse::<usize>().unwrap_or(0), body.to_string()));
+                }
+            }
+        }
+    }
+
+    let home = snapshot.variables.get("HOME").cloned();
+    snapshot.rc_files = files
+        .into_iter()
+        .map(|(path, size, content)| RcFile {
+            path: collapse_home(&path, home.as_deref()),
+            content,
+            truncated: size > MAX_RC_FILE_BYTES,
+        })
+        .collect();
+    snapshot
+}
+
+/// 解析 `env -0` 或 `env` 的输出
+///
+/// 按行输出时不含 `=` 的行视为上一个变量值的延续；
+/// 登录 shell 启动时打印的欢迎信息等会混在第一个变量名前，只保留最后一行
+fn parse_env(body: &str) -> BTreeMap<String, String> {
+    let mut variables = BTreeMap::new();
+    let entries: Vec<&str> = if body.contains('\0') {
+        body.split('\0').collect()
+    } else {
+        body.lines().collect()
+    };
+
+    let mut last: Option<String> = None;
+    for entry in entries {
+        match entry.split_once('=') {
+            Some((name, value)) => {
+                let name = name.rsplit('\n').next().unwrap_or(name).trim();
+                if is_variable_name(name) {
+                    variables.insert(name.to_string(), value.to_string());
+                    last = Some(name.to_string());
+                    continue;
+                }
+                append_continuation(&mut variables, last.as_deref(), entry);
+            }
+            None if entry.is_empty() => {}
+            None => append_continuation(&mut variables, last.as_deref(), entry),
+        }
+    }
+    variables
+}
+
+fn append_continuation(variables: &mut BTreeMap<String, String>, last: Option<&str>, line: &str) {
+    if let Some(value) = last.and_then(|name| variables.get_mut(name)) {
+        value.push('\n');
+        value.push_str(line);
+    }
+}
+
+fn is_variable_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn parse_path(body: &str) -> Vec<PathEntry> {
+    let mut seen = HashSet::new();
+    body.lines()
+        .filter_map(|line| {
+            let (flag, dir) = line.split_once(' ')?;
+            Some(PathEntry {
+                exists: flag == "d",
+                duplicate: !seen.insert(dir.to_string()),
+                dir: dir.to_string(),
+            })
+        })
+        .collect()
+}
+
+fn collapse_home(path: &str, home: Option<&str>) -> String {
+    match home.filter(|home| !home.is_empty()).and_then(|home| path.strip_prefix(home)) {
+        Some(rest) if rest.starts_with('/') => format!("~{}", rest),
+        _ => path.to_string(),
+    }
+}
+
+/// 比较两个快照
+pub fn diff(before: &EnvSnapshot, after: &EnvSnapshot) -> EnvDiff {
+    let before_path: Vec<&str> = before.path.iter().filter(|e| !e.duplicate).map(|e| e.dir.as_str()).collect();
+    let after_path: Vec<&str> = after.path.iter().filter(|e| !e.duplicate).map(|e| e.dir.as_str()).collect();
+    let common_before: Vec<&str> = before_path.iter().copied().filter(|d| after_path.contains(d)).collect();
+    let common_after: Vec<&str> = after_path.iter().copied().filter(|d| before_path.contains(d)).collect();
+
+    let before_files: HashMap<&str, &RcFile> = before.rc_files.iter().map(|f| (f.path.as_str(), f)).collect();
+    let after_files: HashMap<&str, &RcFile> = after.rc_files.iter().map(|f| (f.path.as_str(), f)).collect();
+    let mut rc_files: Vec<RcFileChange> = before_files
+        .keys()
+        .chain(after_files.keys().filter(|path| !before_files.contains_key(*path)))
+        .filter_map(|path| {
+            let kind = match (before_files.get(path), after_files.get(path)) {
+                (Some(_), None) => RcFileChangeKind::Removed,
+                (None, Some(_)) => RcFileChangeKind::Added,
+                (Some(a), Some(b)) if a.content != b.content => RcFileChangeKind::Modified,
+                _ => return None,
+            };
+            Some(RcFileChange { path: path.to_string(), kind })
+        })
+        .collect();
+    rc_files.sort_by(|a, b| a.path.cmp(&b.path));
+
+    EnvDiff {
+        variables: diff_variables(&before.variables, &after.variables),
+        login_variables: diff_variables(&before.login_variables, &after.login_variables),
+        path_added: after_path.iter().filter(|d| !before_path.contains(d)).map(|d| d.to_string()).collect(),
+        path_removed: before_path.iter().filter(|d| !after_path.contains(d)).map(|d| d.to_string()).collect(),
+        path_reordered: common_before != common_after,
+        rc_files,
+    }
+}
+
+fn diff_variables(before: &BTreeMap<String, String>, after: &BTreeMap<String, String>) -> Vec<VariableChange> {
+    let names: std::collections::BTreeSet<&String> = before.keys().chain(after.keys()).collect();
+    names
+        .into_iter()
+        .filter_map(|name| {
+            let (old, new) = (before.get(name), after.get(name));
+            (old != new).then(|| VariableChange {
+                name: name.clone(),
+                before: old.cloned(),
+                after: new.cloned(),
+            })
+        })
+        .collect()
+}
+
+/// 读取某个会话保存的快照（按时间从新到旧）
+pub fn load_snapshots(session_id: &str) -> Result<Vec<EnvSnapshot>> {
+    Ok(load_store()?.remove(session_id).unwrap_or_default())
+}
+
+/// 保存快照，每个会话只保留最近的 [`MAX_SNAPSHOTS_PER_SESSION`] 个
+pub fn save_snapshot(snapshot: &EnvSnapshot) -> Result<()> {
+    let mut store = load_store()?;
+    let snapshots = store.entry(snapshot.session_id.clone()).or_default();
+    snapshots.insert(0, snapshot.clone());
+    snapshots.truncate(MAX_SNAPSHOTS_PER_SESSION);
+
+    let json = serde_json::to_string_pretty(&store)
+        .map_err(|e| SSHError::Storage(format!("Failed to serialize environment snapshots: {}", e)))?;
+    let path = store_path()?;
+    let temp_path = path.with_extension("tmp");
+    std::fs::write(&temp_path, json)?;
+    std::fs::rename(&temp_path, &path)?;
+    Ok(())
+}
+
+/// 按 ID 查找快照
+pub fn find_snapshot(snapshot_id: &str) -> Result<EnvSnapshot> {
+    load_store()?
+        .into_values()
+        .flatten()
+        .find(|snapshot| snapshot.id == snapshot_id)
+        .ok_or_else(|| SSHError::NotFound(format!("Environment snapshot not found: {}", snapshot_id)))
+}
+
+fn load_store() -> Result<HashMap<String, Vec<EnvSnapshot>>> {
+    let path = store_path()?;
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+    let text = std::fs::read_to_string(path)?;
+    serde_json::from_str(&text)
+        .map_err(|e| SSHError::Storage(format!("Failed to parse environment snapshots: {}", e)))
+}
+
+/// 快照文件路径
+fn store_path() -> Result<PathBuf> {
+    Ok(Storage::get_app_storage_dir()?.join("env_snapshots.json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OUTPUT: &str = "@@SSHT-ENV SHELL\n/bin/bash\n\
+        @@SSHT-ENV ENV\nHOME=/home/dev\0PATH=/usr/bin:/home/dev/bin\0MULTI=a\nb\0\n\
+        @@SSHT-ENV LOGIN_ENV\nWelcome!\nHOME=/home/dev\nPATH=/home/dev/.cargo/bin:/usr/bin\n\n\
+        @@SSHT-ENV PATH\nd /usr/bin\nx /home/dev/bin\nd /usr/bin\n\
+        @@SSHT-ENV FILE 20000 /home/dev/.bashrc\nexport PATH=$HOME/.cargo/bin:$PATH\n";
+
+    #[test]
+    fn test_parse_output() {
+        let snapshot = parse_output(OUTPUT);
+        assert_eq!(snapshot.shell.as_deref(), Some("/bin/bash"));
+        assert_eq!(snapshot.variables["PATH"], "/usr/bin:/home/dev/bin");
+        assert_eq!(snapshot.variables["MULTI"], "a\nb");
+        // 登录 shell 的欢迎信息不会变成变量
+        assert_eq!(snapshot.login_variables.len(), 2);
+        assert_eq!(snapshot.login_variables["PATH"], "/home/dev/.cargo/bin:/usr/bin");
+        assert_eq!(
+            snapshot.path,
+            vec![
+                PathEntry { dir: "/usr/bin".to_string(), exists: true, duplicate: false },
+                PathEntry { dir: "/home/dev/bin".to_string(), exists: false, duplicate: false },
+                PathEntry { dir: "/usr/bin".to_string(), exists: true, duplicate: true },
+            ]
+        );
+        assert_eq!(snapshot.rc_files.len(), 1);
+        assert_eq!(snapshot.rc_files[0].path, "~/.bashrc");
+        assert!(snapshot.rc_files[0].truncated);
+    }
+
+    #[test]
+    fn test_diff() {
+        let before = parse_output(OUTPUT);
+        let after = parse_output(
+            "@@SSHT-ENV ENV\nHOME=/home/dev\nPATH=/opt/bin:/home/dev/bin:/usr/bin\n\
+             @@SSHT-ENV PATH\nd /opt/bin\nx /home/dev/bin\nd /usr/bin\n",
+        );
+        let diff = diff(&before, &after);
+        assert_eq!(
+            diff.variables.iter().map(|c| c.name.as_str()).collect::<Vec<_>>(),
+            ["MULTI", "PATH"]
+        );
+        assert_eq!(diff.variables[0].after, None);
+        assert_eq!(diff.path_added, ["/opt/bin"]);
+        assert!(diff.path_removed.is_empty());
+        assert!(diff.path_reordered);
+        assert_eq!(diff.rc_files, vec![RcFileChange { path: "~/.bashrc".to_string(), kind: RcFileChangeKind::Removed }]);
+        assert_eq!(diff.login_variables.len(), 2);
+    }
+}
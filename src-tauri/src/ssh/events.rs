@@ -0,0 +1,123 @@
+//! 会话变更事件
+//!
+//! 记录会话配置和连接实例的增量变更，通过 Tauri 事件通知前端，
+//! 并支持前端按 revision 拉取错过的变更
+
+use crate::ssh::session::SessionInfo;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter};
+
+/// 保留的变更记录数量，超出后 since 查询会退化为全量列表
+const MAX_RETAINED_CHANGES: usize = 512;
+
+/// 变更类型
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum SessionChangeKind {
+    Added,
+    Updated,
+    Removed,
+    ConnectionStatusChanged,
+}
+
+impl SessionChangeKind {
+    /// 对应的 Tauri 事件名
+    pub fn event_name(&self) -> &'static str {
+        match self {
+            SessionChangeKind::Added => "session-added",
+            SessionChangeKind::Updated => "session-updated",
+            SessionChangeKind::Removed => "session-removed",
+            SessionChangeKind::ConnectionStatusChanged => "connection-status-changed",
+        }
+    }
+}
+
+/// 单条变更记录
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionChange {
+    pub revision: u64,
+    pub kind: SessionChangeKind,
+    /// 会话配置 ID 或连接实例 ID
+    pub id: String,
+    /// 变更后的会话信息，删除时为空
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub info: Option<SessionInfo>,
+}
+
+/// since 查询结果
+///
+/// - `full` 为 true 时 `sessions` 是完整列表，前端应整体替换
+/// - 否则只需按顺序应用 `changes`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionListDelta {
+    pub revision: u64,
+    pub full: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sessions: Option<Vec<SessionInfo>>,
+    pub changes: Vec<SessionChange>,
+}
+
+/// 会话变更日志
+pub struct SessionChangeLog {
+    revision: AtomicU64,
+    entries: Mutex<VecDeque<SessionChange>>,
+    app_handle: AppHandle,
+}
+
+impl SessionChangeLog {
+    pub fn new(app_handle: AppHandle) -> Self {
+        Self {
+            revision: AtomicU64::new(0),
+            entries: Mutex::new(VecDeque::new()),
+            app_handle,
+        }
+    }
+
+    /// 当前 revision
+    pub fn revision(&self) -> u64 {
+        self.revision.load(Ordering::SeqCst)
+    }
+
+    /// 记录一条变更并发送对应事件
+    pub fn record(&self, kind: SessionChangeKind, id: &str, info: Option<SessionInfo>) {
+        let change = {
+            let mut entries = self.entries.lock().unwrap();
+            let change = SessionChange {
+                revision: self.revision.fetch_add(1, Ordering::SeqCst) + 1,
+                kind,
+                id: id.to_string(),
+                info,
+            };
+            entries.push_back(change.clone());
+            while entries.len() > MAX_RETAINED_CHANGES {
+                entries.pop_front();
+            }
+            change
+        };
+
+        if let Err(e) = self.app_handle.emit(kind.event_name(), &change) {
+            tracing::warn!("Failed to emit {}: {}", kind.event_name(), e);
+        }
+    }
+
+    /// 获取指定 revision 之后的所有变更
+    ///
+    /// 如果所需的变更已被淘汰，返回 None，调用方应改为返回全量列表
+    pub fn since(&self, revision: u64) -> Option<Vec<SessionChange>> {
+        let entries = self.entries.lock().unwrap();
+        let current = self.revision();
+        if revision > current {
+            return None;
+        }
+        let oldest = entries.front().map(|c| c.revision).unwrap_or(current + 1);
+        if revision + 1 < oldest && revision < current {
+            return None;
+        }
+        Some(entries.iter().filter(|c| c.revision > revision).cloned().collect())
+    }
+}
@@ -0,0 +1,737 @@
+//! 端口转发
+//!
+//! - 本地转发（`ssh -L`）：在本地监听 TCP 端口，将接受的连接通过现有 SSH 连接的
+//!   `direct-tcpip` channel 转发到远程主机
+//! - 远程转发（`ssh -R`）：请求服务器监听端口（`tcpip-forward`），服务器转发回来的
+//!   `forwarded-tcpip` channel 连接到本地目标。远程转发随会话保存，重连后自动重新建立
+//! - 动态转发（`ssh -D`）：在本地提供 SOCKS5 代理，按客户端请求的目标地址
+//!   打开 `direct-tcpip` channel
+//!
+//! 每条转发维护字节计数，并定期以事件形式发送给前端。
+
+use crate::error::{Result, SSHError};
+use crate::ssh::connection::ConnectionInstance;
+use crate::ssh::manager::SSHManager;
+use crate::ssh::session::SessionStatus;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::Mutex;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, warn};
+
+/// 转发统计事件名
+pub const FORWARD_STATS_EVENT: &str = "port-forward-stats";
+
+/// 统计事件发送间隔
+const STATS_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 单次读写缓冲区大小
+const PIPE_BUFFER_SIZE: usize = 32 * 1024;
+
+/// 默认监听地址（仅本机）
+const DEFAULT_BIND_ADDRESS: &str = "127.0.0.1";
+
+/// 转发类型
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum ForwardKind {
+    /// 本地转发：本地端口 -> 远程主机
+    Local,
+    /// 远程转发：服务器端口 -> 本地目标
+    Remote,
+    /// 动态转发：本地 SOCKS5 代理，目标由客户端指定
+    Dynamic,
+}
+
+/// 远程转发配置（随会话保存）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteForwardSpec {
+    /// 服务器监听地址，空字符串表示由服务器决定（受 GatewayPorts 限制）
+    #[serde(default)]
+    pub remote_bind_address: String,
+    /// 服务器监听端口，0 表示由服务器分配
+    pub remote_port: u16,
+    pub local_host: String,
+    pub local_port: u16,
+}
+
+/// 连接上已注册的远程转发
+pub struct RemoteForward {
+    info: ForwardInfo,
+    spec: RemoteForwardSpec,
+    stats: Arc<ForwardStats>,
+    cancel: CancellationToken,
+}
+
+/// 连接的远程转发表，重连后保留并重新向服务器请求
+pub type RemoteForwardTable = Arc<std::sync::Mutex<Vec<RemoteForward>>>;
+
+/// 转发信息（包含实时统计）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForwardInfo {
+    pub id: String,
+    pub connection_id: String,
+    pub kind: ForwardKind,
+    /// 监听地址（本地转发为本机，远程转发为服务器）
+    pub bind_address: String,
+    /// 实际监听的端口（请求端口为 0 时由系统分配）
+    pub bind_port: u16,
+    /// 转发目标（本地转发由服务器连接，远程转发由本机连接，动态转发为空）
+    pub target_host: String,
+    pub target_port: u16,
+    pub created_at: i64,
+    /// 本地 -> 远程的字节数
+    pub bytes_sent: u64,
+    /// 远程 -> 本地的字节数
+    pub bytes_received: u64,
+    pub active_connections: u64,
+    pub total_connections: u64,
+}
+
+/// 转发的字节和连接计数
+#[derive(Default)]
+pub(crate) struct ForwardStats {
+    bytes_sent: AtomicU64,
+    bytes_received: AtomicU64,
+    active_connections: AtomicU64,
+    total_connections: AtomicU64,
+}
+
+/// 转发条目
+struct ForwardEntry {
+    info: ForwardInfo,
+    stats: Arc<ForwardStats>,
+    cancel: CancellationToken,
+}
+
+impl ForwardEntry {
+    /// 带最新统计的转发信息
+    fn snapshot(&self) -> ForwardInfo {
+        snapshot(&self.info, &self.stats)
+    }
+}
+
+fn snapshot(info: &ForwardInfo, stats: &ForwardStats) -> ForwardInfo {
+    ForwardInfo {
+        bytes_sent: stats.bytes_sent.load(Ordering::Relaxed),
+        bytes_received: stats.bytes_received.load(Ordering::Relaxed),
+        active_connections: stats.active_connections.load(Ordering::Relaxed),
+        total_connections: stats.total_connections.load(Ordering::Relaxed),
+        ..info.clone()
+    }
+}
+
+/// 端口转发管理器
+pub struct ForwardManager {
+    ssh_manager: Arc<SSHManager>,
+    app_handle: AppHandle,
+    forwards: Arc<Mutex<HashMap<String, ForwardEntry>>>,
+}
+
+impl ForwardManager {
+    pub fn new(ssh_manager: Arc<SSHManager>, app_handle: AppHandle) -> Self {
+        Self {
+            ssh_manager,
+            app_handle,
+            forwards: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// 创建本地转发
+    ///
+    /// `bind_port` 为 0 时由系统分配端口，实际端口通过返回值获取
+    pub async fn create_local(
+        &self,
+        connection_id: &str,
+        bind_address: Option<String>,
+        bind_port: u16,
+        remote_host: String,
+        remote_port: u16,
+    ) -> Result<ForwardInfo> {
+        if remote_host.trim().is_empty() || remote_port == 0 {
+            return Err(SSHError::ConnectionFailed("远程地址不能为空".to_string()));
+        }
+        self.listen(connection_id, ForwardKind::Local, bind_address, bind_port, remote_host.trim(), remote_port)
+            .await
+    }
+
+    /// 创建动态转发（本地 SOCKS5 代理）
+    ///
+    /// `bind_port` 为 0 时由系统分配端口，实际端口通过返回值获取
+    pub async fn create_dynamic(
+        &self,
+        connection_id: &str,
+        bind_address: Option<String>,
+        bind_port: u16,
+    ) -> Result<ForwardInfo> {
+        self.listen(connection_id, ForwardKind::Dynamic, bind_address, bind_port, "", 0)
+            .await
+    }
+
+    /// 在本地监听端口，并将接受的连接通过 SSH 连接转发
+    async fn listen(
+        &self,
+        connection_id: &str,
+        kind: ForwardKind,
+        bind_address: Option<String>,
+        bind_port: u16,
+        target_host: &str,
+        target_port: u16,
+    ) -> Result<ForwardInfo> {
+        let connection = self.ssh_manager.get_connection(connection_id).await?;
+        if !matches!(connection.status().await, SessionStatus::Connected) {
+            return Err(SSHError::NotConnected);
+        }
+
+        let bind_address = bind_address
+            .map(|a| a.trim().to_string())
+            .filter(|a| !a.is_empty())
+            .unwrap_or_else(|| DEFAULT_BIND_ADDRESS.to_string());
+        let listener = TcpListener::bind((bind_address.as_str(), bind_port))
+            .await
+            .map_err(|e| SSHError::Io(format!("无法监听 {}:{}: {}", bind_address, bind_port, e)))?;
+        let local_addr = listener.local_addr()?;
+
+        let info = ForwardInfo {
+            id: uuid::Uuid::new_v4().to_string(),
+            connection_id: connection_id.to_string(),
+            kind,
+            bind_address,
+            bind_port: local_addr.port(),
+            target_host: target_host.to_string(),
+            target_port,
+            created_at: chrono::Utc::now().timestamp(),
+            bytes_sent: 0,
+            bytes_received: 0,
+            active_connections: 0,
+            total_connections: 0,
+        };
+        let stats = Arc::new(ForwardStats::default());
+        let cancel = CancellationToken::new();
+
+        info!(
+            "{:?} forward {} created: {} -> {}:{} via {}",
+            info.kind, info.id, local_addr, info.target_host, info.target_port, connection_id
+        );
+
+        self.spawn_accept_loop(listener, info.clone(), stats.clone(), cancel.clone());
+        spawn_stats_emitter(self.app_handle.clone(), info.clone(), stats.clone(), cancel.clone());
+
+        self.forwards.lock().await.insert(
+            info.id.clone(),
+            ForwardEntry { info: info.clone(), stats, cancel },
+        );
+
+        Ok(info)
+    }
+
+    /// 创建远程转发，并保存到连接的远程转发表（重连后自动重新建立）
+    pub async fn create_remote(&self, connection_id: &str, spec: RemoteForwardSpec) -> Result<ForwardInfo> {
+        let connection = self.ssh_manager.get_connection(connection_id).await?;
+        if !matches!(connection.status().await, SessionStatus::Connected) {
+            return Err(SSHError::NotConnected);
+        }
+        if spec.local_host.trim().is_empty() || spec.local_port == 0 {
+            return Err(SSHError::ConnectionFailed("本地目标地址不能为空".to_string()));
+        }
+
+        let bound_port = connection
+            .request_remote_forward(&spec.remote_bind_address, spec.remote_port)
+            .await?;
+
+        let info = ForwardInfo {
+            id: uuid::Uuid::new_v4().to_string(),
+            connection_id: connection_id.to_string(),
+            kind: ForwardKind::Remote,
+            bind_address: spec.remote_bind_address.clone(),
+            bind_port: bound_port,
+            target_host: spec.local_host.trim().to_string(),
+            target_port: spec.local_port,
+            created_at: chrono::Utc::now().timestamp(),
+            bytes_sent: 0,
+            bytes_received: 0,
+            active_connections: 0,
+            total_connections: 0,
+        };
+        let stats = Arc::new(ForwardStats::default());
+        let cancel = CancellationToken::new();
+
+        info!(
+            "Remote forward {} created: {}:{} -> {}:{} via {}",
+            info.id, info.bind_address, bound_port, info.target_host, info.target_port, connection_id
+        );
+
+        spawn_stats_emitter(self.app_handle.clone(), info.clone(), stats.clone(), cancel.clone());
+        if let Ok(mut table) = connection.remote_forwards.lock() {
+            table.push(RemoteForward { info: info.clone(), spec, stats, cancel });
+        }
+
+        Ok(info)
+    }
+
+    /// 删除远程转发，返回所属会话 ID 和转发配置（用于更新保存的配置）
+    pub async fn delete_remote(&self, id: &str) -> Result<(String, RemoteForwardSpec)> {
+        for connection in self.ssh_manager.connections().await {
+            let removed = connection.remote_forwards.lock().ok().and_then(|mut table| {
+                let index = table.iter().position(|forward| forward.info.id == id)?;
+                Some(table.remove(index))
+            });
+
+            if let Some(forward) = removed {
+                forward.cancel.cancel();
+                if let Err(e) = connection
+                    .cancel_remote_forward(&forward.spec.remote_bind_address, forward.info.bind_port)
+                    .await
+                {
+                    // 连接已断开时服务器端的监听也已不存在
+                    debug!("Failed to cancel remote forward {}: {}", id, e);
+                }
+                info!("Remote forward {} deleted", id);
+                return Ok((connection.session_id.clone(), forward.spec));
+            }
+        }
+
+        Err(SSHError::NotFound(format!("Forward not found: {}", id)))
+    }
+
+    /// 列出转发（可按连接过滤）
+    pub async fn list(&self, connection_id: Option<&str>) -> Vec<ForwardInfo> {
+        let mut list: Vec<ForwardInfo> = self
+            .forwards
+            .lock()
+            .await
+            .values()
+            .filter(|entry| connection_id.is_none_or(|id| entry.info.connection_id == id))
+            .map(ForwardEntry::snapshot)
+            .collect();
+
+        for connection in self.ssh_manager.connections().await {
+            if connection_id.is_some_and(|id| connection.id != id) {
+                continue;
+            }
+            if let Ok(table) = connection.remote_forwards.lock() {
+                list.extend(table.iter().map(|forward| snapshot(&forward.info, &forward.stats)));
+            }
+        }
+
+        list.sort_by_key(|info| info.created_at);
+        list
+    }
+
+    /// 删除转发，关闭监听端口和所有已转发的连接
+    pub async fn delete(&self, id: &str) -> Result<()> {
+        let entry = self
+            .forwards
+            .lock()
+            .await
+            .remove(id)
+            .ok_or_else(|| SSHError::NotFound(format!("Forward not found: {}", id)))?;
+        entry.cancel.cancel();
+        info!("Forward {} deleted", id);
+        Ok(())
+    }
+
+    /// 删除某个连接的全部转发（连接断开或关闭时调用）
+    pub async fn delete_for_connection(&self, connection_id: &str) {
+        let mut forwards = self.forwards.lock().await;
+        forwards.retain(|_, entry| {
+            if entry.info.connection_id == connection_id {
+                entry.cancel.cancel();
+                false
+            } else {
+                true
+            }
+        });
+    }
+
+    /// 接受本地连接并逐个转发
+    fn spawn_accept_loop(
+        &self,
+        listener: TcpListener,
+        info: ForwardInfo,
+        stats: Arc<ForwardStats>,
+        cancel: CancellationToken,
+    ) {
+        let ssh_manager = self.ssh_manager.clone();
+
+        tokio::spawn(async move {
+            loop {
+                let (socket, peer) = tokio::select! {
+                    _ = cancel.cancelled() => break,
+                    accepted = listener.accept() => match accepted {
+                        Ok(accepted) => accepted,
+                        Err(e) => {
+                            warn!("Forward {} accept failed: {}", info.id, e);
+                            continue;
+                        }
+                    },
+                };
+
+                debug!("Forward {} accepted connection from {}", info.id, peer);
+                let ssh_manager = ssh_manager.clone();
+                let info = info.clone();
+                let stats = stats.clone();
+                let cancel = cancel.child_token();
+
+                tokio::spawn(async move {
+                    stats.total_connections.fetch_add(1, Ordering::Relaxed);
+                    stats.active_connections.fetch_add(1, Ordering::Relaxed);
+
+                    tokio::select! {
+                        _ = cancel.cancelled() => {}
+                        result = forward_socket(&ssh_manager, &info, socket, peer, &stats) => {
+                            if let Err(e) = result {
+                                warn!("Forward {} connection from {} failed: {}", info.id, peer, e);
+                            }
+                        }
+                    }
+
+                    stats.active_connections.fetch_sub(1, Ordering::Relaxed);
+                });
+            }
+            debug!("Forward {} listener closed", info.id);
+        });
+    }
+}
+
+/// 统计变化时定期发送事件
+fn spawn_stats_emitter(app_handle: AppHandle, info: ForwardInfo, stats: Arc<ForwardStats>, cancel: CancellationToken) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(STATS_INTERVAL);
+        let mut last = (0, 0, 0, 0);
+
+        loop {
+            tokio::select! {
+                _ = cancel.cancelled() => break,
+                _ = interval.tick() => {}
+            }
+
+            let current = snapshot(&info, &stats);
+            let key = (
+                current.bytes_sent,
+                current.bytes_received,
+                current.active_connections,
+                current.total_connections,
+            );
+            if key != last {
+                last = key;
+                let _ = app_handle.emit(FORWARD_STATS_EVENT, &current);
+            }
+        }
+    });
+}
+
+/// 连接建立（或重连）后向服务器重新请求远程转发
+///
+/// 首次连接时还会按会话保存的配置创建远程转发
+pub async fn restore_remote_forwards(app_handle: &AppHandle, connection: &ConnectionInstance) {
+    let pending: Vec<(String, RemoteForwardSpec)> = {
+        let Ok(mut table) = connection.remote_forwards.lock() else {
+            return;
+        };
+
+        if table.is_empty() {
+            for spec in &connection.config.remote_forwards {
+                let info = ForwardInfo {
+                    id: uuid::Uuid::new_v4().to_string(),
+                    connection_id: connection.id.clone(),
+                    kind: ForwardKind::Remote,
+                    bind_address: spec.remote_bind_address.clone(),
+                    bind_port: spec.remote_port,
+                    target_host: spec.local_host.clone(),
+                    target_port: spec.local_port,
+                    created_at: chrono::Utc::now().timestamp(),
+                    bytes_sent: 0,
+                    bytes_received: 0,
+                    active_connections: 0,
+                    total_connections: 0,
+                };
+                let stats = Arc::new(ForwardStats::default());
+                let cancel = CancellationToken::new();
+                spawn_stats_emitter(app_handle.clone(), info.clone(), stats.clone(), cancel.clone());
+                table.push(RemoteForward { info, spec: spec.clone(), stats, cancel });
+            }
+        }
+
+        table.iter().map(|forward| (forward.info.id.clone(), forward.spec.clone())).collect()
+    };
+
+    for (id, spec) in pending {
+        match connection.request_remote_forward(&spec.remote_bind_address, spec.remote_port).await {
+            Ok(bound_port) => {
+                info!("Remote forward {} established on port {}", id, bound_port);
+                if let Ok(mut table) = connection.remote_forwards.lock() {
+                    if let Some(forward) = table.iter_mut().find(|forward| forward.info.id == id) {
+                        forward.info.bind_port = bound_port;
+                    }
+                }
+            }
+            Err(e) => warn!("Failed to establish remote forward {}: {}", id, e),
+        }
+    }
+}
+
+/// 服务器转发回来的连接的路由信息
+struct RemoteRoute {
+    id: String,
+    target_host: String,
+    target_port: u16,
+    stats: Arc<ForwardStats>,
+    cancel: CancellationToken,
+}
+
+/// 查找服务器转发回来的连接对应的远程转发（按服务器监听端口匹配）
+fn find_remote_route(table: &RemoteForwardTable, connected_port: u32) -> Option<RemoteRoute> {
+    let table = table.lock().ok()?;
+    table
+        .iter()
+        .find(|forward| u32::from(forward.info.bind_port) == connected_port)
+        .map(|forward| RemoteRoute {
+            id: forward.info.id.clone(),
+            target_host: forward.info.target_host.clone(),
+            target_port: forward.info.target_port,
+            stats: forward.stats.clone(),
+            cancel: forward.cancel.child_token(),
+        })
+}
+
+/// 转发表中是否登记了服务器端口 `connected_port`
+pub(crate) fn has_remote_route(table: &RemoteForwardTable, connected_port: u32) -> bool {
+    find_remote_route(table, connected_port).is_some()
+}
+
+/// 将服务器转发回来的 channel 连接到本地目标
+pub(crate) fn spawn_forwarded_channel<S>(table: &RemoteForwardTable, connected_port: u32, originator: String, stream: S)
+where
+    S: AsyncRead + AsyncWrite + Send + 'static,
+{
+    let Some(RemoteRoute { id, target_host, target_port, stats, cancel }) = find_remote_route(table, connected_port) else {
+        warn!("No remote forward registered for port {}, rejecting connection from {}", connected_port, originator);
+        return;
+    };
+
+    tokio::spawn(async move {
+        stats.total_connections.fetch_add(1, Ordering::Relaxed);
+        stats.active_connections.fetch_add(1, Ordering::Relaxed);
+
+        let result = tokio::select! {
+            _ = cancel.cancelled() => Ok(()),
+            result = async {
+                let socket = TcpStream::connect((target_host.as_str(), target_port)).await?;
+                let _ = socket.set_nodelay(true);
+
+                let (mut local_read, mut local_write) = socket.into_split();
+                let (mut remote_read, mut remote_write) = tokio::io::split(stream);
+
+                // 远程 -> 本地计为接收，本地 -> 远程计为发送
+                tokio::try_join!(
+                    pipe(&mut remote_read, &mut local_write, &stats.bytes_received),
+                    pipe(&mut local_read, &mut remote_write, &stats.bytes_sent),
+                )?;
+                Ok::<(), SSHError>(())
+            } => result,
+        };
+        if let Err(e) = result {
+            warn!("Remote forward {} connection from {} failed: {}", id, originator, e);
+        }
+
+        stats.active_connections.fetch_sub(1, Ordering::Relaxed);
+    });
+}
+
+/// 通过 SSH 连接转发单个本地连接（动态转发先完成 SOCKS5 握手）
+async fn forward_socket(
+    ssh_manager: &SSHManager,
+    info: &ForwardInfo,
+    socket: TcpStream,
+    peer: SocketAddr,
+    stats: &ForwardStats,
+) -> Result<()> {
+    let _ = socket.set_nodelay(true);
+
+    let mut socket = socket;
+    let (target_host, target_port) = match info.kind {
+        ForwardKind::Dynamic => socks5_handshake(&mut socket).await?,
+        _ => (info.target_host.clone(), info.target_port),
+    };
+
+    // 每次都重新获取连接，重连后的转发使用新的后端
+    let opened = match ssh_manager.get_connection(&info.connection_id).await {
+        Ok(connection) => connection.open_direct_tcpip(&target_host, target_port, peer).await,
+        Err(e) => Err(e),
+    };
+    if info.kind == ForwardKind::Dynamic {
+        let reply = if opened.is_ok() { SOCKS5_REPLY_SUCCEEDED } else { SOCKS5_REPLY_HOST_UNREACHABLE };
+        socks5_reply(&mut socket, reply).await?;
+    }
+    // 租约与 channel 一起保持到转发结束
+    let (channel, _lease) = opened?;
+
+    let (mut local_read, mut local_write) = socket.into_split();
+    let (mut remote_read, mut remote_write) = tokio::io::split(channel.into_stream());
+
+    let upstream = pipe(&mut local_read, &mut remote_write, &stats.bytes_sent);
+    let downstream = pipe(&mut remote_read, &mut local_write, &stats.bytes_received);
+
+    // 任一方向出错即结束；正常 EOF 时等待另一方向完成
+    tokio::try_join!(upstream, downstream)?;
+    Ok(())
+}
+
+const SOCKS5_VERSION: u8 = 0x05;
+const SOCKS5_METHOD_NO_AUTH: u8 = 0x00;
+const SOCKS5_METHOD_NONE_ACCEPTABLE: u8 = 0xff;
+const SOCKS5_CMD_CONNECT: u8 = 0x01;
+const SOCKS5_ATYP_IPV4: u8 = 0x01;
+const SOCKS5_ATYP_DOMAIN: u8 = 0x03;
+const SOCKS5_ATYP_IPV6: u8 = 0x04;
+const SOCKS5_REPLY_SUCCEEDED: u8 = 0x00;
+const SOCKS5_REPLY_HOST_UNREACHABLE: u8 = 0x04;
+const SOCKS5_REPLY_COMMAND_NOT_SUPPORTED: u8 = 0x07;
+const SOCKS5_REPLY_ADDRESS_NOT_SUPPORTED: u8 = 0x08;
+
+/// SOCKS5 握手（RFC 1928），仅支持无认证和 CONNECT 命令，返回目标地址
+async fn socks5_handshake<S>(stream: &mut S) -> Result<(String, u16)>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    // 方法协商：VER NMETHODS METHODS...
+    let mut header = [0u8; 2];
+    stream.read_exact(&mut header).await?;
+    if header[0] != SOCKS5_VERSION {
+        return Err(SSHError::NotSupported(format!("SOCKS 版本 {}", header[0])));
+    }
+    let mut methods = vec![0u8; header[1] as usize];
+    stream.read_exact(&mut methods).await?;
+    if !methods.contains(&SOCKS5_METHOD_NO_AUTH) {
+        stream.write_all(&[SOCKS5_VERSION, SOCKS5_METHOD_NONE_ACCEPTABLE]).await?;
+        return Err(SSHError::NotSupported("SOCKS5 客户端要求认证".to_string()));
+    }
+    stream.write_all(&[SOCKS5_VERSION, SOCKS5_METHOD_NO_AUTH]).await?;
+
+    // 请求：VER CMD RSV ATYP DST.ADDR DST.PORT
+    let mut request = [0u8; 4];
+    stream.read_exact(&mut request).await?;
+    if request[1] != SOCKS5_CMD_CONNECT {
+        socks5_reply(stream, SOCKS5_REPLY_COMMAND_NOT_SUPPORTED).await?;
+        return Err(SSHError::NotSupported(format!("SOCKS5 命令 {}", request[1])));
+    }
+
+    let host = match request[3] {
+        SOCKS5_ATYP_IPV4 => {
+            let mut addr = [0u8; 4];
+            stream.read_exact(&mut addr).await?;
+            std::net::Ipv4Addr::from(addr).to_string()
+        }
+        SOCKS5_ATYP_IPV6 => {
+            let mut addr = [0u8; 16];
+            stream.read_exact(&mut addr).await?;
+            std::net::Ipv6Addr::from(addr).to_string()
+        }
+        SOCKS5_ATYP_DOMAIN => {
+            let len = stream.read_u8().await? as usize;
+            let mut domain = vec![0u8; len];
+            stream.read_exact(&mut domain).await?;
+            String::from_utf8_lossy(&domain).to_string()
+        }
+        atyp => {
+            socks5_reply(stream, SOCKS5_REPLY_ADDRESS_NOT_SUPPORTED).await?;
+            return Err(SSHError::NotSupported(format!("SOCKS5 地址类型 {}", atyp)));
+        }
+    };
+    let port = stream.read_u16().await?;
+
+    Ok((host, port))
+}
+
+/// 发送 SOCKS5 应答，绑定地址固定为 0.0.0.0:0
+async fn socks5_reply<S>(stream: &mut S, reply: u8) -> Result<()>
+where
+    S: AsyncWrite + Unpin,
+{
+    stream
+        .write_all(&[SOCKS5_VERSION, reply, 0x00, SOCKS5_ATYP_IPV4, 0, 0, 0, 0, 0, 0])
+        .await?;
+    Ok(())
+}
+
+/// 单向复制数据并累计字节数，读到 EOF 后关闭写端
+async fn pipe<R, W>(reader: &mut R, writer: &mut W, counter: &AtomicU64) -> Result<()>
+where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    let mut buffer = vec![0u8; PIPE_BUFFER_SIZE];
+    loop {
+        let n = reader.read(&mut buffer).await?;
+        if n == 0 {
+            break;
+        }
+        writer.write_all(&buffer[..n]).await?;
+        counter.fetch_add(n as u64, Ordering::Relaxed);
+    }
+    let _ = writer.shutdown().await;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_pipe_counts_bytes() {
+        let (mut client, server) = tokio::io::duplex(64);
+        let (mut server_read, _server_write) = tokio::io::split(server);
+        let mut sink = Vec::new();
+        let counter = AtomicU64::new(0);
+
+        client.write_all(b"hello forward").await.unwrap();
+        drop(client);
+
+        pipe(&mut server_read, &mut sink, &counter).await.unwrap();
+        assert_eq!(sink, b"hello forward");
+        assert_eq!(counter.load(Ordering::Relaxed), 13);
+    }
+
+    #[tokio::test]
+    async fn test_socks5_handshake_domain() {
+        let (mut client, mut server) = tokio::io::duplex(256);
+
+        let handshake = tokio::spawn(async move { socks5_handshake(&mut server).await });
+
+        client.write_all(&[0x05, 0x02, 0x02, 0x00]).await.unwrap();
+        let mut method = [0u8; 2];
+        client.read_exact(&mut method).await.unwrap();
+        assert_eq!(method, [0x05, 0x00]);
+
+        let mut request = vec![0x05, 0x01, 0x00, 0x03, 11];
+        request.extend_from_slice(b"example.com");
+        request.extend_from_slice(&443u16.to_be_bytes());
+        client.write_all(&request).await.unwrap();
+
+        let (host, port) = handshake.await.unwrap().unwrap();
+        assert_eq!(host, "example.com");
+        assert_eq!(port, 443);
+    }
+
+    #[tokio::test]
+    async fn test_socks5_rejects_auth_only_client() {
+        let (mut client, mut server) = tokio::io::duplex(64);
+
+        client.write_all(&[0x05, 0x01, 0x02]).await.unwrap();
+        assert!(socks5_handshake(&mut server).await.is_err());
+
+        let mut reply = [0u8; 2];
+        client.read_exact(&mut reply).await.unwrap();
+        assert_eq!(reply, [0x05, 0xff]);
+    }
+}
@@ -0,0 +1,141 @@
+//! 多地址连接（Happy Eyeballs，参考 RFC 8305）
+//!
+//! 将主机名解析为全部 A/AAAA 记录，IPv6 与 IPv4 交替排列，
+//! 依次错开启动连接尝试，第一个成功的连接胜出。
+//! 避免系统默认顺序下 IPv6 不通时长时间卡在第一个地址上。
+
+use crate::error::{Result, SSHError};
+use crate::ssh::dns::{self, DnsResolverConfig};
+use futures::stream::{FuturesUnordered, StreamExt};
+use std::net::SocketAddr;
+use std::time::Duration;
+use tokio::net::TcpStream;
+
+/// 相邻两次连接尝试之间的间隔（RFC 8305 推荐 250ms）
+const ATTEMPT_DELAY: Duration = Duration::from_millis(250);
+
+/// 单个地址的连接超时
+const ATTEMPT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 解析主机名并建立 TCP 连接
+///
+/// `resolver` 为空时使用系统解析器；自定义解析器失败时直接报错，不回退到系统解析器
+pub async fn connect(host: &str, port: u16, resolver: Option<&DnsResolverConfig>) -> Result<TcpStream> {
+    let addrs = dns::resolve(resolver.unwrap_or(&DnsResolverConfig::System), host, port).await?;
+
+    if addrs.is_empty() {
+        return Err(SSHError::ConnectionFailed(format!("主机 {} 没有可用的地址", host)));
+    }
+
+    tracing::debug!("Resolved {} to {:?}", host, addrs);
+
+    connect_resolved(addrs).await
+}
+
+/// 连接已解析的地址（IPv6 与 IPv4 交替排列后错开尝试）
+pub async fn connect_resolved(addrs: Vec<SocketAddr>) -> Result<TcpStream> {
+    connect_addrs(&interleave_families(addrs)).await
+}
+
+/// 按顺序错开尝试连接多个地址，返回第一个成功的连接
+///
+/// 上一个尝试失败时立即开始下一个，不必等待间隔
+async fn connect_addrs(addrs: &[SocketAddr]) -> Result<TcpStream> {
+    let mut attempts = FuturesUnordered::new();
+    let mut remaining = addrs.iter().copied();
+    let mut errors = Vec::new();
+
+    let start_attempt = |addr: SocketAddr| async move {
+        let result = match tokio::time::timeout(ATTEMPT_TIMEOUT, TcpStream::connect(addr)).await {
+            Ok(result) => result.map_err(|e| e.to_string()),
+            Err(_) => Err(format!("timed out after {:?}", ATTEMPT_TIMEOUT)),
+        };
+        (addr, result)
+    };
+
+    if let Some(addr) = remaining.next() {
+        attempts.push(start_attempt(addr));
+    }
+
+    while !attempts.is_empty() {
+        let has_more = remaining.len() > 0;
+
+        tokio::select! {
+            Some((addr, result)) = attempts.next() => match result {
+                Ok(stream) => {
+                    tracing::info!("Connected to {}", addr);
+                    let _ = stream.set_nodelay(true);
+                    // 其余尝试随 attempts 一起被丢弃
+                    return Ok(stream);
+                }
+                Err(e) => {
+                    tracing::warn!("Connection attempt to {} failed: {}", addr, e);
+                    errors.push(format!("{}: {}", addr, e));
+                    if let Some(next) = remaining.next() {
+                        attempts.push(start_attempt(next));
+                    }
+                }
+            },
+            _ = tokio::time::sleep(ATTEMPT_DELAY), if has_more => {
+                if let Some(next) = remaining.next() {
+                    tracing::debug!("Starting parallel connection attempt to {}", next);
+                    attempts.push(start_attempt(next));
+                }
+            }
+        }
+    }
+
+    Err(SSHError::ConnectionFailed(format!("所有地址连接失败: {}", errors.join("; "))))
+}
+
+/// 按地址族交替排列，以解析结果中第一个地址的地址族开头
+fn interleave_families(addrs: Vec<SocketAddr>) -> Vec<SocketAddr> {
+    let Some(first) = addrs.first() else {
+        return addrs;
+    };
+    let prefer_v6 = first.is_ipv6();
+
+    let (preferred, other): (Vec<_>, Vec<_>) = addrs.into_iter().partition(|a| a.is_ipv6() == prefer_v6);
+    let mut result = Vec::with_capacity(preferred.len() + other.len());
+    let mut preferred = preferred.into_iter();
+    let mut other = other.into_iter();
+
+    loop {
+        match (preferred.next(), other.next()) {
+            (None, None) => break,
+            (a, b) => result.extend(a.into_iter().chain(b)),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interleave_families() {
+        let addrs: Vec<SocketAddr> = ["[2001:db8::1]:22", "[2001:db8::2]:22", "[2001:db8::3]:22", "192.0.2.1:22"]
+            .iter()
+            .map(|a| a.parse().unwrap())
+            .collect();
+
+        let ordered: Vec<String> = interleave_families(addrs).iter().map(|a| a.to_string()).collect();
+        assert_eq!(ordered, vec!["[2001:db8::1]:22", "192.0.2.1:22", "[2001:db8::2]:22", "[2001:db8::3]:22"]);
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_to_working_address() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let good = listener.local_addr().unwrap();
+
+        // 先占用再释放一个端口，得到一个拒绝连接的地址
+        let closed = {
+            let l = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+            l.local_addr().unwrap()
+        };
+
+        let stream = connect_addrs(&[closed, good]).await.unwrap();
+        assert_eq!(stream.peer_addr().unwrap(), good);
+    }
+}
@@ -0,0 +1,148 @@
+//! 连接健康监测
+//!
+//! 每个已建立的连接由后台任务定期探测往返延迟（打开并立即关闭一个 session channel，
+//! 等待服务器确认），并通过 `ssh-health-{connectionId}` 事件发送延迟、丢包率估计
+//! 和最后活动时间，供前端显示连接质量。
+
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::time::Duration;
+
+/// 健康事件前缀
+pub const HEALTH_EVENT_PREFIX: &str = "ssh-health-";
+
+/// 探测间隔
+pub const PROBE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// 单次探测的超时时间，超时计为丢包
+pub const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 计算平均延迟和丢包率的样本数
+const WINDOW: usize = 20;
+
+/// 连接质量
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum HealthQuality {
+    Good,
+    Fair,
+    Poor,
+    /// 最近一次探测失败
+    Down,
+}
+
+/// 健康状态快照（即 `ssh-health-{connectionId}` 事件内容）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthSnapshot {
+    /// 最近一次探测的延迟（失败时为 None）
+    pub latency_ms: Option<u64>,
+    /// 窗口内成功探测的平均延迟
+    pub avg_latency_ms: Option<u64>,
+    /// 窗口内的丢包率估计（0.0 - 1.0）
+    pub packet_loss: f32,
+    /// 最后一次收发终端数据的时间（毫秒时间戳）
+    pub last_activity: Option<i64>,
+    pub quality: HealthQuality,
+    /// 窗口内的样本数
+    pub samples: usize,
+}
+
+/// 最近的探测结果
+#[derive(Debug, Default)]
+pub struct HealthTracker {
+    samples: VecDeque<Option<Duration>>,
+}
+
+impl HealthTracker {
+    /// 记录一次探测结果，None 表示超时或失败
+    pub fn record(&mut self, sample: Option<Duration>) {
+        if self.samples.len() == WINDOW {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    pub fn snapshot(&self, last_activity: Option<i64>) -> HealthSnapshot {
+        let latest = self.samples.back().copied().flatten();
+        let succeeded: Vec<Duration> = self.samples.iter().flatten().copied().collect();
+        let avg = (!succeeded.is_empty())
+            .then(|| succeeded.iter().sum::<Duration>() / succeeded.len() as u32);
+        let packet_loss = if self.samples.is_empty() {
+            0.0
+        } else {
+            (self.samples.len() - succeeded.len()) as f32 / self.samples.len() as f32
+        };
+
+        let quality = match latest {
+            None if !self.samples.is_empty() => HealthQuality::Down,
+            _ if packet_loss > 0.2 => HealthQuality::Poor,
+            Some(latency) if latency > Duration::from_millis(300) => HealthQuality::Poor,
+            _ if packet_loss > 0.0 => HealthQuality::Fair,
+            Some(latency) if latency > Duration::from_millis(100) => HealthQuality::Fair,
+            _ => HealthQuality::Good,
+        };
+
+        HealthSnapshot {
+            latency_ms: latest.map(|d| d.as_millis() as u64),
+            avg_latency_ms: avg.map(|d| d.as_millis() as u64),
+            packet_loss,
+            last_activity,
+            quality,
+            samples: self.samples.len(),
+        }
+    }
+}
+
+/// 最后活动时间（毫秒时间戳，0 表示尚无活动），读写终端数据时无需加锁
+#[derive(Debug, Default)]
+pub struct ActivityClock(AtomicI64);
+
+impl ActivityClock {
+    pub fn touch(&self) {
+        self.0.store(chrono::Utc::now().timestamp_millis(), Ordering::Relaxed);
+    }
+
+    pub fn last(&self) -> Option<i64> {
+        Some(self.0.load(Ordering::Relaxed)).filter(|ms| *ms > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot() {
+        let mut tracker = HealthTracker::default();
+        assert_eq!(tracker.snapshot(None).quality, HealthQuality::Good);
+
+        tracker.record(Some(Duration::from_millis(40)));
+        tracker.record(Some(Duration::from_millis(60)));
+        let snapshot = tracker.snapshot(Some(1));
+        assert_eq!(snapshot.latency_ms, Some(60));
+        assert_eq!(snapshot.avg_latency_ms, Some(50));
+        assert_eq!(snapshot.packet_loss, 0.0);
+        assert_eq!(snapshot.quality, HealthQuality::Good);
+
+        tracker.record(None);
+        assert_eq!(tracker.snapshot(None).quality, HealthQuality::Down);
+
+        tracker.record(Some(Duration::from_millis(50)));
+        let snapshot = tracker.snapshot(None);
+        assert_eq!(snapshot.packet_loss, 0.25);
+        assert_eq!(snapshot.quality, HealthQuality::Poor);
+
+        for _ in 0..WINDOW {
+            tracker.record(Some(Duration::from_millis(150)));
+        }
+        let snapshot = tracker.snapshot(None);
+        assert_eq!(snapshot.samples, WINDOW);
+        assert_eq!(snapshot.quality, HealthQuality::Fair);
+    }
+}
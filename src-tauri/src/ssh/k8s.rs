@@ -0,0 +1,199 @@
+//! Kubernetes Pod 终端
+//!
+//! 在本机或堡垒机（通过 SSH）上调用 `kubectl` 列出命名空间和 Pod，
+//! 并通过 `kubectl exec -it` 进入 Pod
+
+use super::docker::{is_safe_word, DEFAULT_SHELL_PROBE};
+use crate::utils::shell::shell_quote;
+use serde::{Deserialize, Serialize};
+
+/// Pod 信息
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct K8sPod {
+    pub name: String,
+    pub namespace: String,
+    /// Pod 阶段（Running、Pending 等）
+    pub phase: String,
+    /// Pod 内的容器名称
+    pub containers: Vec<String>,
+}
+
+/// kubectl 调用参数
+#[derive(Debug, Clone, Default)]
+pub struct KubectlTarget<'a> {
+    /// kubeconfig 路径，为空时使用 kubectl 默认配置
+    pub kubeconfig: Option<&'a str>,
+    /// kubeconfig 中的 context
+    pub context: Option<&'a str>,
+}
+
+impl KubectlTarget<'_> {
+    /// 公共参数（未转义）
+    fn args(&self) -> Result<Vec<String>, String> {
+        let mut args = Vec::new();
+        if let Some(kubeconfig) = self.kubeconfig.map(str::trim).filter(|s| !s.is_empty()) {
+            if kubeconfig.chars().any(|c| matches!(c, '"' | '`' | '$' | '%' | '!' | '\n' | '\r')) {
+                return Err(format!("无效的 kubeconfig 路径: {}", kubeconfig));
+            }
+            args.push("--kubeconfig".to_string());
+            args.push(kubeconfig.to_string());
+        }
+        if let Some(context) = self.context.map(str::trim).filter(|s| !s.is_empty()) {
+            validate_name("context", context)?;
+            args.push("--context".to_string());
+            args.push(context.to_string());
+        }
+        Ok(args)
+    }
+}
+
+/// 列出命名空间的 kubectl 参数
+pub fn list_namespaces_args(target: &KubectlTarget) -> Result<Vec<String>, String> {
+    let mut args = target.args()?;
+    args.extend(["get", "namespaces", "-o", "json"].map(String::from));
+    Ok(args)
+}
+
+/// 列出 Pod 的 kubectl 参数，`namespace` 为空时列出全部命名空间
+pub fn list_pods_args(target: &KubectlTarget, namespace: Option<&str>) -> Result<Vec<String>, String> {
+    let mut args = target.args()?;
+    args.extend(["get", "pods", "-o", "json"].map(String::from));
+    match namespace.map(str::trim).filter(|s| !s.is_empty()) {
+        Some(namespace) => {
+            validate_name("命名空间", namespace)?;
+            args.push("-n".to_string());
+            args.push(namespace.to_string());
+        }
+        None => args.push("--all-namespaces".to_string()),
+    }
+    Ok(args)
+}
+
+/// 拼接为远程 shell 命令（通过 SSH exec channel 执行）
+pub fn remote_command(args: &[String]) -> String {
+    let mut command = String::from("kubectl");
+    for arg in args {
+        command.push(' ');
+        command.push_str(&shell_quote(arg));
+    }
+    command
+}
+
+/// 解析 `kubectl get namespaces -o json` 的输出
+pub fn parse_namespaces(output: &str) -> Result<Vec<String>, String> {
+    let items = parse_items(output)?;
+    Ok(items
+        .iter()
+        .filter_map(|item| item["metadata"]["name"].as_str().map(str::to_string))
+        .collect())
+}
+
+/// 解析 `kubectl get pods -o json` 的输出
+pub fn parse_pods(output: &str) -> Result<Vec<K8sPod>, String> {
+    let items = parse_items(output)?;
+    Ok(items
+        .iter()
+        .filter_map(|item| {
+            let metadata = &item["metadata"];
+            Some(K8sPod {
+                name: metadata["name"].as_str()?.to_string(),
+                namespace: metadata["namespace"].as_str().unwrap_or("default").to_string(),
+                phase: item["status"]["phase"].as_str().unwrap_or_default().to_string(),
+                containers: item["spec"]["containers"]
+                    .as_array()
+                    .map(|containers| {
+                        containers
+                            .iter()
+                            .filter_map(|c| c["name"].as_str().map(str::to_string))
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+            })
+        })
+        .collect())
+}
+
+fn parse_items(output: &str) -> Result<Vec<serde_json::Value>, String> {
+    let value: serde_json::Value = serde_json::from_str(output)
+        .map_err(|e| format!("kubectl 输出解析失败: {}", e))?;
+    Ok(value["items"].as_array().cloned().unwrap_or_default())
+}
+
+/// 构造进入 Pod 的命令，`shell` 为空时自动选择 bash 或 sh
+///
+/// 除 kubeconfig 路径外的参数都只允许名称字符集，kubeconfig 使用双引号，
+/// 生成的命令在远程 POSIX shell 和本机 `cmd.exe` 中都能执行
+pub fn exec_command(
+    target: &KubectlTarget,
+    namespace: &str,
+    pod: &str,
+    container: Option<&str>,
+    shell: Option<&str>,
+) -> Result<String, String> {
+    let namespace = namespace.trim();
+    let pod = pod.trim();
+    validate_name("命名空间", namespace)?;
+    validate_name("Pod 名称", pod)?;
+
+    let mut command = String::from("kubectl");
+    for arg in target.args()? {
+        if is_safe_word(&arg) {
+            command.push(' ');
+            command.push_str(&arg);
+        } else {
+            command.push_str(&format!(" \"{}\"", arg));
+        }
+    }
+    command.push_str(&format!(" exec -it -n {} {}", namespace, pod));
+    if let Some(container) = container.map(str::trim).filter(|s| !s.is_empty()) {
+        validate_name("容器名称", container)?;
+        command.push_str(&format!(" -c {}", container));
+    }
+
+    match shell.map(str::trim).filter(|s| !s.is_empty()) {
+        Some(shell) if is_safe_word(shell) && !shell.starts_with('-') => {
+            command.push_str(&format!(" -- {}", shell));
+        }
+        Some(shell) => return Err(format!("无效的 shell: {}", shell)),
+        None => command.push_str(&format!(" -- /bin/sh -c \"{}\"", DEFAULT_SHELL_PROBE)),
+    }
+    Ok(command)
+}
+
+fn validate_name(kind: &str, value: &str) -> Result<(), String> {
+    if !is_safe_word(value) || value.starts_with(['-', '.', '/']) {
+        return Err(format!("无效的{}: {}", kind, value));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pods_and_exec_command() {
+        let output = r#"{"items":[
+            {"metadata":{"name":"web-7d9f","namespace":"prod"},
+             "spec":{"containers":[{"name":"nginx"},{"name":"sidecar"}]},
+             "status":{"phase":"Running"}},
+            {"metadata":{"namespace":"prod"}}
+        ]}"#;
+        let pods = parse_pods(output).unwrap();
+        assert_eq!(pods.len(), 1);
+        assert_eq!(pods[0].containers, vec!["nginx", "sidecar"]);
+        assert_eq!(pods[0].phase, "Running");
+
+        let target = KubectlTarget { kubeconfig: Some("/home/me/.kube/prod config"), context: None };
+        assert_eq!(
+            exec_command(&target, "prod", "web-7d9f", Some("nginx"), Some("bash")).unwrap(),
+            "kubectl --kubeconfig \"/home/me/.kube/prod config\" exec -it -n prod web-7d9f -c nginx -- bash"
+        );
+        assert!(exec_command(&KubectlTarget::default(), "prod", "web; id", None, None).is_err());
+        assert_eq!(
+            remote_command(&list_pods_args(&KubectlTarget::default(), None).unwrap()),
+            "kubectl 'get' 'pods' '-o' 'json' '--all-namespaces'"
+        );
+    }
+}
@@ -0,0 +1,141 @@
+//! SSH 密钥生成与部署
+//!
+//! 生成 Ed25519 / RSA 密钥对（OpenSSH 格式，可选口令加密），
+//! 以及把公钥追加到远程 `~/.ssh/authorized_keys` 的命令（相当于 ssh-copy-id）。
+
+use crate::error::{Result, SSHError};
+use crate::utils::shell::shell_quote;
+use russh::keys::ssh_key::private::{Ed25519Keypair, KeypairData, RsaKeypair};
+use russh::keys::ssh_key::LineEnding;
+use russh::keys::{HashAlg, PrivateKey, PublicKey};
+use serde::{Deserialize, Serialize};
+
+/// RSA 默认位数
+pub const DEFAULT_RSA_BITS: usize = 3072;
+
+/// RSA 允许的位数范围
+const MIN_RSA_BITS: usize = 2048;
+const MAX_RSA_BITS: usize = 8192;
+
+/// 密钥类型
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum KeyType {
+    Ed25519,
+    Rsa,
+}
+
+impl KeyType {
+    /// 默认文件名（与 ssh-keygen 一致）
+    pub fn default_file_name(&self) -> &'static str {
+        match self {
+            KeyType::Ed25519 => "id_ed25519",
+            KeyType::Rsa => "id_rsa",
+        }
+    }
+}
+
+/// 生成的密钥对
+#[derive(Debug, Clone)]
+pub struct GeneratedKey {
+    /// OpenSSH 格式私钥（设置口令时已加密）
+    pub private_key: String,
+    /// `authorized_keys` 格式的公钥
+    pub public_key: String,
+    /// SHA256 指纹
+    pub fingerprint: String,
+}
+
+/// 生成密钥对
+///
+/// - `bits`: 仅 RSA 使用，默认 3072，范围 2048-8192
+/// - `passphrase`: 为空时私钥不加密
+pub fn generate_key(
+    key_type: KeyType,
+    bits: Option<usize>,
+    passphrase: Option<&str>,
+    comment: &str,
+) -> Result<GeneratedKey> {
+    let mut rng = rand::rngs::OsRng;
+
+    let key_data = match key_type {
+        KeyType::Ed25519 => KeypairData::from(Ed25519Keypair::random(&mut rng)),
+        KeyType::Rsa => {
+            let bits = bits.unwrap_or(DEFAULT_RSA_BITS);
+            if !(MIN_RSA_BITS..=MAX_RSA_BITS).contains(&bits) {
+                return Err(SSHError::Crypto(format!(
+                    "RSA key size must be between {} and {} bits",
+                    MIN_RSA_BITS, MAX_RSA_BITS
+                )));
+            }
+            let keypair = RsaKeypair::random(&mut rng, bits)
+                .map_err(|e| SSHError::Crypto(format!("Failed to generate RSA key: {}", e)))?;
+            KeypairData::from(keypair)
+        }
+    };
+
+    let mut private_key = PrivateKey::new(key_data, comment)
+        .map_err(|e| SSHError::Crypto(format!("Failed to create private key: {}", e)))?;
+    let public_key = private_key.public_key().to_openssh()
+        .map_err(|e| SSHError::Crypto(format!("Failed to encode public key: {}", e)))?;
+    let fingerprint = private_key.public_key().fingerprint(HashAlg::Sha256).to_string();
+
+    if let Some(passphrase) = passphrase.filter(|p| !p.is_empty()) {
+        private_key = private_key.encrypt(&mut rng, passphrase)
+            .map_err(|e| SSHError::Crypto(format!("Failed to encrypt private key: {}", e)))?;
+    }
+    let private_key = private_key.to_openssh(LineEnding::LF)
+        .map_err(|e| SSHError::Crypto(format!("Failed to encode private key: {}", e)))?
+        .to_string();
+
+    Ok(GeneratedKey { private_key, public_key, fingerprint })
+}
+
+/// 校验 `authorized_keys` 格式的单行公钥，返回去掉首尾空白的公钥
+pub fn validate_public_key(public_key: &str) -> Result<String> {
+    let public_key = public_key.trim();
+    if public_key.contains('\n') || public_key.contains('\r') {
+        return Err(SSHError::Crypto("Public key must be a single line".to_string()));
+    }
+    PublicKey::from_openssh(public_key)
+        .map_err(|e| SSHError::Crypto(format!("Invalid public key: {}", e)))?;
+    Ok(public_key.to_string())
+}
+
+/// 追加公钥到远程 `~/.ssh/authorized_keys` 的命令
+///
+/// 目录和文件不存在时创建，权限分别设置为 700 和 600；公钥已存在时不重复追加。
+/// 输出 `added` 或 `exists`
+pub fn copy_id_command(public_key: &str) -> String {
+    let key = shell_quote(public_key);
+    format!(
+        "umask 077; mkdir -p ~/.ssh && chmod 700 ~/.ssh && touch ~/.ssh/authorized_keys && chmod 600 ~/.ssh/authorized_keys && \
+         if grep -qxF {key} ~/.ssh/authorized_keys; then echo exists; \
+         else {{ [ -z \"$(tail -c1 ~/.ssh/authorized_keys)\" ] || echo; echo {key}; }} >> ~/.ssh/authorized_keys && echo added; fi"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_key_and_copy_id_command() {
+        let key = generate_key(KeyType::Ed25519, None, Some("secret"), "test@host").unwrap();
+        assert!(key.public_key.starts_with("ssh-ed25519 "));
+        assert!(key.public_key.ends_with(" test@host"));
+        assert!(key.fingerprint.starts_with("SHA256:"));
+
+        let parsed = PrivateKey::from_openssh(&key.private_key).unwrap();
+        assert!(parsed.is_encrypted());
+        assert!(parsed.decrypt("secret").is_ok());
+
+        assert!(generate_key(KeyType::Rsa, Some(1024), None, "").is_err());
+
+        let public_key = validate_public_key(&format!("  {}\n", key.public_key)).unwrap();
+        assert!(validate_public_key("ssh-ed25519 not-base64").is_err());
+        let command = copy_id_command(&public_key);
+        assert!(command.contains(&format!("grep -qxF '{}'", public_key)));
+        assert!(command.contains("chmod 600 ~/.ssh/authorized_keys"));
+    }
+}
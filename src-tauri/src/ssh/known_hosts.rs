@@ -0,0 +1,305 @@
+//! 应用管理的 known_hosts
+//!
+//! 服务器主机密钥保存在应用存储目录的 `known_hosts` 文件中（OpenSSH 格式，可直接导出给 OpenSSH 使用）：
+//! 首次连接时记录，之后每次连接校验；会话开启严格主机密钥检查时拒绝密钥已变化的主机。
+
+use crate::config::storage::Storage;
+use crate::error::{Result, SSHError};
+use base64::Engine;
+use russh::keys::{HashAlg, PublicKey};
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// known_hosts 中的一条主机密钥
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct HostKeyEntry {
+    /// 在文件中的行号（从 1 开始）
+    pub line: usize,
+    /// 主机（端口不为 22 时为 `[host]:port`，多个主机以逗号分隔）
+    pub hosts: String,
+    /// 主机名是否经过哈希（OpenSSH 的 HashKnownHosts）
+    pub hashed: bool,
+    /// 密钥类型，如 ssh-ed25519
+    pub key_type: String,
+    /// SHA256 指纹（`SHA256:...`）
+    pub sha256: String,
+    /// MD5 指纹（`MD5:aa:bb:...`）
+    pub md5: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+/// 应用管理的 known_hosts 文件路径
+pub fn store_path() -> Result<PathBuf> {
+    Ok(Storage::get_app_storage_dir()?.join("known_hosts"))
+}
+
+/// known_hosts 中的主机写法：端口为 22 时只写主机名，否则为 `[host]:port`
+pub fn host_pattern(host: &str, port: u16) -> String {
+    if port == 22 {
+        host.to_string()
+    } else {
+        format!("[{}]:{}", host, port)
+    }
+}
+
+/// MD5 指纹（旧版 OpenSSH 的显示格式）
+pub fn md5_fingerprint(blob: &[u8]) -> String {
+    let digest = md5::compute(blob);
+    let hex: Vec<String> = digest.iter().map(|b| format!("{:02x}", b)).collect();
+    format!("MD5:{}", hex.join(":"))
+}
+
+/// 解析一行 known_hosts，注释、空行和无法解析的行返回 None
+fn parse_line(line: usize, text: &str) -> Option<HostKeyEntry> {
+    let text = text.trim();
+    if text.is_empty() || text.starts_with('#') {
+        return None;
+    }
+    let mut fields = text.split_whitespace();
+    let mut hosts = fields.next()?;
+    // @cert-authority / @revoked 标记
+    if hosts.starts_with('@') {
+        hosts = fields.next()?;
+    }
+    let key_type = fields.next()?;
+    let encoded = fields.next()?;
+    let comment = fields.collect::<Vec<_>>().join(" ");
+
+    let blob = base64::engine::general_purpose::STANDARD.decode(encoded).ok()?;
+    let key = russh::keys::parse_public_key_base64(encoded).ok()?;
+
+    Some(HostKeyEntry {
+        line,
+        hosts: hosts.to_string(),
+        hashed: hosts.starts_with("|1|"),
+        key_type: key_type.to_string(),
+        sha256: key.fingerprint(HashAlg::Sha256).to_string(),
+        md5: md5_fingerprint(&blob),
+        comment: (!comment.is_empty()).then_some(comment),
+    })
+}
+
+/// 解析 known_hosts 内容
+pub fn parse_entries(content: &str) -> Vec<HostKeyEntry> {
+    content
+        .lines()
+        .enumerate()
+        .filter_map(|(index, text)| parse_line(index + 1, text))
+        .collect()
+}
+
+/// 条目是否属于 `pattern`（只比较明文主机名，哈希过的主机无法匹配）
+fn entry_matches(entry: &HostKeyEntry, pattern: &str) -> bool {
+    !entry.hashed && entry.hosts.split(',').any(|host| host == pattern)
+}
+
+/// 删除匹配的条目，返回新内容和删除的条数
+///
+/// `key_type` 为空时删除主机的所有密钥
+pub fn remove_from(content: &str, pattern: &str, key_type: Option<&str>) -> (String, usize) {
+    let mut removed = 0;
+    let mut kept = String::with_capacity(content.len());
+    for (index, text) in content.lines().enumerate() {
+        let matched = parse_line(index + 1, text).is_some_and(|entry| {
+            entry_matches(&entry, pattern) && key_type.is_none_or(|t| t == entry.key_type)
+        });
+        if matched {
+            removed += 1;
+        } else {
+            kept.push_str(text);
+            kept.push('\n');
+        }
+    }
+    (kept, removed)
+}
+
+fn read_store() -> Result<String> {
+    let path = store_path()?;
+    if !path.exists() {
+        return Ok(String::new());
+    }
+    std::fs::read_to_string(&path)
+        .map_err(|e| SSHError::Storage(format!("Failed to read known_hosts: {}", e)))
+}
+
+/// 列出所有已信任的主机密钥
+pub fn list() -> Result<Vec<HostKeyEntry>> {
+    Ok(parse_entries(&read_store()?))
+}
+
+/// 删除主机的密钥，返回删除的条数
+pub fn remove(host: &str, port: u16, key_type: Option<&str>) -> Result<usize> {
+    let content = read_store()?;
+    let (kept, removed) = remove_from(&content, &host_pattern(host, port), key_type);
+    if removed > 0 {
+        std::fs::write(store_path()?, kept)
+            .map_err(|e| SSHError::Storage(format!("Failed to write known_hosts: {}", e)))?;
+        tracing::info!("Removed {} host key(s) for {}", removed, host_pattern(host, port));
+    }
+    Ok(removed)
+}
+
+/// 导出 OpenSSH 格式的 known_hosts 内容，指定主机时只导出该主机的密钥
+pub fn export(host: Option<(&str, u16)>) -> Result<String> {
+    let content = read_store()?;
+    let Some((host, port)) = host else {
+        return Ok(content);
+    };
+    let pattern = host_pattern(host, port);
+    Ok(content
+        .lines()
+        .enumerate()
+        .filter(|(index, text)| {
+            parse_line(index + 1, text).is_some_and(|entry| entry_matches(&entry, &pattern))
+        })
+        .map(|(_, text)| format!("{}\n", text))
+        .collect())
+}
+
+/// 主机密钥在 known_hosts 中的状态
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(tag = "state", rename_all = "camelCase")]
+pub enum HostKeyStatus {
+    /// 已记录且一致
+    Trusted,
+    /// 尚未记录（首次连接时记录）
+    Unknown,
+    /// 与记录的密钥不一致
+    Changed { line: usize },
+}
+
+/// 查询主机密钥的状态（不记录新密钥）
+pub fn status(host: &str, port: u16, key: &PublicKey) -> Result<HostKeyStatus> {
+    let path = store_path()?;
+    if !path.exists() {
+        return Ok(HostKeyStatus::Unknown);
+    }
+    match russh::keys::check_known_hosts_path(host, port, key, &path) {
+        Ok(true) => Ok(HostKeyStatus::Trusted),
+        Ok(false) => Ok(HostKeyStatus::Unknown),
+        Err(russh::keys::Error::KeyChanged { line }) => Ok(HostKeyStatus::Changed { line }),
+        Err(e) => Err(SSHError::Storage(format!("Failed to check known_hosts: {}", e))),
+    }
+}
+
+/// 主机密钥校验结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HostKeyVerdict {
+    /// 已记录且一致，或首次连接已记录
+    Trusted,
+    /// 与已记录的密钥不一致（known_hosts 中的行号）
+    Mismatch { line: usize },
+    /// 读取或写入 known_hosts 失败，无法确认密钥
+    StoreError(String),
+}
+
+impl HostKeyVerdict {
+    /// 是否允许连接：不一致或无法确认时，严格模式下拒绝
+    pub fn accepted(&self, strict: bool) -> bool {
+        matches!(self, HostKeyVerdict::Trusted) || !strict
+    }
+}
+
+/// 校验服务器主机密钥
+///
+/// 已记录且一致时通过；未记录时记录后通过；密钥已变化或 known_hosts 无法读写时由调用方按严格检查设置处理
+pub fn verify(host: &str, port: u16, key: &PublicKey) -> HostKeyVerdict {
+    match store_path() {
+        Ok(path) => verify_at(&path, host, port, key),
+        Err(e) => {
+            tracing::warn!("Host key store unavailable, skipping verification: {}", e);
+            HostKeyVerdict::StoreError(e.to_string())
+        }
+    }
+}
+
+/// 对照指定的 known_hosts 文件校验主机密钥
+fn verify_at(path: &std::path::Path, host: &str, port: u16, key: &PublicKey) -> HostKeyVerdict {
+    let fingerprint = key.fingerprint(HashAlg::Sha256);
+
+    match russh::keys::check_known_hosts_path(host, port, key, path) {
+        Ok(true) => HostKeyVerdict::Trusted,
+        Ok(false) => {
+            tracing::info!(
+                "Trusting new host key for {}: {} {}",
+                host_pattern(host, port), key.algorithm(), fingerprint
+            );
+            match russh::keys::known_hosts::learn_known_hosts_path(host, port, key, path) {
+                Ok(()) => HostKeyVerdict::Trusted,
+                Err(e) => {
+                    tracing::warn!("Failed to record host key for {}: {}", host, e);
+                    HostKeyVerdict::StoreError(e.to_string())
+                }
+            }
+        }
+        Err(russh::keys::Error::KeyChanged { line }) => {
+            tracing::warn!(
+                "Host key for {} has changed (known_hosts line {}), new key {}",
+                host_pattern(host, port), line, fingerprint
+            );
+            HostKeyVerdict::Mismatch { line }
+        }
+        Err(e) => {
+            tracing::warn!("Failed to check host key for {}: {}", host, e);
+            HostKeyVerdict::StoreError(e.to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &str = "AAAAC3NzaC1lZDI1NTE5AAAAIJdD7y3aLq454yWBdwLWbieU1ebz9/cu7/QEXn9OIeZJ";
+
+    #[test]
+    fn test_parse_and_remove() {
+        let content = format!(
+            "# trusted hosts\nexample.com ssh-ed25519 {KEY}\n[10.0.0.5]:2222,db ssh-ed25519 {KEY} db key\n"
+        );
+        let entries = parse_entries(&content);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].line, 2);
+        assert_eq!(entries[0].key_type, "ssh-ed25519");
+        assert!(entries[0].sha256.starts_with("SHA256:"));
+        assert_eq!(entries[0].md5.len(), "MD5:".len() + 16 * 3 - 1);
+        assert_eq!(entries[1].comment.as_deref(), Some("db key"));
+
+        assert_eq!(host_pattern("10.0.0.5", 2222), "[10.0.0.5]:2222");
+        let (kept, removed) = remove_from(&content, &host_pattern("10.0.0.5", 2222), None);
+        assert_eq!(removed, 1);
+        assert_eq!(parse_entries(&kept).len(), 1);
+        assert!(kept.starts_with("# trusted hosts\n"));
+
+        let (_, removed) = remove_from(&content, "example.com", Some("ssh-rsa"));
+        assert_eq!(removed, 0);
+    }
+
+    #[test]
+    fn test_verify_at() {
+        const OTHER_KEY: &str = "AAAAC3NzaC1lZDI1NTE5AAAAICBw9ea5IjZNnTr3TX8V62/bI4yKjTNilaQweRh+9iYp";
+        let key = russh::keys::parse_public_key_base64(KEY).unwrap();
+        let other = russh::keys::parse_public_key_base64(OTHER_KEY).unwrap();
+        let dir = std::env::temp_dir().join(format!("ssh-terminal-known-hosts-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("known_hosts");
+
+        // 首次连接记录，之后一致
+        assert_eq!(verify_at(&path, "example.com", 22, &key), HostKeyVerdict::Trusted);
+        assert_eq!(verify_at(&path, "example.com", 22, &key), HostKeyVerdict::Trusted);
+
+        let changed = verify_at(&path, "example.com", 22, &other);
+        assert!(matches!(changed, HostKeyVerdict::Mismatch { .. }));
+        assert!(!changed.accepted(true));
+        assert!(changed.accepted(false));
+
+        // known_hosts 无法读取（这里是一个目录）时不视为密钥变化
+        let unreadable = verify_at(&dir, "example.com", 22, &key);
+        assert!(matches!(unreadable, HostKeyVerdict::StoreError(_)));
+        assert!(!unreadable.accepted(true));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,132 @@
+//! 并发连接数限制
+//!
+//! 限制同一主机（按 host:port 计算，与用户名无关）和全局的并发连接数，
+//! 避免触发服务器的 MaxSessions/MaxStartups 限制或占用过多本机资源。
+//! 开启排队时，超出上限的连接等待其他连接断开后再建立。
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::Duration;
+
+/// 排队等待事件前缀
+pub const CONNECTION_QUEUED_EVENT_PREFIX: &str = "ssh-connection-queued-";
+
+/// 连接数限制设置
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectionLimits {
+    /// 每台主机的最大连接数，0 表示不限制
+    #[serde(default)]
+    pub per_host: u32,
+    /// 全局最大连接数，0 表示不限制
+    #[serde(default)]
+    pub global: u32,
+    /// 超出上限时排队等待，而不是直接失败
+    #[serde(default)]
+    pub queue: bool,
+    /// 排队的最长等待时间（秒）
+    #[serde(default = "default_queue_timeout_secs")]
+    pub queue_timeout_secs: u64,
+}
+
+fn default_queue_timeout_secs() -> u64 {
+    60
+}
+
+impl Default for ConnectionLimits {
+    fn default() -> Self {
+        Self {
+            per_host: 0,
+            global: 0,
+            queue: false,
+            queue_timeout_secs: default_queue_timeout_secs(),
+        }
+    }
+}
+
+impl ConnectionLimits {
+    pub fn is_unlimited(&self) -> bool {
+        self.per_host == 0 && self.global == 0
+    }
+
+    pub fn queue_timeout(&self) -> Duration {
+        Duration::from_secs(self.queue_timeout_secs)
+    }
+
+    /// 检查新连接 `endpoint` 是否超出限制，`active` 为其他活动连接的 endpoint
+    pub fn check<'a>(
+        &self,
+        endpoint: &str,
+        active: impl IntoIterator<Item = &'a str>,
+    ) -> Result<(), LimitExceeded> {
+        let (mut total, mut same_host) = (0u32, 0u32);
+        for other in active {
+            total += 1;
+            if other == endpoint {
+                same_host += 1;
+            }
+        }
+
+        if self.per_host > 0 && same_host >= self.per_host {
+            return Err(LimitExceeded::PerHost {
+                endpoint: endpoint.to_string(),
+                limit: self.per_host,
+            });
+        }
+        if self.global > 0 && total >= self.global {
+            return Err(LimitExceeded::Global { limit: self.global });
+        }
+        Ok(())
+    }
+}
+
+/// 超出的限制
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum LimitExceeded {
+    PerHost { endpoint: String, limit: u32 },
+    Global { limit: u32 },
+}
+
+impl fmt::Display for LimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LimitExceeded::PerHost { endpoint, limit } => {
+                write!(f, "{} 已有 {} 个连接（每台主机最多 {} 个）", endpoint, limit, limit)
+            }
+            LimitExceeded::Global { limit } => write!(f, "已有 {} 个连接（全局最多 {} 个）", limit, limit),
+        }
+    }
+}
+
+/// 按主机计数的标识（主机名不区分大小写）
+pub fn endpoint(host: &str, port: u16) -> String {
+    format!("{}:{}", host.to_ascii_lowercase(), port)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_limits() {
+        let limits = ConnectionLimits {
+            per_host: 2,
+            global: 3,
+            ..Default::default()
+        };
+        let web = endpoint("Web.example.com", 22);
+        assert_eq!(web, "web.example.com:22");
+
+        assert!(limits.check(&web, ["web.example.com:22", "db:22"]).is_ok());
+        assert_eq!(
+            limits.check(&web, ["web.example.com:22", "web.example.com:22"]),
+            Err(LimitExceeded::PerHost { endpoint: web.clone(), limit: 2 })
+        );
+        assert_eq!(
+            limits.check("cache:22", ["web.example.com:22", "db:22", "db:2222"]),
+            Err(LimitExceeded::Global { limit: 3 })
+        );
+        assert!(ConnectionLimits::default().check(&web, [web.as_str(); 100]).is_ok());
+    }
+}
@@ -0,0 +1,194 @@
+//! 登录脚本（expect）
+//!
+//! 交换机、路由器等设备登录后往往还有菜单或二次认证提示。会话可以配置
+//! 按顺序执行的“提示 -> 应答”步骤，channel 打开后自动匹配输出并发送应答。
+//! 脚本整体加密保存在数据库中，应答中的 `${password}` 会替换为会话的登录密码。
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// 等待单个提示的超时时间
+const STEP_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// 匹配缓冲区保留的最大字符数
+const MAX_BUFFER_CHARS: usize = 4096;
+
+/// 应答中代表会话登录密码的占位符
+const PASSWORD_PLACEHOLDER: &str = "${password}";
+
+/// 登录脚本事件名前缀（`ssh-login-script-{connectionId}`）
+pub const LOGIN_SCRIPT_EVENT_PREFIX: &str = "ssh-login-script-";
+
+/// 登录脚本步骤
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LoginStep {
+    /// 等待出现的提示文本（区分大小写的子串匹配）
+    pub prompt: String,
+    /// 匹配后发送的应答
+    pub response: String,
+    /// 应答后是否追加回车
+    #[serde(default = "default_send_newline")]
+    pub send_newline: bool,
+}
+
+fn default_send_newline() -> bool {
+    true
+}
+
+/// 登录脚本执行结果
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum LoginScriptStatus {
+    /// 全部步骤执行完毕
+    Completed,
+    /// 等待某个提示超时，剩余步骤被放弃
+    TimedOut,
+}
+
+/// 登录脚本执行器
+///
+/// 持续接收终端输出，按顺序匹配提示并产生需要写入的应答
+pub struct LoginScriptRunner {
+    steps: VecDeque<LoginStep>,
+    /// 已完成的步骤数
+    completed: usize,
+    /// 上次匹配之后的输出
+    buffer: String,
+    deadline: Instant,
+}
+
+impl LoginScriptRunner {
+    /// 创建执行器，没有步骤时返回 None
+    pub fn new(steps: &[LoginStep], password: Option<&str>) -> Option<Self> {
+        let steps: VecDeque<LoginStep> = steps
+            .iter()
+            .filter(|step| !step.prompt.is_empty())
+            .map(|step| LoginStep {
+                response: match password {
+                    Some(password) => step.response.replace(PASSWORD_PLACEHOLDER, password),
+                    None => step.response.clone(),
+                },
+                ..step.clone()
+            })
+            .collect();
+
+        if steps.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            steps,
+            completed: 0,
+            buffer: String::new(),
+            deadline: Instant::now() + STEP_TIMEOUT,
+        })
+    }
+
+    /// 已完成的步骤数
+    pub fn completed(&self) -> usize {
+        self.completed
+    }
+
+    /// 处理一段终端输出，返回需要依次写入的应答
+    pub fn feed(&mut self, chunk: &[u8]) -> Vec<Vec<u8>> {
+        self.feed_at(chunk, Instant::now())
+    }
+
+    fn feed_at(&mut self, chunk: &[u8], now: Instant) -> Vec<Vec<u8>> {
+        let mut responses = Vec::new();
+        if self.status_at(now).is_some() {
+            return responses;
+        }
+
+        self.buffer.push_str(&String::from_utf8_lossy(chunk));
+
+        // 一段输出中可能同时包含多个提示
+        while let Some(step) = self.steps.front() {
+            let Some(pos) = self.buffer.find(&step.prompt) else {
+                break;
+            };
+            self.buffer.drain(..pos + step.prompt.len());
+
+            let mut response = step.response.clone().into_bytes();
+            if step.send_newline {
+                response.push(b'\r');
+            }
+            responses.push(response);
+
+            self.steps.pop_front();
+            self.completed += 1;
+            self.deadline = now + STEP_TIMEOUT;
+        }
+
+        let excess = self.buffer.chars().count().saturating_sub(MAX_BUFFER_CHARS);
+        if excess > 0 {
+            let cut = self.buffer.char_indices().nth(excess).map_or(0, |(i, _)| i);
+            self.buffer.drain(..cut);
+        }
+
+        responses
+    }
+
+    /// 执行结束时返回结果，仍在等待提示时返回 None
+    pub fn status(&self) -> Option<LoginScriptStatus> {
+        self.status_at(Instant::now())
+    }
+
+    fn status_at(&self, now: Instant) -> Option<LoginScriptStatus> {
+        if self.steps.is_empty() {
+            Some(LoginScriptStatus::Completed)
+        } else if now >= self.deadline {
+            Some(LoginScriptStatus::TimedOut)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(prompt: &str, response: &str) -> LoginStep {
+        LoginStep {
+            prompt: prompt.to_string(),
+            response: response.to_string(),
+            send_newline: true,
+        }
+    }
+
+    #[test]
+    fn test_runs_steps_in_order() {
+        let steps = vec![step("Username:", "admin"), step("Password:", "${password}")];
+        let mut runner = LoginScriptRunner::new(&steps, Some("s3cret")).unwrap();
+
+        // 后面的提示先出现时不匹配
+        assert!(runner.feed(b"Password: ").is_empty());
+        assert_eq!(runner.feed(b"\r\nUser").len(), 0);
+        assert_eq!(runner.feed(b"name: "), vec![b"admin\r".to_vec()]);
+        assert_eq!(runner.status(), None);
+
+        assert_eq!(runner.feed(b"\r\nPassword: "), vec![b"s3cret\r".to_vec()]);
+        assert_eq!(runner.status(), Some(LoginScriptStatus::Completed));
+        assert_eq!(runner.completed(), 2);
+    }
+
+    #[test]
+    fn test_multiple_prompts_in_one_chunk() {
+        let steps = vec![step("[1]", "1"), step("menu>", "q")];
+        let mut runner = LoginScriptRunner::new(&steps, None).unwrap();
+        assert_eq!(runner.feed(b"[1] shell\r\nmenu> "), vec![b"1\r".to_vec(), b"q\r".to_vec()]);
+    }
+
+    #[test]
+    fn test_times_out() {
+        let mut runner = LoginScriptRunner::new(&[step("Login:", "x")], None).unwrap();
+        let later = Instant::now() + STEP_TIMEOUT;
+        assert!(runner.feed_at(b"Login:", later).is_empty());
+        assert_eq!(runner.status_at(later), Some(LoginScriptStatus::TimedOut));
+
+        assert!(LoginScriptRunner::new(&[], None).is_none());
+    }
+}
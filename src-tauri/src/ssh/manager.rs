@@ -1,16 +1,47 @@
 use crate::error::{Result, SSHError};
-use crate::ssh::session::{SessionConfig, SessionConfigUpdate, SessionStatus, SessionInfo};
+use crate::ssh::session::{AuthMethod, BackendType, SessionConfig, SessionConfigUpdate, SessionStatus, SessionInfo};
 use crate::ssh::connection::ConnectionInstance;
-use crate::ssh::backend::SSHBackend;
+use crate::ssh::events::{SessionChangeKind, SessionChangeLog, SessionListDelta};
+use crate::ssh::backend::{KeepaliveTimeoutEvent, SSHBackend, DISCONNECTED_EVENT_PREFIX, KEEPALIVE_TIMEOUT_EVENT_PREFIX};
+use crate::ssh::transcript::TranscriptRange;
+use crate::ssh::dns::DnsResolverConfig;
+use crate::ssh::login_script::{LoginScriptRunner, LOGIN_SCRIPT_EVENT_PREFIX};
+use crate::ssh::protection::{CommandGuard, ConfirmationRequest, PROTECT_CONFIRM_EVENT_PREFIX};
+use crate::ssh::elevation::{self, ElevationAction, ElevationEvent, ELEVATION_EVENT_PREFIX};
+use crate::ssh::limits::{self, ConnectionLimits, CONNECTION_QUEUED_EVENT_PREFIX};
+use crate::ssh::health::{self, HealthSnapshot, HEALTH_EVENT_PREFIX};
+use crate::ssh::stats::{self, StatsSnapshot, STATS_EVENT_PREFIX};
+use crate::ssh::preflight::{self, PreflightReport};
+use crate::ssh::watermark::{WatermarkSettings, WatermarkStamp, WATERMARK_EVENT_PREFIX};
+use crate::ssh::roaming::{self, RoamingEvent, ROAMING_EVENT_PREFIX};
+use crate::ssh::wol;
+use crate::ssh::advisory::{AdvisoryList, ADVISORY_EVENT_PREFIX};
+use crate::ssh::pre_connect;
 #[cfg(not(target_os = "android"))]
-use crate::ssh::backends::DefaultBackend;
+use crate::ssh::security_key::{SecurityKeyEvent, SecurityKeyPrompt, SECURITY_KEY_EVENT_PREFIX};
+use crate::ssh::backends::russh::{RusshTransport, KEEPALIVE_MAX};
+#[cfg(not(target_os = "android"))]
+use crate::database::{repositories::{AppSettingsRepository, ConnectionStatsRepository, UserAuthRepository, VariableRepository}, DbPool};
+use crate::models::connection_stats::ConnectionStatsRecord;
+#[cfg(not(target_os = "android"))]
+use crate::ssh::variables;
+use crate::services::hook_service::{HookEvent, HookService};
+use crate::services::webhook_service::{WebhookEvent, WebhookService};
+use crate::commands::plugins::PluginManagerState;
+#[cfg(not(target_os = "android"))]
+use crate::ssh::backends::{local::LocalBackend, serial::SerialBackend, telnet::TelnetBackend, DefaultBackend};
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::RwLock;
-use tauri::{AppHandle, Emitter};
+use std::time::Instant;
+use tokio::sync::{Notify, RwLock};
+use bytes::Bytes;
+use tauri::ipc::{Channel, InvokeResponseBody};
+use tauri::{AppHandle, Emitter, Manager};
 
 // 常量定义
 const BUFFER_SIZE: usize = 8192;
+/// 排队等待连接名额时的重新检查间隔
+const QUEUE_RECHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
 
 /// SSH管理器：维护Session配置和Connection实例
 #[derive(Clone)]
@@ -19,6 +50,14 @@ pub struct SSHManager {
     sessions: Arc<RwLock<HashMap<String, SessionConfig>>>,
     /// 连接实例：connectionId -> ConnectionInstance
     connections: Arc<RwLock<HashMap<String, ConnectionInstance>>>,
+    /// 会话/连接的增量变更记录
+    change_log: Arc<SessionChangeLog>,
+    /// 连接数限制检查的互斥锁
+    admission: Arc<tokio::sync::Mutex<()>>,
+    /// 有连接断开或连接失败时通知排队的连接
+    slot_released: Arc<Notify>,
+    /// 按主机共享的 SSH 连接
+    hub: ConnectionHub,
     app_handle: AppHandle,
 }
 
@@ -27,10 +66,45 @@ impl SSHManager {
         Self {
             sessions: Arc::new(RwLock::new(HashMap::new())),
             connections: Arc::new(RwLock::new(HashMap::new())),
+            change_log: Arc::new(SessionChangeLog::new(app_handle.clone())),
+            admission: Arc::new(tokio::sync::Mutex::new(())),
+            slot_released: Arc::new(Notify::new()),
+            hub: ConnectionHub::default(),
             app_handle,
         }
     }
 
+    /// 会话变更日志（供存储层在持久化会话变化时记录变更）
+    pub fn change_log(&self) -> &SessionChangeLog {
+        &self.change_log
+    }
+
+    /// 记录连接实例的状态变化
+    async fn notify_connection_status(&self, connection: &ConnectionInstance) {
+        self.change_log.record(
+            SessionChangeKind::ConnectionStatusChanged,
+            &connection.id,
+            Some(connection.session_info().await),
+        );
+    }
+
+    /// 触发连接相关的用户钩子
+    fn fire_connection_hook(event: HookEvent, connection: &ConnectionInstance) {
+        HookService::fire(event, Self::connection_context(connection));
+    }
+
+    /// 钩子和 webhook 使用的连接上下文
+    fn connection_context(connection: &ConnectionInstance) -> serde_json::Value {
+        serde_json::json!({
+            "connectionId": connection.id,
+            "sessionId": connection.session_id,
+            "name": connection.config.name,
+            "host": connection.config.host,
+            "port": connection.config.port,
+            "username": connection.config.username,
+        })
+    }
+
     // ============= Session配置管理 =============
 
     /// 创建新的会话配置（持久化）
@@ -38,12 +112,16 @@ impl SSHManager {
     pub async fn create_session_with_id(&self, id: Option<String>, config: SessionConfig) -> Result<String> {
         let session_id = id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
 
-        {
+        let info = SessionInfo::from_config(&session_id, &config);
+        let existed = {
             let mut sessions = self.sessions.write().await;
-            sessions.insert(session_id.clone(), config);
-        }
+            sessions.insert(session_id.clone(), config).is_some()
+        };
 
-        println!("Created persistent session config: {}", session_id);
+        let kind = if existed { SessionChangeKind::Updated } else { SessionChangeKind::Added };
+        self.change_log.record(kind, &session_id, Some(info));
+
+        tracing::info!("Created persistent session config: {}", session_id);
         Ok(session_id)
     }
 
@@ -65,12 +143,14 @@ impl SSHManager {
             config.clone(),
         );
 
+        let info = connection.session_info().await;
         {
             let mut connections = self.connections.write().await;
             connections.insert(connection_id.clone(), connection);
         }
+        self.change_log.record(SessionChangeKind::Added, &connection_id, Some(info));
 
-        println!("Created temporary connection: {}", connection_id);
+        tracing::info!("Created temporary connection: {}", connection_id);
         Ok(connection_id)
     }
 
@@ -124,14 +204,67 @@ impl SSHManager {
         if let Some(keep_alive_interval) = updates.keep_alive_interval {
             session.keep_alive_interval = keep_alive_interval;
         }
+        if let Some(jump_host) = updates.jump_host {
+            session.jump_host = Some(jump_host);
+        }
+        if let Some(remote_command) = updates.remote_command {
+            // 空字符串表示恢复为登录 shell
+            session.remote_command = Some(remote_command).filter(|c| !c.trim().is_empty());
+        }
+        if let Some(encoding) = updates.encoding {
+            session.encoding = encoding;
+        }
+        if let Some(remote_forwards) = updates.remote_forwards {
+            session.remote_forwards = remote_forwards;
+        }
+        if let Some(login_script) = updates.login_script {
+            session.login_script = login_script;
+        }
+        if let Some(protected) = updates.protected {
+            session.protected = protected;
+        }
+        if let Some(elevation) = updates.elevation {
+            session.elevation = elevation;
+        }
+        if let Some(algorithms) = updates.algorithms {
+            session.algorithms = (!algorithms.is_default()).then_some(algorithms);
+        }
+        if let Some(backend_type) = updates.backend_type {
+            session.backend_type = backend_type;
+        }
+        if let Some(serial) = updates.serial {
+            session.serial = Some(serial);
+        }
+        if let Some(distribution) = updates.wsl_distribution {
+            session.wsl_distribution = Some(distribution).filter(|d| !d.trim().is_empty());
+        }
+        if let Some(roaming) = updates.roaming {
+            session.roaming = roaming;
+        }
+        if let Some(wake_on_lan) = updates.wake_on_lan {
+            session.wake_on_lan = Some(wake_on_lan).filter(|w| !w.mac_address.trim().is_empty());
+        }
+        if let Some(pre_connect) = updates.pre_connect {
+            session.pre_connect = pre_connect;
+        }
 
-        println!("Updated session config: {} ({})", id, session.name);
+        tracing::info!("Updated session config: {} ({})", id, session.name);
+        let info = SessionInfo::from_config(id, session);
+        drop(sessions);
+        self.change_log.record(SessionChangeKind::Updated, id, Some(info));
         Ok(())
     }
 
+    /// 更新已加载会话的 DNS 解析器（会话未加载到内存时忽略）
+    pub async fn set_session_dns_resolver(&self, id: &str, resolver: Option<DnsResolverConfig>) {
+        if let Some(session) = self.sessions.write().await.get_mut(id) {
+            session.dns_resolver = resolver;
+        }
+    }
+
     /// 删除会话配置及其所有连接
     pub async fn delete_session(&self, id: &str) -> Result<()> {
-        println!("Deleting session/connection: {}", id);
+        tracing::info!("Deleting session/connection: {}", id);
 
         // 先尝试作为session配置ID删除
         let session_existed = {
@@ -154,10 +287,13 @@ impl SSHManager {
                 self.disconnect_connection(&conn_id).await?;
                 let mut connections = self.connections.write().await;
                 connections.remove(&conn_id);
-                println!("Removed connection: {}", conn_id);
+                drop(connections);
+                self.change_log.record(SessionChangeKind::Removed, &conn_id, None);
+                tracing::info!("Removed connection: {}", conn_id);
             }
 
-            println!("Deleted session config: {}", id);
+            self.change_log.record(SessionChangeKind::Removed, id, None);
+            tracing::info!("Deleted session config: {}", id);
             Ok(())
         } else {
             // 不是session配置，尝试作为connection ID删除
@@ -165,7 +301,9 @@ impl SSHManager {
             let mut connections = self.connections.write().await;
             connections.remove(id)
                 .ok_or_else(|| SSHError::SessionNotFound(id.to_string()))?;
-            println!("Deleted connection: {}", id);
+            drop(connections);
+            self.change_log.record(SessionChangeKind::Removed, id, None);
+            tracing::info!("Deleted connection: {}", id);
             Ok(())
         }
     }
@@ -185,20 +323,31 @@ impl SSHManager {
     pub async fn create_connection(&self, session_id: &str) -> Result<String> {
         // 获取session配置
         let config = self.get_session_config(session_id).await?;
+        self.create_connection_with_config(session_id, config).await
+    }
 
+    /// 使用指定配置（如应用了连接 profile 的会话配置）创建连接实例
+    pub async fn create_connection_with_config(&self, session_id: &str, config: SessionConfig) -> Result<String> {
         // 创建新的connection实例
         let connection_id = uuid::Uuid::new_v4().to_string();
         let connection = ConnectionInstance::new(connection_id.clone(), session_id.to_string(), config.clone());
+        let info = connection.session_info().await;
 
         {
             let mut connections = self.connections.write().await;
             connections.insert(connection_id.clone(), connection);
         }
+        self.change_log.record(SessionChangeKind::Added, &connection_id, Some(info));
 
-        println!("Created connection instance: {} for session: {}", connection_id, session_id);
+        tracing::info!("Created connection instance: {} for session: {}", connection_id, session_id);
         Ok(connection_id)
     }
 
+    /// 获取全部连接实例
+    pub async fn connections(&self) -> Vec<ConnectionInstance> {
+        self.connections.read().await.values().cloned().collect()
+    }
+
     /// 获取连接实例
     pub async fn get_connection(&self, id: &str) -> Result<ConnectionInstance> {
         let connections = self.connections.read().await;
@@ -209,20 +358,233 @@ impl SSHManager {
     }
 
     /// 连接指定的连接实例
+    /// 全局 DNS 解析器设置（未配置或读取失败时返回 None，即系统解析器）
+    #[cfg(not(target_os = "android"))]
+    fn global_dns_resolver(&self) -> Option<DnsResolverConfig> {
+        let pool = self.app_handle.try_state::<DbPool>()?;
+        let json = AppSettingsRepository::new(pool.inner().clone()).get_dns_resolver().ok()??;
+        serde_json::from_str(&json).ok()
+    }
+
+    /// 会话可用的模板变量（数据库不可用时为空）
+    #[cfg(not(target_os = "android"))]
+    fn template_variables(&self, session_id: &str) -> HashMap<String, String> {
+        let Some(pool) = self.app_handle.try_state::<DbPool>() else {
+            return HashMap::new();
+        };
+        let user = crate::commands::ssh_session::get_current_user_info(pool.inner());
+        VariableRepository::new(pool.inner().clone())
+            .resolve_for_session(&user.user_id, session_id)
+            .unwrap_or_else(|e| {
+                tracing::warn!("Failed to load variables for session {}: {}", session_id, e);
+                HashMap::new()
+            })
+    }
+
+    /// 替换主机名、用户名和启动命令中的模板变量（包括跳板机）
+    #[cfg(not(target_os = "android"))]
+    fn render_templates(&self, session_id: &str, config: &mut SessionConfig) -> Result<()> {
+        let mut loaded: Option<HashMap<String, String>> = None;
+        let mut render = |field: &mut String| -> Result<()> {
+            if !variables::is_template(field) {
+                return Ok(());
+            }
+            let vars = loaded.get_or_insert_with(|| self.template_variables(session_id));
+            *field = variables::render(field, vars)
+                .map_err(|e| SSHError::ConnectionFailed(format!("模板变量替换失败: {}", e)))?;
+            Ok(())
+        };
+
+        let mut hop = Some(config);
+        while let Some(current) = hop {
+            render(&mut current.host)?;
+            render(&mut current.username)?;
+            if let Some(command) = current.remote_command.as_mut() {
+                render(command)?;
+            }
+            hop = current.jump_host.as_deref_mut();
+        }
+        Ok(())
+    }
+
     pub async fn connect_connection(&self, connection_id: &str) -> Result<()> {
         let connection = self.get_connection(connection_id).await?;
-        connection.set_status(SessionStatus::Connecting).await;
+        {
+            let _admission = self.admit_connection(&connection).await?;
+            connection.set_status(SessionStatus::Connecting).await;
+        }
+
+        let result = match self.wake_host(&connection).await {
+            Ok(()) => self.establish_connection(connection_id, connection.clone()).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = &result {
+            // 连接失败时释放名额，避免一直停留在 Connecting
+            if matches!(connection.status().await, SessionStatus::Connecting) {
+                connection.set_status(SessionStatus::Error(e.to_string())).await;
+                self.notify_connection_status(&connection).await;
+            }
+            self.slot_released.notify_waiters();
+        }
+        result
+    }
+
+    /// 会话配置了网络唤醒时发送魔术包，并等待 SSH 端口可以连接
+    ///
+    /// 配置了跳板机时目标主机不在本地网络中，只发送魔术包，不等待端口
+    async fn wake_host(&self, connection: &ConnectionInstance) -> Result<()> {
+        let config = &connection.config;
+        let Some(wake_on_lan) = config.wake_on_lan.as_ref().filter(|_| config.backend_type.is_russh()) else {
+            return Ok(());
+        };
+        if config.jump_host.is_some() {
+            return wol::send_magic_packet(wake_on_lan).await;
+        }
+
+        tracing::info!("[WoL] Waking {}:{} before connecting {}", config.host, config.port, connection.id);
+        #[cfg(not(target_os = "android"))]
+        let resolver = config.dns_resolver.clone().or_else(|| self.global_dns_resolver());
+        #[cfg(target_os = "android")]
+        let resolver = config.dns_resolver.clone();
+        wol::wake(wake_on_lan, &config.host, config.port, resolver.as_ref()).await
+    }
+
+    /// 检查并发连接数限制，超出时按设置排队等待或返回错误
+    ///
+    /// 返回的锁在连接状态变为 Connecting 后释放，避免并发的连接同时通过检查
+    async fn admit_connection(&self, connection: &ConnectionInstance) -> Result<tokio::sync::MutexGuard<'_, ()>> {
+        let mut admission = self.admission.lock().await;
+        let limits = self.connection_limits();
+        if limits.is_unlimited() {
+            return Ok(admission);
+        }
+
+        let endpoint = limits::endpoint(&connection.config.host, connection.config.port);
+        let deadline = Instant::now() + limits.queue_timeout();
+        let mut queued = false;
+        loop {
+            let active = self.active_endpoints(&connection.id).await;
+            let exceeded = match limits.check(&endpoint, active.iter().map(String::as_str)) {
+                Ok(()) => return Ok(admission),
+                Err(exceeded) => exceeded,
+            };
+            if !limits.queue || Instant::now() >= deadline {
+                return Err(SSHError::LimitExceeded(exceeded.to_string()));
+            }
+
+            if !queued {
+                queued = true;
+                tracing::info!("[Limits] Connection {} queued: {}", connection.id, exceeded);
+                let event_name = format!("{}{}", CONNECTION_QUEUED_EVENT_PREFIX, connection.id);
+                if let Err(e) = self.app_handle.emit(&event_name, &exceeded) {
+                    tracing::warn!("[Limits] Failed to emit event {}: {}", event_name, e);
+                }
+            }
+
+            // 连接异常断开时不一定有通知，定期重新检查
+            drop(admission);
+            let wait = deadline.saturating_duration_since(Instant::now()).min(QUEUE_RECHECK_INTERVAL);
+            let _ = tokio::time::timeout(wait, self.slot_released.notified()).await;
+            admission = self.admission.lock().await;
+        }
+    }
+
+    /// 其他正在连接或已连接的连接实例的主机标识
+    async fn active_endpoints(&self, exclude_id: &str) -> Vec<String> {
+        let connections: Vec<ConnectionInstance> = self.connections.read().await.values().cloned().collect();
+        let mut endpoints = Vec::new();
+        for connection in connections {
+            if connection.id == exclude_id {
+                continue;
+            }
+            if matches!(connection.status().await, SessionStatus::Connected | SessionStatus::Connecting) {
+                endpoints.push(limits::endpoint(&connection.config.host, connection.config.port));
+            }
+        }
+        endpoints
+    }
+
+    /// 全局并发连接数限制（未配置或读取失败时不限制）
+    #[cfg(not(target_os = "android"))]
+    fn connection_limits(&self) -> ConnectionLimits {
+        self.app_handle
+            .try_state::<DbPool>()
+            .and_then(|pool| AppSettingsRepository::new(pool.inner().clone()).get_connection_limits().ok()?)
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    #[cfg(target_os = "android")]
+    fn connection_limits(&self) -> ConnectionLimits {
+        ConnectionLimits::default()
+    }
+
+    /// 水印设置（未配置或读取失败时关闭）
+    #[cfg(not(target_os = "android"))]
+    fn watermark_settings(&self) -> WatermarkSettings {
+        self.app_handle
+            .try_state::<DbPool>()
+            .and_then(|pool| AppSettingsRepository::new(pool.inner().clone()).get_watermark().ok()?)
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    #[cfg(target_os = "android")]
+    fn watermark_settings(&self) -> WatermarkSettings {
+        WatermarkSettings::default()
+    }
+
+    /// 当前登录账号的邮箱（未登录时为 None）
+    fn current_account(&self) -> Option<String> {
+        let pool = self.app_handle.try_state::<DbPool>()?;
+        let user = UserAuthRepository::new(pool.inner().clone()).find_current().ok()??;
+        Some(user.email)
+    }
 
-        println!("Starting SSH connection for: {}", connection_id);
+    async fn establish_connection(&self, connection_id: &str, connection: ConnectionInstance) -> Result<()> {
+        self.notify_connection_status(&connection).await;
+
+        tracing::info!("Starting SSH connection for: {}", connection_id);
 
         #[cfg(not(target_os = "android"))]
         {
-            // 桌面平台：使用实际的 SSH 后端
-            // 创建后端实例
-            let mut backend = Box::new(DefaultBackend::new());
+            // 桌面平台：按会话配置创建 SSH、本地 shell、串口、Telnet 或 WSL 后端
+            let mut backend: Box<dyn SSHBackend> = match connection.config.backend_type {
+                BackendType::Russh => {
+                    let mut backend = Box::new(DefaultBackend::new());
+                    backend.set_remote_forward_table(connection.remote_forwards.clone());
+                    backend.set_connection_hub(self.hub.clone());
+                    backend.set_security_key_prompt(self.security_key_prompt(connection_id));
+                    backend
+                }
+                BackendType::Local | BackendType::Wsl => Box::new(LocalBackend::new()),
+                BackendType::Serial => Box::new(SerialBackend::new()),
+                BackendType::Telnet => Box::new(TelnetBackend::new()),
+            };
+
+            // 会话未指定 DNS 解析器时使用全局设置
+            // 配置了跳板机时只有第一跳在本地解析主机名，后续主机由跳板机解析
+            let mut config = connection.config.clone();
+            let mut first_hop = &mut config;
+            while let Some(jump) = first_hop.jump_host.as_deref_mut() {
+                first_hop = jump;
+            }
+            if first_hop.dns_resolver.is_none() {
+                first_hop.dns_resolver = self.global_dns_resolver();
+            }
+            self.render_templates(&connection.session_id, &mut config)?;
+
+            // 端口敲门等连接前动作（针对本地 TCP 连接的第一跳）
+            if config.backend_type.is_russh() && !config.pre_connect.is_empty() {
+                let mut first_hop = &config;
+                while let Some(jump) = first_hop.jump_host.as_deref() {
+                    first_hop = jump;
+                }
+                pre_connect::run_all(&config.pre_connect, &first_hop.host, first_hop.dns_resolver.as_ref()).await?;
+            }
 
             // 建立连接
-            backend.connect(&connection.config).await?;
+            backend.connect(&config).await?;
 
             // 取出 reader
             let reader = backend.reader()
@@ -248,12 +610,44 @@ impl SSHManager {
             let mut connected_at = connection.connected_at.lock().await;
             *connected_at = Some(chrono::Utc::now());
         }
+        self.notify_connection_status(&connection).await;
+        Self::fire_connection_hook(HookEvent::OnConnect, &connection);
+
+        // 登录脚本在 reader 启动前就绪，避免错过第一个提示
+        {
+            let password = match &connection.config.auth_method {
+                AuthMethod::Password { password } => Some(password.as_str()),
+                _ => None,
+            };
+            *connection.login_script.lock().await =
+                LoginScriptRunner::new(&connection.config.login_script, password);
+        }
+        // 重连后丢弃上次连接中等待确认的输入
+        *connection.command_guard.lock().await = connection.config.protected.then(CommandGuard::default);
+        self.start_elevation_timer(&connection).await;
+        self.start_health_monitor(&connection).await;
+        self.start_stats_monitor(&connection).await;
+        self.start_watermark(&connection).await;
+        self.check_server_advisories(&connection).await;
 
-        println!("Starting SSH reader for connection: {}", connection_id);
+        tracing::info!("Starting SSH reader for connection: {}", connection_id);
 
         // 启动读取器
         self.start_backend_reader(connection_id.to_string(), connection.clone());
 
+        // 建立（或重新建立）远程端口转发
+        #[cfg(not(target_os = "android"))]
+        crate::ssh::forwarding::restore_remote_forwards(&self.app_handle, &connection).await;
+
+        // profile 指定的启动命令，同样经过受保护会话的危险命令检查
+        if !connection.config.startup_commands.is_empty() {
+            let mut input = connection.config.startup_commands.join("\r");
+            input.push('\r');
+            if let Err(e) = self.write_to_connection(connection_id, input.into_bytes()).await {
+                tracing::warn!("Failed to send startup commands to {}: {}", connection_id, e);
+            }
+        }
+
         Ok(())
     }
 
@@ -261,6 +655,9 @@ impl SSHManager {
     pub async fn disconnect_connection(&self, id: &str) -> Result<()> {
         let connection = self.get_connection(id).await?;
 
+        // 先更新状态，读取任务据此区分本地断开和远程断开
+        connection.set_status(SessionStatus::Disconnected { reason: None }).await;
+
         // 使用后端断开连接
         {
             let mut backend_guard = connection.backend.lock().await;
@@ -270,11 +667,20 @@ impl SSHManager {
             *backend_guard = None;
         }
 
-        connection.set_status(SessionStatus::Disconnected).await;
+        Self::stop_elevation_timer(&connection).await;
+        Self::stop_health_monitor(&connection).await;
+        Self::stop_stats_monitor(&connection).await;
+        Self::stop_watermark(&connection).await;
+        self.slot_released.notify_waiters();
+        self.record_connection_stats(&connection).await;
 
         // 清除连接时间
-        let mut connected_at = connection.connected_at.lock().await;
-        *connected_at = None;
+        {
+            let mut connected_at = connection.connected_at.lock().await;
+            *connected_at = None;
+        }
+        self.notify_connection_status(&connection).await;
+        Self::fire_connection_hook(HookEvent::OnDisconnect, &connection);
 
         Ok(())
     }
@@ -283,12 +689,38 @@ impl SSHManager {
     pub async fn write_to_connection(&self, id: &str, data: Vec<u8>) -> Result<()> {
         let connection = self.get_connection(id).await?;
 
-        // 记录写入的详细信息
-        let text = String::from_utf8_lossy(&data);
+        // 受保护会话中的危险命令在确认前不写入通道
+        let data = {
+            let mut guard = connection.command_guard.lock().await;
+            match guard.as_mut() {
+                Some(guard) => {
+                    let (data, request) = guard.feed(&data);
+                    if let Some(request) = request {
+                        self.request_protect_confirmation(id, &request);
+                    }
+                    data
+                }
+                None => data,
+            }
+        };
+        if data.is_empty() {
+            return Ok(());
+        }
+
+        // 只记录写入的字节数，不记录输入内容（可能包含密码等敏感信息）
         let data_len = data.len();
-        println!("[SSH Write] Writing {} bytes to connection: {}", data_len, id);
-        println!("[SSH Write] Data content: {:?}", text);
-        println!("[SSH Write] Data bytes (first 50): {:?}", &data[..data_len.min(50)]);
+        tracing::trace!("[SSH Write] Writing {} bytes to connection: {}", data_len, id);
+
+        // 正在断线续连时暂存输入，恢复后补发
+        {
+            let mut roaming = connection.roaming.lock().await;
+            if roaming.is_resuming() {
+                if !roaming.queue_input(&data) {
+                    tracing::warn!("[Roaming] Pending input limit reached on {}, dropping input", id);
+                }
+                return Ok(());
+            }
+        }
 
         // 使用后端的 write 方法
         let mut backend_guard = connection.backend.lock().await;
@@ -297,17 +729,382 @@ impl SSHManager {
         } else {
             return Err(SSHError::NotConnected);
         }
+        connection.last_activity.touch();
+        connection.traffic.add_sent(data_len);
+
+        tracing::trace!("[SSH Write] Successfully wrote {} bytes to connection: {}", data_len, id);
 
-        println!("[SSH Write] Successfully wrote {} bytes to connection: {}", data_len, id);
-        println!("---------------");
+        Ok(())
+    }
+
+    /// 处理受保护会话中危险命令的确认结果
+    ///
+    /// 确认后发送被拦下的回车及其后的输入，取消时发送 Ctrl-C 丢弃远程的命令行
+    pub async fn confirm_protected_input(&self, id: &str, token: &str, approved: bool) -> Result<()> {
+        let connection = self.get_connection(id).await?;
+
+        let (data, request) = {
+            let mut guard = connection.command_guard.lock().await;
+            guard
+                .as_mut()
+                .and_then(|guard| guard.resolve(token, approved))
+                .ok_or_else(|| SSHError::NotFound(format!("No pending confirmation: {}", token)))?
+        };
+        tracing::info!("[Protect] Command on {} {}", id, if approved { "confirmed" } else { "cancelled" });
+        if let Some(request) = request {
+            self.request_protect_confirmation(id, &request);
+        }
+        if data.is_empty() {
+            return Ok(());
+        }
 
+        let mut backend_guard = connection.backend.lock().await;
+        match backend_guard.as_mut() {
+            Some(backend) => backend.write(&data).await?,
+            None => return Err(SSHError::NotConnected),
+        }
+        connection.traffic.add_sent(data.len());
         Ok(())
     }
 
+    /// 以 root/管理员身份连接且设置了时限时启动倒计时
+    async fn start_elevation_timer(&self, connection: &ConnectionInstance) {
+        Self::stop_elevation_timer(connection).await;
+        let Some(policy) = connection.config.elevation.clone() else {
+            return;
+        };
+        if !policy.applies_to(&connection.config.username) {
+            return;
+        }
+
+        *connection.elevation_deadline.lock().await = Some(Instant::now() + policy.duration());
+        tracing::info!("[Elevation] {} expires in {} minute(s) ({:?})", connection.id, policy.minutes, policy.action);
+        Self::emit_elevation(&self.app_handle, &connection.id, ElevationEvent::Countdown {
+            remaining_secs: policy.duration().as_secs(),
+            action: policy.action,
+        });
+
+        let app_handle = self.app_handle.clone();
+        let conn = connection.clone();
+        let task = tokio::spawn(async move {
+            loop {
+                let Some(deadline) = *conn.elevation_deadline.lock().await else {
+                    return;
+                };
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                if remaining.is_zero() {
+                    break;
+                }
+                let notice = elevation::next_notice(remaining);
+                tokio::time::sleep(remaining - notice).await;
+
+                // 等待期间时限可能被延长或取消，重新计算
+                let current = *conn.elevation_deadline.lock().await;
+                if current == Some(deadline) && !notice.is_zero() {
+                    Self::emit_elevation(&app_handle, &conn.id, ElevationEvent::Countdown {
+                        remaining_secs: notice.as_secs(),
+                        action: policy.action,
+                    });
+                }
+            }
+
+            // 先移出任务句柄，避免断开连接时中止自身
+            *conn.elevation_deadline.lock().await = None;
+            conn.elevation_timer.lock().await.take();
+            tracing::info!("[Elevation] {} expired, {:?}", conn.id, policy.action);
+
+            let result = match policy.action {
+                ElevationAction::Disconnect => {
+                    let manager = app_handle.state::<crate::commands::session::SSHManagerState>();
+                    manager.disconnect_connection(&conn.id).await
+                }
+                ElevationAction::Downgrade => {
+                    let command = format!("{}\r", policy.downgrade_command());
+                    let mut backend_guard = conn.backend.lock().await;
+                    match backend_guard.as_mut() {
+                        Some(backend) => backend.write(command.as_bytes()).await.map(|_| {
+                            conn.traffic.add_sent(command.len());
+                        }),
+                        None => Err(SSHError::NotConnected),
+                    }
+                }
+            };
+            if let Err(e) = result {
+                tracing::warn!("[Elevation] Failed to {:?} {}: {}", policy.action, conn.id, e);
+            }
+            Self::emit_elevation(&app_handle, &conn.id, ElevationEvent::Expired { action: policy.action });
+        });
+        *connection.elevation_timer.lock().await = Some(task);
+    }
+
+    async fn stop_elevation_timer(connection: &ConnectionInstance) {
+        *connection.elevation_deadline.lock().await = None;
+        if let Some(task) = connection.elevation_timer.lock().await.take() {
+            task.abort();
+        }
+    }
+
+    /// 延长高权限会话的时限，返回新的剩余秒数
+    pub async fn extend_elevation(&self, id: &str, minutes: u32) -> Result<u64> {
+        let connection = self.get_connection(id).await?;
+        let remaining = {
+            let mut deadline = connection.elevation_deadline.lock().await;
+            let current = deadline
+                .as_mut()
+                .ok_or_else(|| SSHError::NotFound(format!("No elevation time limit on {}", id)))?;
+            *current += std::time::Duration::from_secs(u64::from(minutes) * 60);
+            current.saturating_duration_since(Instant::now()).as_secs()
+        };
+
+        let action = connection.config.elevation.as_ref().map(|p| p.action).unwrap_or_default();
+        tracing::info!("[Elevation] {} extended by {} minute(s)", id, minutes);
+        Self::emit_elevation(&self.app_handle, id, ElevationEvent::Countdown { remaining_secs: remaining, action });
+        Ok(remaining)
+    }
+
+    fn emit_elevation(app_handle: &AppHandle, id: &str, event: ElevationEvent) {
+        let event_name = format!("{}{}", ELEVATION_EVENT_PREFIX, id);
+        if let Err(e) = app_handle.emit(&event_name, event) {
+            tracing::warn!("[Elevation] Failed to emit event {}: {}", event_name, e);
+        }
+    }
+
+    /// 启动连接的健康监测，定期探测延迟并发送 `ssh-health-{connectionId}` 事件
+    async fn start_health_monitor(&self, connection: &ConnectionInstance) {
+        Self::stop_health_monitor(connection).await;
+        *connection.health.lock().await = Default::default();
+        // 只有 SSH 连接支持延迟探测
+        if !connection.config.backend_type.is_russh() {
+            return;
+        }
+
+        let app_handle = self.app_handle.clone();
+        let conn = connection.clone();
+        let task = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(health::PROBE_INTERVAL);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                if !matches!(conn.status().await, SessionStatus::Connected) {
+                    break;
+                }
+
+                let sample = match conn.probe_latency(health::PROBE_TIMEOUT).await {
+                    Ok(Some(latency)) => Some(latency),
+                    // backend 正忙，跳过本次探测
+                    Ok(None) => continue,
+                    Err(e) => {
+                        tracing::debug!("[Health] Probe failed on {}: {}", conn.id, e);
+                        None
+                    }
+                };
+
+                let snapshot = {
+                    let mut tracker = conn.health.lock().await;
+                    tracker.record(sample);
+                    tracker.snapshot(conn.last_activity.last())
+                };
+                let event_name = format!("{}{}", HEALTH_EVENT_PREFIX, conn.id);
+                if let Err(e) = app_handle.emit(&event_name, &snapshot) {
+                    tracing::warn!("[Health] Failed to emit event {}: {}", event_name, e);
+                }
+            }
+        });
+        *connection.health_monitor.lock().await = Some(task);
+    }
+
+    async fn stop_health_monitor(connection: &ConnectionInstance) {
+        if let Some(task) = connection.health_monitor.lock().await.take() {
+            task.abort();
+        }
+    }
+
+    /// 启动流量统计，定期发送 `ssh-stats-{connectionId}` 事件
+    async fn start_stats_monitor(&self, connection: &ConnectionInstance) {
+        Self::stop_stats_monitor(connection).await;
+        *connection.throughput.lock().await = Default::default();
+
+        let app_handle = self.app_handle.clone();
+        let conn = connection.clone();
+        let task = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(stats::STATS_INTERVAL);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                if !matches!(conn.status().await, SessionStatus::Connected) {
+                    break;
+                }
+
+                let uptime_secs = conn.uptime_secs().await;
+                let snapshot = conn.throughput.lock().await.sample(Instant::now(), conn.traffic.totals(), uptime_secs);
+                let event_name = format!("{}{}", STATS_EVENT_PREFIX, conn.id);
+                if let Err(e) = app_handle.emit(&event_name, &snapshot) {
+                    tracing::warn!("[Stats] Failed to emit event {}: {}", event_name, e);
+                }
+            }
+        });
+        *connection.stats_monitor.lock().await = Some(task);
+    }
+
+    async fn stop_stats_monitor(connection: &ConnectionInstance) {
+        if let Some(task) = connection.stats_monitor.lock().await.take() {
+            task.abort();
+        }
+    }
+
+    /// 将连接的累计流量写入使用历史并清零（无流量或数据库不可用时跳过）
+    async fn record_connection_stats(&self, connection: &ConnectionInstance) {
+        let (bytes_sent, bytes_received) = connection.traffic.take();
+        if bytes_sent == 0 && bytes_received == 0 {
+            return;
+        }
+        let Some(connected_at) = *connection.connected_at.lock().await else {
+            return;
+        };
+        let Some(pool) = self.app_handle.try_state::<DbPool>() else {
+            return;
+        };
+
+        let user = crate::commands::ssh_session::get_current_user_info(pool.inner());
+        let record = ConnectionStatsRecord {
+            id: 0,
+            user_id: user.user_id,
+            session_id: connection.session_id.clone(),
+            connection_id: connection.id.clone(),
+            session_name: connection.config.name.clone(),
+            host_key: connection.host_key(),
+            connected_at: connected_at.timestamp(),
+            disconnected_at: chrono::Utc::now().timestamp(),
+            bytes_sent: bytes_sent as i64,
+            bytes_received: bytes_received as i64,
+        };
+        if let Err(e) = ConnectionStatsRepository::new(pool.inner().clone()).insert(&record) {
+            tracing::warn!("[Stats] Failed to record usage of {}: {}", connection.id, e);
+        }
+    }
+
+    /// 连接当前的流量统计
+    pub async fn connection_stats(&self, id: &str) -> Result<StatsSnapshot> {
+        let connection = self.get_connection(id).await?;
+        let uptime_secs = connection.uptime_secs().await;
+        let snapshot = connection.throughput.lock().await.current(connection.traffic.totals(), uptime_secs);
+        Ok(snapshot)
+    }
+
+    /// 开启水印时定期发送 `ssh-watermark-{connectionId}` 事件（连接建立时立即发送一次）
+    async fn start_watermark(&self, connection: &ConnectionInstance) {
+        Self::stop_watermark(connection).await;
+        let settings = self.watermark_settings();
+        if !settings.enabled {
+            return;
+        }
+
+        let account = self.current_account();
+        let target = format!("{}@{}:{}", connection.config.username, connection.config.host, connection.config.port);
+        let app_handle = self.app_handle.clone();
+        let conn = connection.clone();
+        let task = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(settings.interval());
+            loop {
+                interval.tick().await;
+                if !matches!(conn.status().await, SessionStatus::Connected) {
+                    break;
+                }
+
+                let stamp = WatermarkStamp::new(account.clone(), target.clone(), chrono::Utc::now().timestamp_millis());
+                let event_name = format!("{}{}", WATERMARK_EVENT_PREFIX, conn.id);
+                if let Err(e) = app_handle.emit(&event_name, &stamp) {
+                    tracing::warn!("[Watermark] Failed to emit event {}: {}", event_name, e);
+                }
+            }
+        });
+        *connection.watermark_task.lock().await = Some(task);
+    }
+
+    /// 服务器版本命中已知高危漏洞时发送安全公告事件
+    async fn check_server_advisories(&self, connection: &ConnectionInstance) {
+        let Some(server_id) = connection.server_id().await else {
+            return;
+        };
+        tracing::debug!("[Advisory] {} runs {}", connection.id, server_id);
+
+        let app_handle = self.app_handle.clone();
+        let connection_id = connection.id.clone();
+        tokio::task::spawn_blocking(move || {
+            let Some(report) = AdvisoryList::load().check(&server_id) else {
+                return;
+            };
+            tracing::warn!(
+                "[Advisory] {} ({}) is affected by {} known vulnerabilities",
+                connection_id,
+                server_id,
+                report.advisories.len()
+            );
+            let event_name = format!("{}{}", ADVISORY_EVENT_PREFIX, connection_id);
+            if let Err(e) = app_handle.emit(&event_name, &report) {
+                tracing::warn!("[Advisory] Failed to emit event {}: {}", event_name, e);
+            }
+        });
+    }
+
+    async fn stop_watermark(connection: &ConnectionInstance) {
+        if let Some(task) = connection.watermark_task.lock().await.take() {
+            task.abort();
+        }
+    }
+
+    /// 连接前检查会话的 DNS 解析、TCP 可达性、主机密钥和私钥文件（不建立会话）
+    pub async fn preflight(&self, session_id: &str) -> Result<PreflightReport> {
+        let mut config = self.get_session_config(session_id).await?;
+        if !config.backend_type.is_russh() {
+            return Err(SSHError::NotSupported("Pre-flight check is only supported for SSH sessions".to_string()));
+        }
+
+        // 与建立连接时一致：第一跳使用全局 DNS 解析器，替换模板变量
+        #[cfg(not(target_os = "android"))]
+        {
+            let mut first_hop = &mut config;
+            while let Some(jump) = first_hop.jump_host.as_deref_mut() {
+                first_hop = jump;
+            }
+            if first_hop.dns_resolver.is_none() {
+                first_hop.dns_resolver = self.global_dns_resolver();
+            }
+            self.render_templates(session_id, &mut config)?;
+        }
+
+        Ok(preflight::run(&config).await)
+    }
+
+    /// 连接当前的健康状态（尚未探测时为 None）
+    pub async fn connection_health(&self, id: &str) -> Result<Option<HealthSnapshot>> {
+        let connection = self.get_connection(id).await?;
+        let tracker = connection.health.lock().await;
+        Ok((!tracker.is_empty()).then(|| tracker.snapshot(connection.last_activity.last())))
+    }
+
+    /// 发送 `ssh-protect-confirm-{connectionId}` 事件，等待前端确认
+    fn request_protect_confirmation(&self, id: &str, request: &ConfirmationRequest) {
+        tracing::warn!("[Protect] Holding destructive command ({}) on {}", request.pattern, id);
+        let event_name = format!("{}{}", PROTECT_CONFIRM_EVENT_PREFIX, id);
+        if let Err(e) = self.app_handle.emit(&event_name, request) {
+            tracing::warn!("[Protect] Failed to emit event {}: {}", event_name, e);
+        }
+    }
+
     /// 调整连接实例的PTY大小
     pub async fn resize_connection(&self, id: &str, rows: u16, cols: u16) -> Result<()> {
         let connection = self.get_connection(id).await?;
 
+        // 记录尺寸供断线续连后恢复，重连期间不需要后端
+        {
+            let mut roaming = connection.roaming.lock().await;
+            roaming.record_size(rows, cols);
+            if roaming.is_resuming() {
+                return Ok(());
+            }
+        }
+
         // 使用后端的 resize 方法
         let mut backend_guard = connection.backend.lock().await;
         if let Some(ref mut backend) = *backend_guard {
@@ -320,77 +1117,258 @@ impl SSHManager {
     }
 
     /// 启动后端读取器
+    ///
+    /// 读取任务在启动时从连接中取走 reader 的所有权，整个循环期间不再持有
+    /// `backend_reader` 锁。后端支持分块输出时直接转发引用计数的 `Bytes`，
+    /// 不再经过 AsyncRead 拷贝；否则回退到复用读缓冲区的 AsyncRead 循环。
+    /// 重连时 `connect_connection` 会重新注册 reader 并启动新的读取任务。
     fn start_backend_reader(&self, connection_id: String, connection: ConnectionInstance) {
         let app_handle = self.app_handle.clone();
+        let change_log = self.change_log.clone();
+        let manager = self.clone();
 
-        println!("Starting backend reader task for connection: {}", connection_id);
+        tracing::debug!("Starting backend reader task for connection: {}", connection_id);
 
         tokio::spawn(async move {
-            // 检查 reader 是否可用
-            let reader_lock = connection.backend_reader.lock().await;
-            let has_reader = reader_lock.is_some();
-            drop(reader_lock);
+            use tokio::io::AsyncReadExt;
+
+            // 取走 reader 的所有权，后续读取无需再加锁
+            let mut reader = match connection.backend_reader.lock().await.take() {
+                Some(r) => r,
+                None => {
+                    tracing::warn!("No backend reader available for connection: {}", connection_id);
+                    return;
+                }
+            };
 
-            if !has_reader {
-                eprintln!("No backend reader available for connection: {}", connection_id);
-                return;
+            let sink = OutputSink {
+                app_handle,
+                event_name: format!("ssh-output-{}", connection_id),
+                connection: connection.clone(),
+            };
+            let mut read_count: u64 = 0;
+
+            if let Some(mut chunks) = reader.take_chunk_receiver() {
+                // 零拷贝路径：直接转发后端的 Bytes 分块
+                while let Some(chunk) = chunks.recv().await {
+                    read_count += 1;
+                    tracing::trace!("[SSH Read] {} bytes from connection: {} (chunk #{})", chunk.len(), connection_id, read_count);
+                    sink.send(chunk).await;
+                }
+                tracing::info!("Output channel closed, stopping reader for connection: {}", connection_id);
+            } else {
+                let mut buffer = vec![0u8; BUFFER_SIZE];
+
+                loop {
+                    match reader.read(&mut buffer).await {
+                        Ok(n) if n > 0 => {
+                            read_count += 1;
+                            tracing::trace!("[SSH Read] {} bytes from connection: {} (read #{})", n, connection_id, read_count);
+                            sink.send(Bytes::copy_from_slice(&buffer[..n])).await;
+                        }
+                        Ok(_) => {
+                            // EOF，连接关闭
+                            tracing::info!("EOF received, stopping reader for connection: {}", connection_id);
+                            break;
+                        }
+                        Err(e) => {
+                            // 读取错误
+                            tracing::error!("Read error for connection {}: {}", connection_id, e);
+                            break;
+                        }
+                    }
+                }
             }
 
-            println!("Backend reader acquired for connection: {}", connection_id);
+            tracing::info!("Backend reader task ended for connection: {} ({} reads)", connection_id, read_count);
+
+            // 远程 shell 退出、被信号终止或服务器断开：通知前端原因并将连接标记为已断开
+            // （本地主动断开时 disconnect_connection 已先更新状态，这里不再处理）
+            let remote_closed = matches!(connection.status().await, SessionStatus::Connected);
+            let info = reader.disconnect_info();
+
+            // 开启漫游的 SSH 会话在传输中断（而非远程 shell 退出）时后台重连，保留当前终端
+            let transport_dropped = info
+                .as_ref()
+                .is_none_or(|info| info.exit_status.is_none() && info.exit_signal.is_none());
+            let resume = remote_closed && transport_dropped && connection.config.roaming && connection.config.backend_type.is_russh();
+
+            // 服务器不再响应心跳（对端已失效）：russh 不会发出任何提示，这里单独通知前端
+            if remote_closed && info.as_ref().is_some_and(|info| info.keepalive_timeout) {
+                tracing::warn!("Connection {} timed out waiting for keepalive replies", connection_id);
+                let event = KeepaliveTimeoutEvent {
+                    interval_secs: connection.config.keep_alive_interval,
+                    max_missed: KEEPALIVE_MAX,
+                    reconnecting: resume,
+                };
+                let _ = sink.app_handle.emit(&format!("{}{}", KEEPALIVE_TIMEOUT_EVENT_PREFIX, connection_id), &event);
 
-            let mut buffer = [0u8; BUFFER_SIZE];
-            let mut read_count = 0;
+                let mut context = Self::connection_context(&connection);
+                context["reconnecting"] = resume.into();
+                WebhookService::notify(WebhookEvent::HostOffline, context);
+            }
 
-            loop {
-                // 每次循环都重新获取 reader
-                let mut reader_guard = connection.backend_reader.lock().await;
-                let reader = match reader_guard.as_mut() {
-                    Some(r) => r,
-                    None => {
-                        eprintln!("Backend reader disappeared for connection: {}", connection_id);
-                        break;
-                    }
+            if resume {
+                let reason = info.map(|info| info.summary());
+                tracing::info!("Connection {} dropped, resuming in background: {:?}", connection_id, reason);
+                manager.resume_connection(connection, reason).await;
+                return;
+            }
+
+            if let Some(info) = info.filter(|_| remote_closed) {
+                let reason = info.summary();
+                tracing::info!("Connection {} closed by remote: {}", connection_id, reason);
+                let _ = sink.app_handle.emit(&format!("{}{}", DISCONNECTED_EVENT_PREFIX, connection_id), &info);
+
+                // 心跳超时视为连接错误而非正常断开
+                let status = if info.keepalive_timeout {
+                    SessionStatus::Error(reason)
+                } else {
+                    SessionStatus::Disconnected { reason: Some(reason) }
                 };
+                connection.set_status(status).await;
+                manager.record_connection_stats(&connection).await;
+                *connection.connected_at.lock().await = None;
+                Self::stop_health_monitor(&connection).await;
+                Self::stop_stats_monitor(&connection).await;
+                Self::stop_watermark(&connection).await;
+                change_log.record(
+                    SessionChangeKind::ConnectionStatusChanged,
+                    &connection.id,
+                    Some(connection.session_info().await),
+                );
+                Self::fire_connection_hook(HookEvent::OnDisconnect, &connection);
+            }
+        });
+    }
+
+    /// 传输中断后按退避间隔重连，成功后恢复 PTY 尺寸并补发重连期间的输入
+    ///
+    /// 重连期间连接保持 Connecting 状态，输出继续发往原来的通道；
+    /// 用户在此期间断开连接或重连次数用尽时停止，后者按远程断开处理
+    async fn resume_connection(&self, connection: ConnectionInstance, reason: Option<String>) {
+        let id = connection.id.clone();
+        connection.roaming.lock().await.begin_resume();
+
+        // 释放已失效的后端
+        if let Some(mut backend) = connection.backend.lock().await.take() {
+            if let Err(e) = backend.disconnect().await {
+                tracing::debug!("[Roaming] Failed to close dropped backend for {}: {}", id, e);
+            }
+        }
+        Self::stop_health_monitor(&connection).await;
+        Self::stop_stats_monitor(&connection).await;
+        Self::stop_watermark(&connection).await;
+        connection.set_status(SessionStatus::Connecting).await;
+        self.notify_connection_status(&connection).await;
+
+        let mut last_error = reason.unwrap_or_else(|| "Connection closed".to_string());
+        for attempt in 1..=roaming::MAX_ATTEMPTS {
+            self.emit_roaming(&id, &RoamingEvent::Reconnecting { attempt, reason: Some(last_error.clone()) });
+            tokio::time::sleep(roaming::backoff(attempt)).await;
 
-                use tokio::io::AsyncReadExt;
-
-                match reader.read(&mut buffer).await {
-                    Ok(n) if n > 0 => {
-                        read_count += 1;
-                        let data = buffer[..n].to_vec();
-                        let text = String::from_utf8_lossy(&data);
-                        
-                        // 记录读取的详细信息（不打印 Raw bytes）
-                        println!("[SSH Read] Read {} bytes from connection: {} (read #{})", n, connection_id, read_count);
-                        println!("[SSH Read] Text content: {:?}", text);
-
-                        // 释放锁后再发送事件
-                        drop(reader_guard);
-
-                        // 发送事件到前端（使用connectionId）
-                        let event_name = format!("ssh-output-{}", connection_id);
-                        if let Err(e) = app_handle.emit(&event_name, data) {
-                            eprintln!("[SSH Read] Failed to emit event {}: {}", event_name, e);
-                        } else {
-                            println!("[SSH Read] Successfully emitted event: {} ({} bytes)", event_name, n);
+            // 用户在等待期间关闭了连接
+            if !matches!(connection.status().await, SessionStatus::Connecting) {
+                connection.roaming.lock().await.finish_resume();
+                return;
+            }
+
+            match self.establish_connection(&id, connection.clone()).await {
+                Ok(()) => {
+                    let (size, input) = {
+                        let mut roaming = connection.roaming.lock().await;
+                        (roaming.pty_size(), roaming.finish_resume())
+                    };
+                    if let Some((rows, cols)) = size {
+                        if let Err(e) = self.resize_connection(&id, rows, cols).await {
+                            tracing::warn!("[Roaming] Failed to restore PTY size on {}: {}", id, e);
                         }
-                        println!("---------------");
                     }
-                    Ok(_) => {
-                        // EOF，连接关闭
-                        println!("EOF received, stopping reader for connection: {}", connection_id);
-                        break;
-                    }
-                    Err(e) => {
-                        // 读取错误
-                        eprintln!("Read error for connection {}: {}", connection_id, e);
-                        break;
+                    let replayed_input = input.len();
+                    if !input.is_empty() {
+                        if let Err(e) = self.write_to_connection(&id, input).await {
+                            tracing::warn!("[Roaming] Failed to replay pending input on {}: {}", id, e);
+                        }
                     }
+                    tracing::info!("[Roaming] {} resumed after {} attempt(s)", id, attempt);
+                    self.emit_roaming(&id, &RoamingEvent::Resumed { replayed_input });
+                    return;
+                }
+                Err(e) => {
+                    tracing::warn!("[Roaming] Attempt {} failed on {}: {}", attempt, id, e);
+                    last_error = e.to_string();
+                    connection.set_status(SessionStatus::Connecting).await;
                 }
             }
+        }
 
-            println!("Backend reader task ended for connection: {}", connection_id);
-        });
+        connection.roaming.lock().await.finish_resume();
+        self.emit_roaming(&id, &RoamingEvent::Failed { reason: last_error.clone() });
+        connection.set_status(SessionStatus::Disconnected { reason: Some(last_error) }).await;
+        self.record_connection_stats(&connection).await;
+        *connection.connected_at.lock().await = None;
+        self.notify_connection_status(&connection).await;
+        Self::fire_connection_hook(HookEvent::OnDisconnect, &connection);
+        self.slot_released.notify_waiters();
+    }
+
+    /// 安全密钥触摸提示，通过 `ssh-security-key-{connectionId}` 事件通知前端
+    #[cfg(not(target_os = "android"))]
+    fn security_key_prompt(&self, id: &str) -> SecurityKeyPrompt {
+        let app_handle = self.app_handle.clone();
+        let event_name = format!("{}{}", SECURITY_KEY_EVENT_PREFIX, id);
+        Arc::new(move |event: SecurityKeyEvent| {
+            if let Err(e) = app_handle.emit(&event_name, &event) {
+                tracing::warn!("[SecurityKey] Failed to emit event {}: {}", event_name, e);
+            }
+        })
+    }
+
+    fn emit_roaming(&self, id: &str, event: &RoamingEvent) {
+        let event_name = format!("{}{}", ROAMING_EVENT_PREFIX, id);
+        if let Err(e) = self.app_handle.emit(&event_name, event) {
+            tracing::warn!("[Roaming] Failed to emit event {}: {}", event_name, e);
+        }
+    }
+
+    /// 订阅连接的二进制输出
+    ///
+    /// 订阅后输出通过 IPC Channel 以原始字节发送（前端收到 ArrayBuffer），
+    /// 不再发送 `ssh-output-{connectionId}` JSON 事件。
+    /// 开启漫游的连接会先补发上一个通道失效后缓存的输出
+    pub async fn attach_output_channel(&self, id: &str, channel: Channel<InvokeResponseBody>) -> Result<()> {
+        let connection = self.get_connection(id).await?;
+        let mut output_channel = connection.output_channel.lock().await;
+        let missed = connection.roaming.lock().await.take_missed_output();
+        if !missed.is_empty() {
+            tracing::info!("[Roaming] Replaying {} buffered bytes to {}", missed.len(), id);
+            if let Err(e) = channel.send(InvokeResponseBody::Raw(missed)) {
+                tracing::warn!("[Roaming] Failed to replay output to {}: {}", id, e);
+            }
+        }
+        *output_channel = Some(channel);
+        Ok(())
+    }
+
+    /// 查询连接的纯文本输出记录
+    pub async fn transcript_range(&self, id: &str, start: Option<u64>, count: Option<usize>) -> Result<TranscriptRange> {
+        let connection = self.get_connection(id).await?;
+        let transcript = connection.transcript.lock().await;
+        Ok(transcript.range(start, count))
+    }
+
+    /// 开启或关闭 `ssh-transcript-{connectionId}` 实时新行事件
+    pub async fn set_transcript_live_events(&self, id: &str, enabled: bool) -> Result<()> {
+        let connection = self.get_connection(id).await?;
+        connection.transcript.lock().await.set_live_events(enabled);
+        Ok(())
+    }
+
+    /// 清空连接的纯文本输出记录
+    pub async fn clear_transcript(&self, id: &str) -> Result<()> {
+        let connection = self.get_connection(id).await?;
+        connection.transcript.lock().await.clear();
+        Ok(())
     }
 
     // ============= 兼容性方法（暂时保留以支持旧API）============
@@ -405,18 +1383,7 @@ impl SSHManager {
         // 1. 添加所有会话配置（始终显示配置）
         let sessions = self.sessions.read().await;
         for (session_id, config) in sessions.iter() {
-            infos.push(SessionInfo {
-                id: session_id.clone(),
-                name: config.name.clone(),
-                host: config.host.clone(),
-                port: config.port,
-                username: config.username.clone(),
-                status: SessionStatus::Disconnected,
-                connected_at: None,
-                group: config.group.clone(),
-                connection_session_id: None,  // 配置本身
-                connection_id: None,  // 配置本身没有 connection_id
-            });
+            infos.push(SessionInfo::from_config(session_id, config));
         }
 
         // 2. 添加所有连接实例（已连接的）
@@ -428,6 +1395,30 @@ impl SSHManager {
         infos
     }
 
+    /// 增量获取会话列表
+    ///
+    /// 传入上次收到的 revision，返回之后的变更；
+    /// 未传入或变更记录已被淘汰时返回全量列表
+    pub async fn list_sessions_since(&self, since: Option<u64>) -> SessionListDelta {
+        if let Some(changes) = since.and_then(|rev| self.change_log.since(rev)) {
+            return SessionListDelta {
+                revision: changes.last().map(|c| c.revision).unwrap_or_else(|| since.unwrap_or(0)),
+                full: false,
+                sessions: None,
+                changes,
+            };
+        }
+
+        // 先读取 revision 再获取列表，之后的变更前端会通过事件或下次查询收到
+        let revision = self.change_log.revision();
+        SessionListDelta {
+            revision,
+            full: true,
+            sessions: Some(self.list_sessions().await),
+            changes: Vec::new(),
+        }
+    }
+
     /// 兼容旧API：connect_session
     /// 如果传入的是 session_id（会话配置ID），创建新连接并连接
     /// 如果传入的是 connection_id（连接实例ID），直接连接该连接
@@ -471,3 +1462,296 @@ impl SSHManager {
         self.resize_connection(id, rows, cols).await
     }
 }
+
+// ============= 连接共享 =============
+
+/// 共享 SSH 连接上的 channel 类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChannelKind {
+    Shell,
+    Exec,
+    Sftp,
+    Forward,
+}
+
+struct HubEntry {
+    transport: Arc<RusshTransport>,
+    /// 各类型 channel 的租约数量
+    leases: HashMap<ChannelKind, usize>,
+}
+
+/// SSH 连接共享中心
+///
+/// 同一用户、主机、端口、认证身份、算法偏好和跳板机链路只保持一条 SSH 连接，终端、exec、SFTP 和端口转发
+/// 都在这条连接上打开 channel，并通过 [`ChannelLease`] 计数；
+/// 最后一个终端 channel 释放时断开连接，其上的其他 channel 随之关闭
+#[derive(Clone, Default)]
+pub struct ConnectionHub {
+    entries: Arc<std::sync::Mutex<HashMap<String, HubEntry>>>,
+}
+
+impl ConnectionHub {
+    /// 共享连接的主机标识（包含跳板机链路）
+    ///
+    /// 每一跳都带上 [`Self::identity_fingerprint`]，凭据或算法设置不同的会话不会复用
+    /// 已经通过认证的连接，否则错误的凭据也能借用别的会话的连接登录
+    pub fn key_for(config: &SessionConfig) -> String {
+        let mut key = format!(
+            "{}@{}:{}#{}",
+            config.username, config.host, config.port, Self::identity_fingerprint(config)
+        );
+        let mut jump = config.jump_host.as_deref();
+        while let Some(hop) = jump {
+            key.push_str(&format!(
+                " via {}@{}:{}#{}",
+                hop.username, hop.host, hop.port, Self::identity_fingerprint(hop)
+            ));
+            jump = hop.jump_host.as_deref();
+        }
+        key
+    }
+
+    /// 认证身份和连接参数的指纹（SHA-256 前 16 位），不直接包含密码等敏感信息
+    fn identity_fingerprint(config: &SessionConfig) -> String {
+        use sha2::{Digest, Sha256};
+
+        let mut hasher = Sha256::new();
+        match &config.auth_method {
+            AuthMethod::Password { password } => {
+                hasher.update(b"password\0");
+                hasher.update(password.as_bytes());
+            }
+            AuthMethod::PublicKey { private_key_path, passphrase } => {
+                hasher.update(b"publickey\0");
+                hasher.update(private_key_path.as_bytes());
+                hasher.update(b"\0");
+                hasher.update(passphrase.as_deref().unwrap_or_default().as_bytes());
+            }
+        }
+        hasher.update(b"\0");
+        hasher.update(serde_json::to_vec(&config.algorithms).unwrap_or_default());
+        hasher.update([config.strict_host_key_checking as u8]);
+
+        let digest = format!("{:x}", hasher.finalize());
+        digest[..16].to_string()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, HubEntry>> {
+        self.entries.lock().unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// 租用主机已有的连接，没有可用连接时返回 None
+    pub fn acquire(&self, key: &str, kind: ChannelKind) -> Option<ChannelLease> {
+        let mut entries = self.lock();
+        self.acquire_locked(&mut entries, key, kind)
+    }
+
+    fn acquire_locked(
+        &self,
+        entries: &mut HashMap<String, HubEntry>,
+        key: &str,
+        kind: ChannelKind,
+    ) -> Option<ChannelLease> {
+        let entry = entries.get_mut(key)?;
+        if entry.transport.is_closed() {
+            entries.remove(key);
+            return None;
+        }
+        *entry.leases.entry(kind).or_default() += 1;
+        Some(ChannelLease {
+            hub: self.clone(),
+            key: key.to_string(),
+            kind,
+            transport: entry.transport.clone(),
+        })
+    }
+
+    /// 登记新建立的连接并租用
+    ///
+    /// 同一主机的连接同时建立时，先登记的连接被共享，后建立的连接直接断开
+    pub fn register(&self, key: &str, transport: Arc<RusshTransport>, kind: ChannelKind) -> ChannelLease {
+        let mut entries = self.lock();
+        if let Some(lease) = self.acquire_locked(&mut entries, key, kind) {
+            tauri::async_runtime::spawn(async move { transport.disconnect().await });
+            return lease;
+        }
+
+        entries.insert(key.to_string(), HubEntry {
+            transport: transport.clone(),
+            leases: HashMap::from([(kind, 1)]),
+        });
+        tracing::info!("Registered shared SSH connection {}", key);
+        ChannelLease { hub: self.clone(), key: key.to_string(), kind, transport }
+    }
+
+    fn retain(&self, lease: &ChannelLease, kind: ChannelKind) {
+        if let Some(entry) = self.lock().get_mut(&lease.key) {
+            if Arc::ptr_eq(&entry.transport, &lease.transport) {
+                *entry.leases.entry(kind).or_default() += 1;
+            }
+        }
+    }
+
+    fn release(&self, lease: &ChannelLease) {
+        let mut entries = self.lock();
+        let Some(entry) = entries.get_mut(&lease.key) else {
+            return;
+        };
+        // 连接已断开并被新的连接替换
+        if !Arc::ptr_eq(&entry.transport, &lease.transport) {
+            return;
+        }
+        if let Some(count) = entry.leases.get_mut(&lease.kind) {
+            *count = count.saturating_sub(1);
+        }
+        if entry.leases.get(&ChannelKind::Shell).copied().unwrap_or(0) > 0 {
+            return;
+        }
+
+        entries.remove(&lease.key);
+        drop(entries);
+        tracing::info!("Closing shared SSH connection {}", lease.key);
+        let transport = lease.transport.clone();
+        tauri::async_runtime::spawn(async move { transport.disconnect().await });
+    }
+}
+
+/// 共享连接上一个 channel 的租约，释放时减少连接的引用计数
+pub struct ChannelLease {
+    hub: ConnectionHub,
+    key: String,
+    kind: ChannelKind,
+    transport: Arc<RusshTransport>,
+}
+
+impl ChannelLease {
+    /// 租约所在的连接
+    pub fn transport(&self) -> &Arc<RusshTransport> {
+        &self.transport
+    }
+
+    /// 在同一连接上租用另一个 channel
+    pub fn share(&self, kind: ChannelKind) -> ChannelLease {
+        self.hub.retain(self, kind);
+        ChannelLease {
+            hub: self.hub.clone(),
+            key: self.key.clone(),
+            kind,
+            transport: self.transport.clone(),
+        }
+    }
+}
+
+impl Drop for ChannelLease {
+    fn drop(&mut self) {
+        self.hub.release(self);
+    }
+}
+
+/// 终端输出的发送端
+///
+/// 前端订阅了二进制通道时通过 IPC Channel 发送原始字节，否则回退到 JSON 事件
+struct OutputSink {
+    app_handle: AppHandle,
+    event_name: String,
+    connection: ConnectionInstance,
+}
+
+impl OutputSink {
+    async fn send(&self, chunk: Bytes) {
+        self.connection.last_activity.touch();
+        self.connection.traffic.add_received(chunk.len());
+        self.update_transcript(&chunk).await;
+        self.run_login_script(&chunk).await;
+        if let Some(plugins) = self.app_handle.try_state::<PluginManagerState>() {
+            plugins.publish_output(&self.connection.id, &chunk);
+        }
+
+        {
+            let mut channel = self.connection.output_channel.lock().await;
+            let roaming = self.connection.config.roaming;
+            if let Some(ch) = channel.as_ref() {
+                let (body, retained) = if roaming {
+                    // 开启漫游时保留这一块，通道失效时缓存；发送的内容只能拷贝一份
+                    (chunk.to_vec(), Some(chunk))
+                } else {
+                    // Bytes 独占且覆盖整个分配时转换为 Vec 不会拷贝
+                    (Vec::from(chunk), None)
+                };
+                if let Err(e) = ch.send(InvokeResponseBody::Raw(body)) {
+                    // 前端页面已卸载或通道失效，后续输出回退到事件（开启漫游时缓存到重新订阅）
+                    tracing::warn!("[SSH Read] Output channel closed for {}: {}", self.connection.id, e);
+                    *channel = None;
+                    if let Some(chunk) = retained {
+                        let mut state = self.connection.roaming.lock().await;
+                        state.detach_output();
+                        state.buffer_output(&chunk);
+                    }
+                }
+                return;
+            }
+            if roaming && self.connection.roaming.lock().await.buffer_output(&chunk) {
+                return;
+            }
+        }
+
+        // 切片直接序列化，无需额外拷贝
+        if let Err(e) = self.app_handle.emit(&self.event_name, &chunk[..]) {
+            tracing::warn!("[SSH Read] Failed to emit event {}: {}", self.event_name, e);
+        }
+    }
+
+    /// 将输出交给登录脚本，写入匹配到的应答；结束时发送 `ssh-login-script-{connectionId}`
+    async fn run_login_script(&self, chunk: &[u8]) {
+        let (responses, finished) = {
+            let mut script = self.connection.login_script.lock().await;
+            let Some(runner) = script.as_mut() else {
+                return;
+            };
+            let responses = runner.feed(chunk);
+            let finished = runner.status().map(|status| (status, runner.completed()));
+            if finished.is_some() {
+                *script = None;
+            }
+            (responses, finished)
+        };
+
+        if !responses.is_empty() {
+            let mut backend_guard = self.connection.backend.lock().await;
+            if let Some(ref mut backend) = *backend_guard {
+                for response in responses {
+                    // 应答可能包含密码，不记录内容
+                    if let Err(e) = backend.write(&response).await {
+                        tracing::warn!("[Login Script] Failed to write response for {}: {}", self.connection.id, e);
+                        break;
+                    }
+                    self.connection.traffic.add_sent(response.len());
+                }
+            }
+        }
+
+        if let Some((status, completed)) = finished {
+            tracing::info!("[Login Script] {:?} after {} step(s) on {}", status, completed, self.connection.id);
+            let event_name = format!("{}{}", LOGIN_SCRIPT_EVENT_PREFIX, self.connection.id);
+            let payload = serde_json::json!({ "status": status, "completedSteps": completed });
+            if let Err(e) = self.app_handle.emit(&event_name, payload) {
+                tracing::warn!("[Login Script] Failed to emit event {}: {}", event_name, e);
+            }
+        }
+    }
+
+    /// 追加到纯文本记录，订阅了实时事件时发送 `ssh-transcript-{connectionId}`
+    async fn update_transcript(&self, chunk: &[u8]) {
+        let (lines, live_events) = {
+            let mut transcript = self.connection.transcript.lock().await;
+            (transcript.push(chunk), transcript.live_events())
+        };
+
+        if live_events && !lines.is_empty() {
+            let event_name = format!("ssh-transcript-{}", self.connection.id);
+            if let Err(e) = self.app_handle.emit(&event_name, &lines) {
+                tracing::warn!("[SSH Read] Failed to emit event {}: {}", event_name, e);
+            }
+        }
+    }
+}
@@ -1,6 +1,39 @@
 pub mod manager;
 pub mod session;
 pub mod connection;
+pub mod events;
 pub mod backend;
 pub mod backends;
 pub mod pty;
+pub mod benchmark;
+pub mod transcript;
+pub mod quick_connect;
+pub mod happy_eyeballs;
+pub mod known_hosts;
+pub mod dns;
+pub mod forwarding;
+pub mod encoding;
+pub mod login_script;
+pub mod protection;
+pub mod elevation;
+pub mod limits;
+pub mod algorithms;
+pub mod health;
+pub mod stats;
+pub mod preflight;
+pub mod watermark;
+pub mod profiles;
+pub mod config_import;
+pub mod variables;
+pub mod serial;
+pub mod wsl;
+pub mod docker;
+pub mod k8s;
+pub mod roaming;
+pub mod security_key;
+pub mod keys;
+pub mod wol;
+pub mod pre_connect;
+pub mod discovery;
+pub mod advisory;
+pub mod environment;
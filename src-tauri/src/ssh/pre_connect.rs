@@ -0,0 +1,220 @@
+//! 连接前动作（端口敲门 / SDP 网关）
+//!
+//! 受端口敲门或软件定义边界（SDP）网关保护的主机，需要在建立 TCP 连接之前先“敲门”：
+//! 运行本地命令、请求一个 HTTPS 敲门地址，或按顺序向若干端口发送 UDP/TCP 包。
+//! 会话按顺序执行配置的动作，任一动作失败时不再连接。漫游重连前同样会重新执行，
+//! 因为敲门打开的放行窗口通常只持续几十秒。
+
+use crate::error::{Result, SSHError};
+use crate::ssh::dns::{self, DnsResolverConfig};
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+use std::process::Stdio;
+use std::time::Duration;
+
+/// 本地命令和 HTTP 请求的默认超时时间（秒）
+const DEFAULT_TIMEOUT_SECS: u64 = 15;
+
+/// TCP 敲门单次连接的超时时间（敲门端口通常不会应答，只需发出 SYN）
+const TCP_KNOCK_TIMEOUT: Duration = Duration::from_millis(300);
+
+/// 相邻两次敲门之间的默认间隔（毫秒）
+const DEFAULT_KNOCK_DELAY_MS: u64 = 100;
+
+/// 敲门使用的协议
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum KnockProtocol {
+    #[default]
+    Udp,
+    Tcp,
+}
+
+/// 敲门序列中的一个端口
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct KnockPort {
+    pub port: u16,
+    #[serde(default)]
+    pub protocol: KnockProtocol,
+}
+
+/// HTTP 敲门请求的方法
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum KnockHttpMethod {
+    #[default]
+    Get,
+    Post,
+}
+
+/// 连接前动作
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum PreConnectAction {
+    /// 运行本地程序（不经过 shell），退出码为 0 视为成功
+    #[serde(rename_all = "camelCase")]
+    Command {
+        program: String,
+        #[serde(default)]
+        args: Vec<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        timeout_secs: Option<u64>,
+    },
+    /// 请求敲门地址，返回 2xx 视为成功
+    #[serde(rename_all = "camelCase")]
+    HttpRequest {
+        url: String,
+        #[serde(default)]
+        method: KnockHttpMethod,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        timeout_secs: Option<u64>,
+    },
+    /// 按顺序向主机的端口发送敲门包，`host` 为空时使用连接的主机（配置了跳板机时为第一跳）
+    #[serde(rename_all = "camelCase")]
+    PortKnock {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        host: Option<String>,
+        sequence: Vec<KnockPort>,
+        #[serde(default = "default_knock_delay_ms")]
+        delay_ms: u64,
+    },
+}
+
+fn default_knock_delay_ms() -> u64 {
+    DEFAULT_KNOCK_DELAY_MS
+}
+
+impl PreConnectAction {
+    /// 用于日志和错误信息的简短描述
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Command { program, .. } => format!("command '{}'", program),
+            Self::HttpRequest { url, method, .. } => format!("{:?} {}", method, url),
+            Self::PortKnock { sequence, .. } => {
+                let ports: Vec<String> = sequence
+                    .iter()
+                    .map(|knock| format!("{}/{:?}", knock.port, knock.protocol).to_lowercase())
+                    .collect();
+                format!("port knock {}", ports.join(","))
+            }
+        }
+    }
+}
+
+/// 按顺序执行连接前动作
+///
+/// `host` 和 `resolver` 为本地 TCP 连接的目标（配置了跳板机时为第一跳）
+pub async fn run_all(actions: &[PreConnectAction], host: &str, resolver: Option<&DnsResolverConfig>) -> Result<()> {
+    for action in actions {
+        tracing::info!("[PreConnect] Running {}", action.describe());
+        run(action, host, resolver).await.map_err(|e| {
+            SSHError::ConnectionFailed(format!("Pre-connect action {} failed: {}", action.describe(), e))
+        })?;
+    }
+    Ok(())
+}
+
+async fn run(action: &PreConnectAction, host: &str, resolver: Option<&DnsResolverConfig>) -> Result<()> {
+    match action {
+        PreConnectAction::Command { program, args, timeout_secs } => {
+            let timeout = Duration::from_secs(timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS));
+            let child = tokio::process::Command::new(program)
+                .args(args)
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::piped())
+                // 超时后 future 被丢弃时结束子进程
+                .kill_on_drop(true)
+                .spawn()
+                .map_err(|e| SSHError::ConnectionFailed(format!("failed to start: {}", e)))?;
+            let output = tokio::time::timeout(timeout, child.wait_with_output())
+                .await
+                .map_err(|_| SSHError::ConnectionFailed(format!("timed out after {:?}", timeout)))??;
+            if !output.status.success() {
+                return Err(SSHError::ConnectionFailed(format!(
+                    "exited with {}: {}",
+                    output.status,
+                    String::from_utf8_lossy(&output.stderr).trim()
+                )));
+            }
+        }
+        PreConnectAction::HttpRequest { url, method, timeout_secs } => {
+            let client = reqwest::Client::builder()
+                .timeout(Duration::from_secs(timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS)))
+                .build()
+                .map_err(|e| SSHError::ConnectionFailed(e.to_string()))?;
+            let request = match method {
+                KnockHttpMethod::Get => client.get(url),
+                KnockHttpMethod::Post => client.post(url),
+            };
+            let response = request.send().await.map_err(|e| SSHError::ConnectionFailed(e.to_string()))?;
+            if !response.status().is_success() {
+                return Err(SSHError::ConnectionFailed(format!("HTTP {}", response.status())));
+            }
+        }
+        PreConnectAction::PortKnock { host: knock_host, sequence, delay_ms } => {
+            let target = knock_host.as_deref().filter(|h| !h.trim().is_empty()).unwrap_or(host);
+            let ip = dns::resolve(resolver.unwrap_or(&DnsResolverConfig::System), target, 0)
+                .await?
+                .first()
+                .map(|addr| addr.ip())
+                .ok_or_else(|| SSHError::ConnectionFailed(format!("no address for {}", target)))?;
+
+            for (index, knock) in sequence.iter().enumerate() {
+                if index > 0 {
+                    tokio::time::sleep(Duration::from_millis(*delay_ms)).await;
+                }
+                knock_port(SocketAddr::new(ip, knock.port), knock.protocol).await?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 发送一次敲门包
+async fn knock_port(addr: SocketAddr, protocol: KnockProtocol) -> Result<()> {
+    match protocol {
+        KnockProtocol::Udp => {
+            let bind = if addr.is_ipv6() { "[::]:0" } else { "0.0.0.0:0" };
+            let socket = tokio::net::UdpSocket::bind(bind).await?;
+            socket.send_to(&[0u8], addr).await?;
+        }
+        // 敲门端口一般被防火墙丢弃，连接超时或被拒绝都属于正常情况
+        KnockProtocol::Tcp => {
+            let _ = tokio::time::timeout(TCP_KNOCK_TIMEOUT, tokio::net::TcpStream::connect(addr)).await;
+        }
+    }
+    tracing::debug!("[PreConnect] Knocked {} ({:?})", addr, protocol);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_action_serde() {
+        let actions: Vec<PreConnectAction> = serde_json::from_value(serde_json::json!([
+            { "type": "portKnock", "sequence": [{ "port": 7000 }, { "port": 8000, "protocol": "tcp" }] },
+            { "type": "httpRequest", "url": "https://gate.example.com/knock", "method": "POST" },
+            { "type": "command", "program": "fwknop", "args": ["-n", "bastion"] },
+        ]))
+        .unwrap();
+
+        assert_eq!(
+            actions[0],
+            PreConnectAction::PortKnock {
+                host: None,
+                sequence: vec![
+                    KnockPort { port: 7000, protocol: KnockProtocol::Udp },
+                    KnockPort { port: 8000, protocol: KnockProtocol::Tcp },
+                ],
+                delay_ms: DEFAULT_KNOCK_DELAY_MS,
+            }
+        );
+        assert_eq!(actions[0].describe(), "port knock 7000/udp,8000/tcp");
+        assert_eq!(actions[1].describe(), "Post https://gate.example.com/knock");
+        assert_eq!(actions[2].describe(), "command 'fwknop'");
+    }
+}
@@ -0,0 +1,306 @@
+//! 连接前检查（pre-flight）
+//!
+//! 不建立完整会话，依次检查 DNS 解析、TCP 可达性、服务器主机密钥（只完成密钥交换，不认证）
+//! 和私钥文件，返回前端可在连接前展示的检查清单。
+//! 配置了跳板机时网络检查针对第一跳，后续主机由跳板机连接，无法在本地检查。
+
+use crate::error::{Result, SSHError};
+use crate::ssh::backends::russh::RusshBackend;
+use crate::ssh::dns::{self, DnsResolverConfig};
+use crate::ssh::happy_eyeballs;
+use crate::ssh::known_hosts::{self, HostKeyStatus};
+use crate::ssh::security_key;
+use crate::ssh::session::{AuthMethod, SessionConfig};
+use russh::client;
+use russh::keys::{HashAlg, PublicKey};
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::net::TcpStream;
+
+/// 单项网络检查的超时时间
+const CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 检查项
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum CheckKind {
+    Dns,
+    Tcp,
+    HostKey,
+    KeyFile,
+}
+
+/// 检查结果
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum CheckStatus {
+    Passed,
+    /// 可以连接，但需要用户注意（如首次连接的主机、私钥文件权限过宽）
+    Warning,
+    Failed,
+    /// 前置检查失败或无法在本地检查
+    Skipped,
+}
+
+/// 单个检查项
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreflightCheck {
+    pub kind: CheckKind,
+    pub status: CheckStatus,
+    /// 检查对象（主机或私钥文件路径）
+    pub target: String,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+}
+
+impl PreflightCheck {
+    fn new(kind: CheckKind, status: CheckStatus, target: impl Into<String>, message: impl Into<String>) -> Self {
+        Self { kind, status, target: target.into(), message: message.into(), duration_ms: None }
+    }
+
+    fn timed(mut self, started: Instant) -> Self {
+        self.duration_ms = Some(started.elapsed().as_millis() as u64);
+        self
+    }
+}
+
+/// 检查清单
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreflightReport {
+    pub checks: Vec<PreflightCheck>,
+    /// 没有失败的检查项
+    pub ready: bool,
+}
+
+impl PreflightReport {
+    fn new(checks: Vec<PreflightCheck>) -> Self {
+        let ready = checks.iter().all(|check| check.status != CheckStatus::Failed);
+        Self { checks, ready }
+    }
+}
+
+/// 对会话配置执行连接前检查
+///
+/// `config` 应已替换模板变量，第一跳已填入要使用的 DNS 解析器
+pub async fn run(config: &SessionConfig) -> PreflightReport {
+    let mut hops = vec![config];
+    while let Some(jump) = hops.last().copied().and_then(|hop| hop.jump_host.as_deref()) {
+        hops.push(jump);
+    }
+    let first_hop = hops[hops.len() - 1];
+
+    let mut checks = check_network(first_hop).await;
+    if hops.len() > 1 {
+        checks.push(PreflightCheck::new(
+            CheckKind::Tcp,
+            CheckStatus::Skipped,
+            endpoint(config),
+            format!("通过跳板机 {} 连接，无法在本地检查", endpoint(first_hop)),
+        ));
+    }
+
+    for hop in hops.iter().rev() {
+        if let AuthMethod::PublicKey { private_key_path, passphrase } = &hop.auth_method {
+            checks.push(check_key_file(private_key_path, passphrase.clone()).await);
+        }
+    }
+
+    PreflightReport::new(checks)
+}
+
+fn endpoint(config: &SessionConfig) -> String {
+    format!("{}:{}", config.host, config.port)
+}
+
+/// DNS、TCP 和主机密钥检查，前一项失败时后续项跳过
+async fn check_network(config: &SessionConfig) -> Vec<PreflightCheck> {
+    let target = endpoint(config);
+    let skipped = |kind| PreflightCheck::new(kind, CheckStatus::Skipped, target.clone(), "前置检查未通过");
+    let mut checks = Vec::new();
+
+    let started = Instant::now();
+    let resolver = config.dns_resolver.clone().unwrap_or(DnsResolverConfig::System);
+    let addrs = match tokio::time::timeout(CHECK_TIMEOUT, dns::resolve(&resolver, &config.host, config.port)).await {
+        Ok(Ok(addrs)) if !addrs.is_empty() => addrs,
+        Ok(Ok(_)) => Vec::new(),
+        Ok(Err(e)) => {
+            checks.push(PreflightCheck::new(CheckKind::Dns, CheckStatus::Failed, &config.host, e.to_string()).timed(started));
+            checks.extend([skipped(CheckKind::Tcp), skipped(CheckKind::HostKey)]);
+            return checks;
+        }
+        Err(_) => {
+            let message = format!("解析超时（{} 秒）", CHECK_TIMEOUT.as_secs());
+            checks.push(PreflightCheck::new(CheckKind::Dns, CheckStatus::Failed, &config.host, message).timed(started));
+            checks.extend([skipped(CheckKind::Tcp), skipped(CheckKind::HostKey)]);
+            return checks;
+        }
+    };
+    if addrs.is_empty() {
+        checks.push(PreflightCheck::new(CheckKind::Dns, CheckStatus::Failed, &config.host, "没有可用的地址").timed(started));
+        checks.extend([skipped(CheckKind::Tcp), skipped(CheckKind::HostKey)]);
+        return checks;
+    }
+    let resolved = addrs.iter().map(|addr| addr.ip().to_string()).collect::<Vec<_>>().join(", ");
+    checks.push(PreflightCheck::new(CheckKind::Dns, CheckStatus::Passed, &config.host, resolved).timed(started));
+
+    let started = Instant::now();
+    let stream = match happy_eyeballs::connect_resolved(addrs).await {
+        Ok(stream) => stream,
+        Err(e) => {
+            checks.push(PreflightCheck::new(CheckKind::Tcp, CheckStatus::Failed, &target, e.to_string()).timed(started));
+            checks.push(skipped(CheckKind::HostKey));
+            return checks;
+        }
+    };
+    let peer = stream.peer_addr().map(|addr| addr.to_string()).unwrap_or_else(|_| target.clone());
+    checks.push(PreflightCheck::new(CheckKind::Tcp, CheckStatus::Passed, &target, format!("已连接 {}", peer)).timed(started));
+
+    let started = Instant::now();
+    let check = match probe_host_key(config, stream).await {
+        Ok(key) => host_key_check(config, &key),
+        Err(e) => PreflightCheck::new(CheckKind::HostKey, CheckStatus::Failed, &target, e.to_string()),
+    };
+    checks.push(check.timed(started));
+
+    checks
+}
+
+/// 记录服务器主机密钥并中止握手的 russh handler
+struct HostKeyProbe {
+    key: Arc<Mutex<Option<PublicKey>>>,
+}
+
+impl client::Handler for HostKeyProbe {
+    type Error = russh::Error;
+
+    async fn check_server_key(&mut self, server_public_key: &PublicKey) -> std::result::Result<bool, Self::Error> {
+        if let Ok(mut key) = self.key.lock() {
+            *key = Some(server_public_key.clone());
+        }
+        // 拒绝密钥以结束握手，不进行认证
+        Ok(false)
+    }
+}
+
+/// 完成密钥交换，取得服务器的主机密钥（使用会话的算法偏好）
+async fn probe_host_key(config: &SessionConfig, stream: TcpStream) -> Result<PublicKey> {
+    let key = Arc::new(Mutex::new(None));
+    let handler = HostKeyProbe { key: key.clone() };
+    let russh_config = Arc::new(RusshBackend::create_config(config));
+
+    let result = tokio::time::timeout(CHECK_TIMEOUT, client::connect_stream(russh_config, stream, handler)).await;
+    if let Some(key) = key.lock().ok().and_then(|mut key| key.take()) {
+        return Ok(key);
+    }
+    match result {
+        Err(_) => Err(SSHError::ConnectionFailed(format!("SSH 握手超时（{} 秒）", CHECK_TIMEOUT.as_secs()))),
+        Ok(Err(e)) => Err(SSHError::ConnectionFailed(format!("SSH 握手失败: {}", e))),
+        Ok(Ok(_)) => Err(SSHError::ConnectionFailed("服务器未提供主机密钥".to_string())),
+    }
+}
+
+fn host_key_check(config: &SessionConfig, key: &PublicKey) -> PreflightCheck {
+    let target = endpoint(config);
+    let description = format!("{} {}", key.algorithm(), key.fingerprint(HashAlg::Sha256));
+    match known_hosts::status(&config.host, config.port, key) {
+        Ok(HostKeyStatus::Trusted) => PreflightCheck::new(CheckKind::HostKey, CheckStatus::Passed, target, description),
+        Ok(HostKeyStatus::Unknown) => PreflightCheck::new(
+            CheckKind::HostKey,
+            CheckStatus::Warning,
+            target,
+            format!("首次连接，将记录主机密钥 {}", description),
+        ),
+        Ok(HostKeyStatus::Changed { line }) => PreflightCheck::new(
+            CheckKind::HostKey,
+            if config.strict_host_key_checking { CheckStatus::Failed } else { CheckStatus::Warning },
+            target,
+            format!("主机密钥与 known_hosts 第 {} 行记录的不一致，新密钥 {}", line, description),
+        ),
+        Err(e) => PreflightCheck::new(CheckKind::HostKey, CheckStatus::Warning, target, e.to_string()),
+    }
+}
+
+/// 私钥文件权限是否过宽（组或其他用户可读写）
+#[cfg(unix)]
+fn permissions_too_open(metadata: &std::fs::Metadata) -> Option<u32> {
+    use std::os::unix::fs::PermissionsExt;
+    let mode = metadata.permissions().mode() & 0o777;
+    (mode & 0o077 != 0).then_some(mode)
+}
+
+#[cfg(not(unix))]
+fn permissions_too_open(_metadata: &std::fs::Metadata) -> Option<u32> {
+    None
+}
+
+/// 检查私钥文件是否存在、权限和能否用配置的口令解析
+async fn check_key_file(path: &str, passphrase: Option<String>) -> PreflightCheck {
+    let fail = |message: String| PreflightCheck::new(CheckKind::KeyFile, CheckStatus::Failed, path, message);
+
+    if path.trim().is_empty() {
+        return fail("未设置私钥文件".to_string());
+    }
+    let metadata = match std::fs::metadata(path) {
+        Ok(metadata) if metadata.is_file() => metadata,
+        Ok(_) => return fail("不是文件".to_string()),
+        Err(e) => return fail(format!("无法访问私钥文件: {}", e)),
+    };
+
+    let key_path = path.to_string();
+    let loaded = tokio::task::spawn_blocking(move || {
+        russh::keys::load_secret_key(&key_path, passphrase.as_deref()).map(|key| key.algorithm())
+    })
+    .await;
+    let algorithm = match loaded {
+        Ok(Ok(algorithm)) => algorithm,
+        // 安全密钥的私钥文件只有密钥句柄，根据旁边的 .pub 文件识别
+        Ok(Err(e)) => match security_key::sibling_public_key(path).filter(|key| security_key::is_security_key(&key.algorithm())) {
+            Some(key) => key.algorithm(),
+            None => return fail(format!("无法加载私钥: {}", e)),
+        },
+        Err(e) => return fail(format!("无法加载私钥: {}", e)),
+    };
+
+    match permissions_too_open(&metadata) {
+        Some(mode) => PreflightCheck::new(
+            CheckKind::KeyFile,
+            CheckStatus::Warning,
+            path,
+            format!("{} 私钥，权限 {:o} 过宽，建议设置为 600", algorithm, mode),
+        ),
+        None => PreflightCheck::new(CheckKind::KeyFile, CheckStatus::Passed, path, format!("{} 私钥", algorithm)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_key_file_checks() {
+        let dir = std::env::temp_dir().join(format!("preflight-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let missing = dir.join("id_missing");
+        let check = check_key_file(missing.to_str().unwrap(), None).await;
+        assert_eq!(check.status, CheckStatus::Failed);
+
+        let invalid = dir.join("id_invalid");
+        std::fs::write(&invalid, "not a key").unwrap();
+        let check = check_key_file(invalid.to_str().unwrap(), None).await;
+        assert_eq!(check.status, CheckStatus::Failed);
+
+        let report = PreflightReport::new(vec![
+            PreflightCheck::new(CheckKind::Dns, CheckStatus::Passed, "example.com", "93.184.216.34"),
+            PreflightCheck::new(CheckKind::HostKey, CheckStatus::Warning, "example.com:22", "首次连接"),
+        ]);
+        assert!(report.ready);
+        assert!(!PreflightReport::new(vec![check]).ready);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,128 @@
+//! 同一主机的多个连接配置（profile）
+//!
+//! 一个已保存的主机可以有多个命名的 profile（如 dev、debug、root），分别指定用户名、
+//! 共享身份和连接后自动执行的命令，连接时选择其中一个，避免为同一台服务器保存多个
+//! 几乎相同的会话。profile 列表以 JSON 保存在会话的 `profiles` 列中，本身不包含认证信息，
+//! 需要不同凭据时引用共享身份。
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use crate::ssh::session::{AuthMethod, SessionConfig};
+
+/// 连接配置
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectionProfile {
+    /// 为空时保存时自动生成
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    /// 引用的共享身份（用户名和认证方式），优先于 `username`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub identity_id: Option<String>,
+    /// 覆盖主机的用户名（认证方式仍使用主机的设置）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    /// 覆盖主机的远程命令
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote_command: Option<String>,
+    /// shell 打开后依次输入的命令
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub startup_commands: Vec<String>,
+}
+
+impl ConnectionProfile {
+    /// 将 profile 应用到主机配置，`identity` 为引用的共享身份（用户名、认证方式）
+    pub fn apply(&self, config: &mut SessionConfig, identity: Option<(String, AuthMethod)>) {
+        config.name = format!("{} ({})", config.name, self.name);
+        match identity {
+            Some((username, auth_method)) => {
+                config.username = username;
+                config.auth_method = auth_method;
+            }
+            None => {
+                if let Some(username) = &self.username {
+                    config.username = username.clone();
+                }
+            }
+        }
+        if self.remote_command.is_some() {
+            config.remote_command = self.remote_command.clone();
+        }
+        config.startup_commands = self.startup_commands.clone();
+    }
+}
+
+/// 按 ID 或名称（不区分大小写）查找 profile
+pub fn find_profile<'a>(profiles: &'a [ConnectionProfile], key: &str) -> Option<&'a ConnectionProfile> {
+    profiles
+        .iter()
+        .find(|p| p.id == key)
+        .or_else(|| profiles.iter().find(|p| p.name.eq_ignore_ascii_case(key)))
+}
+
+/// 整理待保存的 profile 列表：去除空白、补全 ID，名称不能为空或重复
+pub fn normalize_profiles(profiles: Vec<ConnectionProfile>) -> Result<Vec<ConnectionProfile>, String> {
+    let mut names = HashSet::new();
+    profiles
+        .into_iter()
+        .map(|mut profile| {
+            profile.name = profile.name.trim().to_string();
+            if profile.name.is_empty() {
+                return Err("Profile name cannot be empty".to_string());
+            }
+            if !names.insert(profile.name.to_lowercase()) {
+                return Err(format!("Duplicate profile name: {}", profile.name));
+            }
+            if profile.id.is_empty() {
+                profile.id = uuid::Uuid::new_v4().to_string();
+            }
+            profile.identity_id = profile.identity_id.filter(|id| !id.is_empty());
+            profile.username = profile.username.map(|u| u.trim().to_string()).filter(|u| !u.is_empty());
+            profile.remote_command = profile.remote_command.filter(|c| !c.trim().is_empty());
+            profile.startup_commands.retain(|c| !c.trim().is_empty());
+            Ok(profile)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(name: &str) -> ConnectionProfile {
+        ConnectionProfile {
+            id: String::new(),
+            name: name.to_string(),
+            identity_id: None,
+            username: None,
+            remote_command: None,
+            startup_commands: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_normalize_and_find() {
+        let profiles = normalize_profiles(vec![
+            ConnectionProfile {
+                username: Some(" deploy ".to_string()),
+                startup_commands: vec!["cd /srv/app".to_string(), "  ".to_string()],
+                ..profile(" dev ")
+            },
+            profile("root"),
+        ])
+        .unwrap();
+        assert_eq!(profiles[0].name, "dev");
+        assert_eq!(profiles[0].username.as_deref(), Some("deploy"));
+        assert_eq!(profiles[0].startup_commands, ["cd /srv/app"]);
+        assert!(!profiles[1].id.is_empty());
+
+        assert_eq!(find_profile(&profiles, "ROOT").map(|p| p.name.as_str()), Some("root"));
+        assert_eq!(find_profile(&profiles, &profiles[0].id).map(|p| p.name.as_str()), Some("dev"));
+        assert!(find_profile(&profiles, "debug").is_none());
+
+        assert!(normalize_profiles(vec![profile("dev"), profile("DEV")]).is_err());
+        assert!(normalize_profiles(vec![profile(" ")]).is_err());
+    }
+}
@@ -0,0 +1,280 @@
+//! 受保护会话的危险命令确认
+//!
+//! 标记为受保护的会话（通常是生产主机）会跟踪用户输入的命令行，回车时与危险命令模式
+//! （`rm -rf`、`drop database`、`shutdown` 等）比较。命中时回车及其后的输入暂不写入通道，
+//! 前端收到 `ssh-protect-confirm-{connectionId}` 事件并由用户确认后才继续发送。
+//!
+//! 只能跟踪直接输入的字符，历史命令和 Tab 补全由远程 shell 展开，
+//! 因此这是防止误操作的保护，而不是安全边界。
+
+use serde::Serialize;
+
+/// 需要确认时发送的事件前缀
+pub const PROTECT_CONFIRM_EVENT_PREFIX: &str = "ssh-protect-confirm-";
+
+/// 取消时发送的 Ctrl-C，丢弃远程 shell 中已输入的命令行
+const CANCEL_INPUT: u8 = 0x03;
+
+/// 跟踪的命令行最大长度，超出部分不再参与匹配
+const MAX_LINE_LEN: usize = 4096;
+
+/// 待确认的命令
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfirmationRequest {
+    pub token: String,
+    pub command: String,
+    /// 命中的危险命令模式
+    pub pattern: &'static str,
+}
+
+struct PendingInput {
+    token: String,
+    /// 被拦下的回车及其后的输入
+    held: Vec<u8>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+enum EscapeState {
+    #[default]
+    None,
+    Escape,
+    /// CSI 序列（`ESC [`），直到终止字节
+    Csi,
+    /// SS3 序列（`ESC O`），后跟一个字节
+    Ss3,
+}
+
+/// 单个连接的输入跟踪
+#[derive(Default)]
+pub struct CommandGuard {
+    line: Vec<u8>,
+    escape: EscapeState,
+    pending: Option<PendingInput>,
+}
+
+impl CommandGuard {
+    /// 处理一段输入，返回可以立即写入通道的字节，以及需要确认的命令
+    ///
+    /// 等待确认期间的其他输入会被丢弃，Ctrl-C 直接取消等待中的命令
+    pub fn feed(&mut self, data: &[u8]) -> (Vec<u8>, Option<ConfirmationRequest>) {
+        if self.pending.is_some() {
+            if data.contains(&CANCEL_INPUT) {
+                self.pending = None;
+                self.line.clear();
+                return (vec![CANCEL_INPUT], None);
+            }
+            return (Vec::new(), None);
+        }
+
+        for (index, &byte) in data.iter().enumerate() {
+            if !self.track(byte) {
+                continue;
+            }
+            let command = String::from_utf8_lossy(&self.line).trim().to_string();
+            self.line.clear();
+            if let Some(pattern) = match_destructive(&command) {
+                let token = uuid::Uuid::new_v4().to_string();
+                self.pending = Some(PendingInput {
+                    token: token.clone(),
+                    held: data[index..].to_vec(),
+                });
+                return (data[..index].to_vec(), Some(ConfirmationRequest { token, command, pattern }));
+            }
+        }
+        (data.to_vec(), None)
+    }
+
+    /// 处理用户的确认结果，返回需要写入通道的字节
+    ///
+    /// 确认时发送被拦下的输入（其中后续的命令行仍会检查），取消时发送 Ctrl-C；
+    /// token 与等待中的命令不一致时返回 None
+    pub fn resolve(&mut self, token: &str, approved: bool) -> Option<(Vec<u8>, Option<ConfirmationRequest>)> {
+        if self.pending.as_ref().map(|p| p.token.as_str()) != Some(token) {
+            return None;
+        }
+        let pending = self.pending.take()?;
+        if !approved {
+            return Some((vec![CANCEL_INPUT], None));
+        }
+
+        // 第一个字节是命中时的回车
+        let (enter, rest) = pending.held.split_at(1);
+        let (mut data, request) = self.feed(rest);
+        data.splice(0..0, enter.iter().copied());
+        Some((data, request))
+    }
+
+    /// 更新命令行缓冲，遇到回车时返回 true
+    fn track(&mut self, byte: u8) -> bool {
+        match self.escape {
+            EscapeState::Escape => {
+                self.escape = match byte {
+                    b'[' => EscapeState::Csi,
+                    b'O' => EscapeState::Ss3,
+                    _ => EscapeState::None,
+                };
+                return false;
+            }
+            EscapeState::Csi => {
+                if (0x40..=0x7e).contains(&byte) {
+                    self.escape = EscapeState::None;
+                }
+                return false;
+            }
+            EscapeState::Ss3 => {
+                self.escape = EscapeState::None;
+                return false;
+            }
+            EscapeState::None => {}
+        }
+
+        match byte {
+            b'\r' | b'\n' => return true,
+            0x1b => self.escape = EscapeState::Escape,
+            // 退格删除一个 UTF-8 字符
+            0x7f | 0x08 => {
+                while let Some(last) = self.line.pop() {
+                    if last & 0xc0 != 0x80 {
+                        break;
+                    }
+                }
+            }
+            // Ctrl-C、Ctrl-D、Ctrl-U 清空当前行
+            0x03 | 0x04 | 0x15 => self.line.clear(),
+            // Ctrl-W 删除前一个单词
+            0x17 => {
+                while self.line.last() == Some(&b' ') {
+                    self.line.pop();
+                }
+                while self.line.last().is_some_and(|b| *b != b' ') {
+                    self.line.pop();
+                }
+            }
+            b'\t' => self.push(b' '),
+            byte if byte >= 0x20 => self.push(byte),
+            _ => {}
+        }
+        false
+    }
+
+    fn push(&mut self, byte: u8) {
+        if self.line.len() < MAX_LINE_LEN {
+            self.line.push(byte);
+        }
+    }
+}
+
+/// 检查命令行是否包含危险命令，返回命中的模式
+pub fn match_destructive(command: &str) -> Option<&'static str> {
+    let normalized = command.to_lowercase();
+
+    // SQL 语句可能在数据库客户端中输入，也可能作为 `mysql -e` 的参数
+    let words: Vec<&str> = normalized
+        .split(|c: char| c.is_whitespace() || matches!(c, '"' | '\'' | '`' | ';' | '('))
+        .filter(|w| !w.is_empty())
+        .collect();
+    for pair in words.windows(2) {
+        match (pair[0], pair[1]) {
+            ("drop", "database" | "schema") => return Some("drop database"),
+            ("drop", "table") => return Some("drop table"),
+            ("truncate", "table") => return Some("truncate table"),
+            _ => {}
+        }
+    }
+
+    normalized
+        .split([';', '|', '&', '\n'])
+        .find_map(|segment| match_shell_command(&segment.split_whitespace().collect::<Vec<_>>()))
+}
+
+/// 检查单条 shell 命令（已按空白拆分）
+fn match_shell_command(tokens: &[&str]) -> Option<&'static str> {
+    // 跳过 sudo、环境变量赋值等前缀
+    let start = tokens.iter().position(|token| {
+        !matches!(*token, "sudo" | "doas" | "command" | "exec" | "nohup" | "time" | "env")
+            && !token.contains('=')
+    })?;
+    let program = tokens[start].rsplit('/').next().unwrap_or(tokens[start]);
+    let args = &tokens[start + 1..];
+
+    match program {
+        "rm" => {
+            let flag = |short: char, long: &str| {
+                args.iter().any(|arg| {
+                    *arg == long || (arg.starts_with('-') && !arg.starts_with("--") && arg.contains(short))
+                })
+            };
+            let recursive = flag('r', "--recursive");
+            let force = flag('f', "--force");
+            (recursive && force).then_some("rm -rf")
+        }
+        "shutdown" | "reboot" | "halt" | "poweroff" => Some("shutdown"),
+        "init" | "telinit" if matches!(args.first(), Some(&"0") | Some(&"6")) => Some("shutdown"),
+        "systemctl" if args.iter().any(|arg| matches!(*arg, "poweroff" | "reboot" | "halt" | "kexec")) => {
+            Some("shutdown")
+        }
+        "mkfs" => Some("mkfs"),
+        program if program.starts_with("mkfs.") => Some("mkfs"),
+        "dd" if args.iter().any(|arg| arg.starts_with("of=/dev/")) => Some("dd of=/dev"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_destructive() {
+        assert_eq!(match_destructive("rm -rf /var/lib/mysql"), Some("rm -rf"));
+        assert_eq!(match_destructive("sudo rm -r -f build"), Some("rm -rf"));
+        assert_eq!(match_destructive("cd /tmp && /bin/rm -fR *"), Some("rm -rf"));
+        assert_eq!(match_destructive("mysql -e 'DROP DATABASE prod'"), Some("drop database"));
+        assert_eq!(match_destructive("DROP  TABLE users;"), Some("drop table"));
+        assert_eq!(match_destructive("sudo shutdown -h now"), Some("shutdown"));
+        assert_eq!(match_destructive("systemctl reboot"), Some("shutdown"));
+        assert_eq!(match_destructive("mkfs.ext4 /dev/sdb1"), Some("mkfs"));
+
+        assert_eq!(match_destructive("rm -r build"), None);
+        assert_eq!(match_destructive("ls -rf"), None);
+        assert_eq!(match_destructive("echo drop the table"), None);
+        assert_eq!(match_destructive("systemctl restart nginx"), None);
+    }
+
+    #[test]
+    fn test_guard_holds_enter() {
+        let mut guard = CommandGuard::default();
+
+        let (data, request) = guard.feed(b"ls -la\r");
+        assert_eq!(data, b"ls -la\r");
+        assert!(request.is_none());
+
+        // 退格修正后的命令按最终内容判断
+        let (data, request) = guard.feed(b"rm -rx\x7ff /srv\rls\r");
+        assert_eq!(data, b"rm -rx\x7ff /srv");
+        let request = request.unwrap();
+        assert_eq!(request.command, "rm -rf /srv");
+
+        // 等待确认期间的输入被丢弃
+        assert!(guard.feed(b"y").0.is_empty());
+        assert!(guard.resolve("wrong token", true).is_none());
+
+        let (data, request) = guard.resolve(&request.token, true).unwrap();
+        assert_eq!(data, b"\rls\r");
+        assert!(request.is_none());
+    }
+
+    #[test]
+    fn test_guard_cancel() {
+        let mut guard = CommandGuard::default();
+        let (_, request) = guard.feed(b"\x1b[Ashutdown now\n");
+        let request = request.unwrap();
+        assert_eq!(request.command, "shutdown now");
+
+        let (data, request) = guard.resolve(&request.token, false).unwrap();
+        assert_eq!(data, vec![CANCEL_INPUT]);
+        assert!(request.is_none());
+        assert_eq!(guard.feed(b"uptime\r").0, b"uptime\r");
+    }
+}
@@ -0,0 +1,260 @@
+//! 快速连接目标解析
+//!
+//! 解析命令面板中输入的自由格式目标，支持：
+//! - `host`、`host:2222`、`user@host`、`user@host:2222`
+//! - `ssh://user@host:2222`，以及 `ssh -p 2222 -l user host` 形式的命令行
+//! - IPv6 字面量：`::1`、`[::1]:2222`、`user@[fe80::1%eth0]`
+//!
+//! 以及从文档、资产管理系统中点击的 `ssh://` 和 `sftp://` 链接（见 [`parse_uri`]）
+
+use crate::utils::url::percent_decode;
+use serde::Serialize;
+use std::net::Ipv6Addr;
+
+/// 默认 SSH 端口
+pub const DEFAULT_SSH_PORT: u16 = 22;
+
+/// 解析后的快速连接目标
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct QuickConnectTarget {
+    pub username: Option<String>,
+    /// 主机名、IP 地址或会话别名（IPv6 不含方括号）
+    pub host: String,
+    pub port: Option<u16>,
+}
+
+impl QuickConnectTarget {
+    pub fn port_or_default(&self) -> u16 {
+        self.port.unwrap_or(DEFAULT_SSH_PORT)
+    }
+}
+
+/// 链接协议
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum UriScheme {
+    Ssh,
+    Sftp,
+}
+
+/// 解析后的 `ssh://` 或 `sftp://` 链接
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DeepLinkTarget {
+    pub scheme: UriScheme,
+    pub target: QuickConnectTarget,
+    /// sftp 链接中的远程路径（已解码，`~` 开头或为空时相对于主目录）
+    pub path: Option<String>,
+}
+
+/// 解析 `ssh://[user@]host[:port]` 和 `sftp://[user@]host[:port][/path]` 链接
+///
+/// 用户名中的 `;fingerprint=...` 等连接参数以及链接中的密码会被忽略，查询字符串和片段也会被忽略
+pub fn parse_uri(uri: &str) -> Result<DeepLinkTarget, String> {
+    let uri = uri.trim();
+    let (scheme, rest) = uri.split_once("://").ok_or_else(|| format!("无效的链接: {}", uri))?;
+    let scheme = match scheme.to_ascii_lowercase().as_str() {
+        "ssh" => UriScheme::Ssh,
+        "sftp" => UriScheme::Sftp,
+        other => return Err(format!("不支持的链接协议: {}", other)),
+    };
+
+    let rest = rest.split(['?', '#']).next().unwrap_or_default();
+    let (authority, path) = match rest.find('/') {
+        Some(index) => (&rest[..index], &rest[index..]),
+        None => (rest, ""),
+    };
+
+    let (username, host_port) = match authority.rsplit_once('@') {
+        Some((userinfo, host_port)) => {
+            let user = userinfo.split([';', ':']).next().unwrap_or_default();
+            (Some(percent_decode(user)?), host_port)
+        }
+        None => (None, authority),
+    };
+    if let Some(user) = &username {
+        validate_username(user)?;
+    }
+    let (host, port) = split_host_port(host_port)?;
+    validate_host(&host)?;
+
+    let path = match scheme {
+        // `sftp://host/~/dir` 表示主目录下的路径
+        UriScheme::Sftp if path.len() > 1 => {
+            let path = percent_decode(path)?;
+            Some(path.strip_prefix("/~").map(|p| format!("~{}", p)).unwrap_or(path))
+        }
+        _ => None,
+    };
+
+    Ok(DeepLinkTarget {
+        scheme,
+        target: QuickConnectTarget { username, host, port },
+        path,
+    })
+}
+
+/// 解析快速连接输入
+pub fn parse_target(input: &str) -> Result<QuickConnectTarget, String> {
+    let mut tokens = input.split_whitespace().peekable();
+    if tokens.peek() == Some(&"ssh") {
+        tokens.next();
+    }
+
+    let mut flag_port = None;
+    let mut flag_user = None;
+    let mut target = None;
+
+    while let Some(token) = tokens.next() {
+        match token {
+            "-p" => {
+                let value = tokens.next().ok_or("-p 缺少端口号")?;
+                flag_port = Some(parse_port(value)?);
+            }
+            "-l" => {
+                let value = tokens.next().ok_or("-l 缺少用户名")?;
+                flag_user = Some(value.to_string());
+            }
+            _ if token.starts_with('-') => return Err(format!("不支持的参数: {}", token)),
+            _ if target.is_some() => return Err(format!("无法识别的输入: {}", token)),
+            _ => target = Some(token),
+        }
+    }
+
+    let target = target.ok_or("请输入连接目标，例如 user@host:22")?;
+    let target = target.strip_prefix("ssh://").unwrap_or(target);
+    let target = target.strip_suffix('/').unwrap_or(target);
+
+    let (username, host_port) = match target.rsplit_once('@') {
+        Some((user, rest)) => (Some(user.to_string()), rest),
+        None => (None, target),
+    };
+    let (host, port) = split_host_port(host_port)?;
+
+    let username = flag_user.or(username);
+    if let Some(user) = &username {
+        validate_username(user)?;
+    }
+    validate_host(&host)?;
+
+    Ok(QuickConnectTarget {
+        username,
+        host,
+        // -p 优先于 host:port
+        port: flag_port.or(port),
+    })
+}
+
+/// 拆分主机和端口
+fn split_host_port(input: &str) -> Result<(String, Option<u16>), String> {
+    // [IPv6]:port 或 [IPv6]
+    if let Some(rest) = input.strip_prefix('[') {
+        let (host, after) = rest.split_once(']').ok_or("IPv6 地址缺少 ]")?;
+        let port = match after {
+            "" => None,
+            _ => Some(parse_port(after.strip_prefix(':').ok_or_else(|| format!("无法识别的输入: {}", after))?)?),
+        };
+        return Ok((host.to_string(), port));
+    }
+
+    match input.matches(':').count() {
+        0 => Ok((input.to_string(), None)),
+        1 => {
+            let (host, port) = input.split_once(':').unwrap_or((input, ""));
+            Ok((host.to_string(), Some(parse_port(port)?)))
+        }
+        // 多个冒号视为不带端口的 IPv6 字面量
+        _ => Ok((input.to_string(), None)),
+    }
+}
+
+fn parse_port(value: &str) -> Result<u16, String> {
+    match value.parse::<u16>() {
+        Ok(port) if port > 0 => Ok(port),
+        _ => Err(format!("无效的端口号: {}", value)),
+    }
+}
+
+fn validate_username(username: &str) -> Result<(), String> {
+    if username.is_empty() || username.chars().any(|c| c.is_whitespace() || c == '@' || c.is_control()) {
+        return Err(format!("无效的用户名: {}", username));
+    }
+    Ok(())
+}
+
+fn validate_host(host: &str) -> Result<(), String> {
+    if host.is_empty() {
+        return Err("主机不能为空".to_string());
+    }
+
+    if host.contains(':') {
+        // IPv6，允许 %zone 后缀
+        let address = host.split_once('%').map(|(addr, _)| addr).unwrap_or(host);
+        return address
+            .parse::<Ipv6Addr>()
+            .map(|_| ())
+            .map_err(|_| format!("无效的 IPv6 地址: {}", host));
+    }
+
+    let valid = host.len() <= 253
+        && !host.starts_with('-')
+        && host.chars().all(|c| c.is_alphanumeric() || matches!(c, '-' | '.' | '_'));
+    if !valid {
+        return Err(format!("无效的主机名: {}", host));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target(username: Option<&str>, host: &str, port: Option<u16>) -> QuickConnectTarget {
+        QuickConnectTarget { username: username.map(String::from), host: host.to_string(), port }
+    }
+
+    #[test]
+    fn test_parse_user_host_port() {
+        assert_eq!(parse_target("root@example.com:2222").unwrap(), target(Some("root"), "example.com", Some(2222)));
+        assert_eq!(parse_target("web-01").unwrap(), target(None, "web-01", None));
+        assert_eq!(parse_target("ssh://deploy@10.0.0.5/").unwrap(), target(Some("deploy"), "10.0.0.5", None));
+        assert_eq!(parse_target("ssh -p 2200 -l admin host").unwrap(), target(Some("admin"), "host", Some(2200)));
+    }
+
+    #[test]
+    fn test_parse_ipv6() {
+        assert_eq!(parse_target("::1").unwrap(), target(None, "::1", None));
+        assert_eq!(parse_target("[::1]:2222").unwrap(), target(None, "::1", Some(2222)));
+        assert_eq!(parse_target("me@[fe80::1%eth0]").unwrap(), target(Some("me"), "fe80::1%eth0", None));
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(parse_target("").is_err());
+        assert!(parse_target("host:0").is_err());
+        assert!(parse_target("host:http").is_err());
+        assert!(parse_target("[::1").is_err());
+        assert!(parse_target("bad host!").is_err());
+        assert!(parse_target("fe80::zz").is_err());
+    }
+
+    #[test]
+    fn test_parse_uri() {
+        let link = parse_uri("ssh://deploy;fingerprint=SHA256-abc@web-01.example.com:2222").unwrap();
+        assert_eq!(link.scheme, UriScheme::Ssh);
+        assert_eq!(link.target, target(Some("deploy"), "web-01.example.com", Some(2222)));
+        assert_eq!(link.path, None);
+
+        let link = parse_uri("SFTP://ops:secret@[::1]/var/log/my%20app?x=1").unwrap();
+        assert_eq!(link.scheme, UriScheme::Sftp);
+        assert_eq!(link.target, target(Some("ops"), "::1", None));
+        assert_eq!(link.path.as_deref(), Some("/var/log/my app"));
+        assert_eq!(parse_uri("sftp://host/~/backups").unwrap().path.as_deref(), Some("~/backups"));
+        assert_eq!(parse_uri("sftp://host/").unwrap().path, None);
+
+        assert!(parse_uri("http://host").is_err());
+        assert!(parse_uri("ssh://").is_err());
+        assert!(parse_uri("ssh://us%zzer@host").is_err());
+    }
+}
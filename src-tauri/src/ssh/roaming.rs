@@ -0,0 +1,155 @@
+//! 连接漫游（断线续连）
+//!
+//! 会话开启 `roaming` 后，传输层中断（非远程 shell 正常退出）时不再把连接标记为已断开，
+//! 而是保留终端状态在后台按退避间隔重连：期间的输入暂存、PTY 尺寸记录下来，
+//! 重连成功后恢复尺寸并补发输入，输出继续写入原来的通道，前端无需新开标签页。
+//! 状态变化通过 `ssh-roaming-{connectionId}` 事件通知前端。
+
+use bytes::Bytes;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// 漫游事件前缀
+pub const ROAMING_EVENT_PREFIX: &str = "ssh-roaming-";
+
+/// 最多重连次数，超过后按普通断开处理
+pub const MAX_ATTEMPTS: u32 = 10;
+
+/// 第一次重连前的等待时间
+const BACKOFF_MIN: Duration = Duration::from_secs(1);
+/// 重连间隔上限
+const BACKOFF_MAX: Duration = Duration::from_secs(30);
+
+/// 重连期间暂存输入的上限，超出部分丢弃（避免粘贴大量内容后在恢复时一次性执行）
+const MAX_PENDING_INPUT: usize = 4 * 1024;
+/// 前端输出通道失效期间缓存输出的上限，超出时丢弃最早的分块
+const MAX_MISSED_OUTPUT: usize = 256 * 1024;
+
+/// 第 `attempt` 次（从 1 开始）重连前的等待时间
+pub fn backoff(attempt: u32) -> Duration {
+    BACKOFF_MIN
+        .saturating_mul(1u32 << attempt.saturating_sub(1).min(16))
+        .min(BACKOFF_MAX)
+}
+
+/// `ssh-roaming-{connectionId}` 事件内容
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", tag = "state")]
+pub enum RoamingEvent {
+    /// 传输中断，等待后进行第 `attempt` 次重连
+    Reconnecting {
+        attempt: u32,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        reason: Option<String>,
+    },
+    /// 已恢复，`replayed_input` 为补发的暂存输入字节数
+    #[serde(rename_all = "camelCase")]
+    Resumed { replayed_input: usize },
+    /// 重连失败，连接已断开
+    Failed { reason: String },
+}
+
+/// 连接的漫游状态（重连后保留）
+#[derive(Debug, Default)]
+pub struct RoamingState {
+    /// 是否正在后台重连
+    resuming: bool,
+    /// 重连期间的输入
+    pending_input: Vec<u8>,
+    /// 最近一次设置的 PTY 尺寸（行、列）
+    pty_size: Option<(u16, u16)>,
+    /// 前端输出通道失效后是否缓存输出，重新订阅时补发
+    output_detached: bool,
+    missed_output: VecDeque<Bytes>,
+    missed_len: usize,
+}
+
+impl RoamingState {
+    pub fn is_resuming(&self) -> bool {
+        self.resuming
+    }
+
+    /// 开始重连，清除上次重连遗留的输入
+    pub fn begin_resume(&mut self) {
+        self.resuming = true;
+        self.pending_input.clear();
+    }
+
+    /// 结束重连，返回需要补发的输入
+    pub fn finish_resume(&mut self) -> Vec<u8> {
+        self.resuming = false;
+        std::mem::take(&mut self.pending_input)
+    }
+
+    /// 暂存重连期间的输入，超出上限时返回 false
+    pub fn queue_input(&mut self, data: &[u8]) -> bool {
+        let room = MAX_PENDING_INPUT.saturating_sub(self.pending_input.len());
+        self.pending_input.extend_from_slice(&data[..data.len().min(room)]);
+        data.len() <= room
+    }
+
+    pub fn record_size(&mut self, rows: u16, cols: u16) {
+        self.pty_size = Some((rows, cols));
+    }
+
+    pub fn pty_size(&self) -> Option<(u16, u16)> {
+        self.pty_size
+    }
+
+    /// 前端输出通道失效，后续输出改为缓存
+    pub fn detach_output(&mut self) {
+        self.output_detached = true;
+    }
+
+    /// 输出通道失效期间缓存输出，返回 false 表示未缓存（应直接发送）
+    pub fn buffer_output(&mut self, chunk: &Bytes) -> bool {
+        if !self.output_detached {
+            return false;
+        }
+        self.missed_len += chunk.len();
+        self.missed_output.push_back(chunk.clone());
+        while self.missed_len > MAX_MISSED_OUTPUT {
+            match self.missed_output.pop_front() {
+                Some(dropped) => self.missed_len -= dropped.len(),
+                None => break,
+            }
+        }
+        true
+    }
+
+    /// 前端重新订阅输出，取出缓存的输出
+    pub fn take_missed_output(&mut self) -> Vec<u8> {
+        self.output_detached = false;
+        self.missed_len = 0;
+        self.missed_output.drain(..).flat_map(|chunk| chunk.to_vec()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roaming_state() {
+        assert_eq!(backoff(1), Duration::from_secs(1));
+        assert_eq!(backoff(3), Duration::from_secs(4));
+        assert_eq!(backoff(MAX_ATTEMPTS), BACKOFF_MAX);
+
+        let mut state = RoamingState::default();
+        state.begin_resume();
+        assert!(state.queue_input(b"ls\r"));
+        assert!(!state.queue_input(&vec![b'x'; MAX_PENDING_INPUT]));
+        let input = state.finish_resume();
+        assert_eq!(input.len(), MAX_PENDING_INPUT);
+        assert!(input.starts_with(b"ls\r"));
+        assert!(!state.is_resuming());
+
+        assert!(!state.buffer_output(&Bytes::from_static(b"lost")));
+        state.detach_output();
+        assert!(state.buffer_output(&Bytes::from_static(b"hello ")));
+        assert!(state.buffer_output(&Bytes::from_static(b"world")));
+        assert_eq!(state.take_missed_output(), b"hello world");
+        assert!(!state.buffer_output(&Bytes::from_static(b"live")));
+    }
+}
@@ -0,0 +1,164 @@
+//! FIDO2 安全密钥（`sk-ssh-ed25519@openssh.com`、`sk-ecdsa-sha2-nistp256@openssh.com`）认证
+//!
+//! 安全密钥的私钥文件只保存密钥句柄，签名必须由硬件完成：通过 ssh-agent
+//! （OpenSSH 的 agent 会调用 ssh-sk-helper 与硬件通信）完成签名。
+//! 签名期间需要用户触摸密钥，签名前后通过 `ssh-security-key-{connectionId}` 事件通知前端。
+
+use crate::error::{Result, SSHError};
+use russh::keys::agent::client::{AgentClient, AgentStream};
+use russh::keys::{Algorithm, HashAlg, PrivateKey, PublicKey};
+use russh::{CryptoVec, Signer};
+use serde::Serialize;
+use std::future::Future;
+use std::path::Path;
+use std::sync::Arc;
+
+/// 安全密钥事件名前缀，完整事件名为 `ssh-security-key-{connectionId}`
+pub const SECURITY_KEY_EVENT_PREFIX: &str = "ssh-security-key-";
+
+/// Windows 上 OpenSSH agent 的命名管道
+#[cfg(windows)]
+const WINDOWS_AGENT_PIPE: &str = r"\\.\pipe\openssh-ssh-agent";
+
+/// `ssh-security-key-{connectionId}` 事件内容
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(tag = "state", rename_all = "camelCase")]
+pub enum SecurityKeyEvent {
+    /// 等待用户触摸安全密钥
+    #[serde(rename_all = "camelCase")]
+    TouchRequired {
+        host: String,
+        key_type: String,
+        fingerprint: String,
+    },
+    /// 签名结束（成功或用户未触摸、超时）
+    Done { success: bool },
+}
+
+/// 安全密钥事件的接收者（由连接管理器发送到前端）
+pub type SecurityKeyPrompt = Arc<dyn Fn(SecurityKeyEvent) + Send + Sync>;
+
+/// 是否为 FIDO2 安全密钥类型
+pub fn is_security_key(algorithm: &Algorithm) -> bool {
+    matches!(algorithm, Algorithm::SkEd25519 | Algorithm::SkEcdsaSha2NistP256)
+}
+
+/// 读取私钥文件旁的 `.pub` 公钥（私钥无法解析时用于识别安全密钥）
+pub fn sibling_public_key(private_key_path: &str) -> Option<PublicKey> {
+    let path = format!("{}.pub", private_key_path);
+    if !Path::new(&path).exists() {
+        return None;
+    }
+    russh::keys::load_public_key(&path).ok()
+}
+
+/// ssh-agent 客户端
+pub type Agent = AgentClient<Box<dyn AgentStream + Send + Unpin + 'static>>;
+
+/// 连接系统的 ssh-agent（Unix 上为 SSH_AUTH_SOCK，Windows 上为 OpenSSH agent 命名管道）
+pub async fn connect_agent() -> Result<Agent> {
+    #[cfg(unix)]
+    let agent = AgentClient::connect_env().await.map(AgentClient::dynamic);
+    #[cfg(windows)]
+    let agent = AgentClient::connect_named_pipe(WINDOWS_AGENT_PIPE).await.map(AgentClient::dynamic);
+
+    agent.map_err(|e| {
+        SSHError::AuthenticationFailed(format!("安全密钥需要通过 ssh-agent 签名，但无法连接 ssh-agent: {}", e))
+    })
+}
+
+/// 确保 agent 中有该安全密钥：没有时尝试添加私钥文件中的密钥句柄
+pub async fn ensure_identity(agent: &mut Agent, public_key: &PublicKey, private_key: Option<&PrivateKey>) -> Result<()> {
+    let identities = agent.request_identities().await.unwrap_or_default();
+    if identities.iter().any(|identity| identity.key_data() == public_key.key_data()) {
+        return Ok(());
+    }
+
+    let added = match private_key {
+        Some(private_key) => agent.add_identity(private_key, &[]).await.is_ok(),
+        None => false,
+    };
+    if added {
+        Ok(())
+    } else {
+        Err(SSHError::AuthenticationFailed(
+            "ssh-agent 中没有该安全密钥，请先执行 ssh-add 添加（需要 OpenSSH 8.2 及以上版本）".to_string(),
+        ))
+    }
+}
+
+/// 在签名前后发送触摸提示的签名器
+pub struct TouchPromptSigner<S> {
+    inner: S,
+    host: String,
+    prompt: Option<SecurityKeyPrompt>,
+}
+
+impl<S> TouchPromptSigner<S> {
+    pub fn new(inner: S, host: &str, prompt: Option<SecurityKeyPrompt>) -> Self {
+        Self { inner, host: host.to_string(), prompt }
+    }
+
+    fn notify(&self, event: SecurityKeyEvent) {
+        if let Some(prompt) = &self.prompt {
+            prompt(event);
+        }
+    }
+}
+
+impl<S: Signer + Send> Signer for TouchPromptSigner<S> {
+    type Error = S::Error;
+
+    #[allow(clippy::manual_async_fn)]
+    fn auth_publickey_sign(
+        &mut self,
+        key: &PublicKey,
+        hash_alg: Option<HashAlg>,
+        to_sign: CryptoVec,
+    ) -> impl Future<Output = std::result::Result<CryptoVec, Self::Error>> + Send {
+        // 提前取出需要的数据，返回的 future 不持有借用的参数
+        let security_key = is_security_key(&key.algorithm());
+        if security_key {
+            self.notify(SecurityKeyEvent::TouchRequired {
+                host: self.host.clone(),
+                key_type: key.algorithm().to_string(),
+                fingerprint: key.fingerprint(HashAlg::Sha256).to_string(),
+            });
+        }
+        let key = key.clone();
+        async move {
+            let result = self.inner.auth_publickey_sign(&key, hash_alg, to_sign).await;
+            if security_key {
+                self.notify(SecurityKeyEvent::Done { success: result.is_ok() });
+            }
+            result
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_security_key_types() {
+        assert!(is_security_key(&Algorithm::SkEd25519));
+        assert!(is_security_key(&Algorithm::SkEcdsaSha2NistP256));
+        assert!(!is_security_key(&Algorithm::Ed25519));
+
+        let event = SecurityKeyEvent::TouchRequired {
+            host: "example.com".to_string(),
+            key_type: "sk-ssh-ed25519@openssh.com".to_string(),
+            fingerprint: "SHA256:abc".to_string(),
+        };
+        assert_eq!(
+            serde_json::to_value(&event).unwrap(),
+            serde_json::json!({
+                "state": "touchRequired",
+                "host": "example.com",
+                "keyType": "sk-ssh-ed25519@openssh.com",
+                "fingerprint": "SHA256:abc",
+            })
+        );
+    }
+}
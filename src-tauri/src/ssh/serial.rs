@@ -0,0 +1,154 @@
+//! 串口连接设置
+//!
+//! 用于通过 Console 口管理路由器、交换机等网络设备。串口会话的 `host` 为设备路径
+//! （如 `COM3`、`/dev/ttyUSB0`），线路参数保存在 [`SerialConfig`] 中。
+
+use serde::{Deserialize, Serialize};
+
+/// 校验位
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SerialParity {
+    #[default]
+    None,
+    Odd,
+    Even,
+}
+
+/// 流控制
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SerialFlowControl {
+    #[default]
+    None,
+    /// XON/XOFF
+    Software,
+    /// RTS/CTS
+    Hardware,
+}
+
+/// 串口线路参数（默认 9600 8N1，无流控，与大多数网络设备的 Console 口一致）
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SerialConfig {
+    #[serde(default = "default_baud_rate")]
+    pub baud_rate: u32,
+    /// 数据位（5-8）
+    #[serde(default = "default_data_bits")]
+    pub data_bits: u8,
+    #[serde(default)]
+    pub parity: SerialParity,
+    /// 停止位（1 或 2）
+    #[serde(default = "default_stop_bits")]
+    pub stop_bits: u8,
+    #[serde(default)]
+    pub flow_control: SerialFlowControl,
+}
+
+impl Default for SerialConfig {
+    fn default() -> Self {
+        Self {
+            baud_rate: default_baud_rate(),
+            data_bits: default_data_bits(),
+            parity: SerialParity::None,
+            stop_bits: default_stop_bits(),
+            flow_control: SerialFlowControl::None,
+        }
+    }
+}
+
+impl SerialConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.baud_rate == 0 {
+            return Err("Baud rate must be greater than 0".to_string());
+        }
+        if !(5..=8).contains(&self.data_bits) {
+            return Err(format!("Invalid data bits: {} (expected 5-8)", self.data_bits));
+        }
+        if !matches!(self.stop_bits, 1 | 2) {
+            return Err(format!("Invalid stop bits: {} (expected 1 or 2)", self.stop_bits));
+        }
+        Ok(())
+    }
+}
+
+fn default_baud_rate() -> u32 {
+    9600
+}
+
+fn default_data_bits() -> u8 {
+    8
+}
+
+fn default_stop_bits() -> u8 {
+    1
+}
+
+/// 本机可用的串口
+#[derive(Clone, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SerialPortInfo {
+    /// 设备路径，作为串口会话的 `host`
+    pub path: String,
+    /// 端口类型：usb、pci、bluetooth 或 unknown
+    pub port_type: String,
+    /// USB 转串口设备的描述（厂商、产品名）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// 列出本机串口
+#[cfg(not(target_os = "android"))]
+pub fn list_ports() -> Result<Vec<SerialPortInfo>, String> {
+    use serialport::SerialPortType;
+
+    let ports = serialport::available_ports().map_err(|e| format!("Failed to list serial ports: {}", e))?;
+    Ok(ports
+        .into_iter()
+        .map(|port| {
+            let (port_type, description) = match port.port_type {
+                SerialPortType::UsbPort(usb) => {
+                    let description = [usb.manufacturer, usb.product]
+                        .into_iter()
+                        .flatten()
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    ("usb", (!description.is_empty()).then_some(description))
+                }
+                SerialPortType::PciPort => ("pci", None),
+                SerialPortType::BluetoothPort => ("bluetooth", None),
+                SerialPortType::Unknown => ("unknown", None),
+            };
+            SerialPortInfo {
+                path: port.port_name,
+                port_type: port_type.to_string(),
+                description,
+            }
+        })
+        .collect())
+}
+
+/// 移动平台不支持串口
+#[cfg(target_os = "android")]
+pub fn list_ports() -> Result<Vec<SerialPortInfo>, String> {
+    Ok(Vec::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serial_config_defaults() {
+        let config: SerialConfig = serde_json::from_str(r#"{"baudRate": 115200, "parity": "even"}"#).unwrap();
+        assert_eq!(config.baud_rate, 115200);
+        assert_eq!(config.data_bits, 8);
+        assert_eq!(config.stop_bits, 1);
+        assert_eq!(config.parity, SerialParity::Even);
+        assert_eq!(config.flow_control, SerialFlowControl::None);
+        assert!(config.validate().is_ok());
+
+        assert!(SerialConfig { data_bits: 9, ..Default::default() }.validate().is_err());
+        assert!(SerialConfig { stop_bits: 3, ..Default::default() }.validate().is_err());
+    }
+}
@@ -1,4 +1,13 @@
 use serde::{Deserialize, Serialize};
+use crate::ssh::algorithms::AlgorithmPreferences;
+use crate::ssh::dns::DnsResolverConfig;
+use crate::ssh::elevation::ElevationPolicy;
+use crate::ssh::encoding::TerminalEncoding;
+use crate::ssh::forwarding::RemoteForwardSpec;
+use crate::ssh::login_script::LoginStep;
+use crate::ssh::pre_connect::PreConnectAction;
+use crate::ssh::serial::SerialConfig;
+use crate::ssh::wol::WakeOnLanConfig;
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -23,10 +32,82 @@ pub struct SessionConfig {
     /// 心跳间隔（秒），0表示禁用
     #[serde(default = "default_keep_alive_interval")]
     pub keep_alive_interval: u64,
+    /// 自定义 DNS 解析器，为空时使用全局设置
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dns_resolver: Option<DnsResolverConfig>,
+    /// 跳板机（ProxyJump），跳板机自身也可以再配置跳板机形成多级链路
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jump_host: Option<Box<SessionConfig>>,
+    /// 连接后执行的远程命令（如 `tmux new -A -s main`），为空时启动登录 shell
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote_command: Option<String>,
+    /// 远程主机的字符编码，默认 UTF-8 透传
+    #[serde(default, skip_serializing_if = "TerminalEncoding::is_utf8")]
+    pub encoding: TerminalEncoding,
+    /// 远程端口转发（连接后自动建立）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub remote_forwards: Vec<RemoteForwardSpec>,
+    /// 登录脚本（应答可能包含密码，只从加密存储加载，不参与序列化）
+    #[serde(skip)]
+    pub login_script: Vec<LoginStep>,
+    /// 受保护会话（生产主机），输入危险命令时需要确认
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub protected: bool,
+    /// 以 root/管理员身份连接时的时限，到期后断开或降权
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub elevation: Option<ElevationPolicy>,
+    /// 密钥交换、主机密钥、加密和 MAC 算法偏好，为空时使用默认算法
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub algorithms: Option<AlgorithmPreferences>,
+    /// shell 打开后自动输入的命令（由连接 profile 设置，不单独保存）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub startup_commands: Vec<String>,
+    /// 连接后端，`local` 时在本机 PTY 中启动 shell（忽略主机和认证设置），
+    /// `serial` 时 `host` 为串口设备路径，`wsl` 时启动 `wsl_distribution` 指定的发行版
+    #[serde(default, skip_serializing_if = "BackendType::is_russh")]
+    pub backend_type: BackendType,
+    /// 串口线路参数（`serial` 后端），为空时使用 9600 8N1
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub serial: Option<SerialConfig>,
+    /// WSL 发行版（`wsl` 后端），为空时使用默认发行版
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wsl_distribution: Option<String>,
+    /// 传输中断时在后台重连并恢复到原终端（仅 SSH 后端）
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub roaming: bool,
+    /// 连接前发送网络唤醒魔术包并等待 SSH 端口可连接（仅 SSH 后端）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wake_on_lan: Option<WakeOnLanConfig>,
+    /// 建立 TCP 连接前按顺序执行的动作（端口敲门、HTTPS 敲门地址、本地命令，仅 SSH 后端）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pre_connect: Vec<PreConnectAction>,
+}
+
+/// 会话使用的后端
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum BackendType {
+    /// 通过 SSH 连接远程主机
+    #[default]
+    Russh,
+    /// 本机 shell（`remote_command` 不为空时改为执行该命令）
+    Local,
+    /// 串口（网络设备 Console）
+    Serial,
+    /// Telnet（不支持 SSH 的旧设备，忽略认证设置）
+    Telnet,
+    /// WSL 发行版（仅 Windows，使用本机 PTY）
+    Wsl,
+}
+
+impl BackendType {
+    pub fn is_russh(&self) -> bool {
+        *self == BackendType::Russh
+    }
 }
 
 /// 用于部分更新会话配置的结构体
-#[derive(Clone, Serialize, Deserialize, Debug)]
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct SessionConfigUpdate {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -51,6 +132,36 @@ pub struct SessionConfigUpdate {
     pub group: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub keep_alive_interval: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jump_host: Option<Box<SessionConfig>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote_command: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<TerminalEncoding>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote_forwards: Option<Vec<RemoteForwardSpec>>,
+    #[serde(skip)]
+    pub login_script: Option<Vec<LoginStep>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protected: Option<bool>,
+    /// `Some(None)` 表示取消时限
+    #[serde(skip)]
+    pub elevation: Option<Option<ElevationPolicy>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub algorithms: Option<AlgorithmPreferences>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backend_type: Option<BackendType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub serial: Option<SerialConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wsl_distribution: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub roaming: Option<bool>,
+    /// MAC 地址为空表示关闭网络唤醒
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wake_on_lan: Option<WakeOnLanConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pre_connect: Option<Vec<PreConnectAction>>,
 }
 
 fn default_strict_host_key_checking() -> bool {
@@ -65,17 +176,35 @@ fn default_keep_alive_interval() -> u64 {
     30 // 默认30秒
 }
 
-#[derive(Clone, Serialize, Deserialize, Debug)]
+#[derive(Clone, Serialize, Deserialize)]
 pub enum AuthMethod {
     Password { password: String },
     #[serde(rename_all = "camelCase")]
     PublicKey { private_key_path: String, passphrase: Option<String> },
 }
 
+/// Debug 输出不包含密码和私钥口令
+impl std::fmt::Debug for AuthMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Password { .. } => f.debug_struct("Password").finish_non_exhaustive(),
+            Self::PublicKey { private_key_path, passphrase } => f
+                .debug_struct("PublicKey")
+                .field("private_key_path", private_key_path)
+                .field("has_passphrase", &passphrase.is_some())
+                .finish(),
+        }
+    }
+}
+
 #[derive(Clone, Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub enum SessionStatus {
-    Disconnected,
+    /// 远程关闭或连接中断时带有原因（退出码、信号或服务器的断开原因）
+    Disconnected {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        reason: Option<String>,
+    },
     Connecting,
     Connected,
     Error(String),
@@ -100,3 +229,21 @@ pub struct SessionInfo {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub connection_id: Option<String>,
 }
+
+impl SessionInfo {
+    /// 由会话配置构建信息（配置本身，未连接）
+    pub fn from_config(id: &str, config: &SessionConfig) -> Self {
+        Self {
+            id: id.to_string(),
+            name: config.name.clone(),
+            host: config.host.clone(),
+            port: config.port,
+            username: config.username.clone(),
+            status: SessionStatus::Disconnected { reason: None },
+            connected_at: None,
+            group: config.group.clone(),
+            connection_session_id: None,  // 配置本身
+            connection_id: None,  // 配置本身没有 connection_id
+        }
+    }
+}
@@ -0,0 +1,127 @@
+//! 连接流量统计
+//!
+//! 统计每个连接收发的终端数据字节数，定期通过 `ssh-stats-{connectionId}` 事件发送
+//! 累计字节数、吞吐量和连接时长；断开连接时累计值写入 `connection_stats` 表作为使用历史。
+
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// 流量统计事件前缀
+pub const STATS_EVENT_PREFIX: &str = "ssh-stats-";
+
+/// 统计事件发送间隔
+pub const STATS_INTERVAL: Duration = Duration::from_secs(2);
+
+/// 收发字节计数，读写终端数据时无需加锁
+///
+/// 发送计数包含用户输入以及程序代为写入终端 channel 的数据（确认后的受保护命令、
+/// 登录脚本应答、权限到期时的降权命令）
+#[derive(Debug, Default)]
+pub struct TrafficCounter {
+    sent: AtomicU64,
+    received: AtomicU64,
+}
+
+impl TrafficCounter {
+    pub fn add_sent(&self, bytes: usize) {
+        self.sent.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    pub fn add_received(&self, bytes: usize) {
+        self.received.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    /// 当前累计值 (sent, received)
+    pub fn totals(&self) -> (u64, u64) {
+        (self.sent.load(Ordering::Relaxed), self.received.load(Ordering::Relaxed))
+    }
+
+    /// 取出累计值并清零（断开连接时使用）
+    pub fn take(&self) -> (u64, u64) {
+        (self.sent.swap(0, Ordering::Relaxed), self.received.swap(0, Ordering::Relaxed))
+    }
+}
+
+/// 流量统计快照（即 `ssh-stats-{connectionId}` 事件内容）
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct StatsSnapshot {
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+    /// 最近一个统计间隔的发送速率（字节/秒）
+    pub send_rate: f64,
+    /// 最近一个统计间隔的接收速率（字节/秒）
+    pub receive_rate: f64,
+    /// 已连接时长（秒）
+    pub uptime_secs: u64,
+}
+
+/// 根据相邻两次采样计算吞吐量
+#[derive(Debug, Default)]
+pub struct ThroughputMeter {
+    last_sample: Option<(Instant, u64, u64)>,
+    last_snapshot: StatsSnapshot,
+}
+
+impl ThroughputMeter {
+    /// 记录一次采样并返回快照
+    pub fn sample(&mut self, now: Instant, (sent, received): (u64, u64), uptime_secs: u64) -> StatsSnapshot {
+        let (send_rate, receive_rate) = match self.last_sample {
+            Some((at, last_sent, last_received)) if now > at => {
+                let secs = now.duration_since(at).as_secs_f64();
+                (
+                    sent.saturating_sub(last_sent) as f64 / secs,
+                    received.saturating_sub(last_received) as f64 / secs,
+                )
+            }
+            _ => (0.0, 0.0),
+        };
+        self.last_sample = Some((now, sent, received));
+        self.last_snapshot = StatsSnapshot {
+            bytes_sent: sent,
+            bytes_received: received,
+            send_rate,
+            receive_rate,
+            uptime_secs,
+        };
+        self.last_snapshot.clone()
+    }
+
+    /// 最近一次采样的快照，累计值和时长更新为当前值
+    pub fn current(&self, totals: (u64, u64), uptime_secs: u64) -> StatsSnapshot {
+        StatsSnapshot {
+            bytes_sent: totals.0,
+            bytes_received: totals.1,
+            uptime_secs,
+            ..self.last_snapshot.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_throughput() {
+        let counter = TrafficCounter::default();
+        let mut meter = ThroughputMeter::default();
+        let start = Instant::now();
+
+        counter.add_sent(100);
+        counter.add_received(1000);
+        let snapshot = meter.sample(start, counter.totals(), 0);
+        assert_eq!((snapshot.bytes_sent, snapshot.bytes_received), (100, 1000));
+        assert_eq!(snapshot.receive_rate, 0.0);
+
+        counter.add_received(4000);
+        let snapshot = meter.sample(start + Duration::from_secs(2), counter.totals(), 2);
+        assert_eq!(snapshot.receive_rate, 2000.0);
+        assert_eq!(snapshot.send_rate, 0.0);
+
+        assert_eq!(meter.current(counter.totals(), 3).uptime_secs, 3);
+        assert_eq!(counter.take(), (100, 5000));
+        assert_eq!(counter.totals(), (0, 0));
+    }
+}
@@ -0,0 +1,337 @@
+//! 连接输出的纯文本记录
+//!
+//! 去除 ANSI 控制序列后按行保存终端输出，供屏幕阅读器友好的视图和
+//! 无法解析转义序列的外部工具使用。行号从连接创建开始单调递增，
+//! 超出保留行数的旧行会被丢弃，但不影响后续行号。
+
+use serde::Serialize;
+use std::collections::VecDeque;
+
+/// 默认保留的行数
+pub const DEFAULT_TRANSCRIPT_LINES: usize = 10_000;
+
+/// 单行最大字符数，超出部分丢弃（防止无换行的输出占用过多内存）
+const MAX_LINE_CHARS: usize = 16 * 1024;
+
+/// 查询时默认返回的行数
+pub const DEFAULT_RANGE_COUNT: usize = 200;
+
+/// 记录中的一行
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptLine {
+    /// 行号（从 0 开始，连接生命周期内单调递增）
+    pub index: u64,
+    pub text: String,
+}
+
+/// 按范围查询的结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptRange {
+    /// 仍保留的最早行号
+    pub first_index: u64,
+    /// 下一个完整行的行号（即已完成的总行数）
+    pub next_index: u64,
+    pub lines: Vec<TranscriptLine>,
+    /// 当前尚未换行的内容（如提示符）
+    pub partial: String,
+}
+
+/// 转义序列解析状态
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ParseState {
+    Normal,
+    /// 收到 ESC
+    Escape,
+    /// ESC [ ... 直到结束字节
+    Csi,
+    /// ESC ] ... 直到 BEL 或 ST
+    Osc,
+    /// OSC 中收到 ESC，等待 '\\'
+    OscEscape,
+    /// ESC ( / ESC ) 等字符集选择，再跳过一个字符
+    Charset,
+}
+
+/// 连接输出的纯文本记录
+pub struct Transcript {
+    lines: VecDeque<String>,
+    /// lines[0] 的行号
+    first_index: u64,
+    max_lines: usize,
+    /// 当前行内容和光标位置（处理 \r 覆盖和退格）
+    current: Vec<char>,
+    cursor: usize,
+    state: ParseState,
+    /// CSI 参数（用于识别 K 擦除）
+    csi_params: String,
+    /// 跨分块的不完整 UTF-8 字节
+    pending_utf8: Vec<u8>,
+    /// 是否发送实时新行事件
+    live_events: bool,
+}
+
+impl Transcript {
+    pub fn new(max_lines: usize) -> Self {
+        Self {
+            lines: VecDeque::new(),
+            first_index: 0,
+            max_lines: max_lines.max(1),
+            current: Vec::new(),
+            cursor: 0,
+            state: ParseState::Normal,
+            csi_params: String::new(),
+            pending_utf8: Vec::new(),
+            live_events: false,
+        }
+    }
+
+    pub fn live_events(&self) -> bool {
+        self.live_events
+    }
+
+    pub fn set_live_events(&mut self, enabled: bool) {
+        self.live_events = enabled;
+    }
+
+    /// 追加一段原始输出，返回本次完成的行
+    pub fn push(&mut self, data: &[u8]) -> Vec<TranscriptLine> {
+        let mut completed = Vec::new();
+
+        let mut bytes = std::mem::take(&mut self.pending_utf8);
+        bytes.extend_from_slice(data);
+
+        let mut rest = bytes.as_slice();
+        loop {
+            match std::str::from_utf8(rest) {
+                Ok(text) => {
+                    self.feed_str(text, &mut completed);
+                    break;
+                }
+                Err(e) => {
+                    let (valid, after) = rest.split_at(e.valid_up_to());
+                    // valid_up_to 之前的部分一定是合法 UTF-8
+                    self.feed_str(std::str::from_utf8(valid).unwrap_or_default(), &mut completed);
+                    match e.error_len() {
+                        Some(len) => {
+                            self.feed_char(char::REPLACEMENT_CHARACTER, &mut completed);
+                            rest = &after[len..];
+                        }
+                        None => {
+                            // 字符被分块截断，等待下一段数据
+                            self.pending_utf8 = after.to_vec();
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+
+        completed
+    }
+
+    fn feed_str(&mut self, text: &str, completed: &mut Vec<TranscriptLine>) {
+        for c in text.chars() {
+            self.feed_char(c, completed);
+        }
+    }
+
+    fn feed_char(&mut self, c: char, completed: &mut Vec<TranscriptLine>) {
+        match self.state {
+            ParseState::Normal => match c {
+                '\x1b' => self.state = ParseState::Escape,
+                '\n' => completed.push(self.commit_line()),
+                '\r' => self.cursor = 0,
+                '\x08' => self.cursor = self.cursor.saturating_sub(1),
+                '\t' => self.put_char('\t'),
+                c if c.is_control() => {}
+                c => self.put_char(c),
+            },
+            ParseState::Escape => {
+                self.state = match c {
+                    '[' => {
+                        self.csi_params.clear();
+                        ParseState::Csi
+                    }
+                    ']' => ParseState::Osc,
+                    '(' | ')' | '*' | '+' => ParseState::Charset,
+                    _ => ParseState::Normal,
+                };
+            }
+            ParseState::Csi => {
+                // 结束字节范围 0x40..=0x7E
+                if ('\x40'..='\x7e').contains(&c) {
+                    if c == 'K' {
+                        self.erase_in_line();
+                    }
+                    self.state = ParseState::Normal;
+                } else {
+                    self.csi_params.push(c);
+                }
+            }
+            ParseState::Osc => match c {
+                '\x07' => self.state = ParseState::Normal,
+                '\x1b' => self.state = ParseState::OscEscape,
+                _ => {}
+            },
+            ParseState::OscEscape => {
+                self.state = if c == '\\' { ParseState::Normal } else { ParseState::Osc };
+            }
+            ParseState::Charset => self.state = ParseState::Normal,
+        }
+    }
+
+    /// 在光标处写入字符（覆盖或追加）
+    fn put_char(&mut self, c: char) {
+        if self.cursor < self.current.len() {
+            self.current[self.cursor] = c;
+        } else if self.current.len() < MAX_LINE_CHARS {
+            self.current.push(c);
+        } else {
+            return;
+        }
+        self.cursor += 1;
+    }
+
+    /// CSI K：0/缺省擦除到行尾，1 擦除到行首，2 擦除整行
+    fn erase_in_line(&mut self) {
+        match self.csi_params.as_str() {
+            "" | "0" => self.current.truncate(self.cursor),
+            "1" => {
+                let end = self.cursor.min(self.current.len());
+                self.current[..end].fill(' ');
+            }
+            "2" => self.current.clear(),
+            _ => {}
+        }
+    }
+
+    /// 完成当前行
+    fn commit_line(&mut self) -> TranscriptLine {
+        let text: String = self.current.iter().collect();
+        let text = text.trim_end().to_string();
+        self.current.clear();
+        self.cursor = 0;
+
+        let index = self.next_index();
+        self.lines.push_back(text.clone());
+        if self.lines.len() > self.max_lines {
+            self.lines.pop_front();
+            self.first_index += 1;
+        }
+
+        TranscriptLine { index, text }
+    }
+
+    fn next_index(&self) -> u64 {
+        self.first_index + self.lines.len() as u64
+    }
+
+    /// 查询行范围
+    ///
+    /// `start` 为空时返回最后 `count` 行；早于保留范围的行号从最早保留的行开始返回
+    pub fn range(&self, start: Option<u64>, count: Option<usize>) -> TranscriptRange {
+        let count = count.unwrap_or(DEFAULT_RANGE_COUNT);
+        let next_index = self.next_index();
+        let start = match start {
+            Some(start) => start.max(self.first_index),
+            None => next_index.saturating_sub(count as u64).max(self.first_index),
+        };
+
+        let lines = self
+            .lines
+            .iter()
+            .enumerate()
+            .skip((start - self.first_index) as usize)
+            .take(count)
+            .map(|(offset, text)| TranscriptLine {
+                index: self.first_index + offset as u64,
+                text: text.clone(),
+            })
+            .collect();
+
+        TranscriptRange {
+            first_index: self.first_index,
+            next_index,
+            lines,
+            partial: self.partial(),
+        }
+    }
+
+    /// 当前尚未换行的内容（如提示符）
+    pub fn partial(&self) -> String {
+        self.current.iter().collect::<String>().trim_end().to_string()
+    }
+
+    /// 清空记录（行号继续递增）
+    pub fn clear(&mut self) {
+        self.first_index = self.next_index();
+        self.lines.clear();
+        self.current.clear();
+        self.cursor = 0;
+    }
+}
+
+impl Default for Transcript {
+    fn default() -> Self {
+        Self::new(DEFAULT_TRANSCRIPT_LINES)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(lines: &[TranscriptLine]) -> Vec<&str> {
+        lines.iter().map(|l| l.text.as_str()).collect()
+    }
+
+    #[test]
+    fn test_strips_ansi_sequences() {
+        let mut transcript = Transcript::default();
+        let lines = transcript.push(b"\x1b[01;32mfile.log\x1b[0m\r\n\x1b]0;user@host: ~\x07$ ls\r\n");
+        assert_eq!(texts(&lines), vec!["file.log", "$ ls"]);
+    }
+
+    #[test]
+    fn test_sequences_split_across_chunks() {
+        let mut transcript = Transcript::default();
+        assert!(transcript.push(b"hello \x1b[3").is_empty());
+        let lines = transcript.push(b"1mworld\x1b[0m\n");
+        assert_eq!(texts(&lines), vec!["hello world"]);
+
+        // 被截断的 UTF-8 字符
+        let bytes = "中文\n".as_bytes();
+        assert!(transcript.push(&bytes[..4]).is_empty());
+        let lines = transcript.push(&bytes[4..]);
+        assert_eq!(texts(&lines), vec!["中文"]);
+        assert_eq!(lines[0].index, 1);
+    }
+
+    #[test]
+    fn test_carriage_return_and_backspace() {
+        let mut transcript = Transcript::default();
+        let lines = transcript.push(b"progress 10%\rprogress 100%\n$ lss\x08\x1b[K\n");
+        assert_eq!(texts(&lines), vec!["progress 100%", "$ ls"]);
+    }
+
+    #[test]
+    fn test_range_and_retention() {
+        let mut transcript = Transcript::new(3);
+        for i in 0..5 {
+            transcript.push(format!("line {}\n", i).as_bytes());
+        }
+        transcript.push(b"$ ");
+
+        let range = transcript.range(None, Some(2));
+        assert_eq!(range.first_index, 2);
+        assert_eq!(range.next_index, 5);
+        assert_eq!(texts(&range.lines), vec!["line 3", "line 4"]);
+        assert_eq!(range.lines[0].index, 3);
+        assert_eq!(range.partial, "$");
+
+        let range = transcript.range(Some(0), None);
+        assert_eq!(texts(&range.lines), vec!["line 2", "line 3", "line 4"]);
+    }
+}
@@ -0,0 +1,155 @@
+//! 会话字段模板
+//!
+//! 会话的主机名、用户名和启动命令可以包含 `{{...}}` 占位符，连接时替换：
+//! - `{{name}}`：变量存储中的变量（会话变量优先于全局变量）
+//! - `{{env:NAME}}`：变量存储中的 NAME，未定义时读取本机环境变量
+//! - `{{date}}`、`{{time}}`、`{{datetime}}`、`{{timestamp}}`：当前本地时间，
+//!   `{{date:%Y%m%d}}` 可指定 strftime 格式
+//!
+//! 这样一个模板会话即可连接多个相似的目标（如 `{{env:STAGE}}.example.com`）。
+//! 变量也可以由 `run_and_capture` 从远程命令输出中提取（如容器 ID），供后续片段和 SFTP 路径使用。
+
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Local};
+use std::collections::HashMap;
+
+const OPEN: &str = "{{";
+const CLOSE: &str = "}}";
+
+/// 是否包含模板占位符
+pub fn is_template(text: &str) -> bool {
+    text.contains(OPEN)
+}
+
+/// 替换模板中的占位符，未定义的变量返回错误
+pub fn render(template: &str, variables: &HashMap<String, String>) -> Result<String, String> {
+    render_at(template, variables, Local::now())
+}
+
+fn render_at(template: &str, variables: &HashMap<String, String>, now: DateTime<Local>) -> Result<String, String> {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find(OPEN) {
+        let Some(len) = rest[start + OPEN.len()..].find(CLOSE) else {
+            // 没有闭合的占位符按原样保留
+            break;
+        };
+        output.push_str(&rest[..start]);
+        let expr = rest[start + OPEN.len()..start + OPEN.len() + len].trim();
+        output.push_str(&evaluate(expr, variables, now)?);
+        rest = &rest[start + OPEN.len() + len + CLOSE.len()..];
+    }
+    output.push_str(rest);
+
+    Ok(output)
+}
+
+fn evaluate(expr: &str, variables: &HashMap<String, String>, now: DateTime<Local>) -> Result<String, String> {
+    let (name, arg) = match expr.split_once(':') {
+        Some((name, arg)) => (name.trim(), Some(arg.trim())),
+        None => (expr, None),
+    };
+
+    match (name, arg) {
+        ("env", Some(var)) => variables
+            .get(var)
+            .cloned()
+            .or_else(|| std::env::var(var).ok())
+            .ok_or_else(|| format!("未定义的环境变量: {}", var)),
+        ("date", None) => Ok(now.format("%Y-%m-%d").to_string()),
+        ("time", None) => Ok(now.format("%H:%M:%S").to_string()),
+        ("datetime", None) => Ok(now.format("%Y-%m-%d %H:%M:%S").to_string()),
+        ("timestamp", None) => Ok(now.timestamp().to_string()),
+        ("date", Some(format)) => {
+            let items: Vec<Item> = StrftimeItems::new(format).collect();
+            if items.iter().any(|item| matches!(item, Item::Error)) {
+                return Err(format!("无效的日期格式: {}", format));
+            }
+            Ok(now.format_with_items(items.into_iter()).to_string())
+        }
+        (_, None) if !name.is_empty() => variables
+            .get(name)
+            .cloned()
+            .ok_or_else(|| format!("未定义的变量: {}", name)),
+        _ => Err(format!("无效的占位符: {{{{{}}}}}", expr)),
+    }
+}
+
+/// 变量名只允许字母、数字、`_`、`-` 和 `.`
+pub fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && !matches!(name, "env" | "date" | "time" | "datetime" | "timestamp")
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
+
+/// 用正则表达式从命令输出中提取变量值
+///
+/// 优先取名为 `value` 的捕获组，其次取第一个捕获组，没有捕获组时取整个匹配；
+/// 没有匹配时返回 None
+pub fn capture(output: &str, pattern: &regex::Regex) -> Option<String> {
+    let captures = pattern.captures(output)?;
+    captures
+        .name("value")
+        .or_else(|| captures.get(1))
+        .or_else(|| captures.get(0))
+        .map(|m| m.as_str().trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn variables() -> HashMap<String, String> {
+        HashMap::from([
+            ("STAGE".to_string(), "staging".to_string()),
+            ("region".to_string(), "eu".to_string()),
+        ])
+    }
+
+    #[test]
+    fn test_render_variables() {
+        let now = Local.with_ymd_and_hms(2024, 3, 9, 8, 5, 0).unwrap();
+        let vars = variables();
+
+        assert_eq!(render_at("{{env:STAGE}}.example.com", &vars, now).unwrap(), "staging.example.com");
+        assert_eq!(render_at("web-{{ region }}", &vars, now).unwrap(), "web-eu");
+        assert_eq!(
+            render_at("tmux new -A -s {{date:%Y%m%d}} # {{date}} {{time}}", &vars, now).unwrap(),
+            "tmux new -A -s 20240309 # 2024-03-09 08:05:00"
+        );
+        assert_eq!(render_at("plain {{ unclosed", &vars, now).unwrap(), "plain {{ unclosed");
+    }
+
+    #[test]
+    fn test_render_errors() {
+        let vars = variables();
+        assert!(render("{{missing}}", &vars).is_err());
+        assert!(render("{{env:SSH_TERMINAL_SURELY_UNDEFINED}}", &vars).is_err());
+        assert!(render("{{}}", &vars).is_err());
+        assert!(render("{{date:%Q}}", &vars).is_err());
+    }
+
+    #[test]
+    fn test_valid_name() {
+        assert!(is_valid_name("STAGE"));
+        assert!(is_valid_name("db.host-1"));
+        assert!(!is_valid_name("date"));
+        assert!(!is_valid_name("a b"));
+    }
+
+    #[test]
+    fn test_capture() {
+        let output = "CONTAINER ID   IMAGE\n3f2a9c1b7d4e   nginx:1.27\n";
+        let first_group = regex::Regex::new(r"(?m)^([0-9a-f]{12})\s+nginx").unwrap();
+        assert_eq!(capture(output, &first_group).as_deref(), Some("3f2a9c1b7d4e"));
+
+        let named = regex::Regex::new(r"(\w+):(?P<value>[\d.]+)").unwrap();
+        assert_eq!(capture(output, &named).as_deref(), Some("1.27"));
+
+        let whole = regex::Regex::new(r"nginx:\S+").unwrap();
+        assert_eq!(capture(output, &whole).as_deref(), Some("nginx:1.27"));
+        assert_eq!(capture(output, &regex::Regex::new("redis").unwrap()), None);
+    }
+}
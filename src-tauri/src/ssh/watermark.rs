@@ -0,0 +1,121 @@
+//! 终端水印
+//!
+//! 开启后每个已建立的连接定期发送 `ssh-watermark-{connectionId}` 事件，内容为当前操作者
+//! （登录账号和本机用户）、目标主机和时间。前端将其叠加显示在终端上并写入录制文件，
+//! 审阅录制时可以证明谁在何时操作了哪台主机。
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// 水印事件前缀
+pub const WATERMARK_EVENT_PREFIX: &str = "ssh-watermark-";
+
+/// 水印设置
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WatermarkSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    /// 发送间隔（秒）
+    #[serde(default = "default_interval_secs")]
+    pub interval_secs: u64,
+}
+
+fn default_interval_secs() -> u64 {
+    60
+}
+
+impl Default for WatermarkSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_secs: default_interval_secs(),
+        }
+    }
+}
+
+impl WatermarkSettings {
+    /// 发送间隔，最短 5 秒
+    pub fn interval(&self) -> Duration {
+        Duration::from_secs(self.interval_secs.max(5))
+    }
+}
+
+/// 水印内容（即 `ssh-watermark-{connectionId}` 事件内容）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WatermarkStamp {
+    /// 当前登录的账号（未登录时为 None）
+    pub account: Option<String>,
+    /// 本机操作系统用户
+    pub local_user: String,
+    /// 本机主机名
+    pub local_host: String,
+    /// 远程目标（`user@host:port`）
+    pub target: String,
+    /// 毫秒时间戳
+    pub timestamp: i64,
+    /// 叠加显示的文本
+    pub text: String,
+}
+
+impl WatermarkStamp {
+    pub fn new(account: Option<String>, target: String, timestamp: i64) -> Self {
+        let local_user = std::env::var("USER")
+            .or_else(|_| std::env::var("USERNAME"))
+            .unwrap_or_else(|_| "unknown".to_string());
+        let local_host = std::env::var("HOSTNAME")
+            .or_else(|_| std::env::var("COMPUTERNAME"))
+            .unwrap_or_default();
+        Self::with_local(account, local_user, local_host, target, timestamp)
+    }
+
+    fn with_local(
+        account: Option<String>,
+        local_user: String,
+        local_host: String,
+        target: String,
+        timestamp: i64,
+    ) -> Self {
+        let operator = match (&account, local_host.is_empty()) {
+            (Some(account), true) => format!("{} ({})", account, local_user),
+            (Some(account), false) => format!("{} ({}@{})", account, local_user, local_host),
+            (None, true) => local_user.clone(),
+            (None, false) => format!("{}@{}", local_user, local_host),
+        };
+        let time = chrono::DateTime::from_timestamp_millis(timestamp)
+            .map(|t| t.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+            .unwrap_or_default();
+        let text = format!("{} → {} · {}", operator, target, time);
+        Self {
+            account,
+            local_user,
+            local_host,
+            target,
+            timestamp,
+            text,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stamp_text() {
+        let stamp = WatermarkStamp::with_local(
+            Some("alice@example.com".to_string()),
+            "alice".to_string(),
+            "laptop".to_string(),
+            "root@db1:22".to_string(),
+            0,
+        );
+        assert_eq!(stamp.text, "alice@example.com (alice@laptop) → root@db1:22 · 1970-01-01 00:00:00 UTC");
+
+        let stamp = WatermarkStamp::with_local(None, "bob".to_string(), String::new(), "bob@web:2222".to_string(), 0);
+        assert!(stamp.text.starts_with("bob → bob@web:2222"));
+
+        assert_eq!(WatermarkSettings { enabled: true, interval_secs: 1 }.interval(), Duration::from_secs(5));
+    }
+}
@@ -0,0 +1,144 @@
+//! 网络唤醒（Wake-on-LAN）
+//!
+//! 会话配置了 `wake_on_lan` 时，连接前向广播地址发送魔术包（6 个 0xFF 后接 16 次 MAC 地址），
+//! 然后轮询主机的 SSH 端口，端口可以连接后再开始 SSH 握手，用于唤醒休眠的家庭实验室机器。
+
+use crate::error::{Result, SSHError};
+use crate::ssh::dns::DnsResolverConfig;
+use crate::ssh::happy_eyeballs;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tokio::net::UdpSocket;
+
+/// 魔术包发送的 UDP 端口（discard）
+const WOL_PORT: u16 = 9;
+
+/// 魔术包发送次数（UDP 不可靠，多发几次）
+const PACKET_REPEAT: usize = 3;
+
+/// 轮询端口的间隔和单次连接的超时时间
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// 等待主机启动的默认时间（秒）
+const DEFAULT_WAIT_SECS: u64 = 90;
+
+/// 网络唤醒设置
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct WakeOnLanConfig {
+    /// 目标网卡的 MAC 地址（`aa:bb:cc:dd:ee:ff` 或 `aa-bb-cc-dd-ee-ff`）
+    pub mac_address: String,
+    /// 广播地址，默认 `255.255.255.255`
+    #[serde(default = "default_broadcast_address")]
+    pub broadcast_address: String,
+    /// 等待 SSH 端口可连接的最长时间（秒）
+    #[serde(default = "default_wait_secs")]
+    pub wait_secs: u64,
+}
+
+fn default_broadcast_address() -> String {
+    "255.255.255.255".to_string()
+}
+
+fn default_wait_secs() -> u64 {
+    DEFAULT_WAIT_SECS
+}
+
+/// 解析 MAC 地址
+pub fn parse_mac(mac: &str) -> Result<[u8; 6]> {
+    let invalid = || SSHError::ConnectionFailed(format!("Invalid MAC address: {}", mac));
+    let parts: Vec<&str> = mac.trim().split([':', '-']).collect();
+    if parts.len() != 6 {
+        return Err(invalid());
+    }
+    let mut bytes = [0u8; 6];
+    for (byte, part) in bytes.iter_mut().zip(&parts) {
+        if part.len() != 2 {
+            return Err(invalid());
+        }
+        *byte = u8::from_str_radix(part, 16).map_err(|_| invalid())?;
+    }
+    Ok(bytes)
+}
+
+/// 构造魔术包
+pub fn magic_packet(mac: [u8; 6]) -> Vec<u8> {
+    let mut packet = vec![0xFF; 6];
+    for _ in 0..16 {
+        packet.extend_from_slice(&mac);
+    }
+    packet
+}
+
+/// 发送魔术包
+pub async fn send_magic_packet(config: &WakeOnLanConfig) -> Result<()> {
+    let packet = magic_packet(parse_mac(&config.mac_address)?);
+    let socket = UdpSocket::bind("0.0.0.0:0").await?;
+    socket.set_broadcast(true)?;
+
+    let target = (config.broadcast_address.as_str(), WOL_PORT);
+    for _ in 0..PACKET_REPEAT {
+        socket
+            .send_to(&packet, target)
+            .await
+            .map_err(|e| SSHError::ConnectionFailed(format!(
+                "Failed to send Wake-on-LAN packet to {}: {}",
+                config.broadcast_address, e
+            )))?;
+    }
+    tracing::info!("[WoL] Sent magic packet for {} via {}", config.mac_address, config.broadcast_address);
+    Ok(())
+}
+
+/// 唤醒主机并等待 `host:port` 可以连接
+pub async fn wake(
+    config: &WakeOnLanConfig,
+    host: &str,
+    port: u16,
+    resolver: Option<&DnsResolverConfig>,
+) -> Result<()> {
+    send_magic_packet(config).await?;
+
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(config.wait_secs);
+    loop {
+        let attempt = tokio::time::timeout(POLL_INTERVAL, happy_eyeballs::connect(host, port, resolver)).await;
+        if let Ok(Ok(_stream)) = attempt {
+            tracing::info!("[WoL] {}:{} is reachable", host, port);
+            return Ok(());
+        }
+        if tokio::time::Instant::now() >= deadline {
+            return Err(SSHError::ConnectionFailed(format!(
+                "{}:{} did not become reachable within {}s after Wake-on-LAN",
+                host, port, config.wait_secs
+            )));
+        }
+        // 连接被立即拒绝（主机已启动但 sshd 未就绪，或仍在休眠时路由器返回不可达）时稍后重试
+        if matches!(attempt, Ok(Err(_))) {
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_magic_packet() {
+        let mac = parse_mac("00:1A-2b:3c:4D:5e").unwrap();
+        assert_eq!(mac, [0x00, 0x1a, 0x2b, 0x3c, 0x4d, 0x5e]);
+        assert!(parse_mac("00:1a:2b:3c:4d").is_err());
+        assert!(parse_mac("00:1a:2b:3c:4d:zz").is_err());
+        assert!(parse_mac("001:a:2b:3c:4d:5e").is_err());
+
+        let packet = magic_packet(mac);
+        assert_eq!(packet.len(), 102);
+        assert!(packet[..6].iter().all(|b| *b == 0xFF));
+        assert_eq!(&packet[96..], &mac);
+
+        let config: WakeOnLanConfig =
+            serde_json::from_value(serde_json::json!({ "macAddress": "00:1a:2b:3c:4d:5e" })).unwrap();
+        assert_eq!(config.broadcast_address, "255.255.255.255");
+        assert_eq!(config.wait_secs, DEFAULT_WAIT_SECS);
+    }
+}
@@ -0,0 +1,144 @@
+//! WSL 发行版
+//!
+//! Windows 上通过 `wsl.exe -d <distro>` 在本机 PTY 中打开 WSL shell，
+//! 会话的 `wsl_distribution` 为空时使用默认发行版。
+
+use serde::Serialize;
+
+/// 已安装的 WSL 发行版
+#[derive(Clone, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct WslDistribution {
+    pub name: String,
+    /// 运行状态（Running、Stopped 等）
+    pub state: String,
+    /// WSL 版本（1 或 2）
+    pub version: Option<u8>,
+    /// 是否为默认发行版
+    pub is_default: bool,
+}
+
+/// 发行版名称只允许字母、数字和 `.`、`_`、`-`
+pub fn validate_distribution(name: &str) -> Result<(), String> {
+    let valid = !name.is_empty()
+        && !name.starts_with('-')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'));
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("Invalid WSL distribution name: {}", name))
+    }
+}
+
+/// `wsl.exe` 的参数：指定发行版、从用户主目录启动，`command` 不为空时执行该命令
+pub fn command_args(distribution: Option<&str>, command: Option<&str>) -> Vec<String> {
+    let mut args = Vec::new();
+    if let Some(distribution) = distribution.map(str::trim).filter(|d| !d.is_empty()) {
+        args.push("-d".to_string());
+        args.push(distribution.to_string());
+    }
+    args.push("--cd".to_string());
+    args.push("~".to_string());
+    if let Some(command) = command.map(str::trim).filter(|c| !c.is_empty()) {
+        args.extend(["-e", "sh", "-c"].map(String::from));
+        args.push(command.to_string());
+    }
+    args
+}
+
+/// 解码 `wsl.exe` 的输出（旧版本输出 UTF-16LE，设置 `WSL_UTF8=1` 后为 UTF-8）
+#[cfg(any(windows, test))]
+pub fn decode_output(bytes: &[u8]) -> String {
+    let utf16 = bytes.starts_with(&[0xff, 0xfe]) || (bytes.len() >= 2 && bytes[1] == 0);
+    if !utf16 {
+        return String::from_utf8_lossy(bytes).into_owned();
+    }
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+        .collect();
+    String::from_utf16_lossy(&units).trim_start_matches('\u{feff}').to_string()
+}
+
+/// 解析 `wsl.exe -l -v` 的输出
+///
+/// ```text
+///   NAME            STATE           VERSION
+/// * Ubuntu-22.04    Running         2
+///   Debian          Stopped         2
+/// ```
+#[cfg(any(windows, test))]
+pub fn parse_distributions(output: &str) -> Vec<WslDistribution> {
+    output
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let line = line.trim_end_matches('\r');
+            let (is_default, rest) = match line.trim_start().strip_prefix('*') {
+                Some(rest) => (true, rest),
+                None => (false, line),
+            };
+            let fields: Vec<&str> = rest.split_whitespace().collect();
+            let (name, state, version) = match fields.as_slice() {
+                [name, state, version] => (*name, *state, version.parse().ok()),
+                [name, state] => (*name, *state, None),
+                _ => return None,
+            };
+            Some(WslDistribution {
+                name: name.to_string(),
+                state: state.to_string(),
+                version,
+                is_default,
+            })
+        })
+        .collect()
+}
+
+/// 列出已安装的 WSL 发行版
+#[cfg(windows)]
+pub async fn list_distributions() -> Result<Vec<WslDistribution>, String> {
+    let output = tokio::process::Command::new("wsl.exe")
+        .args(["-l", "-v"])
+        .env("WSL_UTF8", "1")
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run wsl.exe: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "wsl.exe failed: {}",
+            decode_output(&output.stdout).trim()
+        ));
+    }
+    Ok(parse_distributions(&decode_output(&output.stdout)))
+}
+
+/// 仅 Windows 支持 WSL
+#[cfg(not(windows))]
+pub async fn list_distributions() -> Result<Vec<WslDistribution>, String> {
+    Ok(Vec::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_distributions() {
+        let text = "  NAME            STATE           VERSION\r\n* Ubuntu-22.04    Running         2\r\n  Debian          Stopped         1\r\n";
+        let utf16: Vec<u8> = text.encode_utf16().flat_map(|u| u.to_le_bytes()).collect();
+        let distributions = parse_distributions(&decode_output(&utf16));
+        assert_eq!(distributions.len(), 2);
+        assert_eq!(distributions[0].name, "Ubuntu-22.04");
+        assert!(distributions[0].is_default);
+        assert_eq!(distributions[1].version, Some(1));
+        assert!(!distributions[1].is_default);
+
+        assert_eq!(parse_distributions(&decode_output(text.as_bytes())), distributions);
+        assert!(validate_distribution("Ubuntu-22.04").is_ok());
+        assert!(validate_distribution("--exec").is_err());
+        assert_eq!(
+            command_args(Some("Debian"), Some("htop")),
+            vec!["-d", "Debian", "--cd", "~", "-e", "sh", "-c", "htop"]
+        );
+    }
+}
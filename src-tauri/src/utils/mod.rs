@@ -0,0 +1,4 @@
+// 工具模块
+pub mod redaction;
+pub mod shell;
+pub mod url;
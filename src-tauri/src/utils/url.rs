@@ -0,0 +1,37 @@
+//! URL 编码工具
+
+/// 解码 `%XX` 转义，转义不完整或解码结果不是 UTF-8 时返回错误
+pub fn percent_decode(input: &str) -> Result<String, String> {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let byte = input
+                .get(i + 1..i + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                .ok_or_else(|| format!("无效的转义: {}", input))?;
+            decoded.push(byte);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(decoded).map_err(|_| format!("无效的转义: {}", input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percent_decode() {
+        assert_eq!(percent_decode("web%20prod").unwrap(), "web prod");
+        assert_eq!(percent_decode("%E4%B8%AD").unwrap(), "中");
+        assert_eq!(percent_decode("plain").unwrap(), "plain");
+        assert!(percent_decode("100%").is_err());
+        assert!(percent_decode("%zz").is_err());
+        assert!(percent_decode("%FF").is_err());
+    }
+}
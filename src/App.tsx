@@ -1,4 +1,7 @@
 ﻿import { useEffect } from "react";
+import { useTranslation } from "react-i18next";
+import { listen } from "@tauri-apps/api/event";
+import { toast } from "sonner";
 import { BrowserRouter as Router, Routes, Route, useNavigate, useLocation } from "react-router-dom";
 import { MainLayout } from "@/components/layout/MainLayout";
 import { Terminal } from "@/pages/Terminal";
@@ -17,6 +20,8 @@ import { useSidebarStore } from "@/store/sidebarStore";
 import { useAuthStore } from "@/store/authStore";
 import { useSessionStore } from "@/store/sessionStore";
 import { useSftpStore } from "@/store/sftpStore";
+import { useTerminalStore } from "@/store/terminalStore";
+import { useHandoffStore, type HandoffRequest, type OnlineDevice } from "@/store/handoffStore";
 import { MobileLayout } from "@/components/mobile/MobileLayout";
 import { MobileSessionList } from "@/components/mobile/MobileSessionList";
 import { MobileTerminalPage } from "@/components/mobile/MobileTerminalPage";
@@ -31,6 +36,8 @@ function AppContent() {
   const loadSessionsFromStorage = useSessionStore(state => state.loadSessionsFromStorage);
   const toggleSidebar = useSidebarStore(state => state.toggleSidebar);
   const { autoLogin, getCurrentUser } = useAuthStore();
+  const isAuthenticated = useAuthStore(state => state.isAuthenticated);
+  const { t } = useTranslation();
   const clearActiveTasks = useSftpStore(state => state.clearActiveTasks);
 
   useEffect(() => {
@@ -60,6 +67,53 @@ function AppContent() {
     tryAutoLogin();
   }, []); // 只在组件挂载时执行一次
 
+  // 登录后连接设备 WebSocket，用于在设备之间接力会话
+  useEffect(() => {
+    const { start, stop } = useHandoffStore.getState();
+    if (isAuthenticated) {
+      start().catch((error) => console.error('[App] Failed to start session handoff:', error));
+    } else {
+      stop().catch(() => {});
+    }
+  }, [isAuthenticated]);
+
+  // 监听会话接力事件：其他设备请求在本机打开会话
+  useEffect(() => {
+    const unlistenDevices = listen<OnlineDevice[]>('handoff-devices', (event) => {
+      useHandoffStore.getState().setDevices(event.payload);
+    });
+
+    const unlistenHandoff = listen<HandoffRequest>('session-handoff', async (event) => {
+      const { sessionId, sessionName, fromName } = event.payload;
+      const session = useSessionStore.getState().sessions.find(s => s.id === sessionId);
+      if (!session) {
+        toast.error(t('session.handoff.notFound', { session: sessionName }));
+        return;
+      }
+
+      toast.info(t('session.handoff.received', { session: sessionName, device: fromName }));
+      try {
+        const connectionId = await useSessionStore.getState().connectSession(sessionId);
+        useTerminalStore.getState().addTab(connectionId, session.name || `${session.username}@${session.host}`);
+        navigate('/terminal');
+      } catch (error) {
+        toast.error(t('session.error.connectionFailed'), {
+          description: error instanceof Error ? error.message : String(error),
+        });
+      }
+    });
+
+    const unlistenError = listen<string>('handoff-error', (event) => {
+      toast.error(t('session.handoff.failed'), { description: event.payload });
+    });
+
+    return () => {
+      unlistenDevices.then(fn => fn());
+      unlistenHandoff.then(fn => fn());
+      unlistenError.then(fn => fn());
+    };
+  }, [navigate, t]);
+
   // 更新全局快捷键处理器的当前路径
   useEffect(() => {
     globalKeyHandler.setCurrentPath(location.pathname);
@@ -7,6 +7,7 @@
 import { useEffect, useState } from 'react';
 import { useTranslation } from 'react-i18next';
 import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
 import {
   Database,
   RefreshCw,
@@ -42,6 +43,7 @@ interface HotReloadResult {
   success: boolean;
   removedCount: number;
   message: string;
+  errors: string[];
 }
 
 // ==================== 工具函数 ====================
@@ -162,6 +164,12 @@ export function AICachePanel() {
           description: `${result.message}（移除 ${result.removedCount} 个 Provider）`,
           icon: <CheckCircle2 className="h-4 w-4" />
         });
+      } else if (result.errors.length > 0) {
+        playSound('error');
+        toast.error(t('ai.cache.hotReloadInvalid'), {
+          description: result.errors.join('\n'),
+          icon: <AlertCircle className="h-4 w-4" />
+        });
       } else {
         playSound('info');
         toast.info(t('ai.cache.hotReloadCompleted'), {
@@ -188,7 +196,16 @@ export function AICachePanel() {
 
     // 每 5 秒自动刷新缓存信息
     const interval = setInterval(loadCacheInfo, 5000);
-    return () => clearInterval(interval);
+
+    // 其他位置触发的热重载完成后立即刷新
+    const unlisten = listen('ai-reload-status', () => {
+      loadCacheInfo();
+    });
+
+    return () => {
+      clearInterval(interval);
+      unlisten.then((fn) => fn());
+    };
   }, []);
 
   return (
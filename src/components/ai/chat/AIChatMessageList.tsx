@@ -6,9 +6,11 @@
 
 import ReactMarkdown from 'react-markdown';
 import remarkGfm from 'remark-gfm';
+import { useTranslation } from 'react-i18next';
+import { toast } from 'sonner';
 import { useAIStore } from '@/store/aiStore';
 import { ChatMessage } from '@/types/ai';
-import { User, Bot } from 'lucide-react';
+import { User, Bot, GitBranch } from 'lucide-react';
 import { cn } from '@/lib/utils';
 import { useEffect, useRef } from 'react';
 
@@ -17,7 +19,8 @@ interface AIChatMessageListProps {
 }
 
 export function AIChatMessageList({ serverId }: AIChatMessageListProps) {
-  const { conversations, isLoading, streamingConnectionId } = useAIStore();
+  const { t } = useTranslation();
+  const { conversations, isLoading, streamingConnectionId, forkConversation } = useAIStore();
   const messages = conversations.get(serverId) || [];
   const scrollRef = useRef<HTMLDivElement>(null);
   const isStreaming = streamingConnectionId === serverId;
@@ -35,6 +38,16 @@ export function AIChatMessageList({ serverId }: AIChatMessageListProps) {
     prevMessagesLengthRef.current = currentLength;
   }, [messages.length]);
 
+  const handleFork = async (index: number) => {
+    try {
+      await forkConversation(serverId, index);
+      toast.success(t('ai.conversation.forkSuccess'));
+    } catch (error) {
+      console.error('[AIChatMessageList] Failed to fork conversation:', error);
+      toast.error(t('ai.conversation.forkFailed', { error }));
+    }
+  };
+
   if (messages.length === 0 && !isLoading) {
     return (
       <div className="flex items-center justify-center h-full min-h-[200px] p-6 text-center">
@@ -59,6 +72,8 @@ export function AIChatMessageList({ serverId }: AIChatMessageListProps) {
             key={index}
             message={message}
             isStreaming={isLastMessageStreaming}
+            onFork={isStreaming ? undefined : () => handleFork(index)}
+            forkLabel={t('ai.conversation.actionFork')}
           />
         );
       })}
@@ -84,16 +99,18 @@ export function AIChatMessageList({ serverId }: AIChatMessageListProps) {
 interface MessageBubbleProps {
   message: ChatMessage;
   isStreaming?: boolean;
+  onFork?: () => void;  // 从此消息处分叉对话
+  forkLabel?: string;
 }
 
-function MessageBubble({ message, isStreaming }: MessageBubbleProps) {
+function MessageBubble({ message, isStreaming, onFork, forkLabel }: MessageBubbleProps) {
   const isUser = message.role === 'user';
   const isEmpty = !message.content || message.content.trim().length === 0;
 
   return (
     <div
       className={cn(
-        'flex items-start px-6 py-4',
+        'group flex items-start px-6 py-4',
         isUser ? 'justify-end' : 'justify-start',
         isStreaming && !isUser && 'animate-in fade-in slide-in-from-bottom-2 duration-300'
       )}
@@ -192,6 +209,18 @@ function MessageBubble({ message, isStreaming }: MessageBubbleProps) {
             </div>
           )}
         </div>
+
+        {/* 分叉按钮（悬停显示） */}
+        {onFork && (
+          <button
+            type="button"
+            onClick={onFork}
+            title={forkLabel}
+            className="self-center p-1 rounded text-muted-foreground opacity-0 group-hover:opacity-100 hover:text-primary hover:bg-muted transition-opacity"
+          >
+            <GitBranch className="w-3.5 h-3.5" />
+          </button>
+        )}
       </div>
     </div>
   );
@@ -8,7 +8,7 @@ import { useState } from 'react';
 import { useTranslation } from 'react-i18next';
 import { useNavigate } from 'react-router-dom';
 import { AIConversationMeta } from '@/types/ai';
-import { MessageSquare, Calendar, Trash2, Download, Archive } from 'lucide-react';
+import { MessageSquare, Calendar, Trash2, Download, Archive, GitBranch } from 'lucide-react';
 import { formatDistanceToNow } from 'date-fns';
 import { zhCN } from 'date-fns/locale';
 import { useAIStore } from '@/store/aiStore';
@@ -139,7 +139,11 @@ export function ConversationItem({ conversation, onUpdate }: ConversationItemPro
     >
       <div className="flex items-start gap-3">
         <div className="w-6 h-6 rounded bg-primary/5 flex items-center justify-center flex-shrink-0 mt-0.5">
-          <MessageSquare className="w-3 h-3 text-primary/60" />
+          {conversation.parentId ? (
+            <GitBranch className="w-3 h-3 text-primary/60" />
+          ) : (
+            <MessageSquare className="w-3 h-3 text-primary/60" />
+          )}
         </div>
 
         <div className="flex-1 min-w-0">
@@ -160,6 +164,12 @@ export function ConversationItem({ conversation, onUpdate }: ConversationItemPro
             <span className="truncate">{formatDate()}</span>
             <span className="flex-shrink-0">•</span>
             <span>{t('ai.conversation.messageCount', { count: conversation.messageCount })}</span>
+            {conversation.branchPoint !== undefined && (
+              <>
+                <span className="flex-shrink-0">•</span>
+                <span>{t('ai.conversation.branchFrom', { index: conversation.branchPoint + 1 })}</span>
+              </>
+            )}
           </div>
         </div>
 
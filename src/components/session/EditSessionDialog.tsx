@@ -18,10 +18,54 @@ import {
   SelectTrigger,
   SelectValue,
 } from '@/components/ui/select';
-import { Edit, Loader2 } from 'lucide-react';
-import type { SessionInfo, SessionConfig } from '@/types/ssh';
+import { Edit, Loader2, Plus, Trash2 } from 'lucide-react';
+import type { SessionInfo, SessionConfig, AlgorithmPreferences, AlgorithmPreset, ConnectionProfile } from '@/types/ssh';
+import { invoke } from '@tauri-apps/api/core';
 import { toast } from 'sonner';
 
+type AlgorithmListKey = 'kex' | 'hostKey' | 'cipher' | 'mac';
+
+const ALGORITHM_LISTS: { key: AlgorithmListKey; label: string }[] = [
+  { key: 'kex', label: 'session.field.algorithmKex' },
+  { key: 'hostKey', label: 'session.field.algorithmHostKey' },
+  { key: 'cipher', label: 'session.field.algorithmCipher' },
+  { key: 'mac', label: 'session.field.algorithmMac' },
+];
+
+const EMPTY_ALGORITHM_LISTS: Record<AlgorithmListKey, string> = { kex: '', hostKey: '', cipher: '', mac: '' };
+
+// 逗号分隔的算法名 -> 列表
+/** 编辑中的连接配置，启动命令每行一条 */
+interface ProfileDraft {
+  id: string;
+  name: string;
+  identityId: string;
+  username: string;
+  startupCommands: string;
+}
+
+const NO_IDENTITY = '__none__';
+
+const toProfileDraft = (profile: ConnectionProfile): ProfileDraft => ({
+  id: profile.id,
+  name: profile.name,
+  identityId: profile.identityId ?? '',
+  username: profile.username ?? '',
+  startupCommands: (profile.startupCommands ?? []).join('\n'),
+});
+
+const fromProfileDraft = (draft: ProfileDraft, original?: ConnectionProfile): ConnectionProfile => ({
+  ...original,
+  id: draft.id,
+  name: draft.name,
+  identityId: draft.identityId || undefined,
+  username: draft.username || undefined,
+  startupCommands: draft.startupCommands.split('\n').map((c) => c.trim()).filter(Boolean),
+});
+
+const splitAlgorithms = (value: string) =>
+  value.split(',').map((name) => name.trim()).filter(Boolean);
+
 interface EditSessionDialogProps {
   open: boolean;
   onOpenChange: (open: boolean) => void;
@@ -50,6 +94,49 @@ export function EditSessionDialog({
     privateKeyPath: '',
     passphrase: '',
   });
+  const [algorithmPreset, setAlgorithmPreset] = useState<AlgorithmPreset>('default');
+  const [algorithmLists, setAlgorithmLists] = useState(EMPTY_ALGORITHM_LISTS);
+  const [profiles, setProfiles] = useState<ConnectionProfile[]>([]);
+  const [profileDrafts, setProfileDrafts] = useState<ProfileDraft[]>([]);
+  const [identities, setIdentities] = useState<{ id: string; name: string; username: string }[]>([]);
+
+  // 连接配置和可引用的身份，打开时加载
+  useEffect(() => {
+    if (!open || !session) return;
+    setProfiles([]);
+    setProfileDrafts([]);
+    invoke<ConnectionProfile[]>('db_ssh_session_get_profiles', { sessionId: session.id })
+      .then((loaded) => {
+        setProfiles(loaded);
+        setProfileDrafts(loaded.map(toProfileDraft));
+      })
+      .catch((error) => console.error('[EditSessionDialog] 加载连接配置失败:', error));
+    invoke<{ id: string; name: string; username: string }[]>('identity_list')
+      .then(setIdentities)
+      .catch((error) => console.error('[EditSessionDialog] 加载身份列表失败:', error));
+  }, [open, session]);
+
+  const updateProfileDraft = (index: number, changes: Partial<ProfileDraft>) => {
+    setProfileDrafts(profileDrafts.map((draft, i) => (i === index ? { ...draft, ...changes } : draft)));
+  };
+
+  // 算法偏好单独保存在数据库中，打开时加载
+  useEffect(() => {
+    if (!open || !session) return;
+    setAlgorithmPreset('default');
+    setAlgorithmLists(EMPTY_ALGORITHM_LISTS);
+    invoke<AlgorithmPreferences>('db_ssh_session_get_algorithms', { sessionId: session.id })
+      .then((prefs) => {
+        setAlgorithmPreset(prefs.preset);
+        setAlgorithmLists({
+          kex: (prefs.kex ?? []).join(', '),
+          hostKey: (prefs.hostKey ?? []).join(', '),
+          cipher: (prefs.cipher ?? []).join(', '),
+          mac: (prefs.mac ?? []).join(', '),
+        });
+      })
+      .catch((error) => console.error('[EditSessionDialog] 加载算法偏好失败:', error));
+  }, [open, session]);
 
   // 当配置变化时，更新表单数据
   useEffect(() => {
@@ -132,6 +219,13 @@ export function EditSessionDialog({
         port: parseInt(formData.port),
         username: formData.username,
         group: formData.group || t('session.defaultGroup'),
+        algorithms: {
+          preset: algorithmPreset,
+          kex: splitAlgorithms(algorithmLists.kex),
+          hostKey: splitAlgorithms(algorithmLists.hostKey),
+          cipher: splitAlgorithms(algorithmLists.cipher),
+          mac: splitAlgorithms(algorithmLists.mac),
+        },
       };
 
       // 根据选择的认证方式更新认证信息
@@ -153,6 +247,14 @@ export function EditSessionDialog({
       }
 
       await onUpdate(updates);
+      if (session) {
+        await invoke('db_ssh_session_set_profiles', {
+          sessionId: session.id,
+          profiles: profileDrafts.map((draft) =>
+            fromProfileDraft(draft, profiles.find((p) => draft.id && p.id === draft.id))
+          ),
+        });
+      }
       toast.success(t('session.success.updated'));
       onOpenChange(false);
     } catch (error) {
@@ -315,6 +417,112 @@ export function EditSessionDialog({
               </>
             )}
 
+            {/* 算法兼容性 */}
+            <div className="space-y-2">
+              <Label htmlFor="edit-algorithm-preset">{t('session.field.algorithmPreset')}</Label>
+              <Select
+                value={algorithmPreset}
+                onValueChange={(value: AlgorithmPreset) => setAlgorithmPreset(value)}
+              >
+                <SelectTrigger id="edit-algorithm-preset">
+                  <SelectValue />
+                </SelectTrigger>
+                <SelectContent>
+                  <SelectItem value="default">{t('session.field.algorithmPresetDefault')}</SelectItem>
+                  <SelectItem value="legacy">{t('session.field.algorithmPresetLegacy')}</SelectItem>
+                </SelectContent>
+              </Select>
+              <details className="text-sm">
+                <summary className="cursor-pointer text-muted-foreground">
+                  {t('session.field.algorithmCustom')}
+                </summary>
+                <div className="mt-2 space-y-2">
+                  {ALGORITHM_LISTS.map(({ key, label }) => (
+                    <div key={key} className="space-y-1">
+                      <Label htmlFor={`edit-algorithm-${key}`}>{t(label)}</Label>
+                      <Input
+                        id={`edit-algorithm-${key}`}
+                        value={algorithmLists[key]}
+                        onChange={(e) => setAlgorithmLists({ ...algorithmLists, [key]: e.target.value })}
+                      />
+                    </div>
+                  ))}
+                </div>
+              </details>
+            </div>
+
+            {/* 连接配置 */}
+            <div className="space-y-2">
+              <div className="flex items-center justify-between">
+                <Label>{t('session.field.profiles')}</Label>
+                <Button
+                  type="button"
+                  variant="outline"
+                  size="sm"
+                  onClick={() =>
+                    setProfileDrafts([...profileDrafts, { id: '', name: '', identityId: '', username: '', startupCommands: '' }])
+                  }
+                >
+                  <Plus className="h-4 w-4 mr-1" />
+                  {t('session.action.addProfile')}
+                </Button>
+              </div>
+              <p className="text-xs text-muted-foreground">{t('session.field.profilesHint')}</p>
+              {profileDrafts.map((draft, index) => (
+                <div key={draft.id || `new-${index}`} className="space-y-2 rounded-md border p-3">
+                  <div className="grid grid-cols-2 gap-2">
+                    <Input
+                      value={draft.name}
+                      placeholder={t('session.field.profileName')}
+                      onChange={(e) => updateProfileDraft(index, { name: e.target.value })}
+                    />
+                    <div className="flex gap-2">
+                      <Select
+                        value={draft.identityId || NO_IDENTITY}
+                        onValueChange={(value) =>
+                          updateProfileDraft(index, { identityId: value === NO_IDENTITY ? '' : value })
+                        }
+                      >
+                        <SelectTrigger>
+                          <SelectValue />
+                        </SelectTrigger>
+                        <SelectContent>
+                          <SelectItem value={NO_IDENTITY}>{t('session.field.profileNoIdentity')}</SelectItem>
+                          {identities.map((identity) => (
+                            <SelectItem key={identity.id} value={identity.id}>
+                              {identity.name} ({identity.username})
+                            </SelectItem>
+                          ))}
+                        </SelectContent>
+                      </Select>
+                      <Button
+                        type="button"
+                        variant="ghost"
+                        size="icon"
+                        onClick={() => setProfileDrafts(profileDrafts.filter((_, i) => i !== index))}
+                      >
+                        <Trash2 className="h-4 w-4" />
+                      </Button>
+                    </div>
+                  </div>
+                  {!draft.identityId && (
+                    <Input
+                      value={draft.username}
+                      placeholder={t('session.field.profileUsername')}
+                      onChange={(e) => updateProfileDraft(index, { username: e.target.value })}
+                    />
+                  )}
+                  <textarea
+                    value={draft.startupCommands}
+                    rows={2}
+                    placeholder={t('session.field.profileStartupCommands')}
+                    onChange={(e) => updateProfileDraft(index, { startupCommands: e.target.value })}
+                    className="w-full rounded-md border bg-transparent px-3 py-2 text-sm font-mono placeholder:text-muted-foreground focus-visible:outline-none"
+                  />
+                </div>
+              ))}
+            </div>
+
             <DialogFooter>
               <Button
                 type="button"
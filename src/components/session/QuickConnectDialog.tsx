@@ -34,17 +34,21 @@ interface QuickConnectDialogProps {
     privateKeyPath?: string;
     passphrase?: string;
   }) => void;
+  /** 提供时显示协议选择，Telnet 连接不需要认证信息 */
+  onTelnetConnect?: (host: string, port: number) => Promise<void>;
 }
 
 export function QuickConnectDialog({
   open,
   onOpenChange,
   onConnect,
+  onTelnetConnect,
 }: QuickConnectDialogProps) {
   const [loading, setLoading] = useState(false);
   const { t } = useTranslation();
 
   const [formData, setFormData] = useState({
+    protocol: 'ssh',
     host: '',
     port: '22',
     username: '',
@@ -57,6 +61,11 @@ export function QuickConnectDialog({
   const handleSubmit = async (e: React.FormEvent) => {
     e.preventDefault();
 
+    if (formData.protocol === 'telnet' && onTelnetConnect) {
+      await handleTelnetSubmit();
+      return;
+    }
+
     // 基础验证
     if (!formData.host || !formData.username) {
       playSound(SoundEffect.ERROR);
@@ -91,6 +100,7 @@ export function QuickConnectDialog({
 
       // 重置表单
       setFormData({
+        protocol: 'ssh',
         host: '',
         port: '22',
         username: '',
@@ -111,6 +121,39 @@ export function QuickConnectDialog({
     }
   };
 
+  const handleTelnetSubmit = async () => {
+    if (!formData.host || !onTelnetConnect) {
+      playSound(SoundEffect.ERROR);
+      return;
+    }
+
+    setLoading(true);
+    try {
+      await onTelnetConnect(formData.host, parseInt(formData.port) || 23);
+      onOpenChange(false);
+      playSound(SoundEffect.BUTTON_CLICK);
+      setFormData({ ...formData, protocol: 'ssh', host: '', port: '22' });
+    } catch (error) {
+      playSound(SoundEffect.ERROR);
+      console.error('Failed to connect:', error);
+      const errorMessage = error instanceof Error ? error.message : String(error);
+      toast.error(t('session.error.quickConnectFailed'), {
+        description: errorMessage,
+      });
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  // 切换协议时同步默认端口
+  const handleProtocolChange = (protocol: string) => {
+    const defaultPorts: Record<string, string> = { ssh: '22', telnet: '23' };
+    const port = Object.values(defaultPorts).includes(formData.port) ? defaultPorts[protocol] : formData.port;
+    setFormData({ ...formData, protocol, port });
+  };
+
+  const isTelnet = formData.protocol === 'telnet';
+
   return (
     <Dialog open={open} onOpenChange={onOpenChange} closeOnClickOutside={false}>
       <DialogContent className="max-w-md" hideCloseButton>
@@ -125,6 +168,25 @@ export function QuickConnectDialog({
         </DialogHeader>
 
         <form onSubmit={handleSubmit} className="space-y-4">
+          {/* 协议 */}
+          {onTelnetConnect && (
+            <div className="space-y-2">
+              <Label htmlFor="quick-protocol">{t('session.field.protocol')}</Label>
+              <Select value={formData.protocol} onValueChange={handleProtocolChange}>
+                <SelectTrigger id="quick-protocol">
+                  <SelectValue />
+                </SelectTrigger>
+                <SelectContent>
+                  <SelectItem value="ssh">SSH</SelectItem>
+                  <SelectItem value="telnet">Telnet</SelectItem>
+                </SelectContent>
+              </Select>
+              {isTelnet && (
+                <p className="text-xs text-muted-foreground">{t('session.quickConnect.telnetHint')}</p>
+              )}
+            </div>
+          )}
+
           {/* 主机地址 */}
           <div className="space-y-2">
             <Label htmlFor="quick-host">
@@ -157,21 +219,24 @@ export function QuickConnectDialog({
               />
             </div>
 
-            <div className="space-y-2">
-              <Label htmlFor="quick-username">
-                {t('session.field.username')} <span className="text-destructive">*</span>
-              </Label>
-              <Input
-                id="quick-username"
-                placeholder="root"
-                value={formData.username}
-                onChange={(e) => setFormData({ ...formData, username: e.target.value })}
-                required
-              />
-            </div>
+            {!isTelnet && (
+              <div className="space-y-2">
+                <Label htmlFor="quick-username">
+                  {t('session.field.username')} <span className="text-destructive">*</span>
+                </Label>
+                <Input
+                  id="quick-username"
+                  placeholder="root"
+                  value={formData.username}
+                  onChange={(e) => setFormData({ ...formData, username: e.target.value })}
+                  required
+                />
+              </div>
+            )}
           </div>
 
-          {/* 认证方式 */}
+          {/* 认证方式（Telnet 在终端中登录） */}
+          {!isTelnet && (
           <div className="space-y-2">
             <Label htmlFor="quick-auth">{t('session.field.authMethod')}</Label>
             <Select
@@ -187,9 +252,10 @@ export function QuickConnectDialog({
               </SelectContent>
             </Select>
           </div>
+          )}
 
           {/* 密码认证 */}
-          {formData.authMethod === 'password' && (
+          {!isTelnet && formData.authMethod === 'password' && (
             <div className="space-y-2">
               <Label htmlFor="quick-password">
                 {t('session.field.password')} <span className="text-destructive">*</span>
@@ -206,7 +272,7 @@ export function QuickConnectDialog({
           )}
 
           {/* 公钥认证 */}
-          {formData.authMethod === 'publicKey' && (
+          {!isTelnet && formData.authMethod === 'publicKey' && (
             <>
               <div className="space-y-2">
                 <Label htmlFor="quick-key">
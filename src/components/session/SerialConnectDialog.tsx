@@ -0,0 +1,253 @@
+import { useEffect, useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import { invoke } from '@tauri-apps/api/core';
+import {
+  Dialog,
+  DialogContent,
+  DialogDescription,
+  DialogFooter,
+  DialogHeader,
+  DialogTitle,
+} from '@/components/ui/dialog';
+import { Button } from '@/components/ui/button';
+import { Input } from '@/components/ui/input';
+import { Label } from '@/components/ui/label';
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from '@/components/ui/select';
+import { Cable, Loader2, RefreshCw } from 'lucide-react';
+import { playSound } from '@/lib/sounds';
+import { SoundEffect } from '@/lib/sounds';
+import { toast } from 'sonner';
+import type { SerialConfig, SerialPortInfo } from '@/types/ssh';
+
+const BAUD_RATES = [1200, 2400, 4800, 9600, 19200, 38400, 57600, 115200, 230400, 460800, 921600];
+
+const DEFAULT_SETTINGS: SerialConfig = {
+  baudRate: 9600,
+  dataBits: 8,
+  parity: 'none',
+  stopBits: 1,
+  flowControl: 'none',
+};
+
+interface SerialConnectDialogProps {
+  open: boolean;
+  onOpenChange: (open: boolean) => void;
+  onConnect: (path: string, settings: SerialConfig) => Promise<void>;
+}
+
+export function SerialConnectDialog({
+  open,
+  onOpenChange,
+  onConnect,
+}: SerialConnectDialogProps) {
+  const { t } = useTranslation();
+  const [loading, setLoading] = useState(false);
+  const [ports, setPorts] = useState<SerialPortInfo[]>([]);
+  const [path, setPath] = useState('');
+  const [settings, setSettings] = useState<SerialConfig>(DEFAULT_SETTINGS);
+
+  const loadPorts = async () => {
+    try {
+      const result = await invoke<SerialPortInfo[]>('serial_list_ports');
+      setPorts(result);
+      if (!path && result.length > 0) {
+        setPath(result[0].path);
+      }
+    } catch (error) {
+      console.error('Failed to list serial ports:', error);
+      toast.error(t('session.serial.listFailed'), { description: String(error) });
+    }
+  };
+
+  useEffect(() => {
+    if (open) {
+      loadPorts();
+    }
+  }, [open]);
+
+  const handleSubmit = async (e: React.FormEvent) => {
+    e.preventDefault();
+    if (!path.trim()) {
+      playSound(SoundEffect.ERROR);
+      return;
+    }
+
+    setLoading(true);
+    try {
+      await onConnect(path.trim(), settings);
+      onOpenChange(false);
+      playSound(SoundEffect.BUTTON_CLICK);
+    } catch (error) {
+      playSound(SoundEffect.ERROR);
+      console.error('Failed to open serial port:', error);
+      toast.error(t('session.serial.connectFailed'), {
+        description: error instanceof Error ? error.message : String(error),
+      });
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  return (
+    <Dialog open={open} onOpenChange={onOpenChange} closeOnClickOutside={false}>
+      <DialogContent className="max-w-md" hideCloseButton>
+        <DialogHeader>
+          <DialogTitle className="flex items-center gap-2">
+            <Cable className="h-5 w-5 text-primary" />
+            {t('session.serial.title')}
+          </DialogTitle>
+          <DialogDescription>{t('session.serial.description')}</DialogDescription>
+        </DialogHeader>
+
+        <form onSubmit={handleSubmit} className="space-y-4">
+          {/* 串口设备（可从列表选择或手动输入） */}
+          <div className="space-y-2">
+            <Label htmlFor="serial-path">
+              {t('session.serial.port')} <span className="text-destructive">*</span>
+            </Label>
+            <div className="flex gap-2">
+              <Input
+                id="serial-path"
+                list="serial-port-list"
+                placeholder={t('session.serial.portPlaceholder')}
+                value={path}
+                onChange={(e) => setPath(e.target.value)}
+                required
+                autoFocus
+              />
+              <datalist id="serial-port-list">
+                {ports.map((port) => (
+                  <option key={port.path} value={port.path}>
+                    {port.description ?? port.portType}
+                  </option>
+                ))}
+              </datalist>
+              <Button type="button" variant="outline" size="icon" onClick={loadPorts} title={t('session.serial.refresh')}>
+                <RefreshCw className="h-4 w-4" />
+              </Button>
+            </div>
+            {ports.length === 0 && (
+              <p className="text-xs text-muted-foreground">{t('session.serial.noPorts')}</p>
+            )}
+          </div>
+
+          <div className="grid grid-cols-2 gap-4">
+            <div className="space-y-2">
+              <Label htmlFor="serial-baud">{t('session.serial.baudRate')}</Label>
+              <Select
+                value={String(settings.baudRate)}
+                onValueChange={(value) => setSettings({ ...settings, baudRate: parseInt(value) })}
+              >
+                <SelectTrigger id="serial-baud">
+                  <SelectValue />
+                </SelectTrigger>
+                <SelectContent>
+                  {BAUD_RATES.map((rate) => (
+                    <SelectItem key={rate} value={String(rate)}>{rate}</SelectItem>
+                  ))}
+                </SelectContent>
+              </Select>
+            </div>
+
+            <div className="space-y-2">
+              <Label htmlFor="serial-data-bits">{t('session.serial.dataBits')}</Label>
+              <Select
+                value={String(settings.dataBits)}
+                onValueChange={(value) => setSettings({ ...settings, dataBits: parseInt(value) })}
+              >
+                <SelectTrigger id="serial-data-bits">
+                  <SelectValue />
+                </SelectTrigger>
+                <SelectContent>
+                  {[5, 6, 7, 8].map((bits) => (
+                    <SelectItem key={bits} value={String(bits)}>{bits}</SelectItem>
+                  ))}
+                </SelectContent>
+              </Select>
+            </div>
+
+            <div className="space-y-2">
+              <Label htmlFor="serial-parity">{t('session.serial.parity')}</Label>
+              <Select
+                value={settings.parity}
+                onValueChange={(value) => setSettings({ ...settings, parity: value as SerialConfig['parity'] })}
+              >
+                <SelectTrigger id="serial-parity">
+                  <SelectValue />
+                </SelectTrigger>
+                <SelectContent>
+                  <SelectItem value="none">{t('session.serial.parityNone')}</SelectItem>
+                  <SelectItem value="odd">{t('session.serial.parityOdd')}</SelectItem>
+                  <SelectItem value="even">{t('session.serial.parityEven')}</SelectItem>
+                </SelectContent>
+              </Select>
+            </div>
+
+            <div className="space-y-2">
+              <Label htmlFor="serial-stop-bits">{t('session.serial.stopBits')}</Label>
+              <Select
+                value={String(settings.stopBits)}
+                onValueChange={(value) => setSettings({ ...settings, stopBits: parseInt(value) })}
+              >
+                <SelectTrigger id="serial-stop-bits">
+                  <SelectValue />
+                </SelectTrigger>
+                <SelectContent>
+                  <SelectItem value="1">1</SelectItem>
+                  <SelectItem value="2">2</SelectItem>
+                </SelectContent>
+              </Select>
+            </div>
+          </div>
+
+          <div className="space-y-2">
+            <Label htmlFor="serial-flow">{t('session.serial.flowControl')}</Label>
+            <Select
+              value={settings.flowControl}
+              onValueChange={(value) => setSettings({ ...settings, flowControl: value as SerialConfig['flowControl'] })}
+            >
+              <SelectTrigger id="serial-flow">
+                <SelectValue />
+              </SelectTrigger>
+              <SelectContent>
+                <SelectItem value="none">{t('session.serial.flowNone')}</SelectItem>
+                <SelectItem value="software">{t('session.serial.flowSoftware')}</SelectItem>
+                <SelectItem value="hardware">{t('session.serial.flowHardware')}</SelectItem>
+              </SelectContent>
+            </Select>
+          </div>
+
+          <DialogFooter className="mt-4">
+            <Button
+              type="button"
+              variant="outline"
+              onClick={() => onOpenChange(false)}
+              disabled={loading}
+            >
+              {t('dialog.cancel')}
+            </Button>
+            <Button type="submit" disabled={loading}>
+              {loading ? (
+                <>
+                  <Loader2 className="h-4 w-4 mr-2 animate-spin" />
+                  {t('session.status.connecting')}
+                </>
+              ) : (
+                <>
+                  <Cable className="h-4 w-4 mr-2" />
+                  {t('session.action.connectNow')}
+                </>
+              )}
+            </Button>
+          </DialogFooter>
+        </form>
+      </DialogContent>
+    </Dialog>
+  );
+}
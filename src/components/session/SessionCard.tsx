@@ -1,16 +1,25 @@
-﻿import { useState } from 'react';
+﻿import { useEffect, useState } from 'react';
 import { useTranslation } from 'react-i18next';
-import { Terminal, Trash2, Play, Edit } from 'lucide-react';
+import { Terminal, Trash2, Play, Edit, MonitorSmartphone, ChevronDown, Puzzle } from 'lucide-react';
 import { Button } from '@/components/ui/button';
 import { Card, CardContent, CardFooter, CardHeader, CardTitle } from '@/components/ui/card';
+import {
+  DropdownMenu,
+  DropdownMenuContent,
+  DropdownMenuItem,
+  DropdownMenuTrigger,
+} from '@/components/ui/dropdown-menu';
 import { ConnectionStatusBadge } from '@/components/ssh/ConnectionStatusBadge';
-import type { SessionInfo } from '@/types/ssh';
+import type { SessionInfo, ConnectionProfile } from '@/types/ssh';
 import { useSessionStore } from '@/store/sessionStore';
 import { useTerminalStore } from '@/store/terminalStore';
+import { useHandoffStore, type OnlineDevice } from '@/store/handoffStore';
 import { useNavigate } from 'react-router-dom';
 import { playSound } from '@/lib/sounds';
 import { SoundEffect } from '@/lib/sounds';
 import { toast } from 'sonner';
+import { invoke } from '@tauri-apps/api/core';
+import { usePluginMenuItems } from '@/hooks/usePluginMenuItems';
 
 interface SessionCardProps {
   sessionId: string;
@@ -24,9 +33,22 @@ export function SessionCard({ sessionId, onEdit }: SessionCardProps) {
   const navigate = useNavigate();
   const { connectSession, disconnectSession, deleteSession, sessions, createConnection } = useSessionStore();
   const { addTab, removeTab, getTabsByConnection } = useTerminalStore();
+  const { devices, openOnDevice } = useHandoffStore();
+  const [profiles, setProfiles] = useState<ConnectionProfile[]>([]);
+  // 插件提供的会话菜单项
+  const pluginMenu = usePluginMenuItems('session');
 
   // 从 store 中动态获取会话信息
   const session = sessions.find(s => s.id === sessionId);
+  const isConnectionInstance = !!session?.connectionSessionId;
+
+  // 会话配置的连接 profile，用于连接时选择
+  useEffect(() => {
+    if (isConnectionInstance) return;
+    invoke<ConnectionProfile[]>('db_ssh_session_get_profiles', { sessionId })
+      .then(setProfiles)
+      .catch(() => setProfiles([]));
+  }, [sessionId, isConnectionInstance]);
 
   if (!session) {
     return null;
@@ -45,8 +67,8 @@ export function SessionCard({ sessionId, onEdit }: SessionCardProps) {
 
   const displayStatus = getDisplayStatus();
 
-  const handleConnect = async () => {
-    if (displayStatus === 'connected') {
+  const handleConnect = async (profile?: ConnectionProfile) => {
+    if (displayStatus === 'connected' && !profile) {
       // 已连接，创建一个新的连接实例（独立的SSH会话）
       setConnecting(true);
       try {
@@ -67,14 +89,15 @@ export function SessionCard({ sessionId, onEdit }: SessionCardProps) {
         setConnecting(false);
       }
     } else {
-      // 未连接，先连接（现在返回connectionId）
+      // 未连接或指定了 profile，先连接（现在返回connectionId）
       setConnecting(true);
       try {
-        const connectionId = await connectSession(session.id);
+        const connectionId = await connectSession(session.id, profile?.id);
         playSound(SoundEffect.SUCCESS);
         // 连接成功后添加标签页并跳转
         playSound(SoundEffect.TAB_OPEN);
-        addTab(connectionId, session.name || `${session.username}@${session.host}`);
+        const title = session.name || `${session.username}@${session.host}`;
+        addTab(connectionId, profile ? `${title} (${profile.name})` : title);
         navigate('/terminal');
       } catch (error) {
         playSound(SoundEffect.ERROR);
@@ -89,6 +112,19 @@ export function SessionCard({ sessionId, onEdit }: SessionCardProps) {
     }
   };
 
+  const handleOpenOnDevice = async (device: OnlineDevice) => {
+    try {
+      await openOnDevice(session.id, device.clientId);
+      toast.success(t('session.handoff.sent', { device: device.name }));
+    } catch (error) {
+      playSound(SoundEffect.ERROR);
+      const errorMessage = error instanceof Error ? error.message : String(error);
+      toast.error(t('session.handoff.sendFailed'), {
+        description: errorMessage,
+      });
+    }
+  };
+
   const handleDisconnect = async () => {
     try {
       // 获取该会话配置的所有活跃连接实例
@@ -197,7 +233,7 @@ export function SessionCard({ sessionId, onEdit }: SessionCardProps) {
             </Button>
             <Button
               size="sm"
-              onClick={handleConnect}
+              onClick={() => handleConnect()}
               className="flex-1 min-w-[80px]"
             >
               <Terminal className="h-4 w-4 mr-1" />
@@ -217,7 +253,7 @@ export function SessionCard({ sessionId, onEdit }: SessionCardProps) {
             </Button>
             <Button
               size="sm"
-              onClick={handleConnect}
+              onClick={() => handleConnect()}
               disabled={connecting}
               className="flex-1 min-w-[80px]"
             >
@@ -233,8 +269,82 @@ export function SessionCard({ sessionId, onEdit }: SessionCardProps) {
                 </>
               )}
             </Button>
+            {profiles.length > 0 && (
+              <DropdownMenu>
+                <DropdownMenuTrigger asChild>
+                  <Button
+                    size="sm"
+                    variant="outline"
+                    disabled={connecting}
+                    title={t('session.action.connectWithProfile')}
+                    className="min-w-[40px]"
+                  >
+                    <ChevronDown className="h-4 w-4" />
+                  </Button>
+                </DropdownMenuTrigger>
+                <DropdownMenuContent align="end">
+                  {profiles.map(profile => (
+                    <DropdownMenuItem key={profile.id} onClick={() => handleConnect(profile)}>
+                      {profile.name}
+                    </DropdownMenuItem>
+                  ))}
+                </DropdownMenuContent>
+              </DropdownMenu>
+            )}
           </>
         )}
+        {pluginMenu.items.length > 0 && !session.connectionSessionId && (
+          <DropdownMenu onOpenChange={(open) => open && pluginMenu.refresh()}>
+            <DropdownMenuTrigger asChild>
+              <Button
+                size="sm"
+                variant="outline"
+                title={t('session.action.plugins')}
+                className="min-w-[40px]"
+              >
+                <Puzzle className="h-4 w-4" />
+              </Button>
+            </DropdownMenuTrigger>
+            <DropdownMenuContent align="end">
+              {pluginMenu.items.map(item => (
+                <DropdownMenuItem
+                  key={`${item.pluginId}:${item.id}`}
+                  onClick={() => pluginMenu.run(item, {
+                    sessionId: session.id,
+                    host: session.host,
+                    port: session.port,
+                    username: session.username,
+                  })}
+                >
+                  {item.title}
+                  <span className="ml-2 text-xs text-muted-foreground">{item.pluginName}</span>
+                </DropdownMenuItem>
+              ))}
+            </DropdownMenuContent>
+          </DropdownMenu>
+        )}
+        {devices.length > 0 && !session.connectionSessionId && (
+          <DropdownMenu>
+            <DropdownMenuTrigger asChild>
+              <Button
+                size="sm"
+                variant="outline"
+                title={t('session.action.openOnDevice')}
+                className="min-w-[40px]"
+              >
+                <MonitorSmartphone className="h-4 w-4" />
+              </Button>
+            </DropdownMenuTrigger>
+            <DropdownMenuContent align="end">
+              {devices.map(device => (
+                <DropdownMenuItem key={device.clientId} onClick={() => handleOpenOnDevice(device)}>
+                  {device.name || device.clientId.slice(0, 8)}
+                  <span className="ml-2 text-xs text-muted-foreground">{device.platform}</span>
+                </DropdownMenuItem>
+              ))}
+            </DropdownMenuContent>
+          </DropdownMenu>
+        )}
         <Button
           size="sm"
           variant={hasActiveConnection ? "outline" : "destructive"}
@@ -22,14 +22,16 @@ import {
   DialogHeader,
   DialogTitle,
 } from '@/components/ui/dialog';
-import { Loader2, CheckCircle, XCircle, Bot, RotateCcw, Plus, Trash2, ChevronDown } from 'lucide-react';
+import { Loader2, CheckCircle, XCircle, Bot, RotateCcw, Plus, Trash2, ChevronDown, FileDown, FileUp } from 'lucide-react';
 import { useAIStore } from '@/store/aiStore';
 import { AIClient } from '@/lib/ai/aiClient';
 import { toast } from 'sonner';
 import { playSound } from '@/lib/sounds';
 import { SoundEffect } from '@/lib/sounds';
 import { useState, useEffect } from 'react';
-import type { AIProviderConfig, AIProviderType } from '@/types/ai';
+import type { AIConfigImportSummary, AIPromptTemplates, AIProviderConfig, AIProviderType, AISafetyRules } from '@/types/ai';
+import { save, open } from '@tauri-apps/plugin-dialog';
+import { invoke } from '@tauri-apps/api/core';
 import { AICachePanel } from '@/components/AICachePanel';
 
 export function AISettings() {
@@ -38,6 +40,7 @@ export function AISettings() {
     config,
     getDefaultConfig,
     saveConfig,
+    loadConfig,
   } = useAIStore();
 
   const [testingProvider, setTestingProvider] = useState<string | null>(null);
@@ -47,6 +50,12 @@ export function AISettings() {
   // 本地状态
   const [providers, setProviders] = useState<AIProviderConfig[]>([]);
   const [defaultProvider, setDefaultProvider] = useState<string>('');
+  const [promptTemplates, setPromptTemplates] = useState<AIPromptTemplates>({});
+  const [safetyRules, setSafetyRules] = useState<AISafetyRules>({});
+
+  // 导入导出对话框状态
+  const [transferMode, setTransferMode] = useState<'export' | 'import' | null>(null);
+  const [passphrase, setPassphrase] = useState('');
 
   // 展开状态（独立于 enabled 状态）
   const [expandedProviders, setExpandedProviders] = useState<Record<string, boolean>>({});
@@ -62,6 +71,8 @@ export function AISettings() {
     if (config) {
       setDefaultProvider(config.defaultProvider);
       setProviders(config.providers);
+      setPromptTemplates(config.promptTemplates ?? {});
+      setSafetyRules(config.safetyRules ?? {});
     }
   }, [config]);
 
@@ -72,11 +83,15 @@ export function AISettings() {
       await saveConfig({
         providers,
         defaultProvider,
-        shortcuts: {
+        shortcuts: config?.shortcuts ?? {
           explainCommand: 'Ctrl+Shift+A',
           openChat: 'Ctrl+Shift+I',
           nlToCommand: 'Ctrl+Shift+N',
         },
+        promptTemplates,
+        safetyRules: {
+          blockedPatterns: safetyRules.blockedPatterns?.filter((line) => line.trim() !== ''),
+        },
       });
       playSound(SoundEffect.SUCCESS);
       toast.success(t('settings.ai.saveSuccess'));
@@ -92,10 +107,53 @@ export function AISettings() {
         const handleReset = async () => {    const defaultConfig = await getDefaultConfig();
     setProviders(defaultConfig.providers);
     setDefaultProvider(defaultConfig.defaultProvider);
+    setPromptTemplates(defaultConfig.promptTemplates ?? {});
+    setSafetyRules(defaultConfig.safetyRules ?? {});
     playSound(SoundEffect.SUCCESS);
     toast.success(t('settings.ai.resetSuccess'));
   };
 
+  // 导出 / 导入配置文件（口令为空时不包含 API Key）
+  const handleTransfer = async () => {
+    const mode = transferMode;
+    const fileFilters = [{ name: t('settings.ai.transfer.fileType'), extensions: ['json'] }];
+    setTransferMode(null);
+    try {
+      if (mode === 'export') {
+        const path = await save({
+          filters: fileFilters,
+          defaultPath: t('settings.ai.transfer.defaultFileName', { date: new Date().toISOString().slice(0, 10) }),
+        });
+        if (!path) return;
+        await invoke('ai_config_export', { path, passphrase: passphrase || null });
+        playSound(SoundEffect.SUCCESS);
+        toast.success(t('settings.ai.transfer.exportSuccess'));
+      } else if (mode === 'import') {
+        const path = await open({ filters: fileFilters, multiple: false });
+        if (!path || typeof path !== 'string') return;
+        const summary = await invoke<AIConfigImportSummary>('ai_config_import', {
+          path,
+          passphrase: passphrase || null,
+        });
+        await loadConfig();
+        playSound(SoundEffect.SUCCESS);
+        toast.success(t('settings.ai.transfer.importSuccess', summary));
+        if (summary.keysLocked) {
+          toast.warning(t('settings.ai.transfer.keysLocked'));
+        }
+      }
+    } catch (error) {
+      playSound(SoundEffect.ERROR);
+      toast.error(t('settings.ai.transfer.failed', { error }));
+    } finally {
+      setPassphrase('');
+    }
+  };
+
+  const updatePromptTemplate = (key: keyof AIPromptTemplates, value: string) => {
+    setPromptTemplates((prev) => ({ ...prev, [key]: value || undefined }));
+  };
+
   // 测试连接
   const handleTestConnection = async (providerId: string) => {
     setTestingProvider(providerId);
@@ -145,11 +203,13 @@ export function AISettings() {
       await saveConfig({
         providers: newProviders,
         defaultProvider: newDefaultProvider,
-        shortcuts: {
+        shortcuts: config?.shortcuts ?? {
           explainCommand: 'Ctrl+Shift+A',
           openChat: 'Ctrl+Shift+I',
           nlToCommand: 'Ctrl+Shift+N',
         },
+        promptTemplates: config?.promptTemplates,
+        safetyRules: config?.safetyRules,
       });
       playSound(SoundEffect.SUCCESS);
     } catch (error) {
@@ -233,6 +293,7 @@ export function AISettings() {
       case 'qwen':
         return t('settings.ai.providerType.qwen');
       case 'wenxin': return t('settings.ai.providerType.wenxin');
+      case 'server': return t('settings.ai.providerType.server');
       default:
         return type;
           }
@@ -259,6 +320,22 @@ export function AISettings() {
       <div className="flex items-center justify-between">
         <h2 className="text-xl font-semibold">{t('settings.ai.title')}</h2>
         <div className="flex gap-2">
+          <Button
+            variant="outline"
+            size="icon"
+            onClick={() => setTransferMode('export')}
+            title={t('settings.ai.transfer.export')}
+          >
+            <FileDown className="h-4 w-4" />
+          </Button>
+          <Button
+            variant="outline"
+            size="icon"
+            onClick={() => setTransferMode('import')}
+            title={t('settings.ai.transfer.import')}
+          >
+            <FileUp className="h-4 w-4" />
+          </Button>
           <Button
             variant="ghost"
             size="sm"
@@ -487,8 +564,8 @@ export function AISettings() {
             {/* 配置项 - 使用展开状态而不是 enabled 状态 */}
             {expandedProviders[provider.id] && (
               <CardContent className="space-y-4">
-                {/* API Key（除了 ollama 本地服务，其他都需要） */}
-                {provider.type !== 'ollama' && (
+                {/* API Key（除了 ollama 本地服务和服务器托管，其他都需要） */}
+                {provider.type !== 'ollama' && provider.type !== 'server' && (
                   <div className="space-y-2">
                     <Label htmlFor={`${provider.id}-apikey`}>{t('settings.ai.providers.apiKey')}</Label>
                     <Input
@@ -503,27 +580,29 @@ export function AISettings() {
                   </div>
                 )}
 
-                {/* Base URL */}
-                <div className="space-y-2">
-                  <Label htmlFor={`${provider.id}-baseurl`}>{t('settings.ai.providers.baseUrl')}</Label>
-                  <Input
-                    id={`${provider.id}-baseurl`}
-                    value={provider.baseUrl || ''}
-                    onChange={(e) =>
-                      updateProvider(provider.id, { baseUrl: e.target.value })
-                    }
-                    placeholder={
-                      provider.type === 'ollama'
-                        ? 'http://localhost:11434'
-                        : 'https://api.openai.com/v1'
-                    }
-                  />
-                  <p className="text-sm text-muted-foreground">
-                    {provider.type === 'ollama'
-                      ? t('settings.ai.providers.baseUrlOllamaHint')
-                      : t('settings.ai.providers.baseUrlHint')}
-                  </p>
-                </div>
+                {/* Base URL（服务器托管使用同步服务器地址） */}
+                {provider.type !== 'server' && (
+                  <div className="space-y-2">
+                    <Label htmlFor={`${provider.id}-baseurl`}>{t('settings.ai.providers.baseUrl')}</Label>
+                    <Input
+                      id={`${provider.id}-baseurl`}
+                      value={provider.baseUrl || ''}
+                      onChange={(e) =>
+                        updateProvider(provider.id, { baseUrl: e.target.value })
+                      }
+                      placeholder={
+                        provider.type === 'ollama'
+                          ? 'http://localhost:11434'
+                          : 'https://api.openai.com/v1'
+                      }
+                    />
+                    <p className="text-sm text-muted-foreground">
+                      {provider.type === 'ollama'
+                        ? t('settings.ai.providers.baseUrlOllamaHint')
+                        : t('settings.ai.providers.baseUrlHint')}
+                    </p>
+                  </div>
+                )}
 
                 {/* 模型输入 */}
                 <div className="space-y-2">
@@ -590,6 +669,83 @@ export function AISettings() {
         ))}
       </div>
 
+      {/* 提示词模板 */}
+      <Card>
+        <CardHeader>
+          <CardTitle>{t('settings.ai.prompts.title')}</CardTitle>
+        </CardHeader>
+        <CardContent className="space-y-4">
+          <p className="text-xs text-muted-foreground">{t('settings.ai.prompts.hint')}</p>
+          {(['chat', 'explainCommand', 'nlToCommand', 'errorAnalysis'] as const).map((key) => (
+            <div key={key} className="space-y-2">
+              <Label htmlFor={`prompt-${key}`}>{t(`settings.ai.prompts.${key}`)}</Label>
+              <textarea
+                id={`prompt-${key}`}
+                value={promptTemplates[key] ?? ''}
+                rows={3}
+                placeholder={t('settings.ai.prompts.placeholder')}
+                onChange={(e) => updatePromptTemplate(key, e.target.value)}
+                className="w-full rounded-md border bg-transparent px-3 py-2 text-sm placeholder:text-muted-foreground focus-visible:outline-none"
+              />
+            </div>
+          ))}
+        </CardContent>
+      </Card>
+
+      {/* 安全规则 */}
+      <Card>
+        <CardHeader>
+          <CardTitle>{t('settings.ai.safety.title')}</CardTitle>
+        </CardHeader>
+        <CardContent className="space-y-2">
+          <p className="text-xs text-muted-foreground">{t('settings.ai.safety.hint')}</p>
+          <textarea
+            id="safety-blocked-patterns"
+            value={(safetyRules.blockedPatterns ?? []).join('\n')}
+            rows={4}
+            placeholder={t('settings.ai.safety.placeholder')}
+            onChange={(e) =>
+              setSafetyRules({
+                blockedPatterns: e.target.value.split('\n'),
+              })
+            }
+            className="w-full rounded-md border bg-transparent px-3 py-2 font-mono text-sm placeholder:text-muted-foreground focus-visible:outline-none"
+          />
+        </CardContent>
+      </Card>
+
+      {/* 导入导出对话框 */}
+      <Dialog open={transferMode !== null} onOpenChange={(isOpen) => !isOpen && setTransferMode(null)}>
+        <DialogContent>
+          <DialogHeader>
+            <DialogTitle>
+              {transferMode === 'import' ? t('settings.ai.transfer.import') : t('settings.ai.transfer.export')}
+            </DialogTitle>
+            <DialogDescription>
+              {transferMode === 'import'
+                ? t('settings.ai.transfer.importDescription')
+                : t('settings.ai.transfer.exportDescription')}
+            </DialogDescription>
+          </DialogHeader>
+          <div className="space-y-2">
+            <Label htmlFor="ai-transfer-passphrase">{t('settings.ai.transfer.passphrase')}</Label>
+            <Input
+              id="ai-transfer-passphrase"
+              type="password"
+              value={passphrase}
+              onChange={(e) => setPassphrase(e.target.value)}
+              placeholder={t('settings.ai.transfer.passphrasePlaceholder')}
+            />
+          </div>
+          <DialogFooter>
+            <Button variant="outline" onClick={() => setTransferMode(null)}>
+              {t('dialog.cancel')}
+            </Button>
+            <Button onClick={handleTransfer}>{t('dialog.confirm')}</Button>
+          </DialogFooter>
+        </DialogContent>
+      </Dialog>
+
       {/* AI Provider 缓存管理 */}
       <AICachePanel />
 
@@ -0,0 +1,111 @@
+import { useCallback, useEffect, useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import { invoke } from '@tauri-apps/api/core';
+import { Copy, RefreshCw, Trash2 } from 'lucide-react';
+import { Button } from '@/components/ui/button';
+import { playSound, SoundEffect } from '@/lib/sounds';
+import type { HostKeyEntry } from '@/types/ssh';
+
+/** 解析 known_hosts 中的第一个主机：`[host]:port` 或 `host`（端口 22） */
+function parseHostPattern(hosts: string): { host: string; port: number } {
+  const first = hosts.split(',')[0];
+  const match = first.match(/^\[(.+)\]:(\d+)$/);
+  if (match) {
+    return { host: match[1], port: Number(match[2]) };
+  }
+  return { host: first, port: 22 };
+}
+
+/** 已信任的主机密钥：查看指纹、删除（主机重装后密钥变化时）和导出 */
+export function HostKeySettings() {
+  const { t } = useTranslation();
+  const [entries, setEntries] = useState<HostKeyEntry[]>([]);
+  const [loading, setLoading] = useState(false);
+
+  const loadEntries = useCallback(async () => {
+    setLoading(true);
+    try {
+      setEntries(await invoke<HostKeyEntry[]>('ssh_host_keys_list'));
+    } catch (error) {
+      console.error('Failed to load host keys:', error);
+    } finally {
+      setLoading(false);
+    }
+  }, []);
+
+  useEffect(() => {
+    loadEntries();
+  }, [loadEntries]);
+
+  const handleRemove = async (entry: HostKeyEntry) => {
+    const { host, port } = parseHostPattern(entry.hosts);
+    if (!window.confirm(t('settings.hostKeys.removeConfirm', { host: entry.hosts, keyType: entry.keyType }))) {
+      return;
+    }
+    try {
+      await invoke<number>('ssh_host_key_remove', { host, port, keyType: entry.keyType });
+      playSound(SoundEffect.SUCCESS);
+      await loadEntries();
+    } catch (error) {
+      console.error('Failed to remove host key:', error);
+      playSound(SoundEffect.ERROR);
+    }
+  };
+
+  const handleExport = async () => {
+    try {
+      const content = await invoke<string>('ssh_host_key_export', {});
+      await navigator.clipboard.writeText(content);
+      playSound(SoundEffect.SUCCESS);
+    } catch (error) {
+      console.error('Failed to export host keys:', error);
+      playSound(SoundEffect.ERROR);
+    }
+  };
+
+  return (
+    <div className="space-y-4">
+      <div className="flex items-center justify-between">
+        <p className="text-sm text-muted-foreground">{t('settings.hostKeys.description')}</p>
+        <div className="flex gap-2 shrink-0">
+          <Button variant="outline" size="sm" onClick={loadEntries} disabled={loading} className="gap-2">
+            <RefreshCw className="h-4 w-4" />
+            {t('settings.hostKeys.refresh')}
+          </Button>
+          <Button variant="outline" size="sm" onClick={handleExport} disabled={entries.length === 0} className="gap-2">
+            <Copy className="h-4 w-4" />
+            {t('settings.hostKeys.export')}
+          </Button>
+        </div>
+      </div>
+
+      {entries.length === 0 ? (
+        <p className="text-sm text-muted-foreground">{t('settings.hostKeys.empty')}</p>
+      ) : (
+        <div className="divide-y rounded-lg border">
+          {entries.map((entry) => (
+            <div key={entry.line} className="flex items-center justify-between gap-4 p-3">
+              <div className="min-w-0 space-y-1">
+                <div className="font-medium truncate">
+                  {entry.hashed ? t('settings.hostKeys.hashed') : entry.hosts}
+                </div>
+                <div className="text-xs text-muted-foreground font-mono break-all">
+                  {entry.keyType} {entry.sha256}
+                </div>
+              </div>
+              <Button
+                variant="ghost"
+                size="icon"
+                onClick={() => handleRemove(entry)}
+                disabled={entry.hashed}
+                title={t('settings.hostKeys.remove')}
+              >
+                <Trash2 className="h-4 w-4" />
+              </Button>
+            </div>
+          ))}
+        </div>
+      )}
+    </div>
+  );
+}
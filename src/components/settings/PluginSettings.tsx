@@ -0,0 +1,103 @@
+import { useCallback, useEffect, useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import { invoke } from '@tauri-apps/api/core';
+import { RefreshCw } from 'lucide-react';
+import { Button } from '@/components/ui/button';
+import { Switch } from '@/components/ui/switch';
+import { playSound, SoundEffect } from '@/lib/sounds';
+import type { PluginInfo } from '@/types/plugin';
+
+/** 已安装的插件：查看运行状态、启用或禁用、重新扫描插件目录 */
+export function PluginSettings() {
+  const { t } = useTranslation();
+  const [plugins, setPlugins] = useState<PluginInfo[]>([]);
+  const [pluginDir, setPluginDir] = useState('');
+  const [loading, setLoading] = useState(false);
+
+  const loadPlugins = useCallback(async () => {
+    try {
+      setPlugins(await invoke<PluginInfo[]>('plugin_list'));
+    } catch (error) {
+      console.error('Failed to load plugins:', error);
+    }
+  }, []);
+
+  useEffect(() => {
+    loadPlugins();
+    invoke<string>('plugin_dir')
+      .then(setPluginDir)
+      .catch((error) => console.error('Failed to get plugin directory:', error));
+  }, [loadPlugins]);
+
+  const handleReload = async () => {
+    setLoading(true);
+    try {
+      setPlugins(await invoke<PluginInfo[]>('plugin_reload'));
+      playSound(SoundEffect.SUCCESS);
+    } catch (error) {
+      console.error('Failed to reload plugins:', error);
+      playSound(SoundEffect.ERROR);
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  const handleToggle = async (plugin: PluginInfo, enabled: boolean) => {
+    try {
+      await invoke('plugin_set_enabled', { pluginId: plugin.id, enabled });
+      await loadPlugins();
+    } catch (error) {
+      console.error('Failed to update plugin:', error);
+      playSound(SoundEffect.ERROR);
+    }
+  };
+
+  return (
+    <div className="space-y-4">
+      <div className="flex items-center justify-between gap-4">
+        <div className="space-y-1">
+          <p className="text-sm text-muted-foreground">{t('settings.plugins.description')}</p>
+          {pluginDir && (
+            <p className="text-xs text-muted-foreground font-mono break-all">
+              {t('settings.plugins.dir', { dir: pluginDir })}
+            </p>
+          )}
+        </div>
+        <Button variant="outline" size="sm" onClick={handleReload} disabled={loading} className="gap-2 shrink-0">
+          <RefreshCw className="h-4 w-4" />
+          {t('settings.plugins.reload')}
+        </Button>
+      </div>
+
+      {plugins.length === 0 ? (
+        <p className="text-sm text-muted-foreground">{t('settings.plugins.empty')}</p>
+      ) : (
+        <div className="divide-y rounded-lg border">
+          {plugins.map((plugin) => (
+            <div key={plugin.id} className="flex items-center justify-between gap-4 p-3">
+              <div className="min-w-0 space-y-1">
+                <div className="font-medium truncate">
+                  {plugin.name}
+                  <span className="ml-2 text-xs text-muted-foreground">{plugin.version}</span>
+                  <span className="ml-2 text-xs text-muted-foreground">
+                    {plugin.running ? t('settings.plugins.running') : t('settings.plugins.stopped')}
+                  </span>
+                </div>
+                {plugin.description && (
+                  <div className="text-xs text-muted-foreground">{plugin.description}</div>
+                )}
+                {plugin.error && (
+                  <div className="text-xs text-destructive break-all">{plugin.error}</div>
+                )}
+              </div>
+              <Switch
+                checked={plugin.enabled}
+                onCheckedChange={(checked) => handleToggle(plugin, checked)}
+              />
+            </div>
+          ))}
+        </div>
+      )}
+    </div>
+  );
+}
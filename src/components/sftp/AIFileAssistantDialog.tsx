@@ -0,0 +1,235 @@
+/**
+ * AI 文件助手对话框
+ *
+ * 输入自然语言需求 → AI 生成计划 → 审阅确认 → 按顺序执行（传输经由传输队列）
+ */
+
+import { useEffect, useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import { Sparkles, AlertTriangle, CheckCircle, XCircle, Loader2, MinusCircle, Circle } from 'lucide-react';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { toast } from 'sonner';
+import { Dialog, DialogContent, DialogDescription, DialogHeader, DialogTitle } from '@/components/ui/dialog';
+import { Button } from '@/components/ui/button';
+import { ScrollArea } from '@/components/ui/scroll-area';
+import { useAIStore } from '@/store/aiStore';
+import { AIClient } from '@/lib/ai/aiClient';
+import { playSound, SoundEffect } from '@/lib/sounds';
+import type { FilePlan, FileStepProgress, PlannedStep } from '@/types/ai';
+
+interface AIFileAssistantDialogProps {
+  open: boolean;
+  onOpenChange: (open: boolean) => void;
+  connectionId: string;
+  remotePath: string;
+  localPath: string;
+  /** 计划执行完毕（刷新文件列表） */
+  onCompleted: () => void;
+}
+
+export function AIFileAssistantDialog({
+  open,
+  onOpenChange,
+  connectionId,
+  remotePath,
+  localPath,
+  onCompleted,
+}: AIFileAssistantDialogProps) {
+  const { t } = useTranslation();
+  const { config } = useAIStore();
+  const [request, setRequest] = useState('');
+  const [plan, setPlan] = useState<FilePlan | null>(null);
+  const [planning, setPlanning] = useState(false);
+  const [executing, setExecuting] = useState(false);
+  const [progress, setProgress] = useState<Record<number, FileStepProgress>>({});
+
+  const provider = config?.providers?.find((p: any) => p.id === config.defaultProvider && p.enabled);
+
+  // 执行进度
+  useEffect(() => {
+    if (!open) return;
+    const unlisten = listen<FileStepProgress>('ai-file-assistant-step', (event) => {
+      if (event.payload.connectionId !== connectionId) return;
+      setProgress((prev) => ({ ...prev, [event.payload.index]: event.payload }));
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [open, connectionId]);
+
+  const reset = () => {
+    setPlan(null);
+    setProgress({});
+  };
+
+  const handleClose = () => {
+    if (executing) return;
+    reset();
+    setRequest('');
+    onOpenChange(false);
+  };
+
+  const handlePlan = async () => {
+    if (!provider) {
+      toast.error(t('ai.fileAssistant.noProvider'));
+      return;
+    }
+    reset();
+    setPlanning(true);
+    try {
+      setPlan(await AIClient.planFileOperation(provider, connectionId, request, remotePath));
+      playSound(SoundEffect.SUCCESS);
+    } catch (error) {
+      playSound(SoundEffect.ERROR);
+      toast.error(t('ai.fileAssistant.planFailed', { error }));
+    } finally {
+      setPlanning(false);
+    }
+  };
+
+  const handleExecute = async () => {
+    if (!plan) return;
+    setProgress({});
+    setExecuting(true);
+    try {
+      const results = await invoke<FileStepProgress[]>('ai_file_assistant_execute', {
+        connectionId,
+        plan,
+        localDir: localPath,
+      });
+      const failed = results.some((r) => r.status === 'failed');
+      playSound(failed ? SoundEffect.ERROR : SoundEffect.SUCCESS);
+      if (failed) {
+        toast.error(t('ai.fileAssistant.executeFailed'));
+      } else {
+        toast.success(t('ai.fileAssistant.executeSuccess'));
+      }
+      onCompleted();
+    } catch (error) {
+      playSound(SoundEffect.ERROR);
+      toast.error(t('ai.fileAssistant.executeFailedWithReason', { error }));
+    } finally {
+      setExecuting(false);
+    }
+  };
+
+  const describeStep = (step: PlannedStep) => {
+    switch (step.type) {
+      case 'exec':
+        return step.command;
+      case 'download':
+        return `${step.remotePath} → ${localPath}`;
+      case 'upload':
+        return `${step.localPath} → ${step.remotePath}`;
+    }
+  };
+
+  const statusIcon = (index: number) => {
+    switch (progress[index]?.status) {
+      case 'running':
+        return <Loader2 className="h-4 w-4 animate-spin text-primary" />;
+      case 'succeeded':
+        return <CheckCircle className="h-4 w-4 text-green-500" />;
+      case 'failed':
+        return <XCircle className="h-4 w-4 text-destructive" />;
+      case 'skipped':
+        return <MinusCircle className="h-4 w-4 text-muted-foreground" />;
+      default:
+        return <Circle className="h-4 w-4 text-muted-foreground" />;
+    }
+  };
+
+  const hasRisk = plan?.steps.some((step) => step.risk) ?? false;
+  const finished = !executing && Object.keys(progress).length > 0;
+
+  return (
+    <Dialog open={open} onOpenChange={handleClose}>
+      <DialogContent className="max-w-3xl max-h-[85vh] flex flex-col">
+        <DialogHeader>
+          <DialogTitle className="flex items-center gap-2">
+            <Sparkles className="h-5 w-5 text-primary" />
+            {t('ai.fileAssistant.title')}
+          </DialogTitle>
+          <DialogDescription>{t('ai.fileAssistant.description')}</DialogDescription>
+        </DialogHeader>
+
+        <div className="space-y-2">
+          <textarea
+            value={request}
+            rows={2}
+            disabled={planning || executing}
+            placeholder={t('ai.fileAssistant.placeholder')}
+            onChange={(e) => setRequest(e.target.value)}
+            className="w-full rounded-md border bg-transparent px-3 py-2 text-sm placeholder:text-muted-foreground focus-visible:outline-none"
+          />
+          <p className="text-xs text-muted-foreground">
+            {t('ai.fileAssistant.context', { remote: remotePath, local: localPath })}
+          </p>
+        </div>
+
+        {plan && (
+          <div className="flex-1 overflow-hidden flex flex-col gap-2">
+            <p className="text-sm font-medium">{plan.summary}</p>
+            {hasRisk && (
+              <div className="flex items-center gap-2 text-xs text-destructive">
+                <AlertTriangle className="h-4 w-4" />
+                {t('ai.fileAssistant.riskWarning')}
+              </div>
+            )}
+            <ScrollArea className="flex-1 max-h-[40vh] rounded-md border">
+              <ol className="p-3 space-y-3">
+                {plan.steps.map((step, index) => (
+                  <li key={index} className="flex gap-2">
+                    <div className="mt-0.5">{statusIcon(index)}</div>
+                    <div className="flex-1 min-w-0 space-y-1">
+                      <div className="flex items-center gap-2 text-xs text-muted-foreground">
+                        <span className="uppercase font-semibold">{t(`ai.fileAssistant.step.${step.type}`)}</span>
+                        {step.description && <span>{step.description}</span>}
+                        {step.risk && (
+                          <span className="text-destructive">{t('ai.fileAssistant.risk', { pattern: step.risk })}</span>
+                        )}
+                      </div>
+                      <code className="block text-xs font-mono whitespace-pre-wrap break-all">{describeStep(step)}</code>
+                      {progress[index]?.message && (
+                        <pre className="text-xs text-muted-foreground whitespace-pre-wrap break-all">
+                          {progress[index].message}
+                        </pre>
+                      )}
+                    </div>
+                  </li>
+                ))}
+              </ol>
+            </ScrollArea>
+          </div>
+        )}
+
+        <div className="flex justify-end gap-2 pt-4 border-t">
+          <Button variant="outline" onClick={handleClose} disabled={executing}>
+            {t('dialog.close')}
+          </Button>
+          <Button
+            variant={plan ? 'secondary' : 'default'}
+            onClick={handlePlan}
+            disabled={planning || executing || !request.trim()}
+            className="gap-2"
+          >
+            {planning ? <Loader2 className="h-4 w-4 animate-spin" /> : <Sparkles className="h-4 w-4" />}
+            {plan ? t('ai.fileAssistant.replan') : t('ai.fileAssistant.plan')}
+          </Button>
+          {plan && !finished && (
+            <Button
+              variant={hasRisk ? 'destructive' : 'default'}
+              onClick={handleExecute}
+              disabled={executing || planning}
+              className="gap-2"
+            >
+              {executing && <Loader2 className="h-4 w-4 animate-spin" />}
+              {t('ai.fileAssistant.execute')}
+            </Button>
+          )}
+        </div>
+      </DialogContent>
+    </Dialog>
+  );
+}
@@ -9,7 +9,9 @@ import { useTranslation } from 'react-i18next';
 import { invoke } from '@tauri-apps/api/core';
 import { getFileIcon, formatFileSize, formatPermissions, type SftpFileInfo } from '@/types/sftp';
 import { Checkbox } from '@/components/ui/checkbox';
-import { Loader2 } from 'lucide-react';
+import { ContextMenu, ContextMenuContent, ContextMenuItem, ContextMenuTrigger } from '@/components/ui/context-menu';
+import { Loader2, Puzzle } from 'lucide-react';
+import { usePluginMenuItems } from '@/hooks/usePluginMenuItems';
 
 interface FileListProps {
   type: 'local' | 'remote';
@@ -36,6 +38,9 @@ export function FileList({
   const [files, setFiles] = useState<SftpFileInfo[]>([]);
   const [allSelected, setAllSelected] = useState(false);
   const [loading, setLoading] = useState(false);
+  // 插件提供的远程文件右键菜单项
+  const pluginMenu = usePluginMenuItems('sftpFile');
+  const showPluginMenu = type === 'remote' && !!connectionId && pluginMenu.items.length > 0;
 
   // 时间格式化函数
   const formatTimestamp = (timestamp: number): string => {
@@ -127,6 +132,39 @@ export function FileList({
     setAllSelected(!allSelected);
   };
 
+  // 渲染单个文件行
+  const renderRow = (file: SftpFileInfo) => (
+    <div
+      key={file.path}
+      className={`grid grid-cols-12 gap-2 px-4 py-2 border-b hover:bg-muted/30 cursor-pointer transition-colors ${
+        selectedFiles.some((f) => f.path === file.path)
+          ? 'bg-muted/50'
+          : ''
+      }`}
+      onDoubleClick={() => onFileDoubleClick(file)}
+    >
+      <div className="col-span-1 flex items-center" onDoubleClick={(e) => e.stopPropagation()}>
+        <Checkbox
+          checked={selectedFiles.some((f) => f.path === file.path)}
+          onCheckedChange={(checked) => handleCheckboxClick(file, checked)}
+        />
+      </div>
+      <div className="col-span-5 flex items-center gap-2 overflow-hidden">
+        <span className="text-lg">{getFileIcon(file)}</span>
+        <span className="truncate text-sm">{file.name}</span>
+      </div>
+      <div className="col-span-2 flex items-center text-xs text-muted-foreground">
+        {file.isDir ? '-' : formatFileSize(file.size)}
+      </div>
+      <div className="col-span-2 flex items-center text-xs text-muted-foreground">
+        {formatTimestamp(file.modified)}
+      </div>
+      <div className="col-span-2 flex items-center text-xs font-mono text-muted-foreground">
+        {formatPermissions(file.mode)}
+      </div>
+    </div>
+  );
+
   if (isLoading || loading) {
     return (
       <div className="flex items-center justify-center h-full">
@@ -158,37 +196,27 @@ export function FileList({
         </div>
       ) : (
         <div>
-          {files.map((file) => (
-            <div
-              key={file.path}
-              className={`grid grid-cols-12 gap-2 px-4 py-2 border-b hover:bg-muted/30 cursor-pointer transition-colors ${
-                selectedFiles.some((f) => f.path === file.path)
-                  ? 'bg-muted/50'
-                  : ''
-              }`}
-              onDoubleClick={() => onFileDoubleClick(file)}
-            >
-              <div className="col-span-1 flex items-center" onDoubleClick={(e) => e.stopPropagation()}>
-                <Checkbox
-                  checked={selectedFiles.some((f) => f.path === file.path)}
-                  onCheckedChange={(checked) => handleCheckboxClick(file, checked)}
-                />
-              </div>
-              <div className="col-span-5 flex items-center gap-2 overflow-hidden">
-                <span className="text-lg">{getFileIcon(file)}</span>
-                <span className="truncate text-sm">{file.name}</span>
-              </div>
-              <div className="col-span-2 flex items-center text-xs text-muted-foreground">
-                {file.isDir ? '-' : formatFileSize(file.size)}
-              </div>
-              <div className="col-span-2 flex items-center text-xs text-muted-foreground">
-                {formatTimestamp(file.modified)}
-              </div>
-              <div className="col-span-2 flex items-center text-xs font-mono text-muted-foreground">
-                {formatPermissions(file.mode)}
-              </div>
-            </div>
-          ))}
+          {files.map((file) =>
+            showPluginMenu ? (
+              <ContextMenu key={file.path} onOpenChange={(open) => open && pluginMenu.refresh()}>
+                <ContextMenuTrigger asChild>{renderRow(file)}</ContextMenuTrigger>
+                <ContextMenuContent>
+                  {pluginMenu.items.map((item) => (
+                    <ContextMenuItem
+                      key={`${item.pluginId}:${item.id}`}
+                      onClick={() => pluginMenu.run(item, { connectionId, path: file.path, isDir: file.isDir })}
+                      title={item.pluginName}
+                    >
+                      <Puzzle className="h-4 w-4 mr-2" />
+                      {item.title}
+                    </ContextMenuItem>
+                  ))}
+                </ContextMenuContent>
+              </ContextMenu>
+            ) : (
+              renderRow(file)
+            )
+          )}
         </div>
       )}
     </div>
@@ -19,10 +19,15 @@ export const ConnectionStatusBadge = memo(function ConnectionStatusBadge({ statu
     error: { label: t('session.status.error'), className: 'badge-error' },
   };
 
-  const config = statusConfig[status] || statusConfig.disconnected;
+  // 带原因的状态序列化为对象，如 { disconnected: { reason } }、{ error: message }
+  const key = typeof status === 'string' ? status : 'error' in status ? 'error' : 'disconnected';
+  const detail = typeof status === 'string'
+    ? undefined
+    : 'error' in status ? status.error : status.disconnected.reason;
+  const config = statusConfig[key] || statusConfig.disconnected;
 
   return (
-    <Badge variant="outline" className={cn(config.className, className)}>
+    <Badge variant="outline" className={cn(config.className, className)} title={detail}>
       {config.label}
     </Badge>
   );
@@ -7,7 +7,7 @@ import { SearchAddon } from '@xterm/addon-search';
 import { invoke } from '@tauri-apps/api/core';
 import { save } from '@tauri-apps/plugin-dialog';
 import { writeTextFile } from '@tauri-apps/plugin-fs';
-import { Sparkles, Copy, Clipboard, Search, Trash2, RotateCcw, ZoomIn, ZoomOut } from 'lucide-react';
+import { Sparkles, Copy, Clipboard, Search, Trash2, RotateCcw, ZoomIn, ZoomOut, Puzzle } from 'lucide-react';
 import { useTerminalConfigStore } from '@/store/terminalConfigStore';
 import { useTerminalStore } from '@/store/terminalStore';
 import { useKeybindingStore } from '@/store/keybindingStore';
@@ -18,6 +18,8 @@ import { CommandExplainerDialog } from '@/components/ai/command/CommandExplainer
 import { ContextMenu, ContextMenuContent, ContextMenuItem, ContextMenuTrigger, ContextMenuSeparator } from '@/components/ui/context-menu';
 import { normalizeKeyCombo } from '@/lib/keybindingParser';
 import { keybindingActionExecutor } from '@/lib/keybindingActions';
+import { usePluginMenuItems } from '@/hooks/usePluginMenuItems';
+import type { PluginMenuItem } from '@/types/plugin';
 import { toast } from 'sonner';
 import { useTranslation } from 'react-i18next';
 import '@xterm/xterm/css/xterm.css';
@@ -43,6 +45,10 @@ export function XTermWrapper({ connectionId }: XTermWrapperProps) {
   const [tempFontSize, setTempFontSize] = useState<number | null>(null); // 仅用于触发重新渲染
   const [showSearchDialog, setShowSearchDialog] = useState(false);
   const [searchTerm, setSearchTerm] = useState('');
+  // 开启水印时叠加显示的操作者、目标主机和时间
+  const watermark = useTerminalStore((state) => state.watermarks.get(connectionId));
+  // 插件提供的终端右键菜单项
+  const pluginMenu = usePluginMenuItems('terminal');
 
   // NL2CMD 对话框状态
   const [showNLToCmdDialog, setShowNLToCmdDialog] = useState(false);
@@ -629,6 +635,12 @@ export function XTermWrapper({ connectionId }: XTermWrapperProps) {
     }
   };
 
+  // 调用插件菜单项，传入连接 ID 和当前选中的文本
+  const handlePluginMenuItem = (item: PluginMenuItem) => {
+    const selection = terminalRefInstance.current?.getSelection() ?? '';
+    pluginMenu.run(item, { connectionId, selection });
+  };
+
   // 处理 AI 错误分析
   const handleErrorAnalyze = () => {
     if (terminalRefInstance.current && terminalRefInstance.current.hasSelection()) {
@@ -807,15 +819,22 @@ export function XTermWrapper({ connectionId }: XTermWrapperProps) {
   // 使用动态样式，支持 padding
   return (
     <>
-      <ContextMenu>
+      <ContextMenu onOpenChange={(open) => open && pluginMenu.refresh()}>
         <ContextMenuTrigger asChild>
-          <div
-            ref={terminalRef}
-            className="w-full h-full"
-            style={{
-              padding: `${config.padding}px`,
-            }}
-          />
+          <div className="relative w-full h-full">
+            <div
+              ref={terminalRef}
+              className="w-full h-full"
+              style={{
+                padding: `${config.padding}px`,
+              }}
+            />
+            {watermark && (
+              <div className="pointer-events-none select-none absolute bottom-2 right-4 text-xs text-white/30 font-mono">
+                {watermark}
+              </div>
+            )}
+          </div>
         </ContextMenuTrigger>
         <ContextMenuContent>
           <ContextMenuItem disabled={!hasSelection} onClick={handleCopy}>
@@ -868,6 +887,18 @@ export function XTermWrapper({ connectionId }: XTermWrapperProps) {
           <ContextMenuItem onClick={handleExportLog}>
             {t('terminal.contextMenu.exportLog')}
           </ContextMenuItem>
+
+          {pluginMenu.items.length > 0 && <ContextMenuSeparator />}
+          {pluginMenu.items.map((item) => (
+            <ContextMenuItem
+              key={`${item.pluginId}:${item.id}`}
+              onClick={() => handlePluginMenuItem(item)}
+              title={item.pluginName}
+            >
+              <Puzzle className="h-4 w-4 mr-2" />
+              {item.title}
+            </ContextMenuItem>
+          ))}
         </ContextMenuContent>
       </ContextMenu>
 
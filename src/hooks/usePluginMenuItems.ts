@@ -0,0 +1,48 @@
+import { useCallback, useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { toast } from 'sonner';
+import type { MenuContext, PluginMenuItem } from '@/types/plugin';
+
+/**
+ * 插件菜单项 Hook
+ *
+ * 加载某个位置的插件菜单项，菜单打开时调用 refresh 获取最新列表（插件可能已启动或退出）
+ */
+export function usePluginMenuItems(context: MenuContext) {
+  const [items, setItems] = useState<PluginMenuItem[]>([]);
+
+  const refresh = useCallback(async () => {
+    try {
+      setItems(await invoke<PluginMenuItem[]>('plugin_menu_items', { context }));
+    } catch (error) {
+      console.error('Failed to load plugin menu items:', error);
+      setItems([]);
+    }
+  }, [context]);
+
+  useEffect(() => {
+    refresh();
+  }, [refresh]);
+
+  /**
+   * 调用菜单项对应的插件命令，插件返回 `{ message }` 时显示提示
+   */
+  const run = useCallback(async (item: PluginMenuItem, params: Record<string, unknown>) => {
+    try {
+      const result = await invoke<unknown>('plugin_invoke', {
+        pluginId: item.pluginId,
+        command: item.command,
+        params,
+      });
+      const message = (result as { message?: unknown } | null)?.message;
+      if (typeof message === 'string' && message) {
+        toast.success(message);
+      }
+    } catch (error) {
+      console.error('Plugin command failed:', error);
+      toast.error(`${item.pluginName}: ${item.title}`, { description: String(error) });
+    }
+  }, []);
+
+  return { items, refresh, run };
+}
@@ -2,7 +2,7 @@
 
 import { invoke } from '@tauri-apps/api/core';
 import { listen } from '@tauri-apps/api/event';
-import type { AIProviderConfig, ChatMessage } from '@/types/ai';
+import type { AIProviderConfig, AIServerQuota, ChatMessage, FilePlan, GeneratedCommand } from '@/types/ai';
 
 /**
  * AI 客户端类
@@ -98,6 +98,30 @@ export class AIClient {
     });
   }
 
+  /**
+   * 自然语言转命令（结构化输出，用于确认卡片）
+   */
+  static async generateCommandStructured(config: AIProviderConfig, input: string): Promise<GeneratedCommand> {
+    return await invoke<GeneratedCommand>('ai_generate_command_structured', {
+      config: {
+        type: config.type,
+        apiKey: config.apiKey,
+        baseUrl: config.baseUrl,
+        model: config.model,
+        temperature: config.temperature || 0.7,
+        maxTokens: config.maxTokens || 2000,
+      },
+      input,
+    });
+  }
+
+  /**
+   * 获取服务器 AI 代理配额（server 类型 Provider）
+   */
+  static async getServerQuota(): Promise<AIServerQuota> {
+    return await invoke<AIServerQuota>('ai_server_quota');
+  }
+
   /**
    * 分析错误
    */
@@ -115,6 +139,30 @@ export class AIClient {
     });
   }
 
+  /**
+   * 将文件操作需求转换为待审阅的执行计划
+   */
+  static async planFileOperation(
+    config: AIProviderConfig,
+    connectionId: string,
+    request: string,
+    remoteDir?: string
+  ): Promise<FilePlan> {
+    return await invoke<FilePlan>('ai_file_assistant_plan', {
+      config: {
+        type: config.type,
+        apiKey: config.apiKey,
+        baseUrl: config.baseUrl,
+        model: config.model,
+        temperature: config.temperature || 0.7,
+        maxTokens: config.maxTokens || 2000,
+      },
+      connectionId,
+      request,
+      remoteDir,
+    });
+  }
+
   /**
    * 测试连接
    */
@@ -97,6 +97,22 @@ export class AIHistoryManager {
     await invoke('ai_history_update_connection_status', { id, status });
   }
 
+  /**
+   * 从指定消息处分叉对话
+   *
+   * 复制 messageIndex（含）之前的消息创建新对话，原对话不受影响
+   */
+  static async forkConversation(id: string, messageIndex: number): Promise<AIConversationMeta> {
+    return invoke<AIConversationMeta>('ai_history_fork', { id, messageIndex });
+  }
+
+  /**
+   * 获取对话的所有分支
+   */
+  static async listBranches(id: string): Promise<AIConversationMeta[]> {
+    return invoke<AIConversationMeta[]>('ai_history_list_branches', { id });
+  }
+
   /**
    * 导出会话
    */
@@ -44,6 +44,7 @@ export interface HotReloadResult {
   success: boolean;
   removedCount: number;
   message: string;
+  errors: string[];
 }
 
 // ==================== AI 服务类 ====================
@@ -7,6 +7,7 @@ import type {
   RecordingEvent,
   RecordingFile,
   ITerminalRecorder,
+  WatermarkStamp,
 } from '@/types/recording';
 import type { TerminalConfig } from '@/types/terminal';
 
@@ -93,6 +94,27 @@ export class TerminalRecorder implements ITerminalRecorder {
     });
   }
 
+  recordWatermark(stamp: WatermarkStamp): void {
+    if (!this.isRecordingFlag || this.isPausedFlag) return;
+
+    this.addEvent({
+      timestamp: Date.now(),
+      type: 'metadata',
+      data: { key: 'watermark', value: stamp },
+    });
+  }
+
+  // 标记事件（metadata 事件，key 为 'marker'），可用于生成章节
+  recordMarker(label: string): void {
+    if (!this.isRecordingFlag || this.isPausedFlag) return;
+
+    this.addEvent({
+      timestamp: Date.now(),
+      type: 'metadata',
+      data: { key: 'marker', value: { label } },
+    });
+  }
+
   stop(): RecordingFile {
     if (!this.isRecordingFlag) {
       throw new Error('Recorder is not recording');
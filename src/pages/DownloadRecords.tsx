@@ -35,6 +35,7 @@ interface DownloadRecord {
   errorMessage: string | null;
   createdAt: number;
   updatedAt: number;
+  transferKind: 'file' | 'directory' | null;
 }
 
 interface PaginatedDownloadRecords {
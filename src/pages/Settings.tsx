@@ -11,7 +11,10 @@ import {
   RotateCcw,
   Bot,
   Cloud,
-  Globe
+  Globe,
+  Stamp,
+  KeyRound,
+  Puzzle
 } from 'lucide-react';
 import { Input } from '@/components/ui/input';
 import { Button } from '@/components/ui/button';
@@ -24,6 +27,8 @@ import { useTheme } from '@/components/theme-provider';
 import { TerminalSettings } from '@/components/settings/TerminalSettings';
 import { KeybindingsSettings } from '@/components/keybindings/KeybindingsSettings';
 import { AISettings } from '@/components/settings/AISettings';
+import { HostKeySettings } from '@/components/settings/HostKeySettings';
+import { PluginSettings } from '@/components/settings/PluginSettings';
 import { soundManager, playSound } from '@/lib/sounds';
 import { SoundEffect } from '@/lib/sounds';
 import { useTerminalConfigStore } from '@/store/terminalConfigStore';
@@ -53,6 +58,7 @@ export function Settings() {
   const { language, setLanguage } = useLanguageStore();
 
   const [serverUrlInput, setServerUrlInput] = useState('');
+  const [watermark, setWatermark] = useState({ enabled: false, intervalSecs: 60 });
   const [isSavingServerUrl, setIsSavingServerUrl] = useState(false);
 
   // 加载配置
@@ -62,6 +68,24 @@ export function Settings() {
     loadAppSettings();
   }, [loadConfig, loadAIConfig, loadAppSettings]);
 
+  // 终端水印设置单独保存
+  useEffect(() => {
+    invoke<{ enabled: boolean; intervalSecs: number }>('app_settings_get_watermark')
+      .then(setWatermark)
+      .catch((error) => console.error('Failed to load watermark settings:', error));
+  }, []);
+
+  const updateWatermark = async (updates: Partial<typeof watermark>) => {
+    const next = { ...watermark, ...updates };
+    setWatermark(next);
+    try {
+      await invoke('app_settings_set_watermark', { watermark: next });
+    } catch (error) {
+      console.error('Failed to save watermark settings:', error);
+      playSound(SoundEffect.ERROR);
+    }
+  };
+
   // 当 appSettings 加载完成后，更新 serverUrlInput
   useEffect(() => {
     if (appSettings) {
@@ -164,6 +188,14 @@ export function Settings() {
             <Keyboard className="h-4 w-4" />
             {t('settings.tabs.keybindings')}
           </TabsTrigger>
+          <TabsTrigger value="hostKeys" className="gap-2 min-w-fit flex-1">
+            <KeyRound className="h-4 w-4" />
+            {t('settings.tabs.hostKeys')}
+          </TabsTrigger>
+          <TabsTrigger value="plugins" className="gap-2 min-w-fit flex-1">
+            <Puzzle className="h-4 w-4" />
+            {t('settings.tabs.plugins')}
+          </TabsTrigger>
           <TabsTrigger value="ai" className="gap-2 min-w-fit flex-1">
             <Bot className="h-4 w-4" />
             {t('settings.tabs.ai')}
@@ -575,6 +607,38 @@ export function Settings() {
 
             <Separator />
 
+            <div className="space-y-2">
+              <Label className="flex items-center gap-2">
+                <Stamp className="h-4 w-4" />
+                {t('settings.recording.watermark.label')}
+              </Label>
+              <p className="text-sm text-muted-foreground">
+                {t('settings.recording.watermark.description')}
+              </p>
+              <Switch
+                checked={watermark.enabled}
+                onCheckedChange={(checked) => {
+                  updateWatermark({ enabled: checked });
+                  playSound(SoundEffect.TOGGLE_SWITCH);
+                }}
+              />
+              {watermark.enabled && (
+                <div className="flex items-center gap-2">
+                  <Label htmlFor="watermark-interval">{t('settings.recording.watermark.interval')}</Label>
+                  <Input
+                    id="watermark-interval"
+                    type="number"
+                    min={5}
+                    className="w-24"
+                    value={watermark.intervalSecs}
+                    onChange={(e) => updateWatermark({ intervalSecs: Math.max(5, Number(e.target.value) || 60) })}
+                  />
+                </div>
+              )}
+            </div>
+
+            <Separator />
+
             <div className="rounded-lg border p-4 bg-muted/20">
               <h3 className="font-semibold mb-2 flex items-center gap-2">
                 <Volume2 className="h-4 w-4" />
@@ -599,6 +663,18 @@ export function Settings() {
           <KeybindingsSettings />
         </TabsContent>
 
+        {/* {t('settings.tabs.hostKeys')} */}
+        <TabsContent value="hostKeys" className="space-y-6">
+          <h2 className="text-xl font-semibold">{t('settings.tabs.hostKeys')}</h2>
+          <HostKeySettings />
+        </TabsContent>
+
+        {/* {t('settings.tabs.plugins')} */}
+        <TabsContent value="plugins" className="space-y-6">
+          <h2 className="text-xl font-semibold">{t('settings.tabs.plugins')}</h2>
+          <PluginSettings />
+        </TabsContent>
+
         {/* {t('settings.tabs.ai')} 设置 */}
         <TabsContent value="ai" className="space-y-6">
           <AISettings />
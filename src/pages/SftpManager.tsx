@@ -7,13 +7,14 @@
 import { useEffect, useState, useRef } from 'react';
 import { useTranslation } from 'react-i18next';
 import { useNavigate, Outlet, useLocation } from 'react-router-dom';
-import { ArrowLeft, RefreshCw, Upload, Download, HardDrive } from 'lucide-react';
+import { ArrowLeft, RefreshCw, Upload, Download, HardDrive, Sparkles } from 'lucide-react';
 import { Button } from '@/components/ui/button';
 import { Select, SelectContent, SelectItem, SelectTrigger, SelectValue } from '@/components/ui/select';
 import { useSessionStore } from '@/store/sessionStore';
 import { useSftpStore } from '@/store/sftpStore';
 import { toast } from 'sonner';
 import { DualPane } from '@/components/sftp/DualPane';
+import { AIFileAssistantDialog } from '@/components/sftp/AIFileAssistantDialog';
 import { invoke } from '@tauri-apps/api/core';
 import { listen } from '@tauri-apps/api/event';
 import { getCurrentWindow } from '@tauri-apps/api/window';
@@ -82,6 +83,7 @@ export function SftpManager() {
   const [downloading, setDownloading] = useState(false);
   const [remoteRefreshKey, setRemoteRefreshKey] = useState(0);
   const [localRefreshKey, setLocalRefreshKey] = useState(0);
+  const [assistantOpen, setAssistantOpen] = useState(false);
   // @ts-ignore - Used in useEffect closures
   const [uploadProgressMap, setUploadProgressMap] = useState<Map<string, UploadProgressEvent>>(new Map());
   // @ts-ignore - Used in useEffect closures
@@ -660,6 +662,17 @@ export function SftpManager() {
               <Download className="h-4 w-4 mr-2" />
               {downloading ? t('sftp.status.downloading') : t('sftp.action.download')}
             </Button>
+
+            <Button
+              variant="outline"
+              size="sm"
+              onClick={() => setAssistantOpen(true)}
+              disabled={!selectedConnectionId}
+              title={t('ai.fileAssistant.title')}
+            >
+              <Sparkles className="h-4 w-4 mr-2" />
+              {t('ai.fileAssistant.button')}
+            </Button>
           </div>
         </div>
       </div>
@@ -667,6 +680,17 @@ export function SftpManager() {
       {/* 双面板文件管理器 */}
       {selectedConnectionId ? (
         <div className="flex-1 overflow-hidden">
+          <AIFileAssistantDialog
+            open={assistantOpen}
+            onOpenChange={setAssistantOpen}
+            connectionId={selectedConnectionId}
+            remotePath={remotePath}
+            localPath={localPath}
+            onCompleted={() => {
+              setRemoteRefreshKey(prev => prev + 1);
+              setLocalRefreshKey(prev => prev + 1);
+            }}
+          />
           <DualPane
             connectionId={selectedConnectionId}
             remoteRefreshKey={remoteRefreshKey}
@@ -1,6 +1,6 @@
 import { useEffect, useState } from 'react';
 import { useTranslation } from 'react-i18next';
-import { Terminal as TerminalIcon, Plus, FolderOpen } from 'lucide-react';
+import { Terminal as TerminalIcon, Plus, FolderOpen, SquareTerminal, Cable } from 'lucide-react';
 import { useNavigate, useLocation } from 'react-router-dom';
 import { Button } from '@/components/ui/button';
 import { Separator } from '@/components/ui/separator';
@@ -8,6 +8,8 @@ import { TabBar } from '@/components/terminal/TabBar';
 import { XTermWrapper } from '@/components/terminal/XTermWrapper';
 import { ErrorBoundary } from '@/components/terminal/ErrorBoundary';
 import { QuickConnectDialog } from '@/components/session/QuickConnectDialog';
+import { SerialConnectDialog } from '@/components/session/SerialConnectDialog';
+import type { SerialConfig } from '@/types/ssh';
 import { ConnectionStatusBadge } from '@/components/ssh/ConnectionStatusBadge';
 import { RecordingControls, RecordingManager } from '@/components/recording';
 import { AIChatPanel } from '@/components/ai/chat/AIChatPanel';
@@ -23,10 +25,11 @@ export function Terminal() {
   const navigate = useNavigate();
   const location = useLocation();
   const [quickConnectOpen, setQuickConnectOpen] = useState(false);
+  const [serialConnectOpen, setSerialConnectOpen] = useState(false);
   const [isLoading, setIsLoading] = useState(true);
   const [showRecordingManager, setShowRecordingManager] = useState(false);
   const { toggleChat } = useAIStore();
-  const { sessions, loadSessions, createTemporaryConnection, connectSession, disconnectSession } = useSessionStore();
+  const { sessions, loadSessions, createTemporaryConnection, openLocalTerminal, openSerialTerminal, openTelnetTerminal, connectSession, disconnectSession } = useSessionStore();
   const { tabs, addTab, getActiveTab, focusTerminal } = useTerminalStore();
   const { config: terminalConfig } = useTerminalConfigStore();
 
@@ -161,6 +164,32 @@ export function Terminal() {
     setQuickConnectOpen(true);
   };
 
+  const handleLocalTerminal = async () => {
+    try {
+      const connectionId = await openLocalTerminal();
+      playSound(SoundEffect.TAB_OPEN);
+      await connectSession(connectionId);
+      addTab(connectionId, t('terminal.localTerminal'));
+    } catch (error) {
+      playSound(SoundEffect.ERROR);
+      console.error('Failed to open local terminal:', error);
+    }
+  };
+
+  const handleSerialConnect = async (path: string, settings: SerialConfig) => {
+    const connectionId = await openSerialTerminal(path, settings);
+    playSound(SoundEffect.TAB_OPEN);
+    await connectSession(connectionId);
+    addTab(connectionId, path);
+  };
+
+  const handleTelnetConnect = async (host: string, port: number) => {
+    const connectionId = await openTelnetTerminal(host, port);
+    playSound(SoundEffect.TAB_OPEN);
+    await connectSession(connectionId);
+    addTab(connectionId, `telnet://${host}:${port}`);
+  };
+
   const handleSessionManager = () => {
     playSound(SoundEffect.BUTTON_CLICK);
     navigate('/sessions');
@@ -221,6 +250,24 @@ export function Terminal() {
           {t('terminal.newConnection')}
         </Button>
 
+        <Button size="sm" variant="ghost" onClick={handleLocalTerminal} className="gap-1.5">
+          <SquareTerminal className="h-4 w-4" />
+          {t('terminal.localTerminal')}
+        </Button>
+
+        <Button
+          size="sm"
+          variant="ghost"
+          onClick={() => {
+            playSound(SoundEffect.BUTTON_CLICK);
+            setSerialConnectOpen(true);
+          }}
+          className="gap-1.5"
+        >
+          <Cable className="h-4 w-4" />
+          {t('terminal.serialConsole')}
+        </Button>
+
         <Separator orientation="vertical" className="h-5" />
 
         <Button size="sm" variant="ghost" onClick={handleSessionManager} className="gap-1.5">
@@ -308,6 +355,14 @@ export function Terminal() {
         open={quickConnectOpen}
         onOpenChange={setQuickConnectOpen}
         onConnect={handleQuickConnect}
+        onTelnetConnect={handleTelnetConnect}
+      />
+
+      {/* 串口连接对话框 */}
+      <SerialConnectDialog
+        open={serialConnectOpen}
+        onOpenChange={setSerialConnectOpen}
+        onConnect={handleSerialConnect}
       />
 
       {/* 录制文件管理器 */}
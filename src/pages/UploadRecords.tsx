@@ -35,6 +35,9 @@ interface UploadRecord {
   errorMessage: string | null;
   createdAt: number;
   updatedAt: number;
+  checksumSha256: string | null;
+  checksumVerified: boolean | null;
+  transferKind: 'file' | 'directory' | null;
 }
 
 interface PaginatedUploadRecords {
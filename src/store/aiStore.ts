@@ -2,7 +2,7 @@
 
 import { create } from 'zustand';
 import { invoke } from '@tauri-apps/api/core';
-import type { ChatMessage, AIConversation, ServerConversationGroup, ServerIdentity } from '@/types/ai';
+import type { ChatMessage, AIConversation, AIConversationMeta, ServerConversationGroup, ServerIdentity } from '@/types/ai';
 import { AIClient } from '@/lib/ai/aiClient';
 import { aiCache } from '@/lib/ai/cache';
 import { DEFAULT_SYSTEM_PROMPT } from '@/lib/ai/promptTemplates';
@@ -24,6 +24,7 @@ interface AIStore {
   // 历史记录管理（持久化）
   serverGroups: ServerConversationGroup[];    // 按服务器分组的历史
   selectedConversationId: string | null;      // 当前选中的对话 ID（从历史记录中选择）
+  branchConversations: Map<string, AIConversationMeta>; // connectionId -> 正在继续的分支对话（保存时不覆盖父对话）
 
   // 连接状态追踪
   activeConnections: Set<string>;            // 当前活跃的 connectionId
@@ -60,6 +61,7 @@ interface AIStore {
   selectServer: (serverId: string) => Promise<void>;
   selectConversation: (conversationId: string) => Promise<void>;
   createConversation: (serverId: string) => void;
+  forkConversation: (connectionId: string, messageIndex: number) => Promise<void>;
   isServerOnline: (serverId: string) => boolean;
   getServerActiveConnectionCount: (serverId: string) => number;
   updateActiveConnections: (connectionIds: string[]) => void;
@@ -87,6 +89,7 @@ export const useAIStore = create<AIStore>((set, get) => ({
   currentConnectionId: null,
   serverGroups: [],
   selectedConversationId: null,
+  branchConversations: new Map(),
   activeConnections: new Set(),
   streamingConnectionId: null,
   isChatOpen: false,
@@ -162,7 +165,7 @@ export const useAIStore = create<AIStore>((set, get) => ({
 
     try {
       // 添加系统提示词
-      const systemMessage: ChatMessage = { role: 'system', content: DEFAULT_SYSTEM_PROMPT };
+      const systemMessage: ChatMessage = { role: 'system', content: config.promptTemplates?.chat || DEFAULT_SYSTEM_PROMPT };
 
       // 调用流式 AI API
       const contextMessages = [systemMessage, ...newHistory.slice(-20)];
@@ -191,7 +194,7 @@ export const useAIStore = create<AIStore>((set, get) => ({
       const { conversations: finalConversations } = get();
       const finalHistory = finalConversations.get(connectionId);
       if (finalHistory && finalHistory.length > 0) {
-        const conversation = buildConversation(connectionId, finalHistory);
+        const conversation = buildConversation(connectionId, finalHistory, get().branchConversations.get(connectionId));
         await AIHistoryManager.saveConversation(conversation).catch(err => {
           console.error('[AIStore] Failed to save conversation:', err);
         });
@@ -384,7 +387,9 @@ export const useAIStore = create<AIStore>((set, get) => ({
       const { conversations } = get();
       const newConversations = new Map(conversations);
       newConversations.set(serverId, []);
-      set({ conversations: newConversations });
+      const branchConversations = new Map(get().branchConversations);
+      branchConversations.delete(serverId);
+      set({ conversations: newConversations, branchConversations });
       console.log('[AIStore] No conversation history for connection:', serverId);
     }
   },
@@ -407,8 +412,15 @@ export const useAIStore = create<AIStore>((set, get) => ({
       const connectionId = conversation.meta.connectionId;
       const newConversations = new Map(get().conversations);
       newConversations.set(connectionId, messages);
+      const branchConversations = new Map(get().branchConversations);
+      if (conversation.meta.parentId) {
+        branchConversations.set(connectionId, conversation.meta);
+      } else {
+        branchConversations.delete(connectionId);
+      }
       set({
         conversations: newConversations,
+        branchConversations,
         currentConnectionId: connectionId,
         currentServerId: connectionId,
         selectedConversationId: conversationId
@@ -424,8 +436,11 @@ export const useAIStore = create<AIStore>((set, get) => ({
     const { conversations } = get();
     const newConversations = new Map(conversations);
     newConversations.set(connectionId, []); // 清空内存中的对话历史
+    const branchConversations = new Map(get().branchConversations);
+    branchConversations.delete(connectionId);
     set({
       conversations: newConversations,
+      branchConversations,
       currentConnectionId: connectionId,
       currentServerId: connectionId,
       selectedConversationId: null
@@ -433,6 +448,15 @@ export const useAIStore = create<AIStore>((set, get) => ({
     console.log('[AIStore] Created new conversation for connection:', connectionId);
   },
 
+  forkConversation: async (connectionId: string, messageIndex: number) => {
+    // 当前对话的 ID：继续中的分支或连接本身的对话
+    const conversationId = get().branchConversations.get(connectionId)?.id ?? connectionId;
+    const branch = await AIHistoryManager.forkConversation(conversationId, messageIndex);
+    await get().selectConversation(branch.id);
+    await get().loadServerGroups();
+    console.log('[AIStore] Forked conversation', conversationId, 'at message', messageIndex, '->', branch.id);
+  },
+
   isServerOnline: (serverId: string) => {
     const { activeConnections } = get();
     const sessionStore = useSessionStore.getState();
@@ -532,7 +556,7 @@ export const useAIStore = create<AIStore>((set, get) => ({
  * @param connectionId - 连接 ID（每个终端连接的唯一标识，也作为对话ID）
  * @param messages - 消息列表
  */
-function buildConversation(connectionId: string, messages: ChatMessage[]): AIConversation {
+function buildConversation(connectionId: string, messages: ChatMessage[], branch?: AIConversationMeta): AIConversation {
   const firstUserMessage = messages.find(m => m.role === 'user');
   const now = new Date().toISOString();
 
@@ -576,8 +600,8 @@ function buildConversation(connectionId: string, messages: ChatMessage[]): AICon
 
   return {
     meta: {
-      id: connectionId, // 直接使用 connectionId 作为对话ID
-      title: generateTitle(firstUserMessage?.content || '新对话'),
+      id: branch?.id ?? connectionId, // 直接使用 connectionId 作为对话ID（分支对话保留自己的 ID）
+      title: branch?.title ?? generateTitle(firstUserMessage?.content || '新对话'),
       connectionId: connectionId, // 使用 connectionId（连接实例ID）
       serverIdentity: serverIdentity,
       createdAt: now,
@@ -585,6 +609,8 @@ function buildConversation(connectionId: string, messages: ChatMessage[]): AICon
       messageCount: messages.length,
       isArchived: false,
       connectionStatus: 'active' as const,
+      parentId: branch?.parentId,
+      branchPoint: branch?.branchPoint,
     },
     messages: messages.map(m => ({
       ...m,
@@ -0,0 +1,48 @@
+import { create } from 'zustand';
+import { invoke } from '@tauri-apps/api/core';
+
+/** 同一账号在线的其他设备 */
+export interface OnlineDevice {
+  clientId: string;
+  name: string;
+  platform: string;
+  connectedAt: number;
+}
+
+/** 其他设备发来的打开会话请求（后端已校验签名） */
+export interface HandoffRequest {
+  sessionId: string;
+  sessionName: string;
+  fromClientId: string;
+  fromName: string;
+  issuedAt: number;
+  nonce: string;
+}
+
+interface HandoffState {
+  devices: OnlineDevice[];
+  setDevices: (devices: OnlineDevice[]) => void;
+  start: () => Promise<void>;
+  stop: () => Promise<void>;
+  openOnDevice: (sessionId: string, targetClientId: string) => Promise<void>;
+}
+
+export const useHandoffStore = create<HandoffState>((set) => ({
+  devices: [],
+
+  setDevices: (devices) => set({ devices }),
+
+  start: async () => {
+    await invoke('handoff_start');
+    set({ devices: await invoke<OnlineDevice[]>('handoff_list_devices') });
+  },
+
+  stop: async () => {
+    await invoke('handoff_stop');
+    set({ devices: [] });
+  },
+
+  openOnDevice: async (sessionId, targetClientId) => {
+    await invoke('handoff_open_on_device', { sessionId, targetClientId });
+  },
+}));
@@ -8,8 +8,15 @@ import type {
   PlaybackSession,
   PlaybackStatus,
   RecordingFileItem,
+  RecordingChapter,
+  RecordingSearchResult,
+  TranscriptSegment,
+  TranscriptionEngine,
+  CloudRecording,
+  RecordingVisibility,
   VideoExportConfig,
   VideoExportProgress,
+  WatermarkStamp,
 } from '@/types/recording';
 import { TerminalRecorder } from '@/lib/recorder/TerminalRecorder';
 import { VideoRecorder } from '@/lib/recorder/VideoRecorder';
@@ -27,6 +34,9 @@ interface RecordingStore {
   // 录制文件列表
   recordingFiles: RecordingFileItem[];
 
+  // 云端录制列表（最近一次查询的结果）
+  cloudRecordings: CloudRecording[];
+
   // 视频导出进度
   exportProgress: VideoExportProgress | null;
 
@@ -104,6 +114,20 @@ interface RecordingStore {
    */
   recordResize: (connectionId: string, cols: number, rows: number) => void;
 
+  /**
+   * 记录水印事件（操作者、目标主机和时间）
+   * @param connectionId SSH 连接 ID
+   * @param stamp 水印内容
+   */
+  recordWatermark: (connectionId: string, stamp: WatermarkStamp) => void;
+
+  /**
+   * 记录标记事件（可用于生成章节）
+   * @param connectionId SSH 连接 ID
+   * @param label 标记名称
+   */
+  recordMarker: (connectionId: string, label: string) => void;
+
   // ========== 录制文件管理方法 ==========
 
   /**
@@ -145,6 +169,78 @@ interface RecordingStore {
     metadata: Partial<RecordingFile['metadata']>
   ) => Promise<void>;
 
+  /**
+   * 设置录制的章节标记（替换原有章节）
+   * @returns 排序后的章节
+   */
+  setRecordingChapters: (fileId: string, chapters: RecordingChapter[]) => Promise<RecordingChapter[]>;
+
+  /**
+   * 由录制中的标记事件生成章节（保留手动添加的章节）
+   * @param keys 作为章节的 metadata 事件 key，默认为 marker 和 recording_resumed
+   */
+  generateRecordingChapters: (fileId: string, keys?: string[]) => Promise<RecordingChapter[]>;
+
+  /**
+   * 裁剪录制到指定时间段，保存为新的录制文件
+   * @param startMs 起点（相对录制开始的毫秒数）
+   * @param endMs 终点（相对录制开始的毫秒数）
+   * @param includeVideo 同时截取关联的视频
+   * @returns 新录制文件的 ID
+   */
+  trimRecording: (fileId: string, startMs: number, endMs: number, includeVideo?: boolean) => Promise<string>;
+
+  /**
+   * 转写录制视频中的语音，结果保存到录制元数据
+   * @param language 语言代码（如 zh、en），为空时自动识别
+   */
+  transcribeRecording: (
+    fileId: string,
+    engine: TranscriptionEngine,
+    language?: string
+  ) => Promise<TranscriptSegment[]>;
+
+  /**
+   * 搜索录制（会话名称、描述、标签、章节标题和语音转写）
+   */
+  searchRecordings: (query: string) => Promise<RecordingSearchResult[]>;
+
+  // ========== 云端录制方法 ==========
+
+  /**
+   * 上传录制到云端库（需要登录）
+   * @param fileId 本地录制 ID
+   * @param visibility 可见性（默认 private）
+   * @param includeVideo 同时上传关联的视频
+   */
+  uploadRecording: (
+    fileId: string,
+    visibility?: RecordingVisibility,
+    includeVideo?: boolean
+  ) => Promise<CloudRecording[]>;
+
+  /**
+   * 列出云端录制
+   * @param scope mine：自己上传的；shared：共享给所有用户的
+   */
+  listCloudRecordings: (scope?: 'mine' | 'shared') => Promise<void>;
+
+  /**
+   * 下载云端录制到本地录制目录
+   * @returns 本地文件路径
+   */
+  downloadCloudRecording: (recording: CloudRecording) => Promise<string>;
+
+  /**
+   * 设置云端录制的可见性
+   */
+  setCloudRecordingVisibility: (id: string, visibility: RecordingVisibility) => Promise<void>;
+
+  /**
+   * 删除云端录制（不影响本地文件）
+   */
+  deleteCloudRecording: (id: string) => Promise<void>;
+
   /**
    * 导出录制文件为 JSON
    * @param fileId 文件 ID
@@ -199,7 +295,7 @@ interface RecordingStore {
   // ========== 视频导出方法 ==========
 
   /**
-   * 加载视频文件（从磁盘或缓存）
+   * 加载视频文件（从磁盘或缓存），也可加载视频封面和动态预览
    * @param videoFilename 视频文件名
    * @returns 视频 Blob
    */
@@ -234,6 +330,7 @@ export const useRecordingStore = create<RecordingStore>((set, get) => ({
   recordingSessions: new Map(),
   playbackSession: null,
   recordingFiles: [],
+  cloudRecordings: [],
   exportProgress: null,
   videoBlobCache: new Map(),
 
@@ -457,6 +554,20 @@ export const useRecordingStore = create<RecordingStore>((set, get) => ({
     }
   },
 
+  recordWatermark: (connectionId, stamp) => {
+    const session = get().recordingSessions.get(connectionId);
+    if (session?.recorder && session.status === 'recording') {
+      session.recorder.recordWatermark(stamp);
+    }
+  },
+
+  recordMarker: (connectionId, label) => {
+    const session = get().recordingSessions.get(connectionId);
+    if (session?.recorder && session.status === 'recording') {
+      session.recorder.recordMarker(label);
+    }
+  },
+
   recordResize: (connectionId, cols, rows) => {
     const session = get().recordingSessions.get(connectionId);
     if (session?.recorder && session.status === 'recording') {
@@ -531,6 +642,88 @@ export const useRecordingStore = create<RecordingStore>((set, get) => ({
     }
   },
 
+  setRecordingChapters: async (fileId, chapters) => {
+    const saved = await invoke<RecordingChapter[]>('recording_set_chapters', { fileId, chapters });
+    await get().listRecordingFiles();
+    return saved;
+  },
+
+  generateRecordingChapters: async (fileId, keys) => {
+    const chapters = await invoke<RecordingChapter[]>('recording_chapters_from_events', { fileId, keys });
+    await get().listRecordingFiles();
+    return chapters;
+  },
+
+  trimRecording: async (fileId, startMs, endMs, includeVideo = false) => {
+    const newId = await invoke<string>('recording_trim', {
+      fileId,
+      startMs: Math.round(startMs),
+      endMs: Math.round(endMs),
+      includeVideo,
+    });
+    console.log('[RecordingStore] Trimmed recording:', fileId, '->', newId);
+    await get().listRecordingFiles();
+    return newId;
+  },
+
+  transcribeRecording: async (fileId, engine, language) => {
+    const transcript = await invoke<TranscriptSegment[]>('recording_transcribe', {
+      fileId,
+      engine,
+      language,
+    });
+    console.log('[RecordingStore] Transcribed recording:', fileId, transcript.length);
+    await get().listRecordingFiles();
+    return transcript;
+  },
+
+  searchRecordings: async (query) => {
+    return invoke<RecordingSearchResult[]>('recording_search', { query });
+  },
+
+  uploadRecording: async (fileId, visibility = 'private', includeVideo = false) => {
+    const uploaded = await invoke<CloudRecording[]>('recording_upload', {
+      fileId,
+      visibility,
+      includeVideo,
+    });
+    console.log('[RecordingStore] Uploaded recording:', fileId, uploaded.length);
+    return uploaded;
+  },
+
+  listCloudRecordings: async (scope = 'mine') => {
+    try {
+      const recordings = await invoke<CloudRecording[]>('recording_cloud_list', { scope });
+      set({ cloudRecordings: recordings });
+    } catch (error) {
+      console.error('[RecordingStore] Failed to list cloud recordings:', error);
+      set({ cloudRecordings: [] });
+      throw error;
+    }
+  },
+
+  downloadCloudRecording: async (recording) => {
+    const filePath = await invoke<string>('recording_cloud_download', {
+      id: recording.id,
+      fileName: recording.fileName,
+    });
+    // 下载的录制出现在本地列表中
+    await get().listRecordingFiles();
+    return filePath;
+  },
+
+  setCloudRecordingVisibility: async (id, visibility) => {
+    const updated = await invoke<CloudRecording>('recording_cloud_set_visibility', { id, visibility });
+    set({
+      cloudRecordings: get().cloudRecordings.map((r) => (r.id === id ? updated : r)),
+    });
+  },
+
+  deleteCloudRecording: async (id) => {
+    await invoke('recording_cloud_delete', { id });
+    set({ cloudRecordings: get().cloudRecordings.filter((r) => r.id !== id) });
+  },
+
   exportRecordingAsJson: async (fileId) => {
     try {
       const file = get().recordingFiles.find((f) => f.id === fileId);
@@ -675,7 +868,11 @@ export const useRecordingStore = create<RecordingStore>((set, get) => ({
 
       // 确定 MIME 类型
       const extension = videoFilename.split('.').pop()?.toLowerCase();
-      const mimeType = extension === 'webm' ? 'video/webm' : 'video/mp4';
+      const mimeType =
+        extension === 'webm' ? 'video/webm'
+        : extension === 'jpg' ? 'image/jpeg'
+        : extension === 'gif' ? 'image/gif'
+        : 'video/mp4';
 
       // 创建 Blob
       const blob = new Blob([uint8Array], { type: mimeType });
@@ -1,21 +1,31 @@
 import { create } from 'zustand';
 import { persist } from 'zustand/middleware';
 import { invoke } from '@tauri-apps/api/core';
-import type { SessionConfig, SessionInfo } from '@/types/ssh';
+import type { BulkEditReport, BulkSessionChange, K8sTarget, SerialConfig, SessionConfig, SessionGroup, SessionInfo, TrashEntry } from '@/types/ssh';
 import { useAIStore } from './aiStore';
 
 interface SessionStore {
   sessions: SessionInfo[];
   isStorageLoaded: boolean; // 标记是否已从存储加载
   sessionConfigs: Map<string, SessionConfig>; // 缓存完整的会话配置
+  groups: SessionGroup[]; // 分组元数据（颜色、图标、排序）
 
   // 操作
   createTemporaryConnection: (config: SessionConfig) => Promise<string>; // 快速连接，不保存
+  openLocalTerminal: () => Promise<string>; // 本机 shell 连接，不保存
+  openSerialTerminal: (path: string, settings: SerialConfig) => Promise<string>; // 串口连接，不保存
+  openTelnetTerminal: (host: string, port: number) => Promise<string>; // Telnet 连接，不保存
+  openWslTerminal: (distribution?: string) => Promise<string>; // WSL 发行版，不保存
+  openDockerTerminal: (connectionId: string | null, container: string, shell?: string) => Promise<string>; // 容器终端，connectionId 为空时使用本机 Docker
+  openK8sTerminal: (target: K8sTarget, namespace: string, pod: string, container?: string) => Promise<string>; // Pod 终端，不保存
   createSession: (config: SessionConfig) => Promise<string>; // 创建持久化会话
   createConnection: (sessionId: string) => Promise<string>; // 基于现有会话创建新连接实例
   updateSession: (id: string, config: Partial<SessionConfig>) => Promise<void>;
-  deleteSession: (id: string) => Promise<void>;
-  connectSession: (id: string) => Promise<string>; // 现在返回connectionId
+  deleteSession: (id: string) => Promise<void>; // 移入回收站，30 天内可恢复
+  listTrash: () => Promise<TrashEntry[]>;
+  restoreSession: (id: string) => Promise<void>;
+  bulkEditSessions: (ids: string[], change: BulkSessionChange, dryRun: boolean) => Promise<BulkEditReport>; // dryRun 时只返回差异
+  connectSession: (id: string, profile?: string) => Promise<string>; // 现在返回connectionId；profile 为连接配置的 ID 或名称
   disconnectSession: (id: string) => Promise<void>;
   loadSessions: () => Promise<void>;
   loadSessionsFromStorage: () => Promise<void>;
@@ -23,6 +33,12 @@ interface SessionStore {
   saveSessions: () => Promise<void>;
   clearSessions: () => void; // 清除所有会话数据
 
+  // 分组
+  loadGroups: () => Promise<void>;
+  saveGroup: (group: Pick<SessionGroup, 'name' | 'color' | 'icon' | 'description'> & { id?: string }) => Promise<SessionGroup>; // 无 id 时创建
+  deleteGroup: (id: string) => Promise<void>; // 分组下的会话移动到默认分组
+  reorderGroups: (groupIds: string[]) => Promise<void>;
+
   // 查询
   getSession: (id: string) => SessionInfo | undefined;
   getActiveSession: () => SessionInfo | undefined;
@@ -35,6 +51,7 @@ export const useSessionStore = create<SessionStore>()(
       sessions: [],
       isStorageLoaded: false,
       sessionConfigs: new Map(),
+      groups: [],
 
       createTemporaryConnection: async (config) => {
         // 快速连接：直接创建临时连接，不保存到后端
@@ -66,6 +83,90 @@ export const useSessionStore = create<SessionStore>()(
         return connectionId;
       },
 
+      openLocalTerminal: async () => {
+        const connectionId = await invoke<string>('session_open_local', { command: null });
+
+        console.log('Created local terminal connection:', connectionId);
+
+        const aiStore = useAIStore.getState();
+        const currentActive = Array.from(aiStore.activeConnections);
+        aiStore.updateActiveConnections([...currentActive, connectionId]);
+
+        return connectionId;
+      },
+
+      openSerialTerminal: async (path, settings) => {
+        const connectionId = await invoke<string>('session_open_serial', { path, settings });
+
+        console.log('Created serial terminal connection:', connectionId);
+
+        const aiStore = useAIStore.getState();
+        const currentActive = Array.from(aiStore.activeConnections);
+        aiStore.updateActiveConnections([...currentActive, connectionId]);
+
+        return connectionId;
+      },
+
+      openTelnetTerminal: async (host, port) => {
+        const connectionId = await invoke<string>('session_open_telnet', { host, port });
+
+        console.log('Created telnet connection:', connectionId);
+
+        const aiStore = useAIStore.getState();
+        const currentActive = Array.from(aiStore.activeConnections);
+        aiStore.updateActiveConnections([...currentActive, connectionId]);
+
+        return connectionId;
+      },
+
+      openWslTerminal: async (distribution) => {
+        const connectionId = await invoke<string>('session_open_wsl', { distribution: distribution || null });
+
+        console.log('Created WSL connection:', connectionId);
+
+        const aiStore = useAIStore.getState();
+        const currentActive = Array.from(aiStore.activeConnections);
+        aiStore.updateActiveConnections([...currentActive, connectionId]);
+
+        return connectionId;
+      },
+
+      openDockerTerminal: async (connectionId, container, shell) => {
+        const newConnectionId = await invoke<string>('session_open_docker', {
+          connectionId,
+          container,
+          shell: shell || null,
+        });
+
+        console.log('Created docker connection:', newConnectionId);
+
+        const aiStore = useAIStore.getState();
+        const currentActive = Array.from(aiStore.activeConnections);
+        aiStore.updateActiveConnections([...currentActive, newConnectionId]);
+
+        return newConnectionId;
+      },
+
+      openK8sTerminal: async (target, namespace, pod, container) => {
+        const newConnectionId = await invoke<string>('k8s_exec_pod', {
+          connectionId: target.connectionId,
+          kubeconfig: target.kubeconfig || null,
+          context: target.context || null,
+          namespace,
+          pod,
+          container: container || null,
+          shell: null,
+        });
+
+        console.log('Created k8s connection:', newConnectionId);
+
+        const aiStore = useAIStore.getState();
+        const currentActive = Array.from(aiStore.activeConnections);
+        aiStore.updateActiveConnections([...currentActive, newConnectionId]);
+
+        return newConnectionId;
+      },
+
       createSession: async (config) => {
         // 使用数据库命令创建持久化会话
         const sessionConfig = {
@@ -200,6 +301,12 @@ export const useSessionStore = create<SessionStore>()(
           sessionId: id,
           updates,
         });
+        if (config.algorithms !== undefined) {
+          await invoke('db_ssh_session_set_algorithms', {
+            sessionId: id,
+            algorithms: config.algorithms,
+          });
+        }
 
         console.log('[sessionStore] Session updated:', id);
 
@@ -243,7 +350,7 @@ export const useSessionStore = create<SessionStore>()(
         }));
       },
 
-      connectSession: async (id) => {
+      connectSession: async (id, profile) => {
         const startTime = performance.now();
 
         // 确保 session 配置在内存中
@@ -257,7 +364,7 @@ export const useSessionStore = create<SessionStore>()(
           });
         }
 
-        const connectionId = await invoke<string>('session_connect', { sessionId: id });
+        const connectionId = await invoke<string>('session_connect', { sessionId: id, profile });
 
         // 重新加载sessions列表，包含新创建的连接实例
         await get().loadSessions();
@@ -473,9 +580,62 @@ export const useSessionStore = create<SessionStore>()(
       },
 
       clearSessions: () => {
-        set({ sessions: [], sessionConfigs: new Map(), isStorageLoaded: false });
+        set({ sessions: [], sessionConfigs: new Map(), groups: [], isStorageLoaded: false });
         console.log('[sessionStore] Sessions cleared');
       },
+
+      listTrash: async () => {
+        return invoke<TrashEntry[]>('trash_list');
+      },
+
+      restoreSession: async (id) => {
+        await invoke('session_restore', { sessionId: id });
+        await get().reloadSessions();
+      },
+
+      bulkEditSessions: async (ids, change, dryRun) => {
+        const report = await invoke<BulkEditReport>('session_bulk_edit', {
+          sessionIds: ids,
+          change,
+          dryRun,
+        });
+        if (!dryRun && report.changes.length > 0) {
+          await get().reloadSessions();
+        }
+        return report;
+      },
+
+      loadGroups: async () => {
+        const groups = await invoke<SessionGroup[]>('session_group_list');
+        set({ groups });
+      },
+
+      saveGroup: async (group) => {
+        const saved = await invoke<SessionGroup>('session_group_save', {
+          groupId: group.id ?? null,
+          name: group.name,
+          color: group.color ?? null,
+          icon: group.icon ?? null,
+          description: group.description ?? null,
+        });
+        await get().loadGroups();
+        // 重命名会同时修改会话的分组名
+        if (group.id) {
+          await get().reloadSessions();
+        }
+        return saved;
+      },
+
+      deleteGroup: async (id) => {
+        await invoke<number>('session_group_delete', { groupId: id });
+        await get().loadGroups();
+        await get().reloadSessions();
+      },
+
+      reorderGroups: async (groupIds) => {
+        const groups = await invoke<SessionGroup[]>('session_group_reorder', { groupIds });
+        set({ groups });
+      },
     }),
     {
       name: 'ssh-sessions-storage',
@@ -6,6 +6,7 @@ import { WebglAddon } from '@xterm/addon-webgl';
 import { listen, UnlistenFn } from '@tauri-apps/api/event';
 import { invoke } from '@tauri-apps/api/core';
 import { useRecordingStore } from '@/store/recordingStore';
+import type { WatermarkStamp } from '@/types/recording';
 
 export interface TerminalTab {
   id: string;
@@ -27,6 +28,7 @@ interface TerminalInstance {
   onDataDisposable?: { dispose: () => void }; // 存储 onData 监听器的 disposable 对象
   onDataListenerActive?: boolean; // 标记 onData 监听器是否已激活（用于防止重复设置）
   outputUnlisten?: UnlistenFn; // 存储输出监听器的 unlisten 函数
+  watermarkUnlisten?: UnlistenFn; // 存储水印监听器的 unlisten 函数
   onHostKeyDetect?: (host: string, fingerprint: string, keyType: string) => void; // 主机密钥检测回调
   outputListenerActive?: boolean; // 标记输出监听器是否已激活
 }
@@ -36,6 +38,8 @@ interface TerminalStore {
   // 存储每个 connectionId 对应的终端实例
   // 注意：虽然参数名是 sessionId，但实际存储的是 connectionId
   terminalInstances: Map<string, TerminalInstance>;
+  // 每个 connectionId 最近一次收到的水印文本（未开启水印时为空）
+  watermarks: Map<string, string>;
 
   // 标签页操作
   addTab: (connectionId: string, title: string) => string;
@@ -66,6 +70,7 @@ interface TerminalStore {
 export const useTerminalStore = create<TerminalStore>((set, get) => ({
   tabs: [],
   terminalInstances: new Map(),
+  watermarks: new Map(),
 
   addTab: (connectionId, title) => {
     const id = crypto.randomUUID();
@@ -241,6 +246,25 @@ export const useTerminalStore = create<TerminalStore>((set, get) => ({
     }).catch((error) => {
       console.error(`[TerminalStore] Failed to setup listener for ${eventName}:`, error);
     });
+
+    // 水印：叠加显示在终端上，并写入正在进行的录制
+    listen<WatermarkStamp>(`ssh-watermark-${connectionId}`, (event) => {
+      const newWatermarks = new Map(get().watermarks);
+      newWatermarks.set(connectionId, event.payload.text);
+      set({ watermarks: newWatermarks });
+      useRecordingStore.getState().recordWatermark(connectionId, event.payload);
+    }).then((unlisten) => {
+      const updatedInstance = get().terminalInstances.get(connectionId);
+      if (updatedInstance) {
+        const newInstances = new Map(get().terminalInstances);
+        newInstances.set(connectionId, { ...updatedInstance, watermarkUnlisten: unlisten });
+        set({ terminalInstances: newInstances });
+      } else {
+        unlisten();
+      }
+    }).catch((error) => {
+      console.error(`[TerminalStore] Failed to setup watermark listener for ${connectionId}:`, error);
+    });
   },
 
   // 清理输出监听器
@@ -248,15 +272,19 @@ export const useTerminalStore = create<TerminalStore>((set, get) => ({
     const instance = get().terminalInstances.get(connectionId);
     if (instance?.outputUnlisten) {
       instance.outputUnlisten();
+      instance.watermarkUnlisten?.();
 
       // 从 store 中移除 unlisten 引用，并标记监听器未激活
       const newInstances = new Map(get().terminalInstances);
       newInstances.set(connectionId, {
         ...instance,
         outputUnlisten: undefined,
+        watermarkUnlisten: undefined,
         outputListenerActive: false,
       });
-      set({ terminalInstances: newInstances });
+      const newWatermarks = new Map(get().watermarks);
+      newWatermarks.delete(connectionId);
+      set({ terminalInstances: newInstances, watermarks: newWatermarks });
     }
   },
 
@@ -10,7 +10,7 @@ export type ConversationId = string; // 对话ID (AIConversation.id)
 /**
  * AI 服务提供商类型
  */
-export type AIProviderType = 'openai' | 'ollama' | 'qwen' | 'wenxin';
+export type AIProviderType = 'openai' | 'ollama' | 'qwen' | 'wenxin' | 'server'; // server：由同步服务器代理，无需 API Key
 
 /**
  * AI 聊天消息角色
@@ -51,6 +51,35 @@ export interface AIConfig {
     openChat: string; // 快捷键：打开对话
     nlToCommand: string; // 快捷键：自然语言转命令
   };
+  promptTemplates?: AIPromptTemplates; // 自定义提示词模板
+  safetyRules?: AISafetyRules; // AI 生成命令的安全规则
+}
+
+/**
+ * AI 提示词模板（未设置的使用内置模板）
+ */
+export interface AIPromptTemplates {
+  explainCommand?: string;
+  nlToCommand?: string;
+  errorAnalysis?: string;
+  chat?: string;
+}
+
+/**
+ * AI 安全规则
+ */
+export interface AISafetyRules {
+  blockedPatterns?: string[]; // 禁止 AI 生成的命令片段（不区分大小写）
+}
+
+/**
+ * AI 配置导入结果
+ */
+export interface AIConfigImportSummary {
+  added: number;
+  updated: number;
+  keysImported: number;
+  keysLocked: boolean; // 文件包含加密的 API Key 但未提供口令
 }
 
 /**
@@ -105,6 +134,8 @@ export interface AIConversationMeta {
   messageCount: number;
   isArchived: boolean;
   connectionStatus: ConnectionStatus;
+  parentId?: ConversationId; // 分支来源对话
+  branchPoint?: number; // 分叉点：父对话中最后一条被复制的消息索引
 }
 
 /**
@@ -133,3 +164,63 @@ export interface ServerConversationGroup {
  * 导出格式
  */
 export type ExportFormat = 'markdown' | 'json' | 'text';
+
+/**
+ * AI 文件助手计划步骤
+ */
+export type FilePlanStep =
+  | { type: 'exec'; command: string; description: string }
+  | { type: 'download'; remotePath: string; description: string }
+  | { type: 'upload'; localPath: string; remotePath: string; description: string };
+
+/**
+ * 待审阅的计划步骤（risk 为命中的危险命令模式）
+ */
+export type PlannedStep = FilePlanStep & { risk?: string };
+
+/**
+ * AI 文件助手计划
+ */
+export interface FilePlan {
+  summary: string;
+  steps: PlannedStep[];
+}
+
+/**
+ * 计划步骤执行进度（ai-file-assistant-step 事件）
+ */
+export interface FileStepProgress {
+  connectionId: string;
+  index: number;
+  status: 'running' | 'succeeded' | 'failed' | 'skipped';
+  message?: string;
+}
+
+/**
+ * 命令危险等级
+ */
+export type DangerLevel = 'safe' | 'caution' | 'dangerous';
+
+/**
+ * 结构化命令生成结果
+ */
+export interface GeneratedCommand {
+  command: string;
+  explanation: string;
+  dangerLevel: DangerLevel;
+  requiresSudo: boolean;
+  /** 命中的本地危险命令模式 */
+  risk?: string;
+}
+
+/**
+ * 服务器 AI 代理配额
+ */
+export interface AIServerQuota {
+  enabled: boolean;
+  model: string;
+  /** 每日请求次数上限，0 表示不限制 */
+  dailyQuota: number;
+  used: number;
+  remaining?: number;
+}
@@ -63,6 +63,8 @@ export interface AuthResponse {
   serverUrl: string;
   deviceId: string;
   expiresAt: number;
+  /** 邮箱是否已验证（自动登录时未知，为 null） */
+  emailVerified?: boolean | null;
 }
 
 export interface AccountWithProfile {
@@ -0,0 +1,71 @@
+/**
+ * 插件类型定义
+ *
+ * 与后端 Rust 类型对应
+ */
+
+/**
+ * 插件菜单项出现的位置
+ */
+export type MenuContext = 'terminal' | 'sftpFile' | 'session';
+
+/**
+ * 插件可订阅的事件流
+ */
+export type PluginSubscription = 'terminalOutput';
+
+/**
+ * 插件声明的命令
+ */
+export interface PluginCommand {
+  /** 命令名（调用 plugin_invoke 时使用） */
+  name: string;
+  title?: string | null;
+  description?: string | null;
+}
+
+/**
+ * 插件声明的上下文菜单项
+ */
+export interface ContextMenuAction {
+  id: string;
+  title: string;
+  context: MenuContext;
+  /** 点击后调用的命令 */
+  command: string;
+}
+
+/**
+ * 插件信息（plugin_list 返回）
+ */
+export interface PluginInfo {
+  id: string;
+  name: string;
+  version: string;
+  description?: string | null;
+  /** 插件所在目录 */
+  dir: string;
+  enabled: boolean;
+  running: boolean;
+  /** 启动或握手失败的原因 */
+  error?: string | null;
+  commands: PluginCommand[];
+  contextMenu: ContextMenuAction[];
+  subscriptions: PluginSubscription[];
+}
+
+/**
+ * 某个位置的插件菜单项（plugin_menu_items 返回）
+ */
+export interface PluginMenuItem {
+  pluginId: string;
+  pluginName: string;
+  id: string;
+  title: string;
+  command: string;
+}
+
+/**
+ * 插件通过 host/emit 发送的事件前缀（事件名为 `plugin-event-{pluginId}`）
+ */
+export const PLUGIN_EVENT_PREFIX = 'plugin-event-';
@@ -34,6 +34,16 @@ export interface MetadataEventData {
   value: unknown;
 }
 
+// 水印事件数据（metadata 事件，key 为 'watermark'），记录操作者、目标主机和时间
+export interface WatermarkStamp {
+  account: string | null;  // 登录账号
+  localUser: string;       // 本机用户
+  localHost: string;       // 本机主机名
+  target: string;          // user@host:port
+  timestamp: number;       // Unix 时间戳（毫秒）
+  text: string;            // 叠加显示的文本
+}
+
 // 录制文件格式
 export interface RecordingFile {
   version: '1.0';  // 格式版本
@@ -71,6 +81,41 @@ export interface RecordingMetadata {
   };
   // 关联的视频文件路径（相对于 recordings 目录）
   videoFile?: string;
+  chapters?: RecordingChapter[];  // 章节标记（按时间排序）
+  transcript?: TranscriptSegment[];  // 语音转写（按时间排序）
+}
+
+// 章节标记
+export interface RecordingChapter {
+  offset: number;  // 相对录制开始的时间（毫秒）
+  title: string;
+  source?: 'manual' | 'event';  // 手动添加或由标记事件生成，默认为 manual
+}
+
+// 语音转写片段
+export interface TranscriptSegment {
+  start: number;  // 相对录制开始的时间（毫秒）
+  end: number;
+  text: string;
+}
+
+// 转写引擎：OpenAI 兼容的 Whisper 接口或本地 whisper.cpp
+export type TranscriptionEngine =
+  | { engine: 'api'; apiKey: string; baseUrl?: string; model?: string }
+  | { engine: 'local'; modelPath: string; binary?: string };
+
+// 录制搜索结果
+export interface RecordingSearchMatch {
+  field: 'sessionName' | 'description' | 'tag' | 'chapter' | 'transcript';
+  offset?: number;  // 章节和转写命中的时间（毫秒）
+  text: string;
+}
+
+export interface RecordingSearchResult {
+  id: string;
+  sessionName: string;
+  startTime: number;
+  matches: RecordingSearchMatch[];
 }
 
 // 录制状态
@@ -120,6 +165,8 @@ export interface ITerminalRecorder {
   recordInput(data: string): void;
   recordOutput(data: Uint8Array): void;
   recordResize(cols: number, rows: number): void;
+  recordWatermark(stamp: WatermarkStamp): void;
+  recordMarker(label: string): void;
   stop(): RecordingFile;
   pause(): void;
   resume(): void;
@@ -150,4 +197,26 @@ export interface RecordingFileItem {
   metadata: RecordingMetadata;
   createdAt: number;  // 创建时间
   fileSize: number;  // 文件大小（字节）
+  thumbnailFile?: string;  // 视频封面（保存视频时生成，可用 loadVideoBlob 加载）
+  previewFile?: string;  // 视频动态预览（GIF）
+}
+
+// 云端录制的可见性：private 仅自己可见，unlisted 知道 ID 的用户可下载，shared 共享给服务器上的所有用户
+export type RecordingVisibility = 'private' | 'unlisted' | 'shared';
+
+// 云端录制（recording_cloud_list 返回）
+export interface CloudRecording {
+  id: string;
+  title: string;
+  fileName: string;
+  kind: 'recording' | 'cast' | 'video';
+  contentType: string;
+  size: number;  // 文件大小（字节）
+  visibility: RecordingVisibility;
+  sessionName?: string;
+  recordingId?: string;  // 本地录制 ID（同一录制的 JSON 与视频相同）
+  duration?: number;
+  isOwner: boolean;
+  createdAt: number;  // 秒级时间戳
+  updatedAt: number;
 }
@@ -14,10 +14,12 @@ export interface SftpFileInfo {
   path: string;
   /** 文件大小（字节） */
   size: number;
-  /** 是否为目录 */
+  /** 是否为目录（指向目录的符号链接也为 true） */
   isDir: boolean;
   /** 是否为符号链接 */
   isSymlink: boolean;
+  /** 符号链接指向的路径 */
+  linkTarget?: string | null;
   /** 修改时间（Unix 时间戳） */
   modified: number;
   /** Unix 权限模式 */
@@ -28,6 +30,49 @@ export interface SftpFileInfo {
   group?: string;
 }
 
+/**
+ * SFTP 连接路径（`sftp_route` 返回）
+ *
+ * 经由跳板机连接时，文件位于 target 主机上
+ */
+export interface SftpRoute {
+  /** 文件所在的主机（`user@host:port`） */
+  target: string;
+  /** 途经的跳板机，按从本机出发的顺序 */
+  hops: string[];
+}
+
+/**
+ * 递归传输时符号链接的处理方式
+ *
+ * - preserve：在目标端重建链接本身（默认）
+ * - follow：传输链接指向的内容
+ */
+export type SymlinkPolicy = 'preserve' | 'follow';
+
+/**
+ * 目标已存在时的处理策略
+ *
+ * - overwrite：覆盖，目录则合并（默认）
+ * - skip：跳过
+ * - rename：自动重命名为 "name (1).ext"
+ * - fail：报错
+ * - newer：源比目标新时覆盖，否则跳过
+ */
+export type ConflictPolicy = 'overwrite' | 'skip' | 'rename' | 'fail' | 'newer';
+
+/**
+ * 目录上传 / 下载结果
+ */
+export interface DirectoryTransferResult {
+  totalFiles: number;
+  totalDirs: number;
+  totalSize: number;
+  elapsedTimeMs: number;
+  /** 按冲突策略跳过的源文件路径 */
+  skippedFiles: string[];
+}
+
 /**
  * 文件传输操作类型
  */
@@ -72,6 +117,145 @@ export interface TransferProgress {
   status: TransferStatus;
 }
 
+/**
+ * 传输队列状态（`sftp_queue_*` 返回，`sftp-queue-state` 事件内容）
+ */
+export interface TransferQueueState {
+  /** 暂停时排队中的任务不再开始 */
+  paused: boolean;
+  /** 同时进行的任务数量 */
+  maxConcurrent: number;
+  /** 正在进行的任务 */
+  running: TransferProgress[];
+  /** 排队中的任务（按执行顺序） */
+  pending: TransferProgress[];
+}
+
+/**
+ * 传输看门狗配置（`sftp_watchdog_*` 返回）
+ */
+export interface WatchdogConfig {
+  /** 无进度多少秒后判定为卡住 */
+  stallTimeoutSecs: number;
+  /** 无进度多少秒后自动取消，为空时不自动取消 */
+  autoCancelSecs: number | null;
+}
+
+/**
+ * 任务卡住事件（`sftp-task-stalled` 事件内容）
+ */
+export interface TaskStalledEvent {
+  taskId: string;
+  connectionId: string;
+  /** 已经多少秒没有进度 */
+  idleSecs: number;
+  /** 是否已被自动取消 */
+  cancelled: boolean;
+}
+
+/**
+ * 传输完整性校验结果（`TransferResult.checksum`）
+ */
+export interface ChecksumVerification {
+  localSha256: string;
+  remoteSha256: string;
+  /** 两端摘要是否一致 */
+  matched: boolean;
+}
+
+/**
+ * 远程文件搜索条件（`sftp_search` 的 filters 参数）
+ */
+export interface SearchFilters {
+  fileType?: 'any' | 'file' | 'dir';
+  /** 最小大小（字节，含） */
+  minSize?: number | null;
+  /** 最大大小（字节，含） */
+  maxSize?: number | null;
+  /** 修改时间不早于（Unix 时间戳，秒） */
+  modifiedAfter?: number | null;
+  /** 修改时间不晚于（Unix 时间戳，秒） */
+  modifiedBefore?: number | null;
+  /** 最大搜索深度，默认 16 */
+  maxDepth?: number | null;
+  caseSensitive?: boolean;
+  /** 最多返回的匹配数，默认 1000 */
+  limit?: number | null;
+}
+
+/**
+ * 搜索匹配项
+ */
+export interface SearchMatch {
+  path: string;
+  name: string;
+  isDir: boolean;
+  size: number;
+  /** 修改时间（Unix 时间戳，秒） */
+  modified: number;
+}
+
+/**
+ * `sftp-search-result` 事件内容，匹配项分批发送
+ */
+export interface SearchResultEvent {
+  searchId: string;
+  connectionId: string;
+  matches: SearchMatch[];
+}
+
+/**
+ * 搜索汇总（`sftp_search` 返回）
+ */
+export interface SearchSummary {
+  searchId: string;
+  totalMatches: number;
+  /** 达到数量上限，结果被截断 */
+  truncated: boolean;
+  cancelled: boolean;
+  /** 通过远程 find 还是 SFTP 逐层列目录搜索 */
+  method: 'find' | 'sftp';
+}
+
+/**
+ * 本地编辑会话（`sftp_edit_open`、`sftp_edit_list` 返回）
+ */
+export interface EditSessionInfo {
+  editId: string;
+  connectionId: string;
+  remotePath: string;
+  /** 本地临时文件路径 */
+  localPath: string;
+  /** 打开时间（Unix 时间戳，毫秒） */
+  openedAt: number;
+  /** 最近一次成功上传的时间（Unix 时间戳，毫秒） */
+  lastSyncedAt: number | null;
+}
+
+/**
+ * 本地编辑同步状态
+ *
+ * - opened：已下载并打开编辑器
+ * - failed：上传失败，或会话结束时本地文件有未上传的修改（临时文件保留，error 中给出路径）
+ * - closed：会话结束（手动关闭或连接断开），临时文件已删除
+ */
+export type EditSyncStatus = 'opened' | 'uploading' | 'synced' | 'failed' | 'closed';
+
+/**
+ * `sftp-edit-sync` 事件内容
+ */
+export interface EditSyncEvent {
+  editId: string;
+  connectionId: string;
+  remotePath: string;
+  status: EditSyncStatus;
+  /** 上传的字节数（仅 synced） */
+  bytes: number | null;
+  error: string | null;
+  /** Unix 时间戳（毫秒） */
+  timestamp: number;
+}
+
 /**
  * 文件权限模式
  */
@@ -29,9 +29,314 @@ export interface SessionConfig {
   group?: string;
   /** 心跳间隔（秒），0表示禁用（默认30秒） */
   keepAliveInterval?: number;
+  /** SSH 算法偏好（为空时使用默认算法） */
+  algorithms?: AlgorithmPreferences;
+  backendType?: 'russh' | 'local' | 'serial' | 'telnet' | 'wsl'; // local：本机 shell；serial：串口，host 为设备路径；telnet：旧设备；wsl：WSL 发行版（Windows）
+  /** 串口线路参数（serial 后端） */
+  serial?: SerialConfig;
+  /** WSL 发行版（wsl 后端），为空时使用默认发行版 */
+  wslDistribution?: string;
+  /** 传输中断时后台重连并恢复到原终端（仅 SSH 后端） */
+  roaming?: boolean;
+  /** 连接前发送网络唤醒魔术包并等待 SSH 端口可连接；更新时 macAddress 为空表示关闭 */
+  wakeOnLan?: WakeOnLanConfig;
+  /** 建立 TCP 连接前按顺序执行的动作（仅 SSH 后端），任一失败时不再连接 */
+  preConnect?: PreConnectAction[];
 }
 
-export type SessionStatus = 'disconnected' | 'connecting' | 'connected' | 'error';
+/** 网络唤醒设置 */
+export interface WakeOnLanConfig {
+  /** `aa:bb:cc:dd:ee:ff` 或 `aa-bb-cc-dd-ee-ff` */
+  macAddress: string;
+  /** 默认 255.255.255.255 */
+  broadcastAddress?: string;
+  /** 等待 SSH 端口可连接的最长时间（秒），默认 90 */
+  waitSecs?: number;
+}
+
+/** 端口敲门序列中的一个端口，protocol 默认 udp */
+export interface KnockPort {
+  port: number;
+  protocol?: 'udp' | 'tcp';
+}
+
+/** 连接前动作 */
+export type PreConnectAction =
+  /** 运行本地程序（不经过 shell），退出码为 0 视为成功；超时默认 15 秒 */
+  | { type: 'command'; program: string; args?: string[]; timeoutSecs?: number }
+  /** 请求敲门地址，返回 2xx 视为成功 */
+  | { type: 'httpRequest'; url: string; method?: 'GET' | 'POST'; timeoutSecs?: number }
+  /** host 为空时使用连接的主机（配置了跳板机时为第一跳），delayMs 默认 100 */
+  | { type: 'portKnock'; host?: string; sequence: KnockPort[]; delayMs?: number };
+
+/** 串口线路参数 */
+export interface SerialConfig {
+  baudRate: number;
+  dataBits: number; // 5-8
+  parity: 'none' | 'odd' | 'even';
+  stopBits: number; // 1 或 2
+  flowControl: 'none' | 'software' | 'hardware';
+}
+
+/** 本机串口 */
+export interface SerialPortInfo {
+  path: string;
+  portType: 'usb' | 'pci' | 'bluetooth' | 'unknown';
+  description?: string;
+}
+
+/** 已安装的 WSL 发行版 */
+export interface WslDistribution {
+  name: string;
+  state: string;
+  version?: number;
+  isDefault: boolean;
+}
+
+/** 运行中的 Docker 容器 */
+export interface DockerContainer {
+  id: string;
+  name: string;
+  image: string;
+  status: string;
+}
+
+/** Kubernetes Pod */
+export interface K8sPod {
+  name: string;
+  namespace: string;
+  phase: string;
+  containers: string[];
+}
+
+/** kubectl 目标：connectionId 为堡垒机连接，为空时使用本机 kubectl */
+export interface K8sTarget {
+  connectionId: string | null;
+  kubeconfig?: string;
+  context?: string;
+}
+
+export type AlgorithmPreset = 'default' | 'legacy';
+
+/** 各类算法按优先级排列，列表为空时使用预设 */
+export interface AlgorithmPreferences {
+  preset: AlgorithmPreset;
+  kex?: string[];
+  hostKey?: string[];
+  cipher?: string[];
+  mac?: string[];
+}
+
+/** 同一主机的命名连接配置（如 dev、debug、root），连接时选择 */
+export interface ConnectionProfile {
+  id: string;
+  name: string;
+  /** 引用的共享身份，优先于 username */
+  identityId?: string;
+  username?: string;
+  remoteCommand?: string;
+  /** shell 打开后依次输入的命令 */
+  startupCommands?: string[];
+}
+
+export type SessionStatus =
+  | 'disconnected'
+  | 'connecting'
+  | 'connected'
+  | 'error'
+  /** 远程关闭或连接中断，reason 为退出码、信号或服务器的断开原因 */
+  | { disconnected: { reason?: string } }
+  | { error: string };
+
+/** `ssh-disconnected-{connectionId}` 事件内容 */
+export interface DisconnectInfo {
+  exitStatus: number | null;
+  exitSignal: string | null;
+  coreDumped: boolean;
+  reason: string | null;
+  /** 服务器连续多次未响应心跳，连接被判定为失效 */
+  keepaliveTimeout: boolean;
+}
+
+/** `ssh-keepalive-timeout-{connectionId}` 事件内容 */
+export interface KeepaliveTimeoutEvent {
+  intervalSecs: number;
+  maxMissed: number;
+  /** 开启漫游时在后台重连，否则连接状态变为 `{ error: 'keepalive timeout' }` */
+  reconnecting: boolean;
+}
+
+/** `ssh-roaming-{connectionId}` 事件内容 */
+export type RoamingEvent =
+  | { state: 'reconnecting'; attempt: number; reason?: string }
+  | { state: 'resumed'; replayedInput: number }
+  | { state: 'failed'; reason: string };
+
+/** `ssh-security-key-{connectionId}` 事件内容（FIDO2 安全密钥认证） */
+export type SecurityKeyEvent =
+  | { state: 'touchRequired'; host: string; keyType: string; fingerprint: string }
+  | { state: 'done'; success: boolean };
+
+/** 已信任的主机密钥（`ssh_host_keys_list` 返回） */
+export interface HostKeyEntry {
+  line: number;
+  /** 端口不为 22 时为 `[host]:port` */
+  hosts: string;
+  hashed: boolean;
+  keyType: string;
+  sha256: string;
+  md5: string;
+  comment?: string;
+}
+
+/** 连接前检查项（`session_preflight` 返回） */
+export interface PreflightCheck {
+  kind: 'dns' | 'tcp' | 'hostKey' | 'keyFile';
+  status: 'passed' | 'warning' | 'failed' | 'skipped';
+  /** 主机或私钥文件路径 */
+  target: string;
+  message: string;
+  durationMs?: number;
+}
+
+export interface PreflightReport {
+  checks: PreflightCheck[];
+  /** 没有失败的检查项 */
+  ready: boolean;
+}
+
+/** 连接流量统计（`connection_stats` 返回，`ssh-stats-{connectionId}` 事件内容） */
+export interface ConnectionStats {
+  bytesSent: number;
+  bytesReceived: number;
+  /** 字节/秒 */
+  sendRate: number;
+  receiveRate: number;
+  uptimeSecs: number;
+}
+
+/** 连接流量使用记录（`connection_stats_history` 返回） */
+export interface ConnectionStatsRecord {
+  id: number;
+  sessionId: string;
+  connectionId: string;
+  sessionName: string;
+  hostKey: string;
+  connectedAt: number;
+  disconnectedAt: number;
+  bytesSent: number;
+  bytesReceived: number;
+}
+
+/** 使用热力图中一个小时的使用量（本地时间） */
+export interface HeatmapBucket {
+  /** YYYY-MM-DD */
+  date: string;
+  /** 0-23 */
+  hour: number;
+  /** 该小时内开始的连接数 */
+  connections: number;
+  durationSecs: number;
+}
+
+/** 按主机汇总的使用量 */
+export interface HeatmapHost {
+  hostKey: string;
+  sessionName: string;
+  connections: number;
+  durationSecs: number;
+}
+
+/** 使用热力图（`stats_heatmap` 返回） */
+export interface UsageHeatmap {
+  fromDate: string;
+  toDate: string;
+  buckets: HeatmapBucket[];
+  /** 本设备使用最多的主机 */
+  hosts: HeatmapHost[];
+  /** 汇总的设备数，只使用本机数据时为 1 */
+  devices: number;
+}
+
+/** 回收站中的会话（`trash_list` 返回） */
+export interface TrashEntry {
+  id: string;
+  name: string;
+  host: string;
+  port: number;
+  username: string;
+  group: string;
+  /** Unix 秒 */
+  deletedAt: number;
+  /** 超过该时间后不可恢复（Unix 秒） */
+  expiresAt: number;
+}
+
+export type KeyType = 'ed25519' | 'rsa';
+
+/** 生成的密钥文件（`ssh_keygen` 返回） */
+export interface GeneratedKeyInfo {
+  privateKeyPath: string;
+  publicKeyPath: string;
+  /** `authorized_keys` 格式，可传给 `ssh_copy_id` */
+  publicKey: string;
+  fingerprint: string;
+}
+
+/** 修改远程密码的方式：`passwd` 以当前用户身份修改，`chpasswd` 需要 root 权限 */
+export type RotationMethod = 'passwd' | 'chpasswd';
+
+/** 密码轮换结果（`password_rotate` 返回），失败时远程密码会被改回原密码 */
+export interface PasswordRotationReport {
+  sessionId: string;
+  method: RotationMethod;
+  verified: boolean;
+  storageUpdated: boolean;
+  databaseUpdated: boolean;
+}
+
+/** 会话分组（`session_group_list` 返回），会话通过分组名关联 */
+export interface SessionGroup {
+  id: string;
+  userId: string;
+  name: string;
+  /** 颜色，如 `#3b82f6` */
+  color?: string;
+  icon?: string;
+  sortOrder: number;
+  description?: string;
+  serverVer: number;
+  clientVer: number;
+  isDirty: boolean;
+  isDeleted: boolean;
+  lastSyncedAt?: number;
+  createdAt: number;
+  updatedAt: number;
+}
+
+/** 批量编辑的字段修改（`session_bulk_edit`） */
+export type BulkSessionChange =
+  | { field: 'username'; value: string }
+  | { field: 'port'; value: number }
+  | { field: 'keepAliveInterval'; value: number }
+  | { field: 'group'; value: string }
+  | { field: 'strictHostKeyChecking'; value: boolean }
+  | { field: 'identity'; identityId: string | null };
+
+export interface SessionFieldDiff {
+  sessionId: string;
+  sessionName: string;
+  field: string;
+  before: string;
+  after: string;
+}
+
+export interface BulkEditReport {
+  dryRun: boolean;
+  changes: SessionFieldDiff[];
+  unchanged: string[];
+  missing: string[];
+}
 
 export interface SessionInfo {
   id: string;
@@ -48,3 +353,56 @@ export interface SessionInfo {
   /** 如果是连接实例且已连接，这个字段存储实际的connectionId */
   connectionId?: string;
 }
+
+/** 局域网发现的 SSH 主机（discover_ssh_hosts） */
+export interface DiscoveredHost {
+  /** 建议的会话名称 */
+  name: string;
+  /** mDNS 主机名或 IP */
+  host: string;
+  ip: string;
+  port: number;
+  /** mDNS 服务类型，如 `_ssh._tcp` */
+  service?: string;
+  /** SSH 版本标识，如 `SSH-2.0-OpenSSH_9.6` */
+  banner?: string;
+  sources: ('mdns' | 'scan')[];
+}
+
+/** 由 ssh:// 或 sftp:// 链接创建的连接（deeplink-open 事件、deeplink_take_pending） */
+export interface DeepLinkOpened {
+  uri: string;
+  connectionId: string;
+  /** sftp 时打开文件标签页 */
+  scheme: 'ssh' | 'sftp';
+  /** sftp 链接中的远程路径 */
+  path?: string;
+}
+
+/** SSH 服务器安全公告 */
+export interface Advisory {
+  /** CVE 编号 */
+  id: string;
+  product: 'openssh' | 'dropbear';
+  /** 受影响的最低版本（含） */
+  introduced?: string;
+  /** 修复版本 */
+  fixed: string;
+  severity: 'medium' | 'high' | 'critical';
+  summary: string;
+}
+
+/** 服务器版本命中的安全公告（ssh-advisory-{connectionId} 事件） */
+export interface AdvisoryReport {
+  server: {
+    raw: string;
+    product?: 'openssh' | 'dropbear';
+    version?: string;
+    /** 发行版后缀，如 Ubuntu-3ubuntu13.5 */
+    comment?: string;
+  };
+  advisories: Advisory[];
+  /** 标识带有发行版后缀，修复可能已被反向移植 */
+  distroPatchedPossible: boolean;
+  listUpdated: string;
+}
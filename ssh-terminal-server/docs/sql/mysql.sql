@@ -21,6 +21,8 @@ CREATE TABLE IF NOT EXISTS email_logs (
     created_at BIGINT NOT NULL,
     updated_at BIGINT NOT NULL ON UPDATE CURRENT_TIMESTAMP
 ) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+ALTER TABLE session_notes MODIFY content MEDIUMTEXT NOT NULL;
+ALTER TABLE session_attachments MODIFY content MEDIUMTEXT NOT NULL;
 
 -- ============================================
 -- 2. 索引定义
@@ -36,15 +38,42 @@ CREATE INDEX IF NOT EXISTS idx_ssh_sessions_group ON ssh_sessions(group_name);
 CREATE INDEX IF NOT EXISTS idx_ssh_sessions_deleted ON ssh_sessions(deleted_at);
 CREATE INDEX IF NOT EXISTS idx_ssh_sessions_server_ver ON ssh_sessions(server_ver);
 
+-- 会话分组表索引
+CREATE INDEX IF NOT EXISTS idx_session_groups_user_id ON session_groups(user_id);
+CREATE INDEX IF NOT EXISTS idx_session_groups_updated_at ON session_groups(updated_at);
+
+-- 会话笔记表索引
+CREATE INDEX IF NOT EXISTS idx_session_notes_user_id ON session_notes(user_id);
+CREATE INDEX IF NOT EXISTS idx_session_notes_updated_at ON session_notes(updated_at);
+
+-- 会话附件表索引
+CREATE INDEX IF NOT EXISTS idx_session_attachments_user_id ON session_attachments(user_id);
+CREATE INDEX IF NOT EXISTS idx_session_attachments_updated_at ON session_attachments(updated_at);
+
 -- 邮件日志表索引
 CREATE INDEX IF NOT EXISTS idx_email_logs_user_id ON email_logs(user_id);
 CREATE INDEX IF NOT EXISTS idx_email_logs_email ON email_logs(email);
 CREATE INDEX IF NOT EXISTS idx_email_logs_status ON email_logs(status);
 CREATE INDEX IF NOT EXISTS idx_email_logs_created_at ON email_logs(created_at);
 
+-- 录制文件表索引
+CREATE INDEX IF NOT EXISTS idx_recordings_user_id ON recordings(user_id);
+CREATE INDEX IF NOT EXISTS idx_recordings_visibility ON recordings(visibility);
+
+-- 使用热力图表索引
+CREATE INDEX IF NOT EXISTS idx_usage_buckets_user_date ON usage_buckets(user_id, date);
+CREATE INDEX IF NOT EXISTS idx_usage_buckets_device ON usage_buckets(user_id, device_id);
+
+-- 工作区表索引
+CREATE INDEX IF NOT EXISTS idx_workspace_members_workspace_user ON workspace_members(workspace_id, user_id);
+CREATE INDEX IF NOT EXISTS idx_workspace_members_user_id ON workspace_members(user_id);
+CREATE INDEX IF NOT EXISTS idx_shared_sessions_workspace_id ON shared_sessions(workspace_id);
+CREATE INDEX IF NOT EXISTS idx_workspace_audit_logs_workspace_created ON workspace_audit_logs(workspace_id, created_at);
+
 -- ============================================
 -- 注意事项
 -- ============================================
 -- 1. MySQL 使用 ON UPDATE CURRENT_TIMESTAMP 自动更新时间戳，已在表定义中
 -- 2. server_ver 字段在应用层（Repository）中自动递增
 -- 3. 无需创建触发器
+-- 4. session_notes、session_attachments 的 content 列改为 MEDIUMTEXT，TEXT 最大只有 64KB
@@ -40,9 +40,35 @@ CREATE INDEX IF NOT EXISTS idx_ssh_sessions_group ON ssh_sessions(group_name);
 CREATE INDEX IF NOT EXISTS idx_ssh_sessions_deleted ON ssh_sessions(deleted_at);
 CREATE INDEX IF NOT EXISTS idx_ssh_sessions_server_ver ON ssh_sessions(server_ver);
 
+-- 会话分组表索引
+CREATE INDEX IF NOT EXISTS idx_session_groups_user_id ON session_groups(user_id);
+CREATE INDEX IF NOT EXISTS idx_session_groups_updated_at ON session_groups(updated_at);
+
+-- 会话笔记表索引
+CREATE INDEX IF NOT EXISTS idx_session_notes_user_id ON session_notes(user_id);
+CREATE INDEX IF NOT EXISTS idx_session_notes_updated_at ON session_notes(updated_at);
+
+-- 会话附件表索引
+CREATE INDEX IF NOT EXISTS idx_session_attachments_user_id ON session_attachments(user_id);
+CREATE INDEX IF NOT EXISTS idx_session_attachments_updated_at ON session_attachments(updated_at);
+
 -- 邮件日志表索引
 CREATE INDEX IF NOT EXISTS idx_email_logs_user_id ON email_logs(user_id);
 CREATE INDEX IF NOT EXISTS idx_email_logs_email ON email_logs(email);
 CREATE INDEX IF NOT EXISTS idx_email_logs_status ON email_logs(status);
 CREATE INDEX IF NOT EXISTS idx_email_logs_created_at ON email_logs(created_at);
 
+-- 录制文件表索引
+CREATE INDEX IF NOT EXISTS idx_recordings_user_id ON recordings(user_id);
+CREATE INDEX IF NOT EXISTS idx_recordings_visibility ON recordings(visibility);
+
+-- 使用热力图表索引
+CREATE INDEX IF NOT EXISTS idx_usage_buckets_user_date ON usage_buckets(user_id, date);
+CREATE INDEX IF NOT EXISTS idx_usage_buckets_device ON usage_buckets(user_id, device_id);
+
+-- 工作区表索引
+CREATE INDEX IF NOT EXISTS idx_workspace_members_workspace_user ON workspace_members(workspace_id, user_id);
+CREATE INDEX IF NOT EXISTS idx_workspace_members_user_id ON workspace_members(user_id);
+CREATE INDEX IF NOT EXISTS idx_shared_sessions_workspace_id ON shared_sessions(workspace_id);
+CREATE INDEX IF NOT EXISTS idx_workspace_audit_logs_workspace_created ON workspace_audit_logs(workspace_id, created_at);
+
@@ -0,0 +1,13 @@
+use serde::Deserialize;
+
+/// SSH 服务器安全公告配置
+///
+/// 客户端通过 `GET /api/ssh/advisories` 更新本地的 OpenSSH/Dropbear 漏洞列表，
+/// 服务器默认返回内置列表；指定 `file` 后返回该 JSON 文件中的列表（比内置列表新时），
+/// 修改文件后无需重启即可生效
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct AdvisoryConfig {
+    /// 公告列表 JSON 文件路径（格式同接口返回的 data），为空时只使用内置列表
+    #[serde(default)]
+    pub file: Option<String>,
+}
@@ -0,0 +1,70 @@
+use serde::Deserialize;
+
+/// AI 代理配置
+///
+/// 启用后客户端可选择“服务器托管”的 AI Provider，
+/// 请求由服务器使用此处的凭据转发到 OpenAI 兼容接口
+#[derive(Debug, Deserialize, Clone)]
+pub struct AiProxyConfig {
+    /// 是否启用 AI 代理
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// OpenAI 兼容接口地址
+    #[serde(default = "default_base_url")]
+    pub base_url: String,
+
+    /// API Key
+    #[serde(default)]
+    pub api_key: String,
+
+    /// 使用的模型
+    #[serde(default = "default_model")]
+    pub model: String,
+
+    /// 单次请求的最大 token 数（客户端请求的值不能超过它）
+    #[serde(default = "default_max_tokens")]
+    pub max_tokens: u32,
+
+    /// 每个用户每日请求次数上限，0 表示不限制
+    #[serde(default = "default_daily_quota")]
+    pub daily_quota: u64,
+
+    /// 上游请求超时时间（秒）
+    #[serde(default = "default_timeout")]
+    pub timeout_seconds: u64,
+}
+
+impl Default for AiProxyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            base_url: default_base_url(),
+            api_key: String::new(),
+            model: default_model(),
+            max_tokens: default_max_tokens(),
+            daily_quota: default_daily_quota(),
+            timeout_seconds: default_timeout(),
+        }
+    }
+}
+
+fn default_base_url() -> String {
+    "https://api.openai.com/v1".to_string()
+}
+
+fn default_model() -> String {
+    "gpt-4o-mini".to_string()
+}
+
+fn default_max_tokens() -> u32 {
+    2000
+}
+
+fn default_daily_quota() -> u64 {
+    200
+}
+
+fn default_timeout() -> u64 {
+    60
+}
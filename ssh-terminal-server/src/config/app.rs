@@ -1,4 +1,4 @@
-use super::{auth::AuthConfig, database::DatabaseConfig, email::EmailConfig, redis::RedisConfig, server::ServerConfig};
+use super::{advisory::AdvisoryConfig, ai::AiProxyConfig, auth::AuthConfig, database::DatabaseConfig, email::EmailConfig, recording::RecordingStorageConfig, redis::RedisConfig, server::ServerConfig};
 use config::{Config, ConfigError, Environment, File};
 use serde::Deserialize;
 use std::path::PathBuf;
@@ -13,6 +13,15 @@ pub struct AppConfig {
     pub auth: AuthConfig,
     pub redis: RedisConfig,
     pub email: EmailConfig,
+    /// AI 代理（未配置时关闭）
+    #[serde(default)]
+    pub ai: AiProxyConfig,
+    /// 录制文件云端存储（未配置时关闭）
+    #[serde(default)]
+    pub recording: RecordingStorageConfig,
+    /// SSH 服务器安全公告（未配置时使用内置列表）
+    #[serde(default)]
+    pub advisories: AdvisoryConfig,
 }
 
 impl AppConfig {
@@ -57,6 +66,8 @@ impl AppConfig {
             builder = builder.set_default("auth.jwt_secret", default_jwt_secret())?;
             builder = builder.set_default("auth.access_token_expiration_minutes", 15)?;
             builder = builder.set_default("auth.refresh_token_expiration_days", 7)?;
+            builder = builder.set_default("auth.email_verification", "required")?;
+            builder = builder.set_default("auth.unverified_grace_days", 7)?;
 
             // 设置 redis 默认值
             builder = builder.set_default("redis.host", default_redis_host())?;
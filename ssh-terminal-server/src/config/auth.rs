@@ -8,6 +8,39 @@ pub struct AuthConfig {
     pub access_token_expiration_minutes: u64,
     #[serde(default = "default_refresh_token_expiration_days")]
     pub refresh_token_expiration_days: i64,
+    /// 未验证邮箱账号的限制方式（仅在启用邮件功能时生效）
+    #[serde(default)]
+    pub email_verification: EmailVerificationMode,
+    /// 未验证邮箱的账号注册后可以登录的天数
+    #[serde(default = "default_unverified_grace_days")]
+    pub unverified_grace_days: i64,
+}
+
+/// 未验证邮箱账号的限制方式
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum EmailVerificationMode {
+    /// 注册时必须提供验证码（默认）
+    #[default]
+    Required,
+    /// 允许不带验证码注册，未验证的账号只能在宽限期内登录
+    Grace,
+    /// 不限制未验证的账号
+    Off,
+}
+
+impl AuthConfig {
+    /// 未验证账号最后可以登录的时间（Unix 时间戳，秒），不限制时返回 None
+    ///
+    /// Required 模式下新注册的账号都已验证，宽限期只作用于此前以其他模式注册的账号
+    pub fn unverified_login_deadline(&self, created_at: i64) -> Option<i64> {
+        match self.email_verification {
+            EmailVerificationMode::Off => None,
+            EmailVerificationMode::Required | EmailVerificationMode::Grace => {
+                Some(created_at + self.unverified_grace_days.max(0) * 24 * 3600)
+            }
+        }
+    }
 }
 
 fn default_jwt_secret() -> String {
@@ -23,3 +56,7 @@ fn default_access_token_expiration_minutes() -> u64 {
 fn default_refresh_token_expiration_days() -> i64 {
     7
 }
+
+fn default_unverified_grace_days() -> i64 {
+    7
+}
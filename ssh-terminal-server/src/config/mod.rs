@@ -4,3 +4,6 @@ pub mod database;
 pub mod redis;
 pub mod server;
 pub mod email;
+pub mod ai;
+pub mod recording;
+pub mod advisory;
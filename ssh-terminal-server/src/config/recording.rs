@@ -0,0 +1,59 @@
+use serde::Deserialize;
+
+/// 录制文件云端存储配置
+///
+/// 启用后客户端可选择上传录制文件（录制 JSON、.cast 或视频），
+/// 文件保存在服务器本地目录，元数据保存在数据库
+#[derive(Debug, Deserialize, Clone)]
+pub struct RecordingStorageConfig {
+    /// 是否启用录制上传
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// 文件存储目录（相对路径基于工作目录）
+    #[serde(default = "default_storage_dir")]
+    pub storage_dir: String,
+
+    /// 单个文件大小上限（MB）
+    #[serde(default = "default_max_file_size_mb")]
+    pub max_file_size_mb: u64,
+
+    /// 每个用户的存储空间上限（MB），0 表示不限制
+    #[serde(default = "default_user_quota_mb")]
+    pub user_quota_mb: u64,
+}
+
+impl Default for RecordingStorageConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            storage_dir: default_storage_dir(),
+            max_file_size_mb: default_max_file_size_mb(),
+            user_quota_mb: default_user_quota_mb(),
+        }
+    }
+}
+
+impl RecordingStorageConfig {
+    /// 单个文件大小上限（字节）
+    pub fn max_file_size(&self) -> usize {
+        (self.max_file_size_mb * 1024 * 1024) as usize
+    }
+
+    /// 每个用户的存储空间上限（字节），不限制时为 None
+    pub fn user_quota(&self) -> Option<i64> {
+        (self.user_quota_mb > 0).then(|| (self.user_quota_mb * 1024 * 1024) as i64)
+    }
+}
+
+fn default_storage_dir() -> String {
+    "recordings".to_string()
+}
+
+fn default_max_file_size_mb() -> u64 {
+    200
+}
+
+fn default_user_quota_mb() -> u64 {
+    2048
+}
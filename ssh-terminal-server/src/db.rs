@@ -322,6 +322,33 @@ where
     Ok(())
 }
 
+/// 辅助函数：为已有表添加列（列已存在时忽略）
+///
+/// 新建的表已按实体定义包含所有列，这里只处理旧版本创建的表
+async fn add_column_if_missing(
+    db: &DatabaseConnection,
+    builder: &DbBackend,
+    table: &str,
+    column: &str,
+    definition: &str,
+) -> anyhow::Result<()> {
+    let sql = format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition);
+
+    match db.execute(Statement::from_string(*builder, sql)).await {
+        Ok(_) => {
+            tracing::info!("✅ {}.{} 列已添加", table, column);
+        }
+        Err(e) => {
+            let err_msg = e.to_string().to_lowercase();
+            if !(err_msg.contains("duplicate column") || err_msg.contains("already exists")) {
+                return Err(anyhow::anyhow!("添加列 {}.{} 失败: {}", table, column, e));
+            }
+        }
+    }
+
+    Ok(())
+}
+
 /// 创建数据库表结构
 async fn create_tables(db: &DatabaseConnection) -> anyhow::Result<()> {
     tracing::info!("检查数据库表结构...");
@@ -330,13 +357,26 @@ async fn create_tables(db: &DatabaseConnection) -> anyhow::Result<()> {
     let schema = Schema::new(builder);
 
     // 导入所有 entities
-    use crate::domain::entities::{users, user_profiles, ssh_sessions, email_logs};
+    use crate::domain::entities::{users, user_profiles, ssh_sessions, session_groups, session_notes, session_attachments, email_logs, crash_reports, recordings, usage_buckets, workspaces, workspace_members, shared_sessions, workspace_audit_logs};
 
     // 创建所有表（添加新表只需一行！）
     create_single_table(db, &schema, &builder, users::Entity, "用户表").await?;
     create_single_table(db, &schema, &builder, user_profiles::Entity, "用户资料表").await?;
     create_single_table(db, &schema, &builder, ssh_sessions::Entity, "SSH会话表").await?;
+    create_single_table(db, &schema, &builder, session_groups::Entity, "会话分组表").await?;
+    create_single_table(db, &schema, &builder, session_notes::Entity, "会话笔记表").await?;
+    create_single_table(db, &schema, &builder, session_attachments::Entity, "会话附件表").await?;
     create_single_table(db, &schema, &builder, email_logs::Entity, "邮件日志表").await?;
+    create_single_table(db, &schema, &builder, crash_reports::Entity, "崩溃报告表").await?;
+    create_single_table(db, &schema, &builder, recordings::Entity, "录制文件表").await?;
+    create_single_table(db, &schema, &builder, usage_buckets::Entity, "使用热力图表").await?;
+    create_single_table(db, &schema, &builder, workspaces::Entity, "工作区表").await?;
+    create_single_table(db, &schema, &builder, workspace_members::Entity, "工作区成员表").await?;
+    create_single_table(db, &schema, &builder, shared_sessions::Entity, "共享会话表").await?;
+    create_single_table(db, &schema, &builder, workspace_audit_logs::Entity, "工作区审计日志表").await?;
+
+    // 为已有表补充新增的列
+    add_column_if_missing(db, &builder, "users", "email_verified", "BOOLEAN NOT NULL DEFAULT TRUE").await?;
 
     tracing::info!("✅ 数据库表结构检查完成");
 
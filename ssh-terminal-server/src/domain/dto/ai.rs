@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// 聊天消息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AiChatMessage {
+    pub role: String,
+    pub content: String,
+}
+
+impl AiChatMessage {
+    /// 角色必须是 system、user 或 assistant
+    pub fn is_valid(&self) -> bool {
+        matches!(self.role.as_str(), "system" | "user" | "assistant")
+    }
+}
+
+/// 单次请求中消息内容的总长度上限（字节）
+pub const MAX_CHAT_CONTENT_LEN: usize = 256 * 1024;
+
+/// AI 代理聊天请求
+#[derive(Debug, Deserialize, Validate)]
+pub struct AiChatRequest {
+    #[validate(length(min = 1, max = 100))]
+    pub messages: Vec<AiChatMessage>,
+    pub temperature: Option<f32>,
+    pub max_tokens: Option<u32>,
+}
@@ -32,6 +32,19 @@ impl fmt::Debug for LoginRequest {
     }
 }
 
+/// 验证邮箱请求（注册后补充验证）
+#[derive(Deserialize)]
+pub struct VerifyEmailRequest {
+    pub email: String,
+    pub verify_code: String,
+}
+
+impl fmt::Debug for VerifyEmailRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "VerifyEmailRequest {{ email: {}, verify_code: *** }}", self.email)
+    }
+}
+
 /// 删除用户请求
 #[derive(Deserialize)]
 pub struct DeleteUserRequest {
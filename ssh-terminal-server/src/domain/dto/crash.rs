@@ -0,0 +1,34 @@
+use serde::Deserialize;
+use validator::Validate;
+
+/// 提交崩溃报告请求（公开 API，无需认证）
+#[derive(Debug, Deserialize, Validate)]
+pub struct CrashReportRequest {
+    /// 客户端生成的报告 ID
+    #[validate(length(min = 1, max = 64))]
+    pub report_id: String,
+    #[validate(length(max = 64))]
+    pub device_id: Option<String>,
+    #[validate(length(min = 1, max = 32))]
+    pub app_version: String,
+    #[validate(length(min = 1, max = 32))]
+    pub os: String,
+    #[validate(length(max = 64))]
+    pub os_version: Option<String>,
+    #[validate(length(min = 1, max = 32))]
+    pub arch: String,
+    #[validate(length(max = 4096))]
+    pub message: String,
+    /// 崩溃位置（文件:行号）
+    #[validate(length(max = 512))]
+    pub location: Option<String>,
+    /// 调用栈
+    #[validate(length(max = 262144))]
+    pub backtrace: String,
+    /// 崩溃前的日志（按行拼接）
+    #[validate(length(max = 262144))]
+    pub log_tail: String,
+    /// 崩溃时间（毫秒）
+    #[validate(range(min = 0))]
+    pub crashed_at: i64,
+}
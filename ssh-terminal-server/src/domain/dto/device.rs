@@ -0,0 +1,29 @@
+use serde::Deserialize;
+use validator::Validate;
+
+/// 设备 WebSocket 连接参数
+#[derive(Debug, Deserialize, Validate)]
+pub struct DeviceWsQuery {
+    /// 客户端实例 ID（客户端每次启动时生成）
+    #[validate(length(min = 1, max = 64))]
+    pub client_id: String,
+    /// 设备名称（一般为主机名）
+    #[validate(length(max = 128))]
+    pub name: Option<String>,
+    #[validate(length(max = 32))]
+    pub platform: Option<String>,
+}
+
+/// 客户端通过 WebSocket 发送的消息
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DeviceClientMessage {
+    /// 请求同一用户的另一台设备打开会话
+    ///
+    /// payload 和 signature 由客户端生成和校验，服务器只负责转发
+    Handoff {
+        target_client_id: String,
+        payload: String,
+        signature: String,
+    },
+}
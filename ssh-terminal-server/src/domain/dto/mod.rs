@@ -3,3 +3,9 @@ pub mod user;
 pub mod ssh;
 pub mod sync;
 pub mod mail;
+pub mod crash;
+pub mod device;
+pub mod ai;
+pub mod recording;
+pub mod stats;
+pub mod workspace;
@@ -0,0 +1,53 @@
+use serde::Deserialize;
+use validator::Validate;
+
+/// 录制文件可见性
+///
+/// - `private`: 仅上传者可见
+/// - `unlisted`: 不出现在共享列表中，知道 ID 的登录用户可以下载
+/// - `shared`: 出现在所有用户的共享列表中
+pub const VISIBILITIES: [&str; 3] = ["private", "unlisted", "shared"];
+
+/// 录制文件类型及对应的 MIME 类型
+pub fn content_type_for(kind: &str) -> Option<&'static str> {
+    match kind {
+        "recording" => Some("application/json"),
+        "cast" => Some("application/x-asciicast"),
+        "video" => Some("video/webm"),
+        _ => None,
+    }
+}
+
+/// 上传录制文件的查询参数（请求体为文件内容）
+#[derive(Debug, Deserialize, Validate)]
+pub struct UploadRecordingQuery {
+    #[validate(length(min = 1, max = 255))]
+    pub title: String,
+    #[validate(length(min = 1, max = 255))]
+    pub file_name: String,
+    /// recording、cast 或 video
+    pub kind: String,
+    /// 为空时为 private
+    pub visibility: Option<String>,
+    #[validate(length(max = 255))]
+    pub session_name: Option<String>,
+    #[validate(length(max = 64))]
+    pub recording_id: Option<String>,
+    pub duration: Option<f64>,
+    /// 视频的 MIME 类型（如 video/mp4），为空时按 kind 推断
+    #[validate(length(max = 64))]
+    pub content_type: Option<String>,
+}
+
+/// 列出录制文件的查询参数
+#[derive(Debug, Deserialize)]
+pub struct ListRecordingsQuery {
+    /// mine（默认）或 shared
+    pub scope: Option<String>,
+}
+
+/// 更新可见性请求
+#[derive(Debug, Deserialize)]
+pub struct UpdateRecordingVisibilityRequest {
+    pub visibility: String,
+}
@@ -0,0 +1,38 @@
+use serde::Deserialize;
+use validator::Validate;
+
+/// 单次上报的最大分桶数（一年的每小时）
+pub const MAX_USAGE_BUCKETS: usize = 366 * 24;
+
+/// 一个小时的使用量
+#[derive(Debug, Deserialize)]
+pub struct UsageBucketDto {
+    /// 日期（YYYY-MM-DD）
+    pub date: String,
+    /// 小时（0-23）
+    pub hour: i32,
+    pub connections: i64,
+    pub duration_secs: i64,
+}
+
+impl UsageBucketDto {
+    pub fn is_valid(&self) -> bool {
+        chrono::NaiveDate::parse_from_str(&self.date, "%Y-%m-%d").is_ok()
+            && (0..24).contains(&self.hour)
+            && self.connections >= 0
+            && (0..=3600).contains(&self.duration_secs)
+    }
+}
+
+/// 上报本设备的使用量并获取所有设备的汇总
+///
+/// 服务器用本次上报替换该设备 `from_date` 及之后的分桶
+#[derive(Debug, Deserialize, Validate)]
+pub struct UsageHeatmapRequest {
+    #[validate(length(min = 1, max = 64))]
+    pub device_id: String,
+    /// 上报范围的起始日期（YYYY-MM-DD）
+    #[validate(length(min = 10, max = 10))]
+    pub from_date: String,
+    pub buckets: Vec<UsageBucketDto>,
+}
@@ -19,6 +19,34 @@ pub struct SyncRequest {
 
     /// 删除的会话 ID
     pub deleted_session_ids: Vec<String>,
+
+    /// 从回收站恢复的会话 ID（仅这些会话会清除软删除标记）
+    #[serde(default)]
+    pub restored_session_ids: Vec<String>,
+
+    /// 会话分组更新（旧版本客户端不发送）
+    #[serde(default)]
+    pub session_groups: Vec<SessionGroupPushItem>,
+
+    /// 删除的分组 ID
+    #[serde(default)]
+    pub deleted_group_ids: Vec<String>,
+
+    /// 会话笔记更新（只包含用户选择同步的笔记）
+    #[serde(default)]
+    pub session_notes: Vec<SessionNotePushItem>,
+
+    /// 删除或取消同步的笔记（会话 ID）
+    #[serde(default)]
+    pub deleted_note_ids: Vec<String>,
+
+    /// 会话附件更新（只包含用户选择同步的附件）
+    #[serde(default)]
+    pub session_attachments: Vec<SessionAttachmentPushItem>,
+
+    /// 删除或取消同步的附件 ID
+    #[serde(default)]
+    pub deleted_attachment_ids: Vec<String>,
 }
 
 /// SSH 会话推送项
@@ -41,6 +69,45 @@ pub struct SshSessionPushItem {
     pub updated_at: i64,
 }
 
+/// 会话分组推送项
+#[derive(Debug, Deserialize, Validate, Serialize, Clone)]
+pub struct SessionGroupPushItem {
+    pub id: String,
+    pub name: String,
+    pub color: Option<String>,
+    pub icon: Option<String>,
+    pub sort_order: i32,
+    pub description: Option<String>,
+    pub client_ver: i32,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// 会话笔记推送项
+#[derive(Debug, Deserialize, Validate, Serialize, Clone)]
+pub struct SessionNotePushItem {
+    pub session_id: String,
+    pub content: String,
+    pub client_ver: i32,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// 会话附件推送项
+#[derive(Debug, Deserialize, Validate, Serialize, Clone)]
+pub struct SessionAttachmentPushItem {
+    pub id: String,
+    pub session_id: String,
+    pub file_name: String,
+    pub mime_type: Option<String>,
+    pub size: i64,
+    /// 文件内容（Base64 编码）
+    pub content: String,
+    pub client_ver: i32,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
 /// 更新用户资料请求
 #[derive(Debug, Deserialize, Validate, Clone)]
 pub struct UpdateProfileRequest {
@@ -0,0 +1,123 @@
+use serde::Deserialize;
+use validator::Validate;
+
+/// 工作区成员角色
+///
+/// - `owner`: 创建者，拥有全部权限，不能被移除
+/// - `admin`: 管理成员和共享会话，可以查看和导出凭据
+/// - `member`: 只能连接共享会话，不能查看或导出凭据，不能删除共享会话
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkspaceRole {
+    Owner,
+    Admin,
+    Member,
+}
+
+impl WorkspaceRole {
+    pub fn parse(role: &str) -> Option<Self> {
+        match role {
+            "owner" => Some(Self::Owner),
+            "admin" => Some(Self::Admin),
+            "member" => Some(Self::Member),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Owner => "owner",
+            Self::Admin => "admin",
+            Self::Member => "member",
+        }
+    }
+
+    /// 添加和移除成员
+    pub fn can_manage_members(self) -> bool {
+        self != Self::Member
+    }
+
+    /// 共享新会话
+    pub fn can_share_sessions(self) -> bool {
+        self != Self::Member
+    }
+
+    /// 删除共享会话
+    pub fn can_delete_sessions(self) -> bool {
+        self != Self::Member
+    }
+
+    /// 查看和导出共享会话的凭据
+    pub fn can_view_credentials(self) -> bool {
+        self != Self::Member
+    }
+
+    /// 查看审计日志
+    pub fn can_view_audit_logs(self) -> bool {
+        self != Self::Member
+    }
+}
+
+/// 创建工作区请求
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateWorkspaceRequest {
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+}
+
+/// 添加成员请求
+#[derive(Debug, Deserialize, Validate)]
+pub struct AddWorkspaceMemberRequest {
+    #[validate(email)]
+    pub email: String,
+    /// admin 或 member
+    pub role: String,
+}
+
+/// 共享会话请求
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateSharedSessionRequest {
+    #[validate(length(min = 1, max = 255))]
+    pub name: String,
+
+    #[validate(length(min = 1, max = 255))]
+    pub host: String,
+
+    #[validate(range(min = 1, max = 65535))]
+    pub port: u16,
+
+    #[validate(length(min = 1, max = 255))]
+    pub username: String,
+
+    /// 认证信息 JSON（服务器加密保存）
+    #[validate(length(min = 1, max = 65536))]
+    pub credentials: String,
+}
+
+/// 审计日志查询参数
+#[derive(Debug, Deserialize)]
+pub struct ListAuditLogsQuery {
+    /// 返回的最大条数，默认 200
+    pub limit: Option<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_member_role_is_connect_only() {
+        let member = WorkspaceRole::parse("member").unwrap();
+        assert!(!member.can_view_credentials());
+        assert!(!member.can_delete_sessions());
+        assert!(!member.can_share_sessions());
+        assert!(!member.can_manage_members());
+        assert!(!member.can_view_audit_logs());
+
+        for role in [WorkspaceRole::Owner, WorkspaceRole::Admin] {
+            assert!(role.can_view_credentials());
+            assert!(role.can_delete_sessions());
+            assert_eq!(WorkspaceRole::parse(role.as_str()), Some(role));
+        }
+        assert_eq!(WorkspaceRole::parse("guest"), None);
+    }
+}
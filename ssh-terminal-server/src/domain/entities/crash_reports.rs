@@ -0,0 +1,44 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 客户端崩溃报告实体
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "crash_reports")]
+pub struct Model {
+    /// 报告 ID
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    /// 客户端生成的报告 ID（用于去重）
+    #[sea_orm(unique)]
+    pub report_id: String,
+    /// 设备 ID（客户端已登录时提供）
+    pub device_id: Option<String>,
+    /// 客户端版本
+    pub app_version: String,
+    /// 操作系统
+    pub os: String,
+    /// 操作系统版本
+    pub os_version: Option<String>,
+    /// CPU 架构
+    pub arch: String,
+    /// panic 信息
+    #[sea_orm(column_type = "Text")]
+    pub message: String,
+    /// panic 位置
+    pub location: Option<String>,
+    /// 调用栈
+    #[sea_orm(column_type = "Text")]
+    pub backtrace: String,
+    /// 崩溃前的日志
+    #[sea_orm(column_type = "Text")]
+    pub log_tail: String,
+    /// 崩溃时间（毫秒）
+    pub crashed_at: i64,
+    /// 创建时间
+    pub created_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
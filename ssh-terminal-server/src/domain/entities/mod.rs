@@ -1,5 +1,15 @@
 pub mod users;
 pub mod user_profiles;
 pub mod ssh_sessions;
+pub mod session_groups;
+pub mod session_notes;
+pub mod session_attachments;
 pub mod email_logs;
 
+pub mod crash_reports;
+pub mod recordings;
+pub mod usage_buckets;
+pub mod workspaces;
+pub mod workspace_members;
+pub mod shared_sessions;
+pub mod workspace_audit_logs;
@@ -0,0 +1,40 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 云端录制文件实体
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "recordings")]
+pub struct Model {
+    /// 录制 ID
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    /// 上传者 ID
+    pub user_id: String,
+    /// 标题
+    pub title: String,
+    /// 原始文件名
+    pub file_name: String,
+    /// 文件类型：recording（录制 JSON）、cast（asciicast）、video
+    pub kind: String,
+    /// MIME 类型
+    pub content_type: String,
+    /// 文件大小（字节）
+    pub size: i64,
+    /// 可见性：private、unlisted、shared
+    pub visibility: String,
+    /// 录制时的会话名称
+    pub session_name: Option<String>,
+    /// 客户端本地录制 ID（关联同一录制的 JSON 与视频）
+    pub recording_id: Option<String>,
+    /// 时长（秒）
+    pub duration: Option<f64>,
+    /// 创建时间
+    pub created_at: i64,
+    /// 更新时间
+    pub updated_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,38 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 会话分组实体（颜色、图标等展示元数据，会话通过 group_name 关联）
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "session_groups")]
+pub struct Model {
+    /// 分组 ID（由客户端生成）
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    /// 所属用户 ID
+    pub user_id: String,
+    /// 分组名称
+    pub name: String,
+    /// 颜色（如 #3b82f6）
+    pub color: Option<String>,
+    /// 图标名称
+    pub icon: Option<String>,
+    /// 排序序号（越小越靠前）
+    pub sort_order: i32,
+    /// 描述
+    pub description: Option<String>,
+    /// 服务器版本号
+    pub server_ver: i32,
+    /// 客户端版本号
+    pub client_ver: i32,
+    /// 创建时间
+    pub created_at: i64,
+    /// 更新时间
+    pub updated_at: i64,
+    /// 软删除时间
+    pub deleted_at: Option<i64>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
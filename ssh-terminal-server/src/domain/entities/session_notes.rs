@@ -0,0 +1,31 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 会话笔记实体（客户端选择同步的 Markdown 笔记，每个会话一条）
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "session_notes")]
+pub struct Model {
+    /// 所属会话 ID（由客户端生成）
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub session_id: String,
+    /// 所属用户 ID
+    pub user_id: String,
+    /// Markdown 内容
+    #[sea_orm(column_type = "Text")]
+    pub content: String,
+    /// 服务器版本号
+    pub server_ver: i32,
+    /// 客户端版本号
+    pub client_ver: i32,
+    /// 创建时间
+    pub created_at: i64,
+    /// 更新时间
+    pub updated_at: i64,
+    /// 软删除时间
+    pub deleted_at: Option<i64>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,35 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 工作区共享会话实体
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "shared_sessions")]
+pub struct Model {
+    /// 共享会话 ID
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    /// 工作区 ID
+    pub workspace_id: String,
+    /// 名称
+    pub name: String,
+    /// 主机
+    pub host: String,
+    /// 端口
+    pub port: u16,
+    /// 用户名
+    pub username: String,
+    /// 认证信息（服务器端加密，只在连接时返回给成员）
+    #[sea_orm(column_type = "Text")]
+    pub credentials_encrypted: String,
+    /// 共享者 ID
+    pub created_by: String,
+    /// 创建时间
+    pub created_at: i64,
+    /// 更新时间
+    pub updated_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
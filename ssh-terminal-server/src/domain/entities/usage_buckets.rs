@@ -0,0 +1,30 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 使用热力图分桶实体（每台设备每天每小时一行）
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "usage_buckets")]
+pub struct Model {
+    /// 分桶 ID
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    /// 用户 ID
+    pub user_id: String,
+    /// 上报的设备 ID
+    pub device_id: String,
+    /// 日期（客户端本地时间，YYYY-MM-DD）
+    pub date: String,
+    /// 小时（0-23）
+    pub hour: i32,
+    /// 该小时内开始的连接数
+    pub connections: i64,
+    /// 该小时内的连接时长（秒）
+    pub duration_secs: i64,
+    /// 更新时间
+    pub updated_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -17,6 +17,10 @@ pub struct Model {
     pub device_id: Option<String>,
     pub last_sync_at: Option<i64>,
 
+    /// 邮箱是否已验证（加入此字段前注册的账号视为已验证）
+    #[sea_orm(default_value = true)]
+    pub email_verified: bool,
+
     // 软删除
     pub deleted_at: Option<i64>,
 }
@@ -0,0 +1,30 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 工作区审计日志实体（记录对共享主机的连接）
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "workspace_audit_logs")]
+pub struct Model {
+    /// 日志 ID
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    /// 工作区 ID
+    pub workspace_id: String,
+    /// 共享会话 ID
+    pub shared_session_id: String,
+    /// 操作用户 ID
+    pub user_id: String,
+    /// 操作：connect
+    pub action: String,
+    /// 会话名称（会话删除后仍可追溯）
+    pub session_name: String,
+    /// 连接目标（user@host:port）
+    pub target: String,
+    /// 操作时间
+    pub created_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,24 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 工作区成员实体
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "workspace_members")]
+pub struct Model {
+    /// 成员记录 ID
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    /// 工作区 ID
+    pub workspace_id: String,
+    /// 用户 ID
+    pub user_id: String,
+    /// 角色：owner、admin、member
+    pub role: String,
+    /// 加入时间
+    pub created_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,24 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 团队工作区实体
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "workspaces")]
+pub struct Model {
+    /// 工作区 ID
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    /// 名称
+    pub name: String,
+    /// 创建者（所有者）ID
+    pub owner_id: String,
+    /// 创建时间
+    pub created_at: i64,
+    /// 更新时间
+    pub updated_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,154 @@
+use serde::{Deserialize, Serialize};
+
+/// 内置列表的日期，配置的公告文件更新时覆盖内置列表
+const BUNDLED_UPDATED: &str = "2024-07-01";
+
+/// 服务器软件
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ServerProduct {
+    Openssh,
+    Dropbear,
+}
+
+/// 漏洞严重程度
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Medium,
+    High,
+    Critical,
+}
+
+/// 一条安全公告
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Advisory {
+    /// CVE 编号
+    pub id: String,
+    pub product: ServerProduct,
+    /// 受影响的最低版本（含），为空表示更早的版本均受影响
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub introduced: Option<String>,
+    /// 修复版本（不含）
+    pub fixed: String,
+    pub severity: Severity,
+    pub summary: String,
+}
+
+/// 安全公告列表（格式与客户端保存的 `ssh_advisories.json` 相同）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdvisoryList {
+    /// 列表日期（YYYY-MM-DD），客户端只接受比本地列表新的列表
+    pub updated: String,
+    pub advisories: Vec<Advisory>,
+}
+
+impl AdvisoryList {
+    /// 内置列表（与客户端内置列表一致，只收录服务器端的高危漏洞）
+    pub fn bundled() -> Self {
+        use ServerProduct::{Dropbear, Openssh};
+        use Severity::{Critical, High};
+
+        let advisory = |id: &str, product, introduced: Option<&str>, fixed: &str, severity, summary: &str| Advisory {
+            id: id.to_string(),
+            product,
+            introduced: introduced.map(str::to_string),
+            fixed: fixed.to_string(),
+            severity,
+            summary: summary.to_string(),
+        };
+
+        Self {
+            updated: BUNDLED_UPDATED.to_string(),
+            advisories: vec![
+                advisory("CVE-2024-6387", Openssh, Some("8.5"), "9.8", Critical,
+                    "regreSSHion: signal handler race in sshd allows unauthenticated remote code execution as root"),
+                advisory("CVE-2024-6387", Openssh, None, "4.4", Critical,
+                    "regreSSHion: signal handler race in sshd allows unauthenticated remote code execution as root"),
+                advisory("CVE-2021-41617", Openssh, Some("6.2"), "8.8", High,
+                    "sshd runs AuthorizedKeysCommand/AuthorizedPrincipalsCommand with incorrect supplementary groups"),
+                advisory("CVE-2016-10012", Openssh, None, "7.4", High,
+                    "Privilege separation shared memory bounds checks can be bypassed for local privilege escalation"),
+                advisory("CVE-2015-5600", Openssh, None, "7.0", High,
+                    "Keyboard-interactive authentication allows unlimited password attempts within one connection"),
+                advisory("CVE-2016-7406", Dropbear, None, "2016.74", Critical,
+                    "Format string flaw in dropbear allows remote code execution"),
+                advisory("CVE-2017-9078", Dropbear, None, "2017.75", High,
+                    "Double free in server TCP listener cleanup allows authenticated remote code execution"),
+            ],
+        }
+    }
+
+    /// 当前提供的列表：配置的文件可读、格式正确且比内置列表新时使用文件中的列表
+    pub fn load(file: Option<&str>) -> Self {
+        let bundled = Self::bundled();
+        let Some(file) = file.filter(|f| !f.is_empty()) else {
+            return bundled;
+        };
+
+        let parsed = std::fs::read_to_string(file)
+            .map_err(|e| e.to_string())
+            .and_then(|text| serde_json::from_str::<Self>(&text).map_err(|e| e.to_string()));
+        match parsed {
+            Ok(list) if list.updated > bundled.updated => list,
+            Ok(list) => {
+                tracing::debug!("Advisory file {} ({}) is not newer than bundled list", file, list.updated);
+                bundled
+            }
+            Err(e) => {
+                tracing::warn!("Failed to load advisory file {}: {}, using bundled list", file, e);
+                bundled
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundled_list_format() {
+        let json = serde_json::to_value(AdvisoryList::bundled()).unwrap();
+        assert_eq!(json["updated"], BUNDLED_UPDATED);
+        let first = &json["advisories"][0];
+        assert_eq!(first["id"], "CVE-2024-6387");
+        assert_eq!(first["product"], "openssh");
+        assert_eq!(first["introduced"], "8.5");
+        assert_eq!(first["severity"], "critical");
+        // 未设置 introduced 时不输出该字段
+        assert!(json["advisories"][1].get("introduced").is_none());
+    }
+
+    #[test]
+    fn test_load_override_file() {
+        let dir = std::env::temp_dir().join(format!("advisories-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("ssh_advisories.json");
+        let path_str = path.to_str().unwrap();
+
+        assert_eq!(AdvisoryList::load(None).updated, BUNDLED_UPDATED);
+        // 文件不存在或格式错误时使用内置列表
+        assert_eq!(AdvisoryList::load(Some(path_str)).updated, BUNDLED_UPDATED);
+        std::fs::write(&path, "not json").unwrap();
+        assert_eq!(AdvisoryList::load(Some(path_str)).updated, BUNDLED_UPDATED);
+
+        // 比内置列表旧的文件被忽略
+        std::fs::write(&path, r#"{"updated":"2020-01-01","advisories":[]}"#).unwrap();
+        assert_eq!(AdvisoryList::load(Some(path_str)).advisories.len(), AdvisoryList::bundled().advisories.len());
+
+        std::fs::write(
+            &path,
+            r#"{"updated":"2025-03-01","advisories":[{"id":"CVE-2025-26465","product":"openssh","introduced":"6.8","fixed":"9.9p2","severity":"medium","summary":"VerifyHostKeyDNS bypass"}]}"#,
+        )
+        .unwrap();
+        let list = AdvisoryList::load(Some(path_str));
+        assert_eq!(list.updated, "2025-03-01");
+        assert_eq!(list.advisories[0].severity, Severity::Medium);
+        assert_eq!(list.advisories[0].fixed, "9.9p2");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+/// AI 代理配额
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AiQuotaResult {
+    /// 服务器是否提供 AI 代理
+    pub enabled: bool,
+    pub model: String,
+    /// 每日请求次数上限，0 表示不限制
+    pub daily_quota: u64,
+    /// 今日已用次数
+    pub used: u64,
+    /// 剩余次数，不限制时为空
+    pub remaining: Option<u64>,
+}
+
+/// AI 代理聊天结果
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AiChatResult {
+    pub content: String,
+    pub model: String,
+    /// 剩余次数，不限制时为空
+    pub remaining: Option<u64>,
+}
@@ -9,6 +9,7 @@ pub struct RegisterResult {
     pub device_id: String,
     pub access_token: String,
     pub refresh_token: String,
+    pub email_verified: bool,
 }
 
 impl From<(crate::domain::entities::users::Model, String, String)> for RegisterResult {
@@ -26,6 +27,7 @@ impl From<(crate::domain::entities::users::Model, String, String)> for RegisterR
             device_id: user_model.device_id.unwrap_or_default(),
             access_token,
             refresh_token,
+            email_verified: user_model.email_verified,
         }
     }
 }
@@ -36,6 +38,8 @@ pub struct LoginResult {
     pub device_id: String,
     pub access_token: String,
     pub refresh_token: String,
+    /// 邮箱未验证时客户端应提示验证（宽限期过后将无法登录）
+    pub email_verified: bool,
 }
 
 impl From<(crate::domain::entities::users::Model, String, String)> for LoginResult {
@@ -50,6 +54,7 @@ impl From<(crate::domain::entities::users::Model, String, String)> for LoginResu
             device_id: user_model.device_id.unwrap_or_default(),
             access_token,
             refresh_token,
+            email_verified: user_model.email_verified,
         }
     }
 }
@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+
+/// 提交崩溃报告结果
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CrashReportResult {
+    /// 服务器端报告 ID
+    pub id: String,
+}
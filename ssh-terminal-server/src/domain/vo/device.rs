@@ -0,0 +1,26 @@
+use serde::Serialize;
+
+/// 在线设备
+#[derive(Debug, Clone, Serialize)]
+pub struct OnlineDevice {
+    pub client_id: String,
+    pub name: String,
+    pub platform: String,
+    /// 上线时间（秒）
+    pub connected_at: i64,
+}
+
+/// 服务器通过 WebSocket 推送的消息
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DeviceServerMessage {
+    /// 当前用户的在线设备（设备上线、下线时推送）
+    Devices { devices: Vec<OnlineDevice> },
+    /// 其他设备发来的打开会话请求
+    Handoff {
+        from_client_id: String,
+        payload: String,
+        signature: String,
+    },
+    Error { message: String },
+}
@@ -5,6 +5,13 @@ pub mod sync;
 pub mod health;
 pub mod mail;
 pub mod last_update;
+pub mod crash;
+pub mod ai;
+pub mod recording;
+pub mod stats;
+pub mod device;
+pub mod advisory;
+pub mod workspace;
 
 /// 统一的 API 响应结构
 use serde::Serialize;
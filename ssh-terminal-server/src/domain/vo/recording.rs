@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+/// 云端录制文件
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RecordingVO {
+    pub id: String,
+    pub title: String,
+    pub file_name: String,
+    pub kind: String,
+    pub content_type: String,
+    pub size: i64,
+    pub visibility: String,
+    pub session_name: Option<String>,
+    pub recording_id: Option<String>,
+    pub duration: Option<f64>,
+    /// 是否为当前用户上传
+    pub is_owner: bool,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
@@ -23,6 +23,53 @@ pub struct SshSessionVO {
     pub deleted_at: Option<i64>,
 }
 
+/// 会话分组
+#[derive(Debug, Serialize, Clone)]
+pub struct SessionGroupVO {
+    pub id: String,
+    pub user_id: String,
+    pub name: String,
+    pub color: Option<String>,
+    pub icon: Option<String>,
+    pub sort_order: i32,
+    pub description: Option<String>,
+    pub server_ver: i32,
+    pub client_ver: i32,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub deleted_at: Option<i64>,
+}
+
+/// 会话笔记
+#[derive(Debug, Serialize, Clone)]
+pub struct SessionNoteVO {
+    pub session_id: String,
+    pub user_id: String,
+    pub content: String,
+    pub server_ver: i32,
+    pub client_ver: i32,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub deleted_at: Option<i64>,
+}
+
+/// 会话附件（content 为 Base64 编码的文件内容）
+#[derive(Debug, Serialize, Clone)]
+pub struct SessionAttachmentVO {
+    pub id: String,
+    pub session_id: String,
+    pub user_id: String,
+    pub file_name: String,
+    pub mime_type: Option<String>,
+    pub size: i64,
+    pub content: String,
+    pub server_ver: i32,
+    pub client_ver: i32,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub deleted_at: Option<i64>,
+}
+
 /// 分页响应
 #[derive(Debug, Serialize)]
 pub struct PaginatedSshSessions {
@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+/// 所有设备汇总后的一个小时的使用量
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UsageBucketVO {
+    pub date: String,
+    pub hour: i32,
+    pub connections: i64,
+    pub duration_secs: i64,
+}
+
+/// 跨设备汇总的使用热力图
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UsageHeatmapResult {
+    /// 范围内有使用记录的设备数
+    pub devices: u64,
+    pub buckets: Vec<UsageBucketVO>,
+}
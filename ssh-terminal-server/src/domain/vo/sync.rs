@@ -26,6 +26,24 @@ pub struct SyncResponse {
     /// SSH 会话列表（从服务器拉取的新数据）
     pub ssh_sessions: Vec<super::ssh::SshSessionVO>,
 
+    /// 会话分组列表（包含已删除的分组，deleted_at 不为空）
+    pub session_groups: Vec<super::ssh::SessionGroupVO>,
+
+    /// 分组服务器版本号映射（id -> server_ver）
+    pub group_versions: std::collections::HashMap<String, i32>,
+
+    /// 会话笔记列表（包含已删除的笔记，deleted_at 不为空）
+    pub session_notes: Vec<super::ssh::SessionNoteVO>,
+
+    /// 笔记服务器版本号映射（session_id -> server_ver）
+    pub note_versions: std::collections::HashMap<String, i32>,
+
+    /// 会话附件列表（包含已删除的附件，deleted_at 不为空）
+    pub session_attachments: Vec<super::ssh::SessionAttachmentVO>,
+
+    /// 附件服务器版本号映射（id -> server_ver）
+    pub attachment_versions: std::collections::HashMap<String, i32>,
+
     /// === 冲突信息 ===
     /// 需要解决的冲突
     pub conflicts: Vec<ConflictInfo>,
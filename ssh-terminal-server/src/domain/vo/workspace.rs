@@ -0,0 +1,56 @@
+use serde::Serialize;
+
+/// 工作区（包含当前用户的角色）
+#[derive(Debug, Serialize)]
+pub struct WorkspaceVO {
+    pub id: String,
+    pub name: String,
+    pub owner_id: String,
+    /// 当前用户的角色：owner、admin、member
+    pub role: String,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// 工作区成员
+#[derive(Debug, Serialize)]
+pub struct WorkspaceMemberVO {
+    pub user_id: String,
+    pub email: Option<String>,
+    pub role: String,
+    pub created_at: i64,
+}
+
+/// 共享会话（不包含凭据）
+#[derive(Debug, Serialize)]
+pub struct SharedSessionVO {
+    pub id: String,
+    pub workspace_id: String,
+    pub name: String,
+    pub host: String,
+    pub port: u16,
+    pub username: String,
+    pub created_by: String,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// 共享会话的凭据（连接时或管理员导出时返回）
+#[derive(Debug, Serialize)]
+pub struct SharedSessionCredentialsVO {
+    pub session: SharedSessionVO,
+    /// 认证信息 JSON
+    pub credentials: String,
+}
+
+/// 审计日志
+#[derive(Debug, Serialize)]
+pub struct WorkspaceAuditLogVO {
+    pub id: String,
+    pub shared_session_id: String,
+    pub user_id: String,
+    pub action: String,
+    pub session_name: String,
+    pub target: String,
+    pub created_at: i64,
+}
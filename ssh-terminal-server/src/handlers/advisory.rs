@@ -0,0 +1,18 @@
+use crate::AppState;
+use crate::domain::vo::{ApiResponse, advisory::AdvisoryList};
+use crate::infra::middleware::Language;
+use crate::utils::i18n::{t, MessageKey};
+use axum::{extract::State, Json};
+
+/// 获取 SSH 服务器安全公告列表
+///
+/// 客户端连接时用它比对服务器版本，列表比客户端本地的新时才会被采用
+pub async fn get_advisories_handler(
+    State(state): State<AppState>,
+    Language(language): Language,
+) -> Json<ApiResponse<AdvisoryList>> {
+    let list = AdvisoryList::load(state.config.advisories.file.as_deref());
+    tracing::debug!("安全公告列表: updated={}, advisories={}", list.updated, list.advisories.len());
+    let message = t(Some(language.as_str()), MessageKey::SuccessSshAdvisories);
+    Json(ApiResponse::success_with_message(list, &message))
+}
@@ -0,0 +1,99 @@
+use crate::AppState;
+use crate::domain::dto::ai::{AiChatRequest, MAX_CHAT_CONTENT_LEN};
+use crate::domain::vo::{ApiResponse, ai::{AiChatResult, AiQuotaResult}};
+use crate::infra::ai_proxy::{AiProxy, QuotaCheck};
+use crate::infra::middleware::{UserId, Language};
+use crate::utils::i18n::{t, MessageKey};
+use axum::{extract::State, http::StatusCode, Json};
+use validator::Validate;
+
+/// 获取当前用户的 AI 代理配额
+pub async fn get_quota_handler(
+    State(state): State<AppState>,
+    UserId(user_id): UserId,
+    Language(language): Language,
+) -> Result<Json<ApiResponse<AiQuotaResult>>, StatusCode> {
+    let proxy = &state.ai_proxy;
+    let used = if proxy.is_enabled() {
+        proxy.used_today(&state.redis_client, &user_id).await.map_err(|e| {
+            tracing::error!("Failed to read AI quota: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+    } else {
+        0
+    };
+
+    let config = proxy.config();
+    // 超出配额后的请求也会计数，展示时不超过上限
+    let used = if config.daily_quota > 0 { used.min(config.daily_quota) } else { used };
+    let result = AiQuotaResult {
+        enabled: proxy.is_enabled(),
+        model: config.model.clone(),
+        daily_quota: config.daily_quota,
+        used,
+        remaining: proxy.remaining(used),
+    };
+    let message = t(Some(language.as_str()), MessageKey::SuccessAiQuota);
+    Ok(Json(ApiResponse::success_with_message(result, &message)))
+}
+
+/// 使用服务器凭据转发 AI 聊天请求
+///
+/// 未启用代理时返回 503，超出每日配额时返回 429，上游失败时返回 502 并退还本次计入的配额
+pub async fn chat_handler(
+    State(state): State<AppState>,
+    UserId(user_id): UserId,
+    Language(language): Language,
+    Json(request): Json<AiChatRequest>,
+) -> Result<Json<ApiResponse<AiChatResult>>, StatusCode> {
+    let proxy = &state.ai_proxy;
+    if !proxy.is_enabled() {
+        return Err(StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    let content_len: usize = request.messages.iter().map(|m| m.content.len()).sum();
+    if request.validate().is_err()
+        || content_len > MAX_CHAT_CONTENT_LEN
+        || !request.messages.iter().all(|m| m.is_valid())
+    {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    // 计入和退还使用同一个 Key，请求跨越零点时也退还到同一天的计数
+    let quota_key = AiProxy::daily_key(&user_id);
+    let used = match proxy.consume_quota(&state.redis_client, &quota_key).await {
+        Ok(QuotaCheck::Allowed(used)) => used,
+        Ok(QuotaCheck::Exceeded) => {
+            tracing::info!("AI 配额已用完: user_id={}", user_id);
+            return Err(StatusCode::TOO_MANY_REQUESTS);
+        }
+        Err(e) => {
+            tracing::error!("Failed to update AI quota: {}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    tracing::info!(
+        "AI 代理请求: user_id={}, messages={}, used={}",
+        user_id, request.messages.len(), used
+    );
+
+    match proxy.chat(&request.messages, request.temperature, request.max_tokens).await {
+        Ok(content) => {
+            let result = AiChatResult {
+                content,
+                model: proxy.config().model.clone(),
+                remaining: proxy.remaining(used),
+            };
+            let message = t(Some(language.as_str()), MessageKey::SuccessAiChat);
+            Ok(Json(ApiResponse::success_with_message(result, &message)))
+        }
+        Err(e) => {
+            tracing::error!("AI 代理请求失败: {}", e);
+            if let Err(e) = proxy.refund_quota(&state.redis_client, &quota_key).await {
+                tracing::warn!("Failed to refund AI quota: {}", e);
+            }
+            Err(StatusCode::BAD_GATEWAY)
+        }
+    }
+}
@@ -2,12 +2,16 @@ use crate::error::ErrorResponse;
 use crate::infra::middleware::logging::{log_info, RequestId};
 use crate::infra::middleware::Language;
 use crate::infra::middleware::UserId;
-use crate::domain::dto::auth::{RegisterRequest, LoginRequest, RefreshRequest, DeleteUserRequest};
+use crate::domain::dto::auth::{RegisterRequest, LoginRequest, RefreshRequest, DeleteUserRequest, VerifyEmailRequest};
+use crate::domain::dto::mail::SendVerifyCodeRequest;
+use crate::domain::vo::mail::EmailResult;
 use crate::domain::vo::auth::{RegisterResult, LoginResult, RefreshResult};
 use crate::domain::vo::ApiResponse;
 use crate::repositories::user_repository::UserRepository;
+use crate::repositories::email_log_repository::EmailLogRepository;
 use crate::repositories::user_profile_repository::UserProfileRepository;
 use crate::services::auth_service::AuthService;
+use crate::services::mail_service::MailService;
 use crate::utils::i18n::{t, MessageKey};
 use crate::AppState;
 use axum::{
@@ -133,6 +137,92 @@ pub async fn refresh(
     }
 }
 
+/// 验证邮箱（公开 API：宽限期过后未验证的账号无法登录，需要先在这里完成验证）
+pub async fn verify_email(
+    Extension(request_id): Extension<RequestId>,
+    Language(language): Language,
+    State(state): State<AppState>,
+    Json(payload): Json<VerifyEmailRequest>,
+) -> Result<Json<ApiResponse<()>>, ErrorResponse> {
+    log_info(&request_id, "验证邮箱请求", &payload);
+
+    let user_repo = UserRepository::new(state.pool.clone());
+    let user_profile_repo = UserProfileRepository::new(state.pool.clone());
+    let service = AuthService::new(
+        user_repo,
+        user_profile_repo,
+        state.redis_client.clone(),
+        state.config.auth.clone(),
+        state.config.email.clone(),
+    );
+
+    match service.verify_email(payload, Some(language.as_str())).await {
+        Ok(_) => {
+            let message = t(Some(language.as_str()), MessageKey::SuccessEmailVerified);
+            log_info(&request_id, "邮箱验证成功", &message);
+            Ok(Json(ApiResponse::success_with_message((), &message)))
+        }
+        Err(e) => {
+            log_info(&request_id, "邮箱验证失败", e.to_string());
+            Err(ErrorResponse::new(e.to_string()))
+        }
+    }
+}
+
+/// 为已注册但未验证邮箱的账号重新发送验证码
+///
+/// 与注册前发送验证码共用按邮箱计算的限频（60 秒一次、每日 10 次）
+pub async fn resend_verify_code(
+    Extension(request_id): Extension<RequestId>,
+    Language(language): Language,
+    State(state): State<AppState>,
+    Json(payload): Json<SendVerifyCodeRequest>,
+) -> Result<Json<ApiResponse<EmailResult>>, ErrorResponse> {
+    log_info(&request_id, "重新发送验证码请求", &payload);
+
+    let user_repo = UserRepository::new(state.pool.clone());
+    let user_profile_repo = UserProfileRepository::new(state.pool.clone());
+    let service = AuthService::new(
+        user_repo,
+        user_profile_repo,
+        state.redis_client.clone(),
+        state.config.auth.clone(),
+        state.config.email.clone(),
+    );
+    if let Err(e) = service
+        .check_resend_allowed(&payload.email, Some(language.as_str()))
+        .await
+    {
+        log_info(&request_id, "重新发送验证码失败", e.to_string());
+        return Err(ErrorResponse::new(e.to_string()));
+    }
+
+    let mail_service = MailService::new(
+        state.redis_client.clone(),
+        EmailLogRepository::new(state.pool.clone()),
+        state.config.email.clone(),
+    );
+    match mail_service
+        .send_verify_code_sync(&payload.email, &payload.email, &language)
+        .await
+    {
+        Ok(_) => {
+            let message = crate::utils::i18n::t_with_vars(
+                Some(language.as_str()),
+                MessageKey::SuccessEmailQueued,
+                &[("email", &payload.email)],
+            );
+            log_info(&request_id, "验证码已重新发送", &payload.email);
+            Ok(Json(ApiResponse::success_with_message(EmailResult::success(), &message)))
+        }
+        Err(e) => {
+            // 限频和每日上限的提示来自 MailService，直接返回
+            log_info(&request_id, "重新发送验证码失败", e.to_string());
+            Err(ErrorResponse::new(e.to_string()))
+        }
+    }
+}
+
 /// 删除账号
 pub async fn delete_account(
     Extension(request_id): Extension<RequestId>,
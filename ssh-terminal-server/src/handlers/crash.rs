@@ -0,0 +1,52 @@
+use crate::AppState;
+use crate::infra::crash_rate_limit::CrashRateLimit;
+use crate::domain::dto::crash::CrashReportRequest;
+use crate::domain::vo::{ApiResponse, crash::CrashReportResult};
+use crate::infra::middleware::Language;
+use crate::repositories::crash_report_repository::CrashReportRepository;
+use crate::utils::i18n::{t, MessageKey};
+use axum::{extract::{ConnectInfo, State}, Json};
+use std::net::SocketAddr;
+use validator::Validate;
+
+/// 提交客户端崩溃报告（公开 API，无需认证）
+///
+/// 按来源 IP 和设备 ID 限频，超过限制时返回 429
+pub async fn submit_crash_report(
+    State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Language(language): Language,
+    Json(request): Json<CrashReportRequest>,
+) -> Result<Json<ApiResponse<CrashReportResult>>, axum::http::StatusCode> {
+    if request.validate().is_err() {
+        return Err(axum::http::StatusCode::BAD_REQUEST);
+    }
+
+    let rate_limit = CrashRateLimit::new(state.redis_client.clone());
+    match rate_limit.check(&addr.ip().to_string(), request.device_id.as_deref()).await {
+        Ok(true) => {}
+        Ok(false) => {
+            tracing::warn!("崩溃报告提交过于频繁: ip={}, device_id={:?}", addr.ip(), request.device_id);
+            return Err(axum::http::StatusCode::TOO_MANY_REQUESTS);
+        }
+        // Redis 不可用时不阻止上报
+        Err(e) => tracing::warn!("崩溃报告限频检查失败: {}", e),
+    }
+
+    tracing::info!(
+        "收到崩溃报告: report_id={}, version={}, os={}",
+        request.report_id, request.app_version, request.os
+    );
+
+    let repo = CrashReportRepository::new(state.pool.clone());
+    match repo.create(request).await {
+        Ok(report) => {
+            let message = t(Some(language.as_str()), MessageKey::SuccessCrashReport);
+            Ok(Json(ApiResponse::success_with_message(CrashReportResult { id: report.id }, &message)))
+        }
+        Err(e) => {
+            tracing::error!("保存崩溃报告失败: {}", e);
+            Err(axum::http::StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Query, State};
+use axum::response::Response;
+use validator::Validate;
+use crate::domain::dto::device::{DeviceClientMessage, DeviceWsQuery};
+use crate::domain::vo::device::{DeviceServerMessage, OnlineDevice};
+use crate::infra::device_hub::DeviceHub;
+use crate::infra::middleware::{UserId, Language};
+use crate::utils::i18n::{t, MessageKey};
+use crate::AppState;
+
+/// 设备 WebSocket - 在线设备列表和设备间的会话接力
+pub async fn device_ws_handler(
+    State(state): State<AppState>,
+    UserId(user_id): UserId,
+    Language(language): Language,
+    Query(query): Query<DeviceWsQuery>,
+    ws: WebSocketUpgrade,
+) -> Result<Response, axum::http::StatusCode> {
+    if query.validate().is_err() {
+        return Err(axum::http::StatusCode::BAD_REQUEST);
+    }
+
+    let device = OnlineDevice {
+        client_id: query.client_id,
+        name: query.name.unwrap_or_default(),
+        platform: query.platform.unwrap_or_default(),
+        connected_at: chrono::Utc::now().timestamp(),
+    };
+
+    let hub = state.device_hub.clone();
+    Ok(ws.on_upgrade(move |socket| handle_device_socket(socket, hub, user_id, device, language)))
+}
+
+async fn handle_device_socket(
+    mut socket: WebSocket,
+    hub: DeviceHub,
+    user_id: String,
+    device: OnlineDevice,
+    language: String,
+) {
+    let client_id = device.client_id.clone();
+    tracing::info!("Device {} of user {} connected", client_id, user_id);
+    let (tx, mut rx) = hub.register(&user_id, device).await;
+
+    loop {
+        tokio::select! {
+            outgoing = rx.recv() => {
+                let Some(message) = outgoing else { break };
+                let Ok(text) = serde_json::to_string(&message) else { continue };
+                if socket.send(Message::Text(text)).await.is_err() {
+                    break;
+                }
+            }
+            incoming = socket.recv() => {
+                match incoming {
+                    Some(Ok(Message::Text(text))) => {
+                        let reply = handle_client_message(&hub, &user_id, &client_id, &text, &language).await;
+                        if let Some(reply) = reply {
+                            let _ = tx.send(reply);
+                        }
+                    }
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                    Some(Ok(_)) => {}
+                }
+            }
+        }
+    }
+
+    hub.unregister(&user_id, &client_id, &tx).await;
+    tracing::info!("Device {} of user {} disconnected", client_id, user_id);
+}
+
+/// 处理客户端消息，返回需要回复给该客户端的消息
+async fn handle_client_message(
+    hub: &DeviceHub,
+    user_id: &str,
+    client_id: &str,
+    text: &str,
+    language: &str,
+) -> Option<DeviceServerMessage> {
+    let message = match serde_json::from_str::<DeviceClientMessage>(text) {
+        Ok(message) => message,
+        Err(e) => {
+            tracing::warn!("Invalid device message from {}: {}", client_id, e);
+            return Some(DeviceServerMessage::Error { message: e.to_string() });
+        }
+    };
+
+    match message {
+        DeviceClientMessage::Handoff { target_client_id, payload, signature } => {
+            let forwarded = DeviceServerMessage::Handoff {
+                from_client_id: client_id.to_string(),
+                payload,
+                signature,
+            };
+            if target_client_id != client_id && hub.send_to(user_id, &target_client_id, forwarded).await {
+                tracing::info!("Handoff from {} to {} forwarded", client_id, target_client_id);
+                None
+            } else {
+                Some(DeviceServerMessage::Error {
+                    message: t(Some(language), MessageKey::ErrorDeviceOffline),
+                })
+            }
+        }
+    }
+}
@@ -5,3 +5,10 @@ pub mod ssh_session;
 pub mod user_profile;
 pub mod email;
 pub mod last_update;
+pub mod crash;
+pub mod device;
+pub mod ai;
+pub mod recording;
+pub mod stats;
+pub mod advisory;
+pub mod workspace;
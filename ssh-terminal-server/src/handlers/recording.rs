@@ -0,0 +1,229 @@
+use crate::AppState;
+use crate::domain::dto::recording::{
+    content_type_for, ListRecordingsQuery, UpdateRecordingVisibilityRequest, UploadRecordingQuery, VISIBILITIES,
+};
+use crate::domain::entities::recordings;
+use crate::domain::vo::{ApiResponse, recording::RecordingVO};
+use crate::infra::middleware::{UserId, Language};
+use crate::repositories::recording_repository::RecordingRepository;
+use crate::utils::i18n::{t, MessageKey};
+use axum::{
+    body::Bytes,
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use validator::Validate;
+
+fn recording_to_vo(recording: recordings::Model, user_id: &str) -> RecordingVO {
+    RecordingVO {
+        is_owner: recording.user_id == user_id,
+        id: recording.id,
+        title: recording.title,
+        file_name: recording.file_name,
+        kind: recording.kind,
+        content_type: recording.content_type,
+        size: recording.size,
+        visibility: recording.visibility,
+        session_name: recording.session_name,
+        recording_id: recording.recording_id,
+        duration: recording.duration,
+        created_at: recording.created_at,
+        updated_at: recording.updated_at,
+    }
+}
+
+/// 查找当前用户上传的录制，不存在或不属于当前用户时返回 404
+async fn find_owned(repo: &RecordingRepository, id: &str, user_id: &str) -> Result<recordings::Model, StatusCode> {
+    match repo.find_by_id(id).await {
+        Ok(Some(recording)) if recording.user_id == user_id => Ok(recording),
+        Ok(_) => Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            tracing::error!("Failed to query recording: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// 上传录制文件（请求体为文件内容，元数据在查询参数中）
+///
+/// 未启用时返回 503，超出单个文件或用户存储上限时返回 413
+pub async fn upload_recording_handler(
+    State(state): State<AppState>,
+    UserId(user_id): UserId,
+    Language(language): Language,
+    Query(query): Query<UploadRecordingQuery>,
+    body: Bytes,
+) -> Result<Json<ApiResponse<RecordingVO>>, StatusCode> {
+    let store = &state.recording_store;
+    if !store.is_enabled() {
+        return Err(StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    let visibility = query.visibility.as_deref().unwrap_or("private");
+    let Some(default_content_type) = content_type_for(&query.kind) else {
+        return Err(StatusCode::BAD_REQUEST);
+    };
+    if query.validate().is_err() || !VISIBILITIES.contains(&visibility) || body.is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    if body.len() > store.config().max_file_size() {
+        return Err(StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    let repo = RecordingRepository::new(state.pool.clone());
+    if let Some(quota) = store.config().user_quota() {
+        let used = repo.total_size_by_user(&user_id).await.map_err(|e| {
+            tracing::error!("Failed to query recording usage: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+        if used + body.len() as i64 > quota {
+            tracing::info!("录制存储空间不足: user_id={}, used={}", user_id, used);
+            return Err(StatusCode::PAYLOAD_TOO_LARGE);
+        }
+    }
+
+    // 视频可以是 webm 或 mp4，其余类型固定
+    let content_type = match (query.kind.as_str(), query.content_type) {
+        ("video", Some(content_type)) if content_type.starts_with("video/") => content_type,
+        _ => default_content_type.to_string(),
+    };
+
+    let id = uuid::Uuid::new_v4().to_string();
+    if let Err(e) = store.save(&user_id, &id, &body).await {
+        tracing::error!("Failed to save recording file: {}", e);
+        return Err(StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    let now = chrono::Utc::now().timestamp();
+    let recording = recordings::Model {
+        id: id.clone(),
+        user_id: user_id.clone(),
+        title: query.title,
+        file_name: query.file_name,
+        kind: query.kind,
+        content_type,
+        size: body.len() as i64,
+        visibility: visibility.to_string(),
+        session_name: query.session_name,
+        recording_id: query.recording_id,
+        duration: query.duration,
+        created_at: now,
+        updated_at: now,
+    };
+
+    match repo.create(recording).await {
+        Ok(created) => {
+            tracing::info!("录制文件已上传: id={}, user_id={}, size={}", created.id, user_id, created.size);
+            let message = t(Some(language.as_str()), MessageKey::SuccessUploadRecording);
+            Ok(Json(ApiResponse::success_with_message(recording_to_vo(created, &user_id), &message)))
+        }
+        Err(e) => {
+            tracing::error!("Failed to save recording metadata: {}", e);
+            let _ = store.delete(&user_id, &id).await;
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// 列出录制文件：`scope=mine`（默认）为自己上传的，`scope=shared` 为共享给所有用户的
+pub async fn list_recordings_handler(
+    State(state): State<AppState>,
+    UserId(user_id): UserId,
+    Language(language): Language,
+    Query(query): Query<ListRecordingsQuery>,
+) -> Result<Json<ApiResponse<Vec<RecordingVO>>>, StatusCode> {
+    let repo = RecordingRepository::new(state.pool.clone());
+    let result = match query.scope.as_deref().unwrap_or("mine") {
+        "mine" => repo.find_by_user_id(&user_id).await,
+        "shared" => repo.find_shared().await,
+        _ => return Err(StatusCode::BAD_REQUEST),
+    };
+
+    match result {
+        Ok(recordings) => {
+            let vos = recordings.into_iter().map(|r| recording_to_vo(r, &user_id)).collect();
+            let message = t(Some(language.as_str()), MessageKey::SuccessListRecordings);
+            Ok(Json(ApiResponse::success_with_message(vos, &message)))
+        }
+        Err(e) => {
+            tracing::error!("Failed to list recordings: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// 下载录制文件内容
+///
+/// 上传者总是可以下载，其他用户只能下载非 private 的录制
+pub async fn download_recording_handler(
+    State(state): State<AppState>,
+    UserId(user_id): UserId,
+    Path(id): Path<String>,
+) -> Result<Response, StatusCode> {
+    let repo = RecordingRepository::new(state.pool.clone());
+    let recording = match repo.find_by_id(&id).await {
+        Ok(Some(recording)) if recording.user_id == user_id || recording.visibility != "private" => recording,
+        Ok(_) => return Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            tracing::error!("Failed to query recording: {}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    let data = state.recording_store.read(&recording.user_id, &recording.id).await.map_err(|e| {
+        tracing::error!("Failed to read recording file {}: {}", recording.id, e);
+        StatusCode::NOT_FOUND
+    })?;
+
+    Ok(([(header::CONTENT_TYPE, recording.content_type)], data).into_response())
+}
+
+/// 更新录制文件的可见性（仅上传者）
+pub async fn update_recording_visibility_handler(
+    State(state): State<AppState>,
+    UserId(user_id): UserId,
+    Language(language): Language,
+    Path(id): Path<String>,
+    Json(request): Json<UpdateRecordingVisibilityRequest>,
+) -> Result<Json<ApiResponse<RecordingVO>>, StatusCode> {
+    if !VISIBILITIES.contains(&request.visibility.as_str()) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let repo = RecordingRepository::new(state.pool.clone());
+    let existing = find_owned(&repo, &id, &user_id).await?;
+    match repo.update_visibility(existing, &request.visibility).await {
+        Ok(updated) => {
+            let message = t(Some(language.as_str()), MessageKey::SuccessUpdateRecording);
+            Ok(Json(ApiResponse::success_with_message(recording_to_vo(updated, &user_id), &message)))
+        }
+        Err(e) => {
+            tracing::error!("Failed to update recording visibility: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// 删除录制文件（仅上传者），返回被删除的录制 ID
+pub async fn delete_recording_handler(
+    State(state): State<AppState>,
+    UserId(user_id): UserId,
+    Language(language): Language,
+    Path(id): Path<String>,
+) -> Result<Json<ApiResponse<String>>, StatusCode> {
+    let repo = RecordingRepository::new(state.pool.clone());
+    let existing = find_owned(&repo, &id, &user_id).await?;
+
+    if let Err(e) = repo.delete(&existing.id).await {
+        tracing::error!("Failed to delete recording: {}", e);
+        return Err(StatusCode::INTERNAL_SERVER_ERROR);
+    }
+    if let Err(e) = state.recording_store.delete(&user_id, &existing.id).await {
+        tracing::warn!("Failed to delete recording file {}: {}", existing.id, e);
+    }
+
+    let message = t(Some(language.as_str()), MessageKey::SuccessDeleteRecording);
+    Ok(Json(ApiResponse::success_with_message(existing.id, &message)))
+}
@@ -0,0 +1,79 @@
+use crate::AppState;
+use crate::domain::dto::stats::{UsageHeatmapRequest, MAX_USAGE_BUCKETS};
+use crate::domain::entities::usage_buckets;
+use crate::domain::vo::{ApiResponse, stats::{UsageBucketVO, UsageHeatmapResult}};
+use crate::infra::middleware::{UserId, Language};
+use crate::repositories::usage_bucket_repository::UsageBucketRepository;
+use crate::utils::i18n::{t, MessageKey};
+use axum::{extract::State, http::StatusCode, Json};
+use std::collections::{BTreeMap, BTreeSet};
+use validator::Validate;
+
+/// 上报本设备的使用量，返回所有设备汇总后的使用热力图
+///
+/// 服务器只保存按小时汇总的连接数和时长，不保存主机等连接详情
+pub async fn usage_heatmap_handler(
+    State(state): State<AppState>,
+    UserId(user_id): UserId,
+    Language(language): Language,
+    Json(request): Json<UsageHeatmapRequest>,
+) -> Result<Json<ApiResponse<UsageHeatmapResult>>, StatusCode> {
+    if request.validate().is_err()
+        || chrono::NaiveDate::parse_from_str(&request.from_date, "%Y-%m-%d").is_err()
+        || request.buckets.len() > MAX_USAGE_BUCKETS
+        || !request.buckets.iter().all(|b| b.is_valid() && b.date >= request.from_date)
+    {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let now = chrono::Utc::now().timestamp();
+    let buckets = request
+        .buckets
+        .into_iter()
+        .map(|bucket| usage_buckets::Model {
+            id: uuid::Uuid::new_v4().to_string(),
+            user_id: user_id.clone(),
+            device_id: request.device_id.clone(),
+            date: bucket.date,
+            hour: bucket.hour,
+            connections: bucket.connections,
+            duration_secs: bucket.duration_secs,
+            updated_at: now,
+        })
+        .collect();
+
+    let repo = UsageBucketRepository::new(state.pool.clone());
+    repo.replace_device_buckets(&user_id, &request.device_id, &request.from_date, buckets)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to save usage buckets: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    let stored = repo.find_by_user_since(&user_id, &request.from_date).await.map_err(|e| {
+        tracing::error!("Failed to query usage buckets: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let devices: BTreeSet<&str> = stored.iter().map(|b| b.device_id.as_str()).collect();
+    let mut merged: BTreeMap<(String, i32), (i64, i64)> = BTreeMap::new();
+    for bucket in &stored {
+        let entry = merged.entry((bucket.date.clone(), bucket.hour)).or_default();
+        entry.0 += bucket.connections;
+        entry.1 += bucket.duration_secs;
+    }
+
+    let result = UsageHeatmapResult {
+        devices: devices.len() as u64,
+        buckets: merged
+            .into_iter()
+            .map(|((date, hour), (connections, duration_secs))| UsageBucketVO { date, hour, connections, duration_secs })
+            .collect(),
+    };
+    tracing::info!(
+        "使用热力图: user_id={}, devices={}, buckets={}",
+        user_id, result.devices, result.buckets.len()
+    );
+    let message = t(Some(language.as_str()), MessageKey::SuccessUsageHeatmap);
+    Ok(Json(ApiResponse::success_with_message(result, &message)))
+}
@@ -0,0 +1,437 @@
+use crate::AppState;
+use crate::domain::dto::workspace::{
+    AddWorkspaceMemberRequest, CreateSharedSessionRequest, CreateWorkspaceRequest, ListAuditLogsQuery, WorkspaceRole,
+};
+use crate::domain::entities::{shared_sessions, workspace_audit_logs, workspace_members, workspaces};
+use crate::domain::vo::{
+    ApiResponse,
+    workspace::{SharedSessionCredentialsVO, SharedSessionVO, WorkspaceAuditLogVO, WorkspaceMemberVO, WorkspaceVO},
+};
+use crate::infra::middleware::{UserId, Language};
+use crate::repositories::user_repository::UserRepository;
+use crate::repositories::workspace_repository::WorkspaceRepository;
+use crate::utils::encryption::ServerEncryptionService;
+use crate::utils::i18n::{t, MessageKey};
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use validator::Validate;
+
+/// 审计日志默认和最大返回条数
+const DEFAULT_AUDIT_LOG_LIMIT: u64 = 200;
+const MAX_AUDIT_LOG_LIMIT: u64 = 1000;
+
+fn session_to_vo(session: shared_sessions::Model) -> SharedSessionVO {
+    SharedSessionVO {
+        id: session.id,
+        workspace_id: session.workspace_id,
+        name: session.name,
+        host: session.host,
+        port: session.port,
+        username: session.username,
+        created_by: session.created_by,
+        created_at: session.created_at,
+        updated_at: session.updated_at,
+    }
+}
+
+fn internal_error(context: &str, e: anyhow::Error) -> StatusCode {
+    tracing::error!("{}: {}", context, e);
+    StatusCode::INTERNAL_SERVER_ERROR
+}
+
+/// 当前用户在工作区中的角色，不是成员时返回 404（不暴露工作区是否存在）
+async fn member_role(repo: &WorkspaceRepository, workspace_id: &str, user_id: &str) -> Result<WorkspaceRole, StatusCode> {
+    match repo.find_member(workspace_id, user_id).await {
+        Ok(Some(member)) => WorkspaceRole::parse(&member.role).ok_or_else(|| {
+            tracing::error!("Invalid workspace role: {}", member.role);
+            StatusCode::INTERNAL_SERVER_ERROR
+        }),
+        Ok(None) => Err(StatusCode::NOT_FOUND),
+        Err(e) => Err(internal_error("Failed to query workspace member", e)),
+    }
+}
+
+/// 没有权限时返回 403
+fn require(allowed: bool) -> Result<(), StatusCode> {
+    if allowed {
+        Ok(())
+    } else {
+        Err(StatusCode::FORBIDDEN)
+    }
+}
+
+/// 查找工作区中的共享会话，不存在时返回 404
+async fn find_session(
+    repo: &WorkspaceRepository,
+    workspace_id: &str,
+    session_id: &str,
+) -> Result<shared_sessions::Model, StatusCode> {
+    match repo.find_session(workspace_id, session_id).await {
+        Ok(Some(session)) => Ok(session),
+        Ok(None) => Err(StatusCode::NOT_FOUND),
+        Err(e) => Err(internal_error("Failed to query shared session", e)),
+    }
+}
+
+/// 解密共享会话的凭据
+fn decrypt_credentials(session: &shared_sessions::Model) -> Result<String, StatusCode> {
+    ServerEncryptionService::from_env()
+        .and_then(|service| service.decrypt_auth_method(&session.credentials_encrypted))
+        .map_err(|e| internal_error("Failed to decrypt shared session credentials", e))
+}
+
+/// 创建工作区（创建者为 owner）
+pub async fn create_workspace_handler(
+    State(state): State<AppState>,
+    UserId(user_id): UserId,
+    Language(language): Language,
+    Json(request): Json<CreateWorkspaceRequest>,
+) -> Result<Json<ApiResponse<WorkspaceVO>>, StatusCode> {
+    if request.validate().is_err() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let now = chrono::Utc::now().timestamp();
+    let workspace = workspaces::Model {
+        id: uuid::Uuid::new_v4().to_string(),
+        name: request.name,
+        owner_id: user_id.clone(),
+        created_at: now,
+        updated_at: now,
+    };
+    let owner = workspace_members::Model {
+        id: uuid::Uuid::new_v4().to_string(),
+        workspace_id: workspace.id.clone(),
+        user_id: user_id.clone(),
+        role: WorkspaceRole::Owner.as_str().to_string(),
+        created_at: now,
+    };
+
+    let repo = WorkspaceRepository::new(state.pool.clone());
+    repo.create_workspace(workspace.clone(), owner)
+        .await
+        .map_err(|e| internal_error("Failed to create workspace", e))?;
+
+    tracing::info!("工作区已创建: id={}, owner={}", workspace.id, user_id);
+    let vo = WorkspaceVO {
+        id: workspace.id,
+        name: workspace.name,
+        owner_id: workspace.owner_id,
+        role: WorkspaceRole::Owner.as_str().to_string(),
+        created_at: workspace.created_at,
+        updated_at: workspace.updated_at,
+    };
+    let message = t(Some(language.as_str()), MessageKey::SuccessCreateWorkspace);
+    Ok(Json(ApiResponse::success_with_message(vo, &message)))
+}
+
+/// 列出当前用户加入的工作区
+pub async fn list_workspaces_handler(
+    State(state): State<AppState>,
+    UserId(user_id): UserId,
+    Language(language): Language,
+) -> Result<Json<ApiResponse<Vec<WorkspaceVO>>>, StatusCode> {
+    let repo = WorkspaceRepository::new(state.pool.clone());
+    let workspaces = repo
+        .find_workspaces_by_user(&user_id)
+        .await
+        .map_err(|e| internal_error("Failed to list workspaces", e))?;
+
+    let vos = workspaces
+        .into_iter()
+        .map(|(workspace, role)| WorkspaceVO {
+            id: workspace.id,
+            name: workspace.name,
+            owner_id: workspace.owner_id,
+            role,
+            created_at: workspace.created_at,
+            updated_at: workspace.updated_at,
+        })
+        .collect();
+    let message = t(Some(language.as_str()), MessageKey::SuccessListWorkspaces);
+    Ok(Json(ApiResponse::success_with_message(vos, &message)))
+}
+
+/// 列出工作区成员（所有成员可见）
+pub async fn list_members_handler(
+    State(state): State<AppState>,
+    UserId(user_id): UserId,
+    Language(language): Language,
+    Path(workspace_id): Path<String>,
+) -> Result<Json<ApiResponse<Vec<WorkspaceMemberVO>>>, StatusCode> {
+    let repo = WorkspaceRepository::new(state.pool.clone());
+    member_role(&repo, &workspace_id, &user_id).await?;
+
+    let members = repo
+        .find_members(&workspace_id)
+        .await
+        .map_err(|e| internal_error("Failed to list workspace members", e))?;
+
+    let user_repo = UserRepository::new(state.pool.clone());
+    let mut vos = Vec::with_capacity(members.len());
+    for member in members {
+        let email = user_repo.get_email_by_id(&member.user_id).await.unwrap_or(None);
+        vos.push(WorkspaceMemberVO {
+            user_id: member.user_id,
+            email,
+            role: member.role,
+            created_at: member.created_at,
+        });
+    }
+
+    let message = t(Some(language.as_str()), MessageKey::SuccessListWorkspaceMembers);
+    Ok(Json(ApiResponse::success_with_message(vos, &message)))
+}
+
+/// 按邮箱添加成员（owner 和 admin），角色只能是 admin 或 member
+///
+/// 用户不存在时返回 404，已是成员时返回 409
+pub async fn add_member_handler(
+    State(state): State<AppState>,
+    UserId(user_id): UserId,
+    Language(language): Language,
+    Path(workspace_id): Path<String>,
+    Json(request): Json<AddWorkspaceMemberRequest>,
+) -> Result<Json<ApiResponse<WorkspaceMemberVO>>, StatusCode> {
+    let role = WorkspaceRole::parse(&request.role)
+        .filter(|role| *role != WorkspaceRole::Owner)
+        .ok_or(StatusCode::BAD_REQUEST)?;
+    if request.validate().is_err() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let repo = WorkspaceRepository::new(state.pool.clone());
+    require(member_role(&repo, &workspace_id, &user_id).await?.can_manage_members())?;
+
+    let user = match UserRepository::new(state.pool.clone()).find_by_email(&request.email).await {
+        Ok(Some(user)) if user.deleted_at.is_none() => user,
+        Ok(_) => return Err(StatusCode::NOT_FOUND),
+        Err(e) => return Err(internal_error("Failed to query user", e)),
+    };
+    match repo.find_member(&workspace_id, &user.id).await {
+        Ok(None) => {}
+        Ok(Some(_)) => return Err(StatusCode::CONFLICT),
+        Err(e) => return Err(internal_error("Failed to query workspace member", e)),
+    }
+
+    let member = workspace_members::Model {
+        id: uuid::Uuid::new_v4().to_string(),
+        workspace_id: workspace_id.clone(),
+        user_id: user.id.clone(),
+        role: role.as_str().to_string(),
+        created_at: chrono::Utc::now().timestamp(),
+    };
+    repo.add_member(member.clone())
+        .await
+        .map_err(|e| internal_error("Failed to add workspace member", e))?;
+
+    tracing::info!("工作区成员已添加: workspace_id={}, user_id={}, role={}", workspace_id, user.id, member.role);
+    let vo = WorkspaceMemberVO {
+        user_id: member.user_id,
+        email: Some(user.email),
+        role: member.role,
+        created_at: member.created_at,
+    };
+    let message = t(Some(language.as_str()), MessageKey::SuccessAddWorkspaceMember);
+    Ok(Json(ApiResponse::success_with_message(vo, &message)))
+}
+
+/// 移除成员（owner 和 admin），owner 不能被移除；返回被移除的用户 ID
+pub async fn remove_member_handler(
+    State(state): State<AppState>,
+    UserId(user_id): UserId,
+    Language(language): Language,
+    Path((workspace_id, member_user_id)): Path<(String, String)>,
+) -> Result<Json<ApiResponse<String>>, StatusCode> {
+    let repo = WorkspaceRepository::new(state.pool.clone());
+    require(member_role(&repo, &workspace_id, &user_id).await?.can_manage_members())?;
+
+    let target_role = member_role(&repo, &workspace_id, &member_user_id).await?;
+    require(target_role != WorkspaceRole::Owner)?;
+
+    repo.remove_member(&workspace_id, &member_user_id)
+        .await
+        .map_err(|e| internal_error("Failed to remove workspace member", e))?;
+
+    tracing::info!("工作区成员已移除: workspace_id={}, user_id={}", workspace_id, member_user_id);
+    let message = t(Some(language.as_str()), MessageKey::SuccessRemoveWorkspaceMember);
+    Ok(Json(ApiResponse::success_with_message(member_user_id, &message)))
+}
+
+/// 列出共享会话（不包含凭据，所有成员可见）
+pub async fn list_sessions_handler(
+    State(state): State<AppState>,
+    UserId(user_id): UserId,
+    Language(language): Language,
+    Path(workspace_id): Path<String>,
+) -> Result<Json<ApiResponse<Vec<SharedSessionVO>>>, StatusCode> {
+    let repo = WorkspaceRepository::new(state.pool.clone());
+    member_role(&repo, &workspace_id, &user_id).await?;
+
+    let sessions = repo
+        .find_sessions(&workspace_id)
+        .await
+        .map_err(|e| internal_error("Failed to list shared sessions", e))?;
+
+    let vos = sessions.into_iter().map(session_to_vo).collect();
+    let message = t(Some(language.as_str()), MessageKey::SuccessListSharedSessions);
+    Ok(Json(ApiResponse::success_with_message(vos, &message)))
+}
+
+/// 共享会话到工作区（owner 和 admin），凭据使用服务器密钥加密保存
+pub async fn create_session_handler(
+    State(state): State<AppState>,
+    UserId(user_id): UserId,
+    Language(language): Language,
+    Path(workspace_id): Path<String>,
+    Json(request): Json<CreateSharedSessionRequest>,
+) -> Result<Json<ApiResponse<SharedSessionVO>>, StatusCode> {
+    if request.validate().is_err() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let repo = WorkspaceRepository::new(state.pool.clone());
+    require(member_role(&repo, &workspace_id, &user_id).await?.can_share_sessions())?;
+
+    let credentials_encrypted = ServerEncryptionService::from_env()
+        .and_then(|service| service.encrypt_auth_method(&request.credentials))
+        .map_err(|e| internal_error("Failed to encrypt shared session credentials", e))?;
+
+    let now = chrono::Utc::now().timestamp();
+    let session = shared_sessions::Model {
+        id: uuid::Uuid::new_v4().to_string(),
+        workspace_id,
+        name: request.name,
+        host: request.host,
+        port: request.port,
+        username: request.username,
+        credentials_encrypted,
+        created_by: user_id,
+        created_at: now,
+        updated_at: now,
+    };
+
+    let created = repo
+        .create_session(session)
+        .await
+        .map_err(|e| internal_error("Failed to create shared session", e))?;
+
+    tracing::info!("共享会话已创建: id={}, workspace_id={}", created.id, created.workspace_id);
+    let message = t(Some(language.as_str()), MessageKey::SuccessShareSession);
+    Ok(Json(ApiResponse::success_with_message(session_to_vo(created), &message)))
+}
+
+/// 删除共享会话（owner 和 admin，member 返回 403），返回被删除的会话 ID
+pub async fn delete_session_handler(
+    State(state): State<AppState>,
+    UserId(user_id): UserId,
+    Language(language): Language,
+    Path((workspace_id, session_id)): Path<(String, String)>,
+) -> Result<Json<ApiResponse<String>>, StatusCode> {
+    let repo = WorkspaceRepository::new(state.pool.clone());
+    require(member_role(&repo, &workspace_id, &user_id).await?.can_delete_sessions())?;
+
+    let session = find_session(&repo, &workspace_id, &session_id).await?;
+    repo.delete_session(&session.id)
+        .await
+        .map_err(|e| internal_error("Failed to delete shared session", e))?;
+
+    tracing::info!("共享会话已删除: id={}, workspace_id={}, user_id={}", session.id, workspace_id, user_id);
+    let message = t(Some(language.as_str()), MessageKey::SuccessDeleteSharedSession);
+    Ok(Json(ApiResponse::success_with_message(session.id, &message)))
+}
+
+/// 查看或导出共享会话的凭据（owner 和 admin，member 返回 403）
+pub async fn get_credentials_handler(
+    State(state): State<AppState>,
+    UserId(user_id): UserId,
+    Language(language): Language,
+    Path((workspace_id, session_id)): Path<(String, String)>,
+) -> Result<Json<ApiResponse<SharedSessionCredentialsVO>>, StatusCode> {
+    let repo = WorkspaceRepository::new(state.pool.clone());
+    require(member_role(&repo, &workspace_id, &user_id).await?.can_view_credentials())?;
+
+    let session = find_session(&repo, &workspace_id, &session_id).await?;
+    let credentials = decrypt_credentials(&session)?;
+
+    tracing::info!("共享会话凭据已导出: id={}, user_id={}", session.id, user_id);
+    let vo = SharedSessionCredentialsVO {
+        session: session_to_vo(session),
+        credentials,
+    };
+    let message = t(Some(language.as_str()), MessageKey::SuccessGetSharedCredentials);
+    Ok(Json(ApiResponse::success_with_message(vo, &message)))
+}
+
+/// 连接共享会话（所有成员）
+///
+/// 先写入审计日志，再返回连接所需的凭据；审计日志写入失败时拒绝连接
+pub async fn connect_session_handler(
+    State(state): State<AppState>,
+    UserId(user_id): UserId,
+    Language(language): Language,
+    Path((workspace_id, session_id)): Path<(String, String)>,
+) -> Result<Json<ApiResponse<SharedSessionCredentialsVO>>, StatusCode> {
+    let repo = WorkspaceRepository::new(state.pool.clone());
+    member_role(&repo, &workspace_id, &user_id).await?;
+
+    let session = find_session(&repo, &workspace_id, &session_id).await?;
+    let credentials = decrypt_credentials(&session)?;
+
+    let log = workspace_audit_logs::Model {
+        id: uuid::Uuid::new_v4().to_string(),
+        workspace_id,
+        shared_session_id: session.id.clone(),
+        user_id,
+        action: "connect".to_string(),
+        session_name: session.name.clone(),
+        target: format!("{}@{}:{}", session.username, session.host, session.port),
+        created_at: chrono::Utc::now().timestamp(),
+    };
+    repo.add_audit_log(log)
+        .await
+        .map_err(|e| internal_error("Failed to write audit log", e))?;
+
+    let vo = SharedSessionCredentialsVO {
+        session: session_to_vo(session),
+        credentials,
+    };
+    let message = t(Some(language.as_str()), MessageKey::SuccessConnectSharedSession);
+    Ok(Json(ApiResponse::success_with_message(vo, &message)))
+}
+
+/// 查看审计日志（owner 和 admin，member 返回 403）
+pub async fn list_audit_logs_handler(
+    State(state): State<AppState>,
+    UserId(user_id): UserId,
+    Language(language): Language,
+    Path(workspace_id): Path<String>,
+    Query(query): Query<ListAuditLogsQuery>,
+) -> Result<Json<ApiResponse<Vec<WorkspaceAuditLogVO>>>, StatusCode> {
+    let repo = WorkspaceRepository::new(state.pool.clone());
+    require(member_role(&repo, &workspace_id, &user_id).await?.can_view_audit_logs())?;
+
+    let limit = query.limit.unwrap_or(DEFAULT_AUDIT_LOG_LIMIT).clamp(1, MAX_AUDIT_LOG_LIMIT);
+    let logs = repo
+        .find_audit_logs(&workspace_id, limit)
+        .await
+        .map_err(|e| internal_error("Failed to list audit logs", e))?;
+
+    let vos = logs
+        .into_iter()
+        .map(|log| WorkspaceAuditLogVO {
+            id: log.id,
+            shared_session_id: log.shared_session_id,
+            user_id: log.user_id,
+            action: log.action,
+            session_name: log.session_name,
+            target: log.target,
+            created_at: log.created_at,
+        })
+        .collect();
+    let message = t(Some(language.as_str()), MessageKey::SuccessListAuditLogs);
+    Ok(Json(ApiResponse::success_with_message(vos, &message)))
+}
@@ -0,0 +1,178 @@
+use crate::config::ai::AiProxyConfig;
+use crate::domain::dto::ai::AiChatMessage;
+use crate::infra::redis::{redis_client::RedisClient, redis_key::BusinessType, redis_key::RedisKey};
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// 上游 OpenAI 兼容接口请求体
+#[derive(Serialize)]
+struct UpstreamRequest<'a> {
+    model: &'a str,
+    messages: &'a [AiChatMessage],
+    temperature: f32,
+    max_tokens: u32,
+    stream: bool,
+}
+
+#[derive(Deserialize)]
+struct UpstreamResponse {
+    choices: Vec<UpstreamChoice>,
+}
+
+#[derive(Deserialize)]
+struct UpstreamChoice {
+    message: UpstreamMessage,
+}
+
+#[derive(Deserialize)]
+struct UpstreamMessage {
+    content: String,
+}
+
+/// 配额检查结果
+pub enum QuotaCheck {
+    /// 允许请求，附带本次计入后的已用次数
+    Allowed(u64),
+    /// 已达到每日上限
+    Exceeded,
+}
+
+/// AI 代理
+///
+/// 使用服务器配置的凭据转发聊天请求，并按用户统计每日请求次数
+#[derive(Clone)]
+pub struct AiProxy {
+    client: reqwest::Client,
+    config: AiProxyConfig,
+}
+
+impl AiProxy {
+    pub fn new(config: AiProxyConfig) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .connect_timeout(Duration::from_secs(10))
+            .timeout(Duration::from_secs(config.timeout_seconds))
+            .build()?;
+        Ok(Self { client, config })
+    }
+
+    pub fn config(&self) -> &AiProxyConfig {
+        &self.config
+    }
+
+    /// 是否已启用且配置了上游凭据
+    pub fn is_enabled(&self) -> bool {
+        self.config.enabled && !self.config.api_key.is_empty()
+    }
+
+    /// 转发聊天请求，返回模型回复
+    pub async fn chat(
+        &self,
+        messages: &[AiChatMessage],
+        temperature: Option<f32>,
+        max_tokens: Option<u32>,
+    ) -> Result<String> {
+        let url = format!("{}/chat/completions", self.config.base_url.trim_end_matches('/'));
+        let request = UpstreamRequest {
+            model: &self.config.model,
+            messages,
+            temperature: temperature.unwrap_or(0.7).clamp(0.0, 2.0),
+            max_tokens: max_tokens
+                .unwrap_or(self.config.max_tokens)
+                .clamp(1, self.config.max_tokens),
+            stream: false,
+        };
+
+        let response = self
+            .client
+            .post(&url)
+            .bearer_auth(&self.config.api_key)
+            .json(&request)
+            .send()
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow!("Upstream AI error ({}): {}", status, body));
+        }
+
+        let body: UpstreamResponse = response.json().await?;
+        body.choices
+            .into_iter()
+            .next()
+            .map(|choice| choice.message.content)
+            .ok_or_else(|| anyhow!("Upstream AI returned no choices"))
+    }
+
+    /// 用户当日请求计数的 Redis Key
+    ///
+    /// 一次请求的计入和退还必须使用同一个 Key，跨越零点时不会退还到第二天的计数
+    pub fn daily_key(user_id: &str) -> RedisKey {
+        let date = chrono::Utc::now().format("%Y-%m-%d").to_string();
+        RedisKey::new(BusinessType::RateLimit)
+            .add_identifier("ai")
+            .add_identifier("daily")
+            .add_identifier(user_id)
+            .add_identifier(date)
+    }
+
+    /// 今日已用次数
+    pub async fn used_today(&self, redis: &RedisClient, user_id: &str) -> Result<u64> {
+        let count = redis
+            .get_key(&Self::daily_key(user_id))
+            .await
+            .map_err(|e| anyhow!("Redis error: {}", e))?;
+        Ok(count.and_then(|c| c.parse().ok()).unwrap_or(0))
+    }
+
+    /// 计入一次请求并检查每日配额
+    pub async fn consume_quota(&self, redis: &RedisClient, key: &RedisKey) -> Result<QuotaCheck> {
+        let used = redis
+            .incr_key(key)
+            .await
+            .map_err(|e| anyhow!("Redis error: {}", e))?;
+        if used == 1 {
+            redis
+                .expire_key(key, 86400)
+                .await
+                .map_err(|e| anyhow!("Redis error: {}", e))?;
+        }
+
+        if self.config.daily_quota > 0 && used > self.config.daily_quota {
+            return Ok(QuotaCheck::Exceeded);
+        }
+        Ok(QuotaCheck::Allowed(used))
+    }
+
+    /// 退还一次已计入的请求（上游请求失败时调用）
+    ///
+    /// 计数已过期时不再退还，避免创建没有过期时间的负数计数
+    pub async fn refund_quota(&self, redis: &RedisClient, key: &RedisKey) -> Result<()> {
+        let exists = redis
+            .exists_key(key)
+            .await
+            .map_err(|e| anyhow!("Redis error: {}", e))?;
+        if !exists {
+            return Ok(());
+        }
+
+        let remaining = redis
+            .decr_key(key)
+            .await
+            .map_err(|e| anyhow!("Redis error: {}", e))?;
+        if remaining <= 0 {
+            // 检查与递减之间计数恰好过期时，DECR 会新建一个没有过期时间的 Key
+            redis
+                .expire_key(key, 86400)
+                .await
+                .map_err(|e| anyhow!("Redis error: {}", e))?;
+        }
+        Ok(())
+    }
+
+    /// 剩余次数，不限制时返回 None
+    pub fn remaining(&self, used: u64) -> Option<u64> {
+        (self.config.daily_quota > 0).then(|| self.config.daily_quota.saturating_sub(used))
+    }
+}
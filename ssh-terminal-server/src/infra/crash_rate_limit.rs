@@ -0,0 +1,55 @@
+use crate::infra::redis::{redis_client::RedisClient, redis_key::BusinessType, redis_key::RedisKey};
+use anyhow::Result;
+
+/// 每个 IP 每小时最多提交的崩溃报告数量
+const MAX_REPORTS_PER_IP: u64 = 30;
+
+/// 每台设备每小时最多提交的崩溃报告数量
+const MAX_REPORTS_PER_DEVICE: u64 = 10;
+
+/// 限频窗口（秒）
+const WINDOW_SECS: u64 = 3600;
+
+/// 崩溃报告限频管理器（公开接口，按来源 IP 和设备 ID 分别计数）
+pub struct CrashRateLimit {
+    redis_client: RedisClient,
+}
+
+impl CrashRateLimit {
+    pub fn new(redis_client: RedisClient) -> Self {
+        Self { redis_client }
+    }
+
+    fn get_key(scope: &str, id: &str) -> RedisKey {
+        RedisKey::new(BusinessType::RateLimit)
+            .add_identifier("crash")
+            .add_identifier(scope)
+            .add_identifier(id)
+    }
+
+    /// 计数并检查是否超过限制
+    async fn hit(&self, scope: &str, id: &str, limit: u64) -> Result<bool> {
+        let key = Self::get_key(scope, id);
+        let count = self.redis_client.incr_key(&key).await
+            .map_err(|e| anyhow::anyhow!("Redis error: {}", e))?;
+
+        if count == 1 {
+            // 窗口内第一次请求，设置过期时间
+            self.redis_client.expire_key(&key, WINDOW_SECS).await
+                .map_err(|e| anyhow::anyhow!("Redis error: {}", e))?;
+        }
+
+        Ok(count <= limit)
+    }
+
+    /// 检查本次提交是否允许（IP 和设备都未超过限制）
+    pub async fn check(&self, ip: &str, device_id: Option<&str>) -> Result<bool> {
+        if !self.hit("ip", ip, MAX_REPORTS_PER_IP).await? {
+            return Ok(false);
+        }
+        match device_id.filter(|id| !id.is_empty()) {
+            Some(device_id) => self.hit("device", device_id, MAX_REPORTS_PER_DEVICE).await,
+            None => Ok(true),
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use crate::domain::vo::device::{DeviceServerMessage, OnlineDevice};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::{mpsc, RwLock};
+
+type DeviceSender = mpsc::UnboundedSender<DeviceServerMessage>;
+
+struct DeviceEntry {
+    device: OnlineDevice,
+    tx: DeviceSender,
+}
+
+/// 在线设备注册表
+///
+/// 记录每个用户通过 WebSocket 在线的客户端，用于在设备之间转发消息。
+/// 仅保存在当前进程内存中，多实例部署时需要同一用户的设备连接到同一实例
+#[derive(Clone, Default)]
+pub struct DeviceHub {
+    users: Arc<RwLock<HashMap<String, HashMap<String, DeviceEntry>>>>,
+}
+
+impl DeviceHub {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 注册设备，返回推送给该设备的消息通道
+    ///
+    /// 同一 client_id 重复连接时替换旧连接
+    pub async fn register(
+        &self,
+        user_id: &str,
+        device: OnlineDevice,
+    ) -> (DeviceSender, mpsc::UnboundedReceiver<DeviceServerMessage>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        {
+            let mut users = self.users.write().await;
+            users.entry(user_id.to_string()).or_default().insert(
+                device.client_id.clone(),
+                DeviceEntry { device, tx: tx.clone() },
+            );
+        }
+        self.broadcast_devices(user_id).await;
+        (tx, rx)
+    }
+
+    /// 注销设备（仅当该连接仍是当前注册的连接时）
+    pub async fn unregister(&self, user_id: &str, client_id: &str, tx: &DeviceSender) {
+        let removed = {
+            let mut users = self.users.write().await;
+            let Some(devices) = users.get_mut(user_id) else {
+                return;
+            };
+            let removed = match devices.get(client_id) {
+                Some(entry) if entry.tx.same_channel(tx) => devices.remove(client_id).is_some(),
+                _ => false,
+            };
+            if devices.is_empty() {
+                users.remove(user_id);
+            }
+            removed
+        };
+        if removed {
+            self.broadcast_devices(user_id).await;
+        }
+    }
+
+    /// 向用户的某台设备推送消息，设备不在线时返回 false
+    pub async fn send_to(&self, user_id: &str, client_id: &str, message: DeviceServerMessage) -> bool {
+        let users = self.users.read().await;
+        users
+            .get(user_id)
+            .and_then(|devices| devices.get(client_id))
+            .map(|entry| entry.tx.send(message).is_ok())
+            .unwrap_or(false)
+    }
+
+    /// 向用户的所有设备推送在线设备列表
+    async fn broadcast_devices(&self, user_id: &str) {
+        let users = self.users.read().await;
+        let Some(devices) = users.get(user_id) else {
+            return;
+        };
+
+        let mut online: Vec<OnlineDevice> = devices.values().map(|entry| entry.device.clone()).collect();
+        online.sort_by_key(|device| device.connected_at);
+        for entry in devices.values() {
+            let _ = entry.tx.send(DeviceServerMessage::Devices { devices: online.clone() });
+        }
+    }
+}
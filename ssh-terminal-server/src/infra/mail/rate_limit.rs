@@ -1,6 +1,12 @@
 use crate::infra::redis::{redis_client::RedisClient, redis_key::BusinessType, redis_key::RedisKey};
 use anyhow::Result;
 
+/// 同一个验证码允许输错的次数，达到后验证码作废
+pub const MAX_VERIFY_CODE_FAILURES: u64 = 5;
+
+/// 验证码有效期（秒），也是输错计数的保留时间
+pub const VERIFY_CODE_TTL_SECS: u64 = 300;
+
 /// 邮件发送限频管理器
 pub struct MailRateLimit {
     redis_client: RedisClient,
@@ -29,6 +35,36 @@ impl MailRateLimit {
             .add_identifier(date)
     }
 
+    /// 获取邮箱验证码输错计数的 Redis Key
+    fn get_verify_failure_key(email: &str) -> RedisKey {
+        RedisKey::new(BusinessType::RateLimit)
+            .add_identifier("verify_code")
+            .add_identifier("failures")
+            .add_identifier(email)
+    }
+
+    /// 记录一次验证码输错，返回当前验证码累计输错次数
+    pub async fn record_verify_failure(&self, email: &str) -> Result<u64> {
+        let key = Self::get_verify_failure_key(email);
+        let count = self.redis_client.incr_key(&key).await
+            .map_err(|e| anyhow::anyhow!("Redis error: {}", e))?;
+
+        if count == 1 {
+            // 与验证码同时过期
+            self.redis_client.expire_key(&key, VERIFY_CODE_TTL_SECS).await
+                .map_err(|e| anyhow::anyhow!("Redis error: {}", e))?;
+        }
+
+        Ok(count)
+    }
+
+    /// 清除验证码输错计数（验证成功或发送了新验证码时）
+    pub async fn clear_verify_failures(&self, email: &str) -> Result<()> {
+        let key = Self::get_verify_failure_key(email);
+        self.redis_client.delete_key(&key).await
+            .map_err(|e| anyhow::anyhow!("Redis error: {}", e))
+    }
+
     /// 检查用户是否可以发送邮件（60秒限频）
     pub async fn check_rate_limit(&self, user_id: &str) -> Result<bool> {
         let key = Self::get_user_rate_key(user_id);
@@ -1,3 +1,7 @@
 pub mod middleware;
 pub mod redis;
 pub mod mail;
+pub mod device_hub;
+pub mod ai_proxy;
+pub mod recording_store;
+pub mod crash_rate_limit;
@@ -0,0 +1,60 @@
+use crate::config::recording::RecordingStorageConfig;
+use anyhow::{anyhow, Result};
+use std::path::PathBuf;
+
+/// 录制文件存储
+///
+/// 文件按 `{storage_dir}/{user_id}/{recording_id}` 保存，元数据在数据库中
+#[derive(Clone)]
+pub struct RecordingStore {
+    config: RecordingStorageConfig,
+}
+
+impl RecordingStore {
+    pub fn new(config: RecordingStorageConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn config(&self) -> &RecordingStorageConfig {
+        &self.config
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// 文件路径（ID 只允许字母、数字和 `-`，防止路径穿越）
+    fn path(&self, user_id: &str, id: &str) -> Result<PathBuf> {
+        let valid = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid(user_id) || !valid(id) {
+            return Err(anyhow!("Invalid recording path: {}/{}", user_id, id));
+        }
+        Ok(PathBuf::from(&self.config.storage_dir).join(user_id).join(id))
+    }
+
+    /// 保存文件内容
+    pub async fn save(&self, user_id: &str, id: &str, data: &[u8]) -> Result<()> {
+        let path = self.path(user_id, id)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(&path, data).await?;
+        Ok(())
+    }
+
+    /// 读取文件内容
+    pub async fn read(&self, user_id: &str, id: &str) -> Result<Vec<u8>> {
+        let path = self.path(user_id, id)?;
+        Ok(tokio::fs::read(&path).await?)
+    }
+
+    /// 删除文件（文件不存在时忽略）
+    pub async fn delete(&self, user_id: &str, id: &str) -> Result<()> {
+        let path = self.path(user_id, id)?;
+        match tokio::fs::remove_file(&path).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
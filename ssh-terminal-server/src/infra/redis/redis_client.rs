@@ -215,6 +215,12 @@ impl RedisClient {
         main.incr(k, 1).await
     }
 
+    /// 原子递减键值并返回新值
+    pub async fn decr(&self, k: &str) -> redis::RedisResult<i64> {
+        let mut main = self.main.clone();
+        main.decr(k, 1).await
+    }
+
     /// 获取键的剩余生存时间（秒）
     pub async fn ttl(&self, k: &str) -> redis::RedisResult<i64> {
         let mut main = self.main.clone();
@@ -227,6 +233,12 @@ impl RedisClient {
         self.incr(&key_str).await
     }
 
+    /// 使用 RedisKey 原子递减键值并返回新值
+    pub async fn decr_key(&self, key: &RedisKey) -> redis::RedisResult<i64> {
+        let key_str = key.build();
+        self.decr(&key_str).await
+    }
+
     /// 使用 RedisKey 获取剩余生存时间
     pub async fn ttl_key(&self, key: &RedisKey) -> redis::RedisResult<i64> {
         let key_str = key.build();
@@ -12,6 +12,7 @@ mod services;
 mod utils;
 
 use axum::{
+    extract::DefaultBodyLimit,
     routing::{delete, get, post, put},
     Router,
 };
@@ -27,6 +28,9 @@ pub struct AppState {
     pub pool: db::DbPool,
     pub config: config::app::AppConfig,
     pub redis_client: infra::redis::redis_client::RedisClient,
+    pub device_hub: infra::device_hub::DeviceHub,
+    pub ai_proxy: infra::ai_proxy::AiProxy,
+    pub recording_store: infra::recording_store::RecordingStore,
 }
 
 /// 对敏感信息进行半脱敏处理
@@ -125,6 +129,19 @@ async fn main() -> anyhow::Result<()> {
         tracing::info!("Email From: {} <{}>", config.email.from_name, config.email.from_email);
         tracing::info!("Email Worker Pool: {}", config.email.worker_pool_size);
     }
+    tracing::info!("AI Proxy Enabled: {}", config.ai.enabled);
+    if config.ai.enabled {
+        tracing::info!("AI Proxy Base URL: {}", config.ai.base_url);
+        tracing::info!("AI Proxy Model: {}", config.ai.model);
+        tracing::info!("AI Proxy API Key: {}", mask_half(&config.ai.api_key));
+        tracing::info!("AI Proxy Daily Quota: {}", config.ai.daily_quota);
+    }
+    tracing::info!("Recording Upload Enabled: {}", config.recording.enabled);
+    if config.recording.enabled {
+        tracing::info!("Recording Storage Dir: {}", config.recording.storage_dir);
+        tracing::info!("Recording Max File Size: {} MB", config.recording.max_file_size_mb);
+        tracing::info!("Recording User Quota: {} MB", config.recording.user_quota_mb);
+    }
     tracing::info!("===============================");
 
     // 初始化数据库（自动创建数据库和表）
@@ -142,6 +159,9 @@ async fn main() -> anyhow::Result<()> {
         pool: pool.clone(),
         config: config.clone(),
         redis_client: redis_client.clone(),
+        device_hub: infra::device_hub::DeviceHub::new(),
+        ai_proxy: infra::ai_proxy::AiProxy::new(config.ai.clone())?,
+        recording_store: infra::recording_store::RecordingStore::new(config.recording.clone()),
     };
 
     // 启动邮件 Worker（如果启用）
@@ -164,6 +184,8 @@ async fn main() -> anyhow::Result<()> {
             .route("/auth/register", post(handlers::auth::register))
             .route("/auth/login", post(handlers::auth::login))
             .route("/auth/refresh", post(handlers::auth::refresh))
+            // 崩溃报告（公开，客户端选择上传时调用）
+            .route("/crash", post(handlers::crash::submit_crash_report))
             // 邮件 API（公开，无需认证）
             // 同步版本（推荐）：立即返回真实的发送结果
             .route(
@@ -176,6 +198,9 @@ async fn main() -> anyhow::Result<()> {
                 "/api/email/send-verify-code-async",
                 post(handlers::email::send_verify_code_async_handler),
             )
+            // 注册后验证邮箱 / 为未验证的账号重新发送验证码
+            .route("/auth/verify-email", post(handlers::auth::verify_email))
+            .route("/auth/resend-verify", post(handlers::auth::resend_verify_code))
     } else {
         Router::new()
             .route("/health", get(handlers::health::health_check))
@@ -183,6 +208,8 @@ async fn main() -> anyhow::Result<()> {
             .route("/auth/register", post(handlers::auth::register))
             .route("/auth/login", post(handlers::auth::login))
             .route("/auth/refresh", post(handlers::auth::refresh))
+            // 崩溃报告（公开，客户端选择上传时调用）
+            .route("/crash", post(handlers::crash::submit_crash_report))
     };
 
     // ========== 受保护路由 ==========
@@ -230,12 +257,82 @@ async fn main() -> anyhow::Result<()> {
             "/api/ssh/sessions/:id",
             delete(handlers::ssh_session::delete_session_handler),
         )
+        // SSH 服务器安全公告列表
+        .route(
+            "/api/ssh/advisories",
+            get(handlers::advisory::get_advisories_handler),
+        )
         // 同步 API 路由
         .route("/api/sync", post(handlers::sync::sync_handler))
         .route(
             "/api/sync/resolve-conflict",
             post(handlers::sync::resolve_conflict_handler),
         )
+        // AI 代理（使用服务器凭据，按用户限额）
+        .route("/api/ai/chat", post(handlers::ai::chat_handler))
+        .route("/api/ai/quota", get(handlers::ai::get_quota_handler))
+        // 录制文件云端存储（上传的请求体为文件内容，单独放宽请求体大小限制）
+        .route(
+            "/api/recordings",
+            post(handlers::recording::upload_recording_handler)
+                .layer(DefaultBodyLimit::max(config.recording.max_file_size())),
+        )
+        .route(
+            "/api/recordings",
+            get(handlers::recording::list_recordings_handler),
+        )
+        .route(
+            "/api/recordings/:id",
+            delete(handlers::recording::delete_recording_handler),
+        )
+        .route(
+            "/api/recordings/:id/content",
+            get(handlers::recording::download_recording_handler),
+        )
+        .route(
+            "/api/recordings/:id/visibility",
+            put(handlers::recording::update_recording_visibility_handler),
+        )
+        // 使用热力图（各设备上报按小时汇总的使用量）
+        .route("/api/stats/heatmap", post(handlers::stats::usage_heatmap_handler))
+        // 设备 WebSocket（在线设备、会话接力）
+        .route("/api/devices/ws", get(handlers::device::device_ws_handler))
+        // 团队工作区（共享会话按角色限制，连接记录审计日志）
+        .route(
+            "/api/workspaces",
+            post(handlers::workspace::create_workspace_handler)
+                .get(handlers::workspace::list_workspaces_handler),
+        )
+        .route(
+            "/api/workspaces/:id/members",
+            get(handlers::workspace::list_members_handler)
+                .post(handlers::workspace::add_member_handler),
+        )
+        .route(
+            "/api/workspaces/:id/members/:user_id",
+            delete(handlers::workspace::remove_member_handler),
+        )
+        .route(
+            "/api/workspaces/:id/sessions",
+            get(handlers::workspace::list_sessions_handler)
+                .post(handlers::workspace::create_session_handler),
+        )
+        .route(
+            "/api/workspaces/:id/sessions/:session_id",
+            delete(handlers::workspace::delete_session_handler),
+        )
+        .route(
+            "/api/workspaces/:id/sessions/:session_id/credentials",
+            get(handlers::workspace::get_credentials_handler),
+        )
+        .route(
+            "/api/workspaces/:id/sessions/:session_id/connect",
+            post(handlers::workspace::connect_session_handler),
+        )
+        .route(
+            "/api/workspaces/:id/audit-logs",
+            get(handlers::workspace::list_audit_logs_handler),
+        )
         // 邮件状态路由（需要认证）
         .route(
             "/api/email/latest-log",
@@ -278,7 +375,8 @@ async fn main() -> anyhow::Result<()> {
     tracing::info!("Server listening on {}", addr);
     tracing::info!("Press Ctrl+C to stop");
 
-    axum::serve(listener, app).await?;
+    // 公开接口（如崩溃报告）按来源 IP 限频，需要连接信息
+    axum::serve(listener, app.into_make_service_with_connect_info::<std::net::SocketAddr>()).await?;
 
     Ok(())
 }
@@ -0,0 +1,57 @@
+use crate::domain::dto::crash::CrashReportRequest;
+use crate::domain::entities::crash_reports;
+use crate::utils::i18n::{MessageKey, t};
+use anyhow::Result;
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set};
+
+/// 崩溃报告仓库
+pub struct CrashReportRepository {
+    pub(crate) db: DatabaseConnection,
+}
+
+impl CrashReportRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// 保存崩溃报告
+    ///
+    /// 同一个客户端报告重复上传时返回已有记录
+    pub async fn create(&self, request: CrashReportRequest) -> Result<crash_reports::Model> {
+        if let Some(existing) = self.find_by_report_id(&request.report_id).await? {
+            return Ok(existing);
+        }
+
+        let report = crash_reports::ActiveModel {
+            id: Set(uuid::Uuid::new_v4().to_string()),
+            report_id: Set(request.report_id),
+            device_id: Set(request.device_id),
+            app_version: Set(request.app_version),
+            os: Set(request.os),
+            os_version: Set(request.os_version),
+            arch: Set(request.arch),
+            message: Set(request.message),
+            location: Set(request.location),
+            backtrace: Set(request.backtrace),
+            log_tail: Set(request.log_tail),
+            crashed_at: Set(request.crashed_at),
+            created_at: Set(chrono::Utc::now().timestamp()),
+        };
+
+        let result = report.insert(&self.db).await
+            .map_err(|e| anyhow::anyhow!("{}, {}", t(None, MessageKey::ErrorInsertFailed), e))?;
+
+        Ok(result)
+    }
+
+    /// 根据客户端报告 ID 查找
+    pub async fn find_by_report_id(&self, report_id: &str) -> Result<Option<crash_reports::Model>> {
+        let report = crash_reports::Entity::find()
+            .filter(crash_reports::Column::ReportId.eq(report_id))
+            .one(&self.db)
+            .await
+            .map_err(|e| anyhow::anyhow!("{}, {}", t(None, MessageKey::ErrorQueryFailed), e))?;
+
+        Ok(report)
+    }
+}
@@ -2,4 +2,9 @@ pub mod user_repository;
 pub mod user_profile_repository;
 pub mod ssh_session_repository;
 pub mod email_log_repository;
-
+pub mod crash_report_repository;
+pub mod recording_repository;
+pub mod session_group_repository;
+pub mod session_note_repository;
+pub mod usage_bucket_repository;
+pub mod workspace_repository;
@@ -0,0 +1,87 @@
+use crate::domain::entities::recordings::{self, Entity as Recording};
+use crate::utils::i18n::{t, MessageKey};
+use anyhow::Result;
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set};
+
+/// 云端录制文件仓库
+pub struct RecordingRepository {
+    db: DatabaseConnection,
+}
+
+impl RecordingRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// 保存录制元数据
+    /// 注意：id 是 TEXT 类型，使用 Entity::insert() 避免 SQLite last_insert_rowid() 问题
+    pub async fn create(&self, recording: recordings::Model) -> Result<recordings::Model> {
+        let id = recording.id.clone();
+        let active_model: recordings::ActiveModel = recording.into();
+
+        Recording::insert(active_model)
+            .exec(&self.db)
+            .await
+            .map_err(|e| anyhow::anyhow!("{}, {}", t(None, MessageKey::ErrorInsertFailed), e))?;
+
+        Recording::find_by_id(id)
+            .one(&self.db)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("{}", t(None, MessageKey::ErrorInsertQueryFailed)))
+    }
+
+    /// 根据 ID 查找
+    pub async fn find_by_id(&self, id: &str) -> Result<Option<recordings::Model>> {
+        let recording = Recording::find_by_id(id.to_string())
+            .one(&self.db)
+            .await
+            .map_err(|e| anyhow::anyhow!("{}, {}", t(None, MessageKey::ErrorQueryFailed), e))?;
+
+        Ok(recording)
+    }
+
+    /// 用户上传的全部录制（最新的在前）
+    pub async fn find_by_user_id(&self, user_id: &str) -> Result<Vec<recordings::Model>> {
+        let recordings = Recording::find()
+            .filter(recordings::Column::UserId.eq(user_id))
+            .order_by_desc(recordings::Column::CreatedAt)
+            .all(&self.db)
+            .await
+            .map_err(|e| anyhow::anyhow!("{}, {}", t(None, MessageKey::ErrorQueryFailed), e))?;
+
+        Ok(recordings)
+    }
+
+    /// 共享给所有用户的录制（最新的在前）
+    pub async fn find_shared(&self) -> Result<Vec<recordings::Model>> {
+        let recordings = Recording::find()
+            .filter(recordings::Column::Visibility.eq("shared"))
+            .order_by_desc(recordings::Column::CreatedAt)
+            .all(&self.db)
+            .await
+            .map_err(|e| anyhow::anyhow!("{}, {}", t(None, MessageKey::ErrorQueryFailed), e))?;
+
+        Ok(recordings)
+    }
+
+    /// 用户已使用的存储空间（字节）
+    pub async fn total_size_by_user(&self, user_id: &str) -> Result<i64> {
+        Ok(self.find_by_user_id(user_id).await?.iter().map(|r| r.size).sum())
+    }
+
+    /// 更新可见性
+    pub async fn update_visibility(&self, existing: recordings::Model, visibility: &str) -> Result<recordings::Model> {
+        let mut active_model: recordings::ActiveModel = existing.into();
+        active_model.visibility = Set(visibility.to_string());
+        active_model.updated_at = Set(chrono::Utc::now().timestamp()); // 应用层更新时间戳
+
+        let result = active_model.update(&self.db).await?;
+        Ok(result)
+    }
+
+    /// 删除录制元数据
+    pub async fn delete(&self, id: &str) -> Result<()> {
+        Recording::delete_by_id(id.to_string()).exec(&self.db).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,116 @@
+use crate::domain::entities::session_groups::{self, Entity as SessionGroup};
+use crate::utils::i18n::{t, MessageKey};
+use anyhow::Result;
+use sea_orm::prelude::Expr;
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set};
+
+/// 会话分组仓库
+pub struct SessionGroupRepository {
+    db: DatabaseConnection,
+}
+
+impl SessionGroupRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// 用户的全部分组（不含已删除）
+    pub async fn find_by_user_id(&self, user_id: &str) -> Result<Vec<session_groups::Model>> {
+        let groups = SessionGroup::find()
+            .filter(session_groups::Column::UserId.eq(user_id))
+            .filter(session_groups::Column::DeletedAt.is_null())
+            .order_by_asc(session_groups::Column::SortOrder)
+            .all(&self.db)
+            .await?;
+
+        Ok(groups)
+    }
+
+    /// 指定时间之后更新的分组（增量查询，包含已删除的分组，客户端据此删除本地分组）
+    pub async fn find_by_user_id_updated_after(&self, user_id: &str, after: i64) -> Result<Vec<session_groups::Model>> {
+        let groups = SessionGroup::find()
+            .filter(session_groups::Column::UserId.eq(user_id))
+            .filter(session_groups::Column::UpdatedAt.gt(after))
+            .order_by_asc(session_groups::Column::SortOrder)
+            .all(&self.db)
+            .await?;
+
+        Ok(groups)
+    }
+
+    /// 根据 ID 查找（包含已删除的分组）
+    pub async fn find_by_id(&self, id: &str) -> Result<Option<session_groups::Model>> {
+        let group = SessionGroup::find_by_id(id.to_string())
+            .one(&self.db)
+            .await?;
+
+        Ok(group)
+    }
+
+    /// 保存分组：不存在时创建，存在时覆盖（server_ver 递增，已删除的分组会恢复）
+    /// 注意：id 是 TEXT 类型，创建时使用 Entity::insert() 避免 SQLite last_insert_rowid() 问题
+    pub async fn save(&self, group: session_groups::Model) -> Result<session_groups::Model> {
+        let id = group.id.clone();
+
+        match self.find_by_id(&id).await? {
+            Some(existing) => {
+                let mut active_model: session_groups::ActiveModel = existing.clone().into();
+                active_model.name = Set(group.name);
+                active_model.color = Set(group.color);
+                active_model.icon = Set(group.icon);
+                active_model.sort_order = Set(group.sort_order);
+                active_model.description = Set(group.description);
+                active_model.server_ver = Set(existing.server_ver + 1); // 应用层递增
+                active_model.client_ver = Set(group.client_ver);
+                active_model.updated_at = Set(group.updated_at);
+                active_model.deleted_at = Set(None);
+
+                let result = active_model.update(&self.db).await?;
+                Ok(result)
+            }
+            None => {
+                let mut active_model: session_groups::ActiveModel = group.into();
+                active_model.server_ver = Set(1);
+
+                SessionGroup::insert(active_model)
+                    .exec(&self.db)
+                    .await
+                    .map_err(|e| anyhow::anyhow!("{}, {}", t(None, MessageKey::ErrorInsertFailed), e))?;
+
+                SessionGroup::find_by_id(id)
+                    .one(&self.db)
+                    .await?
+                    .ok_or_else(|| anyhow::anyhow!("{}", t(None, MessageKey::ErrorInsertQueryFailed)))
+            }
+        }
+    }
+
+    /// 软删除分组（使用指定时间戳），分组不存在时忽略
+    pub async fn soft_delete_with_time(&self, user_id: &str, id: &str, delete_time: i64) -> Result<()> {
+        SessionGroup::update_many()
+            .filter(session_groups::Column::Id.eq(id))
+            .filter(session_groups::Column::UserId.eq(user_id))
+            .filter(session_groups::Column::DeletedAt.is_null())
+            .col_expr(session_groups::Column::UpdatedAt, Expr::val(delete_time).into())
+            .col_expr(session_groups::Column::DeletedAt, Expr::val(delete_time).into())
+            .exec(&self.db)
+            .await?;
+
+        Ok(())
+    }
+
+    /// 根据 user_id 软删除所有分组
+    pub async fn soft_delete_by_user_id(&self, user_id: &str) -> Result<u64> {
+        let now = chrono::Utc::now().timestamp();
+
+        let result = SessionGroup::update_many()
+            .filter(session_groups::Column::UserId.eq(user_id))
+            .filter(session_groups::Column::DeletedAt.is_null())
+            .col_expr(session_groups::Column::DeletedAt, Expr::val(now).into())
+            .exec(&self.db)
+            .await
+            .map_err(|e| anyhow::anyhow!("{}, {}", t(None, MessageKey::ErrorBatchSoftDeleteFailed), e))?;
+
+        Ok(result.rows_affected)
+    }
+}
@@ -0,0 +1,209 @@
+use crate::domain::entities::session_attachments::{self, Entity as SessionAttachment};
+use crate::domain::entities::session_notes::{self, Entity as SessionNote};
+use crate::utils::i18n::{t, MessageKey};
+use anyhow::Result;
+use sea_orm::prelude::Expr;
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set};
+
+/// 会话笔记与附件仓库
+pub struct SessionNoteRepository {
+    db: DatabaseConnection,
+}
+
+impl SessionNoteRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    // ==================== 笔记 ====================
+
+    /// 用户的全部笔记（不含已删除）
+    pub async fn find_notes_by_user_id(&self, user_id: &str) -> Result<Vec<session_notes::Model>> {
+        let notes = SessionNote::find()
+            .filter(session_notes::Column::UserId.eq(user_id))
+            .filter(session_notes::Column::DeletedAt.is_null())
+            .all(&self.db)
+            .await?;
+
+        Ok(notes)
+    }
+
+    /// 指定时间之后更新的笔记（增量查询，包含已删除的笔记）
+    pub async fn find_notes_updated_after(&self, user_id: &str, after: i64) -> Result<Vec<session_notes::Model>> {
+        let notes = SessionNote::find()
+            .filter(session_notes::Column::UserId.eq(user_id))
+            .filter(session_notes::Column::UpdatedAt.gt(after))
+            .all(&self.db)
+            .await?;
+
+        Ok(notes)
+    }
+
+    /// 根据会话 ID 查找笔记（包含已删除的笔记）
+    pub async fn find_note(&self, session_id: &str) -> Result<Option<session_notes::Model>> {
+        let note = SessionNote::find_by_id(session_id.to_string())
+            .one(&self.db)
+            .await?;
+
+        Ok(note)
+    }
+
+    /// 保存笔记：不存在时创建，存在时覆盖（server_ver 递增，已删除的笔记会恢复）
+    pub async fn save_note(&self, note: session_notes::Model) -> Result<session_notes::Model> {
+        let session_id = note.session_id.clone();
+
+        match self.find_note(&session_id).await? {
+            Some(existing) => {
+                let mut active_model: session_notes::ActiveModel = existing.clone().into();
+                active_model.content = Set(note.content);
+                active_model.server_ver = Set(existing.server_ver + 1); // 应用层递增
+                active_model.client_ver = Set(note.client_ver);
+                active_model.updated_at = Set(note.updated_at);
+                active_model.deleted_at = Set(None);
+
+                let result = active_model.update(&self.db).await?;
+                Ok(result)
+            }
+            None => {
+                let mut active_model: session_notes::ActiveModel = note.into();
+                active_model.server_ver = Set(1);
+
+                SessionNote::insert(active_model)
+                    .exec(&self.db)
+                    .await
+                    .map_err(|e| anyhow::anyhow!("{}, {}", t(None, MessageKey::ErrorInsertFailed), e))?;
+
+                SessionNote::find_by_id(session_id)
+                    .one(&self.db)
+                    .await?
+                    .ok_or_else(|| anyhow::anyhow!("{}", t(None, MessageKey::ErrorInsertQueryFailed)))
+            }
+        }
+    }
+
+    /// 软删除笔记（使用指定时间戳），笔记不存在时忽略
+    pub async fn soft_delete_note(&self, user_id: &str, session_id: &str, delete_time: i64) -> Result<()> {
+        SessionNote::update_many()
+            .filter(session_notes::Column::SessionId.eq(session_id))
+            .filter(session_notes::Column::UserId.eq(user_id))
+            .filter(session_notes::Column::DeletedAt.is_null())
+            .col_expr(session_notes::Column::Content, Expr::val("").into())
+            .col_expr(session_notes::Column::UpdatedAt, Expr::val(delete_time).into())
+            .col_expr(session_notes::Column::DeletedAt, Expr::val(delete_time).into())
+            .exec(&self.db)
+            .await?;
+
+        Ok(())
+    }
+
+    // ==================== 附件 ====================
+
+    /// 用户的全部附件（不含已删除）
+    pub async fn find_attachments_by_user_id(&self, user_id: &str) -> Result<Vec<session_attachments::Model>> {
+        let attachments = SessionAttachment::find()
+            .filter(session_attachments::Column::UserId.eq(user_id))
+            .filter(session_attachments::Column::DeletedAt.is_null())
+            .order_by_asc(session_attachments::Column::CreatedAt)
+            .all(&self.db)
+            .await?;
+
+        Ok(attachments)
+    }
+
+    /// 指定时间之后更新的附件（增量查询，包含已删除的附件）
+    pub async fn find_attachments_updated_after(&self, user_id: &str, after: i64) -> Result<Vec<session_attachments::Model>> {
+        let attachments = SessionAttachment::find()
+            .filter(session_attachments::Column::UserId.eq(user_id))
+            .filter(session_attachments::Column::UpdatedAt.gt(after))
+            .order_by_asc(session_attachments::Column::CreatedAt)
+            .all(&self.db)
+            .await?;
+
+        Ok(attachments)
+    }
+
+    /// 根据 ID 查找附件（包含已删除的附件）
+    pub async fn find_attachment(&self, id: &str) -> Result<Option<session_attachments::Model>> {
+        let attachment = SessionAttachment::find_by_id(id.to_string())
+            .one(&self.db)
+            .await?;
+
+        Ok(attachment)
+    }
+
+    /// 保存附件：不存在时创建，存在时覆盖（server_ver 递增，已删除的附件会恢复）
+    pub async fn save_attachment(&self, attachment: session_attachments::Model) -> Result<session_attachments::Model> {
+        let id = attachment.id.clone();
+
+        match self.find_attachment(&id).await? {
+            Some(existing) => {
+                let mut active_model: session_attachments::ActiveModel = existing.clone().into();
+                active_model.session_id = Set(attachment.session_id);
+                active_model.file_name = Set(attachment.file_name);
+                active_model.mime_type = Set(attachment.mime_type);
+                active_model.size = Set(attachment.size);
+                active_model.content = Set(attachment.content);
+                active_model.server_ver = Set(existing.server_ver + 1); // 应用层递增
+                active_model.client_ver = Set(attachment.client_ver);
+                active_model.updated_at = Set(attachment.updated_at);
+                active_model.deleted_at = Set(None);
+
+                let result = active_model.update(&self.db).await?;
+                Ok(result)
+            }
+            None => {
+                let mut active_model: session_attachments::ActiveModel = attachment.into();
+                active_model.server_ver = Set(1);
+
+                SessionAttachment::insert(active_model)
+                    .exec(&self.db)
+                    .await
+                    .map_err(|e| anyhow::anyhow!("{}, {}", t(None, MessageKey::ErrorInsertFailed), e))?;
+
+                SessionAttachment::find_by_id(id)
+                    .one(&self.db)
+                    .await?
+                    .ok_or_else(|| anyhow::anyhow!("{}", t(None, MessageKey::ErrorInsertQueryFailed)))
+            }
+        }
+    }
+
+    /// 软删除附件（使用指定时间戳，同时清空内容），附件不存在时忽略
+    pub async fn soft_delete_attachment(&self, user_id: &str, id: &str, delete_time: i64) -> Result<()> {
+        SessionAttachment::update_many()
+            .filter(session_attachments::Column::Id.eq(id))
+            .filter(session_attachments::Column::UserId.eq(user_id))
+            .filter(session_attachments::Column::DeletedAt.is_null())
+            .col_expr(session_attachments::Column::Content, Expr::val("").into())
+            .col_expr(session_attachments::Column::UpdatedAt, Expr::val(delete_time).into())
+            .col_expr(session_attachments::Column::DeletedAt, Expr::val(delete_time).into())
+            .exec(&self.db)
+            .await?;
+
+        Ok(())
+    }
+
+    /// 根据 user_id 软删除所有笔记和附件
+    pub async fn soft_delete_by_user_id(&self, user_id: &str) -> Result<u64> {
+        let now = chrono::Utc::now().timestamp();
+
+        let notes = SessionNote::update_many()
+            .filter(session_notes::Column::UserId.eq(user_id))
+            .filter(session_notes::Column::DeletedAt.is_null())
+            .col_expr(session_notes::Column::DeletedAt, Expr::val(now).into())
+            .exec(&self.db)
+            .await
+            .map_err(|e| anyhow::anyhow!("{}, {}", t(None, MessageKey::ErrorBatchSoftDeleteFailed), e))?;
+
+        let attachments = SessionAttachment::update_many()
+            .filter(session_attachments::Column::UserId.eq(user_id))
+            .filter(session_attachments::Column::DeletedAt.is_null())
+            .col_expr(session_attachments::Column::Content, Expr::val("").into())
+            .col_expr(session_attachments::Column::DeletedAt, Expr::val(now).into())
+            .exec(&self.db)
+            .await
+            .map_err(|e| anyhow::anyhow!("{}, {}", t(None, MessageKey::ErrorBatchSoftDeleteFailed), e))?;
+
+        Ok(notes.rows_affected + attachments.rows_affected)
+    }
+}
@@ -119,7 +119,7 @@ impl SshSessionRepository {
             last_synced_at: sea_orm::Set(session.last_synced_at),
             created_at: sea_orm::Set(existing.created_at),
             updated_at: sea_orm::Set(now), // 应用层更新时间戳
-            deleted_at: sea_orm::Set(existing.deleted_at),
+            deleted_at: sea_orm::Set(session.deleted_at),
         };
 
         let result = active_model.update(&self.db).await?;
@@ -147,7 +147,8 @@ impl SshSessionRepository {
             auth_method_encrypted: sea_orm::Set(existing.auth_method_encrypted),
             auth_nonce: sea_orm::Set(existing.auth_nonce),
             auth_key_salt: sea_orm::Set(existing.auth_key_salt),
-            server_ver: sea_orm::Set(existing.server_ver),
+            // 删除也是一次服务器端变更：递增版本，使基于旧版本的推送产生冲突
+            server_ver: sea_orm::Set(existing.server_ver + 1),
             client_ver: sea_orm::Set(existing.client_ver),
             last_synced_at: sea_orm::Set(existing.last_synced_at),
             created_at: sea_orm::Set(existing.created_at),
@@ -194,7 +195,8 @@ impl SshSessionRepository {
             auth_method_encrypted: sea_orm::Set(existing.auth_method_encrypted),
             auth_nonce: sea_orm::Set(existing.auth_nonce),
             auth_key_salt: sea_orm::Set(existing.auth_key_salt),
-            server_ver: sea_orm::Set(existing.server_ver),
+            // 删除也是一次服务器端变更：递增版本，使基于旧版本的推送产生冲突
+            server_ver: sea_orm::Set(existing.server_ver + 1),
             client_ver: sea_orm::Set(existing.client_ver),
             last_synced_at: sea_orm::Set(existing.last_synced_at),
             created_at: sea_orm::Set(existing.created_at),
@@ -0,0 +1,59 @@
+use crate::domain::entities::usage_buckets::{self, Entity as UsageBucket};
+use crate::utils::i18n::{t, MessageKey};
+use anyhow::Result;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, TransactionTrait};
+
+/// 使用热力图分桶仓库
+pub struct UsageBucketRepository {
+    db: DatabaseConnection,
+}
+
+impl UsageBucketRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// 用新上报的分桶替换设备在 `from_date` 及之后的分桶
+    pub async fn replace_device_buckets(
+        &self,
+        user_id: &str,
+        device_id: &str,
+        from_date: &str,
+        buckets: Vec<usage_buckets::Model>,
+    ) -> Result<()> {
+        let txn = self.db.begin().await?;
+
+        UsageBucket::delete_many()
+            .filter(usage_buckets::Column::UserId.eq(user_id))
+            .filter(usage_buckets::Column::DeviceId.eq(device_id))
+            .filter(usage_buckets::Column::Date.gte(from_date))
+            .exec(&txn)
+            .await
+            .map_err(|e| anyhow::anyhow!("{}, {}", t(None, MessageKey::ErrorDeleteFailed), e))?;
+
+        if !buckets.is_empty() {
+            let active_models: Vec<usage_buckets::ActiveModel> = buckets.into_iter().map(Into::into).collect();
+            UsageBucket::insert_many(active_models)
+                .exec_without_returning(&txn)
+                .await
+                .map_err(|e| anyhow::anyhow!("{}, {}", t(None, MessageKey::ErrorInsertFailed), e))?;
+        }
+
+        txn.commit().await?;
+        Ok(())
+    }
+
+    /// 用户所有设备在 `from_date` 及之后的分桶
+    pub async fn find_by_user_since(&self, user_id: &str, from_date: &str) -> Result<Vec<usage_buckets::Model>> {
+        let buckets = UsageBucket::find()
+            .filter(usage_buckets::Column::UserId.eq(user_id))
+            .filter(usage_buckets::Column::Date.gte(from_date))
+            .order_by_asc(usage_buckets::Column::Date)
+            .order_by_asc(usage_buckets::Column::Hour)
+            .all(&self.db)
+            .await
+            .map_err(|e| anyhow::anyhow!("{}, {}", t(None, MessageKey::ErrorQueryFailed), e))?;
+
+        Ok(buckets)
+    }
+}
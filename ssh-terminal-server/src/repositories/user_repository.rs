@@ -89,7 +89,7 @@ impl UserRepository {
     /// 插入用户（created_at 和 updated_at 会自动填充），返回插入后的用户对象
     /// 注意：由于 id 是 TEXT 类型，需要使用 Entity::insert() 而非 ActiveModel::insert()
     /// 以避免 SQLite last_insert_rowid() 问题
-    pub async fn insert(&self, id: String, email: String, password_hash: String, device_id: String) -> Result<users::Model> {
+    pub async fn insert(&self, id: String, email: String, password_hash: String, device_id: String, email_verified: bool) -> Result<users::Model> {
         let user_id = id.clone();
         let now = chrono::Utc::now().timestamp();
 
@@ -98,6 +98,7 @@ impl UserRepository {
             email: Set(email),
             password_hash: Set(password_hash),
             device_id: Set(Some(device_id)),
+            email_verified: Set(email_verified),
             // 手动设置时间戳（Entity::insert 不会触发 ActiveModelBehavior）
             created_at: Set(now),
             updated_at: Set(now),
@@ -120,6 +121,22 @@ impl UserRepository {
         Ok(user)
     }
 
+    /// 标记邮箱已验证
+    pub async fn mark_email_verified(&self, user_id: &str) -> Result<()> {
+        let user = users::Entity::find_by_id(user_id)
+            .filter(users::Column::DeletedAt.is_null())
+            .one(&self.db)
+            .await
+            .map_err(|e| anyhow::anyhow!("{}, {}", t(None, MessageKey::ErrorQueryFailed), e))?
+            .ok_or_else(|| anyhow::anyhow!("{}", t(None, MessageKey::ErrorUserNotFoundOrDeleted)))?;
+
+        let mut user_active: users::ActiveModel = user.into();
+        user_active.email_verified = Set(true);
+        user_active.update(&self.db).await?;
+
+        Ok(())
+    }
+
     /// 根据 ID 删除用户（硬删除，已弃用，建议使用 soft_delete）
     #[allow(dead_code)]
     pub async fn delete_by_id(&self, id: &str) -> Result<()> {
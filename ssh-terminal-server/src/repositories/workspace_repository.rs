@@ -0,0 +1,191 @@
+use crate::domain::entities::shared_sessions::{self, Entity as SharedSession};
+use crate::domain::entities::workspace_audit_logs::{self, Entity as WorkspaceAuditLog};
+use crate::domain::entities::workspace_members::{self, Entity as WorkspaceMember};
+use crate::domain::entities::workspaces::{self, Entity as Workspace};
+use crate::utils::i18n::{t, MessageKey};
+use anyhow::Result;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, QuerySelect, TransactionTrait};
+
+/// 工作区、成员、共享会话与审计日志仓库
+pub struct WorkspaceRepository {
+    db: DatabaseConnection,
+}
+
+impl WorkspaceRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    // ==================== 工作区 ====================
+
+    /// 创建工作区，同时把创建者添加为 owner
+    /// 注意：id 是 TEXT 类型，使用 Entity::insert() 避免 SQLite last_insert_rowid() 问题
+    pub async fn create_workspace(&self, workspace: workspaces::Model, owner: workspace_members::Model) -> Result<()> {
+        let txn = self.db.begin().await?;
+
+        let workspace: workspaces::ActiveModel = workspace.into();
+        Workspace::insert(workspace)
+            .exec(&txn)
+            .await
+            .map_err(|e| anyhow::anyhow!("{}, {}", t(None, MessageKey::ErrorInsertFailed), e))?;
+
+        let owner: workspace_members::ActiveModel = owner.into();
+        WorkspaceMember::insert(owner)
+            .exec(&txn)
+            .await
+            .map_err(|e| anyhow::anyhow!("{}, {}", t(None, MessageKey::ErrorInsertFailed), e))?;
+
+        txn.commit().await?;
+        Ok(())
+    }
+
+    /// 用户加入的所有工作区及其角色
+    pub async fn find_workspaces_by_user(&self, user_id: &str) -> Result<Vec<(workspaces::Model, String)>> {
+        let memberships = WorkspaceMember::find()
+            .filter(workspace_members::Column::UserId.eq(user_id))
+            .all(&self.db)
+            .await
+            .map_err(|e| anyhow::anyhow!("{}, {}", t(None, MessageKey::ErrorQueryFailed), e))?;
+
+        let mut result = Vec::with_capacity(memberships.len());
+        for membership in memberships {
+            if let Some(workspace) = Workspace::find_by_id(membership.workspace_id).one(&self.db).await? {
+                result.push((workspace, membership.role));
+            }
+        }
+        result.sort_by_key(|(workspace, _)| workspace.created_at);
+
+        Ok(result)
+    }
+
+    // ==================== 成员 ====================
+
+    /// 查找用户在工作区中的成员记录
+    pub async fn find_member(&self, workspace_id: &str, user_id: &str) -> Result<Option<workspace_members::Model>> {
+        let member = WorkspaceMember::find()
+            .filter(workspace_members::Column::WorkspaceId.eq(workspace_id))
+            .filter(workspace_members::Column::UserId.eq(user_id))
+            .one(&self.db)
+            .await
+            .map_err(|e| anyhow::anyhow!("{}, {}", t(None, MessageKey::ErrorQueryFailed), e))?;
+
+        Ok(member)
+    }
+
+    /// 工作区的所有成员（按加入时间排序）
+    pub async fn find_members(&self, workspace_id: &str) -> Result<Vec<workspace_members::Model>> {
+        let members = WorkspaceMember::find()
+            .filter(workspace_members::Column::WorkspaceId.eq(workspace_id))
+            .order_by_asc(workspace_members::Column::CreatedAt)
+            .all(&self.db)
+            .await
+            .map_err(|e| anyhow::anyhow!("{}, {}", t(None, MessageKey::ErrorQueryFailed), e))?;
+
+        Ok(members)
+    }
+
+    /// 添加成员
+    pub async fn add_member(&self, member: workspace_members::Model) -> Result<()> {
+        let active_model: workspace_members::ActiveModel = member.into();
+        WorkspaceMember::insert(active_model)
+            .exec(&self.db)
+            .await
+            .map_err(|e| anyhow::anyhow!("{}, {}", t(None, MessageKey::ErrorInsertFailed), e))?;
+
+        Ok(())
+    }
+
+    /// 移除成员
+    pub async fn remove_member(&self, workspace_id: &str, user_id: &str) -> Result<()> {
+        WorkspaceMember::delete_many()
+            .filter(workspace_members::Column::WorkspaceId.eq(workspace_id))
+            .filter(workspace_members::Column::UserId.eq(user_id))
+            .exec(&self.db)
+            .await?;
+
+        Ok(())
+    }
+
+    /// 移除用户的所有成员记录（注销账号时调用）
+    pub async fn remove_memberships_by_user_id(&self, user_id: &str) -> Result<u64> {
+        let result = WorkspaceMember::delete_many()
+            .filter(workspace_members::Column::UserId.eq(user_id))
+            .exec(&self.db)
+            .await?;
+
+        Ok(result.rows_affected)
+    }
+
+    // ==================== 共享会话 ====================
+
+    /// 工作区的所有共享会话（按名称排序）
+    pub async fn find_sessions(&self, workspace_id: &str) -> Result<Vec<shared_sessions::Model>> {
+        let sessions = SharedSession::find()
+            .filter(shared_sessions::Column::WorkspaceId.eq(workspace_id))
+            .order_by_asc(shared_sessions::Column::Name)
+            .all(&self.db)
+            .await
+            .map_err(|e| anyhow::anyhow!("{}, {}", t(None, MessageKey::ErrorQueryFailed), e))?;
+
+        Ok(sessions)
+    }
+
+    /// 查找工作区中的共享会话
+    pub async fn find_session(&self, workspace_id: &str, id: &str) -> Result<Option<shared_sessions::Model>> {
+        let session = SharedSession::find_by_id(id.to_string())
+            .filter(shared_sessions::Column::WorkspaceId.eq(workspace_id))
+            .one(&self.db)
+            .await
+            .map_err(|e| anyhow::anyhow!("{}, {}", t(None, MessageKey::ErrorQueryFailed), e))?;
+
+        Ok(session)
+    }
+
+    /// 创建共享会话
+    pub async fn create_session(&self, session: shared_sessions::Model) -> Result<shared_sessions::Model> {
+        let id = session.id.clone();
+        let active_model: shared_sessions::ActiveModel = session.into();
+
+        SharedSession::insert(active_model)
+            .exec(&self.db)
+            .await
+            .map_err(|e| anyhow::anyhow!("{}, {}", t(None, MessageKey::ErrorInsertFailed), e))?;
+
+        SharedSession::find_by_id(id)
+            .one(&self.db)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("{}", t(None, MessageKey::ErrorInsertQueryFailed)))
+    }
+
+    /// 删除共享会话
+    pub async fn delete_session(&self, id: &str) -> Result<()> {
+        SharedSession::delete_by_id(id.to_string()).exec(&self.db).await?;
+        Ok(())
+    }
+
+    // ==================== 审计日志 ====================
+
+    /// 写入审计日志
+    pub async fn add_audit_log(&self, log: workspace_audit_logs::Model) -> Result<()> {
+        let active_model: workspace_audit_logs::ActiveModel = log.into();
+        WorkspaceAuditLog::insert(active_model)
+            .exec(&self.db)
+            .await
+            .map_err(|e| anyhow::anyhow!("{}, {}", t(None, MessageKey::ErrorInsertFailed), e))?;
+
+        Ok(())
+    }
+
+    /// 工作区最近的审计日志（最新的在前）
+    pub async fn find_audit_logs(&self, workspace_id: &str, limit: u64) -> Result<Vec<workspace_audit_logs::Model>> {
+        let logs = WorkspaceAuditLog::find()
+            .filter(workspace_audit_logs::Column::WorkspaceId.eq(workspace_id))
+            .order_by_desc(workspace_audit_logs::Column::CreatedAt)
+            .limit(limit)
+            .all(&self.db)
+            .await
+            .map_err(|e| anyhow::anyhow!("{}, {}", t(None, MessageKey::ErrorQueryFailed), e))?;
+
+        Ok(logs)
+    }
+}
@@ -7,18 +7,19 @@ use chrono::Utc;
 use jsonwebtoken::{decode, DecodingKey, Validation};
 use rand::Rng;
 
-use crate::config::auth::AuthConfig;
+use crate::config::auth::{AuthConfig, EmailVerificationMode};
 use crate::config::email::EmailConfig;
-use crate::domain::dto::auth::{DeleteUserRequest, LoginRequest, RegisterRequest};
+use crate::domain::dto::auth::{DeleteUserRequest, LoginRequest, RegisterRequest, VerifyEmailRequest};
 use crate::domain::entities::user_profiles;
 use crate::domain::entities::users;
+use crate::infra::mail::rate_limit::{MailRateLimit, MAX_VERIFY_CODE_FAILURES};
 use crate::infra::redis::{
     redis_client::RedisClient,
     redis_key::{BusinessType, RedisKey},
 };
 use crate::repositories::user_profile_repository::UserProfileRepository;
 use crate::repositories::user_repository::UserRepository;
-use crate::utils::i18n::{t, MessageKey};
+use crate::utils::i18n::{t, t_with_vars, MessageKey};
 use crate::utils::jwt::Claims;
 use crate::utils::jwt::TokenService;
 
@@ -220,43 +221,135 @@ impl AuthService {
         Ok(())
     }
 
-    /// 注册用户
-    pub async fn register(
-        &self,
-        request: RegisterRequest,
-        language: Option<&str>,
-    ) -> Result<(users::Model, String, String)> {
-        // 0. 如果启用了邮件验证，检查验证码
-        if self.email_config.enabled {
-            let verify_code = request.verify_code.as_ref().ok_or_else(|| {
-                anyhow::anyhow!("{}", t(language, MessageKey::ErrorVerifyCodeRequired))
-            })?;
-
-            // 从 Redis 获取存储的验证码
-            let key = RedisKey::new(BusinessType::Auth)
-                .add_identifier("verify_code")
-                .add_identifier(&request.email);
+    /// 校验邮箱验证码，成功后删除（一次性使用）
+    ///
+    /// 同一个验证码输错 [`MAX_VERIFY_CODE_FAILURES`] 次后作废，需要重新发送，防止暴力枚举
+    async fn consume_verify_code(&self, email: &str, verify_code: &str, language: Option<&str>) -> Result<()> {
+        // 从 Redis 获取存储的验证码
+        let key = RedisKey::new(BusinessType::Auth)
+            .add_identifier("verify_code")
+            .add_identifier(email);
 
-            let stored_code = self.redis_client.get_key(&key).await.map_err(|e| {
-                anyhow::anyhow!("{}: {}", t(language, MessageKey::ErrorRedisQueryFailed), e)
-            })?;
+        let stored_code = self.redis_client.get_key(&key).await.map_err(|e| {
+            anyhow::anyhow!("{}: {}", t(language, MessageKey::ErrorRedisQueryFailed), e)
+        })?;
 
-            let stored_code = stored_code.ok_or_else(|| {
-                anyhow::anyhow!("{}", t(language, MessageKey::ErrorVerifyCodeExpired))
-            })?;
+        let stored_code = stored_code.ok_or_else(|| {
+            anyhow::anyhow!("{}", t(language, MessageKey::ErrorVerifyCodeExpired))
+        })?;
 
-            // 验证验证码
-            if verify_code.as_str() != stored_code {
+        let rate_limit = MailRateLimit::new(self.redis_client.clone());
+        if verify_code != stored_code {
+            let failures = rate_limit.record_verify_failure(email).await?;
+            if failures < MAX_VERIFY_CODE_FAILURES {
                 return Err(anyhow::anyhow!(
                     "{}",
                     t(language, MessageKey::ErrorVerifyCodeInvalid)
                 ));
             }
 
-            // 验证成功后，删除验证码（一次性使用）
+            tracing::warn!("Verify code for {} discarded after {} failed attempts", email, failures);
             self.redis_client.del(&key.to_string()).await.map_err(|e| {
                 anyhow::anyhow!("{}: {}", t(language, MessageKey::ErrorRedisDeleteFailed), e)
             })?;
+            rate_limit.clear_verify_failures(email).await?;
+            // 之后需要重新发送验证码，提示距离可以重新发送的时间
+            let ttl = match rate_limit.get_rate_ttl(email).await {
+                Ok(ttl) if ttl > 0 => ttl,
+                _ => 60,
+            };
+            return Err(anyhow::anyhow!(
+                "{}",
+                t_with_vars(language, MessageKey::ErrorEmailRateLimit, &[("ttl", &ttl.to_string())])
+            ));
+        }
+
+        self.redis_client.del(&key.to_string()).await.map_err(|e| {
+            anyhow::anyhow!("{}: {}", t(language, MessageKey::ErrorRedisDeleteFailed), e)
+        })?;
+        rate_limit.clear_verify_failures(email).await?;
+
+        Ok(())
+    }
+
+    /// 注册后验证邮箱
+    pub async fn verify_email(&self, request: VerifyEmailRequest, language: Option<&str>) -> Result<()> {
+        let user = self
+            .user_repo
+            .find_by_email(&request.email)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("{}", t(language, MessageKey::ErrorUserNotFound)))?;
+
+        if user.email_verified {
+            return Err(anyhow::anyhow!(
+                "{}",
+                t(language, MessageKey::ErrorEmailAlreadyVerified)
+            ));
+        }
+
+        self.consume_verify_code(&request.email, &request.verify_code, language).await?;
+        self.user_repo.mark_email_verified(&user.id).await?;
+
+        Ok(())
+    }
+
+    /// 未验证邮箱的账号超过宽限期后拒绝登录和刷新 token
+    fn check_email_verified(&self, user: &users::Model, language: Option<&str>) -> Result<()> {
+        if self.email_config.enabled && !user.email_verified {
+            let deadline = self.auth_config.unverified_login_deadline(user.created_at);
+            if deadline.is_some_and(|deadline| Utc::now().timestamp() > deadline) {
+                return Err(anyhow::anyhow!(
+                    "{}",
+                    t(language, MessageKey::ErrorEmailNotVerified)
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// 检查账号是否需要（重新）发送验证邮件：账号存在且邮箱未验证
+    pub async fn check_resend_allowed(&self, email: &str, language: Option<&str>) -> Result<()> {
+        let user = self
+            .user_repo
+            .find_by_email(email)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("{}", t(language, MessageKey::ErrorUserNotFound)))?;
+
+        if user.email_verified {
+            return Err(anyhow::anyhow!(
+                "{}",
+                t(language, MessageKey::ErrorEmailAlreadyVerified)
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// 注册用户
+    pub async fn register(
+        &self,
+        request: RegisterRequest,
+        language: Option<&str>,
+    ) -> Result<(users::Model, String, String)> {
+        // 0. 如果启用了邮件验证，检查验证码（grace/off 模式下可以先注册，之后再验证）
+        // 不强制验证时（未启用邮件功能或 off 模式）视为已验证，
+        // 避免之后开启验证时宽限期早已从注册时间算起过期，导致这些账号立即无法登录
+        let mut email_verified = !self.email_config.enabled
+            || self.auth_config.email_verification == EmailVerificationMode::Off;
+        if self.email_config.enabled {
+            match request.verify_code.as_deref() {
+                Some(verify_code) => {
+                    self.consume_verify_code(&request.email, verify_code, language).await?;
+                    email_verified = true;
+                }
+                None if self.auth_config.email_verification == EmailVerificationMode::Required => {
+                    return Err(anyhow::anyhow!(
+                        "{}",
+                        t(language, MessageKey::ErrorVerifyCodeRequired)
+                    ));
+                }
+                None => {}
+            }
         }
 
         // 1. 检查邮箱是否已存在
@@ -281,7 +374,7 @@ impl AuthService {
         // 5. 插入数据库并获取包含真实 created_at 的用户对象
         let user = self
             .user_repo
-            .insert(user_id.clone(), request.email, password_hash, device_id)
+            .insert(user_id.clone(), request.email, password_hash, device_id, email_verified)
             .await?;
 
         // 6. 生成 token
@@ -351,6 +444,9 @@ impl AuthService {
                 anyhow::anyhow!("{}", t(language, MessageKey::ErrorEmailOrPasswordIncorrect))
             })?;
 
+        // 2.1 未验证邮箱的账号超过宽限期后需要先验证
+        self.check_email_verified(&user, language)?;
+
         // 3. 生成 token
         let (access_token, refresh_token) = TokenService::generate_token_pair(
             &user.id,
@@ -384,7 +480,15 @@ impl AuthService {
             ));
         }
 
-        // 3. 生成新的 token 对
+        // 3. 未验证邮箱的账号超过宽限期后不能继续续期
+        let user = self
+            .user_repo
+            .find_by_id_raw(&user_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("{}", t(language, MessageKey::ErrorUserNotFound)))?;
+        self.check_email_verified(&user, language)?;
+
+        // 4. 生成新的 token 对
         let (new_access_token, new_refresh_token) = TokenService::generate_token_pair(
             &user_id,
             self.auth_config.access_token_expiration_minutes,
@@ -392,7 +496,7 @@ impl AuthService {
             &self.auth_config.jwt_secret,
         )?;
 
-        // 4. 将新 token 添加到 Set 中
+        // 5. 将新 token 添加到 Set 中
         self.save_refresh_token(
             &user_id,
             &new_refresh_token,
@@ -441,6 +545,27 @@ impl AuthService {
             .soft_delete_by_user_id(&request.user_id)
             .await?;
 
+        // 软删除用户的所有会话分组
+        crate::repositories::session_group_repository::SessionGroupRepository::new(
+            self.user_repo.get_db(),
+        )
+        .soft_delete_by_user_id(&request.user_id)
+        .await?;
+
+        // 软删除用户同步的会话笔记和附件
+        crate::repositories::session_note_repository::SessionNoteRepository::new(
+            self.user_repo.get_db(),
+        )
+        .soft_delete_by_user_id(&request.user_id)
+        .await?;
+
+        // 退出所有工作区
+        crate::repositories::workspace_repository::WorkspaceRepository::new(
+            self.user_repo.get_db(),
+        )
+        .remove_memberships_by_user_id(&request.user_id)
+        .await?;
+
         // 删除 Redis 中的 refresh_token
         self.delete_refresh_token(&request.user_id).await?;
 
@@ -3,7 +3,7 @@ use crate::domain::dto::mail::MailTaskDto;
 use crate::domain::vo::mail::EmailResult;
 use crate::infra::mail::mailer::{Mailer, SmtpConfig};
 use crate::infra::mail::queue::MailQueue;
-use crate::infra::mail::rate_limit::MailRateLimit;
+use crate::infra::mail::rate_limit::{MailRateLimit, VERIFY_CODE_TTL_SECS};
 use crate::infra::redis::redis_client::RedisClient;
 use crate::infra::redis::redis_key::{BusinessType, RedisKey};
 use crate::repositories::email_log_repository::EmailLogRepository;
@@ -95,9 +95,14 @@ impl MailService {
             .await
             .context("Failed to save verify code to Redis")?;
         self.redis_client
-            .expire(&verify_key.to_string(), 300) // 5分钟 = 300秒
+            .expire(&verify_key.to_string(), VERIFY_CODE_TTL_SECS)
             .await
             .context("Failed to set verify code expiration")?;
+        // 新验证码重新计算输错次数
+        rate_limit
+            .clear_verify_failures(email)
+            .await
+            .context("Failed to reset verify code failures")?;
 
         // 5. 创建邮件日志记录
         self.email_log_repo
@@ -182,9 +187,14 @@ impl MailService {
             .await
             .context("Failed to save verify code to Redis")?;
         self.redis_client
-            .expire(&verify_key.to_string(), 300) // 5分钟 = 300秒
+            .expire(&verify_key.to_string(), VERIFY_CODE_TTL_SECS)
             .await
             .context("Failed to set verify code expiration")?;
+        // 新验证码重新计算输错次数
+        rate_limit
+            .clear_verify_failures(email)
+            .await
+            .context("Failed to reset verify code failures")?;
 
         // 5. 创建邮件日志记录
         self.email_log_repo
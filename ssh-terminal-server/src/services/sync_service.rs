@@ -2,6 +2,8 @@ use anyhow::Result;
 use sea_orm::DatabaseConnection;
 use crate::domain::dto::sync::*;
 use crate::domain::vo::sync::*;
+use crate::repositories::session_group_repository::SessionGroupRepository;
+use crate::repositories::session_note_repository::SessionNoteRepository;
 use crate::repositories::ssh_session_repository::SshSessionRepository;
 use crate::repositories::user_profile_repository::UserProfileRepository;
 use crate::repositories::user_repository::UserRepository;
@@ -9,6 +11,9 @@ use crate::utils::i18n::{t, t_with_vars, MessageKey};
 use chrono::Utc;
 use uuid;
 
+/// 可同步的单个附件大小上限（字节），更大的附件只保存在客户端本地
+pub const MAX_SYNC_ATTACHMENT_SIZE: usize = 1024 * 1024;
+
 pub struct SyncService {
     db: DatabaseConnection,
 }
@@ -35,6 +40,8 @@ impl SyncService {
 
         let ssh_repo = SshSessionRepository::new(self.db.clone());
         let profile_repo = UserProfileRepository::new(self.db.clone());
+        let group_repo = SessionGroupRepository::new(self.db.clone());
+        let note_repo = SessionNoteRepository::new(self.db.clone());
 
         // === 统一的服务器时间 ===
         let server_time = Utc::now().timestamp();
@@ -177,7 +184,12 @@ impl SyncService {
                             last_synced_at: existing.last_synced_at,
                             created_at: existing.created_at,
                             updated_at: last_sync_at,
-                            deleted_at: existing.deleted_at,
+                            // 只有显式恢复的会话才清除软删除标记，普通更新保持原删除状态
+                            deleted_at: if request.restored_session_ids.contains(&session_item.id) {
+                                None
+                            } else {
+                                existing.deleted_at
+                            },
                         };
 
                         match ssh_repo.update(&session_item.id, updated).await {
@@ -243,6 +255,130 @@ impl SyncService {
             }
         }
 
+        // 4. 处理会话分组（只有展示元数据，不做冲突检测，以最后推送的为准）
+        let mut group_versions = std::collections::HashMap::new();
+        for group_item in &request.session_groups {
+            if let Ok(Some(existing)) = group_repo.find_by_id(&group_item.id).await {
+                if existing.user_id != user_id {
+                    tracing::warn!("Skipping session group owned by another user: {}", group_item.id);
+                    continue;
+                }
+            }
+
+            let group = crate::domain::entities::session_groups::Model {
+                id: group_item.id.clone(),
+                user_id: user_id.to_string(),
+                name: group_item.name.clone(),
+                color: group_item.color.clone(),
+                icon: group_item.icon.clone(),
+                sort_order: group_item.sort_order,
+                description: group_item.description.clone(),
+                server_ver: 1,
+                client_ver: group_item.client_ver,
+                created_at: group_item.created_at,
+                updated_at: last_sync_at,
+                deleted_at: None,
+            };
+
+            match group_repo.save(group).await {
+                Ok(saved) => {
+                    group_versions.insert(saved.id, saved.server_ver);
+                }
+                Err(e) => {
+                    tracing::error!("Failed to save session group {}: {}", group_item.id, e);
+                }
+            }
+        }
+
+        for group_id in &request.deleted_group_ids {
+            if let Err(e) = group_repo.soft_delete_with_time(user_id, group_id, last_sync_at).await {
+                tracing::error!("Failed to delete session group {}: {}", group_id, e);
+            }
+        }
+
+        // 5. 处理会话笔记和附件（用户选择同步的才会推送，以最后推送的为准）
+        let mut note_versions = std::collections::HashMap::new();
+        for note_item in &request.session_notes {
+            if let Ok(Some(existing)) = note_repo.find_note(&note_item.session_id).await {
+                if existing.user_id != user_id {
+                    tracing::warn!("Skipping session note owned by another user: {}", note_item.session_id);
+                    continue;
+                }
+            }
+
+            let note = crate::domain::entities::session_notes::Model {
+                session_id: note_item.session_id.clone(),
+                user_id: user_id.to_string(),
+                content: note_item.content.clone(),
+                server_ver: 1,
+                client_ver: note_item.client_ver,
+                created_at: note_item.created_at,
+                updated_at: last_sync_at,
+                deleted_at: None,
+            };
+
+            match note_repo.save_note(note).await {
+                Ok(saved) => {
+                    note_versions.insert(saved.session_id, saved.server_ver);
+                }
+                Err(e) => {
+                    tracing::error!("Failed to save session note {}: {}", note_item.session_id, e);
+                }
+            }
+        }
+
+        for session_id in &request.deleted_note_ids {
+            if let Err(e) = note_repo.soft_delete_note(user_id, session_id, last_sync_at).await {
+                tracing::error!("Failed to delete session note {}: {}", session_id, e);
+            }
+        }
+
+        let mut attachment_versions = std::collections::HashMap::new();
+        for attachment_item in &request.session_attachments {
+            // Base64 编码后长度约为原始大小的 4/3
+            if attachment_item.content.len() > MAX_SYNC_ATTACHMENT_SIZE.div_ceil(3) * 4 {
+                tracing::warn!("Skipping oversized session attachment: {}", attachment_item.id);
+                continue;
+            }
+
+            if let Ok(Some(existing)) = note_repo.find_attachment(&attachment_item.id).await {
+                if existing.user_id != user_id {
+                    tracing::warn!("Skipping session attachment owned by another user: {}", attachment_item.id);
+                    continue;
+                }
+            }
+
+            let attachment = crate::domain::entities::session_attachments::Model {
+                id: attachment_item.id.clone(),
+                session_id: attachment_item.session_id.clone(),
+                user_id: user_id.to_string(),
+                file_name: attachment_item.file_name.clone(),
+                mime_type: attachment_item.mime_type.clone(),
+                size: attachment_item.size,
+                content: attachment_item.content.clone(),
+                server_ver: 1,
+                client_ver: attachment_item.client_ver,
+                created_at: attachment_item.created_at,
+                updated_at: last_sync_at,
+                deleted_at: None,
+            };
+
+            match note_repo.save_attachment(attachment).await {
+                Ok(saved) => {
+                    attachment_versions.insert(saved.id, saved.server_ver);
+                }
+                Err(e) => {
+                    tracing::error!("Failed to save session attachment {}: {}", attachment_item.id, e);
+                }
+            }
+        }
+
+        for attachment_id in &request.deleted_attachment_ids {
+            if let Err(e) = note_repo.soft_delete_attachment(user_id, attachment_id, last_sync_at).await {
+                tracing::error!("Failed to delete session attachment {}: {}", attachment_id, e);
+            }
+        }
+
         // === 第二阶段：Pull - 拉取最新的服务器数据 ===
         // 增量拉取 SSH 会话：
         // - 如果有 last_sync_at，只返回该时间之后更新的会话
@@ -273,6 +409,36 @@ impl SyncService {
                 .collect()
         };
 
+        // 增量拉取会话分组（增量时包含已删除的分组，首次同步只返回未删除的）
+        let session_groups = match request.last_sync_at {
+            Some(last_sync) => group_repo.find_by_user_id_updated_after(user_id, last_sync).await?,
+            None => group_repo.find_by_user_id(user_id).await?,
+        };
+        let session_groups_vo = session_groups
+            .into_iter()
+            .map(|g| self.group_to_vo(g))
+            .collect();
+
+        // 增量拉取会话笔记和附件（增量时包含已删除的，首次同步只返回未删除的）
+        let (session_notes, session_attachments) = match request.last_sync_at {
+            Some(last_sync) => (
+                note_repo.find_notes_updated_after(user_id, last_sync).await?,
+                note_repo.find_attachments_updated_after(user_id, last_sync).await?,
+            ),
+            None => (
+                note_repo.find_notes_by_user_id(user_id).await?,
+                note_repo.find_attachments_by_user_id(user_id).await?,
+            ),
+        };
+        let session_notes_vo = session_notes
+            .into_iter()
+            .map(|n| self.note_to_vo(n))
+            .collect();
+        let session_attachments_vo = session_attachments
+            .into_iter()
+            .map(|a| self.attachment_to_vo(a))
+            .collect();
+
         // 增量拉取用户资料：
         // - 如果有 last_sync_at，只返回该时间之后更新的资料
         // - 首次同步（last_sync_at 为 None）返回所有资料
@@ -344,6 +510,12 @@ impl SyncService {
             server_versions,
             user_profile: user_profile_vo,
             ssh_sessions: ssh_sessions_vo,
+            session_groups: session_groups_vo,
+            group_versions,
+            session_notes: session_notes_vo,
+            note_versions,
+            session_attachments: session_attachments_vo,
+            attachment_versions,
             conflicts,
             message,
         })
@@ -494,6 +666,56 @@ impl SyncService {
         }
     }
 
+    /// 将 Session Group Model 转换为 VO
+    fn group_to_vo(&self, group: crate::domain::entities::session_groups::Model) -> crate::domain::vo::ssh::SessionGroupVO {
+        crate::domain::vo::ssh::SessionGroupVO {
+            id: group.id,
+            user_id: group.user_id,
+            name: group.name,
+            color: group.color,
+            icon: group.icon,
+            sort_order: group.sort_order,
+            description: group.description,
+            server_ver: group.server_ver,
+            client_ver: group.client_ver,
+            created_at: group.created_at,
+            updated_at: group.updated_at,
+            deleted_at: group.deleted_at,
+        }
+    }
+
+    /// 将 Session Note Model 转换为 VO
+    fn note_to_vo(&self, note: crate::domain::entities::session_notes::Model) -> crate::domain::vo::ssh::SessionNoteVO {
+        crate::domain::vo::ssh::SessionNoteVO {
+            session_id: note.session_id,
+            user_id: note.user_id,
+            content: note.content,
+            server_ver: note.server_ver,
+            client_ver: note.client_ver,
+            created_at: note.created_at,
+            updated_at: note.updated_at,
+            deleted_at: note.deleted_at,
+        }
+    }
+
+    /// 将 Session Attachment Model 转换为 VO
+    fn attachment_to_vo(&self, attachment: crate::domain::entities::session_attachments::Model) -> crate::domain::vo::ssh::SessionAttachmentVO {
+        crate::domain::vo::ssh::SessionAttachmentVO {
+            id: attachment.id,
+            session_id: attachment.session_id,
+            user_id: attachment.user_id,
+            file_name: attachment.file_name,
+            mime_type: attachment.mime_type,
+            size: attachment.size,
+            content: attachment.content,
+            server_ver: attachment.server_ver,
+            client_ver: attachment.client_ver,
+            created_at: attachment.created_at,
+            updated_at: attachment.updated_at,
+            deleted_at: attachment.deleted_at,
+        }
+    }
+
     /// 将 User Profile Model 转换为 VO
     fn profile_to_vo(&self, profile: crate::domain::entities::user_profiles::Model, email: String) -> crate::domain::vo::user::UserProfileVO {
         crate::domain::vo::user::UserProfileVO {
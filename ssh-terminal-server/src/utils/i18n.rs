@@ -33,6 +33,26 @@ pub enum MessageKey {
     SuccessKeepServer,
     SuccessKeepLocal,
     SuccessKeepBoth,
+    SuccessCrashReport,
+    SuccessAiChat,
+    SuccessAiQuota,
+    SuccessUploadRecording,
+    SuccessListRecordings,
+    SuccessUpdateRecording,
+    SuccessDeleteRecording,
+    SuccessUsageHeatmap,
+    SuccessSshAdvisories,
+    SuccessCreateWorkspace,
+    SuccessListWorkspaces,
+    SuccessListWorkspaceMembers,
+    SuccessAddWorkspaceMember,
+    SuccessRemoveWorkspaceMember,
+    SuccessListSharedSessions,
+    SuccessShareSession,
+    SuccessDeleteSharedSession,
+    SuccessGetSharedCredentials,
+    SuccessConnectSharedSession,
+    SuccessListAuditLogs,
 
     // ==================== Error Messages ====================
     ErrorDefault,
@@ -89,6 +109,7 @@ pub enum MessageKey {
     ErrorInvalidToken,
     ErrorVerifyUserFailed,
     ErrorUserIdNotFound,
+    ErrorDeviceOffline,
 
     // ==================== Conflict Messages ====================
     ConflictVersionConflict,
@@ -114,6 +135,9 @@ pub enum MessageKey {
     ErrorEmailInvalidAddress,
     ErrorEmailConnectionFailed,
     ErrorEmailTimeout,
+    SuccessEmailVerified,
+    ErrorEmailNotVerified,
+    ErrorEmailAlreadyVerified,
 }
 
 impl MessageKey {
@@ -143,6 +167,26 @@ impl MessageKey {
             MessageKey::SuccessKeepServer => "api.success.keep_server",
             MessageKey::SuccessKeepLocal => "api.success.keep_local",
             MessageKey::SuccessKeepBoth => "api.success.keep_both",
+            MessageKey::SuccessCrashReport => "api.success.crash_report",
+            MessageKey::SuccessAiChat => "api.success.ai_chat",
+            MessageKey::SuccessAiQuota => "api.success.ai_quota",
+            MessageKey::SuccessUploadRecording => "api.success.upload_recording",
+            MessageKey::SuccessListRecordings => "api.success.list_recordings",
+            MessageKey::SuccessUpdateRecording => "api.success.update_recording",
+            MessageKey::SuccessDeleteRecording => "api.success.delete_recording",
+            MessageKey::SuccessUsageHeatmap => "api.success.usage_heatmap",
+            MessageKey::SuccessSshAdvisories => "api.success.ssh_advisories",
+            MessageKey::SuccessCreateWorkspace => "api.success.create_workspace",
+            MessageKey::SuccessListWorkspaces => "api.success.list_workspaces",
+            MessageKey::SuccessListWorkspaceMembers => "api.success.list_workspace_members",
+            MessageKey::SuccessAddWorkspaceMember => "api.success.add_workspace_member",
+            MessageKey::SuccessRemoveWorkspaceMember => "api.success.remove_workspace_member",
+            MessageKey::SuccessListSharedSessions => "api.success.list_shared_sessions",
+            MessageKey::SuccessShareSession => "api.success.share_session",
+            MessageKey::SuccessDeleteSharedSession => "api.success.delete_shared_session",
+            MessageKey::SuccessGetSharedCredentials => "api.success.get_shared_credentials",
+            MessageKey::SuccessConnectSharedSession => "api.success.connect_shared_session",
+            MessageKey::SuccessListAuditLogs => "api.success.list_audit_logs",
 
             // Error
             MessageKey::ErrorDefault => "api.error.default",
@@ -199,6 +243,7 @@ impl MessageKey {
             MessageKey::ErrorInvalidToken => "api.error.invalid_token",
             MessageKey::ErrorVerifyUserFailed => "api.error.verify_user_failed",
             MessageKey::ErrorUserIdNotFound => "api.error.user_id_not_found",
+            MessageKey::ErrorDeviceOffline => "api.error.device_offline",
 
             // Conflict
             MessageKey::ConflictVersionConflict => "api.conflict.version_conflict",
@@ -224,6 +269,9 @@ impl MessageKey {
             MessageKey::ErrorEmailInvalidAddress => "api.email.error_invalid_address",
             MessageKey::ErrorEmailConnectionFailed => "api.email.error_connection_failed",
             MessageKey::ErrorEmailTimeout => "api.email.error_timeout",
+            MessageKey::SuccessEmailVerified => "api.email.success_verified",
+            MessageKey::ErrorEmailNotVerified => "api.email.error_not_verified",
+            MessageKey::ErrorEmailAlreadyVerified => "api.email.error_already_verified",
         }
     }
 }
@@ -300,7 +348,27 @@ static MESSAGES: Lazy<serde_json::Value> = Lazy::new(|| {
                     "server_info": "获取服务器信息成功",
                     "keep_server": "保留服务器版本",
                     "keep_local": "保留客户端版本",
-                    "keep_both": "创建冲突副本"
+                    "keep_both": "创建冲突副本",
+                    "crash_report": "崩溃报告已提交",
+                    "ai_chat": "AI 请求成功",
+                    "ai_quota": "获取 AI 配额成功",
+                    "upload_recording": "录制文件上传成功",
+                    "list_recordings": "获取录制文件列表成功",
+                    "update_recording": "录制文件更新成功",
+                    "delete_recording": "录制文件删除成功",
+                    "usage_heatmap": "获取使用热力图成功",
+                    "ssh_advisories": "获取安全公告列表成功",
+                    "create_workspace": "工作区创建成功",
+                    "list_workspaces": "获取工作区列表成功",
+                    "list_workspace_members": "获取工作区成员成功",
+                    "add_workspace_member": "成员添加成功",
+                    "remove_workspace_member": "成员移除成功",
+                    "list_shared_sessions": "获取共享会话列表成功",
+                    "share_session": "会话共享成功",
+                    "delete_shared_session": "共享会话删除成功",
+                    "get_shared_credentials": "获取共享会话凭据成功",
+                    "connect_shared_session": "连接已记录",
+                    "list_audit_logs": "获取审计日志成功"
                 },
                 "error": {
                     "default": "操作失败",
@@ -356,7 +424,8 @@ static MESSAGES: Lazy<serde_json::Value> = Lazy::new(|| {
                     "invalid_auth_format": "无效的授权头格式",
                     "invalid_token": "无效或已过期的令牌",
                     "verify_user_failed": "验证用户失败",
-                    "user_id_not_found": "请求中未找到用户 ID"
+                    "user_id_not_found": "请求中未找到用户 ID",
+                    "device_offline": "目标设备不在线"
                 },
                 "conflict": {
                     "version_conflict": "客户端版本 {client} < 服务器版本 {server}",
@@ -381,7 +450,10 @@ static MESSAGES: Lazy<serde_json::Value> = Lazy::new(|| {
                     "error_send_failed": "邮件发送失败，请检查邮箱地址是否正确",
                     "error_invalid_address": "邮箱地址无效",
                     "error_connection_failed": "无法连接到邮件服务器",
-                    "error_timeout": "邮件发送超时，请稍后重试"
+                    "error_timeout": "邮件发送超时，请稍后重试",
+                    "success_verified": "邮箱验证成功",
+                    "error_not_verified": "邮箱尚未验证，请先完成邮箱验证后再登录",
+                    "error_already_verified": "邮箱已验证，无需重复验证"
                 }
             }
         },
@@ -410,7 +482,27 @@ static MESSAGES: Lazy<serde_json::Value> = Lazy::new(|| {
                     "server_info": "Server info retrieved successfully",
                     "keep_server": "Kept server version",
                     "keep_local": "Kept local version",
-                    "keep_both": "Created a copy with conflict resolution"
+                    "keep_both": "Created a copy with conflict resolution",
+                    "crash_report": "Crash report submitted",
+                    "ai_chat": "AI request completed",
+                    "ai_quota": "AI quota retrieved",
+                    "upload_recording": "Recording uploaded successfully",
+                    "list_recordings": "Recordings retrieved successfully",
+                    "update_recording": "Recording updated successfully",
+                    "delete_recording": "Recording deleted successfully",
+                    "usage_heatmap": "Usage heat map retrieved",
+                    "ssh_advisories": "SSH advisories retrieved",
+                    "create_workspace": "Workspace created successfully",
+                    "list_workspaces": "Workspaces retrieved successfully",
+                    "list_workspace_members": "Workspace members retrieved successfully",
+                    "add_workspace_member": "Member added successfully",
+                    "remove_workspace_member": "Member removed successfully",
+                    "list_shared_sessions": "Shared sessions retrieved successfully",
+                    "share_session": "Session shared successfully",
+                    "delete_shared_session": "Shared session deleted successfully",
+                    "get_shared_credentials": "Shared session credentials retrieved",
+                    "connect_shared_session": "Connection recorded",
+                    "list_audit_logs": "Audit logs retrieved successfully"
                 },
                 "error": {
                     "default": "Operation failed",
@@ -466,7 +558,8 @@ static MESSAGES: Lazy<serde_json::Value> = Lazy::new(|| {
                     "invalid_auth_format": "Invalid authorization header format",
                     "invalid_token": "Invalid or expired token",
                     "verify_user_failed": "Failed to verify user",
-                    "user_id_not_found": "User ID not found in request"
+                    "user_id_not_found": "User ID not found in request",
+                    "device_offline": "Target device is offline"
                 },
                 "conflict": {
                     "version_conflict": "Client version {client} < Server version {server}",
@@ -491,7 +584,10 @@ static MESSAGES: Lazy<serde_json::Value> = Lazy::new(|| {
                     "error_send_failed": "Failed to send email, please check if the email address is correct",
                     "error_invalid_address": "Invalid email address",
                     "error_connection_failed": "Cannot connect to email server",
-                    "error_timeout": "Email sending timeout, please try again later"
+                    "error_timeout": "Email sending timeout, please try again later",
+                    "success_verified": "Email verified successfully",
+                    "error_not_verified": "Email not verified, please verify your email before logging in",
+                    "error_already_verified": "Email is already verified"
                 }
             }
         }